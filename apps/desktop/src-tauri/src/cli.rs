@@ -0,0 +1,75 @@
+//! Command-line maintenance commands, handled before the GUI starts.
+//!
+//! ```text
+//! mcpmux db doctor [--repair | --rollback] [--data-dir <path>]
+//! ```
+//!
+//! Works even when the app itself fails to start because of a broken
+//! migration.
+
+use std::path::PathBuf;
+
+use mcpmux_storage::{doctor, DATABASE_FILE};
+
+const USAGE: &str = "Usage: mcpmux db doctor [--repair | --rollback] [--data-dir <path>]";
+
+/// Run `mcpmux db <args>`, returning the process exit code.
+pub fn run_db_command(args: &[String]) -> i32 {
+    if args.first().map(String::as_str) != Some("doctor") {
+        eprintln!("{}", USAGE);
+        return 2;
+    }
+
+    let mut repair = false;
+    let mut rollback = false;
+    let mut data_dir = crate::get_app_data_dir();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--repair" => repair = true,
+            "--rollback" => rollback = true,
+            "--data-dir" => match rest.next() {
+                Some(dir) => data_dir = PathBuf::from(dir),
+                None => {
+                    eprintln!("{}", USAGE);
+                    return 2;
+                }
+            },
+            _ => {
+                eprintln!("Unknown argument: {}\n{}", arg, USAGE);
+                return 2;
+            }
+        }
+    }
+    if repair && rollback {
+        eprintln!("--repair and --rollback are mutually exclusive\n{}", USAGE);
+        return 2;
+    }
+
+    let db_path = data_dir.join(DATABASE_FILE);
+    let result = if rollback {
+        doctor::rollback(&db_path).and_then(|failed| {
+            println!("Previous database kept at {}", failed.display());
+            doctor::diagnose(&db_path)
+        })
+    } else if repair {
+        doctor::repair(&db_path)
+    } else {
+        doctor::diagnose(&db_path)
+    };
+
+    match result {
+        Ok(report) => {
+            println!("{}", report);
+            if report.is_healthy() {
+                0
+            } else {
+                1
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Backup and restore commands
+//!
+//! IPC commands for snapshotting the database, settings and space configs
+//! into versioned archives, and restoring one of them.
+
+use mcpmux_core::AppSettingsService;
+use mcpmux_storage::{BackupInfo, BackupKind};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::info;
+
+use crate::state::AppState;
+
+/// Scheduled backup settings
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupSettings {
+    /// Hours between scheduled backups (0 = disabled)
+    pub interval_hours: u32,
+    /// Number of scheduled backups to keep
+    pub keep_count: u32,
+}
+
+/// Create a manual backup now
+#[tauri::command]
+pub async fn create_backup(state: State<'_, AppState>) -> Result<BackupInfo, String> {
+    state
+        .backup_manager
+        .create_backup(BackupKind::Manual)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List available backups, newest first
+#[tauri::command]
+pub async fn list_backups(state: State<'_, AppState>) -> Result<Vec<BackupInfo>, String> {
+    state
+        .backup_manager
+        .list_backups()
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a backup archive
+#[tauri::command]
+pub async fn delete_backup(file_name: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .backup_manager
+        .delete_backup(&file_name)
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a backup.
+///
+/// Space configs are restored immediately; the database is swapped in on the
+/// next launch, so the UI should relaunch the app afterwards.
+#[tauri::command]
+pub async fn restore_backup(
+    file_name: String,
+    state: State<'_, AppState>,
+) -> Result<BackupInfo, String> {
+    info!("[Backup] Restoring {}", file_name);
+    state
+        .backup_manager
+        .restore_backup(&file_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get scheduled backup settings
+#[tauri::command]
+pub async fn get_backup_settings(state: State<'_, AppState>) -> Result<BackupSettings, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(BackupSettings {
+        interval_hours: settings.get_backup_interval_hours().await,
+        keep_count: settings.get_backup_keep_count().await,
+    })
+}
+
+/// Update scheduled backup settings (picked up at the next scheduler tick)
+#[tauri::command]
+pub async fn set_backup_settings(
+    backup_settings: BackupSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if backup_settings.keep_count == 0 {
+        return Err("Keep count must be at least 1".to_string());
+    }
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_backup_interval_hours(backup_settings.interval_hours)
+        .await
+        .map_err(|e| format!("Failed to save backup settings: {}", e))?;
+    settings
+        .set_backup_keep_count(backup_settings.keep_count)
+        .await
+        .map_err(|e| format!("Failed to save backup settings: {}", e))
+}
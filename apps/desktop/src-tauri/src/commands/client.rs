@@ -4,7 +4,7 @@
 //! Connection modes and per-client FeatureSet grants no longer exist —
 //! routing is entirely driven by WorkspaceBinding + Space default FS.
 
-use mcpmux_core::Client;
+use mcpmux_core::{Client, ClientDetectionService};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 use uuid::Uuid;
@@ -94,7 +94,9 @@ pub async fn delete_client(id: String, state: State<'_, AppState>) -> Result<(),
         .map_err(|e| e.to_string())
 }
 
-/// Create preset clients (Cursor, VS Code, Claude Desktop).
+/// Create a preset client for each supported client installed on this
+/// machine (Claude Desktop, Cursor, VS Code, Windsurf, Zed). Clients that
+/// already have a preset are skipped.
 #[tauri::command]
 pub async fn init_preset_clients(state: State<'_, AppState>) -> Result<(), String> {
     let existing = state
@@ -103,29 +105,19 @@ pub async fn init_preset_clients(state: State<'_, AppState>) -> Result<(), Strin
         .await
         .map_err(|e| e.to_string())?;
 
-    if !existing.iter().any(|c| c.client_type == "cursor") {
-        let cursor = Client::cursor();
-        state
-            .client_repository
-            .create(&cursor)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
-
-    if !existing.iter().any(|c| c.client_type == "vscode") {
-        let vscode = Client::vscode();
-        state
-            .client_repository
-            .create(&vscode)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
+    let found = tokio::task::spawn_blocking(|| ClientDetectionService::new().detect_present())
+        .await
+        .map_err(|e| e.to_string())?;
 
-    if !existing.iter().any(|c| c.client_type == "claude") {
-        let claude = Client::claude_desktop();
+    for installation in found {
+        let client_type = installation.client.client_type();
+        if existing.iter().any(|c| c.client_type == client_type) {
+            continue;
+        }
+        let client = Client::new(installation.label, client_type);
         state
             .client_repository
-            .create(&claude)
+            .create(&client)
             .await
             .map_err(|e| e.to_string())?;
     }
@@ -1,10 +1,25 @@
 //! One-click IDE install commands.
 //!
-//! Opens deep link URIs for VS Code and Cursor to install the McpMux MCP server.
+//! Opens deep link URIs for VS Code and Cursor, writes the McpMux entry into
+//! Windsurf and Zed config files or into any detected client's MCP config,
+//! and reports which clients are installed.
 
-use mcpmux_core::{cursor_deep_link, vscode_deep_link};
+use mcpmux_core::{
+    add_to_config_file, cursor_deep_link, jetbrains_mcp_config, vscode_deep_link,
+    windsurf_config_path, windsurf_server_entry, zed_server_entry, zed_settings_path,
+    ClientDetectionService, ClientInstallation, ConfigApplyResult, ConfigReapplyResult,
+    DetectableClient, InstallClient,
+};
+use serde::Serialize;
 use tracing::info;
 
+/// Whether a client was found on this machine
+#[derive(Debug, Serialize)]
+pub struct ClientDetection {
+    pub client: InstallClient,
+    pub installed: bool,
+}
+
 /// Add McpMux to VS Code via deep link.
 #[tauri::command]
 pub async fn add_to_vscode(gateway_url: String) -> Result<(), String> {
@@ -21,6 +36,100 @@ pub async fn add_to_cursor(gateway_url: String) -> Result<(), String> {
     open_deep_link(&uri)
 }
 
+/// Add McpMux to Windsurf's `mcp_config.json`. Returns the file written.
+#[tauri::command]
+pub async fn add_to_windsurf(gateway_url: String) -> Result<String, String> {
+    let path = windsurf_config_path().ok_or("Could not determine home directory")?;
+    add_to_config_file(&path, "mcpServers", windsurf_server_entry(&gateway_url))
+        .map_err(|e| e.to_string())?;
+    info!(
+        "[ClientInstall] Added McpMux to Windsurf: {}",
+        path.display()
+    );
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Add McpMux to Zed's `settings.json`. Returns the file written.
+#[tauri::command]
+pub async fn add_to_zed(gateway_url: String) -> Result<String, String> {
+    let path = zed_settings_path().ok_or("Could not determine home directory")?;
+    add_to_config_file(&path, "context_servers", zed_server_entry(&gateway_url))
+        .map_err(|e| e.to_string())?;
+    info!("[ClientInstall] Added McpMux to Zed: {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// JSON to paste into JetBrains AI Assistant's MCP settings.
+#[tauri::command]
+pub async fn get_jetbrains_mcp_config(gateway_url: String) -> Result<String, String> {
+    Ok(jetbrains_mcp_config(&gateway_url))
+}
+
+/// Detect which supported clients are installed on this machine.
+#[tauri::command]
+pub async fn detect_installed_clients() -> Result<Vec<ClientDetection>, String> {
+    tokio::task::spawn_blocking(|| {
+        InstallClient::ALL
+            .iter()
+            .map(|client| ClientDetection {
+                client: *client,
+                installed: client.is_installed(),
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Every supported desktop client with what was found for it: whether it's
+/// installed and its existing MCP config.
+#[tauri::command]
+pub async fn detect_client_installations() -> Result<Vec<ClientInstallation>, String> {
+    tokio::task::spawn_blocking(|| ClientDetectionService::new().detect_all())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Add or update the McpMux entry in a detected client's MCP config, in
+/// place. The previous file is backed up next to it.
+#[tauri::command]
+pub async fn apply_client_config(
+    client: DetectableClient,
+    gateway_url: String,
+) -> Result<ConfigApplyResult, String> {
+    let result = tokio::task::spawn_blocking(move || {
+        mcpmux_core::apply_to_client(&ClientDetectionService::new(), client, &gateway_url)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    info!(
+        "[ClientInstall] {:?} McpMux in {}: {}",
+        result.action,
+        client.label(),
+        result.path.display()
+    );
+    Ok(result)
+}
+
+/// Point every client config that already lists McpMux at `gateway_url`,
+/// e.g. after the gateway had to start on another port. Each edited file is
+/// backed up next to it.
+#[tauri::command]
+pub async fn reapply_client_configs(gateway_url: String) -> Result<ConfigReapplyResult, String> {
+    let result = tokio::task::spawn_blocking(move || {
+        mcpmux_core::reapply_to_clients(&ClientDetectionService::new(), &gateway_url)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    info!(
+        "[ClientInstall] Re-applied McpMux to {} client config(s), {} failed",
+        result.applied.len(),
+        result.failed.len()
+    );
+    Ok(result)
+}
+
 /// Open a deep link URI using the system handler.
 fn open_deep_link(uri: &str) -> Result<(), String> {
     #[cfg(target_os = "windows")]
@@ -1,13 +1,16 @@
 //! Config export commands
 //!
-//! IPC commands for generating MCP configuration files for clients.
+//! IPC commands for generating MCP configuration files for clients, either
+//! listing each server directly or pointing the client at the gateway.
 
 use mcpmux_core::{
-    ConfigExporter, ConfigFormat, ResolvedServer, ResolvedTransport, TransportConfig,
+    diff_lines, merge_config, ClientDetectionService, ClientInstallation, ConfigDiff,
+    ConfigExporter, ConfigFormat, DetectableClient, GatewayClientFormat, GatewayConnection,
+    ResolvedServer, ResolvedTransport, TransportConfig,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
 use crate::state::AppState;
@@ -15,13 +18,24 @@ use crate::state::AppState;
 /// Request for exporting configuration
 #[derive(Debug, Deserialize)]
 pub struct ExportConfigRequest {
-    /// Client type: "cursor", "vscode", "claude"
+    /// Client type: "cursor", "vscode", "claude" (servers listed directly) or
+    /// "zed", "continue-gateway", "cline", "openai-agents", "langchain"
+    /// (connects through the gateway)
     pub client_type: String,
     /// Space ID to export config for (use "default" for default space)
     pub space_id: String,
     /// Whether to mask credentials
     #[serde(default)]
     pub mask_credentials: bool,
+    /// Gateway formats: base URL (defaults to localhost on the configured port)
+    #[serde(default)]
+    pub gateway_url: Option<String>,
+    /// Gateway formats: API key of the client (a placeholder is written if unset)
+    #[serde(default)]
+    pub access_key: Option<String>,
+    /// Gateway formats: client ID, used to describe the FeatureSets it gets
+    #[serde(default)]
+    pub client_id: Option<String>,
 }
 
 /// Response for config export
@@ -33,6 +47,13 @@ pub struct ExportConfigResponse {
     pub default_path: Option<String>,
     /// File name suggestion
     pub suggested_filename: String,
+    /// Which FeatureSets the client gets (gateway formats only)
+    pub feature_set_note: Option<String>,
+    /// The client's existing MCP config, which an export would merge into
+    pub existing_config: Option<ClientInstallation>,
+    /// Dry run: what writing to `default_path` would change in the file
+    /// there. `None` when there's no file yet.
+    pub diff: Option<ConfigDiff>,
 }
 
 /// Get the config format from client type
@@ -45,6 +66,14 @@ fn get_format(client_type: &str) -> Result<ConfigFormat, String> {
     }
 }
 
+/// Default config path for any supported client type
+fn default_path_for(client_type: &str) -> Result<Option<PathBuf>, String> {
+    match GatewayClientFormat::parse(client_type) {
+        Some(format) => Ok(format.default_path()),
+        None => Ok(get_format(client_type)?.default_path()),
+    }
+}
+
 /// Resolve a `space_id` argument from the UI: the literal "default" or an
 /// empty string fall back to the system's `is_default` Space.
 async fn get_space_id(state: &AppState, space_id: &str) -> Result<String, String> {
@@ -139,6 +168,11 @@ async fn build_resolved_servers(
                         headers: resolved_headers,
                     }
                 }
+                // Only the gateway can bridge a REST or GraphQL API, run a
+                // WASI module or run the built-in utilities, so there is
+                // nothing a client could connect to directly; nor to a
+                // transport this build doesn't know
+                _ => continue,
             };
 
             resolved.push(ResolvedServer {
@@ -161,12 +195,116 @@ fn resolve_placeholders(template: &str, input_values: &HashMap<String, String>)
     result
 }
 
+/// Describe which FeatureSets a gateway client is served.
+///
+/// Mirrors the resolver: an id-keyed mapping on the client ID wins, otherwise
+/// the client falls back to the default Space's Starter.
+async fn feature_set_note(state: &AppState, client_id: Option<&str>) -> Result<String, String> {
+    let binding = match client_id {
+        Some(id) => state
+            .workspace_binding_repository
+            .find_by_id_key(id)
+            .await
+            .map_err(|e| e.to_string())?,
+        None => None,
+    };
+
+    let Some(binding) = binding else {
+        return Ok(
+            "McpMux serves this client the default Space's Starter FeatureSet.\n\
+             Map the client ID (or an X-Mcpmux-Workspace label) in the Mapping tab to change it."
+                .to_string(),
+        );
+    };
+
+    let space = state
+        .space_service
+        .get(&binding.space_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|s| s.name)
+        .unwrap_or_else(|| binding.space_id.to_string());
+
+    let mut names = Vec::new();
+    for id in &binding.feature_set_ids {
+        let name = state
+            .feature_set_repository
+            .get(id)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|fs| fs.name)
+            .unwrap_or_else(|| id.clone());
+        names.push(name);
+    }
+
+    Ok(if names.is_empty() {
+        format!(
+            "McpMux maps this client to Space \"{}\" with no FeatureSets (no tools).",
+            space
+        )
+    } else {
+        format!(
+            "McpMux serves this client FeatureSets {} from Space \"{}\".",
+            names.join(", "),
+            space
+        )
+    })
+}
+
+/// Generate a gateway config for `format`
+async fn export_gateway_config(
+    state: &AppState,
+    format: GatewayClientFormat,
+    request: &ExportConfigRequest,
+) -> Result<(String, String), String> {
+    let gateway_url = match &request.gateway_url {
+        Some(url) => url.clone(),
+        None => {
+            let port = state
+                .gateway_port_service
+                .load_persisted_port()
+                .await
+                .unwrap_or(mcpmux_core::DEFAULT_GATEWAY_PORT);
+            let host = crate::commands::gateway::load_base_url_host(state).await;
+            mcpmux_gateway::local_base_url(host.as_deref(), port)
+        }
+    };
+    let note = feature_set_note(state, request.client_id.as_deref()).await?;
+    let connection = GatewayConnection {
+        gateway_url,
+        access_key: request
+            .access_key
+            .clone()
+            .filter(|_| !request.mask_credentials),
+        feature_set_note: Some(note.clone()),
+    };
+
+    let content = ConfigExporter::new()
+        .export_gateway(format, &connection)
+        .map_err(|e| e.to_string())?;
+    Ok((content, note))
+}
+
 /// Preview config export (returns JSON string)
 #[tauri::command]
 pub async fn preview_config_export(
     request: ExportConfigRequest,
     state: State<'_, AppState>,
 ) -> Result<ExportConfigResponse, String> {
+    if let Some(format) = GatewayClientFormat::parse(&request.client_type) {
+        let (content, note) = export_gateway_config(&state, format, &request).await?;
+        let default_path = format.default_path();
+        let diff = preview_diff(default_path.as_deref(), &content, format.servers_path())?;
+        return Ok(ExportConfigResponse {
+            content,
+            default_path: default_path.map(|p| p.to_string_lossy().to_string()),
+            suggested_filename: format.suggested_filename().to_string(),
+            feature_set_note: Some(note),
+            existing_config: None,
+            diff,
+        });
+    }
+
     let space_id = get_space_id(&state, &request.space_id).await?;
     let format = get_format(&request.client_type)?;
 
@@ -179,9 +317,13 @@ pub async fn preview_config_export(
         .export_json(format, &servers)
         .map_err(|e| e.to_string())?;
 
-    let default_path = format
-        .default_path()
-        .map(|p| p.to_string_lossy().to_string());
+    let default_path = format.default_path();
+    let diff = preview_diff(
+        default_path.as_deref(),
+        &content,
+        Some(format.servers_path()),
+    )?;
+    let default_path = default_path.map(|p| p.to_string_lossy().to_string());
 
     let suggested_filename = match format {
         ConfigFormat::Cursor => "mcp.json".to_string(),
@@ -189,10 +331,29 @@ pub async fn preview_config_export(
         ConfigFormat::ClaudeDesktop => "claude_desktop_config.json".to_string(),
     };
 
+    let detectable = match format {
+        ConfigFormat::Cursor => Some(DetectableClient::Cursor),
+        ConfigFormat::ClaudeDesktop => Some(DetectableClient::ClaudeDesktop),
+        ConfigFormat::VsCodeContinue => None,
+    };
+    let existing_config = match detectable {
+        Some(client) => {
+            let found =
+                tokio::task::spawn_blocking(move || ClientDetectionService::new().detect(client))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            found.config_path.is_some().then_some(found)
+        }
+        None => None,
+    };
+
     Ok(ExportConfigResponse {
         content,
         default_path,
         suggested_filename,
+        feature_set_note: None,
+        existing_config,
+        diff,
     })
 }
 
@@ -203,20 +364,30 @@ pub async fn export_config_to_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    let space_id = get_space_id(&state, &request.space_id).await?;
-    let format = get_format(&request.client_type)?;
-
-    // Build resolved servers (with actual credentials for file export)
-    let servers = build_resolved_servers(&state, &space_id, false).await?;
-
-    // Create exporter and generate config
-    let exporter = ConfigExporter::new();
-    let content = exporter
-        .export_json(format, &servers)
-        .map_err(|e| e.to_string())?;
+    let (content, servers_path) =
+        if let Some(format) = GatewayClientFormat::parse(&request.client_type) {
+            let content = export_gateway_config(&state, format, &request).await?.0;
+            (content, format.servers_path())
+        } else {
+            let space_id = get_space_id(&state, &request.space_id).await?;
+            let format = get_format(&request.client_type)?;
+
+            // Build resolved servers (with actual credentials for file export)
+            let servers = build_resolved_servers(&state, &space_id, false).await?;
+
+            // Create exporter and generate config
+            let exporter = ConfigExporter::new();
+            let content = exporter
+                .export_json(format, &servers)
+                .map_err(|e| e.to_string())?;
+            (content, Some(format.servers_path()))
+        };
+
+    // Merge into what's already there rather than overwriting it
+    let path = PathBuf::from(&path);
+    let content = merged_with_existing(&path, &content, servers_path)?.0;
 
     // Write to file
-    let path = PathBuf::from(&path);
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
@@ -225,6 +396,41 @@ pub async fn export_config_to_file(
     Ok(path.to_string_lossy().to_string())
 }
 
+/// The export merged into the file at `path`, along with the file's current
+/// content. Formats without a servers map (YAML, code) replace the file.
+fn merged_with_existing(
+    path: &Path,
+    content: &str,
+    servers_path: Option<&[&str]>,
+) -> Result<(String, Option<String>), String> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok((content.to_string(), None))
+        }
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let merged = match servers_path {
+        Some(servers_path) => merge_config(&existing, content, servers_path)
+            .map_err(|e| format!("Can't merge into {}: {}", path.display(), e))?,
+        None => content.to_string(),
+    };
+    Ok((merged, Some(existing)))
+}
+
+/// Diff of the file at `path` against it with the export merged in
+fn preview_diff(
+    path: Option<&Path>,
+    content: &str,
+    servers_path: Option<&[&str]>,
+) -> Result<Option<ConfigDiff>, String> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let (merged, existing) = merged_with_existing(path, content, servers_path)?;
+    Ok(existing.map(|existing| diff_lines(&existing, &merged)))
+}
+
 /// Get default config paths for all clients
 #[tauri::command]
 pub async fn get_config_paths() -> Result<HashMap<String, Option<String>>, String> {
@@ -248,6 +454,12 @@ pub async fn get_config_paths() -> Result<HashMap<String, Option<String>>, Strin
             .default_path()
             .map(|p| p.to_string_lossy().to_string()),
     );
+    paths.insert(
+        "cline".to_string(),
+        GatewayClientFormat::Cline
+            .default_path()
+            .map(|p| p.to_string_lossy().to_string()),
+    );
 
     Ok(paths)
 }
@@ -255,9 +467,7 @@ pub async fn get_config_paths() -> Result<HashMap<String, Option<String>>, Strin
 /// Check if config file exists at default location
 #[tauri::command]
 pub async fn check_config_exists(client_type: String) -> Result<bool, String> {
-    let format = get_format(&client_type)?;
-
-    match format.default_path() {
+    match default_path_for(&client_type)? {
         Some(path) => Ok(path.exists()),
         None => Ok(false),
     }
@@ -266,9 +476,7 @@ pub async fn check_config_exists(client_type: String) -> Result<bool, String> {
 /// Backup existing config before writing
 #[tauri::command]
 pub async fn backup_existing_config(client_type: String) -> Result<Option<String>, String> {
-    let format = get_format(&client_type)?;
-
-    match format.default_path() {
+    match default_path_for(&client_type)? {
         Some(path) if path.exists() => {
             let backup_path = path.with_extension("json.bak");
             std::fs::copy(&path, &backup_path).map_err(|e| e.to_string())?;
@@ -1,6 +1,137 @@
 //! Credential management commands
 //!
-//! IPC commands for managing credentials (API keys, OAuth tokens).
-//! Will be implemented in Phase 1 Task 02 (Credential Security).
+//! IPC commands for moving credentials between machines: credentials, outbound
+//! OAuth registrations and saved server inputs are exported into a
+//! passphrase-encrypted bundle and imported on the other side. Also hosts
+//! master key rotation and key provider selection.
 
-// Placeholder - credential commands will be implemented in Phase 1
+use std::path::PathBuf;
+
+use mcpmux_storage::{KeyProviderKind, SecretsTransferSummary};
+use tauri::{AppHandle, Emitter, State};
+use tracing::{info, warn};
+
+use crate::state::AppState;
+
+/// Export all secrets to a passphrase-encrypted file
+#[tauri::command]
+pub async fn export_secrets(
+    path: String,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<SecretsTransferSummary, String> {
+    let path = PathBuf::from(path);
+    info!(
+        "[Credentials] Exporting secrets bundle to {}",
+        path.display()
+    );
+    mcpmux_storage::export_secrets(
+        &path,
+        &passphrase,
+        state.installed_server_repository.as_ref(),
+        state.credential_repository.as_ref(),
+        state.backend_oauth_repository.as_ref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Import secrets from a passphrase-encrypted file
+///
+/// Only entries for servers already installed in the same Space are applied.
+#[tauri::command]
+pub async fn import_secrets(
+    path: String,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<SecretsTransferSummary, String> {
+    let path = PathBuf::from(path);
+    info!(
+        "[Credentials] Importing secrets bundle from {}",
+        path.display()
+    );
+    mcpmux_storage::import_secrets(
+        &path,
+        &passphrase,
+        state.installed_server_repository.as_ref(),
+        state.credential_repository.as_ref(),
+        state.backend_oauth_repository.as_ref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Rotate the master encryption key and re-encrypt all stored secrets.
+///
+/// Emits `master-key-rotation-progress` events while running. Returns the
+/// number of re-encrypted values.
+#[tauri::command]
+pub async fn rotate_master_key(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    if mcpmux_storage::read_key_provider_kind(state.data_dir()) != state.key_provider_kind {
+        return Err("Key provider changed; restart McpMux before rotating the key".to_string());
+    }
+    info!("[Credentials] Rotating master key");
+    mcpmux_storage::rotate_master_key(
+        &state.database(),
+        &state.encryptor,
+        state.key_provider.as_ref(),
+        |progress| {
+            if let Err(e) = app_handle.emit("master-key-rotation-progress", &progress) {
+                warn!("[Credentials] Failed to emit rotation progress: {}", e);
+            }
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Get the selected master key provider.
+#[tauri::command]
+pub async fn get_key_provider(state: State<'_, AppState>) -> Result<KeyProviderKind, String> {
+    Ok(mcpmux_storage::read_key_provider_kind(state.data_dir()))
+}
+
+/// Move the master key to another provider. Takes effect on restart.
+///
+/// Selecting `passphrase` wraps the key with `passphrase` (or
+/// `MCPMUX_MASTER_PASSPHRASE` when omitted); McpMux asks for it on every
+/// subsequent start unless that variable is set.
+#[tauri::command]
+pub async fn set_key_provider(
+    provider: KeyProviderKind,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let selected = mcpmux_storage::read_key_provider_kind(state.data_dir());
+    if selected != state.key_provider_kind {
+        return Err("Key provider already changed; restart McpMux first".to_string());
+    }
+    if provider == selected {
+        return Ok(());
+    }
+    info!(
+        "[Credentials] Switching master key provider to {:?}",
+        provider
+    );
+    match (provider, passphrase) {
+        (KeyProviderKind::Passphrase, Some(passphrase)) => {
+            let target = mcpmux_storage::PassphraseKeyProvider::new(state.data_dir(), passphrase)
+                .map_err(|e| e.to_string())?;
+            mcpmux_storage::switch_key_provider_to(
+                state.data_dir(),
+                state.key_provider.as_ref(),
+                provider,
+                &target,
+            )
+        }
+        _ => mcpmux_storage::switch_key_provider(
+            state.data_dir(),
+            state.key_provider.as_ref(),
+            provider,
+        ),
+    }
+    .map_err(|e| e.to_string())
+}
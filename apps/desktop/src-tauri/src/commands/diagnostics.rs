@@ -0,0 +1,117 @@
+//! Diagnostics bundle command
+//!
+//! Packs recent app and server logs, gateway and pool status, and version
+//! information into a redacted zip for bug reports.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use mcpmux_core::{
+    probe_tool_versions, DiagnosticsBundle, ManagedRuntimeStatus, ManagedRuntimes,
+    DIAGNOSTICS_LOG_MAX_AGE,
+};
+use mcpmux_storage::Database;
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::commands::gateway::{get_gateway_status, get_pool_stats, GatewayAppState};
+use crate::commands::server_manager::{
+    get_server_statuses, ServerManagerState, ServerStatusResponse,
+};
+use crate::state::AppState;
+
+/// Directory (under the data dir) bundles are written to
+const DIAGNOSTICS_DIR: &str = "diagnostics";
+
+/// Versions and platform details, `system.json` in the bundle
+#[derive(Debug, Serialize)]
+struct SystemInfo {
+    app_version: String,
+    os: &'static str,
+    arch: &'static str,
+    os_family: &'static str,
+    db_schema_version: i64,
+    db_latest_schema_version: i64,
+    /// `--version` of runtimes on PATH, `null` when not installed
+    tools: BTreeMap<String, Option<String>>,
+    managed_runtimes_enabled: bool,
+    managed_runtimes: Vec<ManagedRuntimeStatus>,
+}
+
+/// Create a diagnostics bundle and return its path.
+///
+/// Secrets are redacted from every file in the bundle; see
+/// [`mcpmux_core::redact_secrets`].
+#[tauri::command]
+pub async fn generate_diagnostics_bundle(
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    server_manager_state: State<'_, Arc<RwLock<ServerManagerState>>>,
+) -> Result<String, String> {
+    info!("[Diagnostics] Generating diagnostics bundle");
+
+    let runtimes = ManagedRuntimes::new(state.data_dir(), Some(state.settings_repository.clone()));
+    let system = SystemInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        os_family: std::env::consts::FAMILY,
+        db_schema_version: state.database().lock().await.schema_version(),
+        db_latest_schema_version: Database::latest_schema_version(),
+        tools: probe_tool_versions().await,
+        managed_runtimes_enabled: runtimes.is_enabled().await,
+        managed_runtimes: runtimes.status(),
+    };
+
+    let gateway = get_gateway_status(None, gateway_state.clone(), server_manager_state.clone())
+        .await
+        .map(|status| serde_json::to_value(status).unwrap_or_default())
+        .unwrap_or_else(|e| serde_json::json!({ "error": e }));
+    let pool = get_pool_stats(gateway_state)
+        .await
+        .map(|stats| serde_json::to_value(stats).unwrap_or_default())
+        .unwrap_or_else(|e| serde_json::json!({ "error": e }));
+
+    // Server statuses per Space, by Space name
+    let mut servers: BTreeMap<String, HashMap<String, ServerStatusResponse>> = BTreeMap::new();
+    let spaces = state
+        .space_service
+        .list()
+        .await
+        .map_err(|e| e.to_string())?;
+    for space in spaces {
+        match get_server_statuses(space.id.to_string(), server_manager_state.clone()).await {
+            Ok(statuses) => {
+                servers.insert(format!("{} ({})", space.name, space.id), statuses);
+            }
+            Err(e) => warn!("[Diagnostics] No server statuses for {}: {}", space.id, e),
+        }
+    }
+
+    let file_name = format!(
+        "mcpmux-diagnostics-{}.zip",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = state.data_dir().join(DIAGNOSTICS_DIR).join(file_name);
+    let log_dir = state.server_log_manager.base_dir().to_path_buf();
+
+    // Reading logs and compressing is blocking work
+    let path = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        let mut bundle = DiagnosticsBundle::create(&path)?;
+        bundle.add_json("system.json", &system)?;
+        bundle.add_json("gateway.json", &gateway)?;
+        bundle.add_json("pool.json", &pool)?;
+        bundle.add_json("servers.json", &servers)?;
+        let logs = bundle.add_recent_logs(&log_dir, DIAGNOSTICS_LOG_MAX_AGE)?;
+        info!("[Diagnostics] Added {} log file(s)", logs);
+        bundle.finish()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to create diagnostics bundle: {}", e))?;
+
+    info!("[Diagnostics] Bundle written to {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,186 @@
+//! Tauri commands for environment profiles
+//!
+//! A profile ("dev", "staging", "prod") swaps input values and URLs for all
+//! servers of a Space at once. Changing what the active profile applies
+//! reconnects the enabled servers it affects.
+
+use mcpmux_core::{EnvironmentProfile, ProfileServerOverrides};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::commands::gateway::GatewayAppState;
+use crate::commands::server_manager::{retry_connection, ServerManagerState};
+use crate::state::AppState;
+
+/// A server that could not be reconnected after a profile change
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReconnectFailure {
+    pub server_id: String,
+    pub error: String,
+}
+
+/// Servers reconnected because of a profile change
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileReconnectResult {
+    pub reconnected: Vec<String>,
+    pub failed: Vec<ProfileReconnectFailure>,
+}
+
+/// List the environment profiles of a space
+#[tauri::command]
+pub async fn list_space_profiles(
+    space_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<EnvironmentProfile>, String> {
+    state
+        .environment_profile_service
+        .list(&space_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create an empty, inactive environment profile
+#[tauri::command]
+pub async fn create_space_profile(
+    space_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<EnvironmentProfile, String> {
+    state
+        .environment_profile_service
+        .create(&space_id, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace a profile's name, input values and per-server overrides.
+/// If the profile is active, the servers it changes are reconnected.
+#[tauri::command]
+pub async fn update_space_profile(
+    id: String,
+    name: String,
+    inputs: HashMap<String, String>,
+    servers: HashMap<String, ProfileServerOverrides>,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> Result<EnvironmentProfile, String> {
+    let (profile, affected) = app_state
+        .environment_profile_service
+        .update(&id, &name, inputs, servers)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    reconnect_affected(&profile.space_id, affected, state, gateway_state, app_state).await;
+    Ok(profile)
+}
+
+/// Delete a profile. Deleting the active one returns its servers to their
+/// own settings.
+#[tauri::command]
+pub async fn delete_space_profile(
+    id: String,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    let profile = app_state
+        .environment_profile_service
+        .get(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let affected = app_state
+        .environment_profile_service
+        .delete(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    reconnect_affected(&profile.space_id, affected, state, gateway_state, app_state).await;
+    Ok(())
+}
+
+/// Switch a space to a profile, or back to the servers' own settings with
+/// no `profile_id`, and reconnect the servers whose settings changed.
+#[tauri::command]
+pub async fn set_space_profile(
+    space_id: String,
+    profile_id: Option<String>,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> Result<ProfileReconnectResult, String> {
+    let affected = app_state
+        .environment_profile_service
+        .set_active(&space_id, profile_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(reconnect_affected(&space_id, affected, state, gateway_state, app_state).await)
+}
+
+/// Reconnect the enabled servers among `server_ids`, one at a time. A
+/// failure is recorded and the rest still run.
+async fn reconnect_affected(
+    space_id: &str,
+    server_ids: Vec<String>,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> ProfileReconnectResult {
+    let mut result = ProfileReconnectResult::default();
+    if server_ids.is_empty() {
+        return result;
+    }
+
+    // Disabled servers pick up the profile whenever they are next enabled
+    let enabled = match app_state
+        .installed_server_repository
+        .list_enabled(space_id)
+        .await
+    {
+        Ok(servers) => servers,
+        Err(e) => {
+            warn!("[EnvironmentProfile] Failed to list enabled servers: {}", e);
+            return result;
+        }
+    };
+    let to_reconnect: Vec<String> = server_ids
+        .into_iter()
+        .filter(|id| enabled.iter().any(|s| &s.server_id == id))
+        .collect();
+
+    info!(
+        "[EnvironmentProfile] Reconnecting {} servers in space {}",
+        to_reconnect.len(),
+        space_id
+    );
+
+    for server_id in to_reconnect {
+        match retry_connection(
+            space_id.to_string(),
+            server_id.clone(),
+            state.clone(),
+            gateway_state.clone(),
+            app_state.clone(),
+        )
+        .await
+        {
+            Ok(()) => result.reconnected.push(server_id),
+            Err(error) => {
+                warn!(
+                    "[EnvironmentProfile] Reconnect failed for {}: {}",
+                    server_id, error
+                );
+                result
+                    .failed
+                    .push(ProfileReconnectFailure { server_id, error });
+            }
+        }
+    }
+
+    result
+}
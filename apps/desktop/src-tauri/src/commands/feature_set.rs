@@ -3,7 +3,7 @@
 //! IPC commands for managing feature sets (permission bundles).
 
 use chrono::Utc;
-use mcpmux_core::{FeatureSet, FeatureSetMember, MemberMode, MemberType};
+use mcpmux_core::{AnnotationHint, FeatureSet, FeatureSetMember, MemberMode, MemberType};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::State;
@@ -330,8 +330,12 @@ pub async fn add_feature_set_member(
 
     let member_type = match input.member_type.as_str() {
         "feature_set" => MemberType::FeatureSet,
+        "annotation" => MemberType::Annotation,
         _ => MemberType::Feature,
     };
+    if member_type == MemberType::Annotation && AnnotationHint::parse(&input.member_id).is_none() {
+        return Err(format!("Unknown annotation hint '{}'", input.member_id));
+    }
 
     let mode = input
         .mode
@@ -385,13 +389,7 @@ pub async fn add_feature_set_member(
         }
     }
 
-    let member = FeatureSetMember {
-        id: uuid::Uuid::new_v4().to_string(),
-        feature_set_id: feature_set_id.clone(),
-        member_type,
-        member_id: input.member_id,
-        mode,
-    };
+    let member = FeatureSetMember::new(&feature_set_id, member_type, input.member_id, mode);
 
     feature_set.members.push(member);
     feature_set.updated_at = Utc::now();
@@ -496,11 +494,16 @@ pub async fn set_feature_set_members(
             if m.member_type == "feature_set" && m.member_id == feature_set_id {
                 return false;
             }
+            // Skip unknown annotation hints
+            if m.member_type == "annotation" && AnnotationHint::parse(&m.member_id).is_none() {
+                return false;
+            }
             true
         })
         .map(|input| {
             let member_type = match input.member_type.as_str() {
                 "feature_set" => MemberType::FeatureSet,
+                "annotation" => MemberType::Annotation,
                 _ => MemberType::Feature,
             };
             let mode = input
@@ -509,13 +512,7 @@ pub async fn set_feature_set_members(
                 .and_then(MemberMode::parse)
                 .unwrap_or(MemberMode::Include);
 
-            FeatureSetMember {
-                id: uuid::Uuid::new_v4().to_string(),
-                feature_set_id: feature_set_id.clone(),
-                member_type,
-                member_id: input.member_id,
-                mode,
-            }
+            FeatureSetMember::new(&feature_set_id, member_type, input.member_id, mode)
         })
         .collect();
 
@@ -4,13 +4,18 @@
 
 use crate::commands::server_manager::ServerManagerState;
 use crate::AppState;
-use mcpmux_core::service::{allocate_dynamic_port, is_port_available};
-use mcpmux_core::DomainEvent;
+use mcpmux_core::service::{allocate_dynamic_port, is_port_available, port_owner, PortOwner};
+use mcpmux_core::{DomainEvent, JournaledEvent};
+use mcpmux_gateway::server::rate_limit::{
+    default_rate_limit_rules, RateLimitPolicy, RateLimitRejections,
+};
 use mcpmux_gateway::{
-    ConnectionContext, ConnectionResult, FeatureService, InstalledServerInfo, OAuthCompleteEvent,
-    PoolService, ResolvedTransport, ServerKey, ServerManager,
+    ConnectionContext, ConnectionResult, CorsConfig, FeatureService, InstalledServerInfo,
+    OAuthCompleteEvent, PoolService, ResolvedTransport, ServerKey, ServerManager, TrafficPause,
 };
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::RwLock;
@@ -28,6 +33,8 @@ pub struct GatewayStatus {
     pub active_sessions: usize,
     /// Number of connected backend servers
     pub connected_backends: usize,
+    /// Whether tool calls are paused by the user
+    pub traffic_paused: bool,
 }
 
 /// Backend server status (from pool)
@@ -46,6 +53,8 @@ pub struct BackendStatusResponse {
 pub struct PendingPortConflict {
     pub preferred_port: u16,
     pub source: &'static str,
+    /// The process holding the port, when it can be found
+    pub owner: Option<PortOwner>,
 }
 
 /// Gateway state managed by Tauri
@@ -68,6 +77,8 @@ pub struct GatewayAppState {
     pub pool_service: Option<Arc<PoolService>>,
     /// Feature service for feature discovery/caching
     pub feature_service: Option<Arc<FeatureService>>,
+    /// Routing service, for test tool calls from the desktop
+    pub routing_service: Option<Arc<mcpmux_gateway::RoutingService>>,
     /// Event emitter for triggering MCP notifications (legacy - prefer grant_service)
     pub event_emitter: Option<Arc<mcpmux_gateway::EventEmitter>>,
     /// Grant service for centralized grant management with auto-notifications
@@ -82,8 +93,30 @@ pub struct GatewayAppState {
     /// Surfaced to the desktop Workspaces tab so users can see + act on
     /// every folder connected clients are currently operating in.
     pub session_roots: Option<Arc<mcpmux_gateway::services::SessionRootsRegistry>>,
+    /// Live activity of connected MCP sessions (Active Connections view)
+    pub session_activity: Option<Arc<mcpmux_gateway::services::SessionActivityRegistry>>,
+    /// Package version pinning + update checks for npx/uvx servers
+    pub package_updates: Option<Arc<mcpmux_gateway::services::PackageUpdateService>>,
+    /// Pause switch of the running gateway
+    pub traffic_pause: Option<Arc<TrafficPause>>,
+    /// Whether the user paused tool calls. Kept here rather than only in the
+    /// gateway so a restarted gateway stays paused.
+    pub traffic_paused: bool,
 }
 
+/// How long a stopping gateway waits for in-flight tool calls. Kept short so
+/// app exit stays well inside the ~5s Windows allows before force-killing.
+pub(crate) const GATEWAY_DRAIN_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_millis(1500);
+
+/// Upper bound on a graceful stop: the drain, closing backend servers, and
+/// a little slack for the listener to close.
+const GATEWAY_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(
+    GATEWAY_DRAIN_TIMEOUT.as_millis() as u64
+        + mcpmux_gateway::BACKEND_CLOSE_TIMEOUT.as_millis() as u64
+        + 1500,
+);
+
 /// Gracefully shuts down a running gateway and waits for the axum task
 /// to finish so the TCP listener is released back to the OS.
 ///
@@ -93,13 +126,16 @@ pub struct GatewayAppState {
 /// binding the same port.
 ///
 /// Flow:
-/// 1. Send the graceful-shutdown signal (axum drains in-flight requests).
-/// 2. Await the task up to 2s so Rust Drop closes the listener fd.
+/// 1. Send the graceful-shutdown signal. The gateway stops accepting
+///    connections, lets running tool calls finish, terminates sessions and
+///    closes backend servers (killing stdio children that don't exit).
+/// 2. Await the task up to [`GATEWAY_SHUTDOWN_TIMEOUT`] so Rust Drop closes
+///    the listener fd.
 /// 3. If the task hasn't returned by then, abort as a last resort.
 pub(crate) async fn shutdown_gateway_handle(mut handle: mcpmux_gateway::GatewayServerHandle) {
     let abort = handle.task.abort_handle();
     handle.shutdown();
-    match tokio::time::timeout(std::time::Duration::from_secs(2), handle.task).await {
+    match tokio::time::timeout(GATEWAY_SHUTDOWN_TIMEOUT, handle.task).await {
         Ok(Ok(Ok(()))) => info!("[Gateway] Gateway task exited cleanly"),
         Ok(Ok(Err(e))) => warn!(
             "[Gateway] Gateway task returned error during shutdown: {}",
@@ -109,8 +145,9 @@ pub(crate) async fn shutdown_gateway_handle(mut handle: mcpmux_gateway::GatewayS
         Ok(Err(e)) => warn!("[Gateway] Gateway task join error: {}", e),
         Err(_) => {
             warn!(
-                "[Gateway] Graceful shutdown timed out after 2s — aborting task \
-                 (listener socket may briefly linger in kernel)"
+                "[Gateway] Graceful shutdown timed out after {:?} — aborting task \
+                 (listener socket may briefly linger in kernel)",
+                GATEWAY_SHUTDOWN_TIMEOUT
             );
             abort.abort();
         }
@@ -126,6 +163,24 @@ pub(crate) async fn shutdown_gateway_handle(mut handle: mcpmux_gateway::GatewayS
 /// window.
 const GATEWAY_PUBLIC_BASE_URL_KEY: &str = "gateway.public_base_url";
 const GATEWAY_NETWORK_ACCESS_KEY: &str = "gateway.network_access_enabled";
+/// Host in the local gateway URL (unset = `localhost`)
+const GATEWAY_BASE_URL_HOST_KEY: &str = "gateway.base_url_host";
+const GATEWAY_CORS_ENABLED_KEY: &str = "gateway.cors_enabled";
+/// JSON [`CorsConfig`] replacing the default CORS policies
+const GATEWAY_CORS_KEY: &str = "gateway.cors";
+/// JSON object of path prefix → requests per minute, overriding the defaults.
+/// Superseded by [`GATEWAY_RATE_LIMIT_POLICIES_KEY`] once that is set.
+const GATEWAY_RATE_LIMITS_KEY: &str = "gateway.rate_limits";
+/// JSON array of [`RateLimitPolicy`], the whole policy table
+const GATEWAY_RATE_LIMIT_POLICIES_KEY: &str = "gateway.rate_limit_policies";
+/// JSON [`RequestLimits`] replacing the defaults
+const GATEWAY_REQUEST_LIMITS_KEY: &str = "gateway.request_limits";
+/// Enabled servers connected at the same time on startup
+const GATEWAY_AUTO_CONNECT_CONCURRENCY_KEY: &str = "gateway.auto_connect_concurrency";
+/// Most servers auto-connect may start at once
+const MAX_AUTO_CONNECT_CONCURRENCY: usize = 32;
+/// Port of the gRPC control plane; unset turns it off
+const GATEWAY_GRPC_PORT_KEY: &str = "gateway.grpc_port";
 
 pub(crate) fn normalize_public_base_url(raw: &str) -> Result<Option<String>, String> {
     let trimmed = raw.trim();
@@ -190,14 +245,52 @@ pub(crate) async fn load_public_base_url(app_state: &AppState) -> Option<String>
     load_public_base_url_from_repo(&app_state.settings_repository).await
 }
 
-/// The address the gateway binds to: loopback by default, or `0.0.0.0` (all
-/// interfaces) once the user opts into network access so other devices on the
-/// LAN can reach it.
+/// A host for the local gateway URL: an IP address (IPv6 with or without
+/// brackets) or a hostname. Empty means the `localhost` default.
+pub(crate) fn normalize_base_url_host(raw: &str) -> Result<Option<String>, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let unbracketed = trimmed
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(trimmed);
+    if let Ok(ip) = unbracketed.parse::<std::net::IpAddr>() {
+        return Ok(Some(ip.to_string()));
+    }
+    match url::Host::parse(trimmed) {
+        Ok(url::Host::Domain(domain)) if !domain.contains([':', '/', '@']) => Ok(Some(domain)),
+        _ => Err(format!(
+            "Invalid host {:?}: use an IP address such as 127.0.0.1 or ::1, or a hostname",
+            trimmed
+        )),
+    }
+}
+
+pub(crate) async fn load_base_url_host_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> Option<String> {
+    settings_repository
+        .get(GATEWAY_BASE_URL_HOST_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| normalize_base_url_host(&value).ok().flatten())
+}
+
+pub(crate) async fn load_base_url_host(app_state: &AppState) -> Option<String> {
+    load_base_url_host_from_repo(&app_state.settings_repository).await
+}
+
+/// The address the gateway binds to: both loopback addresses (`127.0.0.1`
+/// and `::1`) by default, or `0.0.0.0` (all interfaces) once the user opts
+/// into network access so other devices on the LAN can reach it.
 pub(crate) fn bind_host_for(network_access: bool) -> &'static str {
     if network_access {
         "0.0.0.0"
     } else {
-        "127.0.0.1"
+        mcpmux_gateway::DUAL_STACK_LOOPBACK
     }
 }
 
@@ -233,12 +326,171 @@ pub(crate) async fn load_gateway_auth_disabled(app_state: &AppState) -> bool {
     load_gateway_auth_disabled_from_repo(&app_state.settings_repository).await
 }
 
-pub(crate) fn advertised_base_url(public_base_url: Option<&str>, port: u16) -> String {
+pub(crate) async fn load_cors_enabled_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> bool {
+    settings_repository
+        .get(GATEWAY_CORS_ENABLED_KEY)
+        .await
+        .ok()
+        .flatten()
+        .map(|value| value != "false")
+        .unwrap_or(true)
+}
+
+/// CORS policies stored in settings; the defaults when unset or unreadable
+pub(crate) async fn load_cors_config_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> CorsConfig {
+    settings_repository
+        .get(GATEWAY_CORS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<CorsConfig>(&json).ok())
+        .filter(|config| config.validate().is_ok())
+        .unwrap_or_default()
+}
+
+/// Per-minute overrides stored in settings, keyed by path prefix
+async fn load_rate_limit_overrides(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> HashMap<String, u32> {
+    settings_repository
+        .get(GATEWAY_RATE_LIMITS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// `rules` with shared per-minute limits set for the given routes
+fn rate_limits_with(
+    mut rules: Vec<RateLimitPolicy>,
+    overrides: &HashMap<String, u32>,
+) -> Vec<RateLimitPolicy> {
+    for (route, per_minute) in overrides {
+        let policy = RateLimitPolicy::per_minute(route, *per_minute);
+        match rules
+            .iter_mut()
+            .find(|p| &p.route == route && p.client_id.is_none())
+        {
+            Some(existing) => *existing = policy,
+            None => rules.push(policy),
+        }
+    }
+    rules
+}
+
+/// The stored policy table, or the defaults with the older per-minute
+/// overrides applied
+pub(crate) async fn load_rate_limits_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> Vec<RateLimitPolicy> {
+    let stored = settings_repository
+        .get(GATEWAY_RATE_LIMIT_POLICIES_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<Vec<RateLimitPolicy>>(&json).ok())
+        .filter(|policies| policies.iter().all(|p| p.validate().is_ok()));
+    match stored {
+        Some(policies) => policies,
+        None => rate_limits_with(
+            default_rate_limit_rules(),
+            &load_rate_limit_overrides(settings_repository).await,
+        ),
+    }
+}
+
+/// Store the whole policy table, replacing the older per-minute overrides
+async fn save_rate_limit_policies(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+    policies: &[RateLimitPolicy],
+) -> Result<(), String> {
+    let json = serde_json::to_string(policies).map_err(|e| e.to_string())?;
+    settings_repository
+        .set(GATEWAY_RATE_LIMIT_POLICIES_KEY, &json)
+        .await
+        .map_err(|e| e.to_string())?;
+    settings_repository
+        .delete(GATEWAY_RATE_LIMITS_KEY)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Body size, header timeout and connection limits stored in settings; the
+/// defaults when unset or unreadable
+pub(crate) async fn load_request_limits_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> RequestLimits {
+    settings_repository
+        .get(GATEWAY_REQUEST_LIMITS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<RequestLimits>(&json).ok())
+        .filter(|limits| limits.validate().is_ok())
+        .unwrap_or_default()
+}
+
+/// Servers auto-connect starts at once, from settings; the default when
+/// unset or out of range
+pub(crate) async fn load_auto_connect_concurrency_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> usize {
+    settings_repository
+        .get(GATEWAY_AUTO_CONNECT_CONCURRENCY_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|n| (1..=MAX_AUTO_CONNECT_CONCURRENCY).contains(n))
+        .unwrap_or(mcpmux_gateway::DEFAULT_AUTO_CONNECT_CONCURRENCY)
+}
+
+pub(crate) async fn load_grpc_port_from_repo(
+    settings_repository: &Arc<dyn mcpmux_core::AppSettingsRepository>,
+) -> Option<u16> {
+    settings_repository
+        .get(GATEWAY_GRPC_PORT_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<u16>().ok())
+        .filter(|port| *port != 0)
+}
+
+/// Apply `change` to the running gateway's config, if it is running.
+/// Returns the new config, or `None` when the gateway is stopped.
+async fn reload_running_gateway(
+    gateway_state: &Arc<RwLock<GatewayAppState>>,
+    change: impl FnOnce(&mut mcpmux_gateway::GatewayConfig),
+) -> Result<Option<mcpmux_gateway::GatewayConfig>, String> {
+    let state = gateway_state.read().await;
+    let Some(handle) = state.handle.as_ref().filter(|h| h.is_active()) else {
+        return Ok(None);
+    };
+    let mut config = handle.config().await;
+    change(&mut config);
+    handle
+        .reload_config(config.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(Some(config))
+}
+
+pub(crate) fn advertised_base_url(
+    public_base_url: Option<&str>,
+    base_url_host: Option<&str>,
+    port: u16,
+) -> String {
     public_base_url
         .map(str::trim)
         .filter(|url| !url.is_empty())
         .map(|url| url.trim_end_matches('/').to_string())
-        .unwrap_or_else(|| format!("http://localhost:{}", port))
+        .unwrap_or_else(|| mcpmux_gateway::local_base_url(base_url_host, port))
 }
 
 pub(crate) fn focus_main_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
@@ -420,10 +672,19 @@ pub(crate) async fn init_gateway_runtime(
     info!("[Gateway] Periodic refresh loop started");
 }
 
+/// Last journal sequence the domain event bridge forwarded, so a bridge
+/// started for a restarted gateway resumes where the previous one stopped.
+/// 0 until the first bridge forwards anything.
+static BRIDGE_CURSOR: AtomicU64 = AtomicU64::new(0);
+
 /// Start domain event bridge from Gateway to Tauri
 ///
 /// Routes all DomainEvents to appropriate frontend channels.
 /// This replaces the old GatewayEvent bridge with a unified DomainEvent system.
+///
+/// With an event journal the bridge reads from it rather than the broadcast,
+/// so a burst of events can't make the UI miss any, and every payload carries
+/// the event's `sequence` for `get_domain_events_since`.
 pub fn start_domain_event_bridge(
     app_handle: &AppHandle,
     gateway_state: Arc<RwLock<mcpmux_gateway::GatewayState>>,
@@ -431,35 +692,45 @@ pub fn start_domain_event_bridge(
     let app_handle_clone = app_handle.clone();
 
     tokio::spawn(async move {
-        let mut event_rx = {
+        let (journal, mut event_rx) = {
             let state = gateway_state.read().await;
-            state.subscribe_domain_events()
+            (state.event_journal(), state.subscribe_domain_events())
         };
 
         info!("[Gateway] Domain event bridge started");
 
-        while let Ok(event) = event_rx.recv().await {
-            let event_type = event.type_name();
-
-            // Some domain events imply a popup the user must see (a workspace
-            // root needs binding, a backend wants OAuth, etc.). Bring the
-            // window forward BEFORE emitting so the popup animates into a
-            // visible window instead of rendering behind another app.
-            if matches!(event, DomainEvent::WorkspaceNeedsBinding { .. }) {
-                focus_main_window(&app_handle_clone);
+        if let Some(journal) = journal {
+            drop(event_rx);
+            let cursor = match BRIDGE_CURSOR.load(Ordering::Acquire) {
+                0 => journal.latest_sequence(),
+                cursor => cursor,
+            };
+            let mut reader = journal.reader(cursor);
+            loop {
+                match reader.next().await {
+                    Ok(Some(entry)) => {
+                        forward_domain_event(&app_handle_clone, &entry.event, Some(entry.sequence));
+                        BRIDGE_CURSOR.store(entry.sequence, Ordering::Release);
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("[Gateway] Failed to read event journal: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    }
+                }
             }
-
-            // Map domain events to UI channels
-            let (channel, payload) = map_domain_event_to_ui(&event);
-
-            trace!(
-                event_type = event_type,
-                channel = channel,
-                "[Gateway] Forwarding domain event to UI"
-            );
-
-            if let Err(e) = app_handle_clone.emit(channel, payload) {
-                error!("[Gateway] Failed to emit {} event: {}", channel, e);
+        } else {
+            loop {
+                match event_rx.recv().await {
+                    Ok(event) => forward_domain_event(&app_handle_clone, &event, None),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            skipped,
+                            "[Gateway] Domain event bridge lagged; UI missed events"
+                        );
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
             }
         }
 
@@ -467,6 +738,59 @@ pub fn start_domain_event_bridge(
     });
 }
 
+/// Emit one domain event on its UI channel
+pub(crate) fn forward_domain_event(
+    app_handle: &AppHandle,
+    event: &DomainEvent,
+    sequence: Option<u64>,
+) {
+    let event_type = event.type_name();
+
+    // Some domain events imply a popup the user must see (a workspace
+    // root needs binding, a backend wants OAuth, etc.). Bring the
+    // window forward BEFORE emitting so the popup animates into a
+    // visible window instead of rendering behind another app.
+    if matches!(event, DomainEvent::WorkspaceNeedsBinding { .. }) {
+        focus_main_window(app_handle);
+    }
+
+    if crate::tray::affects_tray_menu(event) {
+        crate::tray::schedule_tray_refresh(app_handle);
+    }
+
+    // Map domain events to UI channels
+    let (channel, mut payload) = map_domain_event_to_ui(event);
+    if let (Some(sequence), Some(object)) = (sequence, payload.as_object_mut()) {
+        object.insert("sequence".to_string(), sequence.into());
+    }
+
+    trace!(
+        event_type = event_type,
+        channel = channel,
+        "[Gateway] Forwarding domain event to UI"
+    );
+
+    if let Err(e) = app_handle.emit(channel, payload) {
+        error!("[Gateway] Failed to emit {} event: {}", channel, e);
+    }
+}
+
+/// Domain events journaled after `sequence`, oldest first, so the UI can
+/// catch up on what it missed while reloading. Returns at most `limit`
+/// (default 500) events.
+#[tauri::command]
+pub async fn get_domain_events_since(
+    sequence: u64,
+    limit: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<JournaledEvent>, String> {
+    app_state
+        .event_journal_repository
+        .since(sequence, limit.unwrap_or(500).min(5000))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Map a DomainEvent to UI channel and payload
 fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Value) {
     match event {
@@ -499,6 +823,20 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
                 "space_id": space_id,
             }),
         ),
+        // A Space config file failed schema validation and wasn't synced;
+        // the config editor marks each issue on its line
+        DomainEvent::SpaceConfigInvalid {
+            space_id,
+            file,
+            issues,
+        } => (
+            "space-config-invalid",
+            serde_json::json!({
+                "space_id": space_id,
+                "file": file,
+                "issues": issues,
+            }),
+        ),
         // Server lifecycle events
         DomainEvent::ServerInstalled {
             space_id,
@@ -734,6 +1072,21 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
                 "action": "stopped",
             }),
         ),
+        DomainEvent::AutoConnectProgress {
+            total,
+            connected,
+            needs_oauth,
+            failed,
+        } => (
+            "gateway-changed",
+            serde_json::json!({
+                "action": "auto_connect_progress",
+                "total": total,
+                "connected": connected,
+                "needs_oauth": needs_oauth,
+                "failed": failed,
+            }),
+        ),
 
         // MCP capability notifications (informational)
         DomainEvent::ToolsChanged {
@@ -769,6 +1122,29 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
                 "server_id": server_id,
             }),
         ),
+        // A server changed a tool's input schema; the UI offers to accept
+        // it when the tool is pinned
+        DomainEvent::ToolSchemaChanged {
+            space_id,
+            server_id,
+            tool,
+            added,
+            removed,
+            breaking,
+            held,
+        } => (
+            "tool-schema-changed",
+            serde_json::json!({
+                "space_id": space_id,
+                "server_id": server_id,
+                "tool": tool,
+                "added": added,
+                "removed": removed,
+                "breaking": breaking,
+                "held": held,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            }),
+        ),
         DomainEvent::MetaToolInvoked {
             client_id,
             session_id,
@@ -791,6 +1167,30 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
             }),
         ),
 
+        // Audit row for the Connection Log; the text that matched is never
+        // part of the event
+        DomainEvent::PromptInjectionSuspected {
+            space_id,
+            server_id,
+            client_id,
+            source,
+            name,
+            heuristics,
+            blocked,
+        } => (
+            "prompt-injection-suspected",
+            serde_json::json!({
+                "space_id": space_id,
+                "server_id": server_id,
+                "client_id": client_id,
+                "source": source,
+                "name": name,
+                "heuristics": heuristics,
+                "blocked": blocked,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            }),
+        ),
+
         // Workspace binding write → tell the UI to re-load the bindings
         // table. The MCP `list_changed` notifications are handled separately
         // by MCPNotifier subscribing to the same event.
@@ -842,6 +1242,56 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
             }),
         ),
 
+        // No npx/uvx/... for a stdio server; the UI offers the install link
+        DomainEvent::RuntimeMissing {
+            space_id,
+            server_id,
+            ecosystem,
+            runners,
+            install_url,
+        } => (
+            "runtime-missing",
+            serde_json::json!({
+                "space_id": space_id,
+                "server_id": server_id,
+                "ecosystem": ecosystem,
+                "runners": runners,
+                "install_url": install_url,
+            }),
+        ),
+
+        DomainEvent::ServerCrashLoop {
+            space_id,
+            server_id,
+            cause,
+        } => (
+            "server-crash-loop",
+            serde_json::json!({
+                "space_id": space_id,
+                "server_id": server_id,
+                "cause": cause,
+            }),
+        ),
+
+        DomainEvent::ServerUpdateAvailable {
+            space_id,
+            server_id,
+            ecosystem,
+            package,
+            current_version,
+            latest_version,
+        } => (
+            "server-update-available",
+            serde_json::json!({
+                "space_id": space_id,
+                "server_id": server_id,
+                "ecosystem": ecosystem,
+                "package": package,
+                "current_version": current_version,
+                "latest_version": latest_version,
+            }),
+        ),
+
         // A Space's built-in-server config changed. The gateway-side
         // MCPNotifier handles the `tools/list_changed` push to that Space's
         // MCP clients; this forwards it to the desktop UI so an open Built-in
@@ -850,6 +1300,12 @@ fn map_domain_event_to_ui(event: &DomainEvent) -> (&'static str, serde_json::Val
             "builtin-server-config-changed",
             serde_json::json!({ "space_id": space_id }),
         ),
+        // An event this build doesn't know yet goes out on a generic channel
+        // with its serialized form, so the UI can still log it
+        other => (
+            "domain-event",
+            serde_json::to_value(other).unwrap_or(serde_json::Value::Null),
+        ),
     }
 }
 
@@ -881,16 +1337,11 @@ fn create_gateway_dependencies(
 
     // Build dependencies using builder pattern (DI)
     let mut builder = mcpmux_gateway::DependenciesBuilder::new()
-        .with_installed_server_repo(app_state.installed_server_repository.clone())
-        .with_credential_repo(app_state.credential_repository.clone())
-        .with_backend_oauth_repo(app_state.backend_oauth_repository.clone())
-        .with_feature_repo(app_state.server_feature_repository_core.clone())
-        .with_feature_set_repo(app_state.feature_set_repository.clone())
+        .with_repositories(&app_state.repositories)
         .with_server_discovery(app_state.server_discovery.clone())
         .with_log_manager(app_state.server_log_manager.clone())
-        .with_database(app_state.database())
         .with_state_dir(app_state.data_dir().to_path_buf())
-        .with_settings_repo(app_state.settings_repository.clone());
+        .with_managed_policy(app_state.managed_policy.clone());
 
     if let Some(secret) = jwt_secret {
         builder = builder.with_jwt_secret(secret);
@@ -943,9 +1394,66 @@ pub async fn get_gateway_status(
         url: state.url.clone(),
         active_sessions,
         connected_backends,
+        traffic_paused: state.traffic_paused,
     })
 }
 
+/// Live activity of every connected MCP session, most recently active first.
+/// Empty while the gateway is stopped.
+#[tauri::command]
+pub async fn get_session_activity(
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<Vec<mcpmux_gateway::services::SessionActivity>, String> {
+    let state = gateway_state.read().await;
+    Ok(state
+        .session_activity
+        .as_ref()
+        .filter(|_| state.running)
+        .map(|registry| registry.sessions())
+        .unwrap_or_default())
+}
+
+/// Pause or resume every tool call through the gateway. While paused,
+/// sessions and backend connections stay up but `tools/call` is refused
+/// with a "paused by the user" error. Survives gateway restarts.
+#[tauri::command]
+pub async fn pause_gateway_traffic(
+    paused: bool,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    set_traffic_paused(&app_handle, &gateway_state, paused).await;
+    Ok(())
+}
+
+/// Apply the pause switch to the running gateway and tell the UI and tray
+pub(crate) async fn set_traffic_paused<R: tauri::Runtime>(
+    app_handle: &AppHandle<R>,
+    gateway_state: &RwLock<GatewayAppState>,
+    paused: bool,
+) {
+    {
+        let mut state = gateway_state.write().await;
+        state.traffic_paused = paused;
+        if let Some(ref pause) = state.traffic_pause {
+            pause.set_paused(paused);
+        }
+    }
+    info!(
+        "[Gateway] Tool calls {}",
+        if paused { "PAUSED by user" } else { "resumed" }
+    );
+
+    let action = if paused { "paused" } else { "resumed" };
+    if let Err(e) = app_handle.emit("gateway-changed", serde_json::json!({ "action": action })) {
+        warn!(
+            "[Gateway] Failed to emit gateway-changed({}): {}",
+            action, e
+        );
+    }
+    crate::tray::schedule_tray_refresh(app_handle);
+}
+
 /// Start the gateway server.
 ///
 /// `port` forces a specific port (used for ad-hoc overrides from a test or
@@ -1017,8 +1525,13 @@ pub async fn start_gateway(
     };
 
     let public_base_url = load_public_base_url(&app_state).await;
-    let url = advertised_base_url(public_base_url.as_deref(), final_port);
-    let local_url = format!("http://localhost:{}", final_port);
+    let base_url_host = load_base_url_host(&app_state).await;
+    let url = advertised_base_url(
+        public_base_url.as_deref(),
+        base_url_host.as_deref(),
+        final_port,
+    );
+    let local_url = mcpmux_gateway::local_base_url(base_url_host.as_deref(), final_port);
 
     info!("Starting gateway on {} (advertising {})", local_url, url);
 
@@ -1034,7 +1547,17 @@ pub async fn start_gateway(
         host: bind_host_for(network_access).to_string(),
         port: final_port,
         public_base_url: public_base_url.clone(),
-        enable_cors: true,
+        base_url_host: base_url_host.clone(),
+        enable_cors: load_cors_enabled_from_repo(&app_state.settings_repository).await,
+        cors: load_cors_config_from_repo(&app_state.settings_repository).await,
+        rate_limits: load_rate_limits_from_repo(&app_state.settings_repository).await,
+        request_limits: load_request_limits_from_repo(&app_state.settings_repository).await,
+        auto_connect_concurrency: load_auto_connect_concurrency_from_repo(
+            &app_state.settings_repository,
+        )
+        .await,
+        drain_timeout: GATEWAY_DRAIN_TIMEOUT,
+        grpc_port: load_grpc_port_from_repo(&app_state.settings_repository).await,
     };
 
     // Create self-contained gateway server with DI
@@ -1045,10 +1568,13 @@ pub async fn start_gateway(
     let gw_state = server.state();
     let pool_service = server.pool_service();
     let feature_service = server.feature_service();
+    let routing_service = server.routing_service();
     let event_emitter = server.event_emitter();
     let server_manager = server.server_manager();
     let grant_service = server.grant_service();
     let session_roots = server.session_roots();
+    let session_activity = server.session_activity();
+    let package_updates = server.package_updates();
 
     // Seed the system-wide inbound-auth toggle into the running gateway from
     // persisted settings (default: auth required). Live changes go through
@@ -1069,6 +1595,7 @@ pub async fn start_gateway(
     // incoming approval requests reach the React dialog.
     let approval_broker = server.approval_broker();
     attach_approval_publisher(&approval_broker, app_handle.clone()).await;
+    let traffic_pause = server.traffic_pause();
 
     // Start domain event bridge (clean architecture)
     start_domain_event_bridge(&app_handle, gw_state.clone());
@@ -1100,10 +1627,15 @@ pub async fn start_gateway(
     state.gateway_state = Some(gw_state);
     state.pool_service = Some(pool_service);
     state.feature_service = Some(feature_service);
+    state.routing_service = Some(routing_service);
     state.event_emitter = Some(event_emitter);
     state.grant_service = Some(grant_service);
     state.approval_broker = Some(approval_broker);
     state.session_roots = Some(session_roots);
+    state.session_activity = Some(session_activity);
+    state.package_updates = Some(package_updates);
+    traffic_pause.set_paused(state.traffic_paused);
+    state.traffic_pause = Some(traffic_pause);
     info!(
         "[Gateway] Started — url={}, event_emitter={}, grant_service={}",
         url,
@@ -1125,6 +1657,26 @@ pub async fn start_gateway(
     ) {
         warn!("[Gateway] Failed to emit gateway-changed(started): {}", e);
     }
+    // Client configs exported for the preferred port no longer reach the
+    // gateway; the UI offers to update them
+    let preferred_url = advertised_base_url(
+        public_base_url.as_deref(),
+        base_url_host.as_deref(),
+        preferred_port,
+    );
+    if url != preferred_url {
+        if let Err(e) = app_handle.emit(
+            "gateway-port-changed",
+            serde_json::json!({
+                "preferredPort": preferred_port,
+                "port": final_port,
+                "url": url,
+            }),
+        ) {
+            warn!("[Gateway] Failed to emit gateway-port-changed: {}", e);
+        }
+    }
+    crate::tray::schedule_tray_refresh(&app_handle);
 
     Ok(url)
 }
@@ -1136,7 +1688,7 @@ pub async fn stop_gateway(
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     // Take the handle out under the lock, then drop the guard BEFORE
-    // awaiting the shutdown — otherwise the lock is held for up to 2s
+    // awaiting the shutdown — otherwise the lock is held for seconds
     // and every concurrent status query blocks.
     let handle = {
         let mut state = gateway_state.write().await;
@@ -1158,6 +1710,7 @@ pub async fn stop_gateway(
     if let Err(e) = app_handle.emit("gateway-changed", serde_json::json!({"action": "stopped"})) {
         warn!("[Gateway] Failed to emit gateway-changed(stopped): {}", e);
     }
+    crate::tray::schedule_tray_refresh(&app_handle);
 
     Ok(())
 }
@@ -1206,14 +1759,19 @@ pub async fn get_gateway_port_settings(
     })
 }
 
-/// Persist a custom gateway port. Takes effect on the next gateway start.
+/// Persist a custom gateway port and move a running gateway onto it.
 ///
-/// Does NOT touch a running gateway — the UI is expected to offer a
-/// "Restart gateway" action. The port must be in the user-space range
-/// (1024–65535). Ports ≤ 1023 are rejected to avoid privileged-port
-/// surprises on Unix.
+/// The running gateway binds the new port before releasing the old one, so
+/// a busy port fails without persisting anything or dropping the listener.
+/// The port must be in the user-space range (1024–65535). Ports ≤ 1023 are
+/// rejected to avoid privileged-port surprises on Unix.
 #[tauri::command]
-pub async fn set_gateway_port(port: u16, app_state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_gateway_port(
+    port: u16,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
     if port < 1024 {
         return Err(format!(
             "Port {} is in the privileged range (≤ 1023). Choose a port between 1024 and 65535.",
@@ -1221,6 +1779,25 @@ pub async fn set_gateway_port(port: u16, app_state: State<'_, AppState>) -> Resu
         ));
     }
 
+    if let Some(config) = reload_running_gateway(&gateway_state, |c| c.port = port).await? {
+        let url = advertised_base_url(
+            config.public_base_url.as_deref(),
+            config.base_url_host.as_deref(),
+            port,
+        );
+        {
+            let mut state = gateway_state.write().await;
+            state.url = Some(url.clone());
+            state.bound_port = Some(port);
+        }
+        if let Err(e) = app_handle.emit(
+            "gateway-changed",
+            serde_json::json!({ "action": "rebound", "url": url, "port": port }),
+        ) {
+            warn!("[Gateway] Failed to emit gateway-changed(rebound): {}", e);
+        }
+    }
+
     app_state
         .gateway_port_service
         .save_port(port)
@@ -1246,6 +1823,269 @@ pub async fn reset_gateway_port(app_state: State<'_, AppState>) -> Result<(), St
     Ok(())
 }
 
+/// Whether the gateway answers browser CORS requests. Default true.
+#[tauri::command]
+pub async fn get_gateway_cors_enabled(app_state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(load_cors_enabled_from_repo(&app_state.settings_repository).await)
+}
+
+/// Enable/disable CORS. Applies to a running gateway immediately.
+#[tauri::command]
+pub async fn set_gateway_cors_enabled(
+    enabled: bool,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    app_state
+        .settings_repository
+        .set(GATEWAY_CORS_ENABLED_KEY, &enabled.to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+    reload_running_gateway(&gateway_state, |c| c.enable_cors = enabled).await?;
+    info!("[Gateway] CORS enabled set to {}", enabled);
+    Ok(())
+}
+
+/// Allowed origins, methods and headers, with per-route policies.
+#[tauri::command]
+pub async fn get_gateway_cors_config(app_state: State<'_, AppState>) -> Result<CorsConfig, String> {
+    Ok(load_cors_config_from_repo(&app_state.settings_repository).await)
+}
+
+/// Replace the CORS policies, or restore the defaults with `None`. Applies
+/// to a running gateway immediately.
+#[tauri::command]
+pub async fn set_gateway_cors_config(
+    config: Option<CorsConfig>,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    match &config {
+        Some(config) => {
+            config.validate().map_err(|e| e.to_string())?;
+            let json = serde_json::to_string(config).map_err(|e| e.to_string())?;
+            app_state
+                .settings_repository
+                .set(GATEWAY_CORS_KEY, &json)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            app_state
+                .settings_repository
+                .delete(GATEWAY_CORS_KEY)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    let config = config.unwrap_or_default();
+    reload_running_gateway(&gateway_state, |c| c.cors = config).await?;
+    info!("[Gateway] CORS policies updated");
+    Ok(())
+}
+
+/// Effective shared rate limits as path prefix → requests per minute.
+#[tauri::command]
+pub async fn get_gateway_rate_limits(
+    app_state: State<'_, AppState>,
+) -> Result<HashMap<String, u32>, String> {
+    Ok(load_rate_limits_from_repo(&app_state.settings_repository)
+        .await
+        .into_iter()
+        .filter(|policy| policy.client_id.is_none() && policy.window_secs == 60)
+        .map(|policy| (policy.route, policy.max_requests))
+        .collect())
+}
+
+/// Override shared rate limits (path prefix → requests per minute; omitted
+/// prefixes keep their limits). Applies to a running gateway immediately.
+#[tauri::command]
+pub async fn set_gateway_rate_limits(
+    limits: HashMap<String, u32>,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    let current = load_rate_limits_from_repo(&app_state.settings_repository).await;
+    let rules = rate_limits_with(current, &limits);
+    for policy in &rules {
+        policy.validate().map_err(|e| e.to_string())?;
+    }
+    save_rate_limit_policies(&app_state.settings_repository, &rules).await?;
+    reload_running_gateway(&gateway_state, |c| c.rate_limits = rules).await?;
+    info!("[Gateway] Rate limits updated");
+    Ok(())
+}
+
+/// The rate limit policy table: limits by route, for all clients, each
+/// client, or one client.
+#[tauri::command]
+pub async fn get_gateway_rate_limit_policies(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<RateLimitPolicy>, String> {
+    Ok(load_rate_limits_from_repo(&app_state.settings_repository).await)
+}
+
+/// Replace the rate limit policy table, or restore the defaults with `None`.
+/// Applies to a running gateway immediately.
+#[tauri::command]
+pub async fn set_gateway_rate_limit_policies(
+    policies: Option<Vec<RateLimitPolicy>>,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    let settings = &app_state.settings_repository;
+    let rules = match policies {
+        Some(policies) => {
+            for policy in &policies {
+                policy.validate().map_err(|e| e.to_string())?;
+            }
+            save_rate_limit_policies(settings, &policies).await?;
+            policies
+        }
+        None => {
+            for key in [GATEWAY_RATE_LIMIT_POLICIES_KEY, GATEWAY_RATE_LIMITS_KEY] {
+                settings.delete(key).await.map_err(|e| e.to_string())?;
+            }
+            default_rate_limit_rules()
+        }
+    };
+    info!(
+        "[Gateway] Rate limit policies updated ({} rules)",
+        rules.len()
+    );
+    reload_running_gateway(&gateway_state, |c| c.rate_limits = rules).await?;
+    Ok(())
+}
+
+/// Requests the running gateway refused for rate limits, by policy and
+/// client. Empty while the gateway is stopped.
+#[tauri::command]
+pub async fn get_gateway_rate_limit_rejections(
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<Vec<RateLimitRejections>, String> {
+    let state = gateway_state.read().await;
+    Ok(state
+        .handle
+        .as_ref()
+        .map(|handle| handle.rate_limit_rejections())
+        .unwrap_or_default())
+}
+
+/// Largest request body, header read timeout and connections per IP.
+#[tauri::command]
+pub async fn get_gateway_request_limits(
+    app_state: State<'_, AppState>,
+) -> Result<RequestLimits, String> {
+    Ok(load_request_limits_from_repo(&app_state.settings_repository).await)
+}
+
+/// Replace the request limits, or restore the defaults with `None`. Applies
+/// to a running gateway immediately; the header timeout to new connections.
+#[tauri::command]
+pub async fn set_gateway_request_limits(
+    limits: Option<RequestLimits>,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    match &limits {
+        Some(limits) => {
+            limits.validate().map_err(|e| e.to_string())?;
+            let json = serde_json::to_string(limits).map_err(|e| e.to_string())?;
+            app_state
+                .settings_repository
+                .set(GATEWAY_REQUEST_LIMITS_KEY, &json)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            app_state
+                .settings_repository
+                .delete(GATEWAY_REQUEST_LIMITS_KEY)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    let limits = limits.unwrap_or_default();
+    reload_running_gateway(&gateway_state, |c| c.request_limits = limits).await?;
+    info!("[Gateway] Request limits updated");
+    Ok(())
+}
+
+/// How many enabled servers are connected at the same time on startup.
+#[tauri::command]
+pub async fn get_gateway_auto_connect_concurrency(
+    app_state: State<'_, AppState>,
+) -> Result<usize, String> {
+    Ok(load_auto_connect_concurrency_from_repo(&app_state.settings_repository).await)
+}
+
+/// Set how many servers auto-connect starts at once, or restore the default
+/// with `None`. Takes effect the next time the gateway starts.
+#[tauri::command]
+pub async fn set_gateway_auto_connect_concurrency(
+    concurrency: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    match concurrency {
+        Some(n) => {
+            if !(1..=MAX_AUTO_CONNECT_CONCURRENCY).contains(&n) {
+                return Err(format!(
+                    "Auto-connect concurrency must be between 1 and {}",
+                    MAX_AUTO_CONNECT_CONCURRENCY
+                ));
+            }
+            app_state
+                .settings_repository
+                .set(GATEWAY_AUTO_CONNECT_CONCURRENCY_KEY, &n.to_string())
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            app_state
+                .settings_repository
+                .delete(GATEWAY_AUTO_CONNECT_CONCURRENCY_KEY)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    info!("[Gateway] Auto-connect concurrency updated");
+    Ok(())
+}
+
+/// Port of the gRPC control plane, or `None` when it is off.
+#[tauri::command]
+pub async fn get_gateway_grpc_port(app_state: State<'_, AppState>) -> Result<Option<u16>, String> {
+    Ok(load_grpc_port_from_repo(&app_state.settings_repository).await)
+}
+
+/// Serve the gRPC control plane on `port`, or turn it off with `None`.
+/// Takes effect the next time the gateway starts.
+#[tauri::command]
+pub async fn set_gateway_grpc_port(
+    port: Option<u16>,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    match port {
+        Some(0) => return Err("gRPC port must be between 1 and 65535".to_string()),
+        Some(port) => {
+            app_state
+                .settings_repository
+                .set(GATEWAY_GRPC_PORT_KEY, &port.to_string())
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            app_state
+                .settings_repository
+                .delete(GATEWAY_GRPC_PORT_KEY)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    info!("[Gateway] gRPC port updated");
+    Ok(())
+}
+
 /// App-settings key for the system-wide inbound-auth toggle. Stored as
 /// `"true"`/`"false"`; missing means auth is required (the secure default).
 pub const GATEWAY_AUTH_DISABLED_KEY: &str = "gateway.auth_disabled";
@@ -1298,6 +2138,8 @@ pub struct GatewayPublicUrlSettings {
     pub configured_public_base_url: Option<String>,
     pub active_public_base_url: Option<String>,
     pub local_base_url: Option<String>,
+    /// Host in the local URL, `None` for `localhost`
+    pub configured_base_url_host: Option<String>,
 }
 
 #[tauri::command]
@@ -1306,6 +2148,7 @@ pub async fn get_gateway_public_url_settings(
     app_state: State<'_, AppState>,
 ) -> Result<GatewayPublicUrlSettings, String> {
     let configured_public_base_url = load_public_base_url(&app_state).await;
+    let configured_base_url_host = load_base_url_host(&app_state).await;
     let (active_public_base_url, local_base_url) = {
         let state = gateway_state.read().await;
         (
@@ -1314,9 +2157,9 @@ pub async fn get_gateway_public_url_settings(
             } else {
                 None
             },
-            state
-                .bound_port
-                .map(|port| format!("http://localhost:{}", port)),
+            state.bound_port.map(|port| {
+                mcpmux_gateway::local_base_url(configured_base_url_host.as_deref(), port)
+            }),
         )
     };
 
@@ -1324,6 +2167,7 @@ pub async fn get_gateway_public_url_settings(
         configured_public_base_url,
         active_public_base_url,
         local_base_url,
+        configured_base_url_host,
     })
 }
 
@@ -1375,6 +2219,65 @@ pub async fn reset_gateway_public_base_url(app_state: State<'_, AppState>) -> Re
     Ok(())
 }
 
+/// Persist the host used in the local gateway URL, e.g. `127.0.0.1` where
+/// `localhost` resolves to an address clients can't reach. Pass `None` or an
+/// empty string for the `localhost` default. A running gateway advertises
+/// the new URL right away.
+#[tauri::command]
+pub async fn set_gateway_base_url_host(
+    host: Option<String>,
+    app_state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let normalized = match host.as_deref() {
+        Some(raw) => normalize_base_url_host(raw)?,
+        None => None,
+    };
+
+    match normalized.as_deref() {
+        Some(host) => {
+            app_state
+                .settings_repository
+                .set(GATEWAY_BASE_URL_HOST_KEY, host)
+                .await
+                .map_err(|e| e.to_string())?;
+            info!("[Gateway] Persisted base URL host: {}", host);
+        }
+        None => {
+            app_state
+                .settings_repository
+                .delete(GATEWAY_BASE_URL_HOST_KEY)
+                .await
+                .map_err(|e| e.to_string())?;
+            info!("[Gateway] Cleared base URL host — reverting to localhost");
+        }
+    }
+
+    // Exports read the saved host, so a gateway that needs a restart to
+    // accept a new hostname doesn't fail the save.
+    match reload_running_gateway(&gateway_state, |c| c.base_url_host = normalized.clone()).await {
+        Ok(Some(config)) => {
+            let url = advertised_base_url(
+                config.public_base_url.as_deref(),
+                config.base_url_host.as_deref(),
+                config.port,
+            );
+            gateway_state.write().await.url = Some(url.clone());
+            if let Err(e) = app_handle.emit(
+                "gateway-changed",
+                serde_json::json!({ "action": "rebound", "url": url, "port": config.port }),
+            ) {
+                warn!("[Gateway] Failed to emit gateway-changed(rebound): {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("[Gateway] Base URL host saved but not applied: {}", e),
+    }
+
+    Ok(())
+}
+
 /// Whether the gateway is configured to bind all network interfaces (`0.0.0.0`).
 #[tauri::command]
 pub async fn get_gateway_network_access(app_state: State<'_, AppState>) -> Result<bool, String> {
@@ -1439,6 +2342,9 @@ pub struct GatewayStartProbe {
     pub preferred_port: u16,
     pub preferred_available: bool,
     pub source: &'static str,
+    /// The process holding the preferred port, when it's busy and the
+    /// process can be found
+    pub owner: Option<PortOwner>,
 }
 
 async fn resolve_preferred_port(
@@ -1465,10 +2371,19 @@ pub async fn probe_gateway_start(
 ) -> Result<GatewayStartProbe, String> {
     let (preferred_port, source) = resolve_preferred_port(&app_state, port).await;
     let preferred_available = is_port_available(preferred_port);
+    let owner = if preferred_available {
+        None
+    } else {
+        tokio::task::spawn_blocking(move || port_owner(preferred_port))
+            .await
+            .ok()
+            .flatten()
+    };
     Ok(GatewayStartProbe {
         preferred_port,
         preferred_available,
         source: source.as_str(),
+        owner,
     })
 }
 
@@ -1636,15 +2551,40 @@ pub async fn connect_server(
         .ok_or("Pool service not initialized")?;
     drop(state); // Release lock before async work
 
-    // Build transport config from cached definition + input values
-    let transport = mcpmux_gateway::pool::transport::resolution::build_transport_config(
-        &server_definition.transport,
-        &installed,
-        Some(app_state.data_dir()),
-    );
+    // Build transport config from cached definition + input values, with the
+    // space's active environment profile applied
+    let workspace = mcpmux_gateway::pool::transport::resolution::space_workspace(
+        app_state.space_base_dir_repository.as_ref(),
+        &space_uuid,
+    )
+    .await;
+    let profile = mcpmux_gateway::pool::transport::resolution::space_profile(
+        Some(app_state.environment_profile_repository.as_ref()),
+        &space_uuid,
+    )
+    .await;
+    let transport = profile
+        .and_then(|profile| {
+            let (template, profiled) = mcpmux_gateway::pool::transport::resolution::apply_profile(
+                profile.as_ref(),
+                &server_definition.transport,
+                &installed,
+            );
+            mcpmux_gateway::pool::transport::resolution::resolve_transport_config(
+                &template,
+                &profiled,
+                Some(app_state.data_dir()),
+                workspace.as_deref(),
+            )
+        })
+        .map_err(|e| {
+            warn!("[Gateway] {}: {}", server_id, e);
+            e.to_string()
+        })?;
 
     // Connect using pool service (manual connect from API)
-    let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport);
+    let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport)
+        .with_timeouts(installed.effective_timeouts(&server_definition));
     let result = pool_service.connect_server(&ctx).await;
 
     match result {
@@ -1818,6 +2758,7 @@ pub async fn connect_all_enabled_servers(
         mcpmux_core::ServerDefinition,
         mcpmux_core::InstalledServer,
     )> = vec![];
+    let mut invalid_config: Vec<String> = vec![];
 
     for space in &spaces {
         let space_id_str = space.id.to_string();
@@ -1828,6 +2769,17 @@ pub async fn connect_all_enabled_servers(
             .list_enabled(&space_id_str)
             .await
             .map_err(|e| e.to_string())?;
+        let workspace = mcpmux_gateway::pool::transport::resolution::space_workspace(
+            app_state.space_base_dir_repository.as_ref(),
+            &space.id,
+        )
+        .await;
+        let profile = mcpmux_gateway::pool::transport::resolution::space_profile(
+            Some(app_state.environment_profile_repository.as_ref()),
+            &space.id,
+        )
+        .await
+        .map_err(|e| e.to_string());
 
         for installed in installed_servers {
             // Use cached definition from InstalledServer (offline-first approach)
@@ -1875,11 +2827,28 @@ pub async fn connect_all_enabled_servers(
                 has_credentials,
             };
 
-            let transport = mcpmux_gateway::pool::transport::resolution::build_transport_config(
-                &server_definition.transport,
-                &installed,
-                Some(app_state.data_dir()),
-            );
+            let transport = match profile.clone().and_then(|profile| {
+                let (template, profiled) =
+                    mcpmux_gateway::pool::transport::resolution::apply_profile(
+                        profile.as_ref(),
+                        &server_definition.transport,
+                        &installed,
+                    );
+                mcpmux_gateway::pool::transport::resolution::resolve_transport_config(
+                    &template,
+                    &profiled,
+                    Some(app_state.data_dir()),
+                    workspace.as_deref(),
+                )
+                .map_err(|e| e.to_string())
+            }) {
+                Ok(transport) => transport,
+                Err(e) => {
+                    warn!("[Gateway] Not connecting {}: {}", installed.server_id, e);
+                    invalid_config.push(format!("{}: {}", installed.server_id, e));
+                    continue;
+                }
+            };
 
             servers_to_connect.push((server_info, transport, server_definition, installed));
         }
@@ -1899,12 +2868,15 @@ pub async fn connect_all_enabled_servers(
         oauth_required: 0,
         errors: vec![],
     };
+    result.failed += invalid_config.len();
+    result.errors.extend(invalid_config);
 
-    for (server_info, transport, _server_definition, _installed) in servers_to_connect {
+    for (server_info, transport, server_definition, installed) in servers_to_connect {
         let space_uuid = server_info.space_id;
         let server_id = server_info.server_id.clone();
 
-        let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport);
+        let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport)
+            .with_timeouts(installed.effective_timeouts(&server_definition));
         match pool_service.connect_server(&ctx).await {
             ConnectionResult::Connected { reused, features } => {
                 if reused {
@@ -1959,6 +2931,31 @@ pub async fn get_pool_stats(
     })
 }
 
+/// Get pool stats snapshots from each health check round, oldest first
+///
+/// `since` (RFC 3339) limits the result to snapshots taken at or after it,
+/// so the UI can poll for just the new points of a chart.
+#[tauri::command]
+pub async fn get_pool_stats_history(
+    since: Option<String>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<Vec<mcpmux_gateway::PoolStatsSnapshot>, String> {
+    let since = since
+        .map(|since| {
+            chrono::DateTime::parse_from_rfc3339(&since)
+                .map(|since| since.with_timezone(&chrono::Utc))
+                .map_err(|e| format!("Invalid timestamp '{}': {}", since, e))
+        })
+        .transpose()?;
+
+    let state = gateway_state.read().await;
+    Ok(state
+        .pool_service
+        .as_ref()
+        .map(|pool| pool.stats_history(since))
+        .unwrap_or_default())
+}
+
 /// Refresh OAuth tokens on startup for all installed HTTP servers.
 ///
 /// NOTE: This is now a no-op. RMCP's AuthClient handles token refresh automatically
@@ -1997,7 +2994,7 @@ pub struct PoolStatsResponse {
 
 #[cfg(test)]
 mod public_base_url_tests {
-    use super::{advertised_base_url, normalize_public_base_url};
+    use super::{advertised_base_url, normalize_base_url_host, normalize_public_base_url};
 
     #[test]
     fn normalize_accepts_https_origin_and_trims_trailing_slash() {
@@ -2030,20 +3027,46 @@ mod public_base_url_tests {
 
     #[test]
     fn advertised_base_url_falls_back_to_localhost() {
-        assert_eq!(advertised_base_url(None, 45818), "http://localhost:45818");
         assert_eq!(
-            advertised_base_url(Some("   "), 45818),
+            advertised_base_url(None, None, 45818),
             "http://localhost:45818"
         );
         assert_eq!(
-            advertised_base_url(Some("https://mcp.example.com/"), 45818),
+            advertised_base_url(Some("   "), None, 45818),
+            "http://localhost:45818"
+        );
+        assert_eq!(
+            advertised_base_url(Some("https://mcp.example.com/"), Some("::1"), 45818),
             "https://mcp.example.com"
         );
+        assert_eq!(
+            advertised_base_url(None, Some("::1"), 45818),
+            "http://[::1]:45818"
+        );
+    }
+
+    #[test]
+    fn normalize_base_url_host_accepts_ips_and_hostnames() {
+        assert_eq!(normalize_base_url_host(" ").unwrap(), None);
+        assert_eq!(
+            normalize_base_url_host("[::1]").unwrap().as_deref(),
+            Some("::1")
+        );
+        assert_eq!(
+            normalize_base_url_host("127.0.0.1").unwrap().as_deref(),
+            Some("127.0.0.1")
+        );
+        assert_eq!(
+            normalize_base_url_host("My-Host.local").unwrap().as_deref(),
+            Some("my-host.local")
+        );
+        assert!(normalize_base_url_host("http://localhost").is_err());
+        assert!(normalize_base_url_host("localhost:80").is_err());
     }
 
     #[test]
     fn bind_host_for_maps_network_access_to_address() {
-        assert_eq!(super::bind_host_for(false), "127.0.0.1");
+        assert_eq!(super::bind_host_for(false), "localhost");
         assert_eq!(super::bind_host_for(true), "0.0.0.0");
     }
 }
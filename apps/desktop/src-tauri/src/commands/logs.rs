@@ -1,10 +1,19 @@
 //! Tauri commands for server log management
 
+use crate::commands::gateway::GatewayAppState;
 use crate::state::AppState;
-use mcpmux_core::{AppSettingsService, LogLevel, ServerLog};
-use serde::Serialize;
-use tauri::State;
-use tracing::{info, warn};
+use chrono::{DateTime, Utc};
+use mcpmux_core::{
+    AppSettingsService, LogFilter, LogLevel, LogSearchQuery, LogSource, ServerLog,
+    DEFAULT_LOG_SEARCH_LIMIT,
+};
+use mcpmux_gateway::{FaultInjector, FaultPlan, PoolService, TrafficFrame};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 /// Helper to get the system default space ID.
 async fn get_default_space_id(state: &AppState) -> Result<String, String> {
@@ -72,6 +81,150 @@ pub async fn get_server_logs(
     Ok(logs.into_iter().map(ServerLogEntry::from).collect())
 }
 
+/// Parse log source names from the frontend
+fn parse_sources(sources: Option<Vec<String>>) -> Result<Vec<LogSource>, String> {
+    sources
+        .unwrap_or_default()
+        .iter()
+        .map(|s| LogSource::parse(s).ok_or_else(|| format!("Unknown log source: {}", s)))
+        .collect()
+}
+
+/// Parse an RFC 3339 timestamp from the frontend
+fn parse_time(value: Option<String>) -> Result<Option<DateTime<Utc>>, String> {
+    value
+        .map(|v| {
+            DateTime::parse_from_rfc3339(&v)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| format!("Invalid timestamp {}: {}", v, e))
+        })
+        .transpose()
+}
+
+/// One page of log search results
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerLogSearchPage {
+    /// Matches in chronological order
+    pub entries: Vec<ServerLogEntry>,
+    /// Offset of the next (older) page, absent on the last page
+    pub next_offset: Option<usize>,
+}
+
+/// Input for searching a server's logs
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchLogsInput {
+    /// RFC 3339 lower bound
+    pub since: Option<String>,
+    /// RFC 3339 upper bound
+    pub until: Option<String>,
+    pub level_filter: Option<String>,
+    pub sources: Option<Vec<String>>,
+    pub text: Option<String>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// Search a server's logs, including rotated and compressed files.
+///
+/// Results are paged newest first: the first page holds the most recent
+/// matches, and `next_offset` fetches older ones.
+#[tauri::command]
+pub async fn search_server_logs(
+    server_id: String,
+    input: SearchLogsInput,
+    state: State<'_, AppState>,
+) -> Result<ServerLogSearchPage, String> {
+    let space_id = get_default_space_id(&state).await?;
+    let query = LogSearchQuery {
+        filter: LogFilter {
+            min_level: input.level_filter.and_then(|s| LogLevel::parse(&s)),
+            sources: parse_sources(input.sources)?,
+        },
+        since: parse_time(input.since)?,
+        until: parse_time(input.until)?,
+        text: input.text.filter(|t| !t.trim().is_empty()),
+        offset: input.offset.unwrap_or(0),
+        limit: input.limit.unwrap_or(DEFAULT_LOG_SEARCH_LIMIT),
+    };
+
+    let page = state
+        .server_log_manager
+        .search_logs(&space_id, &server_id, &query)
+        .await
+        .map_err(|e| {
+            warn!("[Logs] Failed to search logs for {}: {}", server_id, e);
+            format!("Failed to search logs: {}", e)
+        })?;
+
+    Ok(ServerLogSearchPage {
+        entries: page.entries.into_iter().map(ServerLogEntry::from).collect(),
+        next_offset: page.next_offset,
+    })
+}
+
+/// A live log line, emitted as `server-log-tail`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerLogTailEvent {
+    pub tail_id: String,
+    pub entry: ServerLogEntry,
+}
+
+/// Start streaming new log lines for a server.
+///
+/// Each entry that passes the filters is emitted as a `server-log-tail`
+/// event tagged with the returned tail ID. Stop it with
+/// [`stop_server_log_tail`].
+#[tauri::command]
+pub async fn start_server_log_tail(
+    server_id: String,
+    level_filter: Option<String>,
+    sources: Option<Vec<String>>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let space_id = get_default_space_id(&state).await?;
+    let filter = LogFilter {
+        min_level: level_filter.and_then(|s| LogLevel::parse(&s)),
+        sources: parse_sources(sources)?,
+    };
+
+    let tail_id = Uuid::new_v4().to_string();
+    let mut tail = state
+        .server_log_manager
+        .subscribe_tail(&space_id, &server_id, filter);
+    let event_tail_id = tail_id.clone();
+    let handle = tokio::spawn(async move {
+        while let Some(log) = tail.next().await {
+            let event = ServerLogTailEvent {
+                tail_id: event_tail_id.clone(),
+                entry: ServerLogEntry::from(log),
+            };
+            if let Err(e) = app_handle.emit("server-log-tail", &event) {
+                warn!("[Logs] Failed to emit log tail event: {}", e);
+            }
+        }
+    });
+    state.log_tails.lock().await.insert(tail_id.clone(), handle);
+
+    debug!("[Logs] Started log tail {} for {}", tail_id, server_id);
+    Ok(tail_id)
+}
+
+/// Stop a log tail started with [`start_server_log_tail`]
+#[tauri::command]
+pub async fn stop_server_log_tail(
+    tail_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(handle) = state.log_tails.lock().await.remove(&tail_id) {
+        handle.abort();
+        debug!("[Logs] Stopped log tail {}", tail_id);
+    }
+    Ok(())
+}
+
 /// Clear logs for a server
 #[tauri::command]
 pub async fn clear_server_logs(
@@ -108,6 +261,154 @@ pub async fn get_server_log_file(
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Recorded JSON-RPC traffic for a server
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerTrafficResponse {
+    pub recording: bool,
+    /// Oldest first
+    pub frames: Vec<TrafficFrame>,
+}
+
+/// The running gateway's pool, for traffic recording and fault injection
+async fn running_pool(
+    gateway_state: &Arc<RwLock<GatewayAppState>>,
+) -> Result<Arc<PoolService>, String> {
+    gateway_state
+        .read()
+        .await
+        .pool_service
+        .clone()
+        .ok_or_else(|| "Gateway is not running".to_string())
+}
+
+/// Turn JSON-RPC traffic recording on or off for a server
+#[tauri::command]
+pub async fn set_server_traffic_recording(
+    server_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    let space_id = get_default_space_id(&state).await?;
+    let space_id = Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let pool = running_pool(&gateway_state).await?;
+
+    pool.traffic().set_enabled(space_id, &server_id, enabled);
+    info!(
+        "[Logs] Traffic recording {} for server {}",
+        if enabled { "enabled" } else { "disabled" },
+        server_id
+    );
+    Ok(())
+}
+
+/// Get the JSON-RPC frames recorded for a server
+#[tauri::command]
+pub async fn get_server_traffic(
+    server_id: String,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<ServerTrafficResponse, String> {
+    let space_id = get_default_space_id(&state).await?;
+    let space_id = Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let pool = running_pool(&gateway_state).await?;
+
+    Ok(ServerTrafficResponse {
+        recording: pool.traffic().is_enabled(space_id, &server_id),
+        frames: pool.traffic().frames(space_id, &server_id),
+    })
+}
+
+/// Drop the JSON-RPC frames recorded for a server
+#[tauri::command]
+pub async fn clear_server_traffic(
+    server_id: String,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    let space_id = get_default_space_id(&state).await?;
+    let space_id = Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let pool = running_pool(&gateway_state).await?;
+
+    pool.traffic().clear(space_id, &server_id);
+    Ok(())
+}
+
+/// Faults injected into a server's tool calls
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerFaultsResponse {
+    /// Whether this build accepts faults (debug builds only)
+    pub available: bool,
+    pub plan: FaultPlan,
+}
+
+/// Get the faults injected into a server's tool calls
+#[tauri::command]
+pub async fn get_server_faults(
+    server_id: String,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<ServerFaultsResponse, String> {
+    let space_id = get_default_space_id(&state).await?;
+    let space_id = Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let pool = running_pool(&gateway_state).await?;
+
+    Ok(ServerFaultsResponse {
+        available: FaultInjector::is_available(),
+        plan: pool.faults().get(space_id, &server_id),
+    })
+}
+
+/// Inject faults into a server's tool calls, for testing how clients and
+/// the gateway handle them. An empty plan removes them.
+#[tauri::command]
+pub async fn set_server_faults(
+    server_id: String,
+    plan: FaultPlan,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<(), String> {
+    let space_id = get_default_space_id(&state).await?;
+    let space_id = Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let pool = running_pool(&gateway_state).await?;
+
+    pool.faults()
+        .set(space_id, &server_id, plan.clone())
+        .map_err(|e| e.to_string())?;
+    warn!(
+        "[Logs] Fault injection for server {}: {:?}",
+        server_id, plan
+    );
+    Ok(())
+}
+
+/// Get the saved log filter (`None` = built-in default)
+#[tauri::command]
+pub async fn get_log_level(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(settings.get_log_level().await)
+}
+
+/// Set the log filter (e.g. "debug" or "info,mcpmux_gateway=trace") and
+/// apply it immediately. `None` or blank restores the default.
+#[tauri::command]
+pub async fn set_log_level(
+    level: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let level = level.as_deref().map(str::trim).filter(|l| !l.is_empty());
+    // Apply first so an invalid filter is rejected before it is saved
+    crate::set_log_filter(level)?;
+
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_log_level(level)
+        .await
+        .map_err(|e| format!("Failed to save log level: {}", e))?;
+    info!("[Logs] Log level set to {}", level.unwrap_or("default"));
+    Ok(())
+}
+
 /// Get log retention period in days (0 = keep forever)
 #[tauri::command]
 pub async fn get_log_retention_days(state: State<'_, AppState>) -> Result<u32, String> {
@@ -3,42 +3,59 @@
 //! This module contains all commands that can be invoked from the frontend.
 //! Commands are organized by feature area.
 
+pub mod backup;
 pub mod builtin_servers;
 pub mod client;
 pub mod client_install;
 pub mod config_export;
 pub mod credential;
+pub mod diagnostics;
+pub mod environment_profile;
 pub mod feature_members;
 pub mod feature_set;
 pub mod gateway;
 pub mod logs;
 pub mod meta_tool_approval;
 pub mod oauth;
+pub mod onboarding;
+pub mod os_service;
+pub mod runtimes;
 pub mod server;
 pub mod server_discovery;
 pub mod server_feature;
+pub mod server_group;
 pub mod server_manager;
 pub mod settings;
 pub mod space;
+pub mod sync;
 pub mod workspace_binding;
 pub mod workspace_install;
 
 // Re-export commands for convenience
+pub use backup::*;
 pub use builtin_servers::*;
 pub use client::*;
 pub use client_install::*;
 pub use config_export::*;
+pub use credential::*;
+pub use diagnostics::*;
+pub use environment_profile::*;
 pub use feature_members::*;
 pub use feature_set::*;
 pub use gateway::*;
 pub use logs::*;
 pub use meta_tool_approval::*;
 pub use oauth::*;
+pub use onboarding::*;
+pub use os_service::*;
+pub use runtimes::*;
 pub use server::*;
 pub use server_discovery::*;
 pub use server_feature::*;
+pub use server_group::*;
 pub use server_manager::*;
 pub use settings::*;
 pub use space::*;
+pub use sync::*;
 pub use workspace_binding::*;
 pub use workspace_install::*;
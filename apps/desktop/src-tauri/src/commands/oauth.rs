@@ -745,6 +745,7 @@ pub async fn get_oauth_clients(
             created_at: client.created_at,
             reports_roots: client.reports_roots,
             roots_capability_known: client.roots_capability_known,
+            suspended: client.suspended,
         })
         .collect();
 
@@ -829,6 +830,10 @@ pub struct OAuthClientInfo {
     /// "Reports workspace" (`reports_roots = true`) or "Rootless"
     /// (`reports_roots = false`).
     pub roots_capability_known: bool,
+
+    /// `true` while the client is suspended: every MCP request it makes is
+    /// refused with 403 until it is reinstated.
+    pub suspended: bool,
 }
 
 /// Request to update client settings.
@@ -894,9 +899,42 @@ pub async fn update_oauth_client(
         created_at: updated_client.created_at,
         reports_roots: updated_client.reports_roots,
         roots_capability_known: updated_client.roots_capability_known,
+        suspended: updated_client.suspended,
     })
 }
 
+/// Suspend or reinstate a client. A suspended client keeps its registration,
+/// credentials and grants, but the gateway refuses all of its MCP requests.
+#[tauri::command]
+pub async fn set_oauth_client_suspended(
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    client_id: String,
+    suspended: bool,
+) -> Result<(), String> {
+    let app_state = gateway_state.read().await;
+
+    let Some(ref gw_state) = app_state.gateway_state else {
+        return Err("Gateway not running".to_string());
+    };
+
+    let state = gw_state.read().await;
+    let Some(repo) = state.inbound_client_repository() else {
+        return Err("Database not available".to_string());
+    };
+
+    let found = repo
+        .set_client_suspended(&client_id, suspended)
+        .await
+        .map_err(|e| format!("Failed to update client: {}", e))?;
+    if !found {
+        return Err(format!("Client not found: {}", client_id));
+    }
+
+    state.emit_domain_event(mcpmux_core::DomainEvent::ClientUpdated { client_id });
+
+    Ok(())
+}
+
 /// Delete an OAuth client (direct service access)
 #[tauri::command]
 pub async fn delete_oauth_client(
@@ -979,20 +1017,6 @@ pub struct ApiKeyInfo {
     pub created_at: String,
 }
 
-/// Generate a strong API key: `mcpk_` + 256 bits of v4-UUID randomness.
-/// Returns `(key_id, plaintext, key_prefix)`. Only the hash is ever stored.
-fn generate_api_key() -> (String, String, String) {
-    let key_id = uuid::Uuid::new_v4().to_string();
-    let secret = format!(
-        "{}{}",
-        uuid::Uuid::new_v4().simple(),
-        uuid::Uuid::new_v4().simple()
-    );
-    let plaintext = format!("mcpk_{secret}");
-    let key_prefix: String = plaintext.chars().take(13).collect(); // "mcpk_" + 8 chars
-    (key_id, plaintext, key_prefix)
-}
-
 /// Register a new pre-approved client authenticated by an API key, optionally
 /// locked to a Space. The returned `api_key` is shown once and never stored.
 #[tauri::command]
@@ -1011,75 +1035,30 @@ pub async fn register_api_key_client(
         return Err("Database not available".to_string());
     };
 
-    let trimmed = name.trim();
-    if trimmed.is_empty() {
-        return Err("Client name is required".to_string());
-    }
-
-    let now = chrono::Utc::now().to_rfc3339();
-    let client_id = format!("mcp_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
-    let client = mcpmux_storage::InboundClient {
-        client_id: client_id.clone(),
-        registration_type: mcpmux_storage::RegistrationType::Preregistered,
-        client_name: trimmed.to_string(),
-        client_alias: None,
-        redirect_uris: vec![],
-        grant_types: vec![],
-        response_types: vec![],
-        token_endpoint_auth_method: "none".to_string(),
-        scope: None,
-        approved: true,
-        logo_uri: None,
-        client_uri: None,
-        software_id: None,
-        software_version: None,
-        metadata_url: None,
-        metadata_cached_at: None,
-        metadata_cache_ttl: None,
-        last_seen: None,
-        created_at: now.clone(),
-        updated_at: now,
-        reports_roots: false,
-        roots_capability_known: false,
-    };
-    repo.save_client(&client)
+    let client = mcpmux_gateway::register_api_key_client(repo, &name, locked_space_id.as_deref())
         .await
-        .map_err(|e| format!("Failed to create client: {}", e))?;
-
-    if let Some(ref space) = locked_space_id {
-        repo.set_locked_space(&client_id, Some(space))
-            .await
-            .map_err(|e| format!("Failed to lock client to space: {}", e))?;
-    }
-
-    let (key_id, plaintext, key_prefix) = generate_api_key();
-    repo.create_api_key(&key_id, &client_id, &plaintext, &key_prefix, None, None)
-        .await
-        .map_err(|e| format!("Failed to create API key: {}", e))?;
-
-    info!(
-        "[OAuth] Registered API-key client {} ({})",
-        trimmed, client_id
-    );
+        .map_err(|e| e.to_string())?;
 
     // Best-effort: auto-create a clientId-keyed mapping → the (locked or
     // default) Space's Starter, so the client routes sensibly out of the box
     // and the mapping is visible + editable in the Mapping tab. A failure here
     // must not undo the registration — without an explicit mapping the resolver
     // still falls back to the default Starter.
-    if let Err(e) = auto_map_api_key_client(&app, &client_id, locked_space_id.as_deref()).await {
+    if let Err(e) =
+        auto_map_api_key_client(&app, &client.client_id, locked_space_id.as_deref()).await
+    {
         warn!(
             "[OAuth] auto-map for {} failed (non-fatal): {}",
-            client_id, e
+            client.client_id, e
         );
     }
 
     Ok(RegisteredApiKeyClient {
-        client_id,
-        client_name: trimmed.to_string(),
-        locked_space_id,
-        api_key: plaintext,
-        key_prefix,
+        client_id: client.client_id,
+        client_name: client.client_name,
+        locked_space_id: client.locked_space_id,
+        api_key: client.api_key,
+        key_prefix: client.key_prefix,
     })
 }
 
@@ -1142,7 +1121,7 @@ pub async fn create_client_api_key(
         return Err("Client not found".to_string());
     };
 
-    let (key_id, plaintext, key_prefix) = generate_api_key();
+    let (key_id, plaintext, key_prefix) = mcpmux_gateway::generate_api_key();
     repo.create_api_key(
         &key_id,
         &client_id,
@@ -1223,6 +1202,105 @@ pub async fn revoke_client_api_key(
     Ok(())
 }
 
+// =============================================================================
+// Device pairing
+// =============================================================================
+//
+// Onboards a phone or other remote client without copying an API key: the
+// user scans a QR code (or types a short code) and the device exchanges the
+// code at the gateway's `/oauth/pair` endpoint for its own API key.
+
+/// A one-time pairing code and the QR code that carries it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevicePairing {
+    pub code: String,
+    /// Gateway URL plus code; what the QR code encodes
+    pub pair_url: String,
+    /// Unix timestamp after which the code stops working
+    pub expires_at: i64,
+    /// SVG markup of the QR code
+    pub qr_svg: String,
+}
+
+/// The gateway URL a device elsewhere can use: the public URL, or this
+/// machine's network address when the gateway listens on the network.
+fn remote_base_url(config: &mcpmux_gateway::GatewayConfig) -> Result<String, String> {
+    if let Some(public) = config
+        .public_base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+    {
+        return Ok(public.trim_end_matches('/').to_string());
+    }
+    if !config.is_network_bind() {
+        return Err(
+            "Other devices can't reach the gateway. Turn on network access or set a public URL in Settings first."
+                .to_string(),
+        );
+    }
+    let ip = Some(config.addr().ip())
+        .filter(|ip| !ip.is_unspecified())
+        .or_else(lan_ip)
+        .ok_or("Couldn't determine this machine's network address")?;
+    Ok(format!(
+        "http://{}",
+        std::net::SocketAddr::new(ip, config.port)
+    ))
+}
+
+/// This machine's address on its default route. Connecting a UDP socket
+/// sends nothing; it only makes the OS pick the outgoing interface.
+fn lan_ip() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    socket
+        .local_addr()
+        .ok()
+        .map(|addr| addr.ip())
+        .filter(|ip| !ip.is_unspecified() && !ip.is_loopback())
+}
+
+/// Create a one-time pairing code for a remote device. The device that
+/// redeems it becomes a pre-approved API-key client named `client_name`
+/// (or its own name), optionally locked to a Space.
+#[tauri::command]
+pub async fn create_device_pairing(
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    client_name: Option<String>,
+    locked_space_id: Option<String>,
+) -> Result<DevicePairing, String> {
+    let app_state = gateway_state.read().await;
+    let (Some(handle), Some(gw_state)) = (
+        app_state.handle.as_ref().filter(|h| h.is_active()),
+        app_state.gateway_state.as_ref(),
+    ) else {
+        return Err("Gateway not running".to_string());
+    };
+    let base = remote_base_url(&handle.config().await)?;
+
+    let code = mcpmux_gateway::generate_pairing_code();
+    let pairing = mcpmux_gateway::PendingPairing::new(client_name, locked_space_id);
+    let expires_at = pairing.expires_at;
+    gw_state.write().await.store_pairing(&code, pairing);
+
+    let pair_url = mcpmux_gateway::pairing_url(&base, &code);
+    let qr_svg = qrcode::QrCode::new(pair_url.as_bytes())
+        .map_err(|e| format!("Failed to build QR code: {}", e))?
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(220, 220)
+        .build();
+
+    info!("[OAuth] Created a device pairing code for {}", base);
+    Ok(DevicePairing {
+        code,
+        pair_url,
+        expires_at,
+        qr_svg,
+    })
+}
+
 /// Open a URL without flashing a terminal window (Windows-specific)
 #[cfg(target_os = "windows")]
 fn open_url_no_flash(url: &str) -> Result<(), String> {
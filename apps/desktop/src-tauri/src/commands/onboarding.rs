@@ -0,0 +1,160 @@
+//! First-run onboarding commands.
+//!
+//! One command per setup step, so the UI can walk the user through them and
+//! re-run any that failed. Each step reports `onboarding-progress` events:
+//! `running` when it starts, then `completed` or `failed`.
+
+use mcpmux_core::{
+    DefaultGrants, DetectedClient, DomainEvent, GatewayCheck, OnboardingProgress, OnboardingStep,
+    ServerSuggestion,
+};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::commands::gateway::GatewayAppState;
+use crate::state::AppState;
+
+/// Detect the AI clients installed on this machine.
+#[tauri::command]
+pub async fn onboarding_detect_clients(
+    app_handle: AppHandle,
+) -> Result<Vec<DetectedClient>, String> {
+    let step = OnboardingStep::DetectClients;
+    emit_progress(&app_handle, OnboardingProgress::running(step));
+    let result = tokio::task::spawn_blocking(mcpmux_core::detect_clients)
+        .await
+        .map_err(|e| e.to_string());
+    report(&app_handle, step, result, |clients| {
+        format!("Found {} client(s)", clients.len())
+    })
+}
+
+/// Suggest starter servers for the default Space.
+#[tauri::command]
+pub async fn onboarding_suggest_servers(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<ServerSuggestion>, String> {
+    let step = OnboardingStep::SuggestServers;
+    emit_progress(&app_handle, OnboardingProgress::running(step));
+    let result = async {
+        let space_id = default_space_id(&state).await?;
+        state
+            .onboarding_service
+            .suggest_servers(&space_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+    .await;
+    report(&app_handle, step, result, |suggestions| {
+        format!("{} server(s) suggested", suggestions.len())
+    })
+}
+
+/// Grant the default Space's Starter FeatureSet the features of its
+/// installed servers, unless the user already configured it.
+#[tauri::command]
+pub async fn onboarding_create_default_grants(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<DefaultGrants, String> {
+    let step = OnboardingStep::DefaultGrants;
+    emit_progress(&app_handle, OnboardingProgress::running(step));
+    let result = async {
+        let space_id = default_space_id(&state).await?;
+        let grants = state
+            .onboarding_service
+            .create_default_grants(&space_id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if grants.added > 0 {
+            let gw_state = gateway_state.read().await;
+            if let (Some(gw), Ok(space_uuid)) = (
+                gw_state.gateway_state.as_ref(),
+                uuid::Uuid::parse_str(&space_id),
+            ) {
+                let gw = gw.read().await;
+                gw.emit_domain_event(DomainEvent::FeatureSetMembersChanged {
+                    space_id: space_uuid,
+                    feature_set_id: grants.feature_set_id.clone(),
+                    added_count: grants.added,
+                    removed_count: 0,
+                });
+            }
+        }
+        Ok(grants)
+    }
+    .await;
+    report(&app_handle, step, result, |grants| {
+        if grants.already_configured {
+            "Default grants were already set up".to_string()
+        } else {
+            format!("Granted {} feature(s) by default", grants.added)
+        }
+    })
+}
+
+/// Check that the running gateway answers on its URL.
+#[tauri::command]
+pub async fn onboarding_verify_gateway(
+    app_handle: AppHandle,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<GatewayCheck, String> {
+    let step = OnboardingStep::VerifyGateway;
+    emit_progress(&app_handle, OnboardingProgress::running(step));
+    let url = gateway_state.read().await.url.clone();
+    let result = match url {
+        Some(url) => {
+            let check = mcpmux_core::verify_gateway(&url).await;
+            match &check.error {
+                Some(error) if !check.reachable => Err(format!("{}: {}", url, error)),
+                _ => Ok(check),
+            }
+        }
+        None => Err("Gateway is not running".to_string()),
+    };
+    report(&app_handle, step, result, |check| {
+        format!("Gateway answered in {} ms", check.latency_ms)
+    })
+}
+
+async fn default_space_id(state: &AppState) -> Result<String, String> {
+    state
+        .space_service
+        .get_default()
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|space| space.id.to_string())
+        .ok_or_else(|| "No default space".to_string())
+}
+
+/// Emit the outcome of a step and pass the result through
+fn report<T>(
+    app_handle: &AppHandle,
+    step: OnboardingStep,
+    result: Result<T, String>,
+    summary: impl FnOnce(&T) -> String,
+) -> Result<T, String> {
+    let progress = match &result {
+        Ok(value) => OnboardingProgress::completed(step, summary(value)),
+        Err(e) => OnboardingProgress::failed(step, e.clone()),
+    };
+    info!(
+        step = ?step,
+        status = ?progress.status,
+        "[Onboarding] {}",
+        progress.message.as_deref().unwrap_or_default()
+    );
+    emit_progress(app_handle, progress);
+    result
+}
+
+fn emit_progress(app_handle: &AppHandle, progress: OnboardingProgress) {
+    if let Err(e) = app_handle.emit("onboarding-progress", &progress) {
+        warn!("[Onboarding] Failed to emit progress: {}", e);
+    }
+}
@@ -0,0 +1,95 @@
+//! Gateway service commands.
+//!
+//! Registers `mcpmux-cli serve` with the OS service manager so the gateway
+//! runs at login without the desktop window.
+
+use mcpmux_core::{ServiceManager, ServiceSpec};
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::State;
+use tracing::info;
+
+use crate::state::AppState;
+
+/// Service registration state
+#[derive(Debug, Serialize)]
+pub struct ServiceStatus {
+    /// `None` on platforms without a supported service manager
+    pub manager: Option<ServiceManager>,
+    pub installed: bool,
+}
+
+fn current_manager() -> Result<ServiceManager, String> {
+    ServiceManager::current().ok_or_else(|| "No supported service manager on this platform".into())
+}
+
+/// `mcpmux-cli` is shipped next to the desktop executable
+fn cli_executable() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let name = if cfg!(windows) {
+        "mcpmux-cli.exe"
+    } else {
+        "mcpmux-cli"
+    };
+    let cli = exe
+        .parent()
+        .ok_or("Could not determine install directory")?
+        .join(name);
+    if !cli.exists() {
+        return Err(format!("{} not found next to the app", name));
+    }
+    Ok(cli)
+}
+
+/// Whether the gateway service is registered.
+#[tauri::command]
+pub async fn get_service_status() -> Result<ServiceStatus, String> {
+    let manager = ServiceManager::current();
+    let installed = match manager {
+        Some(manager) => {
+            tokio::task::spawn_blocking(move || mcpmux_core::is_service_installed(manager))
+                .await
+                .map_err(|e| e.to_string())?
+        }
+        None => false,
+    };
+    Ok(ServiceStatus { manager, installed })
+}
+
+/// Install and start the gateway service on the configured port.
+///
+/// Returns where the service definition lives.
+#[tauri::command]
+pub async fn install_service(state: State<'_, AppState>) -> Result<String, String> {
+    let manager = current_manager()?;
+    let port = state
+        .gateway_port_service
+        .load_persisted_port()
+        .await
+        .unwrap_or(mcpmux_core::DEFAULT_GATEWAY_PORT);
+    let spec = ServiceSpec {
+        executable: cli_executable()?,
+        data_dir: state.data_dir().to_path_buf(),
+        serve_args: vec!["--port".to_string(), port.to_string()],
+    };
+
+    let location =
+        tokio::task::spawn_blocking(move || mcpmux_core::install_service(manager, &spec))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("{:#}", e))?;
+    info!("[Service] Installed gateway service: {}", location);
+    Ok(location)
+}
+
+/// Stop and remove the gateway service.
+#[tauri::command]
+pub async fn uninstall_service() -> Result<(), String> {
+    let manager = current_manager()?;
+    tokio::task::spawn_blocking(move || mcpmux_core::uninstall_service(manager))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("{:#}", e))?;
+    info!("[Service] Uninstalled gateway service");
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Managed runtime commands
+//!
+//! IPC commands for installing the pinned Node.js/uv builds McpMux can run
+//! stdio servers with, and for turning their use on or off.
+
+use mcpmux_core::{AppSettingsService, ManagedRuntimeKind, ManagedRuntimeStatus, ManagedRuntimes};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::info;
+
+use crate::state::AppState;
+
+/// Managed runtimes and whether stdio servers use them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManagedRuntimesInfo {
+    pub enabled: bool,
+    pub runtimes: Vec<ManagedRuntimeStatus>,
+}
+
+fn managed_runtimes(state: &AppState) -> ManagedRuntimes {
+    ManagedRuntimes::new(state.data_dir(), Some(state.settings_repository.clone()))
+}
+
+fn parse_kind(kind: &str) -> Result<ManagedRuntimeKind, String> {
+    ManagedRuntimeKind::parse(kind).ok_or_else(|| format!("Unknown runtime: {}", kind))
+}
+
+/// Get installed managed runtimes and whether they're enabled
+#[tauri::command]
+pub async fn get_managed_runtimes(
+    state: State<'_, AppState>,
+) -> Result<ManagedRuntimesInfo, String> {
+    let runtimes = managed_runtimes(&state);
+    Ok(ManagedRuntimesInfo {
+        enabled: runtimes.is_enabled().await,
+        runtimes: runtimes.status(),
+    })
+}
+
+/// Use (or stop using) managed runtimes for stdio servers started from now on
+#[tauri::command]
+pub async fn set_managed_runtimes_enabled(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    AppSettingsService::new(state.settings_repository.clone())
+        .set_managed_runtimes_enabled(enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Download and install a managed runtime (`node` or `uv`)
+#[tauri::command]
+pub async fn install_managed_runtime(
+    kind: String,
+    state: State<'_, AppState>,
+) -> Result<ManagedRuntimesInfo, String> {
+    let kind = parse_kind(&kind)?;
+    info!("[Runtimes] Installing managed {}", kind.as_str());
+    managed_runtimes(&state)
+        .install(kind)
+        .await
+        .map_err(|e| e.to_string())?;
+    get_managed_runtimes(state).await
+}
+
+/// Remove a managed runtime
+#[tauri::command]
+pub async fn uninstall_managed_runtime(
+    kind: String,
+    state: State<'_, AppState>,
+) -> Result<ManagedRuntimesInfo, String> {
+    let kind = parse_kind(&kind)?;
+    info!("[Runtimes] Removing managed {}", kind.as_str());
+    managed_runtimes(&state)
+        .uninstall(kind)
+        .await
+        .map_err(|e| e.to_string())?;
+    get_managed_runtimes(state).await
+}
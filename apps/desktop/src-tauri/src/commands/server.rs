@@ -1,19 +1,35 @@
 //! Server management commands
 
+use crate::commands::gateway::GatewayAppState;
+use crate::commands::server_manager::{disable_server_v2, enable_server_v2, ServerManagerState};
 use crate::AppState;
 use mcpmux_core::application::ServerAppService;
-use mcpmux_core::domain::InstalledServer;
+use mcpmux_core::domain::{
+    CallContext, InstallationSource, InstalledServer, ServerSource, ServerTimeouts, ToolHooks,
+    WasmPermissions,
+};
+use mcpmux_core::{
+    read_client_config, AppSettingsService, BulkInstallReport, ImportClient, InstallManifest,
+    SkippedImport,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::RwLock;
 
+/// Install a registry server in a space.
+///
+/// Servers from an untrusted registry need `confirm_untrusted`; servers from
+/// a trusted registry are installed enabled and connected right away.
 #[tauri::command]
 pub async fn install_server(
     state: State<'_, AppState>,
     app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    manager_state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
     id: String,
     space_id: String,
+    confirm_untrusted: Option<bool>,
 ) -> Result<InstalledServer, String> {
     let service_lock = app_service.read().await;
     let service = service_lock
@@ -34,11 +50,95 @@ pub async fn install_server(
         .await
         .ok_or("Server definition not found")?;
 
+    if definition.source.trust().requires_confirmation() && !confirm_untrusted.unwrap_or(false) {
+        let registry = match &definition.source {
+            ServerSource::Registry { name, .. } => name.as_str(),
+            _ => "an untrusted registry",
+        };
+        return Err(format!(
+            "{} comes from {}, which is not trusted. Confirm to install it anyway.",
+            definition.name, registry
+        ));
+    }
+
     // Pass the full definition for caching (offline support)
-    service
+    let installed = service
         .install(space_uuid, &id, &definition, HashMap::new())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    drop(service_lock);
+
+    if installed.enabled {
+        // A failed connection is shown on the server like any other; the
+        // install itself succeeded
+        if let Err(e) =
+            enable_server_v2(space_id, id.clone(), manager_state, gateway_state, state).await
+        {
+            tracing::warn!(
+                "[install_server] Trusted server {} failed to connect: {}",
+                id,
+                e
+            );
+        }
+    }
+
+    Ok(installed)
+}
+
+/// Install the servers of a manifest, all or none, and connect the ones
+/// enabled. Returns a result per server.
+#[tauri::command]
+pub async fn bulk_install_servers(
+    state: State<'_, AppState>,
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    manager_state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    space_id: String,
+    manifest: InstallManifest,
+    confirm_untrusted: Option<bool>,
+) -> Result<BulkInstallReport, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    state
+        .server_discovery
+        .refresh_if_needed()
+        .await
+        .map_err(|e| e.to_string())?;
+    let report = service
+        .bulk_install(
+            space_uuid,
+            &manifest,
+            &state.server_discovery,
+            confirm_untrusted.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    drop(service_lock);
+
+    for server_id in report.enabled() {
+        if let Err(e) = enable_server_v2(
+            space_id.clone(),
+            server_id.to_string(),
+            manager_state.clone(),
+            gateway_state.clone(),
+            state.clone(),
+        )
+        .await
+        {
+            tracing::warn!(
+                "[bulk_install_servers] Server {} failed to connect: {}",
+                server_id,
+                e
+            );
+        }
+    }
+
+    Ok(report)
 }
 
 #[tauri::command]
@@ -60,6 +160,231 @@ pub async fn uninstall_server(
         .map_err(|e| e.to_string())
 }
 
+/// List servers uninstalled from a space that can still be restored.
+#[tauri::command]
+pub async fn list_trashed_servers(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    space_id: String,
+) -> Result<Vec<InstalledServer>, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    service
+        .list_trash(&space_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore an uninstalled server with its settings and credentials,
+/// reconnecting it if it was enabled.
+#[tauri::command]
+pub async fn restore_server(
+    state: State<'_, AppState>,
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    manager_state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    id: String,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    let restored = service
+        .restore(space_uuid, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    drop(service_lock);
+
+    if restored.enabled {
+        if let Err(e) =
+            enable_server_v2(space_id, id.clone(), manager_state, gateway_state, state).await
+        {
+            tracing::warn!(
+                "[restore_server] Restored server {} failed to connect: {}",
+                id,
+                e
+            );
+        }
+    }
+
+    Ok(restored)
+}
+
+/// Permanently delete an uninstalled server from the trash.
+#[tauri::command]
+pub async fn purge_trashed_server(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    space_id: String,
+) -> Result<(), String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .purge(space_uuid, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get how many days uninstalled servers can be restored (0 = until purged)
+#[tauri::command]
+pub async fn get_server_trash_retention_days(state: State<'_, AppState>) -> Result<u32, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(settings.get_trash_retention_days().await)
+}
+
+/// Set how many days uninstalled servers can be restored (0 = until purged)
+#[tauri::command]
+pub async fn set_server_trash_retention_days(
+    days: u32,
+    state: State<'_, AppState>,
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+) -> Result<(), String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_trash_retention_days(days)
+        .await
+        .map_err(|e| format!("Failed to save trash retention setting: {}", e))?;
+
+    // Apply the new window right away
+    if let Some(service) = app_service.read().await.as_ref() {
+        if let Err(e) = service.purge_expired(days).await {
+            tracing::warn!("[Trash] Purge after retention change failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// List enabled servers of a space unused for the configured number of days,
+/// as candidates to disable.
+#[tauri::command]
+pub async fn get_unused_servers(
+    state: State<'_, AppState>,
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    space_id: String,
+) -> Result<Vec<InstalledServer>, String> {
+    let days = AppSettingsService::new(state.settings_repository.clone())
+        .get_unused_days()
+        .await;
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    service
+        .list_unused(&space_id, days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get how many days without a successful tool call flag a server as unused
+/// (0 = never)
+#[tauri::command]
+pub async fn get_server_unused_days(state: State<'_, AppState>) -> Result<u32, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(settings.get_unused_days().await)
+}
+
+/// Set how many days without a successful tool call flag a server as unused
+/// (0 = never)
+#[tauri::command]
+pub async fn set_server_unused_days(days: u32, state: State<'_, AppState>) -> Result<(), String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_unused_days(days)
+        .await
+        .map_err(|e| format!("Failed to save unused server setting: {}", e))
+}
+
+/// Get whether unused servers are disabled automatically
+#[tauri::command]
+pub async fn get_auto_disable_unused_servers(state: State<'_, AppState>) -> Result<bool, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(settings.get_auto_disable_unused().await)
+}
+
+/// Set whether unused servers are disabled automatically; turning it on
+/// disables the ones unused now.
+#[tauri::command]
+pub async fn set_auto_disable_unused_servers(
+    app: AppHandle,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_auto_disable_unused(enabled)
+        .await
+        .map_err(|e| format!("Failed to save auto-disable setting: {}", e))?;
+    if enabled {
+        disable_unused_servers(&app).await;
+    }
+    Ok(())
+}
+
+/// Disable every server unused for the configured number of days, when
+/// auto-disable is on. Returns how many were disabled.
+pub async fn disable_unused_servers(app: &AppHandle) -> usize {
+    let settings = AppSettingsService::new(app.state::<AppState>().settings_repository.clone());
+    if !settings.get_auto_disable_unused().await {
+        return 0;
+    }
+    let days = settings.get_unused_days().await;
+
+    let unused = {
+        let app_service = app.state::<Arc<RwLock<Option<ServerAppService>>>>();
+        let service_lock = app_service.read().await;
+        let Some(service) = service_lock.as_ref() else {
+            return 0;
+        };
+        match service.list_unused_all(days).await {
+            Ok(unused) => unused,
+            Err(e) => {
+                tracing::warn!("[UnusedServers] Listing unused servers failed: {}", e);
+                return 0;
+            }
+        }
+    };
+
+    let mut disabled = 0;
+    for server in unused {
+        match disable_server_v2(
+            server.space_id.clone(),
+            server.server_id.clone(),
+            app.state(),
+            app.state(),
+            app.state(),
+        )
+        .await
+        {
+            Ok(()) => {
+                tracing::info!(
+                    "[UnusedServers] Disabled {}: unused for {} days",
+                    server.server_id,
+                    days
+                );
+                disabled += 1;
+            }
+            Err(e) => tracing::warn!(
+                "[UnusedServers] Failed to disable {}: {}",
+                server.server_id,
+                e
+            ),
+        }
+    }
+    disabled
+}
+
 #[tauri::command]
 pub async fn list_installed_servers(
     app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
@@ -127,6 +452,160 @@ pub async fn set_server_oauth_connected(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn set_server_inherit_env(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    inherit_env: bool,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .set_inherit_env(space_uuid, &id, inherit_env)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_server_fs_allowed_roots(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    roots: Vec<String>,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .set_fs_allowed_roots(space_uuid, &id, roots)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_server_skip_argument_validation(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    skip: bool,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .set_skip_argument_validation(space_uuid, &id, skip)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set a server's connect and request timeouts, in seconds (`None` = default)
+#[tauri::command]
+pub async fn set_server_timeouts(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    connect_secs: Option<u32>,
+    request_secs: Option<u32>,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let timeouts = ServerTimeouts {
+        connect_secs,
+        request_secs,
+    };
+
+    service
+        .set_timeouts(space_uuid, &id, timeouts)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set the hooks the gateway runs around a server's tool calls
+#[tauri::command]
+pub async fn set_server_tool_hooks(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    hooks: ToolHooks,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .set_tool_hooks(space_uuid, &id, hooks)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set the context the gateway adds to a server's tool calls
+#[tauri::command]
+pub async fn set_server_call_context(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    context: CallContext,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+
+    service
+        .set_call_context(space_uuid, &id, context)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Grant or revoke a WASI server's filesystem and network access
+#[tauri::command]
+pub async fn set_server_wasm_grants(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    id: String,
+    filesystem: bool,
+    network: bool,
+    space_id: String,
+) -> Result<InstalledServer, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let grants = WasmPermissions {
+        filesystem,
+        network,
+    };
+
+    service
+        .set_wasm_grants(space_uuid, &id, grants)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn save_server_inputs(
     app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
@@ -156,3 +635,86 @@ pub async fn save_server_inputs(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Result of importing servers from another client's config
+#[derive(Debug, serde::Serialize)]
+pub struct ClientImportReport {
+    /// Config file that was read
+    pub path: String,
+    /// Server IDs installed into the space (disabled until the user enables them)
+    pub imported: Vec<String>,
+    pub skipped: Vec<SkippedImport>,
+}
+
+/// Import servers from Claude Desktop, Cursor, VS Code or Windsurf.
+///
+/// Reads `path`, or the client's default config when omitted. `names`
+/// restricts the import to those entries. Servers already installed in the
+/// space are skipped.
+#[tauri::command]
+pub async fn import_from_client_config(
+    app_service: State<'_, Arc<RwLock<Option<ServerAppService>>>>,
+    client: ImportClient,
+    space_id: String,
+    path: Option<String>,
+    names: Option<Vec<String>>,
+) -> Result<ClientImportReport, String> {
+    let service_lock = app_service.read().await;
+    let service = service_lock
+        .as_ref()
+        .ok_or("ServerAppService not initialized")?;
+
+    let space_uuid = uuid::Uuid::parse_str(&space_id).map_err(|e| e.to_string())?;
+    let path = match path {
+        Some(p) => std::path::PathBuf::from(p),
+        None => client
+            .detect_config()
+            .ok_or_else(|| format!("No {} config found", client.label()))?,
+    };
+
+    let parsed = read_client_config(client, Some(&path), &space_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut report = ClientImportReport {
+        path: path.display().to_string(),
+        imported: Vec::new(),
+        skipped: parsed.skipped,
+    };
+    for server in parsed.servers {
+        if names.as_ref().is_some_and(|n| !n.contains(&server.name)) {
+            continue;
+        }
+        let server_id = server.definition.id.clone();
+        if service
+            .get(&space_id, &server_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .is_some()
+        {
+            report.skipped.push(SkippedImport {
+                name: server.name,
+                reason: "Already installed in this space".to_string(),
+            });
+            continue;
+        }
+        service
+            .install_with_source(
+                space_uuid,
+                &server_id,
+                &server.definition,
+                server.input_values,
+                InstallationSource::ManualEntry,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        report.imported.push(server_id);
+    }
+
+    tracing::info!(
+        "[import_from_client_config] Imported {} server(s) from {}",
+        report.imported.len(),
+        report.path
+    );
+    Ok(report)
+}
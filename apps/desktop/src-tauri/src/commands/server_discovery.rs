@@ -1,6 +1,12 @@
+use crate::services::RegistryFolderWatcher;
 use crate::AppState;
-use mcpmux_core::{HomeConfig, InstalledServer, ServerDefinition, ServerSource, UiConfig};
-use tauri::State;
+use mcpmux_core::{
+    HomeConfig, InstalledServer, PublisherKey, RegistryAuth, RegistryAuthKind, RegistrySource,
+    RegistrySyncStatus, ServerDefinition, ServerSource, UiConfig,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
 
 /// Discover all available servers (from API + User Spaces)
 #[tauri::command]
@@ -180,3 +186,115 @@ pub async fn get_registry_home_config(
 pub async fn is_registry_offline(state: State<'_, AppState>) -> Result<bool, String> {
     Ok(state.server_discovery.is_offline().await)
 }
+
+/// Offline state and when the served registry data was last synced
+#[tauri::command]
+pub async fn get_registry_sync_status(
+    state: State<'_, AppState>,
+) -> Result<RegistrySyncStatus, String> {
+    Ok(state.server_discovery.sync_status().await)
+}
+
+/// A configured registry and which kind of credentials it has
+#[derive(Debug, Serialize)]
+pub struct RegistrySourceInfo {
+    #[serde(flatten)]
+    pub source: RegistrySource,
+    pub auth: Option<RegistryAuthKind>,
+}
+
+async fn registry_source_infos(state: &AppState) -> Result<Vec<RegistrySourceInfo>, String> {
+    let mut infos = Vec::new();
+    for source in state.server_discovery.registry_sources().await {
+        let auth = state
+            .registry_credential_service
+            .get(&source.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|auth| auth.kind());
+        infos.push(RegistrySourceInfo { source, auth });
+    }
+    Ok(infos)
+}
+
+/// List the registries servers are loaded from, highest priority first
+#[tauri::command]
+pub async fn list_registry_sources(
+    state: State<'_, AppState>,
+) -> Result<Vec<RegistrySourceInfo>, String> {
+    registry_source_infos(&state).await
+}
+
+/// Replace the configured registries (empty = public registry only) and
+/// reload servers from them. Local registry folders are watched for changes.
+#[tauri::command]
+pub async fn set_registry_sources(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    sources: Vec<RegistrySource>,
+) -> Result<Vec<RegistrySourceInfo>, String> {
+    tracing::info!(
+        "[set_registry_sources] Configuring {} registries",
+        sources.len()
+    );
+    state
+        .server_discovery
+        .set_registry_sources(sources)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(watcher) = app.try_state::<Arc<RegistryFolderWatcher>>() {
+        watcher.watch(state.server_discovery.local_registry_dirs().await);
+    }
+    registry_source_infos(&state).await
+}
+
+/// Set or clear (`None`) the credentials sent to a registry, then reload
+/// servers so a private registry's catalog shows up right away
+#[tauri::command]
+pub async fn set_registry_auth(
+    state: State<'_, AppState>,
+    registry_id: String,
+    auth: Option<RegistryAuth>,
+) -> Result<(), String> {
+    if !state
+        .server_discovery
+        .registry_sources()
+        .await
+        .iter()
+        .any(|s| s.id == registry_id)
+    {
+        return Err(format!("Unknown registry: {}", registry_id));
+    }
+    state
+        .registry_credential_service
+        .set(&registry_id, auth.as_ref())
+        .await
+        .map_err(|e| e.to_string())?;
+    state
+        .server_discovery
+        .refresh()
+        .await
+        .map_err(|e| format!("Failed to refresh: {}", e))
+}
+
+/// List the trusted publisher keys
+#[tauri::command]
+pub async fn list_publisher_keys(state: State<'_, AppState>) -> Result<Vec<PublisherKey>, String> {
+    Ok(state.server_discovery.publisher_keys().await)
+}
+
+/// Replace the trusted publisher keys and reload servers, so signed entries
+/// are checked against the new keys
+#[tauri::command]
+pub async fn set_publisher_keys(
+    state: State<'_, AppState>,
+    keys: Vec<PublisherKey>,
+) -> Result<Vec<PublisherKey>, String> {
+    tracing::info!("[set_publisher_keys] Configuring {} keys", keys.len());
+    state
+        .server_discovery
+        .set_publisher_keys(keys)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(state.server_discovery.publisher_keys().await)
+}
@@ -2,10 +2,18 @@
 //!
 //! IPC commands for querying discovered MCP features (tools, prompts, resources).
 
+use std::sync::Arc;
+use std::time::Instant;
+
+use mcpmux_core::ToolAnnotations;
 use mcpmux_storage::{FeatureType, ServerFeature, ServerFeatureRepository};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::State;
+use tokio::sync::RwLock;
+use tracing::info;
+use uuid::Uuid;
 
+use crate::commands::gateway::GatewayAppState;
 use crate::state::AppState;
 
 /// Response for server feature listing
@@ -19,9 +27,15 @@ pub struct ServerFeatureResponse {
     pub display_name: Option<String>,
     pub description: Option<String>,
     pub input_schema: Option<serde_json::Value>,
+    /// Annotation hints, for tools
+    pub annotations: Option<ToolAnnotations>,
     pub discovered_at: String,
     pub last_seen_at: String,
     pub is_available: bool,
+    /// Clients keep this tool's schema until a change is accepted
+    pub schema_pinned: bool,
+    /// Changed tool JSON held back from clients
+    pub pending_schema: Option<serde_json::Value>,
 }
 
 impl From<ServerFeature> for ServerFeatureResponse {
@@ -34,10 +48,14 @@ impl From<ServerFeature> for ServerFeatureResponse {
             feature_name: f.feature_name,
             display_name: f.display_name,
             description: f.description,
+            annotations: (f.feature_type == FeatureType::Tool)
+                .then(|| ToolAnnotations::from_raw_json(f.raw_json.as_ref())),
             input_schema: f.raw_json, // Use raw_json now
             discovered_at: f.discovered_at.to_rfc3339(),
             last_seen_at: f.last_seen_at.to_rfc3339(),
             is_available: f.is_available,
+            schema_pinned: f.schema_pinned,
+            pending_schema: f.pending_raw_json,
         }
     }
 }
@@ -130,6 +148,82 @@ pub async fn get_server_feature(
     Ok(feature.map(Into::into))
 }
 
+/// Pin or unpin a tool's schema. Unpinning hands clients a held-back schema.
+#[tauri::command]
+pub async fn set_feature_schema_pinned(
+    id: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<ServerFeatureResponse, String> {
+    let repo = &state.server_feature_repository;
+    let before = repo
+        .get(&id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Feature not found: {}", id))?;
+    if before.feature_type != FeatureType::Tool {
+        return Err("Only tool schemas can be pinned".to_string());
+    }
+    let feature = repo
+        .set_schema_pinned(&id, pinned)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Feature not found: {}", id))?;
+
+    info!(
+        "[ServerFeature] Schema of {}/{} {}",
+        feature.server_id,
+        feature.feature_name,
+        if pinned { "pinned" } else { "unpinned" }
+    );
+    if before.pending_raw_json.is_some() && feature.pending_raw_json.is_none() {
+        notify_tools_changed(&gateway_state, &feature).await;
+    }
+    Ok(feature.into())
+}
+
+/// Give clients the schema a pinned tool's server changed to.
+#[tauri::command]
+pub async fn accept_feature_schema(
+    id: String,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<ServerFeatureResponse, String> {
+    let feature = state
+        .server_feature_repository
+        .accept_pending_schema(&id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No schema change is waiting for this tool")?;
+
+    info!(
+        "[ServerFeature] Accepted new schema of {}/{}",
+        feature.server_id, feature.feature_name
+    );
+    notify_tools_changed(&gateway_state, &feature).await;
+    Ok(feature.into())
+}
+
+/// Tell connected clients the tool list changed, if the gateway is running
+async fn notify_tools_changed(
+    gateway_state: &Arc<RwLock<GatewayAppState>>,
+    feature: &ServerFeature,
+) {
+    let Ok(space_id) = Uuid::parse_str(&feature.space_id) else {
+        return;
+    };
+    let gw_state = gateway_state.read().await;
+    if let Some(ref gw) = gw_state.gateway_state {
+        gw.read()
+            .await
+            .emit_domain_event(mcpmux_core::DomainEvent::ToolsChanged {
+                space_id,
+                server_id: feature.server_id.clone(),
+            });
+    }
+}
+
 /// Seed server features for E2E testing.
 ///
 /// Accepts an array of feature definitions and upserts them into the database.
@@ -172,6 +266,80 @@ pub async fn seed_server_features(
     Ok(ids)
 }
 
+/// Input for [`test_tool_call`]
+#[derive(Debug, Deserialize)]
+pub struct TestToolCallInput {
+    pub space_id: String,
+    pub server_id: String,
+    /// Tool name as the server reports it (not prefixed)
+    pub tool_name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+    /// FeatureSets whose grants the call is checked against
+    #[serde(default)]
+    pub feature_set_ids: Vec<String>,
+    /// Skip the grant check
+    #[serde(default)]
+    pub bypass_permissions: bool,
+}
+
+/// Result of a test tool call
+#[derive(Debug, Serialize)]
+pub struct TestToolCallResponse {
+    pub content: Vec<serde_json::Value>,
+    pub is_error: bool,
+    pub structured_content: Option<serde_json::Value>,
+    pub meta: Option<serde_json::Value>,
+    /// Wall time of the call, reconnect retries included
+    pub duration_ms: u64,
+}
+
+/// Call a discovered tool with hand-written arguments, for debugging.
+///
+/// Goes through the gateway's routing service like a client call would;
+/// the grant check can be skipped with `bypass_permissions`.
+#[tauri::command]
+pub async fn test_tool_call(
+    input: TestToolCallInput,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<TestToolCallResponse, String> {
+    let space_id = Uuid::parse_str(&input.space_id).map_err(|e| e.to_string())?;
+    if !input.arguments.is_null() && !input.arguments.is_object() {
+        return Err("Tool arguments must be a JSON object".to_string());
+    }
+    let routing = gateway_state
+        .read()
+        .await
+        .routing_service
+        .clone()
+        .ok_or("Gateway is not running")?;
+
+    info!(
+        "[ServerFeature] Test call of {} on {} (bypass_permissions={})",
+        input.tool_name, input.server_id, input.bypass_permissions
+    );
+    let start = Instant::now();
+    let result = routing
+        .test_tool_call(
+            space_id,
+            &input.feature_set_ids,
+            &input.server_id,
+            &input.tool_name,
+            input.arguments,
+            input.bypass_permissions,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(TestToolCallResponse {
+        content: result.content_json().map_err(|e| e.to_string())?,
+        is_error: result.is_error,
+        structured_content: result.structured_content,
+        meta: result.meta.and_then(|meta| serde_json::to_value(meta).ok()),
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
 /// Input for seeding a server feature (E2E testing).
 #[derive(Debug, serde::Deserialize)]
 pub struct SeedFeatureInput {
@@ -0,0 +1,255 @@
+//! Tauri commands for server groups
+//!
+//! Groups are user-defined folders of installed servers ("AWS", "Personal").
+//! Besides organising the Servers page they support bulk enable, disable and
+//! reconnect, and can own a FeatureSet with every feature of their servers.
+
+use mcpmux_core::{DomainEvent, GroupFeatureSetSync, ServerGroup};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::commands::gateway::GatewayAppState;
+use crate::commands::server_manager::{
+    disable_server_v2, enable_server_v2, retry_connection, ServerManagerState,
+};
+use crate::state::AppState;
+
+/// A server that a bulk action could not be applied to
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerGroupActionFailure {
+    pub server_id: String,
+    pub error: String,
+}
+
+/// Outcome of running a bulk action across a group
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServerGroupActionResult {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<ServerGroupActionFailure>,
+}
+
+/// List the server groups of a space
+#[tauri::command]
+pub async fn list_server_groups(
+    space_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ServerGroup>, String> {
+    state
+        .server_group_service
+        .list(&space_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create an empty server group
+#[tauri::command]
+pub async fn create_server_group(
+    space_id: String,
+    name: String,
+    icon: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ServerGroup, String> {
+    state
+        .server_group_service
+        .create(&space_id, &name, icon)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rename a server group or change its icon
+#[tauri::command]
+pub async fn update_server_group(
+    id: String,
+    name: String,
+    icon: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ServerGroup, String> {
+    state
+        .server_group_service
+        .update(&id, &name, icon)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a server group. Its servers and FeatureSet are kept.
+#[tauri::command]
+pub async fn delete_server_group(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .server_group_service
+        .delete(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace the servers in a group
+#[tauri::command]
+pub async fn set_server_group_servers(
+    id: String,
+    server_ids: Vec<String>,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<ServerGroup, String> {
+    let (group, sync) = state
+        .server_group_service
+        .set_servers(&id, server_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(sync) = sync {
+        emit_sync_events(&gateway_state, &group.space_id, &sync).await;
+    }
+    Ok(group)
+}
+
+/// Create the group's FeatureSet, or bring it up to date with the features
+/// of the group's servers. Returns the FeatureSet ID.
+#[tauri::command]
+pub async fn sync_server_group_feature_set(
+    id: String,
+    state: State<'_, AppState>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+) -> Result<String, String> {
+    let group = state
+        .server_group_service
+        .get(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let sync = state
+        .server_group_service
+        .sync_feature_set(&id)
+        .await
+        .map_err(|e| format!("Failed to sync group feature set: {}", e))?;
+
+    emit_sync_events(&gateway_state, &group.space_id, &sync).await;
+    Ok(sync.feature_set.id)
+}
+
+/// Enable, disable or reconnect every server in a group
+///
+/// `action` is one of `enable`, `disable` or `reconnect`. Servers are
+/// handled one at a time; a failure is recorded and the rest still run.
+#[tauri::command]
+pub async fn run_server_group_action(
+    id: String,
+    action: String,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> Result<ServerGroupActionResult, String> {
+    if !matches!(action.as_str(), "enable" | "disable" | "reconnect") {
+        return Err("Invalid action. Use 'enable', 'disable' or 'reconnect'".to_string());
+    }
+
+    let group = app_state
+        .server_group_service
+        .get(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let servers = app_state
+        .server_group_service
+        .installed_servers(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        "[ServerGroup] Running '{}' on {} servers of group {}",
+        action,
+        servers.len(),
+        group.name
+    );
+
+    let mut result = ServerGroupActionResult::default();
+    for server in servers {
+        // Reconnecting only makes sense for servers that are switched on
+        if action == "reconnect" && !server.enabled {
+            continue;
+        }
+
+        let space_id = group.space_id.clone();
+        let server_id = server.server_id.clone();
+        let outcome = match action.as_str() {
+            "enable" => {
+                enable_server_v2(
+                    space_id,
+                    server_id,
+                    state.clone(),
+                    gateway_state.clone(),
+                    app_state.clone(),
+                )
+                .await
+            }
+            "disable" => {
+                disable_server_v2(
+                    space_id,
+                    server_id,
+                    state.clone(),
+                    gateway_state.clone(),
+                    app_state.clone(),
+                )
+                .await
+            }
+            _ => {
+                retry_connection(
+                    space_id,
+                    server_id,
+                    state.clone(),
+                    gateway_state.clone(),
+                    app_state.clone(),
+                )
+                .await
+            }
+        };
+
+        match outcome {
+            Ok(()) => result.succeeded.push(server.server_id),
+            Err(error) => {
+                warn!(
+                    "[ServerGroup] '{}' failed for {}: {}",
+                    action, server.server_id, error
+                );
+                result.failed.push(ServerGroupActionFailure {
+                    server_id: server.server_id,
+                    error,
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Tell the gateway about a group FeatureSet that was created or changed
+async fn emit_sync_events(
+    gateway_state: &State<'_, Arc<RwLock<GatewayAppState>>>,
+    space_id: &str,
+    sync: &GroupFeatureSetSync,
+) {
+    let Ok(space_uuid) = Uuid::parse_str(space_id) else {
+        return;
+    };
+
+    let gw_state = gateway_state.read().await;
+    if let Some(ref gw) = gw_state.gateway_state {
+        let gw = gw.read().await;
+        if sync.created {
+            gw.emit_domain_event(DomainEvent::FeatureSetCreated {
+                space_id: space_uuid,
+                feature_set_id: sync.feature_set.id.clone(),
+                name: sync.feature_set.name.clone(),
+                feature_set_type: Some(sync.feature_set.feature_set_type.as_str().to_string()),
+            });
+        }
+        if sync.added > 0 || sync.removed > 0 {
+            gw.emit_domain_event(DomainEvent::FeatureSetMembersChanged {
+                space_id: space_uuid,
+                feature_set_id: sync.feature_set.id.clone(),
+                added_count: sync.added,
+                removed_count: sync.removed,
+            });
+        }
+    }
+}
@@ -7,14 +7,18 @@
 //! - Connect/Reconnect button based on connection history
 
 use crate::AppState;
-use mcpmux_gateway::pool::transport::resolution::build_transport_config; // Import from gateway
+use mcpmux_core::{DiagnosedCause, InstalledServer, TransportConfig};
+use mcpmux_gateway::pool::transport::resolution::{
+    apply_profile, resolve_transport_config, space_profile, space_workspace, TransportConfigError,
+}; // Import from gateway
 use mcpmux_gateway::{
-    ConnectionContext, ConnectionResult, ConnectionStatus, ServerKey, ServerManager,
+    ConnectionContext, ConnectionResult, ConnectionStatus, ResolvedTransport, ServerHealth,
+    ServerKey, ServerManager,
 };
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -27,6 +31,10 @@ pub struct ServerStatusResponse {
     pub flow_id: u64,
     pub has_connected_before: bool,
     pub message: Option<String>,
+    /// Ping stats while connected; `None` otherwise
+    pub health: Option<ServerHealth>,
+    /// Why the server keeps exiting during startup, when it's crash-looping
+    pub diagnosis: Option<DiagnosedCause>,
 }
 
 /// App state wrapper for ServerManager
@@ -54,10 +62,18 @@ pub async fn get_server_statuses(
         .ok_or("ServerManager not initialized")?;
 
     let statuses = manager.get_all_statuses(space_uuid).await;
+    let mut diagnoses = manager.get_diagnoses(space_uuid).await;
 
     Ok(statuses
         .into_iter()
         .map(|(server_id, (status, flow_id, has_connected, msg))| {
+            let health = match (&manager_state.pool_service, status) {
+                (Some(pool), ConnectionStatus::Connected) => pool
+                    .get_instance(space_uuid, &server_id)
+                    .map(|instance| instance.health()),
+                _ => None,
+            };
+            let diagnosis = diagnoses.remove(&server_id);
             (
                 server_id.clone(),
                 ServerStatusResponse {
@@ -66,6 +82,8 @@ pub async fn get_server_statuses(
                     flow_id,
                     has_connected_before: has_connected,
                     message: msg,
+                    health,
+                    diagnosis,
                 },
             )
         })
@@ -130,16 +148,27 @@ pub async fn enable_server_v2(
     // Set status = Connecting
     manager.set_connecting(&key).await;
 
-    // Build transport config
-    let transport = build_transport_config(
-        &server_definition.transport,
+    // Build transport config with the space's active environment profile;
+    // invalid input values fail before anything is started
+    let transport = match resolve_transport(
+        &app_state,
+        &space_uuid,
         &installed,
-        Some(app_state.data_dir()),
-    );
+        &server_definition.transport,
+    )
+    .await
+    {
+        Ok(transport) => transport,
+        Err(e) => {
+            manager.set_error(&key, e.to_string()).await;
+            return Err(e.to_string());
+        }
+    };
 
     // Attempt connection with auto_reconnect=true to avoid starting OAuth flow
     // If OAuth is needed, we just set AuthRequired and let user click Connect
-    let ctx = ConnectionContext::auto(space_uuid, server_id.clone(), transport);
+    let ctx = ConnectionContext::auto(space_uuid, server_id.clone(), transport)
+        .with_timeouts(installed.effective_timeouts(&server_definition));
     let result = pool_service.connect_server(&ctx).await;
 
     match result {
@@ -184,6 +213,29 @@ pub async fn enable_server_v2(
     }
 }
 
+/// Resolve the transport a server connects with: its definition, the
+/// Space's active environment profile and workspace, and the saved inputs
+async fn resolve_transport(
+    app_state: &AppState,
+    space_uuid: &Uuid,
+    installed: &InstalledServer,
+    template: &TransportConfig,
+) -> Result<ResolvedTransport, TransportConfigError> {
+    let workspace = space_workspace(app_state.space_base_dir_repository.as_ref(), space_uuid).await;
+    let profile = space_profile(
+        Some(app_state.environment_profile_repository.as_ref()),
+        space_uuid,
+    )
+    .await?;
+    let (template, profiled) = apply_profile(profile.as_ref(), template, installed);
+    resolve_transport_config(
+        &template,
+        &profiled,
+        Some(app_state.data_dir()),
+        workspace.as_deref(),
+    )
+}
+
 /// Disable a server (marks as disabled, marks features unavailable, keeps tokens & DCR for fast re-enable)
 #[tauri::command]
 pub async fn disable_server_v2(
@@ -320,12 +372,31 @@ pub async fn start_auth_v2(
     manager.set_connecting(&key).await;
 
     // Build transport config and attempt connection (manual connect from user clicking Connect button)
-    let transport = build_transport_config(
-        &server_definition.transport,
-        &installed,
-        Some(app_state.data_dir()),
-    );
-    let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport);
+    let workspace =
+        space_workspace(app_state.space_base_dir_repository.as_ref(), &space_uuid).await;
+    let profile = space_profile(
+        Some(app_state.environment_profile_repository.as_ref()),
+        &space_uuid,
+    )
+    .await;
+    let transport = match profile.and_then(|profile| {
+        let (template, profiled) =
+            apply_profile(profile.as_ref(), &server_definition.transport, &installed);
+        resolve_transport_config(
+            &template,
+            &profiled,
+            Some(app_state.data_dir()),
+            workspace.as_deref(),
+        )
+    }) {
+        Ok(transport) => transport,
+        Err(e) => {
+            manager.set_error(&key, e.to_string()).await;
+            return Err(e.to_string());
+        }
+    };
+    let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport)
+        .with_timeouts(installed.effective_timeouts(&server_definition));
     let result = pool_service.connect_server(&ctx).await;
 
     match result {
@@ -437,6 +508,137 @@ pub async fn retry_connection(
     enable_server_v2(space_id, server_id, state, gateway_state, app_state).await
 }
 
+/// Restart a connected server if its transport no longer matches its config,
+/// e.g. after its env or args were edited in the Space config file. Enabled
+/// servers that aren't connected, or whose transport is unchanged, are left alone.
+pub(crate) async fn restart_if_transport_changed(
+    app: &AppHandle,
+    space_id: String,
+    server_id: String,
+) {
+    let Ok(space_uuid) = Uuid::parse_str(&space_id) else {
+        return;
+    };
+    let app_state = app.state::<AppState>();
+    let installed = match app_state
+        .installed_server_repository
+        .get_by_server_id(&space_id, &server_id)
+        .await
+    {
+        Ok(Some(installed)) if installed.enabled => installed,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("[ServerManager] Failed to get server {}: {}", server_id, e);
+            return;
+        }
+    };
+    let Some(definition) = installed.get_definition() else {
+        return;
+    };
+    // An invalid config is reported by the regular connect path
+    let Ok(transport) =
+        resolve_transport(&app_state, &space_uuid, &installed, &definition.transport).await
+    else {
+        return;
+    };
+
+    let state = app.state::<Arc<RwLock<ServerManagerState>>>();
+    let changed = state
+        .read()
+        .await
+        .pool_service
+        .as_ref()
+        .is_some_and(|pool| pool.transport_changed(space_uuid, &server_id, &transport));
+    if !changed {
+        return;
+    }
+
+    info!(
+        "[ServerManager] Config of {} changed, restarting it",
+        server_id
+    );
+    if let Err(e) =
+        retry_connection(space_id, server_id.clone(), state, app.state(), app_state).await
+    {
+        warn!("[ServerManager] Failed to restart {}: {}", server_id, e);
+    }
+}
+
+/// A newer release of a server's npm/PyPI package
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerUpdateResponse {
+    pub space_id: String,
+    pub server_id: String,
+    pub ecosystem: String,
+    pub package: String,
+    pub current_version: String,
+    pub latest_version: String,
+}
+
+fn package_update_service(
+    gateway_state: &crate::commands::gateway::GatewayAppState,
+) -> Result<Arc<mcpmux_gateway::services::PackageUpdateService>, String> {
+    gateway_state
+        .package_updates
+        .clone()
+        .ok_or_else(|| "Gateway not running".to_string())
+}
+
+/// Check pinned npx/uvx server packages for newer releases now
+///
+/// The gateway also checks daily; both emit `server-update-available`.
+#[tauri::command]
+pub async fn check_server_updates(
+    gateway_state: State<'_, Arc<RwLock<crate::commands::gateway::GatewayAppState>>>,
+) -> Result<Vec<ServerUpdateResponse>, String> {
+    let service = package_update_service(&gateway_state.read().await)?;
+    let updates = service
+        .check_for_updates()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updates
+        .into_iter()
+        .map(|update| ServerUpdateResponse {
+            space_id: update.space_id.to_string(),
+            server_id: update.server_id,
+            ecosystem: update.ecosystem.as_str().to_string(),
+            package: update.package,
+            current_version: update.current_version,
+            latest_version: update.latest_version,
+        })
+        .collect())
+}
+
+/// Pin a server to the latest release of its package and, if it's enabled,
+/// reconnect so the runner fetches the new version. Returns the new version.
+#[tauri::command]
+pub async fn update_server_package(
+    space_id: String,
+    server_id: String,
+    state: State<'_, Arc<RwLock<ServerManagerState>>>,
+    gateway_state: State<'_, Arc<RwLock<crate::commands::gateway::GatewayAppState>>>,
+    app_state: State<'_, AppState>,
+) -> Result<String, String> {
+    let space_uuid = Uuid::parse_str(&space_id).map_err(|e| format!("Invalid space_id: {}", e))?;
+    let service = package_update_service(&gateway_state.read().await)?;
+
+    let version = service
+        .update_package(space_uuid, &server_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let enabled = app_state
+        .installed_server_repository
+        .get_by_server_id(&space_id, &server_id)
+        .await
+        .map_err(|e| format!("Failed to get server: {}", e))?
+        .is_some_and(|installed| installed.enabled);
+    if enabled {
+        retry_connection(space_id, server_id, state, gateway_state, app_state).await?;
+    }
+    Ok(version)
+}
+
 /// Logout server - Clear OAuth tokens but keep enabled
 ///
 /// Preserves: DCR registration (client_id), input values, enabled flag
@@ -7,11 +7,11 @@
 //! viewing in its own Zustand store (frontend-only state).
 
 use mcpmux_core::{
-    application::UserSpaceSyncService, validate_workspace_root, Space, SpaceBaseDir,
-    WorkspaceRootValidation,
+    application::UserSpaceSyncService, validate_space_config, validate_workspace_root, DomainEvent,
+    Space, SpaceBaseDir, WorkspaceRootValidation,
 };
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -97,7 +97,7 @@ pub async fn create_space(
 
     // Update system tray menu to show the new space
     // Only reached if both space creation and config file writing succeeded
-    if let Err(e) = tray::update_tray_spaces(&app, &state).await {
+    if let Err(e) = tray::update_tray_menu(&app, &state).await {
         warn!("Failed to update tray menu: {}", e);
     }
 
@@ -131,7 +131,7 @@ pub async fn delete_space(
 
     // Update system tray menu to remove the deleted space
     // Only reached if space deletion from DB succeeded
-    if let Err(e) = tray::update_tray_spaces(&app, &state).await {
+    if let Err(e) = tray::update_tray_menu(&app, &state).await {
         warn!("Failed to update tray menu: {}", e);
     }
 
@@ -188,13 +188,28 @@ pub async fn read_space_config(
 pub async fn save_space_config(
     space_id: String,
     content: String,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let config_path = state.space_config_path(&space_id)?;
 
-    // Validate JSON before saving
-    serde_json::from_str::<serde_json::Value>(&content)
-        .map_err(|e| format!("Invalid JSON: {}", e))?;
+    // Check the content against the published schema before saving. The
+    // issues go out as a `SpaceConfigInvalid` event too, so the editor can
+    // mark each one on its line.
+    if let Err(invalid) = validate_space_config(&content) {
+        if let Ok(space_uuid) = Uuid::parse_str(&space_id) {
+            crate::commands::gateway::forward_domain_event(
+                &app,
+                &DomainEvent::SpaceConfigInvalid {
+                    space_id: space_uuid,
+                    file: config_path.display().to_string(),
+                    issues: invalid.issues.clone(),
+                },
+                None,
+            );
+        }
+        return Err(invalid.to_string());
+    }
 
     std::fs::write(&config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
@@ -202,7 +217,8 @@ pub async fn save_space_config(
     // Do not rely solely on the debounced file watcher. The UI reloads immediately
     // after this command returns, so sync the just-saved file into InstalledServer
     // records synchronously to avoid stale/missing custom-server state.
-    let sync_service = UserSpaceSyncService::new(state.installed_server_repository.clone());
+    let sync_service = UserSpaceSyncService::new(state.installed_server_repository.clone())
+        .with_managed_policy(state.managed_policy.clone());
     let sync_result = sync_service
         .sync_from_file(&space_id, &config_path)
         .await
@@ -217,6 +233,13 @@ pub async fn save_space_config(
         );
     }
 
+    // Restart connected servers whose env or args were changed by the edit
+    app.state::<crate::services::TransportReloader>().schedule(
+        &app,
+        &space_id,
+        &sync_result.updated,
+    );
+
     Ok(())
 }
 
@@ -266,10 +289,10 @@ pub async fn remove_server_from_config(
     Ok(false)
 }
 
-/// Refresh the system tray menu to reflect current spaces
+/// Refresh the system tray menu to reflect current spaces and servers
 #[tauri::command]
 pub async fn refresh_tray_menu(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    tray::update_tray_spaces(&app, &state)
+    tray::update_tray_menu(&app, &state)
         .await
         .map_err(|e| format!("Failed to update tray menu: {}", e))
 }
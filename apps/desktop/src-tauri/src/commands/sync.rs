@@ -0,0 +1,86 @@
+//! Config sync commands
+//!
+//! IPC commands for configuring where Spaces, servers, FeatureSets and
+//! workspace bindings are synced, and for running a sync on demand.
+
+use mcpmux_core::{AppSettingsService, ConflictStrategy, SyncReport, SyncTargetConfig};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tracing::warn;
+
+use crate::state::AppState;
+
+/// Config sync settings
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncSettings {
+    /// Where to sync (`None` = sync disabled)
+    pub target: Option<SyncTargetConfig>,
+    pub conflict_strategy: ConflictStrategy,
+    /// Minutes between automatic syncs (0 = manual only)
+    pub interval_minutes: u32,
+}
+
+/// Get config sync settings
+#[tauri::command]
+pub async fn get_sync_settings(state: State<'_, AppState>) -> Result<SyncSettings, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    Ok(SyncSettings {
+        target: settings.get_sync_target().await,
+        conflict_strategy: settings.get_sync_conflict_strategy().await,
+        interval_minutes: settings.get_sync_interval_minutes().await,
+    })
+}
+
+/// Update config sync settings (picked up at the next scheduler tick)
+#[tauri::command]
+pub async fn set_sync_settings(
+    sync_settings: SyncSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(target) = &sync_settings.target {
+        // Fail early on an unusable target (e.g. missing S3 credentials)
+        target.build().map_err(|e| e.to_string())?;
+    }
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    settings
+        .set_sync_target(sync_settings.target.as_ref())
+        .await
+        .map_err(|e| format!("Failed to save sync settings: {}", e))?;
+    settings
+        .set_sync_conflict_strategy(sync_settings.conflict_strategy)
+        .await
+        .map_err(|e| format!("Failed to save sync settings: {}", e))?;
+    settings
+        .set_sync_interval_minutes(sync_settings.interval_minutes)
+        .await
+        .map_err(|e| format!("Failed to save sync settings: {}", e))
+}
+
+/// Sync now with the configured target.
+///
+/// Emits `config-sync-completed` with the report so open views can refresh.
+#[tauri::command]
+pub async fn run_config_sync(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SyncReport, String> {
+    let settings = AppSettingsService::new(state.settings_repository.clone());
+    let target = settings
+        .get_sync_target()
+        .await
+        .ok_or("Config sync is not configured")?
+        .build()
+        .map_err(|e| e.to_string())?;
+    let strategy = settings.get_sync_conflict_strategy().await;
+
+    let report = state
+        .config_sync
+        .sync(target.as_ref(), strategy)
+        .await
+        .map_err(|e| format!("Sync failed: {}", e))?;
+
+    if let Err(e) = app_handle.emit("config-sync-completed", &report) {
+        warn!("[Sync] Failed to emit config-sync-completed: {}", e);
+    }
+    Ok(report)
+}
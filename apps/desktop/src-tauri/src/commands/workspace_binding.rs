@@ -8,7 +8,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use mcpmux_core::{
-    validate_workspace_root as validate_root, BindingType, DomainEvent, FeatureSet, FeatureSetType,
+    validate_workspace_root as validate_root, AnnotationHint, BindingType, DomainEvent, FeatureSet,
     MemberMode, MemberType, ServerFeature, WorkspaceBinding, WorkspaceRootValidation,
 };
 use serde::{Deserialize, Serialize};
@@ -349,15 +349,12 @@ pub async fn update_workspace_binding(
         .ok_or_else(|| format!("binding not found: {}", id))?;
     let old_space_id = existing.space_id;
 
-    let updated = WorkspaceBinding {
-        id: existing.id,
-        workspace_root: key,
-        binding_type,
-        space_id,
-        feature_set_ids,
-        created_at: existing.created_at,
-        updated_at: chrono::Utc::now(),
-    };
+    let mut updated = existing;
+    updated.workspace_root = key;
+    updated.binding_type = binding_type;
+    updated.space_id = space_id;
+    updated.feature_set_ids = feature_set_ids;
+    updated.updated_at = chrono::Utc::now();
 
     state
         .workspace_binding_repository
@@ -508,6 +505,7 @@ pub struct WorkspaceEffectiveFeaturesDto {
 fn collect_member_ids(
     fs: &FeatureSet,
     fs_lookup: &HashMap<String, FeatureSet>,
+    features: &[ServerFeature],
     allowed: &mut HashSet<String>,
     excluded: &mut HashSet<String>,
     visited: &mut HashSet<String>,
@@ -524,12 +522,30 @@ fn collect_member_ids(
                 MemberMode::Exclude => {
                     excluded.insert(m.member_id.clone());
                 }
+                _ => {}
             },
+            MemberType::Annotation => {
+                let Some(hint) = AnnotationHint::parse(&m.member_id) else {
+                    continue;
+                };
+                let ids = features
+                    .iter()
+                    .filter(|f| f.has_hint(hint))
+                    .map(|f| f.id.to_string());
+                match m.mode {
+                    MemberMode::Include => allowed.extend(ids),
+                    MemberMode::Exclude => excluded.extend(ids),
+                    _ => {}
+                }
+            }
             MemberType::FeatureSet => {
                 if let Some(nested) = fs_lookup.get(&m.member_id) {
-                    collect_member_ids(nested, fs_lookup, allowed, excluded, visited);
+                    collect_member_ids(nested, fs_lookup, features, allowed, excluded, visited);
                 }
             }
+            // Member types this build doesn't know grant nothing, as in the
+            // gateway's resolver
+            _ => {}
         }
     }
 }
@@ -687,11 +703,25 @@ pub async fn get_workspace_effective_features(
     //    the user's intent for adding the FS to the binding was to surface
     //    its members. Visiting state is shared across the loop so a nested
     //    FS shared between two parent FSes is walked once.
+    //    Annotation members match against every feature in the Space.
+    let all_features = state
+        .server_feature_repository_core
+        .list_for_space(&space_id.to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+
     let mut allowed = HashSet::<String>::new();
     let mut excluded = HashSet::<String>::new();
     let mut visited = HashSet::<String>::new();
     for fs in &resolved_sets {
-        collect_member_ids(fs, &fs_lookup, &mut allowed, &mut excluded, &mut visited);
+        collect_member_ids(
+            fs,
+            &fs_lookup,
+            &all_features,
+            &mut allowed,
+            &mut excluded,
+            &mut visited,
+        );
     }
     // Cross-FS exclude → include resolution: if any FS lists the feature as
     // an explicit include, override an exclude from a sibling FS. This is
@@ -702,12 +732,6 @@ pub async fn get_workspace_effective_features(
     //    badge denominator), then keep only the FS-filtered subset for the
     //    rendered list. The `is_available` gate is intentionally not
     //    applied here — disconnected features still appear, dimmed.
-    let all_features = state
-        .server_feature_repository_core
-        .list_for_space(&space_id.to_string())
-        .await
-        .map_err(|e| e.to_string())?;
-
     let mut server_totals: HashMap<String, ServerFeatureTotalsDto> = HashMap::new();
     for f in &all_features {
         let entry = server_totals
@@ -721,6 +745,7 @@ pub async fn get_workspace_effective_features(
             mcpmux_core::FeatureType::Tool => entry.tools += 1,
             mcpmux_core::FeatureType::Prompt => entry.prompts += 1,
             mcpmux_core::FeatureType::Resource => entry.resources += 1,
+            _ => {}
         }
     }
 
@@ -762,6 +787,7 @@ pub async fn get_workspace_effective_features(
             mcpmux_core::FeatureType::Tool => tools.push(dto),
             mcpmux_core::FeatureType::Prompt => prompts.push(dto),
             mcpmux_core::FeatureType::Resource => resources.push(dto),
+            _ => {}
         }
     }
     // Stable order: alphabetical by qualified-ish name so the UI doesn't
@@ -784,10 +810,7 @@ pub async fn get_workspace_effective_features(
         .map(|fs| EffectiveFeatureSetDto {
             id: fs.id,
             name: fs.name,
-            feature_set_type: match fs.feature_set_type {
-                FeatureSetType::Starter => "starter".to_string(),
-                FeatureSetType::Custom => "custom".to_string(),
-            },
+            feature_set_type: fs.feature_set_type.as_str().to_string(),
         })
         .collect();
 
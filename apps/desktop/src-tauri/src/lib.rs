@@ -8,6 +8,7 @@ use tauri::{Emitter, Manager};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+mod cli;
 mod commands;
 mod services;
 mod state;
@@ -20,6 +21,8 @@ use commands::gateway::{GatewayAppState, PendingPortConflict};
 use commands::server_manager::ServerManagerState;
 use state::AppState;
 
+pub use cli::run_db_command;
+
 /// Application identifier - read from tauri.conf.json at build time
 /// Single source of truth: tauri.conf.json -> build.rs -> env!()
 const APP_IDENTIFIER: &str = env!("TAURI_APP_IDENTIFIER");
@@ -46,6 +49,41 @@ fn get_logs_dir() -> std::path::PathBuf {
     get_app_data_dir().join("logs")
 }
 
+type LogFilterHandle =
+    tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>;
+
+/// Swaps the log filter at runtime (see [`set_log_filter`])
+static LOG_FILTER: std::sync::OnceLock<LogFilterHandle> = std::sync::OnceLock::new();
+
+/// Filter used when neither RUST_LOG nor a saved log level is set
+fn default_env_filter() -> tracing_subscriber::EnvFilter {
+    // Note: Rust crate names use underscores in tracing (e.g., mcpmux-core → mcpmux_core)
+    tracing_subscriber::EnvFilter::new("info")
+        .add_directive("mcpmux_core=debug".parse().unwrap())
+        .add_directive("mcpmux_gateway=debug".parse().unwrap())
+        .add_directive("mcpmux_storage=debug".parse().unwrap())
+        .add_directive("mcpmux_mcp=debug".parse().unwrap())
+        .add_directive("mcpmux_lib=debug".parse().unwrap())
+        .add_directive("tauri=info".parse().unwrap())
+        .add_directive("tao=warn".parse().unwrap())
+        .add_directive("wry=warn".parse().unwrap())
+}
+
+/// Replace the active log filter without restarting. `None` restores the
+/// default. Fails on invalid directives, leaving the filter unchanged.
+pub(crate) fn set_log_filter(directives: Option<&str>) -> Result<(), String> {
+    let filter = match directives {
+        Some(directives) => tracing_subscriber::EnvFilter::try_new(directives)
+            .map_err(|e| format!("Invalid log level: {}", e))?,
+        None => default_env_filter(),
+    };
+    LOG_FILTER
+        .get()
+        .ok_or("Logging is not initialized")?
+        .reload(filter)
+        .map_err(|e| e.to_string())
+}
+
 /// Initialize tracing for the application with console and file logging
 ///
 /// - Console: colored, compact format
@@ -78,20 +116,11 @@ fn init_tracing() -> tracing_appender::non_blocking::WorkerGuard {
     let (non_blocking_file, guard) = tracing_appender::non_blocking(file_appender);
 
     // Environment filter for log levels
-    // RUST_LOG takes precedence, with sensible defaults for our crates
-    // Note: Rust crate names use underscores in tracing (e.g., mcpmux-core → mcpmux_core)
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        // Default filter when RUST_LOG is not set
-        EnvFilter::new("info")
-            .add_directive("mcpmux_core=debug".parse().unwrap())
-            .add_directive("mcpmux_gateway=debug".parse().unwrap())
-            .add_directive("mcpmux_storage=debug".parse().unwrap())
-            .add_directive("mcpmux_mcp=debug".parse().unwrap())
-            .add_directive("mcpmux_lib=debug".parse().unwrap())
-            .add_directive("tauri=info".parse().unwrap())
-            .add_directive("tao=warn".parse().unwrap())
-            .add_directive("wry=warn".parse().unwrap())
-    });
+    // RUST_LOG takes precedence, with sensible defaults for our crates.
+    // Wrapped in a reload layer so Settings can change it while running.
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| default_env_filter());
+    let (env_filter, filter_handle) = tracing_subscriber::reload::Layer::new(env_filter);
+    let _ = LOG_FILTER.set(filter_handle);
 
     // Console layer: colored, compact
     let console_layer = fmt::layer()
@@ -321,8 +350,22 @@ pub fn run() {
                 });
             }
 
-            // Create event bus and ServerAppService
+            // Serve the registry from its disk snapshot right away, then
+            // sync it in the background so startup doesn't wait on the network
             let app_state: tauri::State<'_, AppState> = app.state();
+            let discovery = app_state.server_discovery.clone();
+            tauri::async_runtime::block_on(async {
+                if let Err(e) = discovery.load_snapshot().await {
+                    warn!("[Startup] Failed to load registry snapshot: {}", e);
+                }
+            });
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = discovery.refresh().await {
+                    warn!("[Startup] Registry sync failed: {}", e);
+                }
+            });
+
+            // Create event bus and ServerAppService
             let event_bus = mcpmux_core::create_shared_event_bus();
             let event_sender = event_bus.sender();
 
@@ -331,10 +374,11 @@ pub fn run() {
                 Some(app_state.server_feature_repository_core.clone()),
                 Some(app_state.credential_repository.clone()),
                 event_sender,
-            );
+            )
+            .with_managed_policy(app_state.managed_policy.clone());
 
             let managed_app_service = Arc::new(RwLock::new(Some(server_app_service)));
-            app.manage(managed_app_service);
+            app.manage(managed_app_service.clone());
 
             // Create gateway state and auto-start gateway
             let gateway_state = Arc::new(RwLock::new(GatewayAppState::default()));
@@ -343,12 +387,8 @@ pub fn run() {
             let server_manager_state = Arc::new(RwLock::new(ServerManagerState::default()));
 
             // Get repositories for pool services (clone before moving into spawn)
-            let db_for_gateway = app_state.database();
-            let installed_server_repo = app_state.installed_server_repository.clone();
-            let credential_repo = app_state.credential_repository.clone();
-            let backend_oauth_repo = app_state.backend_oauth_repository.clone();
-            let feature_set_repo = app_state.feature_set_repository.clone();
-            let feature_repo = app_state.server_feature_repository_core.clone();
+            let repositories = app_state.repositories.clone();
+            let managed_policy = app_state.managed_policy.clone();
             let server_discovery = app_state.server_discovery.clone();
             let server_log_manager = app_state.server_log_manager.clone();
             let port_service = app_state.gateway_port_service.clone();
@@ -359,6 +399,22 @@ pub fn run() {
             let sm_state_clone = server_manager_state.clone();
             let app_handle_for_sm = app.handle().clone();
             tauri::async_runtime::spawn(async move {
+                // Stdio servers orphaned by a crashed previous run may still
+                // hold ports and file locks the servers started below need
+                let reap_dir = app_data_dir.clone();
+                match tauri::async_runtime::spawn_blocking(move || {
+                    mcpmux_core::ChildProcessRegistry::new(&reap_dir).reap_orphans()
+                })
+                .await
+                {
+                    Ok(reaped) if !reaped.is_empty() => info!(
+                        "[Gateway] Terminated {} orphaned server process(es) from a previous run",
+                        reaped.len()
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!("[Gateway] Orphaned process cleanup failed: {}", e),
+                }
+
                 // Check if auto-start is enabled
                 if !port_service.get_auto_start().await {
                     info!("[Gateway] Auto-start disabled, skipping");
@@ -391,11 +447,18 @@ pub fn run() {
                         "[Gateway] Auto-start preferred port {} ({}) still unavailable after waiting — deferring to user",
                         preferred_port, source
                     );
+                    let owner = tauri::async_runtime::spawn_blocking(move || {
+                        mcpmux_core::port_owner(preferred_port)
+                    })
+                    .await
+                    .ok()
+                    .flatten();
                     {
                         let mut state = gw_state_clone.write().await;
                         state.pending_port_conflict = Some(PendingPortConflict {
                             preferred_port,
                             source,
+                            owner: owner.clone(),
                         });
                     }
                     // Emit in case the UI is already listening; the UI also
@@ -405,6 +468,7 @@ pub fn run() {
                         serde_json::json!({
                             "preferredPort": preferred_port,
                             "source": source,
+                            "owner": owner,
                         }),
                     );
                     return;
@@ -420,7 +484,12 @@ pub fn run() {
 
                 let final_port = preferred_port;
                 let public_base_url = crate::commands::gateway::load_public_base_url_from_repo(&settings_repo).await;
-                let url = crate::commands::gateway::advertised_base_url(public_base_url.as_deref(), final_port);
+                let base_url_host = crate::commands::gateway::load_base_url_host_from_repo(&settings_repo).await;
+                let url = crate::commands::gateway::advertised_base_url(
+                    public_base_url.as_deref(),
+                    base_url_host.as_deref(),
+                    final_port,
+                );
                 // Bind all interfaces when the user opted into network access so other
                 // devices on the LAN can reach the gateway; loopback-only otherwise.
                 let network_access =
@@ -428,7 +497,7 @@ pub fn run() {
                 let auth_disabled =
                     crate::commands::gateway::load_gateway_auth_disabled_from_repo(&settings_repo)
                         .await;
-                let local_url = format!("http://localhost:{}", final_port);
+                let local_url = mcpmux_gateway::local_base_url(base_url_host.as_deref(), final_port);
                 info!("Auto-starting gateway on {} (advertising {})", local_url, url);
 
                 // Load JWT signing secret (DPAPI on Windows, keychain elsewhere)
@@ -451,16 +520,11 @@ pub fn run() {
 
                 // Build gateway dependencies using DI builder pattern
                 let mut deps_builder = mcpmux_gateway::DependenciesBuilder::new()
-                    .with_installed_server_repo(installed_server_repo)
-                    .with_credential_repo(credential_repo)
-                    .with_backend_oauth_repo(backend_oauth_repo)
-                    .with_feature_repo(feature_repo)
-                    .with_feature_set_repo(feature_set_repo)
+                    .with_repositories(&repositories)
                     .with_server_discovery(server_discovery)
                     .with_log_manager(server_log_manager)
-                    .with_database(db_for_gateway)
                     .with_state_dir(app_data_dir.clone())
-                    .with_settings_repo(settings_repo);
+                    .with_managed_policy(managed_policy);
 
                 if let Some(secret) = jwt_secret {
                     deps_builder = deps_builder.with_jwt_secret(secret);
@@ -479,7 +543,31 @@ pub fn run() {
                     host: crate::commands::gateway::bind_host_for(network_access).to_string(),
                     port: final_port,
                     public_base_url: public_base_url.clone(),
-                    enable_cors: true,
+                    base_url_host: base_url_host.clone(),
+                    enable_cors: crate::commands::gateway::load_cors_enabled_from_repo(
+                        &settings_repo,
+                    )
+                    .await,
+                    cors: crate::commands::gateway::load_cors_config_from_repo(&settings_repo)
+                        .await,
+                    rate_limits: crate::commands::gateway::load_rate_limits_from_repo(
+                        &settings_repo,
+                    )
+                    .await,
+                    request_limits: crate::commands::gateway::load_request_limits_from_repo(
+                        &settings_repo,
+                    )
+                    .await,
+                    auto_connect_concurrency:
+                        crate::commands::gateway::load_auto_connect_concurrency_from_repo(
+                            &settings_repo,
+                        )
+                        .await,
+                    drain_timeout: crate::commands::gateway::GATEWAY_DRAIN_TIMEOUT,
+                    grpc_port: crate::commands::gateway::load_grpc_port_from_repo(
+                        &settings_repo,
+                    )
+                    .await,
                 };
 
                 // Create self-contained gateway server with DI
@@ -494,11 +582,15 @@ pub fn run() {
                 // Get services from gateway
                 let pool_service = server.pool_service();
                 let feature_service = server.feature_service();
+                let routing_service = server.routing_service();
                 let server_manager_arc = server.server_manager();
                 let event_emitter = server.event_emitter();
                 let grant_service = server.grant_service();
                 let session_roots = server.session_roots();
+                let session_activity = server.session_activity();
+                let package_updates = server.package_updates();
                 let approval_broker = server.approval_broker();
+                let traffic_pause = server.traffic_pause();
 
                 // Wire the approval broker to the desktop event bus so
                 // write meta tools can prompt the React dialog. Without
@@ -544,10 +636,15 @@ pub fn run() {
                 state.gateway_state = Some(gw_inner_state);
                 state.pool_service = Some(pool_service);
                 state.feature_service = Some(feature_service);
+                state.routing_service = Some(routing_service);
                 state.event_emitter = Some(event_emitter);
                 state.grant_service = Some(grant_service);
                 state.approval_broker = Some(approval_broker);
                 state.session_roots = Some(session_roots);
+                state.session_activity = Some(session_activity);
+                state.package_updates = Some(package_updates);
+                traffic_pause.set_paused(state.traffic_paused);
+                state.traffic_pause = Some(traffic_pause);
 
                 info!(
                     "Gateway auto-started successfully on {} - GrantService initialized: {}",
@@ -568,17 +665,21 @@ pub fn run() {
                 ) {
                     warn!("[Gateway] Failed to emit gateway-changed(started): {}", e);
                 }
+                tray::schedule_tray_refresh(&app_handle_for_sm);
             });
 
             app.manage(gateway_state);
             app.manage(server_manager_state);
+            app.manage(services::TransportReloader::new());
 
             // Start file watcher for user space config files (hot-reload)
             {
                 let app_state: tauri::State<'_, AppState> = app.state();
                 let spaces_dir = app_state.spaces_dir().to_path_buf();
                 let installed_repo = app_state.installed_server_repository.clone();
+                let managed_policy = app_state.managed_policy.clone();
                 let app_handle_for_watcher = app.handle().clone();
+                let app_handle_for_issues = app.handle().clone();
 
                 // Use the well-known default space UUID
                 // This is created by the initial migration (001_initial.sql)
@@ -589,7 +690,10 @@ pub fn run() {
                     // Create file watcher with UI event emitter
                     match services::SpaceFileWatcher::new(
                         spaces_dir.clone(),
-                        Arc::new(mcpmux_core::application::UserSpaceSyncService::new(installed_repo)),
+                        Arc::new(
+                            mcpmux_core::application::UserSpaceSyncService::new(installed_repo)
+                                .with_managed_policy(managed_policy),
+                        ),
                         default_space_id,
                         Some(move |space_id: &str, result: &mcpmux_core::application::SyncResult| {
                             // Emit event to refresh UI
@@ -603,6 +707,18 @@ pub fn run() {
                                     warn!("[FileWatcher] Failed to emit event: {}", e);
                                 }
                             }
+                            // Servers whose env or args changed restart on their own
+                            app_handle_for_watcher
+                                .state::<services::TransportReloader>()
+                                .schedule(&app_handle_for_watcher, space_id, &result.updated);
+                        }),
+                        // Files that fail schema validation are reported to the UI
+                        Some(move |event: mcpmux_core::DomainEvent| {
+                            crate::commands::gateway::forward_domain_event(
+                                &app_handle_for_issues,
+                                &event,
+                                None,
+                            );
                         }),
                     ) {
                         Ok(_watcher) => {
@@ -618,6 +734,28 @@ pub fn run() {
                 });
             }
 
+            // Start file watcher for local registry folders (hot-reload)
+            {
+                let app_state: tauri::State<'_, AppState> = app.state();
+                let discovery = app_state.server_discovery.clone();
+                let app_handle_for_registry = app.handle().clone();
+
+                match services::RegistryFolderWatcher::new(discovery.clone(), move || {
+                    if let Err(e) = app_handle_for_registry.emit("registry-updated", ()) {
+                        warn!("[RegistryWatcher] Failed to emit event: {}", e);
+                    }
+                }) {
+                    Ok(watcher) => {
+                        let watcher = Arc::new(watcher);
+                        app.manage(watcher.clone());
+                        tauri::async_runtime::spawn(async move {
+                            watcher.watch(discovery.local_registry_dirs().await);
+                        });
+                    }
+                    Err(e) => warn!("[RegistryWatcher] Failed to start: {}", e),
+                }
+            }
+
             // Start periodic log cleanup task
             {
                 let log_manager = app_state.server_log_manager.clone();
@@ -628,6 +766,15 @@ pub fn run() {
 
                     let settings = AppSettingsService::new(settings_repo_for_cleanup);
 
+                    // Saved log level applies unless RUST_LOG was given
+                    if std::env::var_os("RUST_LOG").is_none() {
+                        if let Some(level) = settings.get_log_level().await {
+                            if let Err(e) = crate::set_log_filter(Some(&level)) {
+                                warn!("[Logs] Ignoring saved log level {:?}: {}", level, e);
+                            }
+                        }
+                    }
+
                     // Run cleanup once at startup
                     let retention_days = settings.get_log_retention_days().await;
                     if retention_days > 0 {
@@ -665,6 +812,148 @@ pub fn run() {
                 });
             }
 
+            // Start trash purge task: servers uninstalled longer ago than
+            // the retention window are deleted for good, at startup and
+            // then daily
+            {
+                let app_service = managed_app_service.clone();
+                let settings_repo_for_trash = app_state.settings_repository.clone();
+
+                tauri::async_runtime::spawn(async move {
+                    use mcpmux_core::AppSettingsService;
+
+                    let settings = AppSettingsService::new(settings_repo_for_trash);
+                    let mut interval =
+                        tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+
+                    loop {
+                        interval.tick().await;
+                        let days = settings.get_trash_retention_days().await;
+                        if let Some(service) = app_service.read().await.as_ref() {
+                            if let Err(e) = service.purge_expired(days).await {
+                                warn!("[Trash] Purging expired servers failed: {}", e);
+                            }
+                        }
+                    }
+                });
+            }
+
+            // Start unused server task: when auto-disable is on, servers
+            // without a successful tool call for the configured number of
+            // days are disabled. First check once the gateway is up, then
+            // daily.
+            {
+                let app_handle = app.handle().clone();
+
+                tauri::async_runtime::spawn(async move {
+                    let day = std::time::Duration::from_secs(24 * 60 * 60);
+                    let mut interval = tokio::time::interval_at(
+                        tokio::time::Instant::now() + std::time::Duration::from_secs(10 * 60),
+                        day,
+                    );
+
+                    loop {
+                        interval.tick().await;
+                        let disabled = commands::disable_unused_servers(&app_handle).await;
+                        if disabled > 0 {
+                            info!("[UnusedServers] Disabled {} unused server(s)", disabled);
+                        }
+                    }
+                });
+            }
+
+            // Start scheduled backup task. Checks hourly so interval changes
+            // apply without a restart; the first backup runs one interval
+            // after launch.
+            {
+                let backup_manager = app_state.backup_manager.clone();
+                let settings_repo_for_backup = app_state.settings_repository.clone();
+
+                tauri::async_runtime::spawn(async move {
+                    use mcpmux_core::AppSettingsService;
+
+                    let settings = AppSettingsService::new(settings_repo_for_backup);
+                    let mut last_backup = std::time::Instant::now();
+                    let mut interval =
+                        tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+                    interval.tick().await;
+
+                    loop {
+                        interval.tick().await;
+                        let hours = settings.get_backup_interval_hours().await;
+                        if hours == 0
+                            || last_backup.elapsed()
+                                < std::time::Duration::from_secs(u64::from(hours) * 60 * 60)
+                        {
+                            continue;
+                        }
+                        last_backup = std::time::Instant::now();
+                        match backup_manager
+                            .create_backup(mcpmux_storage::BackupKind::Scheduled)
+                            .await
+                        {
+                            Ok(info) => debug!("[Backup] Scheduled backup {}", info.file_name),
+                            Err(e) => warn!("[Backup] Scheduled backup failed: {}", e),
+                        }
+                        let keep = settings.get_backup_keep_count().await.max(1) as usize;
+                        if let Err(e) = backup_manager.prune_scheduled(keep) {
+                            warn!("[Backup] Pruning old backups failed: {}", e);
+                        }
+                    }
+                });
+            }
+
+            // Start config sync task. Checks every minute so settings
+            // changes apply without a restart; the first sync runs one
+            // interval after launch.
+            {
+                let config_sync = app_state.config_sync.clone();
+                let settings_repo_for_sync = app_state.settings_repository.clone();
+                let app_handle_for_sync = app.handle().clone();
+
+                tauri::async_runtime::spawn(async move {
+                    use mcpmux_core::AppSettingsService;
+
+                    let settings = AppSettingsService::new(settings_repo_for_sync);
+                    let mut last_sync = std::time::Instant::now();
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+                    interval.tick().await;
+
+                    loop {
+                        interval.tick().await;
+                        let minutes = settings.get_sync_interval_minutes().await;
+                        if minutes == 0
+                            || last_sync.elapsed()
+                                < std::time::Duration::from_secs(u64::from(minutes) * 60)
+                        {
+                            continue;
+                        }
+                        let Some(target_config) = settings.get_sync_target().await else {
+                            continue;
+                        };
+                        last_sync = std::time::Instant::now();
+                        let target = match target_config.build() {
+                            Ok(target) => target,
+                            Err(e) => {
+                                warn!("[Sync] Invalid sync target: {}", e);
+                                continue;
+                            }
+                        };
+                        let strategy = settings.get_sync_conflict_strategy().await;
+                        match config_sync.sync(target.as_ref(), strategy).await {
+                            Ok(report) => {
+                                if let Err(e) =
+                                    app_handle_for_sync.emit("config-sync-completed", &report)
+                                {
+                                    warn!("[Sync] Failed to emit config-sync-completed: {}", e);
+                                }
+                            }
+                            Err(e) => warn!("[Sync] Scheduled sync failed: {}", e),
+                        }
+                    }
+                });
+            }
+
             // Setup system tray
             tray::setup_tray(app.handle())?;
 
@@ -806,8 +1095,8 @@ pub fn run() {
             //
             // Windows console control events (CTRL_CLOSE_EVENT,
             // CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT) give the process
-            // ~5 seconds before force-kill, which is plenty for the
-            // ~2.5s graceful drain downstream.
+            // ~5 seconds before force-kill, which covers the ~4.5s
+            // graceful drain downstream.
             {
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
@@ -889,15 +1178,73 @@ pub fn run() {
             commands::get_registry_ui_config,
             commands::get_registry_home_config,
             commands::is_registry_offline,
+            commands::get_registry_sync_status,
             commands::refresh_registry,
+            commands::list_registry_sources,
+            commands::set_registry_sources,
+            commands::set_registry_auth,
+            commands::list_publisher_keys,
+            commands::set_publisher_keys,
             commands::search_servers,
             // Installed Server commands
             commands::install_server,
+            commands::bulk_install_servers,
             commands::uninstall_server,
+            commands::list_trashed_servers,
+            commands::restore_server,
+            commands::purge_trashed_server,
+            commands::get_server_trash_retention_days,
+            commands::set_server_trash_retention_days,
+            commands::get_unused_servers,
+            commands::get_server_unused_days,
+            commands::set_server_unused_days,
+            commands::get_auto_disable_unused_servers,
+            commands::set_auto_disable_unused_servers,
             commands::list_installed_servers,
             commands::set_server_enabled,
             commands::set_server_oauth_connected,
             commands::save_server_inputs,
+            commands::import_from_client_config,
+            commands::set_server_inherit_env,
+            commands::set_server_fs_allowed_roots,
+            commands::set_server_skip_argument_validation,
+            commands::set_server_timeouts,
+            commands::set_server_tool_hooks,
+            commands::set_server_call_context,
+            commands::set_server_wasm_grants,
+            commands::list_server_groups,
+            commands::create_server_group,
+            commands::update_server_group,
+            commands::delete_server_group,
+            commands::set_server_group_servers,
+            commands::sync_server_group_feature_set,
+            commands::run_server_group_action,
+            commands::list_space_profiles,
+            commands::create_space_profile,
+            commands::update_space_profile,
+            commands::delete_space_profile,
+            commands::set_space_profile,
+            // Credential export/import (machine migration)
+            commands::export_secrets,
+            commands::import_secrets,
+            commands::rotate_master_key,
+            commands::get_key_provider,
+            commands::set_key_provider,
+            // Backup and restore
+            commands::generate_diagnostics_bundle,
+            commands::create_backup,
+            commands::list_backups,
+            commands::delete_backup,
+            commands::restore_backup,
+            commands::get_backup_settings,
+            commands::set_backup_settings,
+            commands::get_managed_runtimes,
+            commands::set_managed_runtimes_enabled,
+            commands::install_managed_runtime,
+            commands::uninstall_managed_runtime,
+            commands::get_sync_settings,
+            commands::set_sync_settings,
+            commands::run_config_sync,
             // FeatureSet commands
             commands::list_feature_sets,
             commands::list_feature_sets_by_space,
@@ -918,7 +1265,10 @@ pub fn run() {
             commands::list_server_features,
             commands::list_server_features_by_server,
             commands::list_server_features_by_type,
+            commands::test_tool_call,
             commands::get_server_feature,
+            commands::set_feature_schema_pinned,
+            commands::accept_feature_schema,
             commands::seed_server_features,
             // Client commands
             commands::list_clients,
@@ -959,16 +1309,51 @@ pub fn run() {
             // Client install commands (one-click IDE setup)
             commands::add_to_vscode,
             commands::add_to_cursor,
+            commands::add_to_windsurf,
+            commands::add_to_zed,
+            commands::get_jetbrains_mcp_config,
+            commands::detect_installed_clients,
+            commands::detect_client_installations,
+            commands::apply_client_config,
+            commands::reapply_client_configs,
+            // Onboarding commands
+            commands::onboarding_detect_clients,
+            commands::onboarding_suggest_servers,
+            commands::onboarding_create_default_grants,
+            commands::onboarding_verify_gateway,
+            // Gateway service commands (run at login without the UI)
+            commands::get_service_status,
+            commands::install_service,
+            commands::uninstall_service,
             // Gateway commands
             commands::get_gateway_status,
+            commands::pause_gateway_traffic,
+            commands::get_session_activity,
+            commands::get_domain_events_since,
             commands::get_gateway_port_settings,
             commands::set_gateway_port,
             commands::reset_gateway_port,
+            commands::get_gateway_cors_enabled,
+            commands::get_gateway_cors_config,
+            commands::set_gateway_cors_enabled,
+            commands::set_gateway_cors_config,
+            commands::get_gateway_rate_limits,
+            commands::set_gateway_rate_limits,
+            commands::get_gateway_rate_limit_policies,
+            commands::set_gateway_rate_limit_policies,
+            commands::get_gateway_rate_limit_rejections,
+            commands::get_gateway_request_limits,
+            commands::set_gateway_request_limits,
+            commands::get_gateway_auto_connect_concurrency,
+            commands::set_gateway_auto_connect_concurrency,
+            commands::get_gateway_grpc_port,
+            commands::set_gateway_grpc_port,
             commands::get_gateway_auth_disabled,
             commands::set_gateway_auth_disabled,
             commands::get_gateway_public_url_settings,
             commands::set_gateway_public_base_url,
             commands::reset_gateway_public_base_url,
+            commands::set_gateway_base_url_host,
             commands::get_gateway_network_access,
             commands::set_gateway_network_access,
             commands::probe_gateway_start,
@@ -982,6 +1367,7 @@ pub fn run() {
             commands::list_connected_servers,
             commands::connect_all_enabled_servers,
             commands::get_pool_stats,
+            commands::get_pool_stats_history,
             commands::refresh_oauth_tokens_on_startup,
             // OAuth commands
             commands::approve_oauth_consent,
@@ -990,11 +1376,13 @@ pub fn run() {
             commands::get_oauth_clients,
             commands::approve_oauth_client,
             commands::update_oauth_client,
+            commands::set_oauth_client_suspended,
             commands::delete_oauth_client,
             commands::register_api_key_client,
             commands::create_client_api_key,
             commands::list_client_api_keys,
             commands::revoke_client_api_key,
+            commands::create_device_pairing,
             commands::open_url,
             // Per-client grants for the rootless fallback path
             commands::get_oauth_client_grants,
@@ -1007,14 +1395,26 @@ pub fn run() {
             commands::start_auth_v2,
             commands::cancel_auth_v2,
             commands::retry_connection,
+            commands::check_server_updates,
+            commands::update_server_package,
             commands::logout_server,
             commands::disconnect_server_v2,
             // Log commands
             commands::get_server_logs,
+            commands::search_server_logs,
+            commands::start_server_log_tail,
+            commands::stop_server_log_tail,
             commands::clear_server_logs,
             commands::get_server_log_file,
+            commands::set_server_traffic_recording,
+            commands::get_server_traffic,
+            commands::clear_server_traffic,
+            commands::get_server_faults,
+            commands::set_server_faults,
             commands::get_log_retention_days,
             commands::set_log_retention_days,
+            commands::get_log_level,
+            commands::set_log_level,
             // App log commands
             get_logs_path,
             open_logs_folder,
@@ -1037,10 +1437,10 @@ pub fn run() {
                 // Windows can leave the TCP socket bound in the kernel —
                 // which is what orphan PID 21408 on :45818 was.
                 //
-                // We block for up to ~2.5s to let the listener close. Any
-                // longer and Windows would kill us with a "process not
-                // responding" dialog. Any shorter and we race with axum's
-                // drain.
+                // We block for up to ~4.5s while in-flight tool calls finish,
+                // sessions close and stdio servers exit. Any longer and
+                // Windows would kill us with a "process not responding"
+                // dialog. Any shorter and stdio children get orphaned.
                 if let Some(gw_state) =
                     app_handle.try_state::<Arc<RwLock<GatewayAppState>>>()
                 {
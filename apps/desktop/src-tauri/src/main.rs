@@ -2,5 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `mcpmux db doctor ...` runs without starting the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("db") {
+        std::process::exit(mcpmux_lib::run_db_command(&args[1..]));
+    }
+
     mcpmux_lib::run()
 }
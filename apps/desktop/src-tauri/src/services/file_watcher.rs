@@ -14,7 +14,7 @@ use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 use mcpmux_core::application::{SyncResult, UserSpaceSyncService};
-use mcpmux_core::InstalledServerRepository;
+use mcpmux_core::{DomainEvent, InstalledServerRepository, InvalidSpaceConfig};
 
 /// File watcher for user space configuration files
 ///
@@ -36,14 +36,18 @@ impl SpaceFileWatcher {
     /// * `sync_service` - Service to sync changes
     /// * `default_space_id` - Default space ID to use for synced servers
     /// * `event_emitter` - Optional callback to emit UI events after sync
-    pub fn new<F>(
+    /// * `issue_reporter` - Optional callback for `SpaceConfigInvalid` events
+    ///   when a file fails schema validation
+    pub fn new<F, G>(
         spaces_dir: PathBuf,
         sync_service: Arc<UserSpaceSyncService>,
         default_space_id: String,
         event_emitter: Option<F>,
+        issue_reporter: Option<G>,
     ) -> Result<Self>
     where
         F: Fn(&str, &SyncResult) + Send + Sync + 'static,
+        G: Fn(DomainEvent) + Send + Sync + 'static,
     {
         // Ensure directory exists
         if !spaces_dir.exists() {
@@ -57,9 +61,10 @@ impl SpaceFileWatcher {
         let sync_clone = sync_service.clone();
         let space_id = default_space_id.clone();
         let emitter = event_emitter.map(Arc::new);
+        let reporter = issue_reporter.map(Arc::new);
 
         tokio::spawn(async move {
-            Self::debounced_handler(rx, sync_clone, space_id, emitter).await;
+            Self::debounced_handler(rx, sync_clone, space_id, emitter, reporter).await;
         });
 
         // Create file watcher
@@ -100,13 +105,15 @@ impl SpaceFileWatcher {
     /// Debounced handler for file changes
     ///
     /// Groups rapid file changes and syncs after a debounce period.
-    async fn debounced_handler<F>(
+    async fn debounced_handler<F, G>(
         mut rx: mpsc::Receiver<PathBuf>,
         sync_service: Arc<UserSpaceSyncService>,
         default_space_id: String,
         event_emitter: Option<Arc<F>>,
+        issue_reporter: Option<Arc<G>>,
     ) where
         F: Fn(&str, &SyncResult) + Send + Sync + 'static,
+        G: Fn(DomainEvent) + Send + Sync + 'static,
     {
         let debounce_duration = Duration::from_millis(500);
         let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
@@ -153,9 +160,23 @@ impl SpaceFileWatcher {
                                     debug!("Sync complete: no changes");
                                 }
                             }
-                            Err(e) => {
-                                error!("Sync failed for {:?}: {}", path, e);
-                            }
+                            Err(e) => match e.downcast_ref::<InvalidSpaceConfig>() {
+                                // The file is mid-edit or has a mistake; the
+                                // servers stay as they were until it's fixed
+                                Some(invalid) => {
+                                    warn!("Not syncing {:?}: {}", path, invalid);
+                                    if let (Some(ref report), Ok(space_uuid)) =
+                                        (&issue_reporter, uuid::Uuid::parse_str(space_id))
+                                    {
+                                        report(DomainEvent::SpaceConfigInvalid {
+                                            space_id: space_uuid,
+                                            file: path.display().to_string(),
+                                            issues: invalid.issues.clone(),
+                                        });
+                                    }
+                                }
+                                None => error!("Sync failed for {:?}: {}", path, e),
+                            },
                         }
                     }
                 }
@@ -198,11 +219,12 @@ impl SpaceFileWatcherBuilder {
     /// Build the file watcher without event emitter
     pub fn build(self) -> Result<SpaceFileWatcher> {
         let sync_service = Arc::new(UserSpaceSyncService::new(self.installed_repo));
-        SpaceFileWatcher::new::<fn(&str, &SyncResult)>(
+        SpaceFileWatcher::new::<fn(&str, &SyncResult), fn(DomainEvent)>(
             self.spaces_dir,
             sync_service,
             self.default_space_id,
             None,
+            None,
         )
     }
 
@@ -217,6 +239,7 @@ impl SpaceFileWatcherBuilder {
             sync_service,
             self.default_space_id,
             Some(emitter),
+            None::<fn(DomainEvent)>,
         )
     }
 }
@@ -3,5 +3,9 @@
 //! Background services for the desktop application.
 
 pub mod file_watcher;
+pub mod registry_watcher;
+pub mod transport_reload;
 
 pub use file_watcher::SpaceFileWatcher;
+pub use registry_watcher::RegistryFolderWatcher;
+pub use transport_reload::TransportReloader;
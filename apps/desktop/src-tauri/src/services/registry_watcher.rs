@@ -0,0 +1,115 @@
+//! File Watcher Service for Local Registries
+//!
+//! Watches the folders of local registries (`file://` registry URLs) and
+//! reloads them when a definition file changes, so a `git pull` of a team
+//! catalog shows up without a restart. Uses debouncing like
+//! [`super::SpaceFileWatcher`], since a checkout touches many files at once.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+use mcpmux_core::ServerDiscoveryService;
+
+/// File watcher for local registry folders
+pub struct RegistryFolderWatcher {
+    watcher: Mutex<RecommendedWatcher>,
+    /// Folders currently watched
+    watched: Mutex<Vec<PathBuf>>,
+}
+
+impl RegistryFolderWatcher {
+    /// Create a watcher that reloads the local registries of `discovery` and
+    /// then calls `on_reload`. Nothing is watched until [`Self::watch`].
+    pub fn new<F>(discovery: Arc<ServerDiscoveryService>, on_reload: F) -> Result<Self>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<()>(100);
+
+        tauri::async_runtime::spawn(async move {
+            Self::debounced_handler(rx, discovery, on_reload).await;
+        });
+
+        let watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+            match res {
+                Ok(event) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+                    ) && event
+                        .paths
+                        .iter()
+                        .any(|p| p.extension().is_some_and(|e| e == "json"))
+                    {
+                        debug!("Local registry change detected: {:?}", event.paths);
+                        // A full channel already has a reload pending
+                        let _ = tx.try_send(());
+                    }
+                }
+                Err(e) => {
+                    error!("Registry folder watcher error: {}", e);
+                }
+            }
+        })?;
+
+        Ok(Self {
+            watcher: Mutex::new(watcher),
+            watched: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Watch exactly `dirs`, e.g. after the configured registries changed.
+    /// A folder that can't be watched is logged and skipped.
+    pub fn watch(&self, dirs: Vec<PathBuf>) {
+        let mut watcher = self.watcher.lock().unwrap_or_else(|e| e.into_inner());
+        let mut watched = self.watched.lock().unwrap_or_else(|e| e.into_inner());
+
+        for dir in watched.iter().filter(|d| !dirs.contains(d)) {
+            if let Err(e) = watcher.unwatch(dir) {
+                debug!("Failed to unwatch {:?}: {}", dir, e);
+            }
+        }
+        let mut now_watched = Vec::new();
+        for dir in dirs {
+            if watched.contains(&dir) {
+                now_watched.push(dir);
+                continue;
+            }
+            match watcher.watch(&dir, RecursiveMode::Recursive) {
+                Ok(()) => {
+                    info!("Watching local registry: {:?}", dir);
+                    now_watched.push(dir);
+                }
+                Err(e) => warn!("Failed to watch local registry {:?}: {}", dir, e),
+            }
+        }
+        *watched = now_watched;
+    }
+
+    /// Reload once changes have been quiet for the debounce period
+    async fn debounced_handler<F>(
+        mut rx: mpsc::Receiver<()>,
+        discovery: Arc<ServerDiscoveryService>,
+        on_reload: F,
+    ) where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let debounce_duration = Duration::from_millis(500);
+
+        while rx.recv().await.is_some() {
+            while let Ok(Some(())) = tokio::time::timeout(debounce_duration, rx.recv()).await {}
+
+            info!("Reloading local registries");
+            match discovery.reload_local_registries().await {
+                Ok(()) => on_reload(),
+                Err(e) => error!("Failed to reload local registries: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Targeted restarts after Space config edits
+//!
+//! A save of a Space config file reaches us twice (the save command and the
+//! file watcher), and editors often save several times in a row. Restart
+//! checks are debounced per server, so a burst of edits restarts a server
+//! once, and only if its resolved transport actually changed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::async_runtime::JoinHandle;
+use tauri::AppHandle;
+
+/// How long a server's config must stay unchanged before it's checked
+const RELOAD_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Debounced restarts of servers whose config was edited
+#[derive(Default)]
+pub struct TransportReloader {
+    /// Pending check per (space_id, server_id)
+    pending: Mutex<HashMap<(String, String), JoinHandle<()>>>,
+}
+
+impl TransportReloader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restart each of `server_ids` that's connected with a different
+    /// transport than its config now resolves to, once edits settle.
+    pub fn schedule(&self, app: &AppHandle, space_id: &str, server_ids: &[String]) {
+        let mut pending = self.pending.lock().unwrap();
+        for server_id in server_ids {
+            let key = (space_id.to_string(), server_id.clone());
+            if let Some(handle) = pending.remove(&key) {
+                handle.abort();
+            }
+
+            let app = app.clone();
+            let (space_id, server_id) = key.clone();
+            let handle = tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(RELOAD_DEBOUNCE).await;
+                crate::commands::server_manager::restart_if_transport_changed(
+                    &app, space_id, server_id,
+                )
+                .await;
+            });
+            pending.insert(key, handle);
+        }
+    }
+}
@@ -3,23 +3,26 @@
 //! This module contains the global application state that is shared
 //! between Tauri commands.
 
+mod unlock;
+
 use mcpmux_core::{
-    AppSettingsRepository, AppSettingsService, CredentialRepository, FeatureSetRepository,
-    GatewayPortService, InboundMcpClientRepository, InstalledServerRepository, LogConfig,
-    OutboundOAuthRepository, ServerDiscoveryService,
-    ServerFeatureRepository as CoreServerFeatureRepository, ServerLogManager,
-    SpaceBaseDirRepository, SpaceBuiltinConfigRepository, SpaceRepository, SpaceService,
-    WorkspaceBindingRepository,
+    AppSettingsRepository, AppSettingsService, ConfigSyncService, CredentialRepository,
+    EnvironmentProfileRepository, EnvironmentProfileService, EventJournalRepository,
+    FeatureSetRepository, GatewayPortService, InboundMcpClientRepository,
+    InstalledServerRepository, LogConfig, ManagedPolicy, OnboardingService,
+    OutboundOAuthRepository, RegistryCredentialService, ServerDiscoveryService,
+    ServerFeatureRepository as CoreServerFeatureRepository, ServerGroupService, ServerLogManager,
+    SpaceBaseDirRepository, SpaceBuiltinConfigRepository, SpaceService, WorkspaceBindingRepository,
 };
 use mcpmux_storage::{
-    Database, FieldEncryptor, SqliteAppSettingsRepository, SqliteCredentialRepository,
-    SqliteFeatureSetRepository, SqliteInboundMcpClientRepository, SqliteInstalledServerRepository,
-    SqliteOutboundOAuthRepository, SqliteServerFeatureRepository, SqliteSpaceBaseDirRepository,
-    SqliteSpaceBuiltinConfigRepository, SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
+    BackupManager, Database, FieldEncryptor, KeyProviderKind, MasterKeyProvider, RepositorySet,
+    ServerFeatureRepository,
 };
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use tracing::info;
 
 /// Global application state accessible from commands.
@@ -34,10 +37,20 @@ pub struct AppState {
     pub gateway_port_service: Arc<GatewayPortService>,
     /// Service for managing spaces
     pub space_service: SpaceService,
+    /// Service for server groups (bulk operations, group FeatureSets)
+    pub server_group_service: ServerGroupService,
+    /// Service for per-Space environment profiles (dev/staging/prod)
+    pub environment_profile_service: EnvironmentProfileService,
     /// Server discovery service for loading servers from API/bundled/user spaces
     pub server_discovery: Arc<ServerDiscoveryService>,
+    /// First-run setup steps
+    pub onboarding_service: OnboardingService,
+    /// Credentials of private registries (stored in the credential repository)
+    pub registry_credential_service: Arc<RegistryCredentialService>,
     /// Server log manager for file-based logging
     pub server_log_manager: Arc<ServerLogManager>,
+    /// Live log tails streaming to the frontend, by tail ID
+    pub log_tails: Mutex<HashMap<String, JoinHandle<()>>>,
     /// Installed server repository (per-space installations)
     pub installed_server_repository: Arc<dyn InstalledServerRepository>,
     /// Credential repository (with encryption)
@@ -54,15 +67,29 @@ pub struct AppState {
     pub space_base_dir_repository: Arc<dyn SpaceBaseDirRepository>,
     /// Per-Space built-in server config (Tool Optimization enablement + tool toggles)
     pub space_builtin_config_repository: Arc<dyn SpaceBuiltinConfigRepository>,
-    /// Server feature repository for discovered MCP features (implements core trait)
-    pub server_feature_repository: Arc<SqliteServerFeatureRepository>,
+    /// Per-Space environment profiles (with encryption), read by the gateway on connect
+    pub environment_profile_repository: Arc<dyn EnvironmentProfileRepository>,
+    /// Journal of domain events, written by the gateway while it runs
+    pub event_journal_repository: Arc<dyn EventJournalRepository>,
+    /// Server feature repository for discovered MCP features
+    pub server_feature_repository: Arc<dyn ServerFeatureRepository>,
     /// Server feature repository cast to core trait (for gateway services)
     pub server_feature_repository_core: Arc<dyn CoreServerFeatureRepository>,
-    /// Field encryptor (used for credential repository creation)
-    #[allow(dead_code)]
+    /// Backup/restore of the database and space configs
+    pub backup_manager: Arc<BackupManager>,
+    /// Config sync between machines
+    pub config_sync: Arc<ConfigSyncService>,
+    /// Master key store (DPAPI / OS keychain / file), used for key rotation
+    pub key_provider: Arc<dyn MasterKeyProvider>,
+    /// Key provider selection active for this run (changes apply on restart)
+    pub key_provider_kind: KeyProviderKind,
+    /// Field encryptor shared by all repositories (rekeyed on rotation)
     pub encryptor: Arc<FieldEncryptor>,
+    /// Organization rules from the machine-wide policy file
+    pub managed_policy: Arc<ManagedPolicy>,
+    /// Every repository, for the gateway
+    pub repositories: RepositorySet,
     /// Shared database connection (kept alive for the app lifetime)
-    #[allow(dead_code)]
     db: Arc<Mutex<Database>>,
 }
 
@@ -72,9 +99,12 @@ impl AppState {
         // Ensure data directory exists
         std::fs::create_dir_all(&data_dir)?;
 
-        // Get or create master key (DPAPI on Windows, OS Keychain elsewhere)
+        // Get or create master key from the selected provider
+        // (default: DPAPI on Windows, OS Keychain elsewhere)
         info!("Retrieving master key...");
-        let key_provider = mcpmux_storage::create_key_provider(&data_dir)?;
+        let key_provider_kind = mcpmux_storage::read_key_provider_kind(&data_dir);
+        let key_provider: Arc<dyn MasterKeyProvider> =
+            Arc::from(unlock::open_key_provider(&data_dir, key_provider_kind)?);
         let master_key = key_provider.get_or_create_key()?;
         info!("Master key retrieved successfully");
 
@@ -85,50 +115,60 @@ impl AppState {
         let db_path = data_dir.join("mcpmux.db");
         info!("Opening database at {:?}", db_path);
 
+        // A restore staged by the previous session replaces the database
+        // before it is opened.
+        if mcpmux_storage::apply_pending_restore(&data_dir)? {
+            info!("Restored database from backup");
+        }
+
         let db = Database::open(&db_path)?;
         let db = Arc::new(Mutex::new(db));
+        let backup_manager = Arc::new(BackupManager::new(data_dir.clone(), db.clone()));
 
-        // Initialize repositories
-        let space_repository: Arc<dyn SpaceRepository> =
-            Arc::new(SqliteSpaceRepository::new(db.clone()));
-
-        let installed_server_repository: Arc<dyn InstalledServerRepository> = Arc::new(
-            SqliteInstalledServerRepository::new(db.clone(), encryptor.clone()),
-        );
-
-        let credential_repository: Arc<dyn CredentialRepository> = Arc::new(
-            SqliteCredentialRepository::new(db.clone(), encryptor.clone()),
-        );
-
-        let backend_oauth_repository: Arc<dyn OutboundOAuthRepository> =
-            Arc::new(SqliteOutboundOAuthRepository::new(db.clone()));
-
-        let feature_set_repository: Arc<dyn FeatureSetRepository> =
-            Arc::new(SqliteFeatureSetRepository::new(db.clone()));
-
-        let client_repository: Arc<dyn InboundMcpClientRepository> =
-            Arc::new(SqliteInboundMcpClientRepository::new(db.clone()));
-
-        let workspace_binding_repository: Arc<dyn WorkspaceBindingRepository> =
-            Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
-
-        let space_base_dir_repository: Arc<dyn SpaceBaseDirRepository> =
-            Arc::new(SqliteSpaceBaseDirRepository::new(db.clone()));
+        // Initialize repositories. The desktop app always keeps its data in
+        // the local SQLite file (backups, restore and key rotation work on it).
+        let repositories = RepositorySet::sqlite(db.clone(), encryptor.clone());
+        let space_repository = repositories.spaces.clone();
+        let installed_server_repository = repositories.installed_servers.clone();
+        let credential_repository = repositories.credentials.clone();
+        let backend_oauth_repository = repositories.outbound_oauth.clone();
+        let feature_set_repository = repositories.feature_sets.clone();
+        let client_repository = repositories.inbound_mcp_clients.clone();
+        let workspace_binding_repository = repositories.workspace_bindings.clone();
+        let space_base_dir_repository = repositories.space_base_dirs.clone();
+        let space_builtin_config_repository = repositories.builtin_configs.clone();
+        let environment_profile_repository = repositories.environment_profiles.clone();
+        let event_journal_repository = repositories.event_journal.clone();
+        let server_feature_repository = repositories.server_features.clone();
+        let server_feature_repository_core = repositories.server_features_core.clone();
 
-        let space_builtin_config_repository: Arc<dyn SpaceBuiltinConfigRepository> =
-            Arc::new(SqliteSpaceBuiltinConfigRepository::new(db.clone()));
-
-        let server_feature_repository = Arc::new(SqliteServerFeatureRepository::new(db.clone()));
-        let server_feature_repository_core: Arc<dyn CoreServerFeatureRepository> =
-            server_feature_repository.clone();
-
-        // Create app settings repository and services
-        let settings_repository: Arc<dyn AppSettingsRepository> =
-            Arc::new(SqliteAppSettingsRepository::new(db.clone()));
+        // Create app settings services
+        let settings_repository = repositories.settings.clone();
         let settings_service = Arc::new(AppSettingsService::new(settings_repository.clone()));
         let gateway_port_service = Arc::new(GatewayPortService::new(settings_repository.clone()));
 
         // Create services
+        let config_sync = Arc::new(ConfigSyncService::new(
+            space_repository.clone(),
+            installed_server_repository.clone(),
+            feature_set_repository.clone(),
+            workspace_binding_repository.clone(),
+            server_feature_repository_core.clone(),
+            data_dir.join("sync"),
+        ));
+
+        let server_group_service = ServerGroupService::new(
+            repositories.server_groups.clone(),
+            installed_server_repository.clone(),
+            feature_set_repository.clone(),
+            server_feature_repository_core.clone(),
+        );
+
+        let environment_profile_service = EnvironmentProfileService::new(
+            environment_profile_repository.clone(),
+            installed_server_repository.clone(),
+        );
+
         let space_service = SpaceService::with_feature_set_repository(
             space_repository,
             feature_set_repository.clone(),
@@ -144,19 +184,25 @@ impl AppState {
             .unwrap_or_else(|_| "https://api.mcpmux.com".to_string());
         info!("Using Registry API URL: {}", registry_url);
 
+        let registry_credential_service = Arc::new(RegistryCredentialService::new(
+            credential_repository.clone(),
+        ));
         let server_discovery = Arc::new(
             ServerDiscoveryService::new(data_dir.clone(), spaces_dir.clone())
                 .with_registry_api(registry_url)
-                .with_settings_service(settings_service),
+                .with_settings_service(settings_service)
+                .with_registry_credentials(registry_credential_service.clone()),
+        );
+
+        let onboarding_service = OnboardingService::new(
+            server_discovery.clone(),
+            installed_server_repository.clone(),
+            feature_set_repository.clone(),
+            server_feature_repository_core.clone(),
         );
 
         // Create server log manager
-        let log_config = LogConfig {
-            base_dir: data_dir.join("logs"),
-            max_file_size: 10 * 1024 * 1024, // 10MB
-            max_files: 30,                   // 30 files
-            compress: true,
-        };
+        let log_config = LogConfig::new(data_dir.join("logs"));
         let server_log_manager = Arc::new(ServerLogManager::new(log_config));
 
         info!("Application state initialized successfully");
@@ -167,8 +213,13 @@ impl AppState {
             settings_repository,
             gateway_port_service,
             space_service,
+            server_group_service,
+            environment_profile_service,
             server_discovery,
+            onboarding_service,
+            registry_credential_service,
             server_log_manager,
+            log_tails: Mutex::new(HashMap::new()),
             installed_server_repository,
             credential_repository,
             backend_oauth_repository,
@@ -177,9 +228,17 @@ impl AppState {
             workspace_binding_repository,
             space_base_dir_repository,
             space_builtin_config_repository,
+            environment_profile_repository,
+            event_journal_repository,
             server_feature_repository,
             server_feature_repository_core,
+            backup_manager,
+            config_sync,
+            key_provider,
+            key_provider_kind,
             encryptor,
+            managed_policy: Arc::new(ManagedPolicy::load()),
+            repositories,
             db,
         })
     }
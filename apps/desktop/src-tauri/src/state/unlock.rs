@@ -0,0 +1,138 @@
+//! Master key unlock for the passphrase key provider.
+//!
+//! Runs before any window exists, so the passphrase is asked for with the
+//! platform's own dialog tools: PowerShell on Windows, `osascript` on macOS,
+//! `zenity` or `kdialog` on Linux. `MCPMUX_MASTER_PASSPHRASE` skips the prompt.
+
+use std::path::Path;
+use std::process::Command;
+
+use mcpmux_storage::{
+    KeyProviderKind, MasterKeyProvider, PassphraseKeyProvider, PASSPHRASE_ENV_VAR,
+};
+use tracing::warn;
+use zeroize::Zeroizing;
+
+/// Wrong passphrases allowed before startup gives up.
+const MAX_ATTEMPTS: usize = 3;
+
+/// Environment variable carrying the prompt text to the dialog process.
+const PROMPT_ENV_VAR: &str = "MCPMUX_PROMPT";
+
+/// Create the key provider of `kind`, asking for the passphrase when needed.
+pub fn open_key_provider(
+    data_dir: &Path,
+    kind: KeyProviderKind,
+) -> anyhow::Result<Box<dyn MasterKeyProvider>> {
+    if kind != KeyProviderKind::Passphrase || std::env::var_os(PASSPHRASE_ENV_VAR).is_some() {
+        return mcpmux_storage::create_key_provider_of_kind(data_dir, kind);
+    }
+
+    let creating = !PassphraseKeyProvider::key_exists_in(data_dir);
+    let mut message = if creating {
+        "Choose a passphrase to protect McpMux's master key".to_string()
+    } else {
+        "Enter the passphrase to unlock McpMux".to_string()
+    };
+
+    for _ in 0..MAX_ATTEMPTS {
+        let passphrase = prompt_passphrase(&message)?
+            .ok_or_else(|| anyhow::anyhow!("Master key passphrase was not entered"))?;
+
+        if creating {
+            let confirmation = prompt_passphrase("Enter the passphrase again")?
+                .ok_or_else(|| anyhow::anyhow!("Master key passphrase was not entered"))?;
+            if *confirmation != *passphrase {
+                message = "Passphrases did not match. Choose a passphrase".to_string();
+                continue;
+            }
+        }
+
+        let provider = match PassphraseKeyProvider::new(data_dir, passphrase.as_str()) {
+            Ok(provider) => provider,
+            Err(e) => {
+                message = format!("{e}. Try again");
+                continue;
+            }
+        };
+        match provider.get_or_create_key() {
+            Ok(_) => return Ok(Box::new(provider)),
+            Err(e) => {
+                warn!("Failed to unlock master key: {}", e);
+                message = "Wrong passphrase. Enter the passphrase to unlock McpMux".to_string();
+            }
+        }
+    }
+    anyhow::bail!("Master key not unlocked after {} attempts", MAX_ATTEMPTS)
+}
+
+/// Show a masked input dialog. `None` when the user cancels.
+///
+/// Windows and macOS read the message from [`PROMPT_ENV_VAR`] so it needs no
+/// script quoting.
+fn prompt_passphrase(message: &str) -> anyhow::Result<Option<Zeroizing<String>>> {
+    for mut command in dialog_commands(message) {
+        command.env(PROMPT_ENV_VAR, message);
+        let output = match command.output() {
+            Ok(output) => output,
+            // Tool not installed; try the next one
+            Err(_) => continue,
+        };
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let mut text = Zeroizing::new(String::from_utf8(output.stdout)?);
+        while text.ends_with(['\n', '\r']) {
+            text.pop();
+        }
+        return Ok(Some(text));
+    }
+    anyhow::bail!(
+        "No dialog tool available to ask for the master key passphrase; set {}",
+        PASSPHRASE_ENV_VAR
+    )
+}
+
+#[cfg(windows)]
+fn dialog_commands(_message: &str) -> Vec<Command> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    const SCRIPT: &str = r#"
+Add-Type -AssemblyName System.Windows.Forms
+$f = New-Object Windows.Forms.Form -Property @{ Text = 'McpMux'; Width = 380; Height = 160; StartPosition = 'CenterScreen'; TopMost = $true; FormBorderStyle = 'FixedDialog'; MaximizeBox = $false; MinimizeBox = $false }
+$l = New-Object Windows.Forms.Label -Property @{ Text = $env:MCPMUX_PROMPT; Left = 10; Top = 10; Width = 350 }
+$t = New-Object Windows.Forms.TextBox -Property @{ Left = 10; Top = 40; Width = 340; UseSystemPasswordChar = $true }
+$b = New-Object Windows.Forms.Button -Property @{ Text = 'Unlock'; Left = 270; Top = 75; DialogResult = 'OK' }
+$f.AcceptButton = $b
+$f.Controls.AddRange(@($l, $t, $b))
+if ($f.ShowDialog() -eq 'OK') { [Console]::Out.Write($t.Text) } else { exit 1 }
+"#;
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .creation_flags(CREATE_NO_WINDOW);
+    vec![command]
+}
+
+#[cfg(target_os = "macos")]
+fn dialog_commands(_message: &str) -> Vec<Command> {
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        r#"display dialog (system attribute "MCPMUX_PROMPT") default answer "" with hidden answer with title "McpMux" buttons {"Cancel", "Unlock"} default button "Unlock""#,
+        "-e",
+        "text returned of result",
+    ]);
+    vec![command]
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn dialog_commands(message: &str) -> Vec<Command> {
+    let mut zenity = Command::new("zenity");
+    zenity
+        .args(["--entry", "--hide-text", "--title=McpMux"])
+        .arg(format!("--text={message}"));
+    let mut kdialog = Command::new("kdialog");
+    kdialog.args(["--title", "McpMux", "--password", message]);
+    vec![zenity, kdialog]
+}
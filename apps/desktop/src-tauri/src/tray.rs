@@ -2,48 +2,124 @@
 //!
 //! Provides a system tray icon with quick access to:
 //! - Space switching
+//! - Server status and quick controls (enable/disable/reconnect)
+//! - Pausing and resuming all tool calls
 //! - Open main window
+//!
+//! The icon and tooltip reflect the gateway's health: a dot on the icon when a
+//! server failed or needs sign-in, a greyed-out icon while the gateway is
+//! stopped.
 //! - Quit application
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use mcpmux_core::{ConnectionStatus, DomainEvent, InstalledServer, Space};
 use tauri::{
     image::Image,
-    menu::{Menu, MenuBuilder, SubmenuBuilder},
+    menu::{Menu, MenuBuilder, Submenu, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, Runtime,
 };
-use tracing::{debug, info};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
 
+use crate::commands::gateway::{set_traffic_paused, GatewayAppState};
+use crate::commands::server_manager::{
+    disable_server_v2, enable_server_v2, retry_connection, ServerManagerState,
+};
 use crate::state::AppState;
 
+/// Set while a menu rebuild is scheduled, so a burst of events (every
+/// server connecting at startup) rebuilds the menu once
+static REFRESH_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// How long a scheduled rebuild waits for more changes
+const REFRESH_DELAY: Duration = Duration::from_millis(250);
+
 /// Tray icon status
-#[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrayStatus {
     /// All systems healthy
     Healthy,
-    /// Some warnings present
+    /// A server is waiting for the user to sign in (OAuth), or tool calls
+    /// are paused
     Warning,
-    /// Errors present
+    /// A server failed to connect
     Error,
-    /// Offline or disabled
+    /// Gateway stopped
     Offline,
 }
 
+/// How many enabled servers are in each state, across all Spaces
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ServerCounts {
+    connected: usize,
+    connecting: usize,
+    needs_auth: usize,
+    errors: usize,
+    disconnected: usize,
+}
+
+impl ServerCounts {
+    fn add(&mut self, status: ConnectionStatus) {
+        match status {
+            ConnectionStatus::Connected => self.connected += 1,
+            ConnectionStatus::Connecting
+            | ConnectionStatus::Refreshing
+            | ConnectionStatus::Authenticating => self.connecting += 1,
+            ConnectionStatus::OAuthRequired => self.needs_auth += 1,
+            ConnectionStatus::Error => self.errors += 1,
+            ConnectionStatus::Disconnected => self.disconnected += 1,
+        }
+    }
+
+    /// Errors outrank sign-in requests; nothing matters while stopped
+    fn status(&self, gateway_running: bool) -> TrayStatus {
+        if !gateway_running {
+            TrayStatus::Offline
+        } else if self.errors > 0 {
+            TrayStatus::Error
+        } else if self.needs_auth > 0 {
+            TrayStatus::Warning
+        } else {
+            TrayStatus::Healthy
+        }
+    }
+
+    /// e.g. "McpMux - 4 connected, 1 error, 1 needs sign-in"
+    fn tooltip(&self, gateway_running: bool) -> String {
+        if !gateway_running {
+            return "McpMux - Gateway stopped".to_string();
+        }
+        let parts: Vec<String> = [
+            (self.connected, "connected", "connected"),
+            (self.connecting, "connecting", "connecting"),
+            (self.needs_auth, "needs sign-in", "need sign-in"),
+            (self.errors, "error", "errors"),
+            (self.disconnected, "disconnected", "disconnected"),
+        ]
+        .into_iter()
+        .filter(|(count, ..)| *count > 0)
+        .map(|(count, one, many)| format!("{} {}", count, if count == 1 { one } else { many }))
+        .collect();
+        if parts.is_empty() {
+            "McpMux - No servers enabled".to_string()
+        } else {
+            format!("McpMux - {}", parts.join(", "))
+        }
+    }
+}
+
 /// Build the system tray for the application
 pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     info!("Setting up system tray...");
 
     let menu = build_tray_menu(app)?;
 
-    // Load tray icon - decode PNG and convert to RGBA
-    let icon_bytes = include_bytes!("../icons/32x32.png");
-    let img = image::load_from_memory(icon_bytes)
-        .map_err(|e| {
-            tauri::Error::InvalidIcon(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
-        })?
-        .to_rgba8();
-    let (width, height) = img.dimensions();
-    let icon = Image::new_owned(img.into_raw(), width, height);
+    let icon = tray_icon(TrayStatus::Healthy)?;
 
     let _tray = TrayIconBuilder::with_id("mcpmux-tray")
         .tooltip("McpMux - MCP Server Manager")
@@ -70,6 +146,9 @@ pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         })
         .build(app)?;
 
+    // Fill in the real spaces and servers
+    schedule_tray_refresh(app);
+
     info!("System tray initialized");
     Ok(())
 }
@@ -81,16 +160,34 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
     let space_submenu = SubmenuBuilder::new(app, "Switch Space")
         .text("space_default", "🌐 Default")
         .build()?;
+    let servers_submenu = SubmenuBuilder::new(app, "Servers")
+        .text("servers_loading", "Loading…")
+        .build()?;
 
-    let menu = MenuBuilder::new(app)
-        .item(&space_submenu)
+    assemble_menu(app, &space_submenu, &servers_submenu, false)
+}
+
+/// The tray menu around its two dynamic submenus
+fn assemble_menu<R: Runtime>(
+    app: &AppHandle<R>,
+    space_submenu: &Submenu<R>,
+    servers_submenu: &Submenu<R>,
+    traffic_paused: bool,
+) -> tauri::Result<Menu<R>> {
+    let pause_label = if traffic_paused {
+        "▶ Resume Tool Calls"
+    } else {
+        "⏸ Pause Tool Calls"
+    };
+    MenuBuilder::new(app)
+        .item(space_submenu)
+        .item(servers_submenu)
+        .text("traffic_toggle", pause_label)
         .separator()
         .text("open", "Open McpMux")
         .separator()
         .text("quit", "Quit")
-        .build()?;
-
-    Ok(menu)
+        .build()
 }
 
 /// Handle menu events
@@ -103,6 +200,9 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, event_id: &str) {
             let space_id = id.strip_prefix("space_").unwrap_or("default");
             handle_switch_space(app, space_id);
         }
+        // Server quick controls
+        id if id.starts_with("server:") => handle_server_action(app, id),
+        "traffic_toggle" => handle_traffic_toggle(app),
         "open" => {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
@@ -131,18 +231,134 @@ fn handle_switch_space<R: Runtime>(app: &AppHandle<R>, space_id: &str) {
     let _ = app.emit("tray:switch-space", space_id);
 }
 
-/// Update tray menu with current spaces
-pub async fn update_tray_spaces<R: Runtime>(
+/// Run a quick action picked from a server's submenu. Menu IDs are
+/// `server:<action>:<space_id>:<server_id>`.
+fn handle_server_action<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
+    let mut parts = menu_id.splitn(4, ':').skip(1);
+    let (Some(action), Some(space_id), Some(server_id)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        debug!("Malformed server menu event: {}", menu_id);
+        return;
+    };
+    info!(
+        "Tray: {} server {} in space {}",
+        action, server_id, space_id
+    );
+
+    let action = action.to_string();
+    let space_id = space_id.to_string();
+    let server_id = server_id.to_string();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match action.as_str() {
+            "enable" => {
+                enable_server_v2(
+                    space_id,
+                    server_id.clone(),
+                    app.state(),
+                    app.state(),
+                    app.state(),
+                )
+                .await
+            }
+            "disable" => {
+                disable_server_v2(
+                    space_id,
+                    server_id.clone(),
+                    app.state(),
+                    app.state(),
+                    app.state(),
+                )
+                .await
+            }
+            "reconnect" => {
+                retry_connection(
+                    space_id,
+                    server_id.clone(),
+                    app.state(),
+                    app.state(),
+                    app.state(),
+                )
+                .await
+            }
+            _ => {
+                debug!("Unknown server action: {}", action);
+                return;
+            }
+        };
+        if let Err(e) = result {
+            warn!("Tray: failed to {} server {}: {}", action, server_id, e);
+        }
+        // Enabling/disabling changes the menu even if no status event follows
+        schedule_tray_refresh(&app);
+    });
+}
+
+/// Flip the pause switch for all tool calls
+fn handle_traffic_toggle<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(gateway_state) = app.try_state::<Arc<RwLock<GatewayAppState>>>() else {
+            return;
+        };
+        let paused = !gateway_state.read().await.traffic_paused;
+        set_traffic_paused(&app, &gateway_state, paused).await;
+    });
+}
+
+/// Whether an event changes what the tray menu shows
+pub fn affects_tray_menu(event: &DomainEvent) -> bool {
+    matches!(
+        event,
+        DomainEvent::SpaceCreated { .. }
+            | DomainEvent::SpaceUpdated { .. }
+            | DomainEvent::SpaceDeleted { .. }
+            | DomainEvent::ServerInstalled { .. }
+            | DomainEvent::ServerUninstalled { .. }
+            | DomainEvent::ServerEnabled { .. }
+            | DomainEvent::ServerDisabled { .. }
+            | DomainEvent::ServerStatusChanged { .. }
+    )
+}
+
+/// Rebuild the tray menu shortly, folding in any changes made meanwhile
+pub fn schedule_tray_refresh<R: Runtime>(app: &AppHandle<R>) {
+    if REFRESH_PENDING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(REFRESH_DELAY).await;
+        REFRESH_PENDING.store(false, Ordering::Release);
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        if let Err(e) = update_tray_menu(&app, &state).await {
+            warn!("Failed to update tray menu: {}", e);
+        }
+    });
+}
+
+/// Update tray menu with current spaces and servers
+pub async fn update_tray_menu<R: Runtime>(
     app: &AppHandle<R>,
     state: &AppState,
 ) -> tauri::Result<()> {
     let spaces = state.space_service.list().await.unwrap_or_default();
     let default_space = state.space_service.get_default().await.ok().flatten();
+    let (gateway_running, traffic_paused) = match app.try_state::<Arc<RwLock<GatewayAppState>>>() {
+        Some(gateway_state) => {
+            let gateway_state = gateway_state.read().await;
+            (gateway_state.running, gateway_state.traffic_paused)
+        }
+        None => (false, false),
+    };
 
     if let Some(tray) = app.tray_by_id("mcpmux-tray") {
         let mut space_menu = SubmenuBuilder::new(app, "Switch Space");
 
-        for space in spaces {
+        for space in &spaces {
             let icon = space.icon.clone().unwrap_or_else(|| "🌐".to_string());
             // Tag the system default Space so the user can tell which one
             // catches sessions whose reported root has no binding.
@@ -157,32 +373,222 @@ pub async fn update_tray_spaces<R: Runtime>(
         }
 
         let space_submenu = space_menu.build()?;
+        let (servers_submenu, counts) = build_servers_submenu(app, state, &spaces).await?;
 
-        let menu = MenuBuilder::new(app)
-            .item(&space_submenu)
-            .separator()
-            .text("open", "Open McpMux")
-            .separator()
-            .text("quit", "Quit")
-            .build()?;
-
+        let menu = assemble_menu(app, &space_submenu, &servers_submenu, traffic_paused)?;
         tray.set_menu(Some(menu))?;
+
+        if gateway_running && traffic_paused {
+            update_tray_status(app, TrayStatus::Warning, "McpMux - Tool calls paused")?;
+        } else {
+            update_tray_status(
+                app,
+                counts.status(gateway_running),
+                &counts.tooltip(gateway_running),
+            )?;
+        }
     }
 
     Ok(())
 }
 
-/// Update tray icon based on status
-#[allow(dead_code)]
-pub fn update_tray_status<R: Runtime>(app: &AppHandle<R>, status: TrayStatus) -> tauri::Result<()> {
-    if let Some(tray) = app.tray_by_id("mcpmux-tray") {
-        let tooltip = match status {
-            TrayStatus::Healthy => "McpMux - All systems healthy",
-            TrayStatus::Warning => "McpMux - Some warnings",
-            TrayStatus::Error => "McpMux - Errors present",
-            TrayStatus::Offline => "McpMux - Offline",
+/// The "Servers" submenu: every installed server with a status dot and its
+/// quick actions, grouped by Space when there is more than one. Also counts
+/// the enabled servers by status.
+async fn build_servers_submenu<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    spaces: &[Space],
+) -> tauri::Result<(Submenu<R>, ServerCounts)> {
+    let manager = match app.try_state::<Arc<RwLock<ServerManagerState>>>() {
+        Some(manager_state) => manager_state.read().await.manager.clone(),
+        None => None,
+    };
+
+    let mut servers_menu = SubmenuBuilder::new(app, "Servers");
+    let mut counts = ServerCounts::default();
+    let mut any = false;
+    for space in spaces {
+        let space_id = space.id.to_string();
+        let servers = state
+            .installed_server_repository
+            .list_for_space(&space_id)
+            .await
+            .unwrap_or_default();
+        if servers.is_empty() {
+            continue;
+        }
+        any = true;
+        let statuses: HashMap<String, ConnectionStatus> = match manager {
+            Some(ref manager) => manager
+                .get_all_statuses(space.id)
+                .await
+                .into_iter()
+                .map(|(server_id, (status, ..))| (server_id, status))
+                .collect(),
+            None => HashMap::new(),
         };
+        for server in servers.iter().filter(|s| s.enabled) {
+            counts.add(statuses.get(&server.server_id).copied().unwrap_or_default());
+        }
+
+        if spaces.len() == 1 {
+            for server in &servers {
+                let status = statuses.get(&server.server_id).copied();
+                servers_menu = servers_menu.item(&server_submenu(app, server, status)?);
+            }
+        } else {
+            let icon = space.icon.as_deref().unwrap_or("🌐");
+            let mut space_menu = SubmenuBuilder::new(app, format!("{} {}", icon, space.name));
+            for server in &servers {
+                let status = statuses.get(&server.server_id).copied();
+                space_menu = space_menu.item(&server_submenu(app, server, status)?);
+            }
+            servers_menu = servers_menu.item(&space_menu.build()?);
+        }
+    }
+    if !any {
+        servers_menu = servers_menu.text("servers_none", "No servers installed");
+    }
+    Ok((servers_menu.build()?, counts))
+}
+
+/// One server's entry: its status and name, with the actions that apply
+fn server_submenu<R: Runtime>(
+    app: &AppHandle<R>,
+    server: &InstalledServer,
+    status: Option<ConnectionStatus>,
+) -> tauri::Result<Submenu<R>> {
+    let label = format!(
+        "{} {}",
+        status_dot(server.enabled, status),
+        server.display_name()
+    );
+    let id = |action: &str| format!("server:{}:{}:{}", action, server.space_id, server.server_id);
+    let menu = SubmenuBuilder::new(app, label);
+    let menu = if server.enabled {
+        menu.text(id("disable"), "Disable")
+            .text(id("reconnect"), "Reconnect")
+    } else {
+        menu.text(id("enable"), "Enable")
+    };
+    menu.build()
+}
+
+/// Dot shown before a server's name in the tray
+fn status_dot(enabled: bool, status: Option<ConnectionStatus>) -> &'static str {
+    if !enabled {
+        return "⚫";
+    }
+    match status.unwrap_or_default() {
+        ConnectionStatus::Connected => "🟢",
+        ConnectionStatus::Connecting
+        | ConnectionStatus::Refreshing
+        | ConnectionStatus::Authenticating => "🟡",
+        ConnectionStatus::OAuthRequired => "🟠",
+        ConnectionStatus::Error => "🔴",
+        ConnectionStatus::Disconnected => "⚪",
+    }
+}
+
+/// Update tray icon and tooltip based on status
+pub fn update_tray_status<R: Runtime>(
+    app: &AppHandle<R>,
+    status: TrayStatus,
+    tooltip: &str,
+) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id("mcpmux-tray") {
+        tray.set_icon(Some(tray_icon(status)?))?;
         tray.set_tooltip(Some(tooltip))?;
     }
     Ok(())
 }
+
+/// The app icon, decoded once
+fn base_icon() -> tauri::Result<&'static image::RgbaImage> {
+    static ICON: OnceLock<image::RgbaImage> = OnceLock::new();
+    if let Some(icon) = ICON.get() {
+        return Ok(icon);
+    }
+    let icon_bytes = include_bytes!("../icons/32x32.png");
+    let img = image::load_from_memory(icon_bytes)
+        .map_err(|e| {
+            tauri::Error::InvalidIcon(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })?
+        .to_rgba8();
+    Ok(ICON.get_or_init(|| img))
+}
+
+/// The tray icon for a status: the app icon, with a coloured dot in the
+/// bottom-right corner for warnings and errors, greyed out when offline
+fn tray_icon(status: TrayStatus) -> tauri::Result<Image<'static>> {
+    let mut img = base_icon()?.clone();
+    match status {
+        TrayStatus::Healthy => {}
+        TrayStatus::Warning => draw_badge(&mut img, [245, 158, 11]),
+        TrayStatus::Error => draw_badge(&mut img, [239, 68, 68]),
+        TrayStatus::Offline => {
+            for pixel in img.pixels_mut() {
+                let [r, g, b, a] = pixel.0;
+                let luma = ((r as u32 * 30 + g as u32 * 59 + b as u32 * 11) / 100) as u8;
+                pixel.0 = [luma, luma, luma, a / 2 + a / 4];
+            }
+        }
+    }
+    let (width, height) = img.dimensions();
+    Ok(Image::new_owned(img.into_raw(), width, height))
+}
+
+/// Paint a filled dot with a white ring over the bottom-right corner
+fn draw_badge(img: &mut image::RgbaImage, [r, g, b]: [u8; 3]) {
+    let (width, height) = img.dimensions();
+    let radius = width.min(height) as f32 * 0.22;
+    let ring = (radius * 0.25).max(1.0);
+    let cx = width as f32 - radius - ring;
+    let cy = height as f32 - radius - ring;
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let dx = x as f32 + 0.5 - cx;
+        let dy = y as f32 + 0.5 - cy;
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance <= radius {
+            pixel.0 = [r, g, b, 255];
+        } else if distance <= radius + ring {
+            pixel.0 = [255, 255, 255, 255];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_and_tooltip_summarize_server_counts() {
+        let mut counts = ServerCounts::default();
+        assert_eq!(counts.status(true), TrayStatus::Healthy);
+        assert_eq!(counts.tooltip(true), "McpMux - No servers enabled");
+
+        for status in [
+            ConnectionStatus::Connected,
+            ConnectionStatus::Connected,
+            ConnectionStatus::OAuthRequired,
+        ] {
+            counts.add(status);
+        }
+        assert_eq!(counts.status(true), TrayStatus::Warning);
+        assert_eq!(
+            counts.tooltip(true),
+            "McpMux - 2 connected, 1 needs sign-in"
+        );
+
+        counts.add(ConnectionStatus::Error);
+        assert_eq!(counts.status(true), TrayStatus::Error);
+        assert_eq!(
+            counts.tooltip(true),
+            "McpMux - 2 connected, 1 needs sign-in, 1 error"
+        );
+
+        assert_eq!(counts.status(false), TrayStatus::Offline);
+        assert_eq!(counts.tooltip(false), "McpMux - Gateway stopped");
+    }
+}
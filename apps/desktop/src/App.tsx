@@ -31,6 +31,7 @@ import { WorkspacesPage } from '@/features/workspaces';
 import { SettingsPage } from '@/features/settings';
 import { BuiltinServersPage } from '@/features/builtinServers';
 import { AutoStartConflictResolver } from '@/features/gateway/AutoStartConflictResolver';
+import { GatewayPortChangedPrompt } from '@/features/gateway/GatewayPortChangedPrompt';
 import { WorkspaceBindingSheet } from '@/features/workspaces';
 import { MetaToolApprovalDialog } from '@/features/metaTools';
 import { useGatewayEvents } from '@/hooks/useDomainEvents';
@@ -390,6 +391,8 @@ function App() {
       <AppContent />
       {/* Resolves deferred auto-start port conflicts — runs once on mount */}
       <AutoStartConflictResolver />
+      {/* Offers to update client configs when the gateway changed port */}
+      <GatewayPortChangedPrompt />
       {/* OAuth consent modal - shown when MCP clients request authorization */}
       <OAuthConsentModal />
       {/* Workspace binding sheet - slides in when a session reports a root
@@ -5,6 +5,8 @@ import { refreshRegistry } from '@/lib/api/registry';
 import Editor, { type Monaco } from '@monaco-editor/react';
 import type { editor } from 'monaco-editor';
 import { useToast, ToastContainer } from '@mcpmux/ui';
+import { useDomainEvents } from '@/hooks/useDomainEvents';
+import type { SpaceConfigInvalidPayload } from '@/hooks/useDomainEvents';
 import USER_SPACE_CONFIG_SCHEMA from '../../../../schemas/user-space.schema.json';
 import { RequestServerCTA } from './Contribute';
 
@@ -71,6 +73,7 @@ export function ConfigEditorModal({
   const editorRef = useRef<editor.IStandaloneCodeEditor | null>(null);
   const monacoRef = useRef<Monaco | null>(null);
   const { toasts, success, error: showError } = useToast();
+  const { subscribe } = useDomainEvents();
 
   // Delay editor mount to avoid glitch during modal open
   useEffect(() => {
@@ -103,6 +106,30 @@ export function ConfigEditorModal({
     }
   };
 
+  // Issues the backend found when saving (or syncing) this Space's file,
+  // marked on their lines alongside Monaco's own schema diagnostics
+  useEffect(() => {
+    return subscribe('space-config-invalid', (payload: SpaceConfigInvalidPayload) => {
+      const model = editorRef.current?.getModel();
+      const monaco = monacoRef.current;
+      if (payload.space_id !== spaceId || !model || !monaco) {
+        return;
+      }
+      monaco.editor.setModelMarkers(
+        model,
+        'mcpmux',
+        payload.issues.map((issue) => ({
+          severity: monaco.MarkerSeverity.Error,
+          message: issue.path ? `${issue.path} ${issue.message}` : issue.message,
+          startLineNumber: issue.line,
+          startColumn: issue.column,
+          endLineNumber: issue.line,
+          endColumn: model.getLineMaxColumn(Math.min(issue.line, model.getLineCount())),
+        }))
+      );
+    });
+  }, [spaceId, subscribe]);
+
   const handleSave = async () => {
     try {
       // Validate JSON
@@ -189,6 +216,11 @@ export function ConfigEditorModal({
   const handleContentChange = (newValue: string | undefined) => {
     if (newValue !== undefined) {
       setContent(newValue);
+      // Backend issues refer to the saved content; drop them on edit
+      const model = editorRef.current?.getModel();
+      if (model && monacoRef.current) {
+        monacoRef.current.editor.setModelMarkers(model, 'mcpmux', []);
+      }
       // Clear any manual errors when content changes
       if (error && (error.startsWith('Invalid JSON') || error.startsWith('Cannot format'))) {
         setError(null);
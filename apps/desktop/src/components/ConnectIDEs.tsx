@@ -5,11 +5,18 @@ import cursorIcon from '@/assets/client-icons/cursor.svg';
 import vscodeIcon from '@/assets/client-icons/vscode.png';
 import claudeIcon from '@/assets/client-icons/claude.svg';
 import windsurfIcon from '@/assets/client-icons/windsurf.svg';
+import zedIcon from '@/assets/client-icons/zed.svg';
 import jetbrainsIcon from '@/assets/client-icons/jetbrains.svg';
 import androidStudioIcon from '@/assets/client-icons/android-studio.svg';
 import opencodeIcon from '@/assets/client-icons/opencode.svg';
 import opencodeIconDark from '@/assets/client-icons/opencode-dark.svg';
-import { addToVscode, addToCursor } from '@/lib/api/clientInstall';
+import {
+  addToVscode,
+  addToCursor,
+  addToWindsurf,
+  addToZed,
+  detectInstalledClients,
+} from '@/lib/api/clientInstall';
 import { ClientBrandIcon } from './ClientBrandIcon';
 
 type GridAction = 'deep_link' | 'copy_command' | 'copy_config';
@@ -45,6 +52,9 @@ interface ConnectIDEsGridProps {
 export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridProps) {
   const [activeId, setActiveId] = useState<string | null>(null);
   const [copiedId, setCopiedId] = useState<string | null>(null);
+  const [installError, setInstallError] = useState<string | null>(null);
+  // Keyed by entry id; clients we can't detect (Claude Code, opencode) stay unset
+  const [installed, setInstalled] = useState<Record<string, boolean>>({});
   const popoverRef = useRef<HTMLDivElement>(null);
 
   const mcpUrl = `${gatewayUrl}/mcp`;
@@ -80,13 +90,29 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
       name: 'Windsurf',
       label: 'Windsurf',
       icon: windsurfIcon,
-      action: 'copy_config',
-      handler: `"mcpmux": {\n  "serverUrl": "${mcpUrl}"\n}`,
+      action: 'deep_link',
+      handler: async () => {
+        await addToWindsurf(gatewayUrl);
+      },
       nextStep:
-        'Copies a JSON snippet. In Windsurf, open Cascade → MCP settings, ' +
-        'paste mcpmux under mcpServers, and hit "Refresh" (or reload Windsurf). ' +
+        'Adds mcpmux to ~/.codeium/windsurf/mcp_config.json. In Windsurf, open ' +
+        'Cascade → MCP settings and hit "Refresh" (or reload Windsurf). ' +
         'Approve on this page when Windsurf reaches the gateway.',
     },
+    {
+      id: 'zed',
+      name: 'Zed',
+      label: 'Zed',
+      icon: zedIcon,
+      action: 'deep_link',
+      handler: async () => {
+        await addToZed(gatewayUrl);
+      },
+      nextStep:
+        'Adds mcpmux under context_servers in Zed’s settings.json; Zed picks it ' +
+        'up without a restart. If your settings.json has comments, export the ' +
+        'Zed config instead and paste it in. Approve on this page when it connects.',
+    },
     {
       id: 'claude-code',
       name: 'Claude Code',
@@ -118,9 +144,10 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
       label: 'JetBrains',
       icon: jetbrainsIcon,
       action: 'copy_config',
-      handler: `"mcpmux": {\n  "url": "${mcpUrl}"\n}`,
+      handler: `{\n  "mcpServers": {\n    "mcpmux": {\n      "url": "${mcpUrl}"\n    }\n  }\n}`,
       nextStep:
-        'Copies a JSON snippet. Paste into the AI Assistant MCP config, then ' +
+        'Copies the MCP config. In Settings → Tools → AI Assistant → Model Context ' +
+        'Protocol, click Add → As JSON and paste it, then ' +
         'restart the IDE — JetBrains only reads MCP config on startup. Approve ' +
         'on this page.',
     },
@@ -147,6 +174,14 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
     },
   ];
 
+  useEffect(() => {
+    detectInstalledClients()
+      .then((detections) =>
+        setInstalled(Object.fromEntries(detections.map((d) => [d.client, d.installed])))
+      )
+      .catch(() => setInstalled({}));
+  }, []);
+
   // Close popover on outside click
   useEffect(() => {
     if (!activeId) return;
@@ -161,7 +196,12 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
 
   const handleDeepLink = async (entry: GridEntry) => {
     if (typeof entry.handler === 'function') {
-      await entry.handler();
+      try {
+        await entry.handler();
+      } catch (e) {
+        setInstallError(String(e));
+        return;
+      }
     }
     setActiveId(null);
   };
@@ -198,7 +238,10 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
                     : 'border-[rgb(var(--border))] bg-[var(--surface)] hover:border-primary-400 hover:bg-primary-500/5'
                 }`}
               title={entry.name}
-              onClick={() => setActiveId(isActive ? null : entry.id)}
+              onClick={() => {
+                setInstallError(null);
+                setActiveId(isActive ? null : entry.id);
+              }}
               data-testid={`client-icon-${entry.id}`}
             >
               {entry.icon ? (
@@ -236,6 +279,18 @@ export function ConnectIDEsGrid({ gatewayUrl, gatewayRunning }: ConnectIDEsGridP
                   {entry.nextStep}
                 </p>
 
+                {installed[entry.id] === false && (
+                  <p className="text-[11px] leading-snug text-orange-500 mb-2.5">
+                    {entry.name} was not detected on this machine.
+                  </p>
+                )}
+
+                {installError && (
+                  <p className="text-[11px] leading-snug text-red-500 mb-2.5 break-words">
+                    {installError}
+                  </p>
+                )}
+
                 {entry.action === 'deep_link' ? (
                   <Button
                     variant="primary"
@@ -294,7 +349,8 @@ export function ConnectIDEs({ gatewayUrl, gatewayRunning }: ConnectIDEsProps) {
           <div>
             <CardTitle>Connect Your IDEs</CardTitle>
             <CardDescription>
-              <span className="font-medium">VS Code &amp; Cursor</span> are one-click; the rest copy
+              <span className="font-medium">VS Code, Cursor, Windsurf &amp; Zed</span> are one-click;
+              the rest copy
               a config you paste into their MCP settings. Either path ends with an approval
               prompt in this app.
             </CardDescription>
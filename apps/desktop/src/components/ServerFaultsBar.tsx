@@ -0,0 +1,88 @@
+import { useEffect, useState } from 'react';
+import { getServerFaults, setServerFaults, type FaultPlan } from '@/lib/api/logs';
+
+interface ServerFaultsBarProps {
+  serverId: string;
+}
+
+const NO_FAULTS: FaultPlan = { drop_percent: 0, delay_ms: 0, expire_oauth: false };
+
+/**
+ * Fault injection controls for a server's tool calls. Only rendered in
+ * debug builds, the only ones that accept faults.
+ */
+export function ServerFaultsBar({ serverId }: ServerFaultsBarProps) {
+  const [available, setAvailable] = useState(false);
+  const [plan, setPlan] = useState<FaultPlan>(NO_FAULTS);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getServerFaults(serverId)
+      .then((faults) => {
+        setAvailable(faults.available);
+        setPlan(faults.plan);
+        setError(null);
+      })
+      .catch((e) => setError(String(e)));
+  }, [serverId]);
+
+  const apply = async (next: FaultPlan) => {
+    try {
+      await setServerFaults(serverId, next);
+      setPlan(next);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!available) return null;
+
+  const inputClass =
+    'w-16 px-1.5 py-1 rounded border border-[rgb(var(--border-subtle))] bg-[rgb(var(--surface-elevated))]';
+
+  return (
+    <div className="flex items-center gap-3 px-4 py-2 border-b border-[rgb(var(--border-subtle))] text-xs">
+      <span className="font-medium text-amber-500">Fault injection</span>
+      <label className="flex items-center gap-1">
+        Drop
+        <input
+          type="number"
+          min={0}
+          max={100}
+          value={plan.drop_percent}
+          onChange={(e) => apply({ ...plan, drop_percent: Number(e.target.value) })}
+          className={inputClass}
+        />
+        %
+      </label>
+      <label className="flex items-center gap-1">
+        Delay
+        <input
+          type="number"
+          min={0}
+          step={100}
+          value={plan.delay_ms}
+          onChange={(e) => apply({ ...plan, delay_ms: Number(e.target.value) })}
+          className={inputClass}
+        />
+        ms
+      </label>
+      <button
+        onClick={() => apply({ ...plan, expire_oauth: true })}
+        disabled={plan.expire_oauth}
+        className="px-2 py-1 rounded-lg border border-[rgb(var(--border-subtle))] disabled:opacity-50"
+        title="Fail the next tool call as if its OAuth token had expired"
+      >
+        {plan.expire_oauth ? 'Expiry pending' : 'Expire OAuth'}
+      </button>
+      <button
+        onClick={() => apply(NO_FAULTS)}
+        className="px-2 py-1 rounded-lg text-[rgb(var(--muted))] hover:bg-[rgb(var(--surface-hover))]"
+      >
+        Reset
+      </button>
+      {error && <span className="text-red-400 truncate">{error}</span>}
+    </div>
+  );
+}
@@ -129,11 +129,20 @@ export function ServerInstallModal() {
   const handleInstall = async () => {
     if (modalState.type !== 'ready' || !selectedSpaceId) return;
 
+    const { server } = modalState;
+    const untrusted = server.source.type === 'Registry' && server.source.trust === 'untrusted';
+    if (
+      untrusted &&
+      !window.confirm(`"${server.name}" comes from an untrusted registry. Install it anyway?`)
+    ) {
+      return;
+    }
+
     setIsInstalling(true);
     setInstallError(null);
 
     try {
-      await installServer(modalState.server.id, selectedSpaceId);
+      await installServer(server.id, selectedSpaceId, untrusted);
       console.log('[Install] Server installed:', modalState.server.id);
       setModalState({ type: 'success', serverName: modalState.server.name });
 
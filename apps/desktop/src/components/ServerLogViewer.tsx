@@ -1,7 +1,14 @@
 import { useEffect, useState, useRef } from 'react';
 import { X, Download, Trash2, RefreshCw } from 'lucide-react';
 import { useToast, ToastContainer, useConfirm } from '@mcpmux/ui';
-import { getServerLogs, clearServerLogs, getServerLogFile, type ServerLogEntry } from '@/lib/api/logs';
+import {
+  getServerLogs,
+  clearServerLogs,
+  getServerLogFile,
+  tailServerLogs,
+  type ServerLogEntry,
+} from '@/lib/api/logs';
+import { ServerTrafficPanel } from './ServerTrafficPanel';
 
 interface ServerLogViewerProps {
   serverId: string;
@@ -12,6 +19,9 @@ interface ServerLogViewerProps {
 const LOG_LEVELS = ['trace', 'debug', 'info', 'warn', 'error'] as const;
 type LogLevel = typeof LOG_LEVELS[number];
 
+/** Entries kept in the viewer; older ones drop off while streaming */
+const MAX_LOGS = 500;
+
 const LEVEL_COLORS: Record<LogLevel, string> = {
   trace: 'text-gray-400',
   debug: 'text-blue-400',
@@ -37,32 +47,38 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [levelFilter, setLevelFilter] = useState<LogLevel | 'all'>('all');
-  const [autoRefresh, setAutoRefresh] = useState(false);
+  const [sourceFilter, setSourceFilter] = useState<string>('all');
+  const [live, setLive] = useState(false);
+  const [view, setView] = useState<'logs' | 'traffic'>('logs');
   const scrollContainerRef = useRef<HTMLDivElement>(null);
   const shouldScrollRef = useRef(true);
   const { toasts, success, error: showError, dismiss } = useToast();
   const { confirm, ConfirmDialogElement } = useConfirm();
 
+  const scrollToBottom = () => {
+    if (shouldScrollRef.current && scrollContainerRef.current) {
+      setTimeout(() => {
+        scrollContainerRef.current?.scrollTo({
+          top: scrollContainerRef.current.scrollHeight,
+          behavior: 'smooth',
+        });
+      }, 100);
+    }
+  };
+
   const loadLogs = async () => {
     try {
       setLoading(true);
       setError(null);
       const fetchedLogs = await getServerLogs(
         serverId,
-        500, // Load last 500 logs
+        MAX_LOGS,
         levelFilter === 'all' ? undefined : levelFilter
       );
       setLogs(fetchedLogs);
       
       // Auto-scroll to bottom if user was at bottom
-      if (shouldScrollRef.current && scrollContainerRef.current) {
-        setTimeout(() => {
-          scrollContainerRef.current?.scrollTo({
-            top: scrollContainerRef.current.scrollHeight,
-            behavior: 'smooth',
-          });
-        }, 100);
-      }
+      scrollToBottom();
     } catch (e) {
       setError(String(e));
     } finally {
@@ -74,16 +90,34 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
     loadLogs();
   }, [serverId, levelFilter]);
 
-  // Auto-refresh every 2 seconds if enabled
+  // Stream new lines while live; the backend applies the filters
   useEffect(() => {
-    if (!autoRefresh) return;
-    
-    const interval = setInterval(() => {
-      loadLogs();
-    }, 2000);
-    
-    return () => clearInterval(interval);
-  }, [autoRefresh, serverId, levelFilter]);
+    if (!live) return;
+
+    let stop: (() => void) | null = null;
+    let cancelled = false;
+    tailServerLogs(
+      serverId,
+      (entry) => {
+        setLogs((prev) => [...prev, entry].slice(-MAX_LOGS));
+        scrollToBottom();
+      },
+      {
+        levelFilter: levelFilter === 'all' ? undefined : levelFilter,
+        sources: sourceFilter === 'all' ? undefined : [sourceFilter],
+      }
+    )
+      .then((unsubscribe) => {
+        if (cancelled) unsubscribe();
+        else stop = unsubscribe;
+      })
+      .catch((e) => setError(String(e)));
+
+    return () => {
+      cancelled = true;
+      stop?.();
+    };
+  }, [live, serverId, levelFilter, sourceFilter]);
 
   // Track scroll position
   const handleScroll = () => {
@@ -132,6 +166,7 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
   };
 
   const filteredLogs = logs.filter(log => {
+    if (sourceFilter !== 'all' && log.source !== sourceFilter) return false;
     if (levelFilter === 'all') return true;
     const logLevelIndex = LOG_LEVELS.indexOf(log.level as LogLevel);
     const filterLevelIndex = LOG_LEVELS.indexOf(levelFilter);
@@ -148,8 +183,25 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
           <div className="flex items-center gap-3">
             <h2 className="text-lg font-semibold">Server Logs</h2>
             <span className="text-sm text-[rgb(var(--muted))]">{serverName}</span>
+            <div className="flex items-center gap-1 ml-2">
+              {(['logs', 'traffic'] as const).map((tab) => (
+                <button
+                  key={tab}
+                  onClick={() => setView(tab)}
+                  className={`px-2 py-1 text-xs rounded-lg transition-colors ${
+                    view === tab
+                      ? 'bg-[rgb(var(--surface-elevated))] text-[rgb(var(--foreground))]'
+                      : 'text-[rgb(var(--muted))] hover:bg-[rgb(var(--surface-hover))]'
+                  }`}
+                >
+                  {tab === 'logs' ? 'Logs' : 'Traffic'}
+                </button>
+              ))}
+            </div>
           </div>
           <div className="flex items-center gap-2">
+            {view === 'logs' && (
+            <>
             {/* Level Filter */}
             <select
               value={levelFilter}
@@ -163,8 +215,22 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
                 </option>
               ))}
             </select>
+
+            {/* Source Filter */}
+            <select
+              value={sourceFilter}
+              onChange={(e) => setSourceFilter(e.target.value)}
+              className="px-3 py-1.5 text-sm bg-[rgb(var(--surface-elevated))] border border-[rgb(var(--border-subtle))] rounded-lg"
+            >
+              <option value="all">All Sources</option>
+              {Object.keys(SOURCE_COLORS).map(source => (
+                <option key={source} value={source}>
+                  {source}
+                </option>
+              ))}
+            </select>
             
-            {/* Refresh with auto-refresh toggle */}
+            {/* Refresh with live tail toggle */}
             <div className="flex items-center gap-1">
               <button
                 onClick={loadLogs}
@@ -175,15 +241,15 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
                 <RefreshCw className={`h-4 w-4 ${loading ? 'animate-spin' : ''}`} />
               </button>
               <button
-                onClick={() => setAutoRefresh(!autoRefresh)}
+                onClick={() => setLive(!live)}
                 className={`px-2 py-1.5 text-xs rounded-lg border transition-colors ${
-                  autoRefresh
+                  live
                     ? 'bg-[rgb(var(--primary))] text-[rgb(var(--primary-foreground))] border-[rgb(var(--primary))]'
                     : 'bg-[rgb(var(--surface-elevated))] border-[rgb(var(--border-subtle))] text-[rgb(var(--muted))]'
                 }`}
-                title="Toggle auto-refresh (every 2s)"
+                title="Stream new log lines as they arrive"
               >
-                Auto
+                Live
               </button>
             </div>
             
@@ -204,6 +270,8 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
             >
               <Trash2 className="h-4 w-4" />
             </button>
+            </>
+            )}
             
             {/* Close */}
             <button
@@ -215,6 +283,10 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
           </div>
         </div>
 
+        {view === 'traffic' ? (
+          <ServerTrafficPanel serverId={serverId} />
+        ) : (
+        <>
         {/* Log Content */}
         <div
           ref={scrollContainerRef}
@@ -273,15 +345,18 @@ export function ServerLogViewer({ serverId, serverName, onClose }: ServerLogView
         <div className="p-3 border-t border-[rgb(var(--border-subtle))] text-xs text-[rgb(var(--muted))] flex items-center justify-between">
           <span>
             {filteredLogs.length} log{filteredLogs.length !== 1 ? 's' : ''}
-            {levelFilter !== 'all' && ` (filtered from ${logs.length} total)`}
+            {(levelFilter !== 'all' || sourceFilter !== 'all') &&
+              ` (filtered from ${logs.length} total)`}
           </span>
-          {autoRefresh && (
+          {live && (
             <span className="flex items-center gap-2">
               <span className="h-2 w-2 bg-[rgb(var(--primary))] rounded-full animate-pulse" />
-              Auto-refreshing...
+              Streaming...
             </span>
           )}
         </div>
+        </>
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,179 @@
+import { useEffect, useState } from 'react';
+import { ArrowLeft, ArrowRight, Download, Trash2 } from 'lucide-react';
+import {
+  getServerTraffic,
+  setServerTrafficRecording,
+  clearServerTraffic,
+  type TrafficFrame,
+} from '@/lib/api/logs';
+import { ServerFaultsBar } from './ServerFaultsBar';
+
+interface ServerTrafficPanelProps {
+  serverId: string;
+}
+
+/** How often frames are fetched while recording */
+const POLL_INTERVAL_MS = 1000;
+
+const formatTimestamp = (ts: string) => {
+  const date = new Date(ts);
+  const hours = date.getHours().toString().padStart(2, '0');
+  const minutes = date.getMinutes().toString().padStart(2, '0');
+  const seconds = date.getSeconds().toString().padStart(2, '0');
+  const ms = date.getMilliseconds().toString().padStart(3, '0');
+  return `${hours}:${minutes}:${seconds}.${ms}`;
+};
+
+const formatSize = (bytes: number) =>
+  bytes < 1024 ? `${bytes} B` : `${(bytes / 1024).toFixed(1)} KB`;
+
+/** Pretty-print a frame; truncated frames aren't valid JSON */
+const prettyFrame = (frame: TrafficFrame) => {
+  if (frame.truncated) return `${frame.raw}…`;
+  try {
+    return JSON.stringify(JSON.parse(frame.raw), null, 2);
+  } catch {
+    return frame.raw;
+  }
+};
+
+/**
+ * Raw JSON-RPC frames exchanged with a server, recorded on demand.
+ */
+export function ServerTrafficPanel({ serverId }: ServerTrafficPanelProps) {
+  const [frames, setFrames] = useState<TrafficFrame[]>([]);
+  const [recording, setRecording] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    try {
+      const traffic = await getServerTraffic(serverId);
+      setFrames(traffic.frames);
+      setRecording(traffic.recording);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  useEffect(() => {
+    load();
+  }, [serverId]);
+
+  useEffect(() => {
+    if (!recording) return;
+    const interval = setInterval(load, POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [recording, serverId]);
+
+  const toggleRecording = async () => {
+    try {
+      await setServerTrafficRecording(serverId, !recording);
+      setRecording(!recording);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      await clearServerTraffic(serverId);
+      setFrames([]);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  /** Save the frames as JSON, e.g. to replay them in a regression test */
+  const handleExport = () => {
+    const blob = new Blob([JSON.stringify(frames, null, 2)], { type: 'application/json' });
+    const url = URL.createObjectURL(blob);
+    const link = document.createElement('a');
+    link.href = url;
+    link.download = `${serverId}-traffic.json`;
+    link.click();
+    URL.revokeObjectURL(url);
+  };
+
+  return (
+    <div className="flex-1 flex flex-col min-h-0">
+      <div className="flex items-center justify-between px-4 py-2 border-b border-[rgb(var(--border-subtle))] text-xs">
+        <span className="text-[rgb(var(--muted))]">
+          Frames are recorded in memory with secrets redacted. The last 500 are kept.
+        </span>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={toggleRecording}
+            className={`px-2 py-1.5 rounded-lg border transition-colors ${
+              recording
+                ? 'bg-red-500 text-white border-red-500'
+                : 'bg-[rgb(var(--surface-elevated))] border-[rgb(var(--border-subtle))] text-[rgb(var(--muted))]'
+            }`}
+            title="Record JSON-RPC frames sent to and received from this server"
+          >
+            {recording ? 'Stop Recording' : 'Record'}
+          </button>
+          <button
+            onClick={handleExport}
+            disabled={frames.length === 0}
+            className="p-1.5 rounded-lg hover:bg-[rgb(var(--surface-hover))] transition-colors text-[rgb(var(--muted))] disabled:opacity-50"
+            title="Export recorded frames as JSON"
+          >
+            <Download className="h-4 w-4" />
+          </button>
+          <button
+            onClick={handleClear}
+            className="p-1.5 rounded-lg hover:bg-[rgb(var(--surface-hover))] transition-colors text-red-400"
+            title="Clear recorded frames"
+          >
+            <Trash2 className="h-4 w-4" />
+          </button>
+        </div>
+      </div>
+      <ServerFaultsBar serverId={serverId} />
+
+      <div className="flex-1 overflow-y-auto p-4 font-mono text-sm">
+        {error ? (
+          <div className="text-red-400">{error}</div>
+        ) : frames.length === 0 ? (
+          <div className="text-center text-[rgb(var(--muted))] py-12">
+            {recording ? 'Waiting for traffic...' : 'No traffic recorded'}
+          </div>
+        ) : (
+          <div className="space-y-1">
+            {frames.map((frame) => (
+              <details
+                key={frame.seq}
+                className="hover:bg-[rgb(var(--surface-hover))] px-2 py-1 rounded"
+              >
+                <summary className="flex gap-3 cursor-pointer list-none">
+                  <span className="text-[rgb(var(--muted))] shrink-0">
+                    {formatTimestamp(frame.timestamp)}
+                  </span>
+                  {frame.direction === 'outgoing' ? (
+                    <ArrowRight className="h-4 w-4 shrink-0 text-blue-400" />
+                  ) : (
+                    <ArrowLeft className="h-4 w-4 shrink-0 text-green-400" />
+                  )}
+                  <span className="flex-1 truncate">
+                    {frame.method ?? (frame.id !== null ? `response #${frame.id}` : 'message')}
+                    {frame.method && frame.id !== null && (
+                      <span className="text-[rgb(var(--muted))]"> #{frame.id}</span>
+                    )}
+                  </span>
+                  <span className="shrink-0 text-[rgb(var(--muted))]">
+                    {formatSize(frame.size)}
+                    {frame.truncated && ' (truncated)'}
+                  </span>
+                </summary>
+                <pre className="mt-1 text-xs bg-[rgb(var(--surface-elevated))] p-2 rounded overflow-x-auto whitespace-pre-wrap break-all">
+                  {prettyFrame(frame)}
+                </pre>
+              </details>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
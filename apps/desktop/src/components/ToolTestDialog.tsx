@@ -0,0 +1,201 @@
+import { useEffect, useState } from 'react';
+import { X, Play, Loader2 } from 'lucide-react';
+import { testToolCall, type ServerFeature, type TestToolCallResponse } from '@/lib/api/serverFeatures';
+import { listFeatureSetsBySpace, type FeatureSet } from '@/lib/api/featureSets';
+
+interface ToolTestDialogProps {
+  spaceId: string;
+  tool: ServerFeature;
+  onClose: () => void;
+}
+
+type JsonSchema = {
+  type?: string;
+  properties?: Record<string, JsonSchema>;
+  required?: string[];
+  default?: unknown;
+  inputSchema?: JsonSchema;
+};
+
+/** Placeholder value for a schema type */
+const placeholder = (schema: JsonSchema): unknown => {
+  if (schema.default !== undefined) return schema.default;
+  switch (schema.type) {
+    case 'string':
+      return '';
+    case 'number':
+    case 'integer':
+      return 0;
+    case 'boolean':
+      return false;
+    case 'array':
+      return [];
+    case 'object':
+      return {};
+    default:
+      return null;
+  }
+};
+
+/** Arguments skeleton with the tool's required parameters filled in */
+const argumentsTemplate = (tool: ServerFeature): string => {
+  // Stored features keep the whole tool definition; the schema is inside it
+  const raw = (tool.input_schema ?? {}) as JsonSchema;
+  const schema = raw.inputSchema ?? raw;
+  const args: Record<string, unknown> = {};
+  for (const name of schema.required ?? []) {
+    args[name] = placeholder(schema.properties?.[name] ?? {});
+  }
+  return JSON.stringify(args, null, 2);
+};
+
+/**
+ * Call a tool with hand-written JSON arguments through the gateway and show
+ * the full result, for debugging a server.
+ */
+export function ToolTestDialog({ spaceId, tool, onClose }: ToolTestDialogProps) {
+  const [args, setArgs] = useState(() => argumentsTemplate(tool));
+  const [bypassPermissions, setBypassPermissions] = useState(true);
+  const [featureSets, setFeatureSets] = useState<FeatureSet[]>([]);
+  const [featureSetId, setFeatureSetId] = useState('');
+  const [running, setRunning] = useState(false);
+  const [result, setResult] = useState<TestToolCallResponse | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listFeatureSetsBySpace(spaceId)
+      .then((sets) => {
+        setFeatureSets(sets);
+        if (sets.length > 0) setFeatureSetId(sets[0].id);
+      })
+      .catch(() => setFeatureSets([]));
+  }, [spaceId]);
+
+  const handleRun = async () => {
+    let parsed: unknown;
+    try {
+      parsed = args.trim() ? JSON.parse(args) : {};
+    } catch (e) {
+      setError(`Invalid JSON: ${e instanceof Error ? e.message : String(e)}`);
+      return;
+    }
+    if (parsed === null || typeof parsed !== 'object' || Array.isArray(parsed)) {
+      setError('Arguments must be a JSON object');
+      return;
+    }
+
+    setRunning(true);
+    setError(null);
+    setResult(null);
+    try {
+      const response = await testToolCall({
+        space_id: spaceId,
+        server_id: tool.server_id,
+        tool_name: tool.feature_name,
+        arguments: parsed as Record<string, unknown>,
+        feature_set_ids: bypassPermissions || !featureSetId ? [] : [featureSetId],
+        bypass_permissions: bypassPermissions,
+      });
+      setResult(response);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+      <div className="bg-[rgb(var(--card))] border border-[rgb(var(--border-subtle))] rounded-xl shadow-xl w-[760px] max-w-[90vw] max-h-[85vh] flex flex-col">
+        {/* Header */}
+        <div className="flex items-center justify-between p-4 border-b border-[rgb(var(--border-subtle))]">
+          <div className="flex items-center gap-3">
+            <h2 className="text-lg font-semibold">Test Tool</h2>
+            <span className="text-sm font-mono text-[rgb(var(--muted))]">
+              {tool.server_id} / {tool.feature_name}
+            </span>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-1.5 rounded-lg hover:bg-[rgb(var(--surface-hover))] transition-colors"
+          >
+            <X className="h-4 w-4" />
+          </button>
+        </div>
+
+        <div className="flex-1 overflow-y-auto p-4 space-y-4">
+          <div>
+            <label className="mb-1 block text-sm font-medium">Arguments (JSON)</label>
+            <textarea
+              value={args}
+              onChange={(e) => setArgs(e.target.value)}
+              spellCheck={false}
+              rows={8}
+              className="w-full rounded-lg border border-[rgb(var(--border-subtle))] bg-[rgb(var(--surface-elevated))] p-2 font-mono text-sm"
+            />
+          </div>
+
+          <div className="flex flex-wrap items-center gap-3 text-sm">
+            <label className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                checked={bypassPermissions}
+                onChange={(e) => setBypassPermissions(e.target.checked)}
+              />
+              Skip FeatureSet permission check
+            </label>
+            {!bypassPermissions && (
+              <select
+                value={featureSetId}
+                onChange={(e) => setFeatureSetId(e.target.value)}
+                className="px-3 py-1.5 text-sm bg-[rgb(var(--surface-elevated))] border border-[rgb(var(--border-subtle))] rounded-lg"
+              >
+                {featureSets.map((fs) => (
+                  <option key={fs.id} value={fs.id}>
+                    {fs.name}
+                  </option>
+                ))}
+              </select>
+            )}
+          </div>
+
+          {error && <div className="text-sm text-red-400 break-words">{error}</div>}
+
+          {result && (
+            <div>
+              <div className="mb-1 flex items-center gap-3 text-sm">
+                <span className={result.is_error ? 'text-red-400' : 'text-green-400'}>
+                  {result.is_error ? 'Tool returned an error' : 'Success'}
+                </span>
+                <span className="text-[rgb(var(--muted))]">{result.duration_ms} ms</span>
+              </div>
+              <pre className="text-xs bg-[rgb(var(--surface-elevated))] p-2 rounded overflow-x-auto whitespace-pre-wrap break-all">
+                {JSON.stringify(
+                  {
+                    content: result.content,
+                    structuredContent: result.structured_content ?? undefined,
+                    _meta: result.meta ?? undefined,
+                  },
+                  null,
+                  2
+                )}
+              </pre>
+            </div>
+          )}
+        </div>
+
+        {/* Footer */}
+        <div className="flex justify-end gap-2 p-3 border-t border-[rgb(var(--border-subtle))]">
+          <button
+            onClick={handleRun}
+            disabled={running}
+            className="flex items-center gap-2 rounded-lg bg-[rgb(var(--primary))] px-3.5 py-1.5 text-sm font-medium text-[rgb(var(--primary-foreground))] transition-colors hover:bg-[rgb(var(--primary-hover))] disabled:opacity-50"
+          >
+            {running ? <Loader2 className="h-4 w-4 animate-spin" /> : <Play className="h-4 w-4" />}
+            Call Tool
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
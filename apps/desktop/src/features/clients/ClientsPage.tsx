@@ -24,6 +24,9 @@ import {
   Globe,
   ShieldOff,
   KeyRound,
+  Smartphone,
+  Ban,
+  Play,
 } from 'lucide-react';
 import { ConnectIDEs } from '@/components/ConnectIDEs';
 import type { GatewayStatus, OAuthClient } from '@/lib/api/gateway';
@@ -32,6 +35,7 @@ import {
   listOAuthClients,
   updateOAuthClient,
   deleteOAuthClient,
+  setOAuthClientSuspended,
   getOAuthClientGrants,
   grantOAuthClientFeatureSet,
   revokeOAuthClientFeatureSet,
@@ -57,6 +61,7 @@ import {
   useSetPendingClientId,
 } from '@/stores';
 import { RegisterApiKeyClientModal } from './RegisterApiKeyClientModal';
+import { PairDeviceModal } from './PairDeviceModal';
 import { ClientApiKeysSection } from './ClientApiKeysSection';
 
 // Bundled icons for well-known AI clients.
@@ -128,6 +133,7 @@ export default function ClientsPage() {
   const [searchQuery, setSearchQuery] = useState('');
   const [selected, setSelected] = useState<OAuthClient | null>(null);
   const [showRegister, setShowRegister] = useState(false);
+  const [showPairDevice, setShowPairDevice] = useState(false);
   const [editAlias, setEditAlias] = useState('');
   const [isSaving, setIsSaving] = useState(false);
   const [gatewayStatus, setGatewayStatus] = useState<GatewayStatus>({
@@ -135,6 +141,7 @@ export default function ClientsPage() {
     url: null,
     active_sessions: 0,
     connected_backends: 0,
+    traffic_paused: false,
   });
 
   const { toasts, success, error: showError, info, dismiss } = useToast();
@@ -251,6 +258,31 @@ export default function ClientsPage() {
     }
   };
 
+  const handleToggleSuspend = async (client: OAuthClient) => {
+    const name = client.client_alias || client.client_name;
+    const suspend = !client.suspended;
+    if (
+      suspend &&
+      !(await confirm({
+        title: 'Suspend client',
+        message: `Block "${name}"? Every request it makes will be refused until you reinstate it. Its registration, keys and grants are kept.`,
+        confirmLabel: 'Suspend',
+        variant: 'danger',
+      }))
+    ) {
+      return;
+    }
+    try {
+      await setOAuthClientSuspended(client.client_id, suspend);
+      const updated = { ...client, suspended: suspend };
+      setClients((prev) => prev.map((c) => (c.client_id === client.client_id ? updated : c)));
+      setSelected(updated);
+      success(suspend ? 'Client suspended' : 'Client reinstated', `"${name}"`);
+    } catch (e) {
+      showError('Failed to update client', e instanceof Error ? e.message : String(e));
+    }
+  };
+
   const filtered = clients.filter((client) => {
     if (!searchQuery) return true;
     const q = searchQuery.toLowerCase();
@@ -292,6 +324,15 @@ export default function ClientsPage() {
                   <RefreshCw className={`mr-2 h-4 w-4 ${isRefreshing ? 'animate-spin' : ''}`} />
                   Refresh
                 </Button>
+                <Button
+                  variant="secondary"
+                  size="md"
+                  onClick={() => setShowPairDevice(true)}
+                  data-testid="pair-device-btn"
+                >
+                  <Smartphone className="mr-2 h-4 w-4" />
+                  Pair device
+                </Button>
                 <Button
                   variant="primary"
                   size="md"
@@ -376,6 +417,12 @@ export default function ClientsPage() {
                             </p>
                           )}
                         </div>
+                        {client.suspended && (
+                          <span className="inline-flex flex-shrink-0 items-center gap-1 rounded-full bg-red-500/10 px-2 py-0.5 text-xs font-medium text-red-600 dark:text-red-400">
+                            <Ban className="h-3 w-3" />
+                            Suspended
+                          </span>
+                        )}
                       </div>
 
                       <div className="flex items-center justify-between text-xs text-[rgb(var(--muted))]">
@@ -414,6 +461,7 @@ export default function ClientsPage() {
             onClose={() => setSelected(null)}
             onSaveAlias={handleSaveAlias}
             onRevoke={() => handleRevoke(selected)}
+            onToggleSuspend={() => handleToggleSuspend(selected)}
             onOpenWorkspaces={() => {
               setSelected(null);
               navigateTo('workspaces');
@@ -434,6 +482,13 @@ export default function ClientsPage() {
         />
       )}
 
+      {showPairDevice && (
+        <PairDeviceModal
+          onClose={() => setShowPairDevice(false)}
+          onPaired={(clientName) => success(`Paired "${clientName}".`)}
+        />
+      )}
+
       <ToastContainer toasts={toasts} onClose={dismiss} />
       {ConfirmDialogElement}
     </div>
@@ -512,6 +567,7 @@ interface SidePanelProps {
   onClose: () => void;
   onSaveAlias: () => void;
   onRevoke: () => void;
+  onToggleSuspend: () => void;
   onOpenWorkspaces: () => void;
   onToastError: (title: string, body?: string) => void;
   onToastSuccess: (title: string, body?: string) => void;
@@ -526,6 +582,7 @@ function SidePanel({
   onClose,
   onSaveAlias,
   onRevoke,
+  onToggleSuspend,
   onOpenWorkspaces,
   onToastError,
   onToastSuccess,
@@ -668,7 +725,26 @@ function SidePanel({
         </section>
       </div>
 
-      <div className="flex-shrink-0 border-t border-[rgb(var(--border))] bg-[rgb(var(--surface-elevated))] p-4">
+      <div className="flex-shrink-0 space-y-2 border-t border-[rgb(var(--border))] bg-[rgb(var(--surface-elevated))] p-4">
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={onToggleSuspend}
+          className="w-full"
+          data-testid="client-suspend-toggle"
+        >
+          {client.suspended ? (
+            <>
+              <Play className="mr-2 h-4 w-4" />
+              Reinstate client
+            </>
+          ) : (
+            <>
+              <Ban className="mr-2 h-4 w-4" />
+              Suspend client
+            </>
+          )}
+        </Button>
         <Button
           variant="ghost"
           size="sm"
@@ -0,0 +1,265 @@
+/**
+ * Pair device modal.
+ *
+ * Onboards a phone or other remote client without copying an API key: McpMux
+ * shows a one-time code as text and as a QR code of the gateway URL plus the
+ * code. The device posts the code to `/oauth/pair` and receives its own API
+ * key, becoming a pre-approved client (optionally locked to a Space).
+ *
+ * Codes work once and expire after 10 minutes. The gateway must be reachable
+ * from the device — network access on, or a public URL set.
+ */
+
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { CheckCircle2, Loader2, Lock, Smartphone, X } from 'lucide-react';
+import { Button, Card, CardContent, CardDescription, CardHeader, CardTitle } from '@mcpmux/ui';
+import { createDevicePairing, type DevicePairing } from '@/lib/api/gateway';
+import { listSpaces, type Space } from '@/lib/api/spaces';
+
+interface PairDeviceModalProps {
+  onClose: () => void;
+  /** Called when a device redeems the code. */
+  onPaired: (clientName: string) => void;
+}
+
+function formatRemaining(seconds: number): string {
+  const m = Math.floor(seconds / 60);
+  const s = seconds % 60;
+  return `${m}:${s.toString().padStart(2, '0')}`;
+}
+
+export function PairDeviceModal({ onClose, onPaired }: PairDeviceModalProps) {
+  const [name, setName] = useState('');
+  const [lockedSpaceId, setLockedSpaceId] = useState('');
+  const [spaces, setSpaces] = useState<Space[]>([]);
+  const [isCreating, setIsCreating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [pairing, setPairing] = useState<DevicePairing | null>(null);
+  const [pairedName, setPairedName] = useState<string | null>(null);
+  const [now, setNow] = useState(() => Math.floor(Date.now() / 1000));
+
+  useEffect(() => {
+    listSpaces()
+      .then(setSpaces)
+      .catch(() => setSpaces([]));
+  }, []);
+
+  // Count down while a code is showing
+  useEffect(() => {
+    if (!pairing || pairedName) return;
+    const timer = setInterval(() => setNow(Math.floor(Date.now() / 1000)), 1000);
+    return () => clearInterval(timer);
+  }, [pairing, pairedName]);
+
+  // A paired device registers as a new pre-approved client
+  useEffect(() => {
+    if (!pairing || pairedName) return;
+    const unlisten = listen<{
+      action: string;
+      client_name?: string;
+      registration_type?: string;
+    }>('client-changed', (event) => {
+      const { action, client_name, registration_type } = event.payload;
+      if (action !== 'registered' || registration_type !== 'preregistered') return;
+      const paired = client_name ?? 'Device';
+      setPairedName(paired);
+      onPaired(paired);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [pairing, pairedName]);
+
+  const remaining = pairing ? Math.max(0, pairing.expiresAt - now) : 0;
+  const expired = pairing !== null && remaining === 0;
+  const lockedSpaceName = lockedSpaceId
+    ? (spaces.find((s) => s.id === lockedSpaceId)?.name ?? 'a Space')
+    : null;
+
+  const handleCreate = async () => {
+    setIsCreating(true);
+    setError(null);
+    try {
+      const created = await createDevicePairing(name.trim() || null, lockedSpaceId || null);
+      setNow(Math.floor(Date.now() / 1000));
+      setPairing(created);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsCreating(false);
+    }
+  };
+
+  return (
+    <div
+      className="animate-in fade-in fixed inset-0 z-50 flex items-center justify-center bg-black/30 p-4 backdrop-blur-[2px] duration-200"
+      onClick={pairing ? undefined : onClose}
+    >
+      <Card className="w-full max-w-lg shadow-2xl" onClick={(e) => e.stopPropagation()}>
+        <CardHeader className="relative">
+          <button
+            onClick={onClose}
+            className="absolute right-4 top-4 rounded-lg p-1.5 text-[rgb(var(--muted))] transition-colors hover:bg-[rgb(var(--surface))] hover:text-[rgb(var(--text))]"
+            aria-label="Close"
+          >
+            <X className="h-5 w-5" />
+          </button>
+          <div className="mb-2 flex h-11 w-11 items-center justify-center rounded-xl bg-[rgb(var(--accent))]/10">
+            <Smartphone className="h-5 w-5 text-[rgb(var(--accent))]" />
+          </div>
+          <CardTitle data-testid="pair-device-title">
+            {pairedName ? 'Device paired' : 'Pair a device'}
+          </CardTitle>
+          <CardDescription>
+            {pairing
+              ? 'Scan the QR code on the device, or enter the code where it asks for one.'
+              : 'Connect a phone or another remote client with a one-time code instead of copying an API key.'}
+          </CardDescription>
+        </CardHeader>
+
+        <CardContent className="space-y-5">
+          {pairedName ? (
+            <>
+              <div className="flex items-center gap-3 rounded-xl border border-emerald-300 bg-emerald-50 p-3.5 dark:border-emerald-700/60 dark:bg-emerald-900/20">
+                <CheckCircle2 className="h-5 w-5 flex-shrink-0 text-emerald-600 dark:text-emerald-400" />
+                <p className="text-sm text-emerald-800 dark:text-emerald-200">
+                  <span className="font-medium">{pairedName}</span> is connected with its own API
+                  key. You can revoke it from its client panel.
+                </p>
+              </div>
+              <div className="flex justify-end">
+                <Button variant="primary" size="md" onClick={onClose}>
+                  Done
+                </Button>
+              </div>
+            </>
+          ) : pairing ? (
+            <>
+              <div className="flex flex-col items-center gap-3">
+                <div
+                  data-testid="pair-device-qr"
+                  className={`rounded-xl bg-white p-2 ${expired ? 'opacity-30' : ''}`}
+                  // Generated by the desktop backend from the pairing URL
+                  dangerouslySetInnerHTML={{ __html: pairing.qrSvg }}
+                />
+                <code
+                  data-testid="pair-device-code"
+                  className="select-all font-mono text-2xl font-semibold tracking-widest"
+                >
+                  {pairing.code}
+                </code>
+                <p className="break-all text-center text-xs text-[rgb(var(--muted))]">
+                  {pairing.pairUrl}
+                </p>
+                {expired ? (
+                  <p className="text-sm text-red-600 dark:text-red-400">
+                    This code expired. Create a new one.
+                  </p>
+                ) : (
+                  <p className="flex items-center gap-1.5 text-sm text-[rgb(var(--muted))]">
+                    <Loader2 className="h-3.5 w-3.5 animate-spin" />
+                    Waiting for the device · expires in {formatRemaining(remaining)}
+                  </p>
+                )}
+                {lockedSpaceName && (
+                  <p className="flex items-center gap-1.5 text-xs text-[rgb(var(--muted))]">
+                    <Lock className="h-3.5 w-3.5" />
+                    The device will be locked to{' '}
+                    <span className="font-medium">{lockedSpaceName}</span>.
+                  </p>
+                )}
+              </div>
+              <div className="flex justify-end gap-2">
+                <Button variant="ghost" size="md" onClick={onClose}>
+                  Cancel
+                </Button>
+                {expired && (
+                  <Button
+                    variant="primary"
+                    size="md"
+                    onClick={handleCreate}
+                    disabled={isCreating}
+                    data-testid="pair-device-renew"
+                  >
+                    New code
+                  </Button>
+                )}
+              </div>
+            </>
+          ) : (
+            <>
+              <div>
+                <label htmlFor="pair-device-name" className="mb-1.5 block text-sm font-medium">
+                  Device name <span className="text-[rgb(var(--muted))]">(optional)</span>
+                </label>
+                <input
+                  id="pair-device-name"
+                  data-testid="pair-device-name"
+                  type="text"
+                  autoFocus
+                  value={name}
+                  onChange={(e) => setName(e.target.value)}
+                  onKeyDown={(e) => {
+                    if (e.key === 'Enter' && !isCreating) void handleCreate();
+                  }}
+                  placeholder="Defaults to the name the device reports"
+                  className="w-full rounded-xl border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3.5 py-2.5 text-sm transition-all focus:border-[rgb(var(--accent))] focus:outline-none focus:ring-2 focus:ring-[rgb(var(--accent))]/40"
+                />
+              </div>
+
+              <div>
+                <label htmlFor="pair-device-lock-space" className="mb-1.5 block text-sm font-medium">
+                  Lock to a Space <span className="text-[rgb(var(--muted))]">(optional)</span>
+                </label>
+                <select
+                  id="pair-device-lock-space"
+                  data-testid="pair-device-lock-space"
+                  value={lockedSpaceId}
+                  onChange={(e) => setLockedSpaceId(e.target.value)}
+                  className="w-full rounded-xl border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3.5 py-2.5 text-sm transition-all focus:border-[rgb(var(--accent))] focus:outline-none focus:ring-2 focus:ring-[rgb(var(--accent))]/40"
+                >
+                  <option value="">No lock — route by mapping (any Space)</option>
+                  {spaces.map((s) => (
+                    <option key={s.id} value={s.id}>
+                      {s.name}
+                    </option>
+                  ))}
+                </select>
+              </div>
+
+              {error && (
+                <p className="text-sm text-red-600 dark:text-red-400" data-testid="pair-device-error">
+                  {error}
+                </p>
+              )}
+
+              <div className="flex justify-end gap-2">
+                <Button variant="ghost" size="md" onClick={onClose} disabled={isCreating}>
+                  Cancel
+                </Button>
+                <Button
+                  variant="primary"
+                  size="md"
+                  onClick={handleCreate}
+                  disabled={isCreating}
+                  data-testid="pair-device-create"
+                >
+                  {isCreating ? (
+                    <>
+                      <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                      Creating…
+                    </>
+                  ) : (
+                    'Create code'
+                  )}
+                </Button>
+              </div>
+            </>
+          )}
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
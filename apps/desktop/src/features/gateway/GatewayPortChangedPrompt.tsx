@@ -0,0 +1,63 @@
+import { useEffect } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { useConfirm, useToast, ToastContainer } from '@mcpmux/ui';
+import type { GatewayPortChangedPayload } from '@/lib/api/gateway';
+import { detectClientInstallations, reapplyClientConfigs } from '@/lib/api/clientInstall';
+
+/**
+ * Mounts at the app root and, when the gateway had to start on a port other
+ * than its preferred one, offers to point every client config that lists
+ * McpMux at the new URL in one go.
+ *
+ * Claude Desktop is left out: its config file can't point at the gateway.
+ */
+export function GatewayPortChangedPrompt() {
+  const { confirm, ConfirmDialogElement } = useConfirm();
+  const { toasts, success, error: showError, dismiss } = useToast();
+
+  useEffect(() => {
+    const unlisten = listen<GatewayPortChangedPayload>('gateway-port-changed', async (event) => {
+      const { preferredPort, port, url } = event.payload;
+      const clients = (await detectClientInstallations()).filter(
+        (c) => c.has_mcpmux && c.client !== 'claude_desktop'
+      );
+      if (clients.length === 0) return;
+
+      const ok = await confirm({
+        title: 'Gateway moved to another port',
+        message:
+          `The gateway is running on :${port} instead of :${preferredPort}, so ` +
+          `${clients.map((c) => c.label).join(', ')} can't reach it. Update their configs ` +
+          `to ${url}? Each file is backed up first.`,
+        confirmLabel: 'Update configs',
+      });
+      if (!ok) return;
+
+      try {
+        const result = await reapplyClientConfigs(url);
+        if (result.applied.length > 0) {
+          success('Client configs updated', result.applied.map((r) => r.path).join('\n'));
+        }
+        for (const failure of result.failed) {
+          const label = clients.find((c) => c.client === failure.client)?.label ?? failure.client;
+          showError(`Could not update ${label}`, failure.error);
+        }
+      } catch (e) {
+        showError('Could not update client configs', e instanceof Error ? e.message : String(e));
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+    // `confirm` and the toast helpers are stable; subscribe once on mount.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  return (
+    <>
+      {ConfirmDialogElement}
+      <ToastContainer toasts={toasts} onClose={dismiss} />
+    </>
+  );
+}
@@ -4,6 +4,7 @@ import {
   startGateway,
   restartGateway,
   parsePortInUseError,
+  describePortOwner,
 } from '@/lib/api/gateway';
 
 /**
@@ -57,13 +58,14 @@ export function useGatewayControl() {
     }
 
     console.log('[Gateway] preferred port taken → prompting user');
+    const holder = probe.owner ? describePortOwner(probe.owner) : 'another process';
     const ok = await confirm({
       title: 'Gateway port is in use',
       message:
         `${capitalize(sourceLabel(probe.source))} (:${probe.preferredPort}) is already ` +
-        `taken by another process. Start the gateway on a different port that the system ` +
-        `picks automatically? Your IDE configs will need to be updated to point at the new ` +
-        `port.`,
+        `taken by ${holder}. Start the gateway on a different port that the system ` +
+        `picks automatically? You'll be offered to update your IDE configs to point at the ` +
+        `new port.`,
       confirmLabel: 'Use another port',
       variant: 'default',
     });
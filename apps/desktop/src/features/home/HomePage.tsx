@@ -203,6 +203,10 @@ export function HomePage() {
     featureSets: 0,
   });
   const [statsLoaded, setStatsLoaded] = useState(false);
+  // Servers finished / total while the gateway connects enabled servers on start
+  const [autoConnect, setAutoConnect] = useState<{ finished: number; total: number } | null>(
+    null
+  );
   const viewSpace = useViewSpace();
 
   const loadStats = useCallback(async () => {
@@ -239,8 +243,17 @@ export function HomePage() {
       loadStats();
     } else if (payload.action === 'stopped') {
       setStats((prev) => ({ ...prev, connectedServers: 0 }));
+      setAutoConnect(null);
+    } else if (payload.action === 'auto_connect_progress') {
+      const finished =
+        (payload.connected ?? 0) + (payload.needs_oauth ?? 0) + (payload.failed ?? 0);
+      setAutoConnect({ finished, total: payload.total ?? 0 });
     }
   });
+  const autoConnectLabel =
+    autoConnect && autoConnect.finished < autoConnect.total
+      ? `Starting up · ${autoConnect.finished}/${autoConnect.total} done`
+      : null;
 
   useServerStatusEvents((payload) => {
     if (payload.status === 'connected' || payload.status === 'disconnected') {
@@ -289,7 +302,7 @@ export function HomePage() {
           valueTestId="stat-servers-value"
           icon={Server}
           label="Tools"
-          sub="Connected / Installed"
+          sub={autoConnectLabel ?? 'Connected / Installed'}
           value={`${stats.connectedServers}/${stats.installedServers}`}
           accent="hsl(199 65% 52%)"
           navTarget="servers"
@@ -6,6 +6,7 @@
 
 import { useEffect, useState } from 'react';
 import { ChevronDown } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
 import { useToast, ToastContainer } from '@mcpmux/ui';
 import { useRegistryStore } from '../../stores/registryStore';
 import { ServerCard } from './ServerCard';
@@ -26,6 +27,7 @@ export function RegistryPage() {
     error,
     selectedServer,
     isOffline,
+    lastSyncedAt,
     loadRegistry,
     setFilter,
     setSort,
@@ -83,6 +85,16 @@ export function RegistryPage() {
     loadRegistry(viewSpace?.id);
   }, [loadRegistry, setSpaceId, viewSpace?.id]);
 
+  // A local registry folder changed on disk
+  useEffect(() => {
+    const unlisten = listen('registry-updated', () => {
+      void loadRegistry(viewSpace?.id);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadRegistry, viewSpace?.id]);
+
   // Debounced search
   useEffect(() => {
     const timer = setTimeout(() => {
@@ -118,15 +130,28 @@ export function RegistryPage() {
   const handleInstall = async (id: string) => {
     const server = servers.find((s) => s.id === id);
     const serverName = server?.name || 'Server';
+    const trust = server?.source.type === 'Registry' ? server.source.trust : undefined;
+    if (
+      trust === 'untrusted' &&
+      !window.confirm(`"${serverName}" comes from an untrusted registry. Install it anyway?`)
+    ) {
+      return;
+    }
     try {
-      await installServer(id, viewSpace?.id);
-      success('Server installed', `"${serverName}" has been installed`, {
-        duration: 6000,
-        action: {
-          label: 'Go to Tools to enable →',
-          onClick: () => navigateTo('servers'),
-        },
-      });
+      await installServer(id, viewSpace?.id, trust === 'untrusted');
+      success(
+        'Server installed',
+        trust === 'trusted'
+          ? `"${serverName}" has been installed and enabled`
+          : `"${serverName}" has been installed`,
+        {
+          duration: 6000,
+          action: {
+            label: trust === 'trusted' ? 'Go to Tools →' : 'Go to Tools to enable →',
+            onClick: () => navigateTo('servers'),
+          },
+        }
+      );
     } catch {
       showToastError('Install failed', `Failed to install "${serverName}"`);
     }
@@ -171,7 +196,9 @@ export function RegistryPage() {
         </div>
         <p className="text-sm text-[rgb(var(--muted))]">
           {isOffline
-            ? 'Showing cached servers (no internet connection)'
+            ? lastSyncedAt
+              ? `Showing servers from the registry snapshot of ${new Date(lastSyncedAt).toLocaleString()} (no connection to the registry)`
+              : 'Showing cached servers (no internet connection)'
             : 'Browse the registry and add new tools to this Space in one click'}
         </p>
       </div>
@@ -133,6 +133,14 @@ export function ServerCard({
       {/* Badges */}
       <div className="flex flex-wrap gap-2 mb-4">
         {getBadges()}
+        {server.signature_status === 'verified' && (
+          <span
+            className="px-2 py-0.5 text-xs rounded-full bg-green-500/20 text-green-600 dark:text-green-400"
+            title="Signed by a trusted publisher key"
+          >
+            🔏 Signed
+          </span>
+        )}
         {getTransportBadge()}
         {getAuthBadge()}
         {server.capabilities?.read_only_mode && (
@@ -0,0 +1,269 @@
+/**
+ * EnvironmentProfileSwitcher - dev/staging/prod profiles for a space
+ *
+ * Switching the active profile swaps input values and URLs for every server
+ * of the space and reconnects the ones that change. Profiles are edited
+ * inline: shared input values as KEY=value lines, plus a URL per server.
+ */
+
+import { useEffect, useState, useCallback } from 'react';
+import { Layers, Pencil, Plus, Trash2, X } from 'lucide-react';
+import type { EnvironmentProfile, ProfileServerOverrides } from '@/lib/api/environmentProfiles';
+import {
+  listSpaceProfiles,
+  createSpaceProfile,
+  updateSpaceProfile,
+  deleteSpaceProfile,
+  setSpaceProfile,
+} from '@/lib/api/environmentProfiles';
+
+export interface EnvironmentProfileSwitcherProps {
+  spaceId: string;
+  servers: { id: string; name: string }[];
+  /** Called after servers were reconnected so the page can refresh state */
+  onServersChanged: () => void;
+  onToast: (message: string, type: 'success' | 'error' | 'info') => void;
+}
+
+interface ProfileDraft {
+  name: string;
+  inputs: string;
+  urls: Record<string, string>;
+}
+
+function toDraft(profile: EnvironmentProfile): ProfileDraft {
+  return {
+    name: profile.name,
+    inputs: Object.entries(profile.inputs)
+      .map(([key, value]) => `${key}=${value}`)
+      .join('\n'),
+    urls: Object.fromEntries(
+      Object.entries(profile.servers)
+        .filter(([, overrides]) => overrides.url)
+        .map(([id, overrides]) => [id, overrides.url as string])
+    ),
+  };
+}
+
+function parseInputs(text: string): Record<string, string> {
+  const inputs: Record<string, string> = {};
+  for (const line of text.split('\n')) {
+    const eq = line.indexOf('=');
+    if (eq <= 0) continue;
+    inputs[line.slice(0, eq).trim()] = line.slice(eq + 1);
+  }
+  return inputs;
+}
+
+export function EnvironmentProfileSwitcher({
+  spaceId,
+  servers,
+  onServersChanged,
+  onToast,
+}: EnvironmentProfileSwitcherProps) {
+  const [profiles, setProfiles] = useState<EnvironmentProfile[]>([]);
+  const [newName, setNewName] = useState<string | null>(null);
+  const [editing, setEditing] = useState<ProfileDraft | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const active = profiles.find((p) => p.is_active) ?? null;
+
+  const reload = useCallback(async () => {
+    try {
+      setProfiles(await listSpaceProfiles(spaceId));
+    } catch (e) {
+      console.error('[EnvironmentProfileSwitcher] Failed to load profiles:', e);
+    }
+  }, [spaceId]);
+
+  useEffect(() => {
+    setEditing(null);
+    reload();
+  }, [reload]);
+
+  const handleSwitch = async (profileId: string | null) => {
+    setBusy(true);
+    setEditing(null);
+    try {
+      const result = await setSpaceProfile(spaceId, profileId);
+      if (result.failed.length > 0) {
+        onToast(
+          `Reconnected ${result.reconnected.length}, failed ${result.failed.length}: ${result.failed
+            .map((f) => f.server_id)
+            .join(', ')}`,
+          'error'
+        );
+      } else if (result.reconnected.length > 0) {
+        onToast(`Reconnected ${result.reconnected.length} servers`, 'success');
+      }
+      await reload();
+      onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleCreate = async () => {
+    if (!newName?.trim()) {
+      setNewName(null);
+      return;
+    }
+    try {
+      await createSpaceProfile(spaceId, newName);
+      setNewName(null);
+      await reload();
+    } catch (e) {
+      onToast(String(e), 'error');
+    }
+  };
+
+  const handleSave = async (profile: EnvironmentProfile) => {
+    if (!editing) return;
+    const serverOverrides: Record<string, ProfileServerOverrides> = {};
+    for (const [id, overrides] of Object.entries(profile.servers)) {
+      serverOverrides[id] = { inputs: overrides.inputs };
+    }
+    for (const [id, url] of Object.entries(editing.urls)) {
+      if (!url.trim()) continue;
+      serverOverrides[id] = { inputs: serverOverrides[id]?.inputs ?? {}, url: url.trim() };
+    }
+    setBusy(true);
+    try {
+      await updateSpaceProfile(profile.id, editing.name, parseInputs(editing.inputs), serverOverrides);
+      setEditing(null);
+      await reload();
+      if (profile.is_active) onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleDelete = async (profile: EnvironmentProfile) => {
+    if (!window.confirm(`Delete profile "${profile.name}"?`)) return;
+    try {
+      await deleteSpaceProfile(profile.id);
+      setEditing(null);
+      await reload();
+      if (profile.is_active) onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    }
+  };
+
+  const actionButton =
+    'flex items-center gap-1.5 rounded-lg border border-[rgb(var(--border))] px-2.5 py-1 text-xs hover:bg-[rgb(var(--surface-hover))] disabled:opacity-50';
+
+  return (
+    <div className="space-y-2" data-testid="environment-profile-switcher">
+      <div className="flex flex-wrap items-center gap-2 text-xs">
+        <Layers className="h-3.5 w-3.5 text-[rgb(var(--muted))]" />
+        <span className="text-[rgb(var(--muted))]">Environment</span>
+        <select
+          value={active?.id ?? ''}
+          disabled={busy}
+          onChange={(e) => handleSwitch(e.target.value || null)}
+          className="rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-2 py-1 text-xs"
+          data-testid="environment-profile-select"
+        >
+          <option value="">Server settings</option>
+          {profiles.map((profile) => (
+            <option key={profile.id} value={profile.id}>
+              {profile.name}
+            </option>
+          ))}
+        </select>
+        {active && (
+          <>
+            <button
+              className={actionButton}
+              disabled={busy}
+              onClick={() => setEditing(editing ? null : toDraft(active))}
+            >
+              <Pencil className="h-3.5 w-3.5" />
+              Edit
+            </button>
+            <button className={actionButton} disabled={busy} onClick={() => handleDelete(active)}>
+              <Trash2 className="h-3.5 w-3.5" />
+              Delete
+            </button>
+          </>
+        )}
+        {newName === null ? (
+          <button
+            className="flex items-center gap-1 text-[rgb(var(--muted))] hover:text-[rgb(var(--foreground))]"
+            onClick={() => setNewName('')}
+          >
+            <Plus className="h-3.5 w-3.5" />
+            New profile
+          </button>
+        ) : (
+          <input
+            autoFocus
+            value={newName}
+            placeholder="staging"
+            onChange={(e) => setNewName(e.target.value)}
+            onBlur={handleCreate}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter') handleCreate();
+              if (e.key === 'Escape') setNewName(null);
+            }}
+            className="w-32 rounded-full border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1 text-xs"
+          />
+        )}
+      </div>
+
+      {active && editing && (
+        <div className="space-y-3 rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] p-3 text-xs">
+          <div className="flex items-center justify-between font-medium">
+            Profile settings
+            <button onClick={() => setEditing(null)} aria-label="Cancel">
+              <X className="h-3.5 w-3.5" />
+            </button>
+          </div>
+          <label className="block space-y-1">
+            <span className="text-[rgb(var(--muted))]">Name</span>
+            <input
+              value={editing.name}
+              onChange={(e) => setEditing({ ...editing, name: e.target.value })}
+              className="w-full rounded border border-[rgb(var(--border))] bg-transparent px-2 py-1"
+            />
+          </label>
+          <label className="block space-y-1">
+            <span className="text-[rgb(var(--muted))]">
+              Input values, one KEY=value per line (applied to servers that declare the input)
+            </span>
+            <textarea
+              rows={4}
+              value={editing.inputs}
+              onChange={(e) => setEditing({ ...editing, inputs: e.target.value })}
+              className="w-full rounded border border-[rgb(var(--border))] bg-transparent px-2 py-1 font-mono"
+            />
+          </label>
+          <div className="space-y-1">
+            <span className="text-[rgb(var(--muted))]">URLs (HTTP servers only)</span>
+            {servers.map((server) => (
+              <div key={server.id} className="flex items-center gap-2">
+                <span className="w-40 truncate">{server.name}</span>
+                <input
+                  value={editing.urls[server.id] ?? ''}
+                  placeholder="Server default"
+                  onChange={(e) =>
+                    setEditing({ ...editing, urls: { ...editing.urls, [server.id]: e.target.value } })
+                  }
+                  className="flex-1 rounded border border-[rgb(var(--border))] bg-transparent px-2 py-1"
+                />
+              </div>
+            ))}
+          </div>
+          <button className={actionButton} disabled={busy} onClick={() => handleSave(active)}>
+            Save
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
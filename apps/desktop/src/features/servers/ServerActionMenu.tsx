@@ -7,11 +7,21 @@
  * - Reconnect: Logout + re-authenticate (OAuth only)
  * - View Logs: Open log viewer
  * - View Definition: View server definition JSON
+ * - Validate Arguments: Toggle gateway checks of tool arguments
  * - Uninstall: Remove server
  */
 
 import { useState, useRef, useEffect } from 'react';
-import { MoreVertical, Settings, RefreshCw, RotateCcw, FileText, Code, Trash2 } from 'lucide-react';
+import {
+  MoreVertical,
+  Settings,
+  RefreshCw,
+  RotateCcw,
+  FileText,
+  Code,
+  ShieldCheck,
+  Trash2,
+} from 'lucide-react';
 
 export interface ServerActionMenuProps {
   serverId: string;
@@ -25,6 +35,8 @@ export interface ServerActionMenuProps {
   onReconnect: () => void;
   onViewLogs: () => void;
   onViewDefinition: () => void;
+  validatesArguments: boolean;
+  onToggleArgumentValidation: () => void;
   onUninstall: () => void;
 }
 
@@ -40,6 +52,8 @@ export function ServerActionMenu({
   onReconnect,
   onViewLogs,
   onViewDefinition,
+  validatesArguments,
+  onToggleArgumentValidation,
   onUninstall,
 }: ServerActionMenuProps) {
   const [isOpen, setIsOpen] = useState(false);
@@ -163,6 +177,20 @@ export function ServerActionMenu({
             View Definition
           </button>
 
+          {/* Validate Arguments - gateway schema check, on unless the server's schemas are wrong */}
+          <button
+            onClick={() => handleAction(onToggleArgumentValidation)}
+            className="w-full flex items-center gap-2 px-3 py-2 text-sm text-[rgb(var(--foreground))] hover:bg-[rgb(var(--surface-hover))] transition-colors"
+            role="menuitemcheckbox"
+            aria-checked={validatesArguments}
+            data-testid={`validate-arguments-${serverId}`}
+          >
+            <ShieldCheck
+              className={`h-4 w-4 ${validatesArguments ? 'text-[rgb(var(--success))]' : 'text-[rgb(var(--muted))]'}`}
+            />
+            {validatesArguments ? 'Validate Arguments: On' : 'Validate Arguments: Off'}
+          </button>
+
           {/* Separator */}
           <div className="my-1 border-t border-[rgb(var(--border-subtle))]" />
 
@@ -0,0 +1,276 @@
+/**
+ * ServerGroupsBar - Group chips above the server list
+ *
+ * Selecting a group filters the list to its servers and shows the bulk
+ * actions: Enable all, Disable all, Reconnect, a FeatureSet with every
+ * feature of the group, and editing which servers are in the group.
+ */
+
+import { useEffect, useState, useCallback } from 'react';
+import { FolderPlus, Power, PowerOff, RefreshCw, Package, Pencil, Trash2, X } from 'lucide-react';
+import type { ServerGroup, ServerGroupAction } from '@/lib/api/serverGroups';
+import {
+  listServerGroups,
+  createServerGroup,
+  deleteServerGroup,
+  setServerGroupServers,
+  syncServerGroupFeatureSet,
+  runServerGroupAction,
+} from '@/lib/api/serverGroups';
+
+export interface ServerGroupsBarProps {
+  spaceId: string;
+  servers: { id: string; name: string }[];
+  selectedGroup: ServerGroup | null;
+  onSelectGroup: (group: ServerGroup | null) => void;
+  /** Called after a bulk action so the page can refresh server state */
+  onServersChanged: () => void;
+  onToast: (message: string, type: 'success' | 'error' | 'info') => void;
+}
+
+const ACTION_LABELS: Record<ServerGroupAction, string> = {
+  enable: 'Enabled',
+  disable: 'Disabled',
+  reconnect: 'Reconnected',
+};
+
+export function ServerGroupsBar({
+  spaceId,
+  servers,
+  selectedGroup,
+  onSelectGroup,
+  onServersChanged,
+  onToast,
+}: ServerGroupsBarProps) {
+  const [groups, setGroups] = useState<ServerGroup[]>([]);
+  const [newName, setNewName] = useState<string | null>(null);
+  const [editing, setEditing] = useState<Set<string> | null>(null);
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      setGroups(await listServerGroups(spaceId));
+    } catch (e) {
+      console.error('[ServerGroupsBar] Failed to load groups:', e);
+    }
+  }, [spaceId]);
+
+  useEffect(() => {
+    reload();
+    onSelectGroup(null);
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [spaceId]);
+
+  const select = (group: ServerGroup | null) => {
+    setEditing(null);
+    onSelectGroup(group);
+  };
+
+  const handleCreate = async () => {
+    if (!newName?.trim()) {
+      setNewName(null);
+      return;
+    }
+    try {
+      const group = await createServerGroup(spaceId, newName);
+      setNewName(null);
+      await reload();
+      select(group);
+    } catch (e) {
+      onToast(String(e), 'error');
+    }
+  };
+
+  const handleAction = async (action: ServerGroupAction) => {
+    if (!selectedGroup) return;
+    setBusy(action);
+    try {
+      const result = await runServerGroupAction(selectedGroup.id, action);
+      if (result.failed.length > 0) {
+        onToast(
+          `${ACTION_LABELS[action]} ${result.succeeded.length}, failed ${result.failed.length}: ${result.failed
+            .map((f) => f.server_id)
+            .join(', ')}`,
+          'error'
+        );
+      } else {
+        onToast(`${ACTION_LABELS[action]} ${result.succeeded.length} servers`, 'success');
+      }
+      onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleSyncFeatureSet = async () => {
+    if (!selectedGroup) return;
+    setBusy('feature-set');
+    try {
+      await syncServerGroupFeatureSet(selectedGroup.id);
+      onToast(`FeatureSet "${selectedGroup.name}" is up to date`, 'success');
+      await reload();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleSaveMembers = async () => {
+    if (!selectedGroup || !editing) return;
+    try {
+      const ordered = servers.map((s) => s.id).filter((id) => editing.has(id));
+      const group = await setServerGroupServers(selectedGroup.id, ordered);
+      setEditing(null);
+      await reload();
+      onSelectGroup(group);
+    } catch (e) {
+      onToast(String(e), 'error');
+    }
+  };
+
+  const handleDelete = async () => {
+    if (!selectedGroup) return;
+    if (!window.confirm(`Delete group "${selectedGroup.name}"? Its servers stay installed.`)) return;
+    try {
+      await deleteServerGroup(selectedGroup.id);
+      select(null);
+      await reload();
+    } catch (e) {
+      onToast(String(e), 'error');
+    }
+  };
+
+  const chip = (active: boolean) =>
+    `rounded-full border px-3 py-1 text-xs font-medium transition-colors ${
+      active
+        ? 'border-[rgb(var(--primary))] bg-[rgb(var(--primary))]/10 text-[rgb(var(--primary))]'
+        : 'border-[rgb(var(--border))] hover:bg-[rgb(var(--surface-hover))]'
+    }`;
+  const actionButton =
+    'flex items-center gap-1.5 rounded-lg border border-[rgb(var(--border))] px-2.5 py-1 text-xs hover:bg-[rgb(var(--surface-hover))] disabled:opacity-50';
+
+  return (
+    <div className="space-y-2" data-testid="server-groups-bar">
+      <div className="flex flex-wrap items-center gap-2">
+        <button className={chip(selectedGroup === null)} onClick={() => select(null)}>
+          All servers
+        </button>
+        {groups.map((group) => (
+          <button
+            key={group.id}
+            className={chip(selectedGroup?.id === group.id)}
+            onClick={() => select(group)}
+            data-testid={`server-group-${group.name}`}
+          >
+            {group.icon && <span className="mr-1">{group.icon}</span>}
+            {group.name}
+            <span className="ml-1.5 text-[rgb(var(--muted))]">{group.server_ids.length}</span>
+          </button>
+        ))}
+        {newName === null ? (
+          <button
+            className="flex items-center gap-1 text-xs text-[rgb(var(--muted))] hover:text-[rgb(var(--foreground))]"
+            onClick={() => setNewName('')}
+          >
+            <FolderPlus className="h-3.5 w-3.5" />
+            New group
+          </button>
+        ) : (
+          <input
+            autoFocus
+            value={newName}
+            placeholder="Group name"
+            onChange={(e) => setNewName(e.target.value)}
+            onBlur={handleCreate}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter') handleCreate();
+              if (e.key === 'Escape') setNewName(null);
+            }}
+            className="w-36 rounded-full border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1 text-xs"
+          />
+        )}
+      </div>
+
+      {selectedGroup && (
+        <div className="flex flex-wrap items-center gap-2">
+          <button
+            className={actionButton}
+            disabled={busy !== null}
+            onClick={() => handleAction('enable')}
+          >
+            <Power className="h-3.5 w-3.5" />
+            Enable all
+          </button>
+          <button
+            className={actionButton}
+            disabled={busy !== null}
+            onClick={() => handleAction('disable')}
+          >
+            <PowerOff className="h-3.5 w-3.5" />
+            Disable all
+          </button>
+          <button
+            className={actionButton}
+            disabled={busy !== null}
+            onClick={() => handleAction('reconnect')}
+          >
+            <RefreshCw className={`h-3.5 w-3.5 ${busy === 'reconnect' ? 'animate-spin' : ''}`} />
+            Reconnect
+          </button>
+          <button className={actionButton} disabled={busy !== null} onClick={handleSyncFeatureSet}>
+            <Package className="h-3.5 w-3.5" />
+            {selectedGroup.feature_set_id ? 'Refresh FeatureSet' : 'Create FeatureSet'}
+          </button>
+          <button
+            className={actionButton}
+            onClick={() => setEditing(editing ? null : new Set(selectedGroup.server_ids))}
+          >
+            <Pencil className="h-3.5 w-3.5" />
+            Edit servers
+          </button>
+          <button className={actionButton} onClick={handleDelete}>
+            <Trash2 className="h-3.5 w-3.5" />
+            Delete group
+          </button>
+        </div>
+      )}
+
+      {selectedGroup && editing && (
+        <div className="rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] p-3">
+          <div className="mb-2 flex items-center justify-between text-xs font-medium">
+            Servers in {selectedGroup.name}
+            <button onClick={() => setEditing(null)} aria-label="Cancel">
+              <X className="h-3.5 w-3.5" />
+            </button>
+          </div>
+          <div className="grid grid-cols-2 gap-1">
+            {servers.map((server) => (
+              <label key={server.id} className="flex items-center gap-2 text-xs">
+                <input
+                  type="checkbox"
+                  checked={editing.has(server.id)}
+                  onChange={(e) => {
+                    const next = new Set(editing);
+                    if (e.target.checked) next.add(server.id);
+                    else next.delete(server.id);
+                    setEditing(next);
+                  }}
+                />
+                {server.name}
+              </label>
+            ))}
+          </div>
+          <button
+            onClick={handleSaveMembers}
+            className="mt-3 rounded-lg bg-[rgb(var(--primary))] px-3 py-1.5 text-xs text-[rgb(var(--primary-foreground))] hover:bg-[rgb(var(--primary-hover))]"
+          >
+            Save
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
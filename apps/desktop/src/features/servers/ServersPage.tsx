@@ -21,26 +21,56 @@ import {
   FolderOpen,
   Compass,
   ArrowRight,
+  Activity,
 } from 'lucide-react';
 import { PageHeader } from '@mcpmux/ui';
 import { ServerActionMenu } from './ServerActionMenu';
+import { ServerGroupsBar } from './ServerGroupsBar';
+import { TrashedServers } from './TrashedServers';
+import { UnusedServers } from './UnusedServers';
+import { EnvironmentProfileSwitcher } from './EnvironmentProfileSwitcher';
+import type { ServerGroup } from '@/lib/api/serverGroups';
 import type {
   ServerViewModel,
   ServerDefinition,
   InstalledServerState,
   InputDefinition,
+  ToolHooks,
+  WasmPermissions,
 } from '../../types/registry';
 import type { ServerFeature } from '@/lib/api/serverFeatures';
-import { listServerFeaturesByServer } from '@/lib/api/serverFeatures';
-import type { ConnectionStatus, ServerStatusResponse } from '@/lib/api/serverManager';
-import { getServerStatuses as fetchServerStatuses } from '@/lib/api/serverManager';
+import {
+  acceptFeatureSchema,
+  listServerFeaturesByServer,
+  setFeatureSchemaPinned,
+} from '@/lib/api/serverFeatures';
+import type {
+  ConnectionStatus,
+  DiagnosedCause,
+  ServerStatusResponse,
+} from '@/lib/api/serverManager';
+import {
+  getServerStatuses as fetchServerStatuses,
+  checkServerUpdates,
+  updateServerPackage,
+} from '@/lib/api/serverManager';
 import { useViewSpace, useNavigateTo } from '@/stores';
 import { useServerManager } from '@/hooks/useServerManager';
 import { useGatewayControl } from '@/features/gateway/useGatewayControl';
 import { useGatewayEvents, useDomainEvents } from '@/hooks/useDomainEvents';
-import type { GatewayChangedPayload, ServerChangedPayload } from '@/hooks/useDomainEvents';
+import type {
+  GatewayChangedPayload,
+  RuntimeMissingPayload,
+  ServerChangedPayload,
+  ServerCrashLoopPayload,
+  ServerUpdateAvailablePayload,
+  SpaceConfigInvalidPayload,
+  ToolSchemaChangedPayload,
+} from '@/hooks/useDomainEvents';
+import { openExternal } from '@/lib/contribute';
 import type { FeaturesUpdatedEvent } from '@/lib/api/serverManager';
 import { ServerLogViewer } from '@/components/ServerLogViewer';
+import { ToolTestDialog } from '@/components/ToolTestDialog';
 import { ConfigEditorModal } from '@/components/ConfigEditorModal';
 import { ServerDefinitionModal } from '@/components/ServerDefinitionModal';
 import { SourceBadge } from '@/components/SourceBadge';
@@ -81,6 +111,12 @@ function mergeDefinitionsWithStates(
       env_overrides: state?.env_overrides ?? {},
       args_append: state?.args_append ?? [],
       extra_headers: state?.extra_headers ?? {},
+      skip_argument_validation: state?.skip_argument_validation ?? false,
+      timeout_overrides: state?.timeouts ?? {},
+      tool_hooks: state?.tool_hooks ?? {},
+      call_context: state?.call_context ?? {},
+      wasm_grants: state?.wasm_grants ?? {},
+      fs_allowed_roots: state?.fs_allowed_roots ?? [],
     } as ServerViewModel;
   });
 }
@@ -111,6 +147,12 @@ function createOfflineServerViewModel(state: InstalledServerState): ServerViewMo
         env_overrides: state.env_overrides ?? {},
         args_append: state.args_append ?? [],
         extra_headers: state.extra_headers ?? {},
+        skip_argument_validation: state.skip_argument_validation ?? false,
+        timeout_overrides: state.timeouts ?? {},
+        tool_hooks: state.tool_hooks ?? {},
+        call_context: state.call_context ?? {},
+        wasm_grants: state.wasm_grants ?? {},
+        fs_allowed_roots: state.fs_allowed_roots ?? [],
       } as ServerViewModel;
     } catch (e) {
       console.warn('[ServersPage] Failed to parse cached_definition, using minimal fallback:', e);
@@ -147,6 +189,12 @@ function createOfflineServerViewModel(state: InstalledServerState): ServerViewMo
     env_overrides: state.env_overrides ?? {},
     args_append: state.args_append ?? [],
     extra_headers: state.extra_headers ?? {},
+    skip_argument_validation: state.skip_argument_validation ?? false,
+    timeout_overrides: state.timeouts ?? {},
+    tool_hooks: state.tool_hooks ?? {},
+    call_context: state.call_context ?? {},
+    wasm_grants: state.wasm_grants ?? {},
+    fs_allowed_roots: state.fs_allowed_roots ?? [],
   } as ServerViewModel;
 }
 
@@ -162,14 +210,67 @@ interface ConfigModalState {
   argsAppend: string[];
   /** Extra HTTP headers (http only) */
   extraHeaders: Record<string, string>;
+  /** Connect timeout override in seconds ('' = default) */
+  connectTimeout: string;
+  /** Request timeout override in seconds ('' = default) */
+  requestTimeout: string;
+  /** Tool hooks as JSON ('' = none) */
+  toolHooks: string;
+  /** `_meta` fields added to tool calls */
+  callContext: Record<string, string>;
+  /** Access granted to a WASI server */
+  wasmGrants: WasmPermissions;
+  /** Directories a WASI server may use, if granted filesystem access */
+  wasmDirs: string[];
+}
+
+/** Timeout field value for the config form */
+function timeoutField(secs: number | undefined): string {
+  return secs === undefined ? '' : String(secs);
+}
+
+/** Example shown in the empty tool hooks field */
+const TOOL_HOOKS_PLACEHOLDER = `{
+  "before": [{ "action": "default", "path": "project_id", "value": "acme" }],
+  "after": [{ "action": "redact", "pattern": "\\\\S+@\\\\S+" }]
+}`;
+
+/** Tool hooks field value for the config form */
+function hooksField(hooks: ToolHooks | undefined): string {
+  const empty = !hooks?.before?.length && !hooks?.after?.length;
+  return empty ? '' : JSON.stringify(hooks, null, 2);
+}
+
+/** Parse the tool hooks field; empty means no hooks */
+function parseHooksField(value: string): ToolHooks {
+  if (value.trim() === '') return {};
+  try {
+    return JSON.parse(value) as ToolHooks;
+  } catch (e) {
+    throw new Error(`Tool hooks are not valid JSON: ${e instanceof Error ? e.message : e}`);
+  }
+}
+
+/** Parse a timeout field; empty means "use the default" */
+function parseTimeoutField(value: string): number | null {
+  const trimmed = value.trim();
+  if (trimmed === '') return null;
+  const secs = Number(trimmed);
+  if (!Number.isInteger(secs) || secs < 0) {
+    throw new Error('Timeouts must be a whole number of seconds');
+  }
+  return secs;
 }
 
 export function ServersPage() {
   const [installedServers, setInstalledServers] = useState<ServerViewModel[]>([]);
+  const [trashedServers, setTrashedServers] = useState<InstalledServerState[]>([]);
+  const [unusedServers, setUnusedServers] = useState<InstalledServerState[]>([]);
   const [gatewayRunning, setGatewayRunning] = useState(false);
   const [gatewayUrl, setGatewayUrl] = useState<string | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [actionLoading, setActionLoading] = useState<string | null>(null);
+  const [selectedGroup, setSelectedGroup] = useState<ServerGroup | null>(null);
   const gatewayControl = useGatewayControl();
   // Bottom toast notifications
   const [toast, setToast] = useState<{
@@ -183,6 +284,12 @@ export function ServersPage() {
     envOverrides: {},
     argsAppend: [],
     extraHeaders: {},
+    connectTimeout: '',
+    requestTimeout: '',
+    toolHooks: '',
+    callContext: {},
+    wasmGrants: {},
+    wasmDirs: [],
   });
 
   // Features state
@@ -190,8 +297,22 @@ export function ServersPage() {
   const [expandedServers, setExpandedServers] = useState<Set<string>>(new Set());
   const [loadingFeatures, setLoadingFeatures] = useState<Set<string>>(new Set());
 
+  // Servers that failed because no npx/uvx/... is installed
+  const [missingRuntimes, setMissingRuntimes] = useState<Record<string, RuntimeMissingPayload>>(
+    {}
+  );
+
+  // Diagnoses of stdio servers that keep exiting during startup
+  const [crashLoops, setCrashLoops] = useState<Record<string, DiagnosedCause>>({});
+
+  // Newer releases of pinned npx/uvx packages, keyed by server id
+  const [availableUpdates, setAvailableUpdates] = useState<
+    Record<string, ServerUpdateAvailablePayload>
+  >({});
+
   // Log viewer state
   const [logViewerServer, setLogViewerServer] = useState<{ id: string; name: string } | null>(null);
+  const [testTool, setTestTool] = useState<ServerFeature | null>(null);
 
   // Definition viewer state
   const [definitionServer, setDefinitionServer] = useState<{ id: string; name: string } | null>(
@@ -307,6 +428,87 @@ export function ServersPage() {
     });
   }, [viewSpace?.id]);
 
+  useEffect(() => {
+    return subscribe('runtime-missing', (payload: RuntimeMissingPayload) => {
+      if (!viewSpace || payload.space_id !== viewSpace.id) {
+        return;
+      }
+      setMissingRuntimes((prev) => ({ ...prev, [payload.server_id]: payload }));
+    });
+  }, [viewSpace?.id]);
+
+  useEffect(() => {
+    return subscribe('server-crash-loop', (payload: ServerCrashLoopPayload) => {
+      if (!viewSpace || payload.space_id !== viewSpace.id) {
+        return;
+      }
+      setCrashLoops((prev) => ({ ...prev, [payload.server_id]: payload.cause }));
+      showToast(`${payload.server_id}: ${payload.cause.summary}`, 'error');
+    });
+  }, [viewSpace?.id]);
+
+  useEffect(() => {
+    return subscribe('tool-schema-changed', (payload: ToolSchemaChangedPayload) => {
+      if (!viewSpace || payload.space_id !== viewSpace.id) {
+        return;
+      }
+      if (payload.held) {
+        showToast(`${payload.server_id}: schema of pinned tool ${payload.tool} changed`, 'info');
+        loadFeaturesForServer(payload.server_id);
+      } else if (payload.breaking) {
+        showToast(`${payload.server_id}: tool ${payload.tool} changed its parameters`, 'info');
+      }
+    });
+  }, [viewSpace?.id]);
+
+  // The Space file doesn't match the schema, so its servers stay as they
+  // were until it's fixed. The config editor shows the issues itself.
+  useEffect(() => {
+    return subscribe('space-config-invalid', (payload: SpaceConfigInvalidPayload) => {
+      if (
+        !viewSpace ||
+        payload.space_id !== viewSpace.id ||
+        editConfigSpace ||
+        payload.issues.length === 0
+      ) {
+        return;
+      }
+      const [first] = payload.issues;
+      const more = payload.issues.length > 1 ? ` (+${payload.issues.length - 1} more)` : '';
+      showToast(
+        `Space config not applied: line ${first.line}: ${first.path} ${first.message}${more}`,
+        'error'
+      );
+    });
+  }, [viewSpace?.id, editConfigSpace]);
+
+  useEffect(() => {
+    return subscribe('server-update-available', (payload: ServerUpdateAvailablePayload) => {
+      if (!viewSpace || payload.space_id !== viewSpace.id) {
+        return;
+      }
+      setAvailableUpdates((prev) => ({ ...prev, [payload.server_id]: payload }));
+    });
+  }, [viewSpace?.id]);
+
+  // The gateway checks daily; also check when the page opens
+  useEffect(() => {
+    if (!gatewayRunning || !viewSpace) {
+      return;
+    }
+    checkServerUpdates()
+      .then((updates) =>
+        setAvailableUpdates(
+          Object.fromEntries(
+            updates
+              .filter((update) => update.space_id === viewSpace.id)
+              .map((update) => [update.server_id, update])
+          )
+        )
+      )
+      .catch((e) => console.warn('[ServersPage] Update check failed:', e));
+  }, [gatewayRunning, viewSpace?.id]);
+
   // Note: Server status changes are handled by useServerManager hook
   // which updates serverStatuses state via events. No need to re-fetch
   // server definitions on status changes - they don't change.
@@ -380,6 +582,13 @@ export function ServersPage() {
           server.last_error = runtime.message || null;
         }
       }
+      setCrashLoops(
+        Object.fromEntries(
+          Object.values(runtimeStatuses)
+            .filter((runtime) => runtime.diagnosis)
+            .map((runtime) => [runtime.server_id, runtime.diagnosis as DiagnosedCause])
+        )
+      );
 
       // Sort by installation time (newest first)
       mergedServers.sort((a, b) => {
@@ -389,6 +598,20 @@ export function ServersPage() {
       });
 
       setInstalledServers(mergedServers);
+      setTrashedServers(
+        viewSpace?.id
+          ? await import('@/lib/api/registry')
+              .then((m) => m.listTrashedServers(viewSpace.id))
+              .catch(() => [])
+          : []
+      );
+      setUnusedServers(
+        viewSpace?.id
+          ? await import('@/lib/api/registry')
+              .then((m) => m.getUnusedServers(viewSpace.id))
+              .catch(() => [])
+          : []
+      );
       setGatewayRunning(gateway.running);
       setGatewayUrl(gateway.url);
     } catch (e) {
@@ -420,6 +643,24 @@ export function ServersPage() {
     }
   };
 
+  // Pin a tool's schema, or accept the change its server made
+  const updateToolSchema = async (feature: ServerFeature, action: 'pin' | 'unpin' | 'accept') => {
+    try {
+      const updated =
+        action === 'accept'
+          ? await acceptFeatureSchema(feature.id)
+          : await setFeatureSchemaPinned(feature.id, action === 'pin');
+      setServerFeatures((prev) => ({
+        ...prev,
+        [feature.server_id]: (prev[feature.server_id] || []).map((f) =>
+          f.id === updated.id ? updated : f
+        ),
+      }));
+    } catch (e) {
+      showToast(`Failed to update ${feature.feature_name}: ${e}`, 'error');
+    }
+  };
+
   // Toggle server expansion
   const toggleExpanded = (serverId: string) => {
     setExpandedServers((prev) => {
@@ -578,6 +819,12 @@ export function ServersPage() {
         envOverrides: { ...(server.env_overrides ?? {}) },
         argsAppend: [...(server.args_append ?? [])],
         extraHeaders: { ...(server.extra_headers ?? {}) },
+        connectTimeout: timeoutField(server.timeout_overrides?.connect_secs),
+        requestTimeout: timeoutField(server.timeout_overrides?.request_secs),
+        toolHooks: hooksField(server.tool_hooks),
+        callContext: { ...(server.call_context?.meta ?? {}) },
+        wasmGrants: { ...(server.wasm_grants ?? {}) },
+        wasmDirs: [...(server.fs_allowed_roots ?? [])],
       });
       return;
     }
@@ -649,6 +896,12 @@ export function ServersPage() {
       envOverrides: { ...(server.env_overrides ?? {}) },
       argsAppend: [...(server.args_append ?? [])],
       extraHeaders: { ...(server.extra_headers ?? {}) },
+      connectTimeout: timeoutField(server.timeout_overrides?.connect_secs),
+      requestTimeout: timeoutField(server.timeout_overrides?.request_secs),
+      toolHooks: hooksField(server.tool_hooks),
+      callContext: { ...(server.call_context?.meta ?? {}) },
+      wasmGrants: { ...(server.wasm_grants ?? {}) },
+      wasmDirs: [...(server.fs_allowed_roots ?? [])],
     });
   };
 
@@ -661,7 +914,56 @@ export function ServersPage() {
 
     setActionLoading(`config-${serverId}`);
     try {
-      const { saveServerInputs } = await import('@/lib/api/registry');
+      const {
+        saveServerInputs,
+        setServerTimeouts,
+        setServerToolHooks,
+        setServerCallContext,
+        setServerWasmGrants,
+        setServerFsAllowedRoots,
+      } = await import('@/lib/api/registry');
+
+      // Timeouts are validated by the backend; only save them if they changed
+      const connectSecs = parseTimeoutField(configModal.connectTimeout);
+      const requestSecs = parseTimeoutField(configModal.requestTimeout);
+      if (
+        connectSecs !== (server.timeout_overrides?.connect_secs ?? null) ||
+        requestSecs !== (server.timeout_overrides?.request_secs ?? null)
+      ) {
+        await setServerTimeouts(serverId, connectSecs, requestSecs, viewSpace?.id ?? '');
+      }
+
+      // Hooks are validated by the backend too; compare as saved
+      const toolHooks = parseHooksField(configModal.toolHooks);
+      if (hooksField(toolHooks) !== hooksField(server.tool_hooks)) {
+        await setServerToolHooks(serverId, toolHooks, viewSpace?.id ?? '');
+      }
+
+      const contextMeta = Object.fromEntries(
+        Object.entries(configModal.callContext).filter(([key]) => key.trim() !== '')
+      );
+      if (JSON.stringify(contextMeta) !== JSON.stringify(server.call_context?.meta ?? {})) {
+        await setServerCallContext(serverId, { meta: contextMeta }, viewSpace?.id ?? '');
+      }
+
+      if (server.transport.type === 'wasm') {
+        const dirs = configModal.wasmDirs.map((d) => d.trim()).filter((d) => d.length > 0);
+        if (dirs.join('\n') !== (server.fs_allowed_roots ?? []).join('\n')) {
+          await setServerFsAllowedRoots(serverId, dirs, viewSpace?.id ?? '');
+        }
+        const grants = configModal.wasmGrants;
+        if (
+          !!grants.filesystem !== !!server.wasm_grants?.filesystem ||
+          !!grants.network !== !!server.wasm_grants?.network
+        ) {
+          await setServerWasmGrants(
+            serverId,
+            !!grants.filesystem,
+            !!grants.network,
+            viewSpace?.id ?? ''
+          );
+        }
+      }
 
       // Save input values with env overrides, args, and headers.
       // Always send the values (even if empty) so that clearing them works.
@@ -683,6 +985,12 @@ export function ServersPage() {
         envOverrides: {},
         argsAppend: [],
         extraHeaders: {},
+        connectTimeout: '',
+        requestTimeout: '',
+        toolHooks: '',
+        callContext: {},
+        wasmGrants: {},
+        wasmDirs: [],
       });
 
       // Only enable if requested (from Enable flow)
@@ -730,6 +1038,12 @@ export function ServersPage() {
       envOverrides: {},
       argsAppend: [],
       extraHeaders: {},
+      connectTimeout: '',
+      requestTimeout: '',
+      toolHooks: '',
+      callContext: {},
+      wasmGrants: {},
+      wasmDirs: [],
     });
   };
 
@@ -766,6 +1080,41 @@ export function ServersPage() {
     }
   };
 
+  const handleUpdatePackage = async (server: ServerViewModel) => {
+    if (!viewSpace) return;
+    setActionLoading(`update-${server.id}`);
+    try {
+      const version = await updateServerPackage(viewSpace.id, server.id);
+      setAvailableUpdates((prev) => {
+        const next = { ...prev };
+        delete next[server.id];
+        return next;
+      });
+      showToast(`${server.name} updated to ${version}`, 'success');
+    } catch (e) {
+      showToast(String(e), 'error');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
+  const handleToggleArgumentValidation = async (server: ServerViewModel) => {
+    const skip = !server.skip_argument_validation;
+    try {
+      const { setServerSkipArgumentValidation } = await import('@/lib/api/registry');
+      await setServerSkipArgumentValidation(server.id, skip, viewSpace?.id ?? '');
+      await loadData();
+      showToast(
+        skip
+          ? `${server.name}: tool arguments are no longer validated`
+          : `${server.name}: tool arguments are validated`,
+        'success'
+      );
+    } catch (e) {
+      showToast(String(e), 'error');
+    }
+  };
+
   const handleUninstall = async (server: ServerViewModel) => {
     const { getUninstallLabel } = await import('@/components/SourceBadge');
     const actionLabel = getUninstallLabel(server.installation_source);
@@ -785,7 +1134,7 @@ export function ServersPage() {
 
       // ServerAppService handles source-aware cleanup automatically:
       // - UserConfig: removes from JSON file + DB
-      // - Registry/ManualEntry: just removes from DB
+      // - Registry/ManualEntry: moves to the trash, restorable below
       await uninstallServer(server.id, viewSpace?.id ?? '');
       await loadData();
       showToast(`${server.name} ${actionLabel.toLowerCase()}ed`, 'success');
@@ -978,7 +1327,27 @@ export function ServersPage() {
         </div>
       ) : (
         <div className="space-y-3">
-          {installedServers.map((server) => {
+          {viewSpace && (
+            <EnvironmentProfileSwitcher
+              spaceId={viewSpace.id}
+              servers={installedServers.map((s) => ({ id: s.id, name: s.name }))}
+              onServersChanged={loadData}
+              onToast={showToast}
+            />
+          )}
+          {viewSpace && (
+            <ServerGroupsBar
+              spaceId={viewSpace.id}
+              servers={installedServers.map((s) => ({ id: s.id, name: s.name }))}
+              selectedGroup={selectedGroup}
+              onSelectGroup={setSelectedGroup}
+              onServersChanged={loadData}
+              onToast={showToast}
+            />
+          )}
+          {installedServers
+            .filter((server) => !selectedGroup || selectedGroup.server_ids.includes(server.id))
+            .map((server) => {
             const serverAction = getServerAction(server);
             const displayStatus = getDisplayStatus(server);
             const enableLoading = actionLoading === `enable-${server.id}`;
@@ -993,6 +1362,11 @@ export function ServersPage() {
             const isConnected = serverAction === 'running' || serverAction === 'connected_auto';
             const isAuthenticating = serverAction === 'authenticating';
             const runtimeMessage = serverStatuses[server.id]?.message;
+            const health = isConnected ? serverStatuses[server.id]?.health : null;
+            const missingRuntime = missingRuntimes[server.id];
+            const crashLoop = crashLoops[server.id];
+            const update = availableUpdates[server.id];
+            const updateLoading = actionLoading === `update-${server.id}`;
 
             return (
               <div
@@ -1100,6 +1474,32 @@ export function ServersPage() {
                             </>
                           )}
 
+                          {/* Ping health for connected servers */}
+                          {health && health.checks > 0 && (
+                            <span
+                              className={`inline-flex items-center gap-1 rounded-md px-2 py-0.5 text-xs ${
+                                health.healthy
+                                  ? 'bg-[rgb(var(--surface-hover))] text-[rgb(var(--muted))]'
+                                  : 'bg-[rgb(var(--warning))]/15 text-[rgb(var(--warning))]'
+                              }`}
+                              title={[
+                                `${Math.round(health.error_rate * 100)}% of the last ${health.checks} pings failed`,
+                                health.p95_latency_ms !== null
+                                  ? `p95 latency ${health.p95_latency_ms} ms`
+                                  : null,
+                                health.last_error,
+                              ]
+                                .filter(Boolean)
+                                .join('\n')}
+                              data-testid={`server-health-${server.id}`}
+                            >
+                              <Activity className="h-3 w-3" />
+                              {health.healthy
+                                ? `${health.avg_latency_ms ?? '–'} ms`
+                                : 'Unhealthy'}
+                            </span>
+                          )}
+
                           {/* Auth Type Badge */}
                           {server.auth && server.auth.type !== 'none' && (
                             <span className="rounded-md bg-[rgb(var(--surface-hover))] px-2 py-0.5 text-xs text-[rgb(var(--muted))]">
@@ -1144,6 +1544,57 @@ export function ServersPage() {
                             </button>
                           </div>
                         )}
+
+                        {/* Keeps exiting during startup: the diagnosed cause and fix */}
+                        {serverAction === 'error' && crashLoop && (
+                          <div
+                            className="mt-2 max-w-md space-y-1 text-xs text-[rgb(var(--muted))]"
+                            data-testid={`crash-diagnosis-${server.id}`}
+                          >
+                            <div className="font-medium text-[rgb(var(--foreground))]">
+                              {crashLoop.summary} (exited {crashLoop.exits} times during startup)
+                            </div>
+                            <div>{crashLoop.suggested_fix}</div>
+                            {crashLoop.evidence && (
+                              <div className="truncate font-mono" title={crashLoop.evidence}>
+                                {crashLoop.evidence}
+                              </div>
+                            )}
+                          </div>
+                        )}
+
+                        {/* No package runner installed: link to the installer */}
+                        {serverAction === 'error' && missingRuntime && (
+                          <div className="mt-2 flex items-center gap-2 text-xs text-[rgb(var(--muted))]">
+                            <span>Needs {missingRuntime.runners.join(', ')}</span>
+                            <span>·</span>
+                            <button
+                              onClick={() => openExternal(missingRuntime.install_url)}
+                              className="cursor-pointer underline transition-colors hover:text-[rgb(var(--foreground))]"
+                              data-testid={`install-runtime-${server.id}`}
+                            >
+                              {missingRuntime.ecosystem === 'npm' ? 'Install Node.js' : 'Install uv'}
+                            </button>
+                          </div>
+                        )}
+
+                        {/* Newer release of the pinned package */}
+                        {update && (
+                          <div className="mt-2 flex items-center gap-2 text-xs text-[rgb(var(--muted))]">
+                            <span>
+                              {update.package} {update.current_version} → {update.latest_version}
+                            </span>
+                            <span>·</span>
+                            <button
+                              onClick={() => handleUpdatePackage(server)}
+                              disabled={updateLoading}
+                              className="cursor-pointer underline transition-colors hover:text-[rgb(var(--foreground))] disabled:opacity-50"
+                              data-testid={`update-package-${server.id}`}
+                            >
+                              {updateLoading ? 'Updating...' : 'Update'}
+                            </button>
+                          </div>
+                        )}
                       </div>
                     </div>
 
@@ -1291,6 +1742,8 @@ export function ServersPage() {
                         onViewDefinition={() =>
                           setDefinitionServer({ id: server.id, name: server.name })
                         }
+                        validatesArguments={!server.skip_argument_validation}
+                        onToggleArgumentValidation={() => handleToggleArgumentValidation(server)}
                         onUninstall={() => handleUninstall(server)}
                       />
                     </div>
@@ -1337,14 +1790,54 @@ export function ServersPage() {
                                     key={feature.id}
                                     className="rounded-lg border border-[rgb(var(--border-subtle))] bg-[rgb(var(--card))] p-3"
                                   >
-                                    <div className="text-sm font-medium">
-                                      {feature.display_name || feature.feature_name}
+                                    <div className="flex items-start justify-between gap-2">
+                                      <div className="text-sm font-medium">
+                                        {feature.display_name || feature.feature_name}
+                                      </div>
+                                      <div className="flex shrink-0 items-center">
+                                        <button
+                                          onClick={() =>
+                                            updateToolSchema(
+                                              feature,
+                                              feature.schema_pinned ? 'unpin' : 'pin'
+                                            )
+                                          }
+                                          className="rounded px-2 py-0.5 text-xs text-[rgb(var(--muted))] transition-colors hover:bg-[rgb(var(--surface-hover))] hover:text-[rgb(var(--foreground))]"
+                                          title={
+                                            feature.schema_pinned
+                                              ? 'Let schema changes reach clients right away'
+                                              : 'Hold back schema changes until you accept them'
+                                          }
+                                        >
+                                          {feature.schema_pinned ? 'Unpin' : 'Pin'}
+                                        </button>
+                                        {server.connection_status === 'connected' && (
+                                          <button
+                                            onClick={() => setTestTool(feature)}
+                                            className="rounded px-2 py-0.5 text-xs text-[rgb(var(--muted))] transition-colors hover:bg-[rgb(var(--surface-hover))] hover:text-[rgb(var(--foreground))]"
+                                            title="Call this tool with custom arguments"
+                                          >
+                                            Test
+                                          </button>
+                                        )}
+                                      </div>
                                     </div>
                                     {feature.description && (
                                       <p className="mt-1 line-clamp-2 text-xs text-[rgb(var(--muted))]">
                                         {feature.description}
                                       </p>
                                     )}
+                                    {feature.pending_schema && (
+                                      <div className="mt-2 flex items-center justify-between gap-2 rounded bg-amber-500/10 px-2 py-1 text-xs text-amber-600">
+                                        <span>Schema changed; clients still see the pinned one</span>
+                                        <button
+                                          onClick={() => updateToolSchema(feature, 'accept')}
+                                          className="shrink-0 rounded px-2 py-0.5 font-medium hover:bg-amber-500/20"
+                                        >
+                                          Accept
+                                        </button>
+                                      </div>
+                                    )}
                                   </div>
                                 ))}
                             </div>
@@ -1419,6 +1912,24 @@ export function ServersPage() {
         </div>
       )}
 
+      {viewSpace && (
+        <UnusedServers
+          spaceId={viewSpace.id}
+          servers={unusedServers}
+          onServersChanged={loadData}
+          onToast={showToast}
+        />
+      )}
+
+      {viewSpace && (
+        <TrashedServers
+          spaceId={viewSpace.id}
+          servers={trashedServers}
+          onServersChanged={loadData}
+          onToast={showToast}
+        />
+      )}
+
       {/* Configuration Modal */}
       {configModal.open && configModal.server && (
         <div
@@ -1497,6 +2008,7 @@ export function ServersPage() {
                           />
                         );
                       case 'select':
+                      case 'enum':
                         return (
                           <select
                             value={currentValue}
@@ -1509,7 +2021,7 @@ export function ServersPage() {
                             </option>
                             {(input.options ?? []).map((opt) => (
                               <option key={opt.value} value={opt.value}>
-                                {opt.label}
+                                {opt.label ?? opt.value}
                               </option>
                             ))}
                           </select>
@@ -1562,6 +2074,21 @@ export function ServersPage() {
                         );
                       case 'text':
                       default:
+                        if (input.multiline) {
+                          return (
+                            <textarea
+                              value={currentValue}
+                              onChange={(e) => handleChange(e.target.value)}
+                              placeholder={
+                                input.placeholder || `Paste ${input.label.toLowerCase()}...`
+                              }
+                              rows={5}
+                              spellCheck={false}
+                              className="input w-full font-mono text-xs"
+                              data-testid={`config-input-${input.id}`}
+                            />
+                          );
+                        }
                         return (
                           <input
                             type={input.secret ? 'password' : 'text'}
@@ -1770,6 +2297,201 @@ export function ServersPage() {
                   </div>
                 </div>
               )}
+
+              {/* Timeouts */}
+              <div>
+                <label className="mb-1 block text-sm font-medium text-[rgb(var(--foreground))]">
+                  Timeouts
+                </label>
+                <p className="mb-2 text-xs text-[rgb(var(--muted))]">
+                  Seconds to wait for the server to start and for each tool call. Leave empty for
+                  the default.
+                </p>
+                <div className="flex gap-2">
+                  <label className="flex-1 text-xs text-[rgb(var(--muted))]">
+                    Connect
+                    <input
+                      type="number"
+                      min={5}
+                      max={600}
+                      value={configModal.connectTimeout}
+                      onChange={(e) =>
+                        setConfigModal({ ...configModal, connectTimeout: e.target.value })
+                      }
+                      placeholder={String(configModal.server.timeouts?.connect_secs ?? 60)}
+                      className="input mt-1 w-full text-sm"
+                      data-testid="config-connect-timeout"
+                    />
+                  </label>
+                  <label className="flex-1 text-xs text-[rgb(var(--muted))]">
+                    Request
+                    <input
+                      type="number"
+                      min={1}
+                      max={3600}
+                      value={configModal.requestTimeout}
+                      onChange={(e) =>
+                        setConfigModal({ ...configModal, requestTimeout: e.target.value })
+                      }
+                      placeholder={String(configModal.server.timeouts?.request_secs ?? 60)}
+                      className="input mt-1 w-full text-sm"
+                      data-testid="config-request-timeout"
+                    />
+                  </label>
+                </div>
+              </div>
+
+              {/* WASI permissions: only what the module requests can be granted */}
+              {configModal.server.transport.type === 'wasm' &&
+                (configModal.server.transport.permissions?.filesystem ||
+                  configModal.server.transport.permissions?.network) && (
+                  <div>
+                    <label className="mb-1 block text-sm font-medium text-[rgb(var(--foreground))]">
+                      Permissions
+                    </label>
+                    <p className="mb-2 text-xs text-[rgb(var(--muted))]">
+                      This module runs sandboxed. Grant only the access you trust it with.
+                    </p>
+                    {configModal.server.transport.permissions?.filesystem && (
+                      <>
+                        <label className="flex items-center gap-2 text-sm">
+                          <input
+                            type="checkbox"
+                            checked={!!configModal.wasmGrants.filesystem}
+                            onChange={(e) =>
+                              setConfigModal({
+                                ...configModal,
+                                wasmGrants: {
+                                  ...configModal.wasmGrants,
+                                  filesystem: e.target.checked,
+                                },
+                              })
+                            }
+                            data-testid="config-wasm-filesystem"
+                          />
+                          Files in these directories (one per line)
+                        </label>
+                        <textarea
+                          value={configModal.wasmDirs.join('\n')}
+                          onChange={(e) =>
+                            setConfigModal({
+                              ...configModal,
+                              wasmDirs: e.target.value.split('\n'),
+                            })
+                          }
+                          placeholder="/path/to/directory"
+                          rows={2}
+                          disabled={!configModal.wasmGrants.filesystem}
+                          className="input mt-1 w-full resize-y font-mono text-sm"
+                          data-testid="config-wasm-dirs"
+                        />
+                      </>
+                    )}
+                    {configModal.server.transport.permissions?.network && (
+                      <label className="mt-2 flex items-center gap-2 text-sm">
+                        <input
+                          type="checkbox"
+                          checked={!!configModal.wasmGrants.network}
+                          onChange={(e) =>
+                            setConfigModal({
+                              ...configModal,
+                              wasmGrants: { ...configModal.wasmGrants, network: e.target.checked },
+                            })
+                          }
+                          data-testid="config-wasm-network"
+                        />
+                        Network access
+                      </label>
+                    )}
+                  </div>
+                )}
+
+              {/* Tool hooks */}
+              <div>
+                <label className="mb-1 block text-sm font-medium text-[rgb(var(--foreground))]">
+                  Tool Hooks
+                </label>
+                <p className="mb-2 text-xs text-[rgb(var(--muted))]">
+                  Steps run on tool call arguments (<code>before</code>) and results (
+                  <code>after</code>), as JSON. Leave empty for none.
+                </p>
+                <textarea
+                  value={configModal.toolHooks}
+                  onChange={(e) => setConfigModal({ ...configModal, toolHooks: e.target.value })}
+                  placeholder={TOOL_HOOKS_PLACEHOLDER}
+                  rows={5}
+                  className="input w-full resize-y font-mono text-sm"
+                  data-testid="config-tool-hooks"
+                />
+              </div>
+
+              {/* Call context */}
+              <div>
+                <label className="mb-1 block text-sm font-medium text-[rgb(var(--foreground))]">
+                  Call Context
+                </label>
+                <p className="mb-2 text-xs text-[rgb(var(--muted))]">
+                  <code>_meta</code> fields added to each tool call, so the server&apos;s logs show
+                  which client made it. Values can use <code>{'{client_name}'}</code>,{' '}
+                  <code>{'{client_id}'}</code>, <code>{'{space_id}'}</code>,{' '}
+                  <code>{'{server_id}'}</code> and <code>{'{tool}'}</code>.
+                </p>
+                <div className="space-y-2">
+                  {Object.entries(configModal.callContext).map(([key, value], idx) => (
+                    <div key={idx} className="flex gap-2">
+                      <input
+                        type="text"
+                        value={key}
+                        onChange={(e) => {
+                          const entries = Object.entries(configModal.callContext);
+                          entries[idx] = [e.target.value, value];
+                          setConfigModal({
+                            ...configModal,
+                            callContext: Object.fromEntries(entries),
+                          });
+                        }}
+                        placeholder="client_name"
+                        className="input flex-1 font-mono text-sm"
+                      />
+                      <input
+                        type="text"
+                        value={value}
+                        onChange={(e) => {
+                          setConfigModal({
+                            ...configModal,
+                            callContext: { ...configModal.callContext, [key]: e.target.value },
+                          });
+                        }}
+                        placeholder="{client_name}"
+                        className="input flex-1 font-mono text-sm"
+                      />
+                      <button
+                        onClick={() => {
+                          // eslint-disable-next-line @typescript-eslint/no-unused-vars
+                          const { [key]: _, ...rest } = configModal.callContext;
+                          setConfigModal({ ...configModal, callContext: rest });
+                        }}
+                        className="px-2 py-1 text-sm text-[rgb(var(--muted))] transition-colors hover:text-[rgb(var(--error))]"
+                        title="Remove"
+                      >
+                        ✕
+                      </button>
+                    </div>
+                  ))}
+                  <button
+                    onClick={() => {
+                      setConfigModal({
+                        ...configModal,
+                        callContext: { ...configModal.callContext, '': '' },
+                      });
+                    }}
+                    className="text-xs text-[rgb(var(--primary))] hover:underline"
+                    data-testid="config-add-call-context"
+                  >
+                    + Add field
+                  </button>
+                </div>
+              </div>
             </div>
 
             {/* Pinned footer — always visible regardless of form length (#163) */}
@@ -1827,6 +2549,10 @@ export function ServersPage() {
       )}
 
       {/* Log Viewer Modal */}
+      {testTool && viewSpace && (
+        <ToolTestDialog spaceId={viewSpace.id} tool={testTool} onClose={() => setTestTool(null)} />
+      )}
+
       {logViewerServer && (
         <ServerLogViewer
           serverId={logViewerServer.id}
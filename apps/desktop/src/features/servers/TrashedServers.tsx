@@ -0,0 +1,101 @@
+/**
+ * TrashedServers - Uninstalled servers that can still be restored
+ *
+ * Uninstalled registry and manually added servers keep their settings,
+ * credentials and discovered tools for the trash retention window. Restore
+ * brings one back as it was; Delete removes it for good.
+ */
+
+import { useState } from 'react';
+import { RotateCcw, Trash2 } from 'lucide-react';
+import type { InstalledServerState } from '@/types/registry';
+import { purgeTrashedServer, restoreServer } from '@/lib/api/registry';
+
+export interface TrashedServersProps {
+  spaceId: string;
+  servers: InstalledServerState[];
+  /** Called after a restore or delete so the page can refresh */
+  onServersChanged: () => void;
+  onToast: (message: string, type: 'success' | 'error' | 'info') => void;
+}
+
+function displayName(server: InstalledServerState): string {
+  return server.server_name ?? server.server_id.split('/').pop() ?? server.server_id;
+}
+
+export function TrashedServers({
+  spaceId,
+  servers,
+  onServersChanged,
+  onToast,
+}: TrashedServersProps) {
+  const [busy, setBusy] = useState<string | null>(null);
+
+  if (servers.length === 0) return null;
+
+  const run = async (server: InstalledServerState, action: 'restore' | 'delete') => {
+    setBusy(`${action}-${server.server_id}`);
+    try {
+      if (action === 'restore') {
+        await restoreServer(server.server_id, spaceId);
+        onToast(`${displayName(server)} restored`, 'success');
+      } else {
+        await purgeTrashedServer(server.server_id, spaceId);
+        onToast(`${displayName(server)} deleted`, 'success');
+      }
+      onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div
+      className="rounded-xl border border-[rgb(var(--border-subtle))] bg-[rgb(var(--surface))] p-4"
+      data-testid="trashed-servers"
+    >
+      <h3 className="text-sm font-semibold">Recently uninstalled</h3>
+      <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+        Restore a server with its settings and credentials, or delete it for good.
+      </p>
+      <ul className="mt-3 space-y-2">
+        {servers.map((server) => (
+          <li
+            key={server.id}
+            className="flex items-center justify-between gap-3 text-sm"
+            data-testid={`trashed-server-${server.server_id}`}
+          >
+            <div className="min-w-0">
+              <span className="font-medium">{displayName(server)}</span>
+              {server.deleted_at && (
+                <span className="ml-2 text-xs text-[rgb(var(--muted))]">
+                  uninstalled {new Date(server.deleted_at).toLocaleDateString()}
+                </span>
+              )}
+            </div>
+            <div className="flex flex-shrink-0 items-center gap-2">
+              <button
+                onClick={() => run(server, 'restore')}
+                disabled={busy !== null}
+                className="flex items-center gap-1.5 rounded-lg border border-[rgb(var(--border))] px-2.5 py-1 text-xs transition-colors hover:bg-[rgb(var(--surface-hover))] disabled:opacity-50"
+              >
+                <RotateCcw className="h-3.5 w-3.5" />
+                Restore
+              </button>
+              <button
+                onClick={() => run(server, 'delete')}
+                disabled={busy !== null}
+                className="flex items-center gap-1.5 rounded-lg px-2.5 py-1 text-xs text-[rgb(var(--error))] transition-colors hover:bg-[rgb(var(--error))]/10 disabled:opacity-50"
+              >
+                <Trash2 className="h-3.5 w-3.5" />
+                Delete
+              </button>
+            </div>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
@@ -0,0 +1,82 @@
+/**
+ * UnusedServers - Enabled servers that haven't been used for a while
+ *
+ * A server counts as unused after the number of days set in Settings without
+ * a successful tool call. Disabling it frees its memory and speeds up
+ * startup; it keeps its settings and credentials and can be enabled again.
+ */
+
+import { useState } from 'react';
+import { PowerOff } from 'lucide-react';
+import type { InstalledServerState } from '@/types/registry';
+import { disableServer } from '@/lib/api/serverManager';
+
+export interface UnusedServersProps {
+  spaceId: string;
+  servers: InstalledServerState[];
+  /** Called after a server is disabled so the page can refresh */
+  onServersChanged: () => void;
+  onToast: (message: string, type: 'success' | 'error' | 'info') => void;
+}
+
+function displayName(server: InstalledServerState): string {
+  return server.server_name ?? server.server_id.split('/').pop() ?? server.server_id;
+}
+
+export function UnusedServers({ spaceId, servers, onServersChanged, onToast }: UnusedServersProps) {
+  const [busy, setBusy] = useState<string | null>(null);
+
+  if (servers.length === 0) return null;
+
+  const disable = async (server: InstalledServerState) => {
+    setBusy(server.server_id);
+    try {
+      await disableServer(spaceId, server.server_id);
+      onToast(`${displayName(server)} disabled`, 'success');
+      onServersChanged();
+    } catch (e) {
+      onToast(String(e), 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div
+      className="rounded-xl border border-[rgb(var(--border-subtle))] bg-[rgb(var(--surface))] p-4"
+      data-testid="unused-servers"
+    >
+      <h3 className="text-sm font-semibold">Not used lately</h3>
+      <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+        These servers are running but no tool call to them has succeeded in a while. Disable them
+        to free memory and speed up startup.
+      </p>
+      <ul className="mt-3 space-y-2">
+        {servers.map((server) => (
+          <li
+            key={server.id}
+            className="flex items-center justify-between gap-3 text-sm"
+            data-testid={`unused-server-${server.server_id}`}
+          >
+            <div className="min-w-0">
+              <span className="font-medium">{displayName(server)}</span>
+              <span className="ml-2 text-xs text-[rgb(var(--muted))]">
+                {server.last_used_at
+                  ? `last used ${new Date(server.last_used_at).toLocaleDateString()}`
+                  : 'never used'}
+              </span>
+            </div>
+            <button
+              onClick={() => disable(server)}
+              disabled={busy !== null}
+              className="flex flex-shrink-0 items-center gap-1.5 rounded-lg border border-[rgb(var(--border))] px-2.5 py-1 text-xs transition-colors hover:bg-[rgb(var(--surface-hover))] disabled:opacity-50"
+            >
+              <PowerOff className="h-3.5 w-3.5" />
+              Disable
+            </button>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
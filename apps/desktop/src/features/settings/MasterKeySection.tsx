@@ -0,0 +1,149 @@
+/**
+ * MasterKeySection - where the master encryption key is stored
+ *
+ * Switching moves the existing key to the new store and takes effect on the
+ * next start. The passphrase store asks for its passphrase here when it is
+ * selected and in a dialog on every start after that.
+ */
+
+import { useEffect, useState } from 'react';
+import { Card, CardHeader, CardTitle, CardDescription, CardContent, Button } from '@mcpmux/ui';
+import { KeyRound, Loader2 } from 'lucide-react';
+import { getKeyProvider, setKeyProvider, type KeyProviderKind } from '@/lib/api/credentials';
+
+export interface MasterKeySectionProps {
+  onToast: (title: string, message: string, type: 'success' | 'error') => void;
+}
+
+const PROVIDERS: { value: KeyProviderKind; label: string }[] = [
+  { value: 'auto', label: 'Platform default' },
+  { value: 'keychain', label: 'OS keychain' },
+  { value: 'file', label: 'Key file' },
+  { value: 'passphrase', label: 'Passphrase' },
+  { value: 'hardware', label: 'TPM / Secure Enclave' },
+];
+
+const MIN_PASSPHRASE_LENGTH = 8;
+
+export function MasterKeySection({ onToast }: MasterKeySectionProps) {
+  const [current, setCurrent] = useState<KeyProviderKind | null>(null);
+  const [selected, setSelected] = useState<KeyProviderKind>('auto');
+  const [passphrase, setPassphrase] = useState('');
+  const [confirmation, setConfirmation] = useState('');
+  const [saving, setSaving] = useState(false);
+  const [pendingRestart, setPendingRestart] = useState(false);
+
+  useEffect(() => {
+    getKeyProvider()
+      .then((kind) => {
+        setCurrent(kind);
+        setSelected(kind);
+      })
+      .catch((err) => console.error('Failed to load key provider:', err));
+  }, []);
+
+  const needsPassphrase = selected === 'passphrase' && current !== 'passphrase';
+  const passphraseError = !needsPassphrase
+    ? null
+    : passphrase.length < MIN_PASSPHRASE_LENGTH
+      ? `At least ${MIN_PASSPHRASE_LENGTH} characters`
+      : passphrase !== confirmation
+        ? 'Passphrases do not match'
+        : null;
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      await setKeyProvider(selected, needsPassphrase ? passphrase : undefined);
+      setCurrent(selected);
+      setPassphrase('');
+      setConfirmation('');
+      setPendingRestart(true);
+      onToast('Master key moved', 'Restart McpMux to use the new store', 'success');
+    } catch (err) {
+      onToast('Failed to move master key', String(err), 'error');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const input =
+    'rounded-md border border-[rgb(var(--border))] bg-transparent px-2 py-1 text-sm';
+
+  return (
+    <Card data-testid="settings-master-key-section">
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <KeyRound className="h-5 w-5" />
+          Master Key Storage
+        </CardTitle>
+        <CardDescription>
+          Where the key that encrypts credentials is kept. The key is moved, not replaced, so
+          nothing needs re-encrypting.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center justify-between gap-4">
+          <label className="text-sm font-medium">Store</label>
+          <select
+            value={selected}
+            onChange={(e) => setSelected(e.target.value as KeyProviderKind)}
+            disabled={current === null || saving || pendingRestart}
+            className="rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1.5 text-sm text-[rgb(var(--foreground))]"
+            data-testid="key-provider-select"
+          >
+            {PROVIDERS.map((p) => (
+              <option key={p.value} value={p.value}>
+                {p.label}
+              </option>
+            ))}
+          </select>
+        </div>
+        {needsPassphrase && (
+          <div className="grid grid-cols-2 gap-2">
+            <input
+              type="password"
+              className={input}
+              placeholder="Passphrase"
+              value={passphrase}
+              onChange={(e) => setPassphrase(e.target.value)}
+              data-testid="key-passphrase-input"
+            />
+            <input
+              type="password"
+              className={input}
+              placeholder="Confirm passphrase"
+              value={confirmation}
+              onChange={(e) => setConfirmation(e.target.value)}
+              data-testid="key-passphrase-confirm-input"
+            />
+            <p className="col-span-2 text-xs text-[rgb(var(--muted))]">
+              {passphraseError ??
+                'McpMux asks for this passphrase on every start. It cannot be recovered.'}
+            </p>
+          </div>
+        )}
+        {pendingRestart && (
+          <p className="text-xs text-[rgb(var(--muted))]">Restart McpMux to use the new store.</p>
+        )}
+        <div className="flex justify-end">
+          <Button
+            size="sm"
+            onClick={save}
+            disabled={
+              current === null ||
+              selected === current ||
+              saving ||
+              pendingRestart ||
+              passphraseError !== null
+            }
+            data-testid="key-provider-save-btn"
+          >
+            {saving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+            Move key
+          </Button>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
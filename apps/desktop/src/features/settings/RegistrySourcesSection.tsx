@@ -0,0 +1,490 @@
+/**
+ * RegistrySourcesSection - the registries servers are discovered from
+ *
+ * Several registries (e.g. a company-internal one next to the public one)
+ * are merged by priority. A namespace keeps a registry's server IDs apart,
+ * and its trust level decides whether its servers are installed enabled
+ * (trusted), disabled (standard) or only after a confirmation (untrusted).
+ * Private registries get a bearer token or basic auth credentials, which are
+ * stored encrypted and never sent back to the UI. A file:// URL reads a
+ * local folder of server definitions instead of a registry API. Entries
+ * signed by a trusted publisher key are marked as signed, and a registry
+ * can require signatures to hide everything else. A registry may also speak
+ * the official MCP registry API instead of McpMux's, or be a Smithery or
+ * mcp.run catalog whose entries are imported.
+ */
+
+import { useEffect, useState } from 'react';
+import { Card, CardHeader, CardTitle, CardDescription, CardContent, Button } from '@mcpmux/ui';
+import { KeyRound, Library, Loader2, Plus, ShieldCheck, Trash2 } from 'lucide-react';
+import {
+  listPublisherKeys,
+  listRegistrySources,
+  setPublisherKeys,
+  setRegistryAuth,
+  setRegistrySources,
+  type PublisherKey,
+  type RegistryAuth,
+  type RegistryAuthKind,
+  type RegistrySource,
+} from '@/lib/api/registry';
+import type { RegistryFormat, RegistryTrust } from '@/types/registry';
+
+export interface RegistrySourcesSectionProps {
+  onToast: (title: string, message: string, type: 'success' | 'error') => void;
+}
+
+const TRUST_LEVELS: { value: RegistryTrust; label: string }[] = [
+  { value: 'trusted', label: 'Trusted – installed enabled' },
+  { value: 'standard', label: 'Standard – installed disabled' },
+  { value: 'untrusted', label: 'Untrusted – install needs confirmation' },
+];
+
+const FORMATS: { value: RegistryFormat; label: string }[] = [
+  { value: 'mcpmux', label: 'McpMux registry API' },
+  { value: 'official', label: 'Official MCP registry API' },
+  { value: 'smithery', label: 'Smithery catalog' },
+  { value: 'mcprun', label: 'mcp.run catalog' },
+];
+
+/** Well-known registries and catalogs that can be added with one click */
+const PRESETS: { label: string; source: RegistrySource }[] = [
+  {
+    label: 'Official MCP registry',
+    source: {
+      id: 'mcp-official',
+      name: 'MCP Registry',
+      url: 'https://registry.modelcontextprotocol.io',
+      priority: -10,
+      namespace: null,
+      trust: 'untrusted',
+      require_signatures: false,
+      format: 'official',
+    },
+  },
+  {
+    label: 'Smithery',
+    source: {
+      id: 'smithery',
+      name: 'Smithery',
+      url: 'https://registry.smithery.ai',
+      priority: -20,
+      namespace: null,
+      trust: 'untrusted',
+      require_signatures: false,
+      format: 'smithery',
+    },
+  },
+  {
+    label: 'mcp.run',
+    source: {
+      id: 'mcp-run',
+      name: 'mcp.run',
+      url: 'https://www.mcp.run',
+      priority: -20,
+      namespace: null,
+      trust: 'untrusted',
+      require_signatures: false,
+      format: 'mcprun',
+    },
+  },
+];
+
+interface AuthDraft {
+  registryId: string;
+  kind: RegistryAuthKind;
+  token: string;
+  username: string;
+  password: string;
+}
+
+export function RegistrySourcesSection({ onToast }: RegistrySourcesSectionProps) {
+  const [sources, setSources] = useState<RegistrySource[] | null>(null);
+  const [savedIds, setSavedIds] = useState<string[]>([]);
+  const [saving, setSaving] = useState(false);
+  const [authDraft, setAuthDraft] = useState<AuthDraft | null>(null);
+  const [publisherKeys, setPublisherKeysState] = useState<PublisherKey[]>([]);
+  const [keyDraft, setKeyDraft] = useState<PublisherKey>({ id: '', name: '', public_key: '' });
+
+  const load = (loaded: RegistrySource[]) => {
+    setSources(loaded);
+    setSavedIds(loaded.map((s) => s.id));
+  };
+
+  useEffect(() => {
+    listRegistrySources()
+      .then(load)
+      .catch((e) => console.error('[RegistrySources] Failed to load:', e));
+    listPublisherKeys()
+      .then(setPublisherKeysState)
+      .catch((e) => console.error('[RegistrySources] Failed to load publisher keys:', e));
+  }, []);
+
+  const saveKeys = async (keys: PublisherKey[]) => {
+    setSaving(true);
+    try {
+      setPublisherKeysState(await setPublisherKeys(keys));
+      setKeyDraft({ id: '', name: '', public_key: '' });
+      onToast('Publisher keys saved', 'Signed servers were checked again', 'success');
+    } catch (e) {
+      onToast('Failed to save publisher keys', String(e), 'error');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const saveAuth = async (auth: RegistryAuth | null, registryId: string) => {
+    setSaving(true);
+    try {
+      await setRegistryAuth(registryId, auth);
+      setAuthDraft(null);
+      load(await listRegistrySources());
+      onToast(
+        auth ? 'Credentials saved' : 'Credentials removed',
+        'Servers were reloaded from the registry',
+        'success'
+      );
+    } catch (e) {
+      onToast('Failed to update credentials', String(e), 'error');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const draftToAuth = (draft: AuthDraft): RegistryAuth =>
+    draft.kind === 'bearer'
+      ? { type: 'bearer', token: draft.token }
+      : { type: 'basic', username: draft.username, password: draft.password };
+
+  const update = (index: number, changes: Partial<RegistrySource>) => {
+    setSources((current) =>
+      current ? current.map((s, i) => (i === index ? { ...s, ...changes } : s)) : current
+    );
+  };
+
+  const add = () => {
+    setSources((current) => [
+      ...(current ?? []),
+      {
+        id: '',
+        name: '',
+        url: 'https://',
+        priority: 10,
+        namespace: null,
+        trust: 'standard',
+        require_signatures: false,
+        format: 'mcpmux',
+      },
+    ]);
+  };
+
+  const addPreset = (preset: RegistrySource) => {
+    setSources((current) => [...(current ?? []), { ...preset }]);
+  };
+
+  const remove = (index: number) => {
+    setSources((current) => (current ? current.filter((_, i) => i !== index) : current));
+  };
+
+  const save = async () => {
+    if (!sources) return;
+    setSaving(true);
+    try {
+      load(await setRegistrySources(sources));
+      onToast('Registries saved', 'Servers were reloaded from the configured registries', 'success');
+    } catch (e) {
+      onToast('Failed to save registries', String(e), 'error');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const input =
+    'rounded-md border border-[rgb(var(--border))] bg-transparent px-2 py-1 text-sm';
+
+  return (
+    <Card data-testid="settings-registries-section">
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Library className="h-5 w-5" />
+          Registries
+        </CardTitle>
+        <CardDescription>
+          Where the Discover page finds servers. When two registries list the same server, the
+          one with the higher priority wins; a namespace lists a registry's servers as
+          namespace/id instead. A file:// URL loads server definition JSON files from a local
+          folder, such as a git checkout, and reloads them when they change.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {sources === null ? (
+          <Loader2 className="h-4 w-4 animate-spin" />
+        ) : (
+          sources.map((source, index) => (
+            <div
+              key={index}
+              className="grid grid-cols-2 gap-2 border-t border-[rgb(var(--border))] pt-3 first:border-t-0 first:pt-0"
+              data-testid={`registry-source-${index}`}
+            >
+              <input
+                className={input}
+                placeholder="ID (e.g. acme)"
+                value={source.id}
+                onChange={(e) => update(index, { id: e.target.value })}
+              />
+              <input
+                className={input}
+                placeholder="Name"
+                value={source.name}
+                onChange={(e) => update(index, { name: e.target.value })}
+              />
+              <input
+                className={`${input} col-span-2`}
+                placeholder="https://registry.example.com or file:///path/to/catalog"
+                value={source.url}
+                onChange={(e) => update(index, { url: e.target.value })}
+              />
+              <label className="flex items-center gap-2 text-xs text-[rgb(var(--muted))]">
+                Priority
+                <input
+                  type="number"
+                  className={`${input} w-20`}
+                  value={source.priority}
+                  onChange={(e) => update(index, { priority: Number(e.target.value) || 0 })}
+                />
+              </label>
+              <input
+                className={input}
+                placeholder="Namespace (optional)"
+                value={source.namespace ?? ''}
+                onChange={(e) => update(index, { namespace: e.target.value || null })}
+              />
+              <select
+                className={input}
+                value={source.trust}
+                onChange={(e) => update(index, { trust: e.target.value as RegistryTrust })}
+              >
+                {TRUST_LEVELS.map((level) => (
+                  <option key={level.value} value={level.value}>
+                    {level.label}
+                  </option>
+                ))}
+              </select>
+              <label className="flex items-center gap-2 text-xs text-[rgb(var(--muted))]">
+                <input
+                  type="checkbox"
+                  checked={source.require_signatures ?? false}
+                  onChange={(e) => update(index, { require_signatures: e.target.checked })}
+                />
+                Require signatures
+              </label>
+              <select
+                className={input}
+                value={source.format ?? 'mcpmux'}
+                disabled={source.url.startsWith('file://')}
+                onChange={(e) => update(index, { format: e.target.value as RegistryFormat })}
+                data-testid={`registry-source-${index}-format`}
+              >
+                {FORMATS.map((format) => (
+                  <option key={format.value} value={format.value}>
+                    {format.label}
+                  </option>
+                ))}
+              </select>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => remove(index)}
+                data-testid={`registry-source-${index}-remove`}
+              >
+                <Trash2 className="h-4 w-4" />
+                <span className="ml-2">Remove</span>
+              </Button>
+              {savedIds.includes(source.id) && (
+                <div className="col-span-2 flex flex-wrap items-center gap-2 text-xs">
+                  <KeyRound className="h-3.5 w-3.5 text-[rgb(var(--muted))]" />
+                  <span className="text-[rgb(var(--muted))]">
+                    {source.auth === 'bearer'
+                      ? 'Bearer token set'
+                      : source.auth === 'basic'
+                        ? 'Basic auth set'
+                        : 'No credentials'}
+                  </span>
+                  {authDraft?.registryId === source.id ? (
+                    <>
+                      <select
+                        className={input}
+                        value={authDraft.kind}
+                        onChange={(e) =>
+                          setAuthDraft({ ...authDraft, kind: e.target.value as RegistryAuthKind })
+                        }
+                      >
+                        <option value="bearer">Bearer token</option>
+                        <option value="basic">Basic auth</option>
+                      </select>
+                      {authDraft.kind === 'bearer' ? (
+                        <input
+                          type="password"
+                          className={input}
+                          placeholder="Token"
+                          value={authDraft.token}
+                          onChange={(e) => setAuthDraft({ ...authDraft, token: e.target.value })}
+                        />
+                      ) : (
+                        <>
+                          <input
+                            className={input}
+                            placeholder="Username"
+                            value={authDraft.username}
+                            onChange={(e) =>
+                              setAuthDraft({ ...authDraft, username: e.target.value })
+                            }
+                          />
+                          <input
+                            type="password"
+                            className={input}
+                            placeholder="Password"
+                            value={authDraft.password}
+                            onChange={(e) =>
+                              setAuthDraft({ ...authDraft, password: e.target.value })
+                            }
+                          />
+                        </>
+                      )}
+                      <Button
+                        size="sm"
+                        disabled={saving}
+                        onClick={() => saveAuth(draftToAuth(authDraft), source.id)}
+                      >
+                        Save
+                      </Button>
+                      <Button variant="ghost" size="sm" onClick={() => setAuthDraft(null)}>
+                        Cancel
+                      </Button>
+                    </>
+                  ) : (
+                    <>
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        onClick={() =>
+                          setAuthDraft({
+                            registryId: source.id,
+                            kind: source.auth ?? 'bearer',
+                            token: '',
+                            username: '',
+                            password: '',
+                          })
+                        }
+                        data-testid={`registry-source-${index}-auth`}
+                      >
+                        {source.auth ? 'Change' : 'Add credentials'}
+                      </Button>
+                      {source.auth && (
+                        <Button
+                          variant="ghost"
+                          size="sm"
+                          disabled={saving}
+                          onClick={() => saveAuth(null, source.id)}
+                        >
+                          Remove
+                        </Button>
+                      )}
+                    </>
+                  )}
+                </div>
+              )}
+            </div>
+          ))
+        )}
+        <div className="flex flex-wrap items-center gap-2">
+          <Button variant="secondary" size="sm" onClick={add} disabled={sources === null}>
+            <Plus className="h-4 w-4" />
+            <span className="ml-2">Add registry</span>
+          </Button>
+          {sources !== null &&
+            PRESETS.filter((preset) => !sources.some((s) => s.id === preset.source.id)).map(
+              (preset) => (
+                <Button
+                  key={preset.source.id}
+                  variant="secondary"
+                  size="sm"
+                  onClick={() => addPreset(preset.source)}
+                  data-testid={`registry-preset-${preset.source.id}`}
+                >
+                  <Plus className="h-4 w-4" />
+                  <span className="ml-2">Add {preset.label}</span>
+                </Button>
+              )
+            )}
+          <Button
+            size="sm"
+            onClick={save}
+            disabled={sources === null || saving}
+            data-testid="registry-sources-save"
+          >
+            {saving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+            Save
+          </Button>
+        </div>
+        <div className="space-y-2 border-t border-[rgb(var(--border))] pt-3">
+          <p className="flex items-center gap-2 text-sm font-medium">
+            <ShieldCheck className="h-4 w-4" />
+            Trusted publishers
+          </p>
+          <p className="text-xs text-[rgb(var(--muted))]">
+            Servers signed with one of these keys are marked as signed. A server whose signature
+            doesn't match was changed after signing and is hidden.
+          </p>
+          {publisherKeys.map((key) => (
+            <div key={key.id} className="flex items-center gap-2 text-xs">
+              <span className="font-medium">{key.id}</span>
+              <span className="text-[rgb(var(--muted))]">{key.name}</span>
+              <code className="truncate text-[rgb(var(--muted))]">{key.public_key}</code>
+              <Button
+                variant="ghost"
+                size="sm"
+                disabled={saving}
+                onClick={() => saveKeys(publisherKeys.filter((k) => k.id !== key.id))}
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+          <div className="grid grid-cols-3 gap-2">
+            <input
+              className={input}
+              placeholder="Key ID"
+              value={keyDraft.id}
+              onChange={(e) => setKeyDraft({ ...keyDraft, id: e.target.value })}
+            />
+            <input
+              className={input}
+              placeholder="Publisher"
+              value={keyDraft.name}
+              onChange={(e) => setKeyDraft({ ...keyDraft, name: e.target.value })}
+            />
+            <input
+              className={input}
+              placeholder="Base64 public key"
+              value={keyDraft.public_key}
+              onChange={(e) => setKeyDraft({ ...keyDraft, public_key: e.target.value })}
+            />
+          </div>
+          <Button
+            variant="secondary"
+            size="sm"
+            disabled={saving || !keyDraft.id || !keyDraft.public_key}
+            onClick={() =>
+              saveKeys([...publisherKeys.filter((k) => k.id !== keyDraft.id), keyDraft])
+            }
+            data-testid="publisher-key-add"
+          >
+            <Plus className="h-4 w-4" />
+            <span className="ml-2">Trust key</span>
+          </Button>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
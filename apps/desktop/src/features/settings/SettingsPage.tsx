@@ -33,6 +33,9 @@ import {
   RotateCcw,
   AlertCircle,
   ShieldOff,
+  Boxes,
+  Download,
+  Hourglass,
 } from 'lucide-react';
 import {
   useAppStore,
@@ -42,8 +45,27 @@ import {
   useSetPendingSettingsSection,
 } from '@/stores';
 import { UpdateChecker } from './UpdateChecker';
+import { RegistrySourcesSection } from './RegistrySourcesSection';
+import { MasterKeySection } from './MasterKeySection';
 import { useGatewayControl } from '@/features/gateway/useGatewayControl';
 import { CONTRIBUTE, openExternal } from '@/lib/contribute';
+import {
+  getManagedRuntimes,
+  setManagedRuntimesEnabled,
+  installManagedRuntime,
+  uninstallManagedRuntime,
+  type ManagedRuntimeKind,
+  type ManagedRuntimesInfo,
+} from '@/lib/api/runtimes';
+import { generateDiagnosticsBundle } from '@/lib/api/logs';
+import {
+  getAutoDisableUnusedServers,
+  getServerTrashRetentionDays,
+  getServerUnusedDays,
+  setAutoDisableUnusedServers,
+  setServerTrashRetentionDays,
+  setServerUnusedDays,
+} from '@/lib/api/registry';
 
 interface StartupSettings {
   autoLaunch: boolean;
@@ -61,6 +83,7 @@ interface GatewayPublicUrlSettings {
   configuredPublicBaseUrl: string | null;
   activePublicBaseUrl: string | null;
   localBaseUrl: string | null;
+  configuredBaseUrlHost: string | null;
 }
 
 export function SettingsPage() {
@@ -70,6 +93,7 @@ export function SettingsPage() {
   const setAnalyticsEnabled = useAppStore((state) => state.setAnalyticsEnabled);
   const [logsPath, setLogsPath] = useState<string>('');
   const [openingLogs, setOpeningLogs] = useState(false);
+  const [bundling, setBundling] = useState(false);
   const { toasts, success, error } = useToast();
   const gatewayControl = useGatewayControl();
 
@@ -151,6 +175,9 @@ export function SettingsPage() {
   const [publicUrlError, setPublicUrlError] = useState<string | null>(null);
   const [savingPublicUrl, setSavingPublicUrl] = useState(false);
   const [resettingPublicUrl, setResettingPublicUrl] = useState(false);
+  const [baseUrlHostDraft, setBaseUrlHostDraft] = useState<string>('');
+  const [baseUrlHostError, setBaseUrlHostError] = useState<string | null>(null);
+  const [savingBaseUrlHost, setSavingBaseUrlHost] = useState(false);
 
   const loadPortSettings = async () => {
     try {
@@ -169,6 +196,8 @@ export function SettingsPage() {
       setPublicUrlSettings(s);
       setPublicUrlDraft(s.configuredPublicBaseUrl ?? '');
       setPublicUrlError(null);
+      setBaseUrlHostDraft(s.configuredBaseUrlHost ?? '');
+      setBaseUrlHostError(null);
     } catch (err) {
       console.error('Failed to load gateway public URL settings:', err);
     }
@@ -228,8 +257,8 @@ export function SettingsPage() {
       await loadPortSettings();
       success(
         'Gateway port saved',
-        portSettings?.activePort && portSettings.activePort !== parsed.port
-          ? `Restart the gateway for port ${parsed.port} to take effect.`
+        portSettings?.activePort
+          ? `Gateway is now listening on port ${parsed.port}.`
           : `Next gateway start will use port ${parsed.port}.`
       );
     } catch (err) {
@@ -305,6 +334,26 @@ export function SettingsPage() {
     }
   };
 
+  const handleSaveBaseUrlHost = async () => {
+    setBaseUrlHostError(null);
+    setSavingBaseUrlHost(true);
+    try {
+      const host = baseUrlHostDraft.trim() || null;
+      await invoke('set_gateway_base_url_host', { host });
+      await loadPublicUrlSettings();
+      success(
+        host ? 'Local URL host saved' : 'Local URL host cleared',
+        'Client configs exported from now on use the new URL.'
+      );
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      setBaseUrlHostError(msg);
+      error('Failed to save local URL host', msg);
+    } finally {
+      setSavingBaseUrlHost(false);
+    }
+  };
+
   const handleRestartGateway = async () => {
     try {
       const outcome = await gatewayControl.restart();
@@ -392,6 +441,136 @@ export function SettingsPage() {
     }
   };
 
+  // How long uninstalled servers can be restored
+  const [trashRetentionDays, setTrashRetentionDays] = useState<number>(30);
+  const [savingTrashRetention, setSavingTrashRetention] = useState(false);
+
+  useEffect(() => {
+    getServerTrashRetentionDays()
+      .then(setTrashRetentionDays)
+      .catch((err) => console.error('Failed to load trash retention setting:', err));
+  }, []);
+
+  const handleTrashRetentionChange = async (days: number) => {
+    const oldDays = trashRetentionDays;
+    setTrashRetentionDays(days);
+    setSavingTrashRetention(true);
+    try {
+      await setServerTrashRetentionDays(days);
+      success(
+        'Settings saved',
+        days === 0
+          ? 'Uninstalled servers are kept until you delete them'
+          : `Uninstalled servers can be restored for ${days} days`
+      );
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error('Failed to save setting', msg);
+      setTrashRetentionDays(oldDays);
+    } finally {
+      setSavingTrashRetention(false);
+    }
+  };
+
+  // When enabled servers count as unused, and whether they're disabled
+  const [unusedDays, setUnusedDays] = useState<number>(30);
+  const [autoDisableUnused, setAutoDisableUnused] = useState(false);
+  const [savingUnused, setSavingUnused] = useState(false);
+
+  useEffect(() => {
+    Promise.all([getServerUnusedDays(), getAutoDisableUnusedServers()])
+      .then(([days, autoDisable]) => {
+        setUnusedDays(days);
+        setAutoDisableUnused(autoDisable);
+      })
+      .catch((err) => console.error('Failed to load unused server settings:', err));
+  }, []);
+
+  const handleUnusedDaysChange = async (days: number) => {
+    const oldDays = unusedDays;
+    setUnusedDays(days);
+    setSavingUnused(true);
+    try {
+      await setServerUnusedDays(days);
+      success(
+        'Settings saved',
+        days === 0
+          ? 'Servers are never flagged as unused'
+          : `Servers unused for ${days} days are flagged`
+      );
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error('Failed to save setting', msg);
+      setUnusedDays(oldDays);
+    } finally {
+      setSavingUnused(false);
+    }
+  };
+
+  const handleAutoDisableUnusedChange = async (enabled: boolean) => {
+    setAutoDisableUnused(enabled);
+    setSavingUnused(true);
+    try {
+      await setAutoDisableUnusedServers(enabled);
+      success(
+        'Settings saved',
+        enabled ? 'Unused servers are disabled automatically' : 'Unused servers stay enabled'
+      );
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error('Failed to save setting', msg);
+      setAutoDisableUnused(!enabled);
+    } finally {
+      setSavingUnused(false);
+    }
+  };
+
+  // Managed runtimes (pinned Node.js / uv for stdio servers)
+  const [managedRuntimes, setManagedRuntimes] = useState<ManagedRuntimesInfo | null>(null);
+  const [savingManagedRuntimes, setSavingManagedRuntimes] = useState(false);
+  const [busyRuntime, setBusyRuntime] = useState<ManagedRuntimeKind | null>(null);
+
+  useEffect(() => {
+    getManagedRuntimes()
+      .then(setManagedRuntimes)
+      .catch((err) => console.error('Failed to load managed runtimes:', err));
+  }, []);
+
+  const updateManagedRuntimesEnabled = async (enabled: boolean) => {
+    setSavingManagedRuntimes(true);
+    try {
+      await setManagedRuntimesEnabled(enabled);
+      setManagedRuntimes((prev) => (prev ? { ...prev, enabled } : prev));
+      success(
+        'Settings saved',
+        enabled
+          ? 'Servers started from now on use the managed runtimes.'
+          : 'Servers started from now on use your own Node.js and Python.'
+      );
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error('Failed to save setting', msg);
+    } finally {
+      setSavingManagedRuntimes(false);
+    }
+  };
+
+  const toggleManagedRuntime = async (kind: ManagedRuntimeKind, installed: boolean) => {
+    setBusyRuntime(kind);
+    try {
+      const info = installed
+        ? await uninstallManagedRuntime(kind)
+        : await installManagedRuntime(kind);
+      setManagedRuntimes(info);
+      success(installed ? 'Runtime removed' : 'Runtime installed');
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error(installed ? 'Failed to remove runtime' : 'Failed to install runtime', msg);
+    } finally {
+      setBusyRuntime(null);
+    }
+  };
+
   // Load the system-wide inbound-auth toggle on mount.
   useEffect(() => {
     invoke<boolean>('get_gateway_auth_disabled')
@@ -509,6 +688,25 @@ export function SettingsPage() {
     }
   };
 
+  const handleDiagnosticsBundle = async () => {
+    setBundling(true);
+    try {
+      const path = await generateDiagnosticsBundle();
+      success('Diagnostics bundle created', path);
+      try {
+        const { revealItemInDir } = await import('@tauri-apps/plugin-opener');
+        await revealItemInDir(path);
+      } catch (err) {
+        console.error('Failed to reveal diagnostics bundle:', err);
+      }
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err);
+      error('Failed to create diagnostics bundle', msg);
+    } finally {
+      setBundling(false);
+    }
+  };
+
   return (
     <>
       <ToastContainer
@@ -815,6 +1013,73 @@ export function SettingsPage() {
                     </div>
                   </div>
 
+                  <div className="border-t border-[rgb(var(--border-subtle))] pt-4">
+                    <div className="flex items-start gap-3">
+                      <Globe className="mt-0.5 h-5 w-5 flex-shrink-0 text-[rgb(var(--muted))]" />
+                      <div className="min-w-0 flex-1">
+                        <label htmlFor="gateway-base-url-host-input" className="text-sm font-medium">
+                          Local URL host
+                        </label>
+                        <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                          Host in the gateway URL given to clients. Leave blank for{' '}
+                          <span className="font-mono">localhost</span>; use{' '}
+                          <span className="font-mono">127.0.0.1</span> or{' '}
+                          <span className="font-mono">::1</span> if a client can&apos;t reach{' '}
+                          <span className="font-mono">localhost</span>.
+                          {publicUrlSettings?.localBaseUrl ? (
+                            <>
+                              {' '}
+                              Local URL:{' '}
+                              <span className="font-mono" data-testid="gateway-local-url">
+                                {publicUrlSettings.localBaseUrl}
+                              </span>
+                              .
+                            </>
+                          ) : null}
+                        </p>
+                        <div className="mt-3 flex flex-wrap items-center gap-2">
+                          <input
+                            id="gateway-base-url-host-input"
+                            type="text"
+                            placeholder="localhost"
+                            value={baseUrlHostDraft}
+                            onChange={(e) => {
+                              setBaseUrlHostDraft(e.target.value);
+                              if (baseUrlHostError) setBaseUrlHostError(null);
+                            }}
+                            disabled={savingBaseUrlHost}
+                            className="focus:ring-primary-500/40 min-w-[200px] rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1.5 font-mono text-sm text-[rgb(var(--foreground))] focus:outline-none focus:ring-2"
+                            data-testid="gateway-base-url-host-input"
+                          />
+                          <Button
+                            variant="primary"
+                            size="sm"
+                            onClick={handleSaveBaseUrlHost}
+                            disabled={
+                              savingBaseUrlHost ||
+                              baseUrlHostDraft.trim() ===
+                                (publicUrlSettings?.configuredBaseUrlHost ?? '')
+                            }
+                            data-testid="gateway-base-url-host-save-btn"
+                          >
+                            {savingBaseUrlHost ? (
+                              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                            ) : null}
+                            Save
+                          </Button>
+                        </div>
+                        {baseUrlHostError ? (
+                          <p
+                            className="mt-2 text-xs text-red-600 dark:text-red-400"
+                            data-testid="gateway-base-url-host-error"
+                          >
+                            {baseUrlHostError}
+                          </p>
+                        ) : null}
+                      </div>
+                    </div>
+                  </div>
+
                   <div className="border-t border-[rgb(var(--border-subtle))] pt-4">
                     <div className="flex items-center justify-between gap-4">
                       <div className="flex min-w-0 flex-1 items-start gap-3">
@@ -827,7 +1092,8 @@ export function SettingsPage() {
                             Bind the gateway to all network interfaces (
                             <span className="font-mono">0.0.0.0</span>) so other machines on your
                             network can connect to the same MCP servers. Off keeps it on{' '}
-                            <span className="font-mono">127.0.0.1</span> (this machine only).
+                            <span className="font-mono">127.0.0.1</span> and{' '}
+                            <span className="font-mono">::1</span> (this machine only).
                             Restart the gateway to apply.
                           </p>
                         </div>
@@ -1009,6 +1275,172 @@ export function SettingsPage() {
           </Card>
         </div>
 
+        {/* Runtimes Section */}
+        <Card data-testid="settings-runtimes-section">
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Boxes className="h-5 w-5" />
+              Runtimes
+            </CardTitle>
+            <CardDescription>
+              Let McpMux download its own Node.js and uv (Python) for servers launched with npx or
+              uvx, so you don't have to install them.
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <div className="flex items-center justify-between gap-4">
+              <div className="flex min-w-0 flex-1 items-start gap-3">
+                <Boxes className="mt-0.5 h-5 w-5 flex-shrink-0 text-[rgb(var(--muted))]" />
+                <div>
+                  <label className="text-sm font-medium">Use managed runtimes</label>
+                  <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                    Installed runtimes take precedence over the ones on your PATH. Applies to
+                    servers started after the change.
+                  </p>
+                </div>
+              </div>
+              <Switch
+                checked={managedRuntimes?.enabled ?? false}
+                onCheckedChange={updateManagedRuntimesEnabled}
+                disabled={!managedRuntimes || savingManagedRuntimes}
+                data-testid="managed-runtimes-switch"
+              />
+            </div>
+            {managedRuntimes?.runtimes.map((runtime) => (
+              <div
+                key={runtime.kind}
+                className="flex items-center justify-between gap-4 border-t border-[rgb(var(--border))] pt-4"
+                data-testid={`managed-runtime-${runtime.kind}`}
+              >
+                <div className="min-w-0">
+                  <p className="text-sm font-medium">
+                    {runtime.name} {runtime.version}
+                  </p>
+                  <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                    {!runtime.supported
+                      ? 'Not available for this platform'
+                      : runtime.installed
+                        ? 'Installed'
+                        : 'Not installed'}
+                  </p>
+                </div>
+                <Button
+                  variant={runtime.installed ? 'ghost' : 'secondary'}
+                  size="sm"
+                  disabled={!runtime.supported || busyRuntime !== null}
+                  onClick={() => toggleManagedRuntime(runtime.kind, runtime.installed)}
+                  data-testid={`managed-runtime-${runtime.kind}-action`}
+                >
+                  {busyRuntime === runtime.kind ? (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  ) : runtime.installed ? (
+                    <Trash2 className="h-4 w-4" />
+                  ) : (
+                    <Download className="h-4 w-4" />
+                  )}
+                  <span className="ml-2">{runtime.installed ? 'Remove' : 'Install'}</span>
+                </Button>
+              </div>
+            ))}
+          </CardContent>
+        </Card>
+
+        {/* Uninstalled Servers Section */}
+        <Card data-testid="settings-trash-section">
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Trash2 className="h-5 w-5" />
+              Uninstalled Servers
+            </CardTitle>
+            <CardDescription>
+              Uninstalled servers keep their settings and credentials for a while, so you can
+              restore them from the Tools page.
+            </CardDescription>
+          </CardHeader>
+          <CardContent>
+            <div className="flex items-center justify-between gap-4">
+              <div className="min-w-0 flex-1">
+                <label className="text-sm font-medium">Keep for</label>
+                <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                  After this period they are deleted for good
+                </p>
+              </div>
+              <select
+                value={trashRetentionDays}
+                onChange={(e) => handleTrashRetentionChange(Number(e.target.value))}
+                disabled={savingTrashRetention}
+                className="rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1.5 text-sm text-[rgb(var(--foreground))]"
+                data-testid="trash-retention-select"
+              >
+                <option value={7}>7 days</option>
+                <option value={14}>14 days</option>
+                <option value={30}>30 days</option>
+                <option value={90}>90 days</option>
+                <option value={0}>Until deleted</option>
+              </select>
+            </div>
+          </CardContent>
+        </Card>
+
+        {/* Unused Servers Section */}
+        <Card data-testid="settings-unused-servers-section">
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Hourglass className="h-5 w-5" />
+              Unused Servers
+            </CardTitle>
+            <CardDescription>
+              Enabled servers without a successful tool call for a while are flagged on the Tools
+              page. Disabling them frees memory and speeds up startup.
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <div className="flex items-center justify-between gap-4">
+              <div className="min-w-0 flex-1">
+                <label className="text-sm font-medium">Unused after</label>
+                <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                  Counted from the last successful tool call, or from when the server was
+                  installed or enabled
+                </p>
+              </div>
+              <select
+                value={unusedDays}
+                onChange={(e) => handleUnusedDaysChange(Number(e.target.value))}
+                disabled={savingUnused}
+                className="rounded-lg border border-[rgb(var(--border))] bg-[rgb(var(--surface))] px-3 py-1.5 text-sm text-[rgb(var(--foreground))]"
+                data-testid="unused-days-select"
+              >
+                <option value={7}>7 days</option>
+                <option value={14}>14 days</option>
+                <option value={30}>30 days</option>
+                <option value={90}>90 days</option>
+                <option value={0}>Never</option>
+              </select>
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div className="min-w-0 flex-1">
+                <label className="text-sm font-medium">Disable automatically</label>
+                <p className="mt-1 text-xs text-[rgb(var(--muted))]">
+                  Checked daily. Re-enable a server any time to use it again.
+                </p>
+              </div>
+              <Switch
+                checked={autoDisableUnused}
+                onCheckedChange={handleAutoDisableUnusedChange}
+                disabled={savingUnused || unusedDays === 0}
+                data-testid="auto-disable-unused-switch"
+              />
+            </div>
+          </CardContent>
+        </Card>
+
+        {/* Registries Section */}
+        <RegistrySourcesSection
+          onToast={(title, message, type) =>
+            type === 'success' ? success(title, message) : error(title, message)
+          }
+        />
+
         {/* Security Section */}
         <div
           ref={registerSection('security')}
@@ -1049,6 +1481,13 @@ export function SettingsPage() {
           </Card>
         </div>
 
+        {/* Master Key Section */}
+        <MasterKeySection
+          onToast={(title, message, type) =>
+            type === 'success' ? success(title, message) : error(title, message)
+          }
+        />
+
         {/* Appearance Section */}
         <Card>
           <CardHeader>
@@ -1212,6 +1651,21 @@ export function SettingsPage() {
                   )}
                   Open Logs Folder
                 </Button>
+                <Button
+                  variant="secondary"
+                  size="sm"
+                  onClick={handleDiagnosticsBundle}
+                  disabled={bundling}
+                  title="Zip recent logs and status, with secrets redacted, to attach to a bug report"
+                  data-testid="diagnostics-bundle-btn"
+                >
+                  {bundling ? (
+                    <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                  ) : (
+                    <Bug className="mr-2 h-4 w-4" />
+                  )}
+                  Create Diagnostics Bundle
+                </Button>
               </div>
               <div className="border-t border-[rgb(var(--border))] pt-4">
                 <div className="flex items-center justify-between gap-4">
@@ -7,14 +7,19 @@
  * ## Available Channels
  *
  * - `space-changed` - Space create/update/delete/activate
+ * - `space-config-invalid` - A Space config file failed schema validation
  * - `server-changed` - Server install/uninstall/enable/disable
  * - `server-status-changed` - Connection status updates
  * - `server-auth-progress` - OAuth countdown timer
  * - `server-features-refreshed` - Features discovered/updated
+ * - `runtime-missing` - No npx/uvx/... installed for a stdio server
+ * - `server-update-available` - Newer release of a pinned npx/uvx package
+ * - `server-crash-loop` - A stdio server keeps exiting during startup
+ * - `tool-schema-changed` - A server changed a tool's input schema
  * - `feature-set-changed` - Feature set create/update/delete
  * - `client-changed` - Client registration/update/delete
  * - `grants-changed` - Grant/revoke permissions
- * - `gateway-changed` - Gateway start/stop
+ * - `gateway-changed` - Gateway start/stop, startup auto-connect progress
  * - `mcp-notification` - MCP capability notifications
  *
  * ## Usage
@@ -42,6 +47,8 @@
 
 import { useEffect, useCallback, useRef, useState } from 'react';
 import { listen, UnlistenFn, Event } from '@tauri-apps/api/event';
+import type { DiagnosedCause } from '@/lib/api/serverManager';
+import type { SpaceConfigIssue } from '@/lib/api/spaces';
 
 // ============================================================================
 // TYPES
@@ -50,10 +57,15 @@ import { listen, UnlistenFn, Event } from '@tauri-apps/api/event';
 /** Domain event channels */
 export type DomainEventChannel =
   | 'space-changed'
+  | 'space-config-invalid'
   | 'server-changed'
   | 'server-status-changed'
   | 'server-auth-progress'
   | 'server-features-refreshed'
+  | 'runtime-missing'
+  | 'server-update-available'
+  | 'server-crash-loop'
+  | 'tool-schema-changed'
   | 'feature-set-changed'
   | 'client-changed'
   | 'grants-changed'
@@ -77,6 +89,14 @@ export interface SpaceChangedPayload extends DomainEventPayload {
   to_space_name?: string;
 }
 
+/** A Space config file failed schema validation and wasn't synced */
+export interface SpaceConfigInvalidPayload extends DomainEventPayload {
+  space_id: string;
+  /** Path of the config file */
+  file: string;
+  issues: SpaceConfigIssue[];
+}
+
 /** Server lifecycle event payloads */
 export interface ServerChangedPayload extends DomainEventPayload {
   action: 'installed' | 'uninstalled' | 'config_updated' | 'enabled' | 'disabled';
@@ -118,6 +138,46 @@ export interface ServerFeaturesRefreshedPayload extends DomainEventPayload {
   removed: string[];
 }
 
+/** No package runner installed for a stdio server's ecosystem */
+export interface RuntimeMissingPayload extends DomainEventPayload {
+  space_id: string;
+  server_id: string;
+  ecosystem: 'npm' | 'pypi';
+  /** Runner binaries that would work, most preferred first */
+  runners: string[];
+  install_url: string;
+}
+
+/** A newer release of a runner-launched server's pinned package */
+export interface ServerUpdateAvailablePayload extends DomainEventPayload {
+  space_id: string;
+  server_id: string;
+  ecosystem: 'npm' | 'pypi';
+  package: string;
+  current_version: string;
+  latest_version: string;
+}
+
+/** A stdio server keeps exiting during startup */
+export interface ServerCrashLoopPayload extends DomainEventPayload {
+  space_id: string;
+  server_id: string;
+  cause: DiagnosedCause;
+}
+
+export interface ToolSchemaChangedPayload extends DomainEventPayload {
+  space_id: string;
+  server_id: string;
+  tool: string;
+  /** Top-level parameters added and dropped */
+  added: string[];
+  removed: string[];
+  /** Calls that fit the old schema may not fit the new one */
+  breaking: boolean;
+  /** The tool is pinned: clients keep the old schema until the change is accepted */
+  held: boolean;
+}
+
 /** Feature set event payloads */
 export interface FeatureSetChangedPayload extends DomainEventPayload {
   action: 'created' | 'updated' | 'deleted' | 'members_changed';
@@ -148,9 +208,14 @@ export interface GrantsChangedPayload extends DomainEventPayload {
 
 /** Gateway event payloads */
 export interface GatewayChangedPayload extends DomainEventPayload {
-  action: 'started' | 'stopped';
+  action: 'started' | 'stopped' | 'paused' | 'resumed' | 'auto_connect_progress';
   url?: string;
   port?: number;
+  /** Startup auto-connect progress: servers being connected and how many finished so far */
+  total?: number;
+  connected?: number;
+  needs_oauth?: number;
+  failed?: number;
 }
 
 /** MCP notification payload */
@@ -163,10 +228,15 @@ export interface MCPNotificationPayload extends DomainEventPayload {
 /** Payload type map for type safety */
 export interface PayloadTypeMap {
   'space-changed': SpaceChangedPayload;
+  'space-config-invalid': SpaceConfigInvalidPayload;
   'server-changed': ServerChangedPayload;
   'server-status-changed': ServerStatusChangedPayload;
   'server-auth-progress': ServerAuthProgressPayload;
   'server-features-refreshed': ServerFeaturesRefreshedPayload;
+  'runtime-missing': RuntimeMissingPayload;
+  'server-update-available': ServerUpdateAvailablePayload;
+  'server-crash-loop': ServerCrashLoopPayload;
+  'tool-schema-changed': ToolSchemaChangedPayload;
   'feature-set-changed': FeatureSetChangedPayload;
   'client-changed': ClientChangedPayload;
   'grants-changed': GrantsChangedPayload;
@@ -192,10 +262,15 @@ export type AllEventsCallback = (
 /** All channels that can receive events */
 const ALL_CHANNELS: DomainEventChannel[] = [
   'space-changed',
+  'space-config-invalid',
   'server-changed',
   'server-status-changed',
   'server-auth-progress',
   'server-features-refreshed',
+  'runtime-missing',
+  'server-update-available',
+  'server-crash-loop',
+  'tool-schema-changed',
   'feature-set-changed',
   'client-changed',
   'grants-changed',
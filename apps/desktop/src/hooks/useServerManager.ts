@@ -26,6 +26,9 @@ import {
   getServerAction,
 } from "../lib/api/serverManager";
 
+/** How often ping stats are re-read (the gateway pings every 30s) */
+const HEALTH_POLL_INTERVAL_MS = 30_000;
+
 interface UseServerManagerOptions {
   spaceId: string;
   /** Called when features are updated */
@@ -153,6 +156,8 @@ export function useServerManager({
             flow_id: event.flow_id,
             has_connected_before: event.has_connected_before,
             message: event.message || null,
+            health: event.status === "connected" ? existing?.health ?? null : null,
+            diagnosis: event.status === "error" ? existing?.diagnosis ?? null : null,
           },
         };
       });
@@ -203,6 +208,30 @@ export function useServerManager({
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [spaceId]);
 
+  // Health stats aren't evented; poll them while the page is open
+  useEffect(() => {
+    if (!spaceId) return;
+
+    const timer = setInterval(async () => {
+      try {
+        const result = await getServerStatuses(spaceId);
+        setStatuses((prev) => {
+          const next = { ...prev };
+          for (const [serverId, status] of Object.entries(result)) {
+            if (next[serverId]) {
+              next[serverId] = { ...next[serverId], health: status.health };
+            }
+          }
+          return next;
+        });
+      } catch {
+        // Keep the last known stats; the next poll will retry
+      }
+    }, HEALTH_POLL_INTERVAL_MS);
+
+    return () => clearInterval(timer);
+  }, [spaceId]);
+
   // Actions
   const enable = useCallback(
     (serverId: string) => enableServer(spaceId, serverId),
@@ -0,0 +1,67 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Manifest stored in every backup archive.
+ */
+export interface BackupManifest {
+  format_version: number;
+  app_version: string;
+  schema_version: number;
+  kind: 'manual' | 'scheduled' | 'pre_restore';
+  created_at: string;
+  space_files: string[];
+}
+
+/**
+ * A backup archive on disk.
+ */
+export interface BackupInfo {
+  file_name: string;
+  size_bytes: number;
+  manifest: BackupManifest;
+}
+
+/**
+ * Scheduled backup settings (interval 0 = disabled).
+ */
+export interface BackupSettings {
+  interval_hours: number;
+  keep_count: number;
+}
+
+/**
+ * Create a manual backup of the database, settings and space configs.
+ */
+export async function createBackup(): Promise<BackupInfo> {
+  return invoke('create_backup');
+}
+
+/**
+ * List backups, newest first.
+ */
+export async function listBackups(): Promise<BackupInfo[]> {
+  return invoke('list_backups');
+}
+
+/**
+ * Delete a backup archive.
+ */
+export async function deleteBackup(fileName: string): Promise<void> {
+  return invoke('delete_backup', { fileName });
+}
+
+/**
+ * Restore a backup. The database is swapped in on next launch — relaunch
+ * the app afterwards.
+ */
+export async function restoreBackup(fileName: string): Promise<BackupInfo> {
+  return invoke('restore_backup', { fileName });
+}
+
+export async function getBackupSettings(): Promise<BackupSettings> {
+  return invoke('get_backup_settings');
+}
+
+export async function setBackupSettings(backupSettings: BackupSettings): Promise<void> {
+  return invoke('set_backup_settings', { backupSettings });
+}
@@ -9,3 +9,83 @@ export async function addToVscode(gatewayUrl: string): Promise<void> {
 export async function addToCursor(gatewayUrl: string): Promise<void> {
   return invoke('add_to_cursor', { gatewayUrl });
 }
+
+/** Add McpMux to Windsurf's mcp_config.json. Resolves to the file written. */
+export async function addToWindsurf(gatewayUrl: string): Promise<string> {
+  return invoke('add_to_windsurf', { gatewayUrl });
+}
+
+/** Add McpMux to Zed's settings.json. Resolves to the file written. */
+export async function addToZed(gatewayUrl: string): Promise<string> {
+  return invoke('add_to_zed', { gatewayUrl });
+}
+
+/** JSON for JetBrains AI Assistant's "Add → As JSON" MCP dialog. */
+export async function getJetbrainsMcpConfig(gatewayUrl: string): Promise<string> {
+  return invoke('get_jetbrains_mcp_config', { gatewayUrl });
+}
+
+export type InstallClient = 'vscode' | 'cursor' | 'windsurf' | 'zed' | 'jetbrains';
+
+export interface ClientDetection {
+  client: InstallClient;
+  installed: boolean;
+}
+
+/** Detect which supported clients are installed on this machine. */
+export async function detectInstalledClients(): Promise<ClientDetection[]> {
+  return invoke('detect_installed_clients');
+}
+
+export type DetectableClient = 'claude_desktop' | 'cursor' | 'vscode' | 'windsurf' | 'zed';
+
+export interface ClientInstallation {
+  client: DetectableClient;
+  label: string;
+  /** Whether the app itself appears to be installed */
+  installed: boolean;
+  /** The client's existing MCP config file */
+  config_path: string | null;
+  /** Servers listed in that config */
+  server_count: number;
+  /** Whether the config already lists McpMux */
+  has_mcpmux: boolean;
+}
+
+/** Detect the supported desktop clients and their existing MCP configs. */
+export async function detectClientInstallations(): Promise<ClientInstallation[]> {
+  return invoke('detect_client_installations');
+}
+
+export interface ConfigApplyResult {
+  client: DetectableClient;
+  /** The config file edited */
+  path: string;
+  /** Copy of the file as it was before the edit */
+  backup_path: string | null;
+  action: 'added' | 'updated' | 'unchanged';
+}
+
+/**
+ * Add or update the McpMux entry in a detected client's MCP config, in place.
+ * The previous file is backed up next to it. Fails for configs with comments.
+ */
+export async function applyClientConfig(
+  client: DetectableClient,
+  gatewayUrl: string
+): Promise<ConfigApplyResult> {
+  return invoke('apply_client_config', { client, gatewayUrl });
+}
+
+export interface ConfigReapplyResult {
+  applied: ConfigApplyResult[];
+  failed: { client: DetectableClient; error: string }[];
+}
+
+/**
+ * Point every client config that already lists McpMux at `gatewayUrl`, e.g.
+ * after the gateway had to start on another port. Edited files are backed up.
+ */
+export async function reapplyClientConfigs(gatewayUrl: string): Promise<ConfigReapplyResult> {
+  return invoke('reapply_client_configs', { gatewayUrl });
+}
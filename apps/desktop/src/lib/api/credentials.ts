@@ -0,0 +1,79 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Counts returned by a secrets export or import.
+ */
+export interface SecretsTransferSummary {
+  credentials: number;
+  oauth_registrations: number;
+  server_inputs: number;
+  /** Entries skipped on import because the server isn't installed here */
+  skipped: number;
+}
+
+/**
+ * Export credentials, OAuth registrations and server inputs to a
+ * passphrase-encrypted file (for moving to another machine).
+ */
+export async function exportSecrets(
+  path: string,
+  passphrase: string
+): Promise<SecretsTransferSummary> {
+  return invoke('export_secrets', { path, passphrase });
+}
+
+/**
+ * Import a secrets bundle created by `exportSecrets`.
+ */
+export async function importSecrets(
+  path: string,
+  passphrase: string
+): Promise<SecretsTransferSummary> {
+  return invoke('import_secrets', { path, passphrase });
+}
+
+/**
+ * Progress payload of the `master-key-rotation-progress` event.
+ */
+export interface MasterKeyRotationProgress {
+  phase: 're_encrypting' | 'storing_key' | 'completed';
+  processed: number;
+  total: number;
+}
+
+/**
+ * Rotate the master encryption key and re-encrypt all stored secrets.
+ * Listen to `master-key-rotation-progress` for progress updates.
+ * Resolves to the number of re-encrypted values.
+ */
+export async function rotateMasterKey(): Promise<number> {
+  return invoke('rotate_master_key');
+}
+
+/**
+ * Where the master encryption key is stored.
+ * - `auto`: DPAPI on Windows, OS keychain (with file fallback) elsewhere
+ * - `passphrase`: wrapped with a passphrase entered at startup
+ *   (or `MCPMUX_MASTER_PASSPHRASE`)
+ * - `hardware`: sealed by the TPM (Windows, Linux) or Secure Enclave (macOS)
+ */
+export type KeyProviderKind = 'auto' | 'keychain' | 'file' | 'passphrase' | 'hardware';
+
+/**
+ * Get the selected master key provider.
+ */
+export async function getKeyProvider(): Promise<KeyProviderKind> {
+  return invoke('get_key_provider');
+}
+
+/**
+ * Move the master key to another provider. Takes effect on restart.
+ * `passphrase` is required when switching to the `passphrase` provider
+ * unless `MCPMUX_MASTER_PASSPHRASE` is set.
+ */
+export async function setKeyProvider(
+  provider: KeyProviderKind,
+  passphrase?: string
+): Promise<void> {
+  return invoke('set_key_provider', { provider, passphrase: passphrase ?? null });
+}
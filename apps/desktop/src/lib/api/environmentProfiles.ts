@@ -0,0 +1,76 @@
+/**
+ * Environment Profiles API
+ *
+ * A profile ("dev", "staging", "prod") swaps input values and URLs for all
+ * servers of a space at once. Switching profiles reconnects the enabled
+ * servers whose settings change.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ProfileServerOverrides {
+  inputs: Record<string, string>;
+  url?: string;
+}
+
+export interface EnvironmentProfile {
+  id: string;
+  space_id: string;
+  name: string;
+  inputs: Record<string, string>;
+  servers: Record<string, ProfileServerOverrides>;
+  is_active: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ProfileReconnectResult {
+  reconnected: string[];
+  failed: { server_id: string; error: string }[];
+}
+
+/**
+ * List the environment profiles of a space
+ */
+export async function listSpaceProfiles(spaceId: string): Promise<EnvironmentProfile[]> {
+  return invoke('list_space_profiles', { spaceId });
+}
+
+/**
+ * Create an empty, inactive profile
+ */
+export async function createSpaceProfile(
+  spaceId: string,
+  name: string
+): Promise<EnvironmentProfile> {
+  return invoke('create_space_profile', { spaceId, name });
+}
+
+/**
+ * Replace a profile's name, input values and per-server overrides
+ */
+export async function updateSpaceProfile(
+  id: string,
+  name: string,
+  inputs: Record<string, string>,
+  servers: Record<string, ProfileServerOverrides>
+): Promise<EnvironmentProfile> {
+  return invoke('update_space_profile', { id, name, inputs, servers });
+}
+
+/**
+ * Delete a profile
+ */
+export async function deleteSpaceProfile(id: string): Promise<void> {
+  return invoke('delete_space_profile', { id });
+}
+
+/**
+ * Switch a space to a profile, or back to the servers' own settings with null
+ */
+export async function setSpaceProfile(
+  spaceId: string,
+  profileId: string | null
+): Promise<ProfileReconnectResult> {
+  return invoke('set_space_profile', { spaceId, profileId });
+}
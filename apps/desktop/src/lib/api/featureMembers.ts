@@ -9,7 +9,7 @@ import { invoke } from '@tauri-apps/api/core';
 export interface FeatureSetMember {
   id: string;
   feature_set_id: string;
-  member_type: 'feature' | 'feature_set';
+  member_type: 'feature' | 'feature_set' | 'annotation';
   member_id: string;
   mode: 'include' | 'exclude';
 }
@@ -29,9 +29,10 @@ export function isStarterFeatureSet(fs: { feature_set_type: FeatureSetType }): b
 }
 
 /**
- * Member type in a feature set.
+ * Member type in a feature set. An `annotation` member's `member_id` is an
+ * `AnnotationHint` and stands for every tool with that hint.
  */
-export type MemberType = 'feature' | 'feature_set';
+export type MemberType = 'feature' | 'feature_set' | 'annotation';
 
 /**
  * Mode for including/excluding members.
@@ -8,6 +8,7 @@ export interface GatewayStatus {
   url: string | null;
   active_sessions: number;
   connected_backends: number;
+  traffic_paused: boolean;
 }
 
 /**
@@ -17,6 +18,18 @@ export interface GatewayPublicUrlSettings {
   configuredPublicBaseUrl: string | null;
   activePublicBaseUrl: string | null;
   localBaseUrl: string | null;
+  /** Host in the local URL; null for `localhost` */
+  configuredBaseUrlHost: string | null;
+}
+
+/**
+ * A domain event as recorded in the event journal. `event` is the serialized
+ * DomainEvent, tagged by `type`.
+ */
+export interface JournaledEvent {
+  sequence: number;
+  recorded_at: string;
+  event: { type: string } & Record<string, unknown>;
 }
 
 /**
@@ -31,6 +44,18 @@ export async function getGatewayStatus(spaceId?: string): Promise<GatewayStatus>
   return invoke('get_gateway_status', { spaceId });
 }
 
+/**
+ * Get the domain events journaled after `sequence`, oldest first. Event
+ * payloads the UI receives carry their `sequence`, so a reloaded view can
+ * pass the last one it saw to catch up.
+ */
+export async function getDomainEventsSince(
+  sequence: number,
+  limit?: number
+): Promise<JournaledEvent[]> {
+  return invoke('get_domain_events_since', { sequence, limit });
+}
+
 /**
  * Get the configured and currently-active public gateway URL settings.
  */
@@ -45,6 +70,14 @@ export async function setGatewayPublicBaseUrl(publicBaseUrl: string | null): Pro
   return invoke('set_gateway_public_base_url', { publicBaseUrl });
 }
 
+/**
+ * Set the host in the local gateway URL, e.g. `127.0.0.1` where `localhost`
+ * doesn't reach the gateway. Pass null to go back to `localhost`.
+ */
+export async function setGatewayBaseUrlHost(host: string | null): Promise<void> {
+  return invoke('set_gateway_base_url_host', { host });
+}
+
 /**
  * Clear the public base URL and return to local-only localhost metadata.
  */
@@ -52,6 +85,20 @@ export async function resetGatewayPublicBaseUrl(): Promise<void> {
   return invoke('reset_gateway_public_base_url');
 }
 
+/**
+ * A process listening on a port.
+ */
+export interface PortOwner {
+  pid: number;
+  /** Executable name, when it can be read */
+  name: string | null;
+}
+
+/** `node (PID 1234)`, or just `PID 1234` without a name. */
+export function describePortOwner(owner: PortOwner): string {
+  return owner.name ? `${owner.name} (PID ${owner.pid})` : `PID ${owner.pid}`;
+}
+
 /**
  * Probe result for a proposed gateway start.
  *
@@ -62,6 +109,8 @@ export interface GatewayStartProbe {
   preferredPort: number;
   preferredAvailable: boolean;
   source: 'override' | 'configured' | 'default';
+  /** The process holding the preferred port, when it's busy and can be found */
+  owner: PortOwner | null;
 }
 
 /**
@@ -79,6 +128,19 @@ export async function probeGatewayStart(port?: number): Promise<GatewayStartProb
 export interface PendingPortConflict {
   preferredPort: number;
   source: 'configured' | 'default';
+  /** The process holding the port, when it can be found */
+  owner: PortOwner | null;
+}
+
+/**
+ * Payload of `gateway-port-changed`: the gateway started on another port than
+ * its preferred one, so client configs pointing at `preferredPort` no longer
+ * reach it.
+ */
+export interface GatewayPortChangedPayload {
+  preferredPort: number;
+  port: number;
+  url: string;
 }
 
 /**
@@ -135,6 +197,49 @@ export async function stopGateway(): Promise<void> {
   return invoke('stop_gateway');
 }
 
+/**
+ * A tool call made by a connected session.
+ */
+export interface RecentToolCall {
+  tool: string;
+  started_at: string;
+  duration_ms: number;
+  success: boolean;
+}
+
+/**
+ * Live activity of one connected MCP session.
+ */
+export interface SessionActivity {
+  session_id: string;
+  client_id: string;
+  client_name: string | null;
+  client_version: string | null;
+  /** Protocol version negotiated with the client */
+  protocol_version: string | null;
+  connected_at: string;
+  last_activity: string;
+  requests_in_flight: number;
+  /** Newest first */
+  recent_tool_calls: RecentToolCall[];
+}
+
+/**
+ * Every connected MCP session, most recently active first. Empty while the
+ * gateway is stopped.
+ */
+export async function getSessionActivity(): Promise<SessionActivity[]> {
+  return invoke('get_session_activity');
+}
+
+/**
+ * Pause or resume tool calls. While paused, the gateway and its sessions stay
+ * up but every `tools/call` is rejected. Survives gateway restarts.
+ */
+export async function pauseGatewayTraffic(paused: boolean): Promise<void> {
+  return invoke('pause_gateway_traffic', { paused });
+}
+
 /**
  * Restart the gateway server. Same semantics as `startGateway`.
  */
@@ -251,6 +356,12 @@ export interface OAuthClient {
    * to either "Reports workspace" or "Rootless".
    */
   roots_capability_known: boolean;
+
+  /**
+   * `true` while the client is suspended. The gateway refuses every MCP
+   * request it makes with 403; its registration and grants are kept.
+   */
+  suspended: boolean;
 }
 
 /**
@@ -277,6 +388,13 @@ export async function updateOAuthClient(
   return invoke('update_oauth_client', { clientId, settings });
 }
 
+/**
+ * Suspend or reinstate an OAuth client.
+ */
+export async function setOAuthClientSuspended(clientId: string, suspended: boolean): Promise<void> {
+  return invoke('set_oauth_client_suspended', { clientId, suspended });
+}
+
 /**
  * Delete an OAuth client.
  */
@@ -395,6 +513,31 @@ export async function revokeClientApiKey(keyId: string): Promise<void> {
   return invoke('revoke_client_api_key', { keyId });
 }
 
+/** A one-time code a remote device redeems at `/oauth/pair` for its own API key. */
+export interface DevicePairing {
+  code: string;
+  /** Gateway URL plus code — what the QR code encodes. */
+  pairUrl: string;
+  /** Unix timestamp (seconds) after which the code stops working. */
+  expiresAt: number;
+  /** SVG markup of the QR code. */
+  qrSvg: string;
+}
+
+/**
+ * Create a pairing code for a phone or other remote client. The device that
+ * redeems it becomes a pre-approved API-key client, optionally locked to a space.
+ */
+export async function createDevicePairing(
+  clientName?: string | null,
+  lockedSpaceId?: string | null
+): Promise<DevicePairing> {
+  return invoke('create_device_pairing', {
+    clientName: clientName ?? null,
+    lockedSpaceId: lockedSpaceId ?? null,
+  });
+}
+
 /**
  * Result of bulk server connection.
  */
@@ -430,6 +573,44 @@ export async function getPoolStats(): Promise<PoolStats> {
   return invoke('get_pool_stats');
 }
 
+/**
+ * One server's state and ping stats in a pool stats snapshot.
+ */
+export interface ServerStatsSnapshot {
+  space_id: string;
+  server_id: string;
+  state: string;
+  healthy: boolean;
+  /** Latency of the most recent ping, null if it failed or none ran */
+  latency_ms: number | null;
+  avg_latency_ms: number | null;
+  /** Share of failed pings in the health window (0.0 - 1.0) */
+  error_rate: number;
+  consecutive_failures: number;
+  requests_served: number;
+}
+
+/**
+ * Pool stats recorded after a health check round.
+ */
+export interface PoolStatsSnapshot {
+  timestamp: string;
+  total_instances: number;
+  connected_instances: number;
+  connecting_instances: number;
+  failed_instances: number;
+  oauth_pending_instances: number;
+  servers: ServerStatsSnapshot[];
+}
+
+/**
+ * Get the pool stats history for charting trends, oldest first.
+ * Pass `since` (an ISO timestamp) to fetch only newer snapshots.
+ */
+export async function getPoolStatsHistory(since?: string): Promise<PoolStatsSnapshot[]> {
+  return invoke('get_pool_stats_history', { since: since ?? null });
+}
+
 /**
  * Result of OAuth token refresh operation.
  */
@@ -456,3 +637,174 @@ export async function refreshOAuthTokensOnStartup(): Promise<RefreshResult> {
 export async function openUrl(url: string): Promise<void> {
   return invoke('open_url', { url });
 }
+
+export type ServiceManager = 'systemd' | 'launchd' | 'task_scheduler';
+
+export interface ServiceStatus {
+  /** null on platforms without a supported service manager */
+  manager: ServiceManager | null;
+  installed: boolean;
+}
+
+/** Whether the gateway is registered to run at login without the UI. */
+export async function getServiceStatus(): Promise<ServiceStatus> {
+  return invoke('get_service_status');
+}
+
+/**
+ * Register and start the gateway as a login service on the configured port.
+ * Resolves to where the service definition lives.
+ */
+export async function installService(): Promise<string> {
+  return invoke('install_service');
+}
+
+/** Stop and remove the gateway login service. */
+export async function uninstallService(): Promise<void> {
+  return invoke('uninstall_service');
+}
+
+/** Whether the gateway answers browser CORS requests (default true). */
+export async function getGatewayCorsEnabled(): Promise<boolean> {
+  return invoke('get_gateway_cors_enabled');
+}
+
+/** Enable/disable CORS. Applies to a running gateway immediately. */
+export async function setGatewayCorsEnabled(enabled: boolean): Promise<void> {
+  return invoke('set_gateway_cors_enabled', { enabled });
+}
+
+/**
+ * Origins, methods and headers a CORS policy allows. `*` allows any, and
+ * `https://*.github.dev` matches every subdomain.
+ */
+export interface CorsPolicy {
+  allowedOrigins: string[];
+  allowedMethods: string[];
+  allowedHeaders: string[];
+}
+
+/** CORS policies: the default, plus overrides by path prefix (longest wins). */
+export interface CorsConfig {
+  default: CorsPolicy;
+  routes: Record<string, CorsPolicy>;
+}
+
+/** Effective CORS policies (the defaults unless changed). */
+export async function getGatewayCorsConfig(): Promise<CorsConfig> {
+  return invoke('get_gateway_cors_config');
+}
+
+/**
+ * Replace the CORS policies, or restore the defaults with `null`. Applies to
+ * a running gateway immediately.
+ */
+export async function setGatewayCorsConfig(config: CorsConfig | null): Promise<void> {
+  return invoke('set_gateway_cors_config', { config });
+}
+
+/** Effective shared rate limits: path prefix → requests per minute. */
+export async function getGatewayRateLimits(): Promise<Record<string, number>> {
+  return invoke('get_gateway_rate_limits');
+}
+
+/**
+ * Override shared rate limits (path prefix → requests per minute). Omitted
+ * prefixes keep their limits. Applies to a running gateway immediately.
+ */
+export async function setGatewayRateLimits(limits: Record<string, number>): Promise<void> {
+  return invoke('set_gateway_rate_limits', { limits });
+}
+
+/**
+ * One rate limit policy. Without `clientId` all clients share the budget;
+ * `*` gives each client its own, and a client ID limits just that client.
+ */
+export interface RateLimitPolicy {
+  route: string;
+  clientId?: string;
+  maxRequests: number;
+  windowSecs: number;
+}
+
+/** Requests refused under one policy (and client) since the gateway started. */
+export interface RateLimitRejections {
+  route: string;
+  clientId?: string;
+  count: number;
+  /** Unix timestamp (seconds) */
+  lastRejectedAt: number;
+}
+
+/** The rate limit policy table in effect. */
+export async function getGatewayRateLimitPolicies(): Promise<RateLimitPolicy[]> {
+  return invoke('get_gateway_rate_limit_policies');
+}
+
+/**
+ * Replace the rate limit policy table, or restore the defaults with `null`.
+ * Applies to a running gateway immediately.
+ */
+export async function setGatewayRateLimitPolicies(
+  policies: RateLimitPolicy[] | null
+): Promise<void> {
+  return invoke('set_gateway_rate_limit_policies', { policies });
+}
+
+/** Requests the running gateway refused for rate limits. */
+export async function getGatewayRateLimitRejections(): Promise<RateLimitRejections[]> {
+  return invoke('get_gateway_rate_limit_rejections');
+}
+
+/** Limits protecting the gateway from clients that send too much. */
+export interface RequestLimits {
+  /** Largest request body accepted, in bytes */
+  maxBodyBytes: number;
+  /** How long a client gets to send a request's headers */
+  headerReadTimeoutSecs: number;
+  /** Open connections allowed from one IP address (local clients share one) */
+  maxConnectionsPerIp: number;
+}
+
+/** Request body size, header timeout and per-IP connection limits. */
+export async function getGatewayRequestLimits(): Promise<RequestLimits> {
+  return invoke('get_gateway_request_limits');
+}
+
+/**
+ * Replace the request limits, or restore the defaults with `null`. Applies to
+ * a running gateway immediately.
+ */
+export async function setGatewayRequestLimits(limits: RequestLimits | null): Promise<void> {
+  return invoke('set_gateway_request_limits', { limits });
+}
+
+/**
+ * How many enabled servers are connected at the same time on startup.
+ */
+export async function getGatewayAutoConnectConcurrency(): Promise<number> {
+  return invoke('get_gateway_auto_connect_concurrency');
+}
+
+/**
+ * Set how many servers connect at once on startup (1-32), or pass null to
+ * restore the default of 4. Takes effect the next time the gateway starts.
+ */
+export async function setGatewayAutoConnectConcurrency(concurrency: number | null): Promise<void> {
+  return invoke('set_gateway_auto_connect_concurrency', { concurrency });
+}
+
+/**
+ * Port of the gRPC control plane, or null when it is off.
+ */
+export async function getGatewayGrpcPort(): Promise<number | null> {
+  return invoke('get_gateway_grpc_port');
+}
+
+/**
+ * Serve the gRPC control plane on a port, or pass null to turn it off.
+ * Takes effect the next time the gateway starts.
+ */
+export async function setGatewayGrpcPort(port: number | null): Promise<void> {
+  return invoke('set_gateway_grpc_port', { port });
+}
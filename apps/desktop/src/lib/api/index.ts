@@ -6,7 +6,14 @@ export * from './featureSets';
 export * from './serverFeatures';
 export * from './clientInstall';
 export * from './clients';
+export * from './onboarding';
 export * from './gateway';
 export * from './serverManager';
 export * from './workspaceBindings';
 export * from './metaTools';
+export * from './credentials';
+export * from './backup';
+export * from './sync';
+export * from './runtimes';
+export * from './serverGroups';
+export * from './environmentProfiles';
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 
 /**
  * Server log entry from the backend.
@@ -26,6 +27,83 @@ export async function getServerLogs(
   });
 }
 
+/**
+ * Search over a server's stored logs, rotated and compressed files included.
+ */
+export interface SearchLogsInput {
+  /** RFC 3339 lower bound */
+  since?: string;
+  /** RFC 3339 upper bound */
+  until?: string;
+  level_filter?: string;
+  sources?: string[];
+  /** Case-insensitive text to find in the message or metadata */
+  text?: string;
+  /** Matches to skip, newest first */
+  offset?: number;
+  limit?: number;
+}
+
+/**
+ * One page of search results. Entries are chronological; `nextOffset`
+ * fetches the next, older page.
+ */
+export interface ServerLogSearchPage {
+  entries: ServerLogEntry[];
+  nextOffset: number | null;
+}
+
+/**
+ * Search a server's logs, newest matches first.
+ */
+export async function searchServerLogs(
+  serverId: string,
+  input: SearchLogsInput
+): Promise<ServerLogSearchPage> {
+  return invoke('search_server_logs', { serverId, input });
+}
+
+/**
+ * A live log line from a tail started with `tailServerLogs`.
+ */
+export interface ServerLogTailEvent {
+  tailId: string;
+  entry: ServerLogEntry;
+}
+
+/**
+ * Stream new log lines for a server as they are written. Entries below
+ * `levelFilter` or from other `sources` are dropped by the backend.
+ * Returns a function that stops the tail.
+ */
+export async function tailServerLogs(
+  serverId: string,
+  onEntry: (entry: ServerLogEntry) => void,
+  options: { levelFilter?: string; sources?: string[] } = {}
+): Promise<() => void> {
+  let tailId: string | null = null;
+  const unlisten = await listen<ServerLogTailEvent>('server-log-tail', (event) => {
+    if (event.payload.tailId === tailId) {
+      onEntry(event.payload.entry);
+    }
+  });
+  try {
+    tailId = await invoke<string>('start_server_log_tail', {
+      serverId,
+      levelFilter: options.levelFilter,
+      sources: options.sources,
+    });
+  } catch (e) {
+    unlisten();
+    throw e;
+  }
+  const id = tailId;
+  return () => {
+    unlisten();
+    invoke('stop_server_log_tail', { tailId: id }).catch(() => {});
+  };
+}
+
 /**
  * Clear logs for a server.
  */
@@ -55,3 +133,110 @@ export async function setLogRetentionDays(days: number): Promise<void> {
   return invoke('set_log_retention_days', { days });
 }
 
+
+/**
+ * Get the saved log filter (null = built-in default).
+ */
+export async function getLogLevel(): Promise<string | null> {
+  return invoke('get_log_level');
+}
+
+/**
+ * Set the log filter, e.g. "debug" or "info,mcpmux_gateway=trace", and apply
+ * it immediately. Pass null to restore the default.
+ */
+export async function setLogLevel(level: string | null): Promise<void> {
+  return invoke('set_log_level', { level });
+}
+
+/**
+ * Zip recent logs, gateway status and version info, with secrets redacted,
+ * for attaching to a bug report. Returns the bundle's path.
+ */
+export async function generateDiagnosticsBundle(): Promise<string> {
+  return invoke('generate_diagnostics_bundle');
+}
+
+/**
+ * One JSON-RPC message exchanged with a server, recorded while traffic
+ * recording is on. `raw` has secrets redacted and is cut short when
+ * `truncated` is set.
+ */
+export interface TrafficFrame {
+  seq: number;
+  timestamp: string;
+  direction: 'outgoing' | 'incoming';
+  method: string | null;
+  id: string | number | null;
+  size: number;
+  truncated: boolean;
+  raw: string;
+}
+
+/**
+ * Recorded traffic for a server, oldest frame first.
+ */
+export interface ServerTraffic {
+  recording: boolean;
+  frames: TrafficFrame[];
+}
+
+/**
+ * Turn JSON-RPC traffic recording on or off for a server. Recording lasts
+ * until it's turned off or the app restarts.
+ */
+export async function setServerTrafficRecording(
+  serverId: string,
+  enabled: boolean
+): Promise<void> {
+  return invoke('set_server_traffic_recording', { serverId, enabled });
+}
+
+/**
+ * Get the frames recorded for a server.
+ */
+export async function getServerTraffic(serverId: string): Promise<ServerTraffic> {
+  return invoke('get_server_traffic', { serverId });
+}
+
+/**
+ * Drop the frames recorded for a server.
+ */
+export async function clearServerTraffic(serverId: string): Promise<void> {
+  return invoke('clear_server_traffic', { serverId });
+}
+
+/**
+ * Faults injected into a server's tool calls. `drop_percent` of calls fail
+ * without reaching the server, every call waits `delay_ms` first, and
+ * `expire_oauth` fails the next call as if its token had expired.
+ */
+export interface FaultPlan {
+  drop_percent: number;
+  delay_ms: number;
+  expire_oauth: boolean;
+}
+
+/**
+ * Faults injected into a server's tool calls. `available` is false in
+ * release builds, which refuse to inject faults.
+ */
+export interface ServerFaults {
+  available: boolean;
+  plan: FaultPlan;
+}
+
+/**
+ * Get the faults injected into a server's tool calls.
+ */
+export async function getServerFaults(serverId: string): Promise<ServerFaults> {
+  return invoke('get_server_faults', { serverId });
+}
+
+/**
+ * Inject faults into a server's tool calls until the app restarts. A plan
+ * with nothing set removes them.
+ */
+export async function setServerFaults(serverId: string, plan: FaultPlan): Promise<void> {
+  return invoke('set_server_faults', { serverId, plan });
+}
@@ -0,0 +1,78 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { ServerDefinition } from '../../types/registry';
+import type { InstallClient } from './clientInstall';
+import type { ImportClient } from './registry';
+
+export type OnboardingStep =
+  | 'detect_clients'
+  | 'suggest_servers'
+  | 'default_grants'
+  | 'verify_gateway';
+
+export type OnboardingStatus = 'running' | 'completed' | 'failed';
+
+/** Payload of the `onboarding-progress` event. */
+export interface OnboardingProgress {
+  step: OnboardingStep;
+  status: OnboardingStatus;
+  /** What happened, or why the step failed */
+  message: string | null;
+}
+
+export interface DetectedClient {
+  label: string;
+  /** Set if McpMux can add itself to this client */
+  install: InstallClient | null;
+  /** Set if this client's MCP config can be imported */
+  import: ImportClient | null;
+  config_path: string | null;
+}
+
+export interface ServerSuggestion {
+  definition: ServerDefinition;
+  /** Already installed in the default Space */
+  installed: boolean;
+}
+
+export interface DefaultGrants {
+  feature_set_id: string;
+  added: number;
+  /** The Starter FeatureSet already had members, so nothing was changed */
+  already_configured: boolean;
+}
+
+export interface GatewayCheck {
+  url: string;
+  reachable: boolean;
+  status: number | null;
+  latency_ms: number;
+  error: string | null;
+}
+
+/** Step 1: detect the AI clients installed on this machine. */
+export async function onboardingDetectClients(): Promise<DetectedClient[]> {
+  return invoke('onboarding_detect_clients');
+}
+
+/** Step 2: suggest starter servers for the default Space. */
+export async function onboardingSuggestServers(): Promise<ServerSuggestion[]> {
+  return invoke('onboarding_suggest_servers');
+}
+
+/** Step 3: grant the default Space's Starter FeatureSet its installed features. */
+export async function onboardingCreateDefaultGrants(): Promise<DefaultGrants> {
+  return invoke('onboarding_create_default_grants');
+}
+
+/** Step 4: check that the running gateway answers on its URL. */
+export async function onboardingVerifyGateway(): Promise<GatewayCheck> {
+  return invoke('onboarding_verify_gateway');
+}
+
+/** Follow the progress of the onboarding steps. */
+export async function onOnboardingProgress(
+  handler: (progress: OnboardingProgress) => void
+): Promise<UnlistenFn> {
+  return listen<OnboardingProgress>('onboarding-progress', (event) => handler(event.payload));
+}
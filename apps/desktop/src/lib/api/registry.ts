@@ -3,7 +3,17 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { RegistryCategory, ServerDefinition, InstalledServerState, UiConfig, HomeConfig } from '../../types/registry';
+import type {
+  RegistryCategory,
+  RegistryFormat,
+  RegistryTrust,
+  ServerDefinition,
+  InstalledServerState,
+  UiConfig,
+  HomeConfig,
+  ToolHooks,
+  CallContext,
+} from '../../types/registry';
 
 /** Discover all servers (definitions from all sources) */
 export async function discoverServers(): Promise<ServerDefinition[]> {
@@ -25,12 +35,83 @@ export async function isRegistryOffline(): Promise<boolean> {
   return invoke<boolean>('is_registry_offline');
 }
 
+/** Offline state and age of the served registry data */
+export interface RegistrySyncStatus {
+  offline: boolean;
+  /** When the oldest served bundle was last confirmed current (ISO 8601) */
+  last_synced_at: string | null;
+}
+
+/** Get the offline state and when the registry data was last synced */
+export async function getRegistrySyncStatus(): Promise<RegistrySyncStatus> {
+  return invoke<RegistrySyncStatus>('get_registry_sync_status');
+}
+
 /** Force refresh server discovery from all sources (ignores cache) 
  * Returns number of newly auto-installed user-configured servers */
 export async function refreshRegistry(): Promise<number> {
   return invoke<number>('refresh_registry');
 }
 
+/** A registry API servers are loaded from */
+export interface RegistrySource {
+  id: string;
+  name: string;
+  url: string;
+  /** Higher wins when registries define the same server ID */
+  priority: number;
+  /** Server IDs of this registry become `<namespace>/<id>` */
+  namespace?: string | null;
+  trust: RegistryTrust;
+  /** Hide entries not signed by a trusted publisher key */
+  require_signatures?: boolean;
+  /** API the registry speaks (ignored for file:// folders) */
+  format?: RegistryFormat;
+  /** Kind of credentials stored for this registry (read-only) */
+  auth?: RegistryAuthKind | null;
+}
+
+/** A trusted publisher key that registry entries can be signed with */
+export interface PublisherKey {
+  id: string;
+  name: string;
+  /** Base64 ed25519 public key */
+  public_key: string;
+}
+
+/** Kind of credentials a private registry uses */
+export type RegistryAuthKind = 'bearer' | 'basic';
+
+/** Credentials sent to a private registry */
+export type RegistryAuth =
+  | { type: 'bearer'; token: string }
+  | { type: 'basic'; username: string; password: string };
+
+/** List the registries servers are loaded from, highest priority first */
+export async function listRegistrySources(): Promise<RegistrySource[]> {
+  return invoke<RegistrySource[]>('list_registry_sources');
+}
+
+/** Replace the configured registries (empty = public registry only) and reload servers */
+export async function setRegistrySources(sources: RegistrySource[]): Promise<RegistrySource[]> {
+  return invoke<RegistrySource[]>('set_registry_sources', { sources });
+}
+
+/** Set or clear (null) the credentials of a registry, then reload servers */
+export async function setRegistryAuth(registryId: string, auth: RegistryAuth | null): Promise<void> {
+  return invoke<void>('set_registry_auth', { registryId, auth });
+}
+
+/** List the trusted publisher keys */
+export async function listPublisherKeys(): Promise<PublisherKey[]> {
+  return invoke<PublisherKey[]>('list_publisher_keys');
+}
+
+/** Replace the trusted publisher keys and reload servers */
+export async function setPublisherKeys(keys: PublisherKey[]): Promise<PublisherKey[]> {
+  return invoke<PublisherKey[]>('set_publisher_keys', { keys });
+}
+
 /** Get a specific server definition */
 export async function getServerDefinition(serverId: string): Promise<ServerDefinition | null> {
   return invoke<ServerDefinition | null>('get_server_definition', { serverId });
@@ -41,16 +122,121 @@ export async function listCategories(): Promise<RegistryCategory[]> {
   return invoke<RegistryCategory[]>('list_registry_categories');
 }
 
-/** Install a server (adds to DB) */
-export async function installServer(id: string, spaceId: string): Promise<void> {
-  return invoke<void>('install_server', { id, spaceId });
+/** Install a server (adds to DB). Servers from an untrusted registry need
+ * `confirmUntrusted`; servers from a trusted one are installed enabled. */
+export async function installServer(
+  id: string,
+  spaceId: string,
+  confirmUntrusted?: boolean
+): Promise<void> {
+  return invoke<void>('install_server', { id, spaceId, confirmUntrusted });
+}
+
+/** One server of an install manifest; `config` makes it a custom server
+ * in the space config format, otherwise `id` is a registry ID */
+export interface ManifestServer {
+  id: string;
+  config?: Record<string, unknown>;
+  inputs?: Record<string, string>;
+  enable?: boolean;
 }
 
-/** Uninstall a server (removes from DB) */
+export type BulkInstallResult = { server_id: string } & (
+  | { status: 'installed'; enabled: boolean }
+  | { status: 'already_installed' }
+  | { status: 'failed'; error: string }
+  | { status: 'not_installed' }
+);
+
+/** Install the servers of a manifest into a space, all or none */
+export async function bulkInstallServers(
+  spaceId: string,
+  servers: ManifestServer[],
+  confirmUntrusted?: boolean
+): Promise<{ results: BulkInstallResult[] }> {
+  return invoke('bulk_install_servers', {
+    spaceId,
+    manifest: { servers },
+    confirmUntrusted,
+  });
+}
+
+/** Uninstall a server. Registry and manually added servers go to the trash;
+ * servers from a config file are removed from it and deleted. */
 export async function uninstallServer(id: string, spaceId: string): Promise<void> {
   return invoke<void>('uninstall_server', { id, spaceId });
 }
 
+/** List uninstalled servers of a space that can still be restored */
+export async function listTrashedServers(spaceId: string): Promise<InstalledServerState[]> {
+  return invoke<InstalledServerState[]>('list_trashed_servers', { spaceId });
+}
+
+/** Restore an uninstalled server with its settings and credentials */
+export async function restoreServer(id: string, spaceId: string): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('restore_server', { id, spaceId });
+}
+
+/** Permanently delete an uninstalled server from the trash */
+export async function purgeTrashedServer(id: string, spaceId: string): Promise<void> {
+  return invoke<void>('purge_trashed_server', { id, spaceId });
+}
+
+/** Days uninstalled servers can be restored (0 = until deleted) */
+export async function getServerTrashRetentionDays(): Promise<number> {
+  return invoke<number>('get_server_trash_retention_days');
+}
+
+/** Set how many days uninstalled servers can be restored (0 = until deleted) */
+export async function setServerTrashRetentionDays(days: number): Promise<void> {
+  return invoke<void>('set_server_trash_retention_days', { days });
+}
+
+/** Enabled servers of a space unused for the configured number of days */
+export async function getUnusedServers(spaceId: string): Promise<InstalledServerState[]> {
+  return invoke<InstalledServerState[]>('get_unused_servers', { spaceId });
+}
+
+/** Days without a successful tool call before a server counts as unused (0 = never) */
+export async function getServerUnusedDays(): Promise<number> {
+  return invoke<number>('get_server_unused_days');
+}
+
+/** Set the days without a successful tool call before a server counts as unused */
+export async function setServerUnusedDays(days: number): Promise<void> {
+  return invoke<void>('set_server_unused_days', { days });
+}
+
+/** Whether unused servers are disabled automatically */
+export async function getAutoDisableUnusedServers(): Promise<boolean> {
+  return invoke<boolean>('get_auto_disable_unused_servers');
+}
+
+/** Set whether unused servers are disabled automatically */
+export async function setAutoDisableUnusedServers(enabled: boolean): Promise<void> {
+  return invoke<void>('set_auto_disable_unused_servers', { enabled });
+}
+
+/** MCP client whose config can be imported */
+export type ImportClient = 'claude_desktop' | 'cursor' | 'vscode' | 'windsurf';
+
+export interface ClientImportReport {
+  path: string;
+  imported: string[];
+  skipped: { name: string; reason: string }[];
+}
+
+/** Import servers from another client's config (default path when omitted).
+ * Env vars and headers become inputs; imported servers start disabled. */
+export async function importFromClientConfig(
+  client: ImportClient,
+  spaceId: string,
+  path?: string,
+  names?: string[]
+): Promise<ClientImportReport> {
+  return invoke<ClientImportReport>('import_from_client_config', { client, spaceId, path, names });
+}
+
 /** List installed servers (returns state from DB) */
 export async function listInstalledServers(spaceId?: string): Promise<InstalledServerState[]> {
   return invoke<InstalledServerState[]>('list_installed_servers', { spaceId });
@@ -80,6 +266,81 @@ export async function setServerOAuthConnected(
   return invoke<void>('set_server_oauth_connected', { id, connected, spaceId });
 }
 
+/** Let a stdio server inherit the full desktop environment (opt-out of sanitization) */
+export async function setServerInheritEnv(
+  id: string,
+  inheritEnv: boolean,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_inherit_env', { id, inheritEnv, spaceId });
+}
+
+/** Set gateway-enforced allowed roots for a filesystem server (empty = unrestricted) */
+export async function setServerFsAllowedRoots(
+  id: string,
+  roots: string[],
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_fs_allowed_roots', { id, roots, spaceId });
+}
+
+/** Forward tool calls without checking arguments against the tool's input schema */
+export async function setServerSkipArgumentValidation(
+  id: string,
+  skip: boolean,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_skip_argument_validation', { id, skip, spaceId });
+}
+
+/** Set connect and request timeouts in seconds; null falls back to the default */
+export async function setServerTimeouts(
+  id: string,
+  connectSecs: number | null,
+  requestSecs: number | null,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_timeouts', {
+    id,
+    connectSecs,
+    requestSecs,
+    spaceId,
+  });
+}
+
+/** Grant or revoke a WASI server's filesystem and network access */
+export async function setServerWasmGrants(
+  id: string,
+  filesystem: boolean,
+  network: boolean,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_wasm_grants', {
+    id,
+    filesystem,
+    network,
+    spaceId,
+  });
+}
+
+/** Set the hooks run around a server's tool calls; empty clears them */
+export async function setServerToolHooks(
+  id: string,
+  hooks: ToolHooks,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_tool_hooks', { id, hooks, spaceId });
+}
+
+/** Set the context added to a server's tool calls; empty clears it */
+export async function setServerCallContext(
+  id: string,
+  context: CallContext,
+  spaceId: string
+): Promise<InstalledServerState> {
+  return invoke<InstalledServerState>('set_server_call_context', { id, context, spaceId });
+}
+
 /** Save input values for a server */
 export async function saveServerInputs(
   id: string,
@@ -0,0 +1,54 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type ManagedRuntimeKind = 'node' | 'uv';
+
+/**
+ * A pinned Node.js or uv build McpMux can install for stdio servers.
+ */
+export interface ManagedRuntimeStatus {
+  kind: ManagedRuntimeKind;
+  name: string;
+  version: string;
+  installed: boolean;
+  /** False when no build exists for this OS/architecture */
+  supported: boolean;
+  bin_dir?: string;
+}
+
+/**
+ * Managed runtimes and whether stdio servers use them.
+ */
+export interface ManagedRuntimesInfo {
+  enabled: boolean;
+  runtimes: ManagedRuntimeStatus[];
+}
+
+/**
+ * Get installed managed runtimes and whether they're enabled.
+ */
+export async function getManagedRuntimes(): Promise<ManagedRuntimesInfo> {
+  return invoke('get_managed_runtimes');
+}
+
+/**
+ * Use (or stop using) managed runtimes for servers started from now on.
+ */
+export async function setManagedRuntimesEnabled(enabled: boolean): Promise<void> {
+  return invoke('set_managed_runtimes_enabled', { enabled });
+}
+
+/**
+ * Download, verify and install a managed runtime.
+ */
+export async function installManagedRuntime(kind: ManagedRuntimeKind): Promise<ManagedRuntimesInfo> {
+  return invoke('install_managed_runtime', { kind });
+}
+
+/**
+ * Remove a managed runtime.
+ */
+export async function uninstallManagedRuntime(
+  kind: ManagedRuntimeKind
+): Promise<ManagedRuntimesInfo> {
+  return invoke('uninstall_managed_runtime', { kind });
+}
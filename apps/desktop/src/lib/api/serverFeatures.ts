@@ -5,6 +5,22 @@ import { invoke } from '@tauri-apps/api/core';
  */
 export type FeatureType = 'tool' | 'prompt' | 'resource';
 
+/**
+ * Annotation hint a FeatureSet can select tools by.
+ */
+export type AnnotationHint = 'read_only' | 'destructive' | 'idempotent' | 'open_world';
+
+/**
+ * A tool's MCP annotations as the server reported them; unset hints are
+ * omitted. Servers set these themselves, so treat them as untrusted.
+ */
+export interface ToolAnnotations {
+  read_only?: boolean;
+  destructive?: boolean;
+  idempotent?: boolean;
+  open_world?: boolean;
+}
+
 /**
  * A discovered feature from an MCP server.
  */
@@ -17,9 +33,15 @@ export interface ServerFeature {
   display_name: string | null;
   description: string | null;
   input_schema: Record<string, unknown> | null;
+  /** Annotation hints, for tools */
+  annotations: ToolAnnotations | null;
   discovered_at: string;
   last_seen_at: string;
   is_available: boolean;
+  /** Clients keep this tool's schema until a change is accepted */
+  schema_pinned: boolean;
+  /** Changed tool JSON held back from clients */
+  pending_schema: Record<string, unknown> | null;
 }
 
 /**
@@ -56,3 +78,50 @@ export async function listServerFeaturesByType(
 export async function getServerFeature(id: string): Promise<ServerFeature | null> {
   return invoke('get_server_feature', { id });
 }
+
+/**
+ * Pin or unpin a tool's schema. Unpinning hands clients a held-back schema.
+ */
+export async function setFeatureSchemaPinned(id: string, pinned: boolean): Promise<ServerFeature> {
+  return invoke('set_feature_schema_pinned', { id, pinned });
+}
+
+/**
+ * Give clients the schema a pinned tool's server changed to.
+ */
+export async function acceptFeatureSchema(id: string): Promise<ServerFeature> {
+  return invoke('accept_feature_schema', { id });
+}
+
+/**
+ * Input for a test tool call from the desktop app.
+ */
+export interface TestToolCallInput {
+  space_id: string;
+  server_id: string;
+  /** Tool name as the server reports it (not prefixed) */
+  tool_name: string;
+  arguments?: Record<string, unknown>;
+  /** FeatureSets whose grants the call is checked against */
+  feature_set_ids?: string[];
+  /** Skip the grant check */
+  bypass_permissions?: boolean;
+}
+
+/**
+ * Full result of a test tool call.
+ */
+export interface TestToolCallResponse {
+  content: unknown[];
+  is_error: boolean;
+  structured_content: unknown | null;
+  meta: Record<string, unknown> | null;
+  duration_ms: number;
+}
+
+/**
+ * Call a tool through the gateway with hand-written arguments, for debugging.
+ */
+export async function testToolCall(input: TestToolCallInput): Promise<TestToolCallResponse> {
+  return invoke('test_tool_call', { input });
+}
@@ -0,0 +1,90 @@
+/**
+ * Server Groups API
+ *
+ * Groups are user-defined folders of installed servers. They support bulk
+ * enable/disable/reconnect and can own a FeatureSet with every feature of
+ * their servers.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ServerGroup {
+  id: string;
+  space_id: string;
+  name: string;
+  icon: string | null;
+  server_ids: string[];
+  feature_set_id: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export type ServerGroupAction = 'enable' | 'disable' | 'reconnect';
+
+export interface ServerGroupActionResult {
+  succeeded: string[];
+  failed: { server_id: string; error: string }[];
+}
+
+/**
+ * List the server groups of a space
+ */
+export async function listServerGroups(spaceId: string): Promise<ServerGroup[]> {
+  return invoke('list_server_groups', { spaceId });
+}
+
+/**
+ * Create an empty server group
+ */
+export async function createServerGroup(
+  spaceId: string,
+  name: string,
+  icon?: string
+): Promise<ServerGroup> {
+  return invoke('create_server_group', { spaceId, name, icon: icon ?? null });
+}
+
+/**
+ * Rename a server group or change its icon
+ */
+export async function updateServerGroup(
+  id: string,
+  name: string,
+  icon?: string | null
+): Promise<ServerGroup> {
+  return invoke('update_server_group', { id, name, icon: icon ?? null });
+}
+
+/**
+ * Delete a server group (its servers and FeatureSet are kept)
+ */
+export async function deleteServerGroup(id: string): Promise<void> {
+  return invoke('delete_server_group', { id });
+}
+
+/**
+ * Replace the servers in a group
+ */
+export async function setServerGroupServers(
+  id: string,
+  serverIds: string[]
+): Promise<ServerGroup> {
+  return invoke('set_server_group_servers', { id, serverIds });
+}
+
+/**
+ * Create or refresh the group's FeatureSet. Returns its ID.
+ */
+export async function syncServerGroupFeatureSet(id: string): Promise<string> {
+  return invoke('sync_server_group_feature_set', { id });
+}
+
+/**
+ * Enable, disable or reconnect every server in a group
+ */
+export async function runServerGroupAction(
+  id: string,
+  action: ServerGroupAction
+): Promise<ServerGroupActionResult> {
+  return invoke('run_server_group_action', { id, action });
+}
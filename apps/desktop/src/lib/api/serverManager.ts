@@ -22,6 +22,49 @@ export type ConnectionStatus =
   | "authenticating"
   | "error";
 
+/**
+ * Rolling ping stats for a connected server - matches backend ServerHealth
+ */
+export interface ServerHealth {
+  /** False once pings keep failing, even though the connection is up */
+  healthy: boolean;
+  checks: number;
+  /** Share of failed pings in the window (0-1) */
+  error_rate: number;
+  avg_latency_ms: number | null;
+  p95_latency_ms: number | null;
+  last_latency_ms: number | null;
+  consecutive_failures: number;
+  last_error: string | null;
+  last_checked_at: string | null;
+}
+
+/**
+ * Common reasons a stdio server exits during startup - matches backend FailureKind
+ */
+export type FailureKind =
+  | "missing_binary"
+  | "missing_dependency"
+  | "invalid_credentials"
+  | "port_in_use"
+  | "file_not_found"
+  | "permission_denied"
+  | "unknown";
+
+/**
+ * Why a stdio server keeps exiting during startup - matches backend DiagnosedCause
+ */
+export interface DiagnosedCause {
+  kind: FailureKind;
+  summary: string;
+  suggested_fix: string;
+  /** The stderr (or error) line the diagnosis is based on */
+  evidence: string | null;
+  /** Last lines of stderr before the process exited */
+  stderr_tail: string[];
+  exits: number;
+}
+
 /**
  * Server status response from get_server_statuses
  */
@@ -31,6 +74,10 @@ export interface ServerStatusResponse {
   flow_id: number;
   has_connected_before: boolean;
   message: string | null;
+  /** Ping stats while connected */
+  health: ServerHealth | null;
+  /** Why the server keeps exiting during startup, when it's crash-looping */
+  diagnosis: DiagnosedCause | null;
 }
 
 // Re-use ServerFeature from serverFeatures.ts to avoid duplication
@@ -165,6 +212,36 @@ export async function retryConnection(
   return invoke("retry_connection", { spaceId, serverId });
 }
 
+/**
+ * A newer release of a pinned npx/uvx server package
+ */
+export interface ServerUpdate {
+  space_id: string;
+  server_id: string;
+  ecosystem: 'npm' | 'pypi';
+  package: string;
+  current_version: string;
+  latest_version: string;
+}
+
+/**
+ * Check pinned server packages for newer releases (requires a running gateway)
+ */
+export async function checkServerUpdates(): Promise<ServerUpdate[]> {
+  return invoke("check_server_updates");
+}
+
+/**
+ * Pin a server to the latest release of its package and reconnect it.
+ * Returns the new version.
+ */
+export async function updateServerPackage(
+  spaceId: string,
+  serverId: string
+): Promise<string> {
+  return invoke("update_server_package", { spaceId, serverId });
+}
+
 /**
  * Logout server - Clear OAuth tokens but keep enabled
  * 
@@ -38,6 +38,19 @@ export async function readSpaceConfig(spaceId: string): Promise<string> {
   return invoke('read_space_config', { spaceId });
 }
 
+/** A problem with a Space config file, found by schema validation */
+export interface SpaceConfigIssue {
+  /** JSON pointer to the offending value; empty for the whole file */
+  path: string;
+  line: number;
+  column: number;
+  message: string;
+}
+
+/**
+ * Save a Space's config file. Content that fails schema validation is
+ * rejected, and its issues are also sent as a `space-config-invalid` event.
+ */
 export async function saveSpaceConfig(spaceId: string, content: string): Promise<void> {
   return invoke('save_space_config', { spaceId, content });
 }
@@ -0,0 +1,59 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Where configuration is synced. S3 credentials come from the standard
+ * AWS_* environment variables and are never stored.
+ */
+export type SyncTargetConfig =
+  | { type: 'file'; path: string }
+  | { type: 'git'; repo_dir: string; file_name?: string | null }
+  | {
+      type: 's3';
+      bucket: string;
+      region: string;
+      key?: string | null;
+      endpoint?: string | null;
+    };
+
+export type ConflictStrategy = 'newest_wins' | 'prefer_local' | 'prefer_remote';
+
+/**
+ * Config sync settings (target null = disabled, interval 0 = manual only).
+ */
+export interface SyncSettings {
+  target: SyncTargetConfig | null;
+  conflict_strategy: ConflictStrategy;
+  interval_minutes: number;
+}
+
+export interface SyncConflict {
+  entity: 'space' | 'installed_server' | 'feature_set' | 'workspace_binding';
+  id: string;
+  kept: 'local' | 'remote';
+}
+
+/**
+ * Outcome of a sync run (also emitted as `config-sync-completed`).
+ */
+export interface SyncReport {
+  target: string;
+  applied: number;
+  pushed: boolean;
+  conflicts: SyncConflict[];
+  pending_members: number;
+}
+
+export async function getSyncSettings(): Promise<SyncSettings> {
+  return invoke('get_sync_settings');
+}
+
+export async function setSyncSettings(syncSettings: SyncSettings): Promise<void> {
+  return invoke('set_sync_settings', { syncSettings });
+}
+
+/**
+ * Sync Spaces, servers, FeatureSets and workspace bindings now.
+ */
+export async function runConfigSync(): Promise<SyncReport> {
+  return invoke('run_config_sync');
+}
@@ -46,6 +46,8 @@ interface RegistryState {
   selectedServer: ServerViewModel | null;
   /** Whether running in offline mode (using disk cache) */
   isOffline: boolean;
+  /** When the registry data was last synced (ISO 8601) */
+  lastSyncedAt: string | null;
 }
 
 interface RegistryActions {
@@ -60,7 +62,7 @@ interface RegistryActions {
   /** Clear all filters */
   clearFilters: () => void;
   /** Install server (create DB record) */
-  installServer: (id: string, spaceId?: string) => Promise<void>;
+  installServer: (id: string, spaceId?: string, confirmUntrusted?: boolean) => Promise<void>;
   /** Enable/Disable server */
   toggleServer: (id: string, enabled: boolean) => Promise<void>;
   /** Uninstall server */
@@ -91,6 +93,7 @@ export const useRegistryStore = create<RegistryState & RegistryActions>((set, ge
   error: null,
   selectedServer: null,
   isOffline: false,
+  lastSyncedAt: null,
 
   // Actions
   loadRegistry: async (spaceId) => {
@@ -101,12 +104,12 @@ export const useRegistryStore = create<RegistryState & RegistryActions>((set, ge
 
     set({ isLoading: true, error: null, spaceId: currentSpaceId });
     try {
-      const [definitions, uiConfig, homeConfig, installedStates, isOffline] = await Promise.all([
+      const [definitions, uiConfig, homeConfig, installedStates, syncStatus] = await Promise.all([
         api.discoverServers(),
         api.getRegistryUiConfig(),
         api.getRegistryHomeConfig(),
         currentSpaceId ? api.listInstalledServers(currentSpaceId) : Promise.resolve([]),
-        api.isRegistryOffline()
+        api.getRegistrySyncStatus()
       ]);
       
       const mergedServers = mergeServers(definitions, installedStates);
@@ -125,7 +128,8 @@ export const useRegistryStore = create<RegistryState & RegistryActions>((set, ge
         homeConfig,
         activeSort: uiConfig?.default_sort ?? 'recommended',
         isLoading: false,
-        isOffline
+        isOffline: syncStatus.offline,
+        lastSyncedAt: syncStatus.last_synced_at
       });
       
       // Apply current filters/sort/search
@@ -159,12 +163,12 @@ export const useRegistryStore = create<RegistryState & RegistryActions>((set, ge
     applyFiltersAndSort(get, set);
   },
 
-  installServer: async (id: string, spaceId?: string) => {
+  installServer: async (id: string, spaceId?: string, confirmUntrusted?: boolean) => {
     const targetSpaceId = spaceId || get().spaceId;
     if (!targetSpaceId) return;
 
     try {
-      await api.installServer(id, targetSpaceId);
+      await api.installServer(id, targetSpaceId, confirmUntrusted);
       
       // Update state locally without reloading
       const { servers, displayServers, selectedServer } = get();
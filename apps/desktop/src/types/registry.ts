@@ -8,12 +8,28 @@ export interface InputDefinition {
   label: string;
   description?: string;
   /** Input type - determines how the field is rendered */
-  type?: 'text' | 'boolean' | 'number' | 'url' | 'select' | 'file_path' | 'directory_path';
+  type?:
+    | 'text'
+    | 'password'
+    | 'boolean'
+    | 'number'
+    | 'url'
+    | 'select'
+    | 'enum'
+    | 'file_path'
+    | 'directory_path';
   required?: boolean;
   /** Predefined options for select input type */
-  options?: { value: string; label: string; description?: string }[];
+  options?: { value: string; label?: string; description?: string }[];
   secret?: boolean;
   placeholder?: string;
+  default?: string;
+  /** Regex the whole value must match, checked before the server starts */
+  pattern?: string;
+  /** Shown instead of the pattern when the value doesn't match */
+  pattern_error?: string;
+  /** Value may span several lines (PEM keys, JSON credentials) */
+  multiline?: boolean;
   /** URL to obtain credentials/values */
   obtain_url?: string;
   /** Instructions for obtaining credentials/values */
@@ -34,13 +50,57 @@ export interface TransportMetadata {
 /** Transport configuration */
 export type TransportConfig =
   | { type: 'stdio'; command: string; args: string[]; env: Record<string, string>; metadata: TransportMetadata }
-  | { type: 'http'; url: string; headers: Record<string, string>; metadata: TransportMetadata };
+  | {
+      type: 'http';
+      url: string;
+      headers: Record<string, string>;
+      query?: Record<string, string>;
+      metadata: TransportMetadata;
+    }
+  | {
+      type: 'openapi';
+      spec_url: string;
+      base_url?: string;
+      headers?: Record<string, string>;
+      query?: Record<string, string>;
+      operations?: string[];
+      metadata: TransportMetadata;
+    }
+  | {
+      type: 'graphql';
+      endpoint: string;
+      headers?: Record<string, string>;
+      operations?: string[];
+      metadata: TransportMetadata;
+    }
+  | {
+      type: 'wasm';
+      module: string;
+      args?: string[];
+      env?: Record<string, string>;
+      permissions?: WasmPermissions;
+      metadata: TransportMetadata;
+    }
+  | {
+      type: 'builtin';
+      allowed_domains?: string[];
+      metadata: TransportMetadata;
+    };
+
+/** How much a registry's servers are trusted */
+export type RegistryTrust = 'trusted' | 'standard' | 'untrusted';
+
+/**
+ * API an HTTP registry speaks: McpMux bundles, the official MCP registry's,
+ * or a third-party catalog whose entries are imported
+ */
+export type RegistryFormat = 'mcpmux' | 'official' | 'smithery' | 'mcprun';
 
 /** Server source */
 export type ServerSource =
   | { type: 'UserSpace'; space_id: string; file_path: string }
   | { type: 'Bundled' }
-  | { type: 'Registry'; url: string; name: string };
+  | { type: 'Registry'; url: string; name: string; trust?: RegistryTrust };
 
 /** Publisher info */
 export interface PublisherInfo {
@@ -72,6 +132,50 @@ export interface ServerDefinition {
   sponsored?: Sponsored;
   media?: Media;
   changelog_url?: string;
+  timeouts?: ServerTimeouts;
+  /** Servers connected before this one at startup */
+  depends_on?: string[];
+  /** Result of checking the publisher signature (absent = unsigned) */
+  signature_status?: SignatureStatus;
+}
+
+/** Publisher signature check of a registry entry */
+export type SignatureStatus = 'unsigned' | 'verified' | 'unknown_key' | 'invalid';
+
+/** Connect and request timeouts in seconds (unset = default) */
+export interface ServerTimeouts {
+  connect_secs?: number;
+  request_secs?: number;
+}
+
+/** Host access a WASI server requests (definition) or was granted (installation) */
+export interface WasmPermissions {
+  filesystem?: boolean;
+  network?: boolean;
+}
+
+/** A tool hook step - see docs/guide/servers.mdx */
+export type ToolHookStep =
+  | { action: 'default'; path: string; value: unknown }
+  | { action: 'set'; path: string; value: unknown }
+  | { action: 'remove'; path: string }
+  | { action: 'rename'; from: string; to: string }
+  | { action: 'redact'; pattern: string; replacement?: string }
+  | { action: 'truncate'; max_chars: number }
+  | { action: 'script'; source: string };
+
+/** A hook step and the tools it applies to (all if `tools` is empty) */
+export type ToolHook = ToolHookStep & { tools?: string[] };
+
+/** Hooks run on tool call arguments (before) and results (after) */
+export interface ToolHooks {
+  before?: ToolHook[];
+  after?: ToolHook[];
+}
+
+/** `_meta` fields added to tool calls; values may use `{client_name}` etc. */
+export interface CallContext {
+  meta?: Record<string, string>;
 }
 
 /** Auth configuration - matches backend snake_case serialization */
@@ -99,8 +203,17 @@ export interface InstalledServerState {
   env_overrides: Record<string, string>;
   args_append: string[];
   extra_headers: Record<string, string>;
+  inherit_env: boolean; // Stdio child inherits the full desktop env (default: sanitized)
+  fs_allowed_roots: string[]; // Gateway-enforced path roots for filesystem servers
+  skip_argument_validation: boolean; // Forward tool calls without checking their inputSchema
+  timeouts?: ServerTimeouts; // Overrides the definition's timeouts
+  tool_hooks?: ToolHooks; // Run by the gateway around tool calls
+  call_context?: CallContext; // Added by the gateway to tool calls
+  wasm_grants?: WasmPermissions; // Access granted to a WASI server
   oauth_connected: boolean;
   source: InstallationSource; // How this server was installed
+  last_used_at?: string | null; // Last successful tool call
+  deleted_at?: string | null; // When it was uninstalled into the trash
   created_at: string;
   updated_at: string;
 }
@@ -123,6 +236,18 @@ export interface ServerViewModel extends ServerDefinition {
   args_append?: string[];
   /** Extra HTTP headers (http only) */
   extra_headers?: Record<string, string>;
+  /** Tool calls are forwarded without checking arguments against the schema */
+  skip_argument_validation?: boolean;
+  /** Timeouts set on this installation (the definition's are in `timeouts`) */
+  timeout_overrides?: ServerTimeouts;
+  /** Hooks run around this server's tool calls */
+  tool_hooks?: ToolHooks;
+  /** Context added to this server's tool calls */
+  call_context?: CallContext;
+  /** Access granted to a WASI server (requested access is in `transport`) */
+  wasm_grants?: WasmPermissions;
+  /** Directories the server may use */
+  fs_allowed_roots?: string[];
 }
 
 /** Registry category */
@@ -0,0 +1,27 @@
+//! `admin-token`: issue or revoke the token for the `/admin` API.
+
+use anyhow::{bail, Result};
+
+use crate::context::CliContext;
+
+pub const USAGE: &str = "mcpmux-cli admin-token rotate|revoke";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let settings = ctx.settings_repository.as_ref();
+    match raw.first().map(String::as_str) {
+        Some("rotate") => {
+            // Replaces any previous token; only its hash is stored
+            let token = mcpmux_gateway::generate_admin_token();
+            mcpmux_gateway::set_admin_token(settings, Some(&token)).await?;
+            println!("{}", token);
+            Ok(())
+        }
+        Some("revoke") => {
+            mcpmux_gateway::set_admin_token(settings, None).await?;
+            eprintln!("Admin token revoked");
+            Ok(())
+        }
+        Some(other) => bail!("Unknown admin-token action: {}", other),
+        None => bail!("Missing admin-token action"),
+    }
+}
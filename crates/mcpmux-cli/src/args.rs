@@ -0,0 +1,119 @@
+//! Minimal argument parsing shared by the subcommands.
+
+use anyhow::{bail, Result};
+
+/// Parsed arguments of one subcommand: positionals, boolean flags and
+/// (possibly repeated) `--name value` options.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl Args {
+    /// Parse `raw`, accepting only the given flags and value options.
+    pub fn parse(raw: &[String], flags: &[&str], options: &[&str]) -> Result<Self> {
+        let mut args = Args::default();
+        let mut rest = raw.iter();
+        while let Some(arg) = rest.next() {
+            if !arg.starts_with("--") {
+                args.positional.push(arg.clone());
+                continue;
+            }
+            // Accept both `--name value` and `--name=value`
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if flags.contains(&name) && inline.is_none() {
+                args.flags.push(name.to_string());
+            } else if options.contains(&name) {
+                let Some(value) = inline.or_else(|| rest.next().cloned()) else {
+                    bail!("{} requires a value", name);
+                };
+                args.options.push((name.to_string(), value));
+            } else {
+                bail!("Unknown argument: {}", arg);
+            }
+        }
+        Ok(args)
+    }
+
+    /// Positional argument at `index`
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(String::as_str)
+    }
+
+    /// Positional argument at `index`, or an error naming it
+    pub fn required(&self, index: usize, name: &str) -> Result<&str> {
+        match self.positional(index) {
+            Some(value) => Ok(value),
+            None => bail!("Missing <{}>", name),
+        }
+    }
+
+    /// Whether a boolean flag was passed
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+
+    /// Last value of an option
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All values of a repeatable option, in order
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_mixed_arguments() {
+        let args = Args::parse(
+            &raw(&[
+                "github",
+                "--space",
+                "Work",
+                "--input=TOKEN=abc",
+                "--input",
+                "ORG=acme",
+                "--enable",
+            ]),
+            &["--enable"],
+            &["--space", "--input"],
+        )
+        .unwrap();
+
+        assert_eq!(args.positional(0), Some("github"));
+        assert_eq!(args.positional(1), None);
+        assert_eq!(args.value("--space"), Some("Work"));
+        assert_eq!(args.values("--input"), vec!["TOKEN=abc", "ORG=acme"]);
+        assert!(args.flag("--enable"));
+        assert!(!args.flag("--revoke"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_missing_values() {
+        assert!(Args::parse(&raw(&["--nope"]), &[], &[]).is_err());
+        assert!(Args::parse(&raw(&["--space"]), &[], &["--space"]).is_err());
+        assert!(Args::parse(&raw(&["--enable=yes"]), &["--enable"], &[]).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Shared state for CLI commands.
+//!
+//! Opens the same data directory, master key and database as the desktop app
+//! and builds the repositories on top of them. `MCPMUX_DATABASE_URL` points
+//! them at a shared PostgreSQL database instead.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use mcpmux_core::{
+    AppSettingsRepository, AppSettingsService, CredentialRepository, EnvironmentProfileRepository,
+    FeatureSetRepository, GatewayPortService, InstalledServerRepository, LogConfig,
+    OutboundOAuthRepository, RegistryCredentialService, ServerDiscoveryService,
+    ServerFeatureRepository, ServerGroupRepository, ServerLogManager, Space, SpaceRepository,
+    SpaceService, UserRepository,
+};
+use mcpmux_storage::{
+    FieldEncryptor, InboundClientRepository, KeyProviderKind, MasterKeyProvider,
+    PassphraseKeyProvider, RepositorySet, StorageConfig, PASSPHRASE_ENV_VAR,
+};
+use uuid::Uuid;
+
+/// Identifier of the desktop app (see `tauri.conf.json`); its data directory
+/// is shared so the CLI and the app see the same Spaces, servers and clients.
+const DESKTOP_APP_IDENTIFIER: &str = "com.mcpmux.desktop";
+
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV_VAR: &str = "MCPMUX_DATA_DIR";
+
+/// Default data directory (`MCPMUX_DATA_DIR`, else the desktop app's)
+pub fn default_data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV_VAR) {
+        return PathBuf::from(dir);
+    }
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(DESKTOP_APP_IDENTIFIER)
+}
+
+/// Repositories and services over one data directory
+pub struct CliContext {
+    data_dir: PathBuf,
+    /// Every repository, for the gateway
+    pub repositories: RepositorySet,
+    pub space_repository: Arc<dyn SpaceRepository>,
+    pub installed_server_repository: Arc<dyn InstalledServerRepository>,
+    pub credential_repository: Arc<dyn CredentialRepository>,
+    pub backend_oauth_repository: Arc<dyn OutboundOAuthRepository>,
+    pub feature_set_repository: Arc<dyn FeatureSetRepository>,
+    pub server_feature_repository: Arc<dyn ServerFeatureRepository>,
+    pub settings_repository: Arc<dyn AppSettingsRepository>,
+    pub environment_profile_repository: Arc<dyn EnvironmentProfileRepository>,
+    pub inbound_client_repository: Arc<dyn InboundClientRepository>,
+    pub user_repository: Arc<dyn UserRepository>,
+    pub server_group_repository: Arc<dyn ServerGroupRepository>,
+    pub space_service: SpaceService,
+}
+
+impl CliContext {
+    /// Open the data directory, creating it (and the database) if missing.
+    pub async fn open(data_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(data_dir)
+            .with_context(|| format!("Failed to create {}", data_dir.display()))?;
+
+        let key_provider = open_key_provider(data_dir)?;
+        let master_key = key_provider.get_or_create_key()?;
+        let encryptor = Arc::new(FieldEncryptor::new(&master_key)?);
+
+        let repos = RepositorySet::open(&StorageConfig::from_env(data_dir), encryptor).await?;
+
+        Ok(Self {
+            data_dir: data_dir.to_path_buf(),
+            space_repository: repos.spaces.clone(),
+            installed_server_repository: repos.installed_servers.clone(),
+            credential_repository: repos.credentials.clone(),
+            backend_oauth_repository: repos.outbound_oauth.clone(),
+            feature_set_repository: repos.feature_sets.clone(),
+            server_feature_repository: repos.server_features_core.clone(),
+            settings_repository: repos.settings.clone(),
+            environment_profile_repository: repos.environment_profiles.clone(),
+            inbound_client_repository: repos.inbound_clients.clone(),
+            user_repository: repos.users.clone(),
+            server_group_repository: repos.server_groups.clone(),
+            space_service: SpaceService::with_feature_set_repository(
+                repos.spaces.clone(),
+                repos.feature_sets.clone(),
+            ),
+            repositories: repos,
+        })
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn port_service(&self) -> GatewayPortService {
+        GatewayPortService::new(self.settings_repository.clone())
+    }
+
+    /// Registry-backed server discovery, as configured in the desktop app
+    pub fn server_discovery(&self) -> Result<Arc<ServerDiscoveryService>> {
+        let spaces_dir = self.data_dir.join("spaces");
+        std::fs::create_dir_all(&spaces_dir)?;
+        let registry_url = std::env::var("MCPMUX_REGISTRY_URL")
+            .unwrap_or_else(|_| "https://api.mcpmux.com".to_string());
+        Ok(Arc::new(
+            ServerDiscoveryService::new(self.data_dir.clone(), spaces_dir)
+                .with_registry_api(registry_url)
+                .with_settings_service(Arc::new(AppSettingsService::new(
+                    self.settings_repository.clone(),
+                )))
+                .with_registry_credentials(Arc::new(RegistryCredentialService::new(
+                    self.credential_repository.clone(),
+                ))),
+        ))
+    }
+
+    pub fn server_log_manager(&self) -> Arc<ServerLogManager> {
+        Arc::new(ServerLogManager::new(LogConfig::new(
+            self.data_dir.join("logs"),
+        )))
+    }
+
+    /// Resolve a `--space` argument (ID or name); the default Space when unset.
+    pub async fn resolve_space(&self, space: Option<&str>) -> Result<Space> {
+        let Some(space) = space else {
+            return self
+                .space_service
+                .get_default()
+                .await?
+                .ok_or_else(|| anyhow!("No default Space found"));
+        };
+        if let Ok(id) = Uuid::parse_str(space) {
+            if let Some(found) = self.space_service.get(&id).await? {
+                return Ok(found);
+            }
+        }
+        self.space_service
+            .list()
+            .await?
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(space))
+            .ok_or_else(|| anyhow!("Space not found: {}", space))
+    }
+}
+
+/// Create the selected master key provider. The passphrase provider asks for
+/// its passphrase on the terminal when `MCPMUX_MASTER_PASSPHRASE` is unset.
+fn open_key_provider(data_dir: &Path) -> Result<Box<dyn MasterKeyProvider>> {
+    use std::io::IsTerminal;
+
+    let kind = mcpmux_storage::read_key_provider_kind(data_dir);
+    if kind == KeyProviderKind::Passphrase
+        && std::env::var_os(PASSPHRASE_ENV_VAR).is_none()
+        && std::io::stdin().is_terminal()
+    {
+        let passphrase = rpassword::prompt_password("Master key passphrase: ")
+            .context("Failed to read master key passphrase")?;
+        return Ok(Box::new(PassphraseKeyProvider::new(data_dir, passphrase)?));
+    }
+    mcpmux_storage::create_key_provider_of_kind(data_dir, kind)
+}
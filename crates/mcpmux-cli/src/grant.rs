@@ -0,0 +1,76 @@
+//! `grant`: grant or revoke a FeatureSet for a client in a Space.
+
+use anyhow::{anyhow, Result};
+use mcpmux_core::FeatureSet;
+use mcpmux_storage::InboundClient;
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str = "mcpmux-cli grant <client> [<feature-set>] [--space <space>] [--revoke]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let args = Args::parse(raw, &["--revoke"], &["--space"])?;
+    let client = find_client(&ctx, args.required(0, "client")?)
+        .await?
+        .ok_or_else(|| anyhow!("Client not found: {}", args.positional(0).unwrap_or("")))?;
+    let space = ctx.resolve_space(args.value("--space")).await?;
+    let space_id = space.id.to_string();
+    let feature_sets = ctx.feature_set_repository.list_by_space(&space_id).await?;
+
+    // Without a FeatureSet, show what the client currently has
+    let Some(wanted) = args.positional(1) else {
+        let granted = ctx
+            .inbound_client_repository
+            .get_grants_for_space(&client.client_id, &space_id)
+            .await?;
+        if granted.is_empty() {
+            println!("{} has no grants in {}", client.client_name, space.name);
+        }
+        for id in granted {
+            println!("{:<40} {}", id, feature_set_name(&feature_sets, &id));
+        }
+        return Ok(());
+    };
+
+    let feature_set = feature_sets
+        .iter()
+        .find(|fs| fs.id == wanted || fs.name.eq_ignore_ascii_case(wanted))
+        .ok_or_else(|| anyhow!("FeatureSet not found in {}: {}", space.name, wanted))?;
+
+    if args.flag("--revoke") {
+        ctx.inbound_client_repository
+            .revoke_feature_set(&client.client_id, &space_id, &feature_set.id)
+            .await?;
+        println!(
+            "Revoked {} from {} in {}",
+            feature_set.name, client.client_name, space.name
+        );
+    } else {
+        ctx.inbound_client_repository
+            .grant_feature_set(&client.client_id, &space_id, &feature_set.id)
+            .await?;
+        println!(
+            "Granted {} to {} in {}",
+            feature_set.name, client.client_name, space.name
+        );
+    }
+    Ok(())
+}
+
+/// Look up a client by ID, then by name
+pub async fn find_client(ctx: &CliContext, client: &str) -> Result<Option<InboundClient>> {
+    let repo = &ctx.inbound_client_repository;
+    if let Some(found) = repo.get_client(client).await? {
+        return Ok(Some(found));
+    }
+    repo.find_client_by_name(client).await
+}
+
+fn feature_set_name<'a>(feature_sets: &'a [FeatureSet], id: &str) -> &'a str {
+    feature_sets
+        .iter()
+        .find(|fs| fs.id == id)
+        .map(|fs| fs.name.as_str())
+        .unwrap_or("(deleted)")
+}
@@ -0,0 +1,107 @@
+//! McpMux headless CLI.
+//!
+//! Runs the gateway and manages servers, grants and tokens without the
+//! desktop app, on the same data directory and database — for CI machines
+//! and servers.
+//!
+//! ```text
+//! mcpmux-cli [--data-dir <path>] <command> ...
+//! ```
+
+mod admin_token;
+mod args;
+mod context;
+mod grant;
+mod policy;
+mod registry;
+mod serve;
+mod server;
+mod service;
+mod token;
+mod user;
+
+use std::path::PathBuf;
+
+use tracing_subscriber::EnvFilter;
+
+use context::CliContext;
+
+fn usage() -> String {
+    format!(
+        "Usage: mcpmux-cli [--data-dir <path>] <command>\n\n\
+         Commands:\n  {}\n  {}\n  {}\n  {}\n  {}\n  {}\n  {}\n  {}\n  {}\n\n\
+         The data directory defaults to ${} or the desktop app's.",
+        serve::USAGE,
+        server::USAGE,
+        grant::USAGE,
+        policy::USAGE,
+        token::USAGE,
+        service::USAGE,
+        admin_token::USAGE,
+        user::USAGE,
+        registry::USAGE,
+        context::DATA_DIR_ENV_VAR
+    )
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut data_dir = None;
+    if args.first().map(String::as_str) == Some("--data-dir") {
+        if args.len() < 2 {
+            eprintln!("{}", usage());
+            std::process::exit(2);
+        }
+        data_dir = Some(PathBuf::from(args.remove(1)));
+        args.remove(0);
+    }
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", usage());
+        std::process::exit(2);
+    };
+    if matches!(command.as_str(), "help" | "--help" | "-h") {
+        println!("{}", usage());
+        return;
+    }
+
+    // Logs go to stderr so stdout stays clean for scripts (e.g. tokens)
+    let default_level = if command == "serve" { "info" } else { "warn" };
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level)),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let data_dir = data_dir.unwrap_or_else(context::default_data_dir);
+    let ctx = match CliContext::open(&data_dir).await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = match command.as_str() {
+        "serve" => serve::run(ctx, rest).await,
+        "server" => server::run(ctx, rest).await,
+        "grant" => grant::run(ctx, rest).await,
+        "policy" => policy::run(ctx, rest).await,
+        "token" => token::run(ctx, rest).await,
+        "service" => service::run(ctx, rest).await,
+        "admin-token" => admin_token::run(ctx, rest).await,
+        "user" => user::run(ctx, rest).await,
+        "registry" => registry::run(ctx, rest).await,
+        other => {
+            eprintln!("Unknown command: {}\n\n{}", other, usage());
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,134 @@
+//! `policy`: reconcile FeatureSets and grants with a policy file.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use mcpmux_core::{
+    plan_space_policy, ClientGrants, PolicyChange, PolicyDocument, PolicyPlan, SpacePolicy,
+    SpaceSnapshot,
+};
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str = "mcpmux-cli policy apply <file> [--dry-run]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing policy action");
+    };
+    match action.as_str() {
+        "apply" => {
+            let args = Args::parse(rest, &["--dry-run"], &[])?;
+            let doc = PolicyDocument::load(Path::new(args.required(0, "file")?))?;
+            apply_policy(&ctx, &doc, args.flag("--dry-run")).await
+        }
+        other => bail!("Unknown policy action: {}", other),
+    }
+}
+
+/// Plan every Space first so a bad reference anywhere changes nothing, then
+/// print the diff and (unless `dry_run`) apply it
+pub async fn apply_policy(ctx: &CliContext, doc: &PolicyDocument, dry_run: bool) -> Result<()> {
+    let mut plans = Vec::new();
+    let mut seen = HashSet::new();
+    for policy in &doc.spaces {
+        let plan = plan(ctx, policy).await?;
+        if !seen.insert(plan.space_id.clone()) {
+            bail!("Space {} is listed twice", plan.space_name);
+        }
+        plans.push(plan);
+    }
+
+    for plan in &plans {
+        println!("{}", plan);
+    }
+    let total: usize = plans.iter().map(|p| p.changes.len()).sum();
+    if dry_run || total == 0 {
+        if dry_run {
+            eprintln!("Dry run: {} change(s) not applied", total);
+        }
+        return Ok(());
+    }
+
+    for plan in &plans {
+        apply(ctx, plan).await?;
+    }
+    eprintln!("Applied {} change(s)", total);
+    Ok(())
+}
+
+async fn plan(ctx: &CliContext, policy: &SpacePolicy) -> Result<PolicyPlan> {
+    let space = ctx.resolve_space(Some(policy.space.as_str())).await?;
+    let space_id = space.id.to_string();
+
+    let mut clients = Vec::new();
+    for client in ctx.inbound_client_repository.list_clients().await? {
+        let feature_set_ids = ctx
+            .inbound_client_repository
+            .get_grants_for_space(&client.client_id, &space_id)
+            .await?;
+        clients.push(ClientGrants {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            feature_set_ids,
+        });
+    }
+    // Server groups keep their own FeatureSets in sync; pruning leaves them
+    let protected = ctx
+        .server_group_repository
+        .list_for_space(&space_id)
+        .await?
+        .into_iter()
+        .filter_map(|group| group.feature_set_id)
+        .collect();
+
+    let snapshot = SpaceSnapshot {
+        feature_sets: ctx.feature_set_repository.list_by_space(&space_id).await?,
+        features: ctx
+            .server_feature_repository
+            .list_for_space(&space_id)
+            .await?,
+        clients,
+        protected,
+        space_id,
+        space_name: space.name,
+    };
+    plan_space_policy(policy, &snapshot)
+}
+
+async fn apply(ctx: &CliContext, plan: &PolicyPlan) -> Result<()> {
+    let feature_sets = &ctx.feature_set_repository;
+    let grants = &ctx.inbound_client_repository;
+    for change in &plan.changes {
+        match change {
+            PolicyChange::CreateFeatureSet { feature_set, .. } => {
+                feature_sets.create(feature_set).await?
+            }
+            PolicyChange::UpdateFeatureSet { feature_set, .. } => {
+                feature_sets.update(feature_set).await?
+            }
+            PolicyChange::DeleteFeatureSet { id, .. } => feature_sets.delete(id).await?,
+            PolicyChange::Grant {
+                client_id,
+                feature_set_id,
+                ..
+            } => {
+                grants
+                    .grant_feature_set(client_id, &plan.space_id, feature_set_id)
+                    .await?
+            }
+            PolicyChange::Revoke {
+                client_id,
+                feature_set_id,
+                ..
+            } => {
+                grants
+                    .revoke_feature_set(client_id, &plan.space_id, feature_set_id)
+                    .await?
+            }
+        }
+    }
+    Ok(())
+}
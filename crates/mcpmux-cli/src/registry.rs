@@ -0,0 +1,116 @@
+//! `registry`: sign server definitions and manage trusted publisher keys.
+//!
+//! A team publishing a catalog runs `keygen` once, signs its definition
+//! files with `sign` before publishing, and users `trust` its public key.
+
+use anyhow::{bail, Context, Result};
+use mcpmux_core::{generate_publisher_key, sign_definition, PublisherKey, ServerDefinition};
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str = "mcpmux-cli registry keygen
+  mcpmux-cli registry sign <file>... --key <private-key-file> --key-id <key-id>
+  mcpmux-cli registry keys
+  mcpmux-cli registry trust <key-id> <public-key> [--name <name>]
+  mcpmux-cli registry untrust <key-id>";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing registry action");
+    };
+
+    match action.as_str() {
+        "keygen" => {
+            Args::parse(rest, &[], &[])?;
+            let (private_key, public_key) = generate_publisher_key()?;
+            // The private key goes to stdout so it can be redirected to a file
+            println!("{}", private_key);
+            eprintln!("Public key: {}", public_key);
+            Ok(())
+        }
+        "sign" => sign(rest),
+        "keys" => {
+            Args::parse(rest, &[], &[])?;
+            let keys = ctx.server_discovery()?.publisher_keys().await;
+            if keys.is_empty() {
+                println!("No trusted publisher keys");
+            }
+            for key in keys {
+                println!("{}\t{}\t{}", key.id, key.name, key.public_key);
+            }
+            Ok(())
+        }
+        "trust" => {
+            let args = Args::parse(rest, &[], &["--name"])?;
+            let key = PublisherKey {
+                id: args.required(0, "key-id")?.to_string(),
+                name: args.value("--name").unwrap_or_default().to_string(),
+                public_key: args.required(1, "public-key")?.to_string(),
+            };
+            let discovery = ctx.server_discovery()?;
+            let mut keys = discovery.publisher_keys().await;
+            keys.retain(|k| k.id != key.id);
+            let id = key.id.clone();
+            keys.push(key);
+            discovery.set_publisher_keys(keys).await?;
+            println!("Trusted publisher key {}", id);
+            Ok(())
+        }
+        "untrust" => {
+            let args = Args::parse(rest, &[], &[])?;
+            let key_id = args.required(0, "key-id")?;
+            let discovery = ctx.server_discovery()?;
+            let mut keys = discovery.publisher_keys().await;
+            let before = keys.len();
+            keys.retain(|k| k.id != key_id);
+            if keys.len() == before {
+                bail!("No trusted publisher key {}", key_id);
+            }
+            discovery.set_publisher_keys(keys).await?;
+            println!("Removed publisher key {}", key_id);
+            Ok(())
+        }
+        other => bail!("Unknown registry action: {}", other),
+    }
+}
+
+/// Add a signature to each definition file, in place. A file may hold one
+/// definition or a list of them.
+fn sign(raw: &[String]) -> Result<()> {
+    let args = Args::parse(raw, &[], &["--key", "--key-id"])?;
+    let Some(key_file) = args.value("--key") else {
+        bail!("Missing --key <private-key-file>");
+    };
+    let Some(key_id) = args.value("--key-id") else {
+        bail!("Missing --key-id <key-id>");
+    };
+    let private_key = std::fs::read_to_string(key_file)
+        .with_context(|| format!("Failed to read private key {}", key_file))?;
+    if args.positional(0).is_none() {
+        bail!("Missing <file>");
+    }
+
+    for file in (0..).map_while(|i| args.positional(i)) {
+        let content =
+            std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+        let mut value: serde_json::Value =
+            serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {}", file))?;
+
+        let entries = match value {
+            serde_json::Value::Array(ref mut items) => items.iter_mut().collect::<Vec<_>>(),
+            ref mut one => vec![one],
+        };
+        for entry in entries {
+            let definition: ServerDefinition = serde_json::from_value(entry.clone())
+                .with_context(|| format!("Invalid server definition in {}", file))?;
+            let signature = sign_definition(&definition, key_id, &private_key)?;
+            entry["signature"] = serde_json::to_value(&signature)?;
+            println!("Signed {} ({})", definition.id, file);
+        }
+
+        std::fs::write(file, serde_json::to_string_pretty(&value)? + "\n")
+            .with_context(|| format!("Failed to write {}", file))?;
+    }
+    Ok(())
+}
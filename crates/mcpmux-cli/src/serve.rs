@@ -0,0 +1,130 @@
+//! `serve`: run the gateway in the foreground until interrupted.
+
+use anyhow::{anyhow, Context, Result};
+use mcpmux_gateway::{DependenciesBuilder, GatewayConfig, GatewayServer};
+use tracing::{info, warn};
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str =
+    "mcpmux-cli serve [--host <host>] [--port <port>] [--public-url <url>] [--base-url-host <host>] [--grpc-port <port>]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let args = Args::parse(
+        raw,
+        &[],
+        &[
+            "--host",
+            "--port",
+            "--public-url",
+            "--base-url-host",
+            "--grpc-port",
+        ],
+    )?;
+
+    let port = match args.value("--port") {
+        Some(port) => port
+            .parse::<u16>()
+            .with_context(|| format!("Invalid port: {}", port))?,
+        None => ctx
+            .port_service()
+            .load_persisted_port()
+            .await
+            .unwrap_or(mcpmux_core::DEFAULT_GATEWAY_PORT),
+    };
+    let grpc_port = match args.value("--grpc-port") {
+        Some(port) => Some(
+            port.parse::<u16>()
+                .with_context(|| format!("Invalid gRPC port: {}", port))?,
+        ),
+        None => None,
+    };
+    let config = GatewayConfig {
+        host: args
+            .value("--host")
+            .unwrap_or(mcpmux_gateway::DUAL_STACK_LOOPBACK)
+            .to_string(),
+        port,
+        public_base_url: args.value("--public-url").map(str::to_string),
+        base_url_host: args.value("--base-url-host").map(str::to_string),
+        grpc_port,
+        ..GatewayConfig::default()
+    };
+
+    // Same startup backfill as the desktop app: every Space gets its
+    // built-in FeatureSets before clients can resolve against them.
+    for space in ctx.space_repository.list().await? {
+        if let Err(e) = ctx
+            .feature_set_repository
+            .ensure_builtin_for_space(&space.id.to_string())
+            .await
+        {
+            warn!(space_id = %space.id, "[CLI] Failed to backfill built-in FeatureSets: {}", e);
+        }
+    }
+
+    // Stdio servers orphaned by a crashed previous run (of this command or
+    // the desktop app) may still hold ports and file locks
+    let registry = mcpmux_core::ChildProcessRegistry::new(ctx.data_dir());
+    let reaped = tokio::task::spawn_blocking(move || registry.reap_orphans()).await?;
+    if !reaped.is_empty() {
+        info!(
+            "[CLI] Terminated {} orphaned server process(es) from a previous run",
+            reaped.len()
+        );
+    }
+
+    let jwt_secret = mcpmux_storage::create_jwt_secret_provider(ctx.data_dir())?
+        .get_or_create_secret()
+        .context("Failed to load JWT signing secret")?;
+
+    let dependencies = DependenciesBuilder::new()
+        .with_repositories(&ctx.repositories)
+        .with_server_discovery(ctx.server_discovery()?)
+        .with_log_manager(ctx.server_log_manager())
+        .with_state_dir(ctx.data_dir().to_path_buf())
+        .with_jwt_secret(jwt_secret)
+        .build()
+        .map_err(|e| anyhow!(e))?;
+
+    info!(
+        "[CLI] Serving on {:?} (advertising {})",
+        config.bind_addrs(),
+        config.base_url()
+    );
+    GatewayServer::new(config, dependencies)
+        .run_with_shutdown(shutdown_signal())
+        .await
+}
+
+/// Resolves on Ctrl+C, or SIGTERM on Unix (service managers, `docker stop`)
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("[CLI] Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("[CLI] Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("[CLI] Shutdown requested");
+}
@@ -0,0 +1,180 @@
+//! `server`: list, install, enable and disable servers in a Space.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use mcpmux_core::{
+    InstallManifest, InstallationSource, ManagedPolicy, ServerAppService, ServerDefinition,
+};
+use tracing::warn;
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str = "mcpmux-cli server list [--space <space>]
+  mcpmux-cli server add <server-id> [--space <space>] [--definition <file>] [--input KEY=VALUE]... [--enable] [--confirm-untrusted]
+  mcpmux-cli server bulk-add <manifest> [--space <space>] [--confirm-untrusted]
+  mcpmux-cli server enable|disable <server-id> [--space <space>]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing server action");
+    };
+    // Events go nowhere here; a running gateway picks up the change from
+    // the database when servers are next (re)connected.
+    let service = ServerAppService::new(
+        ctx.installed_server_repository.clone(),
+        Some(ctx.server_feature_repository.clone()),
+        Some(ctx.credential_repository.clone()),
+        mcpmux_core::create_shared_event_bus().sender(),
+    )
+    .with_managed_policy(Arc::new(ManagedPolicy::load()));
+
+    match action.as_str() {
+        "list" => list(&ctx, &service, rest).await,
+        "add" => add(&ctx, &service, rest).await,
+        "bulk-add" => bulk_add(&ctx, &service, rest).await,
+        "enable" | "disable" => {
+            let args = Args::parse(rest, &[], &["--space"])?;
+            let server_id = args.required(0, "server-id")?;
+            let space = ctx.resolve_space(args.value("--space")).await?;
+            if action == "enable" {
+                service.enable(space.id, server_id).await?;
+            } else {
+                service.disable(space.id, server_id).await?;
+            }
+            println!("{}d {} in {}", action, server_id, space.name);
+            Ok(())
+        }
+        other => bail!("Unknown server action: {}", other),
+    }
+}
+
+async fn list(ctx: &CliContext, service: &ServerAppService, raw: &[String]) -> Result<()> {
+    let args = Args::parse(raw, &[], &["--space"])?;
+    let space = ctx.resolve_space(args.value("--space")).await?;
+    let servers = service.list_for_space(&space.id.to_string()).await?;
+    if servers.is_empty() {
+        println!("No servers installed in {}", space.name);
+        return Ok(());
+    }
+    for server in servers {
+        let source = match server.source {
+            InstallationSource::Registry => "registry",
+            InstallationSource::UserConfig { .. } => "config file",
+            InstallationSource::ManualEntry => "manual",
+            _ => "other",
+        };
+        println!(
+            "{:<32} {:<9} {:<12} {}",
+            server.server_id,
+            if server.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            },
+            source,
+            server.display_name()
+        );
+    }
+    Ok(())
+}
+
+async fn add(ctx: &CliContext, service: &ServerAppService, raw: &[String]) -> Result<()> {
+    let args = Args::parse(
+        raw,
+        &["--enable", "--confirm-untrusted"],
+        &["--space", "--definition", "--input"],
+    )?;
+    let server_id = args.required(0, "server-id")?;
+    let space = ctx.resolve_space(args.value("--space")).await?;
+
+    let mut input_values = HashMap::new();
+    for input in args.values("--input") {
+        let (key, value) = input
+            .split_once('=')
+            .ok_or_else(|| anyhow!("--input expects KEY=VALUE, got {}", input))?;
+        input_values.insert(key.to_string(), value.to_string());
+    }
+
+    let (definition, source) = match args.value("--definition") {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path))?;
+            let mut definition: ServerDefinition = serde_json::from_str(&content)
+                .with_context(|| format!("{} is not a valid server definition", path))?;
+            definition.id = server_id.to_string();
+            (definition, InstallationSource::ManualEntry)
+        }
+        None => {
+            let discovery = ctx.server_discovery()?;
+            if let Err(e) = discovery.refresh_if_needed().await {
+                warn!(
+                    "[CLI] Registry refresh failed, using cached registry: {}",
+                    e
+                );
+            }
+            let definition = discovery
+                .get(server_id)
+                .await
+                .ok_or_else(|| anyhow!("Server not found in registry: {}", server_id))?;
+            if definition.source.trust().requires_confirmation()
+                && !args.flag("--confirm-untrusted")
+            {
+                bail!(
+                    "{} comes from an untrusted registry; pass --confirm-untrusted to install it",
+                    server_id
+                );
+            }
+            (definition, InstallationSource::Registry)
+        }
+    };
+
+    // Servers from a trusted registry are installed enabled
+    let installed = service
+        .install_with_source(space.id, server_id, &definition, input_values, source)
+        .await?;
+    if args.flag("--enable") && !installed.enabled {
+        service.enable(space.id, server_id).await?;
+    }
+    println!(
+        "Installed {} in {}{}",
+        server_id,
+        space.name,
+        if args.flag("--enable") || installed.enabled {
+            " (enabled)"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+async fn bulk_add(ctx: &CliContext, service: &ServerAppService, raw: &[String]) -> Result<()> {
+    let args = Args::parse(raw, &["--confirm-untrusted"], &["--space"])?;
+    let manifest = InstallManifest::load(Path::new(args.required(0, "manifest")?))?;
+    let space = ctx.resolve_space(args.value("--space")).await?;
+
+    let discovery = ctx.server_discovery()?;
+    if let Err(e) = discovery.refresh_if_needed().await {
+        warn!(
+            "[CLI] Registry refresh failed, using cached registry: {}",
+            e
+        );
+    }
+    let report = service
+        .bulk_install(
+            space.id,
+            &manifest,
+            &discovery,
+            args.flag("--confirm-untrusted"),
+        )
+        .await?;
+    println!("{}", report);
+    if !report.is_success() {
+        bail!("Nothing was installed in {}", space.name);
+    }
+    Ok(())
+}
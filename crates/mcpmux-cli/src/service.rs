@@ -0,0 +1,83 @@
+//! `service`: run the gateway at login/boot under the OS service manager.
+
+use anyhow::{anyhow, bail, Context, Result};
+use mcpmux_core::{ServiceManager, ServiceSpec};
+
+use crate::args::Args;
+use crate::context::CliContext;
+
+pub const USAGE: &str =
+    "mcpmux-cli service install [--host <host>] [--port <port>] [--public-url <url>] [--base-url-host <host>]
+  mcpmux-cli service uninstall|status
+  mcpmux-cli service print [--manager systemd|launchd|task-scheduler] [--port <port>]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing service action");
+    };
+    let args = Args::parse(
+        rest,
+        &[],
+        &[
+            "--host",
+            "--port",
+            "--public-url",
+            "--base-url-host",
+            "--manager",
+        ],
+    )?;
+    let manager = match args.value("--manager") {
+        Some(name) => {
+            ServiceManager::parse(name).ok_or_else(|| anyhow!("Unknown manager: {}", name))?
+        }
+        None => ServiceManager::current()
+            .ok_or_else(|| anyhow!("No supported service manager on this platform"))?,
+    };
+
+    match action.as_str() {
+        "install" => {
+            let location = mcpmux_core::install_service(manager, &spec(&ctx, &args)?)?;
+            println!("Installed gateway service: {}", location);
+            Ok(())
+        }
+        "uninstall" => {
+            mcpmux_core::uninstall_service(manager)?;
+            println!("Uninstalled gateway service");
+            Ok(())
+        }
+        "status" => {
+            if mcpmux_core::is_service_installed(manager) {
+                println!("installed");
+            } else {
+                println!("not installed");
+            }
+            Ok(())
+        }
+        "print" => {
+            print!("{}", spec(&ctx, &args)?.render(manager));
+            Ok(())
+        }
+        other => bail!("Unknown service action: {}", other),
+    }
+}
+
+/// Run this binary's `serve` with the same data directory and options
+fn spec(ctx: &CliContext, args: &Args) -> Result<ServiceSpec> {
+    let executable = std::env::current_exe().context("Failed to locate mcpmux-cli")?;
+    // Service managers don't run from our working directory
+    let data_dir = std::fs::canonicalize(ctx.data_dir())
+        .with_context(|| format!("Failed to resolve {}", ctx.data_dir().display()))?;
+
+    let mut serve_args = Vec::new();
+    for option in ["--host", "--port", "--public-url", "--base-url-host"] {
+        if let Some(value) = args.value(option) {
+            serve_args.push(option.to_string());
+            serve_args.push(value.to_string());
+        }
+    }
+    Ok(ServiceSpec {
+        executable,
+        data_dir,
+        serve_args,
+    })
+}
@@ -0,0 +1,104 @@
+//! `token issue`: sign a gateway access token for a client.
+//!
+//! Only the token is printed to stdout so it can be captured in scripts:
+//! `MCPMUX_TOKEN=$(mcpmux-cli token issue ci-runner)`.
+
+use anyhow::{bail, Context, Result};
+use mcpmux_storage::{InboundClient, RegistrationType};
+
+use crate::args::Args;
+use crate::context::CliContext;
+use crate::grant::find_client;
+
+pub const USAGE: &str =
+    "mcpmux-cli token issue <client> [--expires-in <seconds>] [--scope <scope>]";
+
+/// Default token lifetime: one hour, like tokens from the OAuth flow
+const DEFAULT_EXPIRES_IN: i64 = 60 * 60;
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing token action");
+    };
+    if action != "issue" {
+        bail!("Unknown token action: {}", action);
+    }
+
+    let args = Args::parse(rest, &[], &["--expires-in", "--scope"])?;
+    let name = args.required(0, "client")?;
+    let expires_in = match args.value("--expires-in") {
+        Some(value) => value
+            .parse::<i64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .with_context(|| format!("Invalid --expires-in: {}", value))?,
+        None => DEFAULT_EXPIRES_IN,
+    };
+
+    let client = match find_client(&ctx, name).await? {
+        Some(client) => client,
+        None => {
+            let client = register_client(&ctx, name).await?;
+            eprintln!(
+                "Registered client {} ({})",
+                client.client_name, client.client_id
+            );
+            client
+        }
+    };
+    if !client.approved {
+        ctx.inbound_client_repository
+            .approve_client(&client.client_id)
+            .await?;
+    }
+
+    let secret = mcpmux_storage::create_jwt_secret_provider(ctx.data_dir())?
+        .get_or_create_secret()
+        .context("Failed to load JWT signing secret")?;
+    let token = mcpmux_gateway::auth::create_access_token(
+        &client.client_id,
+        args.value("--scope"),
+        expires_in,
+        &secret[..],
+    );
+
+    eprintln!(
+        "Token for {} expires in {}s",
+        client.client_name, expires_in
+    );
+    println!("{}", token);
+    Ok(())
+}
+
+/// Register a pre-approved client for headless use (no redirect URIs, no
+/// consent prompt)
+async fn register_client(ctx: &CliContext, name: &str) -> Result<InboundClient> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let client = InboundClient {
+        client_id: format!("mcp_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]),
+        registration_type: RegistrationType::Preregistered,
+        client_name: name.to_string(),
+        client_alias: None,
+        redirect_uris: vec![],
+        grant_types: vec![],
+        response_types: vec![],
+        token_endpoint_auth_method: "none".to_string(),
+        scope: None,
+        approved: true,
+        logo_uri: None,
+        client_uri: None,
+        software_id: None,
+        software_version: None,
+        metadata_url: None,
+        metadata_cached_at: None,
+        metadata_cache_ttl: None,
+        last_seen: None,
+        created_at: now.clone(),
+        updated_at: now,
+        reports_roots: false,
+        roots_capability_known: false,
+        suspended: false,
+    };
+    ctx.inbound_client_repository.save_client(&client).await?;
+    Ok(client)
+}
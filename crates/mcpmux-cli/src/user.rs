@@ -0,0 +1,88 @@
+//! `user`: manage the people sharing a headless gateway.
+
+use anyhow::{anyhow, bail, Result};
+use mcpmux_core::{User, UserRole};
+
+use crate::args::Args;
+use crate::context::CliContext;
+use crate::grant::find_client;
+
+pub const USAGE: &str = "mcpmux-cli user list|add <name> [--admin]|remove <name>|rotate <name>|give <name> [--space <space>] [--client <client>]";
+
+pub async fn run(ctx: CliContext, raw: &[String]) -> Result<()> {
+    let Some((action, rest)) = raw.split_first() else {
+        bail!("Missing user action");
+    };
+    let users = ctx.user_repository.as_ref();
+    match action.as_str() {
+        "list" => {
+            for user in users.list().await? {
+                println!("{:<38} {:<8} {}", user.id, user.role.as_str(), user.name);
+            }
+            Ok(())
+        }
+        "add" => {
+            let args = Args::parse(rest, &["--admin"], &[])?;
+            let role = if args.flag("--admin") {
+                UserRole::Admin
+            } else {
+                UserRole::Member
+            };
+            let (user, token) =
+                mcpmux_gateway::create_user(users, args.required(0, "name")?, role).await?;
+            eprintln!("Created {} {}", user.role.as_str(), user.name);
+            // Shown once; only its hash is stored
+            println!("{}", token);
+            Ok(())
+        }
+        "remove" => {
+            let user = find_user(&ctx, Args::parse(rest, &[], &[])?.required(0, "name")?).await?;
+            users.delete(&user.id).await?;
+            eprintln!(
+                "Removed {}; their Spaces and clients are admin-only",
+                user.name
+            );
+            Ok(())
+        }
+        "rotate" => {
+            let user = find_user(&ctx, Args::parse(rest, &[], &[])?.required(0, "name")?).await?;
+            println!(
+                "{}",
+                mcpmux_gateway::rotate_user_token(users, &user.id).await?
+            );
+            Ok(())
+        }
+        "give" => {
+            let args = Args::parse(rest, &[], &["--space", "--client"])?;
+            let user = find_user(&ctx, args.required(0, "name")?).await?;
+            if args.value("--space").is_none() && args.value("--client").is_none() {
+                bail!("Nothing to give; pass --space and/or --client");
+            }
+            if let Some(space) = args.value("--space") {
+                let space = ctx.resolve_space(Some(space)).await?;
+                ctx.space_repository
+                    .set_owner(&space.id, Some(&user.id))
+                    .await?;
+                eprintln!("{} now owns Space {}", user.name, space.name);
+            }
+            if let Some(client) = args.value("--client") {
+                let client = find_client(&ctx, client)
+                    .await?
+                    .ok_or_else(|| anyhow!("Client not found: {}", client))?;
+                ctx.inbound_client_repository
+                    .set_client_owner(&client.client_id, Some(&user.id.to_string()))
+                    .await?;
+                eprintln!("{} now owns client {}", user.name, client.client_name);
+            }
+            Ok(())
+        }
+        other => bail!("Unknown user action: {}", other),
+    }
+}
+
+async fn find_user(ctx: &CliContext, name: &str) -> Result<User> {
+    ctx.user_repository
+        .get_by_name(name)
+        .await?
+        .ok_or_else(|| anyhow!("User not found: {}", name))
+}
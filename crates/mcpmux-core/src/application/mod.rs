@@ -56,6 +56,7 @@ pub use server::ServerAppService;
 pub use space::SpaceAppService;
 pub use user_space_sync::{SyncResult, UserSpaceSyncService};
 
+use crate::domain::ManagedPolicy;
 use crate::event_bus::EventBus;
 use crate::repository::*;
 use std::sync::Arc;
@@ -69,6 +70,7 @@ pub struct ApplicationServicesBuilder {
     server_feature_repo: Option<Arc<dyn ServerFeatureRepository>>,
     client_repo: Option<Arc<dyn InboundMcpClientRepository>>,
     credential_repo: Option<Arc<dyn CredentialRepository>>,
+    managed_policy: Option<Arc<ManagedPolicy>>,
 }
 
 impl ApplicationServicesBuilder {
@@ -81,6 +83,7 @@ impl ApplicationServicesBuilder {
             server_feature_repo: None,
             client_repo: None,
             credential_repo: None,
+            managed_policy: None,
         }
     }
 
@@ -119,6 +122,11 @@ impl ApplicationServicesBuilder {
         self
     }
 
+    pub fn with_managed_policy(mut self, policy: Arc<ManagedPolicy>) -> Self {
+        self.managed_policy = Some(policy);
+        self
+    }
+
     /// Build all application services
     pub fn build(self) -> anyhow::Result<ApplicationServices> {
         let event_bus = self
@@ -132,12 +140,16 @@ impl ApplicationServicesBuilder {
                 .space_repo
                 .map(|r| SpaceAppService::new(r, self.feature_set_repo.clone(), sender.clone())),
             server: self.installed_server_repo.map(|r| {
-                ServerAppService::new(
+                let service = ServerAppService::new(
                     r,
                     self.server_feature_repo.clone(),
                     self.credential_repo.clone(),
                     sender.clone(),
-                )
+                );
+                match self.managed_policy.clone() {
+                    Some(policy) => service.with_managed_policy(policy),
+                    None => service,
+                }
             }),
             permission: self
                 .feature_set_repo
@@ -2,15 +2,24 @@
 //!
 //! Manages server installation and configuration with automatic event emission.
 
-use anyhow::{anyhow, Result};
-use std::collections::HashMap;
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::domain::{DomainEvent, InstallationSource, InstalledServer, ServerDefinition};
+use crate::domain::{
+    check_input_values, validate_workspace_root, CallContext, DomainEvent, InstallationSource,
+    InstalledServer, ManagedPolicy, ServerDefinition, ServerTimeouts, ToolHooks, TransportConfig,
+    WasmPermissions, WorkspaceRootValidation,
+};
 use crate::event_bus::EventSender;
 use crate::repository::{CredentialRepository, InstalledServerRepository, ServerFeatureRepository};
+use crate::service::{
+    BulkInstallReport, BulkInstallResult, BulkInstallStatus, InstallManifest, ManifestServer,
+    ServerDiscoveryService,
+};
 
 /// Application service for server installation and management
 pub struct ServerAppService {
@@ -18,6 +27,7 @@ pub struct ServerAppService {
     feature_repo: Option<Arc<dyn ServerFeatureRepository>>,
     credential_repo: Option<Arc<dyn CredentialRepository>>,
     event_sender: EventSender,
+    managed_policy: Arc<ManagedPolicy>,
 }
 
 impl ServerAppService {
@@ -32,9 +42,16 @@ impl ServerAppService {
             feature_repo,
             credential_repo,
             event_sender,
+            managed_policy: Arc::default(),
         }
     }
 
+    /// Refuse installs and enables the organization's policy forbids
+    pub fn with_managed_policy(mut self, policy: Arc<ManagedPolicy>) -> Self {
+        self.managed_policy = policy;
+        self
+    }
+
     /// List all installed servers
     pub async fn list(&self) -> Result<Vec<InstalledServer>> {
         self.server_repo.list().await
@@ -60,6 +77,28 @@ impl ServerAppService {
         definition: &ServerDefinition,
         input_values: HashMap<String, String>,
     ) -> Result<InstalledServer> {
+        self.install_with_source(
+            space_id,
+            server_id,
+            definition,
+            input_values,
+            InstallationSource::Registry,
+        )
+        .await
+    }
+
+    /// Install a server, recording where it came from
+    ///
+    /// Emits: `ServerInstalled`
+    pub async fn install_with_source(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        definition: &ServerDefinition,
+        input_values: HashMap<String, String>,
+        source: InstallationSource,
+    ) -> Result<InstalledServer> {
+        self.managed_policy.check_install(server_id, &source)?;
         let space_id_str = space_id.to_string();
 
         // Check if already installed
@@ -72,12 +111,24 @@ impl ServerAppService {
             return Err(anyhow!("Server already installed in this space"));
         }
 
-        // Create installation (disabled by default, user must enable)
+        // A fresh install replaces an uninstalled copy in the trash
+        if let Some(trashed) = self.get_trashed(&space_id_str, server_id).await? {
+            self.delete_permanently(space_id, &trashed).await?;
+            info!(
+                space_id = %space_id,
+                server_id = server_id,
+                "[ServerAppService] Purged trashed copy before reinstalling"
+            );
+        }
+
+        // Create installation (disabled by default, user must enable,
+        // unless it comes from a trusted registry)
         // Cache the definition for offline use
         let server = InstalledServer::new(&space_id_str, server_id)
             .with_inputs(input_values)
             .with_definition(definition)
-            .with_enabled(false);
+            .with_source(source)
+            .with_enabled(definition.source.trust().enabled_on_install());
 
         self.server_repo.install(&server).await?;
 
@@ -97,10 +148,175 @@ impl ServerAppService {
         Ok(server)
     }
 
+    /// Install every server of a manifest, or none of them
+    ///
+    /// All servers are checked before anything is installed: registry
+    /// lookup, trust, the managed policy and input values. Servers already
+    /// installed are left as they are. If an install still fails, the ones
+    /// installed before it are deleted again.
+    ///
+    /// Emits: `ServerInstalled` (and `ServerEnabled`) per server
+    pub async fn bulk_install(
+        &self,
+        space_id: Uuid,
+        manifest: &InstallManifest,
+        registry: &ServerDiscoveryService,
+        confirm_untrusted: bool,
+    ) -> Result<BulkInstallReport> {
+        manifest.validate()?;
+        let space_id_str = space_id.to_string();
+
+        let mut report = BulkInstallReport::default();
+        let mut plans = Vec::new();
+        let mut seen = HashSet::new();
+        for server in &manifest.servers {
+            let (server_id, status, plan) = match self
+                .check_manifest_server(&space_id_str, server, registry, confirm_untrusted)
+                .await
+            {
+                Ok((definition, _)) if !seen.insert(definition.id.clone()) => (
+                    definition.id,
+                    BulkInstallStatus::Failed {
+                        error: "Same server ID as another entry".to_string(),
+                    },
+                    None,
+                ),
+                Ok((definition, source)) => {
+                    let installed = self
+                        .server_repo
+                        .get_by_server_id(&space_id_str, &definition.id)
+                        .await?
+                        .is_some();
+                    if installed {
+                        (definition.id, BulkInstallStatus::AlreadyInstalled, None)
+                    } else {
+                        (
+                            definition.id.clone(),
+                            BulkInstallStatus::NotInstalled,
+                            Some((definition, source)),
+                        )
+                    }
+                }
+                Err(e) => (
+                    server.id.trim().to_string(),
+                    BulkInstallStatus::Failed {
+                        error: format!("{:#}", e),
+                    },
+                    None,
+                ),
+            };
+            report.results.push(BulkInstallResult { server_id, status });
+            plans.push(plan);
+        }
+        if report
+            .results
+            .iter()
+            .any(|r| matches!(r.status, BulkInstallStatus::Failed { .. }))
+        {
+            return Ok(report);
+        }
+
+        let mut installed: Vec<(usize, InstalledServer)> = Vec::new();
+        for (i, (server, plan)) in manifest.servers.iter().zip(plans).enumerate() {
+            let Some((definition, source)) = plan else {
+                continue;
+            };
+            match self
+                .install_manifest_server(space_id, server, &definition, source)
+                .await
+            {
+                Ok(done) => {
+                    report.results[i].status = BulkInstallStatus::Installed {
+                        enabled: done.enabled,
+                    };
+                    installed.push((i, done));
+                }
+                Err(e) => {
+                    report.results[i].status = BulkInstallStatus::Failed {
+                        error: format!("{:#}", e),
+                    };
+                    for (j, done) in installed {
+                        self.delete_permanently(space_id, &done).await?;
+                        self.event_sender.emit(DomainEvent::ServerUninstalled {
+                            space_id,
+                            server_id: done.server_id.clone(),
+                        });
+                        report.results[j].status = BulkInstallStatus::NotInstalled;
+                    }
+                    warn!(
+                        space_id = %space_id,
+                        server_id = %report.results[i].server_id,
+                        "[ServerAppService] Bulk install failed; rolled back"
+                    );
+                    return Ok(report);
+                }
+            }
+        }
+
+        info!(
+            space_id = %space_id,
+            installed = installed.len(),
+            "[ServerAppService] Bulk installed servers"
+        );
+        Ok(report)
+    }
+
+    /// The definition and source a manifest server would be installed with
+    async fn check_manifest_server(
+        &self,
+        space_id: &str,
+        server: &ManifestServer,
+        registry: &ServerDiscoveryService,
+        confirm_untrusted: bool,
+    ) -> Result<(ServerDefinition, InstallationSource)> {
+        let (definition, source) = match server.custom_definition(space_id) {
+            Some(definition) => (definition, InstallationSource::ManualEntry),
+            None => {
+                let definition = registry
+                    .get(server.id.trim())
+                    .await
+                    .ok_or_else(|| anyhow!("Server not found in registry"))?;
+                if definition.source.trust().requires_confirmation() && !confirm_untrusted {
+                    bail!("Comes from an untrusted registry; confirm to install it");
+                }
+                (definition, InstallationSource::Registry)
+            }
+        };
+        self.managed_policy.check_install(&definition.id, &source)?;
+        check_input_values(&definition.transport.metadata().inputs, &server.inputs)?;
+        Ok((definition, source))
+    }
+
+    async fn install_manifest_server(
+        &self,
+        space_id: Uuid,
+        server: &ManifestServer,
+        definition: &ServerDefinition,
+        source: InstallationSource,
+    ) -> Result<InstalledServer> {
+        let mut installed = self
+            .install_with_source(
+                space_id,
+                &definition.id,
+                definition,
+                server.inputs.clone(),
+                source,
+            )
+            .await?;
+        if server.enable && !installed.enabled {
+            self.enable(space_id, &definition.id).await?;
+            installed.enabled = true;
+        }
+        Ok(installed)
+    }
+
     /// Uninstall a server
     ///
-    /// For UserConfig servers, this also removes the entry from the source JSON file.
-    /// For Registry/ManualEntry servers, this only removes the database record.
+    /// Registry/ManualEntry servers go to the trash with their settings,
+    /// credentials and discovered features, so [`Self::restore`] can bring
+    /// them back until they are purged. UserConfig servers are removed from
+    /// their source JSON file and deleted right away, since the file is what
+    /// defines them.
     ///
     /// Emits: `ServerUninstalled`
     pub async fn uninstall(&self, space_id: Uuid, server_id: &str) -> Result<()> {
@@ -129,7 +345,168 @@ impl ServerAppService {
                     "Removed server from config file"
                 );
             }
+            self.delete_permanently(space_id, &server).await?;
+        } else {
+            self.server_repo.trash(&server.id, Utc::now()).await?;
+
+            // Keep discovered features so FeatureSet members still resolve
+            // after a restore
+            if let Some(ref feature_repo) = self.feature_repo {
+                if let Err(e) = feature_repo
+                    .mark_unavailable(&space_id_str, server_id)
+                    .await
+                {
+                    warn!(
+                        server_id = server_id,
+                        error = %e,
+                        "Failed to mark server features unavailable"
+                    );
+                }
+            }
+        }
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            source = ?server.source,
+            "[ServerAppService] Uninstalled server"
+        );
+
+        // Emit event
+        self.event_sender.emit(DomainEvent::ServerUninstalled {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(())
+    }
+
+    /// List enabled servers of a space without a successful tool call in
+    /// `unused_days`, least recently used first. Disabling them frees their
+    /// memory and speeds up startup.
+    pub async fn list_unused(
+        &self,
+        space_id: &str,
+        unused_days: u32,
+    ) -> Result<Vec<InstalledServer>> {
+        let servers = self.server_repo.list_enabled(space_id).await?;
+        Ok(Self::unused(servers, unused_days))
+    }
+
+    /// [`Self::list_unused`] across all spaces, for auto-disable
+    pub async fn list_unused_all(&self, unused_days: u32) -> Result<Vec<InstalledServer>> {
+        let servers = self.server_repo.list_enabled_all().await?;
+        Ok(Self::unused(servers, unused_days))
+    }
+
+    fn unused(servers: Vec<InstalledServer>, unused_days: u32) -> Vec<InstalledServer> {
+        let now = Utc::now();
+        let mut unused: Vec<_> = servers
+            .into_iter()
+            .filter(|s| s.is_unused(unused_days, now))
+            // Built-in servers run in-process and cost nothing idle
+            .filter(|s| {
+                !s.get_definition().is_some_and(|definition| {
+                    matches!(definition.transport, TransportConfig::Builtin { .. })
+                })
+            })
+            .collect();
+        unused.sort_by_key(|s| s.last_used_at.unwrap_or(s.updated_at));
+        unused
+    }
+
+    /// List uninstalled servers of a space that can still be restored
+    pub async fn list_trash(&self, space_id: &str) -> Result<Vec<InstalledServer>> {
+        Ok(self
+            .server_repo
+            .list_trashed()
+            .await?
+            .into_iter()
+            .filter(|s| s.space_id == space_id)
+            .collect())
+    }
+
+    async fn get_trashed(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> Result<Option<InstalledServer>> {
+        Ok(self
+            .list_trash(space_id)
+            .await?
+            .into_iter()
+            .find(|s| s.server_id == server_id))
+    }
+
+    /// Restore an uninstalled server from the trash, as it was configured
+    ///
+    /// Emits: `ServerInstalled`
+    pub async fn restore(&self, space_id: Uuid, server_id: &str) -> Result<InstalledServer> {
+        let mut server = self
+            .get_trashed(&space_id.to_string(), server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not in trash"))?;
+        self.managed_policy.check_server(&server)?;
+
+        self.server_repo.restore(&server.id).await?;
+        server.deleted_at = None;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            "[ServerAppService] Restored server"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerInstalled {
+            space_id,
+            server_id: server_id.to_string(),
+            server_name: server.display_name().to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Permanently delete a server from the trash
+    pub async fn purge(&self, space_id: Uuid, server_id: &str) -> Result<()> {
+        let server = self
+            .get_trashed(&space_id.to_string(), server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not in trash"))?;
+        self.delete_permanently(space_id, &server).await?;
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            "[ServerAppService] Purged server"
+        );
+        Ok(())
+    }
+
+    /// Permanently delete servers trashed more than `retention_days` ago
+    /// (0 = keep them until purged). Returns how many were deleted.
+    pub async fn purge_expired(&self, retention_days: u32) -> Result<usize> {
+        let now = Utc::now();
+        let mut purged = 0;
+        for server in self.server_repo.list_trashed().await? {
+            if !server.trash_expired(retention_days, now) {
+                continue;
+            }
+            let space_id = Uuid::parse_str(&server.space_id)?;
+            self.delete_permanently(space_id, &server).await?;
+            purged += 1;
         }
+        if purged > 0 {
+            info!(
+                purged = purged,
+                "[ServerAppService] Purged expired servers from the trash"
+            );
+        }
+        Ok(purged)
+    }
+
+    /// Delete a server with its discovered features and credentials
+    async fn delete_permanently(&self, space_id: Uuid, server: &InstalledServer) -> Result<()> {
+        let space_id_str = space_id.to_string();
+        let server_id = server.server_id.as_str();
 
         // Delete discovered features
         if let Some(ref feature_repo) = self.feature_repo {
@@ -156,22 +533,8 @@ impl ServerAppService {
             }
         }
 
-        // Uninstall from database
+        // Delete from database
         self.server_repo.uninstall(&server.id).await?;
-
-        info!(
-            space_id = %space_id,
-            server_id = server_id,
-            source = ?server.source,
-            "[ServerAppService] Uninstalled server"
-        );
-
-        // Emit event
-        self.event_sender.emit(DomainEvent::ServerUninstalled {
-            space_id,
-            server_id: server_id.to_string(),
-        });
-
         Ok(())
     }
 
@@ -227,6 +590,11 @@ impl ServerAppService {
             .await?
             .ok_or_else(|| anyhow!("Server not installed"))?;
 
+        // Reject values of the wrong type now rather than when the server starts
+        if let Some(definition) = server.get_definition() {
+            check_input_values(&definition.transport.metadata().inputs, &input_values)?;
+        }
+
         server.input_values = input_values;
         if let Some(env) = env_overrides {
             server.env_overrides = env;
@@ -256,6 +624,295 @@ impl ServerAppService {
         Ok(server)
     }
 
+    /// Toggle whether a stdio server inherits the full desktop environment
+    ///
+    /// Emits: `ServerConfigUpdated` (the running instance is respawned so the
+    /// new environment takes effect)
+    pub async fn set_inherit_env(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        inherit_env: bool,
+    ) -> Result<InstalledServer> {
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.inherit_env = inherit_env;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            inherit_env = inherit_env,
+            "[ServerAppService] Updated env inheritance"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Set the gateway-enforced allowed roots for a filesystem server
+    ///
+    /// Each root is validated and stored normalized; an empty list removes the
+    /// gateway-side restriction. Emits: `ServerConfigUpdated`
+    pub async fn set_fs_allowed_roots(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        roots: Vec<String>,
+    ) -> Result<InstalledServer> {
+        let mut normalized = Vec::with_capacity(roots.len());
+        for root in &roots {
+            match validate_workspace_root(root) {
+                WorkspaceRootValidation::Ok { normalized: n } => {
+                    if !normalized.contains(&n) {
+                        normalized.push(n);
+                    }
+                }
+                WorkspaceRootValidation::Empty => {}
+                WorkspaceRootValidation::Invalid { reason } => {
+                    return Err(anyhow!("Invalid allowed root '{}': {}", root, reason));
+                }
+            }
+        }
+
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.fs_allowed_roots = normalized;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            roots = server.fs_allowed_roots.len(),
+            "[ServerAppService] Updated filesystem allowed roots"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Toggle whether the gateway skips checking tool call arguments against
+    /// the server's declared input schemas
+    ///
+    /// Emits: `ServerConfigUpdated`
+    pub async fn set_skip_argument_validation(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        skip: bool,
+    ) -> Result<InstalledServer> {
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.skip_argument_validation = skip;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            skip_argument_validation = skip,
+            "[ServerAppService] Updated argument validation"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Set the connect and request timeouts for a server; unset ones fall
+    /// back to the definition's, then to the defaults
+    ///
+    /// Emits: `ServerConfigUpdated` (the server reconnects with the new
+    /// connect timeout)
+    pub async fn set_timeouts(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        timeouts: ServerTimeouts,
+    ) -> Result<InstalledServer> {
+        timeouts.validate()?;
+
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.timeouts = timeouts;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            connect_secs = ?timeouts.connect_secs,
+            request_secs = ?timeouts.request_secs,
+            "[ServerAppService] Updated timeouts"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Set the hooks run around a server's tool calls
+    ///
+    /// Emits: `ServerConfigUpdated`
+    pub async fn set_tool_hooks(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        hooks: ToolHooks,
+    ) -> Result<InstalledServer> {
+        hooks.validate()?;
+
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.tool_hooks = hooks;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            before = server.tool_hooks.before.len(),
+            after = server.tool_hooks.after.len(),
+            "[ServerAppService] Updated tool hooks"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Set the context the gateway adds to a server's tool calls
+    ///
+    /// Emits: `ServerConfigUpdated`
+    pub async fn set_call_context(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        context: CallContext,
+    ) -> Result<InstalledServer> {
+        context.validate()?;
+
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.call_context = context;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            fields = server.call_context.meta.len(),
+            "[ServerAppService] Updated call context"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
+    /// Grant or revoke a WASI server's filesystem and network access
+    ///
+    /// Emits: `ServerConfigUpdated` (the module restarts with the new
+    /// permissions)
+    pub async fn set_wasm_grants(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        grants: WasmPermissions,
+    ) -> Result<InstalledServer> {
+        let space_id_str = space_id.to_string();
+
+        let mut server = self
+            .server_repo
+            .get_by_server_id(&space_id_str, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+
+        server.wasm_grants = grants;
+        server.updated_at = chrono::Utc::now();
+
+        self.server_repo.update(&server).await?;
+
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            filesystem = grants.filesystem,
+            network = grants.network,
+            "[ServerAppService] Updated WASM permission grants"
+        );
+
+        self.event_sender.emit(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+
+        Ok(server)
+    }
+
     /// Enable a server
     ///
     /// Emits: `ServerEnabled`
@@ -267,6 +924,7 @@ impl ServerAppService {
             .get_by_server_id(&space_id_str, server_id)
             .await?
             .ok_or_else(|| anyhow!("Server not installed"))?;
+        self.managed_policy.check_server(&server)?;
 
         self.server_repo.set_enabled(&server.id, true).await?;
 
@@ -8,10 +8,12 @@ use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::domain::config::UserSpaceConfig;
-use crate::domain::{InstallationSource, InstalledServer, ServerDefinition};
+use crate::domain::{
+    validate_space_config, InstallationSource, InstalledServer, ManagedPolicy, ServerDefinition,
+};
 use crate::repository::InstalledServerRepository;
 
 /// Result of a sync operation
@@ -23,6 +25,8 @@ pub struct SyncResult {
     pub updated: Vec<String>,
     /// Server IDs that were removed
     pub removed: Vec<String>,
+    /// Server IDs the managed policy kept out
+    pub blocked: Vec<String>,
 }
 
 impl SyncResult {
@@ -40,12 +44,22 @@ impl SyncResult {
 /// Service for syncing user space JSON config files to InstalledServer records
 pub struct UserSpaceSyncService {
     installed_repo: Arc<dyn InstalledServerRepository>,
+    managed_policy: Arc<ManagedPolicy>,
 }
 
 impl UserSpaceSyncService {
     /// Create a new sync service
     pub fn new(installed_repo: Arc<dyn InstalledServerRepository>) -> Self {
-        Self { installed_repo }
+        Self {
+            installed_repo,
+            managed_policy: Arc::default(),
+        }
+    }
+
+    /// Leave out servers the organization's policy forbids
+    pub fn with_managed_policy(mut self, policy: Arc<ManagedPolicy>) -> Self {
+        self.managed_policy = policy;
+        self
     }
 
     /// Ensure no two user-config entries normalize to the same MCP server id.
@@ -83,7 +97,10 @@ impl UserSpaceSyncService {
     /// * `file_path` - Path to the user space JSON config file
     ///
     /// # Returns
-    /// A `SyncResult` with lists of added, updated, and removed server IDs
+    /// A `SyncResult` with lists of added, updated, and removed server IDs.
+    /// A file that doesn't match the published schema fails with an
+    /// [`InvalidSpaceConfig`](crate::domain::InvalidSpaceConfig) and syncs
+    /// nothing.
     pub async fn sync_from_file(&self, space_id: &str, file_path: &Path) -> Result<SyncResult> {
         info!("Syncing servers from file: {:?}", file_path);
 
@@ -92,17 +109,39 @@ impl UserSpaceSyncService {
             .await
             .with_context(|| format!("Failed to read config file: {:?}", file_path))?;
 
+        // Check the file against the published schema first, so a mistake is
+        // reported with its line and column instead of dropping the entry.
+        // The error is an `InvalidSpaceConfig` callers can downcast to.
+        validate_space_config(&content)?;
+
         let config: UserSpaceConfig = serde_json::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {:?}", file_path))?;
 
         // 2. Convert to ServerDefinitions
-        let definitions = config.to_server_definitions(space_id, file_path.to_path_buf());
+        let mut definitions = config.to_server_definitions(space_id, file_path.to_path_buf());
 
         // User-config keys are normalized into MCP-safe server IDs; reject two
         // entries that collapse to the same ID up front so the sync loop can't
         // silently overwrite one custom server with another.
         Self::ensure_unique_server_ids(&definitions)?;
 
+        // Forbidden servers are treated as absent, so ones already synced
+        // are removed below
+        let mut result = SyncResult::default();
+        let source = InstallationSource::UserConfig {
+            file_path: file_path.to_path_buf(),
+        };
+        definitions.retain(
+            |d| match self.managed_policy.check_install(&d.id, &source) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Skipping {}: {}", d.id, e);
+                    result.blocked.push(d.id.clone());
+                    false
+                }
+            },
+        );
+
         let file_server_ids: HashSet<String> = definitions.iter().map(|d| d.id.clone()).collect();
 
         debug!(
@@ -131,7 +170,16 @@ impl UserSpaceSyncService {
             existing_ids
         );
 
-        let mut result = SyncResult::default();
+        // Uninstalled servers still hold their server ID in the trash
+        let trashed: HashMap<String, uuid::Uuid> = self
+            .installed_repo
+            .list_trashed()
+            .await
+            .with_context(|| "Failed to list trashed servers")?
+            .into_iter()
+            .filter(|s| s.space_id == space_id)
+            .map(|s| (s.server_id, s.id))
+            .collect();
 
         // 4. Add/Update servers from file
         for definition in definitions {
@@ -160,6 +208,16 @@ impl UserSpaceSyncService {
                     })
                     .with_enabled(true); // Auto-enable servers from user config
 
+                // The file's definition replaces a trashed copy
+                if let Some(trashed_id) = trashed.get(&server_id) {
+                    self.installed_repo
+                        .uninstall(trashed_id)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to purge trashed server: {}", server_id)
+                        })?;
+                }
+
                 self.installed_repo
                     .install(&installed)
                     .await
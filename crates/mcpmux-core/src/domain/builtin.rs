@@ -13,6 +13,18 @@
 //! names, and which tools belong to which server. The gateway registers the
 //! executable tool implementations against the same ids; the desktop UI renders
 //! per-Space toggles from these descriptors.
+//!
+//! The utility server (`mcpmux-builtin`: time, fetch, scratchpad memory) is
+//! different: it is installed like any other server and connected through the
+//! pool, so its tools go through discovery and FeatureSet permissions. Only its
+//! transport is built in — the gateway runs it in process.
+
+use crate::branding;
+
+use super::{
+    AuthConfig, HostingType, InputDefinition, PublisherInfo, ServerDefinition, ServerSource,
+    TransportConfig, TransportMetadata,
+};
 
 /// Stable id for the self-management ("Tool Optimization") built-in server.
 /// Used as the per-Space config key and to match the gateway's registered
@@ -95,3 +107,143 @@ pub fn builtin_servers() -> Vec<BuiltinServerDescriptor> {
 pub fn builtin_server(id: &str) -> Option<BuiltinServerDescriptor> {
     builtin_servers().into_iter().find(|s| s.id == id)
 }
+
+/// Server ID of the built-in utility server.
+pub const UTILITY_SERVER_ID: &str = "mcpmux-builtin";
+
+/// Input listing the domains the utility server's `fetch` tool may reach.
+pub const FETCH_ALLOWED_DOMAINS_INPUT: &str = "FETCH_ALLOWED_DOMAINS";
+
+/// Definition of the utility server, listed alongside the registry's servers.
+pub fn utility_server_definition() -> ServerDefinition {
+    ServerDefinition {
+        id: UTILITY_SERVER_ID.to_string(),
+        name: format!("{} Utilities", branding::DISPLAY_NAME),
+        description: Some(
+            "Current time, HTTP fetch limited to the domains you allow, and a scratchpad \
+             memory kept separately for each client. Runs inside the gateway."
+                .to_string(),
+        ),
+        alias: Some("builtin".to_string()),
+        auth: Some(AuthConfig::None),
+        icon: None,
+        transport: TransportConfig::Builtin {
+            allowed_domains: vec![format!("${{input:{}}}", FETCH_ALLOWED_DOMAINS_INPUT)],
+            metadata: TransportMetadata {
+                inputs: vec![InputDefinition {
+                    id: FETCH_ALLOWED_DOMAINS_INPUT.to_string(),
+                    label: "Fetch allowed domains".to_string(),
+                    r#type: "text".to_string(),
+                    required: false,
+                    secret: false,
+                    description: Some(
+                        "Comma-separated domains the fetch tool may request, subdomains \
+                         included. Empty blocks every request."
+                            .to_string(),
+                    ),
+                    default: Some(String::new()),
+                    placeholder: Some("api.github.com, example.com".to_string()),
+                    obtain_url: None,
+                    obtain_instructions: None,
+                    options: vec![],
+                    pattern: None,
+                    pattern_error: None,
+                    multiline: false,
+                }],
+            },
+        },
+        categories: vec!["utilities".to_string()],
+        publisher: Some(PublisherInfo {
+            name: branding::DISPLAY_NAME.to_string(),
+            domain: Some(branding::DOMAIN.to_string()),
+            verified: true,
+            official: true,
+        }),
+        source: ServerSource::Bundled,
+        badges: vec![],
+        hosting_type: HostingType::Local,
+        license: None,
+        license_url: None,
+        installation: None,
+        capabilities: None,
+        sponsored: None,
+        media: None,
+        changelog_url: None,
+        timeouts: Default::default(),
+        depends_on: vec![],
+        signature: None,
+        signature_status: Default::default(),
+    }
+}
+
+/// Split the utility server's configured domains into a clean list.
+///
+/// Entries may hold several domains separated by commas or whitespace (the
+/// input is one text field); schemes, paths and a leading `*.` are dropped.
+pub fn parse_allowed_domains(entries: &[String]) -> Vec<String> {
+    let mut domains: Vec<String> = entries
+        .iter()
+        .flat_map(|entry| entry.split(|c: char| c == ',' || c.is_whitespace()))
+        .map(|domain| {
+            let domain = domain.trim();
+            let domain = domain.split_once("://").map_or(domain, |(_, rest)| rest);
+            let domain = domain.split(['/', ':']).next().unwrap_or_default();
+            domain
+                .trim_start_matches("*.")
+                .trim_matches('.')
+                .to_ascii_lowercase()
+        })
+        .filter(|domain| !domain.is_empty() && !domain.contains("${"))
+        .collect();
+    domains.sort();
+    domains.dedup();
+    domains
+}
+
+/// Whether `host` is one of `allowed` or a subdomain of one.
+pub fn is_domain_allowed(host: &str, allowed: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_domains_are_normalized() {
+        let domains = parse_allowed_domains(&[
+            "api.github.com, https://Example.com/path".to_string(),
+            "*.example.org ${input:UNSET}".to_string(),
+            "".to_string(),
+        ]);
+        assert_eq!(
+            domains,
+            vec!["api.github.com", "example.com", "example.org"]
+        );
+    }
+
+    #[test]
+    fn subdomains_are_allowed_but_lookalikes_are_not() {
+        let allowed = vec!["example.com".to_string()];
+        assert!(is_domain_allowed("example.com", &allowed));
+        assert!(is_domain_allowed("API.example.com", &allowed));
+        assert!(!is_domain_allowed("badexample.com", &allowed));
+        assert!(!is_domain_allowed("example.com.evil.net", &allowed));
+        assert!(!is_domain_allowed("example.com", &[]));
+    }
+
+    #[test]
+    fn utility_definition_round_trips() {
+        let definition = utility_server_definition();
+        let json = serde_json::to_string(&definition).unwrap();
+        let parsed: ServerDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.id, UTILITY_SERVER_ID);
+        assert!(matches!(parsed.transport, TransportConfig::Builtin { .. }));
+    }
+}
@@ -0,0 +1,270 @@
+//! Call context: fields the gateway adds to a server's tool calls.
+//!
+//! Every backend sees calls from the gateway, not from the client that made
+//! them. Call context puts the originating client back into each `tools/call`
+//! as `_meta` fields, so a server's logs can attribute its traffic, e.g.
+//! `{"client_name": "{client_name}", "source": "mcpmux"}`.
+//!
+//! Values are templates. These placeholders are filled in per call:
+//!
+//! | Placeholder     | Value                                        |
+//! |-----------------|----------------------------------------------|
+//! | `{client_id}`   | OAuth client ID of the calling client        |
+//! | `{client_name}` | Name the client gave when it connected       |
+//! | `{space_id}`    | Space the call was routed in                 |
+//! | `{server_id}`   | Server the call is routed to                 |
+//! | `{tool}`        | Tool name, as the server knows it            |
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Most `_meta` fields one server may have
+pub const MAX_CALL_CONTEXT_FIELDS: usize = 32;
+
+/// Longest template, in characters
+const MAX_TEMPLATE_LEN: usize = 1024;
+
+/// `_meta` key MCP itself uses on requests
+const PROGRESS_TOKEN_KEY: &str = "progressToken";
+
+const PLACEHOLDERS: [&str; 5] = ["client_id", "client_name", "space_id", "server_id", "tool"];
+
+/// Context added to one server's tool calls
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallContext {
+    /// `_meta` field name to value template
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+}
+
+/// Where a tool call came from and where it's going
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CallOrigin<'a> {
+    pub client_id: Option<&'a str>,
+    pub client_name: Option<&'a str>,
+    pub space_id: &'a str,
+    pub server_id: &'a str,
+    pub tool: &'a str,
+}
+
+/// Call context that can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidCallContext {
+    #[error("At most {MAX_CALL_CONTEXT_FIELDS} context fields are allowed")]
+    TooMany,
+    #[error("'{0}' is not a valid _meta field name")]
+    InvalidKey(String),
+    #[error("'{0}' is reserved by MCP")]
+    ReservedKey(String),
+    #[error("Value of '{key}' is longer than {MAX_TEMPLATE_LEN} characters")]
+    TooLong { key: String },
+    #[error("Value of '{key}' uses unknown placeholder '{{{placeholder}}}'")]
+    UnknownPlaceholder { key: String, placeholder: String },
+}
+
+impl CallContext {
+    /// Whether no fields are set
+    pub fn is_empty(&self) -> bool {
+        self.meta.is_empty()
+    }
+
+    /// Check that every field name is a valid, unreserved `_meta` key and
+    /// every template only uses known placeholders
+    pub fn validate(&self) -> Result<(), InvalidCallContext> {
+        if self.meta.len() > MAX_CALL_CONTEXT_FIELDS {
+            return Err(InvalidCallContext::TooMany);
+        }
+        for (key, template) in &self.meta {
+            check_key(key)?;
+            if template.chars().count() > MAX_TEMPLATE_LEN {
+                return Err(InvalidCallContext::TooLong { key: key.clone() });
+            }
+            if let Some(placeholder) = placeholders(template).find(|p| !PLACEHOLDERS.contains(p)) {
+                return Err(InvalidCallContext::UnknownPlaceholder {
+                    key: key.clone(),
+                    placeholder: placeholder.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The `_meta` fields for a call from `origin`. Fields that come out
+    /// empty (e.g. `{client_name}` for a client that gave no name) are left
+    /// out.
+    pub fn render(&self, origin: &CallOrigin<'_>) -> Map<String, Value> {
+        self.meta
+            .iter()
+            .filter_map(|(key, template)| {
+                let value = fill(template, origin);
+                (!value.is_empty()).then(|| (key.clone(), Value::String(value)))
+            })
+            .collect()
+    }
+}
+
+/// `_meta` keys are an optional `/`-terminated prefix of dot-separated
+/// labels, then a name of letters, digits, `-`, `_` and `.` that starts and
+/// ends with a letter or digit. Prefixes with a `modelcontextprotocol` or
+/// `mcp` label are reserved.
+fn check_key(key: &str) -> Result<(), InvalidCallContext> {
+    let invalid = || InvalidCallContext::InvalidKey(key.to_string());
+    let (prefix, name) = match key.rsplit_once('/') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, key),
+    };
+    let edges_ok = |s: &str| {
+        s.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && s.ends_with(|c: char| c.is_ascii_alphanumeric())
+    };
+    let name_ok = edges_ok(name)
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !name_ok {
+        return Err(invalid());
+    }
+    if let Some(prefix) = prefix {
+        let labels_ok = prefix.split('.').all(|label| {
+            edges_ok(label) && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+        if !labels_ok {
+            return Err(invalid());
+        }
+        if prefix
+            .split('.')
+            .any(|label| label == "modelcontextprotocol" || label == "mcp")
+        {
+            return Err(InvalidCallContext::ReservedKey(key.to_string()));
+        }
+    }
+    if key == PROGRESS_TOKEN_KEY {
+        return Err(InvalidCallContext::ReservedKey(key.to_string()));
+    }
+    Ok(())
+}
+
+/// Names inside `{...}` in a template
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
+
+fn fill(template: &str, origin: &CallOrigin<'_>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.split_once('}').and_then(|(name, tail)| {
+            let value = match name {
+                "client_id" => origin.client_id.unwrap_or_default(),
+                "client_name" => origin.client_name.unwrap_or_default(),
+                "space_id" => origin.space_id,
+                "server_id" => origin.server_id,
+                "tool" => origin.tool,
+                _ => return None,
+            };
+            Some((value, tail))
+        });
+        match value {
+            Some((value, tail)) => {
+                result.push_str(value);
+                rest = tail;
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(fields: &[(&str, &str)]) -> CallContext {
+        CallContext {
+            meta: fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn render_fills_placeholders_and_drops_empty_fields() {
+        let context = context(&[
+            ("client_name", "{client_name}"),
+            ("com.example/source", "mcpmux:{space_id}/{server_id}"),
+            ("request", "{tool} for {client_id}"),
+            ("missing", "{client_name}"),
+        ]);
+        let origin = CallOrigin {
+            client_id: Some("client-1"),
+            client_name: Some("Cursor"),
+            space_id: "space-1",
+            server_id: "github",
+            tool: "search_code",
+        };
+        assert_eq!(
+            Value::Object(context.render(&origin)),
+            json!({
+                "client_name": "Cursor",
+                "com.example/source": "mcpmux:space-1/github",
+                "request": "search_code for client-1",
+                "missing": "Cursor",
+            })
+        );
+
+        let anonymous = CallOrigin {
+            client_name: None,
+            ..origin
+        };
+        let rendered = context.render(&anonymous);
+        assert!(!rendered.contains_key("client_name"));
+        assert!(!rendered.contains_key("missing"));
+        assert_eq!(rendered.len(), 2);
+    }
+
+    #[test]
+    fn validate_rejects_reserved_keys_and_unknown_placeholders() {
+        assert!(
+            context(&[("client_name", "{client_name}"), ("a.b-c/x_y", "1")])
+                .validate()
+                .is_ok()
+        );
+        assert_eq!(
+            context(&[("io.modelcontextprotocol/name", "x")]).validate(),
+            Err(InvalidCallContext::ReservedKey(
+                "io.modelcontextprotocol/name".to_string()
+            ))
+        );
+        assert_eq!(
+            context(&[("progressToken", "x")]).validate(),
+            Err(InvalidCallContext::ReservedKey("progressToken".to_string()))
+        );
+        for key in ["", "-name", "has space", "bad_prefix!/name"] {
+            assert_eq!(
+                context(&[(key, "x")]).validate(),
+                Err(InvalidCallContext::InvalidKey(key.to_string()))
+            );
+        }
+        assert_eq!(
+            context(&[("user", "{user_email}")]).validate(),
+            Err(InvalidCallContext::UnknownPlaceholder {
+                key: "user".to_string(),
+                placeholder: "user_email".to_string(),
+            })
+        );
+    }
+}
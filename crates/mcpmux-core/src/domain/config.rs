@@ -2,6 +2,7 @@ use crate::domain::server::{
     AuthConfig, HostingType, InputDefinition, PublisherInfo, ServerDefinition, ServerSource,
     TransportConfig, TransportMetadata,
 };
+use crate::domain::WasmPermissions;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,7 @@ lazy_static! {
 
 /// Format A: User Space Configuration File
 #[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct UserSpaceConfig {
     #[serde(rename = "mcpServers")]
     pub servers: HashMap<String, UserServerEntry>,
@@ -21,9 +23,11 @@ pub struct UserSpaceConfig {
 /// A single server entry in Format A (User Space Config)
 ///
 /// **IMPORTANT**: This follows the Standard MCP Format used by VS Code, Cursor, Claude Desktop.
-/// Transport fields (command/args/env OR url/headers) go at the TOP LEVEL.
+/// Transport fields (command/args/env OR url/headers OR openapi OR graphql OR wasm) go at the
+/// TOP LEVEL.
 /// There is NO `transport: {}` wrapper - users copy the CONTENTS of registry transport blocks.
 #[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct UserServerEntry {
     // --- Stdio Transport (command-based) ---
     pub command: Option<String>,
@@ -33,6 +37,25 @@ pub struct UserServerEntry {
     // --- HTTP Transport (URL-based) ---
     pub url: Option<String>,
     pub headers: Option<HashMap<String, String>>,
+    pub query: Option<HashMap<String, String>>,
+
+    // --- OpenAPI bridge (REST API; shares headers/query) ---
+    /// URL or path of an OpenAPI document to expose as tools
+    pub openapi: Option<String>,
+    /// API base URL, if not the document's first server
+    pub base_url: Option<String>,
+    /// Operation IDs (OpenAPI) or root fields (GraphQL) to expose (all if unset)
+    pub operations: Option<Vec<String>>,
+
+    // --- GraphQL bridge (shares headers/operations) ---
+    /// GraphQL endpoint to expose as tools
+    pub graphql: Option<String>,
+
+    // --- WASI module (shares args/env) ---
+    /// Path of a `.wasm` MCP server to run sandboxed
+    pub wasm: Option<String>,
+    /// Host access the module needs (granted per installation)
+    pub permissions: Option<WasmPermissions>,
 
     // --- Common Metadata ---
     pub name: Option<String>,
@@ -40,6 +63,8 @@ pub struct UserServerEntry {
     pub icon: Option<String>,
     pub alias: Option<String>,
     pub auth: Option<AuthConfig>,
+    /// Servers in this Space to connect before this one
+    pub depends_on: Option<Vec<String>>,
 
     // Optional metadata block with inputs definition
     pub metadata: Option<UserServerMetadata>,
@@ -134,6 +159,15 @@ impl UserServerEntry {
             sponsored: None,
             media: None,
             changelog_url: None,
+            timeouts: Default::default(),
+            depends_on: self
+                .depends_on
+                .iter()
+                .flatten()
+                .map(|dep| Self::normalize_server_id(dep))
+                .collect(),
+            signature: None,
+            signature_status: Default::default(),
         }
     }
 
@@ -172,11 +206,39 @@ impl UserServerEntry {
     fn resolve_transport_and_inputs(&self) -> (TransportConfig, Vec<InputDefinition>) {
         // Determine transport type from top-level fields
         // Standard MCP format: command/args/env for stdio, url/headers for http
-        let transport = if let Some(url) = &self.url {
+        let transport = if let Some(spec_url) = &self.openapi {
+            // OpenAPI bridge
+            TransportConfig::OpenApi {
+                spec_url: spec_url.clone(),
+                base_url: self.base_url.clone(),
+                headers: self.headers.clone().unwrap_or_default(),
+                query: self.query.clone().unwrap_or_default(),
+                operations: self.operations.clone().unwrap_or_default(),
+                metadata: TransportMetadata::default(),
+            }
+        } else if let Some(endpoint) = &self.graphql {
+            // GraphQL bridge
+            TransportConfig::GraphQl {
+                endpoint: endpoint.clone(),
+                headers: self.headers.clone().unwrap_or_default(),
+                operations: self.operations.clone().unwrap_or_default(),
+                metadata: TransportMetadata::default(),
+            }
+        } else if let Some(module) = &self.wasm {
+            // WASI module
+            TransportConfig::Wasm {
+                module: module.clone(),
+                args: self.args.clone().unwrap_or_default(),
+                env: self.env.clone().unwrap_or_default(),
+                permissions: self.permissions.unwrap_or_default(),
+                metadata: TransportMetadata::default(),
+            }
+        } else if let Some(url) = &self.url {
             // HTTP transport (URL-based)
             TransportConfig::Http {
                 url: url.clone(),
                 headers: self.headers.clone().unwrap_or_default(),
+                query: self.query.clone().unwrap_or_default(),
                 metadata: TransportMetadata::default(),
             }
         } else if let Some(cmd) = &self.command {
@@ -207,15 +269,12 @@ impl UserServerEntry {
             .unwrap_or_default();
 
         // Check transport.metadata.inputs (Format B copy-paste style)
-        match &transport {
-            TransportConfig::Stdio { metadata, .. } | TransportConfig::Http { metadata, .. } => {
-                for input in &metadata.inputs {
-                    inputs_map.entry(input.id.clone()).or_insert(input.clone());
-                }
-            }
+        for input in &transport.metadata().inputs {
+            inputs_map.entry(input.id.clone()).or_insert(input.clone());
         }
 
         // 3. Auto-discover inputs from placeholders in command, args, and env
+        // (or url, headers and query)
         let mut discovered_ids = std::collections::HashSet::new();
 
         // Scan command
@@ -243,6 +302,68 @@ impl UserServerEntry {
             }
         }
 
+        // Scan url, headers and query parameters
+        if let TransportConfig::Http {
+            url,
+            headers,
+            query,
+            ..
+        } = &transport
+        {
+            for value in std::iter::once(url)
+                .chain(headers.values())
+                .chain(query.values())
+            {
+                for cap in INPUT_REGEX.captures_iter(value) {
+                    discovered_ids.insert(cap[1].to_string());
+                }
+            }
+        }
+
+        // Scan the OpenAPI document location, headers and query parameters
+        if let TransportConfig::OpenApi {
+            spec_url,
+            base_url,
+            headers,
+            query,
+            ..
+        } = &transport
+        {
+            for value in std::iter::once(spec_url)
+                .chain(base_url)
+                .chain(headers.values())
+                .chain(query.values())
+            {
+                for cap in INPUT_REGEX.captures_iter(value) {
+                    discovered_ids.insert(cap[1].to_string());
+                }
+            }
+        }
+
+        // Scan the GraphQL endpoint and headers
+        if let TransportConfig::GraphQl {
+            endpoint, headers, ..
+        } = &transport
+        {
+            for value in std::iter::once(endpoint).chain(headers.values()) {
+                for cap in INPUT_REGEX.captures_iter(value) {
+                    discovered_ids.insert(cap[1].to_string());
+                }
+            }
+        }
+
+        // Scan the WASI module path, args and env
+        if let TransportConfig::Wasm {
+            module, args, env, ..
+        } = &transport
+        {
+            for value in std::iter::once(module).chain(args).chain(env.values()) {
+                for cap in INPUT_REGEX.captures_iter(value) {
+                    discovered_ids.insert(cap[1].to_string());
+                }
+            }
+        }
+
         // Create InputDefinitions for discovered IDs (if not already defined)
         for input_id in discovered_ids {
             inputs_map
@@ -258,6 +379,10 @@ impl UserServerEntry {
                     placeholder: None,
                     obtain_url: None,
                     obtain_instructions: None,
+                    options: Vec::new(),
+                    pattern: None,
+                    pattern_error: None,
+                    multiline: false,
                 });
         }
 
@@ -270,11 +395,7 @@ impl UserServerEntry {
         inputs: Vec<InputDefinition>,
     ) -> TransportConfig {
         // Update the transport's metadata with the consolidated inputs
-        match &mut transport {
-            TransportConfig::Stdio { metadata, .. } | TransportConfig::Http { metadata, .. } => {
-                metadata.inputs = inputs;
-            }
-        }
+        transport.metadata_mut().inputs = inputs;
         transport
     }
 }
@@ -295,11 +416,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -320,11 +449,19 @@ mod tests {
             env: None,
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -346,11 +483,19 @@ mod tests {
             env: None,
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -374,11 +519,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -406,11 +559,19 @@ mod tests {
             ])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -432,11 +593,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: Some(UserServerMetadata {
                 inputs: Some(vec![InputDefinition {
                     id: "API_KEY".to_string(),
@@ -449,6 +618,10 @@ mod tests {
                     placeholder: None,
                     obtain_url: None,
                     obtain_instructions: None,
+                    options: Vec::new(),
+                    pattern: None,
+                    pattern_error: None,
+                    multiline: false,
                 }]),
                 publisher: None,
             }),
@@ -547,11 +720,19 @@ mod tests {
                 "Authorization".to_string(),
                 "Bearer token".to_string(),
             )])),
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -569,6 +750,115 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_http_query_and_placeholders() {
+        let json = r#"{
+            "url": "https://api.example.com/${input:REGION}/mcp",
+            "headers": { "X-Api-Key": "${input:KEY}" },
+            "query": { "project": "${input:PROJECT}", "format": "json" }
+        }"#;
+        let entry: UserServerEntry = serde_json::from_str(json).unwrap();
+
+        let (transport, inputs) = entry.resolve_transport_and_inputs();
+
+        let TransportConfig::Http { query, .. } = &transport else {
+            panic!("Expected HTTP transport");
+        };
+        assert_eq!(query.get("format"), Some(&"json".to_string()));
+        let mut ids: Vec<&str> = inputs.iter().map(|i| i.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["KEY", "PROJECT", "REGION"]);
+    }
+
+    #[test]
+    fn test_graphql_transport_detection() {
+        let json = r#"{
+            "graphql": "https://api.example.com/graphql",
+            "headers": { "Authorization": "Bearer ${input:API_TOKEN}" },
+            "operations": ["viewer", "createIssue"]
+        }"#;
+        let entry: UserServerEntry = serde_json::from_str(json).unwrap();
+
+        let (transport, inputs) = entry.resolve_transport_and_inputs();
+
+        let TransportConfig::GraphQl {
+            endpoint,
+            operations,
+            ..
+        } = &transport
+        else {
+            panic!("Expected GraphQL transport");
+        };
+        assert_eq!(endpoint, "https://api.example.com/graphql");
+        assert_eq!(operations, &vec!["viewer", "createIssue"]);
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].id, "API_TOKEN");
+    }
+
+    #[test]
+    fn test_wasm_transport_detection() {
+        let json = r#"{
+            "wasm": "${input:MODULE_DIR}/notes.wasm",
+            "env": { "NOTES_TOKEN": "${input:NOTES_TOKEN}" },
+            "permissions": { "filesystem": true }
+        }"#;
+        let entry: UserServerEntry = serde_json::from_str(json).unwrap();
+
+        let (transport, inputs) = entry.resolve_transport_and_inputs();
+
+        let TransportConfig::Wasm {
+            module,
+            permissions,
+            ..
+        } = &transport
+        else {
+            panic!("Expected WASM transport");
+        };
+        assert_eq!(module, "${input:MODULE_DIR}/notes.wasm");
+        assert!(permissions.filesystem);
+        assert!(!permissions.network);
+        assert_eq!(inputs.len(), 2);
+    }
+
+    #[test]
+    fn test_openapi_transport_detection() {
+        let json = r#"{
+            "openapi": "https://api.example.com/openapi.json",
+            "headers": { "Authorization": "Bearer ${input:API_TOKEN}" },
+            "operations": ["listPets", "getPet"]
+        }"#;
+        let entry: UserServerEntry = serde_json::from_str(json).unwrap();
+
+        let (transport, inputs) = entry.resolve_transport_and_inputs();
+
+        let TransportConfig::OpenApi {
+            spec_url,
+            base_url,
+            operations,
+            ..
+        } = &transport
+        else {
+            panic!("Expected OpenAPI transport");
+        };
+        assert_eq!(spec_url, "https://api.example.com/openapi.json");
+        assert_eq!(base_url, &None);
+        assert_eq!(operations, &vec!["listPets", "getPet"]);
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].id, "API_TOKEN");
+    }
+
+    #[test]
+    fn test_depends_on_uses_normalized_ids() {
+        let json = r#"{
+            "command": "db-proxy",
+            "depends_on": ["My Postgres", "redis"]
+        }"#;
+        let entry: UserServerEntry = serde_json::from_str(json).unwrap();
+
+        let def = entry.to_server_definition("proxy", "space_1", PathBuf::from("space.json"));
+        assert_eq!(def.depends_on, vec!["mypostgres", "redis"]);
+    }
+
     #[test]
     fn test_stdio_transport_detection() {
         let entry = UserServerEntry {
@@ -580,11 +870,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -613,11 +911,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None, // No explicit auth
+            depends_on: None,
             metadata: None,
         };
 
@@ -638,11 +944,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: Some(AuthConfig::Oauth),
+            depends_on: None,
             metadata: None,
         };
 
@@ -700,11 +1014,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: Some(UserServerMetadata {
                 inputs: Some(vec![InputDefinition {
                     id: "LOG_LEVEL".to_string(),
@@ -717,6 +1039,10 @@ mod tests {
                     placeholder: None,
                     obtain_url: None,
                     obtain_instructions: None,
+                    options: Vec::new(),
+                    pattern: None,
+                    pattern_error: None,
+                    multiline: false,
                 }]),
                 publisher: None,
             }),
@@ -744,11 +1070,19 @@ mod tests {
             )])),
             url: None,
             headers: None,
+            query: None,
+            openapi: None,
+            base_url: None,
+            operations: None,
+            graphql: None,
+            wasm: None,
+            permissions: None,
             name: None,
             description: None,
             icon: None,
             alias: None,
             auth: None,
+            depends_on: None,
             metadata: None,
         };
 
@@ -772,6 +1106,10 @@ mod tests {
             placeholder: None,
             obtain_url: None,
             obtain_instructions: None,
+            options: Vec::new(),
+            pattern: None,
+            pattern_error: None,
+            multiline: false,
         };
 
         let json = serde_json::to_string(&input).unwrap();
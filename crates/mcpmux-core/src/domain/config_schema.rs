@@ -0,0 +1,529 @@
+//! Schema validation of user-edited Space config files.
+//!
+//! Space files are checked against the published schema
+//! (`schemas/user-space.schema.json`) before they are synced, so a typo
+//! surfaces as "line 7, column 5: `/mcpServers/github/args` must be an array"
+//! instead of the server quietly going missing.
+//!
+//! Only the schema keywords the published schema uses are implemented:
+//! `type`, `enum`, `format: uri`, `required`, `properties`,
+//! `additionalProperties`, `items`, `anyOf`, `not` and local `$ref`s.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// The published JSON Schema for Space config files
+pub const USER_SPACE_SCHEMA: &str = include_str!("../../../../schemas/user-space.schema.json");
+
+lazy_static! {
+    static ref SCHEMA: Value =
+        serde_json::from_str(USER_SPACE_SCHEMA).expect("user-space schema is valid JSON");
+    static ref URI_REGEX: Regex = Regex::new(r"^[A-Za-z][A-Za-z0-9+.\-]*:\S+$").unwrap();
+}
+
+/// A problem found in a Space config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceConfigIssue {
+    /// JSON pointer to the offending value; empty for the whole file
+    pub path: String,
+    /// 1-based line of the offending value (or its key)
+    pub line: usize,
+    /// 1-based column of the offending value (or its key)
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SpaceConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        if !self.path.is_empty() {
+            write!(f, "`{}` ", self.path)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Every problem with a Space config file
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid space config: {}", describe(.issues))]
+pub struct InvalidSpaceConfig {
+    pub issues: Vec<SpaceConfigIssue>,
+}
+
+fn describe(issues: &[SpaceConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Check a Space config file against the published schema.
+///
+/// Returns every issue at once, each with the line and column it is at. A
+/// file that isn't JSON at all yields a single issue for the syntax error.
+pub fn validate_space_config(content: &str) -> Result<(), InvalidSpaceConfig> {
+    let value: Value = match serde_json::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            let message = e.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(m, _)| m);
+            return Err(InvalidSpaceConfig {
+                issues: vec![SpaceConfigIssue {
+                    path: String::new(),
+                    line: e.line().max(1),
+                    column: e.column().max(1),
+                    message: format!("is not valid JSON: {}", message),
+                }],
+            });
+        }
+    };
+
+    let mut found = Vec::new();
+    check(&SCHEMA, &value, &mut Vec::new(), &mut found);
+    if found.is_empty() {
+        return Ok(());
+    }
+
+    let issues = found
+        .into_iter()
+        .map(|(path, message)| {
+            let (line, column) = line_column(content, locate(content, &path));
+            SpaceConfigIssue {
+                path: pointer(&path),
+                line,
+                column,
+                message,
+            }
+        })
+        .collect();
+    Err(InvalidSpaceConfig { issues })
+}
+
+/// Check `value` against `schema`, collecting `(path, message)` for every
+/// violation
+fn check(
+    schema: &Value,
+    value: &Value,
+    path: &mut Vec<String>,
+    found: &mut Vec<(Vec<String>, String)>,
+) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    if let Some(target) = schema.get("$ref").and_then(Value::as_str) {
+        if let Some(resolved) = resolve(target) {
+            check(resolved, value, path, found);
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| has_type(value, t)) {
+            found.push((
+                path.clone(),
+                format!("must be {}, not {}", article(&allowed), type_name(value)),
+            ));
+            // Nothing else about a value of the wrong type is worth reporting
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            found.push((
+                path.clone(),
+                format!("must be one of {}", options.join(", ")),
+            ));
+        }
+    }
+
+    if schema.get("format").and_then(Value::as_str) == Some("uri") {
+        if let Some(text) = value.as_str() {
+            if !URI_REGEX.is_match(text) {
+                found.push((path.clone(), "must be an absolute URL".to_string()));
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    found.push((path.clone(), format!("is missing `{}`", key)));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, member) in object {
+            path.push(key.clone());
+            match properties.and_then(|p| p.get(key)) {
+                Some(property) => check(property, member, path, found),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        found.push((path.clone(), "is not a known property".to_string()))
+                    }
+                    Some(additional @ Value::Object(_)) => check(additional, member, path, found),
+                    _ => {}
+                },
+            }
+            path.pop();
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            path.push(index.to_string());
+            check(items, item, path, found);
+            path.pop();
+        }
+    }
+
+    if let Some(forbidden) = schema.get("not") {
+        let mut matched = Vec::new();
+        check(forbidden, value, path, &mut matched);
+        if matched.is_empty() {
+            let message = match forbidden.get("required").and_then(Value::as_array) {
+                Some(keys) => format!("must not have {}", keys_list(keys)),
+                None => "matches a shape that is not allowed".to_string(),
+            };
+            found.push((path.clone(), message));
+        }
+    }
+
+    if let Some(branches) = schema.get("anyOf").and_then(Value::as_array) {
+        check_any_of(branches, value, path, found);
+    }
+}
+
+/// `anyOf`: fine if one branch matches. Otherwise report why the intended
+/// branch didn't, or what would make the value match one.
+fn check_any_of(
+    branches: &[Value],
+    value: &Value,
+    path: &mut Vec<String>,
+    found: &mut Vec<(Vec<String>, String)>,
+) {
+    let mut attempts = Vec::with_capacity(branches.len());
+    for branch in branches {
+        let mut branch_found = Vec::new();
+        check(branch, value, path, &mut branch_found);
+        if branch_found.is_empty() {
+            return;
+        }
+        attempts.push((deref(branch), branch_found));
+    }
+
+    // Report the branch the value was evidently meant to be: the first whose
+    // required keys it has
+    let intended = attempts.iter().find(|(branch, _)| {
+        required_keys(branch)
+            .iter()
+            .all(|key| value.get(key.as_str()).is_some())
+    });
+    if let Some((_, branch_found)) = intended {
+        found.extend(branch_found.iter().cloned());
+        return;
+    }
+
+    let keys: Vec<Value> = attempts
+        .iter()
+        .flat_map(|(branch, _)| required_keys(branch))
+        .map(Value::String)
+        .collect();
+    let message = if keys.is_empty() {
+        "doesn't match any of the allowed shapes".to_string()
+    } else {
+        format!("needs one of {}", keys_list(&keys))
+    };
+    found.push((path.clone(), message));
+}
+
+/// A local `#/...` reference into the schema
+fn resolve(target: &str) -> Option<&'static Value> {
+    target.strip_prefix('#').and_then(|p| SCHEMA.pointer(p))
+}
+
+fn deref(schema: &Value) -> &Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(resolve)
+        .unwrap_or(schema)
+}
+
+fn required_keys(schema: &Value) -> Vec<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn keys_list(keys: &[Value]) -> String {
+    keys.iter()
+        .filter_map(Value::as_str)
+        .map(|k| format!("`{}`", k))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "an object",
+        Value::Array(_) => "an array",
+        Value::String(_) => "a string",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::Null => "null",
+    }
+}
+
+fn article(types: &[&str]) -> String {
+    types
+        .iter()
+        .map(|t| match *t {
+            "object" | "array" | "integer" => format!("an {}", t),
+            "null" => "null".to_string(),
+            _ => format!("a {}", t),
+        })
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// JSON pointer for a path of keys and indexes
+fn pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Byte offset of the value at `path` in `text`, or of its key for object
+/// members. `text` must be valid JSON.
+fn locate(text: &str, path: &[String]) -> usize {
+    let bytes = text.as_bytes();
+    let mut pos = skip_ws(bytes, 0);
+    for (depth, segment) in path.iter().enumerate() {
+        match bytes.get(pos) {
+            Some(b'{') => {
+                pos = skip_ws(bytes, pos + 1);
+                loop {
+                    if bytes.get(pos) != Some(&b'"') {
+                        return pos;
+                    }
+                    let key_start = pos;
+                    let key_end = skip_string(bytes, pos);
+                    let key: String =
+                        serde_json::from_str(&text[key_start..key_end]).unwrap_or_default();
+                    // Past the `:`
+                    pos = skip_ws(bytes, skip_ws(bytes, key_end) + 1);
+                    if key == *segment {
+                        if depth + 1 == path.len() {
+                            return key_start;
+                        }
+                        break;
+                    }
+                    pos = skip_ws(bytes, skip_value(bytes, pos));
+                    if bytes.get(pos) == Some(&b',') {
+                        pos = skip_ws(bytes, pos + 1);
+                    }
+                }
+            }
+            Some(b'[') => {
+                let Ok(index) = segment.parse::<usize>() else {
+                    return pos;
+                };
+                pos = skip_ws(bytes, pos + 1);
+                for _ in 0..index {
+                    pos = skip_ws(bytes, skip_value(bytes, pos));
+                    if bytes.get(pos) == Some(&b',') {
+                        pos = skip_ws(bytes, pos + 1);
+                    }
+                }
+            }
+            _ => return pos,
+        }
+    }
+    pos
+}
+
+fn skip_ws(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Position just past the string starting at `pos`
+fn skip_string(bytes: &[u8], mut pos: usize) -> usize {
+    pos += 1;
+    while let Some(&b) = bytes.get(pos) {
+        pos += 1;
+        match b {
+            b'\\' => pos += 1,
+            b'"' => break,
+            _ => {}
+        }
+    }
+    pos
+}
+
+/// Position just past the value starting at `pos`
+fn skip_value(bytes: &[u8], pos: usize) -> usize {
+    match bytes.get(pos) {
+        Some(b'"') => skip_string(bytes, pos),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0usize;
+            let mut pos = pos;
+            while let Some(&b) = bytes.get(pos) {
+                match b {
+                    b'"' => {
+                        pos = skip_string(bytes, pos);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return pos + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+            pos
+        }
+        _ => {
+            let mut pos = pos;
+            while bytes
+                .get(pos)
+                .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+            {
+                pos += 1;
+            }
+            pos
+        }
+    }
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(content: &str) -> Vec<SpaceConfigIssue> {
+        validate_space_config(content)
+            .expect_err("config should be rejected")
+            .issues
+    }
+
+    #[test]
+    fn accepts_every_kind_of_server() {
+        let config = r#"{
+  "mcpServers": {
+    "fs": { "command": "npx", "args": ["-y", "fs"], "env": { "A": "1" } },
+    "remote": { "url": "https://example.com/mcp", "headers": { "X": "y" } },
+    "rest": { "openapi": "./api.json", "operations": ["listPets"] },
+    "gql": { "graphql": "https://api.example.com/graphql" },
+    "sandboxed": { "wasm": "./server.wasm", "permissions": { "network": true } },
+    "keyed": {
+      "command": "node",
+      "auth": { "type": "api_key" },
+      "metadata": { "inputs": [{ "id": "PORT", "label": "Port", "type": "number" }] }
+    }
+  }
+}"#;
+        assert_eq!(validate_space_config(config), Ok(()));
+    }
+
+    #[test]
+    fn reports_each_issue_with_its_position() {
+        let config = r#"{
+  "mcpServers": {
+    "fs": {
+      "command": "npx",
+      "args": "-y fs"
+    },
+    "remote": { "url": "example.com/mcp" },
+    "empty": {},
+    "old": { "transport": { "type": "stdio" }, "command": "npx" }
+  },
+  "servers": {}
+}"#;
+        let found = issues(config);
+        let summary: Vec<_> = found
+            .iter()
+            .map(|i| (i.path.as_str(), i.line, i.column, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "/mcpServers/fs/args",
+                    5,
+                    7,
+                    "must be an array, not a string"
+                ),
+                ("/mcpServers/remote/url", 7, 17, "must be an absolute URL"),
+                (
+                    "/mcpServers/empty",
+                    8,
+                    5,
+                    "needs one of `command`, `url`, `openapi`, `graphql`, `wasm`"
+                ),
+                ("/mcpServers/old", 9, 5, "must not have `transport`"),
+                ("/servers", 11, 3, "is not a known property"),
+            ]
+        );
+        assert!(found[0]
+            .to_string()
+            .starts_with("line 5, column 7: `/mcpServers/fs/args`"));
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_their_position() {
+        let found = issues("{\n  \"mcpServers\": {\n    \"fs\": { \"command\": \"npx\", }\n  }\n}");
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].line, found[0].path.as_str()), (3, ""));
+        assert!(found[0]
+            .message
+            .starts_with("is not valid JSON: trailing comma"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Platform and workspace variables in stdio server configs.
+//!
+//! A custom server config is often shared between machines, so the command
+//! and its arguments need to adapt:
+//!
+//! - `${windows:...}` / `${unix:...}` expand to their contents on that
+//!   platform and to nothing elsewhere, e.g.
+//!   `"command": "${windows:npx.cmd}${unix:npx}"`. Variants may contain other
+//!   variables (`${windows:${env:USERPROFILE}\\bin}`).
+//! - `${workspace}` (or VS Code's `${workspaceFolder}`) expands to the Space's
+//!   base directory.
+//!
+//! `${input:...}` placeholders and secret references such as `${env:HOME}`
+//! are left for their own resolution steps.
+
+/// Platform a `${windows:...}`/`${unix:...}` variant applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    Windows,
+    /// Everything that isn't Windows: Linux, macOS, BSDs
+    Unix,
+}
+
+impl Platform {
+    /// The platform McpMux is running on
+    fn current() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Unix
+        }
+    }
+
+    fn variant_prefix(self) -> &'static str {
+        match self {
+            Platform::Windows => "windows:",
+            Platform::Unix => "unix:",
+        }
+    }
+}
+
+const PLATFORM_PREFIXES: [&str; 2] = ["windows:", "unix:"];
+const WORKSPACE_VARIABLES: [&str; 2] = ["${workspace}", "${workspaceFolder}"];
+
+/// Whether a config string refers to the workspace directory
+pub fn uses_workspace(template: &str) -> bool {
+    WORKSPACE_VARIABLES.iter().any(|var| template.contains(var))
+}
+
+/// Expand platform variants and the workspace variable for this platform.
+///
+/// With no `workspace`, `${workspace}` is left in place; callers that need
+/// it check [`uses_workspace`] first and report the missing directory.
+pub fn expand_config_template(template: &str, workspace: Option<&str>) -> String {
+    expand_for(template, Platform::current(), workspace)
+}
+
+/// [`expand_config_template`] for a given platform
+fn expand_for(template: &str, platform: Platform, workspace: Option<&str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if let Some(var) = WORKSPACE_VARIABLES
+            .iter()
+            .find(|var| tail.starts_with(**var))
+        {
+            out.push_str(workspace.unwrap_or(var));
+            rest = &tail[var.len()..];
+            continue;
+        }
+
+        let body = &tail[2..];
+        let variant = PLATFORM_PREFIXES
+            .iter()
+            .find(|prefix| body.starts_with(**prefix));
+        match (variant, variant.and_then(|_| closing_brace(body))) {
+            (Some(prefix), Some(end)) => {
+                if *prefix == platform.variant_prefix() {
+                    let inner = &body[prefix.len()..end];
+                    out.push_str(&expand_for(inner, platform, workspace));
+                }
+                rest = &body[end + 1..];
+            }
+            // Any other `${...}` (or an unterminated variant) is copied as-is
+            _ => {
+                out.push_str("${");
+                rest = body;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Index of the `}` closing a `${`-opened variable, allowing nested ones
+fn closing_brace(body: &str) -> Option<usize> {
+    let bytes = body.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' if depth == 0 => return Some(i),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Expand a list of arguments, dropping those that only held variants for
+/// another platform (so `"${windows:/c}"` is no argument at all on Unix)
+pub fn expand_config_args(args: &[String], workspace: Option<&str>) -> Vec<String> {
+    args.iter()
+        .filter_map(|arg| {
+            let expanded = expand_config_template(arg, workspace);
+            (arg.is_empty() || !expanded.is_empty()).then_some(expanded)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_the_matching_platform_variant() {
+        let command = "${windows:npx.cmd}${unix:npx}";
+        assert_eq!(expand_for(command, Platform::Windows, None), "npx.cmd");
+        assert_eq!(expand_for(command, Platform::Unix, None), "npx");
+
+        // Nested variables survive for their own resolution step
+        let dir = "${windows:${env:USERPROFILE}\\bin}${unix:${env:HOME}/bin}";
+        assert_eq!(
+            expand_for(dir, Platform::Windows, None),
+            "${env:USERPROFILE}\\bin"
+        );
+        assert_eq!(expand_for(dir, Platform::Unix, None), "${env:HOME}/bin");
+    }
+
+    #[test]
+    fn substitutes_the_workspace() {
+        let arg = "--root=${workspace}/src:${workspaceFolder}";
+        assert_eq!(
+            expand_for(arg, Platform::Unix, Some("/home/me/proj")),
+            "--root=/home/me/proj/src:/home/me/proj"
+        );
+        assert!(uses_workspace(arg));
+        // Unknown workspace: left for the caller to report
+        assert_eq!(expand_for(arg, Platform::Unix, None), arg);
+    }
+
+    #[test]
+    fn leaves_other_variables_and_plain_text_alone() {
+        for text in [
+            "${input:API_KEY}",
+            "${env:HOME}/.config",
+            "unix:///var/run/docker.sock",
+            "${unix:unterminated",
+            "cost: $5 {approx}",
+        ] {
+            assert_eq!(expand_for(text, Platform::Unix, Some("/w")), text);
+        }
+    }
+
+    #[test]
+    fn drops_arguments_for_other_platforms() {
+        let args: Vec<String> = ["${windows:/c}", "run", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let expected: Vec<&str> = if cfg!(windows) {
+            vec!["/c", "run", ""]
+        } else {
+            vec!["run", ""]
+        };
+        assert_eq!(expand_config_args(&args, None), expected);
+    }
+}
@@ -0,0 +1,558 @@
+//! Content filter - data loss prevention on tool results
+//!
+//! Every tool result passes through the filter before the client sees it.
+//! Each rule pairs a detector (card numbers, US social security numbers, a
+//! list of regular expressions, or any [`ContentDetector`] an embedder
+//! registers) with what to do when it matches:
+//!
+//! - `redact` replaces each match, e.g. with `[redacted]`
+//! - `block` withholds the whole result and returns an error instead
+//! - `log` passes the result through and records that it matched
+//!
+//! Rules are configured in the managed policy (`content_filters`), so users
+//! can't turn them off:
+//!
+//! ```json
+//! {
+//!   "content_filters": [
+//!     { "detector": "credit_card", "action": "redact" },
+//!     { "detector": "ssn", "action": "block", "tools": ["hr-*/*"] },
+//!     { "name": "project codes", "detector": "regex", "patterns": ["ACME-\\d{6}"], "action": "log" }
+//!   ]
+//! }
+//! ```
+//!
+//! Only the rule names and match counts are reported, never the matches.
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::tool_hooks::{content_text, glob_match};
+
+/// Largest compiled `regex` pattern, in bytes
+const MAX_PATTERN_SIZE: usize = 1 << 20;
+
+/// Finds sensitive spans in text
+pub trait ContentDetector: Send + Sync {
+    /// Byte ranges of every match, in order and not overlapping
+    fn find(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// Payment card numbers: 13 to 19 digits, optionally grouped with spaces
+/// or dashes, that pass the Luhn check
+pub struct CreditCardDetector {
+    regex: Regex,
+}
+
+impl CreditCardDetector {
+    pub fn new() -> Self {
+        Self {
+            regex: Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").expect("valid card pattern"),
+        }
+    }
+}
+
+impl Default for CreditCardDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentDetector for CreditCardDetector {
+    fn find(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex
+            .find_iter(text)
+            .filter(|m| luhn_valid(m.as_str()))
+            .map(|m| m.range())
+            .collect()
+    }
+}
+
+/// US social security numbers written `123-45-6789`, skipping numbers that
+/// are never issued (area 000, 666 or 9xx, group 00, serial 0000)
+pub struct SsnDetector {
+    regex: Regex,
+}
+
+impl SsnDetector {
+    pub fn new() -> Self {
+        Self {
+            regex: Regex::new(r"\b(\d{3})-(\d{2})-(\d{4})\b").expect("valid SSN pattern"),
+        }
+    }
+}
+
+impl Default for SsnDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentDetector for SsnDetector {
+    fn find(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex
+            .captures_iter(text)
+            .filter(|caps| {
+                let area = &caps[1];
+                area != "000"
+                    && area != "666"
+                    && !area.starts_with('9')
+                    && &caps[2] != "00"
+                    && &caps[3] != "0000"
+            })
+            .filter_map(|caps| caps.get(0).map(|m| m.range()))
+            .collect()
+    }
+}
+
+/// Matches of any of a list of regular expressions
+pub struct RegexDetector {
+    regexes: Vec<Regex>,
+}
+
+impl RegexDetector {
+    pub fn new(patterns: &[String]) -> Result<Self, regex::Error> {
+        let regexes = patterns
+            .iter()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .size_limit(MAX_PATTERN_SIZE)
+                    .build()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { regexes })
+    }
+}
+
+impl ContentDetector for RegexDetector {
+    fn find(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = self
+            .regexes
+            .iter()
+            .flat_map(|regex| regex.find_iter(text).map(|m| m.range()))
+            .filter(|range| !range.is_empty())
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        merge_overlapping(ranges)
+    }
+}
+
+/// What a rule does when its detector matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    /// Replace each match
+    Redact,
+    /// Withhold the whole result
+    Block,
+    /// Only record the match
+    Log,
+}
+
+impl fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FilterAction::Redact => "redact",
+            FilterAction::Block => "block",
+            FilterAction::Log => "log",
+        })
+    }
+}
+
+/// A built-in detector, as configured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "detector", rename_all = "snake_case")]
+pub enum DetectorConfig {
+    CreditCard,
+    Ssn,
+    Regex { patterns: Vec<String> },
+}
+
+impl DetectorConfig {
+    fn name(&self) -> &'static str {
+        match self {
+            DetectorConfig::CreditCard => "credit_card",
+            DetectorConfig::Ssn => "ssn",
+            DetectorConfig::Regex { .. } => "regex",
+        }
+    }
+}
+
+/// One configured rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentFilterRule {
+    /// Name reported when the rule matches; the detector's by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub detector: DetectorConfig,
+    pub action: FilterAction,
+    /// Tools the rule applies to, as `<server_id>/<tool>` patterns; all
+    /// tools when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Text that replaces a match when redacting; `[redacted]` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+/// A rule that can't be compiled
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Content filter '{rule}': {reason}")]
+pub struct InvalidContentFilter {
+    pub rule: String,
+    pub reason: String,
+}
+
+/// A rule ready to run
+#[derive(Clone)]
+pub struct ContentRule {
+    name: String,
+    detector: Arc<dyn ContentDetector>,
+    action: FilterAction,
+    tools: Vec<String>,
+    replacement: String,
+}
+
+impl ContentRule {
+    pub fn new(
+        name: impl Into<String>,
+        detector: Arc<dyn ContentDetector>,
+        action: FilterAction,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            detector,
+            action,
+            tools: Vec::new(),
+            replacement: "[redacted]".to_string(),
+        }
+    }
+
+    /// Limit the rule to tools matching `<server_id>/<tool>` patterns
+    pub fn with_tools(mut self, tools: Vec<String>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Set the text that replaces a match when redacting
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Compile a configured rule
+    pub fn compile(rule: &ContentFilterRule) -> Result<Self, InvalidContentFilter> {
+        let name = rule
+            .name
+            .clone()
+            .unwrap_or_else(|| rule.detector.name().to_string());
+        let invalid = |reason: String| InvalidContentFilter {
+            rule: name.clone(),
+            reason,
+        };
+        if rule.tools.iter().any(|pattern| pattern.trim().is_empty()) {
+            return Err(invalid("tool patterns can't be empty".to_string()));
+        }
+        let detector: Arc<dyn ContentDetector> = match &rule.detector {
+            DetectorConfig::CreditCard => Arc::new(CreditCardDetector::new()),
+            DetectorConfig::Ssn => Arc::new(SsnDetector::new()),
+            DetectorConfig::Regex { patterns } => {
+                if patterns.is_empty() {
+                    return Err(invalid("at least one pattern is required".to_string()));
+                }
+                Arc::new(
+                    RegexDetector::new(patterns)
+                        .map_err(|e| invalid(format!("invalid pattern: {}", e)))?,
+                )
+            }
+        };
+        let mut compiled =
+            Self::new(name.clone(), detector, rule.action).with_tools(rule.tools.clone());
+        if let Some(replacement) = &rule.replacement {
+            compiled = compiled.with_replacement(replacement.clone());
+        }
+        Ok(compiled)
+    }
+
+    fn applies_to(&self, tool: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|pattern| glob_match(pattern, tool))
+    }
+}
+
+impl fmt::Debug for ContentRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentRule")
+            .field("name", &self.name)
+            .field("action", &self.action)
+            .field("tools", &self.tools)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How often one rule matched a result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContentFinding {
+    pub rule: String,
+    pub action: FilterAction,
+    pub matches: usize,
+}
+
+/// What the filter found in a result
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterOutcome {
+    pub findings: Vec<ContentFinding>,
+}
+
+impl FilterOutcome {
+    /// The first blocking rule that matched
+    pub fn blocked_by(&self) -> Option<&str> {
+        self.findings
+            .iter()
+            .find(|finding| finding.action == FilterAction::Block)
+            .map(|finding| finding.rule.as_str())
+    }
+}
+
+/// The rules run on every tool result
+#[derive(Debug, Clone, Default)]
+pub struct ContentFilter {
+    rules: Vec<ContentRule>,
+}
+
+impl ContentFilter {
+    /// Compile configured rules
+    pub fn from_rules(rules: &[ContentFilterRule]) -> Result<Self, InvalidContentFilter> {
+        Ok(Self {
+            rules: rules
+                .iter()
+                .map(ContentRule::compile)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Add a rule, e.g. one with a custom detector
+    pub fn with_rule(mut self, rule: ContentRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Whether there are no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule applies to `tool` (`<server_id>/<tool>`)
+    pub fn applies_to(&self, tool: &str) -> bool {
+        self.rules.iter().any(|rule| rule.applies_to(tool))
+    }
+
+    /// Run the rules that apply to `tool` on a result: the text of each
+    /// content item and the strings of the structured content. Redactions
+    /// are made in place; a blocking match is only reported, and the caller
+    /// withholds the result.
+    pub fn apply(
+        &self,
+        tool: &str,
+        content: &mut [Value],
+        structured: Option<&mut Value>,
+    ) -> FilterOutcome {
+        let mut texts: Vec<&mut String> = content.iter_mut().filter_map(content_text).collect();
+        if let Some(value) = structured {
+            collect_strings(value, &mut texts);
+        }
+
+        let mut outcome = FilterOutcome::default();
+        for rule in self.rules.iter().filter(|rule| rule.applies_to(tool)) {
+            let mut matches = 0;
+            for text in texts.iter_mut() {
+                let ranges = rule.detector.find(text);
+                if ranges.is_empty() {
+                    continue;
+                }
+                matches += ranges.len();
+                if rule.action == FilterAction::Redact {
+                    **text = replace_ranges(text, &ranges, &rule.replacement);
+                }
+            }
+            if matches > 0 {
+                outcome.findings.push(ContentFinding {
+                    rule: rule.name.clone(),
+                    action: rule.action,
+                    matches,
+                });
+            }
+        }
+        outcome
+    }
+}
+
+fn collect_strings<'a>(value: &'a mut Value, texts: &mut Vec<&'a mut String>) {
+    match value {
+        Value::String(text) => texts.push(text),
+        Value::Array(items) => {
+            for item in items {
+                collect_strings(item, texts);
+            }
+        }
+        Value::Object(object) => {
+            for item in object.values_mut() {
+                collect_strings(item, texts);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn merge_overlapping(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+fn replace_ranges(text: &str, ranges: &[Range<usize>], replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    for range in ranges {
+        out.push_str(&text[at..range.start]);
+        out.push_str(replacement);
+        at = range.end;
+    }
+    out.push_str(&text[at..]);
+    out
+}
+
+fn luhn_valid(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2, d * 2) {
+            (0, _) => d,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn filter(rules: Value) -> ContentFilter {
+        let rules: Vec<ContentFilterRule> = serde_json::from_value(rules).expect("rules");
+        ContentFilter::from_rules(&rules).expect("valid rules")
+    }
+
+    #[test]
+    fn test_detectors() {
+        let cards = CreditCardDetector::new();
+        assert_eq!(cards.find("card 4111 1111 1111 1111 ok").len(), 1);
+        assert_eq!(cards.find("4111-1111-1111-1111").len(), 1);
+        // Fails the Luhn check
+        assert!(cards.find("4111 1111 1111 1112").is_empty());
+        assert!(cards.find("order 12345").is_empty());
+
+        let ssns = SsnDetector::new();
+        assert_eq!(ssns.find("ssn 123-45-6789").len(), 1);
+        assert!(ssns.find("000-12-3456 666-12-3456 900-12-3456").is_empty());
+        assert!(ssns.find("123-00-6789 123-45-0000").is_empty());
+    }
+
+    #[test]
+    fn test_apply_actions() {
+        let filter = filter(json!([
+            { "detector": "credit_card", "action": "redact" },
+            { "detector": "ssn", "action": "block", "tools": ["hr/*"] },
+            { "name": "codes", "detector": "regex", "patterns": ["ACME-\\d+"], "action": "log" }
+        ]));
+
+        let mut content =
+            vec![json!({ "type": "text", "text": "paid with 4111111111111111, ref ACME-42" })];
+        let mut structured = json!({ "card": { "number": "4111 1111 1111 1111" } });
+        let outcome = filter.apply("billing/get", &mut content, Some(&mut structured));
+        assert_eq!(content[0]["text"], "paid with [redacted], ref ACME-42");
+        assert_eq!(structured["card"]["number"], "[redacted]");
+        assert_eq!(outcome.blocked_by(), None);
+        assert_eq!(
+            outcome.findings,
+            vec![
+                ContentFinding {
+                    rule: "credit_card".to_string(),
+                    action: FilterAction::Redact,
+                    matches: 2,
+                },
+                ContentFinding {
+                    rule: "codes".to_string(),
+                    action: FilterAction::Log,
+                    matches: 1,
+                },
+            ]
+        );
+
+        // The SSN rule only covers the hr server
+        let mut content = vec![json!({ "type": "text", "text": "ssn 123-45-6789" })];
+        assert!(filter
+            .apply("billing/get", &mut content, None)
+            .findings
+            .is_empty());
+        assert_eq!(
+            filter.apply("hr/employee", &mut content, None).blocked_by(),
+            Some("ssn")
+        );
+    }
+
+    #[test]
+    fn test_custom_detector() {
+        struct Secret;
+        impl ContentDetector for Secret {
+            fn find(&self, text: &str) -> Vec<Range<usize>> {
+                text.match_indices("hunter2")
+                    .map(|(at, m)| at..at + m.len())
+                    .collect()
+            }
+        }
+        let filter = ContentFilter::default().with_rule(
+            ContentRule::new("passwords", Arc::new(Secret), FilterAction::Redact)
+                .with_replacement("***"),
+        );
+        let mut content = vec![json!({ "type": "text", "text": "pw hunter2" })];
+        filter.apply("any/tool", &mut content, None);
+        assert_eq!(content[0]["text"], "pw ***");
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let rules: Vec<ContentFilterRule> = serde_json::from_value(
+            json!([{ "detector": "regex", "patterns": ["("], "action": "log" }]),
+        )
+        .unwrap();
+        assert!(ContentFilter::from_rules(&rules).is_err());
+        let rules: Vec<ContentFilterRule> = serde_json::from_value(
+            json!([{ "detector": "regex", "patterns": [], "action": "log" }]),
+        )
+        .unwrap();
+        assert!(ContentFilter::from_rules(&rules).is_err());
+        assert!(serde_json::from_value::<ContentFilterRule>(
+            json!({ "detector": "iban", "action": "log" })
+        )
+        .is_err());
+    }
+}
@@ -124,6 +124,21 @@ impl Credential {
         }
     }
 
+    /// Create the username and password credentials of HTTP basic auth.
+    pub fn basic_auth(
+        space_id: Uuid,
+        server_id: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> [Self; 2] {
+        let server_id = server_id.into();
+        let mut user = Self::api_key(space_id, server_id.clone(), username);
+        user.credential_type = CredentialType::BasicAuthUser;
+        let mut pass = Self::api_key(space_id, server_id, password);
+        pass.credential_type = CredentialType::BasicAuthPass;
+        [user, pass]
+    }
+
     /// Create an OAuth access token credential.
     pub fn access_token(
         space_id: Uuid,
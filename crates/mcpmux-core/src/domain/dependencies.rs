@@ -0,0 +1,116 @@
+//! Startup ordering for servers that depend on other servers.
+//!
+//! A server definition can list the servers it needs running first
+//! (`depends_on`), e.g. a proxy that talks to a database server. At startup
+//! the servers of a Space are connected in dependency order.
+
+use std::collections::{HashMap, HashSet};
+
+/// The order to connect a Space's servers in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartupOrder {
+    /// Server IDs, each after every server it depends on
+    pub order: Vec<String>,
+    /// Servers that are part of a dependency cycle, or depend on one.
+    /// They can't be ordered and shouldn't be started.
+    pub cyclic: Vec<String>,
+}
+
+/// Order servers so each comes after its dependencies.
+///
+/// `servers` are `(server_id, depends_on)` pairs. Dependencies on servers
+/// that aren't in the list don't affect the order; the caller decides what
+/// to do about them. Otherwise the input order is kept, so servers without
+/// dependencies start in the order they were given.
+pub fn startup_order<'a, I>(servers: I) -> StartupOrder
+where
+    I: IntoIterator<Item = (&'a str, &'a [String])>,
+{
+    let servers: Vec<(&str, &[String])> = servers.into_iter().collect();
+    let known: HashSet<&str> = servers.iter().map(|(id, _)| *id).collect();
+
+    // Number of listed dependencies still to be started, per server
+    let mut pending: HashMap<&str, usize> = servers
+        .iter()
+        .map(|(id, deps)| {
+            let deps: HashSet<&str> = deps
+                .iter()
+                .map(String::as_str)
+                .filter(|dep| known.contains(dep) && dep != id)
+                .collect();
+            (*id, deps.len())
+        })
+        .collect();
+
+    let mut order: Vec<String> = Vec::with_capacity(servers.len());
+    let mut started: HashSet<&str> = HashSet::new();
+    // Lowest-indexed ready server first keeps the input order stable
+    while let Some((id, _)) = servers
+        .iter()
+        .find(|(id, _)| !started.contains(id) && pending[id] == 0)
+    {
+        started.insert(id);
+        order.push(id.to_string());
+
+        for (dependent, deps) in &servers {
+            if !started.contains(dependent) && dependent != id && deps.iter().any(|d| d == id) {
+                if let Some(count) = pending.get_mut(dependent) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+        }
+    }
+
+    let mut cyclic = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for (id, _) in &servers {
+        if !started.contains(id) && seen.insert(id) {
+            cyclic.push(id.to_string());
+        }
+    }
+
+    StartupOrder { order, cyclic }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deps(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn dependencies_start_first_and_input_order_is_kept() {
+        let proxy = deps(&["postgres", "redis"]);
+        let none = deps(&[]);
+        let servers = [
+            ("proxy", proxy.as_slice()),
+            ("github", none.as_slice()),
+            ("postgres", none.as_slice()),
+            ("redis", none.as_slice()),
+        ];
+
+        let plan = startup_order(servers);
+        assert_eq!(plan.order, vec!["github", "postgres", "redis", "proxy"]);
+        assert!(plan.cyclic.is_empty());
+    }
+
+    #[test]
+    fn unknown_dependencies_are_ignored_and_cycles_reported() {
+        let on_b = deps(&["b"]);
+        let on_a = deps(&["a"]);
+        let on_a_and_missing = deps(&["a", "not-installed"]);
+        let on_self = deps(&["d"]);
+        let servers = [
+            ("a", on_b.as_slice()),
+            ("b", on_a.as_slice()),
+            ("c", on_a_and_missing.as_slice()),
+            ("d", on_self.as_slice()),
+        ];
+
+        let plan = startup_order(servers);
+        assert_eq!(plan.order, vec!["d"]);
+        assert_eq!(plan.cyclic, vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,288 @@
+//! Startup failure diagnosis for stdio servers.
+//!
+//! A server that dies during startup usually says why on stderr, buried in a
+//! stack trace: a runner that can't find a binary, an API client rejecting an
+//! empty key, a port someone else holds. [`diagnose_startup_failure`] matches
+//! the captured stderr (and the connect error) against common failures so the
+//! UI can show the cause and a fix instead of "connection closed".
+
+use serde::{Deserialize, Serialize};
+
+/// How many stderr lines a diagnosis keeps for display
+pub const STDERR_TAIL_LINES: usize = 20;
+
+/// Common reasons a stdio server exits during startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// The command or a binary it runs isn't installed
+    MissingBinary,
+    /// A Node.js module or Python package the server imports is missing
+    MissingDependency,
+    /// An API key or token was rejected or not provided
+    InvalidCredentials,
+    /// A port the server listens on is already taken
+    PortInUse,
+    /// A file or directory the server was pointed at doesn't exist
+    FileNotFound,
+    /// The server isn't allowed to read, write or execute something
+    PermissionDenied,
+    /// Nothing recognisable in the output
+    Unknown,
+}
+
+impl FailureKind {
+    /// Lower-case substrings that identify each failure, checked in order:
+    /// a missing binary before a missing file, since both report ENOENT.
+    const PATTERNS: &'static [(FailureKind, &'static [&'static str])] = &[
+        (
+            FailureKind::MissingBinary,
+            &[
+                "command not found",
+                "is not recognized as an internal or external command",
+                "executable file not found",
+                "no such file or directory (os error 2)",
+            ],
+        ),
+        (
+            FailureKind::PortInUse,
+            &[
+                "eaddrinuse",
+                "address already in use",
+                "port is already allocated",
+                "only one usage of each socket address",
+            ],
+        ),
+        (
+            FailureKind::InvalidCredentials,
+            &[
+                "invalid api key",
+                "invalid_api_key",
+                "incorrect api key",
+                "api key is required",
+                "api key not found",
+                "missing api key",
+                "bad credentials",
+                "authentication failed",
+                "invalid token",
+                "token is required",
+                "401 unauthorized",
+                "status code 401",
+                "unauthorized",
+            ],
+        ),
+        (
+            FailureKind::MissingDependency,
+            &[
+                "cannot find module",
+                "err_module_not_found",
+                "modulenotfounderror",
+                "no module named",
+                "importerror",
+            ],
+        ),
+        (
+            FailureKind::PermissionDenied,
+            &[
+                "eacces",
+                "eperm",
+                "permission denied",
+                "operation not permitted",
+            ],
+        ),
+        (
+            FailureKind::FileNotFound,
+            &["enoent", "no such file or directory", "filenotfounderror"],
+        ),
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingBinary => "missing_binary",
+            Self::MissingDependency => "missing_dependency",
+            Self::InvalidCredentials => "invalid_credentials",
+            Self::PortInUse => "port_in_use",
+            Self::FileNotFound => "file_not_found",
+            Self::PermissionDenied => "permission_denied",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::MissingBinary => "A command the server needs is not installed",
+            Self::MissingDependency => "The server is missing a module it imports",
+            Self::InvalidCredentials => "The server rejected its API key or token",
+            Self::PortInUse => "A port the server needs is already in use",
+            Self::FileNotFound => "A file or directory the server uses doesn't exist",
+            Self::PermissionDenied => "The server was denied access to a file or command",
+            Self::Unknown => "The server exited during startup",
+        }
+    }
+
+    fn suggested_fix(&self) -> &'static str {
+        match self {
+            Self::MissingBinary => {
+                "Install the missing command, or make sure it is on your PATH, then retry."
+            }
+            Self::MissingDependency => {
+                "Reinstall the server's package; if it's pinned, update it to a release that \
+                 includes the dependency."
+            }
+            Self::InvalidCredentials => {
+                "Open the server's configuration and check its API key or token, then retry."
+            }
+            Self::PortInUse => {
+                "Stop the other process using the port, or configure the server to use a \
+                 different one."
+            }
+            Self::FileNotFound => {
+                "Check the paths in the server's arguments and environment variables."
+            }
+            Self::PermissionDenied => {
+                "Check the permissions on the files and directories the server is given."
+            }
+            Self::Unknown => "Check the server logs for details.",
+        }
+    }
+}
+
+/// Why a stdio server keeps exiting during startup, and what to do about it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosedCause {
+    pub kind: FailureKind,
+    /// One-line description of the failure
+    pub summary: String,
+    pub suggested_fix: String,
+    /// The stderr (or error) line the diagnosis is based on
+    pub evidence: Option<String>,
+    /// Last lines of stderr before the process exited
+    pub stderr_tail: Vec<String>,
+    /// Early exits counted before the diagnosis was made
+    pub exits: u32,
+}
+
+/// Classify why a server exited during startup from its stderr and the
+/// connect error. Later stderr lines win: the last recognisable line is
+/// usually the one that killed the process.
+pub fn diagnose_startup_failure(error: &str, stderr: &[String], exits: u32) -> DiagnosedCause {
+    let matched = stderr
+        .iter()
+        .rev()
+        .map(String::as_str)
+        .chain(std::iter::once(error))
+        .find_map(|line| classify_line(line).map(|kind| (kind, line)));
+    let (kind, evidence) = match matched {
+        Some((kind, line)) => (kind, Some(line.trim().to_string())),
+        None => (FailureKind::Unknown, None),
+    };
+
+    DiagnosedCause {
+        kind,
+        summary: kind.summary().to_string(),
+        suggested_fix: kind.suggested_fix().to_string(),
+        evidence,
+        stderr_tail: stderr[stderr.len().saturating_sub(STDERR_TAIL_LINES)..].to_vec(),
+        exits,
+    }
+}
+
+fn classify_line(line: &str) -> Option<FailureKind> {
+    let lower = line.to_lowercase();
+    // Node reports a missing binary as "spawn <cmd> ENOENT"
+    if let Some(start) = lower.find("spawn ") {
+        if lower[start..].contains(" enoent") {
+            return Some(FailureKind::MissingBinary);
+        }
+    }
+    FailureKind::PATTERNS
+        .iter()
+        .find(|(_, needles)| needles.iter().any(|needle| lower.contains(needle)))
+        .map(|(kind, _)| *kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn classifies_common_startup_failures() {
+        let cases = [
+            (
+                "sh: 1: mcp-server-foo: command not found",
+                FailureKind::MissingBinary,
+            ),
+            ("Error: spawn docker ENOENT", FailureKind::MissingBinary),
+            (
+                "Error: listen EADDRINUSE: address already in use :::3000",
+                FailureKind::PortInUse,
+            ),
+            (
+                "Error: Invalid API key provided",
+                FailureKind::InvalidCredentials,
+            ),
+            (
+                "HttpError: 401 Unauthorized",
+                FailureKind::InvalidCredentials,
+            ),
+            (
+                "Error: Cannot find module '@modelcontextprotocol/sdk'",
+                FailureKind::MissingDependency,
+            ),
+            (
+                "ModuleNotFoundError: No module named 'mcp'",
+                FailureKind::MissingDependency,
+            ),
+            (
+                "Error: ENOENT: no such file or directory, open '/data/db.sqlite'",
+                FailureKind::FileNotFound,
+            ),
+            (
+                "Error: EACCES: permission denied, mkdir '/opt/x'",
+                FailureKind::PermissionDenied,
+            ),
+        ];
+        for (line, kind) in cases {
+            assert_eq!(classify_line(line), Some(kind), "{}", line);
+        }
+        assert_eq!(classify_line("Server listening on stdio"), None);
+    }
+
+    #[test]
+    fn diagnosis_uses_last_recognisable_stderr_line() {
+        let stderr = lines(
+            "Starting server...\n\
+             warning: could not read config file: ENOENT\n\
+             Error: GITHUB_TOKEN is invalid: Bad credentials\n    at main (index.js:10:5)",
+        );
+        let cause = diagnose_startup_failure("connection closed", &stderr, 3);
+        assert_eq!(cause.kind, FailureKind::InvalidCredentials);
+        assert_eq!(
+            cause.evidence.as_deref(),
+            Some("Error: GITHUB_TOKEN is invalid: Bad credentials")
+        );
+        assert_eq!(cause.stderr_tail.len(), 4);
+        assert_eq!(cause.exits, 3);
+    }
+
+    #[test]
+    fn falls_back_to_the_error_then_unknown() {
+        let cause = diagnose_startup_failure(
+            "Failed to spawn process: No such file or directory (os error 2).",
+            &[],
+            1,
+        );
+        assert_eq!(cause.kind, FailureKind::MissingBinary);
+
+        let stderr: Vec<String> = (0..30).map(|i| format!("line {}", i)).collect();
+        let cause = diagnose_startup_failure("connection closed", &stderr, 2);
+        assert_eq!(cause.kind, FailureKind::Unknown);
+        assert_eq!(cause.evidence, None);
+        assert_eq!(cause.stderr_tail.len(), STDERR_TAIL_LINES);
+        assert_eq!(cause.stderr_tail[0], "line 10");
+    }
+}
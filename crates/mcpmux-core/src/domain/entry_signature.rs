@@ -0,0 +1,252 @@
+//! Entry signatures - detect tampering with registry server definitions
+//!
+//! A publisher signs what a definition executes: its `id` and `transport`
+//! (command, args, env, URL, headers). The signature is an ed25519 signature
+//! over the canonical JSON of those two fields (object keys sorted, no
+//! whitespace), so a registry or mirror that changes the command a server
+//! runs breaks it. Display fields (name, description, media) aren't covered
+//! and can be edited by the registry.
+//!
+//! Which publisher keys are trusted is up to the user ([`PublisherKey`]).
+
+use base64::Engine;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+
+use super::ServerDefinition;
+
+const BASE64: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD;
+
+/// A publisher's signature of a server definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntrySignature {
+    /// ID of the [`PublisherKey`] that made the signature
+    pub key_id: String,
+    /// Base64 ed25519 signature of the signing payload
+    pub signature: String,
+}
+
+/// A trusted publisher key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublisherKey {
+    /// Referenced by [`EntrySignature::key_id`]
+    pub id: String,
+    /// Who the key belongs to
+    pub name: String,
+    /// Base64 ed25519 public key (32 bytes)
+    pub public_key: String,
+}
+
+impl PublisherKey {
+    /// Check that the ID is set and the public key is a valid ed25519 key
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Publisher key ID cannot be empty".to_string());
+        }
+        match BASE64.decode(self.public_key.trim()) {
+            Ok(bytes) if bytes.len() == 32 => Ok(()),
+            _ => Err(format!(
+                "Publisher key '{}' must be a base64 ed25519 public key",
+                self.id
+            )),
+        }
+    }
+}
+
+/// Check a trust store: each key is valid and no two share an ID
+pub fn validate_publisher_keys(keys: &[PublisherKey]) -> Result<(), String> {
+    for (i, key) in keys.iter().enumerate() {
+        key.validate()?;
+        if keys[..i].iter().any(|k| k.id == key.id) {
+            return Err(format!("Duplicate publisher key ID: {}", key.id));
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of checking a definition's signature, set on discovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// No signature
+    #[default]
+    Unsigned,
+    /// Signed by a trusted publisher key
+    Verified,
+    /// Signed with a key that isn't in the trust store
+    UnknownKey,
+    /// The signature doesn't match: the definition was changed after signing
+    Invalid,
+}
+
+impl SignatureStatus {
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Self::Unsigned)
+    }
+}
+
+/// The bytes a publisher signs: canonical JSON of `id` and `transport`
+pub fn signing_payload(definition: &ServerDefinition) -> Result<Vec<u8>, serde_json::Error> {
+    let payload = serde_json::json!({
+        "id": definition.id,
+        "transport": serde_json::to_value(&definition.transport)?,
+    });
+    serde_json::to_vec(&canonicalize(payload))
+}
+
+/// Sort object keys recursively, so the payload doesn't depend on field order
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, canonicalize(v)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonicalize).collect())
+        }
+        other => other,
+    }
+}
+
+/// Check a definition's signature against the trusted publisher keys
+pub fn verify_definition(definition: &ServerDefinition, keys: &[PublisherKey]) -> SignatureStatus {
+    let Some(ref signature) = definition.signature else {
+        return SignatureStatus::Unsigned;
+    };
+    let Some(key) = keys.iter().find(|k| k.id == signature.key_id) else {
+        return SignatureStatus::UnknownKey;
+    };
+
+    let verified = (|| {
+        let public_key = BASE64.decode(key.public_key.trim()).ok()?;
+        let signature = BASE64.decode(signature.signature.trim()).ok()?;
+        let payload = signing_payload(definition).ok()?;
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&payload, &signature)
+            .ok()
+    })();
+    match verified {
+        Some(()) => SignatureStatus::Verified,
+        None => SignatureStatus::Invalid,
+    }
+}
+
+/// A new publisher key pair: the base64 PKCS#8 private key (keep it secret)
+/// and the base64 public key (share it)
+pub fn generate_publisher_key() -> anyhow::Result<(String, String)> {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new())
+        .map_err(|_| anyhow::anyhow!("Failed to generate key pair"))?;
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+        .map_err(|_| anyhow::anyhow!("Failed to load generated key pair"))?;
+    Ok((
+        BASE64.encode(pkcs8.as_ref()),
+        BASE64.encode(key_pair.public_key().as_ref()),
+    ))
+}
+
+/// Sign a definition with a base64 PKCS#8 private key
+pub fn sign_definition(
+    definition: &ServerDefinition,
+    key_id: &str,
+    private_key: &str,
+) -> anyhow::Result<EntrySignature> {
+    let pkcs8 = BASE64
+        .decode(private_key.trim())
+        .map_err(|e| anyhow::anyhow!("Private key is not base64: {}", e))?;
+    let key_pair = Ed25519KeyPair::from_pkcs8(&pkcs8)
+        .map_err(|_| anyhow::anyhow!("Private key is not an ed25519 PKCS#8 key"))?;
+    let payload = signing_payload(definition)?;
+    Ok(EntrySignature {
+        key_id: key_id.to_string(),
+        signature: BASE64.encode(key_pair.sign(&payload).as_ref()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TransportConfig;
+
+    fn definition() -> ServerDefinition {
+        serde_json::from_value(serde_json::json!({
+            "id": "github",
+            "name": "GitHub",
+            "transport": {
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn signed() -> (ServerDefinition, PublisherKey) {
+        let (private_key, public_key) = generate_publisher_key().unwrap();
+        let mut definition = definition();
+        definition.signature = Some(sign_definition(&definition, "acme", &private_key).unwrap());
+        let key = PublisherKey {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            public_key,
+        };
+        (definition, key)
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let (definition, key) = signed();
+        assert!(key.validate().is_ok());
+        assert_eq!(
+            verify_definition(&definition, &[key]),
+            SignatureStatus::Verified
+        );
+        assert_eq!(
+            verify_definition(&definition, &[]),
+            SignatureStatus::UnknownKey
+        );
+        assert_eq!(
+            verify_definition(&self::definition(), &[]),
+            SignatureStatus::Unsigned
+        );
+    }
+
+    #[test]
+    fn test_changed_command_is_invalid() {
+        let (mut definition, key) = signed();
+        if let TransportConfig::Stdio { ref mut args, .. } = definition.transport {
+            args.push("--exfiltrate".to_string());
+        }
+        assert_eq!(
+            verify_definition(&definition, &[key]),
+            SignatureStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn test_display_fields_are_not_signed() {
+        let (mut definition, key) = signed();
+        definition.name = "GitHub (renamed)".to_string();
+        definition.description = Some("Edited by the registry".to_string());
+        assert_eq!(
+            verify_definition(&definition, &[key]),
+            SignatureStatus::Verified
+        );
+    }
+
+    #[test]
+    fn test_validate_publisher_keys() {
+        let (_, key) = signed();
+        assert!(validate_publisher_keys(std::slice::from_ref(&key)).is_ok());
+        assert!(validate_publisher_keys(&[key.clone(), key.clone()]).is_err());
+
+        let mut bad = key;
+        bad.public_key = "bm90IGEga2V5".to_string();
+        assert!(bad.validate().is_err());
+    }
+}
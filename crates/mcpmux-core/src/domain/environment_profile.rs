@@ -0,0 +1,242 @@
+//! EnvironmentProfile entity - switchable dev/staging/prod settings for a Space
+//!
+//! A profile swaps input values and URLs for the servers of a Space at once,
+//! e.g. pointing every server at staging endpoints. At most one profile per
+//! Space is active; with none active, servers use their own settings.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{InstalledServer, TransportConfig};
+
+/// Longest allowed profile name, in characters
+pub const MAX_PROFILE_NAME_LEN: usize = 32;
+
+/// Settings a profile changes for one server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileServerOverrides {
+    /// Input values for this server, over the profile's shared ones
+    #[serde(default)]
+    pub inputs: HashMap<String, String>,
+
+    /// URL to use instead of the definition's (HTTP servers only; the API
+    /// base URL for OpenAPI servers, the endpoint for GraphQL ones)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ProfileServerOverrides {
+    /// Whether nothing is overridden
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.url.is_none()
+    }
+}
+
+/// A named set of input values and URLs for the servers of a Space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentProfile {
+    /// Unique identifier
+    pub id: String,
+
+    /// The space this profile belongs to
+    pub space_id: String,
+
+    /// Human-readable name ("dev", "staging", "prod"), unique within the space
+    pub name: String,
+
+    /// Input values for every server that declares the input
+    #[serde(default)]
+    pub inputs: HashMap<String, String>,
+
+    /// Per-server overrides, by server ID
+    #[serde(default)]
+    pub servers: HashMap<String, ProfileServerOverrides>,
+
+    /// Whether this is the space's active profile
+    #[serde(default)]
+    pub is_active: bool,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EnvironmentProfile {
+    /// Create an empty, inactive profile in a space
+    pub fn new(name: impl Into<String>, space_id: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            space_id: space_id.into(),
+            name: name.into(),
+            inputs: HashMap::new(),
+            servers: HashMap::new(),
+            is_active: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Apply this profile to a server, returning the transport and
+    /// installation to connect with.
+    ///
+    /// Only inputs the server declares are set, so a shared value such as
+    /// `API_URL` never leaks into servers that don't use it.
+    pub fn apply(
+        &self,
+        transport: &TransportConfig,
+        installed: &InstalledServer,
+    ) -> (TransportConfig, InstalledServer) {
+        let mut transport = transport.clone();
+        let mut installed = installed.clone();
+        installed
+            .input_values
+            .extend(self.input_values_for(&transport, &installed.server_id));
+
+        let url_override = self
+            .servers
+            .get(&installed.server_id)
+            .and_then(|o| o.url.as_ref());
+        match (url_override, &mut transport) {
+            (Some(new_url), TransportConfig::Http { url, .. }) => *url = new_url.clone(),
+            (Some(new_url), TransportConfig::OpenApi { base_url, .. }) => {
+                *base_url = Some(new_url.clone())
+            }
+            (Some(new_url), TransportConfig::GraphQl { endpoint, .. }) => {
+                *endpoint = new_url.clone()
+            }
+            _ => {}
+        }
+        (transport, installed)
+    }
+
+    /// Whether applying this profile changes anything for a server
+    pub fn affects(&self, transport: &TransportConfig, server_id: &str) -> bool {
+        let moves_url = matches!(
+            transport,
+            TransportConfig::Http { .. }
+                | TransportConfig::OpenApi { .. }
+                | TransportConfig::GraphQl { .. }
+        ) && self.servers.get(server_id).is_some_and(|o| o.url.is_some());
+        moves_url || !self.input_values_for(transport, server_id).is_empty()
+    }
+
+    fn input_values_for(
+        &self,
+        transport: &TransportConfig,
+        server_id: &str,
+    ) -> HashMap<String, String> {
+        let declared: HashSet<&str> = transport
+            .metadata()
+            .inputs
+            .iter()
+            .map(|input| input.id.as_str())
+            .collect();
+        let server_inputs = self.servers.get(server_id).map(|o| &o.inputs);
+
+        self.inputs
+            .iter()
+            .chain(server_inputs.into_iter().flatten())
+            .filter(|(id, _)| declared.contains(id.as_str()))
+            .map(|(id, value)| (id.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Check a profile name, returning it trimmed
+pub fn validate_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(format!(
+            "Profile name must be at most {} characters",
+            MAX_PROFILE_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{InputDefinition, TransportMetadata};
+
+    fn http_transport(inputs: &[&str]) -> TransportConfig {
+        TransportConfig::Http {
+            url: "https://api.example.com/${input:REGION}/mcp".to_string(),
+            headers: HashMap::new(),
+            query: HashMap::new(),
+            metadata: TransportMetadata {
+                inputs: inputs
+                    .iter()
+                    .map(|id| InputDefinition {
+                        id: id.to_string(),
+                        label: id.to_string(),
+                        r#type: "text".to_string(),
+                        required: false,
+                        secret: false,
+                        description: None,
+                        default: None,
+                        placeholder: None,
+                        obtain_url: None,
+                        obtain_instructions: None,
+                        options: Vec::new(),
+                        pattern: None,
+                        pattern_error: None,
+                        multiline: false,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_apply_sets_declared_inputs_and_url() {
+        let mut profile = EnvironmentProfile::new("staging", "space_1");
+        profile
+            .inputs
+            .insert("REGION".to_string(), "eu-staging".to_string());
+        profile
+            .inputs
+            .insert("DATABASE_URL".to_string(), "postgres://staging".to_string());
+        profile.servers.insert(
+            "api".to_string(),
+            ProfileServerOverrides {
+                inputs: HashMap::from([("REGION".to_string(), "us-staging".to_string())]),
+                url: Some("https://staging.example.com/mcp".to_string()),
+            },
+        );
+
+        let transport = http_transport(&["REGION"]);
+        let mut installed = InstalledServer::new("space_1", "api");
+        installed
+            .input_values
+            .insert("REGION".to_string(), "eu".to_string());
+
+        let (transport, installed) = profile.apply(&transport, &installed);
+        assert_eq!(installed.input_values["REGION"], "us-staging");
+        assert!(!installed.input_values.contains_key("DATABASE_URL"));
+        let TransportConfig::Http { url, .. } = transport else {
+            panic!("Expected HTTP transport");
+        };
+        assert_eq!(url, "https://staging.example.com/mcp");
+
+        assert!(profile.affects(&http_transport(&["REGION"]), "other"));
+        assert!(!profile.affects(&http_transport(&["TOKEN"]), "other"));
+        assert!(profile.affects(&http_transport(&["TOKEN"]), "api"));
+    }
+
+    #[test]
+    fn test_validate_profile_name() {
+        assert_eq!(validate_profile_name(" prod "), Ok("prod".to_string()));
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name(&"p".repeat(MAX_PROFILE_NAME_LEN + 1)).is_err());
+    }
+}
@@ -17,7 +17,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::ServerFeature;
+use super::{DiagnosedCause, ServerFeature, SpaceConfigIssue};
 
 // ============================================================================
 // CACHED FEATURES (moved from gateway to core for event payloads)
@@ -165,6 +165,7 @@ impl ConnectionStatus {
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum DomainEvent {
     // ════════════════════════════════════════════════════════════════════════
     // SPACE MANAGEMENT
@@ -183,6 +184,15 @@ pub enum DomainEvent {
     /// A space was deleted
     SpaceDeleted { space_id: Uuid },
 
+    /// A space's config file failed schema validation and wasn't synced; its
+    /// servers stay as they were until the file is fixed
+    SpaceConfigInvalid {
+        space_id: Uuid,
+        /// Path of the config file
+        file: String,
+        issues: Vec<SpaceConfigIssue>,
+    },
+
     // ════════════════════════════════════════════════════════════════════════
     // SERVER LIFECYCLE (Configuration)
     // ════════════════════════════════════════════════════════════════════════
@@ -246,6 +256,38 @@ pub enum DomainEvent {
         removed: Vec<String>,
     },
 
+    /// A stdio server couldn't start because no package runner for its
+    /// ecosystem (npm, PyPI) is installed
+    RuntimeMissing {
+        space_id: Uuid,
+        server_id: String,
+        /// "npm" or "pypi"
+        ecosystem: String,
+        /// Runner binaries that would work, most preferred first
+        runners: Vec<String>,
+        /// Where to download the preferred runner
+        install_url: String,
+    },
+
+    /// A stdio server keeps exiting during startup; `cause` says why and
+    /// how to fix it
+    ServerCrashLoop {
+        space_id: Uuid,
+        server_id: String,
+        cause: DiagnosedCause,
+    },
+
+    /// A newer version of a runner-launched server's package was published
+    ServerUpdateAvailable {
+        space_id: Uuid,
+        server_id: String,
+        /// "npm" or "pypi"
+        ecosystem: String,
+        package: String,
+        current_version: String,
+        latest_version: String,
+    },
+
     // ════════════════════════════════════════════════════════════════════════
     // FEATURE SETS
     // ════════════════════════════════════════════════════════════════════════
@@ -328,6 +370,16 @@ pub enum DomainEvent {
     /// Gateway server stopped
     GatewayStopped,
 
+    /// Startup auto-connect progress, sent once it starts and again as each
+    /// enabled server finishes connecting, needs OAuth or fails
+    AutoConnectProgress {
+        /// Enabled servers being connected
+        total: usize,
+        connected: usize,
+        needs_oauth: usize,
+        failed: usize,
+    },
+
     // ════════════════════════════════════════════════════════════════════════
     // MCP CAPABILITY CHANGES (pass-through from backend servers)
     // ════════════════════════════════════════════════════════════════════════
@@ -340,6 +392,21 @@ pub enum DomainEvent {
     /// Backend server notified that its resources changed
     ResourcesChanged { space_id: Uuid, server_id: String },
 
+    /// A tool's input schema changed between two discoveries
+    ToolSchemaChanged {
+        space_id: Uuid,
+        server_id: String,
+        tool: String,
+        /// Top-level parameters added and dropped
+        added: Vec<String>,
+        removed: Vec<String>,
+        /// Calls that fit the old schema may not fit the new one
+        breaking: bool,
+        /// The tool is pinned: clients keep the old schema until the change
+        /// is accepted
+        held: bool,
+    },
+
     // ════════════════════════════════════════════════════════════════════════
     // WORKSPACE BINDINGS (root → FeatureSet resolution)
     // ════════════════════════════════════════════════════════════════════════
@@ -401,6 +468,24 @@ pub enum DomainEvent {
         summary: String,
     },
 
+    // ════════════════════════════════════════════════════════════════════════
+    // CONTENT SCANNING
+    // ════════════════════════════════════════════════════════════════════════
+    /// A tool result or resource read looked like a prompt injection.
+    /// `name` is the tool name or resource URI; `heuristics` are the
+    /// matched heuristics, never the text. `blocked` is whether it was
+    /// withheld rather than annotated.
+    PromptInjectionSuspected {
+        space_id: Uuid,
+        server_id: String,
+        client_id: Option<String>,
+        /// `"tool" | "resource"`
+        source: String,
+        name: String,
+        heuristics: Vec<String>,
+        blocked: bool,
+    },
+
     // ════════════════════════════════════════════════════════════════════════
     // BUILT-IN SERVERS
     // ════════════════════════════════════════════════════════════════════════
@@ -423,6 +508,7 @@ impl DomainEvent {
             Self::SpaceCreated { .. } => "space_created",
             Self::SpaceUpdated { .. } => "space_updated",
             Self::SpaceDeleted { .. } => "space_deleted",
+            Self::SpaceConfigInvalid { .. } => "space_config_invalid",
             Self::ServerInstalled { .. } => "server_installed",
             Self::ServerUninstalled { .. } => "server_uninstalled",
             Self::ServerConfigUpdated { .. } => "server_config_updated",
@@ -431,6 +517,9 @@ impl DomainEvent {
             Self::ServerStatusChanged { .. } => "server_status_changed",
             Self::ServerAuthProgress { .. } => "server_auth_progress",
             Self::ServerFeaturesRefreshed { .. } => "server_features_refreshed",
+            Self::RuntimeMissing { .. } => "runtime_missing",
+            Self::ServerCrashLoop { .. } => "server_crash_loop",
+            Self::ServerUpdateAvailable { .. } => "server_update_available",
             Self::FeatureSetCreated { .. } => "feature_set_created",
             Self::FeatureSetUpdated { .. } => "feature_set_updated",
             Self::FeatureSetDeleted { .. } => "feature_set_deleted",
@@ -443,13 +532,16 @@ impl DomainEvent {
             Self::ClientTokenIssued { .. } => "client_token_issued",
             Self::GatewayStarted { .. } => "gateway_started",
             Self::GatewayStopped => "gateway_stopped",
+            Self::AutoConnectProgress { .. } => "auto_connect_progress",
             Self::ToolsChanged { .. } => "tools_changed",
             Self::PromptsChanged { .. } => "prompts_changed",
             Self::ResourcesChanged { .. } => "resources_changed",
+            Self::ToolSchemaChanged { .. } => "tool_schema_changed",
             Self::WorkspaceBindingChanged { .. } => "workspace_binding_changed",
             Self::WorkspaceNeedsBinding { .. } => "workspace_needs_binding",
             Self::SessionRootsChanged => "session_roots_changed",
             Self::MetaToolInvoked { .. } => "meta_tool_invoked",
+            Self::PromptInjectionSuspected { .. } => "prompt_injection_suspected",
             Self::BuiltinServerConfigChanged { .. } => "builtin_server_config_changed",
         }
     }
@@ -500,6 +592,7 @@ impl DomainEvent {
             Self::SpaceCreated { space_id, .. }
             | Self::SpaceUpdated { space_id, .. }
             | Self::SpaceDeleted { space_id }
+            | Self::SpaceConfigInvalid { space_id, .. }
             | Self::ServerInstalled { space_id, .. }
             | Self::ServerUninstalled { space_id, .. }
             | Self::ServerConfigUpdated { space_id, .. }
@@ -508,6 +601,9 @@ impl DomainEvent {
             | Self::ServerStatusChanged { space_id, .. }
             | Self::ServerAuthProgress { space_id, .. }
             | Self::ServerFeaturesRefreshed { space_id, .. }
+            | Self::RuntimeMissing { space_id, .. }
+            | Self::ServerCrashLoop { space_id, .. }
+            | Self::ServerUpdateAvailable { space_id, .. }
             | Self::FeatureSetCreated { space_id, .. }
             | Self::FeatureSetUpdated { space_id, .. }
             | Self::FeatureSetDeleted { space_id, .. }
@@ -516,8 +612,10 @@ impl DomainEvent {
             | Self::ToolsChanged { space_id, .. }
             | Self::PromptsChanged { space_id, .. }
             | Self::ResourcesChanged { space_id, .. }
+            | Self::ToolSchemaChanged { space_id, .. }
             | Self::WorkspaceBindingChanged { space_id, .. }
             | Self::WorkspaceNeedsBinding { space_id, .. }
+            | Self::PromptInjectionSuspected { space_id, .. }
             | Self::BuiltinServerConfigChanged { space_id } => Some(*space_id),
 
             Self::ClientRegistered { .. }
@@ -527,6 +625,7 @@ impl DomainEvent {
             | Self::ClientTokenIssued { .. }
             | Self::GatewayStarted { .. }
             | Self::GatewayStopped
+            | Self::AutoConnectProgress { .. }
             | Self::SessionRootsChanged
             | Self::MetaToolInvoked { .. } => None,
         }
@@ -545,9 +644,14 @@ impl DomainEvent {
             | Self::ServerStatusChanged { server_id, .. }
             | Self::ServerAuthProgress { server_id, .. }
             | Self::ServerFeaturesRefreshed { server_id, .. }
+            | Self::RuntimeMissing { server_id, .. }
+            | Self::ServerCrashLoop { server_id, .. }
+            | Self::ServerUpdateAvailable { server_id, .. }
             | Self::ToolsChanged { server_id, .. }
             | Self::PromptsChanged { server_id, .. }
-            | Self::ResourcesChanged { server_id, .. } => Some(server_id),
+            | Self::ResourcesChanged { server_id, .. }
+            | Self::ToolSchemaChanged { server_id, .. }
+            | Self::PromptInjectionSuspected { server_id, .. } => Some(server_id),
             _ => None,
         }
     }
@@ -561,6 +665,7 @@ impl DomainEvent {
             | Self::ClientTokenIssued { client_id, .. }
             | Self::ClientGrantChanged { client_id, .. }
             | Self::WorkspaceNeedsBinding { client_id, .. } => Some(client_id),
+            Self::PromptInjectionSuspected { client_id, .. } => client_id.as_deref(),
             _ => None,
         }
     }
@@ -585,6 +690,44 @@ impl DomainEvent {
     pub fn timestamp(&self) -> DateTime<Utc> {
         Utc::now()
     }
+
+    /// The event as JSON for subscribers outside the app (`/events`, the
+    /// gRPC control plane): session IDs are dropped and secret-looking
+    /// values in strings are redacted
+    pub fn to_sanitized_json(&self) -> serde_json::Value {
+        let mut json = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = json.as_object_mut() {
+            fields.remove("session_id");
+        }
+        redact_strings(&mut json);
+        json
+    }
+}
+
+fn redact_strings(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => *text = crate::redact_secrets(text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_strings),
+        serde_json::Value::Object(fields) => fields.values_mut().for_each(redact_strings),
+        _ => {}
+    }
+}
+
+/// Which events an external subscriber wants
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Only events about this Space
+    pub space_id: Option<Uuid>,
+    /// Only these event types (see [`DomainEvent::type_name`]); all when empty
+    pub types: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &DomainEvent) -> bool {
+        self.space_id
+            .is_none_or(|space_id| event.space_id() == Some(space_id))
+            && (self.types.is_empty() || self.types.iter().any(|t| t == event.type_name()))
+    }
 }
 
 // ============================================================================
@@ -623,6 +766,17 @@ impl DomainEventEnvelope {
     }
 }
 
+/// An event as recorded in the event journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaledEvent {
+    /// Position in the journal; increases with every event and is never reused
+    pub sequence: u64,
+    /// When the event was journaled
+    pub recorded_at: DateTime<Utc>,
+    /// The actual event
+    pub event: DomainEvent,
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -664,6 +818,69 @@ mod tests {
         assert!(!space.affects_mcp_capabilities());
     }
 
+    #[test]
+    fn sanitized_json_drops_session_ids_and_redacts_secrets() {
+        let event = DomainEvent::MetaToolInvoked {
+            client_id: "client-1".to_string(),
+            session_id: Some("sess-1".to_string()),
+            tool_name: "mcpmux_activate".to_string(),
+            decision: "read".to_string(),
+            resolved_feature_set_id: None,
+            summary: "api_key=abc123def456".to_string(),
+        };
+        let json = event.to_sanitized_json();
+        assert_eq!(json["type"], "meta_tool_invoked");
+        assert_eq!(json["client_id"], "client-1");
+        assert!(json.get("session_id").is_none());
+        assert_eq!(json["summary"], "api_key=[REDACTED]");
+
+        let event = DomainEvent::ServerStatusChanged {
+            space_id: Uuid::new_v4(),
+            server_id: "github".to_string(),
+            status: ConnectionStatus::Error,
+            flow_id: 1,
+            has_connected_before: false,
+            message: Some("401 for Bearer abcdefgh12345678".to_string()),
+            features: None,
+        };
+        assert_eq!(
+            event.to_sanitized_json()["message"],
+            "401 for Bearer [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn event_filter_matches_space_and_types() {
+        let space_id = Uuid::new_v4();
+        let enabled = DomainEvent::ServerEnabled {
+            space_id,
+            server_id: "github".to_string(),
+        };
+        let elsewhere = DomainEvent::ServerEnabled {
+            space_id: Uuid::new_v4(),
+            server_id: "github".to_string(),
+        };
+        let stopped = DomainEvent::GatewayStopped;
+
+        let all = EventFilter::default();
+        assert!(all.matches(&enabled) && all.matches(&stopped));
+
+        let in_space = EventFilter {
+            space_id: Some(space_id),
+            ..Default::default()
+        };
+        assert!(in_space.matches(&enabled));
+        assert!(!in_space.matches(&elsewhere));
+        assert!(!in_space.matches(&stopped));
+
+        let by_type = EventFilter {
+            types: vec!["gateway_stopped".to_string()],
+            ..Default::default()
+        };
+        assert!(by_type.matches(&stopped));
+        assert!(!by_type.matches(&enabled));
+    }
+
     #[test]
     fn test_space_id_extraction() {
         let event = DomainEvent::ServerInstalled {
@@ -16,6 +16,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::AnnotationHint;
+
 /// The type of a FeatureSet.
 ///
 /// `Starter` is auto-created once per Space; `Custom` covers everything
@@ -25,6 +27,7 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[derive(Default)]
+#[non_exhaustive]
 pub enum FeatureSetType {
     /// Auto-created with the Space and used as the **default fallback** for
     /// unmapped folders / rootless sessions. Its members are editable, but its
@@ -59,9 +62,10 @@ impl FeatureSetType {
 }
 
 /// Mode for including or excluding a member
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
+#[non_exhaustive]
 pub enum MemberMode {
     #[default]
     Include,
@@ -86,13 +90,16 @@ impl MemberMode {
 }
 
 /// Type of member in a featureset
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum MemberType {
     /// Another featureset (composition)
     FeatureSet,
     /// A specific feature (tool, prompt, or resource)
     Feature,
+    /// Every tool in the space with an annotation hint
+    Annotation,
 }
 
 impl MemberType {
@@ -100,6 +107,7 @@ impl MemberType {
         match self {
             Self::FeatureSet => "feature_set",
             Self::Feature => "feature",
+            Self::Annotation => "annotation",
         }
     }
 
@@ -107,6 +115,7 @@ impl MemberType {
         match s {
             "feature_set" => Some(Self::FeatureSet),
             "feature" => Some(Self::Feature),
+            "annotation" => Some(Self::Annotation),
             _ => None,
         }
     }
@@ -114,6 +123,7 @@ impl MemberType {
 
 /// A member of a featureset (either another featureset or a feature)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct FeatureSetMember {
     /// Unique identifier for this membership
     pub id: String,
@@ -121,13 +131,30 @@ pub struct FeatureSetMember {
     pub feature_set_id: String,
     /// Type of member
     pub member_type: MemberType,
-    /// ID of the member (feature ID for Feature, featureset ID for FeatureSet)
+    /// ID of the member (feature ID for Feature, featureset ID for FeatureSet,
+    /// hint such as `read_only` for Annotation)
     pub member_id: String,
     /// Include or exclude
     pub mode: MemberMode,
 }
 
 impl FeatureSetMember {
+    /// Create a new member of any type
+    pub fn new(
+        feature_set_id: impl Into<String>,
+        member_type: MemberType,
+        member_id: impl Into<String>,
+        mode: MemberMode,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            feature_set_id: feature_set_id.into(),
+            member_type,
+            member_id: member_id.into(),
+            mode,
+        }
+    }
+
     /// Create a new member that includes a feature
     pub fn include_feature(feature_set_id: &str, feature_id: &str) -> Self {
         Self {
@@ -160,6 +187,28 @@ impl FeatureSetMember {
             mode: MemberMode::Include,
         }
     }
+
+    /// Create a new member that includes every tool with an annotation hint
+    pub fn include_annotation(feature_set_id: &str, hint: AnnotationHint) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            feature_set_id: feature_set_id.to_string(),
+            member_type: MemberType::Annotation,
+            member_id: hint.as_str().to_string(),
+            mode: MemberMode::Include,
+        }
+    }
+
+    /// Create a new member that excludes every tool with an annotation hint
+    pub fn exclude_annotation(feature_set_id: &str, hint: AnnotationHint) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            feature_set_id: feature_set_id.to_string(),
+            member_type: MemberType::Annotation,
+            member_id: hint.as_str().to_string(),
+            mode: MemberMode::Exclude,
+        }
+    }
 }
 
 /// FeatureSet defines a bundle of permissions using explicit feature selection.
@@ -168,6 +217,7 @@ impl FeatureSetMember {
 /// features (tools, prompts, resources). The `Default` type is auto-created per
 /// space; its effective members can be edited by the user just like a Custom set.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct FeatureSet {
     /// Unique identifier
     pub id: String,
@@ -382,6 +432,10 @@ mod tests {
             Some(MemberType::FeatureSet)
         );
         assert_eq!(MemberType::parse("feature"), Some(MemberType::Feature));
+        assert_eq!(
+            MemberType::parse("annotation"),
+            Some(MemberType::Annotation)
+        );
         assert_eq!(MemberType::parse("invalid"), None);
     }
 
@@ -410,6 +464,14 @@ mod tests {
         assert_eq!(member.mode, MemberMode::Include);
     }
 
+    #[test]
+    fn test_exclude_annotation_member() {
+        let member = FeatureSetMember::exclude_annotation("fs_123", AnnotationHint::Destructive);
+        assert_eq!(member.member_id, "destructive");
+        assert_eq!(member.member_type, MemberType::Annotation);
+        assert_eq!(member.mode, MemberMode::Exclude);
+    }
+
     // Builder pattern tests
     #[test]
     fn test_featureset_with_description() {
@@ -0,0 +1,311 @@
+//! Typed `${input:...}` values.
+//!
+//! Server definitions declare the inputs their transport needs (API keys,
+//! ports, log levels). Before a server is started, the values the user saved
+//! are merged with the declared defaults and checked against each input's
+//! type, options and pattern, so a typo surfaces as "Port must be a number"
+//! in McpMux instead of a crash inside the server.
+//!
+//! Values that contain a secret reference (`${env:...}`, `${keychain:...}`)
+//! are only resolved at connect time, so they are checked for presence only.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::server::InputDefinition;
+use crate::parse_secret_references;
+
+/// How an input's value is interpreted, from its declared `type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// Free text, including `password`, `url` and path inputs
+    String,
+    Number,
+    Boolean,
+    /// One of the declared `options`
+    Enum,
+}
+
+impl InputDefinition {
+    /// How this input's value is interpreted
+    pub fn kind(&self) -> InputKind {
+        match self.r#type.to_ascii_lowercase().as_str() {
+            "number" | "integer" => InputKind::Number,
+            "boolean" | "bool" => InputKind::Boolean,
+            "select" | "enum" => InputKind::Enum,
+            _ => InputKind::String,
+        }
+    }
+
+    /// Check a single value against this input's type, options and pattern.
+    ///
+    /// Returns the value in canonical form (booleans as `true`/`false`,
+    /// multi-line values with `\n` line endings). Messages never include
+    /// the value itself, since it may be a secret.
+    pub fn check_value(&self, value: &str) -> Result<String, String> {
+        let value = if self.multiline {
+            value.replace("\r\n", "\n")
+        } else if value.contains(['\n', '\r']) {
+            return Err("must be a single line".to_string());
+        } else {
+            value.to_string()
+        };
+
+        let value = match self.kind() {
+            InputKind::String => value,
+            InputKind::Number => {
+                let trimmed = value.trim();
+                match trimmed.parse::<f64>() {
+                    Ok(n) if n.is_finite() => trimmed.to_string(),
+                    _ => return Err("must be a number".to_string()),
+                }
+            }
+            InputKind::Boolean => match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => "true".to_string(),
+                "false" | "0" | "no" | "off" => "false".to_string(),
+                _ => return Err("must be true or false".to_string()),
+            },
+            InputKind::Enum => {
+                // A select without options can't be checked
+                if !self.options.is_empty() && !self.options.iter().any(|o| o.value == value) {
+                    let allowed: Vec<&str> =
+                        self.options.iter().map(|o| o.value.as_str()).collect();
+                    return Err(format!("must be one of: {}", allowed.join(", ")));
+                }
+                value
+            }
+        };
+
+        if let Some(pattern) = &self.pattern {
+            match Regex::new(&format!("^(?:{})$", pattern)) {
+                Ok(regex) if !regex.is_match(&value) => {
+                    return Err(self
+                        .pattern_error
+                        .clone()
+                        .unwrap_or_else(|| "is not in the expected format".to_string()));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        input = %self.id,
+                        error = %e,
+                        "[Inputs] Ignoring invalid pattern in server definition"
+                    );
+                }
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+/// An input whose value can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InputValueError {
+    pub input_id: String,
+    pub label: String,
+    pub message: String,
+}
+
+/// Every problem with a server's input values, found before it is started
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid server configuration: {}", describe(.errors))]
+pub struct InvalidInputValues {
+    pub errors: Vec<InputValueError>,
+}
+
+fn describe(errors: &[InputValueError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{} {}", e.label, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Merge saved input values with the declared defaults, without checking them.
+///
+/// An empty saved value counts as unset, so the default applies. Values for
+/// undeclared inputs are kept as they are.
+pub fn merge_input_defaults(
+    inputs: &[InputDefinition],
+    user_values: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut merged = user_values.clone();
+    for input in inputs {
+        let unset = merged.get(&input.id).is_none_or(|v| v.is_empty());
+        if let (true, Some(default)) = (unset, &input.default) {
+            merged.insert(input.id.clone(), default.clone());
+        }
+    }
+    merged
+}
+
+/// Merge saved input values with the declared defaults and check them.
+///
+/// Returns the values to substitute into `${input:...}` placeholders, or
+/// every missing required input and invalid value at once.
+pub fn resolve_input_values(
+    inputs: &[InputDefinition],
+    user_values: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, InvalidInputValues> {
+    resolve(inputs, user_values, true)
+}
+
+/// Check the values a user is saving, before they are stored.
+///
+/// Like [`resolve_input_values`], except that missing required inputs are
+/// allowed: a configuration may be saved before it is complete.
+pub fn check_input_values(
+    inputs: &[InputDefinition],
+    user_values: &HashMap<String, String>,
+) -> Result<(), InvalidInputValues> {
+    resolve(inputs, user_values, false).map(|_| ())
+}
+
+fn resolve(
+    inputs: &[InputDefinition],
+    user_values: &HashMap<String, String>,
+    require: bool,
+) -> Result<HashMap<String, String>, InvalidInputValues> {
+    let mut values = merge_input_defaults(inputs, user_values);
+    let mut errors = Vec::new();
+
+    for input in inputs {
+        let error = |message: String| InputValueError {
+            input_id: input.id.clone(),
+            label: input.label.clone(),
+            message,
+        };
+        let Some(value) = values.get(&input.id).filter(|v| !v.is_empty()) else {
+            if require && input.required {
+                errors.push(error("is required".to_string()));
+            }
+            continue;
+        };
+        if !parse_secret_references(value).is_empty() {
+            continue;
+        }
+        match input.check_value(value) {
+            Ok(canonical) => {
+                values.insert(input.id.clone(), canonical);
+            }
+            Err(message) => errors.push(error(message)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(InvalidInputValues { errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(id: &str, kind: &str) -> InputDefinition {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "label": id,
+            "type": kind,
+        }))
+        .unwrap()
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn checks_typed_values() {
+        let port = input("PORT", "number");
+        assert_eq!(port.check_value(" 8080 "), Ok("8080".to_string()));
+        assert_eq!(port.check_value("80a"), Err("must be a number".to_string()));
+
+        let verbose = input("VERBOSE", "boolean");
+        assert_eq!(verbose.check_value("Yes"), Ok("true".to_string()));
+        assert_eq!(verbose.check_value("0"), Ok("false".to_string()));
+        assert!(verbose.check_value("maybe").is_err());
+
+        let level: InputDefinition = serde_json::from_value(serde_json::json!({
+            "id": "LEVEL",
+            "label": "Log level",
+            "type": "select",
+            "options": ["info", { "value": "debug", "label": "Debug" }],
+        }))
+        .unwrap();
+        assert_eq!(level.kind(), InputKind::Enum);
+        assert_eq!(level.options[1].label.as_deref(), Some("Debug"));
+        assert_eq!(level.check_value("debug"), Ok("debug".to_string()));
+        assert_eq!(
+            level.check_value("trace"),
+            Err("must be one of: info, debug".to_string())
+        );
+    }
+
+    #[test]
+    fn checks_patterns_and_line_breaks() {
+        let mut token = input("TOKEN", "password");
+        token.pattern = Some("ghp_[A-Za-z0-9]+".to_string());
+        token.pattern_error = Some("must be a classic GitHub token (ghp_...)".to_string());
+        assert!(token.check_value("ghp_abc123").is_ok());
+        // The pattern must match the whole value
+        assert_eq!(
+            token.check_value("xghp_abc"),
+            Err("must be a classic GitHub token (ghp_...)".to_string())
+        );
+        assert_eq!(
+            token.check_value("ghp_a\nb"),
+            Err("must be a single line".to_string())
+        );
+
+        let mut key = input("KEY", "password");
+        key.multiline = true;
+        assert_eq!(
+            key.check_value("-----BEGIN KEY-----\r\nabc\r\n-----END KEY-----"),
+            Ok("-----BEGIN KEY-----\nabc\n-----END KEY-----".to_string())
+        );
+    }
+
+    #[test]
+    fn resolves_defaults_and_reports_every_problem() {
+        let mut api_key = input("API_KEY", "password");
+        api_key.required = true;
+        let mut level = input("LEVEL", "text");
+        level.default = Some("info".to_string());
+        let port = input("PORT", "number");
+        let inputs = vec![api_key, level, port];
+
+        let err =
+            resolve_input_values(&inputs, &values(&[("LEVEL", ""), ("PORT", "http")])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid server configuration: API_KEY is required; PORT must be a number"
+        );
+
+        let resolved =
+            resolve_input_values(&inputs, &values(&[("API_KEY", "sk-1"), ("PORT", "80")])).unwrap();
+        assert_eq!(resolved["LEVEL"], "info");
+        assert_eq!(resolved["PORT"], "80");
+
+        // Saving an incomplete configuration is fine, a wrong value is not
+        assert!(check_input_values(&inputs, &values(&[("PORT", "80")])).is_ok());
+        assert!(check_input_values(&inputs, &values(&[("PORT", "http")])).is_err());
+    }
+
+    #[test]
+    fn secret_references_are_checked_at_connect_time() {
+        let mut port = input("PORT", "number");
+        port.required = true;
+        let resolved =
+            resolve_input_values(&[port], &values(&[("PORT", "${env:MCP_PORT}")])).unwrap();
+        assert_eq!(resolved["PORT"], "${env:MCP_PORT}");
+    }
+}
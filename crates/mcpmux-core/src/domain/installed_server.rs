@@ -6,11 +6,15 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use super::ServerDefinition;
+use super::{
+    is_filesystem_server, CallContext, PathPolicy, ServerDefinition, ServerTimeouts, ToolHooks,
+    WasmPermissions,
+};
 
 /// Tracks how a server was installed (for sync/cleanup decisions)
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum InstallationSource {
     /// Installed from Registry via UI
     #[default]
@@ -38,6 +42,7 @@ pub enum InstallationSource {
 /// Note: Connection status is NOT stored here - it's runtime-only state
 /// managed by ServerManager and communicated via events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct InstalledServer {
     /// Unique installation ID
     pub id: Uuid,
@@ -74,9 +79,64 @@ pub struct InstalledServer {
     #[serde(default)]
     pub extra_headers: HashMap<String, String>,
 
+    /// Whether a stdio child process inherits the full desktop environment.
+    ///
+    /// Off by default: children only see PATH, an OS baseline, and the
+    /// server's declared env/inputs, so unrelated secrets in the user's
+    /// environment are not leaked to community servers.
+    #[serde(default)]
+    pub inherit_env: bool,
+
+    /// Allowed roots enforced by the gateway on tool-call path arguments
+    /// when the server is tagged as a filesystem server (see [`PathPolicy`]).
+    /// Empty = no gateway-side path restriction.
+    #[serde(default)]
+    pub fs_allowed_roots: Vec<String>,
+
+    /// Package version a runner-launched stdio server (`npx pkg`, `uvx pkg`)
+    /// is pinned to. Recorded when the server first runs; replaces whatever
+    /// version the definition names. `None` = run what the definition says.
+    #[serde(default)]
+    pub package_version: Option<String>,
+
+    /// Whether the gateway forwards tool calls without checking their
+    /// arguments against the tool's declared `inputSchema`. For servers
+    /// whose published schemas are inaccurate; off = validate.
+    #[serde(default)]
+    pub skip_argument_validation: bool,
+
+    /// Connect and request timeouts set for this installation; unset ones
+    /// come from the definition (see [`InstalledServer::effective_timeouts`])
+    #[serde(default)]
+    pub timeouts: ServerTimeouts,
+
+    /// Hooks run by the gateway around this server's tool calls
+    #[serde(default)]
+    pub tool_hooks: ToolHooks,
+
+    /// Context the gateway adds to this server's tool calls
+    #[serde(default)]
+    pub call_context: CallContext,
+
+    /// Host access the user granted a WASI server; it only gets what its
+    /// definition also requests
+    #[serde(default)]
+    pub wasm_grants: WasmPermissions,
+
     /// Whether OAuth authentication has been completed
     pub oauth_connected: bool,
 
+    /// When a tool call to this server last succeeded; auto-connect starts
+    /// recently used servers first
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// When the server was uninstalled into the trash; `None` while it is
+    /// installed. Trashed servers keep their settings and credentials until
+    /// restored or purged.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+
     /// How this server was installed (for sync/cleanup decisions)
     #[serde(default)]
     pub source: InstallationSource,
@@ -105,7 +165,17 @@ impl InstalledServer {
             env_overrides: HashMap::new(),
             args_append: Vec::new(),
             extra_headers: HashMap::new(),
+            inherit_env: false,
+            fs_allowed_roots: Vec::new(),
+            package_version: None,
+            skip_argument_validation: false,
+            timeouts: ServerTimeouts::default(),
+            tool_hooks: ToolHooks::default(),
+            call_context: CallContext::default(),
+            wasm_grants: WasmPermissions::default(),
             oauth_connected: false,
+            last_used_at: None,
+            deleted_at: None,
             source: InstallationSource::default(),
             created_at: now,
             updated_at: now,
@@ -166,11 +236,57 @@ impl InstalledServer {
         self.updated_at = Utc::now();
     }
 
+    /// Gateway path policy for this installation.
+    ///
+    /// `Some` only when the cached definition is tagged as a filesystem server
+    /// and at least one valid allowed root is configured.
+    pub fn path_policy(&self) -> Option<PathPolicy> {
+        if self.fs_allowed_roots.is_empty() {
+            return None;
+        }
+        if !self
+            .get_definition()
+            .is_some_and(|d| is_filesystem_server(&d))
+        {
+            return None;
+        }
+        let policy = PathPolicy::new(&self.fs_allowed_roots);
+        (!policy.is_empty()).then_some(policy)
+    }
+
+    /// Timeouts to connect with: this installation's, then the definition's
+    pub fn effective_timeouts(&self, definition: &ServerDefinition) -> ServerTimeouts {
+        self.timeouts.or(definition.timeouts)
+    }
+
     /// Check if this server came from a user config file
     pub fn is_from_user_config(&self) -> bool {
         matches!(self.source, InstallationSource::UserConfig { .. })
     }
 
+    /// Whether a trashed server is older than `retention_days` (0 = kept
+    /// until purged)
+    pub fn trash_expired(&self, retention_days: u32, now: DateTime<Utc>) -> bool {
+        match self.deleted_at {
+            Some(deleted_at) if retention_days > 0 => {
+                now - deleted_at >= chrono::Duration::days(i64::from(retention_days))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the server is enabled but hasn't been used for `unused_days`
+    /// (0 = never flag). Counted from its last successful tool call, or from
+    /// its last change (install, enable, settings) when that is later.
+    pub fn is_unused(&self, unused_days: u32, now: DateTime<Utc>) -> bool {
+        let since = self
+            .last_used_at
+            .map_or(self.updated_at, |used| used.max(self.updated_at));
+        self.enabled
+            && unused_days > 0
+            && now - since >= chrono::Duration::days(i64::from(unused_days))
+    }
+
     /// Get the source file path if this server came from a user config
     pub fn source_file_path(&self) -> Option<&PathBuf> {
         match &self.source {
@@ -193,6 +309,40 @@ mod tests {
         assert!(!server.enabled, "New servers should be disabled by default");
     }
 
+    #[test]
+    fn test_trash_expired() {
+        let now = Utc::now();
+        let mut server = InstalledServer::new("space_default", "test.server");
+        assert!(
+            !server.trash_expired(30, now),
+            "Installed servers never expire"
+        );
+
+        server.deleted_at = Some(now - chrono::Duration::days(31));
+        assert!(server.trash_expired(30, now));
+        assert!(!server.trash_expired(60, now));
+        assert!(!server.trash_expired(0, now), "0 keeps trash until purged");
+    }
+
+    #[test]
+    fn test_is_unused() {
+        let now = Utc::now();
+        let mut server = InstalledServer::new("space_default", "test.server").with_enabled(true);
+        server.updated_at = now - chrono::Duration::days(40);
+        assert!(server.is_unused(30, now));
+        assert!(!server.is_unused(0, now), "0 never flags servers");
+
+        server.last_used_at = Some(now - chrono::Duration::days(2));
+        assert!(!server.is_unused(30, now));
+
+        server.last_used_at = None;
+        server.enabled = false;
+        assert!(
+            !server.is_unused(30, now),
+            "Disabled servers aren't flagged"
+        );
+    }
+
     #[test]
     fn test_with_enabled() {
         let server = InstalledServer::new("space_default", "test.server").with_enabled(true);
@@ -337,6 +487,62 @@ mod tests {
         assert!(server.env_overrides.is_empty());
         assert!(server.args_append.is_empty());
         assert!(server.extra_headers.is_empty());
+        assert!(!server.inherit_env, "Env inheritance must be opt-in");
+        assert!(server.fs_allowed_roots.is_empty());
+        assert!(server.package_version.is_none());
+        assert!(!server.skip_argument_validation);
+        assert!(server.timeouts.is_empty());
+        assert!(server.tool_hooks.is_empty());
+        assert!(server.call_context.is_empty());
+        assert!(server.wasm_grants.is_empty());
+    }
+
+    #[test]
+    fn test_path_policy_requires_filesystem_tag_and_roots() {
+        let mut definition: ServerDefinition = serde_json::from_value(serde_json::json!({
+            "id": "fs",
+            "name": "Filesystem",
+            "transport": { "type": "stdio", "command": "npx" }
+        }))
+        .expect("definition");
+
+        let mut server = InstalledServer::new("space_default", "fs").with_definition(&definition);
+        server.fs_allowed_roots = vec!["/home/user/projects".to_string()];
+        assert!(
+            server.path_policy().is_none(),
+            "Untagged servers are not policed"
+        );
+
+        definition.categories = vec!["filesystem".to_string()];
+        server = server.with_definition(&definition);
+        let policy = server.path_policy().expect("policy for tagged server");
+        assert!(policy.allows("/home/user/projects/readme.md"));
+        assert!(!policy.allows("/etc/passwd"));
+
+        server.fs_allowed_roots.clear();
+        assert!(
+            server.path_policy().is_none(),
+            "No roots, nothing to enforce"
+        );
+    }
+
+    #[test]
+    fn test_effective_timeouts_prefer_installation() {
+        let definition: ServerDefinition = serde_json::from_value(serde_json::json!({
+            "id": "slow",
+            "name": "Slow",
+            "transport": { "type": "stdio", "command": "docker" },
+            "timeouts": { "connect_secs": 300, "request_secs": 120 }
+        }))
+        .expect("definition");
+
+        let mut server = InstalledServer::new("space_default", "slow");
+        assert_eq!(server.effective_timeouts(&definition), definition.timeouts);
+
+        server.timeouts.request_secs = Some(900);
+        let effective = server.effective_timeouts(&definition);
+        assert_eq!(effective.connect_secs, Some(300));
+        assert_eq!(effective.request_secs, Some(900));
     }
 
     #[test]
@@ -0,0 +1,294 @@
+//! Managed policy - organization rules an administrator provisions
+//!
+//! An IT department can drop a policy file in a machine-wide location that
+//! only administrators can write (see [`managed_policy_path`]). Its rules
+//! sit above everything a user configures: the install layer refuses
+//! servers the policy forbids, and the gateway never exposes features it
+//! excludes, whatever FeatureSets grant. There is deliberately no setting or
+//! environment variable that points elsewhere or turns it off.
+//!
+//! ```json
+//! {
+//!   "allowed_servers": ["github", "atlassian-*"],
+//!   "forbidden_tools": ["github/delete_repository"],
+//!   "excluded_patterns": ["*/drop_*", "*delete*"],
+//!   "disable_custom_servers": true,
+//!   "content_filters": [{ "detector": "credit_card", "action": "redact" }]
+//! }
+//! ```
+//!
+//! Tools and features are named `<server_id>/<feature_name>`; `*` matches
+//! any run of characters, so `*/drop_*` matches that tool on every server.
+//! `content_filters` are the rules of the [`ContentFilter`] run on tool
+//! results; `prompt_injection` turns on the [`InjectionScanner`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info};
+
+use super::tool_hooks::glob_match;
+use super::{
+    ContentFilter, ContentFilterRule, ContentRule, FeatureType, InjectionScanConfig,
+    InjectionScanner, InstallationSource, InstalledServer, ServerFeature,
+};
+
+/// File name of the managed policy
+pub const MANAGED_POLICY_FILE: &str = "managed-policy.json";
+
+/// Machine-wide location of the managed policy:
+///
+/// - Linux: `/etc/mcpmux/managed-policy.json`
+/// - macOS: `/Library/Application Support/McpMux/managed-policy.json`
+/// - Windows: `%ProgramData%\McpMux\managed-policy.json`
+pub fn managed_policy_path() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let dir = std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+        .join("McpMux");
+    #[cfg(target_os = "macos")]
+    let dir = PathBuf::from("/Library/Application Support/McpMux");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dir = PathBuf::from("/etc/mcpmux");
+    dir.join(MANAGED_POLICY_FILE)
+}
+
+/// Organization rules users can't override
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManagedPolicy {
+    /// Server IDs that may be installed, with `*` wildcards; any when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_servers: Option<Vec<String>>,
+
+    /// Tools never exposed, as `<server_id>/<tool>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_tools: Vec<String>,
+
+    /// Tools, prompts and resources never exposed, as `<server_id>/<name>`
+    /// patterns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_patterns: Vec<String>,
+
+    /// Only registry servers may be installed or connected, not ones from
+    /// space files or entered by hand
+    #[serde(default)]
+    pub disable_custom_servers: bool,
+
+    /// Detectors run on every tool result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_filters: Vec<ContentFilterRule>,
+
+    /// Scan tool results and resources for prompt injections; off when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_injection: Option<InjectionScanConfig>,
+}
+
+/// Why the managed policy refuses a server
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ManagedPolicyViolation {
+    #[error("Server '{0}' is not on your organization's allowlist")]
+    ServerNotAllowed(String),
+    #[error("Custom servers are disabled by your organization ('{0}')")]
+    CustomServersDisabled(String),
+}
+
+impl ManagedPolicy {
+    /// Load the machine-wide policy. No file means no rules; a file that
+    /// can't be read or parsed locks everything down rather than letting a
+    /// broken policy lift its own rules.
+    pub fn load() -> Self {
+        let path = managed_policy_path();
+        match Self::load_from(&path) {
+            Ok(Some(policy)) => {
+                info!("[ManagedPolicy] Enforcing {}", path.display());
+                policy
+            }
+            Ok(None) => Self::default(),
+            Err(e) => {
+                error!("[ManagedPolicy] {:#}; denying all servers", e);
+                Self::deny_all()
+            }
+        }
+    }
+
+    /// Load a policy file; `None` when it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let policy: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid managed policy {}", path.display()))?;
+        ContentFilter::from_rules(&policy.content_filters)
+            .with_context(|| format!("Invalid managed policy {}", path.display()))?;
+        if let Some(config) = &policy.prompt_injection {
+            InjectionScanner::new(config)
+                .with_context(|| format!("Invalid managed policy {}", path.display()))?;
+        }
+        Ok(Some(policy))
+    }
+
+    /// Allow no server and expose no feature
+    pub fn deny_all() -> Self {
+        Self {
+            allowed_servers: Some(Vec::new()),
+            forbidden_tools: Vec::new(),
+            excluded_patterns: vec!["*".to_string()],
+            disable_custom_servers: true,
+            content_filters: Vec::new(),
+            prompt_injection: None,
+        }
+    }
+
+    /// Whether the policy has any rule
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether a server ID is on the allowlist
+    pub fn allows_server(&self, server_id: &str) -> bool {
+        self.allowed_servers
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|p| glob_match(p, server_id)))
+    }
+
+    /// Check a server about to be installed from `source`
+    pub fn check_install(
+        &self,
+        server_id: &str,
+        source: &InstallationSource,
+    ) -> Result<(), ManagedPolicyViolation> {
+        if self.disable_custom_servers && !matches!(source, InstallationSource::Registry) {
+            return Err(ManagedPolicyViolation::CustomServersDisabled(
+                server_id.to_string(),
+            ));
+        }
+        if !self.allows_server(server_id) {
+            return Err(ManagedPolicyViolation::ServerNotAllowed(
+                server_id.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check an installed server before enabling or connecting it
+    pub fn check_server(&self, server: &InstalledServer) -> Result<(), ManagedPolicyViolation> {
+        self.check_install(&server.server_id, &server.source)
+    }
+
+    /// The content filter its rules make up. Rules that don't compile are
+    /// dropped; [`Self::load_from`] has already refused them.
+    pub fn content_filter(&self) -> ContentFilter {
+        self.content_filters
+            .iter()
+            .filter_map(|rule| ContentRule::compile(rule).ok())
+            .fold(ContentFilter::default(), ContentFilter::with_rule)
+    }
+
+    /// The prompt-injection scanner, when enabled. Patterns that don't
+    /// compile disable it; [`Self::load_from`] has already refused them.
+    pub fn injection_scanner(&self) -> Option<InjectionScanner> {
+        InjectionScanner::new(self.prompt_injection.as_ref()?).ok()
+    }
+
+    /// Whether a feature may be exposed to clients
+    pub fn allows_feature(&self, feature: &ServerFeature) -> bool {
+        if !self.allows_server(&feature.server_id) {
+            return false;
+        }
+        let name = format!("{}/{}", feature.server_id, feature.feature_name);
+        if feature.feature_type == FeatureType::Tool
+            && self.forbidden_tools.iter().any(|t| t == &name)
+        {
+            return false;
+        }
+        !self.excluded_patterns.iter().any(|p| glob_match(p, &name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ManagedPolicy {
+        serde_json::from_str(
+            r#"{
+                "allowed_servers": ["github", "atlassian-*"],
+                "forbidden_tools": ["github/delete_repository"],
+                "excluded_patterns": ["*/drop_*"],
+                "disable_custom_servers": true
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_install_rules() {
+        let policy = policy();
+        assert!(policy.is_active());
+        assert!(!ManagedPolicy::default().is_active());
+
+        assert_eq!(
+            policy.check_install("atlassian-jira", &InstallationSource::Registry),
+            Ok(())
+        );
+        assert!(policy
+            .check_install("github", &InstallationSource::Registry)
+            .is_ok());
+        assert_eq!(
+            policy.check_install("slack", &InstallationSource::Registry),
+            Err(ManagedPolicyViolation::ServerNotAllowed(
+                "slack".to_string()
+            ))
+        );
+        assert_eq!(
+            policy.check_install("github", &InstallationSource::ManualEntry),
+            Err(ManagedPolicyViolation::CustomServersDisabled(
+                "github".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_feature_rules() {
+        let policy = policy();
+        let tool = |server: &str, name: &str| ServerFeature::tool("s", server, name);
+        assert!(policy.allows_feature(&tool("github", "list_issues")));
+        assert!(!policy.allows_feature(&tool("github", "delete_repository")));
+        assert!(!policy.allows_feature(&tool("atlassian-jira", "drop_project")));
+        assert!(!policy.allows_feature(&tool("slack", "post")));
+        // Forbidden tools are tools only; patterns cover every feature type
+        assert!(policy.allows_feature(&ServerFeature::prompt("s", "github", "delete_repository")));
+        assert!(!policy.allows_feature(&ServerFeature::prompt("s", "github", "drop_all")));
+
+        let locked = ManagedPolicy::deny_all();
+        assert!(!locked.allows_feature(&tool("mcpmux-builtin", "fetch")));
+        assert!(!locked.allows_server("github"));
+    }
+
+    #[test]
+    fn test_load_from() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MANAGED_POLICY_FILE);
+        assert_eq!(ManagedPolicy::load_from(&path).unwrap(), None);
+
+        std::fs::write(&path, r#"{"forbidden_tools": ["a/b"]}"#).unwrap();
+        let loaded = ManagedPolicy::load_from(&path).unwrap().unwrap();
+        assert_eq!(loaded.forbidden_tools, ["a/b"]);
+
+        std::fs::write(&path, r#"{"alowed_servers": []}"#).unwrap();
+        assert!(ManagedPolicy::load_from(&path).is_err());
+
+        std::fs::write(
+            &path,
+            r#"{"content_filters": [{"detector": "regex", "patterns": ["("], "action": "block"}]}"#,
+        )
+        .unwrap();
+        assert!(ManagedPolicy::load_from(&path).is_err());
+    }
+}
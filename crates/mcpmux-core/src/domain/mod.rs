@@ -1,42 +1,113 @@
 //! Domain entities, value objects, and events
 //!
 //! This module contains all domain-level types for McpMux:
-//! - Entities (Space, InstalledServer, FeatureSet, Client, etc.)
+//! - Entities (Space, InstalledServer, FeatureSet, Client, User, etc.)
 //! - Value Objects (ConnectionStatus, FeatureType, etc.)
 //! - Domain Events (DomainEvent enum for event-driven architecture)
 
 mod builtin;
+mod call_context;
 mod client;
 pub mod config;
+mod config_schema;
+mod config_template;
+mod content_filter;
 mod credential;
+mod dependencies;
+mod diagnosis;
+mod entry_signature;
+mod environment_profile;
 mod event;
 mod feature_set;
+mod injection_scan;
+mod input_values;
 mod installed_server;
+mod managed_policy;
 mod outbound_oauth_registration;
+mod path_policy;
+mod registry_source;
 mod server;
 mod server_feature;
+mod server_group;
 mod server_log;
 mod space;
+mod timeouts;
+mod tool_arguments;
+mod tool_hooks;
+mod user;
+mod wasm;
 mod workspace_binding;
 
 // Export event types first (ConnectionStatus is defined here)
-pub use event::{ConnectionStatus, DiscoveredCapabilities, DomainEvent, DomainEventEnvelope};
+pub use event::{
+    ConnectionStatus, DiscoveredCapabilities, DomainEvent, DomainEventEnvelope, EventFilter,
+    JournaledEvent,
+};
 
 // Export entities (installed_server re-exports ConnectionStatus from event)
 pub use builtin::{
-    builtin_server, builtin_servers, BuiltinServerDescriptor, BuiltinToolDescriptor,
-    TOOL_OPTIMIZATION_SERVER_ID,
+    builtin_server, builtin_servers, is_domain_allowed, parse_allowed_domains,
+    utility_server_definition, BuiltinServerDescriptor, BuiltinToolDescriptor,
+    FETCH_ALLOWED_DOMAINS_INPUT, TOOL_OPTIMIZATION_SERVER_ID, UTILITY_SERVER_ID,
 };
+pub use call_context::{CallContext, CallOrigin, InvalidCallContext, MAX_CALL_CONTEXT_FIELDS};
 pub use client::*;
 pub use config::*;
+pub use config_schema::{
+    validate_space_config, InvalidSpaceConfig, SpaceConfigIssue, USER_SPACE_SCHEMA,
+};
+pub use config_template::{expand_config_args, expand_config_template, uses_workspace};
+pub use content_filter::{
+    ContentDetector, ContentFilter, ContentFilterRule, ContentFinding, ContentRule,
+    CreditCardDetector, DetectorConfig, FilterAction, FilterOutcome, InvalidContentFilter,
+    RegexDetector, SsnDetector,
+};
 pub use credential::*;
+pub use dependencies::{startup_order, StartupOrder};
+pub use diagnosis::{diagnose_startup_failure, DiagnosedCause, FailureKind, STDERR_TAIL_LINES};
+pub use entry_signature::{
+    generate_publisher_key, sign_definition, signing_payload, validate_publisher_keys,
+    verify_definition, EntrySignature, PublisherKey, SignatureStatus,
+};
+pub use environment_profile::{
+    validate_profile_name, EnvironmentProfile, ProfileServerOverrides, MAX_PROFILE_NAME_LEN,
+};
 pub use feature_set::*;
+pub use injection_scan::{
+    injection_warning, InjectionAction, InjectionScanConfig, InjectionScanner, CUSTOM_HEURISTIC,
+};
+pub use input_values::{
+    check_input_values, merge_input_defaults, resolve_input_values, InputKind, InputValueError,
+    InvalidInputValues,
+};
 pub use installed_server::{InstallationSource, InstalledServer};
+pub use managed_policy::{
+    managed_policy_path, ManagedPolicy, ManagedPolicyViolation, MANAGED_POLICY_FILE,
+};
 pub use outbound_oauth_registration::*;
+pub use path_policy::{is_filesystem_server, PathPolicy, PathViolation};
+pub use registry_source::{
+    validate_registry_sources, RegistryAuth, RegistryAuthKind, RegistryFormat, RegistrySource,
+    RegistryTrust, MAX_REGISTRY_ID_LEN, OFFICIAL_REGISTRY_ID, OFFICIAL_REGISTRY_URL,
+    PUBLIC_REGISTRY_ID,
+};
 pub use server::*;
 pub use server_feature::*;
+pub use server_group::{validate_group_name, ServerGroup, MAX_GROUP_NAME_LEN};
 pub use server_log::*;
 pub use space::*;
+pub use timeouts::{
+    InvalidTimeout, ServerTimeouts, CONNECT_TIMEOUT_SECS, DEFAULT_CONNECT_TIMEOUT,
+    DEFAULT_REQUEST_TIMEOUT, REQUEST_TIMEOUT_SECS,
+};
+pub use tool_arguments::{
+    tool_input_schema, validate_tool_arguments, SchemaViolation, MAX_SCHEMA_VIOLATIONS,
+};
+pub use tool_hooks::{
+    HookStep, InvalidToolHook, ToolHook, ToolHookFailed, ToolHooks, MAX_SCRIPT_LEN, MAX_TOOL_HOOKS,
+};
+pub use user::{validate_user_name, User, UserRole, MAX_USER_NAME_LEN};
+pub use wasm::WasmPermissions;
 pub use workspace_binding::{
     longest_matching_base, normalize_workspace_root, path_is_within, validate_workspace_root,
     BindingType, WorkspaceBinding, WorkspaceRootValidation,
@@ -0,0 +1,309 @@
+//! Gateway-enforced filesystem path policy.
+//!
+//! Filesystem-type MCP servers (registry category `filesystem`) usually scope
+//! themselves to the directories passed on their command line — but that is
+//! the backend's own configuration, and a misconfigured or malicious server
+//! can read anything the user can. This policy lets the gateway enforce a
+//! second, independent boundary: tool arguments that look like paths are
+//! inspected before dispatch and the call is rejected when any of them falls
+//! outside the user-configured allowed roots.
+//!
+//! Matching is lexical. Paths are normalized with the same rules as workspace
+//! roots ([`normalize_workspace_root`]) and `.` / `..` segments are collapsed,
+//! so `/allowed/../etc/passwd` is caught. Symlinks inside an allowed root are
+//! not resolved.
+
+use serde_json::Value;
+use thiserror::Error;
+
+use super::workspace_binding::{normalize_workspace_root, path_is_within};
+use super::ServerDefinition;
+
+/// Registry categories that mark a server as a filesystem server.
+const FILESYSTEM_CATEGORIES: &[&str] = &["filesystem", "file-system"];
+
+/// Argument keys (lower-cased) that always carry a path.
+const PATH_KEYS: &[&str] = &[
+    "path",
+    "paths",
+    "file",
+    "files",
+    "filename",
+    "filepath",
+    "dir",
+    "directory",
+    "folder",
+    "root",
+    "cwd",
+    "source",
+    "src",
+    "destination",
+    "dest",
+    "target",
+];
+
+/// True when the server definition is tagged as a filesystem server.
+pub fn is_filesystem_server(definition: &ServerDefinition) -> bool {
+    definition.categories.iter().any(|c| {
+        FILESYSTEM_CATEGORIES
+            .iter()
+            .any(|fs| c.eq_ignore_ascii_case(fs))
+    })
+}
+
+/// A tool argument that points outside the allowed roots.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Path '{path}' in argument '{argument}' is outside the allowed roots for this server")]
+pub struct PathViolation {
+    /// JSON pointer-ish location of the offending argument (e.g. `paths[1]`)
+    pub argument: String,
+    /// The path as supplied by the client
+    pub path: String,
+}
+
+/// Set of allowed roots for one installed filesystem server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPolicy {
+    roots: Vec<String>,
+}
+
+impl PathPolicy {
+    /// Build a policy from user-entered roots. Entries that don't normalize to
+    /// an absolute path are dropped.
+    pub fn new<I, S>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let roots = roots
+            .into_iter()
+            .filter_map(|r| normalize_policy_path(&expand_home(r.as_ref().trim())))
+            .collect();
+        Self { roots }
+    }
+
+    /// Normalized allowed roots.
+    pub fn roots(&self) -> &[String] {
+        &self.roots
+    }
+
+    /// True when no usable root is configured (nothing to enforce).
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Check a single path. Relative paths are resolved against the first
+    /// allowed root, matching how filesystem servers treat them.
+    pub fn allows(&self, path: &str) -> bool {
+        let expanded = expand_home(path.trim());
+        let absolute = match normalize_policy_path(&expanded) {
+            Some(p) => p,
+            None => match self.roots.first() {
+                Some(base) => match normalize_policy_path(&join(base, &expanded)) {
+                    Some(p) => p,
+                    None => return false,
+                },
+                None => return false,
+            },
+        };
+        self.roots
+            .iter()
+            .any(|root| path_is_within(&absolute, root))
+    }
+
+    /// Inspect tool arguments and return the first path-like value outside
+    /// the allowed roots.
+    pub fn check_arguments(&self, arguments: &Value) -> Result<(), PathViolation> {
+        let mut found = Vec::new();
+        collect_paths(arguments, "", false, &mut found);
+        for (argument, path) in found {
+            if !self.allows(&path) {
+                return Err(PathViolation { argument, path });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// True when an argument key conventionally carries a filesystem path.
+fn is_path_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    PATH_KEYS.contains(&key.as_str())
+        || key.ends_with("path")
+        || key.ends_with("paths")
+        || key.ends_with("_dir")
+        || key.ends_with("directory")
+}
+
+/// Walk the argument tree collecting `(location, path)` pairs. Strings under
+/// a path-like key are paths; `file://` URIs are paths wherever they appear.
+fn collect_paths(value: &Value, location: &str, path_key: bool, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(s) => {
+            let is_uri = s
+                .get(..7)
+                .is_some_and(|p| p.eq_ignore_ascii_case("file://"));
+            if (path_key || is_uri) && !s.trim().is_empty() {
+                out.push((location.to_string(), s.clone()));
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_paths(item, &format!("{location}[{i}]"), path_key, out);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let child = if location.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{location}.{key}")
+                };
+                collect_paths(item, &child, is_path_key(key), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Expand a leading `~` to the user's home directory.
+fn expand_home(path: &str) -> String {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return path.to_string(),
+    };
+    match dirs::home_dir() {
+        Some(home) => format!("{}{}", home.display(), rest),
+        None => path.to_string(),
+    }
+}
+
+fn join(base: &str, relative: &str) -> String {
+    let sep = if base.contains('\\') { '\\' } else { '/' };
+    format!("{}{}{}", base.trim_end_matches(sep), sep, relative)
+}
+
+/// Normalize to the workspace-root form and collapse `.` / `..` segments.
+/// Returns `None` for relative paths.
+fn normalize_policy_path(path: &str) -> Option<String> {
+    let normalized = normalize_workspace_root(path);
+    let bytes = normalized.as_bytes();
+
+    // Split off the absolute prefix: `\\` (UNC), `c:\` (drive) or `/` (POSIX).
+    let (prefix, sep) = if normalized.starts_with("\\\\") {
+        ("\\\\", '\\')
+    } else if bytes.len() >= 3 && bytes[1] == b':' && bytes[2] == b'\\' {
+        (&normalized[..3], '\\')
+    } else if normalized.starts_with('/') {
+        ("/", '/')
+    } else {
+        return None;
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in normalized[prefix.len()..].split(sep) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    Some(format!("{}{}", prefix, segments.join(&sep.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy() -> PathPolicy {
+        PathPolicy::new(["/home/user/projects", "/tmp/scratch/"])
+    }
+
+    #[test]
+    fn allows_paths_inside_roots() {
+        let p = policy();
+        assert!(p.allows("/home/user/projects"));
+        assert!(p.allows("/home/user/projects/app/src/main.rs"));
+        assert!(p.allows("/tmp/scratch/out.txt"));
+    }
+
+    #[test]
+    fn rejects_paths_outside_roots() {
+        let p = policy();
+        assert!(!p.allows("/etc/passwd"));
+        assert!(!p.allows("/home/user/projects-other/x"));
+        assert!(!p.allows("/home/user"));
+    }
+
+    #[test]
+    fn collapses_parent_segments() {
+        let p = policy();
+        assert!(!p.allows("/home/user/projects/../.ssh/id_rsa"));
+        assert!(p.allows("/home/user/projects/a/../b"));
+    }
+
+    #[test]
+    fn resolves_relative_paths_against_first_root() {
+        let p = policy();
+        assert!(p.allows("src/lib.rs"));
+        assert!(!p.allows("../../.aws/credentials"));
+    }
+
+    #[test]
+    fn handles_file_uris_and_windows_paths() {
+        let p = PathPolicy::new(["C:\\Users\\me\\Work"]);
+        assert!(p.allows("c:/users/me/work/notes.md"));
+        assert!(p.allows("file:///C:/Users/me/Work/a.txt"));
+        assert!(!p.allows("C:\\Windows\\System32"));
+    }
+
+    #[test]
+    fn check_arguments_inspects_path_keys() {
+        let p = policy();
+        let ok = json!({ "path": "/home/user/projects/a.txt", "content": "/etc/passwd" });
+        assert!(p.check_arguments(&ok).is_ok(), "Non-path keys are ignored");
+
+        let bad = json!({
+            "paths": ["/home/user/projects/a", "/etc/shadow"],
+        });
+        let err = p.check_arguments(&bad).unwrap_err();
+        assert_eq!(err.argument, "paths[1]");
+        assert_eq!(err.path, "/etc/shadow");
+    }
+
+    #[test]
+    fn check_arguments_inspects_nested_and_uri_values() {
+        let p = policy();
+        let nested = json!({ "edits": [{ "sourcePath": "/var/log/syslog" }] });
+        assert_eq!(
+            p.check_arguments(&nested).unwrap_err().argument,
+            "edits[0].sourcePath"
+        );
+
+        let uri = json!({ "resource": "file:///etc/hosts" });
+        assert!(p.check_arguments(&uri).is_err());
+    }
+
+    #[test]
+    fn invalid_roots_are_dropped() {
+        let p = PathPolicy::new(["", "relative/dir"]);
+        assert!(p.is_empty());
+        assert!(!p.allows("/anything"));
+    }
+
+    #[test]
+    fn detects_filesystem_category() {
+        let mut def: ServerDefinition = serde_json::from_value(json!({
+            "id": "fs",
+            "name": "Filesystem",
+            "transport": { "type": "stdio", "command": "npx" }
+        }))
+        .unwrap();
+        assert!(!is_filesystem_server(&def));
+        def.categories = vec!["FileSystem".to_string()];
+        assert!(is_filesystem_server(&def));
+    }
+}
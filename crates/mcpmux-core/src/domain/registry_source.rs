@@ -0,0 +1,414 @@
+//! RegistrySource - a registry API that server definitions are loaded from
+//!
+//! McpMux can browse several registries at once, e.g. a company-internal
+//! registry next to the public one. Each source has:
+//! - a **priority**: when two registries define the same server ID, the one
+//!   with the higher priority wins;
+//! - an optional **namespace**: its server IDs become `<namespace>/<id>`, so
+//!   they never collide with other registries;
+//! - a **trust level** that decides the defaults its servers get on install;
+//! - whether its entries must be **signed** by a trusted publisher key (see
+//!   [`super::EntrySignature`]).
+//!
+//! A private registry may also need credentials ([`RegistryAuth`]). Those are
+//! secrets, so they live in the credential store rather than in settings.
+//!
+//! A `file://` URL makes a local registry: a folder of server definition
+//! JSON files (e.g. a git checkout of a team catalog) instead of an API.
+//! An HTTP registry either serves McpMux bundles, speaks the official MCP
+//! registry API, or is a third-party catalog (Smithery, mcp.run) whose
+//! entries are imported ([`RegistryFormat`]).
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// ID of the built-in public McpMux registry
+pub const PUBLIC_REGISTRY_ID: &str = "mcpmux";
+
+/// ID used for the official MCP registry when it's added
+pub const OFFICIAL_REGISTRY_ID: &str = "mcp-official";
+
+/// URL of the official MCP registry
+pub const OFFICIAL_REGISTRY_URL: &str = "https://registry.modelcontextprotocol.io";
+
+/// Longest allowed registry ID or namespace, in characters
+pub const MAX_REGISTRY_ID_LEN: usize = 32;
+
+/// How much a registry's servers are trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistryTrust {
+    /// Vetted servers (e.g. a company-internal registry): installed enabled
+    Trusted,
+    /// Installed disabled, the user enables them
+    #[default]
+    Standard,
+    /// Installed disabled, and only after the user confirms the install
+    Untrusted,
+}
+
+impl RegistryTrust {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Trusted => "trusted",
+            Self::Standard => "standard",
+            Self::Untrusted => "untrusted",
+        }
+    }
+
+    /// Whether servers from this registry start enabled once installed
+    pub fn enabled_on_install(&self) -> bool {
+        matches!(self, Self::Trusted)
+    }
+
+    /// Whether installing a server from this registry needs confirmation
+    pub fn requires_confirmation(&self) -> bool {
+        matches!(self, Self::Untrusted)
+    }
+}
+
+/// API an HTTP registry speaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistryFormat {
+    /// McpMux registry API (`/v1/bundle`)
+    #[default]
+    McpMux,
+    /// Official MCP registry API (`/v0/servers` of `server.json` entries)
+    Official,
+    /// Smithery registry API, for servers hosted by Smithery
+    Smithery,
+    /// mcp.run servlet catalog
+    McpRun,
+}
+
+/// Credentials sent to a private registry
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegistryAuth {
+    /// `Authorization: Bearer <token>`
+    Bearer { token: String },
+    /// HTTP basic auth
+    Basic { username: String, password: String },
+}
+
+/// Which kind of credentials a registry has, without the secrets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryAuthKind {
+    Bearer,
+    Basic,
+}
+
+impl RegistryAuth {
+    pub fn kind(&self) -> RegistryAuthKind {
+        match self {
+            Self::Bearer { .. } => RegistryAuthKind::Bearer,
+            Self::Basic { .. } => RegistryAuthKind::Basic,
+        }
+    }
+
+    /// Check that no field is empty
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Bearer { token } if token.trim().is_empty() => {
+                Err("Registry token cannot be empty".to_string())
+            }
+            Self::Basic { username, .. } if username.trim().is_empty() => {
+                Err("Registry username cannot be empty".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Keep secrets out of logs
+impl std::fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bearer { .. } => f.write_str("Bearer(***)"),
+            Self::Basic { username, .. } => write!(f, "Basic({}, ***)", username),
+        }
+    }
+}
+
+/// A configured registry API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrySource {
+    /// Stable identifier, also used for the bundle cache file
+    pub id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Base URL of the registry API (the bundle is fetched from `/v1/bundle`),
+    /// or the `file://` URL of a local folder of server definitions
+    pub url: String,
+
+    /// Higher wins when registries define the same server ID
+    #[serde(default)]
+    pub priority: i32,
+
+    /// Prefix for this registry's server IDs (`<namespace>/<id>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Defaults its servers get on install
+    #[serde(default)]
+    pub trust: RegistryTrust,
+
+    /// Hide entries that aren't signed by a trusted publisher key
+    #[serde(default)]
+    pub require_signatures: bool,
+
+    /// API the registry speaks (ignored for local folders)
+    #[serde(default)]
+    pub format: RegistryFormat,
+}
+
+impl RegistrySource {
+    /// The public McpMux registry at `url`
+    pub fn public(url: impl Into<String>) -> Self {
+        Self {
+            id: PUBLIC_REGISTRY_ID.to_string(),
+            name: "McpMux Registry".to_string(),
+            url: url.into(),
+            priority: 0,
+            namespace: None,
+            trust: RegistryTrust::Standard,
+            require_signatures: false,
+            format: RegistryFormat::McpMux,
+        }
+    }
+
+    /// The official MCP registry at registry.modelcontextprotocol.io
+    pub fn official() -> Self {
+        Self {
+            id: OFFICIAL_REGISTRY_ID.to_string(),
+            name: "MCP Registry".to_string(),
+            url: OFFICIAL_REGISTRY_URL.to_string(),
+            priority: -10,
+            namespace: None,
+            trust: RegistryTrust::Untrusted,
+            require_signatures: false,
+            format: RegistryFormat::Official,
+        }
+    }
+
+    /// The ID a server of this registry is listed under
+    pub fn server_id(&self, id: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}/{}", namespace, id),
+            None => id.to_string(),
+        }
+    }
+
+    /// Folder of a local registry, if the URL is a `file://` URL
+    pub fn local_dir(&self) -> Option<PathBuf> {
+        let path = self.url.strip_prefix("file://")?;
+        let path = urlencoding::decode(path).ok()?.into_owned();
+        // file:///C:/catalog -> C:/catalog
+        let bytes = path.as_bytes();
+        let path = if bytes.len() > 2
+            && bytes[0] == b'/'
+            && bytes[1].is_ascii_alphabetic()
+            && bytes[2] == b':'
+        {
+            path[1..].to_string()
+        } else {
+            path
+        };
+        Some(PathBuf::from(path))
+    }
+
+    /// Check the ID, namespace and URL
+    pub fn validate(&self) -> Result<(), String> {
+        validate_slug("Registry ID", &self.id)?;
+        if let Some(namespace) = &self.namespace {
+            validate_slug("Namespace", namespace)?;
+        }
+        if self.name.trim().is_empty() {
+            return Err("Registry name cannot be empty".to_string());
+        }
+        if let Some(dir) = self.local_dir() {
+            if !dir.is_absolute() {
+                return Err(format!(
+                    "Local registry folder must be an absolute path: {}",
+                    self.url
+                ));
+            }
+        } else if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            return Err(format!(
+                "Registry URL must start with http://, https:// or file://: {}",
+                self.url
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Check a configured registry list: each source is valid, and no two share
+/// an ID or a namespace.
+pub fn validate_registry_sources(sources: &[RegistrySource]) -> Result<(), String> {
+    for (i, source) in sources.iter().enumerate() {
+        source.validate()?;
+        let earlier = &sources[..i];
+        if earlier.iter().any(|s| s.id == source.id) {
+            return Err(format!("Duplicate registry ID: {}", source.id));
+        }
+        if source.namespace.is_some() && earlier.iter().any(|s| s.namespace == source.namespace) {
+            return Err(format!(
+                "Namespace '{}' is used by more than one registry",
+                source.namespace.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_slug(what: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("{} cannot be empty", what));
+    }
+    if value.chars().count() > MAX_REGISTRY_ID_LEN {
+        return Err(format!(
+            "{} must be at most {} characters",
+            what, MAX_REGISTRY_ID_LEN
+        ));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(format!(
+            "{} may only contain lowercase letters, digits and '-': {}",
+            what, value
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn internal() -> RegistrySource {
+        RegistrySource {
+            id: "acme".to_string(),
+            name: "Acme Internal".to_string(),
+            url: "https://registry.acme.internal".to_string(),
+            priority: 10,
+            namespace: Some("acme".to_string()),
+            trust: RegistryTrust::Trusted,
+            require_signatures: false,
+            format: RegistryFormat::McpMux,
+        }
+    }
+
+    #[test]
+    fn test_server_id_is_namespaced() {
+        assert_eq!(internal().server_id("github"), "acme/github");
+        assert_eq!(
+            RegistrySource::public("https://api.mcpmux.com").server_id("github"),
+            "github"
+        );
+    }
+
+    #[test]
+    fn test_trust_defaults() {
+        assert!(RegistryTrust::Trusted.enabled_on_install());
+        assert!(!RegistryTrust::Standard.enabled_on_install());
+        assert!(!RegistryTrust::Untrusted.enabled_on_install());
+        assert!(RegistryTrust::Untrusted.requires_confirmation());
+        assert!(!RegistryTrust::Standard.requires_confirmation());
+    }
+
+    #[test]
+    fn test_deserialize_defaults() {
+        let source: RegistrySource = serde_json::from_str(
+            r#"{"id":"extra","name":"Extra","url":"https://extra.example.com"}"#,
+        )
+        .unwrap();
+        assert_eq!(source.priority, 0);
+        assert_eq!(source.namespace, None);
+        assert_eq!(source.trust, RegistryTrust::Standard);
+        assert!(!source.require_signatures);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(internal().validate().is_ok());
+
+        let mut bad = internal();
+        bad.id = "Acme Corp".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = internal();
+        bad.url = "ftp://registry.acme.internal".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = internal();
+        bad.namespace = Some(String::new());
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_local_dir() {
+        let mut local = internal();
+        local.url = "file:///srv/team%20catalog".to_string();
+        assert_eq!(local.local_dir(), Some(PathBuf::from("/srv/team catalog")));
+        assert_eq!(internal().local_dir(), None);
+
+        local.url = "file:///C:/catalog".to_string();
+        assert_eq!(local.local_dir(), Some(PathBuf::from("C:/catalog")));
+
+        #[cfg(unix)]
+        {
+            local.url = "file:///srv/catalog".to_string();
+            assert!(local.validate().is_ok());
+            local.url = "file://catalog".to_string();
+            assert!(local.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_auth_debug_hides_secrets() {
+        let bearer = RegistryAuth::Bearer {
+            token: "s3cret".to_string(),
+        };
+        let basic = RegistryAuth::Basic {
+            username: "ci".to_string(),
+            password: "s3cret".to_string(),
+        };
+        assert!(!format!("{:?}", bearer).contains("s3cret"));
+        assert!(!format!("{:?}", basic).contains("s3cret"));
+        assert_eq!(basic.kind(), RegistryAuthKind::Basic);
+    }
+
+    #[test]
+    fn test_auth_validate() {
+        assert!(RegistryAuth::Bearer {
+            token: " ".to_string()
+        }
+        .validate()
+        .is_err());
+        assert!(RegistryAuth::Basic {
+            username: "ci".to_string(),
+            password: String::new(),
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicates() {
+        let public = RegistrySource::public("https://api.mcpmux.com");
+        assert!(validate_registry_sources(&[public.clone(), internal()]).is_ok());
+        assert!(validate_registry_sources(&[public.clone(), public.clone()]).is_err());
+
+        let mut other = internal();
+        other.id = "acme-2".to_string();
+        assert!(validate_registry_sources(&[internal(), other]).is_err());
+    }
+}
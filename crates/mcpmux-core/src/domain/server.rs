@@ -2,8 +2,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::{EntrySignature, RegistryTrust, ServerTimeouts, SignatureStatus, WasmPermissions};
+
 /// The canonical internal representation for ALL servers (Unified Runtime Model).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ServerDefinition {
     /// Unique identifier (e.g., "com.anthropic.github")
     pub id: String,
@@ -65,6 +68,22 @@ pub struct ServerDefinition {
 
     /// Changelog URL (v2.1)
     pub changelog_url: Option<String>,
+
+    /// Connect and request timeouts the server needs, if not the defaults
+    #[serde(default, skip_serializing_if = "ServerTimeouts::is_empty")]
+    pub timeouts: ServerTimeouts,
+
+    /// IDs of servers in the same Space that must be connected before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Publisher signature over the ID and transport
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<EntrySignature>,
+
+    /// Result of checking `signature`, set on discovery
+    #[serde(default, skip_serializing_if = "SignatureStatus::is_unsigned")]
+    pub signature_status: SignatureStatus,
     // NOTE: Runtime state like 'enabled' is NOT stored here.
     // It is injected at the application layer by merging with DB state.
 }
@@ -77,6 +96,7 @@ impl ServerDefinition {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum ServerSource {
     /// Loaded from a user-defined JSON file in the spaces directory
     UserSpace {
@@ -87,11 +107,28 @@ pub enum ServerSource {
     #[default]
     Bundled,
     /// Loaded from a remote or custom registry (API, NPM, etc.)
-    Registry { url: String, name: String },
+    Registry {
+        url: String,
+        name: String,
+        #[serde(default)]
+        trust: RegistryTrust,
+    },
+}
+
+impl ServerSource {
+    /// Trust level of the registry this server came from. User-configured
+    /// and bundled servers count as standard.
+    pub fn trust(&self) -> RegistryTrust {
+        match self {
+            Self::Registry { trust, .. } => *trust,
+            _ => RegistryTrust::Standard,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum TransportType {
     Stdio,
     Http,
@@ -99,6 +136,7 @@ pub enum TransportType {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum TransportConfig {
     Stdio {
         command: String,
@@ -113,6 +151,71 @@ pub enum TransportConfig {
         url: String,
         #[serde(default)]
         headers: HashMap<String, String>,
+        /// Query parameters added to the URL of every request
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        query: HashMap<String, String>,
+        #[serde(default)]
+        metadata: TransportMetadata,
+    },
+    /// A REST API described by an OpenAPI document, bridged to MCP tools by
+    /// the gateway (one tool per operation)
+    #[serde(rename = "openapi")]
+    OpenApi {
+        /// URL (or local path) of the OpenAPI 3 document, in JSON
+        spec_url: String,
+        /// API base URL; defaults to the document's first `servers` entry
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+        /// Headers sent with every request, e.g. credentials
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        /// Query parameters added to every request
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        query: HashMap<String, String>,
+        /// Operation IDs to expose; all operations if empty
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        operations: Vec<String>,
+        #[serde(default)]
+        metadata: TransportMetadata,
+    },
+    /// The built-in utility server, run inside the gateway (see
+    /// [`crate::utility_server_definition`])
+    Builtin {
+        /// Domains the `fetch` tool may reach, subdomains included; entries
+        /// may list several, separated by commas or whitespace
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_domains: Vec<String>,
+        #[serde(default)]
+        metadata: TransportMetadata,
+    },
+    /// A GraphQL API, bridged to MCP tools by the gateway (one tool per root
+    /// query or mutation field)
+    #[serde(rename = "graphql")]
+    GraphQl {
+        /// GraphQL endpoint; its schema is read by introspection
+        endpoint: String,
+        /// Headers sent with every request, e.g. credentials
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        /// Root fields to expose; all queries and mutations if empty
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        operations: Vec<String>,
+        #[serde(default)]
+        metadata: TransportMetadata,
+    },
+    /// An MCP server compiled to WASI, run sandboxed over stdio (see
+    /// [`crate::WasmPermissions`])
+    Wasm {
+        /// Path of the `.wasm` module
+        module: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// The only environment variables the module sees
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        env: HashMap<String, String>,
+        /// Host access the module needs; each must be granted by the user
+        #[serde(default, skip_serializing_if = "WasmPermissions::is_empty")]
+        permissions: WasmPermissions,
         #[serde(default)]
         metadata: TransportMetadata,
     },
@@ -124,6 +227,22 @@ impl TransportConfig {
         match self {
             TransportConfig::Stdio { metadata, .. } => metadata,
             TransportConfig::Http { metadata, .. } => metadata,
+            TransportConfig::OpenApi { metadata, .. } => metadata,
+            TransportConfig::GraphQl { metadata, .. } => metadata,
+            TransportConfig::Builtin { metadata, .. } => metadata,
+            TransportConfig::Wasm { metadata, .. } => metadata,
+        }
+    }
+
+    /// Mutable metadata for this transport
+    pub fn metadata_mut(&mut self) -> &mut TransportMetadata {
+        match self {
+            TransportConfig::Stdio { metadata, .. } => metadata,
+            TransportConfig::Http { metadata, .. } => metadata,
+            TransportConfig::OpenApi { metadata, .. } => metadata,
+            TransportConfig::GraphQl { metadata, .. } => metadata,
+            TransportConfig::Builtin { metadata, .. } => metadata,
+            TransportConfig::Wasm { metadata, .. } => metadata,
         }
     }
 }
@@ -136,6 +255,7 @@ pub struct TransportMetadata {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct InputDefinition {
     pub id: String,
     pub label: String,
@@ -152,14 +272,78 @@ pub struct InputDefinition {
     // Additional helpful metadata for acquiring credentials
     pub obtain_url: Option<String>,
     pub obtain_instructions: Option<String>,
+
+    // Validation, checked before the server is started (see `resolve_input_values`)
+    /// Allowed values for a `select`/`enum` input
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<InputOption>,
+    /// Regex the whole value must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Message shown instead of the pattern when it doesn't match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_error: Option<String>,
+    /// Whether the value may span several lines (PEM keys, JSON credentials)
+    #[serde(default)]
+    pub multiline: bool,
 }
 
 fn default_input_type() -> String {
     "text".to_string()
 }
 
+/// One allowed value of a `select`/`enum` input.
+///
+/// Definitions may list plain strings (`["info", "debug"]`) or objects with a
+/// display label; both are stored as objects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "InputOptionRepr")]
+#[non_exhaustive]
+pub struct InputOption {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InputOptionRepr {
+    Value(String),
+    Full {
+        value: String,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+    },
+}
+
+impl From<InputOptionRepr> for InputOption {
+    fn from(repr: InputOptionRepr) -> Self {
+        match repr {
+            InputOptionRepr::Value(value) => Self {
+                value,
+                label: None,
+                description: None,
+            },
+            InputOptionRepr::Full {
+                value,
+                label,
+                description,
+            } => Self {
+                value,
+                label,
+                description,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum AuthConfig {
     None,
     ApiKey { instructions: Option<String> },
@@ -1,13 +1,18 @@
 //! ServerFeature entity - discovered MCP features from servers
 
+use std::collections::{HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::tool_input_schema;
+
 /// Type of MCP feature
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
+#[non_exhaustive]
 pub enum FeatureType {
     /// Tool that can be invoked
     #[default]
@@ -37,11 +42,93 @@ impl FeatureType {
     }
 }
 
+/// A behavior hint from a tool's MCP annotations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationHint {
+    /// Doesn't modify its environment (`readOnlyHint`)
+    ReadOnly,
+    /// May make destructive updates (`destructiveHint`)
+    Destructive,
+    /// Repeated calls with the same arguments have no further effect
+    /// (`idempotentHint`)
+    Idempotent,
+    /// Reaches outside systems such as the web (`openWorldHint`)
+    OpenWorld,
+}
+
+impl AnnotationHint {
+    pub const ALL: [AnnotationHint; 4] = [
+        Self::ReadOnly,
+        Self::Destructive,
+        Self::Idempotent,
+        Self::OpenWorld,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Destructive => "destructive",
+            Self::Idempotent => "idempotent",
+            Self::OpenWorld => "open_world",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|hint| hint.as_str() == s)
+    }
+}
+
+/// A tool's annotations as the server reported them. Unset hints take the
+/// MCP defaults: not read-only, destructive, not idempotent, open world.
+/// Servers set these themselves, so they describe a tool rather than
+/// guarantee its behavior.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolAnnotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world: Option<bool>,
+}
+
+impl ToolAnnotations {
+    /// Read the `annotations` of a tool's raw JSON
+    pub fn from_raw_json(raw_json: Option<&serde_json::Value>) -> Self {
+        let Some(annotations) = raw_json.and_then(|json| json.get("annotations")) else {
+            return Self::default();
+        };
+        let hint = |key: &str| annotations.get(key).and_then(serde_json::Value::as_bool);
+        Self {
+            read_only: hint("readOnlyHint"),
+            destructive: hint("destructiveHint"),
+            idempotent: hint("idempotentHint"),
+            open_world: hint("openWorldHint"),
+        }
+    }
+
+    /// Whether the hint holds, with MCP defaults for unset hints. A
+    /// read-only tool is never destructive.
+    pub fn has(&self, hint: AnnotationHint) -> bool {
+        let read_only = self.read_only.unwrap_or(false);
+        match hint {
+            AnnotationHint::ReadOnly => read_only,
+            AnnotationHint::Destructive => !read_only && self.destructive.unwrap_or(true),
+            AnnotationHint::Idempotent => self.idempotent.unwrap_or(false),
+            AnnotationHint::OpenWorld => self.open_world.unwrap_or(true),
+        }
+    }
+}
+
 /// A discovered feature from an MCP server
 ///
 /// Features are discovered when a server is connected and reports
 /// its available tools, prompts, and resources.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ServerFeature {
     /// Unique ID for this feature record
     pub id: Uuid,
@@ -73,6 +160,10 @@ pub struct ServerFeature {
     /// forward-compatible with any MCP protocol changes
     pub raw_json: Option<serde_json::Value>,
 
+    /// Tool annotations, read from `raw_json`
+    #[serde(default)]
+    pub annotations: ToolAnnotations,
+
     /// When this feature was first discovered
     pub discovered_at: DateTime<Utc>,
 
@@ -81,6 +172,15 @@ pub struct ServerFeature {
 
     /// Whether this feature is currently available
     pub is_available: bool,
+
+    /// Clients keep this tool's schema when the server changes it, until
+    /// the change is accepted
+    #[serde(default)]
+    pub schema_pinned: bool,
+
+    /// The changed raw JSON of a pinned tool, held back from clients
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_raw_json: Option<serde_json::Value>,
 }
 
 impl ServerFeature {
@@ -102,9 +202,12 @@ impl ServerFeature {
             display_name: None,
             description: None,
             raw_json: None,
+            annotations: ToolAnnotations::default(),
             discovered_at: now,
             last_seen_at: now,
             is_available: true,
+            schema_pinned: false,
+            pending_raw_json: None,
         }
     }
 
@@ -147,12 +250,24 @@ impl ServerFeature {
         self
     }
 
-    /// Set raw JSON (complete backend feature object)
+    /// Set raw JSON (complete backend feature object) and the annotations
+    /// in it
     pub fn with_raw_json(mut self, json: serde_json::Value) -> Self {
+        self.annotations = ToolAnnotations::from_raw_json(Some(&json));
         self.raw_json = Some(json);
         self
     }
 
+    /// Whether this is a tool whose annotations have `hint`
+    pub fn has_hint(&self, hint: AnnotationHint) -> bool {
+        self.feature_type == FeatureType::Tool && self.annotations.has(hint)
+    }
+
+    /// The `inputSchema` in the raw JSON
+    pub fn input_schema(&self) -> Option<&serde_json::Value> {
+        self.raw_json.as_ref().and_then(tool_input_schema)
+    }
+
     /// Set server alias
     pub fn with_server_alias(mut self, alias: Option<String>) -> Self {
         self.server_alias = alias;
@@ -194,8 +309,14 @@ impl ServerFeature {
     /// Resources don't need prefixing because URIs have built-in namespacing via their scheme.
     ///
     /// Uses underscore separator for maximum client compatibility (Cursor only allows [a-z0-9_-])
+    ///
+    /// An empty alias means the name is already qualified (a feature of a
+    /// federated McpMux gateway) and is used as is.
     pub fn qualified_name(&self) -> String {
         match self.feature_type {
+            FeatureType::Tool | FeatureType::Prompt if self.prefix().is_empty() => {
+                self.feature_name.clone()
+            }
             FeatureType::Tool | FeatureType::Prompt => {
                 // Tools and prompts need prefixing for disambiguation
                 // Use underscore separator for Cursor compatibility
@@ -219,6 +340,212 @@ impl ServerFeature {
     }
 }
 
+/// How a tool's input schema changed between two discoveries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolSchemaChange {
+    pub tool: String,
+    /// Top-level parameters the new schema adds
+    pub added: Vec<String>,
+    /// Top-level parameters the new schema drops
+    pub removed: Vec<String>,
+    /// Whether calls that fit the old schema may not fit the new one: a
+    /// parameter was dropped, became required or changed type
+    pub breaking: bool,
+    /// Whether the tool is pinned, so clients keep the old schema
+    pub held: bool,
+}
+
+impl ToolSchemaChange {
+    pub fn between(
+        tool: &str,
+        old: Option<&serde_json::Value>,
+        new: Option<&serde_json::Value>,
+        held: bool,
+    ) -> Self {
+        fn properties(schema: Option<&serde_json::Value>) -> Vec<(&str, &serde_json::Value)> {
+            schema
+                .and_then(|s| s.get("properties"))
+                .and_then(serde_json::Value::as_object)
+                .map(|p| p.iter().map(|(k, v)| (k.as_str(), v)).collect())
+                .unwrap_or_default()
+        }
+        fn required(schema: Option<&serde_json::Value>) -> HashSet<&str> {
+            schema
+                .and_then(|s| s.get("required"))
+                .and_then(serde_json::Value::as_array)
+                .map(|r| r.iter().filter_map(serde_json::Value::as_str).collect())
+                .unwrap_or_default()
+        }
+
+        let old_properties: HashMap<&str, &serde_json::Value> =
+            properties(old).into_iter().collect();
+        let new_properties: HashMap<&str, &serde_json::Value> =
+            properties(new).into_iter().collect();
+        let mut added: Vec<String> = new_properties
+            .keys()
+            .filter(|k| !old_properties.contains_key(*k))
+            .map(|k| k.to_string())
+            .collect();
+        added.sort();
+        let mut removed: Vec<String> = old_properties
+            .keys()
+            .filter(|k| !new_properties.contains_key(*k))
+            .map(|k| k.to_string())
+            .collect();
+        removed.sort();
+        let old_required = required(old);
+        let newly_required = required(new).into_iter().any(|k| !old_required.contains(k));
+        let retyped = old_properties.iter().any(|(k, old)| {
+            new_properties
+                .get(k)
+                .is_some_and(|new| old.get("type") != new.get("type"))
+        });
+        Self {
+            tool: tool.to_string(),
+            breaking: !removed.is_empty() || newly_required || retyped,
+            added,
+            removed,
+            held,
+        }
+    }
+}
+
+/// What changed between the features cached for a server and the ones it
+/// just reported
+///
+/// Features are matched by type and name. The store never clears a field a
+/// server stops sending, so a missing display name, description or raw JSON
+/// does not count as a change.
+///
+/// A pinned tool whose input schema changed keeps its cached JSON; the new
+/// JSON is held in `pending_raw_json` until the user accepts it.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureDiff {
+    /// New features, and cached ones that were unavailable and are back
+    pub added: Vec<ServerFeature>,
+    /// Features whose display name, description or raw JSON changed
+    pub updated: Vec<ServerFeature>,
+    /// Cached available features the server no longer reports
+    pub removed: Vec<ServerFeature>,
+    /// Pinned tools whose pending JSON changed; clients see no difference
+    pub held: Vec<ServerFeature>,
+    /// Tools whose input schema changed, pinned or not
+    pub schema_changes: Vec<ToolSchemaChange>,
+}
+
+impl FeatureDiff {
+    /// Diff the features of one server against its cached features
+    pub fn between(cached: &[ServerFeature], discovered: &[ServerFeature]) -> Self {
+        let cached_by_key: HashMap<(&FeatureType, &str), &ServerFeature> = cached
+            .iter()
+            .map(|f| ((&f.feature_type, f.feature_name.as_str()), f))
+            .collect();
+        let mut diff = Self::default();
+
+        for feature in discovered {
+            let Some(old) =
+                cached_by_key.get(&(&feature.feature_type, feature.feature_name.as_str()))
+            else {
+                diff.added.push(feature.clone());
+                continue;
+            };
+            let feature = diff.pin(old, feature);
+            if !old.is_available {
+                diff.added.push(feature);
+            } else if Self::details_changed(old, &feature) {
+                diff.updated.push(feature);
+            } else if feature.pending_raw_json != old.pending_raw_json {
+                diff.held.push(feature);
+            }
+        }
+
+        let discovered_keys: HashSet<(&FeatureType, &str)> = discovered
+            .iter()
+            .map(|f| (&f.feature_type, f.feature_name.as_str()))
+            .collect();
+        diff.removed = cached
+            .iter()
+            .filter(|f| f.is_available)
+            .filter(|f| !discovered_keys.contains(&(&f.feature_type, f.feature_name.as_str())))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /// `feature` as it should be cached given the pin of `old`, recording
+    /// a change of its input schema
+    fn pin(&mut self, old: &ServerFeature, feature: &ServerFeature) -> ServerFeature {
+        let schema_changed = feature.feature_type == FeatureType::Tool
+            && feature.raw_json.is_some()
+            && old.input_schema() != feature.input_schema();
+        if !old.schema_pinned {
+            if schema_changed {
+                self.schema_changes.push(ToolSchemaChange::between(
+                    &feature.feature_name,
+                    old.input_schema(),
+                    feature.input_schema(),
+                    false,
+                ));
+            }
+            return feature.clone();
+        }
+
+        if !schema_changed {
+            // Back to the pinned schema: nothing is pending any more
+            return ServerFeature {
+                schema_pinned: true,
+                pending_raw_json: None,
+                ..feature.clone()
+            };
+        }
+        if old.pending_raw_json != feature.raw_json {
+            self.schema_changes.push(ToolSchemaChange::between(
+                &feature.feature_name,
+                old.input_schema(),
+                feature.input_schema(),
+                true,
+            ));
+        }
+        ServerFeature {
+            pending_raw_json: feature.raw_json.clone(),
+            last_seen_at: feature.last_seen_at,
+            is_available: true,
+            ..old.clone()
+        }
+    }
+
+    fn details_changed(old: &ServerFeature, new: &ServerFeature) -> bool {
+        fn differs<T: PartialEq>(old: &Option<T>, new: &Option<T>) -> bool {
+            new.is_some() && new != old
+        }
+        differs(&old.display_name, &new.display_name)
+            || differs(&old.description, &new.description)
+            || differs(&old.raw_json, &new.raw_json)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.held.is_empty()
+    }
+
+    /// Whether clients see a difference; held schemas aren't one
+    pub fn is_visible(&self) -> bool {
+        !(self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty())
+    }
+
+    /// Whether anything of `feature_type` was added, updated or removed
+    pub fn touches(&self, feature_type: FeatureType) -> bool {
+        self.added
+            .iter()
+            .chain(&self.updated)
+            .chain(&self.removed)
+            .any(|f| f.feature_type == feature_type)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +564,31 @@ mod tests {
         assert!(feature.is_available);
     }
 
+    #[test]
+    fn test_annotations() {
+        let feature = ServerFeature::tool("space_1", "github", "get_issue").with_raw_json(
+            serde_json::json!({ "name": "get_issue", "annotations": { "readOnlyHint": true } }),
+        );
+        assert_eq!(feature.annotations.read_only, Some(true));
+        assert!(feature.has_hint(AnnotationHint::ReadOnly));
+        assert!(!feature.has_hint(AnnotationHint::Destructive));
+        assert!(feature.has_hint(AnnotationHint::OpenWorld));
+
+        // Unannotated tools get the MCP defaults
+        let bare = ServerFeature::tool("space_1", "github", "delete_repo");
+        assert!(bare.has_hint(AnnotationHint::Destructive));
+        assert!(!bare.has_hint(AnnotationHint::Idempotent));
+        assert!(
+            !ServerFeature::prompt("space_1", "github", "p").has_hint(AnnotationHint::Destructive)
+        );
+
+        assert_eq!(
+            AnnotationHint::parse("open_world"),
+            Some(AnnotationHint::OpenWorld)
+        );
+        assert_eq!(AnnotationHint::parse("openWorldHint"), None);
+    }
+
     #[test]
     fn test_unique_key() {
         let feature = ServerFeature::tool("space_1", "com.cloudflare/docs-mcp", "search_docs");
@@ -246,4 +598,107 @@ mod tests {
             "space_1:com.cloudflare/docs-mcp:tool:search_docs"
         );
     }
+
+    #[test]
+    fn test_qualified_name_with_empty_alias_is_unprefixed() {
+        let feature = ServerFeature::tool("space_1", "team-gateway", "gh_get_me");
+        assert_eq!(feature.qualified_name(), "team-gateway_gh_get_me");
+
+        let feature = feature.with_server_alias(Some(String::new()));
+        assert_eq!(feature.qualified_name(), "gh_get_me");
+    }
+
+    #[test]
+    fn test_feature_diff() {
+        let search = ServerFeature::tool("space_1", "docs", "search").with_description("Search");
+        let mut fetch = ServerFeature::tool("space_1", "docs", "fetch");
+        fetch.mark_unavailable();
+        let summarize = ServerFeature::prompt("space_1", "docs", "summarize");
+        let cached = vec![search.clone(), fetch.clone(), summarize];
+
+        // Same features, one without its description: nothing to write
+        let unchanged = vec![
+            ServerFeature::tool("space_1", "docs", "search"),
+            ServerFeature::prompt("space_1", "docs", "summarize"),
+        ];
+        assert!(FeatureDiff::between(&cached, &unchanged).is_empty());
+
+        let discovered = vec![
+            search.with_description("Search the docs"),
+            ServerFeature::tool("space_1", "docs", "fetch"),
+            ServerFeature::resource("space_1", "docs", "docs://index"),
+        ];
+        let diff = FeatureDiff::between(&cached, &discovered);
+        let names = |features: &[ServerFeature]| -> Vec<String> {
+            features.iter().map(|f| f.feature_name.clone()).collect()
+        };
+        assert_eq!(names(&diff.added), vec!["fetch", "docs://index"]);
+        assert_eq!(names(&diff.updated), vec!["search"]);
+        assert_eq!(names(&diff.removed), vec!["summarize"]);
+        assert!(diff.touches(FeatureType::Tool));
+        assert!(diff.touches(FeatureType::Prompt));
+        assert!(diff.touches(FeatureType::Resource));
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let tool = |schema: serde_json::Value| {
+            ServerFeature::tool("space_1", "github", "search")
+                .with_raw_json(serde_json::json!({ "name": "search", "inputSchema": schema }))
+        };
+        let v1 = tool(serde_json::json!({
+            "type": "object",
+            "properties": { "query": { "type": "string" }, "page": { "type": "integer" } }
+        }));
+        let v2 = tool(serde_json::json!({
+            "type": "object",
+            "properties": { "query": { "type": "string" }, "repo": { "type": "string" } },
+            "required": ["repo"]
+        }));
+
+        // Unpinned: the new schema goes through
+        let diff = FeatureDiff::between(std::slice::from_ref(&v1), std::slice::from_ref(&v2));
+        assert_eq!(diff.updated.len(), 1);
+        assert_eq!(
+            diff.schema_changes,
+            [ToolSchemaChange {
+                tool: "search".to_string(),
+                added: vec!["repo".to_string()],
+                removed: vec!["page".to_string()],
+                breaking: true,
+                held: false,
+            }]
+        );
+
+        // Pinned: clients keep v1 and v2 waits
+        let mut pinned = v1.clone();
+        pinned.schema_pinned = true;
+        let diff = FeatureDiff::between(std::slice::from_ref(&pinned), std::slice::from_ref(&v2));
+        assert!(!diff.is_visible() && !diff.touches(FeatureType::Tool));
+        assert!(diff.schema_changes[0].held);
+        let held = &diff.held[0];
+        assert_eq!(held.raw_json, v1.raw_json);
+        assert_eq!(held.pending_raw_json, v2.raw_json);
+
+        // Seeing v2 again changes nothing; going back to v1 drops it
+        let diff = FeatureDiff::between(std::slice::from_ref(held), std::slice::from_ref(&v2));
+        assert!(diff.is_empty() && diff.schema_changes.is_empty());
+        let diff = FeatureDiff::between(std::slice::from_ref(held), std::slice::from_ref(&v1));
+        assert_eq!(diff.held[0].pending_raw_json, None);
+        assert!(diff.held[0].schema_pinned);
+
+        // An optional parameter is not breaking
+        let v3 = tool(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "page": { "type": "integer" },
+                "sort": { "type": "string" }
+            }
+        }));
+        let change =
+            ToolSchemaChange::between("search", v1.input_schema(), v3.input_schema(), false);
+        assert_eq!(change.added, ["sort"]);
+        assert!(!change.breaking);
+    }
 }
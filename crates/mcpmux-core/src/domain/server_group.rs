@@ -0,0 +1,124 @@
+//! ServerGroup entity - user-defined folders of installed servers
+//!
+//! Groups ("AWS", "Personal") organise the servers installed in a Space and
+//! let the user enable, disable or reconnect all of them at once. A group can
+//! also own a FeatureSet that includes every feature of its servers.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest allowed group name, in characters
+pub const MAX_GROUP_NAME_LEN: usize = 64;
+
+/// A named group of servers installed in one Space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerGroup {
+    /// Unique identifier
+    pub id: String,
+
+    /// The space this group belongs to
+    pub space_id: String,
+
+    /// Human-readable name, unique within the space
+    pub name: String,
+
+    /// Optional emoji or icon URL
+    pub icon: Option<String>,
+
+    /// Registry IDs of the grouped servers, in display order
+    #[serde(default)]
+    pub server_ids: Vec<String>,
+
+    /// FeatureSet with every feature of the grouped servers, once created
+    pub feature_set_id: Option<String>,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ServerGroup {
+    /// Create an empty group in a space
+    pub fn new(name: impl Into<String>, space_id: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            space_id: space_id.into(),
+            name: name.into(),
+            icon: None,
+            server_ids: Vec::new(),
+            feature_set_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Add icon
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Whether a server is in this group
+    pub fn contains(&self, server_id: &str) -> bool {
+        self.server_ids.iter().any(|id| id == server_id)
+    }
+
+    /// Replace the grouped servers, dropping duplicates and keeping the
+    /// first occurrence's position
+    pub fn set_servers(&mut self, server_ids: impl IntoIterator<Item = String>) {
+        self.server_ids.clear();
+        for id in server_ids {
+            if !self.contains(&id) {
+                self.server_ids.push(id);
+            }
+        }
+    }
+}
+
+/// Check a group name, returning it trimmed
+pub fn validate_group_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Group name is required".to_string());
+    }
+    if name.chars().count() > MAX_GROUP_NAME_LEN {
+        return Err(format!(
+            "Group name must be at most {} characters",
+            MAX_GROUP_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_servers_drops_duplicates() {
+        let mut group = ServerGroup::new("AWS", "space_1").with_icon("☁️");
+        group.set_servers(
+            ["aws-docs", "aws-cdk", "aws-docs", "aws-cost"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        assert_eq!(group.server_ids, vec!["aws-docs", "aws-cdk", "aws-cost"]);
+        assert!(group.contains("aws-cdk"));
+        assert!(!group.contains("github"));
+        assert!(group.feature_set_id.is_none());
+    }
+
+    #[test]
+    fn test_validate_group_name() {
+        assert_eq!(
+            validate_group_name("  Personal "),
+            Ok("Personal".to_string())
+        );
+        assert!(validate_group_name("   ").is_err());
+        assert!(validate_group_name(&"x".repeat(MAX_GROUP_NAME_LEN + 1)).is_err());
+    }
+}
@@ -119,10 +119,98 @@ impl LogSource {
             Self::Server => "server",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "app" => Some(Self::App),
+            "stdout" => Some(Self::Stdout),
+            "stderr" => Some(Self::Stderr),
+            "http-request" => Some(Self::HttpRequest),
+            "http-response" => Some(Self::HttpResponse),
+            "sse-event" => Some(Self::SseEvent),
+            "connection" => Some(Self::Connection),
+            "oauth" => Some(Self::OAuth),
+            "server" => Some(Self::Server),
+            _ => None,
+        }
+    }
+}
+
+/// Level and source filter for log tails and searches
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Minimum level, `None` for all levels
+    pub min_level: Option<LogLevel>,
+    /// Sources to include, empty for all sources
+    pub sources: Vec<LogSource>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, log: &ServerLog) -> bool {
+        self.min_level.is_none_or(|level| log.level >= level)
+            && (self.sources.is_empty() || self.sources.contains(&log.source))
+    }
+}
+
+/// Results per page when a search doesn't set a limit
+pub const DEFAULT_LOG_SEARCH_LIMIT: usize = 100;
+
+/// Search over a server's stored logs, rotated files included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSearchQuery {
+    pub filter: LogFilter,
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries at or before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Case-insensitive text to find in the message or metadata
+    pub text: Option<String>,
+    /// Matches to skip, newest first
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for LogSearchQuery {
+    fn default() -> Self {
+        Self {
+            filter: LogFilter::default(),
+            since: None,
+            until: None,
+            text: None,
+            offset: 0,
+            limit: DEFAULT_LOG_SEARCH_LIMIT,
+        }
+    }
+}
+
+impl LogSearchQuery {
+    pub fn matches(&self, log: &ServerLog) -> bool {
+        self.filter.matches(log)
+            && self.since.is_none_or(|since| log.timestamp >= since)
+            && self.until.is_none_or(|until| log.timestamp <= until)
+            && self.text.as_deref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                log.message.to_lowercase().contains(&text)
+                    || log
+                        .metadata
+                        .as_ref()
+                        .is_some_and(|meta| meta.to_string().to_lowercase().contains(&text))
+            })
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone, Default)]
+pub struct LogSearchPage {
+    /// Matches in chronological order
+    pub entries: Vec<ServerLog>,
+    /// Offset of the next (older) page, `None` on the last page
+    pub next_offset: Option<usize>,
 }
 
 /// Configuration for log rotation
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct LogConfig {
     /// Base directory for logs
     pub base_dir: PathBuf,
@@ -137,10 +225,11 @@ pub struct LogConfig {
     pub compress: bool,
 }
 
-impl Default for LogConfig {
-    fn default() -> Self {
+impl LogConfig {
+    /// Default rotation, logging under `base_dir`
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
         Self {
-            base_dir: PathBuf::from("logs"),
+            base_dir: base_dir.into(),
             max_file_size: 10 * 1024 * 1024, // 10MB
             max_files: 30,                   // 30 files
             compress: true,
@@ -148,6 +237,12 @@ impl Default for LogConfig {
     }
 }
 
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self::new("logs")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
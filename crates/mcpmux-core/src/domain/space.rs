@@ -8,6 +8,7 @@ use uuid::Uuid;
 ///
 /// Examples: "Work", "Personal", "Client Project"
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Space {
     /// Unique identifier
     pub id: Uuid,
@@ -27,6 +28,10 @@ pub struct Space {
     /// Sort order for display
     pub sort_order: i32,
 
+    /// User who owns the space on a shared gateway; `None` for admins only
+    #[serde(default)]
+    pub owner_id: Option<Uuid>,
+
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
 
@@ -45,6 +50,7 @@ impl Space {
             description: None,
             is_default: false,
             sort_order: 0,
+            owner_id: None,
             created_at: now,
             updated_at: now,
         }
@@ -62,6 +68,12 @@ impl Space {
         self
     }
 
+    /// Give the space to a user
+    pub fn with_owner(mut self, owner_id: Uuid) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
     /// Mark as default space
     pub fn set_default(mut self) -> Self {
         self.is_default = true;
@@ -0,0 +1,147 @@
+//! Per-server connect and request timeouts.
+//!
+//! Most servers start in a few seconds and answer quickly, but some (a
+//! Docker image on first pull, a tool that runs a long query) need longer.
+//! A server definition can declare the timeouts it needs, and each
+//! installation can override them.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How long to wait for a server to start and finish the MCP handshake
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long to wait for a tool call, prompt or resource read
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Allowed connect timeouts, in seconds
+pub const CONNECT_TIMEOUT_SECS: RangeInclusive<u32> = 5..=600;
+
+/// Allowed request timeouts, in seconds
+pub const REQUEST_TIMEOUT_SECS: RangeInclusive<u32> = 1..=3600;
+
+/// Timeouts for one server, in seconds. `None` = use the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerTimeouts {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_secs: Option<u32>,
+}
+
+/// A timeout outside the allowed range
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{name} timeout must be between {} and {} seconds", .range.start(), .range.end())]
+pub struct InvalidTimeout {
+    pub name: &'static str,
+    pub range: RangeInclusive<u32>,
+}
+
+impl ServerTimeouts {
+    /// Whether neither timeout is set
+    pub fn is_empty(&self) -> bool {
+        self.connect_secs.is_none() && self.request_secs.is_none()
+    }
+
+    /// Check that every set timeout is within its allowed range
+    pub fn validate(&self) -> Result<(), InvalidTimeout> {
+        let checks = [
+            ("Connect", self.connect_secs, CONNECT_TIMEOUT_SECS),
+            ("Request", self.request_secs, REQUEST_TIMEOUT_SECS),
+        ];
+        for (name, secs, range) in checks {
+            if secs.is_some_and(|secs| !range.contains(&secs)) {
+                return Err(InvalidTimeout { name, range });
+            }
+        }
+        Ok(())
+    }
+
+    /// These timeouts, with unset ones taken from `fallback`
+    pub fn or(self, fallback: ServerTimeouts) -> Self {
+        Self {
+            connect_secs: self.connect_secs.or(fallback.connect_secs),
+            request_secs: self.request_secs.or(fallback.request_secs),
+        }
+    }
+
+    /// Connect timeout to use, or `default` when unset. Values from a
+    /// definition aren't validated, so they are clamped into range here.
+    pub fn connect_timeout(&self, default: Duration) -> Duration {
+        clamped(self.connect_secs, &CONNECT_TIMEOUT_SECS).unwrap_or(default)
+    }
+
+    /// Request timeout to use, or [`DEFAULT_REQUEST_TIMEOUT`] when unset
+    pub fn request_timeout(&self) -> Duration {
+        clamped(self.request_secs, &REQUEST_TIMEOUT_SECS).unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+}
+
+fn clamped(secs: Option<u32>, range: &RangeInclusive<u32>) -> Option<Duration> {
+    secs.map(|secs| Duration::from_secs(secs.clamp(*range.start(), *range.end()).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_wins_and_defaults_fill_in() {
+        let definition = ServerTimeouts {
+            connect_secs: Some(180),
+            request_secs: Some(300),
+        };
+        let installed = ServerTimeouts {
+            request_secs: Some(30),
+            ..Default::default()
+        };
+
+        let effective = installed.or(definition);
+        assert_eq!(
+            effective.connect_timeout(DEFAULT_CONNECT_TIMEOUT),
+            Duration::from_secs(180)
+        );
+        assert_eq!(effective.request_timeout(), Duration::from_secs(30));
+
+        let unset = ServerTimeouts::default();
+        assert!(unset.is_empty());
+        assert_eq!(
+            unset.connect_timeout(DEFAULT_CONNECT_TIMEOUT),
+            DEFAULT_CONNECT_TIMEOUT
+        );
+        assert_eq!(unset.request_timeout(), DEFAULT_REQUEST_TIMEOUT);
+    }
+
+    #[test]
+    fn rejects_and_clamps_out_of_range_values() {
+        let too_long = ServerTimeouts {
+            request_secs: Some(86_400),
+            ..Default::default()
+        };
+        assert_eq!(
+            too_long.validate().unwrap_err().to_string(),
+            "Request timeout must be between 1 and 3600 seconds"
+        );
+        assert_eq!(too_long.request_timeout(), Duration::from_secs(3600));
+
+        let too_short = ServerTimeouts {
+            connect_secs: Some(0),
+            ..Default::default()
+        };
+        assert!(too_short.validate().is_err());
+        assert_eq!(
+            too_short.connect_timeout(DEFAULT_CONNECT_TIMEOUT),
+            Duration::from_secs(5)
+        );
+
+        assert!(ServerTimeouts {
+            connect_secs: Some(120),
+            request_secs: Some(600),
+        }
+        .validate()
+        .is_ok());
+    }
+}
@@ -0,0 +1,485 @@
+//! Tool argument validation against a backend's declared `inputSchema`.
+//!
+//! Clients (and the models driving them) regularly send arguments that don't
+//! match a tool's schema: a number as a string, a missing required field, a
+//! misspelled property. Many servers answer with an opaque stack trace or, worse,
+//! act on the bad input. The gateway checks arguments first and reports every
+//! violation at once, so the caller can fix the call in one round trip.
+//!
+//! This is the subset of JSON Schema that tool schemas use in practice: `type`,
+//! `enum`/`const`, object `properties`/`required`/`additionalProperties`,
+//! array `items` and length bounds, string length and `pattern`, numeric
+//! bounds, `allOf`/`anyOf`/`oneOf`/`not`, and local `$ref`s. Unknown keywords
+//! (including `format`) are ignored, so an unusual schema errs towards letting
+//! the call through.
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Violations reported per call; more are summarized as a count
+pub const MAX_SCHEMA_VIOLATIONS: usize = 20;
+
+/// Depth at which `$ref` chains are assumed to be cycles
+const MAX_REF_DEPTH: usize = 32;
+
+/// One way the arguments don't match the schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Error)]
+#[error("{}: {message}", if path.is_empty() { "arguments" } else { path.as_str() })]
+pub struct SchemaViolation {
+    /// Location of the offending value (e.g. `options.paths[1]`), empty for
+    /// the arguments object itself
+    pub path: String,
+    pub message: String,
+}
+
+/// Check tool arguments against a tool's `inputSchema`.
+///
+/// Returns every violation found, up to [`MAX_SCHEMA_VIOLATIONS`]. A schema
+/// that isn't a JSON object (or `true`) accepts anything.
+pub fn validate_tool_arguments(schema: &Value, arguments: &Value) -> Vec<SchemaViolation> {
+    let mut validator = Validator {
+        root: schema,
+        violations: Vec::new(),
+    };
+    validator.validate(schema, arguments, "", 0);
+    validator.violations.truncate(MAX_SCHEMA_VIOLATIONS);
+    validator.violations
+}
+
+/// The `inputSchema` of a tool definition as stored in
+/// [`ServerFeature::raw_json`](super::ServerFeature::raw_json)
+pub fn tool_input_schema(raw_tool: &Value) -> Option<&Value> {
+    raw_tool
+        .get("inputSchema")
+        .or_else(|| raw_tool.get("input_schema"))
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    violations: Vec<SchemaViolation>,
+}
+
+impl<'a> Validator<'a> {
+    fn violation(&mut self, path: &str, message: String) {
+        self.violations.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    /// Whether `value` matches `schema`, without recording anything
+    fn matches(&self, schema: &'a Value, value: &Value, depth: usize) -> bool {
+        let mut probe = Validator {
+            root: self.root,
+            violations: Vec::new(),
+        };
+        probe.validate(schema, value, "", depth);
+        probe.violations.is_empty()
+    }
+
+    fn validate(&mut self, schema: &'a Value, value: &Value, path: &str, depth: usize) {
+        let schema = match schema {
+            Value::Bool(false) => {
+                self.violation(path, "no value is allowed here".to_string());
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if depth < MAX_REF_DEPTH {
+                if let Some(target) = self.resolve_ref(reference) {
+                    self.validate(target, value, path, depth + 1);
+                }
+            }
+        }
+
+        // OpenAPI-style `nullable`, common in generated schemas
+        if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+            return;
+        }
+
+        if let Some(expected) = schema.get("type") {
+            if !type_matches(expected, value) {
+                self.violation(
+                    path,
+                    format!(
+                        "expected {}, got {}",
+                        describe_type(expected),
+                        json_type(value)
+                    ),
+                );
+                // Type-specific keywords would only repeat the mismatch
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                self.violation(path, format!("must be one of {}", list_values(allowed)));
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                self.violation(path, format!("must be {}", constant));
+            }
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(schema, object, path, depth),
+            Value::Array(items) => self.validate_array(schema, items, path, depth),
+            Value::String(text) => self.validate_string(schema, text, path),
+            Value::Number(_) => self.validate_number(schema, value, path),
+            _ => {}
+        }
+
+        self.validate_combinators(schema, value, path, depth);
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &'a Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    self.violation(path, format!("missing required property '{}'", name));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+        for (name, item) in object {
+            let item_path = child_path(path, name);
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(property) => self.validate(property, item, &item_path, depth),
+                None => match additional {
+                    Some(Value::Bool(false)) => {
+                        let known = properties
+                            .map(|properties| {
+                                properties.keys().map(String::as_str).collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default();
+                        let message = if known.is_empty() {
+                            format!("unexpected property '{}'", name)
+                        } else {
+                            format!(
+                                "unexpected property '{}' (expected one of: {})",
+                                name,
+                                known.join(", ")
+                            )
+                        };
+                        self.violation(path, message);
+                    }
+                    Some(additional @ Value::Object(_)) => {
+                        self.validate(additional, item, &item_path, depth)
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    fn validate_array(
+        &mut self,
+        schema: &'a Map<String, Value>,
+        items: &[Value],
+        path: &str,
+        depth: usize,
+    ) {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                self.violation(path, format!("must have at least {} item(s)", min));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if items.len() as u64 > max {
+                self.violation(path, format!("must have at most {} item(s)", max));
+            }
+        }
+
+        match schema.get("items").or_else(|| schema.get("prefixItems")) {
+            // Tuple form: one schema per position
+            Some(Value::Array(schemas)) => {
+                for (i, (item_schema, item)) in schemas.iter().zip(items).enumerate() {
+                    self.validate(item_schema, item, &format!("{}[{}]", path, i), depth);
+                }
+            }
+            Some(item_schema) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.validate(item_schema, item, &format!("{}[{}]", path, i), depth);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn validate_string(&mut self, schema: &Map<String, Value>, text: &str, path: &str) {
+        let length = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if length < min {
+                self.violation(path, format!("must be at least {} character(s)", min));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+            if length > max {
+                self.violation(path, format!("must be at most {} character(s)", max));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            // Schemas use ECMA regexes; one the regex crate can't parse is skipped
+            if let Ok(regex) = Regex::new(pattern) {
+                if !regex.is_match(text) {
+                    self.violation(path, format!("must match the pattern {}", pattern));
+                }
+            }
+        }
+    }
+
+    fn validate_number(&mut self, schema: &Map<String, Value>, value: &Value, path: &str) {
+        let Some(number) = value.as_f64() else {
+            return;
+        };
+        let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+        if let Some(min) = bound("minimum") {
+            if number < min {
+                self.violation(path, format!("must be at least {}", min));
+            }
+        }
+        if let Some(max) = bound("maximum") {
+            if number > max {
+                self.violation(path, format!("must be at most {}", max));
+            }
+        }
+        if let Some(min) = bound("exclusiveMinimum") {
+            if number <= min {
+                self.violation(path, format!("must be greater than {}", min));
+            }
+        }
+        if let Some(max) = bound("exclusiveMaximum") {
+            if number >= max {
+                self.violation(path, format!("must be less than {}", max));
+            }
+        }
+    }
+
+    fn validate_combinators(
+        &mut self,
+        schema: &'a Map<String, Value>,
+        value: &Value,
+        path: &str,
+        depth: usize,
+    ) {
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for sub in all {
+                self.validate(sub, value, path, depth);
+            }
+        }
+        if let Some(any) = schema.get("anyOf").and_then(Value::as_array) {
+            if !any.iter().any(|sub| self.matches(sub, value, depth)) {
+                self.violation(
+                    path,
+                    "does not match any of the allowed schemas (anyOf)".to_string(),
+                );
+            }
+        }
+        if let Some(one) = schema.get("oneOf").and_then(Value::as_array) {
+            let matching = one
+                .iter()
+                .filter(|sub| self.matches(sub, value, depth))
+                .count();
+            if matching != 1 {
+                self.violation(
+                    path,
+                    format!(
+                        "must match exactly one of the allowed schemas (oneOf), matched {}",
+                        matching
+                    ),
+                );
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.matches(not, value, depth) {
+                self.violation(path, "matches a schema it must not match (not)".to_string());
+            }
+        }
+    }
+
+    /// Resolve a local reference such as `#/$defs/Item` against the root schema
+    fn resolve_ref(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            return Some(self.root);
+        }
+        self.root.pointer(pointer)
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        // Unknown type names can't be checked
+        _ => true,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn list_values(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "minLength": 1 },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "sort": { "enum": ["stars", "updated"] },
+                "filters": {
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/filter" }
+                }
+            },
+            "required": ["query"],
+            "additionalProperties": false,
+            "$defs": {
+                "filter": {
+                    "type": "object",
+                    "properties": { "field": { "type": "string" } },
+                    "required": ["field"]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn accepts_matching_arguments() {
+        let args = json!({
+            "query": "mcp",
+            "limit": 10,
+            "sort": "stars",
+            "filters": [{ "field": "language" }]
+        });
+        assert_eq!(validate_tool_arguments(&search_schema(), &args), vec![]);
+        // No schema, nothing to check
+        assert_eq!(validate_tool_arguments(&json!(true), &args), vec![]);
+    }
+
+    #[test]
+    fn reports_every_violation_with_its_path() {
+        let args = json!({
+            "limit": "10",
+            "sort": "name",
+            "filters": [{ "field": "language" }, { "value": 1 }],
+            "page": 2
+        });
+        let violations: Vec<String> = validate_tool_arguments(&search_schema(), &args)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "arguments: missing required property 'query'",
+                "limit: expected integer, got string",
+                "sort: must be one of \"stars\", \"updated\"",
+                "filters[1]: missing required property 'field'",
+                "arguments: unexpected property 'page' (expected one of: query, limit, sort, filters)",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_bounds_and_combinators() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "id": { "anyOf": [{ "type": "string", "pattern": "^[a-z]+$" }, { "type": "integer" }] },
+                "tags": { "type": "array", "maxItems": 2 },
+                "note": { "type": "string", "nullable": true }
+            }
+        });
+        let violations = validate_tool_arguments(
+            &schema,
+            &json!({ "limit": 0, "id": "ABC", "tags": [1, 2, 3] }),
+        );
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, vec!["limit", "id", "tags"]);
+
+        assert!(validate_tool_arguments(&schema, &json!({ "id": 7, "note": null })).is_empty());
+        assert!(validate_tool_arguments(&schema, &json!({ "limit": 2.0 })).is_empty());
+    }
+
+    #[test]
+    fn finds_the_schema_in_a_tool_definition() {
+        let tool = json!({ "name": "search", "inputSchema": { "type": "object" } });
+        assert_eq!(tool_input_schema(&tool), Some(&json!({ "type": "object" })));
+        assert_eq!(tool_input_schema(&json!({ "name": "x" })), None);
+    }
+}
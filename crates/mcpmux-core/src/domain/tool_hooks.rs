@@ -0,0 +1,658 @@
+//! Tool hooks: rewrite the arguments and results of a server's tool calls.
+//!
+//! Hooks are set per installed server and run by the gateway around each
+//! routed call. `before` hooks edit the arguments before they are validated
+//! and forwarded (fill in a default project ID, drop an argument the server
+//! rejects); `after` hooks edit the text of the result before the client sees
+//! it (redact email addresses, cap long output).
+//!
+//! Most hooks are declarative steps. What they can't express goes in a
+//! `script` step, written in Rhai. Scripts run in a sandboxed engine: they
+//! only see the JSON they are handed, can't import modules, read files or
+//! reach the network, and are stopped once they exceed their operation,
+//! call depth or data size limits.
+
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Scope};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Most hooks one server may have, `before` and `after` together
+pub const MAX_TOOL_HOOKS: usize = 32;
+
+/// Largest compiled `redact` pattern, in bytes
+const MAX_PATTERN_SIZE: usize = 1 << 20;
+
+/// Longest `script` source, in bytes
+pub const MAX_SCRIPT_LEN: usize = 16 * 1024;
+
+/// Most operations one run of a script may take
+const MAX_SCRIPT_OPERATIONS: u64 = 100_000;
+
+/// Largest string, array and map a script may build. Strings are generous
+/// since results routinely carry long text.
+const MAX_SCRIPT_STRING_SIZE: usize = 16 << 20;
+const MAX_SCRIPT_COLLECTION_SIZE: usize = 10_000;
+
+lazy_static! {
+    static ref SCRIPT_ENGINE: Engine = script_engine();
+}
+
+fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_SCRIPT_OPERATIONS)
+        .set_max_call_levels(16)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(MAX_SCRIPT_STRING_SIZE)
+        .set_max_array_size(MAX_SCRIPT_COLLECTION_SIZE)
+        .set_max_map_size(MAX_SCRIPT_COLLECTION_SIZE)
+        .set_module_resolver(DummyModuleResolver::new())
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {});
+    engine.disable_symbol("eval");
+    engine
+}
+
+/// Hooks for one server's tool calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolHooks {
+    /// Run on the arguments, in order, before the call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<ToolHook>,
+    /// Run on the result's text, in order, after the call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<ToolHook>,
+}
+
+/// One step, and the tools it applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolHook {
+    /// Tool names the step applies to, where `*` matches anything; all
+    /// tools when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    #[serde(flatten)]
+    pub step: HookStep,
+}
+
+/// What a hook does. Argument paths are keys separated by dots, e.g.
+/// `options.limit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum HookStep {
+    /// Set an argument the caller left out (`before` only)
+    Default { path: String, value: Value },
+    /// Set an argument, replacing the caller's value (`before` only)
+    Set { path: String, value: Value },
+    /// Remove an argument (`before` only)
+    Remove { path: String },
+    /// Move an argument to another path (`before` only)
+    Rename { from: String, to: String },
+    /// Replace matches of a regular expression in every string argument, or
+    /// in the result's text
+    Redact {
+        pattern: String,
+        #[serde(default = "default_replacement")]
+        replacement: String,
+    },
+    /// Cut each text of the result down to `max_chars` characters (`after`
+    /// only)
+    Truncate { max_chars: usize },
+    /// Run a Rhai script. Before the call it can edit the arguments, an
+    /// object map named `args`; after it, the result's content blocks, an
+    /// array named `content`, and its structured content, `structured` (`()`
+    /// when there is none).
+    Script { source: String },
+}
+
+fn default_replacement() -> String {
+    "[redacted]".to_string()
+}
+
+/// A hook that can't run
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidToolHook {
+    #[error("At most {MAX_TOOL_HOOKS} hooks are allowed")]
+    TooMany,
+    #[error("{phase} hook {index}: {reason}")]
+    Step {
+        phase: &'static str,
+        /// 1-based position in its list
+        index: usize,
+        reason: String,
+    },
+}
+
+/// A hook script that failed while running
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Hook script for tool '{tool}' failed: {reason}")]
+pub struct ToolHookFailed {
+    pub tool: String,
+    pub reason: String,
+}
+
+impl ToolHooks {
+    /// Whether there are no hooks
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Check that every hook can run in its phase
+    pub fn validate(&self) -> Result<(), InvalidToolHook> {
+        if self.before.len() + self.after.len() > MAX_TOOL_HOOKS {
+            return Err(InvalidToolHook::TooMany);
+        }
+        let phases = [
+            ("Before", &self.before, true),
+            ("After", &self.after, false),
+        ];
+        for (phase, hooks, before) in phases {
+            for (i, hook) in hooks.iter().enumerate() {
+                hook.check(before).map_err(|reason| InvalidToolHook::Step {
+                    phase,
+                    index: i + 1,
+                    reason,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the `before` hooks that apply to `tool` on its arguments. Only a
+    /// script can fail; the arguments are then left half edited, and the
+    /// call should not go out.
+    pub fn apply_before(&self, tool: &str, arguments: &mut Value) -> Result<(), ToolHookFailed> {
+        for hook in self.before.iter().filter(|hook| hook.applies_to(tool)) {
+            if !arguments.is_object() {
+                *arguments = Value::Object(Map::new());
+            }
+            let Value::Object(object) = arguments else {
+                return Ok(());
+            };
+            match &hook.step {
+                HookStep::Default { path, value } => {
+                    if get_path(object, path).is_none() {
+                        set_path(object, path, value.clone());
+                    }
+                }
+                HookStep::Set { path, value } => set_path(object, path, value.clone()),
+                HookStep::Remove { path } => {
+                    remove_path(object, path);
+                }
+                HookStep::Rename { from, to } => {
+                    if let Some(value) = remove_path(object, from) {
+                        set_path(object, to, value);
+                    }
+                }
+                HookStep::Redact {
+                    pattern,
+                    replacement,
+                } => {
+                    if let Some(regex) = compile(pattern) {
+                        redact_strings(arguments, &regex, replacement);
+                    }
+                }
+                HookStep::Truncate { .. } => {}
+                HookStep::Script { source } => {
+                    let failed = |reason: String| ToolHookFailed {
+                        tool: tool.to_string(),
+                        reason,
+                    };
+                    let mut scope = Scope::new();
+                    scope.push_dynamic("args", to_dynamic(arguments).map_err(failed)?);
+                    run_script(source, &mut scope).map_err(failed)?;
+                    let edited =
+                        from_dynamic(scope.get_value::<Dynamic>("args")).map_err(failed)?;
+                    if !edited.is_object() {
+                        return Err(failed("args must be left an object map".to_string()));
+                    }
+                    *arguments = edited;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any `after` hook applies to `tool`
+    pub fn has_after(&self, tool: &str) -> bool {
+        self.after.iter().any(|hook| hook.applies_to(tool))
+    }
+
+    /// Run the `after` hooks that apply to `tool` on its result: the text of
+    /// each content item, and the strings of the structured content. Scripts
+    /// see the whole result, and may add or remove content blocks. Only a
+    /// script can fail, leaving the result half edited.
+    pub fn apply_after(
+        &self,
+        tool: &str,
+        content: &mut Vec<Value>,
+        structured: Option<&mut Value>,
+    ) -> Result<(), ToolHookFailed> {
+        let mut structured = structured;
+        for hook in self.after.iter().filter(|hook| hook.applies_to(tool)) {
+            let mut texts: Vec<&mut String> = content.iter_mut().filter_map(content_text).collect();
+            match &hook.step {
+                HookStep::Redact {
+                    pattern,
+                    replacement,
+                } => {
+                    let Some(regex) = compile(pattern) else {
+                        continue;
+                    };
+                    for text in texts.iter_mut() {
+                        if let std::borrow::Cow::Owned(redacted) =
+                            regex.replace_all(text, replacement.as_str())
+                        {
+                            **text = redacted;
+                        }
+                    }
+                    if let Some(value) = structured.as_deref_mut() {
+                        redact_strings(value, &regex, replacement);
+                    }
+                }
+                HookStep::Truncate { max_chars } => {
+                    for text in texts.iter_mut() {
+                        truncate_chars(text, *max_chars);
+                    }
+                }
+                HookStep::Script { source } => {
+                    let failed = |reason: String| ToolHookFailed {
+                        tool: tool.to_string(),
+                        reason,
+                    };
+                    let mut scope = Scope::new();
+                    scope.push_dynamic("content", to_dynamic(&*content).map_err(failed)?);
+                    let current = match structured.as_deref() {
+                        Some(value) => to_dynamic(value).map_err(failed)?,
+                        None => Dynamic::UNIT,
+                    };
+                    scope.push_dynamic("structured", current);
+                    run_script(source, &mut scope).map_err(failed)?;
+
+                    let edited =
+                        from_dynamic(scope.get_value::<Dynamic>("content")).map_err(failed)?;
+                    let Value::Array(blocks) = edited else {
+                        return Err(failed("content must be left an array".to_string()));
+                    };
+                    *content = blocks;
+                    if let Some(value) = structured.as_deref_mut() {
+                        *value = from_dynamic(scope.get_value::<Dynamic>("structured"))
+                            .map_err(failed)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToolHook {
+    fn applies_to(&self, tool: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|pattern| glob_match(pattern, tool))
+    }
+
+    fn check(&self, before: bool) -> Result<(), String> {
+        if self.tools.iter().any(|pattern| pattern.trim().is_empty()) {
+            return Err("tool names can't be empty".to_string());
+        }
+        let paths: Vec<&String> = match &self.step {
+            HookStep::Default { path, .. }
+            | HookStep::Set { path, .. }
+            | HookStep::Remove { path } => vec![path],
+            HookStep::Rename { from, to } => vec![from, to],
+            HookStep::Redact { pattern, .. } => {
+                RegexBuilder::new(pattern)
+                    .size_limit(MAX_PATTERN_SIZE)
+                    .build()
+                    .map_err(|e| format!("invalid pattern: {}", e))?;
+                Vec::new()
+            }
+            HookStep::Truncate { max_chars } => {
+                if *max_chars == 0 {
+                    return Err("max_chars must be at least 1".to_string());
+                }
+                Vec::new()
+            }
+            HookStep::Script { source } => {
+                if source.len() > MAX_SCRIPT_LEN {
+                    return Err(format!("scripts can be at most {} bytes", MAX_SCRIPT_LEN));
+                }
+                SCRIPT_ENGINE
+                    .compile(source)
+                    .map_err(|e| format!("invalid script: {}", e))?;
+                Vec::new()
+            }
+        };
+        if let Some(path) = paths.iter().find(|path| path.split('.').any(str::is_empty)) {
+            return Err(format!("invalid argument path '{}'", path));
+        }
+        let runs_before = !matches!(self.step, HookStep::Truncate { .. });
+        let runs_after = matches!(
+            self.step,
+            HookStep::Redact { .. } | HookStep::Truncate { .. } | HookStep::Script { .. }
+        );
+        match (before, runs_before, runs_after) {
+            (true, false, _) => Err("truncate only runs after the call".to_string()),
+            (false, _, false) => {
+                Err("only redact, truncate and script run after the call".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Compile and run a script in the sandboxed engine, with `scope` holding
+/// its inputs
+fn run_script(source: &str, scope: &mut Scope) -> Result<(), String> {
+    let ast = SCRIPT_ENGINE.compile(source).map_err(|e| e.to_string())?;
+    SCRIPT_ENGINE
+        .run_ast_with_scope(scope, &ast)
+        .map_err(|e| e.to_string())
+}
+
+fn to_dynamic(value: &impl Serialize) -> Result<Dynamic, String> {
+    rhai::serde::to_dynamic(value).map_err(|e| e.to_string())
+}
+
+fn from_dynamic(value: Option<Dynamic>) -> Result<Value, String> {
+    match value {
+        Some(value) => rhai::serde::from_dynamic(&value).map_err(|e| e.to_string()),
+        None => Ok(Value::Null),
+    }
+}
+
+fn compile(pattern: &str) -> Option<Regex> {
+    RegexBuilder::new(pattern)
+        .size_limit(MAX_PATTERN_SIZE)
+        .build()
+        .ok()
+}
+
+/// Match a tool name against a pattern where `*` matches any run of
+/// characters
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+fn get_path<'a>(object: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut keys = path.split('.');
+    let mut value = object.get(keys.next()?)?;
+    for key in keys {
+        value = value.as_object()?.get(key)?;
+    }
+    Some(value)
+}
+
+/// Set a value, creating objects along the path (and replacing non-objects)
+fn set_path(object: &mut Map<String, Value>, path: &str, value: Value) {
+    let Some((parents, key)) = split_path(path) else {
+        return;
+    };
+    let mut object = object;
+    for parent in parents {
+        let entry = object
+            .entry(parent.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        let Value::Object(child) = entry else {
+            return;
+        };
+        object = child;
+    }
+    object.insert(key.to_string(), value);
+}
+
+fn remove_path(object: &mut Map<String, Value>, path: &str) -> Option<Value> {
+    let (parents, key) = split_path(path)?;
+    let mut object = object;
+    for parent in parents {
+        object = object.get_mut(parent)?.as_object_mut()?;
+    }
+    object.remove(key)
+}
+
+fn split_path(path: &str) -> Option<(Vec<&str>, &str)> {
+    let mut keys: Vec<&str> = path.split('.').collect();
+    let key = keys.pop().filter(|key| !key.is_empty())?;
+    Some((keys, key))
+}
+
+fn redact_strings(value: &mut Value, regex: &Regex, replacement: &str) {
+    match value {
+        Value::String(text) => {
+            if let std::borrow::Cow::Owned(redacted) = regex.replace_all(text, replacement) {
+                *text = redacted;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_strings(item, regex, replacement);
+            }
+        }
+        Value::Object(object) => {
+            for item in object.values_mut() {
+                redact_strings(item, regex, replacement);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The text of a `text` content item, or of an embedded text resource
+pub(crate) fn content_text(item: &mut Value) -> Option<&mut String> {
+    let item = item.as_object_mut()?;
+    let text = match item.get("type").and_then(Value::as_str) {
+        Some("text") => item.get_mut("text")?,
+        Some("resource") => item.get_mut("resource")?.get_mut("text")?,
+        _ => return None,
+    };
+    match text {
+        Value::String(text) => Some(text),
+        _ => None,
+    }
+}
+
+fn truncate_chars(text: &mut String, max_chars: usize) {
+    if let Some((at, _)) = text.char_indices().nth(max_chars) {
+        text.truncate(at);
+        text.push_str("\n[truncated]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hooks(value: Value) -> ToolHooks {
+        serde_json::from_value(value).expect("hooks")
+    }
+
+    #[test]
+    fn before_hooks_rewrite_arguments() {
+        let hooks = hooks(json!({
+            "before": [
+                { "action": "default", "path": "project_id", "value": "acme" },
+                { "action": "set", "path": "options.limit", "value": 50 },
+                { "action": "remove", "path": "debug", "tools": ["search_*"] },
+                { "action": "rename", "from": "q", "to": "query" }
+            ]
+        }));
+        hooks.validate().expect("valid");
+
+        let mut arguments = json!({ "q": "rust", "debug": true, "options": 5 });
+        hooks.apply_before("search_issues", &mut arguments).unwrap();
+        assert_eq!(
+            arguments,
+            json!({ "project_id": "acme", "options": { "limit": 50 }, "query": "rust" })
+        );
+
+        // The caller's value wins over a default; `remove` is for search tools
+        let mut arguments = json!({ "project_id": "other", "debug": true });
+        hooks.apply_before("create_issue", &mut arguments).unwrap();
+        assert_eq!(arguments["project_id"], "other");
+        assert_eq!(arguments["debug"], true);
+
+        let mut arguments = Value::Null;
+        hooks.apply_before("create_issue", &mut arguments).unwrap();
+        assert_eq!(arguments["project_id"], "acme");
+    }
+
+    #[test]
+    fn after_hooks_redact_and_truncate_results() {
+        let hooks = hooks(json!({
+            "after": [
+                { "action": "redact", "pattern": "[\\w.+-]+@[\\w-]+\\.[\\w.]+", "replacement": "[email]" },
+                { "action": "truncate", "max_chars": 20, "tools": ["get_user"] }
+            ]
+        }));
+        hooks.validate().expect("valid");
+
+        let mut content = vec![
+            json!({ "type": "text", "text": "Owner: ada@example.com, since 1843 and still counting" }),
+            json!({ "type": "image", "data": "ada@example.com", "mimeType": "image/png" }),
+        ];
+        let mut structured = json!({ "owner": { "email": "ada@example.com" } });
+        hooks
+            .apply_after("get_user", &mut content, Some(&mut structured))
+            .unwrap();
+
+        assert_eq!(content[0]["text"], "Owner: [email], sinc\n[truncated]");
+        assert_eq!(content[1]["data"], "ada@example.com");
+        assert_eq!(structured["owner"]["email"], "[email]");
+    }
+
+    #[test]
+    fn scripts_edit_arguments_and_results() {
+        let hooks = hooks(json!({
+            "before": [{
+                "action": "script",
+                "source": "if args.limit == () || args.limit > 100 { args.limit = 100; } args.query = args.query.to_lower();"
+            }],
+            "after": [{
+                "action": "script",
+                "source": "content = content.filter(|block| block.type == \"text\"); content.push(#{ type: \"text\", text: `${content.len()} blocks` }); structured.count = structured.items.len();"
+            }]
+        }));
+        hooks.validate().expect("valid");
+
+        let mut arguments = json!({ "query": "Rust", "limit": 500 });
+        hooks.apply_before("search", &mut arguments).unwrap();
+        assert_eq!(arguments, json!({ "query": "rust", "limit": 100 }));
+
+        let mut content = vec![
+            json!({ "type": "text", "text": "first" }),
+            json!({ "type": "image", "data": "aGVsbG8=", "mimeType": "image/png" }),
+        ];
+        let mut structured = json!({ "items": [1, 2, 3] });
+        hooks
+            .apply_after("search", &mut content, Some(&mut structured))
+            .unwrap();
+        assert_eq!(
+            content,
+            vec![
+                json!({ "type": "text", "text": "first" }),
+                json!({ "type": "text", "text": "1 blocks" }),
+            ]
+        );
+        assert_eq!(structured, json!({ "items": [1, 2, 3], "count": 3 }));
+    }
+
+    #[test]
+    fn scripts_run_sandboxed() {
+        let failing = [
+            // Runaway loops stop at the operation limit
+            "loop { }",
+            // No modules, so no way to reach files
+            "import \"secrets\" as s;",
+            "let s = \"x\"; loop { s += s; }",
+            "throw \"rejected\";",
+            "args = 42;",
+        ];
+        for source in failing {
+            let hooks = hooks(json!({ "before": [{ "action": "script", "source": source }] }));
+            let mut arguments = json!({ "path": "/tmp" });
+            let error = hooks
+                .apply_before("read_file", &mut arguments)
+                .expect_err(source);
+            assert_eq!(error.tool, "read_file");
+        }
+
+        assert!(
+            hooks(json!({ "before": [{ "action": "script", "source": "eval(\"1\")" }] }))
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn has_after_only_counts_hooks_for_the_tool() {
+        let hooks = hooks(json!({
+            "after": [{ "action": "truncate", "max_chars": 20, "tools": ["get_*"] }]
+        }));
+        assert!(hooks.has_after("get_user"));
+        assert!(!hooks.has_after("list_users"));
+        assert!(!ToolHooks::default().has_after("get_user"));
+    }
+
+    #[test]
+    fn hooks_are_checked_for_their_phase() {
+        let invalid = [
+            json!({ "after": [{ "action": "set", "path": "a", "value": 1 }] }),
+            json!({ "before": [{ "action": "truncate", "max_chars": 10 }] }),
+            json!({ "before": [{ "action": "redact", "pattern": "(" }] }),
+            json!({ "before": [{ "action": "remove", "path": "a..b" }] }),
+            json!({ "before": [{ "action": "script", "source": "args.limit = " }] }),
+            json!({ "before": [{ "action": "script", "source": "x".repeat(MAX_SCRIPT_LEN + 1) }] }),
+        ];
+        for value in invalid {
+            assert!(hooks(value.clone()).validate().is_err(), "{}", value);
+        }
+
+        let too_many = ToolHooks {
+            before: vec![
+                ToolHook {
+                    tools: Vec::new(),
+                    step: HookStep::Remove {
+                        path: "a".to_string()
+                    },
+                };
+                MAX_TOOL_HOOKS + 1
+            ],
+            after: Vec::new(),
+        };
+        assert_eq!(too_many.validate(), Err(InvalidToolHook::TooMany));
+    }
+
+    #[test]
+    fn tool_patterns_match_with_wildcards() {
+        assert!(glob_match("search_*", "search_issues"));
+        assert!(glob_match("*_issue", "create_issue"));
+        assert!(glob_match("get_*_by_*", "get_user_by_email"));
+        assert!(glob_match("list", "list"));
+        assert!(!glob_match("list", "list_all"));
+        assert!(!glob_match("a*a", "a"));
+    }
+}
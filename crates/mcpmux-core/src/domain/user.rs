@@ -0,0 +1,138 @@
+//! User entity - a person sharing a headless gateway
+//!
+//! A gateway run for a small team has one user per person. Admins manage
+//! everything; members manage only the Spaces and clients they own (and so
+//! the grants between them). A single-user install has no users at all and
+//! is administered with the gateway-wide admin token.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Space;
+
+/// Longest allowed user name, in characters
+pub const MAX_USER_NAME_LEN: usize = 64;
+
+/// What a user may manage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    /// Every Space, client and user
+    Admin,
+    /// Only the Spaces and clients they own
+    #[default]
+    Member,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::Member => "member",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "admin" => Some(Self::Admin),
+            "member" => Some(Self::Member),
+            _ => None,
+        }
+    }
+}
+
+/// A person with their own admin token on a shared gateway
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Display name, unique on the gateway
+    pub name: String,
+
+    pub role: UserRole,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl User {
+    pub fn new(name: impl Into<String>, role: UserRole) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            role,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    /// Whether something owned by `owner` (a user ID) is this user's to manage
+    pub fn owns(&self, owner: Option<&str>) -> bool {
+        self.is_admin() || owner.is_some_and(|owner| owner == self.id.to_string())
+    }
+
+    pub fn can_access_space(&self, space: &Space) -> bool {
+        self.is_admin() || space.owner_id == Some(self.id)
+    }
+}
+
+/// Check a user name, returning it trimmed
+pub fn validate_user_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("User name is required".to_string());
+    }
+    if name.chars().count() > MAX_USER_NAME_LEN {
+        return Err(format!(
+            "User name must be at most {} characters",
+            MAX_USER_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_members_only_reach_what_they_own() {
+        let alice = User::new("alice", UserRole::Member);
+        let admin = User::new("root", UserRole::Admin);
+
+        let mine = Space::new("Alice").with_owner(alice.id);
+        let shared = Space::new("Shared");
+        assert!(alice.can_access_space(&mine));
+        assert!(!alice.can_access_space(&shared));
+        assert!(admin.can_access_space(&mine));
+        assert!(admin.can_access_space(&shared));
+
+        let alice_id = alice.id.to_string();
+        assert!(alice.owns(Some(&alice_id)));
+        assert!(!alice.owns(Some(&admin.id.to_string())));
+        assert!(!alice.owns(None));
+        assert!(admin.owns(None));
+    }
+
+    #[test]
+    fn test_roles_round_trip() {
+        for role in [UserRole::Admin, UserRole::Member] {
+            assert_eq!(UserRole::parse(role.as_str()), Some(role));
+        }
+        assert_eq!(UserRole::parse("ADMIN"), Some(UserRole::Admin));
+        assert_eq!(UserRole::parse("owner"), None);
+        assert_eq!(UserRole::default(), UserRole::Member);
+        assert!(validate_user_name("  ").is_err());
+        assert_eq!(validate_user_name(" bob "), Ok("bob".to_string()));
+    }
+}
@@ -0,0 +1,61 @@
+//! WASI servers: MCP servers compiled to WebAssembly, run by the gateway on
+//! its embedded wasmtime engine.
+//!
+//! A module starts with no access to the host: no files, no network, and
+//! only the environment variables it is given. A server definition declares
+//! what its module needs ([`WasmPermissions`]), and the user grants each
+//! permission per installation. A module only gets what was both requested
+//! and granted; filesystem access covers the server's allowed roots only.
+
+use serde::{Deserialize, Serialize};
+
+/// Host access a WASI module can have
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmPermissions {
+    /// Read and write the server's allowed roots
+    #[serde(default)]
+    pub filesystem: bool,
+    /// Open network connections and resolve host names
+    #[serde(default)]
+    pub network: bool,
+}
+
+impl WasmPermissions {
+    /// Whether no permission is set
+    pub fn is_empty(&self) -> bool {
+        !self.filesystem && !self.network
+    }
+
+    /// The permissions in both `self` (requested) and `granted`
+    pub fn granted(&self, granted: &WasmPermissions) -> WasmPermissions {
+        WasmPermissions {
+            filesystem: self.filesystem && granted.filesystem,
+            network: self.network && granted.network,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_requested_and_granted_permissions_apply() {
+        let requested = WasmPermissions {
+            filesystem: true,
+            network: false,
+        };
+        let granted = WasmPermissions {
+            filesystem: true,
+            network: true,
+        };
+        assert_eq!(
+            requested.granted(&granted),
+            WasmPermissions {
+                filesystem: true,
+                network: false,
+            }
+        );
+        assert!(requested.granted(&WasmPermissions::default()).is_empty());
+    }
+}
@@ -62,6 +62,7 @@ impl BindingType {
 /// "no Space tools" mapping (the folder still routes to this Space; built-in
 /// servers apply per Space).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct WorkspaceBinding {
     pub id: Uuid,
     pub workspace_root: String,
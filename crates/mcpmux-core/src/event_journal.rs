@@ -0,0 +1,247 @@
+//! Event Journal - persistent, sequenced record of domain events
+//!
+//! The event bus is a broadcast channel: a consumer that falls more than the
+//! channel's capacity behind, or isn't running at all (the UI while its
+//! window reloads, anything across a restart), misses those events for good.
+//! The journal stores every event with a sequence number, so a consumer can
+//! remember the last sequence it handled and pick up from there.
+//!
+//! ```text
+//! broadcast ──► journal writer ──► EventJournalRepository (SQLite)
+//!                     │
+//!                     └─ latest sequence ──► JournalReader::next()
+//! ```
+//!
+//! The writer is a bus subscriber itself, but all it does per event is one
+//! insert, so it keeps up with bursts the other consumers can't. Old events
+//! are pruned to keep the journal bounded; a reader that falls further
+//! behind than the retention sees a jump in sequence numbers.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, warn};
+
+use crate::{DomainEvent, EventJournalRepository, JournaledEvent, RepoResult};
+
+/// Events kept in the journal by default
+pub const DEFAULT_JOURNAL_RETENTION: u64 = 10_000;
+
+/// Appends between prunes
+const PRUNE_INTERVAL: u64 = 500;
+
+/// Events a reader fetches from storage at a time
+const READ_BATCH: usize = 256;
+
+/// Handle to a running journal
+#[derive(Clone)]
+pub struct EventJournal {
+    repo: Arc<dyn EventJournalRepository>,
+    latest: watch::Receiver<u64>,
+}
+
+impl EventJournal {
+    /// Start journaling every event `events` receives, keeping the newest
+    /// `retention`. The writer stops when the channel closes.
+    pub async fn start(
+        repo: Arc<dyn EventJournalRepository>,
+        mut events: broadcast::Receiver<DomainEvent>,
+        retention: u64,
+    ) -> RepoResult<Self> {
+        let (latest_tx, latest) = watch::channel(repo.latest_sequence().await?);
+        let writer = repo.clone();
+        tokio::spawn(async move {
+            let mut since_prune = 0;
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(
+                            skipped,
+                            "[EventJournal] Writer lagged; events were not journaled"
+                        );
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                match writer.append(&event).await {
+                    Ok(sequence) => {
+                        latest_tx.send_replace(sequence);
+                    }
+                    Err(e) => {
+                        warn!(
+                            event_type = event.type_name(),
+                            "[EventJournal] Failed to journal event: {}", e
+                        );
+                        continue;
+                    }
+                }
+                since_prune += 1;
+                if since_prune >= PRUNE_INTERVAL {
+                    since_prune = 0;
+                    match writer.prune(retention).await {
+                        Ok(pruned) => debug!(pruned, "[EventJournal] Pruned old events"),
+                        Err(e) => warn!("[EventJournal] Failed to prune: {}", e),
+                    }
+                }
+            }
+            debug!("[EventJournal] Writer stopped");
+        });
+        Ok(Self { repo, latest })
+    }
+
+    /// Sequence number of the newest journaled event
+    pub fn latest_sequence(&self) -> u64 {
+        *self.latest.borrow()
+    }
+
+    /// Events journaled after `sequence`, oldest first, at most `limit`
+    pub async fn since(&self, sequence: u64, limit: usize) -> RepoResult<Vec<JournaledEvent>> {
+        self.repo.since(sequence, limit).await
+    }
+
+    /// A reader of the events journaled after `sequence`. Pass
+    /// [`latest_sequence`](Self::latest_sequence) to only see new events.
+    pub fn reader(&self, sequence: u64) -> JournalReader {
+        JournalReader {
+            repo: self.repo.clone(),
+            latest: self.latest.clone(),
+            cursor: sequence,
+            buffered: VecDeque::new(),
+        }
+    }
+}
+
+/// Reads journaled events in order, waiting for new ones once caught up
+pub struct JournalReader {
+    repo: Arc<dyn EventJournalRepository>,
+    latest: watch::Receiver<u64>,
+    cursor: u64,
+    buffered: VecDeque<JournaledEvent>,
+}
+
+impl JournalReader {
+    /// Sequence number of the last event returned
+    pub fn cursor(&self) -> u64 {
+        self.cursor
+    }
+
+    /// The next event. Returns `None` once the writer has stopped and every
+    /// event has been read.
+    pub async fn next(&mut self) -> RepoResult<Option<JournaledEvent>> {
+        loop {
+            if let Some(event) = self.buffered.pop_front() {
+                self.cursor = event.sequence;
+                return Ok(Some(event));
+            }
+            let latest = *self.latest.borrow_and_update();
+            let writer_stopped = latest <= self.cursor && self.latest.changed().await.is_err();
+            let batch = self.repo.since(self.cursor, READ_BATCH).await?;
+            if batch.is_empty() {
+                if writer_stopped {
+                    return Ok(None);
+                }
+                // Everything up to `latest` was pruned before we read it
+                self.cursor = self.cursor.max(latest);
+            }
+            self.buffered.extend(batch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventBus;
+    use chrono::Utc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryJournal {
+        events: Mutex<Vec<JournaledEvent>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventJournalRepository for MemoryJournal {
+        async fn append(&self, event: &DomainEvent) -> RepoResult<u64> {
+            let mut events = self.events.lock().await;
+            let sequence = events.last().map_or(1, |e| e.sequence + 1);
+            events.push(JournaledEvent {
+                sequence,
+                recorded_at: Utc::now(),
+                event: event.clone(),
+            });
+            Ok(sequence)
+        }
+
+        async fn since(&self, sequence: u64, limit: usize) -> RepoResult<Vec<JournaledEvent>> {
+            let events = self.events.lock().await;
+            Ok(events
+                .iter()
+                .filter(|e| e.sequence > sequence)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn latest_sequence(&self) -> RepoResult<u64> {
+            Ok(self.events.lock().await.last().map_or(0, |e| e.sequence))
+        }
+
+        async fn prune(&self, keep: u64) -> RepoResult<u64> {
+            let mut events = self.events.lock().await;
+            let excess = events.len().saturating_sub(keep as usize);
+            events.drain(..excess);
+            Ok(excess as u64)
+        }
+    }
+
+    async fn next_type(reader: &mut JournalReader) -> &'static str {
+        tokio::time::timeout(Duration::from_secs(5), reader.next())
+            .await
+            .expect("event was journaled")
+            .unwrap()
+            .unwrap()
+            .event
+            .type_name()
+    }
+
+    #[tokio::test]
+    async fn readers_catch_up_from_their_cursor() {
+        let bus = EventBus::new();
+        let journal = EventJournal::start(
+            Arc::new(MemoryJournal::default()),
+            bus.raw_sender().subscribe(),
+            DEFAULT_JOURNAL_RETENTION,
+        )
+        .await
+        .unwrap();
+        let mut live = journal.reader(journal.latest_sequence());
+
+        let sender = bus.sender();
+        sender.emit(DomainEvent::GatewayStarted {
+            url: "http://localhost:3100".to_string(),
+            port: 3100,
+        });
+        sender.emit(DomainEvent::SessionRootsChanged);
+        sender.emit(DomainEvent::GatewayStopped);
+
+        assert_eq!(next_type(&mut live).await, "gateway_started");
+        assert_eq!(next_type(&mut live).await, "session_roots_changed");
+        assert_eq!(next_type(&mut live).await, "gateway_stopped");
+        assert_eq!(live.cursor(), 3);
+
+        // A reader that stopped after the first event picks up where it left off
+        let mut resumed = journal.reader(1);
+        assert_eq!(next_type(&mut resumed).await, "session_roots_changed");
+        assert_eq!(next_type(&mut resumed).await, "gateway_stopped");
+
+        // Once the bus is gone the reader drains and ends
+        drop(sender);
+        drop(bus);
+        let end = tokio::time::timeout(Duration::from_secs(5), live.next()).await;
+        assert!(end.expect("writer stopped").unwrap().is_none());
+    }
+}
@@ -11,11 +11,13 @@
 //! - `service` - Domain services
 //! - `application` - Application services with event emission
 //! - `event_bus` - Central event distribution system
+//! - `event_journal` - Persistent, sequenced record of domain events
 
 pub mod application;
 pub mod branding;
 pub mod domain;
 pub mod event_bus;
+pub mod event_journal;
 pub mod registry;
 pub mod repository;
 pub mod service;
@@ -33,6 +35,7 @@ pub use application::{
 pub use event_bus::{
     create_shared_event_bus, EventBus, EventReceiver, EventSender, SharedEventBus,
 };
+pub use event_journal::{EventJournal, JournalReader, DEFAULT_JOURNAL_RETENTION};
 
 use std::path::{Path, PathBuf};
 
@@ -167,6 +167,10 @@ pub struct InputDefinition {
     /// Maximum length for text inputs
     pub max_length: Option<usize>,
 
+    /// Whether the value may span several lines
+    #[serde(default)]
+    pub multiline: bool,
+
     /// Options for select type
     #[serde(default)]
     pub options: Vec<SelectOption>,
@@ -387,6 +391,10 @@ pub enum TransportConfig {
         #[serde(default)]
         headers: HashMap<String, String>,
 
+        /// Query parameters added to the URL (can use ${input:xxx} placeholders)
+        #[serde(default)]
+        query: HashMap<String, String>,
+
         /// Transport metadata (inputs, etc.)
         #[serde(default)]
         metadata: TransportMetadata,
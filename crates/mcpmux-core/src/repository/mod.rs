@@ -4,11 +4,13 @@
 //! the implementation (SQLite, in-memory, etc.)
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::domain::{
-    Client, Credential, CredentialType, FeatureSet, FeatureSetMember, InstalledServer, MemberMode,
-    OutboundOAuthRegistration, ServerFeature, Space, SpaceBaseDir, WorkspaceBinding,
+    Client, Credential, CredentialType, DomainEvent, EnvironmentProfile, FeatureSet,
+    FeatureSetMember, InstalledServer, JournaledEvent, MemberMode, OutboundOAuthRegistration,
+    ServerFeature, ServerGroup, Space, SpaceBaseDir, User, WorkspaceBinding,
 };
 
 /// Result type for repository operations
@@ -37,6 +39,19 @@ pub trait SpaceRepository: Send + Sync {
 
     /// Set a space as default
     async fn set_default(&self, id: &Uuid) -> RepoResult<()>;
+
+    /// Give a space to a user, or back to admins only with `None`
+    async fn set_owner(&self, id: &Uuid, owner_id: Option<&Uuid>) -> RepoResult<()>;
+
+    /// Spaces a user may manage: all of them for admins, else those they own
+    async fn list_for_user(&self, user: &User) -> RepoResult<Vec<Space>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|space| user.can_access_space(space))
+            .collect())
+    }
 }
 
 /// Per-Space base-directory repository.
@@ -99,6 +114,16 @@ pub trait InstalledServerRepository: Send + Sync {
     /// Uninstall a server (delete)
     async fn uninstall(&self, id: &Uuid) -> RepoResult<()>;
 
+    /// Move a server to the trash. Trashed servers are left out of every
+    /// other query until restored.
+    async fn trash(&self, id: &Uuid, at: DateTime<Utc>) -> RepoResult<()>;
+
+    /// Take a server out of the trash
+    async fn restore(&self, id: &Uuid) -> RepoResult<()>;
+
+    /// List trashed servers across all spaces, most recently trashed first
+    async fn list_trashed(&self) -> RepoResult<Vec<InstalledServer>>;
+
     /// Get enabled servers for a space
     async fn list_enabled(&self, space_id: &str) -> RepoResult<Vec<InstalledServer>>;
 
@@ -111,6 +136,9 @@ pub trait InstalledServerRepository: Send + Sync {
     /// Set OAuth connected status
     async fn set_oauth_connected(&self, id: &Uuid, connected: bool) -> RepoResult<()>;
 
+    /// Record when a tool call to the server last succeeded
+    async fn set_last_used(&self, id: &Uuid, at: DateTime<Utc>) -> RepoResult<()>;
+
     /// Update input values for a server
     async fn update_inputs(
         &self,
@@ -211,6 +239,103 @@ pub trait FeatureSetRepository: Send + Sync {
     async fn get_feature_members(&self, feature_set_id: &str) -> RepoResult<Vec<FeatureSetMember>>;
 }
 
+/// ServerGroup repository trait
+#[async_trait]
+pub trait ServerGroupRepository: Send + Sync {
+    /// Get the groups of a space, ordered by name
+    async fn list_for_space(&self, space_id: &str) -> RepoResult<Vec<ServerGroup>>;
+
+    /// Get a group by ID
+    async fn get(&self, id: &str) -> RepoResult<Option<ServerGroup>>;
+
+    /// Create a new group
+    async fn create(&self, group: &ServerGroup) -> RepoResult<()>;
+
+    /// Update a group (name, icon, servers, feature set)
+    async fn update(&self, group: &ServerGroup) -> RepoResult<()>;
+
+    /// Delete a group (its servers and feature set are kept)
+    async fn delete(&self, id: &str) -> RepoResult<()>;
+}
+
+/// User repository trait
+///
+/// Users authenticate with their own admin token; only its hash is stored.
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    /// Get all users, by name
+    async fn list(&self) -> RepoResult<Vec<User>>;
+
+    /// Get a user by ID
+    async fn get(&self, id: &Uuid) -> RepoResult<Option<User>>;
+
+    /// Get a user by name, ignoring case
+    async fn get_by_name(&self, name: &str) -> RepoResult<Option<User>>;
+
+    /// Get the user whose admin token hashes to `token_hash`
+    async fn get_by_token_hash(&self, token_hash: &str) -> RepoResult<Option<User>>;
+
+    /// Create a user (without a token)
+    async fn create(&self, user: &User) -> RepoResult<()>;
+
+    /// Update a user's name and role
+    async fn update(&self, user: &User) -> RepoResult<()>;
+
+    /// Replace a user's admin token hash, or clear it with `None`
+    async fn set_token_hash(&self, id: &Uuid, token_hash: Option<&str>) -> RepoResult<()>;
+
+    /// Delete a user; what they owned goes back to admins only
+    async fn delete(&self, id: &Uuid) -> RepoResult<()>;
+}
+
+/// Event journal repository trait
+///
+/// An append-only log of domain events. Sequence numbers start at 1 and
+/// keep increasing across restarts and pruning.
+#[async_trait]
+pub trait EventJournalRepository: Send + Sync {
+    /// Record an event, returning its sequence number
+    async fn append(&self, event: &DomainEvent) -> RepoResult<u64>;
+
+    /// Events recorded after `sequence`, oldest first, at most `limit`
+    async fn since(&self, sequence: u64, limit: usize) -> RepoResult<Vec<JournaledEvent>>;
+
+    /// Sequence number of the newest event, or 0 if the journal is empty
+    async fn latest_sequence(&self) -> RepoResult<u64>;
+
+    /// Delete all but the newest `keep` events, returning how many were deleted
+    async fn prune(&self, keep: u64) -> RepoResult<u64>;
+}
+
+/// EnvironmentProfile repository trait
+///
+/// Profile input values may hold secrets; implementations store them
+/// encrypted, like installed servers' input values.
+#[async_trait]
+pub trait EnvironmentProfileRepository: Send + Sync {
+    /// Get the profiles of a space, ordered by name
+    async fn list_for_space(&self, space_id: &str) -> RepoResult<Vec<EnvironmentProfile>>;
+
+    /// Get a profile by ID
+    async fn get(&self, id: &str) -> RepoResult<Option<EnvironmentProfile>>;
+
+    /// Get the active profile of a space, if one is selected
+    async fn get_active(&self, space_id: &str) -> RepoResult<Option<EnvironmentProfile>>;
+
+    /// Create a new profile
+    async fn create(&self, profile: &EnvironmentProfile) -> RepoResult<()>;
+
+    /// Update a profile's name and overrides
+    async fn update(&self, profile: &EnvironmentProfile) -> RepoResult<()>;
+
+    /// Delete a profile
+    async fn delete(&self, id: &str) -> RepoResult<()>;
+
+    /// Make a profile the active one of its space, or clear the active
+    /// profile with `None`
+    async fn set_active(&self, space_id: &str, profile_id: Option<&str>) -> RepoResult<()>;
+}
+
 /// Inbound MCP Client repository trait
 ///
 /// Manages MCP client entities (apps connecting TO McpMux).
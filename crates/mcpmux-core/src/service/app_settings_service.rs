@@ -8,6 +8,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use crate::service::{ConflictStrategy, SyncTargetConfig};
 use crate::AppSettingsRepository;
 
 // =============================================================================
@@ -22,6 +23,8 @@ pub mod keys {
         pub const PORT: &str = "gateway.port";
         /// Auto-start gateway on app launch (bool)
         pub const AUTO_START: &str = "gateway.auto_start";
+        /// SHA-256 hex of the remote admin API token (unset = admin API off)
+        pub const ADMIN_TOKEN_HASH: &str = "gateway.admin_token_hash";
     }
 
     /// OAuth callback settings namespace
@@ -42,12 +45,55 @@ pub mod keys {
     pub mod logs {
         /// Number of days to retain log files (u32, 0 = keep forever)
         pub const RETENTION_DAYS: &str = "logs.retention_days";
+        /// Log filter, e.g. "debug" or "info,mcpmux_gateway=trace" (unset = built-in default)
+        pub const LEVEL: &str = "logs.level";
+    }
+
+    /// Backup settings namespace
+    pub mod backup {
+        /// Hours between scheduled backups (u32, 0 = disabled)
+        pub const INTERVAL_HOURS: &str = "backup.interval_hours";
+        /// Number of scheduled backups to keep (u32)
+        pub const KEEP_COUNT: &str = "backup.keep_count";
+    }
+
+    /// Config sync settings namespace
+    pub mod sync {
+        /// Where to sync (JSON `SyncTargetConfig`, absent = sync disabled)
+        pub const TARGET: &str = "sync.target";
+        /// How to resolve conflicting edits (`ConflictStrategy`)
+        pub const CONFLICT_STRATEGY: &str = "sync.conflict_strategy";
+        /// Minutes between automatic syncs (u32, 0 = manual only)
+        pub const INTERVAL_MINUTES: &str = "sync.interval_minutes";
     }
 
     /// Registry settings namespace
     pub mod registry {
         /// Cached ETag from last bundle fetch
         pub const BUNDLE_ETAG: &str = "registry.bundle_etag";
+        /// Cached Last-Modified from last bundle fetch
+        pub const BUNDLE_LAST_MODIFIED: &str = "registry.bundle_last_modified";
+        /// Configured registries (JSON `Vec<RegistrySource>`, absent = public registry only)
+        pub const SOURCES: &str = "registry.sources";
+        /// Trusted publisher keys (JSON `Vec<PublisherKey>`)
+        pub const PUBLISHER_KEYS: &str = "registry.publisher_keys";
+    }
+
+    /// Managed runtimes namespace
+    pub mod runtimes {
+        /// Whether stdio servers use McpMux-installed Node.js/uv (bool)
+        pub const MANAGED_ENABLED: &str = "runtimes.managed_enabled";
+    }
+
+    /// Installed servers namespace
+    pub mod servers {
+        /// Days uninstalled servers stay in the trash (u32, 0 = until purged)
+        pub const TRASH_RETENTION_DAYS: &str = "servers.trash_retention_days";
+        /// Days without a successful tool call before an enabled server is
+        /// flagged as unused (u32, 0 = never)
+        pub const UNUSED_DAYS: &str = "servers.unused_days";
+        /// Whether unused servers are disabled automatically (bool)
+        pub const AUTO_DISABLE_UNUSED: &str = "servers.auto_disable_unused";
     }
 }
 
@@ -264,6 +310,214 @@ impl AppSettingsService {
             .await
     }
 
+    /// Get the persisted log filter, if one was set.
+    pub async fn get_log_level(&self) -> Option<String> {
+        self.repository
+            .get(keys::logs::LEVEL)
+            .await
+            .ok()
+            .flatten()
+            .filter(|level| !level.trim().is_empty())
+    }
+
+    /// Set the log filter, or clear it with `None` to use the default.
+    pub async fn set_log_level(&self, level: Option<&str>) -> anyhow::Result<()> {
+        info!("[Settings] Setting log level to {:?}", level);
+        match level.map(str::trim).filter(|level| !level.is_empty()) {
+            Some(level) => self.repository.set(keys::logs::LEVEL, level).await,
+            None => self.repository.delete(keys::logs::LEVEL).await,
+        }
+    }
+
+    // =========================================================================
+    // Backup settings
+    // =========================================================================
+
+    /// Default interval between scheduled backups (daily)
+    pub const DEFAULT_BACKUP_INTERVAL_HOURS: u32 = 24;
+
+    /// Default number of scheduled backups kept
+    pub const DEFAULT_BACKUP_KEEP_COUNT: u32 = 7;
+
+    /// Get the scheduled backup interval in hours (0 = disabled).
+    pub async fn get_backup_interval_hours(&self) -> u32 {
+        self.get_typed(keys::backup::INTERVAL_HOURS)
+            .await
+            .unwrap_or(Self::DEFAULT_BACKUP_INTERVAL_HOURS)
+    }
+
+    /// Set the scheduled backup interval in hours (0 = disabled).
+    pub async fn set_backup_interval_hours(&self, hours: u32) -> anyhow::Result<()> {
+        info!("[Settings] Setting backup interval to {} hours", hours);
+        self.repository
+            .set(keys::backup::INTERVAL_HOURS, &hours.to_string())
+            .await
+    }
+
+    /// Get how many scheduled backups to keep.
+    pub async fn get_backup_keep_count(&self) -> u32 {
+        self.get_typed(keys::backup::KEEP_COUNT)
+            .await
+            .unwrap_or(Self::DEFAULT_BACKUP_KEEP_COUNT)
+    }
+
+    /// Set how many scheduled backups to keep.
+    pub async fn set_backup_keep_count(&self, count: u32) -> anyhow::Result<()> {
+        info!("[Settings] Keeping {} scheduled backups", count);
+        self.repository
+            .set(keys::backup::KEEP_COUNT, &count.to_string())
+            .await
+    }
+
+    // =========================================================================
+    // Config sync settings
+    // =========================================================================
+
+    /// Default interval between automatic syncs
+    pub const DEFAULT_SYNC_INTERVAL_MINUTES: u32 = 15;
+
+    /// Get the configured sync target (`None` = sync disabled).
+    pub async fn get_sync_target(&self) -> Option<SyncTargetConfig> {
+        self.get_typed(keys::sync::TARGET).await
+    }
+
+    /// Set or clear the sync target.
+    pub async fn set_sync_target(&self, target: Option<&SyncTargetConfig>) -> anyhow::Result<()> {
+        match target {
+            Some(target) => {
+                info!("[Settings] Setting sync target to {:?}", target);
+                self.set_typed(keys::sync::TARGET, target).await
+            }
+            None => {
+                info!("[Settings] Disabling config sync");
+                self.repository.delete(keys::sync::TARGET).await
+            }
+        }
+    }
+
+    /// Get the sync conflict strategy.
+    pub async fn get_sync_conflict_strategy(&self) -> ConflictStrategy {
+        self.get_typed(keys::sync::CONFLICT_STRATEGY)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Set the sync conflict strategy.
+    pub async fn set_sync_conflict_strategy(
+        &self,
+        strategy: ConflictStrategy,
+    ) -> anyhow::Result<()> {
+        info!(
+            "[Settings] Setting sync conflict strategy to {:?}",
+            strategy
+        );
+        self.set_typed(keys::sync::CONFLICT_STRATEGY, &strategy)
+            .await
+    }
+
+    /// Get the automatic sync interval in minutes (0 = manual only).
+    pub async fn get_sync_interval_minutes(&self) -> u32 {
+        self.get_typed(keys::sync::INTERVAL_MINUTES)
+            .await
+            .unwrap_or(Self::DEFAULT_SYNC_INTERVAL_MINUTES)
+    }
+
+    /// Set the automatic sync interval in minutes (0 = manual only).
+    pub async fn set_sync_interval_minutes(&self, minutes: u32) -> anyhow::Result<()> {
+        info!("[Settings] Setting sync interval to {} minutes", minutes);
+        self.repository
+            .set(keys::sync::INTERVAL_MINUTES, &minutes.to_string())
+            .await
+    }
+
+    // =========================================================================
+    // Managed runtimes settings
+    // =========================================================================
+
+    /// Get whether stdio servers use managed runtimes (default: false).
+    pub async fn get_managed_runtimes_enabled(&self) -> bool {
+        self.get_string(keys::runtimes::MANAGED_ENABLED)
+            .await
+            .map(|v| v == "true")
+            .unwrap_or(false)
+    }
+
+    /// Set whether stdio servers use managed runtimes.
+    pub async fn set_managed_runtimes_enabled(&self, enabled: bool) -> anyhow::Result<()> {
+        info!("[Settings] Setting managed runtimes enabled to {}", enabled);
+        self.repository
+            .set(
+                keys::runtimes::MANAGED_ENABLED,
+                if enabled { "true" } else { "false" },
+            )
+            .await
+    }
+
+    // =========================================================================
+    // Installed server settings
+    // =========================================================================
+
+    /// Default time uninstalled servers can be restored (30 days)
+    pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+    /// Get how many days uninstalled servers stay in the trash (0 = until
+    /// purged).
+    pub async fn get_trash_retention_days(&self) -> u32 {
+        self.get_typed(keys::servers::TRASH_RETENTION_DAYS)
+            .await
+            .unwrap_or(Self::DEFAULT_TRASH_RETENTION_DAYS)
+    }
+
+    /// Set how many days uninstalled servers stay in the trash.
+    pub async fn set_trash_retention_days(&self, days: u32) -> anyhow::Result<()> {
+        info!("[Settings] Keeping trashed servers for {} days", days);
+        self.repository
+            .set(keys::servers::TRASH_RETENTION_DAYS, &days.to_string())
+            .await
+    }
+
+    /// Default time before an enabled server counts as unused (30 days)
+    pub const DEFAULT_UNUSED_DAYS: u32 = 30;
+
+    /// Get how many days without a successful tool call flag a server as
+    /// unused (0 = never).
+    pub async fn get_unused_days(&self) -> u32 {
+        self.get_typed(keys::servers::UNUSED_DAYS)
+            .await
+            .unwrap_or(Self::DEFAULT_UNUSED_DAYS)
+    }
+
+    /// Set how many days without a successful tool call flag a server as
+    /// unused.
+    pub async fn set_unused_days(&self, days: u32) -> anyhow::Result<()> {
+        info!("[Settings] Flagging servers unused for {} days", days);
+        self.repository
+            .set(keys::servers::UNUSED_DAYS, &days.to_string())
+            .await
+    }
+
+    /// Check if unused servers are disabled automatically (default: false).
+    pub async fn get_auto_disable_unused(&self) -> bool {
+        self.get_string(keys::servers::AUTO_DISABLE_UNUSED)
+            .await
+            .map(|v| v == "true")
+            .unwrap_or(false)
+    }
+
+    /// Set whether unused servers are disabled automatically.
+    pub async fn set_auto_disable_unused(&self, enabled: bool) -> anyhow::Result<()> {
+        info!(
+            "[Settings] Setting auto-disable unused servers to {}",
+            enabled
+        );
+        self.repository
+            .set(
+                keys::servers::AUTO_DISABLE_UNUSED,
+                if enabled { "true" } else { "false" },
+            )
+            .await
+    }
+
     // =========================================================================
     // Utility methods
     // =========================================================================
@@ -371,6 +625,18 @@ mod tests {
         assert!(service.get_gateway_auto_start().await);
     }
 
+    #[tokio::test]
+    async fn test_managed_runtimes_enabled() {
+        let repo = Arc::new(InMemorySettingsRepository::new());
+        let service = AppSettingsService::new(repo);
+
+        // Default is false
+        assert!(!service.get_managed_runtimes_enabled().await);
+
+        service.set_managed_runtimes_enabled(true).await.unwrap();
+        assert!(service.get_managed_runtimes_enabled().await);
+    }
+
     #[tokio::test]
     async fn test_theme() {
         let repo = Arc::new(InMemorySettingsRepository::new());
@@ -384,6 +650,49 @@ mod tests {
         assert_eq!(service.get_theme().await, "dark");
     }
 
+    #[tokio::test]
+    async fn test_backup_settings() {
+        let repo = Arc::new(InMemorySettingsRepository::new());
+        let service = AppSettingsService::new(repo);
+
+        assert_eq!(service.get_backup_interval_hours().await, 24);
+        assert_eq!(service.get_backup_keep_count().await, 7);
+
+        service.set_backup_interval_hours(0).await.unwrap();
+        service.set_backup_keep_count(3).await.unwrap();
+        assert_eq!(service.get_backup_interval_hours().await, 0);
+        assert_eq!(service.get_backup_keep_count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_sync_settings() {
+        let repo = Arc::new(InMemorySettingsRepository::new());
+        let service = AppSettingsService::new(repo);
+
+        assert!(service.get_sync_target().await.is_none());
+        assert_eq!(
+            service.get_sync_conflict_strategy().await,
+            ConflictStrategy::NewestWins
+        );
+
+        let target = SyncTargetConfig::File {
+            path: "/mnt/share/mcpmux-sync.json".into(),
+        };
+        service.set_sync_target(Some(&target)).await.unwrap();
+        service
+            .set_sync_conflict_strategy(ConflictStrategy::PreferRemote)
+            .await
+            .unwrap();
+        assert_eq!(service.get_sync_target().await, Some(target));
+        assert_eq!(
+            service.get_sync_conflict_strategy().await,
+            ConflictStrategy::PreferRemote
+        );
+
+        service.set_sync_target(None).await.unwrap();
+        assert!(service.get_sync_target().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_typed_json_value() {
         let repo = Arc::new(InMemorySettingsRepository::new());
@@ -425,4 +734,33 @@ mod tests {
         service.set_log_retention_days(0).await.unwrap();
         assert_eq!(service.get_log_retention_days().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_unused_server_settings() {
+        let repo = Arc::new(InMemorySettingsRepository::new());
+        let service = AppSettingsService::new(repo);
+
+        assert_eq!(service.get_unused_days().await, 30);
+        assert!(!service.get_auto_disable_unused().await);
+
+        service.set_unused_days(14).await.unwrap();
+        service.set_auto_disable_unused(true).await.unwrap();
+        assert_eq!(service.get_unused_days().await, 14);
+        assert!(service.get_auto_disable_unused().await);
+    }
+
+    #[tokio::test]
+    async fn test_log_level() {
+        let repo = Arc::new(InMemorySettingsRepository::new());
+        let service = AppSettingsService::new(repo);
+
+        assert_eq!(service.get_log_level().await, None);
+
+        service.set_log_level(Some(" debug ")).await.unwrap();
+        assert_eq!(service.get_log_level().await.as_deref(), Some("debug"));
+
+        // Blank clears back to the default
+        service.set_log_level(Some("")).await.unwrap();
+        assert_eq!(service.get_log_level().await, None);
+    }
 }
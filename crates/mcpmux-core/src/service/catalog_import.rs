@@ -0,0 +1,632 @@
+//! Importers for third-party server catalogs: Smithery and mcp.run.
+//!
+//! Both catalogs list servers that run on their own hosting, so their entries
+//! become HTTP servers:
+//!
+//! - **Smithery**: a deployed server is reached at `<deploymentUrl>/mcp`.
+//!   The Smithery API key and the server's settings (its `configSchema`) are
+//!   passed as query parameters, each backed by an input. Servers without an
+//!   HTTP connection are skipped; their stdio setup is a JavaScript function
+//!   that only Smithery's CLI can run.
+//! - **mcp.run**: servlets run inside an mcp.run profile, so each entry asks
+//!   for the MCP URL of the profile the servlet is installed into.
+//!
+//! Imported entries are ordinary [`ServerDefinition`]s, so they are browsed,
+//! searched and installed like any registry's servers.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::official_registry_client::input_id;
+use crate::domain::{
+    AuthConfig, HostingType, InputDefinition, InputOption, PublisherInfo, RegistryAuth,
+    ServerDefinition, ServerSource, TransportConfig, TransportMetadata,
+};
+
+/// URL of the Smithery registry API
+pub const SMITHERY_REGISTRY_URL: &str = "https://registry.smithery.ai";
+
+/// URL of mcp.run
+pub const MCP_RUN_URL: &str = "https://www.mcp.run";
+
+/// Entries requested per Smithery page
+const SMITHERY_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on Smithery servers per fetch. Each one needs its own detail
+/// request, so the catalog is capped rather than fetched whole.
+const MAX_SMITHERY_SERVERS: usize = 300;
+
+/// Input that holds the Smithery API key
+const SMITHERY_API_KEY_INPUT: &str = "SMITHERY_API_KEY";
+
+/// Input that holds the mcp.run profile URL
+const MCP_RUN_PROFILE_INPUT: &str = "MCP_RUN_PROFILE_URL";
+
+/// A third-party catalog servers can be imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Catalog {
+    Smithery,
+    McpRun,
+}
+
+impl Catalog {
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Smithery => "Smithery",
+            Self::McpRun => "mcp.run",
+        }
+    }
+}
+
+// ============================================
+// Smithery
+// ============================================
+
+/// A Smithery server as listed by `/servers`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmitheryServerSummary {
+    pub qualified_name: String,
+    #[serde(default)]
+    pub is_deployed: bool,
+}
+
+/// A Smithery server as returned by `/servers/{qualifiedName}`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmitheryServer {
+    /// e.g. `@owner/weather` or `exa`
+    pub qualified_name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub deployment_url: Option<String>,
+    #[serde(default)]
+    pub connections: Vec<SmitheryConnection>,
+}
+
+/// A way to connect to a Smithery server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmitheryConnection {
+    /// `http` or `stdio`
+    #[serde(rename = "type")]
+    pub connection_type: String,
+    #[serde(default)]
+    pub deployment_url: Option<String>,
+    /// JSON schema of the server's settings
+    #[serde(default)]
+    pub config_schema: Option<Value>,
+}
+
+/// Whether a setting name looks like it holds a credential
+fn looks_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["key", "token", "secret", "password"]
+        .iter()
+        .any(|word| name.contains(word))
+}
+
+/// Inputs for the top-level properties of a JSON schema, as
+/// `(property name, input)` pairs in schema order
+fn schema_inputs(schema: &Value) -> Vec<(String, InputDefinition)> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return vec![];
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let choices: Vec<String> = property
+                .get("enum")
+                .and_then(Value::as_array)
+                .map(|e| {
+                    e.iter()
+                        .filter_map(|v| match v {
+                            Value::String(s) => Some(s.clone()),
+                            Value::Number(n) => Some(n.to_string()),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let input_type = match property.get("type").and_then(Value::as_str) {
+                _ if !choices.is_empty() => "select",
+                Some("number") | Some("integer") => "number",
+                Some("boolean") => "boolean",
+                _ => "text",
+            };
+            let default = property.get("default").and_then(|d| match d {
+                Value::String(s) => Some(s.clone()),
+                Value::Null => None,
+                other => Some(other.to_string()),
+            });
+            let input = InputDefinition {
+                id: input_id(name),
+                label: property
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or(name)
+                    .to_string(),
+                r#type: input_type.to_string(),
+                required: required.contains(&name.as_str()),
+                secret: looks_secret(name),
+                description: property
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                default,
+                placeholder: None,
+                obtain_url: None,
+                obtain_instructions: None,
+                options: choices
+                    .into_iter()
+                    .map(|value| InputOption {
+                        value,
+                        label: None,
+                        description: None,
+                    })
+                    .collect(),
+                pattern: None,
+                pattern_error: None,
+                multiline: false,
+            };
+            (name.clone(), input)
+        })
+        .collect()
+}
+
+/// Map a Smithery server to a server definition. `None` if it has no HTTP
+/// connection with a deployment URL.
+pub fn smithery_server_to_definition(server: &SmitheryServer) -> Option<ServerDefinition> {
+    let connection = server
+        .connections
+        .iter()
+        .find(|c| c.connection_type == "http")?;
+    let deployment_url = connection
+        .deployment_url
+        .as_ref()
+        .or(server.deployment_url.as_ref())?
+        .trim_end_matches('/');
+    let url = if deployment_url.ends_with("/mcp") {
+        deployment_url.to_string()
+    } else {
+        format!("{}/mcp", deployment_url)
+    };
+
+    let mut inputs = vec![InputDefinition {
+        id: SMITHERY_API_KEY_INPUT.to_string(),
+        label: "Smithery API Key".to_string(),
+        r#type: "text".to_string(),
+        required: true,
+        secret: true,
+        description: Some("Authenticates the connection to Smithery's hosting".to_string()),
+        default: None,
+        placeholder: None,
+        obtain_url: Some("https://smithery.ai/account/api-keys".to_string()),
+        obtain_instructions: None,
+        options: vec![],
+        pattern: None,
+        pattern_error: None,
+        multiline: false,
+    }];
+    let mut query = HashMap::from([(
+        "api_key".to_string(),
+        format!("${{input:{}}}", SMITHERY_API_KEY_INPUT),
+    )]);
+    if let Some(ref schema) = connection.config_schema {
+        for (name, input) in schema_inputs(schema) {
+            if inputs.iter().any(|i| i.id == input.id) {
+                continue;
+            }
+            query.insert(name, format!("${{input:{}}}", input.id));
+            inputs.push(input);
+        }
+    }
+
+    let name = server.qualified_name.trim_start_matches('@');
+    let publisher = name.split_once('/').map(|(owner, _)| PublisherInfo {
+        name: owner.to_string(),
+        domain: None,
+        verified: false,
+        official: false,
+    });
+    let short_name = name.rsplit('/').next().unwrap_or(name);
+
+    Some(ServerDefinition {
+        id: server.qualified_name.clone(),
+        name: server
+            .display_name
+            .clone()
+            .unwrap_or_else(|| short_name.to_string()),
+        description: server.description.clone(),
+        alias: None,
+        auth: Some(AuthConfig::ApiKey {
+            instructions: Some("Needs a Smithery API key".to_string()),
+        }),
+        icon: server.icon_url.clone(),
+        transport: TransportConfig::Http {
+            url,
+            headers: HashMap::new(),
+            query,
+            metadata: TransportMetadata { inputs },
+        },
+        categories: vec![],
+        publisher,
+        source: ServerSource::default(),
+        badges: vec![],
+        hosting_type: HostingType::Remote,
+        license: None,
+        license_url: None,
+        installation: None,
+        capabilities: None,
+        sponsored: None,
+        media: None,
+        changelog_url: server.homepage.clone(),
+        timeouts: Default::default(),
+        depends_on: vec![],
+        signature: None,
+        signature_status: Default::default(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct SmitheryServerList {
+    #[serde(default)]
+    servers: Vec<SmitheryServerSummary>,
+    #[serde(default)]
+    pagination: Option<SmitheryPagination>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SmitheryPagination {
+    current_page: u32,
+    total_pages: u32,
+}
+
+// ============================================
+// mcp.run
+// ============================================
+
+/// An mcp.run servlet
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpRunServlet {
+    /// `owner/name`
+    pub slug: String,
+    #[serde(default)]
+    pub meta: McpRunServletMeta,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct McpRunServletMeta {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Map an mcp.run servlet to a server definition. Its URL is that of the
+/// mcp.run profile it's installed into, which the user provides on install.
+pub fn mcp_run_servlet_to_definition(
+    base_url: &str,
+    servlet: &McpRunServlet,
+) -> Option<ServerDefinition> {
+    let (owner, name) = servlet.slug.split_once('/')?;
+    let page = format!("{}/{}", base_url.trim_end_matches('/'), servlet.slug);
+
+    Some(ServerDefinition {
+        id: format!("mcp.run/{}", servlet.slug),
+        name: servlet
+            .meta
+            .title
+            .clone()
+            .unwrap_or_else(|| name.to_string()),
+        description: servlet.meta.description.clone(),
+        alias: None,
+        auth: Some(AuthConfig::ApiKey {
+            instructions: Some(
+                "Install the servlet into an mcp.run profile, then use the profile's MCP URL"
+                    .to_string(),
+            ),
+        }),
+        icon: None,
+        transport: TransportConfig::Http {
+            url: format!("${{input:{}}}", MCP_RUN_PROFILE_INPUT),
+            headers: HashMap::new(),
+            query: HashMap::new(),
+            metadata: TransportMetadata {
+                inputs: vec![InputDefinition {
+                    id: MCP_RUN_PROFILE_INPUT.to_string(),
+                    label: "mcp.run Profile URL".to_string(),
+                    r#type: "url".to_string(),
+                    required: true,
+                    // The URL is signed and grants access to the profile
+                    secret: true,
+                    description: Some(
+                        "MCP URL of the mcp.run profile the servlet is installed into"
+                            .to_string(),
+                    ),
+                    default: None,
+                    placeholder: Some("https://www.mcp.run/api/mcp/...".to_string()),
+                    obtain_url: Some(page.clone()),
+                    obtain_instructions: Some(
+                        "Install the servlet into a profile on mcp.run and copy the profile's MCP URL"
+                            .to_string(),
+                    ),
+                    options: vec![],
+                    pattern: None,
+                    pattern_error: None,
+                    multiline: false,
+                }],
+            },
+        },
+        categories: vec![],
+        publisher: Some(PublisherInfo {
+            name: owner.to_string(),
+            domain: None,
+            verified: false,
+            official: false,
+        }),
+        source: ServerSource::default(),
+        badges: vec![],
+        hosting_type: HostingType::Remote,
+        license: None,
+        license_url: None,
+        installation: None,
+        capabilities: None,
+        sponsored: None,
+        media: None,
+        changelog_url: Some(page),
+        timeouts: Default::default(),
+        depends_on: vec![],
+        signature: None,
+        signature_status: Default::default(),
+    })
+}
+
+/// `/api/servlets` returns a list, or the list under `servlets`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum McpRunServletList {
+    List(Vec<Value>),
+    Wrapped { servlets: Vec<Value> },
+}
+
+// ============================================
+// Client Implementation
+// ============================================
+
+/// Client that imports the servers of a third-party catalog
+pub struct CatalogImportClient {
+    catalog: Catalog,
+    base_url: String,
+    client: reqwest::Client,
+    auth: Option<RegistryAuth>,
+}
+
+impl CatalogImportClient {
+    pub fn new(catalog: Catalog, base_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("McpMux/1.0")
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            catalog,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            auth: None,
+        }
+    }
+
+    /// Send credentials with every request (Smithery requires an API key as
+    /// a bearer token)
+    pub fn with_auth(mut self, auth: RegistryAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut request = self.client.get(url);
+        request = match &self.auth {
+            Some(RegistryAuth::Bearer { token }) => request.bearer_auth(token),
+            Some(RegistryAuth::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            None => request,
+        };
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", self.catalog.label()))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("{} returned status: {}", self.catalog.label(), status);
+        }
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", self.catalog.label()))
+    }
+
+    /// Fetch the catalog's servers. Entries that can't be mapped are skipped.
+    pub async fn fetch_servers(&self) -> Result<Vec<ServerDefinition>> {
+        match self.catalog {
+            Catalog::Smithery => self.fetch_smithery().await,
+            Catalog::McpRun => self.fetch_mcp_run().await,
+        }
+    }
+
+    async fn fetch_smithery(&self) -> Result<Vec<ServerDefinition>> {
+        tracing::info!("Fetching Smithery servers from {}", self.base_url);
+
+        // Only deployed servers can be reached over HTTP
+        let mut names = Vec::new();
+        let mut page = 1;
+        while names.len() < MAX_SMITHERY_SERVERS {
+            let url = format!(
+                "{}/servers?q={}&page={}&pageSize={}",
+                self.base_url,
+                urlencoding::encode("is:deployed"),
+                page,
+                SMITHERY_PAGE_SIZE
+            );
+            let list: SmitheryServerList = self.get_json(&url).await?;
+            let count = list.servers.len();
+            names.extend(
+                list.servers
+                    .into_iter()
+                    .filter(|s| s.is_deployed)
+                    .map(|s| s.qualified_name),
+            );
+            let last_page = list
+                .pagination
+                .is_none_or(|p| p.current_page >= p.total_pages);
+            if count == 0 || last_page {
+                break;
+            }
+            page += 1;
+        }
+        names.truncate(MAX_SMITHERY_SERVERS);
+
+        let mut definitions = Vec::new();
+        for name in names {
+            // Qualified names are `@owner/name`, which the API takes as a path
+            let url = format!("{}/servers/{}", self.base_url, name);
+            let server: SmitheryServer = match self.get_json(&url).await {
+                Ok(server) => server,
+                Err(e) => {
+                    tracing::debug!("Skipping Smithery server {}: {}", name, e);
+                    continue;
+                }
+            };
+            match smithery_server_to_definition(&server) {
+                Some(definition) => definitions.push(definition),
+                None => tracing::debug!("Skipping Smithery server {}: no HTTP connection", name),
+            }
+        }
+
+        tracing::info!("Imported {} servers from Smithery", definitions.len());
+        Ok(definitions)
+    }
+
+    async fn fetch_mcp_run(&self) -> Result<Vec<ServerDefinition>> {
+        let url = format!("{}/api/servlets", self.base_url);
+        tracing::info!("Fetching mcp.run servlets from {}", url);
+
+        let servlets = match self.get_json::<McpRunServletList>(&url).await? {
+            McpRunServletList::List(servlets) => servlets,
+            McpRunServletList::Wrapped { servlets } => servlets,
+        };
+        let definitions: Vec<_> = servlets
+            .into_iter()
+            .filter_map(|value| serde_json::from_value::<McpRunServlet>(value).ok())
+            .filter_map(|servlet| mcp_run_servlet_to_definition(&self.base_url, &servlet))
+            .collect();
+
+        tracing::info!("Imported {} servlets from mcp.run", definitions.len());
+        Ok(definitions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smithery_server_with_config() {
+        let server: SmitheryServer = serde_json::from_value(serde_json::json!({
+            "qualifiedName": "@acme/weather",
+            "displayName": "Weather",
+            "deploymentUrl": "https://server.smithery.ai/@acme/weather",
+            "connections": [
+                { "type": "stdio", "stdioFunction": "config => ({ command: 'node' })" },
+                {
+                    "type": "http",
+                    "configSchema": {
+                        "type": "object",
+                        "required": ["apiKey"],
+                        "properties": {
+                            "apiKey": { "type": "string", "description": "Weather API key" },
+                            "units": { "type": "string", "enum": ["metric", "imperial"], "default": "metric" }
+                        }
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let definition = smithery_server_to_definition(&server).unwrap();
+        assert_eq!(definition.id, "@acme/weather");
+        assert_eq!(definition.publisher.unwrap().name, "acme");
+        let TransportConfig::Http {
+            url,
+            query,
+            metadata,
+            ..
+        } = definition.transport
+        else {
+            panic!("expected http");
+        };
+        assert_eq!(url, "https://server.smithery.ai/@acme/weather/mcp");
+        assert_eq!(query["api_key"], "${input:SMITHERY_API_KEY}");
+        assert_eq!(query["apiKey"], "${input:APIKEY}");
+        assert_eq!(query["units"], "${input:UNITS}");
+
+        let api_key = metadata.inputs.iter().find(|i| i.id == "APIKEY").unwrap();
+        assert!(api_key.required && api_key.secret);
+        let units = metadata.inputs.iter().find(|i| i.id == "UNITS").unwrap();
+        assert_eq!(units.r#type, "select");
+        assert_eq!(units.default.as_deref(), Some("metric"));
+        assert!(!units.required);
+    }
+
+    #[test]
+    fn test_smithery_stdio_only_is_skipped() {
+        let server: SmitheryServer = serde_json::from_value(serde_json::json!({
+            "qualifiedName": "local-only",
+            "connections": [{ "type": "stdio" }]
+        }))
+        .unwrap();
+        assert!(smithery_server_to_definition(&server).is_none());
+    }
+
+    #[test]
+    fn test_mcp_run_servlet() {
+        let servlet: McpRunServlet = serde_json::from_value(serde_json::json!({
+            "slug": "dylibso/eval-py",
+            "meta": { "description": "Evaluate Python" }
+        }))
+        .unwrap();
+
+        let definition = mcp_run_servlet_to_definition(MCP_RUN_URL, &servlet).unwrap();
+        assert_eq!(definition.id, "mcp.run/dylibso/eval-py");
+        assert_eq!(definition.name, "eval-py");
+        let TransportConfig::Http { url, metadata, .. } = definition.transport else {
+            panic!("expected http");
+        };
+        assert_eq!(url, "${input:MCP_RUN_PROFILE_URL}");
+        assert_eq!(
+            metadata.inputs[0].obtain_url.as_deref(),
+            Some("https://www.mcp.run/dylibso/eval-py")
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Persistent record of spawned stdio server processes.
+//!
+//! Every stdio server the gateway spawns gets a pidfile under
+//! `<data_dir>/pids/`, removed again when the process exits. A pidfile that
+//! outlives its gateway means the gateway crashed or was force-killed, and its
+//! `npx`/`uvx`/... children may still be running, holding ports and file
+//! locks. [`ChildProcessRegistry::reap_orphans`] finds and terminates them on
+//! the next launch.
+//!
+//! PIDs are reused by the OS, so each record also stores the process start
+//! time (for both the child and the gateway that spawned it). A process is
+//! only treated as the recorded one when its start time still matches; when
+//! the start time can't be read, nothing is killed.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// How long a terminated process group gets to exit before it is killed
+#[cfg(unix)]
+const TERMINATE_GRACE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// A spawned stdio server process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildProcessRecord {
+    pub pid: u32,
+    /// Opaque start-time marker, see [`process_start_marker`]
+    pub start_marker: Option<String>,
+    /// The gateway process that spawned it
+    pub owner_pid: u32,
+    pub owner_start_marker: Option<String>,
+    pub space_id: String,
+    pub server_id: String,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+}
+
+impl ChildProcessRecord {
+    fn owner_is_running(&self) -> bool {
+        is_same_process(self.owner_pid, self.owner_start_marker.as_deref())
+    }
+
+    fn is_running(&self) -> bool {
+        is_same_process(self.pid, self.start_marker.as_deref())
+    }
+}
+
+/// Pidfiles for stdio server processes, one JSON file per process
+#[derive(Debug, Clone)]
+pub struct ChildProcessRegistry {
+    dir: PathBuf,
+}
+
+impl ChildProcessRegistry {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("pids"),
+        }
+    }
+
+    fn path_for(&self, pid: u32) -> PathBuf {
+        self.dir.join(format!("{}.json", pid))
+    }
+
+    /// Record a process this gateway just spawned
+    pub fn record(
+        &self,
+        pid: u32,
+        space_id: &str,
+        server_id: &str,
+        command: &str,
+    ) -> Result<ChildProcessRecord> {
+        let owner_pid = std::process::id();
+        let record = ChildProcessRecord {
+            pid,
+            start_marker: process_start_marker(pid),
+            owner_pid,
+            owner_start_marker: process_start_marker(owner_pid),
+            space_id: space_id.to_string(),
+            server_id: server_id.to_string(),
+            command: command.to_string(),
+            started_at: Utc::now(),
+        };
+        self.write(&record)?;
+        Ok(record)
+    }
+
+    fn write(&self, record: &ChildProcessRecord) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path_for(record.pid);
+        std::fs::write(&path, serde_json::to_vec_pretty(record)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Forget a process once it has exited
+    pub fn remove(&self, pid: u32) {
+        let path = self.path_for(pid);
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(
+                    "[ChildProcesses] Failed to remove {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// All recorded processes. Unreadable pidfiles are deleted.
+    pub fn list(&self) -> Vec<ChildProcessRecord> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut records = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<ChildProcessRecord>(&bytes).ok())
+            {
+                Some(record) => records.push(record),
+                None => {
+                    warn!("[ChildProcesses] Discarding unreadable {}", path.display());
+                    let _ = std::fs::remove_file(&path);
+                }
+            }
+        }
+        records.sort_by_key(|r| r.started_at);
+        records
+    }
+
+    /// Terminate processes left behind by a gateway that is no longer running.
+    ///
+    /// Records owned by a live gateway (this one, or another process sharing
+    /// the data directory) are left alone. Returns the processes that were
+    /// terminated. Blocks for up to a couple of seconds per process group
+    /// that ignores SIGTERM, so call it off the async runtime.
+    pub fn reap_orphans(&self) -> Vec<ChildProcessRecord> {
+        let mut reaped = Vec::new();
+        for record in self.list() {
+            if record.owner_is_running() {
+                continue;
+            }
+            if record.is_running() {
+                info!(
+                    pid = record.pid,
+                    server_id = %record.server_id,
+                    "[ChildProcesses] Terminating orphaned {} left by a previous run",
+                    record.command
+                );
+                terminate_process_tree(&record);
+                reaped.push(record.clone());
+            } else {
+                debug!(
+                    pid = record.pid,
+                    "[ChildProcesses] Recorded process already exited"
+                );
+            }
+            self.remove(record.pid);
+        }
+        reaped
+    }
+}
+
+/// True when `pid` is running and started at `marker`
+fn is_same_process(pid: u32, marker: Option<&str>) -> bool {
+    match marker {
+        Some(marker) => process_start_marker(pid).as_deref() == Some(marker),
+        None => false,
+    }
+}
+
+/// A string identifying when `pid` started, or `None` if it isn't running
+/// (or its start time can't be read on this platform)
+#[cfg(target_os = "linux")]
+pub fn process_start_marker(pid: u32) -> Option<String> {
+    // Field 22 of /proc/<pid>/stat is the start time in clock ticks since
+    // boot. The command name (field 2) may contain spaces, so count fields
+    // from the closing parenthesis. A zombie has already exited.
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let mut fields = stat[stat.rfind(')')? + 1..].split_whitespace();
+    if fields.next()? == "Z" {
+        return None;
+    }
+    fields.nth(18).map(str::to_string)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn process_start_marker(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "lstart="])
+        .output()
+        .ok()?;
+    let marker = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !marker.is_empty()).then_some(marker)
+}
+
+#[cfg(windows)]
+pub fn process_start_marker(pid: u32) -> Option<String> {
+    let script = format!(
+        "(Get-Process -Id {} -ErrorAction Stop).StartTime.ToFileTimeUtc()",
+        pid
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .ok()?;
+    let marker = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !marker.is_empty()).then_some(marker)
+}
+
+/// Stop the process and everything it spawned (npx → node, uvx → python)
+#[cfg(unix)]
+fn terminate_process_tree(record: &ChildProcessRecord) {
+    use std::time::{Duration, Instant};
+
+    // Stdio servers are spawned as process-group leaders, so signalling the
+    // negative PID reaches the whole group.
+    let group = format!("-{}", record.pid);
+    let _ = Command::new("kill").args(["-TERM", "--", &group]).output();
+
+    let deadline = Instant::now() + TERMINATE_GRACE;
+    while Instant::now() < deadline {
+        if !record.is_running() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    warn!(
+        pid = record.pid,
+        "[ChildProcesses] Process ignored SIGTERM, killing"
+    );
+    let _ = Command::new("kill").args(["-KILL", "--", &group]).output();
+}
+
+#[cfg(windows)]
+fn terminate_process_tree(record: &ChildProcessRecord) {
+    if let Err(e) = Command::new("taskkill")
+        .args(["/PID", &record.pid.to_string(), "/T", "/F"])
+        .output()
+    {
+        warn!(pid = record.pid, "[ChildProcesses] taskkill failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_for(pid: u32, owner_pid: u32) -> ChildProcessRecord {
+        ChildProcessRecord {
+            pid,
+            start_marker: process_start_marker(pid),
+            owner_pid,
+            owner_start_marker: process_start_marker(owner_pid),
+            space_id: "space".to_string(),
+            server_id: "server".to_string(),
+            command: "sleep".to_string(),
+            started_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn records_round_trip_and_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ChildProcessRegistry::new(dir.path());
+        let record = registry
+            .record(std::process::id(), "space", "server", "node")
+            .unwrap();
+        assert_eq!(registry.list(), vec![record.clone()]);
+
+        // Owned by this (running) process, so nothing is reaped
+        assert!(registry.reap_orphans().is_empty());
+        assert_eq!(registry.list().len(), 1);
+
+        registry.remove(record.pid);
+        assert!(registry.list().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reaps_children_of_a_dead_gateway_only() {
+        use std::os::unix::process::CommandExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ChildProcessRegistry::new(dir.path());
+
+        // An exited process stands in for the crashed gateway
+        let mut dead_owner = Command::new("true").spawn().unwrap();
+        let dead_owner_pid = dead_owner.id();
+        dead_owner.wait().unwrap();
+
+        let mut orphan = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let mut owned = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let mut reused = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+
+        let mut orphan_record = record_for(orphan.id(), dead_owner_pid);
+        orphan_record.owner_start_marker = Some("gone".to_string());
+        registry.write(&orphan_record).unwrap();
+        registry
+            .write(&record_for(owned.id(), std::process::id()))
+            .unwrap();
+        // Same PID, different start time: the PID was reused, don't kill
+        let mut reused_record = record_for(reused.id(), dead_owner_pid);
+        reused_record.start_marker = Some("0".to_string());
+        registry.write(&reused_record).unwrap();
+
+        let reaped = registry.reap_orphans();
+        assert_eq!(reaped, vec![orphan_record]);
+        assert!(orphan.wait().unwrap().code().is_none(), "killed by signal");
+        assert!(owned.try_wait().unwrap().is_none());
+        assert!(reused.try_wait().unwrap().is_none());
+        assert_eq!(registry.list().len(), 1);
+
+        owned.kill().unwrap();
+        reused.kill().unwrap();
+    }
+}
@@ -0,0 +1,318 @@
+//! Installed client detection.
+//!
+//! Scans this machine for the desktop AI clients McpMux works with and for
+//! the MCP config each of them already has: where it is, how many servers
+//! it lists and whether McpMux is one of them. Preset-client seeding and
+//! config export both start from this.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::client_import::strip_jsonc;
+use super::{ImportClient, InstallClient};
+
+/// A desktop AI client that can be detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectableClient {
+    ClaudeDesktop,
+    Cursor,
+    #[serde(rename = "vscode")]
+    VsCode,
+    Windsurf,
+    Zed,
+}
+
+impl DetectableClient {
+    pub const ALL: [DetectableClient; 5] = [
+        Self::ClaudeDesktop,
+        Self::Cursor,
+        Self::VsCode,
+        Self::Windsurf,
+        Self::Zed,
+    ];
+
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClaudeDesktop => "Claude Desktop",
+            Self::Cursor => "Cursor",
+            Self::VsCode => "VS Code",
+            Self::Windsurf => "Windsurf",
+            Self::Zed => "Zed",
+        }
+    }
+
+    /// `client_type` of the matching preset client
+    pub fn client_type(&self) -> &'static str {
+        match self {
+            Self::ClaudeDesktop => "claude",
+            Self::Cursor => "cursor",
+            Self::VsCode => "vscode",
+            Self::Windsurf => "windsurf",
+            Self::Zed => "zed",
+        }
+    }
+
+    /// How McpMux adds itself to this client, if it can
+    pub fn install_client(&self) -> Option<InstallClient> {
+        match self {
+            Self::ClaudeDesktop => None,
+            Self::Cursor => Some(InstallClient::Cursor),
+            Self::VsCode => Some(InstallClient::Vscode),
+            Self::Windsurf => Some(InstallClient::Windsurf),
+            Self::Zed => Some(InstallClient::Zed),
+        }
+    }
+
+    /// How this client's servers are imported, if they can be
+    pub fn import_client(&self) -> Option<ImportClient> {
+        match self {
+            Self::ClaudeDesktop => Some(ImportClient::ClaudeDesktop),
+            Self::Cursor => Some(ImportClient::Cursor),
+            Self::VsCode => Some(ImportClient::VsCode),
+            Self::Windsurf => Some(ImportClient::Windsurf),
+            Self::Zed => None,
+        }
+    }
+}
+
+/// What was found for one client
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientInstallation {
+    pub client: DetectableClient,
+    pub label: String,
+    /// Whether the app itself appears to be installed
+    pub installed: bool,
+    /// The client's existing MCP config file
+    pub config_path: Option<PathBuf>,
+    /// Servers listed in that config
+    pub server_count: usize,
+    /// Whether the config already lists McpMux
+    pub has_mcpmux: bool,
+}
+
+impl ClientInstallation {
+    /// Installed, or at least left an MCP config behind
+    pub fn is_present(&self) -> bool {
+        self.installed || self.config_path.is_some()
+    }
+}
+
+/// Scans for installed clients and their MCP configs
+#[derive(Debug, Clone)]
+pub struct ClientDetectionService {
+    home: Option<PathBuf>,
+    config: Option<PathBuf>,
+    /// Whether to look for the apps themselves, not just their configs
+    check_installs: bool,
+}
+
+impl Default for ClientDetectionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientDetectionService {
+    /// Scan the current user's home and config directories
+    pub fn new() -> Self {
+        Self {
+            home: dirs::home_dir(),
+            config: dirs::config_dir(),
+            check_installs: true,
+        }
+    }
+
+    /// Scan configs under the given directories only; the apps themselves
+    /// are never reported as installed
+    pub fn with_dirs(home: impl Into<PathBuf>, config: impl Into<PathBuf>) -> Self {
+        Self {
+            home: Some(home.into()),
+            config: Some(config.into()),
+            check_installs: false,
+        }
+    }
+
+    /// Every client, found or not. Blocking: it reads the filesystem and
+    /// looks at PATH.
+    pub fn detect_all(&self) -> Vec<ClientInstallation> {
+        DetectableClient::ALL
+            .iter()
+            .map(|client| self.detect(*client))
+            .collect()
+    }
+
+    /// Only the clients found on this machine
+    pub fn detect_present(&self) -> Vec<ClientInstallation> {
+        self.detect_all()
+            .into_iter()
+            .filter(ClientInstallation::is_present)
+            .collect()
+    }
+
+    /// Look for one client
+    pub fn detect(&self, client: DetectableClient) -> ClientInstallation {
+        let config_path = self
+            .config_candidates(client)
+            .into_iter()
+            .find(|p| p.is_file());
+        let (server_count, has_mcpmux) = config_path
+            .as_deref()
+            .map(|path| read_servers(client, path))
+            .unwrap_or_default();
+
+        ClientInstallation {
+            client,
+            label: client.label().to_string(),
+            installed: self.check_installs && self.is_installed(client),
+            config_path,
+            server_count,
+            has_mcpmux,
+        }
+    }
+
+    /// MCP config files the client may have, most specific first
+    pub fn config_candidates(&self, client: DetectableClient) -> Vec<PathBuf> {
+        let home = self.home.as_deref();
+        let config = self.config.as_deref();
+        match client {
+            // Application Support on macOS, AppData\Roaming on Windows
+            DetectableClient::ClaudeDesktop => config
+                .map(|c| c.join("Claude").join("claude_desktop_config.json"))
+                .into_iter()
+                .collect(),
+            DetectableClient::Cursor => home
+                .map(|h| h.join(".cursor").join("mcp.json"))
+                .into_iter()
+                .collect(),
+            DetectableClient::VsCode => config
+                .map(|c| {
+                    let user = c.join("Code").join("User");
+                    vec![user.join("mcp.json"), user.join("settings.json")]
+                })
+                .unwrap_or_default(),
+            DetectableClient::Windsurf => home
+                .map(|h| h.join(".codeium").join("windsurf").join("mcp_config.json"))
+                .into_iter()
+                .collect(),
+            DetectableClient::Zed => if cfg!(target_os = "windows") {
+                config.map(|c| c.join("Zed").join("settings.json"))
+            } else {
+                home.map(|h| h.join(".config").join("zed").join("settings.json"))
+            }
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn is_installed(&self, client: DetectableClient) -> bool {
+        match client.install_client() {
+            Some(install) => install.is_installed(),
+            None => self.claude_desktop_locations().iter().any(|p| p.exists()),
+        }
+    }
+
+    /// App bundle or install folder of Claude Desktop. There is no official
+    /// Linux build, so there it only counts as installed once it has written
+    /// its config folder.
+    fn claude_desktop_locations(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if cfg!(target_os = "macos") {
+            paths.push(PathBuf::from("/Applications/Claude.app"));
+            paths.extend(
+                self.home
+                    .as_ref()
+                    .map(|h| h.join("Applications/Claude.app")),
+            );
+        } else if cfg!(target_os = "windows") {
+            paths.extend(dirs::data_local_dir().map(|l| l.join("AnthropicClaude")));
+        } else {
+            paths.extend(self.config.as_ref().map(|c| c.join("Claude")));
+        }
+        paths
+    }
+}
+
+/// Servers listed in a client config, and whether McpMux is one of them.
+/// An unreadable config counts as empty.
+fn read_servers(client: DetectableClient, path: &Path) -> (usize, bool) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return (0, false);
+    };
+    let Ok(root) = serde_json::from_str::<Value>(&strip_jsonc(&content)) else {
+        return (0, false);
+    };
+    let servers = match client {
+        DetectableClient::Zed => root.get("context_servers"),
+        _ => {
+            // VS Code settings.json nests everything under "mcp"
+            let scope = root.get("mcp").filter(|v| v.is_object()).unwrap_or(&root);
+            scope.get("mcpServers").or_else(|| scope.get("servers"))
+        }
+    };
+    match servers.and_then(Value::as_object) {
+        Some(servers) => (
+            servers.len(),
+            servers.keys().any(|k| k.eq_ignore_ascii_case("mcpmux")),
+        ),
+        None => (0, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn reads_existing_configs_of_each_client() {
+        let home = tempfile::tempdir().unwrap();
+        let config = tempfile::tempdir().unwrap();
+        let service = ClientDetectionService::with_dirs(home.path(), config.path());
+
+        write(
+            &config.path().join("Claude/claude_desktop_config.json"),
+            r#"{"mcpServers": {"github": {}, "fs": {}}}"#,
+        );
+        write(
+            &home.path().join(".cursor/mcp.json"),
+            r#"{"mcpServers": {"mcpmux": {"url": "http://localhost:45818/mcp"}}}"#,
+        );
+        write(
+            &config.path().join("Code/User/settings.json"),
+            "{\n  // comment\n  \"mcp\": {\"servers\": {\"a\": {}}},\n}",
+        );
+        write(
+            &home.path().join(".codeium/windsurf/mcp_config.json"),
+            "not json",
+        );
+
+        let found = service.detect_present();
+        let by_client = |client| found.iter().find(|c| c.client == client);
+
+        let claude = by_client(DetectableClient::ClaudeDesktop).unwrap();
+        assert_eq!((claude.server_count, claude.has_mcpmux), (2, false));
+        let cursor = by_client(DetectableClient::Cursor).unwrap();
+        assert_eq!((cursor.server_count, cursor.has_mcpmux), (1, true));
+        let vscode = by_client(DetectableClient::VsCode).unwrap();
+        assert_eq!(vscode.server_count, 1);
+        assert!(vscode
+            .config_path
+            .as_ref()
+            .unwrap()
+            .ends_with("settings.json"));
+        let windsurf = by_client(DetectableClient::Windsurf).unwrap();
+        assert_eq!(windsurf.server_count, 0);
+
+        // No Zed config, and installs aren't checked with explicit dirs
+        assert!(by_client(DetectableClient::Zed).is_none());
+        assert!(found.iter().all(|c| !c.installed));
+    }
+}
@@ -0,0 +1,550 @@
+//! Import servers from other MCP clients' configuration files.
+//!
+//! Reads the `mcpServers` block of Claude Desktop, Cursor and Windsurf
+//! configs, and the `servers` block of VS Code's `mcp.json` (or the `mcp`
+//! section of its `settings.json`), and turns each entry into a
+//! [`ServerDefinition`] plus input values ready to install into a Space.
+//!
+//! Literal env var and header values become `${input:...}` inputs so they are
+//! stored encrypted with the installation instead of in the cached definition.
+//! References such as `${env:VAR}` are kept as-is. VS Code `${input:id}`
+//! prompts become McpMux inputs without a value — VS Code keeps those in its
+//! own secret storage.
+//!
+//! Imported servers are copies: later edits to the client's file are not
+//! picked up, and uninstalling does not touch the file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::config_export::ConfigFormat;
+use crate::domain::config::{UserServerEntry, UserServerMetadata};
+use crate::domain::{InputDefinition, ServerDefinition, ServerSource};
+
+lazy_static! {
+    static ref VSCODE_INPUT_REGEX: Regex = Regex::new(r"\$\{input:([^}]+)\}").unwrap();
+}
+
+/// Client whose MCP config can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportClient {
+    ClaudeDesktop,
+    Cursor,
+    #[serde(rename = "vscode")]
+    VsCode,
+    Windsurf,
+}
+
+impl ImportClient {
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClaudeDesktop => "Claude Desktop",
+            Self::Cursor => "Cursor",
+            Self::VsCode => "VS Code",
+            Self::Windsurf => "Windsurf",
+        }
+    }
+
+    /// Candidate user-level config files, most specific first.
+    pub fn default_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::ClaudeDesktop => ConfigFormat::ClaudeDesktop
+                .default_path()
+                .into_iter()
+                .collect(),
+            Self::Cursor => ConfigFormat::Cursor.default_path().into_iter().collect(),
+            Self::VsCode => dirs::config_dir()
+                .map(|c| {
+                    let user = c.join("Code").join("User");
+                    vec![user.join("mcp.json"), user.join("settings.json")]
+                })
+                .unwrap_or_default(),
+            Self::Windsurf => dirs::home_dir()
+                .map(|h| h.join(".codeium").join("windsurf").join("mcp_config.json"))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// First existing default config file.
+    pub fn detect_config(&self) -> Option<PathBuf> {
+        self.default_paths().into_iter().find(|p| p.is_file())
+    }
+}
+
+/// A server parsed from a client config, ready to install.
+#[derive(Debug, Clone)]
+pub struct ImportedServer {
+    /// Key of the entry in the client config
+    pub name: String,
+    pub definition: ServerDefinition,
+    /// Values for inputs created from literal env vars and headers
+    pub input_values: HashMap<String, String>,
+}
+
+/// An entry that could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Parsed client config.
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfigImport {
+    pub servers: Vec<ImportedServer>,
+    pub skipped: Vec<SkippedImport>,
+}
+
+/// Server entry as written by any of the supported clients.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientServerEntry {
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    /// Windsurf uses `serverUrl` for remote servers
+    #[serde(alias = "serverUrl")]
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+/// VS Code `inputs` entry.
+#[derive(Debug, Deserialize)]
+struct VsCodeInput {
+    id: String,
+    description: Option<String>,
+    #[serde(default)]
+    password: bool,
+    default: Option<String>,
+}
+
+/// Read and parse a client config (the detected default when `path` is `None`).
+pub async fn read_client_config(
+    client: ImportClient,
+    path: Option<&Path>,
+    space_id: &str,
+) -> Result<ClientConfigImport> {
+    let path = match path {
+        Some(p) => p.to_path_buf(),
+        None => client
+            .detect_config()
+            .with_context(|| format!("No {} config found", client.label()))?,
+    };
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    parse_client_config(client, &content, space_id, &path)
+}
+
+/// Parse a client config into installable servers.
+pub fn parse_client_config(
+    client: ImportClient,
+    content: &str,
+    space_id: &str,
+    file_path: &Path,
+) -> Result<ClientConfigImport> {
+    let root: Value = serde_json::from_str(&strip_jsonc(content))
+        .with_context(|| format!("{} config is not valid JSON", client.label()))?;
+
+    // VS Code settings.json nests everything under "mcp"
+    let scope = root.get("mcp").filter(|v| v.is_object()).unwrap_or(&root);
+    let servers = scope
+        .get("mcpServers")
+        .or_else(|| scope.get("servers"))
+        .and_then(Value::as_object)
+        .with_context(|| format!("No MCP servers found in {} config", client.label()))?;
+
+    let prompts: HashMap<String, VsCodeInput> = scope
+        .get("inputs")
+        .and_then(|v| serde_json::from_value::<Vec<VsCodeInput>>(v.clone()).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| (i.id.clone(), i))
+        .collect();
+
+    let mut result = ClientConfigImport::default();
+    let mut seen_ids = HashSet::new();
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+
+    for name in names {
+        let skip = |reason: &str| SkippedImport {
+            name: name.clone(),
+            reason: reason.to_string(),
+        };
+        if name.eq_ignore_ascii_case("mcpmux") {
+            result.skipped.push(skip("Points at McpMux itself"));
+            continue;
+        }
+        let entry: ClientServerEntry = match serde_json::from_value(servers[name].clone()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                result
+                    .skipped
+                    .push(skip(&format!("Unrecognized entry: {}", e)));
+                continue;
+            }
+        };
+        if entry.command.is_none() && entry.url.is_none() {
+            result.skipped.push(skip("Neither a command nor a URL"));
+            continue;
+        }
+
+        let imported = convert_entry(name, entry, &prompts, space_id, file_path);
+        if !seen_ids.insert(imported.definition.id.clone()) {
+            result.skipped.push(skip(&format!(
+                "Same server ID as another entry ({})",
+                imported.definition.id
+            )));
+            continue;
+        }
+        result.servers.push(imported);
+    }
+
+    Ok(result)
+}
+
+fn convert_entry(
+    name: &str,
+    entry: ClientServerEntry,
+    prompts: &HashMap<String, VsCodeInput>,
+    space_id: &str,
+    file_path: &Path,
+) -> ImportedServer {
+    let mut inputs: Vec<InputDefinition> = Vec::new();
+    let mut input_values = HashMap::new();
+    let mut prompt_ids = HashSet::new();
+
+    // Rewrite VS Code prompts to McpMux input ids
+    let mut rewrite = |value: &str| -> String {
+        VSCODE_INPUT_REGEX
+            .replace_all(value, |caps: &regex::Captures| {
+                let id = input_id(&caps[1]);
+                if prompt_ids.insert(id.clone()) {
+                    let prompt = prompts.get(&caps[1]);
+                    inputs.push(InputDefinition {
+                        id: id.clone(),
+                        label: id.clone(),
+                        r#type: if prompt.is_some_and(|p| !p.password) {
+                            "text".to_string()
+                        } else {
+                            "password".to_string()
+                        },
+                        required: true,
+                        secret: prompt.is_none_or(|p| p.password),
+                        description: prompt.and_then(|p| p.description.clone()),
+                        default: prompt.and_then(|p| p.default.clone()),
+                        placeholder: None,
+                        obtain_url: None,
+                        obtain_instructions: None,
+                        options: Vec::new(),
+                        pattern: None,
+                        pattern_error: None,
+                        multiline: false,
+                    });
+                }
+                format!("${{input:{}}}", id)
+            })
+            .into_owned()
+    };
+
+    let command = entry.command.as_deref().map(&mut rewrite);
+    let args: Vec<String> = entry.args.iter().map(|a| rewrite(a)).collect();
+    let url = entry.url.as_deref().map(&mut rewrite);
+    let mut env = HashMap::new();
+    for (key, value) in &entry.env {
+        env.insert(key.clone(), rewrite(value));
+    }
+    let mut headers = HashMap::new();
+    for (key, value) in &entry.headers {
+        headers.insert(key.clone(), rewrite(value));
+    }
+
+    // Literal values move into encrypted inputs
+    let mut literal_inputs = Vec::new();
+    for (key, value) in env.iter_mut() {
+        if !value.contains("${") {
+            let id = input_id(key);
+            literal_inputs.push((id.clone(), key.clone(), std::mem::take(value)));
+            *value = format!("${{input:{}}}", id);
+        }
+    }
+    for (key, value) in headers.iter_mut() {
+        if !value.contains("${") {
+            let id = format!("HEADER_{}", input_id(key));
+            literal_inputs.push((id.clone(), key.clone(), std::mem::take(value)));
+            *value = format!("${{input:{}}}", id);
+        }
+    }
+    for (id, key, value) in literal_inputs {
+        inputs.push(InputDefinition {
+            id: id.clone(),
+            label: key.clone(),
+            r#type: if looks_secret(&key) {
+                "password".to_string()
+            } else {
+                "text".to_string()
+            },
+            required: true,
+            secret: looks_secret(&key),
+            description: None,
+            default: None,
+            placeholder: None,
+            obtain_url: None,
+            obtain_instructions: None,
+            options: Vec::new(),
+            pattern: None,
+            pattern_error: None,
+            multiline: false,
+        });
+        input_values.insert(id, value);
+    }
+
+    let is_http = url.is_some();
+    let user_entry = UserServerEntry {
+        command: if is_http { None } else { command },
+        args: (!is_http).then_some(args),
+        env: (!is_http).then_some(env),
+        url,
+        headers: is_http.then_some(headers),
+        query: None,
+        openapi: None,
+        base_url: None,
+        operations: None,
+        graphql: None,
+        wasm: None,
+        permissions: None,
+        name: Some(name.to_string()),
+        description: None,
+        icon: None,
+        alias: None,
+        auth: None,
+        depends_on: None,
+        metadata: Some(UserServerMetadata {
+            inputs: Some(inputs),
+            publisher: None,
+        }),
+    };
+    let mut definition = user_entry.to_server_definition(name, space_id, file_path.to_path_buf());
+    // Not tied to the source file (see module docs)
+    definition.source = ServerSource::default();
+
+    ImportedServer {
+        name: name.to_string(),
+        definition,
+        input_values,
+    }
+}
+
+/// McpMux input id (`[A-Z_][A-Z0-9_]*`) for an env var, header or VS Code id.
+fn input_id(key: &str) -> String {
+    let id: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if id.starts_with(|c: char| c.is_ascii_digit()) || id.is_empty() {
+        format!("_{}", id)
+    } else {
+        id
+    }
+}
+
+fn looks_secret(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    [
+        "KEY",
+        "TOKEN",
+        "SECRET",
+        "PASSWORD",
+        "AUTHORIZATION",
+        "CREDENTIAL",
+    ]
+    .iter()
+    .any(|marker| key.contains(marker))
+}
+
+/// Strip `//` and `/* */` comments and trailing commas (VS Code JSONC).
+pub(super) fn strip_jsonc(input: &str) -> String {
+    let mut uncommented = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            uncommented.push(c);
+            match c {
+                '\\' => uncommented.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                chars.by_ref().find(|&next| next == '\n');
+                uncommented.push('\n');
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                chars.by_ref().find(|&next| {
+                    let end = prev == '*' && next == '/';
+                    prev = next;
+                    end
+                });
+                uncommented.push(' ');
+            }
+            _ => {
+                in_string = c == '"';
+                uncommented.push(c);
+            }
+        }
+    }
+
+    // Second pass: commas followed only by whitespace and a closing bracket
+    let mut out = String::with_capacity(uncommented.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in uncommented.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = uncommented[i + 1..].trim_start().chars().next();
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TransportConfig;
+
+    fn parse(client: ImportClient, json: &str) -> ClientConfigImport {
+        parse_client_config(client, json, "space-1", Path::new("/tmp/config.json")).unwrap()
+    }
+
+    #[test]
+    fn test_claude_desktop_env_becomes_inputs() {
+        let result = parse(
+            ImportClient::ClaudeDesktop,
+            r#"{"mcpServers": {
+                "github": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "env": {"GITHUB_TOKEN": "ghp_123", "HOME_DIR": "${env:HOME}"}
+                },
+                "mcpmux": {"url": "http://localhost:45818/mcp"},
+                "broken": {"args": []}
+            }}"#,
+        );
+
+        assert_eq!(result.servers.len(), 1);
+        assert_eq!(result.skipped.len(), 2);
+        let server = &result.servers[0];
+        assert_eq!(server.input_values["GITHUB_TOKEN"], "ghp_123");
+
+        let TransportConfig::Stdio { env, metadata, .. } = &server.definition.transport else {
+            panic!("expected stdio");
+        };
+        assert_eq!(env["GITHUB_TOKEN"], "${input:GITHUB_TOKEN}");
+        assert_eq!(env["HOME_DIR"], "${env:HOME}");
+        assert!(metadata
+            .inputs
+            .iter()
+            .any(|i| i.id == "GITHUB_TOKEN" && i.secret));
+        // The cached definition never holds the literal
+        assert!(!serde_json::to_string(&server.definition)
+            .unwrap()
+            .contains("ghp_123"));
+    }
+
+    #[test]
+    fn test_windsurf_server_url_and_headers() {
+        let result = parse(
+            ImportClient::Windsurf,
+            r#"{"mcpServers": {"remote": {
+                "serverUrl": "https://example.com/mcp",
+                "headers": {"Authorization": "Bearer abc"}
+            }}}"#,
+        );
+        let server = &result.servers[0];
+        let TransportConfig::Http { url, headers, .. } = &server.definition.transport else {
+            panic!("expected http");
+        };
+        assert_eq!(url, "https://example.com/mcp");
+        assert_eq!(headers["Authorization"], "${input:HEADER_AUTHORIZATION}");
+        assert_eq!(server.input_values["HEADER_AUTHORIZATION"], "Bearer abc");
+    }
+
+    #[test]
+    fn test_vscode_settings_with_comments_and_prompts() {
+        let result = parse(
+            ImportClient::VsCode,
+            r#"{
+                // user settings
+                "editor.fontSize": 14,
+                "mcp": {
+                    "inputs": [
+                        {"type": "promptString", "id": "api-key", "description": "API key", "password": true},
+                    ],
+                    "servers": {
+                        "perplexity": {
+                            "type": "stdio",
+                            "command": "npx",
+                            "args": ["server-perplexity-ask"],
+                            "env": {"PERPLEXITY_API_KEY": "${input:api-key}"}, /* trailing */
+                        },
+                    },
+                },
+            }"#,
+        );
+        let server = &result.servers[0];
+        assert!(server.input_values.is_empty());
+        let TransportConfig::Stdio { env, metadata, .. } = &server.definition.transport else {
+            panic!("expected stdio");
+        };
+        assert_eq!(env["PERPLEXITY_API_KEY"], "${input:API_KEY}");
+        let input = metadata.inputs.iter().find(|i| i.id == "API_KEY").unwrap();
+        assert!(input.secret);
+        assert_eq!(input.description.as_deref(), Some("API key"));
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        let stripped = strip_jsonc(r#"{"url": "https://a/b//c", "s": "/* x */", "a": [1,],}"#);
+        let value: Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value["url"], "https://a/b//c");
+        assert_eq!(value["s"], "/* x */");
+        assert_eq!(value["a"], serde_json::json!([1]));
+    }
+}
@@ -1,11 +1,364 @@
 //! Client IDE install helpers.
 //!
-//! Deep link URI generators for VS Code and Cursor one-click MCP server install.
+//! Deep link URI generators for VS Code and Cursor one-click MCP server install,
+//! config-file installers for Windsurf and Zed, in-place edits of a detected
+//! client's MCP config, the JSON that JetBrains AI Assistant accepts, and
+//! detection of which of these clients are installed.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{ClientDetectionService, DetectableClient};
+
+/// Server name McpMux registers itself under in client configs
+const SERVER_NAME: &str = "mcpmux";
+
+/// Client McpMux can add itself to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallClient {
+    Vscode,
+    Cursor,
+    Windsurf,
+    Zed,
+    /// Any JetBrains IDE with AI Assistant
+    Jetbrains,
+}
+
+impl InstallClient {
+    pub const ALL: [InstallClient; 5] = [
+        Self::Vscode,
+        Self::Cursor,
+        Self::Windsurf,
+        Self::Zed,
+        Self::Jetbrains,
+    ];
+
+    /// Launcher names looked up on PATH
+    fn executables(&self) -> &'static [&'static str] {
+        match self {
+            Self::Vscode => &["code"],
+            Self::Cursor => &["cursor"],
+            Self::Windsurf => &["windsurf"],
+            Self::Zed => &["zed", "zeditor"],
+            Self::Jetbrains => &[
+                "idea",
+                "pycharm",
+                "webstorm",
+                "goland",
+                "clion",
+                "rider",
+                "phpstorm",
+                "rustrover",
+            ],
+        }
+    }
+
+    /// App bundles, install folders and config folders that only exist once
+    /// the client has been installed (or run)
+    fn known_locations(&self) -> Vec<PathBuf> {
+        let home = dirs::home_dir();
+        let config = dirs::config_dir();
+        let local = dirs::data_local_dir();
+        let mut paths = Vec::new();
+        let (app, programs) = match self {
+            Self::Vscode => {
+                paths.extend(config.map(|c| c.join("Code")));
+                ("Visual Studio Code.app", "Microsoft VS Code")
+            }
+            Self::Cursor => {
+                paths.extend(home.as_ref().map(|h| h.join(".cursor")));
+                ("Cursor.app", "cursor")
+            }
+            Self::Windsurf => {
+                paths.extend(home.as_ref().map(|h| h.join(".codeium").join("windsurf")));
+                ("Windsurf.app", "Windsurf")
+            }
+            Self::Zed => {
+                paths.extend(zed_settings_path().and_then(|p| p.parent().map(Path::to_path_buf)));
+                ("Zed.app", "Zed")
+            }
+            Self::Jetbrains => {
+                // Per-IDE config folders (IntelliJIdea2025.1, PyCharm2024.3, ...)
+                paths.extend(config.map(|c| c.join("JetBrains")));
+                paths.extend(local.map(|l| l.join("JetBrains").join("Toolbox")));
+                return paths;
+            }
+        };
+        if cfg!(target_os = "macos") {
+            paths.push(Path::new("/Applications").join(app));
+            paths.extend(home.map(|h| h.join("Applications").join(app)));
+        }
+        if cfg!(target_os = "windows") {
+            paths.extend(local.map(|l| l.join("Programs").join(programs)));
+        }
+        paths
+    }
+
+    /// Whether the client appears to be installed on this machine
+    pub fn is_installed(&self) -> bool {
+        self.executables()
+            .iter()
+            .any(|exe| find_on_path(exe).is_some())
+            || self.known_locations().iter().any(|p| p.exists())
+    }
+}
+
+/// Find an executable on PATH (with the usual Windows extensions)
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let candidates: &[&str] = if cfg!(target_os = "windows") {
+        &["exe", "cmd", "bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(&path).find_map(|dir| {
+        candidates.iter().find_map(|ext| {
+            let file = dir.join(name).with_extension(ext);
+            file.is_file().then_some(file)
+        })
+    })
+}
+
+/// Windsurf's MCP config (`~/.codeium/windsurf/mcp_config.json`)
+pub fn windsurf_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".codeium").join("windsurf").join("mcp_config.json"))
+}
+
+/// Zed's user settings (`~/.config/zed/settings.json`, `%APPDATA%\Zed` on Windows)
+pub fn zed_settings_path() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        dirs::config_dir().map(|c| c.join("Zed").join("settings.json"))
+    } else {
+        dirs::home_dir().map(|h| h.join(".config").join("zed").join("settings.json"))
+    }
+}
+
+/// Windsurf `mcpServers` entry for the gateway
+pub fn windsurf_server_entry(gateway_url: &str) -> serde_json::Value {
+    serde_json::json!({ "serverUrl": format!("{}/mcp", gateway_url) })
+}
+
+/// Zed `context_servers` entry for the gateway
+pub fn zed_server_entry(gateway_url: &str) -> serde_json::Value {
+    serde_json::json!({ "url": format!("{}/mcp", gateway_url) })
+}
+
+/// JSON for JetBrains AI Assistant's "Add → As JSON" MCP dialog.
+///
+/// AI Assistant keeps MCP servers in IDE settings with no deep link or CLI
+/// to add one, so this is pasted by the user.
+pub fn jetbrains_mcp_config(gateway_url: &str) -> String {
+    let config = serde_json::json!({
+        "mcpServers": {
+            SERVER_NAME: { "url": format!("{}/mcp", gateway_url) }
+        }
+    });
+    serde_json::to_string_pretty(&config).unwrap_or_default()
+}
+
+/// Add (or replace) the `mcpmux` entry under `section` of a JSON config file,
+/// keeping everything else in it. Creates the file if missing.
+///
+/// Refuses files that aren't plain JSON (e.g. Zed settings with comments)
+/// rather than rewriting them without the comments.
+pub fn add_to_config_file(path: &Path, section: &str, entry: serde_json::Value) -> Result<()> {
+    let existing = read_config(path)?;
+    let (config, _) = merge_entry(path, existing.as_deref(), section, entry)?;
+    write_config(path, &config)
+}
+
+/// What applying the McpMux entry to a client's config did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigApplyAction {
+    Added,
+    Updated,
+    /// The entry was already up to date; the file wasn't touched
+    Unchanged,
+}
+
+/// Outcome of [`apply_to_client`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigApplyResult {
+    pub client: DetectableClient,
+    /// The config file edited
+    pub path: PathBuf,
+    /// Copy of the file as it was before the edit
+    pub backup_path: Option<PathBuf>,
+    pub action: ConfigApplyAction,
+}
+
+/// Add or update the McpMux entry in a detected client's MCP config, in
+/// place. Other entries and settings, and their order, are kept. An
+/// existing file is first copied to `<file>.<timestamp>.bak`.
+///
+/// Like [`add_to_config_file`], refuses files with comments. Claude Desktop
+/// only takes local servers in its config file, so it isn't supported.
+pub fn apply_to_client(
+    detection: &ClientDetectionService,
+    client: DetectableClient,
+    gateway_url: &str,
+) -> Result<ConfigApplyResult> {
+    let (section, entry) = match client {
+        DetectableClient::Cursor => (
+            "mcpServers",
+            serde_json::json!({ "url": format!("{}/mcp", gateway_url) }),
+        ),
+        DetectableClient::VsCode => (
+            "servers",
+            serde_json::json!({ "type": "http", "url": format!("{}/mcp", gateway_url) }),
+        ),
+        DetectableClient::Windsurf => ("mcpServers", windsurf_server_entry(gateway_url)),
+        DetectableClient::Zed => ("context_servers", zed_server_entry(gateway_url)),
+        DetectableClient::ClaudeDesktop => bail!(
+            "Claude Desktop can't be pointed at the gateway through its config file; \
+             add McpMux under Settings → Connectors instead"
+        ),
+    };
+    // The first candidate is the dedicated MCP file (VS Code's mcp.json
+    // rather than settings.json)
+    let path = detection
+        .config_candidates(client)
+        .into_iter()
+        .next()
+        .context("Could not determine the config location")?;
+
+    let existing = read_config(&path)?;
+    let (config, previous) = merge_entry(&path, existing.as_deref(), section, entry.clone())?;
+    let action = match previous {
+        None => ConfigApplyAction::Added,
+        Some(previous) if previous == entry => {
+            return Ok(ConfigApplyResult {
+                client,
+                path,
+                backup_path: None,
+                action: ConfigApplyAction::Unchanged,
+            })
+        }
+        Some(_) => ConfigApplyAction::Updated,
+    };
+
+    let backup_path = match existing {
+        Some(content) => {
+            let mut name = path.file_name().unwrap_or_default().to_os_string();
+            name.push(format!(".{}.bak", Utc::now().format("%Y%m%d-%H%M%S")));
+            let backup = path.with_file_name(name);
+            std::fs::write(&backup, content)
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+            Some(backup)
+        }
+        None => None,
+    };
+    write_config(&path, &config)?;
+
+    Ok(ConfigApplyResult {
+        client,
+        path,
+        backup_path,
+        action,
+    })
+}
+
+/// A client config [`reapply_to_clients`] couldn't update
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigApplyFailure {
+    pub client: DetectableClient,
+    pub error: String,
+}
+
+/// Outcome of [`reapply_to_clients`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigReapplyResult {
+    pub applied: Vec<ConfigApplyResult>,
+    pub failed: Vec<ConfigApplyFailure>,
+}
+
+/// Point every detected client config that already lists McpMux at
+/// `gateway_url`, e.g. after the gateway had to move to another port.
+/// Configs without an McpMux entry are left alone, and a client that fails
+/// doesn't stop the others.
+pub fn reapply_to_clients(
+    detection: &ClientDetectionService,
+    gateway_url: &str,
+) -> ConfigReapplyResult {
+    let mut result = ConfigReapplyResult::default();
+    for installation in detection.detect_all() {
+        if !installation.has_mcpmux || installation.client == DetectableClient::ClaudeDesktop {
+            continue;
+        }
+        match apply_to_client(detection, installation.client, gateway_url) {
+            Ok(applied) => result.applied.push(applied),
+            Err(e) => result.failed.push(ConfigApplyFailure {
+                client: installation.client,
+                error: e.to_string(),
+            }),
+        }
+    }
+    result
+}
+
+/// Content of a config file, `None` if it doesn't exist
+fn read_config(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Put `entry` under `section.mcpmux` of a config. Returns the new config
+/// and the entry it replaced.
+fn merge_entry(
+    path: &Path,
+    existing: Option<&str>,
+    section: &str,
+    entry: serde_json::Value,
+) -> Result<(serde_json::Value, Option<serde_json::Value>)> {
+    let mut config: serde_json::Value = match existing {
+        Some(content) if !content.trim().is_empty() => {
+            serde_json::from_str(content).with_context(|| {
+                format!(
+                    "{} is not plain JSON (it may contain comments); add the mcpmux entry by hand",
+                    path.display()
+                )
+            })?
+        }
+        _ => serde_json::json!({}),
+    };
+
+    let Some(root) = config.as_object_mut() else {
+        bail!("{} does not contain a JSON object", path.display());
+    };
+    let servers = root.entry(section).or_insert_with(|| serde_json::json!({}));
+    let Some(servers) = servers.as_object_mut() else {
+        bail!("\"{}\" in {} is not an object", section, path.display());
+    };
+    let previous = servers.insert(SERVER_NAME.to_string(), entry);
+    Ok((config, previous))
+}
+
+/// Write a config, going through a temporary file so a failed write never
+/// leaves it half-written
+fn write_config(path: &Path, config: &serde_json::Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".mcpmux-tmp");
+    let temp = path.with_file_name(temp_name);
+    std::fs::write(&temp, serde_json::to_string_pretty(config)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
 
 /// Generate the VS Code deep link URI for one-click MCP install.
 pub fn vscode_deep_link(gateway_url: &str) -> String {
     let config = serde_json::json!({
-        "name": "mcpmux",
+        "name": SERVER_NAME,
         "type": "http",
         "url": format!("{}/mcp", gateway_url)
     });
@@ -47,4 +400,164 @@ mod tests {
         assert!(link.contains("name=McpMux"));
         assert!(link.contains("config="));
     }
+
+    #[test]
+    fn test_add_to_config_file_keeps_other_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mcp_config.json");
+        std::fs::write(
+            &path,
+            r#"{"mcpServers": {"github": {"command": "gh"}}, "other": 1}"#,
+        )
+        .unwrap();
+
+        add_to_config_file(
+            &path,
+            "mcpServers",
+            windsurf_server_entry("http://localhost:45818"),
+        )
+        .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["other"], 1);
+        assert_eq!(config["mcpServers"]["github"]["command"], "gh");
+        assert_eq!(
+            config["mcpServers"]["mcpmux"]["serverUrl"],
+            "http://localhost:45818/mcp"
+        );
+    }
+
+    #[test]
+    fn test_add_to_config_file_creates_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zed").join("settings.json");
+
+        add_to_config_file(
+            &path,
+            "context_servers",
+            zed_server_entry("http://localhost:45818"),
+        )
+        .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            config["context_servers"]["mcpmux"]["url"],
+            "http://localhost:45818/mcp"
+        );
+    }
+
+    #[test]
+    fn test_add_to_config_file_refuses_jsonc() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let original = "// Zed settings\n{\"theme\": \"One Dark\"}";
+        std::fs::write(&path, original).unwrap();
+
+        assert!(add_to_config_file(&path, "context_servers", serde_json::json!({})).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_apply_to_client_backs_up_and_merges() {
+        let home = tempfile::tempdir().unwrap();
+        let config = tempfile::tempdir().unwrap();
+        let detection = ClientDetectionService::with_dirs(home.path(), config.path());
+        let path = home.path().join(".cursor").join("mcp.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let original = r#"{"theme": "dark", "mcpServers": {"github": {"command": "gh"}}}"#;
+        std::fs::write(&path, original).unwrap();
+
+        let applied = apply_to_client(
+            &detection,
+            DetectableClient::Cursor,
+            "http://localhost:45818",
+        )
+        .unwrap();
+        assert_eq!(applied.action, ConfigApplyAction::Added);
+        let backup = applied.backup_path.expect("existing file was backed up");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), original);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let keys: Vec<String> = serde_json::from_str::<serde_json::Value>(&written).unwrap()
+            ["mcpServers"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        assert_eq!(keys, ["github", "mcpmux"]);
+        assert!(written.find("theme") < written.find("mcpServers"));
+
+        let again = apply_to_client(
+            &detection,
+            DetectableClient::Cursor,
+            "http://localhost:45818",
+        )
+        .unwrap();
+        assert_eq!(again.action, ConfigApplyAction::Unchanged);
+        assert!(again.backup_path.is_none());
+
+        let moved = apply_to_client(
+            &detection,
+            DetectableClient::Cursor,
+            "http://localhost:3100",
+        )
+        .unwrap();
+        assert_eq!(moved.action, ConfigApplyAction::Updated);
+
+        assert!(apply_to_client(
+            &detection,
+            DetectableClient::ClaudeDesktop,
+            "http://localhost:45818"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reapply_to_clients_updates_only_configs_with_mcpmux() {
+        let home = tempfile::tempdir().unwrap();
+        let config = tempfile::tempdir().unwrap();
+        let detection = ClientDetectionService::with_dirs(home.path(), config.path());
+        let cursor = home.path().join(".cursor").join("mcp.json");
+        std::fs::create_dir_all(cursor.parent().unwrap()).unwrap();
+        std::fs::write(
+            &cursor,
+            r#"{"mcpServers": {"mcpmux": {"url": "http://localhost:45818/mcp"}}}"#,
+        )
+        .unwrap();
+        let windsurf = home
+            .path()
+            .join(".codeium")
+            .join("windsurf")
+            .join("mcp_config.json");
+        std::fs::create_dir_all(windsurf.parent().unwrap()).unwrap();
+        let untouched = r#"{"mcpServers": {"github": {"command": "gh"}}}"#;
+        std::fs::write(&windsurf, untouched).unwrap();
+
+        let result = reapply_to_clients(&detection, "http://localhost:3100");
+        assert!(result.failed.is_empty());
+        assert_eq!(result.applied.len(), 1);
+        assert_eq!(result.applied[0].client, DetectableClient::Cursor);
+        assert_eq!(result.applied[0].action, ConfigApplyAction::Updated);
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&cursor).unwrap()).unwrap();
+        assert_eq!(
+            written["mcpServers"]["mcpmux"]["url"],
+            "http://localhost:3100/mcp"
+        );
+        assert_eq!(std::fs::read_to_string(&windsurf).unwrap(), untouched);
+    }
+
+    #[test]
+    fn test_jetbrains_mcp_config() {
+        let config: serde_json::Value =
+            serde_json::from_str(&jetbrains_mcp_config("http://localhost:45818")).unwrap();
+        assert_eq!(
+            config["mcpServers"]["mcpmux"]["url"],
+            "http://localhost:45818/mcp"
+        );
+    }
 }
@@ -5,6 +5,9 @@
 //! - VS Code Continue (.continuerc / settings.json)
 //! - Claude Desktop (claude_desktop_config.json)
 //!
+//! and ready-to-paste gateway configs (one McpMux entry with its access key)
+//! for Zed, Continue, Cline, the OpenAI Agents SDK and LangChain.
+//!
 //! This module works with:
 //! - `RegistryServer` - Server definition from registry (transport config, inputs)
 //! - `InstalledServer` - User's installation with input values
@@ -66,6 +69,120 @@ impl ConfigFormat {
             }
         }
     }
+
+    /// Where the servers map sits in this format, for merging an export
+    /// into an existing file (see [`merge_config`](super::merge_config))
+    pub fn servers_path(&self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Cursor | ConfigFormat::ClaudeDesktop => &["mcpServers"],
+            ConfigFormat::VsCodeContinue => &["experimental", "modelContextProtocol", "servers"],
+        }
+    }
+}
+
+/// Client connecting to the McpMux gateway instead of to each server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayClientFormat {
+    /// Zed `context_servers` block (settings.json)
+    Zed,
+    /// Continue `mcpServers` block (config.yaml)
+    Continue,
+    /// Cline cline_mcp_settings.json
+    Cline,
+    /// OpenAI Agents SDK (Python)
+    OpenAiAgents,
+    /// LangChain MCP adapters (Python)
+    LangChain,
+}
+
+impl GatewayClientFormat {
+    /// Parse a client type from the UI
+    pub fn parse(client_type: &str) -> Option<Self> {
+        match client_type.to_lowercase().as_str() {
+            "zed" => Some(Self::Zed),
+            "continue-gateway" | "continue-yaml" => Some(Self::Continue),
+            "cline" => Some(Self::Cline),
+            "openai-agents" | "openai" => Some(Self::OpenAiAgents),
+            "langchain" => Some(Self::LangChain),
+            _ => None,
+        }
+    }
+
+    /// Config file that can be written as a whole.
+    ///
+    /// `None` for snippets and for files holding unrelated settings (Zed's
+    /// settings.json, Continue's config.yaml) — those are pasted instead.
+    pub fn default_path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Cline => dirs::config_dir().map(|c| {
+                c.join("Code")
+                    .join("User")
+                    .join("globalStorage")
+                    .join("saoudrizwan.claude-dev")
+                    .join("settings")
+                    .join("cline_mcp_settings.json")
+            }),
+            _ => None,
+        }
+    }
+
+    /// File name suggestion
+    pub fn suggested_filename(&self) -> &'static str {
+        match self {
+            Self::Zed => "settings.json",
+            Self::Continue => "config.yaml",
+            Self::Cline => "cline_mcp_settings.json",
+            Self::OpenAiAgents => "mcpmux_agent.py",
+            Self::LangChain => "mcpmux_langchain.py",
+        }
+    }
+
+    /// Where the servers map sits, for formats that can be merged into an
+    /// existing JSON file; `None` for YAML and code snippets
+    pub fn servers_path(&self) -> Option<&'static [&'static str]> {
+        match self {
+            Self::Zed => Some(&["context_servers"]),
+            Self::Cline => Some(&["mcpServers"]),
+            Self::Continue | Self::OpenAiAgents | Self::LangChain => None,
+        }
+    }
+}
+
+/// How a client reaches the gateway
+#[derive(Debug, Clone)]
+pub struct GatewayConnection {
+    /// Gateway base URL (without `/mcp`)
+    pub gateway_url: String,
+    /// API key sent as a Bearer token; a placeholder is written when `None`
+    pub access_key: Option<String>,
+    /// Which FeatureSets the client gets, written as a comment where the
+    /// format allows one
+    pub feature_set_note: Option<String>,
+}
+
+/// Placeholder written when no access key is supplied
+pub const ACCESS_KEY_PLACEHOLDER: &str = "<MCPMUX_API_KEY>";
+
+impl GatewayConnection {
+    fn mcp_url(&self) -> String {
+        format!("{}/mcp", self.gateway_url.trim_end_matches('/'))
+    }
+
+    fn authorization(&self) -> String {
+        format!(
+            "Bearer {}",
+            self.access_key.as_deref().unwrap_or(ACCESS_KEY_PLACEHOLDER)
+        )
+    }
+
+    /// Note lines prefixed with a comment marker
+    fn comment(&self, marker: &str) -> String {
+        self.feature_set_note
+            .iter()
+            .flat_map(|note| note.lines())
+            .map(|line| format!("{} {}\n", marker, line))
+            .collect()
+    }
 }
 
 /// Cursor MCP configuration format
@@ -375,6 +492,106 @@ impl ConfigExporter {
         }
     }
 
+    /// Export a gateway config for a client that connects through McpMux
+    pub fn export_gateway(
+        &self,
+        format: GatewayClientFormat,
+        connection: &GatewayConnection,
+    ) -> Result<String, serde_json::Error> {
+        let url = connection.mcp_url();
+        let auth = connection.authorization();
+        // JSON string literals are valid YAML and Python literals too
+        let quoted_url = serde_json::to_string(&url)?;
+        let quoted_auth = serde_json::to_string(&auth)?;
+
+        Ok(match format {
+            GatewayClientFormat::Zed => {
+                let config = serde_json::json!({
+                    "context_servers": {
+                        "mcpmux": {
+                            "url": url,
+                            "headers": { "Authorization": auth },
+                        }
+                    }
+                });
+                format!(
+                    "{}{}",
+                    connection.comment("//"),
+                    serde_json::to_string_pretty(&config)?
+                )
+            }
+            GatewayClientFormat::Continue => format!(
+                "{}mcpServers:\n  - name: McpMux\n    type: streamable-http\n    url: {}\n    requestOptions:\n      headers:\n        Authorization: {}\n",
+                connection.comment("#"),
+                quoted_url,
+                quoted_auth
+            ),
+            GatewayClientFormat::Cline => {
+                // Plain JSON: the note is returned alongside, not embedded
+                let config = serde_json::json!({
+                    "mcpServers": {
+                        "mcpmux": {
+                            "type": "streamableHttp",
+                            "url": url,
+                            "headers": { "Authorization": auth },
+                            "disabled": false,
+                        }
+                    }
+                });
+                serde_json::to_string_pretty(&config)?
+            }
+            GatewayClientFormat::OpenAiAgents => format!(
+                r#"{}import asyncio
+
+from agents import Agent, Runner
+from agents.mcp import MCPServerStreamableHttp
+
+
+async def main():
+    async with MCPServerStreamableHttp(
+        name="McpMux",
+        params={{"url": {}, "headers": {{"Authorization": {}}}}},
+    ) as mcpmux:
+        agent = Agent(name="Assistant", mcp_servers=[mcpmux])
+        result = await Runner.run(agent, "List the tools you can use.")
+        print(result.final_output)
+
+
+asyncio.run(main())
+"#,
+                connection.comment("#"),
+                quoted_url,
+                quoted_auth
+            ),
+            GatewayClientFormat::LangChain => format!(
+                r#"{}import asyncio
+
+from langchain_mcp_adapters.client import MultiServerMCPClient
+
+
+async def main():
+    client = MultiServerMCPClient(
+        {{
+            "mcpmux": {{
+                "transport": "streamable_http",
+                "url": {},
+                "headers": {{"Authorization": {}}},
+            }}
+        }}
+    )
+    tools = await client.get_tools()
+    print([tool.name for tool in tools])
+
+
+asyncio.run(main())
+"#,
+                connection.comment("#"),
+                quoted_url,
+                quoted_auth
+            ),
+        })
+    }
+
     /// Resolve multiple servers from registry and installed data
     pub fn resolve_servers(
         &self,
@@ -472,6 +689,66 @@ mod tests {
             .contains_key("io.github.modelcontextprotocol/memory"));
     }
 
+    fn gateway_connection(note: Option<&str>) -> GatewayConnection {
+        GatewayConnection {
+            gateway_url: "http://localhost:45818/".to_string(),
+            access_key: Some("mcpk_test".to_string()),
+            feature_set_note: note.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_gateway_json_formats() {
+        let exporter = ConfigExporter::new();
+        let conn = gateway_connection(Some("FeatureSets: Starter"));
+
+        let zed = exporter
+            .export_gateway(GatewayClientFormat::Zed, &conn)
+            .unwrap();
+        assert!(zed.starts_with("// FeatureSets: Starter\n"));
+        let json: serde_json::Value =
+            serde_json::from_str(zed.split_once('\n').unwrap().1).unwrap();
+        let server = &json["context_servers"]["mcpmux"];
+        assert_eq!(server["url"], "http://localhost:45818/mcp");
+        assert_eq!(server["headers"]["Authorization"], "Bearer mcpk_test");
+
+        let cline = exporter
+            .export_gateway(GatewayClientFormat::Cline, &conn)
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&cline).unwrap();
+        assert_eq!(json["mcpServers"]["mcpmux"]["type"], "streamableHttp");
+    }
+
+    #[test]
+    fn test_gateway_snippets_embed_url_and_key() {
+        let exporter = ConfigExporter::new();
+        let conn = gateway_connection(Some("Mapped to Work"));
+
+        for format in [
+            GatewayClientFormat::Continue,
+            GatewayClientFormat::OpenAiAgents,
+            GatewayClientFormat::LangChain,
+        ] {
+            let out = exporter.export_gateway(format, &conn).unwrap();
+            assert!(out.starts_with("# Mapped to Work\n"), "{out}");
+            assert!(out.contains("\"http://localhost:45818/mcp\""), "{out}");
+            assert!(out.contains("\"Bearer mcpk_test\""), "{out}");
+        }
+    }
+
+    #[test]
+    fn test_gateway_placeholder_without_key() {
+        let conn = GatewayConnection {
+            access_key: None,
+            ..gateway_connection(None)
+        };
+        let out = ConfigExporter::new()
+            .export_gateway(GatewayClientFormat::LangChain, &conn)
+            .unwrap();
+        assert!(out.contains(ACCESS_KEY_PLACEHOLDER));
+        assert!(out.starts_with("import asyncio"));
+    }
+
     #[test]
     fn test_resolve_placeholders() {
         let template = "https://api.example.com/${input:api_key}/v1";
@@ -0,0 +1,617 @@
+//! Conflict-safe merge of exported servers into an existing client config.
+//!
+//! Writing an export over a client's config would drop every server the
+//! user added by hand, their other settings, and (for JSONC files such as
+//! VS Code or Zed settings) their comments. Instead the exported servers are
+//! spliced into the existing text: each one replaces the entry of the same
+//! name or is appended after the others, and every other byte of the file
+//! stays where it was. The same merge backs the dry-run diff the export
+//! preview shows.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+
+use super::client_import::strip_jsonc;
+
+/// Indentation used when the file gives no hint
+const DEFAULT_INDENT: &str = "  ";
+
+/// Above this many line pairs, the diff lists every line as changed rather
+/// than searching for the smallest diff
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Merge the servers of an exported config into `existing`.
+///
+/// `servers_path` is where the servers map sits in both configs (e.g.
+/// `["mcpServers"]`). Servers are replaced whole, so an exported server
+/// never keeps stale keys from the old entry. An empty `existing` yields
+/// the export unchanged.
+pub fn merge_config(existing: &str, exported: &str, servers_path: &[&str]) -> Result<String> {
+    if existing.trim().is_empty() {
+        return Ok(exported.to_string());
+    }
+    // Gateway exports for JSONC targets open with a comment
+    let exported: Value =
+        serde_json::from_str(&strip_jsonc(exported)).context("Export is not valid JSON")?;
+    let mut servers = &exported;
+    for key in servers_path {
+        servers = servers
+            .get(key)
+            .with_context(|| format!("Export has no \"{}\"", servers_path.join(".")))?;
+    }
+    let servers = servers.as_object().with_context(|| {
+        format!(
+            "\"{}\" in the export is not an object",
+            servers_path.join(".")
+        )
+    })?;
+
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+    let mut text = existing.to_string();
+    for name in names {
+        text = set_member(&text, servers_path, name, &servers[name])?;
+    }
+    Ok(text)
+}
+
+/// Set `key` to `value` in the object at `path`, editing `text` in place.
+/// Objects missing along the path are created. Comments, formatting and
+/// member order elsewhere in the document are untouched.
+pub fn set_member(text: &str, path: &[&str], key: &str, value: &Value) -> Result<String> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser {
+        text,
+        tokens: &tokens,
+        pos: 0,
+    };
+    let root = match parser.value()? {
+        Node::Object(object) => object,
+        Node::Other => bail!("The config does not contain a JSON object"),
+    };
+    let indent = indent_unit(text);
+
+    let mut object = &root;
+    for (depth, segment) in path.iter().enumerate() {
+        let member = object.members.iter().find(|m| m.key == *segment);
+        match member {
+            Some(Member {
+                value: Node::Object(child),
+                ..
+            }) => object = child,
+            Some(_) => bail!(
+                "\"{}\" in the config is not an object",
+                path[..=depth].join(".")
+            ),
+            None => {
+                // Build the rest of the path as the new member's value
+                let mut nested = serde_json::Map::new();
+                nested.insert(key.to_string(), value.clone());
+                let mut nested = Value::Object(nested);
+                for missing in path[depth + 1..].iter().rev() {
+                    let mut wrapper = serde_json::Map::new();
+                    wrapper.insert(missing.to_string(), nested);
+                    nested = Value::Object(wrapper);
+                }
+                return Ok(insert_member(text, object, segment, &nested, &indent));
+            }
+        }
+    }
+
+    match object.members.iter().find(|m| m.key == key) {
+        Some(member) => {
+            let member_indent = line_indent(text, member.key_start);
+            let rendered = render(value, &indent, &member_indent);
+            let mut out = String::with_capacity(text.len() + rendered.len());
+            out.push_str(&text[..member.value_start]);
+            out.push_str(&rendered);
+            out.push_str(&text[member.value_end..]);
+            Ok(out)
+        }
+        None => Ok(insert_member(text, object, key, value, &indent)),
+    }
+}
+
+/// Kind of a line in a [`diff_lines`] result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+/// One line of a diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// What writing a config would change, line by line
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConfigDiff {
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl ConfigDiff {
+    fn new(lines: Vec<DiffLine>) -> Self {
+        let count = |kind| lines.iter().filter(|l: &&DiffLine| l.kind == kind).count();
+        Self {
+            added: count(DiffKind::Added),
+            removed: count(DiffKind::Removed),
+            lines,
+        }
+    }
+
+    /// Whether writing would change anything
+    pub fn has_changes(&self) -> bool {
+        self.added + self.removed > 0
+    }
+}
+
+/// Line diff of `old` against `new` (longest common subsequence)
+pub fn diff_lines(old: &str, new: &str) -> ConfigDiff {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        let mut lines: Vec<DiffLine> = old.iter().map(|t| line(DiffKind::Removed, t)).collect();
+        lines.extend(new.iter().map(|t| line(DiffKind::Added, t)));
+        return ConfigDiff::new(lines);
+    }
+
+    // lcs[i][j]: common lines of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(line(DiffKind::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(line(DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            lines.push(line(DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|t| line(DiffKind::Removed, t)));
+    lines.extend(new[j..].iter().map(|t| line(DiffKind::Added, t)));
+    ConfigDiff::new(lines)
+}
+
+/// Add a member at the end of `object`
+fn insert_member(text: &str, object: &Object, key: &str, value: &Value, indent: &str) -> String {
+    let quoted_key = serde_json::to_string(key).unwrap_or_default();
+    let mut out = String::with_capacity(text.len() + 64);
+
+    let Some(last) = object.members.last() else {
+        // Empty object: open it up onto its own lines
+        let base = line_indent(text, object.open);
+        let member_indent = format!("{}{}", base, indent);
+        let member = format!(
+            "\n{}{}: {}\n{}",
+            member_indent,
+            quoted_key,
+            render(value, indent, &member_indent),
+            base
+        );
+        let inner = &text[object.open + 1..object.close];
+        out.push_str(&text[..=object.open]);
+        if inner.trim().is_empty() {
+            out.push_str(&member);
+        } else {
+            // Only comments inside; keep them above the new member
+            out.push_str(inner.trim_end());
+            out.push_str(&member);
+        }
+        out.push_str(&text[object.close..]);
+        return out;
+    };
+
+    let single_line = !text[object.open..object.close].contains('\n');
+    let member_indent = line_indent(text, last.key_start);
+    let rendered = if single_line {
+        value.to_string()
+    } else {
+        render(value, indent, &member_indent)
+    };
+
+    // A trailing comma (JSONC) is kept; otherwise one goes right after the
+    // last value, before any comment that follows it on the line
+    let (comma, after) = match last.trailing_comma {
+        Some(comma) => ("", comma + 1),
+        None => (",", last.value_end),
+    };
+    // Insert at the end of the line when only a comment follows
+    let rest_of_line_end = text[after..]
+        .find('\n')
+        .map_or(object.close, |n| after + n)
+        .min(object.close);
+    let rest = text[after..rest_of_line_end].trim();
+    let insert_at = if !single_line && (rest.is_empty() || rest.starts_with("//")) {
+        rest_of_line_end
+    } else {
+        after
+    };
+    let member = if single_line {
+        format!(" {}: {}", quoted_key, rendered)
+    } else {
+        format!("\n{}{}: {}", member_indent, quoted_key, rendered)
+    };
+    let trailing = if last.trailing_comma.is_some() && !single_line {
+        ","
+    } else {
+        ""
+    };
+
+    out.push_str(&text[..last.value_end]);
+    out.push_str(comma);
+    out.push_str(&text[last.value_end..insert_at]);
+    out.push_str(&member);
+    out.push_str(trailing);
+    out.push_str(&text[insert_at..]);
+    out
+}
+
+/// Pretty-print `value` for a member whose line starts with `member_indent`
+fn render(value: &Value, indent: &str, member_indent: &str) -> String {
+    let mut buf = Vec::new();
+    let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+    let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    if value.serialize(&mut ser).is_err() {
+        return value.to_string();
+    }
+    let pretty = String::from_utf8(buf).unwrap_or_else(|_| value.to_string());
+    pretty.replace('\n', &format!("\n{}", member_indent))
+}
+
+/// Leading whitespace of the line containing byte `pos`
+fn line_indent(text: &str, pos: usize) -> String {
+    let start = text[..pos].rfind('\n').map_or(0, |n| n + 1);
+    text[start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// The indentation step the file uses: that of its first indented line
+fn indent_unit(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or_else(|| DEFAULT_INDENT.to_string())
+}
+
+// --- JSONC scanning ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Colon,
+    Comma,
+    String,
+    /// Number, `true`, `false` or `null`
+    Literal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Split JSONC into tokens, skipping whitespace and comments
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..]
+                    .find("*/")
+                    .map(|n| i + 2 + n + 2)
+                    .context("Unterminated comment in config")?;
+                continue;
+            }
+            b'{' => TokenKind::OpenBrace,
+            b'}' => TokenKind::CloseBrace,
+            b'[' => TokenKind::OpenBracket,
+            b']' => TokenKind::CloseBracket,
+            b':' => TokenKind::Colon,
+            b',' => TokenKind::Comma,
+            b'"' => {
+                i += 1;
+                loop {
+                    match bytes.get(i) {
+                        Some(b'\\') => i += 2,
+                        Some(b'"') => break,
+                        Some(_) => i += 1,
+                        None => bail!("Unterminated string in config"),
+                    }
+                }
+                TokenKind::String
+            }
+            c if c == b'-' || c.is_ascii_alphanumeric() => {
+                while i + 1 < bytes.len()
+                    && (bytes[i + 1].is_ascii_alphanumeric() || b"+-.".contains(&bytes[i + 1]))
+                {
+                    i += 1;
+                }
+                TokenKind::Literal
+            }
+            _ => bail!("Unexpected character at byte {} of the config", i),
+        };
+        i += 1;
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    Ok(tokens)
+}
+
+enum Node {
+    Object(Object),
+    Other,
+}
+
+struct Object {
+    /// Byte offsets of the braces
+    open: usize,
+    close: usize,
+    members: Vec<Member>,
+}
+
+struct Member {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+    value: Node,
+    /// Byte offset of a comma after the last member (allowed in JSONC)
+    trailing_comma: Option<usize>,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Result<Token> {
+        let token = *self
+            .tokens
+            .get(self.pos)
+            .context("Unexpected end of config")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn peek(&self) -> Option<TokenKind> {
+        self.tokens.get(self.pos).map(|t| t.kind)
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<Token> {
+        let token = self.next()?;
+        if token.kind != kind {
+            bail!("Config is not valid JSON near byte {}", token.start);
+        }
+        Ok(token)
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        Ok(self.spanned_value()?.0)
+    }
+
+    /// Parse a value; returns it with its start and end byte offsets
+    fn spanned_value(&mut self) -> Result<(Node, usize, usize)> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::OpenBrace => {
+                let object = self.object(token.start)?;
+                let end = object.close + 1;
+                Ok((Node::Object(object), token.start, end))
+            }
+            TokenKind::OpenBracket => {
+                let mut end;
+                loop {
+                    if self.peek() == Some(TokenKind::CloseBracket) {
+                        end = self.next()?.end;
+                        break;
+                    }
+                    self.spanned_value()?;
+                    let separator = self.next()?;
+                    end = separator.end;
+                    match separator.kind {
+                        TokenKind::Comma => continue,
+                        TokenKind::CloseBracket => break,
+                        _ => bail!("Config is not valid JSON near byte {}", separator.start),
+                    }
+                }
+                Ok((Node::Other, token.start, end))
+            }
+            TokenKind::String | TokenKind::Literal => Ok((Node::Other, token.start, token.end)),
+            _ => bail!("Config is not valid JSON near byte {}", token.start),
+        }
+    }
+
+    /// Parse the rest of an object whose `{` is at byte `open`
+    fn object(&mut self, open: usize) -> Result<Object> {
+        let mut members: Vec<Member> = Vec::new();
+        loop {
+            if self.peek() == Some(TokenKind::CloseBrace) {
+                let close = self.next()?.start;
+                return Ok(Object {
+                    open,
+                    close,
+                    members,
+                });
+            }
+            let key_token = self.expect(TokenKind::String)?;
+            let key: String = serde_json::from_str(&self.text[key_token.start..key_token.end])
+                .with_context(|| {
+                    format!("Invalid key at byte {} of the config", key_token.start)
+                })?;
+            self.expect(TokenKind::Colon)?;
+            let (value, value_start, value_end) = self.spanned_value()?;
+            members.push(Member {
+                key,
+                key_start: key_token.start,
+                value_start,
+                value_end,
+                value,
+                trailing_comma: None,
+            });
+            let separator = self.next()?;
+            match separator.kind {
+                TokenKind::Comma => {
+                    if self.peek() == Some(TokenKind::CloseBrace) {
+                        if let Some(last) = members.last_mut() {
+                            last.trailing_comma = Some(separator.start);
+                        }
+                    }
+                }
+                TokenKind::CloseBrace => {
+                    return Ok(Object {
+                        open,
+                        close: separator.start,
+                        members,
+                    })
+                }
+                _ => bail!("Config is not valid JSON near byte {}", separator.start),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const EXPORT: &str = r#"{"mcpServers": {"github": {"command": "npx", "args": ["gh"]}, "fs": {"url": "http://fs"}}}"#;
+
+    #[test]
+    fn merge_keeps_comments_order_and_unknown_keys() {
+        let existing = r#"{
+    // Personal servers
+    "theme": "dark",
+    "mcpServers": {
+        "github": { "command": "old", "env": { "TOKEN": "x" } },
+        "mine": { "command": "mine" } // added by hand
+    },
+    "zzz": [1, 2, /* two */ 3]
+}
+"#;
+        let merged = merge_config(existing, EXPORT, &["mcpServers"]).unwrap();
+        let expected = r#"{
+    // Personal servers
+    "theme": "dark",
+    "mcpServers": {
+        "github": {
+            "command": "npx",
+            "args": [
+                "gh"
+            ]
+        },
+        "mine": { "command": "mine" }, // added by hand
+        "fs": {
+            "url": "http://fs"
+        }
+    },
+    "zzz": [1, 2, /* two */ 3]
+}
+"#;
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn merge_accepts_commented_exports() {
+        let export = "// Gets: Starter\n{\"context_servers\": {\"mcpmux\": {\"url\": \"u\"}}}";
+        let merged = merge_config("{\"theme\": \"x\"}", export, &["context_servers"]).unwrap();
+        assert_eq!(
+            merged,
+            r#"{"theme": "x", "context_servers": {"mcpmux":{"url":"u"}}}"#
+        );
+    }
+
+    #[test]
+    fn merge_creates_missing_sections() {
+        let merged = merge_config("{\n  \"other\": true,\n}\n", EXPORT, &["mcpServers"]).unwrap();
+        let parsed: Value = serde_json::from_str(&merged.replace(",\n}", "\n}")).unwrap();
+        assert_eq!(parsed["other"], true);
+        assert_eq!(parsed["mcpServers"]["fs"]["url"], "http://fs");
+
+        let nested = set_member("{}", &["a", "b"], "c", &json!(1)).unwrap();
+        assert_eq!(
+            nested,
+            "{\n  \"a\": {\n    \"b\": {\n      \"c\": 1\n    }\n  }\n}"
+        );
+
+        let single = set_member(r#"{"x": 1}"#, &[], "y", &json!({"z": 2})).unwrap();
+        assert_eq!(single, r#"{"x": 1, "y": {"z":2}}"#);
+
+        assert!(set_member(r#"{"mcpServers": []}"#, &["mcpServers"], "a", &json!(1)).is_err());
+        assert!(set_member("{\"a\": ", &[], "b", &json!(1)).is_err());
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nB\nc\nd\n");
+        let kinds: Vec<DiffKind> = diff.lines.iter().map(|l| l.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                DiffKind::Unchanged,
+                DiffKind::Removed,
+                DiffKind::Added,
+                DiffKind::Unchanged,
+                DiffKind::Added
+            ]
+        );
+        assert_eq!((diff.added, diff.removed), (2, 1));
+        assert!(!diff_lines("same", "same").has_changes());
+    }
+}
@@ -0,0 +1,925 @@
+//! Config sync between machines (opt-in).
+//!
+//! Serializes non-secret state — Spaces, installed servers, FeatureSets and
+//! workspace bindings — into a [`SyncSnapshot`] stored at a user-chosen
+//! [`SyncTarget`]. Each sync is a three-way merge between the last snapshot
+//! this machine synced (the *base*), the local state and the remote snapshot:
+//! a side that didn't change since the base yields to the one that did, and
+//! entities changed on both sides are resolved by [`ConflictStrategy`].
+//!
+//! Secrets never leave the machine: input values and OAuth state are dropped,
+//! and env/header overrides are only synced when they hold secret references
+//! (`${env:...}`, `${keychain:...}`, ...). Local secrets are kept when a
+//! remote change is applied. Servers installed from user config files are
+//! skipped — those files are the source of truth and can be synced directly.
+//!
+//! FeatureSet members reference discovered features by
+//! `(server_id, feature_type, feature_name)` because feature ids are assigned
+//! per machine.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::sync_target::SyncTarget;
+use crate::domain::{
+    FeatureSet, InstallationSource, InstalledServer, MemberMode, MemberType, Space,
+    WorkspaceBinding,
+};
+use crate::repository::{
+    FeatureSetRepository, InstalledServerRepository, ServerFeatureRepository, SpaceRepository,
+    WorkspaceBindingRepository,
+};
+
+/// Current snapshot format version.
+pub const SYNC_FORMAT_VERSION: u32 = 1;
+
+/// File (under the sync state dir) holding the last synced snapshot.
+const BASE_SNAPSHOT_FILE: &str = "sync-base.json";
+
+/// How to resolve an entity changed on both machines since the last sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Keep the version with the later `updated_at` (local on ties)
+    #[default]
+    NewestWins,
+    PreferLocal,
+    PreferRemote,
+}
+
+/// Which side a conflict was resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncSide {
+    Local,
+    Remote,
+}
+
+/// An entity changed on both sides since the last sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    /// `space`, `installed_server`, `feature_set` or `workspace_binding`
+    pub entity: String,
+    pub id: String,
+    pub kept: SyncSide,
+}
+
+/// FeatureSet member referencing a feature by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SyncedFeatureMember {
+    pub server_id: String,
+    pub feature_type: String,
+    pub feature_name: String,
+    pub mode: String,
+}
+
+/// FeatureSet with portable members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedFeatureSet {
+    #[serde(flatten)]
+    pub feature_set: FeatureSet,
+    #[serde(default)]
+    pub feature_members: Vec<SyncedFeatureMember>,
+}
+
+/// Non-secret configuration exchanged between machines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub format_version: u32,
+    /// Machine that wrote the snapshot
+    pub device: String,
+    pub created_at: DateTime<Utc>,
+    pub spaces: Vec<Space>,
+    pub installed_servers: Vec<InstalledServer>,
+    pub feature_sets: Vec<SyncedFeatureSet>,
+    pub workspace_bindings: Vec<WorkspaceBinding>,
+}
+
+impl SyncSnapshot {
+    /// Whether both snapshots hold the same entities (ignoring metadata).
+    pub fn same_content(&self, other: &SyncSnapshot) -> bool {
+        let content = |s: &SyncSnapshot| {
+            serde_json::to_value((
+                &s.spaces,
+                &s.installed_servers,
+                &s.feature_sets,
+                &s.workspace_bindings,
+            ))
+            .ok()
+        };
+        content(self) == content(other)
+    }
+}
+
+/// Outcome of a sync run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Location synced with
+    pub target: String,
+    /// Local entities created, updated or deleted
+    pub applied: usize,
+    /// Whether a new snapshot was pushed
+    pub pushed: bool,
+    pub conflicts: Vec<SyncConflict>,
+    /// FeatureSet members whose feature isn't discovered on this machine yet
+    pub pending_members: usize,
+}
+
+/// Whether a value only refers to a secret instead of containing one.
+fn is_secret_reference(value: &str) -> bool {
+    value.contains("${")
+}
+
+/// Remove everything secret or machine-specific from an installed server.
+fn strip_secrets(mut server: InstalledServer) -> InstalledServer {
+    server.input_values.clear();
+    server.oauth_connected = false;
+    server.last_used_at = None;
+    server.deleted_at = None;
+    server.env_overrides.retain(|_, v| is_secret_reference(v));
+    server.extra_headers.retain(|_, v| is_secret_reference(v));
+    server
+}
+
+/// Name of this machine, recorded in pushed snapshots.
+fn device_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Syncs local configuration with a [`SyncTarget`].
+pub struct ConfigSyncService {
+    space_repo: Arc<dyn SpaceRepository>,
+    installed_repo: Arc<dyn InstalledServerRepository>,
+    feature_set_repo: Arc<dyn FeatureSetRepository>,
+    binding_repo: Arc<dyn WorkspaceBindingRepository>,
+    feature_repo: Arc<dyn ServerFeatureRepository>,
+    state_dir: PathBuf,
+    /// Serializes manual and scheduled runs
+    run_lock: tokio::sync::Mutex<()>,
+}
+
+impl ConfigSyncService {
+    /// Create a sync service keeping its base snapshot in `state_dir`.
+    pub fn new(
+        space_repo: Arc<dyn SpaceRepository>,
+        installed_repo: Arc<dyn InstalledServerRepository>,
+        feature_set_repo: Arc<dyn FeatureSetRepository>,
+        binding_repo: Arc<dyn WorkspaceBindingRepository>,
+        feature_repo: Arc<dyn ServerFeatureRepository>,
+        state_dir: PathBuf,
+    ) -> Self {
+        Self {
+            space_repo,
+            installed_repo,
+            feature_set_repo,
+            binding_repo,
+            feature_repo,
+            state_dir,
+            run_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Pull, merge, apply locally and push.
+    pub async fn sync(
+        &self,
+        target: &dyn SyncTarget,
+        strategy: ConflictStrategy,
+    ) -> Result<SyncReport> {
+        let _guard = self.run_lock.lock().await;
+        let mut report = SyncReport {
+            target: target.describe(),
+            ..Default::default()
+        };
+        info!("[Sync] Syncing with {}", report.target);
+
+        let base = self.load_base().await?;
+        let local = self.snapshot(base.as_ref()).await?;
+        let remote = match target.pull().await? {
+            Some(data) => Some(parse_snapshot(&data)?),
+            None => None,
+        };
+
+        let mut merged = match &remote {
+            Some(remote) => {
+                let (merged, conflicts) = merge_snapshots(base.as_ref(), &local, remote, strategy)?;
+                report.conflicts = conflicts;
+                merged
+            }
+            None => local.clone(),
+        };
+
+        self.apply(&local, &merged, &mut report).await?;
+
+        merged.device = device_name();
+        merged.created_at = Utc::now();
+        if remote.as_ref().is_none_or(|r| !r.same_content(&merged)) {
+            target.push(&serde_json::to_vec_pretty(&merged)?).await?;
+            report.pushed = true;
+        }
+        self.save_base(&merged).await?;
+
+        info!(
+            "[Sync] Done: {} local change(s), {} conflict(s), pushed: {}",
+            report.applied,
+            report.conflicts.len(),
+            report.pushed
+        );
+        Ok(report)
+    }
+
+    /// Collect the local, secret-free snapshot.
+    ///
+    /// Members of `base` whose feature isn't discovered here are carried over:
+    /// the user can't have removed what this machine never saw.
+    pub async fn snapshot(&self, base: Option<&SyncSnapshot>) -> Result<SyncSnapshot> {
+        let mut spaces = self.space_repo.list().await?;
+        for space in &mut spaces {
+            // Each machine has its own default space
+            space.is_default = false;
+        }
+        spaces.sort_by_key(|s| s.id);
+
+        let mut installed_servers: Vec<InstalledServer> = self
+            .installed_repo
+            .list()
+            .await?
+            .into_iter()
+            .filter(|s| !matches!(s.source, InstallationSource::UserConfig { .. }))
+            .map(strip_secrets)
+            .collect();
+        installed_servers.sort_by_key(|s| s.id);
+
+        let base_members: HashMap<&str, &Vec<SyncedFeatureMember>> = base
+            .map(|b| {
+                b.feature_sets
+                    .iter()
+                    .map(|fs| (fs.feature_set.id.as_str(), &fs.feature_members))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut feature_sets = Vec::new();
+        for mut fs in self.feature_set_repo.list().await? {
+            let Some(space_id) = fs.space_id.clone() else {
+                continue;
+            };
+            if fs.is_deleted {
+                continue;
+            }
+            let features = self.feature_repo.list_for_space(&space_id).await?;
+            let by_id: HashMap<String, _> =
+                features.iter().map(|f| (f.id.to_string(), f)).collect();
+            let known: HashSet<(&str, &str, &str)> = features
+                .iter()
+                .map(|f| {
+                    (
+                        f.server_id.as_str(),
+                        f.feature_type.as_str(),
+                        f.feature_name.as_str(),
+                    )
+                })
+                .collect();
+
+            let mut members: Vec<SyncedFeatureMember> = self
+                .feature_set_repo
+                .get_feature_members(&fs.id)
+                .await?
+                .into_iter()
+                .filter(|m| m.member_type == MemberType::Feature)
+                .filter_map(|m| {
+                    by_id.get(&m.member_id).map(|f| SyncedFeatureMember {
+                        server_id: f.server_id.clone(),
+                        feature_type: f.feature_type.as_str().to_string(),
+                        feature_name: f.feature_name.clone(),
+                        mode: m.mode.as_str().to_string(),
+                    })
+                })
+                .collect();
+            if let Some(previous) = base_members.get(fs.id.as_str()) {
+                members.extend(
+                    previous
+                        .iter()
+                        .filter(|m| {
+                            !known.contains(&(
+                                m.server_id.as_str(),
+                                m.feature_type.as_str(),
+                                m.feature_name.as_str(),
+                            ))
+                        })
+                        .cloned(),
+                );
+            }
+            members.sort();
+            members.dedup();
+
+            fs.members.clear();
+            feature_sets.push(SyncedFeatureSet {
+                feature_set: fs,
+                feature_members: members,
+            });
+        }
+        feature_sets.sort_by(|a, b| a.feature_set.id.cmp(&b.feature_set.id));
+
+        let mut workspace_bindings = self.binding_repo.list().await?;
+        workspace_bindings.sort_by_key(|b| b.id);
+
+        Ok(SyncSnapshot {
+            format_version: SYNC_FORMAT_VERSION,
+            device: device_name(),
+            created_at: Utc::now(),
+            spaces,
+            installed_servers,
+            feature_sets,
+            workspace_bindings,
+        })
+    }
+
+    /// Bring local state from `local` to `merged`.
+    async fn apply(
+        &self,
+        local: &SyncSnapshot,
+        merged: &SyncSnapshot,
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        // Creates and updates, parents first
+        let default_space = self.space_repo.get_default().await?.map(|s| s.id);
+        let local_spaces = index(&local.spaces, |s| s.id.to_string());
+        for space in &merged.spaces {
+            match local_spaces.get(&space.id.to_string()) {
+                None => {
+                    self.space_repo.create(space).await?;
+                    self.feature_set_repo
+                        .ensure_builtin_for_space(&space.id.to_string())
+                        .await?;
+                    report.applied += 1;
+                }
+                Some(existing) if !same(*existing, space) => {
+                    let mut updated = space.clone();
+                    updated.is_default = default_space == Some(space.id);
+                    self.space_repo.update(&updated).await?;
+                    report.applied += 1;
+                }
+                Some(_) => {}
+            }
+        }
+
+        let local_servers = index(&local.installed_servers, |s| s.id.to_string());
+        let trashed: HashSet<Uuid> = self
+            .installed_repo
+            .list_trashed()
+            .await?
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        for server in &merged.installed_servers {
+            match local_servers.get(&server.id.to_string()) {
+                None => {
+                    // Synced back while in this machine's trash
+                    if trashed.contains(&server.id) {
+                        self.installed_repo.uninstall(&server.id).await?;
+                    }
+                    self.installed_repo.install(server).await?;
+                    report.applied += 1;
+                }
+                Some(existing) if !same(*existing, server) => {
+                    let mut updated = server.clone();
+                    // Keep this machine's secrets
+                    if let Some(current) = self.installed_repo.get(&server.id).await? {
+                        updated.input_values = current.input_values;
+                        updated.oauth_connected = current.oauth_connected;
+                        for (k, v) in current.env_overrides {
+                            if !is_secret_reference(&v) {
+                                updated.env_overrides.entry(k).or_insert(v);
+                            }
+                        }
+                        for (k, v) in current.extra_headers {
+                            if !is_secret_reference(&v) {
+                                updated.extra_headers.entry(k).or_insert(v);
+                            }
+                        }
+                    }
+                    self.installed_repo.update(&updated).await?;
+                    report.applied += 1;
+                }
+                Some(_) => {}
+            }
+        }
+
+        let local_sets = index(&local.feature_sets, |fs| fs.feature_set.id.clone());
+        for fs in &merged.feature_sets {
+            match local_sets.get(&fs.feature_set.id) {
+                None => {
+                    // Builtin sets have per-space ids and may already exist
+                    // (e.g. created with a Space applied above)
+                    if self
+                        .feature_set_repo
+                        .get(&fs.feature_set.id)
+                        .await?
+                        .is_some()
+                    {
+                        self.feature_set_repo.update(&fs.feature_set).await?;
+                    } else {
+                        self.feature_set_repo.create(&fs.feature_set).await?;
+                    }
+                    report.pending_members += self.apply_members(fs).await?;
+                    report.applied += 1;
+                }
+                Some(existing) if !same(*existing, fs) => {
+                    self.feature_set_repo.update(&fs.feature_set).await?;
+                    report.pending_members += self.apply_members(fs).await?;
+                    report.applied += 1;
+                }
+                Some(_) => {
+                    report.pending_members += self.unresolved_members(fs).await?;
+                }
+            }
+        }
+
+        let local_bindings = index(&local.workspace_bindings, |b| b.id.to_string());
+        for binding in &merged.workspace_bindings {
+            match local_bindings.get(&binding.id.to_string()) {
+                None => {
+                    self.binding_repo.create(binding).await?;
+                    report.applied += 1;
+                }
+                Some(existing) if *existing != binding => {
+                    self.binding_repo.update(binding).await?;
+                    report.applied += 1;
+                }
+                Some(_) => {}
+            }
+        }
+
+        // Deletions, children first
+        let keep: HashSet<Uuid> = merged.workspace_bindings.iter().map(|b| b.id).collect();
+        for binding in local
+            .workspace_bindings
+            .iter()
+            .filter(|b| !keep.contains(&b.id))
+        {
+            self.binding_repo.delete(&binding.id).await?;
+            report.applied += 1;
+        }
+
+        let keep: HashSet<&str> = merged
+            .feature_sets
+            .iter()
+            .map(|fs| fs.feature_set.id.as_str())
+            .collect();
+        for fs in local
+            .feature_sets
+            .iter()
+            .filter(|fs| !keep.contains(fs.feature_set.id.as_str()) && !fs.feature_set.is_builtin)
+        {
+            self.feature_set_repo.delete(&fs.feature_set.id).await?;
+            report.applied += 1;
+        }
+
+        let keep: HashSet<Uuid> = merged.installed_servers.iter().map(|s| s.id).collect();
+        for server in local
+            .installed_servers
+            .iter()
+            .filter(|s| !keep.contains(&s.id))
+        {
+            self.installed_repo.uninstall(&server.id).await?;
+            report.applied += 1;
+        }
+
+        let keep: HashSet<Uuid> = merged.spaces.iter().map(|s| s.id).collect();
+        for space in local.spaces.iter().filter(|s| !keep.contains(&s.id)) {
+            if default_space == Some(space.id) {
+                warn!("[Sync] Not deleting default space {}", space.name);
+                continue;
+            }
+            self.space_repo.delete(&space.id).await?;
+            report.applied += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Make a FeatureSet's feature members match `fs`. Returns how many
+    /// members reference features not discovered on this machine.
+    async fn apply_members(&self, fs: &SyncedFeatureSet) -> Result<usize> {
+        let Some(space_id) = fs.feature_set.space_id.as_deref() else {
+            return Ok(0);
+        };
+        let features = self.feature_repo.list_for_space(space_id).await?;
+        let lookup: HashMap<(&str, &str, &str), String> = features
+            .iter()
+            .map(|f| {
+                (
+                    (
+                        f.server_id.as_str(),
+                        f.feature_type.as_str(),
+                        f.feature_name.as_str(),
+                    ),
+                    f.id.to_string(),
+                )
+            })
+            .collect();
+
+        let mut desired: HashMap<String, MemberMode> = HashMap::new();
+        let mut pending = 0;
+        for m in &fs.feature_members {
+            match lookup.get(&(
+                m.server_id.as_str(),
+                m.feature_type.as_str(),
+                m.feature_name.as_str(),
+            )) {
+                Some(id) => {
+                    let mode = MemberMode::parse(&m.mode).unwrap_or_default();
+                    desired.insert(id.clone(), mode);
+                }
+                None => pending += 1,
+            }
+        }
+
+        let id = &fs.feature_set.id;
+        for current in self.feature_set_repo.get_feature_members(id).await? {
+            if current.member_type != MemberType::Feature {
+                continue;
+            }
+            match desired.remove(&current.member_id) {
+                Some(mode) if mode == current.mode => {}
+                Some(mode) => {
+                    self.feature_set_repo
+                        .remove_feature_member(id, &current.member_id)
+                        .await?;
+                    self.feature_set_repo
+                        .add_feature_member(id, &current.member_id, mode)
+                        .await?;
+                }
+                None => {
+                    self.feature_set_repo
+                        .remove_feature_member(id, &current.member_id)
+                        .await?;
+                }
+            }
+        }
+        for (feature_id, mode) in desired {
+            self.feature_set_repo
+                .add_feature_member(id, &feature_id, mode)
+                .await?;
+        }
+        Ok(pending)
+    }
+
+    /// Members of `fs` whose feature isn't discovered on this machine.
+    async fn unresolved_members(&self, fs: &SyncedFeatureSet) -> Result<usize> {
+        let Some(space_id) = fs.feature_set.space_id.as_deref() else {
+            return Ok(0);
+        };
+        let features = self.feature_repo.list_for_space(space_id).await?;
+        let known: HashSet<(&str, &str, &str)> = features
+            .iter()
+            .map(|f| {
+                (
+                    f.server_id.as_str(),
+                    f.feature_type.as_str(),
+                    f.feature_name.as_str(),
+                )
+            })
+            .collect();
+        Ok(fs
+            .feature_members
+            .iter()
+            .filter(|m| {
+                !known.contains(&(
+                    m.server_id.as_str(),
+                    m.feature_type.as_str(),
+                    m.feature_name.as_str(),
+                ))
+            })
+            .count())
+    }
+
+    async fn load_base(&self) -> Result<Option<SyncSnapshot>> {
+        match tokio::fs::read(self.state_dir.join(BASE_SNAPSHOT_FILE)).await {
+            Ok(data) => match parse_snapshot(&data) {
+                Ok(snapshot) => Ok(Some(snapshot)),
+                Err(e) => {
+                    // Without a base every difference becomes a conflict,
+                    // which is safe; losing the file must not block sync.
+                    warn!("[Sync] Ignoring unreadable base snapshot: {}", e);
+                    Ok(None)
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context("Failed to read sync base snapshot"),
+        }
+    }
+
+    async fn save_base(&self, snapshot: &SyncSnapshot) -> Result<()> {
+        tokio::fs::create_dir_all(&self.state_dir).await?;
+        tokio::fs::write(
+            self.state_dir.join(BASE_SNAPSHOT_FILE),
+            serde_json::to_vec_pretty(snapshot)?,
+        )
+        .await
+        .context("Failed to write sync base snapshot")
+    }
+}
+
+/// Parse a snapshot, rejecting versions newer than this build.
+pub fn parse_snapshot(data: &[u8]) -> Result<SyncSnapshot> {
+    let snapshot: SyncSnapshot =
+        serde_json::from_slice(data).context("Not a McpMux sync snapshot")?;
+    if snapshot.format_version > SYNC_FORMAT_VERSION {
+        anyhow::bail!(
+            "Sync snapshot version {} is newer than supported version {}; update McpMux",
+            snapshot.format_version,
+            SYNC_FORMAT_VERSION
+        );
+    }
+    Ok(snapshot)
+}
+
+/// Three-way merge of `local` and `remote` against their common `base`.
+pub fn merge_snapshots(
+    base: Option<&SyncSnapshot>,
+    local: &SyncSnapshot,
+    remote: &SyncSnapshot,
+    strategy: ConflictStrategy,
+) -> Result<(SyncSnapshot, Vec<SyncConflict>)> {
+    let mut conflicts = Vec::new();
+    let merged = SyncSnapshot {
+        format_version: SYNC_FORMAT_VERSION,
+        device: local.device.clone(),
+        created_at: Utc::now(),
+        spaces: merge_entities(
+            "space",
+            base.map(|b| b.spaces.as_slice()),
+            &local.spaces,
+            &remote.spaces,
+            strategy,
+            &mut conflicts,
+        )?,
+        installed_servers: merge_entities(
+            "installed_server",
+            base.map(|b| b.installed_servers.as_slice()),
+            &local.installed_servers,
+            &remote.installed_servers,
+            strategy,
+            &mut conflicts,
+        )?,
+        feature_sets: merge_entities(
+            "feature_set",
+            base.map(|b| b.feature_sets.as_slice()),
+            &local.feature_sets,
+            &remote.feature_sets,
+            strategy,
+            &mut conflicts,
+        )?,
+        workspace_bindings: merge_entities(
+            "workspace_binding",
+            base.map(|b| b.workspace_bindings.as_slice()),
+            &local.workspace_bindings,
+            &remote.workspace_bindings,
+            strategy,
+            &mut conflicts,
+        )?,
+    };
+    Ok((merged, conflicts))
+}
+
+/// Merge one entity collection keyed by its `id` field.
+fn merge_entities<T: Serialize + DeserializeOwned>(
+    entity: &str,
+    base: Option<&[T]>,
+    local: &[T],
+    remote: &[T],
+    strategy: ConflictStrategy,
+    conflicts: &mut Vec<SyncConflict>,
+) -> Result<Vec<T>> {
+    let base = by_id(base.unwrap_or_default())?;
+    let local = by_id(local)?;
+    let remote = by_id(remote)?;
+
+    let ids: std::collections::BTreeSet<&String> = base
+        .keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .collect();
+
+    let mut merged = Vec::new();
+    for id in ids {
+        let (b, l, r) = (base.get(id), local.get(id), remote.get(id));
+        let chosen = if l == r || r == b {
+            l
+        } else if l == b {
+            r
+        } else {
+            let kept = resolve_conflict(l, r, strategy);
+            conflicts.push(SyncConflict {
+                entity: entity.to_string(),
+                id: id.clone(),
+                kept,
+            });
+            match kept {
+                SyncSide::Local => l,
+                SyncSide::Remote => r,
+            }
+        };
+        if let Some(value) = chosen {
+            merged.push(serde_json::from_value(value.clone())?);
+        }
+    }
+    Ok(merged)
+}
+
+fn resolve_conflict(
+    local: Option<&Value>,
+    remote: Option<&Value>,
+    strategy: ConflictStrategy,
+) -> SyncSide {
+    match strategy {
+        ConflictStrategy::PreferLocal => SyncSide::Local,
+        ConflictStrategy::PreferRemote => SyncSide::Remote,
+        ConflictStrategy::NewestWins => match (local, remote) {
+            // An edit beats a deletion
+            (Some(_), None) => SyncSide::Local,
+            (None, Some(_)) => SyncSide::Remote,
+            (l, r) => {
+                if updated_at(r) > updated_at(l) {
+                    SyncSide::Remote
+                } else {
+                    SyncSide::Local
+                }
+            }
+        },
+    }
+}
+
+fn updated_at(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    value?
+        .get("updated_at")?
+        .as_str()?
+        .parse::<DateTime<Utc>>()
+        .ok()
+}
+
+fn by_id<T: Serialize>(items: &[T]) -> Result<BTreeMap<String, Value>> {
+    items
+        .iter()
+        .map(|item| {
+            let value = serde_json::to_value(item)?;
+            let id = match value.get("id") {
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => anyhow::bail!("Sync entity without id"),
+            };
+            Ok((id, value))
+        })
+        .collect()
+}
+
+fn index<T, F: Fn(&T) -> String>(items: &[T], key: F) -> HashMap<String, &T> {
+    items.iter().map(|item| (key(item), item)).collect()
+}
+
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::FeatureType;
+
+    fn snapshot(spaces: Vec<Space>) -> SyncSnapshot {
+        SyncSnapshot {
+            format_version: SYNC_FORMAT_VERSION,
+            device: "test".to_string(),
+            created_at: Utc::now(),
+            spaces,
+            installed_servers: vec![],
+            feature_sets: vec![],
+            workspace_bindings: vec![],
+        }
+    }
+
+    fn renamed(space: &Space, name: &str, minutes: i64) -> Space {
+        let mut s = space.clone();
+        s.name = name.to_string();
+        s.updated_at = space.updated_at + chrono::Duration::minutes(minutes);
+        s
+    }
+
+    #[test]
+    fn test_one_sided_changes_merge_without_conflict() {
+        let a = Space::new("A");
+        let b = Space::new("B");
+        let base = snapshot(vec![a.clone(), b.clone()]);
+
+        // Local renamed A; remote deleted B and added C
+        let c = Space::new("C");
+        let local = snapshot(vec![renamed(&a, "A2", 1), b.clone()]);
+        let remote = snapshot(vec![a.clone(), c.clone()]);
+
+        let (merged, conflicts) =
+            merge_snapshots(Some(&base), &local, &remote, ConflictStrategy::NewestWins).unwrap();
+        assert!(conflicts.is_empty());
+        let mut names: Vec<_> = merged.spaces.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["A2", "C"]);
+    }
+
+    #[test]
+    fn test_conflict_strategies() {
+        let a = Space::new("A");
+        let base = snapshot(vec![a.clone()]);
+        let local = snapshot(vec![renamed(&a, "local", 1)]);
+        let remote = snapshot(vec![renamed(&a, "remote", 2)]);
+
+        let name = |strategy| {
+            let (merged, conflicts) =
+                merge_snapshots(Some(&base), &local, &remote, strategy).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            merged.spaces[0].name.clone()
+        };
+        assert_eq!(name(ConflictStrategy::NewestWins), "remote");
+        assert_eq!(name(ConflictStrategy::PreferLocal), "local");
+        assert_eq!(name(ConflictStrategy::PreferRemote), "remote");
+    }
+
+    #[test]
+    fn test_edit_beats_delete_when_newest_wins() {
+        let a = Space::new("A");
+        let base = snapshot(vec![a.clone()]);
+        let local = snapshot(vec![]);
+        let remote = snapshot(vec![renamed(&a, "edited", 1)]);
+
+        let (merged, conflicts) =
+            merge_snapshots(Some(&base), &local, &remote, ConflictStrategy::NewestWins).unwrap();
+        assert_eq!(conflicts[0].kept, SyncSide::Remote);
+        assert_eq!(merged.spaces[0].name, "edited");
+    }
+
+    #[test]
+    fn test_first_sync_is_a_union() {
+        let local = snapshot(vec![Space::new("Laptop")]);
+        let remote = snapshot(vec![Space::new("Desktop")]);
+        let (merged, conflicts) =
+            merge_snapshots(None, &local, &remote, ConflictStrategy::NewestWins).unwrap();
+        assert!(conflicts.is_empty());
+        assert_eq!(merged.spaces.len(), 2);
+    }
+
+    #[test]
+    fn test_secrets_are_stripped() {
+        let mut server = InstalledServer::new("space", "github");
+        server.oauth_connected = true;
+        server
+            .input_values
+            .insert("TOKEN".to_string(), "ghp_secret".to_string());
+        server
+            .env_overrides
+            .insert("LITERAL".to_string(), "plain-secret".to_string());
+        server
+            .env_overrides
+            .insert("REF".to_string(), "${keychain:github}".to_string());
+        server.extra_headers.insert(
+            "Authorization".to_string(),
+            "Bearer ${env:API_TOKEN}".to_string(),
+        );
+
+        let stripped = strip_secrets(server);
+        assert!(stripped.input_values.is_empty());
+        assert!(!stripped.oauth_connected);
+        assert_eq!(stripped.env_overrides.len(), 1);
+        assert!(stripped.env_overrides.contains_key("REF"));
+        assert_eq!(stripped.extra_headers.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_newer_format() {
+        let mut s = snapshot(vec![]);
+        s.format_version = SYNC_FORMAT_VERSION + 1;
+        let data = serde_json::to_vec(&s).unwrap();
+        assert!(parse_snapshot(&data).is_err());
+    }
+
+    #[test]
+    fn test_feature_type_names_roundtrip() {
+        // Synced members store feature types by name
+        for t in [
+            FeatureType::Tool,
+            FeatureType::Prompt,
+            FeatureType::Resource,
+        ] {
+            assert_eq!(FeatureType::parse(t.as_str()), Some(t));
+        }
+    }
+}
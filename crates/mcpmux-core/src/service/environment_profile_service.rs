@@ -0,0 +1,190 @@
+//! Environment profile service - dev/staging/prod settings for a Space
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use tracing::info;
+
+use crate::domain::{validate_profile_name, EnvironmentProfile, ProfileServerOverrides};
+use crate::repository::{EnvironmentProfileRepository, InstalledServerRepository};
+
+/// Service for managing environment profiles.
+///
+/// Changes that alter what the active profile applies return the IDs of the
+/// installed servers whose connection settings changed, so the caller can
+/// reconnect them.
+pub struct EnvironmentProfileService {
+    profile_repo: Arc<dyn EnvironmentProfileRepository>,
+    server_repo: Arc<dyn InstalledServerRepository>,
+}
+
+impl EnvironmentProfileService {
+    pub fn new(
+        profile_repo: Arc<dyn EnvironmentProfileRepository>,
+        server_repo: Arc<dyn InstalledServerRepository>,
+    ) -> Self {
+        Self {
+            profile_repo,
+            server_repo,
+        }
+    }
+
+    /// List the profiles of a space
+    pub async fn list(&self, space_id: &str) -> Result<Vec<EnvironmentProfile>> {
+        self.profile_repo.list_for_space(space_id).await
+    }
+
+    /// Get a profile by ID
+    pub async fn get(&self, id: &str) -> Result<EnvironmentProfile> {
+        self.profile_repo
+            .get(id)
+            .await?
+            .ok_or_else(|| anyhow!("Environment profile not found"))
+    }
+
+    /// Create an empty, inactive profile
+    pub async fn create(&self, space_id: &str, name: &str) -> Result<EnvironmentProfile> {
+        let name = validate_profile_name(name).map_err(|e| anyhow!(e))?;
+        self.ensure_name_free(space_id, &name, None).await?;
+
+        let profile = EnvironmentProfile::new(name, space_id);
+        self.profile_repo.create(&profile).await?;
+
+        info!(
+            profile_id = %profile.id,
+            space_id = space_id,
+            name = %profile.name,
+            "[EnvironmentProfileService] Created profile"
+        );
+        Ok(profile)
+    }
+
+    /// Replace a profile's name and overrides. Returns the profile and, if it
+    /// is active, the servers whose settings changed.
+    pub async fn update(
+        &self,
+        id: &str,
+        name: &str,
+        inputs: HashMap<String, String>,
+        mut servers: HashMap<String, ProfileServerOverrides>,
+    ) -> Result<(EnvironmentProfile, Vec<String>)> {
+        let old = self.get(id).await?;
+        let name = validate_profile_name(name).map_err(|e| anyhow!(e))?;
+        self.ensure_name_free(&old.space_id, &name, Some(id))
+            .await?;
+
+        servers.retain(|_, overrides| !overrides.is_empty());
+        for (server_id, overrides) in &servers {
+            if let Some(url) = &overrides.url {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    bail!("URL for {} must start with http:// or https://", server_id);
+                }
+            }
+        }
+
+        let mut profile = old.clone();
+        profile.name = name;
+        profile.inputs = inputs;
+        profile.servers = servers;
+        profile.updated_at = chrono::Utc::now();
+        self.profile_repo.update(&profile).await?;
+
+        let affected = if profile.is_active {
+            self.affected_servers(&profile.space_id, &[&old, &profile])
+                .await?
+        } else {
+            Vec::new()
+        };
+        Ok((profile, affected))
+    }
+
+    /// Delete a profile. Returns the servers whose settings changed, which is
+    /// none unless the profile was active.
+    pub async fn delete(&self, id: &str) -> Result<Vec<String>> {
+        let profile = self.get(id).await?;
+        self.profile_repo.delete(id).await?;
+        info!(
+            profile_id = id,
+            "[EnvironmentProfileService] Deleted profile"
+        );
+
+        if profile.is_active {
+            self.affected_servers(&profile.space_id, &[&profile]).await
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Switch a space to a profile, or back to the servers' own settings with
+    /// `None`. Returns the servers whose settings changed.
+    pub async fn set_active(
+        &self,
+        space_id: &str,
+        profile_id: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let previous = self.profile_repo.get_active(space_id).await?;
+        let next = match profile_id {
+            Some(id) => {
+                let profile = self.get(id).await?;
+                if profile.space_id != space_id {
+                    bail!("Environment profile belongs to another space");
+                }
+                Some(profile)
+            }
+            None => None,
+        };
+
+        if previous.as_ref().map(|p| &p.id) == next.as_ref().map(|p| &p.id) {
+            return Ok(Vec::new());
+        }
+
+        self.profile_repo.set_active(space_id, profile_id).await?;
+        info!(
+            space_id = space_id,
+            profile = next.as_ref().map(|p| p.name.as_str()).unwrap_or("(none)"),
+            "[EnvironmentProfileService] Switched active profile"
+        );
+
+        let profiles: Vec<&EnvironmentProfile> = previous.iter().chain(next.iter()).collect();
+        self.affected_servers(space_id, &profiles).await
+    }
+
+    /// Installed servers of a space that any of `profiles` changes
+    async fn affected_servers(
+        &self,
+        space_id: &str,
+        profiles: &[&EnvironmentProfile],
+    ) -> Result<Vec<String>> {
+        let installed = self.server_repo.list_for_space(space_id).await?;
+        Ok(installed
+            .into_iter()
+            .filter(|server| {
+                let definition = server.get_definition();
+                profiles.iter().any(|profile| match &definition {
+                    Some(def) => profile.affects(&def.transport, &server.server_id),
+                    None => profile.servers.contains_key(&server.server_id),
+                })
+            })
+            .map(|server| server.server_id)
+            .collect())
+    }
+
+    async fn ensure_name_free(
+        &self,
+        space_id: &str,
+        name: &str,
+        except: Option<&str>,
+    ) -> Result<()> {
+        let taken = self
+            .profile_repo
+            .list_for_space(space_id)
+            .await?
+            .into_iter()
+            .any(|p| Some(p.id.as_str()) != except && p.name.eq_ignore_ascii_case(name));
+        if taken {
+            bail!("An environment profile named \"{}\" already exists", name);
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@
 //! Manages port allocation and persistence for the MCP gateway server.
 //! Uses AppSettingsRepository for persistence.
 
+use serde::Serialize;
 use std::net::TcpListener;
 use std::sync::Arc;
 use std::time::Duration;
@@ -89,12 +90,17 @@ pub fn is_port_available(port: u16) -> bool {
 /// prompt afterward.
 pub const AUTOSTART_PORT_WAIT: Duration = Duration::from_secs(6);
 
-/// How often [`wait_for_port_available`] re-probes while waiting.
-const PORT_WAIT_PROBE_INTERVAL: Duration = Duration::from_millis(400);
+/// First re-probe delay of [`wait_for_port_available`]; each later one doubles,
+/// up to [`PORT_WAIT_MAX_PROBE_INTERVAL`].
+const PORT_WAIT_PROBE_INTERVAL: Duration = Duration::from_millis(200);
 
-/// Wait up to `timeout` for `port` to become bind-available, re-probing every
-/// [`PORT_WAIT_PROBE_INTERVAL`]. Returns `true` as soon as the port is free, or
-/// `false` if it never frees within the window.
+/// Longest delay between re-probes of [`wait_for_port_available`].
+const PORT_WAIT_MAX_PROBE_INTERVAL: Duration = Duration::from_millis(1600);
+
+/// Wait up to `timeout` for `port` to become bind-available, re-probing with
+/// backoff: after [`PORT_WAIT_PROBE_INTERVAL`], then twice as long each time,
+/// capped at [`PORT_WAIT_MAX_PROBE_INTERVAL`]. Returns `true` as soon as the
+/// port is free, or `false` if it never frees within the window.
 ///
 /// The common case (port already free) returns immediately with a single
 /// synchronous probe and never sleeps. Only a busy port pays the wait — this
@@ -112,12 +118,14 @@ pub async fn wait_for_port_available(port: u16, timeout: Duration) -> bool {
         port, timeout
     );
 
-    // Track elapsed time by counting fixed-interval sleeps rather than reading
-    // a clock — keeps the loop deterministic and trivial to test.
+    // Track elapsed time by adding up the sleeps rather than reading a
+    // clock — keeps the loop deterministic and trivial to test.
     let mut waited = Duration::ZERO;
+    let mut interval = PORT_WAIT_PROBE_INTERVAL;
     while waited < timeout {
-        tokio::time::sleep(PORT_WAIT_PROBE_INTERVAL).await;
-        waited = waited.saturating_add(PORT_WAIT_PROBE_INTERVAL);
+        tokio::time::sleep(interval).await;
+        waited = waited.saturating_add(interval);
+        interval = (interval * 2).min(PORT_WAIT_MAX_PROBE_INTERVAL);
         if is_port_available(port) {
             info!(
                 "[PortService] Port {} became available after ~{:?}",
@@ -127,9 +135,151 @@ pub async fn wait_for_port_available(port: u16, timeout: Duration) -> bool {
         }
     }
 
+    if let Some(owner) = port_owner(port) {
+        warn!("[PortService] Port {} is held by {}", port, owner);
+    }
     false
 }
 
+/// A process listening on a port
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    /// Executable name, when it can be read
+    pub name: Option<String>,
+}
+
+impl std::fmt::Display for PortOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} (PID {})", name, self.pid),
+            None => write!(f, "PID {}", self.pid),
+        }
+    }
+}
+
+/// The process listening on TCP `port`, if any can be found. Processes of
+/// other users may not be visible, so `None` doesn't mean the port is free.
+#[cfg(target_os = "linux")]
+pub fn port_owner(port: u16) -> Option<PortOwner> {
+    let inodes: Vec<String> = ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|table| std::fs::read_to_string(table).ok())
+        .flat_map(|table| listening_socket_inodes(&table, port))
+        .collect();
+    if inodes.is_empty() {
+        return None;
+    }
+
+    // The socket shows up as a `socket:[<inode>]` link among the owner's fds
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            let inode = target
+                .strip_prefix("socket:[")
+                .and_then(|rest| rest.strip_suffix(']'));
+            if inode.is_some_and(|inode| inodes.iter().any(|i| i == inode)) {
+                return Some(PortOwner {
+                    pid,
+                    name: process_name(pid),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Inodes of the sockets listening on `port` in a `/proc/net/tcp` table
+#[cfg(target_os = "linux")]
+fn listening_socket_inodes(table: &str, port: u16) -> Vec<String> {
+    // Columns: sl, local_address (hex ip:port), rem_address, st (0A = LISTEN),
+    // tx:rx queue, tr:when, retrnsmt, uid, timeout, inode
+    const LISTEN: &str = "0A";
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            if u16::from_str_radix(local_port, 16).ok()? != port || *fields.get(3)? != LISTEN {
+                return None;
+            }
+            fields.get(9).map(|inode| inode.to_string())
+        })
+        .collect()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn port_owner(port: u16) -> Option<PortOwner> {
+    // -F prints one field per line: `p<pid>` then `c<command>`
+    let output = std::process::Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-Fpc"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pid = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix('p'))?
+        .parse()
+        .ok()?;
+    let name = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix('c'))
+        .map(str::to_string);
+    Some(PortOwner { pid, name })
+}
+
+#[cfg(windows)]
+pub fn port_owner(port: u16) -> Option<PortOwner> {
+    // Rows look like `TCP  127.0.0.1:45818  0.0.0.0:0  LISTENING  1234`
+    let output = std::process::Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .ok()?;
+    let suffix = format!(":{}", port);
+    let pid = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, local, _, "LISTENING", pid] if local.ends_with(&suffix) => pid.parse().ok(),
+                _ => None,
+            }
+        })?;
+    Some(PortOwner {
+        pid,
+        name: process_name(pid),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn process_name(pid: u32) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .ok()
+        .map(|name| name.trim().to_string())
+}
+
+#[cfg(windows)]
+fn process_name(pid: u32) -> Option<String> {
+    // CSV rows look like `"node.exe","1234","Console",...`
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = stdout.lines().next()?.split(',').next()?.trim_matches('"');
+    (!name.is_empty() && !name.starts_with("INFO:")).then(|| name.to_string())
+}
+
 /// Allocate a dynamic port by letting the OS assign one.
 pub fn allocate_dynamic_port() -> Result<u16, PortAllocationError> {
     let listener = TcpListener::bind(("127.0.0.1", 0))
@@ -395,6 +545,31 @@ mod tests {
         assert!(wait_for_port_available(port, Duration::from_secs(3)).await);
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_listening_socket_inodes() {
+        let table = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:B31A 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:B31A 0100007F:9C40 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1 0000000000000000 20 4 30 10 -1
+   2: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1717 1 0000000000000000 100 0 0 10 0
+";
+        // 0xB31A = 45850; the established connection on it isn't a listener
+        assert_eq!(listening_socket_inodes(table, 45850), ["4242"]);
+        assert!(listening_socket_inodes(table, 45818).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_port_owner_finds_this_process() {
+        let held = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = held.local_addr().unwrap().port();
+        let owner = port_owner(port).expect("listener has an owner");
+        assert_eq!(owner.pid, std::process::id());
+
+        drop(held);
+        assert!(port_owner(port).is_none());
+    }
+
     #[tokio::test]
     async fn test_service_persistence() {
         let settings = Arc::new(InMemorySettings::new());
@@ -0,0 +1,272 @@
+//! Install manifests: servers to provision into a Space in one go
+//!
+//! A manifest (YAML or JSON) lists registry servers and custom ones with
+//! their input values, so a team can bootstrap a machine from a file:
+//!
+//! ```yaml
+//! servers:
+//!   - id: github
+//!     inputs:
+//!       GITHUB_TOKEN: ${env:GITHUB_TOKEN}
+//!     enable: true
+//!   - id: docs
+//!     config:
+//!       command: npx
+//!       args: ["-y", "@acme/docs-mcp"]
+//! ```
+//!
+//! `config` takes a server in the space config format (`command`/`args`/
+//! `env`, `url`/`headers`, ...); without it `id` is looked up in the
+//! registry. [`ServerAppService::bulk_install`] installs every server of a
+//! manifest or none of them.
+//!
+//! [`ServerAppService::bulk_install`]: crate::ServerAppService::bulk_install
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::config::UserServerEntry;
+use crate::domain::ServerDefinition;
+
+/// A parsed install manifest
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstallManifest {
+    #[serde(default)]
+    pub servers: Vec<ManifestServer>,
+}
+
+/// One server to install
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestServer {
+    /// Registry server ID, or the ID to install a custom server under
+    pub id: String,
+
+    /// Custom server in the space config format; looked up in the registry
+    /// when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<UserServerEntry>,
+
+    /// Input values; `${env:VAR}` style references are resolved at connect
+    /// time like any other
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub inputs: HashMap<String, String>,
+
+    /// Enable after installing (servers from a trusted registry always are)
+    #[serde(default)]
+    pub enable: bool,
+}
+
+impl InstallManifest {
+    /// Parse a manifest; JSON when `path` ends in `.json`, YAML otherwise
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let manifest: Self = if is_json {
+            serde_json::from_str(content)
+                .with_context(|| format!("Invalid install manifest {}", path.display()))?
+        } else {
+            serde_yaml::from_str(content)
+                .with_context(|| format!("Invalid install manifest {}", path.display()))?
+        };
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Read and parse a manifest file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content, path)
+    }
+
+    /// Reject empty and repeated server IDs
+    pub fn validate(&self) -> Result<()> {
+        let mut seen = HashSet::new();
+        for server in &self.servers {
+            let id = server.id.trim();
+            if id.is_empty() {
+                bail!("Server IDs can't be empty");
+            }
+            if !seen.insert(id) {
+                bail!("Server {} is listed twice", id);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ManifestServer {
+    /// Definition of a custom server, under its normalized ID; `None` for a
+    /// registry server
+    pub fn custom_definition(&self, space_id: &str) -> Option<ServerDefinition> {
+        self.config
+            .as_ref()
+            .map(|entry| entry.to_server_definition(self.id.trim(), space_id, PathBuf::new()))
+    }
+}
+
+/// What happened to one server of a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BulkInstallStatus {
+    Installed {
+        enabled: bool,
+    },
+    /// Left as it was
+    AlreadyInstalled,
+    Failed {
+        error: String,
+    },
+    /// Valid, but not installed (or removed again) because another server
+    /// failed
+    NotInstalled,
+}
+
+/// Result for one server of a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkInstallResult {
+    pub server_id: String,
+    #[serde(flatten)]
+    pub status: BulkInstallStatus,
+}
+
+/// Per-server results of a bulk install, in manifest order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BulkInstallReport {
+    pub results: Vec<BulkInstallResult>,
+}
+
+impl BulkInstallReport {
+    /// Whether every server is installed
+    pub fn is_success(&self) -> bool {
+        !self.results.iter().any(|r| {
+            matches!(
+                r.status,
+                BulkInstallStatus::Failed { .. } | BulkInstallStatus::NotInstalled
+            )
+        })
+    }
+
+    /// IDs of the servers this install enabled
+    pub fn enabled(&self) -> impl Iterator<Item = &str> {
+        self.results.iter().filter_map(|r| match r.status {
+            BulkInstallStatus::Installed { enabled: true } => Some(r.server_id.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for BulkInstallReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, result) in self.results.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match &result.status {
+                BulkInstallStatus::Installed { enabled } => write!(
+                    f,
+                    "{:<32} installed{}",
+                    result.server_id,
+                    if *enabled { " (enabled)" } else { "" }
+                )?,
+                BulkInstallStatus::AlreadyInstalled => {
+                    write!(f, "{:<32} already installed", result.server_id)?
+                }
+                BulkInstallStatus::Failed { error } => {
+                    write!(f, "{:<32} failed: {}", result.server_id, error)?
+                }
+                BulkInstallStatus::NotInstalled => {
+                    write!(f, "{:<32} not installed", result.server_id)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = InstallManifest::parse(
+            r#"
+servers:
+  - id: github
+    inputs:
+      GITHUB_TOKEN: ${env:GITHUB_TOKEN}
+    enable: true
+  - id: My Docs
+    config:
+      command: npx
+      args: ["-y", "@acme/docs-mcp"]
+"#,
+            Path::new("manifest.yaml"),
+        )
+        .unwrap();
+        assert_eq!(manifest.servers.len(), 2);
+        assert!(manifest.servers[0].enable);
+        assert!(manifest.servers[0].custom_definition("space").is_none());
+        let custom = manifest.servers[1].custom_definition("space").unwrap();
+        assert_eq!(custom.id, "mydocs");
+
+        let json = InstallManifest::parse(
+            r#"{"servers": [{"id": "github"}]}"#,
+            Path::new("manifest.json"),
+        )
+        .unwrap();
+        assert!(!json.servers[0].enable);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let path = Path::new("manifest.json");
+        assert!(
+            InstallManifest::parse(r#"{"servers": [{"id": "a"}, {"id": "a"}]}"#, path).is_err()
+        );
+        assert!(InstallManifest::parse(r#"{"servers": [{"id": " "}]}"#, path).is_err());
+        assert!(
+            InstallManifest::parse(r#"{"servers": [{"id": "a", "enabled": true}]}"#, path).is_err()
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let result = |id: &str, status| BulkInstallResult {
+            server_id: id.to_string(),
+            status,
+        };
+        let mut report = BulkInstallReport {
+            results: vec![
+                result("github", BulkInstallStatus::Installed { enabled: true }),
+                result("docs", BulkInstallStatus::AlreadyInstalled),
+            ],
+        };
+        assert!(report.is_success());
+        assert_eq!(report.enabled().collect::<Vec<_>>(), ["github"]);
+
+        report.results.push(result(
+            "slack",
+            BulkInstallStatus::Failed {
+                error: "Server not found in registry".to_string(),
+            },
+        ));
+        assert!(!report.is_success());
+        assert_eq!(
+            serde_json::to_value(&report.results[2]).unwrap(),
+            serde_json::json!({
+                "server_id": "slack",
+                "status": "failed",
+                "error": "Server not found in registry"
+            })
+        );
+    }
+}
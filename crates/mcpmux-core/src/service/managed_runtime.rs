@@ -0,0 +1,535 @@
+//! Managed runtimes: pinned, portable Node.js and uv kept in the data dir.
+//!
+//! Most registry servers are launched with `npx` or `uvx`. Users without a
+//! developer toolchain can opt into managed runtimes instead of installing
+//! Node.js or Python themselves: McpMux downloads an official portable build
+//! into `<data_dir>/runtimes/`, verifies it against the publisher's SHA-256
+//! checksums, and puts its `bin` directory first on stdio servers' PATH.
+//! uv fetches a managed Python on first use, which is kept under the same
+//! directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+use crate::repository::AppSettingsRepository;
+
+use super::AppSettingsService;
+
+/// Pinned Node.js release (LTS)
+pub const MANAGED_NODE_VERSION: &str = "24.11.0";
+/// Pinned uv release
+pub const MANAGED_UV_VERSION: &str = "0.9.5";
+
+/// A runtime McpMux can install for stdio servers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManagedRuntimeKind {
+    /// Node.js, providing `node`, `npm` and `npx`
+    Node,
+    /// uv, providing `uv` and `uvx` (and a managed Python)
+    Uv,
+}
+
+impl ManagedRuntimeKind {
+    pub const ALL: [ManagedRuntimeKind; 2] = [ManagedRuntimeKind::Node, ManagedRuntimeKind::Uv];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Node => "node",
+            Self::Uv => "uv",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "node" | "nodejs" | "node.js" => Some(Self::Node),
+            "uv" | "python" => Some(Self::Uv),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Node => "Node.js",
+            Self::Uv => "uv (Python)",
+        }
+    }
+
+    pub fn version(&self) -> &'static str {
+        match self {
+            Self::Node => MANAGED_NODE_VERSION,
+            Self::Uv => MANAGED_UV_VERSION,
+        }
+    }
+
+    /// Binary whose presence means the runtime is usable
+    fn marker_binary(&self) -> &'static str {
+        match (self, cfg!(windows)) {
+            (Self::Node, false) => "npx",
+            (Self::Node, true) => "npx.cmd",
+            (Self::Uv, false) => "uvx",
+            (Self::Uv, true) => "uvx.exe",
+        }
+    }
+}
+
+/// Where a runtime's release archive lives for one platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeAsset {
+    pub url: String,
+    /// Archive file name, as listed in the checksum file
+    pub file_name: String,
+    /// Published checksums for the archive
+    pub checksum_url: String,
+    /// Directory holding the executables, relative to the extraction root
+    pub bin_subdir: PathBuf,
+}
+
+impl RuntimeAsset {
+    /// Release archive for `kind` on `os`/`arch` (as in `std::env::consts`)
+    pub fn for_platform(kind: ManagedRuntimeKind, os: &str, arch: &str) -> Option<Self> {
+        match kind {
+            ManagedRuntimeKind::Node => {
+                let os_name = match os {
+                    "linux" => "linux",
+                    "macos" => "darwin",
+                    "windows" => "win",
+                    _ => return None,
+                };
+                let arch_name = match arch {
+                    "x86_64" => "x64",
+                    "aarch64" => "arm64",
+                    _ => return None,
+                };
+                let version = MANAGED_NODE_VERSION;
+                let dir = format!("node-v{}-{}-{}", version, os_name, arch_name);
+                let ext = if os == "windows" { "zip" } else { "tar.gz" };
+                let file_name = format!("{}.{}", dir, ext);
+                let base = format!("https://nodejs.org/dist/v{}", version);
+                let bin_subdir = if os == "windows" {
+                    PathBuf::from(&dir)
+                } else {
+                    Path::new(&dir).join("bin")
+                };
+                Some(Self {
+                    url: format!("{}/{}", base, file_name),
+                    file_name,
+                    checksum_url: format!("{}/SHASUMS256.txt", base),
+                    bin_subdir,
+                })
+            }
+            ManagedRuntimeKind::Uv => {
+                let target = match (os, arch) {
+                    ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+                    ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+                    ("macos", "x86_64") => "x86_64-apple-darwin",
+                    ("macos", "aarch64") => "aarch64-apple-darwin",
+                    ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+                    ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+                    _ => return None,
+                };
+                let dir = format!("uv-{}", target);
+                let ext = if os == "windows" { "zip" } else { "tar.gz" };
+                let file_name = format!("{}.{}", dir, ext);
+                let url = format!(
+                    "https://github.com/astral-sh/uv/releases/download/{}/{}",
+                    MANAGED_UV_VERSION, file_name
+                );
+                Some(Self {
+                    checksum_url: format!("{}.sha256", url),
+                    url,
+                    file_name,
+                    // The Windows zip has no top-level directory
+                    bin_subdir: if os == "windows" {
+                        PathBuf::new()
+                    } else {
+                        PathBuf::from(dir)
+                    },
+                })
+            }
+        }
+    }
+
+    /// Release archive for the platform McpMux is running on
+    pub fn current(kind: ManagedRuntimeKind) -> Option<Self> {
+        Self::for_platform(kind, std::env::consts::OS, std::env::consts::ARCH)
+    }
+}
+
+/// Find the checksum for `file_name` in a `sha256sum`-style listing
+/// (`<hex>  <file>` per line; a single-file listing may omit the name)
+pub fn parse_checksum(listing: &str, file_name: &str) -> Option<String> {
+    let lines: Vec<&str> = listing
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let single = lines.len() == 1;
+    lines.into_iter().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next().map(|n| n.trim_start_matches('*'));
+        let matches = match name {
+            Some(name) => name == file_name,
+            None => single,
+        };
+        (matches && hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()))
+            .then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// State of one managed runtime
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedRuntimeStatus {
+    pub kind: ManagedRuntimeKind,
+    pub name: String,
+    pub version: String,
+    pub installed: bool,
+    /// False when no build exists for this OS/architecture
+    pub supported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bin_dir: Option<String>,
+}
+
+/// PATH entries and environment stdio servers get from managed runtimes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManagedRuntimeEnv {
+    /// Prepended to PATH, in order
+    pub bin_dirs: Vec<PathBuf>,
+    /// Set unless the server configures them itself
+    pub env: Vec<(String, String)>,
+}
+
+/// Installs managed runtimes and reports which ones stdio servers should use
+pub struct ManagedRuntimes {
+    root: PathBuf,
+    settings: Option<Arc<dyn AppSettingsRepository>>,
+}
+
+impl ManagedRuntimes {
+    pub fn new(data_dir: &Path, settings: Option<Arc<dyn AppSettingsRepository>>) -> Self {
+        Self {
+            root: data_dir.join("runtimes"),
+            settings,
+        }
+    }
+
+    fn install_dir(&self, kind: ManagedRuntimeKind) -> PathBuf {
+        self.root
+            .join(format!("{}-{}", kind.as_str(), kind.version()))
+    }
+
+    /// Directory holding the runtime's executables once installed
+    pub fn bin_dir(&self, kind: ManagedRuntimeKind) -> Option<PathBuf> {
+        let asset = RuntimeAsset::current(kind)?;
+        Some(self.install_dir(kind).join(asset.bin_subdir))
+    }
+
+    pub fn is_installed(&self, kind: ManagedRuntimeKind) -> bool {
+        self.bin_dir(kind)
+            .is_some_and(|dir| dir.join(kind.marker_binary()).is_file())
+    }
+
+    pub fn status(&self) -> Vec<ManagedRuntimeStatus> {
+        ManagedRuntimeKind::ALL
+            .iter()
+            .map(|kind| {
+                let installed = self.is_installed(*kind);
+                ManagedRuntimeStatus {
+                    kind: *kind,
+                    name: kind.display_name().to_string(),
+                    version: kind.version().to_string(),
+                    installed,
+                    supported: RuntimeAsset::current(*kind).is_some(),
+                    bin_dir: installed
+                        .then(|| self.bin_dir(*kind))
+                        .flatten()
+                        .map(|dir| dir.to_string_lossy().to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether stdio servers should use managed runtimes (default: off)
+    pub async fn is_enabled(&self) -> bool {
+        match &self.settings {
+            Some(repo) => {
+                AppSettingsService::new(repo.clone())
+                    .get_managed_runtimes_enabled()
+                    .await
+            }
+            None => false,
+        }
+    }
+
+    /// What stdio servers get from managed runtimes right now: nothing when
+    /// disabled, otherwise the installed runtimes' executables and state dirs
+    pub async fn stdio_env(&self) -> ManagedRuntimeEnv {
+        if !self.is_enabled().await {
+            return ManagedRuntimeEnv::default();
+        }
+        let mut result = ManagedRuntimeEnv::default();
+        for kind in ManagedRuntimeKind::ALL {
+            if !self.is_installed(kind) {
+                continue;
+            }
+            if let Some(dir) = self.bin_dir(kind) {
+                result.bin_dirs.push(dir);
+            }
+            if kind == ManagedRuntimeKind::Uv {
+                // Keep uv's Python builds and caches alongside the runtime
+                // rather than in the user's home directory
+                for (key, dir) in [
+                    ("UV_PYTHON_INSTALL_DIR", "python"),
+                    ("UV_CACHE_DIR", "uv-cache"),
+                    ("UV_TOOL_DIR", "uv-tools"),
+                ] {
+                    result.env.push((
+                        key.to_string(),
+                        self.root.join(dir).to_string_lossy().to_string(),
+                    ));
+                }
+            }
+        }
+        result
+    }
+
+    /// Download, verify and unpack the pinned release, replacing any other
+    /// version of the same runtime
+    pub async fn install(&self, kind: ManagedRuntimeKind) -> Result<()> {
+        let asset = RuntimeAsset::current(kind).ok_or_else(|| {
+            anyhow!(
+                "No managed {} build for {}/{}",
+                kind.display_name(),
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )
+        })?;
+        let client = reqwest::Client::builder()
+            .user_agent("McpMux/1.0")
+            .build()?;
+
+        info!(
+            "[ManagedRuntimes] Installing {} {}",
+            kind.display_name(),
+            kind.version()
+        );
+        let listing = client
+            .get(&asset.checksum_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let expected = parse_checksum(&listing, &asset.file_name)
+            .ok_or_else(|| anyhow!("No checksum published for {}", asset.file_name))?;
+
+        let downloads = self.root.join(".downloads");
+        tokio::fs::create_dir_all(&downloads).await?;
+        let archive = downloads.join(&asset.file_name);
+        let actual = download(&client, &asset.url, &archive).await?;
+        if actual != expected {
+            let _ = tokio::fs::remove_file(&archive).await;
+            bail!(
+                "Checksum mismatch for {} (expected {}, got {})",
+                asset.file_name,
+                expected,
+                actual
+            );
+        }
+
+        // Unpack next to the final location, then swap it in
+        let target = self.install_dir(kind);
+        let staging = target.with_extension("partial");
+        let _ = tokio::fs::remove_dir_all(&staging).await;
+        tokio::fs::create_dir_all(&staging).await?;
+        let extracted = extract(&archive, &staging).await;
+        let _ = tokio::fs::remove_file(&archive).await;
+        extracted?;
+        if !staging
+            .join(&asset.bin_subdir)
+            .join(kind.marker_binary())
+            .is_file()
+        {
+            let _ = tokio::fs::remove_dir_all(&staging).await;
+            bail!(
+                "{} archive did not contain {}",
+                asset.file_name,
+                kind.marker_binary()
+            );
+        }
+        let _ = tokio::fs::remove_dir_all(&target).await;
+        tokio::fs::rename(&staging, &target)
+            .await
+            .with_context(|| format!("Failed to move runtime into {}", target.display()))?;
+
+        self.remove_other_versions(kind).await;
+        info!(
+            "[ManagedRuntimes] Installed {} {} into {}",
+            kind.display_name(),
+            kind.version(),
+            target.display()
+        );
+        Ok(())
+    }
+
+    /// Delete every installed version of a runtime
+    pub async fn uninstall(&self, kind: ManagedRuntimeKind) -> Result<()> {
+        let target = self.install_dir(kind);
+        if target.exists() {
+            tokio::fs::remove_dir_all(&target)
+                .await
+                .with_context(|| format!("Failed to remove {}", target.display()))?;
+        }
+        self.remove_other_versions(kind).await;
+        Ok(())
+    }
+
+    async fn remove_other_versions(&self, kind: ManagedRuntimeKind) {
+        let current = self.install_dir(kind);
+        let prefix = format!("{}-", kind.as_str());
+        let Ok(mut entries) = tokio::fs::read_dir(&self.root).await else {
+            return;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let is_other_version = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(&prefix))
+                && path != current;
+            if is_other_version && path.is_dir() {
+                if let Err(e) = tokio::fs::remove_dir_all(&path).await {
+                    warn!(
+                        "[ManagedRuntimes] Failed to remove {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Stream `url` into `dest`, returning the SHA-256 hex of the content
+async fn download(client: &reqwest::Client, url: &str, dest: &Path) -> Result<String> {
+    let mut response = client.get(url).send().await?.error_for_status()?;
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut hasher = digest::Context::new(&digest::SHA256);
+    while let Some(chunk) = response.chunk().await? {
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    Ok(hasher
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Unpack a `.tar.gz` or `.zip` with the system `tar` (bsdtar on Windows 10+
+/// and macOS reads zip archives too)
+async fn extract(archive: &Path, dest: &Path) -> Result<()> {
+    let flags = if archive.extension().and_then(|e| e.to_str()) == Some("zip") {
+        "-xf"
+    } else {
+        "-xzf"
+    };
+    let output = tokio::process::Command::new("tar")
+        .arg(flags)
+        .arg(archive)
+        .arg("-C")
+        .arg(dest)
+        .output()
+        .await
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to extract {}: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assets_follow_publisher_naming() {
+        let node =
+            RuntimeAsset::for_platform(ManagedRuntimeKind::Node, "macos", "aarch64").unwrap();
+        assert_eq!(
+            node.url,
+            format!(
+                "https://nodejs.org/dist/v{0}/node-v{0}-darwin-arm64.tar.gz",
+                MANAGED_NODE_VERSION
+            )
+        );
+        assert_eq!(
+            node.bin_subdir,
+            Path::new(&format!("node-v{}-darwin-arm64", MANAGED_NODE_VERSION)).join("bin")
+        );
+
+        let uv = RuntimeAsset::for_platform(ManagedRuntimeKind::Uv, "windows", "x86_64").unwrap();
+        assert_eq!(uv.file_name, "uv-x86_64-pc-windows-msvc.zip");
+        assert_eq!(uv.checksum_url, format!("{}.sha256", uv.url));
+        assert_eq!(uv.bin_subdir, PathBuf::new());
+
+        assert!(
+            RuntimeAsset::for_platform(ManagedRuntimeKind::Node, "freebsd", "x86_64").is_none()
+        );
+    }
+
+    #[test]
+    fn parses_checksum_listings() {
+        let hash = "a".repeat(64);
+        let listing = format!(
+            "{}  node-a.tar.gz\n{}  node-b.tar.gz\n",
+            "b".repeat(64),
+            hash
+        );
+        assert_eq!(
+            parse_checksum(&listing, "node-b.tar.gz"),
+            Some(hash.clone())
+        );
+        assert_eq!(parse_checksum(&listing, "node-c.tar.gz"), None);
+        // uv's per-asset .sha256 files
+        assert_eq!(
+            parse_checksum(&format!("{} *uv.zip\n", hash), "uv.zip"),
+            Some(hash.clone())
+        );
+        assert_eq!(parse_checksum(&hash, "uv.zip"), Some(hash));
+        assert_eq!(parse_checksum("nothex  uv.zip", "uv.zip"), None);
+    }
+
+    #[tokio::test]
+    async fn installed_runtime_is_detected_but_unused_until_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtimes = ManagedRuntimes::new(dir.path(), None);
+        let Some(bin_dir) = runtimes.bin_dir(ManagedRuntimeKind::Uv) else {
+            return; // no uv build for this platform
+        };
+        assert!(!runtimes.is_installed(ManagedRuntimeKind::Uv));
+
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        std::fs::write(bin_dir.join(ManagedRuntimeKind::Uv.marker_binary()), b"").unwrap();
+        assert!(runtimes.is_installed(ManagedRuntimeKind::Uv));
+        let status = runtimes.status();
+        assert!(status[1].installed && !status[0].installed);
+
+        // No settings repository means the subsystem is off
+        assert_eq!(runtimes.stdio_env().await, ManagedRuntimeEnv::default());
+    }
+}
@@ -3,24 +3,77 @@
 //! Business logic that operates on domain entities via repositories.
 
 pub mod app_settings_service;
+mod catalog_import;
+mod child_processes;
 mod cimd_fetcher;
+mod client_detection;
+mod client_import;
 mod client_install;
 mod config_export;
+mod config_merge;
+mod config_sync;
+mod diagnostics_bundle;
+mod environment_profile_service;
 pub mod gateway_port_service;
+mod install_manifest;
+mod managed_runtime;
+mod official_registry_client;
+mod onboarding;
+mod os_service;
+mod policy;
 mod registry_api_client;
+mod registry_credential_service;
+mod secret_resolver;
 mod server_discovery;
+mod server_group_service;
 mod server_log_manager;
 mod space_service;
+mod sync_target;
 
 pub use app_settings_service::{keys, AppSettingsService};
+pub use catalog_import::*;
+pub use child_processes::{process_start_marker, ChildProcessRecord, ChildProcessRegistry};
 pub use cimd_fetcher::*;
-pub use client_install::{cursor_deep_link, vscode_deep_link};
+pub use client_detection::{ClientDetectionService, ClientInstallation, DetectableClient};
+pub use client_import::*;
+pub use client_install::{
+    add_to_config_file, apply_to_client, cursor_deep_link, jetbrains_mcp_config,
+    reapply_to_clients, vscode_deep_link, windsurf_config_path, windsurf_server_entry,
+    zed_server_entry, zed_settings_path, ConfigApplyAction, ConfigApplyFailure, ConfigApplyResult,
+    ConfigReapplyResult, InstallClient,
+};
 pub use config_export::*;
+pub use config_merge::{diff_lines, merge_config, set_member, ConfigDiff, DiffKind, DiffLine};
+pub use config_sync::*;
+pub use diagnostics_bundle::*;
+pub use environment_profile_service::EnvironmentProfileService;
 pub use gateway_port_service::{
-    allocate_dynamic_port, is_port_available, wait_for_port_available, GatewayPortService,
-    PortAllocationError, PortResolution, AUTOSTART_PORT_WAIT, DEFAULT_GATEWAY_PORT,
+    allocate_dynamic_port, is_port_available, port_owner, wait_for_port_available,
+    GatewayPortService, PortAllocationError, PortOwner, PortResolution, AUTOSTART_PORT_WAIT,
+    DEFAULT_GATEWAY_PORT,
+};
+pub use install_manifest::{
+    BulkInstallReport, BulkInstallResult, BulkInstallStatus, InstallManifest, ManifestServer,
+};
+pub use managed_runtime::*;
+pub use official_registry_client::*;
+pub use onboarding::{
+    detect_clients, verify_gateway, DefaultGrants, DetectedClient, GatewayCheck,
+    OnboardingProgress, OnboardingService, OnboardingStatus, OnboardingStep, ServerSuggestion,
+};
+pub use os_service::{
+    install_service, is_service_installed, uninstall_service, ServiceManager, ServiceSpec,
+    LAUNCHD_LABEL, SCHEDULED_TASK_NAME, SYSTEMD_UNIT,
+};
+pub use policy::{
+    plan_space_policy, ClientGrants, FeatureSetPolicy, PolicyChange, PolicyDocument, PolicyPlan,
+    SpacePolicy, SpaceSnapshot,
 };
 pub use registry_api_client::*;
+pub use registry_credential_service::{RegistryCredentialService, REGISTRY_CREDENTIALS_SPACE};
+pub use secret_resolver::*;
 pub use server_discovery::*;
+pub use server_group_service::{GroupFeatureSetSync, ServerGroupService};
 pub use server_log_manager::*;
 pub use space_service::*;
+pub use sync_target::*;
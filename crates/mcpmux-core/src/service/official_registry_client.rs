@@ -0,0 +1,740 @@
+//! Client for the official MCP registry (registry.modelcontextprotocol.io).
+//!
+//! The official registry lists `server.json` entries page by page from
+//! `/v0/servers`. Each entry describes how to run the server: `packages`
+//! (an npm/PyPI/OCI/NuGet package started locally over stdio) and/or
+//! `remotes` (a hosted streamable HTTP endpoint). This module maps them to
+//! [`ServerDefinition`]s, so they can be browsed, searched and installed
+//! like entries of the McpMux registry:
+//!
+//! - a stdio package becomes the command that runs it (`npx`, `uvx`,
+//!   `docker run`, `dnx`), preferred over remotes;
+//! - otherwise the first streamable HTTP remote becomes an HTTP transport;
+//! - environment variables, headers and arguments without a fixed value,
+//!   and `{variable}` placeholders, become inputs (`${input:ID}`).
+//!
+//! Entries with neither a supported package nor a remote are skipped.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+
+use crate::domain::{
+    AuthConfig, HostingType, InputDefinition, InputOption, PublisherInfo, RegistryAuth,
+    ServerDefinition, ServerSource, TransportConfig, TransportMetadata,
+};
+
+/// Entries requested per page
+const PAGE_SIZE: u32 = 100;
+
+/// Upper bound on pages per fetch, in case a registry never stops paging
+const MAX_PAGES: usize = 200;
+
+/// `_meta` key the official registry puts its own metadata under
+const OFFICIAL_META_KEY: &str = "io.modelcontextprotocol.registry/official";
+
+// ============================================
+// server.json Types
+// ============================================
+
+/// A `server.json` entry
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficialServer {
+    /// Reverse-DNS name, e.g. `io.github.owner/weather`
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub repository: Option<OfficialRepository>,
+    #[serde(default, alias = "website_url")]
+    pub website_url: Option<String>,
+    #[serde(default)]
+    pub packages: Vec<OfficialPackage>,
+    #[serde(default)]
+    pub remotes: Vec<OfficialRemote>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficialRepository {
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// A package that runs the server locally
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficialPackage {
+    /// `npm`, `pypi`, `oci`, `nuget`, `mcpb`
+    #[serde(alias = "registry_type", alias = "registry_name")]
+    pub registry_type: String,
+    /// Package name (or image reference for `oci`)
+    #[serde(alias = "name")]
+    pub identifier: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Command that runs the package, e.g. `npx`
+    #[serde(default, alias = "runtime_hint")]
+    pub runtime_hint: Option<String>,
+    #[serde(default)]
+    pub transport: Option<OfficialTransport>,
+    #[serde(default, alias = "runtime_arguments")]
+    pub runtime_arguments: Vec<OfficialArgument>,
+    #[serde(default, alias = "package_arguments")]
+    pub package_arguments: Vec<OfficialArgument>,
+    #[serde(default, alias = "environment_variables")]
+    pub environment_variables: Vec<OfficialKeyValue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficialTransport {
+    /// `stdio`, `streamable-http` or `sse`
+    #[serde(rename = "type")]
+    pub transport_type: String,
+}
+
+/// A hosted endpoint of the server
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficialRemote {
+    /// `streamable-http` or `sse`
+    #[serde(rename = "type", alias = "transport_type")]
+    pub transport_type: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<OfficialKeyValue>,
+}
+
+/// A value the user may have to provide
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficialInput {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, alias = "is_required")]
+    pub is_required: bool,
+    #[serde(default, alias = "is_secret")]
+    pub is_secret: bool,
+    /// Fixed value; may contain `{variable}` placeholders
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    /// `string`, `number`, `boolean` or `filepath`
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, OfficialInput>,
+}
+
+/// An environment variable or header
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficialKeyValue {
+    pub name: String,
+    #[serde(flatten)]
+    pub input: OfficialInput,
+}
+
+/// A command-line argument
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficialArgument {
+    /// `positional` or `named`
+    #[serde(rename = "type")]
+    pub argument_type: String,
+    /// Flag of a named argument, e.g. `--port`
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, alias = "value_hint")]
+    pub value_hint: Option<String>,
+    #[serde(flatten)]
+    pub input: OfficialInput,
+}
+
+// ============================================
+// Conversion
+// ============================================
+
+/// Turn a name into an input ID (`${input:ID}` only allows `A-Z0-9_`)
+pub(crate) fn input_id(name: &str) -> String {
+    let id: String = name
+        .trim_start_matches('-')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if id.starts_with(|c: char| c.is_ascii_digit()) || id.is_empty() {
+        format!("_{}", id)
+    } else {
+        id
+    }
+}
+
+/// Collects the inputs a transport needs, once per ID
+#[derive(Default)]
+struct Inputs {
+    inputs: Vec<InputDefinition>,
+}
+
+impl Inputs {
+    fn add(&mut self, id: &str, label: &str, input: &OfficialInput) {
+        if self.inputs.iter().any(|i| i.id == id) {
+            return;
+        }
+        self.inputs.push(InputDefinition {
+            id: id.to_string(),
+            label: label.to_string(),
+            r#type: match input.format.as_deref() {
+                _ if !input.choices.is_empty() => "select",
+                Some("number") => "number",
+                Some("boolean") => "boolean",
+                Some("filepath") => "file_path",
+                _ => "text",
+            }
+            .to_string(),
+            required: input.is_required,
+            secret: input.is_secret,
+            description: input.description.clone(),
+            default: input.default.clone(),
+            placeholder: None,
+            obtain_url: None,
+            obtain_instructions: None,
+            options: input
+                .choices
+                .iter()
+                .map(|value| InputOption {
+                    value: value.clone(),
+                    label: None,
+                    description: None,
+                })
+                .collect(),
+            pattern: None,
+            pattern_error: None,
+            multiline: false,
+        });
+    }
+
+    /// The value template of `input`: its fixed value with `{variable}`
+    /// placeholders turned into inputs, or a new input named `name`
+    fn template(&mut self, name: &str, input: &OfficialInput) -> String {
+        match &input.value {
+            Some(value) => {
+                let mut value = value.clone();
+                for (variable, spec) in &input.variables {
+                    let id = input_id(variable);
+                    value =
+                        value.replace(&format!("{{{}}}", variable), &format!("${{input:{}}}", id));
+                    self.add(&id, variable, spec);
+                }
+                value
+            }
+            None => {
+                let id = input_id(name);
+                self.add(&id, name, input);
+                format!("${{input:{}}}", id)
+            }
+        }
+    }
+}
+
+/// Append the command-line form of `arguments`
+fn push_arguments(args: &mut Vec<String>, arguments: &[OfficialArgument], inputs: &mut Inputs) {
+    for (i, argument) in arguments.iter().enumerate() {
+        let name = argument
+            .name
+            .clone()
+            .or_else(|| argument.value_hint.clone())
+            .unwrap_or_else(|| format!("arg_{}", i + 1));
+        if argument.argument_type == "named" {
+            if let Some(ref flag) = argument.name {
+                args.push(flag.clone());
+            }
+            // A named argument without a value is a plain flag
+            let is_flag = argument.input.value.is_none()
+                && argument.input.format.as_deref() == Some("boolean");
+            if is_flag {
+                continue;
+            }
+        }
+        args.push(inputs.template(&name, &argument.input));
+    }
+}
+
+/// `identifier` pinned to `version`, in the package manager's syntax
+fn package_spec(package: &OfficialPackage, separator: &str) -> String {
+    match package.version.as_deref() {
+        Some(version) if !version.is_empty() && version != "latest" => {
+            format!("{}{}{}", package.identifier, separator, version)
+        }
+        _ => package.identifier.clone(),
+    }
+}
+
+/// The stdio transport that runs `package`, if it's a supported stdio package
+fn package_transport(package: &OfficialPackage) -> Option<TransportConfig> {
+    let is_stdio = package
+        .transport
+        .as_ref()
+        .is_none_or(|t| t.transport_type == "stdio");
+    if !is_stdio {
+        return None;
+    }
+
+    let mut inputs = Inputs::default();
+    let mut env = HashMap::new();
+    for variable in &package.environment_variables {
+        env.insert(
+            variable.name.clone(),
+            inputs.template(&variable.name, &variable.input),
+        );
+    }
+
+    let mut args = Vec::new();
+    push_arguments(&mut args, &package.runtime_arguments, &mut inputs);
+    let (default_command, spec) = match package.registry_type.as_str() {
+        "npm" => {
+            args.push("-y".to_string());
+            ("npx", package_spec(package, "@"))
+        }
+        "pypi" => ("uvx", package_spec(package, "==")),
+        "oci" => {
+            let mut docker_args = vec!["run".to_string(), "-i".to_string(), "--rm".to_string()];
+            for variable in &package.environment_variables {
+                // Passed through from the environment McpMux sets
+                docker_args.push("-e".to_string());
+                docker_args.push(variable.name.clone());
+            }
+            docker_args.append(&mut args);
+            args = docker_args;
+            let spec = if package.identifier.contains(['@', ':']) {
+                package.identifier.clone()
+            } else {
+                package_spec(package, ":")
+            };
+            ("docker", spec)
+        }
+        "nuget" => {
+            let spec = package_spec(package, "@");
+            args.push(spec);
+            args.push("--yes".to_string());
+            if !package.package_arguments.is_empty() {
+                args.push("--".to_string());
+            }
+            push_arguments(&mut args, &package.package_arguments, &mut inputs);
+            return Some(stdio_transport(package, "dnx", args, env, inputs));
+        }
+        _ => return None,
+    };
+    args.push(spec);
+    push_arguments(&mut args, &package.package_arguments, &mut inputs);
+
+    Some(stdio_transport(package, default_command, args, env, inputs))
+}
+
+fn stdio_transport(
+    package: &OfficialPackage,
+    default_command: &str,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    inputs: Inputs,
+) -> TransportConfig {
+    TransportConfig::Stdio {
+        command: package
+            .runtime_hint
+            .clone()
+            .unwrap_or_else(|| default_command.to_string()),
+        args,
+        env,
+        metadata: TransportMetadata {
+            inputs: inputs.inputs,
+        },
+    }
+}
+
+/// The HTTP transport of a streamable HTTP `remote`
+fn remote_transport(remote: &OfficialRemote) -> Option<TransportConfig> {
+    if remote.transport_type != "streamable-http" {
+        return None;
+    }
+    let mut inputs = Inputs::default();
+    let headers = remote
+        .headers
+        .iter()
+        .map(|header| {
+            (
+                header.name.clone(),
+                inputs.template(&header.name, &header.input),
+            )
+        })
+        .collect();
+    Some(TransportConfig::Http {
+        url: remote.url.clone(),
+        headers,
+        query: HashMap::new(),
+        metadata: TransportMetadata {
+            inputs: inputs.inputs,
+        },
+    })
+}
+
+/// Map a `server.json` entry to a server definition. `None` if it has
+/// neither a supported stdio package nor a streamable HTTP remote.
+pub fn official_server_to_definition(server: &OfficialServer) -> Option<ServerDefinition> {
+    let local = server.packages.iter().find_map(package_transport);
+    let remote = server.remotes.iter().find_map(remote_transport);
+    let hosting_type = match (&local, &remote) {
+        (Some(_), Some(_)) => HostingType::Hybrid,
+        (None, Some(_)) => HostingType::Remote,
+        _ => HostingType::Local,
+    };
+    let transport = local.or(remote)?;
+
+    let needs_secret = transport
+        .metadata()
+        .inputs
+        .iter()
+        .any(|i| i.secret && i.required);
+    let (namespace, short_name) = server
+        .name
+        .split_once('/')
+        .unwrap_or(("", server.name.as_str()));
+
+    Some(ServerDefinition {
+        id: server.name.clone(),
+        name: server
+            .title
+            .clone()
+            .unwrap_or_else(|| short_name.to_string()),
+        description: server.description.clone(),
+        alias: None,
+        auth: Some(if needs_secret {
+            AuthConfig::ApiKey { instructions: None }
+        } else {
+            AuthConfig::None
+        }),
+        icon: None,
+        transport,
+        categories: vec![],
+        publisher: (!namespace.is_empty()).then(|| PublisherInfo {
+            name: namespace.to_string(),
+            domain: None,
+            verified: false,
+            official: false,
+        }),
+        source: ServerSource::default(),
+        badges: vec![],
+        hosting_type,
+        license: None,
+        license_url: None,
+        installation: None,
+        capabilities: None,
+        sponsored: None,
+        media: None,
+        changelog_url: server
+            .repository
+            .as_ref()
+            .and_then(|r| r.url.clone())
+            .or_else(|| server.website_url.clone()),
+        timeouts: Default::default(),
+        depends_on: vec![],
+        signature: None,
+        signature_status: Default::default(),
+    })
+}
+
+// ============================================
+// Client Implementation
+// ============================================
+
+/// Page of `/v0/servers`
+#[derive(Debug, Deserialize)]
+struct ServerListResponse {
+    #[serde(default)]
+    servers: Vec<serde_json::Value>,
+    #[serde(default)]
+    metadata: Option<ServerListMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerListMetadata {
+    #[serde(default, alias = "next_cursor", rename = "nextCursor")]
+    next_cursor: Option<String>,
+}
+
+/// Parse one listed entry. Entries are `{ "server": {...}, "_meta": {...} }`;
+/// older registries list the `server.json` itself with `_meta` inside.
+/// Returns `None` for entries that aren't the latest version or were deleted.
+fn parse_entry(entry: serde_json::Value) -> Option<OfficialServer> {
+    let meta = entry
+        .get("_meta")
+        .or_else(|| entry.get("server").and_then(|s| s.get("_meta")))
+        .and_then(|m| m.get(OFFICIAL_META_KEY))
+        .cloned();
+    if let Some(meta) = meta {
+        if meta.get("isLatest").and_then(|v| v.as_bool()) == Some(false)
+            || meta.get("status").and_then(|v| v.as_str()) == Some("deleted")
+        {
+            return None;
+        }
+    }
+
+    let server = match entry.get("server") {
+        Some(server) => server.clone(),
+        None => entry,
+    };
+    match serde_json::from_value::<OfficialServer>(server) {
+        Ok(server) => Some(server),
+        Err(e) => {
+            tracing::debug!("Skipping unreadable official registry entry: {}", e);
+            None
+        }
+    }
+}
+
+/// Client for registries that speak the official MCP registry API
+pub struct OfficialRegistryClient {
+    base_url: String,
+    client: reqwest::Client,
+    auth: Option<RegistryAuth>,
+}
+
+impl OfficialRegistryClient {
+    pub fn new(base_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("McpMux/1.0")
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            auth: None,
+        }
+    }
+
+    /// Send credentials with every request (private registries)
+    pub fn with_auth(mut self, auth: RegistryAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Fetch the latest version of every server, following the pagination
+    /// cursor. Entries that can't be mapped are skipped.
+    pub async fn fetch_servers(&self) -> Result<Vec<ServerDefinition>> {
+        let url = format!("{}/v0/servers", self.base_url);
+        tracing::info!("Fetching official registry servers from {}", url);
+
+        let mut definitions = Vec::new();
+        let mut seen = HashSet::new();
+        let mut skipped = 0;
+        let mut cursor: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut page_url = format!("{}?limit={}&version=latest", url, PAGE_SIZE);
+            if let Some(ref cursor) = cursor {
+                page_url.push_str(&format!("&cursor={}", urlencoding::encode(cursor)));
+            }
+            let mut request = self.client.get(&page_url);
+            request = match &self.auth {
+                Some(RegistryAuth::Bearer { token }) => request.bearer_auth(token),
+                Some(RegistryAuth::Basic { username, password }) => {
+                    request.basic_auth(username, Some(password))
+                }
+                None => request,
+            };
+
+            let response = request
+                .send()
+                .await
+                .context("Failed to send request to the MCP registry")?;
+            let status = response.status();
+            if !status.is_success() {
+                anyhow::bail!("MCP registry returned status: {}", status);
+            }
+            let page: ServerListResponse = response
+                .json()
+                .await
+                .context("Failed to parse MCP registry server list")?;
+
+            for server in page.servers.into_iter().filter_map(parse_entry) {
+                if !seen.insert(server.name.clone()) {
+                    continue;
+                }
+                match official_server_to_definition(&server) {
+                    Some(definition) => definitions.push(definition),
+                    None => skipped += 1,
+                }
+            }
+
+            cursor = page
+                .metadata
+                .and_then(|m| m.next_cursor)
+                .filter(|c| !c.is_empty());
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        tracing::info!(
+            "Fetched {} servers from the MCP registry ({} without a supported package or remote)",
+            definitions.len(),
+            skipped
+        );
+        Ok(definitions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(json: serde_json::Value) -> OfficialServer {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_npm_package() {
+        let definition = official_server_to_definition(&server(serde_json::json!({
+            "name": "io.github.acme/weather",
+            "description": "Weather forecasts",
+            "version": "1.2.0",
+            "packages": [{
+                "registryType": "npm",
+                "identifier": "@acme/weather-mcp",
+                "version": "1.2.0",
+                "transport": { "type": "stdio" },
+                "packageArguments": [
+                    { "type": "named", "name": "--units", "value": "metric" }
+                ],
+                "environmentVariables": [
+                    { "name": "WEATHER_API_KEY", "isRequired": true, "isSecret": true }
+                ]
+            }]
+        })))
+        .unwrap();
+
+        assert_eq!(definition.id, "io.github.acme/weather");
+        assert_eq!(definition.name, "weather");
+        assert!(matches!(definition.auth, Some(AuthConfig::ApiKey { .. })));
+        let TransportConfig::Stdio {
+            command,
+            args,
+            env,
+            metadata,
+        } = definition.transport
+        else {
+            panic!("expected stdio");
+        };
+        assert_eq!(command, "npx");
+        assert_eq!(
+            args,
+            vec!["-y", "@acme/weather-mcp@1.2.0", "--units", "metric"]
+        );
+        assert_eq!(env["WEATHER_API_KEY"], "${input:WEATHER_API_KEY}");
+        assert_eq!(metadata.inputs.len(), 1);
+        assert!(metadata.inputs[0].secret);
+    }
+
+    #[test]
+    fn test_oci_package_passes_env_to_docker() {
+        let definition = official_server_to_definition(&server(serde_json::json!({
+            "name": "io.github.acme/db",
+            "packages": [{
+                "registryType": "oci",
+                "identifier": "ghcr.io/acme/db-mcp",
+                "version": "2.0.0",
+                "environmentVariables": [{ "name": "DB_URL", "isRequired": true }]
+            }]
+        })))
+        .unwrap();
+
+        let TransportConfig::Stdio { command, args, .. } = definition.transport else {
+            panic!("expected stdio");
+        };
+        assert_eq!(command, "docker");
+        assert_eq!(
+            args,
+            vec![
+                "run",
+                "-i",
+                "--rm",
+                "-e",
+                "DB_URL",
+                "ghcr.io/acme/db-mcp:2.0.0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_with_header_variables() {
+        let definition = official_server_to_definition(&server(serde_json::json!({
+            "name": "com.example/search",
+            "title": "Example Search",
+            "remotes": [
+                { "type": "sse", "url": "https://example.com/sse" },
+                {
+                    "type": "streamable-http",
+                    "url": "https://example.com/mcp",
+                    "headers": [{
+                        "name": "Authorization",
+                        "value": "Bearer {api_key}",
+                        "variables": { "api_key": { "isRequired": true, "isSecret": true } }
+                    }]
+                }
+            ]
+        })))
+        .unwrap();
+
+        assert_eq!(definition.name, "Example Search");
+        assert_eq!(definition.hosting_type, HostingType::Remote);
+        let TransportConfig::Http {
+            url,
+            headers,
+            metadata,
+            ..
+        } = definition.transport
+        else {
+            panic!("expected http");
+        };
+        assert_eq!(url, "https://example.com/mcp");
+        assert_eq!(headers["Authorization"], "Bearer ${input:API_KEY}");
+        assert_eq!(metadata.inputs[0].id, "API_KEY");
+    }
+
+    #[test]
+    fn test_unsupported_entries_are_skipped() {
+        assert!(official_server_to_definition(&server(serde_json::json!({
+            "name": "io.github.acme/bundle",
+            "packages": [{ "registryType": "mcpb", "identifier": "https://example.com/x.mcpb" }]
+        })))
+        .is_none());
+    }
+
+    #[test]
+    fn test_parse_entry_shapes() {
+        let wrapped = serde_json::json!({
+            "server": { "name": "a/b" },
+            "_meta": { OFFICIAL_META_KEY: { "isLatest": true, "status": "active" } }
+        });
+        assert_eq!(parse_entry(wrapped).unwrap().name, "a/b");
+
+        let old = serde_json::json!({
+            "name": "a/c",
+            "_meta": { OFFICIAL_META_KEY: { "isLatest": false } }
+        });
+        assert!(parse_entry(old).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! First-run onboarding service.
+//!
+//! Backs the guided setup the desktop app shows instead of an empty screen.
+//! Setup is a fixed sequence of steps the UI drives one at a time:
+//!
+//! 1. detect the AI clients installed on this machine
+//! 2. suggest starter servers from the registry
+//! 3. grant the default Space's Starter FeatureSet what's installed
+//! 4. check that the gateway answers on its URL
+//!
+//! Every step can be re-run; none of them undoes choices the user already
+//! made.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::domain::{MemberMode, ServerDefinition, ServerFeature};
+use crate::repository::{FeatureSetRepository, InstalledServerRepository, ServerFeatureRepository};
+
+use super::{ClientDetectionService, ImportClient, InstallClient, ServerDiscoveryService};
+
+/// Servers suggested when the registry has no featured list
+const FALLBACK_SUGGESTIONS: usize = 6;
+
+/// How long the gateway check waits for `/health`
+const GATEWAY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A step of the guided setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    DetectClients,
+    SuggestServers,
+    DefaultGrants,
+    VerifyGateway,
+}
+
+/// Where a step is at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Progress of one step, as reported to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingProgress {
+    pub step: OnboardingStep,
+    pub status: OnboardingStatus,
+    /// What happened, or why the step failed
+    pub message: Option<String>,
+}
+
+impl OnboardingProgress {
+    pub fn running(step: OnboardingStep) -> Self {
+        Self {
+            step,
+            status: OnboardingStatus::Running,
+            message: None,
+        }
+    }
+
+    pub fn completed(step: OnboardingStep, message: impl Into<String>) -> Self {
+        Self {
+            step,
+            status: OnboardingStatus::Completed,
+            message: Some(message.into()),
+        }
+    }
+
+    pub fn failed(step: OnboardingStep, error: impl Into<String>) -> Self {
+        Self {
+            step,
+            status: OnboardingStatus::Failed,
+            message: Some(error.into()),
+        }
+    }
+}
+
+/// An AI client found on this machine
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedClient {
+    /// Display name
+    pub label: String,
+    /// Set if McpMux can add itself to this client
+    pub install: Option<InstallClient>,
+    /// Set if this client's MCP config can be imported
+    pub import: Option<ImportClient>,
+    /// The client's MCP config file, if it has one
+    pub config_path: Option<PathBuf>,
+}
+
+/// A server suggested to start with
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerSuggestion {
+    pub definition: ServerDefinition,
+    /// Whether it's already installed in the Space
+    pub installed: bool,
+}
+
+/// Outcome of the default grants step
+#[derive(Debug, Clone, Serialize)]
+pub struct DefaultGrants {
+    /// The Starter FeatureSet of the Space
+    pub feature_set_id: String,
+    /// Features added to it
+    pub added: usize,
+    /// Set when the Starter already had members, so nothing was changed
+    pub already_configured: bool,
+}
+
+/// Outcome of the gateway check
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayCheck {
+    pub url: String,
+    pub reachable: bool,
+    /// HTTP status of `/health`, if the gateway answered
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Service behind the first-run setup steps
+pub struct OnboardingService {
+    discovery: Arc<ServerDiscoveryService>,
+    server_repo: Arc<dyn InstalledServerRepository>,
+    feature_set_repo: Arc<dyn FeatureSetRepository>,
+    feature_repo: Arc<dyn ServerFeatureRepository>,
+}
+
+impl OnboardingService {
+    pub fn new(
+        discovery: Arc<ServerDiscoveryService>,
+        server_repo: Arc<dyn InstalledServerRepository>,
+        feature_set_repo: Arc<dyn FeatureSetRepository>,
+        feature_repo: Arc<dyn ServerFeatureRepository>,
+    ) -> Self {
+        Self {
+            discovery,
+            server_repo,
+            feature_set_repo,
+            feature_repo,
+        }
+    }
+
+    /// Starter servers: the registry's featured servers, or the first few
+    /// by name if it has none. Servers already installed in the Space are
+    /// flagged rather than dropped, so the UI can show them as done.
+    pub async fn suggest_servers(&self, space_id: &str) -> Result<Vec<ServerSuggestion>> {
+        let mut candidates = self.discovery.featured().await;
+        if candidates.is_empty() {
+            candidates = self.discovery.list().await;
+            candidates.sort_by_key(|s| s.name.to_lowercase());
+            candidates.truncate(FALLBACK_SUGGESTIONS);
+        }
+
+        let installed: HashSet<String> = self
+            .server_repo
+            .list_for_space(space_id)
+            .await?
+            .into_iter()
+            .map(|s| s.server_id)
+            .collect();
+        Ok(suggestions(candidates, &installed))
+    }
+
+    /// Grant the Space's Starter FeatureSet every available feature of its
+    /// enabled servers, so clients that connect without a binding can use
+    /// them. A Starter that already has members was set up by the user and
+    /// is left alone.
+    pub async fn create_default_grants(&self, space_id: &str) -> Result<DefaultGrants> {
+        self.feature_set_repo
+            .ensure_builtin_for_space(space_id)
+            .await?;
+        let starter = self
+            .feature_set_repo
+            .get_starter_for_space(space_id)
+            .await?
+            .ok_or_else(|| anyhow!("Space {} has no Starter FeatureSet", space_id))?;
+
+        let members = self
+            .feature_set_repo
+            .get_feature_members(&starter.id)
+            .await?;
+        if !members.is_empty() {
+            return Ok(DefaultGrants {
+                feature_set_id: starter.id,
+                added: 0,
+                already_configured: true,
+            });
+        }
+
+        let enabled: HashSet<String> = self
+            .server_repo
+            .list_for_space(space_id)
+            .await?
+            .into_iter()
+            .filter(|s| s.enabled)
+            .map(|s| s.server_id)
+            .collect();
+        let features = self.feature_repo.list_for_space(space_id).await?;
+        let grants = default_grant_features(&features, &enabled);
+        for feature_id in &grants {
+            self.feature_set_repo
+                .add_feature_member(&starter.id, feature_id, MemberMode::Include)
+                .await?;
+        }
+
+        info!(
+            space_id,
+            feature_set_id = %starter.id,
+            added = grants.len(),
+            "[Onboarding] Granted default features"
+        );
+        Ok(DefaultGrants {
+            feature_set_id: starter.id,
+            added: grants.len(),
+            already_configured: false,
+        })
+    }
+}
+
+/// Every supported AI client found on this machine. Blocking: it looks at
+/// PATH and the filesystem.
+pub fn detect_clients() -> Vec<DetectedClient> {
+    let mut clients: Vec<DetectedClient> = ClientDetectionService::new()
+        .detect_present()
+        .into_iter()
+        .map(|found| DetectedClient {
+            label: found.label,
+            install: found.client.install_client(),
+            import: found.client.import_client(),
+            config_path: found.config_path,
+        })
+        .collect();
+
+    // JetBrains IDEs keep their MCP settings inside the IDE, so there's no
+    // config to find; McpMux can still hand them its JSON
+    if InstallClient::Jetbrains.is_installed() {
+        clients.push(DetectedClient {
+            label: "JetBrains IDEs".to_string(),
+            install: Some(InstallClient::Jetbrains),
+            import: None,
+            config_path: None,
+        });
+    }
+    clients
+}
+
+/// Check that the gateway at `url` answers its health endpoint
+pub async fn verify_gateway(url: &str) -> GatewayCheck {
+    let health = format!("{}/health", url.trim_end_matches('/'));
+    let started = Instant::now();
+    let result = match reqwest::Client::builder()
+        .timeout(GATEWAY_CHECK_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client.get(&health).send().await,
+        Err(e) => {
+            return GatewayCheck {
+                url: url.to_string(),
+                reachable: false,
+                status: None,
+                latency_ms: 0,
+                error: Some(e.to_string()),
+            }
+        }
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            GatewayCheck {
+                url: url.to_string(),
+                reachable: status.is_success(),
+                status: Some(status.as_u16()),
+                latency_ms,
+                error: (!status.is_success()).then(|| format!("Gateway returned {}", status)),
+            }
+        }
+        Err(e) => GatewayCheck {
+            url: url.to_string(),
+            reachable: false,
+            status: None,
+            latency_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn suggestions(
+    candidates: Vec<ServerDefinition>,
+    installed: &HashSet<String>,
+) -> Vec<ServerSuggestion> {
+    candidates
+        .into_iter()
+        .map(|definition| ServerSuggestion {
+            installed: installed.contains(&definition.id),
+            definition,
+        })
+        .collect()
+}
+
+/// IDs of the available features that belong to an enabled server
+fn default_grant_features(features: &[ServerFeature], enabled: &HashSet<String>) -> Vec<String> {
+    features
+        .iter()
+        .filter(|f| f.is_available && enabled.contains(&f.server_id))
+        .map(|f| f.id.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_only_available_features_of_enabled_servers() {
+        let enabled: HashSet<String> = ["github".to_string()].into();
+        let search = ServerFeature::tool("space", "github", "search");
+        let mut gone = ServerFeature::tool("space", "github", "old_tool");
+        gone.mark_unavailable();
+        let disabled = ServerFeature::prompt("space", "slack", "summarize");
+
+        let grants = default_grant_features(&[search.clone(), gone, disabled], &enabled);
+        assert_eq!(grants, [search.id.to_string()]);
+    }
+}
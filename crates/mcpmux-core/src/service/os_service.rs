@@ -0,0 +1,396 @@
+//! Run the gateway at boot/login without the desktop UI.
+//!
+//! Generates and registers a per-user service definition that runs
+//! `mcpmux-cli serve`:
+//! - Linux: systemd user unit (`~/.config/systemd/user/mcpmux-gateway.service`)
+//! - macOS: launchd agent (`~/Library/LaunchAgents/com.mcpmux.gateway.plist`)
+//! - Windows: Task Scheduler task started at logon
+//!
+//! Services run as the user rather than system-wide so the gateway can reach
+//! the user's keychain / DPAPI-protected master key and data directory.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+/// systemd unit name
+pub const SYSTEMD_UNIT: &str = "mcpmux-gateway.service";
+/// launchd job label
+pub const LAUNCHD_LABEL: &str = "com.mcpmux.gateway";
+/// Task Scheduler task name
+pub const SCHEDULED_TASK_NAME: &str = "McpMux Gateway";
+
+/// OS facility that supervises the gateway
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceManager {
+    Systemd,
+    Launchd,
+    TaskScheduler,
+}
+
+impl ServiceManager {
+    /// Service manager of the running OS
+    pub fn current() -> Option<Self> {
+        if cfg!(target_os = "linux") {
+            Some(Self::Systemd)
+        } else if cfg!(target_os = "macos") {
+            Some(Self::Launchd)
+        } else if cfg!(target_os = "windows") {
+            Some(Self::TaskScheduler)
+        } else {
+            None
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "systemd" => Some(Self::Systemd),
+            "launchd" => Some(Self::Launchd),
+            "task_scheduler" | "task-scheduler" | "schtasks" => Some(Self::TaskScheduler),
+            _ => None,
+        }
+    }
+
+    /// Where the definition is written (`None` for Task Scheduler, which
+    /// keeps its own store)
+    pub fn definition_path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Systemd => {
+                dirs::config_dir().map(|c| c.join("systemd").join("user").join(SYSTEMD_UNIT))
+            }
+            Self::Launchd => dirs::home_dir().map(|h| {
+                h.join("Library")
+                    .join("LaunchAgents")
+                    .join(format!("{}.plist", LAUNCHD_LABEL))
+            }),
+            Self::TaskScheduler => None,
+        }
+    }
+}
+
+/// What the service runs
+#[derive(Debug, Clone)]
+pub struct ServiceSpec {
+    /// Path to the `mcpmux-cli` binary
+    pub executable: PathBuf,
+    /// Data directory passed with `--data-dir`
+    pub data_dir: PathBuf,
+    /// Arguments after `serve` (e.g. `--port 45818`)
+    pub serve_args: Vec<String>,
+}
+
+impl ServiceSpec {
+    /// Full argument list after the executable
+    fn arguments(&self) -> Vec<String> {
+        let mut args = vec![
+            "--data-dir".to_string(),
+            self.data_dir.to_string_lossy().to_string(),
+            "serve".to_string(),
+        ];
+        args.extend(self.serve_args.iter().cloned());
+        args
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.data_dir.join("logs").join("gateway-service.log")
+    }
+
+    /// Render the definition for `manager`
+    pub fn render(&self, manager: ServiceManager) -> String {
+        match manager {
+            ServiceManager::Systemd => self.systemd_unit(),
+            ServiceManager::Launchd => self.launchd_plist(),
+            ServiceManager::TaskScheduler => self.task_scheduler_xml(),
+        }
+    }
+
+    fn systemd_unit(&self) -> String {
+        // Quote every word; `%` starts a specifier in unit files
+        let quote = |s: &str| {
+            format!(
+                "\"{}\"",
+                s.replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('%', "%%")
+            )
+        };
+        let exec = std::iter::once(self.executable.to_string_lossy().to_string())
+            .chain(self.arguments())
+            .map(|word| quote(&word))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            "[Unit]
+Description=McpMux gateway
+After=network-online.target
+Wants=network-online.target
+
+[Service]
+Type=simple
+ExecStart={exec}
+Restart=on-failure
+RestartSec=5
+# SIGTERM lets the gateway close its listener before exiting
+KillSignal=SIGTERM
+TimeoutStopSec=30
+
+[Install]
+WantedBy=default.target
+"
+        )
+    }
+
+    fn launchd_plist(&self) -> String {
+        let args = std::iter::once(self.executable.to_string_lossy().to_string())
+            .chain(self.arguments())
+            .map(|arg| format!("        <string>{}</string>", xml_escape(&arg)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let log = xml_escape(&self.log_path().to_string_lossy());
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{args}
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ExitTimeOut</key>
+    <integer>30</integer>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#
+        )
+    }
+
+    fn task_scheduler_xml(&self) -> String {
+        let arguments = self
+            .arguments()
+            .iter()
+            .map(|arg| windows_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>McpMux gateway</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger>
+      <Enabled>true</Enabled>
+    </LogonTrigger>
+  </Triggers>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <RestartOnFailure>
+      <Interval>PT1M</Interval>
+      <Count>3</Count>
+    </RestartOnFailure>
+  </Settings>
+  <Actions>
+    <Exec>
+      <Command>{}</Command>
+      <Arguments>{}</Arguments>
+    </Exec>
+  </Actions>
+</Task>
+"#,
+            xml_escape(&self.executable.to_string_lossy()),
+            xml_escape(&arguments)
+        )
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Quote a Windows command-line argument when needed
+fn windows_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    format!("\"{}\"", arg.replace('"', "\\\""))
+}
+
+/// Run a service-manager command, failing with its stderr
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        bail!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn write_definition(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Install (or replace) the service and start it.
+///
+/// Returns where the definition lives.
+pub fn install_service(manager: ServiceManager, spec: &ServiceSpec) -> Result<String> {
+    std::fs::create_dir_all(spec.data_dir.join("logs"))?;
+    let definition = spec.render(manager);
+
+    let location = match manager {
+        ServiceManager::Systemd => {
+            let path = manager.definition_path().context("No config directory")?;
+            write_definition(&path, &definition)?;
+            run("systemctl", &["--user", "daemon-reload"])?;
+            run("systemctl", &["--user", "enable", "--now", SYSTEMD_UNIT])?;
+            path.to_string_lossy().to_string()
+        }
+        ServiceManager::Launchd => {
+            let path = manager.definition_path().context("No home directory")?;
+            let path_str = path.to_string_lossy().to_string();
+            // Reload a previous definition; failing to unload is fine
+            let _ = run("launchctl", &["unload", &path_str]);
+            write_definition(&path, &definition)?;
+            run("launchctl", &["load", "-w", &path_str])?;
+            path_str
+        }
+        ServiceManager::TaskScheduler => {
+            // schtasks reads the definition as UTF-16 with a BOM
+            let xml_path = spec.data_dir.join("gateway-task.xml");
+            let bytes: Vec<u8> = std::iter::once(0xFEFF)
+                .chain(definition.encode_utf16())
+                .flat_map(u16::to_le_bytes)
+                .collect();
+            std::fs::write(&xml_path, bytes)
+                .with_context(|| format!("Failed to write {}", xml_path.display()))?;
+            let xml = xml_path.to_string_lossy().to_string();
+            run(
+                "schtasks",
+                &["/Create", "/TN", SCHEDULED_TASK_NAME, "/XML", &xml, "/F"],
+            )?;
+            let _ = std::fs::remove_file(&xml_path);
+            run("schtasks", &["/Run", "/TN", SCHEDULED_TASK_NAME])?;
+            format!("Task Scheduler: {}", SCHEDULED_TASK_NAME)
+        }
+    };
+
+    info!("[Service] Installed gateway service ({:?})", manager);
+    Ok(location)
+}
+
+/// Stop and remove the service. No-op when it isn't installed.
+pub fn uninstall_service(manager: ServiceManager) -> Result<()> {
+    if !is_service_installed(manager) {
+        return Ok(());
+    }
+    match manager {
+        ServiceManager::Systemd => {
+            run("systemctl", &["--user", "disable", "--now", SYSTEMD_UNIT])?;
+            if let Some(path) = manager.definition_path() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            run("systemctl", &["--user", "daemon-reload"])?;
+        }
+        ServiceManager::Launchd => {
+            if let Some(path) = manager.definition_path() {
+                let _ = run("launchctl", &["unload", "-w", &path.to_string_lossy()]);
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        ServiceManager::TaskScheduler => {
+            // Not running is fine; /End terminates the process
+            let _ = run("schtasks", &["/End", "/TN", SCHEDULED_TASK_NAME]);
+            run("schtasks", &["/Delete", "/TN", SCHEDULED_TASK_NAME, "/F"])?;
+        }
+    }
+    info!("[Service] Uninstalled gateway service ({:?})", manager);
+    Ok(())
+}
+
+/// Whether the service is registered
+pub fn is_service_installed(manager: ServiceManager) -> bool {
+    match manager {
+        ServiceManager::TaskScheduler => run("schtasks", &["/Query", "/TN", SCHEDULED_TASK_NAME])
+            .map(|_| true)
+            .unwrap_or(false),
+        _ => manager
+            .definition_path()
+            .map(|p| p.exists())
+            .unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ServiceSpec {
+        ServiceSpec {
+            executable: PathBuf::from("/opt/mcpmux/mcpmux-cli"),
+            data_dir: PathBuf::from("/home/me/My Data"),
+            serve_args: vec!["--port".to_string(), "45818".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_systemd_unit_quotes_exec_start() {
+        let unit = spec().render(ServiceManager::Systemd);
+        assert!(unit.contains(
+            r#"ExecStart="/opt/mcpmux/mcpmux-cli" "--data-dir" "/home/me/My Data" "serve" "--port" "45818""#
+        ));
+        assert!(unit.contains("KillSignal=SIGTERM"));
+        assert!(unit.contains("WantedBy=default.target"));
+    }
+
+    #[test]
+    fn test_launchd_plist_lists_arguments() {
+        let plist = spec().render(ServiceManager::Launchd);
+        assert!(plist.contains("<string>com.mcpmux.gateway</string>"));
+        assert!(plist.contains("<string>/home/me/My Data</string>"));
+        assert!(plist.contains("<string>serve</string>"));
+        assert!(plist.contains("gateway-service.log"));
+    }
+
+    #[test]
+    fn test_task_scheduler_xml_quotes_arguments() {
+        let xml = spec().render(ServiceManager::TaskScheduler);
+        assert!(xml.contains("<LogonTrigger>"));
+        assert!(xml.contains(
+            "<Arguments>--data-dir &quot;/home/me/My Data&quot; serve --port 45818</Arguments>"
+        ));
+    }
+}
@@ -0,0 +1,668 @@
+//! Policy as code: FeatureSets and client grants declared in a file
+//!
+//! A policy file (YAML or JSON, kept in git) lists, per Space, the
+//! FeatureSets that should exist with their members and the FeatureSets each
+//! client should be granted:
+//!
+//! ```yaml
+//! spaces:
+//!   - space: Work
+//!     prune: true
+//!     feature_sets:
+//!       - name: GitHub read-only
+//!         include: [github/list_issues, github/get_issue]
+//!       - name: Everything but deletes
+//!         feature_sets: [Starter]
+//!         exclude: [github/delete_repo, "annotation:destructive"]
+//!     grants:
+//!       cursor: [GitHub read-only]
+//! ```
+//!
+//! Features are named `<server_id>/<feature_name>` so renaming a server's
+//! alias doesn't change the file; `annotation:<hint>` stands for every tool
+//! with that annotation hint. [`plan_space_policy`] compares a Space's
+//! policy with what is stored and returns the changes that make the database
+//! match; applying them is left to the caller, since grants live in storage.
+//!
+//! Listed FeatureSets and listed clients' grants are reconciled exactly.
+//! With `prune`, custom FeatureSets missing from the file are deleted and
+//! clients missing from `grants` lose their grants in the Space. Built-in
+//! sets (the Starter) may have their members set but are never renamed or
+//! deleted.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    AnnotationHint, FeatureSet, FeatureSetMember, FeatureSetType, MemberMode, MemberType,
+    ServerFeature,
+};
+
+/// A parsed policy file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyDocument {
+    #[serde(default)]
+    pub spaces: Vec<SpacePolicy>,
+}
+
+/// Desired FeatureSets and grants of one Space
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpacePolicy {
+    /// Space ID or name
+    pub space: String,
+
+    /// Delete custom FeatureSets and revoke grants of clients the file
+    /// doesn't mention
+    #[serde(default)]
+    pub prune: bool,
+
+    #[serde(default)]
+    pub feature_sets: Vec<FeatureSetPolicy>,
+
+    /// Client ID or name → names of the FeatureSets it is granted
+    #[serde(default)]
+    pub grants: BTreeMap<String, Vec<String>>,
+}
+
+/// Desired state of one FeatureSet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureSetPolicy {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Features to include, as `<server_id>/<feature_name>` or
+    /// `annotation:<hint>`
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Features to exclude, as `<server_id>/<feature_name>` or
+    /// `annotation:<hint>`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Names of FeatureSets whose features are included
+    #[serde(default)]
+    pub feature_sets: Vec<String>,
+}
+
+impl PolicyDocument {
+    /// Parse a policy; JSON when `path` ends in `.json`, YAML otherwise
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let doc: Self = if is_json {
+            serde_json::from_str(content)
+                .with_context(|| format!("Invalid policy file {}", path.display()))?
+        } else {
+            serde_yaml::from_str(content)
+                .with_context(|| format!("Invalid policy file {}", path.display()))?
+        };
+        Ok(doc)
+    }
+
+    /// Read and parse a policy file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content, path)
+    }
+}
+
+/// A client and the FeatureSets it is granted in a Space
+#[derive(Debug, Clone)]
+pub struct ClientGrants {
+    pub client_id: String,
+    pub client_name: String,
+    pub feature_set_ids: Vec<String>,
+}
+
+/// What is stored for a Space, as input to [`plan_space_policy`]
+#[derive(Debug, Clone, Default)]
+pub struct SpaceSnapshot {
+    pub space_id: String,
+    pub space_name: String,
+    /// The Space's FeatureSets, with members
+    pub feature_sets: Vec<FeatureSet>,
+    /// The Space's discovered features
+    pub features: Vec<ServerFeature>,
+    /// Every registered client with its grants in the Space
+    pub clients: Vec<ClientGrants>,
+    /// FeatureSets pruning leaves alone, e.g. those owned by server groups
+    pub protected: HashSet<String>,
+}
+
+/// One change needed to make a Space match its policy
+#[derive(Debug, Clone)]
+pub enum PolicyChange {
+    CreateFeatureSet {
+        feature_set: FeatureSet,
+        /// Members as `+ include github/list_issues` lines
+        details: Vec<String>,
+    },
+    UpdateFeatureSet {
+        /// The FeatureSet as it should be stored, members included
+        feature_set: FeatureSet,
+        details: Vec<String>,
+    },
+    DeleteFeatureSet {
+        id: String,
+        name: String,
+    },
+    Grant {
+        client_id: String,
+        client_name: String,
+        feature_set_id: String,
+        feature_set_name: String,
+    },
+    Revoke {
+        client_id: String,
+        client_name: String,
+        feature_set_id: String,
+        feature_set_name: String,
+    },
+}
+
+impl fmt::Display for PolicyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (summary, details): (String, &[String]) = match self {
+            Self::CreateFeatureSet {
+                feature_set,
+                details,
+            } => (format!("+ feature set \"{}\"", feature_set.name), details),
+            Self::UpdateFeatureSet {
+                feature_set,
+                details,
+            } => (format!("~ feature set \"{}\"", feature_set.name), details),
+            Self::DeleteFeatureSet { name, .. } => (format!("- feature set \"{}\"", name), &[]),
+            Self::Grant {
+                client_name,
+                feature_set_name,
+                ..
+            } => (
+                format!("+ grant \"{}\" to {}", feature_set_name, client_name),
+                &[],
+            ),
+            Self::Revoke {
+                client_name,
+                feature_set_name,
+                ..
+            } => (
+                format!("- grant \"{}\" to {}", feature_set_name, client_name),
+                &[],
+            ),
+        };
+        write!(f, "{}", summary)?;
+        for line in details {
+            write!(f, "\n    {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Changes for one Space, in the order they must be applied
+#[derive(Debug, Clone, Default)]
+pub struct PolicyPlan {
+    pub space_id: String,
+    pub space_name: String,
+    pub changes: Vec<PolicyChange>,
+}
+
+impl PolicyPlan {
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for PolicyPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Space {}", self.space_name)?;
+        if self.changes.is_empty() {
+            return write!(f, ": up to date");
+        }
+        for change in &self.changes {
+            for line in change.to_string().lines() {
+                write!(f, "\n  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a member is written in a policy file and in the diff
+fn member_line(member: &FeatureSetMember, labels: &HashMap<String, String>) -> String {
+    let label = labels
+        .get(&member.member_id)
+        .cloned()
+        .unwrap_or_else(|| member.member_id.clone());
+    match (member.member_type, member.mode) {
+        (MemberType::FeatureSet, _) => format!("feature set \"{}\"", label),
+        (MemberType::Feature, MemberMode::Include) => format!("include {}", label),
+        (MemberType::Feature, MemberMode::Exclude) => format!("exclude {}", label),
+        (MemberType::Annotation, MemberMode::Include) => format!("include annotation:{}", label),
+        (MemberType::Annotation, MemberMode::Exclude) => format!("exclude annotation:{}", label),
+    }
+}
+
+/// Work out what to change so `snapshot` matches `policy`
+pub fn plan_space_policy(policy: &SpacePolicy, snapshot: &SpaceSnapshot) -> Result<PolicyPlan> {
+    // Features by `<server_id>/<feature_name>`; a tool and a prompt of the
+    // same name are both meant
+    let mut features: HashMap<String, Vec<String>> = HashMap::new();
+    let mut labels: HashMap<String, String> = HashMap::new();
+    for feature in &snapshot.features {
+        let key = format!("{}/{}", feature.server_id, feature.feature_name);
+        features
+            .entry(key.clone())
+            .or_default()
+            .push(feature.id.to_string());
+        labels.insert(feature.id.to_string(), key);
+    }
+
+    let mut existing: HashMap<String, &FeatureSet> = HashMap::new();
+    for fs in &snapshot.feature_sets {
+        existing.insert(fs.name.to_lowercase(), fs);
+        labels.insert(fs.id.clone(), fs.name.clone());
+    }
+
+    // Give new sets their IDs up front so other sets and grants can refer
+    // to them
+    let mut ids: HashMap<String, String> = existing
+        .iter()
+        .map(|(name, fs)| (name.clone(), fs.id.clone()))
+        .collect();
+    let mut declared = HashSet::new();
+    let mut created = HashMap::new();
+    for fs_policy in &policy.feature_sets {
+        let key = fs_policy.name.trim().to_lowercase();
+        if key.is_empty() {
+            bail!("FeatureSet name is required in Space {}", policy.space);
+        }
+        if !declared.insert(key.clone()) {
+            bail!("FeatureSet \"{}\" is declared twice", fs_policy.name);
+        }
+        if !existing.contains_key(&key) {
+            let fs = FeatureSet::new_custom(fs_policy.name.trim(), &snapshot.space_id);
+            ids.insert(key, fs.id.clone());
+            labels.insert(fs.id.clone(), fs.name.clone());
+            created.insert(fs.name.to_lowercase(), fs);
+        }
+    }
+    let feature_set_id = |name: &str| {
+        ids.get(&name.trim().to_lowercase())
+            .cloned()
+            .ok_or_else(|| anyhow!("FeatureSet not found in {}: {}", snapshot.space_name, name))
+    };
+
+    let mut changes = Vec::new();
+    let mut deletes = Vec::new();
+    for fs_policy in &policy.feature_sets {
+        let key = fs_policy.name.trim().to_lowercase();
+        let fs_id = feature_set_id(&key)?;
+
+        let mut members: Vec<FeatureSetMember> = Vec::new();
+        let mut seen = HashSet::new();
+        for name in &fs_policy.feature_sets {
+            let included = feature_set_id(name)?;
+            if included == fs_id {
+                bail!("FeatureSet \"{}\" includes itself", fs_policy.name);
+            }
+            if seen.insert((MemberType::FeatureSet, included.clone())) {
+                members.push(FeatureSetMember::include_featureset(&fs_id, &included));
+            }
+        }
+        for (refs, mode) in [
+            (&fs_policy.include, MemberMode::Include),
+            (&fs_policy.exclude, MemberMode::Exclude),
+        ] {
+            for reference in refs {
+                if let Some(hint) = reference.trim().strip_prefix("annotation:") {
+                    let hint = AnnotationHint::parse(hint.trim()).ok_or_else(|| {
+                        anyhow!(
+                            "Unknown annotation hint in FeatureSet \"{}\": {} (expected one of {})",
+                            fs_policy.name,
+                            reference,
+                            AnnotationHint::ALL.map(|h| h.as_str()).join(", ")
+                        )
+                    })?;
+                    if !seen.insert((MemberType::Annotation, hint.as_str().to_string())) {
+                        bail!(
+                            "{} is listed twice in FeatureSet \"{}\"",
+                            reference,
+                            fs_policy.name
+                        );
+                    }
+                    members.push(match mode {
+                        MemberMode::Include => FeatureSetMember::include_annotation(&fs_id, hint),
+                        MemberMode::Exclude => FeatureSetMember::exclude_annotation(&fs_id, hint),
+                    });
+                    continue;
+                }
+                let matched = features.get(reference.trim()).ok_or_else(|| {
+                    anyhow!(
+                        "Feature not found in {}: {} (features are named <server_id>/<feature_name> \
+                         and must have been discovered by connecting the server)",
+                        snapshot.space_name,
+                        reference
+                    )
+                })?;
+                for feature_id in matched {
+                    if seen.insert((MemberType::Feature, feature_id.clone())) {
+                        members.push(match mode {
+                            MemberMode::Include => {
+                                FeatureSetMember::include_feature(&fs_id, feature_id)
+                            }
+                            MemberMode::Exclude => {
+                                FeatureSetMember::exclude_feature(&fs_id, feature_id)
+                            }
+                        });
+                    } else {
+                        bail!(
+                            "{} is listed twice in FeatureSet \"{}\"",
+                            reference,
+                            fs_policy.name
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(mut fs) = created.remove(&key) {
+            fs.description = fs_policy.description.clone();
+            fs.icon = fs_policy.icon.clone();
+            let mut details: Vec<String> = members
+                .iter()
+                .map(|m| format!("+ {}", member_line(m, &labels)))
+                .collect();
+            details.sort();
+            fs.members = members;
+            changes.push(PolicyChange::CreateFeatureSet {
+                feature_set: fs,
+                details,
+            });
+            continue;
+        }
+
+        let current = existing[&key];
+        let mut fs = current.clone();
+        let mut details = Vec::new();
+        if !current.is_builtin {
+            if fs_policy.description != current.description {
+                details.push(format!(
+                    "description: {:?} -> {:?}",
+                    current.description.as_deref().unwrap_or(""),
+                    fs_policy.description.as_deref().unwrap_or("")
+                ));
+                fs.description = fs_policy.description.clone();
+            }
+            if fs_policy.icon != current.icon {
+                details.push(format!(
+                    "icon: {:?} -> {:?}",
+                    current.icon.as_deref().unwrap_or(""),
+                    fs_policy.icon.as_deref().unwrap_or("")
+                ));
+                fs.icon = fs_policy.icon.clone();
+            }
+        }
+        let identity = |m: &FeatureSetMember| (m.member_type, m.member_id.clone(), m.mode);
+        let wanted: HashSet<_> = members.iter().map(identity).collect();
+        let had: HashSet<_> = current.members.iter().map(identity).collect();
+        let mut removed: Vec<String> = current
+            .members
+            .iter()
+            .filter(|m| !wanted.contains(&identity(m)))
+            .map(|m| format!("- {}", member_line(m, &labels)))
+            .collect();
+        removed.sort();
+        let mut added: Vec<String> = members
+            .iter()
+            .filter(|m| !had.contains(&identity(m)))
+            .map(|m| format!("+ {}", member_line(m, &labels)))
+            .collect();
+        added.sort();
+        details.extend(added);
+        details.extend(removed);
+        if !details.is_empty() {
+            fs.members = members;
+            fs.updated_at = chrono::Utc::now();
+            changes.push(PolicyChange::UpdateFeatureSet {
+                feature_set: fs,
+                details,
+            });
+        }
+    }
+
+    if policy.prune {
+        let mut stale: Vec<&FeatureSet> = snapshot
+            .feature_sets
+            .iter()
+            .filter(|fs| {
+                fs.feature_set_type == FeatureSetType::Custom
+                    && !fs.is_builtin
+                    && !declared.contains(&fs.name.to_lowercase())
+                    && !snapshot.protected.contains(&fs.id)
+            })
+            .collect();
+        stale.sort_by(|a, b| a.name.cmp(&b.name));
+        deletes.extend(stale.into_iter().map(|fs| PolicyChange::DeleteFeatureSet {
+            id: fs.id.clone(),
+            name: fs.name.clone(),
+        }));
+    }
+    let deleted: HashSet<&str> = deletes
+        .iter()
+        .filter_map(|change| match change {
+            PolicyChange::DeleteFeatureSet { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    // Grants, client by client
+    let mut listed = HashSet::new();
+    for (reference, names) in &policy.grants {
+        let client = snapshot
+            .clients
+            .iter()
+            .find(|c| c.client_id == *reference)
+            .or_else(|| {
+                snapshot
+                    .clients
+                    .iter()
+                    .find(|c| c.client_name.eq_ignore_ascii_case(reference))
+            })
+            .ok_or_else(|| anyhow!("Client not found: {}", reference))?;
+        if !listed.insert(client.client_id.clone()) {
+            bail!("Client {} is listed twice in grants", client.client_name);
+        }
+        let wanted: BTreeSet<String> = names
+            .iter()
+            .map(|name| feature_set_id(name))
+            .collect::<Result<_>>()?;
+        changes.extend(grant_changes(client, &wanted, &labels, &deleted));
+    }
+    if policy.prune {
+        for client in &snapshot.clients {
+            if !listed.contains(&client.client_id) {
+                changes.extend(grant_changes(client, &BTreeSet::new(), &labels, &deleted));
+            }
+        }
+    }
+
+    // Deleting a set drops its grants, so deletes go last
+    changes.extend(deletes);
+    Ok(PolicyPlan {
+        space_id: snapshot.space_id.clone(),
+        space_name: snapshot.space_name.clone(),
+        changes,
+    })
+}
+
+fn grant_changes(
+    client: &ClientGrants,
+    wanted: &BTreeSet<String>,
+    labels: &HashMap<String, String>,
+    deleted: &HashSet<&str>,
+) -> Vec<PolicyChange> {
+    let had: BTreeSet<String> = client.feature_set_ids.iter().cloned().collect();
+    let name = |id: &String| labels.get(id).cloned().unwrap_or_else(|| id.clone());
+    let grants = wanted.difference(&had).map(|id| PolicyChange::Grant {
+        client_id: client.client_id.clone(),
+        client_name: client.client_name.clone(),
+        feature_set_id: id.clone(),
+        feature_set_name: name(id),
+    });
+    // A deleted set's grants go with it
+    let revokes = had
+        .difference(wanted)
+        .filter(|id| !deleted.contains(id.as_str()))
+        .map(|id| PolicyChange::Revoke {
+            client_id: client.client_id.clone(),
+            client_name: client.client_name.clone(),
+            feature_set_id: id.clone(),
+            feature_set_name: name(id),
+        });
+    grants.chain(revokes).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACE: &str = "00000000-0000-0000-0000-000000000001";
+
+    fn snapshot() -> SpaceSnapshot {
+        let starter = FeatureSet::new_starter(SPACE);
+        let old = FeatureSet::new_custom("Old", SPACE);
+        let mut docs = FeatureSet::new_custom("Docs", SPACE);
+        let list = ServerFeature::tool(SPACE, "github", "list_issues");
+        let delete = ServerFeature::tool(SPACE, "github", "delete_repo");
+        docs.members = vec![FeatureSetMember::include_feature(
+            &docs.id,
+            &delete.id.to_string(),
+        )];
+        SpaceSnapshot {
+            space_id: SPACE.to_string(),
+            space_name: "Work".to_string(),
+            clients: vec![
+                ClientGrants {
+                    client_id: "c1".to_string(),
+                    client_name: "Cursor".to_string(),
+                    feature_set_ids: vec![starter.id.clone()],
+                },
+                ClientGrants {
+                    client_id: "c2".to_string(),
+                    client_name: "Zed".to_string(),
+                    feature_set_ids: vec![old.id.clone(), starter.id.clone()],
+                },
+            ],
+            feature_sets: vec![starter, old, docs],
+            features: vec![list, delete],
+            protected: HashSet::new(),
+        }
+    }
+
+    const POLICY: &str = r#"
+spaces:
+  - space: Work
+    prune: true
+    feature_sets:
+      - name: GitHub read-only
+        include: [github/list_issues]
+      - name: Docs
+        description: Documentation
+        include: [github/list_issues]
+        exclude: [github/delete_repo, "annotation:destructive"]
+    grants:
+      cursor: [GitHub read-only]
+"#;
+
+    #[test]
+    fn plans_creates_updates_grants_and_prunes() {
+        let doc = PolicyDocument::parse(POLICY, Path::new("policy.yaml")).unwrap();
+        let snapshot = snapshot();
+        let plan = plan_space_policy(&doc.spaces[0], &snapshot).unwrap();
+
+        let text = plan.to_string();
+        assert!(
+            text.contains("+ feature set \"GitHub read-only\"\n      + include github/list_issues")
+        );
+        assert!(text.contains("~ feature set \"Docs\""));
+        assert!(text.contains("+ exclude github/delete_repo"));
+        assert!(text.contains("+ exclude annotation:destructive"));
+        assert!(text.contains("- include github/delete_repo"));
+        assert!(text.contains("+ grant \"GitHub read-only\" to Cursor"));
+        assert!(text.contains("- grant \"Starter\" to Cursor"));
+        // Zed isn't listed, so pruning revokes the Starter; Old's grant goes
+        // with the set
+        assert!(text.contains("- grant \"Starter\" to Zed"));
+        assert!(!text.contains("\"Old\" to Zed"));
+        assert!(text.ends_with("- feature set \"Old\""));
+
+        // The new set's ID is what the grant refers to
+        let created = plan
+            .changes
+            .iter()
+            .find_map(|c| match c {
+                PolicyChange::CreateFeatureSet { feature_set, .. } => Some(feature_set.id.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert!(plan.changes.iter().any(|c| matches!(
+            c,
+            PolicyChange::Grant { client_id, feature_set_id, .. }
+                if client_id == "c1" && *feature_set_id == created
+        )));
+    }
+
+    #[test]
+    fn matching_state_has_no_changes() {
+        let mut snapshot = snapshot();
+        let starter_id = snapshot.feature_sets[0].id.clone();
+        snapshot.feature_sets.truncate(2);
+        let policy = SpacePolicy {
+            space: "Work".to_string(),
+            grants: BTreeMap::from([("c1".to_string(), vec!["starter".to_string()])]),
+            ..Default::default()
+        };
+        let plan = plan_space_policy(&policy, &snapshot).unwrap();
+        assert!(!plan.has_changes(), "{}", plan);
+        assert_eq!(snapshot.clients[0].feature_set_ids, [starter_id]);
+        assert!(plan.to_string().ends_with("up to date"));
+    }
+
+    #[test]
+    fn rejects_unknown_references() {
+        let snapshot = snapshot();
+        let json = r#"{"spaces": [{"space": "Work", "feature_sets": [{"name": "X", "include": ["github/nope"]}]}]}"#;
+        let doc = PolicyDocument::parse(json, Path::new("policy.json")).unwrap();
+        let err = plan_space_policy(&doc.spaces[0], &snapshot).unwrap_err();
+        assert!(err.to_string().contains("github/nope"));
+
+        let json = r#"{"spaces": [{"space": "Work", "feature_sets": [{"name": "X", "exclude": ["annotation:scary"]}]}]}"#;
+        let doc = PolicyDocument::parse(json, Path::new("policy.json")).unwrap();
+        assert!(plan_space_policy(&doc.spaces[0], &snapshot).is_err());
+
+        let policy = SpacePolicy {
+            space: "Work".to_string(),
+            grants: BTreeMap::from([("Emacs".to_string(), vec![])]),
+            ..Default::default()
+        };
+        assert!(plan_space_policy(&policy, &snapshot).is_err());
+
+        assert!(
+            PolicyDocument::parse("spaces: [{space: W, typo: 1}]", Path::new("p.yml")).is_err()
+        );
+    }
+}
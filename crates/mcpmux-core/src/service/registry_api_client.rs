@@ -4,14 +4,14 @@
 //! All server discovery, filtering, and searching is done client-side
 //! against the cached bundle data.
 //!
-//! Supports ETag-based conditional fetching to avoid re-downloading
-//! unchanged bundles.
+//! Supports conditional fetching (`If-None-Match` / `If-Modified-Since`) to
+//! avoid re-downloading unchanged bundles.
 
 use anyhow::{Context as _, Result};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::domain::ServerDefinition;
+use crate::domain::{RegistryAuth, ServerDefinition};
 
 /// Response wrapper from Registry API
 #[derive(Debug, Deserialize)]
@@ -103,13 +103,29 @@ pub struct HomeConfig {
 // Fetch Result
 // ============================================
 
-/// Result of fetching a bundle with ETag support
+/// Cache validators of a fetched bundle, sent back on the next fetch so an
+/// unchanged bundle is answered with 304
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleValidators {
+    /// `ETag` response header
+    pub etag: Option<String>,
+    /// `Last-Modified` response header
+    pub last_modified: Option<String>,
+}
+
+impl BundleValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Result of a conditional bundle fetch
 #[derive(Debug)]
 pub enum FetchBundleResult {
     /// New or updated bundle received
     Updated {
         bundle: Box<RegistryBundle>,
-        etag: Option<String>,
+        validators: BundleValidators,
     },
     /// Bundle unchanged (304 Not Modified)
     NotModified,
@@ -126,6 +142,7 @@ pub enum FetchBundleResult {
 pub struct RegistryApiClient {
     base_url: String,
     client: reqwest::Client,
+    auth: Option<RegistryAuth>,
 }
 
 impl RegistryApiClient {
@@ -137,7 +154,17 @@ impl RegistryApiClient {
             .build()
             .expect("Failed to build HTTP client");
 
-        Self { base_url, client }
+        Self {
+            base_url,
+            client,
+            auth: None,
+        }
+    }
+
+    /// Send credentials with every request (private registries)
+    pub fn with_auth(mut self, auth: RegistryAuth) -> Self {
+        self.auth = Some(auth);
+        self
     }
 
     /// Get the base URL
@@ -147,23 +174,34 @@ impl RegistryApiClient {
 
     /// Fetch complete registry bundle from /v1/bundle
     ///
-    /// If `current_etag` is provided, sends `If-None-Match` header.
-    /// Returns `NotModified` if server responds with 304.
+    /// Sends `If-None-Match` / `If-Modified-Since` for the validators of the
+    /// cached bundle. Returns `NotModified` if server responds with 304.
     ///
     /// This is the ONLY method used for fetching registry data.
     /// All filtering, searching, and sorting is done client-side.
-    pub async fn fetch_bundle(&self, current_etag: Option<&str>) -> Result<FetchBundleResult> {
+    pub async fn fetch_bundle(&self, cached: &BundleValidators) -> Result<FetchBundleResult> {
         let url = format!("{}/v1/bundle", self.base_url);
 
         tracing::info!("Fetching registry bundle from {}", url);
 
         let mut request = self.client.get(&url);
+        request = match &self.auth {
+            Some(RegistryAuth::Bearer { token }) => request.bearer_auth(token),
+            Some(RegistryAuth::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            None => request,
+        };
 
-        // Add If-None-Match header if we have a cached ETag
-        if let Some(etag) = current_etag {
+        // Conditional headers for the cached bundle
+        if let Some(ref etag) = cached.etag {
             tracing::debug!("Sending If-None-Match: {}", etag);
             request = request.header("If-None-Match", etag);
         }
+        if let Some(ref last_modified) = cached.last_modified {
+            tracing::debug!("Sending If-Modified-Since: {}", last_modified);
+            request = request.header("If-Modified-Since", last_modified);
+        }
 
         let response = request
             .send()
@@ -178,16 +216,34 @@ impl RegistryApiClient {
             return Ok(FetchBundleResult::NotModified);
         }
 
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            anyhow::bail!(
+                "Registry API rejected the request ({}): {}",
+                status,
+                if self.auth.is_some() {
+                    "check the registry's credentials"
+                } else {
+                    "the registry requires credentials"
+                }
+            );
+        }
+
         if !status.is_success() {
             anyhow::bail!("Registry API returned status: {}", status);
         }
 
-        // Extract ETag from response headers
-        let etag = response
-            .headers()
-            .get("etag")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+        // Extract cache validators from response headers
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let validators = BundleValidators {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
 
         let api_response: ApiResponse<RegistryBundle> = response
             .json()
@@ -197,18 +253,18 @@ impl RegistryApiClient {
         let bundle = api_response.data;
 
         tracing::info!(
-            "Fetched {} servers, {} filters, {} sort options (version: {}, updated: {}, etag: {:?})",
+            "Fetched {} servers, {} filters, {} sort options (version: {}, updated: {}, {:?})",
             bundle.servers.len(),
             bundle.ui.filters.len(),
             bundle.ui.sort_options.len(),
             bundle.version,
             bundle.updated_at,
-            etag
+            validators
         );
 
         Ok(FetchBundleResult::Updated {
             bundle: Box::new(bundle),
-            etag,
+            validators,
         })
     }
 }
@@ -225,10 +281,10 @@ mod tests {
                 .unwrap_or_else(|_| "https://api.mcpmux.com".to_string()),
         );
 
-        let result = client.fetch_bundle(None).await;
+        let result = client.fetch_bundle(&BundleValidators::default()).await;
 
         // This will fail if dev server is not running - that's expected
-        if let Ok(FetchBundleResult::Updated { bundle, etag }) = result {
+        if let Ok(FetchBundleResult::Updated { bundle, validators }) = result {
             assert!(
                 !bundle.servers.is_empty(),
                 "Should have at least one server"
@@ -238,7 +294,7 @@ mod tests {
                 !bundle.ui.sort_options.is_empty(),
                 "Should have sort options"
             );
-            assert!(etag.is_some(), "Should have ETag");
+            assert!(validators.etag.is_some(), "Should have ETag");
         }
     }
 
@@ -250,13 +306,10 @@ mod tests {
         );
 
         // First fetch to get ETag
-        let first_result = client.fetch_bundle(None).await;
-        if let Ok(FetchBundleResult::Updated {
-            etag: Some(etag), ..
-        }) = first_result
-        {
+        let first_result = client.fetch_bundle(&BundleValidators::default()).await;
+        if let Ok(FetchBundleResult::Updated { validators, .. }) = first_result {
             // Second fetch with ETag should return NotModified
-            let second_result = client.fetch_bundle(Some(&etag)).await;
+            let second_result = client.fetch_bundle(&validators).await;
             if let Ok(FetchBundleResult::NotModified) = second_result {
                 // Success!
             } else {
@@ -264,4 +317,70 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_fetch_bundle_sends_credentials() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 401 Unauthorized\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let client = RegistryApiClient::new(url).with_auth(RegistryAuth::Bearer {
+            token: "tok".to_string(),
+        });
+        let err = client
+            .fetch_bundle(&BundleValidators::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("credentials"));
+
+        let request = server.await.unwrap();
+        assert!(request.contains("authorization: bearer tok"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_bundle_sends_validators() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 304 Not Modified\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let cached = BundleValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: Some("Wed, 14 Oct 2026 08:00:00 GMT".to_string()),
+        };
+        let result = RegistryApiClient::new(url)
+            .fetch_bundle(&cached)
+            .await
+            .unwrap();
+        assert!(matches!(result, FetchBundleResult::NotModified));
+
+        let request = server.await.unwrap();
+        assert!(request.contains("if-none-match: \"v1\""));
+        assert!(request.contains("if-modified-since: wed, 14 oct 2026 08:00:00 gmt"));
+    }
 }
@@ -0,0 +1,232 @@
+//! Registry credential service - credentials of private registries
+//!
+//! Registry credentials are stored through the [`CredentialRepository`], so
+//! they're encrypted like server credentials. Registries aren't part of a
+//! Space; their rows use the nil space ID and a `registry:<id>` server ID.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{Credential, CredentialType, RegistryAuth};
+use crate::repository::CredentialRepository;
+
+/// Space ID registry credentials are stored under
+pub const REGISTRY_CREDENTIALS_SPACE: Uuid = Uuid::nil();
+
+/// Credential server ID of a registry
+fn credential_key(registry_id: &str) -> String {
+    format!("registry:{}", registry_id)
+}
+
+/// Service for reading and replacing registry credentials
+pub struct RegistryCredentialService {
+    credential_repo: Arc<dyn CredentialRepository>,
+}
+
+impl RegistryCredentialService {
+    pub fn new(credential_repo: Arc<dyn CredentialRepository>) -> Self {
+        Self { credential_repo }
+    }
+
+    /// Get the credentials of a registry, if it has any
+    pub async fn get(&self, registry_id: &str) -> Result<Option<RegistryAuth>> {
+        let credentials = self
+            .credential_repo
+            .get_all(&REGISTRY_CREDENTIALS_SPACE, &credential_key(registry_id))
+            .await?;
+        let value = |credential_type: CredentialType| {
+            credentials
+                .iter()
+                .find(|c| c.credential_type == credential_type)
+                .map(|c| c.value.clone())
+        };
+
+        if let Some(token) = value(CredentialType::ApiKey) {
+            return Ok(Some(RegistryAuth::Bearer { token }));
+        }
+        Ok(
+            value(CredentialType::BasicAuthUser).map(|username| RegistryAuth::Basic {
+                username,
+                password: value(CredentialType::BasicAuthPass).unwrap_or_default(),
+            }),
+        )
+    }
+
+    /// Replace the credentials of a registry; `None` removes them
+    pub async fn set(&self, registry_id: &str, auth: Option<&RegistryAuth>) -> Result<()> {
+        if let Some(auth) = auth {
+            auth.validate().map_err(|e| anyhow!(e))?;
+        }
+
+        let key = credential_key(registry_id);
+        self.credential_repo
+            .delete_all(&REGISTRY_CREDENTIALS_SPACE, &key)
+            .await?;
+
+        let credentials: Vec<Credential> = match auth {
+            None => vec![],
+            Some(RegistryAuth::Bearer { token }) => {
+                vec![Credential::api_key(REGISTRY_CREDENTIALS_SPACE, &key, token)]
+            }
+            Some(RegistryAuth::Basic { username, password }) => {
+                Credential::basic_auth(REGISTRY_CREDENTIALS_SPACE, &key, username, password).into()
+            }
+        };
+        for credential in &credentials {
+            self.credential_repo.save(credential).await?;
+        }
+
+        info!(
+            registry_id = registry_id,
+            auth = ?auth.map(RegistryAuth::kind),
+            "[RegistryCredentialService] Updated registry credentials"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tokio::sync::RwLock;
+
+    /// In-memory repository for testing
+    #[derive(Default)]
+    struct InMemoryCredentialRepository {
+        rows: RwLock<Vec<Credential>>,
+    }
+
+    #[async_trait]
+    impl CredentialRepository for InMemoryCredentialRepository {
+        async fn get(
+            &self,
+            space_id: &Uuid,
+            server_id: &str,
+            credential_type: &CredentialType,
+        ) -> Result<Option<Credential>> {
+            Ok(self
+                .rows
+                .read()
+                .await
+                .iter()
+                .find(|c| {
+                    &c.space_id == space_id
+                        && c.server_id == server_id
+                        && &c.credential_type == credential_type
+                })
+                .cloned())
+        }
+
+        async fn get_all(&self, space_id: &Uuid, server_id: &str) -> Result<Vec<Credential>> {
+            Ok(self
+                .rows
+                .read()
+                .await
+                .iter()
+                .filter(|c| &c.space_id == space_id && c.server_id == server_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn save(&self, credential: &Credential) -> Result<()> {
+            let mut rows = self.rows.write().await;
+            rows.retain(|c| {
+                !(c.space_id == credential.space_id
+                    && c.server_id == credential.server_id
+                    && c.credential_type == credential.credential_type)
+            });
+            rows.push(credential.clone());
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            space_id: &Uuid,
+            server_id: &str,
+            credential_type: &CredentialType,
+        ) -> Result<()> {
+            self.rows.write().await.retain(|c| {
+                !(&c.space_id == space_id
+                    && c.server_id == server_id
+                    && &c.credential_type == credential_type)
+            });
+            Ok(())
+        }
+
+        async fn delete_all(&self, space_id: &Uuid, server_id: &str) -> Result<()> {
+            self.rows
+                .write()
+                .await
+                .retain(|c| !(&c.space_id == space_id && c.server_id == server_id));
+            Ok(())
+        }
+
+        async fn clear_tokens(&self, _space_id: &Uuid, _server_id: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_for_space(&self, space_id: &Uuid) -> Result<Vec<Credential>> {
+            Ok(self
+                .rows
+                .read()
+                .await
+                .iter()
+                .filter(|c| &c.space_id == space_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn service() -> RegistryCredentialService {
+        RegistryCredentialService::new(Arc::new(InMemoryCredentialRepository::default()))
+    }
+
+    #[tokio::test]
+    async fn test_bearer_round_trip() {
+        let service = service();
+        assert_eq!(service.get("acme").await.unwrap(), None);
+
+        let auth = RegistryAuth::Bearer {
+            token: "tok".to_string(),
+        };
+        service.set("acme", Some(&auth)).await.unwrap();
+        assert_eq!(service.get("acme").await.unwrap(), Some(auth));
+        assert_eq!(service.get("other").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_switching_kind_replaces_old_credentials() {
+        let service = service();
+        service
+            .set(
+                "acme",
+                Some(&RegistryAuth::Bearer {
+                    token: "tok".to_string(),
+                }),
+            )
+            .await
+            .unwrap();
+
+        let basic = RegistryAuth::Basic {
+            username: "ci".to_string(),
+            password: "pw".to_string(),
+        };
+        service.set("acme", Some(&basic)).await.unwrap();
+        assert_eq!(service.get("acme").await.unwrap(), Some(basic));
+
+        service.set("acme", None).await.unwrap();
+        assert_eq!(service.get("acme").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_empty_token() {
+        let auth = RegistryAuth::Bearer {
+            token: String::new(),
+        };
+        assert!(service().set("acme", Some(&auth)).await.is_err());
+    }
+}
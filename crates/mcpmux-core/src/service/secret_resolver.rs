@@ -0,0 +1,567 @@
+//! Secret references in server configuration
+//!
+//! Server inputs, env values, args and headers may contain references of the
+//! form `${scheme:reference}` instead of a literal secret. They are resolved
+//! at connect time — the resolved value is handed to the transport and never
+//! written back to SQLite or logged.
+//!
+//! Built-in schemes:
+//! - `${env:VAR}` — environment variable of the McpMux process
+//! - `${vault:path#field}` — HashiCorp Vault (KV v1 or v2), configured via
+//!   `VAULT_ADDR` / `VAULT_TOKEN` (and optional `VAULT_NAMESPACE`)
+//! - `${op:vault/item/field}` — 1Password Connect, configured via
+//!   `OP_CONNECT_HOST` / `OP_CONNECT_TOKEN`
+//!
+//! `${keychain:item}` is provided by the storage layer, which owns the OS
+//! keychain integration. Other schemes (notably `${input:ID}`, which is
+//! substituted earlier from saved inputs) are left untouched.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use thiserror::Error;
+use tracing::debug;
+
+/// Schemes that are always treated as secret references. A reference using
+/// one of these without a registered resolver is an error rather than being
+/// passed through literally.
+pub const SECRET_SCHEMES: &[&str] = &["env", "keychain", "vault", "op"];
+
+/// Errors raised while resolving a secret reference.
+///
+/// Messages name the reference, never the resolved value.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SecretError {
+    #[error("Secret backend '{scheme}' is not configured")]
+    NotConfigured { scheme: String },
+    #[error("Secret '{scheme}:{reference}' was not found")]
+    NotFound { scheme: String, reference: String },
+    #[error("Invalid secret reference '{scheme}:{reference}': {reason}")]
+    InvalidReference {
+        scheme: String,
+        reference: String,
+        reason: String,
+    },
+    #[error("Secret backend '{scheme}' failed: {message}")]
+    Backend { scheme: String, message: String },
+}
+
+/// Resolves references for one scheme (e.g. `env`, `vault`).
+#[async_trait]
+pub trait SecretResolver: Send + Sync {
+    /// Scheme handled by this resolver, without the trailing colon.
+    fn scheme(&self) -> &str;
+
+    /// Resolve the part of the reference after `scheme:`.
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError>;
+}
+
+/// A `${scheme:reference}` occurrence inside a template string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretReference {
+    pub scheme: String,
+    pub reference: String,
+    /// Byte range of the whole `${...}` placeholder in the template
+    pub span: std::ops::Range<usize>,
+}
+
+/// Find every `${scheme:reference}` placeholder in a string.
+pub fn parse_secret_references(template: &str) -> Vec<SecretReference> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = template[offset..].find("${") {
+        let start = offset + start;
+        let Some(len) = template[start..].find('}') else {
+            break;
+        };
+        let end = start + len + 1;
+        let inner = &template[start + 2..end - 1];
+        if let Some((scheme, reference)) = inner.split_once(':') {
+            let valid_scheme = !scheme.is_empty()
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if valid_scheme && !reference.is_empty() {
+                found.push(SecretReference {
+                    scheme: scheme.to_ascii_lowercase(),
+                    reference: reference.to_string(),
+                    span: start..end,
+                });
+            }
+        }
+        offset = end;
+    }
+    found
+}
+
+/// Set of resolvers keyed by scheme.
+#[derive(Clone, Default)]
+pub struct SecretResolverRegistry {
+    resolvers: HashMap<String, Arc<dyn SecretResolver>>,
+}
+
+impl SecretResolverRegistry {
+    /// Empty registry (resolves nothing; secret schemes are reported as not configured).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with `env` plus any external backend configured through the
+    /// process environment.
+    pub fn from_env() -> Self {
+        let mut registry = Self::new().with_resolver(Arc::new(EnvSecretResolver));
+        if let Some(vault) = VaultSecretResolver::from_env() {
+            registry = registry.with_resolver(Arc::new(vault));
+        }
+        if let Some(op) = OnePasswordConnectResolver::from_env() {
+            registry = registry.with_resolver(Arc::new(op));
+        }
+        registry
+    }
+
+    /// Register (or replace) the resolver for its scheme.
+    pub fn with_resolver(mut self, resolver: Arc<dyn SecretResolver>) -> Self {
+        self.resolvers
+            .insert(resolver.scheme().to_ascii_lowercase(), resolver);
+        self
+    }
+
+    /// Registered schemes.
+    pub fn schemes(&self) -> Vec<&str> {
+        let mut schemes: Vec<&str> = self.resolvers.keys().map(String::as_str).collect();
+        schemes.sort_unstable();
+        schemes
+    }
+
+    fn handles(&self, scheme: &str) -> bool {
+        self.resolvers.contains_key(scheme) || SECRET_SCHEMES.contains(&scheme)
+    }
+
+    /// True when the string contains at least one reference this registry
+    /// would act on.
+    pub fn has_references(&self, template: &str) -> bool {
+        parse_secret_references(template)
+            .iter()
+            .any(|r| self.handles(&r.scheme))
+    }
+
+    /// Substitute every secret reference in `template`.
+    pub async fn resolve_str(&self, template: &str) -> Result<String, SecretError> {
+        let references = parse_secret_references(template);
+        if references.is_empty() {
+            return Ok(template.to_string());
+        }
+
+        let mut result = String::with_capacity(template.len());
+        let mut last = 0;
+        for r in references {
+            if !self.handles(&r.scheme) {
+                continue;
+            }
+            let resolver =
+                self.resolvers
+                    .get(&r.scheme)
+                    .ok_or_else(|| SecretError::NotConfigured {
+                        scheme: r.scheme.clone(),
+                    })?;
+            debug!("[SecretResolver] Resolving {}:{}", r.scheme, r.reference);
+            let value = resolver.resolve(&r.reference).await?;
+            result.push_str(&template[last..r.span.start]);
+            result.push_str(&value);
+            last = r.span.end;
+        }
+        result.push_str(&template[last..]);
+        Ok(result)
+    }
+
+    /// Substitute references in every value of a map (keys are left as-is).
+    pub async fn resolve_map(&self, map: &mut HashMap<String, String>) -> Result<(), SecretError> {
+        for value in map.values_mut() {
+            if self.has_references(value) {
+                *value = self.resolve_str(value).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Substitute references in a list of strings.
+    pub async fn resolve_all(&self, values: &mut [String]) -> Result<(), SecretError> {
+        for value in values.iter_mut() {
+            if self.has_references(value) {
+                *value = self.resolve_str(value).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SecretResolverRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretResolverRegistry")
+            .field("schemes", &self.schemes())
+            .finish()
+    }
+}
+
+/// `${env:VAR}` — reads the McpMux process environment.
+pub struct EnvSecretResolver;
+
+#[async_trait]
+impl SecretResolver for EnvSecretResolver {
+    fn scheme(&self) -> &str {
+        "env"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        std::env::var(reference).map_err(|_| SecretError::NotFound {
+            scheme: "env".to_string(),
+            reference: reference.to_string(),
+        })
+    }
+}
+
+fn backend_error(scheme: &str, message: impl std::fmt::Display) -> SecretError {
+    SecretError::Backend {
+        scheme: scheme.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default()
+}
+
+/// `${vault:path#field}` — HashiCorp Vault KV secrets.
+///
+/// `path` is the full API path below `/v1/` (e.g. `secret/data/github` for
+/// KV v2). `#field` may be omitted when the secret holds a single key.
+pub struct VaultSecretResolver {
+    addr: String,
+    token: String,
+    namespace: Option<String>,
+    http_client: reqwest::Client,
+}
+
+impl VaultSecretResolver {
+    pub fn new(addr: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            namespace: None,
+            http_client: http_client(),
+        }
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Configure from `VAULT_ADDR` / `VAULT_TOKEN` / `VAULT_NAMESPACE`.
+    pub fn from_env() -> Option<Self> {
+        let addr = std::env::var("VAULT_ADDR").ok().filter(|v| !v.is_empty())?;
+        let token = std::env::var("VAULT_TOKEN")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        let resolver = Self::new(addr, token);
+        Some(match std::env::var("VAULT_NAMESPACE") {
+            Ok(ns) if !ns.is_empty() => resolver.with_namespace(ns),
+            _ => resolver,
+        })
+    }
+}
+
+/// Pick a field out of a Vault read response (`data.data` for KV v2,
+/// `data` for KV v1).
+fn extract_vault_field(body: &Value, field: Option<&str>) -> Option<String> {
+    let data = body.get("data")?;
+    let data = match data.get("data") {
+        Some(inner) if inner.is_object() && data.get("metadata").is_some() => inner,
+        _ => data,
+    };
+    let map = data.as_object()?;
+    let value = match field {
+        Some(field) => map.get(field)?,
+        None if map.len() == 1 => map.values().next()?,
+        None => return None,
+    };
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+#[async_trait]
+impl SecretResolver for VaultSecretResolver {
+    fn scheme(&self) -> &str {
+        "vault"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        let (path, field) = match reference.split_once('#') {
+            Some((path, field)) => (path, Some(field)),
+            None => (reference, None),
+        };
+        let path = path.trim_matches('/');
+        if path.is_empty() {
+            return Err(SecretError::InvalidReference {
+                scheme: "vault".to_string(),
+                reference: reference.to_string(),
+                reason: "missing secret path".to_string(),
+            });
+        }
+
+        let mut request = self
+            .http_client
+            .get(format!("{}/v1/{}", self.addr, path))
+            .header("X-Vault-Token", &self.token);
+        if let Some(ns) = &self.namespace {
+            request = request.header("X-Vault-Namespace", ns);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| backend_error("vault", e))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(SecretError::NotFound {
+                scheme: "vault".to_string(),
+                reference: reference.to_string(),
+            });
+        }
+        if !response.status().is_success() {
+            return Err(backend_error(
+                "vault",
+                format!("HTTP {}", response.status()),
+            ));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| backend_error("vault", e))?;
+        extract_vault_field(&body, field).ok_or_else(|| SecretError::NotFound {
+            scheme: "vault".to_string(),
+            reference: reference.to_string(),
+        })
+    }
+}
+
+/// `${op:vault/item/field}` — 1Password Connect server.
+///
+/// Vault and item may be given by title or by ID; `field` matches a field
+/// label or ID.
+pub struct OnePasswordConnectResolver {
+    host: String,
+    token: String,
+    http_client: reqwest::Client,
+}
+
+impl OnePasswordConnectResolver {
+    pub fn new(host: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            host: host.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            http_client: http_client(),
+        }
+    }
+
+    /// Configure from `OP_CONNECT_HOST` / `OP_CONNECT_TOKEN`.
+    pub fn from_env() -> Option<Self> {
+        let host = std::env::var("OP_CONNECT_HOST")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        let token = std::env::var("OP_CONNECT_TOKEN")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        Some(Self::new(host, token))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value, SecretError> {
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| backend_error("op", e))?;
+        if !response.status().is_success() {
+            return Err(backend_error("op", format!("HTTP {}", response.status())));
+        }
+        response.json().await.map_err(|e| backend_error("op", e))
+    }
+
+    /// Look up an ID by title via a Connect list endpoint; fall back to
+    /// treating the name as an ID.
+    async fn lookup_id(&self, url: &str, attr: &str, name: &str) -> Result<String, SecretError> {
+        let filter = format!("{} eq \"{}\"", attr, name.replace('"', "\\\""));
+        let list = self
+            .get_json(&format!("{}?filter={}", url, urlencoding::encode(&filter)))
+            .await?;
+        Ok(list
+            .as_array()
+            .and_then(|items| items.first())
+            .and_then(|item| item.get("id"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| name.to_string()))
+    }
+}
+
+/// Find a field value by label or ID in a Connect item.
+fn extract_op_field(item: &Value, field: &str) -> Option<String> {
+    item.get("fields")?
+        .as_array()?
+        .iter()
+        .find(|f| {
+            f.get("label").and_then(Value::as_str) == Some(field)
+                || f.get("id").and_then(Value::as_str) == Some(field)
+        })?
+        .get("value")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[async_trait]
+impl SecretResolver for OnePasswordConnectResolver {
+    fn scheme(&self) -> &str {
+        "op"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+        let parts: Vec<&str> = reference.trim_matches('/').splitn(3, '/').collect();
+        let [vault, item, field] = parts[..] else {
+            return Err(SecretError::InvalidReference {
+                scheme: "op".to_string(),
+                reference: reference.to_string(),
+                reason: "expected vault/item/field".to_string(),
+            });
+        };
+
+        let vault_id = self
+            .lookup_id(&format!("{}/v1/vaults", self.host), "name", vault)
+            .await?;
+        let items_url = format!("{}/v1/vaults/{}/items", self.host, vault_id);
+        let item_id = self.lookup_id(&items_url, "title", item).await?;
+        let item = self.get_json(&format!("{}/{}", items_url, item_id)).await?;
+
+        extract_op_field(&item, field).ok_or_else(|| SecretError::NotFound {
+            scheme: "op".to_string(),
+            reference: reference.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct StaticResolver;
+
+    #[async_trait]
+    impl SecretResolver for StaticResolver {
+        fn scheme(&self) -> &str {
+            "keychain"
+        }
+
+        async fn resolve(&self, reference: &str) -> Result<String, SecretError> {
+            match reference {
+                "github" => Ok("ghp_secret".to_string()),
+                _ => Err(SecretError::NotFound {
+                    scheme: "keychain".to_string(),
+                    reference: reference.to_string(),
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn parses_references() {
+        let refs = parse_secret_references("Bearer ${keychain:github} and ${input:X} ${bad}");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].scheme, "keychain");
+        assert_eq!(refs[0].reference, "github");
+        assert_eq!(refs[1].scheme, "input");
+    }
+
+    #[tokio::test]
+    async fn resolves_registered_schemes_and_keeps_others() {
+        let registry = SecretResolverRegistry::new().with_resolver(Arc::new(StaticResolver));
+        let out = registry
+            .resolve_str("token=${keychain:github};id=${input:ID}")
+            .await
+            .unwrap();
+        assert_eq!(out, "token=ghp_secret;id=${input:ID}");
+    }
+
+    #[tokio::test]
+    async fn unconfigured_secret_scheme_is_an_error() {
+        let registry = SecretResolverRegistry::new();
+        let err = registry.resolve_str("${vault:secret/x}").await.unwrap_err();
+        assert_eq!(
+            err,
+            SecretError::NotConfigured {
+                scheme: "vault".to_string()
+            }
+        );
+        assert!(!registry.has_references("${input:ID}"));
+    }
+
+    #[tokio::test]
+    async fn missing_secret_error_does_not_leak_values() {
+        let registry = SecretResolverRegistry::new().with_resolver(Arc::new(StaticResolver));
+        let mut env = HashMap::from([("TOKEN".to_string(), "${keychain:nope}".to_string())]);
+        let err = registry.resolve_map(&mut env).await.unwrap_err();
+        assert_eq!(err.to_string(), "Secret 'keychain:nope' was not found");
+    }
+
+    #[tokio::test]
+    async fn env_resolver_reads_process_env() {
+        let registry = SecretResolverRegistry::new().with_resolver(Arc::new(EnvSecretResolver));
+        let mut args = vec!["--home=${env:PATH}".to_string()];
+        registry.resolve_all(&mut args).await.unwrap();
+        assert_eq!(
+            args[0],
+            format!("--home={}", std::env::var("PATH").unwrap())
+        );
+    }
+
+    #[test]
+    fn extracts_vault_kv_fields() {
+        let v2 = json!({ "data": { "data": { "token": "abc" }, "metadata": { "version": 3 } } });
+        assert_eq!(
+            extract_vault_field(&v2, Some("token")).as_deref(),
+            Some("abc")
+        );
+        assert_eq!(extract_vault_field(&v2, None).as_deref(), Some("abc"));
+
+        let v1 = json!({ "data": { "user": "u", "pass": "p" } });
+        assert_eq!(extract_vault_field(&v1, Some("pass")).as_deref(), Some("p"));
+        assert_eq!(
+            extract_vault_field(&v1, None),
+            None,
+            "Ambiguous without #field"
+        );
+    }
+
+    #[test]
+    fn extracts_op_fields_by_label_or_id() {
+        let item = json!({
+            "fields": [
+                { "id": "username", "label": "username", "value": "me" },
+                { "id": "abc123", "label": "api key", "value": "sk-1" }
+            ]
+        });
+        assert_eq!(extract_op_field(&item, "api key").as_deref(), Some("sk-1"));
+        assert_eq!(extract_op_field(&item, "abc123").as_deref(), Some("sk-1"));
+        assert_eq!(extract_op_field(&item, "missing"), None);
+    }
+}
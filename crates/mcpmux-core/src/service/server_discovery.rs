@@ -3,24 +3,119 @@
 //! This service uses the bundle-only strategy (see ADR-001).
 //! All filtering and searching is done client-side against cached data.
 //!
-//! Offline support: The bundle is cached to disk after successful fetch,
-//! and loaded from disk when the API is unreachable.
+//! Several registries can be configured (see [`RegistrySource`]). Their
+//! bundles are merged by priority: when two registries define the same
+//! server ID, the higher-priority one wins, and a namespaced registry lists
+//! its servers as `<namespace>/<id>`. User spaces override everything.
+//! Private registries get their credentials from the
+//! [`RegistryCredentialService`]. A local registry (`file://` URL) is read
+//! from a folder of server definition JSON files instead of an API.
+//!
+//! Signed entries are checked against the trusted publisher keys before they
+//! are served (see [`EntrySignature`]): an entry whose signature doesn't
+//! match was tampered with and is dropped, and a registry that requires
+//! signatures only serves entries from trusted publishers.
+//!
+//! Offline support: Each bundle is cached to disk after successful fetch as
+//! a snapshot. [`ServerDiscoveryService::load_snapshot`] serves discovery and
+//! search from the snapshots right away at startup, and a registry whose API
+//! is unreachable keeps being served from its snapshot. Refreshes are
+//! conditional (`If-None-Match` / `If-Modified-Since`), so an unchanged
+//! bundle costs a 304 instead of a download.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
-use crate::domain::{ServerDefinition, ServerSource, UserSpaceConfig};
+#[cfg(doc)]
+use crate::domain::EntrySignature;
+use crate::domain::{utility_server_definition, UTILITY_SERVER_ID};
+use crate::domain::{
+    validate_publisher_keys, validate_registry_sources, verify_definition, PublisherKey,
+    RegistryAuth, RegistryFormat, RegistrySource, ServerDefinition, ServerSource, SignatureStatus,
+    UserSpaceConfig, PUBLIC_REGISTRY_ID,
+};
 use crate::service::app_settings_service::{keys, AppSettingsService};
+use crate::service::catalog_import::{Catalog, CatalogImportClient};
+use crate::service::official_registry_client::OfficialRegistryClient;
 use crate::service::registry_api_client::{
-    FetchBundleResult, HomeConfig, RegistryApiClient, RegistryBundle, UiConfig,
+    BundleValidators, FetchBundleResult, HomeConfig, RegistryApiClient, RegistryBundle, UiConfig,
 };
+use crate::service::registry_credential_service::RegistryCredentialService;
 
 const BUNDLE_CACHE_FILENAME: &str = "registry-bundle.json";
 
+/// A file of a local registry: one server definition, or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocalDefinitionFile {
+    One(Box<ServerDefinition>),
+    Many(Vec<ServerDefinition>),
+}
+
+/// Read a local registry: every `*.json` file under `dir`, skipping hidden
+/// folders such as `.git`. Files that don't parse are skipped with a warning.
+async fn load_local_bundle(dir: &Path) -> anyhow::Result<RegistryBundle> {
+    if !dir.is_dir() {
+        anyhow::bail!("Local registry folder not found: {}", dir.display());
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if entry.file_type().await?.is_dir() {
+                if !hidden {
+                    pending.push(path);
+                }
+            } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    let mut servers = Vec::new();
+    for path in files {
+        let parsed = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(serde_json::from_str::<LocalDefinitionFile>(&content)?));
+        match parsed {
+            Ok(LocalDefinitionFile::One(definition)) => servers.push(*definition),
+            Ok(LocalDefinitionFile::Many(definitions)) => servers.extend(definitions),
+            Err(e) => warn!("Skipping local registry file {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(RegistryBundle {
+        version: "local".to_string(),
+        updated_at: Utc::now().to_rfc3339(),
+        servers,
+        categories: vec![],
+        ui: default_ui_config(),
+        home: None,
+    })
+}
+
+/// Whether the registries are reachable, and how current the served data is
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrySyncStatus {
+    /// Some registry is served from its disk snapshot
+    pub offline: bool,
+    /// When the oldest served bundle was last confirmed current by its API
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
 /// Default UI config used when no bundle is available
 fn default_ui_config() -> UiConfig {
     UiConfig {
@@ -31,6 +126,78 @@ fn default_ui_config() -> UiConfig {
     }
 }
 
+/// Merge registry bundles into one server map.
+///
+/// `bundles` must be ordered highest priority first; on an ID conflict the
+/// first registry wins. Each server's ID is namespaced and its source set to
+/// the registry it came from.
+pub fn merge_registry_bundles(
+    bundles: &[(RegistrySource, Vec<ServerDefinition>)],
+) -> HashMap<String, ServerDefinition> {
+    let mut merged = HashMap::new();
+    for (source, servers) in bundles {
+        for server in servers {
+            let id = source.server_id(&server.id);
+            if merged.contains_key(&id) {
+                info!(
+                    "Registry '{}' server {} shadowed by a higher-priority registry",
+                    source.id, id
+                );
+                continue;
+            }
+            let mut server = server.clone();
+            server.id = id.clone();
+            server.source = ServerSource::Registry {
+                url: source.url.clone(),
+                name: source.name.clone(),
+                trust: source.trust,
+            };
+            merged.insert(id, server);
+        }
+    }
+    merged
+}
+
+/// Order registries highest priority first, keeping the configured order
+/// between equal priorities
+/// Check the signatures of a registry's servers and record the outcome on
+/// each. Tampered entries are dropped, and so are entries without a trusted
+/// signature if the registry requires signatures.
+pub fn verify_registry_servers(
+    source: &RegistrySource,
+    servers: &[ServerDefinition],
+    keys: &[PublisherKey],
+) -> Vec<ServerDefinition> {
+    servers
+        .iter()
+        .filter_map(|server| {
+            let status = verify_definition(server, keys);
+            if status == SignatureStatus::Invalid {
+                warn!(
+                    "Dropping '{}' from registry '{}': its signature doesn't match (tampered?)",
+                    server.id, source.id
+                );
+                return None;
+            }
+            if source.require_signatures && status != SignatureStatus::Verified {
+                warn!(
+                    "Dropping '{}' from registry '{}': not signed by a trusted publisher ({:?})",
+                    server.id, source.id, status
+                );
+                return None;
+            }
+            let mut server = server.clone();
+            server.signature_status = status;
+            Some(server)
+        })
+        .collect()
+}
+
+fn by_priority(mut sources: Vec<RegistrySource>) -> Vec<RegistrySource> {
+    sources.sort_by_key(|s| std::cmp::Reverse(s.priority));
+    sources
+}
+
 pub struct ServerDiscoveryService {
     /// In-memory cache of all discovered servers, keyed by ID.
     servers: Arc<RwLock<HashMap<String, ServerDefinition>>>,
@@ -38,20 +205,26 @@ pub struct ServerDiscoveryService {
     spaces_dir: PathBuf,
     /// Path to app data directory (e.g. %LOCALAPPDATA%/mcpmux)
     data_dir: PathBuf,
-    /// HTTP client for fetching from Registry API
-    registry_client: Option<RegistryApiClient>,
+    /// Registry used when none are configured in settings
+    default_registry: Option<RegistrySource>,
     /// App settings service for persistent storage
     settings_service: Option<Arc<AppSettingsService>>,
+    /// Credentials of private registries
+    registry_credentials: Option<Arc<RegistryCredentialService>>,
     /// Last refresh timestamp
     last_refresh: Arc<RwLock<Option<Instant>>>,
     /// Cached UI configuration from bundle
     ui_config: Arc<RwLock<UiConfig>>,
     /// Cached home configuration from bundle
     home_config: Arc<RwLock<Option<HomeConfig>>>,
-    /// Whether currently running from disk cache (offline mode)
+    /// Whether any registry is served from disk cache (offline mode)
     is_offline: Arc<RwLock<bool>>,
-    /// Cached ETag from last successful API fetch (in-memory cache)
-    cached_etag: Arc<RwLock<Option<String>>>,
+    /// Cache validators from the last successful API fetches, by registry ID
+    cached_validators: Arc<RwLock<HashMap<String, BundleValidators>>>,
+    /// When each served bundle was last confirmed current, by registry ID
+    synced_at: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    /// Last loaded bundle of each registry, by registry ID
+    bundles: Arc<RwLock<HashMap<String, RegistryBundle>>>,
 }
 
 impl ServerDiscoveryService {
@@ -64,28 +237,41 @@ impl ServerDiscoveryService {
             servers: Arc::new(RwLock::new(HashMap::new())),
             spaces_dir,
             data_dir,
-            registry_client: None,
+            default_registry: None,
             settings_service: None,
+            registry_credentials: None,
             last_refresh: Arc::new(RwLock::new(None)),
             ui_config: Arc::new(RwLock::new(default_ui_config())),
             home_config: Arc::new(RwLock::new(None)),
             is_offline: Arc::new(RwLock::new(false)),
-            cached_etag: Arc::new(RwLock::new(None)),
+            cached_validators: Arc::new(RwLock::new(HashMap::new())),
+            synced_at: Arc::new(RwLock::new(HashMap::new())),
+            bundles: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    /// Create with Registry API client enabled
+    /// Create with the public Registry API as the default registry
     pub fn with_registry_api(mut self, base_url: String) -> Self {
-        self.registry_client = Some(RegistryApiClient::new(base_url));
+        self.default_registry = Some(RegistrySource::public(base_url));
         self
     }
 
-    /// Create with App Settings service for persistent ETag storage
+    /// Create with App Settings service for persistent cache validators and
+    /// configured registries
     pub fn with_settings_service(mut self, settings: Arc<AppSettingsService>) -> Self {
         self.settings_service = Some(settings);
         self
     }
 
+    /// Create with the credential service of private registries
+    pub fn with_registry_credentials(
+        mut self,
+        credentials: Arc<RegistryCredentialService>,
+    ) -> Self {
+        self.registry_credentials = Some(credentials);
+        self
+    }
+
     /// Check if cache should be refreshed (> 5 minutes old)
     pub async fn should_refresh(&self) -> bool {
         let last = self.last_refresh.read().await;
@@ -100,24 +286,139 @@ impl ServerDiscoveryService {
         *self.is_offline.read().await
     }
 
+    /// Offline state and age of the served registry data
+    pub async fn sync_status(&self) -> RegistrySyncStatus {
+        RegistrySyncStatus {
+            offline: *self.is_offline.read().await,
+            last_synced_at: self.synced_at.read().await.values().min().copied(),
+        }
+    }
+
+    // ============================================
+    // Registry Configuration
+    // ============================================
+
+    /// The registries servers are loaded from, highest priority first.
+    ///
+    /// Configured registries replace the default one entirely.
+    pub async fn registry_sources(&self) -> Vec<RegistrySource> {
+        let configured = match self.settings_service {
+            Some(ref settings) => {
+                settings
+                    .get_typed::<Vec<RegistrySource>>(keys::registry::SOURCES)
+                    .await
+            }
+            None => None,
+        };
+        by_priority(configured.unwrap_or_else(|| self.default_registry.iter().cloned().collect()))
+    }
+
+    /// Replace the configured registries and reload from them.
+    ///
+    /// An empty list goes back to the default registry. Credentials of
+    /// registries no longer configured are removed.
+    pub async fn set_registry_sources(&self, sources: Vec<RegistrySource>) -> anyhow::Result<()> {
+        validate_registry_sources(&sources).map_err(anyhow::Error::msg)?;
+        let settings = self
+            .settings_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Registry sources need the settings service"))?;
+
+        if let Some(ref credentials) = self.registry_credentials {
+            let removed: Vec<RegistrySource> = self
+                .registry_sources()
+                .await
+                .into_iter()
+                .filter(|old| !sources.iter().any(|s| s.id == old.id))
+                .collect();
+            for old in removed {
+                credentials.set(&old.id, None).await?;
+            }
+        }
+
+        if sources.is_empty() {
+            settings.delete(keys::registry::SOURCES).await?;
+        } else {
+            settings
+                .set_typed(keys::registry::SOURCES, &sources)
+                .await?;
+        }
+        info!("Configured {} registry sources", sources.len());
+
+        self.refresh().await
+    }
+
+    /// The trusted publisher keys
+    pub async fn publisher_keys(&self) -> Vec<PublisherKey> {
+        match self.settings_service {
+            Some(ref settings) => settings
+                .get_typed::<Vec<PublisherKey>>(keys::registry::PUBLISHER_KEYS)
+                .await
+                .unwrap_or_default(),
+            None => vec![],
+        }
+    }
+
+    /// Replace the trusted publisher keys and reload, so every entry is
+    /// checked against the new keys
+    pub async fn set_publisher_keys(
+        &self,
+        publisher_keys: Vec<PublisherKey>,
+    ) -> anyhow::Result<()> {
+        validate_publisher_keys(&publisher_keys).map_err(anyhow::Error::msg)?;
+        let settings = self
+            .settings_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Publisher keys need the settings service"))?;
+
+        if publisher_keys.is_empty() {
+            settings.delete(keys::registry::PUBLISHER_KEYS).await?;
+        } else {
+            settings
+                .set_typed(keys::registry::PUBLISHER_KEYS, &publisher_keys)
+                .await?;
+        }
+        info!("Configured {} publisher keys", publisher_keys.len());
+
+        self.refresh().await
+    }
+
+    /// Folders of the configured local registries
+    pub async fn local_registry_dirs(&self) -> Vec<PathBuf> {
+        self.registry_sources()
+            .await
+            .iter()
+            .filter_map(RegistrySource::local_dir)
+            .collect()
+    }
+
     // ============================================
     // Bundle Disk Cache
     // ============================================
 
-    /// Get the path to the cached bundle file
-    fn bundle_cache_path(&self) -> PathBuf {
-        self.data_dir.join("cache").join(BUNDLE_CACHE_FILENAME)
+    /// Get the path to a registry's cached bundle file
+    fn bundle_cache_path(&self, registry_id: &str) -> PathBuf {
+        let file_name = if registry_id == PUBLIC_REGISTRY_ID {
+            BUNDLE_CACHE_FILENAME.to_string()
+        } else {
+            format!("registry-bundle-{}.json", registry_id)
+        };
+        self.data_dir.join("cache").join(file_name)
     }
 
     /// Save bundle to disk for offline use
-    async fn save_bundle_to_disk(&self, bundle: &RegistryBundle) -> anyhow::Result<()> {
+    async fn save_bundle_to_disk(
+        &self,
+        registry_id: &str,
+        bundle: &RegistryBundle,
+    ) -> anyhow::Result<()> {
         // Ensure cache directory exists
         let cache_dir = self.data_dir.join("cache");
         if !cache_dir.exists() {
             tokio::fs::create_dir_all(&cache_dir).await?;
         }
 
-        let path = self.bundle_cache_path();
+        let path = self.bundle_cache_path(registry_id);
         let json = serde_json::to_string_pretty(bundle)?;
         tokio::fs::write(&path, json).await?;
 
@@ -126,8 +427,8 @@ impl ServerDiscoveryService {
     }
 
     /// Load bundle from disk cache
-    async fn load_bundle_from_disk(&self) -> Option<RegistryBundle> {
-        let path = self.bundle_cache_path();
+    async fn load_bundle_from_disk(&self, registry_id: &str) -> Option<RegistryBundle> {
+        let path = self.bundle_cache_path(registry_id);
 
         if !path.exists() {
             return None;
@@ -156,25 +457,80 @@ impl ServerDiscoveryService {
         }
     }
 
+    /// When a registry's cached bundle was last written or confirmed current
+    async fn bundle_cache_synced_at(&self, registry_id: &str) -> Option<DateTime<Utc>> {
+        let metadata = tokio::fs::metadata(self.bundle_cache_path(registry_id))
+            .await
+            .ok()?;
+        metadata.modified().ok().map(DateTime::<Utc>::from)
+    }
+
+    /// Mark a registry's cached bundle as confirmed current (after a 304)
+    async fn touch_bundle_cache(&self, registry_id: &str) {
+        let path = self.bundle_cache_path(registry_id);
+        let touched = match tokio::fs::OpenOptions::new().write(true).open(&path).await {
+            Ok(file) => file.into_std().await.set_modified(SystemTime::now()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = touched {
+            warn!("Failed to update cached bundle time: {}", e);
+        }
+    }
+
     // ============================================
-    // ETag Storage (via AppSettings)
+    // Cache Validator Storage (via AppSettings)
     // ============================================
 
-    /// Save ETag to persistent storage
-    async fn save_etag(&self, etag: &str) {
-        if let Some(ref settings) = self.settings_service {
-            if let Err(e) = settings.set_string(keys::registry::BUNDLE_ETAG, etag).await {
-                warn!("Failed to save ETag to settings: {}", e);
+    /// Settings key of one of a registry's cache validators
+    fn validator_key(base: &str, registry_id: &str) -> String {
+        if registry_id == PUBLIC_REGISTRY_ID {
+            base.to_string()
+        } else {
+            format!("{}.{}", base, registry_id)
+        }
+    }
+
+    /// Save cache validators to persistent storage
+    async fn save_validators(&self, registry_id: &str, validators: &BundleValidators) {
+        let Some(ref settings) = self.settings_service else {
+            return;
+        };
+        for (base, value) in [
+            (keys::registry::BUNDLE_ETAG, &validators.etag),
+            (
+                keys::registry::BUNDLE_LAST_MODIFIED,
+                &validators.last_modified,
+            ),
+        ] {
+            let key = Self::validator_key(base, registry_id);
+            let result = match value {
+                Some(value) => settings.set_string(&key, value).await,
+                None => settings.delete(&key).await,
+            };
+            if let Err(e) = result {
+                warn!("Failed to save cache validator to settings: {}", e);
             }
         }
     }
 
-    /// Load ETag from persistent storage
-    async fn load_etag(&self) -> Option<String> {
-        if let Some(ref settings) = self.settings_service {
-            settings.get_string(keys::registry::BUNDLE_ETAG).await
-        } else {
-            None
+    /// Load cache validators from persistent storage
+    async fn load_validators(&self, registry_id: &str) -> BundleValidators {
+        let Some(ref settings) = self.settings_service else {
+            return BundleValidators::default();
+        };
+        BundleValidators {
+            etag: settings
+                .get_string(&Self::validator_key(
+                    keys::registry::BUNDLE_ETAG,
+                    registry_id,
+                ))
+                .await,
+            last_modified: settings
+                .get_string(&Self::validator_key(
+                    keys::registry::BUNDLE_LAST_MODIFIED,
+                    registry_id,
+                ))
+                .await,
         }
     }
 
@@ -182,168 +538,359 @@ impl ServerDiscoveryService {
     // Refresh Logic
     // ============================================
 
-    /// Initialize the service by loading from Registry API (with disk cache fallback) and user spaces.
+    /// Load one registry's bundle: from its API, or from the disk cache when
+    /// the API is unreachable. Returns the bundle and whether it came from
+    /// the offline fallback.
     ///
-    /// Uses ETag-based conditional fetching to avoid re-downloading unchanged bundles.
-    pub async fn refresh(&self) -> anyhow::Result<()> {
-        let mut merged_servers = HashMap::new();
-        let mut offline_mode = false;
+    /// Uses conditional fetching to avoid re-downloading unchanged bundles.
+    /// A local registry is read from its folder.
+    async fn load_registry_bundle(
+        &self,
+        source: &RegistrySource,
+    ) -> (Option<RegistryBundle>, bool) {
+        if let Some(dir) = source.local_dir() {
+            return match load_local_bundle(&dir).await {
+                Ok(bundle) => {
+                    info!(
+                        "Loaded {} servers from local registry '{}'",
+                        bundle.servers.len(),
+                        source.id
+                    );
+                    (Some(bundle), false)
+                }
+                Err(e) => {
+                    warn!("Failed to load local registry '{}': {}", source.id, e);
+                    (None, false)
+                }
+            };
+        }
+        match source.format {
+            RegistryFormat::McpMux => {}
+            RegistryFormat::Official => return self.load_catalog_bundle(source, None).await,
+            RegistryFormat::Smithery => {
+                return self
+                    .load_catalog_bundle(source, Some(Catalog::Smithery))
+                    .await
+            }
+            RegistryFormat::McpRun => {
+                return self
+                    .load_catalog_bundle(source, Some(Catalog::McpRun))
+                    .await
+            }
+        }
 
-        // Get current ETag (from memory, or load from settings on first run)
-        // IMPORTANT: Only use ETag if cache file exists, otherwise force fresh fetch
-        let cache_file_exists = self.bundle_cache_path().exists();
-        let current_etag = if cache_file_exists {
-            let etag = self.cached_etag.read().await;
-            if etag.is_some() {
-                etag.clone()
-            } else {
-                drop(etag);
-                // Try loading from settings
-                let disk_etag = self.load_etag().await;
-                if let Some(ref e) = disk_etag {
-                    let mut etag_lock = self.cached_etag.write().await;
-                    *etag_lock = Some(e.clone());
+        // Get current validators (from memory, or load from settings on first run)
+        // IMPORTANT: Only send validators if cache file exists, otherwise force fresh fetch
+        let cache_file_exists = self.bundle_cache_path(&source.id).exists();
+        let cached = if cache_file_exists {
+            let in_memory = self.cached_validators.read().await.get(&source.id).cloned();
+            match in_memory {
+                Some(validators) => validators,
+                None => {
+                    // Try loading from settings
+                    let stored = self.load_validators(&source.id).await;
+                    if !stored.is_empty() {
+                        self.cached_validators
+                            .write()
+                            .await
+                            .insert(source.id.clone(), stored.clone());
+                    }
+                    stored
                 }
-                disk_etag
             }
         } else {
-            // No cache file - don't send ETag (force fresh fetch)
-            info!("Cache file missing, forcing fresh fetch (ignoring stored ETag)");
-            None
+            // No cache file - don't send validators (force fresh fetch)
+            info!(
+                "Cache file missing for registry '{}', forcing fresh fetch (ignoring stored validators)",
+                source.id
+            );
+            BundleValidators::default()
         };
 
-        // 1. Try to load from Registry API first
-        let bundle_result = if let Some(ref client) = self.registry_client {
-            match client.fetch_bundle(current_etag.as_deref()).await {
-                Ok(FetchBundleResult::NotModified) => {
-                    // Bundle unchanged - but we still need to ensure memory is populated
-                    info!("Registry bundle unchanged (304 Not Modified)");
-
-                    // Check if in-memory cache is empty (e.g., after app restart)
-                    let memory_empty = {
-                        let servers = self.servers.read().await;
-                        servers.is_empty()
-                    };
-
-                    if memory_empty {
-                        // Load from disk cache to populate memory
-                        info!("Memory empty, loading bundle from disk cache");
-                        if let Some(cached_bundle) = self.load_bundle_from_disk().await {
-                            // Use the cached bundle (don't return early)
-                            Some(cached_bundle)
-                        } else {
-                            warn!("No disk cache available despite 304 response");
-                            None
-                        }
-                    } else {
-                        // Memory already has data, just update timestamp
-                        let mut last_refresh = self.last_refresh.write().await;
-                        *last_refresh = Some(Instant::now());
-
-                        // Still need to reload user spaces in case they changed
-                        self.reload_user_spaces().await;
-
-                        return Ok(());
-                    }
+        let mut client = RegistryApiClient::new(source.url.clone());
+        if let Some(auth) = self.registry_auth(source).await {
+            client = client.with_auth(auth);
+        }
+        match client.fetch_bundle(&cached).await {
+            Ok(FetchBundleResult::NotModified) => {
+                // Bundle unchanged - reuse the one in memory, or the disk
+                // cache after an app restart
+                info!(
+                    "Registry '{}' bundle unchanged (304 Not Modified)",
+                    source.id
+                );
+                self.touch_bundle_cache(&source.id).await;
+                if let Some(bundle) = self.bundles.read().await.get(&source.id) {
+                    return (Some(bundle.clone()), false);
+                }
+                let cached = self.load_bundle_from_disk(&source.id).await;
+                if cached.is_none() {
+                    warn!("No disk cache available despite 304 response");
+                }
+                (cached, false)
+            }
+            Ok(FetchBundleResult::Updated { bundle, validators }) => {
+                let bundle = *bundle; // Unbox the bundle
+                info!(
+                    "Loaded {} servers from registry '{}' (v{}, updated {})",
+                    bundle.servers.len(),
+                    source.id,
+                    bundle.version,
+                    bundle.updated_at
+                );
+
+                // Save bundle to disk for offline use
+                if let Err(e) = self.save_bundle_to_disk(&source.id, &bundle).await {
+                    warn!("Failed to cache bundle to disk: {}", e);
                 }
-                Ok(FetchBundleResult::Updated { bundle, etag }) => {
-                    let bundle = *bundle; // Unbox the bundle
-                    info!(
-                        "Loaded {} servers from Registry API (v{}, updated {})",
-                        bundle.servers.len(),
-                        bundle.version,
-                        bundle.updated_at
-                    );
 
-                    // Save bundle to disk for offline use
-                    if let Err(e) = self.save_bundle_to_disk(&bundle).await {
-                        warn!("Failed to cache bundle to disk: {}", e);
-                    }
+                // Save validators to memory and disk
+                self.save_validators(&source.id, &validators).await;
+                self.cached_validators
+                    .write()
+                    .await
+                    .insert(source.id.clone(), validators);
 
-                    // Save ETag to memory and disk
-                    if let Some(ref e) = etag {
-                        let mut etag_lock = self.cached_etag.write().await;
-                        *etag_lock = Some(e.clone());
-                        self.save_etag(e).await;
-                    }
+                (Some(bundle), false)
+            }
+            Err(e) => self.load_offline_bundle(source, e).await,
+        }
+    }
 
-                    Some(bundle)
+    /// Fetch a registry that doesn't serve McpMux bundles: a third-party
+    /// `catalog`, or the official MCP registry API if `None`. Its servers are
+    /// wrapped in a bundle for caching and merging.
+    async fn load_catalog_bundle(
+        &self,
+        source: &RegistrySource,
+        catalog: Option<Catalog>,
+    ) -> (Option<RegistryBundle>, bool) {
+        let auth = self.registry_auth(source).await;
+        let fetched = match catalog {
+            None => {
+                let mut client = OfficialRegistryClient::new(source.url.clone());
+                if let Some(auth) = auth {
+                    client = client.with_auth(auth);
                 }
-                Err(e) => {
-                    warn!(
-                        "Failed to fetch from Registry API: {}. Trying disk cache...",
-                        e
-                    );
-
-                    // Try loading from disk cache
-                    if let Some(cached_bundle) = self.load_bundle_from_disk().await {
-                        info!("Using cached bundle from disk (offline mode)");
-                        offline_mode = true;
-                        Some(cached_bundle)
-                    } else {
-                        warn!("No disk cache available. Running offline with no registry servers.");
-                        offline_mode = true;
-                        None
-                    }
+                client.fetch_servers().await
+            }
+            Some(catalog) => {
+                let mut client = CatalogImportClient::new(catalog, source.url.clone());
+                if let Some(auth) = auth {
+                    client = client.with_auth(auth);
                 }
+                client.fetch_servers().await
             }
+        };
+        match fetched {
+            Ok(servers) => {
+                info!(
+                    "Loaded {} servers from registry '{}'",
+                    servers.len(),
+                    source.id
+                );
+                let bundle = RegistryBundle {
+                    version: catalog.map_or("official", |c| c.label()).to_string(),
+                    updated_at: Utc::now().to_rfc3339(),
+                    servers,
+                    categories: vec![],
+                    ui: default_ui_config(),
+                    home: None,
+                };
+                if let Err(e) = self.save_bundle_to_disk(&source.id, &bundle).await {
+                    warn!("Failed to cache bundle to disk: {}", e);
+                }
+                (Some(bundle), false)
+            }
+            Err(e) => self.load_offline_bundle(source, e).await,
+        }
+    }
+
+    /// The disk cache of a registry that couldn't be fetched (offline mode)
+    async fn load_offline_bundle(
+        &self,
+        source: &RegistrySource,
+        error: anyhow::Error,
+    ) -> (Option<RegistryBundle>, bool) {
+        warn!(
+            "Failed to fetch from registry '{}': {}. Trying disk cache...",
+            source.id, error
+        );
+
+        let cached = self.load_bundle_from_disk(&source.id).await;
+        if cached.is_some() {
+            info!("Using cached bundle from disk (offline mode)");
         } else {
-            // No API client configured, try disk cache
-            if let Some(cached_bundle) = self.load_bundle_from_disk().await {
-                info!("No API client configured. Using cached bundle from disk.");
-                offline_mode = true;
-                Some(cached_bundle)
-            } else {
+            warn!(
+                "No disk cache available. Running offline with no servers from '{}'.",
+                source.id
+            );
+        }
+        (cached, true)
+    }
+
+    /// Stored credentials of a private registry
+    async fn registry_auth(&self, source: &RegistrySource) -> Option<RegistryAuth> {
+        let credentials = self.registry_credentials.as_ref()?;
+        match credentials.get(&source.id).await {
+            Ok(auth) => auth,
+            Err(e) => {
+                warn!(
+                    "Failed to load credentials of registry '{}': {}",
+                    source.id, e
+                );
                 None
             }
-        };
+        }
+    }
+
+    /// Serve discovery and search from the disk snapshots of the registries,
+    /// without touching the network.
+    ///
+    /// Meant for startup: the snapshot counts as fresh, so the first requests
+    /// don't wait for the registries. Call [`Self::refresh`] afterwards (e.g.
+    /// in the background) to sync it. Returns whether any snapshot existed.
+    pub async fn load_snapshot(&self) -> anyhow::Result<bool> {
+        let mut sources = self.registry_sources().await;
+        if sources.is_empty() {
+            sources.push(RegistrySource::public("cached"));
+        }
 
-        // 2. Process bundle if available
-        let got_bundle = bundle_result.is_some();
-        let base_servers = if let Some(bundle) = bundle_result {
-            // Update UI config
-            {
-                let mut ui_lock = self.ui_config.write().await;
-                *ui_lock = bundle.ui.clone();
+        let mut loaded = Vec::new();
+        for source in sources {
+            if source.local_dir().is_some() {
+                // Reading the folder is as fast as reading a snapshot
+                if let (Some(bundle), _) = self.load_registry_bundle(&source).await {
+                    loaded.push((source, bundle, Some(Utc::now())));
+                }
+            } else if let Some(bundle) = self.load_bundle_from_disk(&source.id).await {
+                let synced_at = self.bundle_cache_synced_at(&source.id).await;
+                loaded.push((source, bundle, synced_at));
             }
+        }
+        if loaded.is_empty() {
+            info!("No registry snapshot on disk, first refresh will fetch");
+            return Ok(false);
+        }
 
-            // Update home config
-            {
-                let mut home_lock = self.home_config.write().await;
-                *home_lock = bundle.home.clone();
+        info!("Serving {} registries from disk snapshot", loaded.len());
+        let offline = self.is_offline().await;
+        self.apply_bundles(loaded, offline).await?;
+        Ok(true)
+    }
+
+    /// Initialize the service by loading from every registry (with disk cache fallback) and user spaces.
+    pub async fn refresh(&self) -> anyhow::Result<()> {
+        let sources = self.registry_sources().await;
+        let mut offline_mode = false;
+        let mut loaded = Vec::new();
+
+        // 1. Load each registry's bundle
+        if sources.is_empty() {
+            // No registry configured, try the public disk cache
+            if let Some(cached_bundle) = self.load_bundle_from_disk(PUBLIC_REGISTRY_ID).await {
+                info!("No registry configured. Using cached bundle from disk.");
+                offline_mode = true;
+                let synced_at = self.bundle_cache_synced_at(PUBLIC_REGISTRY_ID).await;
+                loaded.push((RegistrySource::public("cached"), cached_bundle, synced_at));
+            }
+        }
+        for source in &sources {
+            let (bundle, offline) = self.load_registry_bundle(source).await;
+            offline_mode |= offline;
+            if let Some(bundle) = bundle {
+                let synced_at = if offline {
+                    self.bundle_cache_synced_at(&source.id).await
+                } else {
+                    Some(Utc::now())
+                };
+                loaded.push((source.clone(), bundle, synced_at));
             }
+        }
 
-            // Mark source as Registry
-            let registry_url = self
-                .registry_client
-                .as_ref()
-                .map(|c| c.base_url().to_string())
-                .unwrap_or_else(|| "cached".to_string());
+        self.apply_bundles(loaded, offline_mode).await
+    }
 
-            bundle
-                .servers
-                .into_iter()
-                .map(|mut s| {
-                    s.source = ServerSource::Registry {
-                        url: registry_url.clone(),
-                        name: "McpMux Registry".to_string(),
-                    };
-                    s
-                })
-                .collect::<Vec<_>>()
-        } else {
-            vec![]
-        };
+    /// Re-read the local registries' folders, keeping the other registries'
+    /// bundles as they are. Used when files in a local registry change.
+    pub async fn reload_local_registries(&self) -> anyhow::Result<()> {
+        let bundles = self.bundles.read().await.clone();
+        let synced_at = self.synced_at.read().await.clone();
+
+        let mut loaded = Vec::new();
+        for source in self.registry_sources().await {
+            if source.local_dir().is_some() {
+                if let (Some(bundle), _) = self.load_registry_bundle(&source).await {
+                    loaded.push((source, bundle, Some(Utc::now())));
+                }
+            } else if let Some(bundle) = bundles.get(&source.id) {
+                let at = synced_at.get(&source.id).copied();
+                loaded.push((source, bundle.clone(), at));
+            }
+        }
 
-        // Update offline status
+        let offline = self.is_offline().await;
+        self.apply_bundles(loaded, offline).await
+    }
+
+    /// Serve the given registry bundles (highest priority first) together
+    /// with the user spaces
+    async fn apply_bundles(
+        &self,
+        loaded: Vec<(RegistrySource, RegistryBundle, Option<DateTime<Utc>>)>,
+        offline_mode: bool,
+    ) -> anyhow::Result<()> {
+        // 2. UI and home config come from the highest-priority registry
+        // that provides them
+        let got_bundle = !loaded.is_empty();
+        if let Some((_, bundle, _)) = loaded
+            .iter()
+            .find(|(_, b, _)| !b.ui.filters.is_empty())
+            .or_else(|| loaded.first())
         {
-            let mut offline_lock = self.is_offline.write().await;
-            *offline_lock = offline_mode;
+            *self.ui_config.write().await = bundle.ui.clone();
         }
+        let home = loaded.iter().find_map(|(source, bundle, _)| {
+            bundle.home.clone().map(|mut home| {
+                home.featured_server_ids = home
+                    .featured_server_ids
+                    .iter()
+                    .map(|id| source.server_id(id))
+                    .collect();
+                home
+            })
+        });
+        *self.home_config.write().await = home;
 
-        for server in base_servers {
-            merged_servers.insert(server.id.clone(), server);
+        // Update offline status and data age
+        {
+            let mut offline_lock = self.is_offline.write().await;
+            *offline_lock = offline_mode;
         }
+        *self.synced_at.write().await = loaded
+            .iter()
+            .filter_map(|(source, _, at)| at.map(|at| (source.id.clone(), at)))
+            .collect();
+
+        let publisher_keys = self.publisher_keys().await;
+        let mut merged_servers = merge_registry_bundles(
+            &loaded
+                .iter()
+                .map(|(source, bundle, _)| {
+                    let servers = verify_registry_servers(source, &bundle.servers, &publisher_keys);
+                    (source.clone(), servers)
+                })
+                .collect::<Vec<_>>(),
+        );
+        *self.bundles.write().await = loaded
+            .into_iter()
+            .map(|(source, bundle, _)| (source.id, bundle))
+            .collect();
+
+        // The utility server runs inside the gateway, so it is always listed
+        merged_servers
+            .entry(UTILITY_SERVER_ID.to_string())
+            .or_insert_with(utility_server_definition);
 
         // 3. Load User Spaces (highest priority - overrides everything)
         match self.load_user_spaces().await {
@@ -383,30 +930,6 @@ impl ServerDiscoveryService {
         Ok(())
     }
 
-    /// Reload only user spaces (called when bundle is unchanged via 304)
-    async fn reload_user_spaces(&self) {
-        // Get current servers (registry servers from cache)
-        let mut servers = self.servers.read().await.clone();
-
-        // Remove existing user space servers (they might have changed)
-        servers.retain(|_, s| !matches!(s.source, ServerSource::UserSpace { .. }));
-
-        // Load fresh user spaces
-        match self.load_user_spaces().await {
-            Ok(user_servers) => {
-                info!("Reloaded {} user-configured servers", user_servers.len());
-                for server in user_servers {
-                    servers.insert(server.id.clone(), server);
-                }
-            }
-            Err(e) => error!("Failed to reload user spaces: {}", e),
-        }
-
-        // Update cache
-        let mut lock = self.servers.write().await;
-        *lock = servers;
-    }
-
     async fn load_user_spaces(&self) -> anyhow::Result<Vec<ServerDefinition>> {
         let mut results = Vec::new();
 
@@ -519,3 +1042,229 @@ impl ServerDiscoveryService {
         self.home_config.read().await.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RegistryTrust;
+
+    fn definition(id: &str, name: &str) -> ServerDefinition {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "description": null,
+            "alias": null,
+            "auth": null,
+            "icon": null,
+            "transport": { "type": "http", "url": "https://example.com/mcp" },
+            "publisher": null,
+            "license": null,
+            "license_url": null,
+            "installation": null,
+            "capabilities": null,
+            "sponsored": null,
+            "media": null,
+            "changelog_url": null
+        }))
+        .unwrap()
+    }
+
+    fn registry(id: &str, priority: i32, namespace: Option<&str>) -> RegistrySource {
+        RegistrySource {
+            id: id.to_string(),
+            name: id.to_string(),
+            url: format!("https://{}.example.com", id),
+            priority,
+            namespace: namespace.map(str::to_string),
+            trust: RegistryTrust::Trusted,
+            require_signatures: false,
+            format: RegistryFormat::McpMux,
+        }
+    }
+
+    #[test]
+    fn test_higher_priority_registry_wins() {
+        let sources = by_priority(vec![
+            registry("public", 0, None),
+            registry("acme", 10, None),
+        ]);
+        assert_eq!(sources[0].id, "acme");
+
+        let bundles: Vec<_> = sources
+            .into_iter()
+            .map(|s| {
+                let servers = vec![definition("github", &format!("GitHub ({})", s.id))];
+                (s, servers)
+            })
+            .collect();
+        let merged = merge_registry_bundles(&bundles);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged["github"].name, "GitHub (acme)");
+        assert!(matches!(
+            &merged["github"].source,
+            ServerSource::Registry { name, trust: RegistryTrust::Trusted, .. } if name == "acme"
+        ));
+    }
+
+    #[test]
+    fn test_namespaced_registry_does_not_collide() {
+        let bundles = vec![
+            (
+                registry("acme", 10, Some("acme")),
+                vec![definition("github", "Acme GitHub")],
+            ),
+            (
+                registry("public", 0, None),
+                vec![definition("github", "GitHub")],
+            ),
+        ];
+        let merged = merge_registry_bundles(&bundles);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["github"].name, "GitHub");
+        assert_eq!(merged["acme/github"].name, "Acme GitHub");
+        assert_eq!(merged["acme/github"].id, "acme/github");
+    }
+
+    #[test]
+    fn test_equal_priorities_keep_configured_order() {
+        let sources = by_priority(vec![
+            registry("first", 5, None),
+            registry("second", 5, None),
+        ]);
+        assert_eq!(sources[0].id, "first");
+        assert_eq!(sources[1].id, "second");
+    }
+
+    #[tokio::test]
+    async fn test_default_registry_when_none_configured() {
+        let dir = std::env::temp_dir();
+        let service = ServerDiscoveryService::new(dir.clone(), dir)
+            .with_registry_api("https://api.mcpmux.com".to_string());
+        let sources = service.registry_sources().await;
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].id, PUBLIC_REGISTRY_ID);
+    }
+
+    fn bundle(servers: Vec<ServerDefinition>) -> RegistryBundle {
+        RegistryBundle {
+            version: "1".to_string(),
+            updated_at: "2026-10-01T00:00:00Z".to_string(),
+            servers,
+            categories: vec![],
+            ui: default_ui_config(),
+            home: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_snapshot_serves_without_network() {
+        let dir = tempfile::tempdir().unwrap();
+        // Nothing listens on port 1, so every fetch fails
+        let service =
+            ServerDiscoveryService::new(dir.path().to_path_buf(), dir.path().join("spaces"))
+                .with_registry_api("http://127.0.0.1:1".to_string());
+        assert!(!service.load_snapshot().await.unwrap());
+
+        service
+            .save_bundle_to_disk(
+                PUBLIC_REGISTRY_ID,
+                &bundle(vec![definition("github", "GitHub")]),
+            )
+            .await
+            .unwrap();
+        assert!(service.load_snapshot().await.unwrap());
+        assert_eq!(service.search("git").await.len(), 1);
+        assert!(!service.should_refresh().await);
+        assert!(service.sync_status().await.last_synced_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_falls_back_to_snapshot_when_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let service =
+            ServerDiscoveryService::new(dir.path().to_path_buf(), dir.path().join("spaces"))
+                .with_registry_api("http://127.0.0.1:1".to_string());
+        service
+            .save_bundle_to_disk(
+                PUBLIC_REGISTRY_ID,
+                &bundle(vec![definition("github", "GitHub")]),
+            )
+            .await
+            .unwrap();
+
+        service.refresh().await.unwrap();
+
+        let status = service.sync_status().await;
+        assert!(status.offline);
+        assert!(status.last_synced_at.is_some());
+        assert!(service.get("github").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_local_registry_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let catalog = dir.path().join("catalog");
+        std::fs::create_dir_all(catalog.join("team")).unwrap();
+        std::fs::create_dir_all(catalog.join(".git")).unwrap();
+        std::fs::write(
+            catalog.join("github.json"),
+            r#"{"id":"github","name":"GitHub","transport":{"type":"http","url":"https://example.com/mcp"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            catalog.join("team").join("more.json"),
+            r#"[{"id":"jira","name":"Jira","transport":{"type":"http","url":"https://example.com/mcp"}}]"#,
+        )
+        .unwrap();
+        std::fs::write(catalog.join(".git").join("ignored.json"), "{}").unwrap();
+        std::fs::write(catalog.join("broken.json"), "not json").unwrap();
+
+        let mut local = registry("team", 10, Some("team"));
+        local.url = format!("file://{}", catalog.display());
+        let mut service =
+            ServerDiscoveryService::new(dir.path().to_path_buf(), dir.path().join("spaces"));
+        service.default_registry = Some(local);
+        service.refresh().await.unwrap();
+
+        // Both catalog entries, plus the built-in utility server
+        assert_eq!(service.list().await.len(), 3);
+        assert!(service.get("team/jira").await.is_some());
+        assert!(service.get(UTILITY_SERVER_ID).await.is_some());
+        assert_eq!(service.local_registry_dirs().await, vec![catalog.clone()]);
+
+        std::fs::remove_file(catalog.join("github.json")).unwrap();
+        service.reload_local_registries().await.unwrap();
+        assert!(service.get("team/github").await.is_none());
+        assert!(!service.sync_status().await.offline);
+    }
+
+    #[test]
+    fn test_verify_registry_servers() {
+        let (private_key, public_key) = crate::domain::generate_publisher_key().unwrap();
+        let keys = vec![PublisherKey {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            public_key,
+        }];
+        let mut signed = definition("signed", "Signed");
+        signed.signature =
+            Some(crate::domain::sign_definition(&signed, "acme", &private_key).unwrap());
+        let mut tampered = definition("tampered", "Tampered");
+        tampered.signature = signed.signature.clone();
+        let servers = vec![signed, tampered, definition("unsigned", "Unsigned")];
+
+        let mut source = registry("acme", 0, None);
+        let verified = verify_registry_servers(&source, &servers, &keys);
+        let ids: Vec<_> = verified.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["signed", "unsigned"]);
+        assert_eq!(verified[0].signature_status, SignatureStatus::Verified);
+        assert_eq!(verified[1].signature_status, SignatureStatus::Unsigned);
+
+        source.require_signatures = true;
+        let verified = verify_registry_servers(&source, &servers, &keys);
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].id, "signed");
+    }
+}
@@ -0,0 +1,269 @@
+//! Server group service - business logic for grouping installed servers
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use tracing::info;
+
+use crate::domain::{validate_group_name, FeatureSet, InstalledServer, MemberMode, ServerGroup};
+use crate::repository::{
+    FeatureSetRepository, InstalledServerRepository, ServerFeatureRepository, ServerGroupRepository,
+};
+
+/// Outcome of bringing a group's FeatureSet up to date
+#[derive(Debug, Clone)]
+pub struct GroupFeatureSetSync {
+    /// The group's FeatureSet
+    pub feature_set: FeatureSet,
+    /// Whether the FeatureSet was created by this sync
+    pub created: bool,
+    /// Features added to the set
+    pub added: usize,
+    /// Features removed from the set (their server left the group)
+    pub removed: usize,
+}
+
+/// Service for managing server groups
+pub struct ServerGroupService {
+    group_repo: Arc<dyn ServerGroupRepository>,
+    server_repo: Arc<dyn InstalledServerRepository>,
+    feature_set_repo: Arc<dyn FeatureSetRepository>,
+    feature_repo: Arc<dyn ServerFeatureRepository>,
+}
+
+impl ServerGroupService {
+    pub fn new(
+        group_repo: Arc<dyn ServerGroupRepository>,
+        server_repo: Arc<dyn InstalledServerRepository>,
+        feature_set_repo: Arc<dyn FeatureSetRepository>,
+        feature_repo: Arc<dyn ServerFeatureRepository>,
+    ) -> Self {
+        Self {
+            group_repo,
+            server_repo,
+            feature_set_repo,
+            feature_repo,
+        }
+    }
+
+    /// List the groups of a space. Servers that were uninstalled since they
+    /// were grouped are left out.
+    pub async fn list(&self, space_id: &str) -> Result<Vec<ServerGroup>> {
+        let installed: HashSet<String> = self
+            .server_repo
+            .list_for_space(space_id)
+            .await?
+            .into_iter()
+            .map(|s| s.server_id)
+            .collect();
+
+        let mut groups = self.group_repo.list_for_space(space_id).await?;
+        for group in &mut groups {
+            group.server_ids.retain(|id| installed.contains(id));
+        }
+        Ok(groups)
+    }
+
+    /// Get a group by ID
+    pub async fn get(&self, id: &str) -> Result<ServerGroup> {
+        self.group_repo
+            .get(id)
+            .await?
+            .ok_or_else(|| anyhow!("Server group not found"))
+    }
+
+    /// Create an empty group
+    pub async fn create(
+        &self,
+        space_id: &str,
+        name: &str,
+        icon: Option<String>,
+    ) -> Result<ServerGroup> {
+        let name = validate_group_name(name).map_err(|e| anyhow!(e))?;
+        self.ensure_name_free(space_id, &name, None).await?;
+
+        let mut group = ServerGroup::new(name, space_id);
+        if let Some(icon) = icon.filter(|i| !i.is_empty()) {
+            group = group.with_icon(icon);
+        }
+        self.group_repo.create(&group).await?;
+
+        info!(
+            group_id = %group.id,
+            space_id = space_id,
+            name = %group.name,
+            "[ServerGroupService] Created server group"
+        );
+        Ok(group)
+    }
+
+    /// Rename a group and change its icon (`None` clears it)
+    pub async fn update(&self, id: &str, name: &str, icon: Option<String>) -> Result<ServerGroup> {
+        let mut group = self.get(id).await?;
+        let name = validate_group_name(name).map_err(|e| anyhow!(e))?;
+        self.ensure_name_free(&group.space_id, &name, Some(id))
+            .await?;
+
+        group.name = name;
+        group.icon = icon.filter(|i| !i.is_empty());
+        group.updated_at = chrono::Utc::now();
+        self.group_repo.update(&group).await?;
+        Ok(group)
+    }
+
+    /// Delete a group. Its servers stay installed and its FeatureSet is
+    /// kept, since workspaces may be bound to it.
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        self.group_repo.delete(id).await?;
+        info!(group_id = id, "[ServerGroupService] Deleted server group");
+        Ok(())
+    }
+
+    /// Replace the servers in a group. Every server must be installed in
+    /// the group's space. The group's FeatureSet, if any, is synced.
+    pub async fn set_servers(
+        &self,
+        id: &str,
+        server_ids: Vec<String>,
+    ) -> Result<(ServerGroup, Option<GroupFeatureSetSync>)> {
+        let mut group = self.get(id).await?;
+
+        let installed: HashSet<String> = self
+            .server_repo
+            .list_for_space(&group.space_id)
+            .await?
+            .into_iter()
+            .map(|s| s.server_id)
+            .collect();
+        if let Some(missing) = server_ids.iter().find(|id| !installed.contains(*id)) {
+            bail!("Server {} is not installed in this space", missing);
+        }
+
+        group.set_servers(server_ids);
+        group.updated_at = chrono::Utc::now();
+        self.group_repo.update(&group).await?;
+
+        info!(
+            group_id = id,
+            servers = group.server_ids.len(),
+            "[ServerGroupService] Updated group servers"
+        );
+
+        let sync = match group.feature_set_id {
+            Some(_) => Some(self.sync_feature_set(id).await?),
+            None => None,
+        };
+        Ok((group, sync))
+    }
+
+    /// The installed servers of a group, in group order
+    pub async fn installed_servers(&self, id: &str) -> Result<Vec<InstalledServer>> {
+        let group = self.get(id).await?;
+        let mut installed = self.server_repo.list_for_space(&group.space_id).await?;
+        installed.retain(|s| group.contains(&s.server_id));
+        installed.sort_by_key(|s| group.server_ids.iter().position(|id| *id == s.server_id));
+        Ok(installed)
+    }
+
+    /// Create the group's FeatureSet, or bring it up to date: it includes
+    /// every feature discovered so far for the group's servers.
+    ///
+    /// Only include-members are managed; features the user excluded from
+    /// the set stay excluded.
+    pub async fn sync_feature_set(&self, id: &str) -> Result<GroupFeatureSetSync> {
+        let mut group = self.get(id).await?;
+
+        let existing = match &group.feature_set_id {
+            Some(fs_id) => self.feature_set_repo.get(fs_id).await?,
+            None => None,
+        };
+        let (feature_set, created) = match existing {
+            Some(fs) => (fs, false),
+            None => {
+                let mut fs =
+                    FeatureSet::new_custom(&group.name, &group.space_id).with_description(format!(
+                        "Every tool, prompt and resource from the {} server group",
+                        group.name
+                    ));
+                if let Some(icon) = &group.icon {
+                    fs = fs.with_icon(icon.clone());
+                }
+                self.feature_set_repo.create(&fs).await?;
+
+                group.feature_set_id = Some(fs.id.clone());
+                group.updated_at = chrono::Utc::now();
+                self.group_repo.update(&group).await?;
+                (fs, true)
+            }
+        };
+
+        let mut wanted: HashSet<String> = HashSet::new();
+        for server_id in &group.server_ids {
+            let features = self
+                .feature_repo
+                .list_for_server(&group.space_id, server_id)
+                .await?;
+            wanted.extend(features.into_iter().map(|f| f.id.to_string()));
+        }
+
+        let members = self
+            .feature_set_repo
+            .get_feature_members(&feature_set.id)
+            .await?;
+        let present: HashSet<&str> = members.iter().map(|m| m.member_id.as_str()).collect();
+
+        let mut removed = 0;
+        for member in &members {
+            if member.mode == MemberMode::Include && !wanted.contains(&member.member_id) {
+                self.feature_set_repo
+                    .remove_feature_member(&feature_set.id, &member.member_id)
+                    .await?;
+                removed += 1;
+            }
+        }
+
+        let mut added = 0;
+        for feature_id in &wanted {
+            if !present.contains(feature_id.as_str()) {
+                self.feature_set_repo
+                    .add_feature_member(&feature_set.id, feature_id, MemberMode::Include)
+                    .await?;
+                added += 1;
+            }
+        }
+
+        info!(
+            group_id = id,
+            feature_set_id = %feature_set.id,
+            added,
+            removed,
+            "[ServerGroupService] Synced group feature set"
+        );
+
+        Ok(GroupFeatureSetSync {
+            feature_set,
+            created,
+            added,
+            removed,
+        })
+    }
+
+    async fn ensure_name_free(
+        &self,
+        space_id: &str,
+        name: &str,
+        except: Option<&str>,
+    ) -> Result<()> {
+        let taken = self
+            .group_repo
+            .list_for_space(space_id)
+            .await?
+            .into_iter()
+            .any(|g| Some(g.id.as_str()) != except && g.name.eq_ignore_ascii_case(name));
+        if taken {
+            bail!("A server group named \"{}\" already exists", name);
+        }
+        Ok(())
+    }
+}
@@ -1,31 +1,50 @@
 //! Server log manager - file-based logging per server
+//!
+//! Besides writing each entry to disk, the manager broadcasts it so live
+//! tails ([`ServerLogManager::subscribe_tail`]) see new lines as they arrive.
 
-use crate::{LogConfig, LogLevel, ServerLog};
+use crate::{LogConfig, LogFilter, LogLevel, LogSearchPage, LogSearchQuery, ServerLog};
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use std::collections::HashMap;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::fs::{File, OpenOptions};
 use tokio::io::AsyncWriteExt;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
 use tracing::{debug, info, warn};
 
+/// Entries buffered for live tails before a slow one starts skipping
+const TAIL_CHANNEL_CAPACITY: usize = 1024;
+
+/// A log entry as broadcast to live tails
+#[derive(Debug, Clone)]
+struct TailedLog {
+    space_id: String,
+    server_id: String,
+    log: ServerLog,
+}
+
 /// Server log manager
 pub struct ServerLogManager {
     config: LogConfig,
     writers: Arc<RwLock<HashMap<String, Arc<Mutex<ServerLogWriter>>>>>,
+    tail_tx: broadcast::Sender<TailedLog>,
 }
 
 impl ServerLogManager {
     /// Create a new log manager
     pub fn new(config: LogConfig) -> Self {
+        let (tail_tx, _) = broadcast::channel(TAIL_CHANNEL_CAPACITY);
         Self {
             config,
             writers: Arc::new(RwLock::new(HashMap::new())),
+            tail_tx,
         }
     }
 
@@ -78,7 +97,30 @@ impl ServerLogManager {
     pub async fn append(&self, space_id: &str, server_id: &str, log: ServerLog) -> Result<()> {
         let writer = self.get_writer(space_id, server_id).await?;
         let mut w = writer.lock().await;
-        w.write(log).await
+        // No receivers is the common case; skip the clone
+        let tailed = (self.tail_tx.receiver_count() > 0).then(|| TailedLog {
+            space_id: space_id.to_string(),
+            server_id: server_id.to_string(),
+            log: log.clone(),
+        });
+        w.write(log).await?;
+        if let Some(tailed) = tailed {
+            let _ = self.tail_tx.send(tailed);
+        }
+        Ok(())
+    }
+
+    /// Follow new log entries for a server as they are appended.
+    ///
+    /// Only entries appended after this call are seen; read existing ones
+    /// with [`read_logs`](Self::read_logs) first.
+    pub fn subscribe_tail(&self, space_id: &str, server_id: &str, filter: LogFilter) -> LogTail {
+        LogTail {
+            rx: self.tail_tx.subscribe(),
+            space_id: space_id.to_string(),
+            server_id: server_id.to_string(),
+            filter,
+        }
     }
 
     /// Read recent logs (tail behavior)
@@ -119,6 +161,67 @@ impl ServerLogManager {
         Ok(logs)
     }
 
+    /// Search a server's logs, newest first, across the current file and
+    /// every rotated one (compressed or not).
+    ///
+    /// Rotated files are named after the time they were rotated, so each
+    /// file's time span is known without opening it; files outside the
+    /// query's range are skipped. Within a page, entries are returned in
+    /// chronological order.
+    pub async fn search_logs(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        query: &LogSearchQuery,
+    ) -> Result<LogSearchPage> {
+        let safe_server_id = Self::sanitize_server_id(server_id);
+        let log_dir = self.config.base_dir.join(space_id).join(safe_server_id);
+        if !log_dir.exists() {
+            return Ok(LogSearchPage::default());
+        }
+
+        let mut skipped = 0;
+        let mut entries = Vec::new();
+        'files: for file in list_log_files(&log_dir).await? {
+            if !file.overlaps(query.since, query.until) {
+                continue;
+            }
+            let content = match file.read().await {
+                Ok(content) => content,
+                Err(e) => {
+                    // Rotated files can be compressed or cleaned up underneath us
+                    debug!("Skipping log file {:?}: {}", file.path, e);
+                    continue;
+                }
+            };
+            for line in content.lines().rev() {
+                let Ok(log) = serde_json::from_str::<ServerLog>(line) else {
+                    continue;
+                };
+                if !query.matches(&log) {
+                    continue;
+                }
+                if skipped < query.offset {
+                    skipped += 1;
+                    continue;
+                }
+                entries.push(log);
+                // One extra tells whether there is another page
+                if entries.len() > query.limit {
+                    break 'files;
+                }
+            }
+        }
+
+        let has_more = entries.len() > query.limit;
+        entries.truncate(query.limit);
+        entries.reverse();
+        Ok(LogSearchPage {
+            entries,
+            next_offset: has_more.then_some(query.offset + query.limit),
+        })
+    }
+
     /// Clear logs for a server
     pub async fn clear_logs(&self, space_id: &str, server_id: &str) -> Result<()> {
         let key = format!("{}/{}", space_id, server_id);
@@ -260,6 +363,41 @@ impl ServerLogManager {
     }
 }
 
+/// Live tail of one server's logs, from [`ServerLogManager::subscribe_tail`]
+pub struct LogTail {
+    rx: broadcast::Receiver<TailedLog>,
+    space_id: String,
+    server_id: String,
+    filter: LogFilter,
+}
+
+impl LogTail {
+    /// Wait for the next entry that passes the filter. Entries a slow
+    /// consumer falls behind on are skipped. Returns `None` once the
+    /// manager is dropped.
+    pub async fn next(&mut self) -> Option<ServerLog> {
+        loop {
+            match self.rx.recv().await {
+                Ok(tailed) => {
+                    if tailed.space_id == self.space_id
+                        && tailed.server_id == self.server_id
+                        && self.filter.matches(&tailed.log)
+                    {
+                        return Some(tailed.log);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!(
+                        "Log tail for {}/{} lagged, skipped {} entries",
+                        self.space_id, self.server_id, skipped
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
 /// Writer for a single server's logs
 struct ServerLogWriter {
     log_dir: PathBuf,
@@ -386,6 +524,94 @@ impl ServerLogWriter {
     }
 }
 
+/// A server's log file and the span of time its entries fall in
+#[derive(Debug)]
+struct LogFile {
+    path: PathBuf,
+    compressed: bool,
+    /// Rotation time of the previous file; entries are newer than this
+    after: Option<DateTime<Utc>>,
+    /// Rotation time of this file, `None` for `current.log`
+    rotated_at: Option<DateTime<Utc>>,
+}
+
+impl LogFile {
+    fn overlaps(&self, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> bool {
+        let ends_before =
+            matches!((since, self.rotated_at), (Some(since), Some(end)) if end < since);
+        let starts_after =
+            matches!((until, self.after), (Some(until), Some(start)) if start > until);
+        !ends_before && !starts_after
+    }
+
+    async fn read(&self) -> Result<String> {
+        let bytes = tokio::fs::read(&self.path).await?;
+        if !self.compressed {
+            return Ok(String::from_utf8_lossy(&bytes).into_owned());
+        }
+        tokio::task::spawn_blocking(move || {
+            let mut content = String::new();
+            GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+            Ok(content)
+        })
+        .await?
+    }
+}
+
+/// Parse the rotation time from a rotated file name (`2024-01-31-235959.log[.gz]`)
+fn parse_rotated_at(name: &str) -> Option<DateTime<Utc>> {
+    let stem = name
+        .strip_suffix(".log.gz")
+        .or_else(|| name.strip_suffix(".log"))?;
+    NaiveDateTime::parse_from_str(stem, "%Y-%m-%d-%H%M%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// A server's log files, newest first
+async fn list_log_files(log_dir: &Path) -> Result<Vec<LogFile>> {
+    let mut rotated: Vec<(DateTime<Utc>, PathBuf, bool)> = Vec::new();
+    let mut entries = tokio::fs::read_dir(log_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !is_removable_log_file(&path) {
+            continue;
+        }
+        if let Some(rotated_at) = parse_rotated_at(name) {
+            rotated.push((rotated_at, path.clone(), name.ends_with(".gz")));
+        }
+    }
+    // While a file is being compressed both versions exist; read the plain one
+    rotated.sort_by_key(|(rotated_at, _, compressed)| (*rotated_at, *compressed));
+    rotated.dedup_by_key(|(rotated_at, _, _)| *rotated_at);
+
+    let mut files = Vec::with_capacity(rotated.len() + 1);
+    let mut after = None;
+    for (rotated_at, path, compressed) in rotated {
+        files.push(LogFile {
+            path,
+            compressed,
+            after,
+            rotated_at: Some(rotated_at),
+        });
+        after = Some(rotated_at);
+    }
+    let current = log_dir.join("current.log");
+    if current.exists() {
+        files.push(LogFile {
+            path: current,
+            compressed: false,
+            after,
+            rotated_at: None,
+        });
+    }
+    files.reverse();
+    Ok(files)
+}
+
 /// Check if a path is a removable log file (not `current.log`)
 fn is_removable_log_file(path: &Path) -> bool {
     let name = match path.file_name().and_then(|n| n.to_str()) {
@@ -521,6 +747,201 @@ mod tests {
         assert_eq!(logs[1].message, "Error msg");
     }
 
+    #[tokio::test]
+    async fn test_subscribe_tail_filters_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = LogConfig {
+            base_dir: temp_dir.path().to_path_buf(),
+            max_file_size: 1024 * 1024,
+            max_files: 5,
+            compress: false,
+        };
+        let manager = ServerLogManager::new(config);
+
+        manager
+            .append(
+                "space1",
+                "server1",
+                ServerLog::new(LogLevel::Error, LogSource::Stderr, "Before subscribe"),
+            )
+            .await
+            .unwrap();
+
+        let mut tail = manager.subscribe_tail(
+            "space1",
+            "server1",
+            LogFilter {
+                min_level: Some(LogLevel::Warn),
+                sources: vec![LogSource::Stderr, LogSource::Connection],
+            },
+        );
+
+        let entries = [
+            ("server1", LogLevel::Info, LogSource::Stderr, "Too quiet"),
+            ("server1", LogLevel::Error, LogSource::App, "Wrong source"),
+            (
+                "server2",
+                LogLevel::Error,
+                LogSource::Stderr,
+                "Other server",
+            ),
+            ("server1", LogLevel::Warn, LogSource::Stderr, "First match"),
+            (
+                "server1",
+                LogLevel::Error,
+                LogSource::Connection,
+                "Second match",
+            ),
+        ];
+        for (server_id, level, source, message) in entries {
+            manager
+                .append("space1", server_id, ServerLog::new(level, source, message))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(tail.next().await.unwrap().message, "First match");
+        assert_eq!(tail.next().await.unwrap().message, "Second match");
+
+        drop(manager);
+        assert!(tail.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_logs_across_rotated_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = LogConfig {
+            base_dir: temp_dir.path().to_path_buf(),
+            max_file_size: 1024 * 1024,
+            max_files: 10,
+            compress: false,
+        };
+        let manager = ServerLogManager::new(config);
+        let server_dir = temp_dir.path().join("space1").join("server1");
+        tokio::fs::create_dir_all(&server_dir).await.unwrap();
+
+        let at = |hour: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let lines = |entries: &[(u32, LogLevel, LogSource, &str)]| {
+            entries
+                .iter()
+                .map(|(hour, level, source, message)| {
+                    let mut log = ServerLog::new(*level, source.clone(), *message);
+                    log.timestamp = at(*hour);
+                    serde_json::to_string(&log).unwrap() + "\n"
+                })
+                .collect::<String>()
+        };
+
+        // Oldest file, compressed, rotated at 02:00
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(
+                lines(&[
+                    (0, LogLevel::Error, LogSource::Stderr, "Token expired"),
+                    (1, LogLevel::Info, LogSource::App, "Started"),
+                ])
+                .as_bytes(),
+            )
+            .unwrap();
+        tokio::fs::write(
+            server_dir.join("2024-01-01-020000.log.gz"),
+            encoder.finish().unwrap(),
+        )
+        .await
+        .unwrap();
+        // Rotated at 04:00
+        tokio::fs::write(
+            server_dir.join("2024-01-01-040000.log"),
+            lines(&[
+                (2, LogLevel::Warn, LogSource::OAuth, "token refresh failed"),
+                (3, LogLevel::Info, LogSource::Stdout, "ready"),
+            ]),
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(
+            server_dir.join("current.log"),
+            lines(&[
+                (5, LogLevel::Error, LogSource::Stderr, "TOKEN rejected"),
+                (6, LogLevel::Debug, LogSource::App, "ping"),
+            ]),
+        )
+        .await
+        .unwrap();
+
+        let messages = |page: &LogSearchPage| {
+            page.entries
+                .iter()
+                .map(|log| log.message.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Free text across all three files, chronological within the page
+        let query = LogSearchQuery {
+            text: Some("token".to_string()),
+            ..Default::default()
+        };
+        let page = manager
+            .search_logs("space1", "server1", &query)
+            .await
+            .unwrap();
+        assert_eq!(
+            messages(&page),
+            vec!["Token expired", "token refresh failed", "TOKEN rejected"]
+        );
+        assert_eq!(page.next_offset, None);
+
+        // Newest first across pages
+        let query = LogSearchQuery {
+            limit: 2,
+            ..Default::default()
+        };
+        let page = manager
+            .search_logs("space1", "server1", &query)
+            .await
+            .unwrap();
+        assert_eq!(messages(&page), vec!["TOKEN rejected", "ping"]);
+        assert_eq!(page.next_offset, Some(2));
+        let query = LogSearchQuery { offset: 2, ..query };
+        let page = manager
+            .search_logs("space1", "server1", &query)
+            .await
+            .unwrap();
+        assert_eq!(messages(&page), vec!["token refresh failed", "ready"]);
+        assert_eq!(page.next_offset, Some(4));
+
+        // Time range, level and source
+        let query = LogSearchQuery {
+            filter: LogFilter {
+                min_level: Some(LogLevel::Warn),
+                sources: vec![LogSource::Stderr, LogSource::OAuth],
+            },
+            since: Some(at(1)),
+            until: Some(at(5)),
+            ..Default::default()
+        };
+        let page = manager
+            .search_logs("space1", "server1", &query)
+            .await
+            .unwrap();
+        assert_eq!(
+            messages(&page),
+            vec!["token refresh failed", "TOKEN rejected"]
+        );
+
+        let page = manager
+            .search_logs("space1", "missing", &LogSearchQuery::default())
+            .await
+            .unwrap();
+        assert!(page.entries.is_empty());
+    }
+
     #[tokio::test]
     async fn test_cleanup_logs_older_than() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,55 @@
+/*
+ * McpMux C ABI
+ *
+ * Connect McpMux's installed servers and call their tools from C, Node
+ * (koffi, ffi-napi) or Python (ctypes), reusing McpMux's connection pool,
+ * OAuth tokens and FeatureSet permissions.
+ *
+ * Functions returning `char *` return JSON, freed with mcpmux_string_free().
+ * On failure they return NULL and mcpmux_last_error() says why.
+ */
+
+#ifndef MCPMUX_H
+#define MCPMUX_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct McpmuxPool McpmuxPool;
+
+/* Open an McpMux data directory, e.g. the desktop app's. NULL on failure. */
+McpmuxPool *mcpmux_pool_open(const char *data_dir);
+
+/* Disconnect every server and free the pool. NULL is ignored. */
+void mcpmux_pool_close(McpmuxPool *pool);
+
+/* Connect every enabled server.
+ * {"connected": [...], "already_connected": [...], "needs_oauth": [...],
+ *  "failed": [{"server_id": ..., "error": ...}]} */
+char *mcpmux_pool_connect_all(const McpmuxPool *pool);
+
+/* Connect one installed server; same JSON as mcpmux_pool_connect_all(). */
+char *mcpmux_pool_connect(const McpmuxPool *pool, const char *space_id,
+                          const char *server_id);
+
+/* Tools the inbound client may call: a JSON array of MCP tools with
+ * qualified names. */
+char *mcpmux_pool_list_tools(const McpmuxPool *pool, const char *client_id);
+
+/* Call a tool by its qualified name. arguments_json is a JSON object or
+ * NULL. Returns the MCP CallToolResult. */
+char *mcpmux_pool_call_tool(const McpmuxPool *pool, const char *client_id,
+                            const char *name, const char *arguments_json);
+
+/* Why the last call on this thread failed, or NULL. Do not free. */
+const char *mcpmux_last_error(void);
+
+/* Free a string returned by this library. NULL is ignored. */
+void mcpmux_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MCPMUX_H */
@@ -0,0 +1,362 @@
+//! McpMux C ABI
+//!
+//! Lets Node, Python and other non-Rust hosts reuse McpMux's connection
+//! pool, OAuth handling and permission checks instead of reimplementing MCP
+//! clients. A pool opens the desktop app's data directory (or the PostgreSQL
+//! database in `MCPMUX_DATABASE_URL`), connects its installed servers and
+//! calls their tools through an [`EmbeddedGateway`].
+//!
+//! The declarations are in `include/mcpmux.h`. Results are JSON strings the
+//! caller frees with [`mcpmux_string_free`]; on failure a function returns
+//! NULL and [`mcpmux_last_error`] describes why. Calls block the calling
+//! thread until the pool's runtime has finished them.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use mcpmux_sdk::gateway::{
+    AutoConnectResult, ClientContext, DependenciesBuilder, EmbeddedGateway, LogConfig,
+    ServerDiscoveryService, ServerLogManager, DEFAULT_AUTO_CONNECT_CONCURRENCY,
+};
+use mcpmux_sdk::storage::{create_key_provider, FieldEncryptor, RepositorySet, StorageConfig};
+use serde_json::{json, Value};
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+/// Client name shown in logs and call history for calls made through FFI
+const FFI_CLIENT_NAME: &str = "mcpmux-ffi";
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Servers connected through one data directory
+pub struct McpmuxPool {
+    runtime: Runtime,
+    gateway: EmbeddedGateway,
+}
+
+impl McpmuxPool {
+    /// Open a data directory laid out as the desktop app's
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(data_dir)
+            .with_context(|| format!("Failed to create {}", data_dir.display()))?;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("mcpmux-ffi")
+            .build()?;
+
+        let master_key = create_key_provider(data_dir)?.get_or_create_key()?;
+        let encryptor = Arc::new(FieldEncryptor::new(&master_key)?);
+        let repos = runtime.block_on(RepositorySet::open(
+            &StorageConfig::from_env(data_dir),
+            encryptor,
+        ))?;
+        let spaces_dir = data_dir.join("spaces");
+        std::fs::create_dir_all(&spaces_dir)?;
+
+        let dependencies = DependenciesBuilder::new()
+            .with_repositories(&repos)
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                data_dir.to_path_buf(),
+                spaces_dir,
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(LogConfig::new(
+                data_dir.join("logs"),
+            ))))
+            .with_state_dir(data_dir.to_path_buf())
+            .build()
+            .map_err(|e| anyhow!(e))?;
+
+        // Services spawn background tasks as they start
+        let gateway = {
+            let _guard = runtime.enter();
+            EmbeddedGateway::new(dependencies)
+        };
+        Ok(Self { runtime, gateway })
+    }
+
+    pub fn gateway(&self) -> &EmbeddedGateway {
+        &self.gateway
+    }
+
+    /// Connect every enabled server
+    pub fn connect_all(&self) -> Result<Value> {
+        let result = self.runtime.block_on(
+            self.gateway
+                .connect_servers(DEFAULT_AUTO_CONNECT_CONCURRENCY),
+        )?;
+        Ok(connect_result_json(&result))
+    }
+
+    /// Connect one installed server
+    pub fn connect(&self, space_id: &str, server_id: &str) -> Result<Value> {
+        let space_id =
+            Uuid::parse_str(space_id).with_context(|| format!("Invalid space ID: {}", space_id))?;
+        let result = self
+            .runtime
+            .block_on(self.gateway.connect_server(space_id, server_id))?;
+        Ok(connect_result_json(&result))
+    }
+
+    /// Tools the client may call, with qualified names
+    pub fn list_tools(&self, client_id: &str) -> Result<Value> {
+        let tools = self
+            .runtime
+            .block_on(self.gateway.list_tools(&client(client_id)))?;
+        Ok(serde_json::to_value(tools)?)
+    }
+
+    /// Call a tool by its qualified name
+    pub fn call_tool(&self, client_id: &str, name: &str, arguments: Value) -> Result<Value> {
+        let result =
+            self.runtime
+                .block_on(self.gateway.call_tool(&client(client_id), name, arguments))?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    /// Close every server connection
+    pub fn close(self) {
+        self.runtime.block_on(self.gateway.shutdown());
+    }
+}
+
+fn client(client_id: &str) -> ClientContext {
+    ClientContext::new(client_id).with_client_name(FFI_CLIENT_NAME)
+}
+
+fn connect_result_json(result: &AutoConnectResult) -> Value {
+    json!({
+        "connected": result.connected,
+        "already_connected": result.already_connected,
+        "needs_oauth": result.needs_oauth,
+        "failed": result
+            .failed
+            .iter()
+            .map(|(server_id, error)| json!({ "server_id": server_id, "error": error }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Run `f`, turning errors and panics into the default and a last error
+fn ffi_call<T>(default: T, f: impl FnOnce() -> Result<T>) -> T {
+    LAST_ERROR.with(|e| *e.borrow_mut() = None);
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            set_last_error(format!("{:#}", e));
+            default
+        }
+        Err(_) => {
+            set_last_error("McpMux panicked".to_string());
+            default
+        }
+    }
+}
+
+/// Borrow a C string argument
+///
+/// # Safety
+/// `s` is NULL or a valid NUL-terminated string that outlives the call.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Result<&'a str> {
+    if s.is_null() {
+        return Err(anyhow!("{} is NULL", name));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .with_context(|| format!("{} is not UTF-8", name))
+}
+
+/// Borrow the pool argument
+///
+/// # Safety
+/// `pool` is NULL or was returned by [`mcpmux_pool_open`] and not closed.
+unsafe fn pool_arg<'a>(pool: *const McpmuxPool) -> Result<&'a McpmuxPool> {
+    pool.as_ref().ok_or_else(|| anyhow!("pool is NULL"))
+}
+
+fn json_string(value: Value) -> Result<*mut c_char> {
+    Ok(CString::new(serde_json::to_string(&value)?)?.into_raw())
+}
+
+/// Open the McpMux data directory at `data_dir`; NULL on failure
+///
+/// # Safety
+/// `data_dir` is NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_open(data_dir: *const c_char) -> *mut McpmuxPool {
+    ffi_call(ptr::null_mut(), || {
+        let data_dir = arg(data_dir, "data_dir")?;
+        let pool = McpmuxPool::open(Path::new(data_dir))?;
+        Ok(Box::into_raw(Box::new(pool)))
+    })
+}
+
+/// Disconnect every server and free the pool. NULL is ignored.
+///
+/// # Safety
+/// `pool` is NULL or was returned by [`mcpmux_pool_open`] and not closed.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_close(pool: *mut McpmuxPool) {
+    if pool.is_null() {
+        return;
+    }
+    let pool = Box::from_raw(pool);
+    ffi_call((), || {
+        pool.close();
+        Ok(())
+    })
+}
+
+/// Connect every enabled server. Returns
+/// `{"connected", "already_connected", "needs_oauth", "failed"}` as JSON.
+///
+/// # Safety
+/// `pool` is NULL or a pool that has not been closed.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_connect_all(pool: *const McpmuxPool) -> *mut c_char {
+    ffi_call(ptr::null_mut(), || {
+        json_string(pool_arg(pool)?.connect_all()?)
+    })
+}
+
+/// Connect one installed server; returns the same JSON as
+/// [`mcpmux_pool_connect_all`]
+///
+/// # Safety
+/// `pool` is NULL or a pool that has not been closed; the strings are NULL
+/// or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_connect(
+    pool: *const McpmuxPool,
+    space_id: *const c_char,
+    server_id: *const c_char,
+) -> *mut c_char {
+    ffi_call(ptr::null_mut(), || {
+        let pool = pool_arg(pool)?;
+        json_string(pool.connect(arg(space_id, "space_id")?, arg(server_id, "server_id")?)?)
+    })
+}
+
+/// Tools the inbound client `client_id` may call, as a JSON array of MCP
+/// tools
+///
+/// # Safety
+/// `pool` is NULL or a pool that has not been closed; `client_id` is NULL
+/// or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_list_tools(
+    pool: *const McpmuxPool,
+    client_id: *const c_char,
+) -> *mut c_char {
+    ffi_call(ptr::null_mut(), || {
+        let pool = pool_arg(pool)?;
+        json_string(pool.list_tools(arg(client_id, "client_id")?)?)
+    })
+}
+
+/// Call a tool by its qualified name for the inbound client `client_id`.
+/// `arguments_json` is a JSON object, or NULL for none. Returns the MCP
+/// `CallToolResult` as JSON.
+///
+/// # Safety
+/// `pool` is NULL or a pool that has not been closed; the strings are NULL
+/// or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_pool_call_tool(
+    pool: *const McpmuxPool,
+    client_id: *const c_char,
+    name: *const c_char,
+    arguments_json: *const c_char,
+) -> *mut c_char {
+    ffi_call(ptr::null_mut(), || {
+        let pool = pool_arg(pool)?;
+        let arguments = if arguments_json.is_null() {
+            json!({})
+        } else {
+            serde_json::from_str(arg(arguments_json, "arguments_json")?)
+                .context("arguments_json is not valid JSON")?
+        };
+        json_string(pool.call_tool(arg(client_id, "client_id")?, arg(name, "name")?, arguments)?)
+    })
+}
+
+/// Why the last call on this thread failed, or NULL if it succeeded. Valid
+/// until the next call on this thread; do not free.
+#[no_mangle]
+pub extern "C" fn mcpmux_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Free a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `s` is NULL or a string returned by this library and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mcpmux_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> Option<String> {
+        let e = mcpmux_last_error();
+        (!e.is_null()).then(|| unsafe { CStr::from_ptr(e) }.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn null_arguments_fail_with_last_error() {
+        unsafe {
+            assert!(mcpmux_pool_open(ptr::null()).is_null());
+            assert_eq!(last_error().as_deref(), Some("data_dir is NULL"));
+
+            assert!(mcpmux_pool_connect_all(ptr::null()).is_null());
+            assert_eq!(last_error().as_deref(), Some("pool is NULL"));
+
+            mcpmux_pool_close(ptr::null_mut());
+            mcpmux_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn success_clears_last_error() {
+        set_last_error("earlier".to_string());
+        assert_eq!(ffi_call(0, || Ok(1)), 1);
+        assert_eq!(last_error(), None);
+
+        assert_eq!(ffi_call(0, || Err(anyhow!("nope"))), 0);
+        assert_eq!(last_error().as_deref(), Some("nope"));
+    }
+
+    #[test]
+    fn connect_result_lists_failures_by_server() {
+        let result = AutoConnectResult {
+            connected: vec!["a".to_string()],
+            failed: vec![("b".to_string(), "refused".to_string())],
+            ..Default::default()
+        };
+        assert_eq!(
+            connect_result_json(&result),
+            json!({
+                "connected": ["a"],
+                "already_connected": [],
+                "needs_oauth": [],
+                "failed": [{ "server_id": "b", "error": "refused" }],
+            })
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Build script that generates the gRPC control plane from its proto
+//!
+//! Uses a vendored `protoc` so building needs nothing beyond Cargo.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_build::configure()
+        .build_client(true)
+        .build_server(true)
+        .compile_protos(&["proto/mcpmux/admin/v1/admin.proto"], &["proto"])?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! API-key clients
+//!
+//! A "preregistered", pre-approved inbound client authenticated by a
+//! long-lived API key. Created from the desktop app or by pairing a device
+//! (see [`crate::server::pairing`]).
+
+use mcpmux_storage::{InboundClient, InboundClientRepository, RegistrationType};
+use serde::Serialize;
+use tracing::info;
+
+/// A newly-registered API-key client. `api_key` is returned once; only its
+/// SHA-256 hash is stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisteredApiKeyClient {
+    pub client_id: String,
+    pub client_name: String,
+    pub locked_space_id: Option<String>,
+    pub api_key: String,
+    pub key_prefix: String,
+}
+
+/// Generate a strong API key: `mcpk_` + 256 bits of v4-UUID randomness.
+/// Returns `(key_id, plaintext, key_prefix)`. Only the hash is ever stored.
+pub fn generate_api_key() -> (String, String, String) {
+    let key_id = uuid::Uuid::new_v4().to_string();
+    let secret = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let plaintext = format!("mcpk_{secret}");
+    let key_prefix: String = plaintext.chars().take(13).collect(); // "mcpk_" + 8 chars
+    (key_id, plaintext, key_prefix)
+}
+
+/// Register a pre-approved client named `name`, optionally locked to a
+/// Space, and issue its first API key.
+pub async fn register_api_key_client(
+    repo: &dyn InboundClientRepository,
+    name: &str,
+    locked_space_id: Option<&str>,
+) -> anyhow::Result<RegisteredApiKeyClient> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Client name is required");
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let client_id = format!("mcp_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let client = InboundClient {
+        client_id: client_id.clone(),
+        registration_type: RegistrationType::Preregistered,
+        client_name: name.to_string(),
+        client_alias: None,
+        redirect_uris: vec![],
+        grant_types: vec![],
+        response_types: vec![],
+        token_endpoint_auth_method: "none".to_string(),
+        scope: None,
+        approved: true,
+        logo_uri: None,
+        client_uri: None,
+        software_id: None,
+        software_version: None,
+        metadata_url: None,
+        metadata_cached_at: None,
+        metadata_cache_ttl: None,
+        last_seen: None,
+        created_at: now.clone(),
+        updated_at: now,
+        reports_roots: false,
+        roots_capability_known: false,
+        suspended: false,
+    };
+    repo.save_client(&client)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create client: {}", e))?;
+
+    if let Some(space) = locked_space_id {
+        repo.set_locked_space(&client_id, Some(space))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to lock client to space: {}", e))?;
+    }
+
+    let (key_id, plaintext, key_prefix) = generate_api_key();
+    repo.create_api_key(&key_id, &client_id, &plaintext, &key_prefix, None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create API key: {}", e))?;
+
+    info!("[Auth] Registered API-key client {} ({})", name, client_id);
+
+    Ok(RegisteredApiKeyClient {
+        client_id,
+        client_name: name.to_string(),
+        locked_space_id: locked_space_id.map(str::to_string),
+        api_key: plaintext,
+        key_prefix,
+    })
+}
@@ -19,6 +19,10 @@ use uuid::Uuid;
 
 use super::server::GatewayState;
 
+mod api_keys;
+
+pub use api_keys::{generate_api_key, register_api_key_client, RegisteredApiKeyClient};
+
 type HmacSha256 = Hmac<Sha256>;
 
 /// Authenticated client from request
@@ -235,6 +239,20 @@ pub struct TokenClaims {
     pub iat: i64, // Issued at timestamp
 }
 
+/// Scope prefix that selects a Space for a token's requests (`space:<id>`)
+pub const SPACE_SCOPE_PREFIX: &str = "space:";
+
+impl TokenClaims {
+    /// The Space selected by a `space:<id>` entry in the token's scope, if any
+    pub fn space_scope(&self) -> Option<&str> {
+        self.scope
+            .as_deref()?
+            .split_whitespace()
+            .find_map(|s| s.strip_prefix(SPACE_SCOPE_PREFIX))
+            .filter(|id| !id.is_empty())
+    }
+}
+
 /// Extractor for authenticated client claims (ISP pattern)
 ///
 /// Usage in handlers: `claims: TokenClaims`
@@ -497,6 +515,18 @@ mod jwt_tests {
         assert_eq!(claims.scope, Some("mcp".to_string()));
     }
 
+    #[test]
+    fn test_space_scope() {
+        let secret = b"test_secret_key_32_bytes_long!!";
+        let token = create_access_token("test_client", Some("mcp space:abc"), 3600, secret);
+        let claims = validate_token(&token, secret).unwrap();
+        assert_eq!(claims.space_scope(), Some("abc"));
+
+        let token = create_access_token("test_client", Some("mcp"), 3600, secret);
+        let claims = validate_token(&token, secret).unwrap();
+        assert_eq!(claims.space_scope(), None);
+    }
+
     #[test]
     fn test_invalid_signature() {
         let secret1 = b"test_secret_key_32_bytes_long!!";
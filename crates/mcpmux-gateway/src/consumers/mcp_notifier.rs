@@ -25,6 +25,7 @@ use tokio::sync::broadcast;
 use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
+use super::session_outbox::{ListKind, SessionOutbox};
 use crate::pool::FeatureService;
 use crate::services::FeatureSetResolverService;
 
@@ -45,6 +46,12 @@ use crate::services::FeatureSetResolverService;
 /// trigger). This is what closes the "FS edit doesn't reflect until
 /// reconnect" loophole.
 ///
+/// **Delivery is queued per session.** Notifications go into each session's
+/// [`SessionOutbox`], which collapses repeats of the same list and is
+/// drained by that session's own task. Handling an event never waits on a
+/// client, so a burst of events (auto-connect at startup) can't back up the
+/// event channel, and one slow client doesn't delay the others.
+///
 /// **Other duties (unchanged):**
 /// - Listens to DomainEvents from the EventBus.
 /// - Throttles per (space_id, notification_type) to prevent flapping.
@@ -92,12 +99,13 @@ enum NotificationType {
 const THROTTLE_WINDOW: Duration = Duration::from_secs(1);
 
 /// One registered MCP session — the gateway's view of a single live
-/// `mcp-session-id`. The peer is what we push notifications to; the
-/// `client_id` is kept for per-client fanout (e.g. on grant change).
+/// `mcp-session-id`. Notifications for the peer are queued in the outbox;
+/// the `client_id` is kept for per-client fanout (e.g. on grant change).
 #[derive(Clone)]
 struct SessionEntry {
     peer: Arc<Peer<RoleServer>>,
     client_id: String,
+    outbox: Arc<SessionOutbox>,
     /// True once the SSE stream for this session is open and notifications
     /// will actually deliver. Sessions register on `initialize`; the
     /// stream-active flag flips when the gateway opens the SSE side.
@@ -109,6 +117,7 @@ impl SessionEntry {
         Self {
             peer,
             client_id,
+            outbox: Arc::new(SessionOutbox::default()),
             has_active_stream: false,
         }
     }
@@ -177,10 +186,16 @@ impl MCPNotifier {
         client_id: String,
         peer: Arc<Peer<RoleServer>>,
     ) {
-        let entry = SessionEntry::new(client_id.clone(), peer);
+        let entry = SessionEntry::new(client_id.clone(), peer.clone());
+        entry
+            .outbox
+            .spawn_delivery(session_id.clone(), client_id.clone(), peer);
         let mut sessions = self.sessions.write();
-        let is_reconnect = sessions.contains_key(&session_id);
-        sessions.insert(session_id.clone(), entry);
+        let replaced = sessions.insert(session_id.clone(), entry);
+        let is_reconnect = replaced.is_some();
+        if let Some(replaced) = replaced {
+            replaced.outbox.close();
+        }
         info!(
             %session_id,
             %client_id,
@@ -260,6 +275,7 @@ impl MCPNotifier {
     pub fn unregister_session(&self, session_id: &str) {
         let mut sessions = self.sessions.write();
         if let Some(removed) = sessions.remove(session_id) {
+            removed.outbox.close();
             info!(
                 %session_id,
                 client_id = %removed.client_id,
@@ -392,7 +408,9 @@ impl MCPNotifier {
         {
             let mut sessions = self.sessions.write();
             for sid in &dead {
-                sessions.remove(sid);
+                if let Some(removed) = sessions.remove(sid) {
+                    removed.outbox.close();
+                }
             }
         }
         // Also clean the session_roots registry the resolver consults so
@@ -427,10 +445,15 @@ impl MCPNotifier {
                         notifier.handle_event(event).await;
                     }
                     Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        // The skipped events may have changed any list in
+                        // any space; have every session refetch rather than
+                        // leave one on a stale list
                         warn!(
                             skipped_events = skipped,
-                            "[MCPNotifier] ⚠️ Lagged behind, skipped {} events", skipped
+                            "[MCPNotifier] ⚠️ Lagged behind, skipped {} events; re-sending all lists",
+                            skipped
                         );
+                        notifier.notify_all_sessions_lists_changed().await;
                     }
                     Err(broadcast::error::RecvError::Closed) => {
                         warn!("[MCPNotifier] ❌ Event channel closed, stopping");
@@ -737,12 +760,10 @@ impl MCPNotifier {
             THROTTLE_WINDOW.as_secs()
         );
 
-        // Send all three types directly (bypassing individual throttles since we're
-        // in a batch operation). Mark timestamps after sending to suppress subsequent
+        // Queue all three types directly (bypassing individual throttles since we're
+        // in a batch operation). Mark timestamps after queueing to suppress subsequent
         // individual notifications.
-        self.send_tools_list_changed(space_id, now).await;
-        self.send_prompts_list_changed(space_id, now).await;
-        self.send_resources_list_changed(space_id, now).await;
+        self.send_list_changed(space_id, &ListKind::ALL).await;
 
         // Update all hashes to prevent subsequent individual notifications
         {
@@ -798,7 +819,7 @@ impl MCPNotifier {
         }
 
         let now = Instant::now();
-        self.send_tools_list_changed(space_id, now).await;
+        self.send_list_changed(space_id, &[ListKind::Tools]).await;
 
         // 3. Update State (only after successful send)
         {
@@ -812,22 +833,21 @@ impl MCPNotifier {
         }
     }
 
-    /// Internal method to actually send tools/list_changed notification (no throttling)
-    async fn send_tools_list_changed(&self, space_id: Uuid, _timestamp: Instant) {
+    /// Queue list_changed notifications of `kinds` for every session in
+    /// the space with an active stream (no throttling)
+    async fn send_list_changed(&self, space_id: Uuid, kinds: &[ListKind]) {
         // DEBUG: Kill switch to disable all notifications
         if DISABLE_ALL_NOTIFICATIONS {
-            trace!(space_id = %space_id, "[MCPNotifier] 🚫 NOTIFICATIONS DISABLED - skipping tools/list_changed");
+            trace!(space_id = %space_id, ?kinds, "[MCPNotifier] 🚫 NOTIFICATIONS DISABLED - skipping list_changed");
             return;
         }
 
-        // Get sessions in this space with active streams, paired with
-        // their session_id + client_id for per-push log attribution.
         let targets = self.get_peers_for_space_with_streams(space_id).await;
 
         if targets.is_empty() {
             debug!(
                 space_id = %space_id,
-                "[MCPNotifier] No sessions with active streams to notify about tools"
+                "[MCPNotifier] No sessions with active streams to notify"
             );
             return;
         }
@@ -835,44 +855,21 @@ impl MCPNotifier {
         info!(
             space_id = %space_id,
             session_count = targets.len(),
-            "[MCPNotifier] 📤 Sending tools/list_changed to {} session(s) with active streams",
+            ?kinds,
+            "[MCPNotifier] 📤 Queueing list_changed for {} session(s) with active streams",
             targets.len()
         );
 
-        let mut success_count = 0;
-        let mut failure_count = 0;
-
-        for (session_id, client_id, peer) in targets {
-            match peer.notify_tool_list_changed().await {
-                Ok(_) => {
-                    success_count += 1;
-                    debug!(
-                        %session_id,
-                        %client_id,
-                        %space_id,
-                        "[MCPNotifier] ✅ Sent tools/list_changed to session"
-                    );
-                }
-                Err(e) => {
-                    failure_count += 1;
-                    warn!(
-                        %session_id,
-                        %client_id,
-                        error = ?e,
-                        "[MCPNotifier] Failed to send tools/list_changed to session"
-                    );
-                }
-            }
-        }
-
-        if failure_count > 0 {
-            warn!(
-                space_id = %space_id,
-                success = success_count,
-                failed = failure_count,
-                "[MCPNotifier] Completed tools/list_changed notifications with {} failures",
-                failure_count
+        for (session_id, entry) in targets {
+            debug!(
+                %session_id,
+                client_id = %entry.client_id,
+                %space_id,
+                "[MCPNotifier] Queued list_changed for session"
             );
+            for &kind in kinds {
+                entry.outbox.push(kind);
+            }
         }
     }
 
@@ -881,41 +878,24 @@ impl MCPNotifier {
     ///
     /// Session-keyed: iterates `sessions`, re-runs the FeatureSet resolver
     /// per session (same path as the request handlers), and returns the
-    /// `(session_id, client_id, peer)` triples whose session resolves into
-    /// `space_id`. Threading session_id through to the call site lets the
-    /// log lines on each `peer.notify_*_list_changed()` prove *which*
-    /// session got the push — important for verifying that two windows of
-    /// the same client routing into different spaces don't cross-talk.
+    /// `(session_id, entry)` pairs whose session resolves into `space_id`.
+    /// Threading session_id through to the call site lets the log lines on
+    /// each queued push prove *which* session got it — important for
+    /// verifying that two windows of the same client routing into
+    /// different spaces don't cross-talk.
     async fn get_peers_for_space_with_streams(
         &self,
         space_id: Uuid,
-    ) -> Vec<(String, String, Arc<Peer<RoleServer>>)> {
-        let session_list: Vec<(String, String, Arc<Peer<RoleServer>>)> = {
-            let sessions = self.sessions.read();
-            sessions
-                .iter()
-                .filter(|(_, e)| e.has_active_stream)
-                .map(|(sid, entry)| (sid.clone(), entry.client_id.clone(), entry.peer.clone()))
-                .collect()
-        };
-
-        let dead = self.reap_dead_sessions(
-            &session_list
-                .iter()
-                .map(|(sid, _, peer)| (sid.clone(), peer.clone()))
-                .collect::<Vec<_>>(),
-        );
-        let dead_set: std::collections::HashSet<&str> = dead.iter().map(String::as_str).collect();
+    ) -> Vec<(String, SessionEntry)> {
+        let live = self.live_sessions(|_| true);
 
         let mut matching = Vec::new();
 
-        for (session_id, client_id, peer) in session_list {
-            if dead_set.contains(session_id.as_str()) {
-                continue;
-            }
+        for (session_id, entry) in live {
+            let client_id = entry.client_id.as_str();
             match self
                 .feature_set_resolver
-                .resolve(Some(&session_id), Some(&client_id))
+                .resolve(Some(&session_id), Some(client_id))
                 .await
             {
                 Ok(resolved) if resolved.space_id == Some(space_id) => {
@@ -925,7 +905,7 @@ impl MCPNotifier {
                         %space_id,
                         "[MCPNotifier] Session in target space with active stream"
                     );
-                    matching.push((session_id, client_id, peer));
+                    matching.push((session_id, entry));
                 }
                 Ok(resolved) => {
                     debug!(
@@ -974,7 +954,7 @@ impl MCPNotifier {
         }
 
         let now = Instant::now();
-        self.send_prompts_list_changed(space_id, now).await;
+        self.send_list_changed(space_id, &[ListKind::Prompts]).await;
 
         // 3. Update State
         self.state_hashes
@@ -985,45 +965,6 @@ impl MCPNotifier {
             .insert((space_id, NotificationType::Prompts), now);
     }
 
-    /// Internal method to actually send prompts/list_changed notification (no throttling)
-    async fn send_prompts_list_changed(&self, space_id: Uuid, _timestamp: Instant) {
-        // DEBUG: Kill switch to disable all notifications
-        if DISABLE_ALL_NOTIFICATIONS {
-            trace!(space_id = %space_id, "[MCPNotifier] 🚫 NOTIFICATIONS DISABLED - skipping prompts/list_changed");
-            return;
-        }
-
-        let targets = self.get_peers_for_space_with_streams(space_id).await;
-
-        if targets.is_empty() {
-            return;
-        }
-
-        info!(
-            space_id = %space_id,
-            session_count = targets.len(),
-            "[MCPNotifier] 📤 Sending prompts/list_changed to {} session(s)",
-            targets.len()
-        );
-
-        for (session_id, client_id, peer) in targets {
-            match peer.notify_prompt_list_changed().await {
-                Ok(_) => debug!(
-                    %session_id,
-                    %client_id,
-                    %space_id,
-                    "[MCPNotifier] ✅ Sent prompts/list_changed to session"
-                ),
-                Err(e) => warn!(
-                    %session_id,
-                    %client_id,
-                    error = ?e,
-                    "[MCPNotifier] Failed to send prompts/list_changed to session"
-                ),
-            }
-        }
-    }
-
     /// Notify all peers in a space that resources list has changed (with throttling and deduping)
     async fn notify_resources_list_changed(&self, space_id: Uuid) {
         // 1. Content-Based Deduping
@@ -1048,7 +989,8 @@ impl MCPNotifier {
         }
 
         let now = Instant::now();
-        self.send_resources_list_changed(space_id, now).await;
+        self.send_list_changed(space_id, &[ListKind::Resources])
+            .await;
 
         // 3. Update State
         self.state_hashes
@@ -1059,45 +1001,6 @@ impl MCPNotifier {
             .insert((space_id, NotificationType::Resources), now);
     }
 
-    /// Internal method to actually send resources/list_changed notification (no throttling)
-    async fn send_resources_list_changed(&self, space_id: Uuid, _timestamp: Instant) {
-        // DEBUG: Kill switch to disable all notifications
-        if DISABLE_ALL_NOTIFICATIONS {
-            trace!(space_id = %space_id, "[MCPNotifier] 🚫 NOTIFICATIONS DISABLED - skipping resources/list_changed");
-            return;
-        }
-
-        let targets = self.get_peers_for_space_with_streams(space_id).await;
-
-        if targets.is_empty() {
-            return;
-        }
-
-        info!(
-            space_id = %space_id,
-            session_count = targets.len(),
-            "[MCPNotifier] 📤 Sending resources/list_changed to {} session(s)",
-            targets.len()
-        );
-
-        for (session_id, client_id, peer) in targets {
-            match peer.notify_resource_list_changed().await {
-                Ok(_) => debug!(
-                    %session_id,
-                    %client_id,
-                    %space_id,
-                    "[MCPNotifier] ✅ Sent resources/list_changed to session"
-                ),
-                Err(e) => warn!(
-                    %session_id,
-                    %client_id,
-                    error = ?e,
-                    "[MCPNotifier] Failed to send resources/list_changed to session"
-                ),
-            }
-        }
-    }
-
     /// Send all three list_changed notifications to a single peer, bypassing
     /// the space-level hash dedup and throttle.
     ///
@@ -1118,20 +1021,7 @@ impl MCPNotifier {
         // editors, parallel CLI invocations). Push the notification on
         // every active session for that client_id; client-side dedup is
         // their problem, but missing a session would be ours.
-        let snapshot: Vec<(String, Arc<Peer<RoleServer>>)> = {
-            let sessions = self.sessions.read();
-            sessions
-                .iter()
-                .filter(|(_, e)| e.client_id == client_id && e.has_active_stream)
-                .map(|(sid, e)| (sid.clone(), e.peer.clone()))
-                .collect()
-        };
-        let dead = self.reap_dead_sessions(&snapshot);
-        let dead_set: std::collections::HashSet<&str> = dead.iter().map(String::as_str).collect();
-        let live: Vec<(String, Arc<Peer<RoleServer>>)> = snapshot
-            .into_iter()
-            .filter(|(sid, _)| !dead_set.contains(sid.as_str()))
-            .collect();
+        let live = self.live_sessions(|entry| entry.client_id == client_id);
 
         if live.is_empty() {
             debug!(
@@ -1147,8 +1037,9 @@ impl MCPNotifier {
             "[MCPNotifier] 📤 per-client list_changed (resolution flipped or grant edited)"
         );
 
-        for (session_id, peer) in &live {
-            Self::push_lists_to_session(session_id, client_id, peer).await;
+        for (session_id, entry) in &live {
+            debug!(%session_id, %client_id, "[MCPNotifier] Queued all lists for session");
+            entry.outbox.push_all();
         }
     }
 
@@ -1163,25 +1054,7 @@ impl MCPNotifier {
             return;
         }
 
-        let snapshot: Vec<(String, String, Arc<Peer<RoleServer>>)> = {
-            let sessions = self.sessions.read();
-            sessions
-                .iter()
-                .filter(|(_, e)| e.has_active_stream)
-                .map(|(sid, e)| (sid.clone(), e.client_id.clone(), e.peer.clone()))
-                .collect()
-        };
-        let dead = self.reap_dead_sessions(
-            &snapshot
-                .iter()
-                .map(|(sid, _, peer)| (sid.clone(), peer.clone()))
-                .collect::<Vec<_>>(),
-        );
-        let dead_set: HashSet<&str> = dead.iter().map(String::as_str).collect();
-        let live: Vec<_> = snapshot
-            .into_iter()
-            .filter(|(sid, _, _)| !dead_set.contains(sid.as_str()))
-            .collect();
+        let live = self.live_sessions(|_| true);
 
         if live.is_empty() {
             debug!("[MCPNotifier] no active sessions — skipping all-session list_changed");
@@ -1190,55 +1063,40 @@ impl MCPNotifier {
 
         info!(
             session_count = live.len(),
-            "[MCPNotifier] 📤 all-session list_changed (workspace binding changed)"
+            "[MCPNotifier] 📤 all-session list_changed (resolution may have changed)"
         );
 
-        for (session_id, client_id, peer) in &live {
-            Self::push_lists_to_session(session_id, client_id, peer).await;
+        for (session_id, entry) in &live {
+            debug!(
+                %session_id,
+                client_id = %entry.client_id,
+                "[MCPNotifier] Queued all lists for session"
+            );
+            entry.outbox.push_all();
         }
     }
 
-    /// Push all three list_changed notifications to one session, logging
-    /// each outcome. Shared by the per-client and all-session fanouts.
-    async fn push_lists_to_session(session_id: &str, client_id: &str, peer: &Peer<RoleServer>) {
-        match peer.notify_tool_list_changed().await {
-            Ok(_) => debug!(
-                %session_id,
-                %client_id,
-                "[MCPNotifier] ✅ Sent tools/list_changed to session (per-peer)"
-            ),
-            Err(e) => warn!(
-                %session_id,
-                %client_id,
-                error = ?e,
-                "[MCPNotifier] failed tools/list_changed"
-            ),
-        }
-        match peer.notify_prompt_list_changed().await {
-            Ok(_) => debug!(
-                %session_id,
-                %client_id,
-                "[MCPNotifier] ✅ Sent prompts/list_changed to session (per-peer)"
-            ),
-            Err(e) => warn!(
-                %session_id,
-                %client_id,
-                error = ?e,
-                "[MCPNotifier] failed prompts/list_changed"
-            ),
-        }
-        match peer.notify_resource_list_changed().await {
-            Ok(_) => debug!(
-                %session_id,
-                %client_id,
-                "[MCPNotifier] ✅ Sent resources/list_changed to session (per-peer)"
-            ),
-            Err(e) => warn!(
-                %session_id,
-                %client_id,
-                error = ?e,
-                "[MCPNotifier] failed resources/list_changed"
-            ),
-        }
+    /// Sessions with an active stream that match `filter`, after reaping
+    /// the ones whose transport has closed
+    fn live_sessions(&self, filter: impl Fn(&SessionEntry) -> bool) -> Vec<(String, SessionEntry)> {
+        let snapshot: Vec<(String, SessionEntry)> = {
+            let sessions = self.sessions.read();
+            sessions
+                .iter()
+                .filter(|(_, e)| e.has_active_stream && filter(e))
+                .map(|(sid, e)| (sid.clone(), e.clone()))
+                .collect()
+        };
+        let dead = self.reap_dead_sessions(
+            &snapshot
+                .iter()
+                .map(|(sid, e)| (sid.clone(), e.peer.clone()))
+                .collect::<Vec<_>>(),
+        );
+        let dead_set: HashSet<&str> = dead.iter().map(String::as_str).collect();
+        snapshot
+            .into_iter()
+            .filter(|(sid, _)| !dead_set.contains(sid.as_str()))
+            .collect()
     }
 }
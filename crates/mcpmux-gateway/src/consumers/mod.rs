@@ -31,6 +31,7 @@
 
 mod mcp_notifier;
 mod oauth_handler;
+mod session_outbox;
 
 pub use mcp_notifier::MCPNotifier;
 pub use oauth_handler::OAuthEventHandler;
@@ -0,0 +1,148 @@
+//! Per-session outbox for list_changed notifications
+//!
+//! Each MCP session gets its own outbox and delivery task, so a slow or
+//! stalled client can't hold up the event loop or the other sessions. The
+//! outbox coalesces: a burst of `tools/list_changed` (say, twenty servers
+//! connecting at startup) collapses into one pending entry, because the
+//! client refetches the whole list either way. At most one notification of
+//! each kind is ever queued, so the outbox can't grow, and a notification
+//! queued while an earlier one of the same kind is being sent is delivered
+//! after it, never lost.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rmcp::{service::Peer, RoleServer};
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+/// A list whose contents changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ListKind {
+    Tools,
+    Prompts,
+    Resources,
+}
+
+impl ListKind {
+    pub(super) const ALL: [ListKind; 3] = [ListKind::Tools, ListKind::Prompts, ListKind::Resources];
+
+    fn method(self) -> &'static str {
+        match self {
+            ListKind::Tools => "tools/list_changed",
+            ListKind::Prompts => "prompts/list_changed",
+            ListKind::Resources => "resources/list_changed",
+        }
+    }
+}
+
+/// Notifications waiting to be delivered to one session
+#[derive(Default)]
+pub(super) struct SessionOutbox {
+    /// Pending kinds, in the order they were first queued
+    pending: Mutex<Vec<ListKind>>,
+    wake: Notify,
+    closed: AtomicBool,
+}
+
+impl SessionOutbox {
+    /// Queue a notification; a no-op if one of this kind is already waiting
+    pub(super) fn push(&self, kind: ListKind) {
+        {
+            let mut pending = self.pending.lock();
+            if pending.contains(&kind) {
+                return;
+            }
+            pending.push(kind);
+        }
+        self.wake.notify_one();
+    }
+
+    /// Queue every kind of notification
+    pub(super) fn push_all(&self) {
+        for kind in ListKind::ALL {
+            self.push(kind);
+        }
+    }
+
+    /// Stop delivering; the delivery task exits once it wakes
+    pub(super) fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.wake.notify_one();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Take everything that's waiting
+    fn take(&self) -> Vec<ListKind> {
+        std::mem::take(&mut *self.pending.lock())
+    }
+
+    /// Start the task that delivers this outbox to `peer`. It runs until the
+    /// outbox is closed or the session's transport is.
+    pub(super) fn spawn_delivery(
+        self: &Arc<Self>,
+        session_id: String,
+        client_id: String,
+        peer: Arc<Peer<RoleServer>>,
+    ) {
+        let outbox = self.clone();
+        tokio::spawn(async move {
+            loop {
+                outbox.wake.notified().await;
+                if outbox.is_closed() || peer.is_transport_closed() {
+                    break;
+                }
+                for kind in outbox.take() {
+                    let result = match kind {
+                        ListKind::Tools => peer.notify_tool_list_changed().await,
+                        ListKind::Prompts => peer.notify_prompt_list_changed().await,
+                        ListKind::Resources => peer.notify_resource_list_changed().await,
+                    };
+                    match result {
+                        Ok(_) => debug!(
+                            %session_id,
+                            %client_id,
+                            "[MCPNotifier] ✅ Sent {} to session",
+                            kind.method()
+                        ),
+                        Err(e) => warn!(
+                            %session_id,
+                            %client_id,
+                            error = ?e,
+                            "[MCPNotifier] Failed to send {} to session",
+                            kind.method()
+                        ),
+                    }
+                }
+            }
+            debug!(%session_id, "[MCPNotifier] Session delivery task stopped");
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_notifications_of_a_kind_coalesce() {
+        let outbox = SessionOutbox::default();
+        outbox.push(ListKind::Tools);
+        outbox.push(ListKind::Resources);
+        outbox.push(ListKind::Tools);
+        outbox.push_all();
+        assert_eq!(
+            outbox.take(),
+            [ListKind::Tools, ListKind::Resources, ListKind::Prompts]
+        );
+
+        // Queued after a take: delivered in the next round, not lost
+        outbox.push(ListKind::Tools);
+        assert_eq!(outbox.take(), [ListKind::Tools]);
+        assert!(outbox.take().is_empty());
+    }
+}
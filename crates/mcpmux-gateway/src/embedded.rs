@@ -0,0 +1,388 @@
+//! Embedded Gateway
+//!
+//! The aggregation and permission engine without the HTTP server, for Rust
+//! applications that link McpMux as a library. Requests are made on behalf
+//! of a [`ClientContext`] and resolve, authorize and route exactly as the
+//! same MCP requests to `/mcp` do: qualified names, FeatureSet grants,
+//! workspace bindings and the built-in `mcpmux_*` tools all apply.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use mcpmux_core::DomainEvent;
+use rmcp::model::{CallToolResult, GetPromptResult, Prompt, Resource, ResourceContents, Tool};
+use serde_json::{Map, Value};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::pool::ToolCaller;
+use crate::server::{
+    AutoConnectResult, GatewayDependencies, GatewayState, ServiceContainer, BACKEND_CLOSE_TIMEOUT,
+    PAUSED_MESSAGE,
+};
+
+/// The client a request is made for
+#[derive(Debug, Clone)]
+pub struct ClientContext {
+    /// Inbound client ID, as registered with the gateway
+    pub client_id: String,
+    /// Session whose roots pick a workspace binding; see
+    /// [`EmbeddedGateway::set_roots`]
+    pub session_id: Option<String>,
+    /// Shown in logs and call history, like an MCP client's `clientInfo.name`
+    pub client_name: Option<String>,
+}
+
+impl ClientContext {
+    pub fn new(client_id: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            session_id: None,
+            client_name: None,
+        }
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_client_name(mut self, name: impl Into<String>) -> Self {
+        self.client_name = Some(name.into());
+        self
+    }
+}
+
+/// In-process gateway
+///
+/// Cheap to clone; clones share servers, caches and sessions.
+#[derive(Clone)]
+pub struct EmbeddedGateway {
+    services: Arc<ServiceContainer>,
+}
+
+impl EmbeddedGateway {
+    /// Create a gateway over the given dependencies. No servers are
+    /// connected until [`connect_servers`](Self::connect_servers).
+    pub fn new(dependencies: GatewayDependencies) -> Self {
+        let (domain_event_tx, _) = broadcast::channel(256);
+        let mut state = GatewayState::new(domain_event_tx.clone());
+        state.set_inbound_client_repository(dependencies.inbound_client_repo.clone());
+        state.set_client_metadata_service(dependencies.client_metadata_service.clone());
+        let services = ServiceContainer::initialize(
+            &dependencies,
+            domain_event_tx,
+            Arc::new(RwLock::new(state)),
+        );
+        Self::from_services(Arc::new(services))
+    }
+
+    /// Share the services of an existing gateway, e.g. one also serving HTTP
+    pub fn from_services(services: Arc<ServiceContainer>) -> Self {
+        Self { services }
+    }
+
+    pub fn services(&self) -> &Arc<ServiceContainer> {
+        &self.services
+    }
+
+    /// Domain events: servers connecting, features changing, tool calls
+    pub async fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.services
+            .gateway_state
+            .read()
+            .await
+            .subscribe_domain_events()
+    }
+
+    /// Connect every enabled server, as the HTTP gateway does on startup
+    pub async fn connect_servers(&self, concurrency: usize) -> Result<AutoConnectResult> {
+        let startup = &self.services.startup_orchestrator;
+        if let Err(e) = startup.mark_all_features_unavailable().await {
+            warn!("[Embedded] Failed to mark features unavailable: {}", e);
+        }
+        if let Err(e) = startup.resolve_server_prefixes().await {
+            warn!("[Embedded] Failed to resolve server prefixes: {}", e);
+        }
+        if let Err(e) = startup.refresh_oauth_tokens().await {
+            warn!("[Embedded] Token refresh failed: {}", e);
+        }
+        startup.auto_connect_enabled_servers(concurrency).await
+    }
+
+    /// Connect one installed server, whether or not it is enabled
+    pub async fn connect_server(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+    ) -> Result<AutoConnectResult> {
+        self.services
+            .startup_orchestrator
+            .connect_installed_server(&space_id.to_string(), server_id)
+            .await
+    }
+
+    /// Report the workspace roots of a session, as `roots/list` does for
+    /// MCP clients
+    pub fn set_roots<I, S>(&self, session_id: &str, roots: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.services.session_roots.set(session_id, roots);
+    }
+
+    /// Close every server connection
+    pub async fn shutdown(&self) {
+        self.services.drain_tracker.start_draining();
+        self.services
+            .pool_services
+            .pool_service
+            .shutdown_all(BACKEND_CLOSE_TIMEOUT)
+            .await;
+    }
+
+    /// Tools the client may call, with qualified names
+    pub async fn list_tools(&self, client: &ClientContext) -> Result<Vec<Tool>> {
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        tools_for_grants(&self.services, space_id, &feature_set_ids).await
+    }
+
+    /// Call a tool by its qualified name. Arguments failing the tool's
+    /// schema fail with [`InvalidToolArguments`](crate::InvalidToolArguments).
+    pub async fn call_tool(
+        &self,
+        client: &ClientContext,
+        name: &str,
+        arguments: Value,
+    ) -> Result<CallToolResult> {
+        let session_id = client.session_id.as_deref();
+        let mut activity = self.services.session_activity.begin(session_id);
+        activity.tool_call(name);
+
+        let _in_flight = self
+            .services
+            .drain_tracker
+            .begin()
+            .ok_or_else(|| anyhow!("Gateway is shutting down"))?;
+        if self.services.traffic_pause.is_paused() {
+            bail!(PAUSED_MESSAGE);
+        }
+        info!(tool = %name, client = %client.client_id, "[Embedded] call_tool");
+
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        let registry = &self.services.meta_tool_registry;
+        let result = if crate::services::is_meta_tool(name)
+            && registry.contains(name)
+            && registry.is_tool_enabled_for_space(&space_id, name).await
+        {
+            match registry
+                .call(name, &client.client_id, session_id, arguments)
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => e.into_call_tool_result(),
+            }
+        } else {
+            let caller = ToolCaller {
+                client_id: &client.client_id,
+                client_name: client.client_name.as_deref(),
+                progress: None,
+            };
+            self.services
+                .pool_services
+                .routing_service
+                .call_tool(space_id, &feature_set_ids, name, arguments, Some(caller))
+                .await?
+                .into_mcp_result()
+        };
+
+        if !result.is_error.unwrap_or(false) {
+            activity.succeeded();
+        }
+        Ok(result)
+    }
+
+    /// Prompts the client may get, with qualified names
+    pub async fn list_prompts(&self, client: &ClientContext) -> Result<Vec<Prompt>> {
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        prompts_for_grants(&self.services, space_id, &feature_set_ids).await
+    }
+
+    /// Get a prompt by its qualified name
+    pub async fn get_prompt(
+        &self,
+        client: &ClientContext,
+        name: &str,
+        arguments: Option<Map<String, Value>>,
+    ) -> Result<GetPromptResult> {
+        let _activity = self
+            .services
+            .session_activity
+            .begin(client.session_id.as_deref());
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        let Some((server_id, prompt_name)) =
+            authorized_prompt(&self.services, space_id, &feature_set_ids, name).await?
+        else {
+            bail!("Prompt '{}' not authorized", name);
+        };
+        let result = self
+            .services
+            .pool_services
+            .pool_service
+            .get_prompt(space_id, &server_id, &prompt_name, arguments)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Resources the client may read
+    pub async fn list_resources(&self, client: &ClientContext) -> Result<Vec<Resource>> {
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        resources_for_grants(&self.services, space_id, &feature_set_ids).await
+    }
+
+    /// Read a resource by URI
+    pub async fn read_resource(
+        &self,
+        client: &ClientContext,
+        uri: &str,
+    ) -> Result<Vec<ResourceContents>> {
+        let _activity = self
+            .services
+            .session_activity
+            .begin(client.session_id.as_deref());
+        let (space_id, feature_set_ids) = self.resolve(client).await?;
+        let Some(server_id) =
+            authorized_resource(&self.services, space_id, &feature_set_ids, uri).await?
+        else {
+            bail!("Resource '{}' not authorized", uri);
+        };
+        let contents = self
+            .services
+            .pool_services
+            .routing_service
+            .read_resource(space_id, &server_id, uri, Some(&client.client_id))
+            .await?;
+        Ok(contents
+            .into_iter()
+            .filter_map(|v| serde_json::from_value(v).ok())
+            .collect())
+    }
+
+    /// The Space and FeatureSets the client's requests route through
+    async fn resolve(&self, client: &ClientContext) -> Result<(Uuid, Vec<String>)> {
+        let resolved = self
+            .services
+            .authorization_service
+            .resolve(client.session_id.as_deref(), Some(&client.client_id))
+            .await?;
+        let space_id = resolved
+            .space_id
+            .ok_or_else(|| anyhow!("No space resolved (no default space configured)"))?;
+        Ok((space_id, resolved.feature_set_ids))
+    }
+}
+
+/// Granted tools with qualified names, then the Space's enabled meta tools
+pub(crate) async fn tools_for_grants(
+    services: &ServiceContainer,
+    space_id: Uuid,
+    feature_set_ids: &[String],
+) -> Result<Vec<Tool>> {
+    let tools = services
+        .pool_services
+        .feature_service
+        .get_tools_for_grants(&space_id.to_string(), feature_set_ids)
+        .await?;
+    let mut mcp_tools: Vec<Tool> = tools
+        .iter()
+        .filter_map(|f| {
+            let mut tool: Tool = serde_json::from_value(f.raw_json.clone()?).ok()?;
+            tool.name = f.qualified_name().into();
+            Some(tool)
+        })
+        .collect();
+    mcp_tools.extend(
+        services
+            .meta_tool_registry
+            .list_as_tools_for_space(&space_id)
+            .await,
+    );
+    Ok(mcp_tools)
+}
+
+/// Granted prompts with qualified names
+pub(crate) async fn prompts_for_grants(
+    services: &ServiceContainer,
+    space_id: Uuid,
+    feature_set_ids: &[String],
+) -> Result<Vec<Prompt>> {
+    let prompts = services
+        .pool_services
+        .feature_service
+        .get_prompts_for_grants(&space_id.to_string(), feature_set_ids)
+        .await?;
+    Ok(prompts
+        .iter()
+        .filter_map(|f| {
+            let mut prompt: Prompt = serde_json::from_value(f.raw_json.clone()?).ok()?;
+            prompt.name = f.qualified_name();
+            Some(prompt)
+        })
+        .collect())
+}
+
+/// Granted resources
+pub(crate) async fn resources_for_grants(
+    services: &ServiceContainer,
+    space_id: Uuid,
+    feature_set_ids: &[String],
+) -> Result<Vec<Resource>> {
+    let resources = services
+        .pool_services
+        .feature_service
+        .get_resources_for_grants(&space_id.to_string(), feature_set_ids)
+        .await?;
+    Ok(resources
+        .iter()
+        .filter_map(|f| serde_json::from_value(f.raw_json.clone()?).ok())
+        .collect())
+}
+
+/// Server and server-side name of a granted, available prompt. Matches the
+/// qualified name the list uses, so a listed prompt is always gettable.
+pub(crate) async fn authorized_prompt(
+    services: &ServiceContainer,
+    space_id: Uuid,
+    feature_set_ids: &[String],
+    name: &str,
+) -> Result<Option<(String, String)>> {
+    let prompts = services
+        .pool_services
+        .feature_service
+        .get_prompts_for_grants(&space_id.to_string(), feature_set_ids)
+        .await?;
+    Ok(prompts
+        .into_iter()
+        .find(|p| p.is_available && p.qualified_name() == name)
+        .map(|p| (p.server_id, p.feature_name)))
+}
+
+/// Server of a granted, available resource
+pub(crate) async fn authorized_resource(
+    services: &ServiceContainer,
+    space_id: Uuid,
+    feature_set_ids: &[String],
+    uri: &str,
+) -> Result<Option<String>> {
+    let resources = services
+        .pool_services
+        .feature_service
+        .get_resources_for_grants(&space_id.to_string(), feature_set_ids)
+        .await?;
+    Ok(resources
+        .into_iter()
+        .find(|r| r.is_available && r.qualified_name() == uri)
+        .map(|r| r.server_id))
+}
@@ -7,9 +7,11 @@
 //! - Client access key authentication
 //! - Dependency Injection for clean architecture
 //! - Event-driven architecture via DomainEvent consumers
+//! - In-process embedding without the HTTP server ([`EmbeddedGateway`])
 
 pub mod auth;
 pub mod consumers;
+pub mod embedded;
 pub mod logging;
 pub mod mcp;
 pub mod oauth;
@@ -18,12 +20,16 @@ pub mod pool;
 pub mod server;
 pub mod services;
 
-pub use auth::AccessKeyAuth;
+pub use auth::{generate_api_key, register_api_key_client, AccessKeyAuth, RegisteredApiKeyClient};
 pub use oauth::{OAuthConfig, OAuthManager, OAuthToken};
 pub use permissions::{PermissionFilter, PermissionSet};
 pub use server::{
-    AutoConnectResult, DependenciesBuilder, GatewayConfig, GatewayDependencies, GatewayServer,
-    GatewayServerHandle, GatewayState, PendingAuthorization, StartupOrchestrator,
+    create_user, generate_admin_token, generate_pairing_code, local_base_url, pairing_url,
+    rotate_user_token, set_admin_token, AdminPrincipal, AutoConnectResult, CorsConfig, CorsPolicy,
+    DependenciesBuilder, GatewayConfig, GatewayDependencies, GatewayReloader, GatewayServer,
+    GatewayServerHandle, GatewayState, PendingAuthorization, PendingPairing, RequestLimits,
+    StartupOrchestrator, TrafficPause, ADMIN_TOKEN_PREFIX, BACKEND_CLOSE_TIMEOUT,
+    DEFAULT_AUTO_CONNECT_CONCURRENCY, DUAL_STACK_LOOPBACK, PAIRING_CODE_TTL, PAUSED_MESSAGE,
 };
 
 // Pool module - SOLID architecture
@@ -40,10 +46,13 @@ pub use pool::{
     DatabaseCredentialStore,
     // Instance types
     DiscoveredFeatures,
+    FaultInjector,
+    FaultPlan,
     FeatureService,
     InstalledServerInfo,
     InstanceKey,
     InstanceState,
+    InvalidToolArguments,
     McpClient,
     McpClientConnection,
     McpClientHandler,
@@ -57,6 +66,9 @@ pub use pool::{
     // Service Factory (DRY)
     PoolServices,
     PoolStats,
+    PoolStatsSnapshot,
+    ProgressRelay,
+    ProgressSink,
     ReconnectResult,
     ResolvedTransport,
     // Routing types
@@ -64,12 +76,18 @@ pub use pool::{
     RoutedResource,
     RoutedTool,
     RoutingService,
+    ServerHealth,
     ServerInstance,
     ServerKey,
     ServerManager,
     ServerState,
+    ServerStatsSnapshot,
     ServiceFactory,
     TokenService,
+    ToolCaller,
+    TrafficDirection,
+    TrafficFrame,
+    TrafficRecorder,
     TransportConnectResult,
     TransportFactory,
     TransportType,
@@ -81,5 +99,8 @@ pub use services::{EventEmitter, GrantService, PrefixCacheService};
 // MCP module (rmcp-based implementation)
 pub use mcp::McpMuxGatewayHandler;
 
+// In-process gateway for library consumers (no HTTP)
+pub use embedded::{ClientContext, EmbeddedGateway};
+
 // Event-driven architecture consumers
 pub use consumers::MCPNotifier;
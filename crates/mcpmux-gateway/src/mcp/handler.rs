@@ -14,7 +14,13 @@ use tracing::{debug, info, warn};
 
 use super::context::{extract_oauth_context, extract_session_id, OAuthContext};
 use crate::consumers::MCPNotifier;
+use crate::embedded::{
+    authorized_prompt, authorized_resource, prompts_for_grants, resources_for_grants,
+    tools_for_grants,
+};
+use crate::pool::{InvalidToolArguments, ProgressSink, ToolCaller};
 use crate::server::ServiceContainer;
+use crate::services::SessionInfo;
 
 /// McpMux Gateway Handler
 ///
@@ -351,24 +357,35 @@ impl McpMuxGatewayHandler {
     }
 }
 
+/// Capabilities of the aggregated server: the union of what backends offer,
+/// with list-changed notifications as backends connect and disconnect.
+/// Also advertised in `/.well-known/mcp.json`.
+pub fn gateway_capabilities() -> ServerCapabilities {
+    use rmcp::model::{PromptsCapability, ResourcesCapability, ToolsCapability};
+
+    ServerCapabilities::builder()
+        .enable_tools_with(ToolsCapability {
+            list_changed: Some(true),
+        })
+        .enable_prompts_with(PromptsCapability {
+            list_changed: Some(true),
+        })
+        .enable_resources_with(ResourcesCapability {
+            subscribe: Some(false),
+            list_changed: Some(true),
+        })
+        .build()
+}
+
 impl ServerHandler for McpMuxGatewayHandler {
     fn get_info(&self) -> ServerInfo {
-        use rmcp::model::{PromptsCapability, ResourcesCapability, ToolsCapability};
-
         // Note: get_info is called frequently, no logging needed
 
-        let capabilities = ServerCapabilities::builder()
-            .enable_tools_with(ToolsCapability {
-                list_changed: Some(true),
-            })
-            .enable_prompts_with(PromptsCapability {
-                list_changed: Some(true),
-            })
-            .enable_resources_with(ResourcesCapability {
-                subscribe: Some(false),
-                list_changed: Some(true),
-            })
-            .build();
+        let mut capabilities = gateway_capabilities();
+        self.services
+            .prefix_cache_service
+            .federation()
+            .advertise(&mut capabilities);
         let mut server_info = Implementation::new("mcpmux-gateway", env!("CARGO_PKG_VERSION"));
         server_info.title = Some("McpMux".to_string());
         let mut info = ServerInfo::new(capabilities);
@@ -475,6 +492,22 @@ impl ServerHandler for McpMuxGatewayHandler {
             // Mark the SSE stream as active immediately — RMCP's session
             // transport handles streaming + message caching internally.
             self.notification_bridge.mark_session_stream_active(sid);
+
+            let client_info = peer.peer_info();
+            let transport = peer.clone();
+            self.services.session_activity.register(
+                sid,
+                SessionInfo {
+                    client_id: oauth_ctx.client_id.clone(),
+                    client_name: client_info.map(|info| info.client_info.name.clone()),
+                    client_version: client_info.map(|info| info.client_info.version.clone()),
+                    protocol_version: client_info.map(|info| {
+                        self.negotiate_protocol_version(&info.protocol_version.to_string())
+                            .to_string()
+                    }),
+                },
+                move || transport.is_transport_closed(),
+            );
         } else {
             warn!(
                 client_id = %oauth_ctx.client_id,
@@ -719,6 +752,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
         let session_id_owned = extract_session_id(&context.extensions);
+        let _activity = self
+            .services
+            .session_activity
+            .begin(session_id_owned.as_deref());
         // Bridge the init race: roots-capable sessions whose first
         // `list_roots()` raced this request get a one-shot 300 ms probe
         // here so they end up at the right routing decision instead of
@@ -736,39 +773,15 @@ impl ServerHandler for McpMuxGatewayHandler {
             .resolve_routing(session_id_owned.as_deref(), &oauth_ctx.client_id)
             .await?;
 
-        // Get tools via FeatureService — using the *resolved* space.
-        let tools = self
-            .services
-            .pool_services
-            .feature_service
-            .get_tools_for_grants(&space_id.to_string(), &feature_set_ids)
+        // Granted tools of the *resolved* space with qualified names, then
+        // the Space's built-in `mcpmux_*` (Tool Optimization) tools. Those are
+        // empty when that built-in server is disabled for the Space, and any
+        // individual tools the Space has turned off are filtered out — all
+        // configured per Space via the Built-in Servers tab.
+        let mcp_tools = tools_for_grants(&self.services, space_id, &feature_set_ids)
             .await
             .map_err(|e| McpError::internal_error(format!("Failed to get tools: {}", e), None))?;
 
-        // Convert to MCP Tool types with qualified names (prefix.tool_name)
-        let mut mcp_tools: Vec<Tool> = tools
-            .iter()
-            .filter_map(|f| {
-                f.raw_json.as_ref().and_then(|json| {
-                    let mut tool: Tool = serde_json::from_value(json.clone()).ok()?;
-                    // Replace name with qualified name (prefix.tool_name)
-                    tool.name = f.qualified_name().into();
-                    Some(tool)
-                })
-            })
-            .collect();
-
-        // Append the resolved Space's built-in `mcpmux_*` (Tool Optimization)
-        // tools. The set is empty when that built-in server is disabled for the
-        // Space, and any individual tools the Space has turned off are filtered
-        // out — all configured per Space via the Built-in Servers tab.
-        mcp_tools.extend(
-            self.services
-                .meta_tool_registry
-                .list_as_tools_for_space(&space_id)
-                .await,
-        );
-
         // Log tool names at DEBUG level for visibility
         let tool_names: Vec<String> = mcp_tools.iter().map(|t| t.name.to_string()).collect();
         debug!(
@@ -789,6 +802,26 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
+        let session_id_owned = extract_session_id(&context.extensions);
+        let session_id = session_id_owned.as_deref();
+        let mut activity = self.services.session_activity.begin(session_id);
+        activity.tool_call(&params.name);
+
+        // Held until the call returns so shutdown can wait for it
+        let _in_flight = self
+            .services
+            .drain_tracker
+            .begin()
+            .ok_or_else(|| McpError::internal_error("Gateway is shutting down", None))?;
+
+        if self.services.traffic_pause.is_paused() {
+            info!(tool = %params.name, "call_tool refused: traffic paused");
+            return Err(McpError::internal_error(
+                crate::server::PAUSED_MESSAGE,
+                None,
+            ));
+        }
+
         // Tool calls are important - log at INFO
         info!(
             tool = %params.name,
@@ -796,9 +829,6 @@ impl ServerHandler for McpMuxGatewayHandler {
             "call_tool"
         );
 
-        let session_id_owned = extract_session_id(&context.extensions);
-        let session_id = session_id_owned.as_deref();
-
         // Bridge the init race on the call side too: a tools/call can land
         // while a roots-capable session is still PendingRoots (client
         // resumed and immediately invoked a tool it listed on a previous
@@ -841,12 +871,26 @@ impl ServerHandler for McpMuxGatewayHandler {
                 .call(&params.name, &oauth_ctx.client_id, session_id, args)
                 .await
             {
-                Ok(result) => Ok(result),
+                Ok(result) => {
+                    if !result.is_error.unwrap_or(false) {
+                        activity.succeeded();
+                    }
+                    Ok(result)
+                }
                 Err(e) => Ok(e.into_call_tool_result()),
             };
         }
 
         // Call tool via routing service (handles auth and routing)
+        let progress = ProgressSink::from_request(&context.peer, &context.meta);
+        let caller = ToolCaller {
+            client_id: &oauth_ctx.client_id,
+            client_name: context
+                .peer
+                .peer_info()
+                .map(|info| info.client_info.name.as_str()),
+            progress: progress.as_ref(),
+        };
         let tool_result = self
             .services
             .pool_services
@@ -856,59 +900,70 @@ impl ServerHandler for McpMuxGatewayHandler {
                 &feature_set_ids,
                 &params.name,
                 serde_json::to_value(params.arguments.unwrap_or_default()).unwrap_or_default(),
+                Some(caller),
             )
             .await
-            .map_err(|e| McpError::internal_error(format!("Tool call failed: {}", e), None))?;
+            .map_err(|e| match e.downcast_ref::<InvalidToolArguments>() {
+                // The caller can fix these, so say exactly what's wrong
+                Some(invalid) => McpError::invalid_params(
+                    invalid.to_string(),
+                    Some(serde_json::json!({ "violations": invalid.violations })),
+                ),
+                None => McpError::internal_error(format!("Tool call failed: {}", e), None),
+            })?;
 
         // Convert ToolCallResult to MCP CallToolResult without dropping
         // structuredContent or protocol-level _meta from the upstream server.
         let result = tool_result.into_mcp_result();
 
-        // Log result summary - show content types and approximate sizes
-        let content_summary: Vec<String> = result
-            .content
-            .iter()
-            .map(|c| {
-                // Content is Annotated<RawContent>, serialize to inspect type
-                if let Ok(json) = serde_json::to_value(c) {
-                    let content_type = json
-                        .get("type")
-                        .and_then(|t| t.as_str())
-                        .unwrap_or("unknown");
-                    match content_type {
-                        "text" => {
-                            let len = json
-                                .get("text")
-                                .and_then(|t| t.as_str())
-                                .map(|s| s.len())
-                                .unwrap_or(0);
-                            format!("text({}c)", len)
-                        }
-                        "image" => {
-                            let mime = json.get("mimeType").and_then(|m| m.as_str()).unwrap_or("?");
-                            format!("image({})", mime)
-                        }
-                        "resource" => {
-                            let uri = json
-                                .get("resource")
-                                .and_then(|r| r.get("uri"))
-                                .and_then(|u| u.as_str())
-                                .unwrap_or("?");
-                            format!("resource({})", uri)
+        // Log result summary - show content types and approximate sizes.
+        // Only built when it will be logged.
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            let content_summary: Vec<String> = result
+                .content
+                .iter()
+                .map(|c| {
+                    // Text needs no serializing; results can be megabytes
+                    if let Some(text) = c.as_text() {
+                        return format!("text({}c)", text.text.len());
+                    }
+                    // Content is Annotated<RawContent>, serialize to inspect type
+                    if let Ok(json) = serde_json::to_value(c) {
+                        let content_type = json
+                            .get("type")
+                            .and_then(|t| t.as_str())
+                            .unwrap_or("unknown");
+                        match content_type {
+                            "image" => {
+                                let mime =
+                                    json.get("mimeType").and_then(|m| m.as_str()).unwrap_or("?");
+                                format!("image({})", mime)
+                            }
+                            "resource" => {
+                                let uri = json
+                                    .get("resource")
+                                    .and_then(|r| r.get("uri"))
+                                    .and_then(|u| u.as_str())
+                                    .unwrap_or("?");
+                                format!("resource({})", uri)
+                            }
+                            _ => content_type.to_string(),
                         }
-                        _ => content_type.to_string(),
+                    } else {
+                        "?".to_string()
                     }
-                } else {
-                    "?".to_string()
-                }
-            })
-            .collect();
-        debug!(
-            tool = %params.name,
-            is_error = result.is_error.unwrap_or(false),
-            content = ?content_summary,
-            "call_tool result"
-        );
+                })
+                .collect();
+            debug!(
+                tool = %params.name,
+                is_error = result.is_error.unwrap_or(false),
+                content = ?content_summary,
+                "call_tool result"
+            );
+        }
+        if !result.is_error.unwrap_or(false) {
+            activity.succeeded();
+        }
 
         Ok(result)
     }
@@ -922,6 +977,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
         let session_id_owned = extract_session_id(&context.extensions);
+        let _activity = self
+            .services
+            .session_activity
+            .begin(session_id_owned.as_deref());
         self.ensure_roots_probed(
             &context.peer,
             session_id_owned.as_deref(),
@@ -932,27 +991,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .resolve_routing(session_id_owned.as_deref(), &oauth_ctx.client_id)
             .await?;
 
-        let prompts = self
-            .services
-            .pool_services
-            .feature_service
-            .get_prompts_for_grants(&space_id.to_string(), &feature_set_ids)
+        let mcp_prompts = prompts_for_grants(&self.services, space_id, &feature_set_ids)
             .await
             .map_err(|e| McpError::internal_error(format!("Failed to get prompts: {}", e), None))?;
 
-        // Convert to MCP Prompt types with qualified names (prefix.prompt_name)
-        let mcp_prompts: Vec<Prompt> = prompts
-            .iter()
-            .filter_map(|f| {
-                f.raw_json.as_ref().and_then(|json| {
-                    let mut prompt: Prompt = serde_json::from_value(json.clone()).ok()?;
-                    // Replace name with qualified name (prefix.prompt_name)
-                    prompt.name = f.qualified_name();
-                    Some(prompt)
-                })
-            })
-            .collect();
-
         // Log prompt names at DEBUG level
         let prompt_names: Vec<String> = mcp_prompts.iter().map(|p| p.name.to_string()).collect();
         debug!(
@@ -973,6 +1015,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
         let session_id_owned = extract_session_id(&context.extensions);
+        let _activity = self
+            .services
+            .session_activity
+            .begin(session_id_owned.as_deref());
         // Same init-race bridge as call_tool — keep list==get symmetric.
         self.ensure_roots_probed(
             &context.peer,
@@ -989,21 +1035,15 @@ impl ServerHandler for McpMuxGatewayHandler {
         // (ServerFeature::qualified_name). Guarantees "if it lists, it's
         // callable"; no dependency on the prefix-cache reverse lookup (which
         // could be stale and reject a listed prompt). Mirrors call_tool.
-        let authorized_prompts = self
-            .services
-            .pool_services
-            .feature_service
-            .get_prompts_for_grants(&space_id.to_string(), &feature_set_ids)
-            .await
-            .map_err(|e| {
-                McpError::internal_error(format!("Failed to verify authorization: {}", e), None)
-            })?;
+        let authorized =
+            authorized_prompt(&self.services, space_id, &feature_set_ids, &params.name)
+                .await
+                .map_err(|e| {
+                    McpError::internal_error(format!("Failed to verify authorization: {}", e), None)
+                })?;
 
-        let (server_id, prompt_name) = match authorized_prompts
-            .iter()
-            .find(|p| p.is_available && p.qualified_name() == params.name)
-        {
-            Some(p) => (p.server_id.clone(), p.feature_name.clone()),
+        let (server_id, prompt_name) = match authorized {
+            Some(prompt) => prompt,
             None => {
                 return Err(McpError::invalid_params(
                     format!("Prompt '{}' not authorized", params.name),
@@ -1037,6 +1077,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
         let session_id_owned = extract_session_id(&context.extensions);
+        let _activity = self
+            .services
+            .session_activity
+            .begin(session_id_owned.as_deref());
         self.ensure_roots_probed(
             &context.peer,
             session_id_owned.as_deref(),
@@ -1047,25 +1091,12 @@ impl ServerHandler for McpMuxGatewayHandler {
             .resolve_routing(session_id_owned.as_deref(), &oauth_ctx.client_id)
             .await?;
 
-        let resources = self
-            .services
-            .pool_services
-            .feature_service
-            .get_resources_for_grants(&space_id.to_string(), &feature_set_ids)
+        let mcp_resources = resources_for_grants(&self.services, space_id, &feature_set_ids)
             .await
             .map_err(|e| {
                 McpError::internal_error(format!("Failed to get resources: {}", e), None)
             })?;
 
-        let mcp_resources: Vec<Resource> = resources
-            .iter()
-            .filter_map(|f| {
-                f.raw_json
-                    .as_ref()
-                    .and_then(|json| serde_json::from_value(json.clone()).ok())
-            })
-            .collect();
-
         // Log resource URIs at DEBUG level
         let resource_uris: Vec<String> = mcp_resources.iter().map(|r| r.uri.to_string()).collect();
         debug!(
@@ -1086,6 +1117,10 @@ impl ServerHandler for McpMuxGatewayHandler {
             .get_oauth_context(&context.extensions)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
         let session_id_owned = extract_session_id(&context.extensions);
+        let _activity = self
+            .services
+            .session_activity
+            .begin(session_id_owned.as_deref());
         // Same init-race bridge as call_tool — keep list==read symmetric.
         self.ensure_roots_probed(
             &context.peer,
@@ -1101,21 +1136,15 @@ impl ServerHandler for McpMuxGatewayHandler {
         // resource set (resources are namespaced by URI, so qualified_name ==
         // feature_name == uri). The server_id comes from the matched feature,
         // so a listed resource is always readable. Mirrors call_tool / get_prompt.
-        let authorized_resources = self
-            .services
-            .pool_services
-            .feature_service
-            .get_resources_for_grants(&space_id.to_string(), &feature_set_ids)
-            .await
-            .map_err(|e| {
-                McpError::internal_error(format!("Failed to verify authorization: {}", e), None)
-            })?;
+        let authorized =
+            authorized_resource(&self.services, space_id, &feature_set_ids, &params.uri)
+                .await
+                .map_err(|e| {
+                    McpError::internal_error(format!("Failed to verify authorization: {}", e), None)
+                })?;
 
-        let server_id = match authorized_resources
-            .iter()
-            .find(|r| r.is_available && r.qualified_name() == params.uri)
-        {
-            Some(r) => r.server_id.clone(),
+        let server_id = match authorized {
+            Some(server_id) => server_id,
             None => {
                 return Err(McpError::invalid_params(
                     format!("Resource '{}' not authorized", params.uri),
@@ -1127,8 +1156,13 @@ impl ServerHandler for McpMuxGatewayHandler {
         let contents_values = self
             .services
             .pool_services
-            .pool_service
-            .read_resource(space_id, &server_id, &params.uri)
+            .routing_service
+            .read_resource(
+                space_id,
+                &server_id,
+                &params.uri,
+                Some(&oauth_ctx.client_id),
+            )
             .await
             .map_err(|e| McpError::internal_error(format!("Read resource failed: {}", e), None))?;
 
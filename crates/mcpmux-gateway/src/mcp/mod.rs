@@ -13,5 +13,5 @@ pub mod context;
 pub mod handler;
 pub mod oauth_middleware;
 
-pub use handler::McpMuxGatewayHandler;
+pub use handler::{gateway_capabilities, McpMuxGatewayHandler};
 pub use oauth_middleware::mcp_oauth_middleware;
@@ -16,7 +16,10 @@ use tracing::{debug, info, warn};
 
 use crate::auth::validate_token;
 use crate::logging::TraceContext;
+use crate::server::is_body_too_large;
+use crate::server::rate_limit::RateLimiter;
 use crate::server::ServiceContainer;
+use crate::services::SpaceSelectionError;
 
 /// Synthetic client identity used when system-wide inbound auth is disabled and
 /// a connection arrives without a (valid) Bearer token. Routing still prefers
@@ -26,7 +29,8 @@ const ANONYMOUS_CLIENT_ID: &str = "mcpmux-anonymous";
 
 /// OAuth middleware for MCP endpoints using rmcp
 ///
-/// Extracts Bearer token → Verifies JWT → Resolves space → Injects OAuthContext
+/// Extracts Bearer token → Verifies JWT → Refuses suspended clients →
+/// Applies per-client rate limits → Resolves space → Injects OAuthContext
 pub async fn mcp_oauth_middleware(
     axum::extract::State(services): axum::extract::State<Arc<ServiceContainer>>,
     mut request: Request<Body>,
@@ -119,11 +123,43 @@ pub async fn mcp_oauth_middleware(
         None
     };
 
+    // A `space:<id>` token scope selects a Space, like the header below
+    let token_space = claims
+        .as_ref()
+        .and_then(|c| c.space_scope())
+        .map(str::to_owned);
+
     // Resolve (client_id, space_id) from the authenticated identity (JWT or API
     // key); when auth is disabled, fall back to an anonymous identity on the
     // default space.
     let authed_client_id = claims.map(|c| c.client_id).or(api_key_client_id);
     let (client_id, space_id) = if let Some(cid) = authed_client_id {
+        // A suspended client keeps its credentials, so check on every request
+        // rather than at token issue: suspending takes effect immediately.
+        match services
+            .dependencies
+            .inbound_client_repo
+            .is_client_suspended(&cid)
+            .await
+        {
+            Ok(false) => {}
+            Ok(true) => {
+                warn!(trace_id = %trace_id, client_id = %cid, "Request from suspended client");
+                return suspended_response();
+            }
+            Err(e) => {
+                warn!(
+                    trace_id = %trace_id,
+                    client_id = %cid,
+                    "Failed to check client suspension: {}", e
+                );
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to check client status: {}", e),
+                )
+                    .into_response();
+            }
+        }
         match services
             .space_resolver_service
             .resolve_space_for_client(&cid)
@@ -176,6 +212,52 @@ pub async fn mcp_oauth_middleware(
         }
     };
 
+    // Per-client rate limits, now that the client is known
+    let rate_limit = request
+        .extensions()
+        .get::<RateLimiter>()
+        .and_then(|limiter| limiter.check(request.uri().path(), Some(&client_id)));
+    if let Some(Err(status)) = rate_limit {
+        warn!(trace_id = %trace_id, client_id = %client_id, "Client rate limit exceeded");
+        return status.rejection();
+    }
+
+    // Per-request Space selection via the `X-McpMux-Space` header (else the
+    // token's `space:` scope), so IDE windows on different projects can use
+    // different Spaces through one gateway URL. Unlike the workspace header
+    // below, the selection is checked against the client's grants: a client
+    // can only pick a Space it was granted FeatureSets in.
+    let requested_space = request
+        .headers()
+        .get("x-mcpmux-space")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+        .or(token_space);
+    let selected_space = match requested_space {
+        Some(requested) => match services
+            .space_resolver_service
+            .select_space(&client_id, &requested)
+            .await
+        {
+            Ok(id) => Some(id),
+            Err(e) => {
+                warn!(
+                    trace_id = %trace_id,
+                    client_id = %client_id,
+                    "Space selection rejected: {}", e
+                );
+                let status = match e {
+                    SpaceSelectionError::InvalidId(_) => StatusCode::BAD_REQUEST,
+                    SpaceSelectionError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::FORBIDDEN,
+                };
+                return (status, e.to_string()).into_response();
+            }
+        },
+        None => None,
+    };
+    let space_id = selected_space.unwrap_or(space_id);
+
     // Inject OAuth context via custom headers (rmcp will preserve these)
     request.headers_mut().insert(
         "x-mcpmux-client-id",
@@ -211,6 +293,16 @@ pub async fn mcp_oauth_middleware(
     if let Some((sid, ws)) = pin {
         services.session_roots.set_pinned(&sid, &ws);
     }
+    // The resolver routes by session, so record the selected Space against it
+    if let (Some(sid), Some(space)) = (
+        request
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok()),
+        selected_space,
+    ) {
+        services.session_roots.set_selected_space(sid, space);
+    }
 
     // Extract MCP method from body if POST
     let mcp_method = if request.method() == axum::http::Method::POST {
@@ -235,6 +327,11 @@ pub async fn mcp_oauth_middleware(
                 request = axum::http::Request::from_parts(parts, Body::from(body_bytes));
                 method
             }
+            Err(e) if is_body_too_large(&e) => {
+                warn!(trace_id = %trace_id, "Request body over the size limit");
+                return (StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large")
+                    .into_response();
+            }
             Err(e) => {
                 warn!(trace_id = %trace_id, "Failed to read body: {}", e);
                 return (
@@ -250,7 +347,10 @@ pub async fn mcp_oauth_middleware(
         None
     };
 
-    let response = next.run(request).await;
+    let mut response = next.run(request).await;
+    if let Some(Ok(status)) = rate_limit {
+        status.apply_headers(response.headers_mut());
+    }
 
     // Log errors only
     let status = response.status();
@@ -267,6 +367,15 @@ pub async fn mcp_oauth_middleware(
     response
 }
 
+/// Response to every request from a suspended client
+fn suspended_response() -> Response<Body> {
+    let body = serde_json::json!({
+        "error": "client_suspended",
+        "error_description": "This client has been suspended in McpMux. Ask the user to reinstate it.",
+    });
+    (StatusCode::FORBIDDEN, axum::Json(body)).into_response()
+}
+
 /// Generate unauthorized response with RFC 9728 protected-resource discovery.
 fn unauthorized_response(base_url: &str, message: &str) -> Response<Body> {
     let resource_metadata_url = format!(
@@ -140,6 +140,7 @@ fn build_inbound_client_from_request(
         // on the first `initialize` for any session of this client.
         reports_roots: false,
         roots_capability_known: false,
+        suspended: false,
     }
 }
 
@@ -338,7 +339,7 @@ pub fn validate_redirect_uris(uris: &[String]) -> Result<(), DcrError> {
 ///
 /// Uses the database as the single source of truth (no in-memory registry)
 pub async fn process_dcr_request(
-    repo: &mcpmux_storage::InboundClientRepository,
+    repo: &dyn mcpmux_storage::InboundClientRepository,
     request: DcrRequest,
 ) -> Result<DcrResponse, DcrError> {
     info!(
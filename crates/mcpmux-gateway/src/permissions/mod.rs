@@ -204,6 +204,10 @@ fn matches_glob(pattern: &str, text: &str) -> bool {
     if pattern == text {
         return true;
     }
+    // Without wildcards a pattern names exactly one item
+    if !pattern.contains('*') {
+        return false;
+    }
 
     // Simple wildcard matching
     let parts: Vec<&str> = pattern.split('*').collect();
@@ -254,6 +258,7 @@ mod tests {
         assert!(matches_glob("*.delete_*", "github.delete_branch"));
         assert!(matches_glob("*_list", "repos_list"));
         assert!(!matches_glob("github.*", "atlassian.get_page"));
+        assert!(!matches_glob("github", "github.delete_branch"));
     }
 
     #[test]
@@ -294,4 +299,103 @@ mod tests {
         assert!(filter.can_access_tool(&client_id, "github.create_issue"));
         assert!(!filter.can_access_tool(&client_id, "atlassian.get_page"));
     }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn name() -> impl Strategy<Value = String> {
+            "[a-z][a-z._]{0,11}"
+        }
+
+        fn patterns() -> impl Strategy<Value = Vec<String>> {
+            prop::collection::vec("[a-z._*]{1,10}", 0..4)
+        }
+
+        /// The same patterns for tools, prompts and resources
+        fn permission_set(include: Vec<String>, exclude: Vec<String>) -> PermissionSet {
+            PermissionSet {
+                id: Uuid::new_v4(),
+                tools_include: include.clone(),
+                tools_exclude: exclude.clone(),
+                prompts_include: include.clone(),
+                prompts_exclude: exclude.clone(),
+                resources_include: include,
+                resources_exclude: exclude,
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn exclude_always_wins(
+                include in patterns(),
+                mut exclude in patterns(),
+                name in name(),
+                wildcard in any::<bool>(),
+            ) {
+                exclude.push(if wildcard { "*".to_string() } else { name.clone() });
+                let set = permission_set(include, exclude);
+                prop_assert!(!set.allows_tool(&name));
+                prop_assert!(!set.allows_prompt(&name));
+                prop_assert!(!set.allows_resource(&name));
+            }
+
+            #[test]
+            fn empty_include_denies(exclude in patterns(), name in name()) {
+                let set = permission_set(Vec::new(), exclude);
+                prop_assert!(!set.allows_tool(&name));
+                prop_assert!(!set.allows_prompt(&name));
+                prop_assert!(!set.allows_resource(&name));
+            }
+
+            #[test]
+            fn allowed_means_included_and_not_excluded(
+                include in patterns(),
+                exclude in patterns(),
+                name in name(),
+            ) {
+                let included = include.iter().any(|p| matches_glob(p, &name));
+                let excluded = exclude.iter().any(|p| matches_glob(p, &name));
+                let set = permission_set(include, exclude);
+                let allowed = set.allows_tool(&name);
+                prop_assert_eq!(allowed, included && !excluded);
+                prop_assert_eq!(set.allows_prompt(&name), allowed);
+                prop_assert_eq!(set.allows_resource(&name), allowed);
+            }
+
+            #[test]
+            fn any_granted_set_allows(
+                sets in prop::collection::vec((patterns(), patterns()), 0..4),
+                name in name(),
+            ) {
+                let sets: Vec<PermissionSet> = sets
+                    .into_iter()
+                    .map(|(include, exclude)| permission_set(include, exclude))
+                    .collect();
+                let expected = sets.iter().any(|set| set.allows_tool(&name));
+
+                let mut filter = PermissionFilter::new();
+                let client_id = Uuid::new_v4();
+                filter.set_client_permissions(client_id, sets);
+                prop_assert_eq!(filter.can_access_tool(&client_id, &name), expected);
+                prop_assert!(!filter.can_access_tool(&Uuid::new_v4(), &name));
+            }
+
+            #[test]
+            fn literal_patterns_match_only_themselves(pattern in "[a-z._]{1,10}", name in name()) {
+                prop_assert_eq!(matches_glob(&pattern, &name), pattern == name);
+            }
+
+            #[test]
+            fn wildcards_match_what_they_surround(
+                prefix in "[a-z._]{0,5}",
+                middle in "[a-z._*]{0,5}",
+                suffix in "[a-z._]{0,5}",
+            ) {
+                let name = format!("{}{}{}", prefix, middle, suffix);
+                let pattern = format!("{}*{}", prefix, suffix);
+                prop_assert!(matches_glob(&pattern, &name));
+            }
+        }
+    }
 }
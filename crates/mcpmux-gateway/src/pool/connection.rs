@@ -11,20 +11,25 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
-use mcpmux_core::{CredentialRepository, OutboundOAuthRepository, ServerLogManager};
+use mcpmux_core::{
+    CredentialRepository, OutboundOAuthRepository, SecretResolverRegistry, ServerLogManager,
+    DEFAULT_CONNECT_TIMEOUT,
+};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use super::crash_loop::CrashLoopTracker;
 use super::features::{CachedFeatures, FeatureService};
-use super::instance::{DiscoveredFeatures, McpClientConnection, ServerInstance};
+use super::instance::{DiscoveredFeatures, McpClient, McpClientConnection, ServerInstance};
 use super::oauth::{OAuthInitResult, OutboundOAuthManager};
+use super::progress::ProgressRelay;
 use super::token::TokenService;
+use super::traffic::TrafficRecorder;
+use super::transport::resolution::resolve_secret_references;
 use super::transport::{
     ResolvedTransport, TransportConnectResult, TransportFactory, TransportType,
 };
-
-/// Default connection timeout
-const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+use crate::services::{FederationAdvertisement, FederationLoop};
 
 /// Result of a connection attempt
 #[derive(Debug)]
@@ -58,6 +63,12 @@ pub struct ConnectionService {
     log_manager: Option<Arc<ServerLogManager>>,
     connect_timeout: Duration,
     event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    secret_resolvers: Option<Arc<SecretResolverRegistry>>,
+    child_processes: Option<Arc<mcpmux_core::ChildProcessRegistry>>,
+    managed_runtimes: Option<Arc<mcpmux_core::ManagedRuntimes>>,
+    crash_loops: CrashLoopTracker,
+    traffic: TrafficRecorder,
+    progress: Arc<ProgressRelay>,
 }
 
 impl ConnectionService {
@@ -77,6 +88,12 @@ impl ConnectionService {
             log_manager: None,
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
             event_tx: None,
+            secret_resolvers: None,
+            child_processes: None,
+            managed_runtimes: None,
+            crash_loops: CrashLoopTracker::default(),
+            traffic: TrafficRecorder::default(),
+            progress: Arc::default(),
         }
     }
 
@@ -93,11 +110,107 @@ impl ConnectionService {
         self
     }
 
+    /// Connect timeout for servers that don't set their own
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = timeout;
         self
     }
 
+    /// Resolve `${env:..}` / `${keychain:..}` / vault references at connect time
+    pub fn with_secret_resolvers(mut self, resolvers: Arc<SecretResolverRegistry>) -> Self {
+        self.secret_resolvers = Some(resolvers);
+        self
+    }
+
+    /// Record spawned stdio servers so orphans can be reaped after a crash
+    pub fn with_child_processes(
+        mut self,
+        child_processes: Arc<mcpmux_core::ChildProcessRegistry>,
+    ) -> Self {
+        self.child_processes = Some(child_processes);
+        self
+    }
+
+    /// Let stdio servers use McpMux-installed Node.js/uv
+    pub fn with_managed_runtimes(
+        mut self,
+        managed_runtimes: Arc<mcpmux_core::ManagedRuntimes>,
+    ) -> Self {
+        self.managed_runtimes = Some(managed_runtimes);
+        self
+    }
+
+    /// Pass servers' progress on tool calls through `relay`, the one the
+    /// routing service registers the calls with
+    pub fn with_progress_relay(mut self, relay: Arc<ProgressRelay>) -> Self {
+        self.progress = relay;
+        self
+    }
+
+    /// Why a stdio server keeps exiting during startup, if it does
+    pub fn crash_diagnosis(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+    ) -> Option<mcpmux_core::DiagnosedCause> {
+        self.crash_loops.diagnosis(space_id, server_id)
+    }
+
+    /// Opt-in JSON-RPC traffic recording for backend servers
+    pub fn traffic(&self) -> &TrafficRecorder {
+        &self.traffic
+    }
+
+    /// Count an early exit; once the server is crash-looping, log and emit
+    /// the diagnosis and put its summary in the returned error
+    async fn handle_early_exit(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        error: String,
+        stderr: &[String],
+    ) -> String {
+        let Some(cause) = self
+            .crash_loops
+            .record_exit(space_id, server_id, &error, stderr)
+        else {
+            return error;
+        };
+
+        warn!(
+            "[ConnectionService] {}/{} is crash-looping ({} early exits): {}",
+            space_id,
+            server_id,
+            cause.exits,
+            cause.kind.as_str()
+        );
+        self.log_connection_event(
+            &space_id,
+            server_id,
+            mcpmux_core::LogLevel::Error,
+            format!(
+                "Server keeps exiting during startup: {}. {}",
+                cause.summary, cause.suggested_fix
+            ),
+            Some(serde_json::json!({
+                "kind": cause.kind,
+                "exits": cause.exits,
+                "evidence": &cause.evidence,
+            })),
+        )
+        .await;
+
+        let error = format!("{}. {}", cause.summary, cause.suggested_fix);
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(mcpmux_core::DomainEvent::ServerCrashLoop {
+                space_id,
+                server_id: server_id.to_string(),
+                cause,
+            });
+        }
+        error
+    }
+
     /// Get the OAuth manager for checking pending flows
     pub fn oauth_manager(&self) -> Arc<OutboundOAuthManager> {
         self.oauth_manager.clone()
@@ -173,6 +286,28 @@ impl ConnectionService {
             }
         }
 
+        // Resolve secret references last so the DCR URL can carry them too.
+        // Only the reference is ever logged, never the resolved value.
+        if let Some(resolvers) = &self.secret_resolvers {
+            if let Err(e) = resolve_secret_references(&mut final_config, resolvers).await {
+                warn!(
+                    "[ConnectionService] Secret resolution failed for {}/{}: {}",
+                    space_id, server_id, e
+                );
+                self.log_connection_event(
+                    &space_id,
+                    server_id,
+                    mcpmux_core::LogLevel::Error,
+                    format!("Secret resolution failed: {}", e),
+                    None,
+                )
+                .await;
+                return ConnectionResult::Failed {
+                    error: e.to_string(),
+                };
+            }
+        }
+
         info!(
             "[ConnectionService] Connecting {}/{} via {:?}",
             space_id,
@@ -184,6 +319,10 @@ impl ConnectionService {
         let transport_name = match &final_config {
             ResolvedTransport::Stdio { .. } => "STDIO",
             ResolvedTransport::Http { .. } => "HTTP",
+            ResolvedTransport::OpenApi { .. } => "OpenAPI",
+            ResolvedTransport::GraphQl { .. } => "GraphQL",
+            ResolvedTransport::Builtin { .. } => "Built-in",
+            ResolvedTransport::Wasm(_) => "WASI",
         };
         self.log_connection_event(
             &space_id,
@@ -202,13 +341,31 @@ impl ConnectionService {
             Arc::clone(&self.credential_repo),
             Arc::clone(&self.backend_oauth_repo),
             self.log_manager.clone(),
-            self.connect_timeout,
+            ctx.timeouts.connect_timeout(self.connect_timeout),
             self.event_tx.clone(),
+            self.child_processes.clone(),
+            self.managed_runtimes.clone(),
+            Some(self.traffic.server(space_id, server_id)),
+            Some(self.progress.clone()),
         );
 
         // Attempt connection
         match transport.connect().await {
             TransportConnectResult::Connected(client) => {
+                if let Err(e) = self.register_federation(space_id, server_id, &client) {
+                    self.log_connection_event(
+                        &space_id,
+                        server_id,
+                        mcpmux_core::LogLevel::Error,
+                        e.to_string(),
+                        None,
+                    )
+                    .await;
+                    return ConnectionResult::Failed {
+                        error: e.to_string(),
+                    };
+                }
+
                 // Discover and cache features
                 let features = match feature_service
                     .discover_and_cache(&space_id.to_string(), server_id, &client)
@@ -255,6 +412,7 @@ impl ConnectionService {
                 )
                 .await;
 
+                self.crash_loops.clear(space_id, server_id);
                 ConnectionResult::Connected {
                     reused: false,
                     features,
@@ -287,6 +445,12 @@ impl ConnectionService {
 
                 ConnectionResult::Failed { error }
             }
+            TransportConnectResult::Exited { error, stderr } => {
+                let error = self
+                    .handle_early_exit(space_id, server_id, error, &stderr)
+                    .await;
+                ConnectionResult::Failed { error }
+            }
         }
     }
 
@@ -339,6 +503,8 @@ impl ConnectionService {
         .await;
 
         instance.mark_connecting();
+        instance.set_timeouts(ctx.timeouts);
+        instance.set_config_hash(config.config_hash());
 
         // Create transport
         let transport = TransportFactory::create(
@@ -348,13 +514,24 @@ impl ConnectionService {
             Arc::clone(&self.credential_repo),
             Arc::clone(&self.backend_oauth_repo),
             self.log_manager.clone(),
-            self.connect_timeout,
+            ctx.timeouts.connect_timeout(self.connect_timeout),
             self.event_tx.clone(),
+            self.child_processes.clone(),
+            self.managed_runtimes.clone(),
+            Some(self.traffic.server(space_id, server_id)),
+            Some(self.progress.clone()),
         );
 
         // Attempt connection
         match transport.connect().await {
             TransportConnectResult::Connected(client) => {
+                if let Err(e) = self.register_federation(space_id, server_id, &client) {
+                    instance.mark_failed(e.to_string());
+                    return ConnectionResult::Failed {
+                        error: e.to_string(),
+                    };
+                }
+
                 // Discover and cache features
                 let features = match feature_service
                     .discover_and_cache(&space_id.to_string(), server_id, &client)
@@ -388,10 +565,12 @@ impl ConnectionService {
 
                 let connection = match config.transport_type() {
                     TransportType::Stdio => McpClientConnection::Stdio { client },
-                    TransportType::Http => McpClientConnection::Http { client },
+                    // Every other transport is remote
+                    _ => McpClientConnection::Http { client },
                 };
 
                 instance.mark_connected(discovered_features, connection);
+                self.crash_loops.clear(space_id, server_id);
 
                 info!(
                     "[ConnectionService] Connected {}/{} - {} features",
@@ -414,6 +593,13 @@ impl ConnectionService {
                 instance.mark_failed(error.clone());
                 ConnectionResult::Failed { error }
             }
+            TransportConnectResult::Exited { error, stderr } => {
+                let error = self
+                    .handle_early_exit(space_id, server_id, error, &stderr)
+                    .await;
+                instance.mark_failed(error.clone());
+                ConnectionResult::Failed { error }
+            }
         }
     }
 
@@ -441,6 +627,9 @@ impl ConnectionService {
         )
         .await;
 
+        // A disabled server starts counting crashes afresh
+        self.crash_loops.clear(space_id, server_id);
+
         // Clear tokens (keeps client_id for re-auth)
         self.token_service.clear_tokens(space_id, server_id).await?;
 
@@ -495,13 +684,15 @@ impl ConnectionService {
             TransportType::Http => ResolvedTransport::Http {
                 url: server_url.clone(),
                 headers: std::collections::HashMap::new(),
+                query: std::collections::HashMap::new(),
             },
-            TransportType::Stdio => {
+            _ => {
                 // Should not happen for OAuth, but fallback to Http if somehow we got here
-                warn!("[ConnectionService] Unexpected STDIO transport for OAuth reconnection, defaulting to HTTP");
+                warn!("[ConnectionService] Unexpected {:?} transport for OAuth reconnection, defaulting to HTTP", instance.transport_type);
                 ResolvedTransport::Http {
                     url: server_url.clone(),
                     headers: std::collections::HashMap::new(),
+                    query: std::collections::HashMap::new(),
                 }
             }
         };
@@ -514,13 +705,24 @@ impl ConnectionService {
             Arc::clone(&self.credential_repo),
             Arc::clone(&self.backend_oauth_repo),
             self.log_manager.clone(),
-            self.connect_timeout,
+            instance.timeouts().connect_timeout(self.connect_timeout),
             self.event_tx.clone(),
+            self.child_processes.clone(),
+            self.managed_runtimes.clone(),
+            Some(self.traffic.server(space_id, server_id)),
+            Some(self.progress.clone()),
         );
 
         // Attempt connection
         match transport.connect().await {
             TransportConnectResult::Connected(client) => {
+                if let Err(e) = self.register_federation(space_id, server_id, &client) {
+                    instance.mark_failed(e.to_string());
+                    return ConnectionResult::Failed {
+                        error: e.to_string(),
+                    };
+                }
+
                 // Discover and cache features
                 let features = match feature_service
                     .discover_and_cache(&space_id.to_string(), server_id, &client)
@@ -557,7 +759,8 @@ impl ConnectionService {
 
                 let connection = match config.transport_type() {
                     TransportType::Stdio => McpClientConnection::Stdio { client },
-                    TransportType::Http => McpClientConnection::Http { client },
+                    // Every other transport is remote
+                    _ => McpClientConnection::Http { client },
                 };
 
                 instance.mark_connected(discovered_features, connection);
@@ -584,13 +787,34 @@ impl ConnectionService {
                     auth_url: String::new(),
                 }
             }
-            TransportConnectResult::Failed(error) => {
+            TransportConnectResult::Failed(error)
+            | TransportConnectResult::Exited { error, .. } => {
                 instance.mark_failed(error.clone());
                 ConnectionResult::Failed { error }
             }
         }
     }
 
+    /// Record a backend that is another McpMux gateway, refusing a loop
+    ///
+    /// The client is dropped (closing the connection) when this fails.
+    fn register_federation(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        client: &McpClient,
+    ) -> Result<(), FederationLoop> {
+        let Some(advertisement) = client
+            .peer_info()
+            .and_then(|info| FederationAdvertisement::from_capabilities(&info.capabilities))
+        else {
+            return Ok(());
+        };
+        self.prefix_cache
+            .federation()
+            .register(&space_id.to_string(), server_id, advertisement)
+    }
+
     /// Handle OAuth required - initiate OAuth flow (only for manual connects, not auto-reconnect)
     async fn handle_oauth_required(
         &self,
@@ -3,6 +3,7 @@
 //! This module provides a context object that bundles per-connection parameters,
 //! reducing function signature complexity throughout the connection pipeline.
 
+use mcpmux_core::ServerTimeouts;
 use uuid::Uuid;
 
 use super::transport::ResolvedTransport;
@@ -36,6 +37,9 @@ pub struct ConnectionContext {
     /// - `true`: Don't start OAuth flow or open browser (background reconnection)
     /// - `false`: Full OAuth flow with browser if needed (user clicked Connect)
     pub auto_reconnect: bool,
+
+    /// Connect and request timeouts (unset = defaults)
+    pub timeouts: ServerTimeouts,
 }
 
 impl ConnectionContext {
@@ -46,6 +50,7 @@ impl ConnectionContext {
             server_id: server_id.into(),
             transport,
             auto_reconnect: false,
+            timeouts: ServerTimeouts::default(),
         }
     }
 
@@ -55,6 +60,12 @@ impl ConnectionContext {
         self
     }
 
+    /// Set the server's connect and request timeouts (builder pattern).
+    pub fn with_timeouts(mut self, timeouts: ServerTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     /// Convenience: create context for manual user-initiated connection.
     pub fn manual(
         space_id: Uuid,
@@ -0,0 +1,138 @@
+//! Crash-loop detection for stdio servers
+//!
+//! One early exit is often transient (a slow registry, a flaky network).
+//! A server that keeps dying within seconds of starting is broken, and
+//! retrying won't help until the user fixes something. Early exits are
+//! counted per server over a sliding window; once a server crosses the
+//! threshold its stderr is diagnosed ([`mcpmux_core::diagnose_startup_failure`])
+//! and the cause is kept until it connects or is disabled.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use mcpmux_core::{diagnose_startup_failure, DiagnosedCause};
+use uuid::Uuid;
+
+/// Early exits within [`CRASH_LOOP_WINDOW`] that make a crash loop
+pub const CRASH_LOOP_THRESHOLD: usize = 3;
+
+/// Window early exits are counted over
+pub const CRASH_LOOP_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+type Key = (Uuid, String);
+
+/// Early exits and diagnoses per (space, server)
+#[derive(Default)]
+pub struct CrashLoopTracker {
+    exits: DashMap<Key, VecDeque<Instant>>,
+    diagnoses: DashMap<Key, DiagnosedCause>,
+}
+
+impl CrashLoopTracker {
+    /// Record an early exit. Returns the diagnosis once the server is in a
+    /// crash loop.
+    pub fn record_exit(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        error: &str,
+        stderr: &[String],
+    ) -> Option<DiagnosedCause> {
+        self.record_exit_at(space_id, server_id, error, stderr, Instant::now())
+    }
+
+    fn record_exit_at(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        error: &str,
+        stderr: &[String],
+        now: Instant,
+    ) -> Option<DiagnosedCause> {
+        let key = (space_id, server_id.to_string());
+        let exits = {
+            let mut exits = self.exits.entry(key.clone()).or_default();
+            while exits
+                .front()
+                .is_some_and(|exit| now.duration_since(*exit) >= CRASH_LOOP_WINDOW)
+            {
+                exits.pop_front();
+            }
+            exits.push_back(now);
+            exits.len()
+        };
+        if exits < CRASH_LOOP_THRESHOLD {
+            return None;
+        }
+
+        let cause = diagnose_startup_failure(error, stderr, exits as u32);
+        self.diagnoses.insert(key, cause.clone());
+        Some(cause)
+    }
+
+    /// The diagnosis of a server in a crash loop
+    pub fn diagnosis(&self, space_id: Uuid, server_id: &str) -> Option<DiagnosedCause> {
+        self.diagnoses
+            .get(&(space_id, server_id.to_string()))
+            .map(|cause| cause.clone())
+    }
+
+    /// Forget a server's exits, once it connects or is disabled
+    pub fn clear(&self, space_id: Uuid, server_id: &str) {
+        let key = (space_id, server_id.to_string());
+        self.exits.remove(&key);
+        self.diagnoses.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpmux_core::FailureKind;
+
+    #[test]
+    fn diagnoses_repeated_early_exits() {
+        let tracker = CrashLoopTracker::default();
+        let space_id = Uuid::new_v4();
+        let stderr = vec!["Error: listen EADDRINUSE: address already in use :::8080".to_string()];
+        let start = Instant::now();
+
+        for i in 0..2 {
+            let at = start + Duration::from_secs(i * 10);
+            assert!(tracker
+                .record_exit_at(space_id, "web", "connection closed", &stderr, at)
+                .is_none());
+        }
+        let cause = tracker
+            .record_exit_at(
+                space_id,
+                "web",
+                "connection closed",
+                &stderr,
+                start + Duration::from_secs(20),
+            )
+            .unwrap();
+        assert_eq!(cause.kind, FailureKind::PortInUse);
+        assert_eq!(cause.exits, 3);
+        assert_eq!(tracker.diagnosis(space_id, "web"), Some(cause));
+        assert_eq!(tracker.diagnosis(space_id, "other"), None);
+
+        tracker.clear(space_id, "web");
+        assert_eq!(tracker.diagnosis(space_id, "web"), None);
+    }
+
+    #[test]
+    fn exits_outside_the_window_dont_count() {
+        let tracker = CrashLoopTracker::default();
+        let space_id = Uuid::new_v4();
+        let start = Instant::now();
+
+        for i in 0..5 {
+            let at = start + CRASH_LOOP_WINDOW * i;
+            assert!(tracker
+                .record_exit_at(space_id, "slow", "connection closed", &[], at)
+                .is_none());
+        }
+    }
+}
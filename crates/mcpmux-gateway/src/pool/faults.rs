@@ -0,0 +1,185 @@
+//! Fault injection for local testing
+//!
+//! Lets a developer make a healthy server misbehave at the routing layer:
+//! drop a share of its tool calls, delay them, or fail the next one with an
+//! expired OAuth token. Clients' retry handling and the gateway's own error
+//! paths (auth reconnects, timeouts, error results) can then be exercised
+//! without a broken server. Only debug builds accept faults; release builds
+//! refuse to set them, so routing never injects anything there. Faults are
+//! kept in memory and cleared on restart.
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use dashmap::DashMap;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Error of a call dropped by fault injection
+pub const DROPPED_MESSAGE: &str = "Request dropped by fault injection";
+
+/// Error of a call failed with an injected OAuth expiry. Matches the
+/// gateway's auth error detection, so it takes the reconnect-and-retry path.
+pub const OAUTH_EXPIRED_MESSAGE: &str = "401 Unauthorized: token expired (fault injection)";
+
+/// Faults injected into one server's tool calls
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FaultPlan {
+    /// Percentage of calls failed without reaching the server, 0 to 100
+    pub drop_percent: u8,
+    /// Delay before each call is sent, in milliseconds
+    pub delay_ms: u64,
+    /// Fail the next call as if its OAuth token had expired. Cleared once
+    /// it has fired.
+    pub expire_oauth: bool,
+}
+
+impl FaultPlan {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.drop_percent > 100 {
+            bail!("drop_percent must be between 0 and 100");
+        }
+        Ok(())
+    }
+
+    /// Whether a call drops, given a roll in `0..100`
+    fn drops(&self, roll: u8) -> bool {
+        roll < self.drop_percent
+    }
+}
+
+/// What happens to one tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    /// Fail without sending the call
+    Drop,
+    /// Fail as if the server rejected an expired token
+    ExpireOAuth,
+}
+
+/// Fault plans per (space, server)
+#[derive(Default)]
+pub struct FaultInjector {
+    plans: DashMap<(Uuid, String), FaultPlan>,
+}
+
+impl FaultInjector {
+    /// Whether this build accepts faults
+    pub fn is_available() -> bool {
+        cfg!(debug_assertions)
+    }
+
+    /// Replace a server's faults; an empty plan removes them
+    pub fn set(&self, space_id: Uuid, server_id: &str, plan: FaultPlan) -> Result<()> {
+        if !Self::is_available() {
+            bail!("Fault injection is only available in debug builds");
+        }
+        plan.validate()?;
+        let key = (space_id, server_id.to_string());
+        if plan.is_empty() {
+            self.plans.remove(&key);
+        } else {
+            self.plans.insert(key, plan);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, space_id: Uuid, server_id: &str) -> FaultPlan {
+        self.plans
+            .get(&(space_id, server_id.to_string()))
+            .map(|plan| plan.clone())
+            .unwrap_or_default()
+    }
+
+    /// Remove every server's faults
+    pub fn clear(&self) {
+        self.plans.clear();
+    }
+
+    /// Decide the faults of a call to `server_id`: how long to hold it, and
+    /// whether it fails instead of reaching the server
+    pub fn roll(&self, space_id: Uuid, server_id: &str) -> Option<(Duration, Option<Fault>)> {
+        if self.plans.is_empty() {
+            return None;
+        }
+        let mut plan = self.plans.get_mut(&(space_id, server_id.to_string()))?;
+        let fault = if std::mem::take(&mut plan.expire_oauth) {
+            Some(Fault::ExpireOAuth)
+        } else if plan.drops(rand::thread_rng().gen_range(0..100)) {
+            Some(Fault::Drop)
+        } else {
+            None
+        };
+        Some((plan.delay(), fault))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plans_drop_their_share_of_calls() {
+        let plan = FaultPlan {
+            drop_percent: 30,
+            ..Default::default()
+        };
+        assert_eq!((0..100).filter(|&roll| plan.drops(roll)).count(), 30);
+        assert!((0..100).all(|roll| !FaultPlan::default().drops(roll)));
+
+        let all = FaultPlan {
+            drop_percent: 100,
+            ..Default::default()
+        };
+        assert!((0..100).all(|roll| all.drops(roll)));
+        assert!(FaultPlan {
+            drop_percent: 101,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn oauth_expiry_fires_once() {
+        let faults = FaultInjector::default();
+        let space_id = Uuid::new_v4();
+        assert_eq!(faults.roll(space_id, "github"), None);
+
+        faults
+            .set(
+                space_id,
+                "github",
+                FaultPlan {
+                    delay_ms: 250,
+                    expire_oauth: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let delay = Duration::from_millis(250);
+        assert_eq!(
+            faults.roll(space_id, "github"),
+            Some((delay, Some(Fault::ExpireOAuth)))
+        );
+        assert_eq!(faults.roll(space_id, "github"), Some((delay, None)));
+        assert!(!faults.get(space_id, "github").expire_oauth);
+        assert_eq!(faults.roll(space_id, "slack"), None);
+
+        faults
+            .set(space_id, "github", FaultPlan::default())
+            .unwrap();
+        assert_eq!(faults.roll(space_id, "github"), None);
+    }
+}
@@ -56,11 +56,7 @@ pub fn convert_to_feature<T: ToServerFeature>(
     let name = item.name();
     let raw_json = item.to_raw_json();
 
-    let mut feature = match T::feature_type() {
-        FeatureType::Tool => ServerFeature::tool(space_id, server_id, &name),
-        FeatureType::Prompt => ServerFeature::prompt(space_id, server_id, &name),
-        FeatureType::Resource => ServerFeature::resource(space_id, server_id, &name),
-    };
+    let mut feature = ServerFeature::new(space_id, server_id, T::feature_type(), &name);
 
     if let Some(desc) = item.description() {
         feature = feature.with_description(desc);
@@ -8,18 +8,29 @@ use tracing::{debug, info, warn};
 
 use super::{convert_to_feature, resource_to_feature, CachedFeatures};
 use crate::pool::instance::McpClient;
-use mcpmux_core::ServerFeatureRepository;
+use crate::services::{is_meta_tool, PrefixCacheService};
+use mcpmux_core::{FeatureDiff, FeatureType, ServerFeature, ServerFeatureRepository};
 
 /// Handles feature discovery and caching from MCP clients
 pub struct FeatureDiscoveryService {
     feature_repo: Arc<dyn ServerFeatureRepository>,
+    /// Flattens the prefixes of federated gateways
+    prefix_cache: Option<Arc<PrefixCacheService>>,
 }
 
 impl FeatureDiscoveryService {
     const LIST_TIMEOUT: Duration = Duration::from_secs(10);
 
     pub fn new(feature_repo: Arc<dyn ServerFeatureRepository>) -> Self {
-        Self { feature_repo }
+        Self {
+            feature_repo,
+            prefix_cache: None,
+        }
+    }
+
+    pub fn with_prefix_cache(mut self, prefix_cache: Arc<PrefixCacheService>) -> Self {
+        self.prefix_cache = Some(prefix_cache);
+        self
     }
 
     async fn with_list_timeout<T, E, F>(label: &str, fut: F) -> Option<Result<T, E>>
@@ -52,6 +63,8 @@ impl FeatureDiscoveryService {
         );
 
         let mut discovered = CachedFeatures::default();
+        // Types whose list failed keep their cached features
+        let mut failed = Vec::new();
         let capabilities = client.peer_info().map(|info| info.capabilities.clone());
         let capabilities_known = capabilities.is_some();
         let has_tools = capabilities
@@ -84,8 +97,11 @@ impl FeatureDiscoveryService {
                         discovered.tools.len()
                     );
                 }
-                Some(Err(e)) => warn!("[FeatureDiscovery] Failed to list tools: {}", e),
-                None => {}
+                Some(Err(e)) => {
+                    warn!("[FeatureDiscovery] Failed to list tools: {}", e);
+                    failed.push(FeatureType::Tool);
+                }
+                None => failed.push(FeatureType::Tool),
             }
         } else {
             debug!(
@@ -105,8 +121,11 @@ impl FeatureDiscoveryService {
                         discovered.prompts.len()
                     );
                 }
-                Some(Err(e)) => warn!("[FeatureDiscovery] Failed to list prompts: {}", e),
-                None => {}
+                Some(Err(e)) => {
+                    warn!("[FeatureDiscovery] Failed to list prompts: {}", e);
+                    failed.push(FeatureType::Prompt);
+                }
+                None => failed.push(FeatureType::Prompt),
             }
         } else {
             debug!("[FeatureDiscovery] Skipping prompts/list: server explicitly did not advertise prompts capability");
@@ -124,31 +143,117 @@ impl FeatureDiscoveryService {
                         discovered.resources.len()
                     );
                 }
-                Some(Err(e)) => warn!("[FeatureDiscovery] Failed to list resources: {}", e),
-                None => {}
+                Some(Err(e)) => {
+                    warn!("[FeatureDiscovery] Failed to list resources: {}", e);
+                    failed.push(FeatureType::Resource);
+                }
+                None => failed.push(FeatureType::Resource),
             }
         } else {
             debug!("[FeatureDiscovery] Skipping resources/list: server explicitly did not advertise resources capability");
         }
 
-        // Cache all features in database
-        let all_features = discovered.all_features();
-        if !all_features.is_empty() {
-            if let Err(e) = self.feature_repo.upsert_many(&all_features).await {
-                warn!("[FeatureDiscovery] Failed to cache features: {}", e);
-            } else {
-                info!(
-                    "[FeatureDiscovery] Cached {} features for {}/{}",
-                    all_features.len(),
-                    space_id,
-                    server_id
-                );
+        if let Some(prefix_cache) = &self.prefix_cache {
+            if prefix_cache.federation().is_federated(space_id, server_id) {
+                Self::flatten_federated(prefix_cache, space_id, server_id, &mut discovered).await;
             }
         }
 
+        // Only write what changed since the last discovery
+        discovered.changes = self
+            .cache_changes(space_id, server_id, &discovered, &failed)
+            .await;
+
         Ok(discovered)
     }
 
+    /// Diff `discovered` against the cached features and write the
+    /// difference: added and updated features are upserted, removed ones
+    /// marked unavailable. Cached features of the `failed` types are left
+    /// alone, and a failed read of the cache rewrites everything.
+    async fn cache_changes(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        discovered: &CachedFeatures,
+        failed: &[FeatureType],
+    ) -> FeatureDiff {
+        let all_features = discovered.all_features();
+        let cached = match self.feature_repo.list_for_server(space_id, server_id).await {
+            Ok(cached) => cached
+                .into_iter()
+                .filter(|f| !failed.contains(&f.feature_type))
+                .collect(),
+            Err(e) => {
+                warn!("[FeatureDiscovery] Failed to read cached features: {}", e);
+                Vec::new()
+            }
+        };
+        let changes = FeatureDiff::between(&cached, &all_features);
+        if changes.is_empty() {
+            debug!(
+                "[FeatureDiscovery] Features of {}/{} unchanged",
+                space_id, server_id
+            );
+            return changes;
+        }
+
+        let removed = changes.removed.iter().cloned().map(|mut feature| {
+            feature.mark_unavailable();
+            feature
+        });
+        let changed: Vec<ServerFeature> = changes
+            .added
+            .iter()
+            .chain(&changes.updated)
+            .chain(&changes.held)
+            .cloned()
+            .chain(removed)
+            .collect();
+        if let Err(e) = self.feature_repo.upsert_many(&changed).await {
+            warn!("[FeatureDiscovery] Failed to cache features: {}", e);
+        }
+
+        info!(
+            "[FeatureDiscovery] Features of {}/{}: {} added, {} updated, {} removed, {} held",
+            space_id,
+            server_id,
+            changes.added.len(),
+            changes.updated.len(),
+            changes.removed.len(),
+            changes.held.len()
+        );
+        changes
+    }
+
+    /// Prepare the features of another McpMux gateway
+    ///
+    /// Its `mcpmux_*` tools manage that gateway, not this one, and would
+    /// shadow ours, so they are dropped. The inner prefixes of the rest are
+    /// claimed so they keep the names that gateway gave them.
+    async fn flatten_federated(
+        prefix_cache: &PrefixCacheService,
+        space_id: &str,
+        server_id: &str,
+        discovered: &mut CachedFeatures,
+    ) {
+        discovered.tools.retain(|t| !is_meta_tool(&t.feature_name));
+
+        let inner_prefixes = discovered
+            .tools
+            .iter()
+            .chain(discovered.prompts.iter())
+            .filter_map(|f| f.feature_name.split_once('_'))
+            .map(|(prefix, _)| prefix.to_string());
+        let claimed = prefix_cache
+            .claim_flat_prefixes(space_id, server_id, inner_prefixes)
+            .await;
+        info!(
+            "[FeatureDiscovery] Federated gateway {}/{}: flattened prefixes {:?}",
+            space_id, server_id, claimed
+        );
+    }
+
     /// Mark all features for a server as unavailable (on disconnect)
     pub async fn mark_unavailable(&self, space_id: &str, server_id: &str) -> Result<()> {
         self.feature_repo
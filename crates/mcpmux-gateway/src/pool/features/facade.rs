@@ -5,7 +5,9 @@ use std::sync::Arc;
 
 use crate::pool::instance::McpClient;
 use crate::services::PrefixCacheService;
-use mcpmux_core::{FeatureSetRepository, FeatureType, ServerFeature, ServerFeatureRepository};
+use mcpmux_core::{
+    FeatureSetRepository, FeatureType, ManagedPolicy, ServerFeature, ServerFeatureRepository,
+};
 
 use super::{
     CachedFeatures, FeatureDiscoveryService, FeatureResolutionService, FeatureRoutingService,
@@ -16,6 +18,7 @@ pub struct FeatureService {
     discovery: Arc<FeatureDiscoveryService>,
     resolution: Arc<FeatureResolutionService>,
     routing: Arc<FeatureRoutingService>,
+    managed_policy: Arc<ManagedPolicy>,
 }
 
 impl FeatureService {
@@ -24,7 +27,10 @@ impl FeatureService {
         feature_set_repo: Arc<dyn FeatureSetRepository>,
         prefix_cache: Arc<PrefixCacheService>,
     ) -> Self {
-        let discovery = Arc::new(FeatureDiscoveryService::new(feature_repo.clone()));
+        let discovery = Arc::new(
+            FeatureDiscoveryService::new(feature_repo.clone())
+                .with_prefix_cache(prefix_cache.clone()),
+        );
 
         let resolution = Arc::new(FeatureResolutionService::new(
             feature_repo.clone(),
@@ -41,7 +47,22 @@ impl FeatureService {
             discovery,
             resolution,
             routing,
+            managed_policy: Arc::default(),
+        }
+    }
+
+    /// Hide features the organization's policy excludes, whatever the
+    /// grants say
+    pub fn with_managed_policy(mut self, policy: Arc<ManagedPolicy>) -> Self {
+        self.managed_policy = policy;
+        self
+    }
+
+    fn enforce(&self, mut features: Vec<ServerFeature>) -> Vec<ServerFeature> {
+        if self.managed_policy.is_active() {
+            features.retain(|f| self.managed_policy.allows_feature(f));
         }
+        features
     }
 
     // Delegate to FeatureDiscoveryService
@@ -70,9 +91,11 @@ impl FeatureService {
         space_id: &str,
         feature_set_ids: &[String],
     ) -> Result<Vec<ServerFeature>> {
-        self.resolution
+        let features = self
+            .resolution
             .resolve_feature_sets(space_id, feature_set_ids, None)
-            .await
+            .await?;
+        Ok(self.enforce(features))
     }
 
     /// Get all available features for a space (optionally filtered by type)
@@ -81,9 +104,11 @@ impl FeatureService {
         space_id: &str,
         filter_type: Option<FeatureType>,
     ) -> Result<Vec<ServerFeature>> {
-        self.resolution
+        let features = self
+            .resolution
             .get_all_features_for_space(space_id, filter_type)
-            .await
+            .await?;
+        Ok(self.enforce(features))
     }
 
     // Type-specific helpers
@@ -92,9 +117,11 @@ impl FeatureService {
         space_id: &str,
         feature_set_ids: &[String],
     ) -> Result<Vec<ServerFeature>> {
-        self.resolution
+        let features = self
+            .resolution
             .resolve_feature_sets(space_id, feature_set_ids, Some(FeatureType::Tool))
-            .await
+            .await?;
+        Ok(self.enforce(features))
     }
 
     pub async fn get_prompts_for_grants(
@@ -102,9 +129,11 @@ impl FeatureService {
         space_id: &str,
         feature_set_ids: &[String],
     ) -> Result<Vec<ServerFeature>> {
-        self.resolution
+        let features = self
+            .resolution
             .resolve_feature_sets(space_id, feature_set_ids, Some(FeatureType::Prompt))
-            .await
+            .await?;
+        Ok(self.enforce(features))
     }
 
     pub async fn get_resources_for_grants(
@@ -112,9 +141,11 @@ impl FeatureService {
         space_id: &str,
         feature_set_ids: &[String],
     ) -> Result<Vec<ServerFeature>> {
-        self.resolution
+        let features = self
+            .resolution
             .resolve_feature_sets(space_id, feature_set_ids, Some(FeatureType::Resource))
-            .await
+            .await?;
+        Ok(self.enforce(features))
     }
 
     // Delegate to FeatureRoutingService (with type-specific helpers)
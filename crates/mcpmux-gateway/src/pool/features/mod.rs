@@ -15,7 +15,7 @@ pub use facade::FeatureService;
 pub use resolution::FeatureResolutionService;
 pub use routing::FeatureRoutingService;
 
-use mcpmux_core::ServerFeature;
+use mcpmux_core::{FeatureDiff, ServerFeature};
 
 /// Discovered features from an MCP server connection
 #[derive(Debug, Clone, Default, serde::Serialize)]
@@ -23,6 +23,9 @@ pub struct CachedFeatures {
     pub tools: Vec<ServerFeature>,
     pub prompts: Vec<ServerFeature>,
     pub resources: Vec<ServerFeature>,
+    /// How these differ from what was cached before discovery
+    #[serde(skip)]
+    pub changes: FeatureDiff,
 }
 
 impl CachedFeatures {
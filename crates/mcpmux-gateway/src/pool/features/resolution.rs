@@ -7,8 +7,8 @@ use tracing::{debug, warn};
 
 use crate::services::PrefixCacheService;
 use mcpmux_core::{
-    FeatureSet, FeatureSetRepository, FeatureType, MemberMode, MemberType, ServerFeature,
-    ServerFeatureRepository,
+    AnnotationHint, FeatureSet, FeatureSetRepository, FeatureType, MemberMode, MemberType,
+    ServerFeature, ServerFeatureRepository,
 };
 
 /// Helper to apply include/exclude mode (DRY)
@@ -21,6 +21,8 @@ fn apply_mode_to_set(
     match mode {
         MemberMode::Include => allowed.extend(feature_ids),
         MemberMode::Exclude => excluded.extend(feature_ids),
+        // A mode this build doesn't know grants nothing
+        _ => {}
     }
 }
 
@@ -63,11 +65,8 @@ impl FeatureResolutionService {
 
         // Enrich with prefixes
         for feature in &mut result {
-            let prefix = self
-                .prefix_cache
-                .get_prefix_for_server(space_id, &feature.server_id)
-                .await;
-            feature.server_alias = Some(prefix);
+            let alias = self.prefix_cache.alias_for_feature(space_id, feature).await;
+            feature.server_alias = Some(alias);
         }
 
         Ok(result)
@@ -164,11 +163,8 @@ impl FeatureResolutionService {
 
         // Enrich with prefixes
         for feature in &mut result {
-            let prefix = self
-                .prefix_cache
-                .get_prefix_for_server(space_id, &feature.server_id)
-                .await;
-            feature.server_alias = Some(prefix);
+            let alias = self.prefix_cache.alias_for_feature(space_id, feature).await;
+            feature.server_alias = Some(alias);
         }
 
         Ok(result)
@@ -192,6 +188,24 @@ impl FeatureResolutionService {
                         excluded,
                     );
                 }
+                MemberType::Annotation => {
+                    let Some(hint) = AnnotationHint::parse(&member.member_id) else {
+                        warn!(
+                            "[FeatureResolution] Skipping unknown annotation hint {} in FeatureSet {}",
+                            member.member_id, feature_set.id
+                        );
+                        continue;
+                    };
+                    apply_mode_to_set(
+                        member.mode,
+                        all_features
+                            .iter()
+                            .filter(|f| f.has_hint(hint))
+                            .map(|f| f.id.to_string()),
+                        allowed,
+                        excluded,
+                    );
+                }
                 MemberType::FeatureSet => {
                     // Composition: recurse into the nested FS, walking its
                     // members the same way. Both Default and Custom sets
@@ -220,6 +234,13 @@ impl FeatureResolutionService {
                         .await?;
                     }
                 }
+                _ => {
+                    warn!(
+                        "[FeatureResolution] Skipping member of unknown type {} in FeatureSet {}",
+                        member.member_type.as_str(),
+                        feature_set.id
+                    );
+                }
             }
         }
         Ok(())
@@ -0,0 +1,167 @@
+//! Backend health tracking
+//!
+//! A connected server can stop answering long before a tool call fails:
+//! a wedged stdio process, a remote endpoint timing out. The pool pings every
+//! connected instance on an interval and keeps a rolling window of the
+//! results per instance, so the UI can flag a server that is connected but
+//! unhealthy.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// How often connected instances are pinged
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a ping may take before it counts as a failure
+pub const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of recent pings the stats are computed over
+const WINDOW_SIZE: usize = 20;
+
+/// Consecutive failed pings after which a server is unhealthy
+const UNHEALTHY_AFTER_FAILURES: u32 = 2;
+
+/// Error rate over the window above which a server is unhealthy
+const UNHEALTHY_ERROR_RATE: f64 = 0.5;
+
+/// Rolling ping results for one instance
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    /// Latency of each recent ping, `None` for a failed one
+    samples: VecDeque<Option<Duration>>,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    last_checked_at: Option<DateTime<Utc>>,
+}
+
+impl HealthTracker {
+    pub fn record_success(&mut self, latency: Duration) {
+        self.push(Some(latency));
+        self.consecutive_failures = 0;
+        self.last_error = None;
+    }
+
+    pub fn record_failure(&mut self, error: impl Into<String>) {
+        self.push(None);
+        self.consecutive_failures += 1;
+        self.last_error = Some(error.into());
+    }
+
+    fn push(&mut self, sample: Option<Duration>) {
+        if self.samples.len() == WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.last_checked_at = Some(Utc::now());
+    }
+
+    /// Stats over the current window
+    pub fn snapshot(&self) -> ServerHealth {
+        let mut latencies: Vec<Duration> = self.samples.iter().flatten().copied().collect();
+        latencies.sort();
+        let failures = self.samples.len() - latencies.len();
+        let error_rate = if self.samples.is_empty() {
+            0.0
+        } else {
+            failures as f64 / self.samples.len() as f64
+        };
+        let avg_latency_ms = (!latencies.is_empty()).then(|| {
+            let total: Duration = latencies.iter().sum();
+            (total / latencies.len() as u32).as_millis() as u64
+        });
+        let p95_latency_ms = (!latencies.is_empty()).then(|| {
+            let index = (latencies.len() * 95).div_ceil(100) - 1;
+            latencies[index].as_millis() as u64
+        });
+
+        ServerHealth {
+            healthy: self.consecutive_failures < UNHEALTHY_AFTER_FAILURES
+                && error_rate <= UNHEALTHY_ERROR_RATE,
+            checks: self.samples.len(),
+            error_rate,
+            avg_latency_ms,
+            p95_latency_ms,
+            last_latency_ms: self
+                .samples
+                .back()
+                .copied()
+                .flatten()
+                .map(|latency| latency.as_millis() as u64),
+            consecutive_failures: self.consecutive_failures,
+            last_error: self.last_error.clone(),
+            last_checked_at: self.last_checked_at,
+        }
+    }
+}
+
+/// Health of a connected server, from its recent pings
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerHealth {
+    /// False once pings keep failing, even though the connection is up
+    pub healthy: bool,
+    /// Pings in the window
+    pub checks: usize,
+    /// Share of failed pings in the window (0.0 - 1.0)
+    pub error_rate: f64,
+    pub avg_latency_ms: Option<u64>,
+    pub p95_latency_ms: Option<u64>,
+    /// Latency of the most recent ping, `None` if it failed
+    pub last_latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_rolling_stats() {
+        let mut tracker = HealthTracker::default();
+        assert!(tracker.snapshot().healthy);
+        assert_eq!(tracker.snapshot().avg_latency_ms, None);
+
+        for ms in 1..=20 {
+            tracker.record_success(Duration::from_millis(ms * 10));
+        }
+        let health = tracker.snapshot();
+        assert_eq!(health.checks, 20);
+        assert_eq!(health.avg_latency_ms, Some(105));
+        assert_eq!(health.p95_latency_ms, Some(190));
+        assert_eq!(health.last_latency_ms, Some(200));
+        assert_eq!(health.error_rate, 0.0);
+
+        // The window stays at its size; old samples fall out
+        tracker.record_failure("timed out");
+        let health = tracker.snapshot();
+        assert_eq!(health.checks, 20);
+        assert_eq!(health.error_rate, 0.05);
+        assert_eq!(health.avg_latency_ms, Some(110));
+        assert_eq!(health.last_latency_ms, None);
+        assert!(health.healthy);
+    }
+
+    #[test]
+    fn unhealthy_after_repeated_failures() {
+        let mut tracker = HealthTracker::default();
+        tracker.record_success(Duration::from_millis(5));
+        tracker.record_failure("timed out");
+        assert!(tracker.snapshot().healthy);
+
+        tracker.record_failure("connection closed");
+        let health = tracker.snapshot();
+        assert!(!health.healthy);
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.last_error.as_deref(), Some("connection closed"));
+
+        tracker.record_success(Duration::from_millis(5));
+        let health = tracker.snapshot();
+        assert_eq!(health.consecutive_failures, 0);
+        // Still 2 of 4 failed: at the threshold, not over it
+        assert!(health.healthy);
+    }
+}
@@ -0,0 +1,119 @@
+//! Pool stats history
+//!
+//! [`PoolStats`] and [`ServerHealth`](super::ServerHealth) only describe the
+//! pool right now. After every health check round the pool also records a
+//! snapshot of both into a fixed-size ring buffer, so the UI can chart
+//! connections, latency and errors over the last few hours.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::instance::InstanceState;
+use super::service::PoolStats;
+
+/// Snapshots kept: three hours at the default health check interval
+pub const STATS_HISTORY_SIZE: usize = 360;
+
+/// Pool stats at one point in time
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStatsSnapshot {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub stats: PoolStats,
+    /// Every instance in the pool when the snapshot was taken
+    pub servers: Vec<ServerStatsSnapshot>,
+}
+
+/// One instance's state and ping stats at the time of a snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerStatsSnapshot {
+    pub space_id: Uuid,
+    pub server_id: String,
+    pub state: InstanceState,
+    pub healthy: bool,
+    /// Latency of the most recent ping, `None` if it failed or none ran
+    pub latency_ms: Option<u64>,
+    pub avg_latency_ms: Option<u64>,
+    /// Share of failed pings in the health window (0.0 - 1.0)
+    pub error_rate: f64,
+    pub consecutive_failures: u32,
+    pub requests_served: u64,
+}
+
+/// Ring buffer of the most recent snapshots, oldest first
+#[derive(Debug)]
+pub struct StatsHistory {
+    snapshots: VecDeque<PoolStatsSnapshot>,
+    capacity: usize,
+}
+
+impl Default for StatsHistory {
+    fn default() -> Self {
+        Self::with_capacity(STATS_HISTORY_SIZE)
+    }
+}
+
+impl StatsHistory {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add a snapshot, dropping the oldest once full
+    pub fn push(&mut self, snapshot: PoolStatsSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Snapshots taken at or after `since` (all of them for `None`), oldest first
+    pub fn since(&self, since: Option<DateTime<Utc>>) -> Vec<PoolStatsSnapshot> {
+        self.snapshots
+            .iter()
+            .filter(|snapshot| since.is_none_or(|since| snapshot.timestamp >= since))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(connected: usize) -> PoolStatsSnapshot {
+        PoolStatsSnapshot {
+            timestamp: Utc::now(),
+            stats: PoolStats {
+                connected_instances: connected,
+                ..Default::default()
+            },
+            servers: vec![],
+        }
+    }
+
+    #[test]
+    fn keeps_the_most_recent_snapshots() {
+        let mut history = StatsHistory::with_capacity(3);
+        for connected in 1..=5 {
+            history.push(snapshot(connected));
+        }
+        let connected: Vec<usize> = history
+            .since(None)
+            .iter()
+            .map(|s| s.stats.connected_instances)
+            .collect();
+        assert_eq!(connected, vec![3, 4, 5]);
+
+        let future = Utc::now() + chrono::Duration::seconds(60);
+        assert!(history.since(Some(future)).is_empty());
+    }
+}
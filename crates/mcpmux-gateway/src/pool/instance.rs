@@ -8,7 +8,7 @@ use std::time::Instant;
 
 use std::sync::Arc;
 
-use mcpmux_core::{DomainEvent, LogLevel, LogSource, ServerLog, ServerLogManager};
+use mcpmux_core::{DomainEvent, LogLevel, LogSource, ServerLog, ServerLogManager, ServerTimeouts};
 use parking_lot::RwLock;
 use rmcp::model::{ClientCapabilities, ClientInfo, Implementation, LoggingLevel};
 use rmcp::service::{NotificationContext, RunningService};
@@ -17,6 +17,9 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use super::health::{HealthTracker, ServerHealth};
+use super::progress::ProgressRelay;
+
 // Re-export TransportType from mcpmux-core as the single source of truth
 pub use mcpmux_core::TransportType;
 
@@ -31,6 +34,7 @@ pub struct McpClientHandler {
     space_id: Uuid,
     event_tx: Option<tokio::sync::broadcast::Sender<DomainEvent>>,
     log_manager: Option<Arc<ServerLogManager>>,
+    progress: Option<Arc<ProgressRelay>>,
 }
 
 impl std::fmt::Debug for McpClientHandler {
@@ -59,9 +63,16 @@ impl McpClientHandler {
             space_id,
             event_tx,
             log_manager,
+            progress: None,
         }
     }
 
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Convert MCP protocol LoggingLevel to our internal LogLevel
     fn convert_logging_level(level: &LoggingLevel) -> LogLevel {
         match level {
@@ -211,6 +222,20 @@ impl rmcp::ClientHandler for McpClientHandler {
         }
     }
 
+    fn on_progress(
+        &self,
+        params: rmcp::model::ProgressNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        // Progress on a routed tool call goes back to the client that made it
+        let progress = self.progress.clone();
+        async move {
+            if let Some(progress) = progress {
+                progress.forward(params).await;
+            }
+        }
+    }
+
     fn on_logging_message(
         &self,
         params: rmcp::model::LoggingMessageNotificationParam,
@@ -282,6 +307,38 @@ impl InstanceKey {
             description: format!("http:{}", url),
         }
     }
+
+    /// Create instance key for an OpenAPI bridge.
+    pub fn openapi(space_id: Uuid, spec_url: &str) -> Self {
+        Self {
+            space_id,
+            description: format!("openapi:{}", spec_url),
+        }
+    }
+
+    /// Create instance key for a GraphQL bridge.
+    pub fn graphql(space_id: Uuid, endpoint: &str) -> Self {
+        Self {
+            space_id,
+            description: format!("graphql:{}", endpoint),
+        }
+    }
+
+    /// Create instance key for a WASI module.
+    pub fn wasm(space_id: Uuid, module: &str) -> Self {
+        Self {
+            space_id,
+            description: format!("wasm:{}", module),
+        }
+    }
+
+    /// Create instance key for the built-in utility server.
+    pub fn builtin(space_id: Uuid) -> Self {
+        Self {
+            space_id,
+            description: "builtin:utilities".to_string(),
+        }
+    }
 }
 
 /// Connection state for a server instance.
@@ -353,6 +410,13 @@ pub struct ServerInstance {
     pub stats: RwLock<InstanceStats>,
     /// Discovered features (populated after connection)
     pub features: RwLock<Option<DiscoveredFeatures>>,
+    /// Rolling ping results for the current connection
+    health: RwLock<HealthTracker>,
+    /// Connect and request timeouts from the last connect
+    timeouts: RwLock<ServerTimeouts>,
+    /// [`ResolvedTransport::config_hash`](super::transport::ResolvedTransport::config_hash)
+    /// of the transport from the last connect
+    config_hash: RwLock<Option<u64>>,
     /// The actual MCP client connection
     client: RwLock<Option<McpClientConnection>>,
 }
@@ -384,6 +448,9 @@ impl ServerInstance {
             transport_type,
             stats: RwLock::new(InstanceStats::default()),
             features: RwLock::new(None),
+            health: RwLock::new(HealthTracker::default()),
+            timeouts: RwLock::new(ServerTimeouts::default()),
+            config_hash: RwLock::new(None),
             client: RwLock::new(None),
         }
     }
@@ -414,6 +481,7 @@ impl ServerInstance {
         stats.last_error = None;
 
         *self.features.write() = Some(features);
+        *self.health.write() = HealthTracker::default();
         *self.client.write() = Some(connection);
     }
 
@@ -443,6 +511,41 @@ impl ServerInstance {
         stats.last_error = Some(error.to_string());
     }
 
+    /// Record a ping round-trip.
+    pub fn record_ping_success(&self, latency: std::time::Duration) {
+        self.health.write().record_success(latency);
+    }
+
+    /// Record a failed or timed-out ping.
+    pub fn record_ping_failure(&self, error: &str) {
+        self.health.write().record_failure(error);
+    }
+
+    /// Health stats from recent pings.
+    pub fn health(&self) -> ServerHealth {
+        self.health.read().snapshot()
+    }
+
+    /// Timeouts this server was last connected with.
+    pub fn timeouts(&self) -> ServerTimeouts {
+        *self.timeouts.read()
+    }
+
+    /// Remember the timeouts for reconnects and requests.
+    pub fn set_timeouts(&self, timeouts: ServerTimeouts) {
+        *self.timeouts.write() = timeouts;
+    }
+
+    /// Hash of the transport this server was last connected with.
+    pub fn config_hash(&self) -> Option<u64> {
+        *self.config_hash.read()
+    }
+
+    /// Remember the transport hash, to tell later whether it changed.
+    pub fn set_config_hash(&self, hash: u64) {
+        *self.config_hash.write() = Some(hash);
+    }
+
     /// Get discovered features.
     pub fn get_features(&self) -> Option<DiscoveredFeatures> {
         self.features.read().clone()
@@ -470,6 +573,15 @@ impl ServerInstance {
         guard.as_ref().and_then(|conn| conn.client().map(f))
     }
 
+    /// Detach the MCP client connection, leaving the instance disconnected.
+    ///
+    /// The caller owns the returned connection and decides how to close it;
+    /// dropping it kills a stdio child without waiting for it to exit.
+    pub fn take_connection(&self) -> Option<McpClientConnection> {
+        self.stats.write().state = InstanceState::Disconnected;
+        self.client.write().take()
+    }
+
     /// Get the server URL from the instance key (for HTTP/SSE transports).
     /// Returns None for STDIO transports.
     pub fn get_url(&self) -> Option<String> {
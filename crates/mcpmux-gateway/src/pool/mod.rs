@@ -12,21 +12,37 @@
 
 mod connection;
 mod context;
+mod crash_loop;
 mod credential_store;
+mod faults;
 mod features;
+mod health;
+mod history;
 mod instance;
 mod oauth;
 mod oauth_utils;
+mod progress;
 mod routing;
 mod server_manager;
 mod service;
 mod service_factory;
 mod token;
+mod traffic;
 pub mod transport;
 
 // Context
 pub use context::ConnectionContext;
 
+// Health checks
+pub use health::{ServerHealth, HEALTH_CHECK_INTERVAL};
+pub use history::{PoolStatsSnapshot, ServerStatsSnapshot, STATS_HISTORY_SIZE};
+
+// Fault injection
+pub use faults::{Fault, FaultInjector, FaultPlan, DROPPED_MESSAGE, OAUTH_EXPIRED_MESSAGE};
+
+// Traffic recording
+pub use traffic::{ServerTraffic, TrafficDirection, TrafficFrame, TrafficRecorder};
+
 // Instance types
 pub use instance::{
     DiscoveredFeatures, InstanceKey, InstanceState, McpClient, McpClientConnection,
@@ -39,10 +55,15 @@ pub use oauth::{
     OAuthCallback, OAuthCompleteEvent, OAuthInitResult, OAuthTokenInfo, OutboundOAuthManager,
 };
 
+// Progress forwarding
+pub use progress::{ProgressRelay, ProgressSink};
+
 // SOLID Services
 pub use connection::{ConnectionResult, ConnectionService};
 pub use features::{CachedFeatures, FeatureService};
-pub use routing::{RoutedPrompt, RoutedResource, RoutedTool, RoutingService};
+pub use routing::{
+    InvalidToolArguments, RoutedPrompt, RoutedResource, RoutedTool, RoutingService, ToolCaller,
+};
 pub use service::{InstalledServerInfo, PoolService, PoolStats, ReconnectResult};
 pub use token::TokenService;
 pub use transport::{ResolvedTransport, Transport, TransportConnectResult, TransportFactory};
@@ -141,8 +141,8 @@ pub struct OutboundOAuthManager {
 struct CallbackServerState {
     /// Port the server is listening on
     port: u16,
-    /// Shutdown signal sender
-    _shutdown_tx: tokio::sync::watch::Sender<bool>,
+    /// Stops the server when dropped
+    _shutdown: tokio_util::sync::DropGuard,
 }
 
 impl OutboundOAuthManager {
@@ -454,6 +454,15 @@ impl OutboundOAuthManager {
             port, port_source
         );
 
+        // Also listen on [::1], for browsers and proxies that rewrite the
+        // redirect to `localhost` on IPv6-first systems. Best effort: the
+        // redirect URI itself names 127.0.0.1.
+        let mut listeners = vec![listener];
+        match TcpListener::bind((std::net::Ipv6Addr::LOCALHOST, port)).await {
+            Ok(l) => listeners.push(l),
+            Err(e) => debug!("[OAuth] Not listening on [::1]:{}: {}", port, e),
+        }
+
         // Persist the port for future runs (if it's not already persisted at this value)
         if persisted_port != Some(port) {
             if let Some(ref settings) = self.settings_repo {
@@ -465,8 +474,8 @@ impl OutboundOAuthManager {
             }
         }
 
-        // Create shutdown channel
-        let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+        // Cancelled when the server state is dropped
+        let shutdown = tokio_util::sync::CancellationToken::new();
 
         // Clone pending_by_state for the handler
         let pending_map = self.pending_by_state.clone();
@@ -591,21 +600,19 @@ impl OutboundOAuthManager {
             .with_state(pending_map);
 
         // Spawn persistent server task
+        let stop = shutdown.clone();
         tokio::spawn(async move {
-            let server = axum::serve(listener, app).with_graceful_shutdown(async move {
-                let _ = shutdown_rx.changed().await;
-                info!("[OAuth] Shared callback server shutting down");
-            });
-
-            if let Err(e) = server.await {
+            let limiter = crate::server::Limiter::new(&Default::default());
+            if let Err(e) = crate::server::serve_all(listeners, app, limiter, stop).await {
                 error!("[OAuth] Shared callback server error: {}", e);
             }
+            info!("[OAuth] Shared callback server shut down");
         });
 
         // Store server state
         *server_guard = Some(CallbackServerState {
             port,
-            _shutdown_tx: shutdown_tx,
+            _shutdown: shutdown.drop_guard(),
         });
 
         Ok(port)
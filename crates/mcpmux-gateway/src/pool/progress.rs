@@ -0,0 +1,268 @@
+//! Progress relay: forward a backend's progress during a tool call to the
+//! client that made the call.
+//!
+//! Backends that stream a long result (a large file read, a slow search)
+//! report it as `notifications/progress`, often with the partial output in
+//! `message`. A backend connection is shared by every client of the Space,
+//! so each routed call gets its own gateway progress token. When the backend
+//! reports progress against that token, the notification is passed on to the
+//! calling client under the client's own token.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use rmcp::model::{Meta, ProgressNotificationParam, ProgressToken};
+use rmcp::service::Peer;
+use rmcp::RoleServer;
+use serde_json::Value;
+use tracing::debug;
+use uuid::Uuid;
+
+/// `_meta` key that carries a request's progress token
+const PROGRESS_TOKEN_KEY: &str = "progressToken";
+
+/// Calls waiting for progress, by the token the gateway gave the backend.
+///
+/// One relay is shared by a pool's backend connections, which receive the
+/// progress, and its routing service, which registers the calls.
+#[derive(Default)]
+pub struct ProgressRelay {
+    routes: DashMap<String, ProgressSink>,
+}
+
+impl ProgressRelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start forwarding progress reported against a new token to `sink`
+    pub(crate) fn register(self: &Arc<Self>, sink: &ProgressSink) -> ProgressRoute {
+        let token = format!("mcpmux-{}", Uuid::new_v4());
+        self.routes.insert(token.clone(), sink.clone());
+        ProgressRoute {
+            relay: self.clone(),
+            token,
+        }
+    }
+
+    /// Pass a backend's progress notification on to the client whose call
+    /// it belongs to. Progress for calls that already returned is dropped.
+    pub(crate) async fn forward(&self, mut params: ProgressNotificationParam) {
+        let token = match serde_json::to_value(&params.progress_token) {
+            Ok(Value::String(token)) => token,
+            _ => return,
+        };
+        // Clone out of the map so no lock is held across the send
+        let Some(sink) = self.routes.get(&token).map(|sink| sink.clone()) else {
+            return;
+        };
+        params.progress_token = sink.token;
+        if let Err(e) = sink.peer.notify_progress(params).await {
+            debug!("[ProgressRelay] Failed to forward progress: {}", e);
+        }
+    }
+}
+
+/// The client a call's progress goes to, and the token it asked for
+#[derive(Clone)]
+pub struct ProgressSink {
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+}
+
+impl std::fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressSink")
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+impl ProgressSink {
+    /// A sink for a request whose `_meta` is `meta`, or `None` if the client
+    /// didn't ask for progress
+    pub fn from_request(peer: &Peer<RoleServer>, meta: &Meta) -> Option<Self> {
+        let token = meta.0.get(PROGRESS_TOKEN_KEY).cloned()?;
+        let token = serde_json::from_value(token).ok()?;
+        Some(Self {
+            peer: peer.clone(),
+            token,
+        })
+    }
+}
+
+/// A call's registration with the relay; progress stops being forwarded
+/// when it's dropped
+pub(crate) struct ProgressRoute {
+    relay: Arc<ProgressRelay>,
+    token: String,
+}
+
+impl ProgressRoute {
+    /// Put the token in the `_meta` of the backend request
+    pub(crate) fn attach(&self, meta: &mut Meta) {
+        meta.0.insert(
+            PROGRESS_TOKEN_KEY.to_string(),
+            Value::String(self.token.clone()),
+        );
+    }
+}
+
+impl Drop for ProgressRoute {
+    fn drop(&mut self) {
+        self.relay.routes.remove(&self.token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rmcp::model::{
+        ClientCapabilities, ClientInfo, Implementation, ServerCapabilities, ServerInfo,
+    };
+    use rmcp::service::{NotificationContext, RunningService};
+    use rmcp::{RoleClient, ServerHandler, ServiceExt};
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    struct Backend;
+
+    impl ServerHandler for Backend {
+        fn get_info(&self) -> ServerInfo {
+            ServerInfo::new(ServerCapabilities::default())
+        }
+    }
+
+    /// A client of the gateway, recording the progress it's sent
+    struct Client(mpsc::UnboundedSender<ProgressNotificationParam>);
+
+    impl rmcp::ClientHandler for Client {
+        fn get_info(&self) -> ClientInfo {
+            ClientInfo::new(
+                ClientCapabilities::default(),
+                Implementation::new("progress-test", "1.0.0"),
+            )
+        }
+
+        fn on_progress(
+            &self,
+            params: ProgressNotificationParam,
+            _context: NotificationContext<RoleClient>,
+        ) -> impl std::future::Future<Output = ()> + Send + '_ {
+            let _ = self.0.send(params);
+            async {}
+        }
+    }
+
+    struct Connected {
+        server: RunningService<RoleServer, Backend>,
+        _client: RunningService<RoleClient, Client>,
+        progress: mpsc::UnboundedReceiver<ProgressNotificationParam>,
+    }
+
+    impl Connected {
+        async fn start() -> Self {
+            let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+            let (tx, progress) = mpsc::unbounded_channel();
+            let (server, client) =
+                tokio::join!(Backend.serve(server_io), Client(tx).serve(client_io));
+            Self {
+                server: server.unwrap(),
+                _client: client.unwrap(),
+                progress,
+            }
+        }
+
+        /// A sink for a call the client made with progress token `token`
+        fn sink(&self, token: Value) -> ProgressSink {
+            let mut meta = Meta::new();
+            meta.0.insert(PROGRESS_TOKEN_KEY.to_string(), token);
+            ProgressSink::from_request(self.server.peer(), &meta).unwrap()
+        }
+
+        async fn next_progress(&mut self) -> Option<ProgressNotificationParam> {
+            tokio::time::timeout(Duration::from_millis(500), self.progress.recv())
+                .await
+                .ok()
+                .flatten()
+        }
+    }
+
+    /// Progress the backend reports against `token`
+    fn backend_progress(token: &str, message: &str) -> ProgressNotificationParam {
+        serde_json::from_value(json!({
+            "progressToken": token,
+            "progress": 1,
+            "message": message,
+        }))
+        .unwrap()
+    }
+
+    fn route_token(route: &ProgressRoute) -> String {
+        let mut meta = Meta::new();
+        route.attach(&mut meta);
+        meta.0[PROGRESS_TOKEN_KEY].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_register_gives_each_call_its_own_token() {
+        let connected = Connected::start().await;
+        let relay = Arc::new(ProgressRelay::new());
+
+        let first = relay.register(&connected.sink(json!("a")));
+        let second = relay.register(&connected.sink(json!("a")));
+
+        let (first_token, second_token) = (route_token(&first), route_token(&second));
+        assert!(first_token.starts_with("mcpmux-"));
+        assert_ne!(first_token, second_token);
+        assert_eq!(relay.routes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_forward_uses_the_client_token() {
+        let mut connected = Connected::start().await;
+        let relay = Arc::new(ProgressRelay::new());
+        let route = relay.register(&connected.sink(json!(7)));
+
+        relay
+            .forward(backend_progress(&route_token(&route), "half done"))
+            .await;
+
+        let forwarded = connected.next_progress().await.expect("progress forwarded");
+        assert_eq!(
+            serde_json::to_value(&forwarded.progress_token).unwrap(),
+            json!(7)
+        );
+        assert_eq!(forwarded.message.as_deref(), Some("half done"));
+    }
+
+    #[tokio::test]
+    async fn test_route_removed_when_call_returns() {
+        let mut connected = Connected::start().await;
+        let relay = Arc::new(ProgressRelay::new());
+        let route = relay.register(&connected.sink(json!("call-1")));
+        let token = route_token(&route);
+
+        drop(route);
+        assert!(relay.routes.is_empty());
+
+        relay.forward(backend_progress(&token, "late")).await;
+        assert!(connected.next_progress().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_relays_are_independent() {
+        let mut connected = Connected::start().await;
+        let relay = Arc::new(ProgressRelay::new());
+        let other = ProgressRelay::new();
+        let route = relay.register(&connected.sink(json!("call-1")));
+
+        other
+            .forward(backend_progress(&route_token(&route), "elsewhere"))
+            .await;
+        assert!(connected.next_progress().await.is_none());
+    }
+}
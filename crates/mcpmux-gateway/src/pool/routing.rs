@@ -3,23 +3,37 @@
 //! RoutingService handles:
 //! - Listing tools/prompts/resources filtered by client grants
 //! - Dispatching tool calls to the correct backend server
+//! - Validating tool arguments against the backend's declared input schema
+//! - Running per-server tool hooks on arguments and results
+//! - Adding per-server call context (`_meta` fields) to forwarded calls
+//! - Forwarding a backend's progress on a call to the calling client
+//! - Enforcing the filesystem path policy on tool arguments
+//! - Injecting configured faults into tool calls (debug builds only)
 //! - Handling 401 errors with automatic token refresh and retry
 //!
 //! Uses FeatureService for permission resolution and TokenService for refresh.
 
 use std::sync::Arc;
-use std::time::Duration;
 
 use anyhow::{anyhow, Result};
-use mcpmux_core::{FeatureType, LogLevel, LogSource, ServerLog, ServerLogManager};
+use mcpmux_core::{
+    injection_warning, tool_input_schema, validate_tool_arguments, CallOrigin, ContentFilter,
+    DomainEvent, FeatureType, FilterOutcome, InjectionAction, InjectionScanner, InstalledServer,
+    InstalledServerRepository, LogLevel, LogSource, SchemaViolation, ServerLog, ServerLogManager,
+    ToolAnnotations, ToolHooks, TransportConfig, DEFAULT_REQUEST_TIMEOUT,
+};
 use rmcp::model::{CallToolRequestParams, CallToolResult, Content, Meta};
 use serde_json::Value;
+use thiserror::Error;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use super::connection::ConnectionResult;
+use super::faults::{Fault, DROPPED_MESSAGE, OAUTH_EXPIRED_MESSAGE};
 use super::features::FeatureService;
+use super::progress::{ProgressRelay, ProgressSink};
 use super::service::PoolService;
+use super::transport::BUILTIN_CLIENT_ARGUMENT;
 
 /// A tool as returned by the routing service
 #[derive(Debug, Clone)]
@@ -28,6 +42,7 @@ pub struct RoutedTool {
     pub server_id: String,
     pub description: Option<String>,
     pub input_schema: Option<Value>,
+    pub annotations: ToolAnnotations,
 }
 
 /// A prompt as returned by the routing service
@@ -50,7 +65,12 @@ pub struct RoutedResource {
 /// Result of a tool call
 #[derive(Debug)]
 pub struct ToolCallResult {
-    pub content: Vec<Value>,
+    /// Content blocks as the server sent them. They pass through to the
+    /// client as is, so a multi-megabyte result is not copied into JSON
+    /// values and parsed back on the way. They are not kept as raw JSON:
+    /// the rmcp client has already parsed them into `Content` by the time
+    /// the gateway sees the result.
+    pub content: Vec<Content>,
     pub is_error: bool,
     pub structured_content: Option<Value>,
     pub meta: Option<Meta>,
@@ -59,27 +79,96 @@ pub struct ToolCallResult {
 impl ToolCallResult {
     fn from_mcp_result(result: CallToolResult) -> Self {
         Self {
-            content: result
-                .content
-                .into_iter()
-                .map(|item| serde_json::to_value(item).unwrap_or(Value::Null))
-                .collect(),
+            content: result.content,
             is_error: result.is_error.unwrap_or(false),
             structured_content: result.structured_content,
             meta: result.meta,
         }
     }
 
-    pub(crate) fn into_mcp_result(self) -> CallToolResult {
-        let content: Vec<Content> = self
-            .content
+    /// Error result carrying a single text message, surfaced to the client
+    /// as a failed tool call rather than a protocol error.
+    fn error_text(message: impl Into<String>) -> Self {
+        Self {
+            content: vec![Content::text(message.into())],
+            is_error: true,
+            structured_content: None,
+            meta: None,
+        }
+    }
+
+    /// The content blocks as JSON
+    pub fn content_json(&self) -> serde_json::Result<Vec<Value>> {
+        self.content.iter().map(serde_json::to_value).collect()
+    }
+
+    /// Replace the content with blocks edited as JSON. Fails, leaving the
+    /// content as it was, if any block no longer reads as a content block.
+    fn set_content_json(&mut self, content: Vec<Value>) -> serde_json::Result<()> {
+        self.content = content
             .into_iter()
-            .filter_map(|item| serde_json::from_value(item).ok())
-            .collect();
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    /// Run the `after` hooks for `tool`. Only then is the content converted
+    /// to JSON and back.
+    fn apply_after_hooks(&mut self, hooks: &ToolHooks, tool: &str) -> Result<()> {
+        if !hooks.has_after(tool) {
+            return Ok(());
+        }
+        let mut content = self.content_json()?;
+        hooks.apply_after(tool, &mut content, self.structured_content.as_mut())?;
+        self.set_content_json(content).map_err(|e| {
+            anyhow!(
+                "After hooks for tool {} produced an invalid content block: {}",
+                tool,
+                e
+            )
+        })
+    }
+
+    /// Run the content filter for `tool` (`<server_id>/<tool>`), redacting
+    /// in place. A blocking match replaces the result with an error, and so
+    /// does a redacted block that no longer reads as a content block: the
+    /// result is withheld rather than passed on with that block missing.
+    fn apply_content_filter(&mut self, filter: &ContentFilter, tool: &str) -> FilterOutcome {
+        if !filter.applies_to(tool) {
+            return FilterOutcome::default();
+        }
+        let mut content = match self.content_json() {
+            Ok(content) => content,
+            Err(e) => {
+                warn!(tool = %tool, error = %e, "Result content could not be filtered");
+                *self = Self::error_text(
+                    "Result withheld: content filter redaction could not be applied",
+                );
+                return FilterOutcome::default();
+            }
+        };
+        let outcome = filter.apply(tool, &mut content, self.structured_content.as_mut());
+        if let Some(rule) = outcome.blocked_by() {
+            *self = Self::error_text(format!(
+                "Result withheld: it matched content filter '{}'",
+                rule
+            ));
+        } else if !outcome.findings.is_empty() {
+            if let Err(e) = self.set_content_json(content) {
+                warn!(tool = %tool, error = %e, "Redacted content is not a valid content block");
+                *self = Self::error_text(
+                    "Result withheld: content filter redaction could not be applied",
+                );
+            }
+        }
+        outcome
+    }
+
+    pub(crate) fn into_mcp_result(self) -> CallToolResult {
         let mut result = if self.is_error {
-            CallToolResult::error(content)
+            CallToolResult::error(self.content)
         } else {
-            CallToolResult::success(content)
+            CallToolResult::success(self.content)
         };
         result.structured_content = self.structured_content;
         result.meta = self.meta;
@@ -87,14 +176,47 @@ impl ToolCallResult {
     }
 }
 
-/// Default timeout for MCP tool calls (60 seconds)
-const TOOL_CALL_TIMEOUT: Duration = Duration::from_secs(60);
+/// Tool call arguments that don't match the tool's declared `inputSchema`.
+///
+/// Returned (inside `anyhow::Error`) before the call reaches the backend, so
+/// the MCP handler can answer with an invalid-params error that lists every
+/// violation.
+#[derive(Debug, Error)]
+#[error("Invalid arguments for tool '{tool}': {}", summarize_violations(.violations))]
+pub struct InvalidToolArguments {
+    pub tool: String,
+    pub violations: Vec<SchemaViolation>,
+}
+
+fn summarize_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The client a tool call is made for
+#[derive(Debug, Clone, Copy)]
+pub struct ToolCaller<'a> {
+    /// OAuth client ID
+    pub client_id: &'a str,
+    /// Name the client gave when it connected (`clientInfo.name`)
+    pub client_name: Option<&'a str>,
+    /// Where to send the server's progress, if the client asked for it
+    pub progress: Option<&'a ProgressSink>,
+}
 
 /// RoutingService dispatches requests to backend MCP servers
 pub struct RoutingService {
     feature_service: Arc<FeatureService>,
     pool_service: Arc<PoolService>,
     log_manager: Arc<ServerLogManager>,
+    installed_server_repo: Option<Arc<dyn InstalledServerRepository>>,
+    content_filter: Arc<ContentFilter>,
+    injection_scanner: Option<Arc<InjectionScanner>>,
+    event_tx: Option<tokio::sync::broadcast::Sender<DomainEvent>>,
+    progress: Arc<ProgressRelay>,
 }
 
 impl RoutingService {
@@ -107,6 +229,85 @@ impl RoutingService {
             feature_service,
             pool_service,
             log_manager,
+            installed_server_repo: None,
+            content_filter: Arc::default(),
+            injection_scanner: None,
+            event_tx: None,
+            progress: Arc::default(),
+        }
+    }
+
+    /// Enable per-installation checks (filesystem path policy, argument
+    /// validation opt-out) on tool calls
+    pub fn with_installed_server_repo(mut self, repo: Arc<dyn InstalledServerRepository>) -> Self {
+        self.installed_server_repo = Some(repo);
+        self
+    }
+
+    /// Run these detectors on every tool result
+    pub fn with_content_filter(mut self, filter: Arc<ContentFilter>) -> Self {
+        self.content_filter = filter;
+        self
+    }
+
+    /// Scan tool results and resources for prompt injections
+    pub fn with_injection_scanner(mut self, scanner: Arc<InjectionScanner>) -> Self {
+        self.injection_scanner = Some(scanner);
+        self
+    }
+
+    /// Register calls' progress with `relay`, the one the backend
+    /// connections forward progress through
+    pub fn with_progress_relay(mut self, relay: Arc<ProgressRelay>) -> Self {
+        self.progress = relay;
+        self
+    }
+
+    /// Set event sender for reporting suspected prompt injections
+    pub fn with_event_tx(mut self, event_tx: tokio::sync::broadcast::Sender<DomainEvent>) -> Self {
+        self.event_tx = Some(event_tx);
+        self
+    }
+
+    /// Look up the installation a tool call is routed to, for its per-server
+    /// call checks.
+    ///
+    /// Lookup failures are returned rather than treated as "no installation
+    /// settings": the call would otherwise go out without the server's path
+    /// policy and hooks.
+    async fn installed_server(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> Result<Option<InstalledServer>> {
+        let Some(repo) = self.installed_server_repo.as_ref() else {
+            return Ok(None);
+        };
+        repo.get_by_server_id(space_id, server_id)
+            .await
+            .map_err(|e| anyhow!("Installed server lookup failed for {}: {}", server_id, e))
+    }
+
+    /// Note that a tool call to `installed` succeeded, for auto-connect to
+    /// start it early and unused-server suggestions to leave it alone.
+    /// Written at most once a minute per server so busy servers don't turn
+    /// every tool call into a database write.
+    async fn record_last_used(&self, installed: &InstalledServer) {
+        let Some(repo) = self.installed_server_repo.as_ref() else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        if installed
+            .last_used_at
+            .is_some_and(|last| now - last < chrono::Duration::minutes(1))
+        {
+            return;
+        }
+        if let Err(e) = repo.set_last_used(&installed.id, now).await {
+            debug!(
+                "[RoutingService] Failed to record last use of {}: {}",
+                installed.server_id, e
+            );
         }
     }
 
@@ -135,6 +336,7 @@ impl RoutingService {
                 server_id: f.server_id.clone(),
                 description: f.description.clone(),
                 input_schema: None, // Raw JSON is used in handlers now
+                annotations: f.annotations,
             })
             .collect();
 
@@ -212,13 +414,14 @@ impl RoutingService {
         Ok(resources)
     }
 
-    /// Call a tool on a backend server
+    /// Call a tool on a backend server on behalf of `caller`
     pub async fn call_tool(
         &self,
         space_id: Uuid,
         feature_set_ids: &[String],
         tool_name: &str,
         arguments: Value,
+        caller: Option<ToolCaller<'_>>,
     ) -> Result<ToolCallResult> {
         let space_id_str = space_id.to_string();
 
@@ -238,8 +441,12 @@ impl RoutingService {
             f.feature_type == FeatureType::Tool && f.is_available && f.qualified_name() == tool_name
         });
 
-        let (server_id, actual_tool_name) = match feature {
-            Some(f) => (f.server_id.clone(), f.feature_name.clone()),
+        let (server_id, actual_tool_name, input_schema) = match feature {
+            Some(f) => (
+                f.server_id.clone(),
+                f.feature_name.clone(),
+                f.raw_json.as_ref().and_then(tool_input_schema).cloned(),
+            ),
             None => {
                 let available = allowed_features
                     .iter()
@@ -261,6 +468,168 @@ impl RoutingService {
             tool_name, server_id, actual_tool_name
         );
 
+        self.dispatch_tool_call(
+            space_id,
+            server_id,
+            actual_tool_name,
+            input_schema,
+            arguments,
+            caller,
+        )
+        .await
+    }
+
+    /// Call a tool by server and tool name from the desktop app, for
+    /// debugging. Takes the same path as [`call_tool`](Self::call_tool) after
+    /// authorization: argument validation, path policy, logging and
+    /// auth-error reconnects. The
+    /// tool must be granted by `feature_set_ids` unless `bypass_grants` is set.
+    pub async fn test_tool_call(
+        &self,
+        space_id: Uuid,
+        feature_set_ids: &[String],
+        server_id: &str,
+        tool_name: &str,
+        arguments: Value,
+        bypass_grants: bool,
+    ) -> Result<ToolCallResult> {
+        let space_id_str = space_id.to_string();
+        let features = if bypass_grants {
+            self.feature_service
+                .get_all_features_for_space(&space_id_str, Some(FeatureType::Tool))
+                .await?
+        } else {
+            self.feature_service
+                .resolve_feature_sets(&space_id_str, feature_set_ids)
+                .await?
+        };
+
+        let found = features.iter().find(|f| {
+            f.feature_type == FeatureType::Tool
+                && f.is_available
+                && f.server_id == server_id
+                && f.feature_name == tool_name
+        });
+        let Some(feature) = found else {
+            return Err(if bypass_grants {
+                anyhow!("Tool '{}' not found on server '{}'", tool_name, server_id)
+            } else {
+                anyhow!(
+                    "Tool '{}' on server '{}' is not allowed by the selected grants",
+                    tool_name,
+                    server_id
+                )
+            });
+        };
+        let input_schema = feature
+            .raw_json
+            .as_ref()
+            .and_then(tool_input_schema)
+            .cloned();
+
+        info!(
+            "[RoutingService] Test call of tool {} on server {} (bypass_grants={})",
+            tool_name, server_id, bypass_grants
+        );
+        self.dispatch_tool_call(
+            space_id,
+            server_id.to_string(),
+            tool_name.to_string(),
+            input_schema,
+            arguments,
+            None,
+        )
+        .await
+    }
+
+    /// Validate the arguments and check the path policy, then call an
+    /// authorized tool on its server, reconnecting and retrying once on auth
+    /// errors
+    async fn dispatch_tool_call(
+        &self,
+        space_id: Uuid,
+        server_id: String,
+        actual_tool_name: String,
+        input_schema: Option<Value>,
+        mut arguments: Value,
+        caller: Option<ToolCaller<'_>>,
+    ) -> Result<ToolCallResult> {
+        let space_id_str = space_id.to_string();
+        let installed = self.installed_server(&space_id_str, &server_id).await?;
+
+        // Hooks edit the arguments first, so what they set is validated and
+        // checked against the path policy like the caller's own arguments
+        let hooks = installed
+            .as_ref()
+            .map(|installed| &installed.tool_hooks)
+            .filter(|hooks| !hooks.is_empty());
+        if let Some(hooks) = hooks {
+            if let Err(e) = hooks.apply_before(&actual_tool_name, &mut arguments) {
+                warn!(
+                    "[RoutingService] Tool '{}' REJECTED: {}",
+                    actual_tool_name, e
+                );
+                return Err(e.into());
+            }
+        }
+
+        let validate = !installed
+            .as_ref()
+            .is_some_and(|installed| installed.skip_argument_validation);
+        if let Some(schema) = input_schema.as_ref().filter(|_| validate) {
+            let violations = validate_tool_arguments(schema, &arguments);
+            if !violations.is_empty() {
+                warn!(
+                    "[RoutingService] Tool '{}' REJECTED: {} argument violation(s)",
+                    actual_tool_name,
+                    violations.len()
+                );
+                // Paths and messages only: they name the offending keys and
+                // what was expected, never the argument values
+                self.log(
+                    &space_id,
+                    &server_id,
+                    LogLevel::Warn,
+                    format!("Rejected tool call: {}", actual_tool_name),
+                    Some(serde_json::json!({
+                        "tool": actual_tool_name,
+                        "reason": "invalid_arguments",
+                        "violations": violations,
+                    })),
+                )
+                .await;
+                return Err(InvalidToolArguments {
+                    tool: actual_tool_name,
+                    violations,
+                }
+                .into());
+            }
+        }
+
+        let path_violation = installed
+            .as_ref()
+            .and_then(InstalledServer::path_policy)
+            .and_then(|policy| policy.check_arguments(&arguments).err());
+        if let Some(violation) = path_violation {
+            warn!(
+                "[RoutingService] Tool '{}' BLOCKED by path policy: {}",
+                actual_tool_name, violation
+            );
+            self.log(
+                &space_id,
+                &server_id,
+                LogLevel::Warn,
+                format!("Blocked tool call: {}", actual_tool_name),
+                Some(serde_json::json!({
+                    "tool": actual_tool_name,
+                    "argument": violation.argument,
+                    "reason": "path_outside_allowed_roots"
+                })),
+            )
+            .await;
+            return Ok(ToolCallResult::error_text(violation.to_string()));
+        }
+
         info!(
             "[RoutingService] Calling tool {} on server {}",
             actual_tool_name, server_id
@@ -286,6 +655,53 @@ impl RoutingService {
         )
         .await;
 
+        // The built-in utilities keep a scratchpad per client, so tell them
+        // who is calling. Set after validation and always overwritten, so a
+        // client can't read another's scratchpad.
+        let is_builtin = installed
+            .as_ref()
+            .and_then(InstalledServer::get_definition)
+            .is_some_and(|definition| {
+                matches!(definition.transport, TransportConfig::Builtin { .. })
+            });
+        if let (true, Some(object)) = (is_builtin, arguments.as_object_mut()) {
+            object.insert(
+                BUILTIN_CLIENT_ARGUMENT.to_string(),
+                Value::String(
+                    caller
+                        .map(|caller| caller.client_id)
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+            );
+        }
+
+        // Call context lets the server's logs attribute the call to the
+        // client it came from
+        let mut meta = installed
+            .as_ref()
+            .map(|installed| &installed.call_context)
+            .filter(|context| !context.is_empty())
+            .map(|context| {
+                Meta(context.render(&CallOrigin {
+                    client_id: caller.map(|caller| caller.client_id),
+                    client_name: caller.and_then(|caller| caller.client_name),
+                    space_id: &space_id_str,
+                    server_id: &server_id,
+                    tool: &actual_tool_name,
+                }))
+            })
+            .filter(|meta| !meta.0.is_empty());
+
+        // Progress the server reports on this call (often partial output) is
+        // passed on as it arrives, until the call returns
+        let progress_route = caller
+            .and_then(|caller| caller.progress)
+            .map(|sink| self.progress.register(sink));
+        if let Some(route) = &progress_route {
+            route.attach(meta.get_or_insert_with(Meta::new));
+        }
+
         // Define the call operation
         // Function to execute the call on the instance
         async fn execute_call(
@@ -294,6 +710,7 @@ impl RoutingService {
             server_id: String,
             tool_name: String,
             args: Value,
+            meta: Option<Meta>,
         ) -> Result<ToolCallResult> {
             let instance = pool
                 .get_instance(space_id, &server_id)
@@ -305,16 +722,18 @@ impl RoutingService {
             // We can assume RunningService is not cloneable but its peer() returns a Service handle which is.
             // Let's use with_client to get the handle out.
             let client_handle = instance.with_client(|client| client.peer().clone());
+            let timeout = instance.timeouts().request_timeout();
 
             match client_handle {
                 Some(client) => {
                     let mut params = CallToolRequestParams::new(tool_name.to_string());
                     params.arguments = args.as_object().cloned();
+                    params.meta = meta;
 
-                    // Wrap call_tool with timeout to prevent hanging
-                    let res = tokio::time::timeout(TOOL_CALL_TIMEOUT, client.call_tool(params))
+                    // Wrap call_tool with the server's request timeout to prevent hanging
+                    let res = tokio::time::timeout(timeout, client.call_tool(params))
                         .await
-                        .map_err(|_| anyhow!("Tool call timed out after {:?}", TOOL_CALL_TIMEOUT))?
+                        .map_err(|_| anyhow!("Tool call timed out after {:?}", timeout))?
                         .map_err(|e| anyhow!("MCP call failed: {}", e))?;
 
                     Ok(ToolCallResult::from_mcp_result(res))
@@ -327,21 +746,36 @@ impl RoutingService {
         // NOTE: Preemptive token refresh is no longer needed here.
         // RMCP's AuthClient with DatabaseCredentialStore handles token refresh
         // automatically on every HTTP request when needed.
+        let request_timeout = self
+            .pool_service
+            .get_instance(space_id, &server_id)
+            .map(|instance| instance.timeouts().request_timeout())
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT);
         info!(
             "[RoutingService] Executing tool call: {} on {} (timeout: {:?})",
-            actual_tool_name, server_id, TOOL_CALL_TIMEOUT
+            actual_tool_name, server_id, request_timeout
         );
 
         let call_start = std::time::Instant::now();
-        match execute_call(
-            self.pool_service.clone(),
-            space_id,
-            server_id.clone(),
-            actual_tool_name.clone(),
-            arguments.clone(),
-        )
-        .await
+        let first_attempt = match self
+            .inject_fault(&space_id, &server_id, &actual_tool_name)
+            .await
         {
+            Some(Fault::Drop) => Err(anyhow!(DROPPED_MESSAGE)),
+            Some(Fault::ExpireOAuth) => Err(anyhow!(OAUTH_EXPIRED_MESSAGE)),
+            None => {
+                execute_call(
+                    self.pool_service.clone(),
+                    space_id,
+                    server_id.clone(),
+                    actual_tool_name.clone(),
+                    arguments.clone(),
+                    meta.clone(),
+                )
+                .await
+            }
+        };
+        let result = match first_attempt {
             Ok(result) => {
                 let duration = call_start.elapsed();
                 if result.is_error {
@@ -362,7 +796,7 @@ impl RoutingService {
                                 "Auth error in tool result for '{}' - auto-reconnecting",
                                 actual_tool_name
                             ),
-                            Some(serde_json::json!({ "result": result.content, "duration_ms": duration.as_millis() })),
+                            Some(serde_json::json!({ "result": result.content_json().ok(), "duration_ms": duration.as_millis() })),
                         )
                         .await;
 
@@ -384,6 +818,7 @@ impl RoutingService {
                                     server_id.clone(),
                                     actual_tool_name.clone(),
                                     arguments.clone(),
+                                    meta.clone(),
                                 )
                                 .await
                                 {
@@ -463,7 +898,7 @@ impl RoutingService {
                             &server_id,
                             LogLevel::Error,
                             format!("Tool execution error: {}", actual_tool_name),
-                            Some(serde_json::json!({ "result": result.content, "duration_ms": duration.as_millis() }))
+                            Some(serde_json::json!({ "result": result.content_json().ok(), "duration_ms": duration.as_millis() }))
                         ).await;
                         Ok(result)
                     }
@@ -484,7 +919,7 @@ impl RoutingService {
                                 "Auth error in tool result for '{}' (is_error=false) - auto-reconnecting",
                                 actual_tool_name
                             ),
-                            Some(serde_json::json!({ "result": result.content, "duration_ms": duration.as_millis() })),
+                            Some(serde_json::json!({ "result": result.content_json().ok(), "duration_ms": duration.as_millis() })),
                         )
                         .await;
 
@@ -506,6 +941,7 @@ impl RoutingService {
                                     server_id.clone(),
                                     actual_tool_name.clone(),
                                     arguments.clone(),
+                                    meta.clone(),
                                 )
                                 .await
                                 {
@@ -613,6 +1049,7 @@ impl RoutingService {
                                 server_id.clone(),
                                 actual_tool_name.clone(),
                                 arguments.clone(),
+                                meta.clone(),
                             )
                             .await
                             {
@@ -694,6 +1131,176 @@ impl RoutingService {
                     Err(e)
                 }
             }
+        };
+
+        let mut result = result?;
+        // Only calls that succeed count as use
+        if let (false, Some(installed)) = (result.is_error, &installed) {
+            self.record_last_used(installed).await;
+        }
+        if let Some(hooks) = hooks {
+            result.apply_after_hooks(hooks, &actual_tool_name)?;
+        }
+        if !self.content_filter.is_empty() {
+            let qualified = format!("{}/{}", server_id, actual_tool_name);
+            let outcome = result.apply_content_filter(&self.content_filter, &qualified);
+            if !outcome.findings.is_empty() {
+                // Rule names and counts only, never what matched
+                let level = if outcome.blocked_by().is_some() {
+                    LogLevel::Warn
+                } else {
+                    LogLevel::Info
+                };
+                self.log(
+                    &space_id,
+                    &server_id,
+                    level,
+                    format!(
+                        "Content filter matched result of tool: {}",
+                        actual_tool_name
+                    ),
+                    Some(serde_json::json!({
+                        "tool": actual_tool_name,
+                        "findings": outcome.findings,
+                    })),
+                )
+                .await;
+            }
+        }
+        if let Some(scanner) = &self.injection_scanner {
+            let mut found = scanner.scan_value(&Value::Array(result.content_json()?));
+            if let Some(structured) = &result.structured_content {
+                for name in scanner.scan_value(structured) {
+                    if !found.contains(&name) {
+                        found.push(name);
+                    }
+                }
+            }
+            if !found.is_empty() {
+                let blocked = scanner.action() == InjectionAction::Block;
+                self.report_injection(
+                    &space_id,
+                    &server_id,
+                    caller.map(|caller| caller.client_id),
+                    "tool",
+                    &actual_tool_name,
+                    &found,
+                    blocked,
+                )
+                .await;
+                if blocked {
+                    result = ToolCallResult::error_text(format!(
+                        "Result withheld: it looks like a prompt injection ({})",
+                        found.join(", ")
+                    ));
+                } else {
+                    result
+                        .content
+                        .insert(0, Content::text(injection_warning(&found)));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Read a resource from its server, scanning it for prompt injections
+    /// when the scanner is on
+    pub async fn read_resource(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        uri: &str,
+        client_id: Option<&str>,
+    ) -> Result<Vec<Value>> {
+        let mut contents = self
+            .pool_service
+            .read_resource(space_id, server_id, uri)
+            .await?;
+        let Some(scanner) = &self.injection_scanner else {
+            return Ok(contents);
+        };
+
+        let mut found: Vec<&'static str> = Vec::new();
+        let mut flagged = Vec::new();
+        for (i, item) in contents.iter().enumerate() {
+            let names = scanner.scan_value(item);
+            if !names.is_empty() {
+                flagged.push((i, names.clone()));
+            }
+            for name in names {
+                if !found.contains(&name) {
+                    found.push(name);
+                }
+            }
+        }
+        if found.is_empty() {
+            return Ok(contents);
+        }
+
+        let blocked = scanner.action() == InjectionAction::Block;
+        self.report_injection(
+            &space_id, server_id, client_id, "resource", uri, &found, blocked,
+        )
+        .await;
+        if blocked {
+            return Err(anyhow!(
+                "Resource withheld: it looks like a prompt injection ({})",
+                found.join(", ")
+            ));
+        }
+        // The warning goes in front of each flagged text
+        for (i, names) in flagged {
+            if let Some(Value::String(text)) = contents[i].get_mut("text") {
+                *text = format!("{}\n\n{}", injection_warning(&names), text);
+            }
+        }
+        Ok(contents)
+    }
+
+    /// Log a suspected prompt injection and report it as a domain event.
+    /// Only the heuristic names are recorded, never the text.
+    #[allow(clippy::too_many_arguments)]
+    async fn report_injection(
+        &self,
+        space_id: &Uuid,
+        server_id: &str,
+        client_id: Option<&str>,
+        source: &str,
+        name: &str,
+        heuristics: &[&str],
+        blocked: bool,
+    ) {
+        warn!(
+            "[RoutingService] Suspected prompt injection in {} {} from {} ({}; blocked={})",
+            source,
+            name,
+            server_id,
+            heuristics.join(", "),
+            blocked
+        );
+        self.log(
+            space_id,
+            server_id,
+            LogLevel::Warn,
+            format!("Suspected prompt injection in {}: {}", source, name),
+            Some(serde_json::json!({
+                "source": source,
+                "name": name,
+                "heuristics": heuristics,
+                "blocked": blocked,
+            })),
+        )
+        .await;
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(DomainEvent::PromptInjectionSuspected {
+                space_id: *space_id,
+                server_id: server_id.to_string(),
+                client_id: client_id.map(str::to_string),
+                source: source.to_string(),
+                name: name.to_string(),
+                heuristics: heuristics.iter().map(|h| h.to_string()).collect(),
+                blocked,
+            });
         }
     }
 
@@ -720,6 +1327,29 @@ impl RoutingService {
         }
     }
 
+    /// Hold a call for its server's injected delay, then pick the fault it
+    /// fails with, if any
+    async fn inject_fault(&self, space_id: &Uuid, server_id: &str, tool: &str) -> Option<Fault> {
+        let (delay, fault) = self.pool_service.faults().roll(*space_id, server_id)?;
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        let fault = fault?;
+        warn!(
+            "[RoutingService] Injecting {:?} into tool call {} on {}",
+            fault, tool, server_id
+        );
+        self.log(
+            space_id,
+            server_id,
+            LogLevel::Warn,
+            format!("Injected fault into tool call: {}", tool),
+            Some(serde_json::json!({ "tool": tool, "fault": fault })),
+        )
+        .await;
+        Some(fault)
+    }
+
     /// Check if an error string indicates authentication is needed
     fn is_auth_error(error_str: &str) -> bool {
         let indicators = [
@@ -738,22 +1368,26 @@ impl RoutingService {
     /// (`is_error: true` with 401 in the text) rather than HTTP-level errors.
     /// The SDK may have already refreshed the token, but the server's internal
     /// session can be stale. A fresh connection (reconnect) fixes this.
-    fn content_has_auth_error(content: &[Value]) -> bool {
-        for item in content {
-            if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
-                let lower = text.to_lowercase();
-                if Self::is_auth_error(&lower) {
-                    return true;
-                }
-            }
-        }
-        false
+    ///
+    /// Such errors are short messages, so long text (real output that may
+    /// mention a token) is not scanned.
+    fn content_has_auth_error(content: &[Content]) -> bool {
+        const MAX_AUTH_ERROR_LEN: usize = 4096;
+        content
+            .iter()
+            .filter_map(|item| item.as_text())
+            .filter(|text| text.text.len() <= MAX_AUTH_ERROR_LEN)
+            .any(|text| Self::is_auth_error(&text.text.to_lowercase()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ToolCallResult;
+    use mcpmux_core::{
+        ContentFilter, ContentFilterRule, DetectorConfig, FilterAction, HookStep, ToolHook,
+        ToolHooks,
+    };
     use rmcp::model::{CallToolResult, Content, Meta};
     use serde_json::json;
 
@@ -775,4 +1409,84 @@ mod tests {
         assert_eq!(forwarded.meta, Some(meta));
         assert_eq!(forwarded.is_error, Some(false));
     }
+
+    #[test]
+    fn redaction_keeps_every_content_block() {
+        let filter = ContentFilter::from_rules(&[ContentFilterRule {
+            name: None,
+            detector: DetectorConfig::CreditCard,
+            action: FilterAction::Redact,
+            tools: Vec::new(),
+            replacement: None,
+        }])
+        .unwrap();
+        let mut result = ToolCallResult::from_mcp_result(CallToolResult::success(vec![
+            Content::text("paid with 4111 1111 1111 1111"),
+            Content::image("aGVsbG8=", "image/png"),
+            Content::text("receipt sent"),
+        ]));
+
+        let outcome = result.apply_content_filter(&filter, "billing/charge");
+
+        assert_eq!(outcome.findings.len(), 1);
+        assert!(!result.is_error);
+        assert_eq!(result.content.len(), 3);
+        assert_eq!(
+            result.content[0].as_text().map(|text| text.text.as_str()),
+            Some("paid with [redacted]")
+        );
+        assert_eq!(result.content[1], Content::image("aGVsbG8=", "image/png"));
+        assert_eq!(result.content[2], Content::text("receipt sent"));
+    }
+
+    #[test]
+    fn after_hooks_keep_every_content_block() {
+        let hooks = ToolHooks {
+            before: Vec::new(),
+            after: vec![ToolHook {
+                tools: Vec::new(),
+                step: HookStep::Redact {
+                    pattern: "secret-\\d+".to_string(),
+                    replacement: "[redacted]".to_string(),
+                },
+            }],
+        };
+        let mut result = ToolCallResult::from_mcp_result(CallToolResult::success(vec![
+            Content::image("aGVsbG8=", "image/png"),
+            Content::text("token secret-42"),
+        ]));
+
+        result.apply_after_hooks(&hooks, "lookup").unwrap();
+
+        assert_eq!(
+            result.content,
+            vec![
+                Content::image("aGVsbG8=", "image/png"),
+                Content::text("token [redacted]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn after_hooks_that_break_a_block_fail_the_call() {
+        let hooks = ToolHooks {
+            before: Vec::new(),
+            after: vec![ToolHook {
+                tools: Vec::new(),
+                step: HookStep::Script {
+                    source: "content[0].type = \"unknown\";".to_string(),
+                },
+            }],
+        };
+        let mut result = ToolCallResult::from_mcp_result(CallToolResult::success(vec![
+            Content::text("first"),
+            Content::text("second"),
+        ]));
+
+        let error = result
+            .apply_after_hooks(&hooks, "lookup")
+            .expect_err("an invalid block must not be dropped");
+        assert!(error.to_string().contains("invalid content block"));
+        assert_eq!(result.content.len(), 2);
+    }
 }
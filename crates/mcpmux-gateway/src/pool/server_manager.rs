@@ -15,7 +15,9 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
-use mcpmux_core::{DiscoveredCapabilities, DomainEvent};
+use mcpmux_core::{
+    DiagnosedCause, DiscoveredCapabilities, DomainEvent, FeatureType, ServerFeature,
+};
 use tokio::sync::{broadcast, Mutex, OwnedMutexGuard, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, trace, warn};
@@ -131,6 +133,9 @@ pub struct ServerState {
     pub features: Option<CachedFeatures>,
     /// Error message if status is Error
     pub error: Option<String>,
+    /// Why the server keeps exiting during startup, if status is Error
+    /// because it's crash-looping
+    pub diagnosis: Option<DiagnosedCause>,
     /// OAuth flow state if Authenticating
     pub auth: Option<AuthFlowState>,
 
@@ -160,6 +165,7 @@ impl Default for ServerState {
             has_connected_before: false,
             features: None,
             error: None,
+            diagnosis: None,
             auth: None,
             connect_lock: None,
             auth_lock: None,
@@ -268,6 +274,19 @@ impl ServerManager {
         result
     }
 
+    /// Diagnoses of crash-looping servers in a space
+    pub async fn get_diagnoses(&self, space_id: Uuid) -> HashMap<String, DiagnosedCause> {
+        let mut result = HashMap::new();
+        for entry in self.states.iter() {
+            if entry.key().space_id == space_id {
+                if let Some(diagnosis) = &entry.value().read().await.diagnosis {
+                    result.insert(entry.key().server_id.clone(), diagnosis.clone());
+                }
+            }
+        }
+        result
+    }
+
     /// Count currently connected servers across all spaces
     pub async fn connected_count(&self) -> usize {
         let mut count = 0;
@@ -295,7 +314,7 @@ impl ServerManager {
     }
 
     /// Emit a domain event (unified event system)
-    fn emit(&self, event: DomainEvent) {
+    pub(crate) fn emit(&self, event: DomainEvent) {
         // Trace Refreshing events to find the source
         if let DomainEvent::ServerStatusChanged {
             ref server_id,
@@ -997,6 +1016,7 @@ impl ServerManager {
         state.has_connected_before = true;
         state.features = Some(features.clone());
         state.error = None;
+        state.diagnosis = None;
         state.connect_lock = None;
 
         self.emit(DomainEvent::ServerStatusChanged {
@@ -1009,42 +1029,60 @@ impl ServerManager {
             features: Some(self.to_discovered_capabilities(&features)),
         });
 
-        // Also emit FeaturesUpdated event for UI to refresh features
-        let feature_count = features.total_count();
-        if feature_count > 0 {
+        // Tell the UI and MCP clients only about what discovery changed
+        let changes = &features.changes;
+        for change in &changes.schema_changes {
+            info!(
+                server_id = %key.server_id,
+                tool = %change.tool,
+                breaking = change.breaking,
+                held = change.held,
+                "[ServerManager] Tool schema changed"
+            );
+            self.emit(DomainEvent::ToolSchemaChanged {
+                space_id: key.space_id,
+                server_id: key.server_id.clone(),
+                tool: change.tool.clone(),
+                added: change.added.clone(),
+                removed: change.removed.clone(),
+                breaking: change.breaking,
+                held: change.held,
+            });
+        }
+        if changes.is_visible() {
             info!(
                 server_id = %key.server_id,
-                feature_count = feature_count,
+                added = changes.added.len(),
+                updated = changes.updated.len(),
+                removed = changes.removed.len(),
                 "[ServerManager] Emitting features updated event"
             );
-            // Generate added list from feature names
-            let mut added = Vec::new();
-            added.extend(features.tools.iter().map(|t| t.feature_name.clone()));
-            added.extend(features.prompts.iter().map(|p| p.feature_name.clone()));
-            added.extend(features.resources.iter().map(|r| r.feature_name.clone()));
+            let names = |changed: &[ServerFeature]| -> Vec<String> {
+                changed.iter().map(|f| f.feature_name.clone()).collect()
+            };
 
             self.emit(DomainEvent::ServerFeaturesRefreshed {
                 server_id: key.server_id.clone(),
                 space_id: key.space_id,
                 features: self.to_discovered_capabilities(&features),
-                added,
-                removed: Vec::new(),
+                added: names(&changes.added),
+                removed: names(&changes.removed),
             });
 
-            // Emit MCP list_changed notifications for clients to re-fetch
-            if !features.tools.is_empty() {
+            // Emit MCP list_changed notifications for changed feature types
+            if changes.touches(FeatureType::Tool) {
                 self.emit(DomainEvent::ToolsChanged {
                     server_id: key.server_id.clone(),
                     space_id: key.space_id,
                 });
             }
-            if !features.prompts.is_empty() {
+            if changes.touches(FeatureType::Prompt) {
                 self.emit(DomainEvent::PromptsChanged {
                     server_id: key.server_id.clone(),
                     space_id: key.space_id,
                 });
             }
-            if !features.resources.is_empty() {
+            if changes.touches(FeatureType::Resource) {
                 self.emit(DomainEvent::ResourcesChanged {
                     server_id: key.server_id.clone(),
                     space_id: key.space_id,
@@ -1115,6 +1153,9 @@ impl ServerManager {
 
         state.status = ConnectionStatus::Error;
         state.error = Some(error.clone());
+        state.diagnosis = self
+            .connection_service
+            .crash_diagnosis(key.space_id, &key.server_id);
         state.connect_lock = None;
         state.auth_lock = None;
         state.auth = None;
@@ -1157,6 +1198,7 @@ impl ServerManager {
 
         state.status = ConnectionStatus::Disconnected;
         state.error = None;
+        state.diagnosis = None;
         state.features = None;
         state.auth = None;
         state.connect_lock = None;
@@ -11,20 +11,27 @@
 //! - Providing access to server instances for routing
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use dashmap::DashMap;
+use serde::Serialize;
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use super::connection::{ConnectionResult, ConnectionService};
 use super::context::ConnectionContext;
+use super::faults::FaultInjector;
 use super::features::{CachedFeatures, FeatureService};
-use super::instance::{InstanceKey, InstanceState, ServerInstance};
+use super::health::PING_TIMEOUT;
+use super::history::{PoolStatsSnapshot, ServerStatsSnapshot, StatsHistory};
+use super::instance::{InstanceKey, InstanceState, McpClientConnection, ServerInstance};
 use super::oauth::OutboundOAuthManager;
 use super::token::TokenService;
-use super::transport::{ResolvedTransport, TransportType};
+use super::traffic::TrafficRecorder;
+use super::transport::ResolvedTransport;
 
 /// Check if an error string indicates an authentication/authorization failure
 fn is_auth_error(error_str: &str) -> bool {
@@ -63,7 +70,7 @@ impl ReconnectResult {
 }
 
 /// Pool statistics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct PoolStats {
     pub total_instances: usize,
     pub connected_instances: usize,
@@ -88,6 +95,10 @@ pub struct PoolService {
     feature_service: Arc<FeatureService>,
     /// Token service (exposed for routing)
     token_service: Arc<TokenService>,
+    /// Snapshot of the pool after each health check round
+    history: parking_lot::Mutex<StatsHistory>,
+    /// Faults injected into tool calls, for local testing
+    faults: FaultInjector,
 }
 
 impl PoolService {
@@ -102,6 +113,8 @@ impl PoolService {
             connection_service,
             feature_service,
             token_service,
+            history: parking_lot::Mutex::new(StatsHistory::default()),
+            faults: FaultInjector::default(),
         }
     }
 
@@ -120,6 +133,16 @@ impl PoolService {
         self.connection_service.oauth_manager()
     }
 
+    /// Get the JSON-RPC traffic recorder for backend servers
+    pub fn traffic(&self) -> &TrafficRecorder {
+        self.connection_service.traffic()
+    }
+
+    /// Get the faults injected into tool calls (debug builds only)
+    pub fn faults(&self) -> &FaultInjector {
+        &self.faults
+    }
+
     /// Read a resource from a backend server
     ///
     /// On auth errors, automatically reconnects the server and retries once.
@@ -172,10 +195,11 @@ impl PoolService {
                 use rmcp::model::ReadResourceRequestParams;
 
                 let params = ReadResourceRequestParams::new(uri);
+                let timeout = instance.timeouts().request_timeout();
 
-                let res = client
-                    .read_resource(params)
+                let res = tokio::time::timeout(timeout, client.read_resource(params))
                     .await
+                    .map_err(|_| anyhow::anyhow!("Resource read timed out after {:?}", timeout))?
                     .map_err(|e| anyhow::anyhow!("MCP read_resource failed: {}", e))?;
 
                 let content: Vec<Value> = res
@@ -249,10 +273,11 @@ impl PoolService {
 
                 let mut params = GetPromptRequestParams::new(prompt_name);
                 params.arguments = arguments;
+                let timeout = instance.timeouts().request_timeout();
 
-                let res = client
-                    .get_prompt(params)
+                let res = tokio::time::timeout(timeout, client.get_prompt(params))
                     .await
+                    .map_err(|_| anyhow::anyhow!("Get prompt timed out after {:?}", timeout))?
                     .map_err(|e| anyhow::anyhow!("MCP get_prompt failed: {}", e))?;
 
                 // Return the full response as JSON
@@ -302,19 +327,24 @@ impl PoolService {
         }
 
         // Create new instance
-        let transport_type = match &ctx.transport {
-            ResolvedTransport::Stdio { .. } => TransportType::Stdio,
-            ResolvedTransport::Http { .. } => TransportType::Http,
-        };
+        let transport_type = ctx.transport.transport_type();
 
         // Use proper InstanceKey constructors that include the URL
         let instance_key = match &ctx.transport {
-            ResolvedTransport::Stdio { command, args, env } => {
-                InstanceKey::stdio(ctx.space_id, command, args, env)
-            }
+            ResolvedTransport::Stdio {
+                command, args, env, ..
+            } => InstanceKey::stdio(ctx.space_id, command, args, env),
             ResolvedTransport::Http { url, headers, .. } => {
                 InstanceKey::http(ctx.space_id, url, headers)
             }
+            ResolvedTransport::OpenApi { spec_url, .. } => {
+                InstanceKey::openapi(ctx.space_id, spec_url)
+            }
+            ResolvedTransport::GraphQl { endpoint, .. } => {
+                InstanceKey::graphql(ctx.space_id, endpoint)
+            }
+            ResolvedTransport::Builtin { .. } => InstanceKey::builtin(ctx.space_id),
+            ResolvedTransport::Wasm(module) => InstanceKey::wasm(ctx.space_id, &module.path),
         };
 
         let instance = Arc::new(ServerInstance::new(
@@ -375,6 +405,21 @@ impl PoolService {
             .map(|r| r.clone())
     }
 
+    /// Whether a connected server was started with a different transport
+    /// than `transport`, e.g. after its env or args were edited, and needs a
+    /// restart to pick it up. Servers that aren't connected don't.
+    pub fn transport_changed(
+        &self,
+        space_id: Uuid,
+        server_id: &str,
+        transport: &ResolvedTransport,
+    ) -> bool {
+        self.get_instance(space_id, server_id)
+            .filter(|instance| instance.is_healthy())
+            .and_then(|instance| instance.config_hash())
+            .is_some_and(|hash| hash != transport.config_hash())
+    }
+
     /// Check if a server is connected
     pub fn is_connected(&self, space_id: Uuid, server_id: &str) -> bool {
         self.get_instance(space_id, server_id)
@@ -391,6 +436,106 @@ impl PoolService {
             .collect()
     }
 
+    /// Ping one instance and record the result in its health stats
+    async fn ping_instance(instance: &ServerInstance) {
+        use rmcp::model::{ClientRequest, PingRequest};
+
+        let Some(client) = instance.with_client(|client| client.peer().clone()) else {
+            return;
+        };
+        let started = Instant::now();
+        let result = tokio::time::timeout(
+            PING_TIMEOUT,
+            client.send_request(ClientRequest::PingRequest(PingRequest::default())),
+        )
+        .await;
+        match result {
+            Ok(Ok(_)) => instance.record_ping_success(started.elapsed()),
+            Ok(Err(e)) => instance.record_ping_failure(&format!("Ping failed: {}", e)),
+            Err(_) => instance
+                .record_ping_failure(&format!("Ping timed out after {}s", PING_TIMEOUT.as_secs())),
+        }
+    }
+
+    /// Ping every connected instance once
+    pub async fn check_health(&self) {
+        let instances: Vec<Arc<ServerInstance>> = self
+            .instances
+            .iter()
+            .filter(|entry| entry.value().is_healthy())
+            .map(|entry| entry.value().clone())
+            .collect();
+
+        futures::future::join_all(instances.iter().map(|instance| async move {
+            Self::ping_instance(instance).await;
+            let health = instance.health();
+            if !health.healthy {
+                warn!(
+                    "[PoolService] {} is connected but unhealthy: {}",
+                    instance.server_id,
+                    health.last_error.as_deref().unwrap_or("high error rate")
+                );
+            }
+        }))
+        .await;
+
+        self.record_snapshot();
+    }
+
+    /// Add the current stats and per-server health to the history
+    fn record_snapshot(&self) {
+        let mut servers: Vec<ServerStatsSnapshot> = self
+            .instances
+            .iter()
+            .map(|entry| {
+                let instance = entry.value();
+                let health = instance.health();
+                let stats = instance.stats.read();
+                ServerStatsSnapshot {
+                    space_id: entry.key().0,
+                    server_id: instance.server_id.clone(),
+                    state: stats.state,
+                    healthy: health.healthy,
+                    latency_ms: health.last_latency_ms,
+                    avg_latency_ms: health.avg_latency_ms,
+                    error_rate: health.error_rate,
+                    consecutive_failures: health.consecutive_failures,
+                    requests_served: stats.requests_served,
+                }
+            })
+            .collect();
+        servers.sort_by(|a, b| (a.space_id, &a.server_id).cmp(&(b.space_id, &b.server_id)));
+
+        self.history.lock().push(PoolStatsSnapshot {
+            timestamp: chrono::Utc::now(),
+            stats: self.stats(),
+            servers,
+        });
+    }
+
+    /// Start the health check loop (call this once at startup).
+    ///
+    /// Pings every connected instance each `interval` until `cancel` fires;
+    /// the rolling results are available from [`ServerInstance::health`],
+    /// and each round adds a snapshot to [`Self::stats_history`].
+    pub fn start_health_checks(
+        self: Arc<Self>,
+        interval: Duration,
+        cancel: CancellationToken,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = ticks.tick() => self.check_health().await,
+                }
+            }
+            debug!("[PoolService] Health checks stopped");
+        })
+    }
+
     /// Get pool statistics
     pub fn stats(&self) -> PoolStats {
         let mut stats = PoolStats::default();
@@ -409,6 +554,15 @@ impl PoolService {
         stats
     }
 
+    /// Stats snapshots from each health check round since `since` (all kept
+    /// snapshots for `None`), oldest first
+    pub fn stats_history(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Vec<PoolStatsSnapshot> {
+        self.history.lock().since(since)
+    }
+
     /// Reconnect an existing instance (e.g., after OAuth completes)
     ///
     /// This is called when OAuth flow completes to reconnect with the new token.
@@ -462,6 +616,48 @@ impl PoolService {
         Ok(())
     }
 
+    /// Close every backend connection (gateway shutdown - keeps tokens)
+    ///
+    /// Each connection is cancelled so its transport closes in order: stdio
+    /// servers get their stdin closed and are killed if they don't exit.
+    /// Connections still closing when `timeout` elapses are dropped, which
+    /// kills any remaining child processes.
+    pub async fn shutdown_all(&self, timeout: std::time::Duration) {
+        let keys: Vec<(Uuid, String)> = self.instances.iter().map(|e| e.key().clone()).collect();
+        let mut closing = Vec::new();
+        for key in keys {
+            let Some((_, instance)) = self.instances.remove(&key) else {
+                continue;
+            };
+            if let Some(connection) = instance.take_connection() {
+                closing.push(async move {
+                    let client = match connection {
+                        McpClientConnection::Stdio { client } => client,
+                        McpClientConnection::Http { client } => client,
+                    };
+                    if let Err(e) = client.cancel().await {
+                        warn!("[PoolService] Failed to close {}/{}: {}", key.0, key.1, e);
+                    }
+                });
+            }
+        }
+
+        if closing.is_empty() {
+            return;
+        }
+        let count = closing.len();
+        info!("[PoolService] Closing {} backend connection(s)", count);
+        if tokio::time::timeout(timeout, futures::future::join_all(closing))
+            .await
+            .is_err()
+        {
+            warn!(
+                "[PoolService] Backend connections still closing after {:?} — killing",
+                timeout
+            );
+        }
+    }
+
     /// Reconnect all enabled servers on startup
     ///
     /// This is called when the gateway starts to restore connections
@@ -9,8 +9,8 @@ use crate::server::GatewayDependencies;
 use mcpmux_core::DomainEvent;
 
 use super::{
-    ConnectionService, FeatureService, OutboundOAuthManager, PoolService, RoutingService,
-    ServerManager, TokenService,
+    ConnectionService, FeatureService, OutboundOAuthManager, PoolService, ProgressRelay,
+    RoutingService, ServerManager, TokenService,
 };
 
 /// Bundle of all pool services - follows DRY principle
@@ -23,6 +23,8 @@ pub struct PoolServices {
     pub oauth_manager: Arc<OutboundOAuthManager>,
     pub routing_service: Arc<RoutingService>,
     pub server_manager: Arc<ServerManager>,
+    /// Progress from backends on tool calls, on its way to the callers
+    pub progress_relay: Arc<ProgressRelay>,
 }
 
 /// Factory for creating pool services
@@ -63,30 +65,45 @@ impl ServiceFactory {
         }
         let oauth_manager = Arc::new(oauth_manager);
 
+        // ProgressRelay - shared by the connections and the routing service
+        let progress_relay = Arc::new(ProgressRelay::new());
+
         // ConnectionService - manages connect/disconnect lifecycle
-        let connection_service = Arc::new(
-            ConnectionService::new(
-                token_service.clone(),
-                oauth_manager.clone(),
-                deps.credential_repo.clone(),
-                deps.backend_oauth_repo.clone(),
-                prefix_cache.clone(),
-            )
-            .with_log_manager(deps.log_manager.clone())
-            .with_event_tx(event_tx.clone()),
-        );
+        let mut connection_service = ConnectionService::new(
+            token_service.clone(),
+            oauth_manager.clone(),
+            deps.credential_repo.clone(),
+            deps.backend_oauth_repo.clone(),
+            prefix_cache.clone(),
+        )
+        .with_log_manager(deps.log_manager.clone())
+        .with_event_tx(event_tx.clone())
+        .with_secret_resolvers(deps.secret_resolvers.clone())
+        .with_progress_relay(progress_relay.clone());
+        if let Some(state_dir) = &deps.state_dir {
+            connection_service = connection_service
+                .with_child_processes(Arc::new(mcpmux_core::ChildProcessRegistry::new(state_dir)))
+                .with_managed_runtimes(Arc::new(mcpmux_core::ManagedRuntimes::new(
+                    state_dir,
+                    deps.settings_repo.clone(),
+                )));
+        }
+        let connection_service = Arc::new(connection_service);
 
         // FeatureService - discovers and caches MCP features
-        let feature_service = Arc::new(FeatureService::new(
-            deps.feature_repo.clone(),
-            deps.feature_set_repo.clone(),
-            prefix_cache.clone(), // Clone here since we use it again below
-        ));
+        let feature_service = Arc::new(
+            FeatureService::new(
+                deps.feature_repo.clone(),
+                deps.feature_set_repo.clone(),
+                prefix_cache.clone(), // Clone here since we use it again below
+            )
+            .with_managed_policy(deps.managed_policy.clone()),
+        );
 
         // ServerManager - event-driven orchestrator for server state
         // No longer has circular dependency with PoolService
         let server_manager = Arc::new(ServerManager::new(
-            event_tx,
+            event_tx.clone(),
             feature_service.clone(),
             connection_service.clone(),
             prefix_cache.clone(),
@@ -102,11 +119,19 @@ impl ServiceFactory {
 
         // RoutingService - handles request dispatch
         // NOTE: No longer needs token_service - RMCP's AuthClient handles token refresh per-request
-        let routing_service = Arc::new(RoutingService::new(
+        let mut routing_service = RoutingService::new(
             feature_service.clone(),
             pool_service.clone(),
             deps.log_manager.clone(),
-        ));
+        )
+        .with_installed_server_repo(deps.installed_server_repo.clone())
+        .with_content_filter(deps.content_filter.clone())
+        .with_progress_relay(progress_relay.clone())
+        .with_event_tx(event_tx);
+        if let Some(scanner) = &deps.injection_scanner {
+            routing_service = routing_service.with_injection_scanner(scanner.clone());
+        }
+        let routing_service = Arc::new(routing_service);
 
         PoolServices {
             pool_service,
@@ -116,6 +141,7 @@ impl ServiceFactory {
             oauth_manager,
             routing_service,
             server_manager,
+            progress_relay,
         }
     }
 }
@@ -0,0 +1,313 @@
+//! Opt-in JSON-RPC traffic recording
+//!
+//! When a server misbehaves, the quickest way to find out why is to look at
+//! exactly what went over the wire. Every backend transport is wrapped in a
+//! [`RecordingTransport`]; while recording is turned on for a server, each
+//! frame it sends or receives is serialized, redacted with
+//! [`mcpmux_core::redact_secrets`] and kept in a size-capped ring buffer.
+//! Recording is off by default, costs one atomic load per frame while off,
+//! and is not persisted across restarts.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use mcpmux_core::redact_secrets;
+use parking_lot::Mutex;
+use rmcp::model::{ClientJsonRpcMessage, ServerJsonRpcMessage};
+use rmcp::transport::{IntoTransport, Transport as RmcpTransport};
+use rmcp::RoleClient;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Frames kept per server
+pub const MAX_TRAFFIC_FRAMES: usize = 500;
+
+/// Bytes of frame text kept per server
+pub const MAX_TRAFFIC_BYTES: usize = 2 * 1024 * 1024;
+
+/// Larger frames are truncated to this many bytes
+const MAX_FRAME_BYTES: usize = 64 * 1024;
+
+type Key = (Uuid, String);
+
+/// Which way a frame went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrafficDirection {
+    /// Gateway to server
+    Outgoing,
+    /// Server to gateway
+    Incoming,
+}
+
+/// One recorded JSON-RPC message. Exported recordings read back into it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficFrame {
+    /// Increases by one per frame, across clears
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub direction: TrafficDirection,
+    /// `method` of requests and notifications
+    pub method: Option<String>,
+    /// JSON-RPC `id` of requests and responses
+    pub id: Option<serde_json::Value>,
+    /// Size of the serialized frame before truncation
+    pub size: usize,
+    pub truncated: bool,
+    /// The frame as JSON, with secrets redacted
+    pub raw: String,
+}
+
+#[derive(Default)]
+struct TrafficBuffer {
+    frames: VecDeque<TrafficFrame>,
+    bytes: usize,
+    next_seq: u64,
+}
+
+/// Recording switch and ring buffer for one server
+#[derive(Default)]
+pub struct ServerTraffic {
+    enabled: AtomicBool,
+    buffer: Mutex<TrafficBuffer>,
+}
+
+impl ServerTraffic {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Record a message if recording is on
+    fn record<T: Serialize>(&self, direction: TrafficDirection, message: &T) {
+        if !self.is_enabled() {
+            return;
+        }
+        let Ok(value) = serde_json::to_value(message) else {
+            return;
+        };
+        let method = value
+            .get("method")
+            .and_then(|method| method.as_str())
+            .map(str::to_string);
+        let id = value.get("id").cloned();
+        self.push(direction, method, id, value.to_string());
+    }
+
+    fn push(
+        &self,
+        direction: TrafficDirection,
+        method: Option<String>,
+        id: Option<serde_json::Value>,
+        raw: String,
+    ) {
+        let size = raw.len();
+        let truncated = size > MAX_FRAME_BYTES;
+        let mut raw = raw;
+        if truncated {
+            let mut end = MAX_FRAME_BYTES;
+            while !raw.is_char_boundary(end) {
+                end -= 1;
+            }
+            raw.truncate(end);
+        }
+        let raw = redact_secrets(&raw);
+
+        let mut buffer = self.buffer.lock();
+        let frame = TrafficFrame {
+            seq: buffer.next_seq,
+            timestamp: Utc::now(),
+            direction,
+            method,
+            id,
+            size,
+            truncated,
+            raw,
+        };
+        buffer.next_seq += 1;
+        buffer.bytes += frame.raw.len();
+        buffer.frames.push_back(frame);
+        while buffer.frames.len() > MAX_TRAFFIC_FRAMES || buffer.bytes > MAX_TRAFFIC_BYTES {
+            let Some(evicted) = buffer.frames.pop_front() else {
+                break;
+            };
+            buffer.bytes -= evicted.raw.len();
+        }
+    }
+
+    /// Recorded frames, oldest first
+    pub fn frames(&self) -> Vec<TrafficFrame> {
+        self.buffer.lock().frames.iter().cloned().collect()
+    }
+
+    fn clear(&self) {
+        let mut buffer = self.buffer.lock();
+        buffer.frames.clear();
+        buffer.bytes = 0;
+    }
+}
+
+/// Traffic recording per (space, server)
+#[derive(Default)]
+pub struct TrafficRecorder {
+    servers: DashMap<Key, Arc<ServerTraffic>>,
+}
+
+impl TrafficRecorder {
+    /// A server's recorder, handed to its transport at connect time so
+    /// recording can be toggled while it stays connected
+    pub fn server(&self, space_id: Uuid, server_id: &str) -> Arc<ServerTraffic> {
+        self.servers
+            .entry((space_id, server_id.to_string()))
+            .or_default()
+            .clone()
+    }
+
+    /// Turn recording on or off. Frames already recorded are kept.
+    pub fn set_enabled(&self, space_id: Uuid, server_id: &str, enabled: bool) {
+        self.server(space_id, server_id).set_enabled(enabled);
+    }
+
+    pub fn is_enabled(&self, space_id: Uuid, server_id: &str) -> bool {
+        self.servers
+            .get(&(space_id, server_id.to_string()))
+            .is_some_and(|traffic| traffic.is_enabled())
+    }
+
+    /// Recorded frames, oldest first
+    pub fn frames(&self, space_id: Uuid, server_id: &str) -> Vec<TrafficFrame> {
+        self.servers
+            .get(&(space_id, server_id.to_string()))
+            .map(|traffic| traffic.frames())
+            .unwrap_or_default()
+    }
+
+    /// Drop a server's recorded frames
+    pub fn clear(&self, space_id: Uuid, server_id: &str) {
+        if let Some(traffic) = self.servers.get(&(space_id, server_id.to_string())) {
+            traffic.clear();
+        }
+    }
+}
+
+/// rmcp client transport that records the frames passing through it
+pub struct RecordingTransport<T> {
+    inner: T,
+    traffic: Option<Arc<ServerTraffic>>,
+}
+
+/// Wrap a transport so its frames are recorded; `None` records nothing
+pub fn record_traffic<A, E>(
+    transport: impl IntoTransport<RoleClient, E, A>,
+    traffic: Option<Arc<ServerTraffic>>,
+) -> RecordingTransport<impl RmcpTransport<RoleClient, Error = E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    RecordingTransport {
+        inner: transport.into_transport(),
+        traffic,
+    }
+}
+
+impl<T> RmcpTransport<RoleClient> for RecordingTransport<T>
+where
+    T: RmcpTransport<RoleClient>,
+{
+    type Error = T::Error;
+
+    fn send(
+        &mut self,
+        item: ClientJsonRpcMessage,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        if let Some(traffic) = &self.traffic {
+            traffic.record(TrafficDirection::Outgoing, &item);
+        }
+        self.inner.send(item)
+    }
+
+    fn receive(&mut self) -> impl Future<Output = Option<ServerJsonRpcMessage>> + Send {
+        async move {
+            let message = self.inner.receive().await;
+            if let (Some(traffic), Some(message)) = (&self.traffic, &message) {
+                traffic.record(TrafficDirection::Incoming, message);
+            }
+            message
+        }
+    }
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn records_only_while_enabled() {
+        let recorder = TrafficRecorder::default();
+        let space_id = Uuid::new_v4();
+        let traffic = recorder.server(space_id, "github");
+
+        traffic.record(TrafficDirection::Outgoing, &json!({"method": "ping"}));
+        assert!(recorder.frames(space_id, "github").is_empty());
+
+        recorder.set_enabled(space_id, "github", true);
+        assert!(recorder.is_enabled(space_id, "github"));
+        traffic.record(
+            TrafficDirection::Outgoing,
+            &json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                    "params": {"arguments": {"api_key": "abc123"}}}),
+        );
+        traffic.record(
+            TrafficDirection::Incoming,
+            &json!({"jsonrpc": "2.0", "id": 1, "result": {}}),
+        );
+
+        let frames = recorder.frames(space_id, "github");
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].method.as_deref(), Some("tools/call"));
+        assert_eq!(frames[0].id, Some(json!(1)));
+        assert!(frames[0].raw.contains("[REDACTED]"));
+        assert!(!frames[0].raw.contains("abc123"));
+        assert_eq!(frames[1].direction, TrafficDirection::Incoming);
+        assert_eq!(frames[1].method, None);
+
+        recorder.clear(space_id, "github");
+        assert!(recorder.frames(space_id, "github").is_empty());
+        assert!(recorder.is_enabled(space_id, "github"));
+        assert!(!recorder.is_enabled(space_id, "other"));
+    }
+
+    #[test]
+    fn ring_buffer_is_capped() {
+        let traffic = ServerTraffic::default();
+        traffic.set_enabled(true);
+        for i in 0..MAX_TRAFFIC_FRAMES + 10 {
+            traffic.record(TrafficDirection::Incoming, &json!({"id": i}));
+        }
+        let frames = traffic.frames();
+        assert_eq!(frames.len(), MAX_TRAFFIC_FRAMES);
+        assert_eq!(frames[0].seq, 10);
+
+        // Large frames are truncated and push out older ones by size
+        let big = "x".repeat(MAX_FRAME_BYTES * 2);
+        for _ in 0..MAX_TRAFFIC_BYTES / MAX_FRAME_BYTES + 1 {
+            traffic.record(TrafficDirection::Incoming, &json!({ "data": big }));
+        }
+        let frames = traffic.frames();
+        assert!(frames.iter().all(|frame| frame.truncated));
+        assert_eq!(frames.len(), MAX_TRAFFIC_BYTES / MAX_FRAME_BYTES);
+        assert!(frames[0].size > MAX_FRAME_BYTES);
+    }
+}
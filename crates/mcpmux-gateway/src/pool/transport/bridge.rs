@@ -0,0 +1,56 @@
+//! In-process MCP servers
+//!
+//! Backends that don't speak MCP themselves (REST and GraphQL APIs) are
+//! adapted by an MCP server running inside the gateway. It is served on one
+//! end of an in-memory pipe and the usual client connects to the other end,
+//! so discovery, routing and traffic recording work as for any other backend.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mcpmux_core::ServerLogManager;
+use rmcp::{ServerHandler, ServiceExt};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::create_client_handler;
+use crate::pool::instance::McpClient;
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::{record_traffic, ServerTraffic};
+
+/// Buffer of the in-memory pipe between the server and its client
+const PIPE_BUFFER: usize = 64 * 1024;
+
+/// Serve `server` in process and connect a client to it
+///
+/// The server stops when the client is dropped.
+pub(super) async fn connect_in_process<S: ServerHandler>(
+    server: S,
+    server_id: &str,
+    space_id: Uuid,
+    event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    log_manager: Option<Arc<ServerLogManager>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
+    connect_timeout: Duration,
+) -> Result<McpClient, String> {
+    let (server_io, client_io) = tokio::io::duplex(PIPE_BUFFER);
+    let id = server_id.to_string();
+    tokio::spawn(async move {
+        match server.serve(server_io).await {
+            Ok(running) => {
+                let _ = running.waiting().await;
+            }
+            Err(e) => warn!(server_id = %id, "In-process server failed to start: {}", e),
+        }
+    });
+
+    let client_handler =
+        create_client_handler(server_id, space_id, event_tx, log_manager, progress);
+    let connect_future = client_handler.serve(record_traffic(client_io, traffic));
+    match tokio::time::timeout(connect_timeout, connect_future).await {
+        Ok(Ok(client)) => Ok(client),
+        Ok(Err(e)) => Err(format!("Bridge handshake failed: {}", e)),
+        Err(_) => Err(format!("Connection timeout ({:?})", connect_timeout)),
+    }
+}
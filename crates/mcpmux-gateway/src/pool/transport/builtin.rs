@@ -0,0 +1,577 @@
+//! Built-in utility server transport
+//!
+//! Runs the `mcpmux-builtin` utility server inside the gateway (see
+//! `bridge`). Its tools:
+//! - `current_time`: the current time, in UTC and an optional offset
+//! - `fetch`: an HTTP request to one of the allowed domains (redirects
+//!   included); nothing is allowed unless configured
+//! - `memory_set` / `memory_get` / `memory_list` / `memory_delete`: a
+//!   scratchpad kept separately for each client
+//!
+//! The routing service adds the calling client's ID to every call as
+//! [`CLIENT_ARGUMENT`], after argument validation so callers can't choose it.
+//! The scratchpad lives as long as the connection.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{FixedOffset, Utc};
+use mcpmux_core::{is_domain_allowed, LogLevel, LogSource, ServerLog, ServerLogManager};
+use rmcp::model::{
+    CallToolRequestParams, CallToolResult, Content, Implementation, JsonObject, ListToolsResult,
+    PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
+use serde_json::{json, Value};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::bridge::connect_in_process;
+use super::TransportType;
+use super::{Transport, TransportConnectResult};
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::ServerTraffic;
+
+/// Argument the routing service sets to the calling client's ID
+pub const CLIENT_ARGUMENT: &str = "_mcpmux_client";
+
+/// Largest response body `fetch` returns
+const MAX_FETCH_BYTES: usize = 256 * 1024;
+
+/// How long a `fetch` request may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Redirects `fetch` follows (each to an allowed domain)
+const MAX_REDIRECTS: usize = 5;
+
+/// Keys one client may store in its scratchpad
+const MAX_MEMORY_ENTRIES: usize = 256;
+
+/// Longest value the scratchpad stores, in bytes
+const MAX_MEMORY_VALUE_BYTES: usize = 64 * 1024;
+
+/// Parse a UTC offset: `Z`, `UTC`, `+02:00`, `-0530` or `+2`
+fn parse_utc_offset(offset: &str) -> Option<FixedOffset> {
+    let offset = offset.trim();
+    if offset.eq_ignore_ascii_case("z") || offset.eq_ignore_ascii_case("utc") {
+        return FixedOffset::east_opt(0);
+    }
+    let (sign, rest) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Scratchpads, by client
+#[derive(Default)]
+struct Scratchpads {
+    clients: RwLock<HashMap<String, BTreeMap<String, String>>>,
+}
+
+impl Scratchpads {
+    fn set(&self, client: &str, key: &str, value: &str) -> Result<(), String> {
+        if value.len() > MAX_MEMORY_VALUE_BYTES {
+            return Err(format!(
+                "Value is too long ({} bytes, at most {})",
+                value.len(),
+                MAX_MEMORY_VALUE_BYTES
+            ));
+        }
+        let mut clients = self.clients.write().unwrap_or_else(|e| e.into_inner());
+        let entries = clients.entry(client.to_string()).or_default();
+        if !entries.contains_key(key) && entries.len() >= MAX_MEMORY_ENTRIES {
+            return Err(format!(
+                "Scratchpad is full ({} keys); delete some first",
+                MAX_MEMORY_ENTRIES
+            ));
+        }
+        entries.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn get(&self, client: &str, key: &str) -> Option<String> {
+        self.clients
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(client)?
+            .get(key)
+            .cloned()
+    }
+
+    fn keys(&self, client: &str) -> Vec<String> {
+        self.clients
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(client)
+            .map(|entries| entries.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn delete(&self, client: &str, key: &str) -> bool {
+        self.clients
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(client)
+            .is_some_and(|entries| entries.remove(key).is_some())
+    }
+}
+
+fn tool(name: &'static str, description: &'static str, schema: Value, read_only: bool) -> Tool {
+    let schema: JsonObject = serde_json::from_value(schema).unwrap_or_default();
+    let mut tool = Tool::new(name, description, Arc::new(schema));
+    let mut annotations = tool.annotations.unwrap_or_default();
+    annotations.read_only_hint = Some(read_only);
+    tool.annotations = Some(annotations);
+    tool
+}
+
+fn tools() -> Vec<Tool> {
+    vec![
+        tool(
+            "current_time",
+            "Get the current date and time, in UTC and optionally at a UTC offset.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "utc_offset": {
+                        "type": "string",
+                        "description": "Offset to also show the time at, e.g. \"+02:00\" or \"-05:30\""
+                    }
+                }
+            }),
+            true,
+        ),
+        tool(
+            "fetch",
+            "Make an HTTP request and return the status and response body. Only domains the user has allowed can be reached.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "url": { "type": "string", "description": "http(s) URL to request" },
+                    "method": {
+                        "type": "string",
+                        "enum": ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD"],
+                        "description": "HTTP method (default GET)"
+                    },
+                    "headers": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "Request headers"
+                    },
+                    "body": { "type": "string", "description": "Request body" }
+                },
+                "required": ["url"]
+            }),
+            false,
+        ),
+        tool(
+            "memory_set",
+            "Store a value in your scratchpad under a key, replacing any previous value. Other clients can't see it.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "key": { "type": "string" },
+                    "value": { "type": "string" }
+                },
+                "required": ["key", "value"]
+            }),
+            false,
+        ),
+        tool(
+            "memory_get",
+            "Read a value from your scratchpad.",
+            json!({
+                "type": "object",
+                "properties": { "key": { "type": "string" } },
+                "required": ["key"]
+            }),
+            true,
+        ),
+        tool(
+            "memory_list",
+            "List the keys in your scratchpad.",
+            json!({ "type": "object", "properties": {} }),
+            true,
+        ),
+        tool(
+            "memory_delete",
+            "Delete a key from your scratchpad.",
+            json!({
+                "type": "object",
+                "properties": { "key": { "type": "string" } },
+                "required": ["key"]
+            }),
+            false,
+        ),
+    ]
+}
+
+fn text_result(text: impl Into<String>) -> CallToolResult {
+    CallToolResult::success(vec![Content::text(text.into())])
+}
+
+fn error_result(text: impl Into<String>) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(text.into())])
+}
+
+/// In-process utility server
+#[derive(Clone)]
+struct UtilityServer {
+    http: reqwest::Client,
+    allowed_domains: Arc<Vec<String>>,
+    scratchpads: Arc<Scratchpads>,
+}
+
+impl UtilityServer {
+    fn new(allowed_domains: Vec<String>) -> Result<Self, String> {
+        let allowed_domains = Arc::new(allowed_domains);
+        let redirect_domains = allowed_domains.clone();
+        let http = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                let allowed = attempt
+                    .url()
+                    .host_str()
+                    .is_some_and(|host| is_domain_allowed(host, &redirect_domains));
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if allowed {
+                    attempt.follow()
+                } else {
+                    attempt.error("redirect to a domain that is not allowed")
+                }
+            }))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self {
+            http,
+            allowed_domains,
+            scratchpads: Arc::default(),
+        })
+    }
+
+    fn current_time(&self, args: &JsonObject) -> CallToolResult {
+        let now = Utc::now();
+        let mut result = json!({
+            "utc": now.to_rfc3339(),
+            "unix": now.timestamp(),
+            "weekday": now.format("%A").to_string(),
+        });
+        if let Some(offset) = args.get("utc_offset").and_then(Value::as_str) {
+            let Some(offset) = parse_utc_offset(offset) else {
+                return error_result(format!("Invalid UTC offset: {}", offset));
+            };
+            let local = now.with_timezone(&offset);
+            result["local"] = json!(local.to_rfc3339());
+            result["local_weekday"] = json!(local.format("%A").to_string());
+        }
+        text_result(result.to_string())
+    }
+
+    async fn fetch(&self, args: &JsonObject) -> CallToolResult {
+        let Some(url) = args.get("url").and_then(Value::as_str) else {
+            return error_result("Missing url");
+        };
+        let url = match url::Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+            Ok(_) => return error_result("Only http and https URLs can be fetched"),
+            Err(e) => return error_result(format!("Invalid URL: {}", e)),
+        };
+        let host = url.host_str().unwrap_or_default();
+        if !is_domain_allowed(host, &self.allowed_domains) {
+            return error_result(if self.allowed_domains.is_empty() {
+                "No domains are allowed; add some in the server's configuration".to_string()
+            } else {
+                format!(
+                    "{} is not an allowed domain (allowed: {})",
+                    host,
+                    self.allowed_domains.join(", ")
+                )
+            });
+        }
+
+        let method = args.get("method").and_then(Value::as_str).unwrap_or("GET");
+        let method = match reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes()) {
+            Ok(method) => method,
+            Err(_) => return error_result(format!("Invalid method: {}", method)),
+        };
+        let mut request = self.http.request(method, url);
+        if let Some(headers) = args.get("headers").and_then(Value::as_object) {
+            for (name, value) in headers {
+                if let Some(value) = value.as_str() {
+                    request = request.header(name, value);
+                }
+            }
+        }
+        if let Some(body) = args.get("body").and_then(Value::as_str) {
+            request = request.body(body.to_string());
+        }
+
+        let mut response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return error_result(format!("Request failed: {}", e)),
+        };
+        let status = response.status();
+        let mut body = Vec::new();
+        let mut truncated = false;
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let room = MAX_FETCH_BYTES - body.len();
+                    if chunk.len() > room {
+                        body.extend_from_slice(&chunk[..room]);
+                        truncated = true;
+                        break;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => return error_result(format!("Failed to read response: {}", e)),
+            }
+        }
+
+        let mut text = format!("HTTP {}\n\n{}", status, String::from_utf8_lossy(&body));
+        if truncated {
+            text.push_str(&format!(
+                "\n\n[Response truncated to {} bytes]",
+                MAX_FETCH_BYTES
+            ));
+        }
+        text_result(text)
+    }
+
+    fn memory(&self, tool: &str, client: &str, args: &JsonObject) -> CallToolResult {
+        let key = args.get("key").and_then(Value::as_str);
+        match (tool, key) {
+            ("memory_list", _) => text_result(json!(self.scratchpads.keys(client)).to_string()),
+            ("memory_set", Some(key)) => {
+                let value = args
+                    .get("value")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                match self.scratchpads.set(client, key, value) {
+                    Ok(()) => text_result(format!("Stored {}", key)),
+                    Err(e) => error_result(e),
+                }
+            }
+            ("memory_get", Some(key)) => match self.scratchpads.get(client, key) {
+                Some(value) => text_result(value),
+                None => error_result(format!("No value stored under {}", key)),
+            },
+            ("memory_delete", Some(key)) => {
+                if self.scratchpads.delete(client, key) {
+                    text_result(format!("Deleted {}", key))
+                } else {
+                    error_result(format!("No value stored under {}", key))
+                }
+            }
+            _ => error_result("Missing key"),
+        }
+    }
+}
+
+impl ServerHandler for UtilityServer {
+    fn get_info(&self) -> ServerInfo {
+        let capabilities = ServerCapabilities::builder().enable_tools().build();
+        let mut info = ServerInfo::new(capabilities);
+        info.server_info = Implementation::new("mcpmux-builtin", env!("CARGO_PKG_VERSION"));
+        info
+    }
+
+    async fn list_tools(
+        &self,
+        _params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(tools()))
+    }
+
+    async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let mut args = params.arguments.unwrap_or_default();
+        let client = match args.remove(CLIENT_ARGUMENT) {
+            Some(Value::String(client)) => client,
+            _ => String::new(),
+        };
+        match params.name.as_ref() {
+            "current_time" => Ok(self.current_time(&args)),
+            "fetch" => Ok(self.fetch(&args).await),
+            name @ ("memory_set" | "memory_get" | "memory_list" | "memory_delete") => {
+                Ok(self.memory(name, &client, &args))
+            }
+            name => Err(McpError::invalid_params(
+                format!("Unknown tool: {}", name),
+                None,
+            )),
+        }
+    }
+}
+
+/// Transport running the built-in utility server
+pub struct BuiltinTransport {
+    allowed_domains: Vec<String>,
+    space_id: Uuid,
+    server_id: String,
+    log_manager: Option<Arc<ServerLogManager>>,
+    connect_timeout: Duration,
+    event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
+}
+
+impl BuiltinTransport {
+    pub fn new(
+        allowed_domains: Vec<String>,
+        space_id: Uuid,
+        server_id: String,
+        log_manager: Option<Arc<ServerLogManager>>,
+        connect_timeout: Duration,
+        event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    ) -> Self {
+        Self {
+            allowed_domains,
+            space_id,
+            server_id,
+            log_manager,
+            connect_timeout,
+            event_tx,
+            traffic: None,
+            progress: None,
+        }
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Log a message
+    async fn log(&self, level: LogLevel, message: String) {
+        if let Some(log_manager) = &self.log_manager {
+            let log = ServerLog::new(level, LogSource::Connection, message);
+            if let Err(e) = log_manager
+                .append(&self.space_id.to_string(), &self.server_id, log)
+                .await
+            {
+                error!("Failed to write log: {}", e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for BuiltinTransport {
+    async fn connect(&self) -> TransportConnectResult {
+        let server = match UtilityServer::new(self.allowed_domains.clone()) {
+            Ok(server) => server,
+            Err(err) => return TransportConnectResult::Failed(err),
+        };
+        self.log(
+            LogLevel::Info,
+            if self.allowed_domains.is_empty() {
+                "Starting built-in utilities; fetch has no allowed domains".to_string()
+            } else {
+                format!(
+                    "Starting built-in utilities; fetch may reach {}",
+                    self.allowed_domains.join(", ")
+                )
+            },
+        )
+        .await;
+
+        match connect_in_process(
+            server,
+            &self.server_id,
+            self.space_id,
+            self.event_tx.clone(),
+            self.log_manager.clone(),
+            self.traffic.clone(),
+            self.progress.clone(),
+            self.connect_timeout,
+        )
+        .await
+        {
+            Ok(client) => {
+                info!(server_id = %self.server_id, "Built-in utilities connected");
+                TransportConnectResult::Connected(client)
+            }
+            Err(err) => {
+                error!(server_id = %self.server_id, "{}", err);
+                self.log(LogLevel::Error, err.clone()).await;
+                TransportConnectResult::Failed(err)
+            }
+        }
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Http
+    }
+
+    fn description(&self) -> String {
+        "builtin:utilities".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_offsets_parse() {
+        let seconds = |offset: &str| parse_utc_offset(offset).map(|o| o.local_minus_utc());
+        assert_eq!(seconds("Z"), Some(0));
+        assert_eq!(seconds("+02:00"), Some(7200));
+        assert_eq!(seconds("-0530"), Some(-19800));
+        assert_eq!(seconds("+9"), Some(32400));
+        assert_eq!(seconds("+25:00"), None);
+        assert_eq!(seconds("Europe/Paris"), None);
+    }
+
+    #[test]
+    fn scratchpads_are_per_client() {
+        let scratchpads = Scratchpads::default();
+        scratchpads.set("cursor", "plan", "step 1").unwrap();
+        assert_eq!(scratchpads.get("cursor", "plan").as_deref(), Some("step 1"));
+        assert_eq!(scratchpads.get("claude", "plan"), None);
+        assert!(scratchpads.keys("claude").is_empty());
+
+        assert!(scratchpads
+            .set("cursor", "big", &"x".repeat(MAX_MEMORY_VALUE_BYTES + 1))
+            .is_err());
+        assert!(scratchpads.delete("cursor", "plan"));
+        assert!(!scratchpads.delete("cursor", "plan"));
+    }
+
+    #[test]
+    fn tools_are_listed_without_the_client_argument() {
+        for tool in tools() {
+            let properties = tool.input_schema.get("properties").unwrap();
+            assert!(properties.get(CLIENT_ARGUMENT).is_none(), "{}", tool.name);
+        }
+    }
+}
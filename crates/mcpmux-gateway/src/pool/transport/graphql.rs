@@ -0,0 +1,735 @@
+//! GraphQL bridge transport
+//!
+//! Exposes a GraphQL API as MCP tools. The schema is read by introspection at
+//! connect time and every root query and mutation field becomes a tool: the
+//! field's arguments make up the tool's input schema, and calling the tool
+//! sends the operation with the arguments as variables. The selection set is
+//! generated from the return type (its scalar fields, a few levels deep).
+//!
+//! Like the OpenAPI bridge, this is an in-process MCP server (see `bridge`).
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mcpmux_core::{LogLevel, LogSource, ServerLog, ServerLogManager};
+use rmcp::model::{
+    CallToolRequestParams, CallToolResult, Content, Implementation, JsonObject, ListToolsResult,
+    PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
+use serde_json::{json, Value};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::bridge::connect_in_process;
+use super::TransportType;
+use super::{Transport, TransportConnectResult};
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::ServerTraffic;
+
+/// Introspection query reading the types the bridge needs
+const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: false) {
+        name
+        description
+        args { name description type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name description type { ...TypeRef } }
+      enumValues(includeDeprecated: false) { name }
+    }
+  }
+}
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } } }
+}"#;
+
+/// How many levels of nested objects a generated selection set includes
+const MAX_SELECTION_DEPTH: usize = 2;
+
+/// How many levels of nested input objects are described in tool schemas
+const MAX_INPUT_DEPTH: usize = 4;
+
+/// Introspected types, by name
+struct Schema<'a> {
+    types: HashMap<&'a str, &'a Value>,
+}
+
+impl<'a> Schema<'a> {
+    fn new(introspection: &'a Value) -> Self {
+        let types = introspection
+            .get("types")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|t| Some((t.get("name")?.as_str()?, t)))
+            .collect();
+        Self { types }
+    }
+
+    /// The named type a (possibly wrapped) type reference points to
+    fn named(&self, type_ref: &Value) -> Option<&'a Value> {
+        self.types
+            .get(unwrap_type(type_ref).get("name")?.as_str()?)
+            .copied()
+    }
+
+    /// JSON schema for an input type, and whether it is required (non-null)
+    fn input_schema(&self, type_ref: &Value, depth: usize) -> (Value, bool) {
+        match type_ref.get("kind").and_then(Value::as_str) {
+            Some("NON_NULL") => {
+                let (schema, _) = self.input_schema(&type_ref["ofType"], depth);
+                (schema, true)
+            }
+            Some("LIST") => {
+                let (items, _) = self.input_schema(&type_ref["ofType"], depth);
+                (json!({ "type": "array", "items": items }), false)
+            }
+            Some("SCALAR") => {
+                let schema = match type_ref.get("name").and_then(Value::as_str) {
+                    Some("Int") => json!({ "type": "integer" }),
+                    Some("Float") => json!({ "type": "number" }),
+                    Some("Boolean") => json!({ "type": "boolean" }),
+                    Some("String") | Some("ID") => json!({ "type": "string" }),
+                    // Custom scalars accept whatever the server parses
+                    Some(name) => json!({ "description": name }),
+                    None => json!({}),
+                };
+                (schema, false)
+            }
+            Some("ENUM") => {
+                let values: Vec<Value> = self
+                    .named(type_ref)
+                    .and_then(|t| t.get("enumValues"))
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.get("name").cloned())
+                    .collect();
+                (json!({ "type": "string", "enum": values }), false)
+            }
+            Some("INPUT_OBJECT") => {
+                let Some(input) = self.named(type_ref).filter(|_| depth > 0) else {
+                    return (json!({ "type": "object" }), false);
+                };
+                let fields = input.get("inputFields").and_then(Value::as_array);
+                (self.object_schema(fields, depth - 1), false)
+            }
+            _ => (json!({}), false),
+        }
+    }
+
+    /// JSON object schema for field arguments or input fields
+    fn object_schema(&self, fields: Option<&Vec<Value>>, depth: usize) -> Value {
+        let mut properties = JsonObject::new();
+        let mut required = Vec::new();
+        for field in fields.into_iter().flatten() {
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let (mut schema, is_required) = self.input_schema(&field["type"], depth);
+            if let (Some(object), Some(description)) = (
+                schema.as_object_mut(),
+                field.get("description").filter(|d| d.is_string()),
+            ) {
+                object
+                    .entry("description")
+                    .or_insert_with(|| description.clone());
+            }
+            properties.insert(name.to_string(), schema);
+            if is_required {
+                required.push(Value::String(name.to_string()));
+            }
+        }
+
+        let mut schema = JsonObject::new();
+        schema.insert("type".to_string(), json!("object"));
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        Value::Object(schema)
+    }
+
+    /// Selection set for an output type; `None` for scalars and enums
+    fn selection(&self, type_ref: &Value, depth: usize) -> Option<String> {
+        let named = self.named(type_ref)?;
+        match named.get("kind").and_then(Value::as_str) {
+            Some("OBJECT") | Some("INTERFACE") => {}
+            Some("UNION") => return Some("{ __typename }".to_string()),
+            _ => return None,
+        }
+
+        let mut selected = Vec::new();
+        for field in named
+            .get("fields")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            // Fields with required arguments can't be selected without values
+            let needs_arguments = field
+                .get("args")
+                .and_then(Value::as_array)
+                .is_some_and(|args| {
+                    args.iter()
+                        .any(|arg| arg["type"]["kind"].as_str() == Some("NON_NULL"))
+                });
+            if needs_arguments {
+                continue;
+            }
+            match unwrap_type(&field["type"])
+                .get("kind")
+                .and_then(Value::as_str)
+            {
+                Some("SCALAR") | Some("ENUM") => selected.push(name.to_string()),
+                _ if depth > 0 => {
+                    if let Some(nested) = self.selection(&field["type"], depth - 1) {
+                        selected.push(format!("{} {}", name, nested));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if selected.is_empty() {
+            selected.push("__typename".to_string());
+        }
+        Some(format!("{{ {} }}", selected.join(" ")))
+    }
+}
+
+/// The innermost type of a type reference, without NON_NULL and LIST
+fn unwrap_type(type_ref: &Value) -> &Value {
+    let mut current = type_ref;
+    while let Some(inner) = current.get("ofType").filter(|t| !t.is_null()) {
+        current = inner;
+    }
+    current
+}
+
+/// GraphQL type as written in a variable definition, e.g. `[String!]!`
+fn type_name(type_ref: &Value) -> String {
+    match type_ref.get("kind").and_then(Value::as_str) {
+        Some("NON_NULL") => format!("{}!", type_name(&type_ref["ofType"])),
+        Some("LIST") => format!("[{}]", type_name(&type_ref["ofType"])),
+        _ => type_ref
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// A root field exposed as a tool
+#[derive(Debug, Clone)]
+struct Operation {
+    name: String,
+    field: String,
+    description: String,
+    /// Operation document, with every argument as a variable
+    document: String,
+    input_schema: JsonObject,
+}
+
+impl Operation {
+    fn tool(&self) -> Tool {
+        Tool::new(
+            self.name.clone(),
+            self.description.clone(),
+            Arc::new(self.input_schema.clone()),
+        )
+    }
+}
+
+/// Every root query and mutation field, or only those in `only`
+fn parse_operations(introspection: &Value, only: &[String]) -> Vec<Operation> {
+    let schema = Schema::new(introspection);
+    let mut operations = Vec::new();
+    let mut used_names = HashSet::new();
+
+    for (keyword, root_key) in [("query", "queryType"), ("mutation", "mutationType")] {
+        let Some(root) = introspection
+            .pointer(&format!("/{}/name", root_key))
+            .and_then(Value::as_str)
+            .and_then(|name| schema.types.get(name))
+        else {
+            continue;
+        };
+
+        for field in root
+            .get("fields")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(field_name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            if !only.is_empty() && !only.iter().any(|o| o == field_name) {
+                continue;
+            }
+
+            let mut name = field_name.to_string();
+            if used_names.contains(&name) {
+                name = format!("{}_{}", field_name, keyword);
+            }
+            used_names.insert(name.clone());
+
+            let args = field.get("args").and_then(Value::as_array);
+            let variables: Vec<String> = args
+                .into_iter()
+                .flatten()
+                .filter_map(|arg| {
+                    let arg_name = arg.get("name")?.as_str()?;
+                    Some(format!("${}: {}", arg_name, type_name(&arg["type"])))
+                })
+                .collect();
+            let arguments: Vec<String> = args
+                .into_iter()
+                .flatten()
+                .filter_map(|arg| arg.get("name")?.as_str())
+                .map(|arg_name| format!("{}: ${}", arg_name, arg_name))
+                .collect();
+
+            let mut document = keyword.to_string();
+            if !variables.is_empty() {
+                document.push_str(&format!("({})", variables.join(", ")));
+            }
+            document.push_str(&format!(" {{ {}", field_name));
+            if !arguments.is_empty() {
+                document.push_str(&format!("({})", arguments.join(", ")));
+            }
+            if let Some(selection) = schema.selection(&field["type"], MAX_SELECTION_DEPTH) {
+                document.push_str(&format!(" {}", selection));
+            }
+            document.push_str(" }");
+
+            let description = field
+                .get("description")
+                .and_then(Value::as_str)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("GraphQL {} `{}`", keyword, field_name));
+
+            let input_schema = match schema.object_schema(args, MAX_INPUT_DEPTH) {
+                Value::Object(object) => object,
+                _ => JsonObject::new(),
+            };
+
+            operations.push(Operation {
+                name,
+                field: field_name.to_string(),
+                description,
+                document,
+                input_schema,
+            });
+        }
+    }
+    operations
+}
+
+/// The messages of a GraphQL response's errors, if it has any
+fn error_messages(response: &Value) -> Option<String> {
+    let errors = response
+        .get("errors")
+        .and_then(Value::as_array)
+        .filter(|errors| !errors.is_empty())?;
+    let messages: Vec<&str> = errors
+        .iter()
+        .filter_map(|e| e.get("message").and_then(Value::as_str))
+        .collect();
+    Some(messages.join("; "))
+}
+
+/// Tool result for a GraphQL response: the field's data, or the errors
+fn tool_result(field: &str, response: &Value) -> CallToolResult {
+    if let Some(messages) = error_messages(response) {
+        return CallToolResult::error(vec![Content::text(format!("GraphQL error: {}", messages))]);
+    }
+
+    let data = response
+        .get("data")
+        .and_then(|data| data.get(field))
+        .cloned()
+        .unwrap_or(Value::Null);
+    let text = serde_json::to_string_pretty(&data).unwrap_or_else(|_| data.to_string());
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+/// Send a GraphQL request
+async fn post(
+    http: &reqwest::Client,
+    endpoint: &str,
+    headers: &HashMap<String, String>,
+    body: &Value,
+) -> Result<Value, String> {
+    let mut request = http.post(endpoint).json(body);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    // Servers often answer errors with a non-2xx status and a GraphQL body
+    match serde_json::from_str::<Value>(&text) {
+        Ok(value) if value.get("data").is_some() || value.get("errors").is_some() => Ok(value),
+        _ if !status.is_success() => Err(format!("HTTP {}: {}", status, text)),
+        _ => Err(format!("Not a GraphQL response: {}", text)),
+    }
+}
+
+/// In-process MCP server calling the API
+#[derive(Clone)]
+struct GraphQlBridge {
+    http: reqwest::Client,
+    endpoint: String,
+    headers: HashMap<String, String>,
+    operations: Arc<Vec<Operation>>,
+}
+
+impl ServerHandler for GraphQlBridge {
+    fn get_info(&self) -> ServerInfo {
+        let capabilities = ServerCapabilities::builder().enable_tools().build();
+        let mut server_info =
+            Implementation::new("mcpmux-graphql-bridge", env!("CARGO_PKG_VERSION"));
+        server_info.title = Some(self.endpoint.clone());
+        let mut info = ServerInfo::new(capabilities);
+        info.server_info = server_info;
+        info
+    }
+
+    async fn list_tools(
+        &self,
+        _params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(
+            self.operations.iter().map(Operation::tool).collect(),
+        ))
+    }
+
+    async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(operation) = self.operations.iter().find(|op| op.name == params.name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown tool: {}", params.name),
+                None,
+            ));
+        };
+        let body = json!({
+            "query": operation.document,
+            "variables": params.arguments.unwrap_or_default(),
+        });
+        Ok(
+            match post(&self.http, &self.endpoint, &self.headers, &body).await {
+                Ok(response) => tool_result(&operation.field, &response),
+                Err(e) => CallToolResult::error(vec![Content::text(e)]),
+            },
+        )
+    }
+}
+
+/// Transport bridging a GraphQL API
+pub struct GraphQlTransport {
+    endpoint: String,
+    headers: HashMap<String, String>,
+    operations: Vec<String>,
+    space_id: Uuid,
+    server_id: String,
+    log_manager: Option<Arc<ServerLogManager>>,
+    connect_timeout: Duration,
+    event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
+}
+
+impl GraphQlTransport {
+    pub fn new(
+        endpoint: String,
+        headers: HashMap<String, String>,
+        space_id: Uuid,
+        server_id: String,
+        log_manager: Option<Arc<ServerLogManager>>,
+        connect_timeout: Duration,
+        event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    ) -> Self {
+        Self {
+            endpoint,
+            headers,
+            operations: Vec::new(),
+            space_id,
+            server_id,
+            log_manager,
+            connect_timeout,
+            event_tx,
+            traffic: None,
+            progress: None,
+        }
+    }
+
+    /// Only expose these root fields
+    pub fn with_operations(mut self, operations: Vec<String>) -> Self {
+        self.operations = operations;
+        self
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Log a message
+    async fn log(&self, level: LogLevel, message: String) {
+        if let Some(log_manager) = &self.log_manager {
+            let log = ServerLog::new(level, LogSource::Connection, message);
+            if let Err(e) = log_manager
+                .append(&self.space_id.to_string(), &self.server_id, log)
+                .await
+            {
+                error!("Failed to write log: {}", e);
+            }
+        }
+    }
+
+    async fn fail(&self, err: String) -> TransportConnectResult {
+        error!(server_id = %self.server_id, "{}", err);
+        self.log(LogLevel::Error, err.clone()).await;
+        TransportConnectResult::Failed(err)
+    }
+}
+
+#[async_trait]
+impl Transport for GraphQlTransport {
+    async fn connect(&self) -> TransportConnectResult {
+        info!(
+            server_id = %self.server_id,
+            endpoint = %self.endpoint,
+            "Connecting to GraphQL API via bridge"
+        );
+
+        let http = reqwest::Client::new();
+        let introspection = tokio::time::timeout(
+            self.connect_timeout,
+            post(
+                &http,
+                &self.endpoint,
+                &self.headers,
+                &json!({ "query": INTROSPECTION_QUERY }),
+            ),
+        )
+        .await;
+        let schema = match introspection {
+            Ok(Ok(response)) => match response.pointer("/data/__schema") {
+                Some(schema) => schema.clone(),
+                None => {
+                    let reason = error_messages(&response)
+                        .unwrap_or_else(|| "no schema in the response".to_string());
+                    return self.fail(format!("Introspection failed: {}", reason)).await;
+                }
+            },
+            Ok(Err(e)) => return self.fail(format!("Introspection failed: {}", e)).await,
+            Err(_) => {
+                return self
+                    .fail(format!("Connection timeout ({:?})", self.connect_timeout))
+                    .await
+            }
+        };
+
+        let operations = parse_operations(&schema, &self.operations);
+        if operations.is_empty() {
+            return self
+                .fail("GraphQL schema has no queries or mutations to expose".to_string())
+                .await;
+        }
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Bridging {} operations of {}",
+                operations.len(),
+                self.endpoint
+            ),
+        )
+        .await;
+
+        let bridge = GraphQlBridge {
+            http,
+            endpoint: self.endpoint.clone(),
+            headers: self.headers.clone(),
+            operations: Arc::new(operations),
+        };
+        match connect_in_process(
+            bridge,
+            &self.server_id,
+            self.space_id,
+            self.event_tx.clone(),
+            self.log_manager.clone(),
+            self.traffic.clone(),
+            self.progress.clone(),
+            self.connect_timeout,
+        )
+        .await
+        {
+            Ok(client) => {
+                info!(server_id = %self.server_id, "GraphQL bridge connected");
+                TransportConnectResult::Connected(client)
+            }
+            Err(err) => self.fail(err).await,
+        }
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Http
+    }
+
+    fn description(&self) -> String {
+        format!("graphql:{}", self.endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(name: &str) -> Value {
+        json!({ "kind": "SCALAR", "name": name, "ofType": null })
+    }
+
+    fn non_null(inner: Value) -> Value {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": inner })
+    }
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn schema() -> Value {
+        json!({
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "types": [
+                {
+                    "kind": "OBJECT", "name": "Query",
+                    "fields": [
+                        {
+                            "name": "issue",
+                            "description": "Look up an issue",
+                            "args": [{ "name": "id", "description": "Issue ID", "type": non_null(scalar("ID")) }],
+                            "type": named("OBJECT", "Issue")
+                        },
+                        { "name": "count", "args": [], "type": scalar("Int") }
+                    ]
+                },
+                {
+                    "kind": "OBJECT", "name": "Mutation",
+                    "fields": [{
+                        "name": "createIssue",
+                        "args": [{ "name": "input", "type": non_null(named("INPUT_OBJECT", "IssueInput")) }],
+                        "type": named("OBJECT", "Issue")
+                    }]
+                },
+                {
+                    "kind": "OBJECT", "name": "Issue",
+                    "fields": [
+                        { "name": "id", "args": [], "type": non_null(scalar("ID")) },
+                        { "name": "state", "args": [], "type": named("ENUM", "State") },
+                        { "name": "author", "args": [], "type": named("OBJECT", "User") },
+                        {
+                            "name": "comments",
+                            "args": [{ "name": "first", "type": non_null(scalar("Int")) }],
+                            "type": named("OBJECT", "User")
+                        }
+                    ]
+                },
+                {
+                    "kind": "OBJECT", "name": "User",
+                    "fields": [{ "name": "login", "args": [], "type": scalar("String") }]
+                },
+                { "kind": "ENUM", "name": "State", "enumValues": [{ "name": "OPEN" }, { "name": "CLOSED" }] },
+                {
+                    "kind": "INPUT_OBJECT", "name": "IssueInput",
+                    "inputFields": [
+                        { "name": "title", "type": non_null(scalar("String")) },
+                        { "name": "labels", "type": { "kind": "LIST", "name": null, "ofType": non_null(scalar("String")) } },
+                        { "name": "state", "type": named("ENUM", "State") }
+                    ]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn root_fields_become_tools() {
+        let operations = parse_operations(&schema(), &[]);
+        let names: Vec<&str> = operations.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, vec!["issue", "count", "createIssue"]);
+
+        let issue = &operations[0];
+        assert_eq!(issue.description, "Look up an issue");
+        assert_eq!(
+            issue.document,
+            "query($id: ID!) { issue(id: $id) { id state author { login } } }"
+        );
+        assert_eq!(issue.input_schema["required"], json!(["id"]));
+        assert_eq!(issue.input_schema["properties"]["id"]["type"], "string");
+
+        assert_eq!(operations[1].document, "query { count }");
+
+        let create = &operations[2];
+        assert_eq!(
+            create.document,
+            "mutation($input: IssueInput!) { createIssue(input: $input) { id state author { login } } }"
+        );
+        let input = &create.input_schema["properties"]["input"];
+        assert_eq!(input["required"], json!(["title"]));
+        assert_eq!(input["properties"]["labels"]["items"]["type"], "string");
+        assert_eq!(
+            input["properties"]["state"]["enum"],
+            json!(["OPEN", "CLOSED"])
+        );
+
+        let only = parse_operations(&schema(), &["createIssue".to_string()]);
+        assert_eq!(only.len(), 1);
+    }
+
+    #[test]
+    fn results_carry_field_data_or_errors() {
+        let ok = tool_result("count", &json!({ "data": { "count": 3 } }));
+        assert_eq!(ok.is_error, Some(false));
+
+        let failed = tool_result(
+            "count",
+            &json!({ "data": null, "errors": [{ "message": "Not allowed" }] }),
+        );
+        assert_eq!(failed.is_error, Some(true));
+    }
+}
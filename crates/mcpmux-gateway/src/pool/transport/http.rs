@@ -21,6 +21,8 @@ use uuid::Uuid;
 use super::TransportType;
 use super::{create_client_handler, Transport, TransportConnectResult};
 use crate::pool::credential_store::DatabaseCredentialStore;
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::{record_traffic, ServerTraffic};
 
 /// HTTP transport for Streamable HTTP MCP servers
 ///
@@ -30,6 +32,7 @@ use crate::pool::credential_store::DatabaseCredentialStore;
 pub struct HttpTransport {
     url: String,
     headers: HashMap<String, String>,
+    query: HashMap<String, String>,
     space_id: Uuid,
     server_id: String,
     credential_repo: Arc<dyn CredentialRepository>,
@@ -37,6 +40,8 @@ pub struct HttpTransport {
     log_manager: Option<Arc<ServerLogManager>>,
     connect_timeout: Duration,
     event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
 }
 
 impl HttpTransport {
@@ -55,6 +60,7 @@ impl HttpTransport {
         Self {
             url,
             headers,
+            query: HashMap::new(),
             space_id,
             server_id,
             credential_repo,
@@ -62,9 +68,29 @@ impl HttpTransport {
             log_manager,
             connect_timeout,
             event_tx,
+            traffic: None,
+            progress: None,
         }
     }
 
+    /// Query parameters to add to the URL of every request
+    pub fn with_query(mut self, query: HashMap<String, String>) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Log a message
     async fn log(&self, level: LogLevel, source: LogSource, message: String) {
         if let Some(log_manager) = &self.log_manager {
@@ -253,7 +279,7 @@ impl HttpTransport {
             Err(err) => return TransportConnectResult::Failed(err),
         };
         let auth_client = AuthClient::new(base_client, auth_manager);
-        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.url.as_str());
+        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.endpoint_url());
         let transport = StreamableHttpClientTransport::with_client(auth_client, transport_config);
 
         let client_handler = create_client_handler(
@@ -261,9 +287,10 @@ impl HttpTransport {
             self.space_id,
             self.event_tx.clone(),
             self.log_manager.clone(),
+            self.progress.clone(),
         );
 
-        let connect_future = client_handler.serve(transport);
+        let connect_future = client_handler.serve(record_traffic(transport, self.traffic.clone()));
         match tokio::time::timeout(self.connect_timeout, connect_future).await {
             Ok(Ok(client)) => {
                 info!(
@@ -376,7 +403,7 @@ impl HttpTransport {
             Err(err) => return TransportConnectResult::Failed(err),
         };
 
-        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.url.as_str());
+        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.endpoint_url());
         let transport = StreamableHttpClientTransport::with_client(client, transport_config);
 
         let client_handler = create_client_handler(
@@ -384,9 +411,10 @@ impl HttpTransport {
             self.space_id,
             self.event_tx.clone(),
             self.log_manager.clone(),
+            self.progress.clone(),
         );
 
-        let connect_future = client_handler.serve(transport);
+        let connect_future = client_handler.serve(record_traffic(transport, self.traffic.clone()));
         match tokio::time::timeout(self.connect_timeout, connect_future).await {
             Ok(Ok(client)) => {
                 info!(
@@ -435,6 +463,34 @@ impl HttpTransport {
         }
     }
 
+    /// The URL requests are sent to: `url` with the configured query parameters,
+    /// which replace any of the same name already in it.
+    ///
+    /// Logs, OAuth discovery and the instance key keep using the bare `url`,
+    /// since query parameters often carry API keys.
+    fn endpoint_url(&self) -> String {
+        if self.query.is_empty() {
+            return self.url.clone();
+        }
+        let Ok(mut endpoint) = url::Url::parse(&self.url) else {
+            // Reported by connect(); nothing to add the parameters to
+            return self.url.clone();
+        };
+        let kept: Vec<(String, String)> = endpoint
+            .query_pairs()
+            .filter(|(k, _)| !self.query.contains_key(k.as_ref()))
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        let mut added: Vec<_> = self.query.iter().collect();
+        added.sort();
+        endpoint
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(kept)
+            .extend_pairs(added);
+        endpoint.to_string()
+    }
+
     /// Build a reqwest HeaderMap from definition-provided headers.
     ///
     /// These headers (resolved from `${input:ID}` placeholders) are always applied
@@ -501,16 +557,17 @@ impl HttpTransport {
             Err(err) => return TransportConnectResult::Failed(err),
         };
 
-        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.url.as_str());
+        let transport_config = StreamableHttpClientTransportConfig::with_uri(self.endpoint_url());
         let transport = StreamableHttpClientTransport::with_client(client, transport_config);
         let client_handler = create_client_handler(
             &self.server_id,
             self.space_id,
             self.event_tx.clone(),
             self.log_manager.clone(),
+            self.progress.clone(),
         );
 
-        let connect_future = client_handler.serve(transport);
+        let connect_future = client_handler.serve(record_traffic(transport, self.traffic.clone()));
         match tokio::time::timeout(self.connect_timeout, connect_future).await {
             Ok(Ok(client)) => {
                 info!(
@@ -860,6 +917,39 @@ mod tests {
         assert!(!HttpTransport::requires_oauth("timeout"));
     }
 
+    // ── endpoint_url tests ──
+
+    #[test]
+    fn test_endpoint_url_adds_query_parameters() {
+        let transport = make_transport(HashMap::new(), Arc::new(MockCredentialRepo::new()));
+        assert_eq!(transport.endpoint_url(), "https://example.com/mcp");
+
+        let transport = HttpTransport::new(
+            "https://example.com/mcp?format=xml&v=2".to_string(),
+            HashMap::new(),
+            Uuid::new_v4(),
+            "test-server".to_string(),
+            Arc::new(MockCredentialRepo::new()),
+            Arc::new(MockOAuthRepo),
+            None,
+            Duration::from_secs(10),
+            None,
+        )
+        .with_query(HashMap::from([
+            ("format".to_string(), "json".to_string()),
+            ("api_key".to_string(), "a b&c".to_string()),
+        ]));
+        assert_eq!(
+            transport.endpoint_url(),
+            "https://example.com/mcp?v=2&api_key=a+b%26c&format=json"
+        );
+        // The bare URL is what gets logged
+        assert_eq!(
+            transport.description(),
+            "http:https://example.com/mcp?format=xml&v=2"
+        );
+    }
+
     // ── build_default_headers tests ──
 
     #[test]
@@ -4,10 +4,16 @@
 //! This follows the Open/Closed Principle - new transports can be added without
 //! modifying existing code.
 
+mod bridge;
+mod builtin;
+mod graphql;
 mod http;
+mod openapi;
 pub mod resolution;
+pub mod runtime;
 pub mod shell_env;
 mod stdio;
+mod wasm;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,13 +22,19 @@ use async_trait::async_trait;
 use mcpmux_core::{CredentialRepository, OutboundOAuthRepository, ServerLogManager};
 use uuid::Uuid;
 
+pub use builtin::{BuiltinTransport, CLIENT_ARGUMENT as BUILTIN_CLIENT_ARGUMENT};
+pub use graphql::GraphQlTransport;
 pub use http::HttpTransport;
+pub use openapi::OpenApiTransport;
 pub use stdio::{configure_child_process_platform, StdioTransport};
+pub use wasm::{WasiModule, WasmTransport};
 
 // Re-export TransportType from mcpmux-core as the single source of truth
 pub use mcpmux_core::TransportType;
 
 use super::instance::{McpClient, McpClientHandler};
+use super::progress::ProgressRelay;
+use super::traffic::ServerTraffic;
 
 /// Result of a transport connection attempt
 pub enum TransportConnectResult {
@@ -32,6 +44,12 @@ pub enum TransportConnectResult {
     OAuthRequired { server_url: String },
     /// Connection failed
     Failed(String),
+    /// A stdio server process exited during startup
+    Exited {
+        error: String,
+        /// Last stderr lines before it exited
+        stderr: Vec<String>,
+    },
 }
 
 /// Transport trait for MCP connections
@@ -61,27 +79,65 @@ pub enum ResolvedTransport {
         command: String,
         args: Vec<String>,
         env: HashMap<String, String>,
+        /// Pass the full desktop environment instead of the sanitized baseline
+        inherit_env: bool,
     },
     Http {
         url: String,
         headers: HashMap<String, String>,
+        /// Query parameters added to the URL of every request
+        query: HashMap<String, String>,
+    },
+    /// REST API bridged to MCP tools in process
+    OpenApi {
+        spec_url: String,
+        base_url: Option<String>,
+        headers: HashMap<String, String>,
+        query: HashMap<String, String>,
+        /// Operation IDs to expose; all if empty
+        operations: Vec<String>,
+    },
+    /// GraphQL API bridged to MCP tools in process
+    GraphQl {
+        endpoint: String,
+        headers: HashMap<String, String>,
+        /// Root fields to expose; all queries and mutations if empty
+        operations: Vec<String>,
+    },
+    /// Built-in utility server, run in process
+    Builtin {
+        /// Domains `fetch` may reach, already normalized
+        allowed_domains: Vec<String>,
     },
+    /// WASI module, run in process with the access it was granted
+    Wasm(WasiModule),
 }
 
 impl ResolvedTransport {
     /// Get the transport type for this config
+    ///
+    /// The OpenAPI and GraphQL bridges count as HTTP: they run no process
+    /// and talk to the API over HTTP. So do the built-in utilities. A WASI
+    /// module counts as stdio, which is how it talks.
     pub fn transport_type(&self) -> TransportType {
         match self {
-            ResolvedTransport::Stdio { .. } => TransportType::Stdio,
-            ResolvedTransport::Http { .. } => TransportType::Http,
+            ResolvedTransport::Stdio { .. } | ResolvedTransport::Wasm(_) => TransportType::Stdio,
+            ResolvedTransport::Http { .. }
+            | ResolvedTransport::OpenApi { .. }
+            | ResolvedTransport::GraphQl { .. }
+            | ResolvedTransport::Builtin { .. } => TransportType::Http,
         }
     }
 
-    /// Get URL for HTTP transports
+    /// Get URL for HTTP transports (MCP endpoints only, for OAuth)
     pub fn url(&self) -> Option<&str> {
         match self {
             ResolvedTransport::Http { url, .. } => Some(url),
-            ResolvedTransport::Stdio { .. } => None,
+            ResolvedTransport::Stdio { .. }
+            | ResolvedTransport::OpenApi { .. }
+            | ResolvedTransport::GraphQl { .. }
+            | ResolvedTransport::Builtin { .. }
+            | ResolvedTransport::Wasm(_) => None,
         }
     }
 
@@ -92,10 +148,16 @@ impl ResolvedTransport {
 
         let mut hasher = DefaultHasher::new();
         match self {
-            ResolvedTransport::Stdio { command, args, env } => {
+            ResolvedTransport::Stdio {
+                command,
+                args,
+                env,
+                inherit_env,
+            } => {
                 "stdio".hash(&mut hasher);
                 command.hash(&mut hasher);
                 args.hash(&mut hasher);
+                inherit_env.hash(&mut hasher);
                 let mut env_pairs: Vec<_> = env.iter().collect();
                 env_pairs.sort_by_key(|(k, _)| *k);
                 for (k, v) in env_pairs {
@@ -103,7 +165,11 @@ impl ResolvedTransport {
                     v.hash(&mut hasher);
                 }
             }
-            ResolvedTransport::Http { url, headers } => {
+            ResolvedTransport::Http {
+                url,
+                headers,
+                query,
+            } => {
                 "http".hash(&mut hasher);
                 url.hash(&mut hasher);
                 let mut header_pairs: Vec<_> = headers.iter().collect();
@@ -115,6 +181,60 @@ impl ResolvedTransport {
                         v.hash(&mut hasher);
                     }
                 }
+                // Query parameters pick the endpoint, so they all count
+                let mut query_pairs: Vec<_> = query.iter().collect();
+                query_pairs.sort();
+                query_pairs.hash(&mut hasher);
+            }
+            ResolvedTransport::OpenApi {
+                spec_url,
+                base_url,
+                headers,
+                query,
+                operations,
+            } => {
+                "openapi".hash(&mut hasher);
+                spec_url.hash(&mut hasher);
+                base_url.hash(&mut hasher);
+                operations.hash(&mut hasher);
+                let mut header_pairs: Vec<_> = headers
+                    .iter()
+                    .filter(|(k, _)| !k.eq_ignore_ascii_case("authorization"))
+                    .collect();
+                header_pairs.sort();
+                header_pairs.hash(&mut hasher);
+                let mut query_pairs: Vec<_> = query.iter().collect();
+                query_pairs.sort();
+                query_pairs.hash(&mut hasher);
+            }
+            ResolvedTransport::GraphQl {
+                endpoint,
+                headers,
+                operations,
+            } => {
+                "graphql".hash(&mut hasher);
+                endpoint.hash(&mut hasher);
+                operations.hash(&mut hasher);
+                let mut header_pairs: Vec<_> = headers
+                    .iter()
+                    .filter(|(k, _)| !k.eq_ignore_ascii_case("authorization"))
+                    .collect();
+                header_pairs.sort();
+                header_pairs.hash(&mut hasher);
+            }
+            ResolvedTransport::Builtin { allowed_domains } => {
+                "builtin".hash(&mut hasher);
+                allowed_domains.hash(&mut hasher);
+            }
+            ResolvedTransport::Wasm(module) => {
+                "wasm".hash(&mut hasher);
+                module.path.hash(&mut hasher);
+                module.args.hash(&mut hasher);
+                module.dirs.hash(&mut hasher);
+                module.network.hash(&mut hasher);
+                let mut env_pairs: Vec<_> = module.env.iter().collect();
+                env_pairs.sort();
+                env_pairs.hash(&mut hasher);
             }
         }
         hasher.finish()
@@ -139,29 +259,117 @@ impl TransportFactory {
         log_manager: Option<Arc<ServerLogManager>>,
         connect_timeout: std::time::Duration,
         event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+        child_processes: Option<Arc<mcpmux_core::ChildProcessRegistry>>,
+        managed_runtimes: Option<Arc<mcpmux_core::ManagedRuntimes>>,
+        traffic: Option<Arc<ServerTraffic>>,
+        progress: Option<Arc<ProgressRelay>>,
     ) -> Box<dyn Transport> {
         match config {
-            ResolvedTransport::Stdio { command, args, env } => Box::new(StdioTransport::new(
-                command.clone(),
-                args.clone(),
-                env.clone(),
-                space_id,
-                server_id,
-                log_manager,
-                connect_timeout,
-                event_tx,
-            )),
-            ResolvedTransport::Http { url, headers } => Box::new(HttpTransport::new(
-                url.clone(),
-                headers.clone(),
-                space_id,
-                server_id,
-                credential_repo,
-                backend_oauth_repo,
-                log_manager,
-                connect_timeout,
-                event_tx,
-            )),
+            ResolvedTransport::Stdio {
+                command,
+                args,
+                env,
+                inherit_env,
+            } => Box::new(
+                StdioTransport::new(
+                    command.clone(),
+                    args.clone(),
+                    env.clone(),
+                    space_id,
+                    server_id,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_inherit_env(*inherit_env)
+                .with_child_processes(child_processes)
+                .with_managed_runtimes(managed_runtimes)
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
+            ResolvedTransport::Http {
+                url,
+                headers,
+                query,
+            } => Box::new(
+                HttpTransport::new(
+                    url.clone(),
+                    headers.clone(),
+                    space_id,
+                    server_id,
+                    credential_repo,
+                    backend_oauth_repo,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_query(query.clone())
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
+            ResolvedTransport::OpenApi {
+                spec_url,
+                base_url,
+                headers,
+                query,
+                operations,
+            } => Box::new(
+                OpenApiTransport::new(
+                    spec_url.clone(),
+                    base_url.clone(),
+                    space_id,
+                    server_id,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_auth(headers.clone(), query.clone())
+                .with_operations(operations.clone())
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
+            ResolvedTransport::GraphQl {
+                endpoint,
+                headers,
+                operations,
+            } => Box::new(
+                GraphQlTransport::new(
+                    endpoint.clone(),
+                    headers.clone(),
+                    space_id,
+                    server_id,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_operations(operations.clone())
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
+            ResolvedTransport::Builtin { allowed_domains } => Box::new(
+                BuiltinTransport::new(
+                    allowed_domains.clone(),
+                    space_id,
+                    server_id,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
+            ResolvedTransport::Wasm(module) => Box::new(
+                WasmTransport::new(
+                    module.clone(),
+                    space_id,
+                    server_id,
+                    log_manager,
+                    connect_timeout,
+                    event_tx,
+                )
+                .with_traffic(traffic)
+                .with_progress_relay(progress),
+            ),
         }
     }
 }
@@ -172,6 +380,7 @@ pub fn create_client_handler(
     space_id: uuid::Uuid,
     event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
     log_manager: Option<Arc<ServerLogManager>>,
+    progress: Option<Arc<ProgressRelay>>,
 ) -> McpClientHandler {
-    McpClientHandler::new(server_id, space_id, event_tx, log_manager)
+    McpClientHandler::new(server_id, space_id, event_tx, log_manager).with_progress_relay(progress)
 }
@@ -0,0 +1,863 @@
+//! OpenAPI bridge transport
+//!
+//! Exposes a REST API described by an OpenAPI 3 document as MCP tools. The
+//! document is loaded at connect time and every operation becomes a tool:
+//! its parameters and JSON request body make up the tool's input schema, and
+//! calling the tool sends the request and returns the response body.
+//!
+//! The bridge is an in-process MCP server (see `bridge`), so feature
+//! discovery, routing and FeatureSet permissions treat it like any other
+//! backend.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mcpmux_core::{LogLevel, LogSource, ServerLog, ServerLogManager};
+use rmcp::model::{
+    CallToolRequestParams, CallToolResult, Content, Implementation, JsonObject, ListToolsResult,
+    PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
+use serde_json::{json, Value};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::bridge::connect_in_process;
+use super::TransportType;
+use super::{Transport, TransportConnectResult};
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::ServerTraffic;
+
+/// HTTP methods an OpenAPI path item can define operations for
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How many `$ref`s deep schemas are inlined; recursive schemas stop here
+const MAX_REF_DEPTH: usize = 8;
+
+/// Tool argument carrying the JSON request body
+const BODY_ARGUMENT: &str = "body";
+
+/// Longest tool name clients accept
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+}
+
+#[derive(Debug, Clone)]
+struct OperationParameter {
+    name: String,
+    location: ParameterLocation,
+}
+
+/// An API operation exposed as a tool
+#[derive(Debug, Clone)]
+struct Operation {
+    name: String,
+    method: String,
+    path: String,
+    description: String,
+    parameters: Vec<OperationParameter>,
+    has_body: bool,
+    input_schema: JsonObject,
+}
+
+/// A request ready to send
+#[derive(Debug, PartialEq)]
+struct PreparedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Value>,
+}
+
+impl Operation {
+    fn tool(&self) -> Tool {
+        Tool::new(
+            self.name.clone(),
+            self.description.clone(),
+            Arc::new(self.input_schema.clone()),
+        )
+    }
+
+    /// Build the request for a call, with `default_query` (e.g. an API key)
+    /// added to every URL
+    fn prepare(
+        &self,
+        base_url: &str,
+        default_query: &HashMap<String, String>,
+        arguments: &JsonObject,
+    ) -> Result<PreparedRequest, String> {
+        let mut path = self.path.clone();
+        let mut query: Vec<(String, String)> = default_query
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        query.sort();
+        let mut headers = Vec::new();
+
+        for parameter in &self.parameters {
+            let Some(value) = arguments.get(&parameter.name).filter(|v| !v.is_null()) else {
+                if parameter.location == ParameterLocation::Path {
+                    return Err(format!("Missing path parameter '{}'", parameter.name));
+                }
+                continue;
+            };
+            match parameter.location {
+                ParameterLocation::Path => {
+                    path = path.replace(
+                        &format!("{{{}}}", parameter.name),
+                        &urlencoding::encode(&argument_string(value)),
+                    );
+                }
+                ParameterLocation::Query => match value {
+                    Value::Array(items) => query.extend(
+                        items
+                            .iter()
+                            .map(|item| (parameter.name.clone(), argument_string(item))),
+                    ),
+                    value => query.push((parameter.name.clone(), argument_string(value))),
+                },
+                ParameterLocation::Header => {
+                    headers.push((parameter.name.clone(), argument_string(value)));
+                }
+            }
+        }
+
+        let mut url = format!("{}{}", base_url.trim_end_matches('/'), path);
+        if !query.is_empty() {
+            let encoded: Vec<String> = query
+                .iter()
+                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                .collect();
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&encoded.join("&"));
+        }
+
+        let body = if self.has_body {
+            arguments.get(BODY_ARGUMENT).cloned()
+        } else {
+            None
+        };
+
+        Ok(PreparedRequest {
+            method: self.method.clone(),
+            url,
+            headers,
+            body,
+        })
+    }
+}
+
+fn argument_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Follow a local `$ref` (`#/components/...`)
+fn lookup_ref<'a>(spec: &'a Value, reference: &str) -> Option<&'a Value> {
+    spec.pointer(reference.strip_prefix('#')?)
+}
+
+/// Follow `$ref`s until reaching an object that isn't one
+fn resolve_ref<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_REF_DEPTH {
+        match current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| lookup_ref(spec, r))
+        {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Copy a schema with its `$ref`s inlined, since clients can't see the
+/// document's components
+fn inline_refs(spec: &Value, value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                return match lookup_ref(spec, reference) {
+                    Some(target) if depth > 0 => inline_refs(spec, target, depth - 1),
+                    _ => json!({}),
+                };
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), inline_refs(spec, v, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| inline_refs(spec, item, depth))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// A tool name from the operation ID, or from the method and path
+fn tool_name(operation_id: Option<&str>, method: &str, path: &str) -> String {
+    let raw = match operation_id {
+        Some(id) => id.to_string(),
+        None => format!("{}_{}", method, path),
+    };
+    let mut name = String::new();
+    for c in raw.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '-' {
+            c
+        } else {
+            '_'
+        };
+        if !(c == '_' && name.ends_with('_')) {
+            name.push(c);
+        }
+    }
+    let name: String = name
+        .trim_matches('_')
+        .chars()
+        .take(MAX_TOOL_NAME_LEN)
+        .collect();
+    if name.is_empty() {
+        method.to_string()
+    } else {
+        name
+    }
+}
+
+/// The operation's parameters, path-level ones first, overridden by
+/// operation-level ones with the same name and location
+fn operation_parameters<'a>(
+    spec: &'a Value,
+    path_item: &'a Value,
+    operation: &'a Value,
+) -> Vec<&'a Value> {
+    let mut parameters: Vec<&Value> = Vec::new();
+    let lists = [path_item.get("parameters"), operation.get("parameters")];
+    for parameter in lists
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+        .flatten()
+    {
+        let parameter = resolve_ref(spec, parameter);
+        let key = (parameter.get("name"), parameter.get("in"));
+        parameters.retain(|p| (p.get("name"), p.get("in")) != key);
+        parameters.push(parameter);
+    }
+    parameters
+}
+
+/// The JSON schema of an operation's request body, and whether it is required
+fn request_body_schema(spec: &Value, operation: &Value) -> Option<(Value, bool)> {
+    let body = resolve_ref(spec, operation.get("requestBody")?);
+    let content = body.get("content")?.as_object()?;
+    let media = content.get("application/json").or_else(|| {
+        content
+            .iter()
+            .find(|(media_type, _)| media_type.ends_with("+json"))
+            .map(|(_, media)| media)
+    })?;
+    let schema = media
+        .get("schema")
+        .map(|s| inline_refs(spec, s, MAX_REF_DEPTH))
+        .unwrap_or_else(|| json!({}));
+    let required = body
+        .get("required")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    Some((schema, required))
+}
+
+/// Every operation in the document, or only those in `only` (by operation ID)
+fn parse_operations(spec: &Value, only: &[String]) -> Vec<Operation> {
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut operations = Vec::new();
+    let mut used_names = HashSet::new();
+    for (path, path_item) in paths {
+        let path_item = resolve_ref(spec, path_item);
+        for method in METHODS {
+            let Some(operation) = path_item.get(method) else {
+                continue;
+            };
+            let operation_id = operation.get("operationId").and_then(Value::as_str);
+            if !only.is_empty() && !operation_id.is_some_and(|id| only.iter().any(|o| o == id)) {
+                continue;
+            }
+
+            let mut name = tool_name(operation_id, method, path);
+            let mut suffix = 2;
+            while used_names.contains(&name) {
+                name = format!("{}_{}", tool_name(operation_id, method, path), suffix);
+                suffix += 1;
+            }
+            used_names.insert(name.clone());
+
+            let text = |key: &str| operation.get(key).and_then(Value::as_str);
+            let description = match (text("summary"), text("description")) {
+                (Some(summary), Some(description)) if summary != description => {
+                    format!("{}\n\n{}", summary, description)
+                }
+                (Some(summary), _) => summary.to_string(),
+                (None, Some(description)) => description.to_string(),
+                (None, None) => format!("{} {}", method.to_uppercase(), path),
+            };
+
+            let mut properties = JsonObject::new();
+            let mut required = Vec::new();
+            let mut parameters = Vec::new();
+            for parameter in operation_parameters(spec, path_item, operation) {
+                let Some(param_name) = parameter.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let location = match parameter.get("in").and_then(Value::as_str) {
+                    Some("path") => ParameterLocation::Path,
+                    Some("query") => ParameterLocation::Query,
+                    Some("header") => ParameterLocation::Header,
+                    // Cookies are left to the configured headers
+                    _ => continue,
+                };
+                if properties.contains_key(param_name) {
+                    continue;
+                }
+
+                let mut schema = parameter
+                    .get("schema")
+                    .map(|s| inline_refs(spec, s, MAX_REF_DEPTH))
+                    .unwrap_or_else(|| json!({ "type": "string" }));
+                if let (Some(object), Some(description)) =
+                    (schema.as_object_mut(), parameter.get("description"))
+                {
+                    object
+                        .entry("description")
+                        .or_insert_with(|| description.clone());
+                }
+                properties.insert(param_name.to_string(), schema);
+
+                let is_required = location == ParameterLocation::Path
+                    || parameter
+                        .get("required")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                if is_required {
+                    required.push(Value::String(param_name.to_string()));
+                }
+                parameters.push(OperationParameter {
+                    name: param_name.to_string(),
+                    location,
+                });
+            }
+
+            let body = request_body_schema(spec, operation);
+            if let Some((schema, body_required)) = &body {
+                properties.insert(BODY_ARGUMENT.to_string(), schema.clone());
+                if *body_required {
+                    required.push(Value::String(BODY_ARGUMENT.to_string()));
+                }
+            }
+
+            let mut input_schema = JsonObject::new();
+            input_schema.insert("type".to_string(), json!("object"));
+            input_schema.insert("properties".to_string(), Value::Object(properties));
+            if !required.is_empty() {
+                input_schema.insert("required".to_string(), Value::Array(required));
+            }
+
+            operations.push(Operation {
+                name,
+                method: method.to_uppercase(),
+                path: path.clone(),
+                description,
+                parameters,
+                has_body: body.is_some(),
+                input_schema,
+            });
+        }
+    }
+    operations
+}
+
+/// The API base URL from the document's first server, resolved against the
+/// document's own URL when relative
+fn spec_base_url(spec: &Value, spec_url: &str) -> Option<String> {
+    let server = spec.get("servers")?.as_array()?.first()?;
+    let mut url = server.get("url")?.as_str()?.to_string();
+    if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+        for (name, variable) in variables {
+            if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                url = url.replace(&format!("{{{}}}", name), default);
+            }
+        }
+    }
+    if !url.contains("://") {
+        url = url::Url::parse(spec_url).ok()?.join(&url).ok()?.to_string();
+    }
+    Some(url.trim_end_matches('/').to_string())
+}
+
+/// In-process MCP server calling the API
+#[derive(Clone)]
+struct OpenApiBridge {
+    http: reqwest::Client,
+    title: String,
+    base_url: String,
+    headers: HashMap<String, String>,
+    query: HashMap<String, String>,
+    operations: Arc<Vec<Operation>>,
+}
+
+impl OpenApiBridge {
+    async fn call(&self, operation: &Operation, arguments: &JsonObject) -> CallToolResult {
+        let request = match operation.prepare(&self.base_url, &self.query, arguments) {
+            Ok(request) => request,
+            Err(e) => return CallToolResult::error(vec![Content::text(e)]),
+        };
+        let method = match reqwest::Method::from_bytes(request.method.as_bytes()) {
+            Ok(method) => method,
+            Err(e) => return CallToolResult::error(vec![Content::text(e.to_string())]),
+        };
+
+        let mut builder = self.http.request(method, &request.url);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        match builder.send().await {
+            Ok(response) => {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                if status.is_success() {
+                    let text = if text.is_empty() {
+                        format!("HTTP {}", status)
+                    } else {
+                        text
+                    };
+                    CallToolResult::success(vec![Content::text(text)])
+                } else {
+                    CallToolResult::error(vec![Content::text(format!("HTTP {}: {}", status, text))])
+                }
+            }
+            Err(e) => CallToolResult::error(vec![Content::text(format!("Request failed: {}", e))]),
+        }
+    }
+}
+
+impl ServerHandler for OpenApiBridge {
+    fn get_info(&self) -> ServerInfo {
+        let capabilities = ServerCapabilities::builder().enable_tools().build();
+        let mut server_info =
+            Implementation::new("mcpmux-openapi-bridge", env!("CARGO_PKG_VERSION"));
+        server_info.title = Some(self.title.clone());
+        let mut info = ServerInfo::new(capabilities);
+        info.server_info = server_info;
+        info
+    }
+
+    async fn list_tools(
+        &self,
+        _params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(
+            self.operations.iter().map(Operation::tool).collect(),
+        ))
+    }
+
+    async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(operation) = self.operations.iter().find(|op| op.name == params.name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown tool: {}", params.name),
+                None,
+            ));
+        };
+        let arguments = params.arguments.unwrap_or_default();
+        Ok(self.call(operation, &arguments).await)
+    }
+}
+
+/// Transport bridging a REST API described by an OpenAPI document
+pub struct OpenApiTransport {
+    spec_url: String,
+    base_url: Option<String>,
+    headers: HashMap<String, String>,
+    query: HashMap<String, String>,
+    operations: Vec<String>,
+    space_id: Uuid,
+    server_id: String,
+    log_manager: Option<Arc<ServerLogManager>>,
+    connect_timeout: Duration,
+    event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
+}
+
+impl OpenApiTransport {
+    pub fn new(
+        spec_url: String,
+        base_url: Option<String>,
+        space_id: Uuid,
+        server_id: String,
+        log_manager: Option<Arc<ServerLogManager>>,
+        connect_timeout: Duration,
+        event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    ) -> Self {
+        Self {
+            spec_url,
+            base_url,
+            headers: HashMap::new(),
+            query: HashMap::new(),
+            operations: Vec::new(),
+            space_id,
+            server_id,
+            log_manager,
+            connect_timeout,
+            event_tx,
+            traffic: None,
+            progress: None,
+        }
+    }
+
+    /// Headers and query parameters sent with every request, including the
+    /// document fetch
+    pub fn with_auth(
+        mut self,
+        headers: HashMap<String, String>,
+        query: HashMap<String, String>,
+    ) -> Self {
+        self.headers = headers;
+        self.query = query;
+        self
+    }
+
+    /// Only expose these operation IDs
+    pub fn with_operations(mut self, operations: Vec<String>) -> Self {
+        self.operations = operations;
+        self
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Log a message
+    async fn log(&self, level: LogLevel, message: String) {
+        if let Some(log_manager) = &self.log_manager {
+            let log = ServerLog::new(level, LogSource::Connection, message);
+            if let Err(e) = log_manager
+                .append(&self.space_id.to_string(), &self.server_id, log)
+                .await
+            {
+                error!("Failed to write log: {}", e);
+            }
+        }
+    }
+
+    async fn fail(&self, err: String) -> TransportConnectResult {
+        error!(server_id = %self.server_id, "{}", err);
+        self.log(LogLevel::Error, err.clone()).await;
+        TransportConnectResult::Failed(err)
+    }
+
+    /// Fetch (or read) and parse the OpenAPI document
+    async fn load_spec(&self, http: &reqwest::Client) -> Result<Value, String> {
+        let text = if self.spec_url.starts_with("http://") || self.spec_url.starts_with("https://")
+        {
+            let mut url = self.spec_url.clone();
+            let mut query: Vec<_> = self.query.iter().collect();
+            query.sort();
+            for (k, v) in query {
+                url.push(if url.contains('?') { '&' } else { '?' });
+                url.push_str(&format!(
+                    "{}={}",
+                    urlencoding::encode(k),
+                    urlencoding::encode(v)
+                ));
+            }
+            let mut request = http.get(&url).timeout(self.connect_timeout);
+            for (name, value) in &self.headers {
+                request = request.header(name, value);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch OpenAPI document: {}", e))?;
+            if !response.status().is_success() {
+                return Err(format!(
+                    "Failed to fetch OpenAPI document: HTTP {}",
+                    response.status()
+                ));
+            }
+            response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read OpenAPI document: {}", e))?
+        } else {
+            let path = self
+                .spec_url
+                .strip_prefix("file://")
+                .unwrap_or(&self.spec_url);
+            tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| format!("Failed to read OpenAPI document {}: {}", path, e))?
+        };
+
+        let spec: Value = serde_json::from_str(&text).map_err(|e| {
+            format!(
+                "OpenAPI document is not valid JSON (YAML is not supported): {}",
+                e
+            )
+        })?;
+        if !matches!(spec.get("openapi").and_then(Value::as_str), Some(v) if v.starts_with('3')) {
+            return Err("Only OpenAPI 3 documents are supported".to_string());
+        }
+        Ok(spec)
+    }
+}
+
+#[async_trait]
+impl Transport for OpenApiTransport {
+    async fn connect(&self) -> TransportConnectResult {
+        info!(
+            server_id = %self.server_id,
+            spec_url = %self.spec_url,
+            "Connecting to REST API via OpenAPI bridge"
+        );
+
+        let http = reqwest::Client::new();
+        let spec = match self.load_spec(&http).await {
+            Ok(spec) => spec,
+            Err(err) => return self.fail(err).await,
+        };
+
+        let Some(base_url) = self
+            .base_url
+            .clone()
+            .filter(|url| !url.is_empty())
+            .or_else(|| spec_base_url(&spec, &self.spec_url))
+        else {
+            return self
+                .fail("OpenAPI document lists no servers; set the API base URL".to_string())
+                .await;
+        };
+
+        let operations = parse_operations(&spec, &self.operations);
+        if operations.is_empty() {
+            return self
+                .fail("OpenAPI document has no operations to expose".to_string())
+                .await;
+        }
+        let title = spec
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .unwrap_or("OpenAPI")
+            .to_string();
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Bridging {} operations of {} at {}",
+                operations.len(),
+                title,
+                base_url
+            ),
+        )
+        .await;
+
+        let bridge = OpenApiBridge {
+            http,
+            title,
+            base_url,
+            headers: self.headers.clone(),
+            query: self.query.clone(),
+            operations: Arc::new(operations),
+        };
+
+        match connect_in_process(
+            bridge,
+            &self.server_id,
+            self.space_id,
+            self.event_tx.clone(),
+            self.log_manager.clone(),
+            self.traffic.clone(),
+            self.progress.clone(),
+            self.connect_timeout,
+        )
+        .await
+        {
+            Ok(client) => {
+                info!(server_id = %self.server_id, "OpenAPI bridge connected");
+                TransportConnectResult::Connected(client)
+            }
+            Err(err) => self.fail(err).await,
+        }
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Http
+    }
+
+    fn description(&self) -> String {
+        format!("openapi:{}", self.spec_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Petstore", "version": "1.0" },
+            "servers": [{ "url": "/v{version}", "variables": { "version": { "default": "2" } } }],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } }
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "summary": "Get a pet",
+                        "parameters": [
+                            { "$ref": "#/components/parameters/Fields" },
+                            { "name": "X-Trace", "in": "header", "schema": { "type": "string" } }
+                        ]
+                    },
+                    "put": {
+                        "summary": "Replace a pet",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Fields": { "name": "fields", "in": "query", "description": "Fields to return", "schema": { "type": "array", "items": { "type": "string" } } }
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "friends": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn operations_become_tools_with_inlined_schemas() {
+        let operations = parse_operations(&petstore(), &[]);
+        let names: Vec<&str> = operations.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, vec!["getPet", "put_pets_petId"]);
+
+        let get = &operations[0];
+        assert_eq!(get.description, "Get a pet");
+        assert_eq!(get.input_schema["required"], json!(["petId"]));
+        assert_eq!(
+            get.input_schema["properties"]["fields"]["description"],
+            "Fields to return"
+        );
+
+        let put = &operations[1];
+        assert!(put.has_body);
+        assert_eq!(put.input_schema["required"], json!(["petId", "body"]));
+        let body = &put.input_schema["properties"]["body"];
+        assert_eq!(body["properties"]["name"]["type"], "string");
+        assert_eq!(body["properties"]["friends"]["items"]["type"], "object");
+
+        let only = parse_operations(&petstore(), &["getPet".to_string()]);
+        assert_eq!(only.len(), 1);
+    }
+
+    #[test]
+    fn prepare_fills_path_query_and_headers() {
+        let operations = parse_operations(&petstore(), &[]);
+        let arguments: JsonObject = serde_json::from_value(json!({
+            "petId": 7,
+            "fields": ["name", "age"],
+            "X-Trace": "abc"
+        }))
+        .unwrap();
+        let default_query = HashMap::from([("api_key".to_string(), "k 1".to_string())]);
+
+        let request = operations[0]
+            .prepare("https://api.example.com/v2/", &default_query, &arguments)
+            .unwrap();
+        assert_eq!(
+            request,
+            PreparedRequest {
+                method: "GET".to_string(),
+                url: "https://api.example.com/v2/pets/7?api_key=k%201&fields=name&fields=age"
+                    .to_string(),
+                headers: vec![("X-Trace".to_string(), "abc".to_string())],
+                body: None,
+            }
+        );
+
+        let missing = operations[0].prepare(
+            "https://api.example.com",
+            &HashMap::new(),
+            &JsonObject::new(),
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn base_url_resolves_variables_and_relative_urls() {
+        assert_eq!(
+            spec_base_url(&petstore(), "https://api.example.com/openapi.json"),
+            Some("https://api.example.com/v2".to_string())
+        );
+        assert_eq!(
+            spec_base_url(&json!({ "openapi": "3.1.0" }), "spec.json"),
+            None
+        );
+        assert_eq!(
+            tool_name(None, "get", "/users/{id}/repos"),
+            "get_users_id_repos"
+        );
+    }
+}
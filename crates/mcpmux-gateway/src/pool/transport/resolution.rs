@@ -2,65 +2,201 @@
 //!
 //! Handles building the actual runtime transport configuration from
 //! the static registry definition and user-specific installation settings.
-
-use super::ResolvedTransport;
-use mcpmux_core::{InstalledServer, TransportConfig as RegistryConfig};
+//!
+//! Stdio commands, args and env values may use `${windows:...}`/`${unix:...}`
+//! variants and `${workspace}` (see [`mcpmux_core::expand_config_template`]);
+//! `${env:...}` and other secret references are resolved at connect time.
+//!
+//! The Space's active environment profile, if any, is applied first (see
+//! [`space_profile`] and [`apply_profile`]).
+
+use super::runtime::RunnerPackage;
+use super::{ResolvedTransport, WasiModule};
+use mcpmux_core::{
+    expand_config_args, expand_config_template, merge_input_defaults, parse_allowed_domains,
+    resolve_input_values, uses_workspace, EnvironmentProfile, EnvironmentProfileRepository,
+    InstalledServer, InvalidInputValues, SecretError, SecretResolverRegistry,
+    SpaceBaseDirRepository, TransportConfig as RegistryConfig,
+};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use uuid::Uuid;
 
 const MCP_STATE_DIR_ENV: &str = "MCP_STATE_DIR";
 
-/// Build a merged input_values map that includes defaults for any inputs
-/// not explicitly provided by the user.
-fn merge_input_defaults(
-    registry_transport: &RegistryConfig,
-    user_values: &HashMap<String, String>,
-) -> HashMap<String, String> {
-    let mut merged = user_values.clone();
-    let metadata = registry_transport.metadata();
-    for input in &metadata.inputs {
-        if !merged.contains_key(&input.id) {
-            if let Some(ref default_val) = input.default {
-                tracing::debug!(
-                    "[TransportResolution] Using default for input '{}': '{}'",
-                    input.id,
-                    default_val
-                );
-                merged.insert(input.id.clone(), default_val.clone());
-            }
+/// Why a server's transport config can't be built
+#[derive(Debug, Error)]
+pub enum TransportConfigError {
+    #[error(transparent)]
+    InvalidInputs(#[from] InvalidInputValues),
+
+    #[error("Server config uses ${{workspace}}, but its Space has no base directory")]
+    NoWorkspace,
+
+    #[error("Failed to load the Space's environment profile: {0}")]
+    Profile(String),
+}
+
+/// The directory `${workspace}` expands to for a Space: its first base
+/// directory, if it has any.
+pub async fn space_workspace(
+    repo: &dyn SpaceBaseDirRepository,
+    space_id: &Uuid,
+) -> Option<PathBuf> {
+    match repo.list_by_space(space_id).await {
+        Ok(dirs) => dirs
+            .into_iter()
+            .min_by_key(|dir| dir.created_at)
+            .map(|dir| PathBuf::from(dir.path)),
+        Err(e) => {
+            tracing::warn!(
+                "[TransportResolution] Failed to load base dirs for space {}: {}",
+                space_id,
+                e
+            );
+            None
         }
     }
-    merged
 }
 
-/// Build transport config from registry transport and installed server
+/// The active environment profile of a Space, if one is selected.
+///
+/// Unlike [`space_workspace`], a failure is an error: connecting without the
+/// profile would silently reach the wrong environment. Without a repository
+/// (profiles not supported by the host) there is never a profile.
+pub async fn space_profile(
+    repo: Option<&dyn EnvironmentProfileRepository>,
+    space_id: &Uuid,
+) -> Result<Option<EnvironmentProfile>, TransportConfigError> {
+    let Some(repo) = repo else {
+        return Ok(None);
+    };
+    repo.get_active(&space_id.to_string())
+        .await
+        .map_err(|e| TransportConfigError::Profile(e.to_string()))
+}
+
+/// A server's transport template and installation with `profile` applied,
+/// ready for [`resolve_transport_config`]
+pub fn apply_profile(
+    profile: Option<&EnvironmentProfile>,
+    registry_transport: &RegistryConfig,
+    installed: &InstalledServer,
+) -> (RegistryConfig, InstalledServer) {
+    match profile {
+        Some(profile) => profile.apply(registry_transport, installed),
+        None => (registry_transport.clone(), installed.clone()),
+    }
+}
+
+/// Build the transport config a server is started with, after checking its
+/// input values against the definition's typed inputs.
+///
+/// Use this on every connect path: a missing required input, a value of the
+/// wrong type, or a `${workspace}` with no `workspace` to expand to is
+/// reported here instead of surfacing as a backend startup failure.
+pub fn resolve_transport_config(
+    registry_transport: &RegistryConfig,
+    installed: &InstalledServer,
+    base_state_dir: Option<&Path>,
+    workspace: Option<&Path>,
+) -> Result<ResolvedTransport, TransportConfigError> {
+    let effective_values = resolve_input_values(
+        &registry_transport.metadata().inputs,
+        &installed.input_values,
+    )?;
+    let workspace = workspace.map(|dir| dir.to_string_lossy().to_string());
+    if workspace.is_none() && needs_workspace(registry_transport, installed) {
+        return Err(TransportConfigError::NoWorkspace);
+    }
+    Ok(build_with_values(
+        registry_transport,
+        installed,
+        base_state_dir,
+        workspace.as_deref(),
+        effective_values,
+    ))
+}
+
+/// Whether a stdio config refers to `${workspace}` anywhere it is expanded
+fn needs_workspace(registry_transport: &RegistryConfig, installed: &InstalledServer) -> bool {
+    let RegistryConfig::Stdio {
+        command, args, env, ..
+    } = registry_transport
+    else {
+        return false;
+    };
+    std::iter::once(command)
+        .chain(args)
+        .chain(env.values())
+        .chain(&installed.args_append)
+        .chain(installed.env_overrides.values())
+        .any(|template| uses_workspace(template))
+}
+
+/// Build transport config from registry transport and installed server,
+/// filling in input defaults without checking the values.
+///
+/// For inspecting a configuration (package update checks, previews); connect
+/// paths use [`resolve_transport_config`].
 pub fn build_transport_config(
     registry_transport: &RegistryConfig,
     installed: &InstalledServer,
     base_state_dir: Option<&Path>,
+) -> ResolvedTransport {
+    let effective_values = merge_input_defaults(
+        &registry_transport.metadata().inputs,
+        &installed.input_values,
+    );
+    build_with_values(
+        registry_transport,
+        installed,
+        base_state_dir,
+        None,
+        effective_values,
+    )
+}
+
+fn build_with_values(
+    registry_transport: &RegistryConfig,
+    installed: &InstalledServer,
+    base_state_dir: Option<&Path>,
+    workspace: Option<&str>,
+    effective_values: HashMap<String, String>,
 ) -> ResolvedTransport {
     tracing::debug!(
         "[TransportResolution] Building config for {}/{} with {} input values",
         installed.space_id,
         installed.server_id,
-        installed.input_values.len()
+        effective_values.len()
     );
 
-    // Merge user-provided values with defaults from input definitions
-    let effective_values = merge_input_defaults(registry_transport, &installed.input_values);
-
     match registry_transport {
         RegistryConfig::Stdio {
             command, args, env, ..
         } => {
-            let resolved_command = resolve_placeholders(command, &effective_values);
-            let mut resolved_args: Vec<String> = args
+            let resolved_command = expand_config_template(
+                &resolve_placeholders(command, &effective_values),
+                workspace,
+            );
+            let substituted: Vec<String> = args
                 .iter()
                 .map(|arg| resolve_placeholders(arg, &effective_values))
                 .collect();
+            let mut resolved_args = expand_config_args(&substituted, workspace);
+
+            // Run the pinned package version, if one was recorded
+            if let Some(version) = &installed.package_version {
+                if let Some(package) =
+                    RunnerPackage::from_command(&resolved_command, &resolved_args)
+                {
+                    resolved_args = package.pin(&resolved_args, version);
+                }
+            }
 
             // Append user's extra args
-            resolved_args.extend(installed.args_append.clone());
+            resolved_args.extend(expand_config_args(&installed.args_append, workspace));
 
             // Build env from registry + input values + env_overrides
             let mut resolved_env = HashMap::new();
@@ -77,7 +213,7 @@ pub fn build_transport_config(
                     k,
                     v
                 );
-                resolved_env.insert(k.clone(), resolved_value);
+                insert_env(&mut resolved_env, k, &resolved_value, workspace);
             }
 
             // 2. Add input values (user-provided + defaults) directly as env vars
@@ -88,7 +224,9 @@ pub fn build_transport_config(
             resolved_env.extend(effective_values.clone());
 
             // 3. Apply user's env overrides
-            resolved_env.extend(installed.env_overrides.clone());
+            for (k, v) in &installed.env_overrides {
+                insert_env(&mut resolved_env, k, v, workspace);
+            }
 
             // 4. Inject MCP_STATE_DIR if not already set
             apply_state_dir_env(&mut resolved_env, base_state_dir, installed);
@@ -102,9 +240,15 @@ pub fn build_transport_config(
                 command: resolved_command,
                 args: resolved_args,
                 env: resolved_env,
+                inherit_env: installed.inherit_env,
             }
         }
-        RegistryConfig::Http { url, headers, .. } => {
+        RegistryConfig::Http {
+            url,
+            headers,
+            query,
+            ..
+        } => {
             let resolved_url = resolve_placeholders(url, &effective_values);
 
             // Resolve headers from registry
@@ -116,14 +260,130 @@ pub fn build_transport_config(
             // Add user's extra headers
             resolved_headers.extend(installed.extra_headers.clone());
 
+            let resolved_query = query
+                .iter()
+                .map(|(k, v)| (k.clone(), resolve_placeholders(v, &effective_values)))
+                .collect();
+
             ResolvedTransport::Http {
                 url: resolved_url,
                 headers: resolved_headers,
+                query: resolved_query,
+            }
+        }
+        RegistryConfig::OpenApi {
+            spec_url,
+            base_url,
+            headers,
+            query,
+            operations,
+            ..
+        } => {
+            let mut resolved_headers: HashMap<String, String> = headers
+                .iter()
+                .map(|(k, v)| (k.clone(), resolve_placeholders(v, &effective_values)))
+                .collect();
+            resolved_headers.extend(installed.extra_headers.clone());
+
+            ResolvedTransport::OpenApi {
+                spec_url: resolve_placeholders(spec_url, &effective_values),
+                base_url: base_url
+                    .as_ref()
+                    .map(|url| resolve_placeholders(url, &effective_values)),
+                headers: resolved_headers,
+                query: query
+                    .iter()
+                    .map(|(k, v)| (k.clone(), resolve_placeholders(v, &effective_values)))
+                    .collect(),
+                operations: operations.clone(),
+            }
+        }
+        RegistryConfig::GraphQl {
+            endpoint,
+            headers,
+            operations,
+            ..
+        } => {
+            let mut resolved_headers: HashMap<String, String> = headers
+                .iter()
+                .map(|(k, v)| (k.clone(), resolve_placeholders(v, &effective_values)))
+                .collect();
+            resolved_headers.extend(installed.extra_headers.clone());
+
+            ResolvedTransport::GraphQl {
+                endpoint: resolve_placeholders(endpoint, &effective_values),
+                headers: resolved_headers,
+                operations: operations.clone(),
+            }
+        }
+        RegistryConfig::Wasm {
+            module,
+            args,
+            env,
+            permissions,
+            ..
+        } => {
+            // The module sees only the definition's env and the user's
+            // overrides, and only the access it asked for and was granted
+            let mut resolved_env = HashMap::new();
+            for (k, v) in env {
+                let resolved_value = resolve_placeholders(v, &effective_values);
+                insert_env(&mut resolved_env, k, &resolved_value, workspace);
+            }
+            for (k, v) in &installed.env_overrides {
+                insert_env(&mut resolved_env, k, v, workspace);
+            }
+
+            let substituted: Vec<String> = args
+                .iter()
+                .map(|arg| resolve_placeholders(arg, &effective_values))
+                .collect();
+            let mut resolved_args = expand_config_args(&substituted, workspace);
+            resolved_args.extend(expand_config_args(&installed.args_append, workspace));
+
+            let granted = permissions.granted(&installed.wasm_grants);
+            let dirs = if granted.filesystem {
+                expand_config_args(&installed.fs_allowed_roots, workspace)
+            } else {
+                Vec::new()
+            };
+            let module =
+                expand_config_template(&resolve_placeholders(module, &effective_values), workspace);
+
+            ResolvedTransport::Wasm(WasiModule {
+                path: module,
+                args: resolved_args,
+                env: resolved_env,
+                dirs,
+                network: granted.network,
+            })
+        }
+        RegistryConfig::Builtin {
+            allowed_domains, ..
+        } => {
+            let resolved: Vec<String> = allowed_domains
+                .iter()
+                .map(|d| resolve_placeholders(d, &effective_values))
+                .collect();
+            ResolvedTransport::Builtin {
+                allowed_domains: parse_allowed_domains(&resolved),
             }
         }
     }
 }
 
+/// Insert an env var after expanding platform variants and `${workspace}`.
+/// A variable that only had a variant for another platform is left unset
+/// rather than set to an empty string.
+fn insert_env(env: &mut HashMap<String, String>, key: &str, value: &str, workspace: Option<&str>) {
+    let expanded = expand_config_template(value, workspace);
+    if expanded.is_empty() && !value.is_empty() {
+        env.remove(key);
+    } else {
+        env.insert(key.to_string(), expanded);
+    }
+}
+
 fn apply_state_dir_env(
     resolved_env: &mut HashMap<String, String>,
     base_state_dir: Option<&Path>,
@@ -157,28 +417,84 @@ fn resolve_placeholders(template: &str, input_values: &HashMap<String, String>)
     result
 }
 
+/// Resolve secret references (`${env:..}`, `${keychain:..}`, `${vault:..}`,
+/// `${op:..}`) in a transport config, in place.
+///
+/// Runs at connect time, after `${input:..}` substitution, so an input value
+/// may itself be a secret reference. Errors name the reference only.
+pub async fn resolve_secret_references(
+    config: &mut ResolvedTransport,
+    resolvers: &SecretResolverRegistry,
+) -> Result<(), SecretError> {
+    match config {
+        ResolvedTransport::Stdio {
+            command, args, env, ..
+        } => {
+            if resolvers.has_references(command) {
+                *command = resolvers.resolve_str(command).await?;
+            }
+            resolvers.resolve_all(args).await?;
+            resolvers.resolve_map(env).await
+        }
+        ResolvedTransport::Http {
+            url,
+            headers,
+            query,
+        } => {
+            if resolvers.has_references(url) {
+                *url = resolvers.resolve_str(url).await?;
+            }
+            resolvers.resolve_map(query).await?;
+            resolvers.resolve_map(headers).await
+        }
+        ResolvedTransport::OpenApi {
+            spec_url,
+            base_url,
+            headers,
+            query,
+            ..
+        } => {
+            for url in std::iter::once(spec_url).chain(base_url.iter_mut()) {
+                if resolvers.has_references(url) {
+                    *url = resolvers.resolve_str(url).await?;
+                }
+            }
+            resolvers.resolve_map(query).await?;
+            resolvers.resolve_map(headers).await
+        }
+        ResolvedTransport::GraphQl {
+            endpoint, headers, ..
+        } => {
+            if resolvers.has_references(endpoint) {
+                *endpoint = resolvers.resolve_str(endpoint).await?;
+            }
+            resolvers.resolve_map(headers).await
+        }
+        ResolvedTransport::Wasm(module) => {
+            resolvers.resolve_all(&mut module.args).await?;
+            resolvers.resolve_map(&mut module.env).await
+        }
+        ResolvedTransport::Builtin { .. } => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mcpmux_core::{InputDefinition, TransportMetadata};
+    use mcpmux_core::{InputDefinition, TransportMetadata, WasmPermissions};
 
     fn make_installed(input_values: HashMap<String, String>) -> InstalledServer {
         InstalledServer::new("test-space", "test-server").with_inputs(input_values)
     }
 
     fn make_input(id: &str, default: Option<&str>) -> InputDefinition {
-        InputDefinition {
-            id: id.to_string(),
-            label: id.to_string(),
-            r#type: "text".to_string(),
-            required: default.is_none(),
-            secret: false,
-            description: None,
-            default: default.map(|s| s.to_string()),
-            placeholder: None,
-            obtain_url: None,
-            obtain_instructions: None,
-        }
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "label": id,
+            "required": default.is_none(),
+            "default": default,
+        }))
+        .expect("input definition")
     }
 
     #[test]
@@ -284,6 +600,7 @@ mod tests {
         let transport = RegistryConfig::Http {
             url: "https://api.example.com/${input:API_VERSION}/mcp".to_string(),
             headers: HashMap::new(),
+            query: HashMap::new(),
             metadata: TransportMetadata {
                 inputs: vec![make_input("API_VERSION", Some("v2"))],
             },
@@ -302,12 +619,16 @@ mod tests {
     }
 
     #[test]
-    fn test_default_resolves_in_http_headers() {
+    fn test_default_resolves_in_http_headers_and_query() {
         let transport = RegistryConfig::Http {
             url: "https://api.example.com/mcp".to_string(),
             headers: HashMap::from([("X-Api-Key".to_string(), "${input:API_KEY}".to_string())]),
+            query: HashMap::from([("workspace".to_string(), "${input:WORKSPACE}".to_string())]),
             metadata: TransportMetadata {
-                inputs: vec![make_input("API_KEY", Some("default-key"))],
+                inputs: vec![
+                    make_input("API_KEY", Some("default-key")),
+                    make_input("WORKSPACE", Some("main")),
+                ],
             },
         };
 
@@ -316,13 +637,88 @@ mod tests {
         let resolved = build_transport_config(&transport, &installed, None);
 
         match resolved {
-            ResolvedTransport::Http { headers, .. } => {
+            ResolvedTransport::Http { headers, query, .. } => {
                 assert_eq!(headers.get("X-Api-Key"), Some(&"default-key".to_string()));
+                assert_eq!(query.get("workspace"), Some(&"main".to_string()));
             }
             _ => panic!("Expected Http transport"),
         }
     }
 
+    #[test]
+    fn test_openapi_resolves_spec_url_and_auth() {
+        let transport = RegistryConfig::OpenApi {
+            spec_url: "https://${input:HOST}/openapi.json".to_string(),
+            base_url: None,
+            headers: HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer ${input:TOKEN}".to_string(),
+            )]),
+            query: HashMap::new(),
+            operations: vec!["listIssues".to_string()],
+            metadata: TransportMetadata {
+                inputs: vec![
+                    make_input("HOST", Some("api.example.com")),
+                    make_input("TOKEN", Some("secret")),
+                ],
+            },
+        };
+
+        let installed = make_installed(HashMap::new());
+
+        let resolved = build_transport_config(&transport, &installed, None);
+
+        match resolved {
+            ResolvedTransport::OpenApi {
+                spec_url,
+                headers,
+                operations,
+                ..
+            } => {
+                assert_eq!(spec_url, "https://api.example.com/openapi.json");
+                assert_eq!(
+                    headers.get("Authorization"),
+                    Some(&"Bearer secret".to_string())
+                );
+                assert_eq!(operations, vec!["listIssues".to_string()]);
+            }
+            _ => panic!("Expected OpenApi transport"),
+        }
+    }
+
+    #[test]
+    fn test_wasm_runs_with_granted_permissions_only() {
+        let transport = RegistryConfig::Wasm {
+            module: "/opt/servers/notes.wasm".to_string(),
+            args: vec![],
+            env: HashMap::from([("TOKEN".to_string(), "${input:TOKEN}".to_string())]),
+            permissions: WasmPermissions {
+                filesystem: true,
+                network: true,
+            },
+            metadata: TransportMetadata {
+                inputs: vec![make_input("TOKEN", Some("secret"))],
+            },
+        };
+
+        let mut installed = make_installed(HashMap::new());
+        installed.fs_allowed_roots = vec!["/home/ada/notes".to_string()];
+        installed.wasm_grants.filesystem = true;
+
+        match build_transport_config(&transport, &installed, None) {
+            ResolvedTransport::Wasm(module) => {
+                assert_eq!(module.path, "/opt/servers/notes.wasm");
+                assert_eq!(module.dirs, vec!["/home/ada/notes".to_string()]);
+                assert!(!module.network);
+                assert_eq!(
+                    module.env,
+                    HashMap::from([("TOKEN".to_string(), "secret".to_string())])
+                );
+            }
+            _ => panic!("Expected Wasm transport"),
+        }
+    }
+
     #[test]
     fn test_multiple_defaults_some_overridden() {
         let transport = RegistryConfig::Stdio {
@@ -387,6 +783,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inherit_env_follows_installation() {
+        let transport = RegistryConfig::Stdio {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            metadata: TransportMetadata::default(),
+        };
+
+        let mut installed = make_installed(HashMap::new());
+        match build_transport_config(&transport, &installed, None) {
+            ResolvedTransport::Stdio { inherit_env, .. } => {
+                assert!(!inherit_env, "Sanitized env should be the default")
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+
+        installed.inherit_env = true;
+        match build_transport_config(&transport, &installed, None) {
+            ResolvedTransport::Stdio { inherit_env, .. } => assert!(inherit_env),
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
+
+    #[test]
+    fn test_package_version_pins_runner_package() {
+        let transport = RegistryConfig::Stdio {
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), "@scope/server".to_string()],
+            env: HashMap::new(),
+            metadata: TransportMetadata::default(),
+        };
+
+        let mut installed = make_installed(HashMap::new());
+        installed.args_append = vec!["--verbose".to_string()];
+        installed.package_version = Some("1.4.2".to_string());
+        match build_transport_config(&transport, &installed, None) {
+            ResolvedTransport::Stdio { args, .. } => {
+                assert_eq!(args, vec!["-y", "@scope/server@1.4.2", "--verbose"])
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
+
     #[test]
     fn test_merge_input_defaults_only_fills_missing() {
         let transport = RegistryConfig::Stdio {
@@ -403,9 +843,119 @@ mod tests {
 
         let user_values = HashMap::from([("A".to_string(), "user_a".to_string())]);
 
-        let merged = merge_input_defaults(&transport, &user_values);
+        let merged = merge_input_defaults(&transport.metadata().inputs, &user_values);
 
         assert_eq!(merged.get("A"), Some(&"user_a".to_string()));
         assert_eq!(merged.get("B"), Some(&"default_b".to_string()));
     }
+
+    #[test]
+    fn test_resolve_rejects_invalid_inputs_before_connecting() {
+        let mut port = make_input("PORT", None);
+        port.r#type = "number".to_string();
+        let transport = RegistryConfig::Stdio {
+            command: "node".to_string(),
+            args: vec!["--port".to_string(), "${input:PORT}".to_string()],
+            env: HashMap::from([("API_KEY".to_string(), "${input:API_KEY}".to_string())]),
+            metadata: TransportMetadata {
+                inputs: vec![make_input("API_KEY", None), port],
+            },
+        };
+
+        let installed = make_installed(HashMap::from([("PORT".to_string(), "eighty".to_string())]));
+        let err = match resolve_transport_config(&transport, &installed, None, None) {
+            Err(TransportConfigError::InvalidInputs(err)) => err,
+            other => panic!("Expected invalid inputs, got {:?}", other.map(|_| ())),
+        };
+        let invalid: Vec<&str> = err.errors.iter().map(|e| e.input_id.as_str()).collect();
+        assert_eq!(invalid, vec!["API_KEY", "PORT"]);
+
+        let installed = make_installed(HashMap::from([
+            ("PORT".to_string(), " 8080 ".to_string()),
+            ("API_KEY".to_string(), "secret123".to_string()),
+        ]));
+        match resolve_transport_config(&transport, &installed, None, None).unwrap() {
+            ResolvedTransport::Stdio { args, .. } => assert_eq!(args, vec!["--port", "8080"]),
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
+
+    #[test]
+    fn test_platform_variants_and_workspace_expanded() {
+        let transport = RegistryConfig::Stdio {
+            command: "${windows:npx.cmd}${unix:npx}".to_string(),
+            args: vec![
+                "${windows:--win-only}".to_string(),
+                "--root=${workspace}".to_string(),
+            ],
+            env: HashMap::from([
+                ("SHELL_FLAG".to_string(), "${windows:/c}".to_string()),
+                (
+                    "CONFIG".to_string(),
+                    "${workspace}/${input:FILE}".to_string(),
+                ),
+            ]),
+            metadata: TransportMetadata {
+                inputs: vec![make_input("FILE", Some("mcp.json"))],
+            },
+        };
+        let installed = make_installed(HashMap::new());
+
+        let err = resolve_transport_config(&transport, &installed, None, None).unwrap_err();
+        assert!(matches!(err, TransportConfigError::NoWorkspace));
+
+        let workspace = Path::new("/home/me/proj");
+        match resolve_transport_config(&transport, &installed, None, Some(workspace)).unwrap() {
+            ResolvedTransport::Stdio {
+                command, args, env, ..
+            } => {
+                assert_eq!(
+                    env.get("CONFIG"),
+                    Some(&"/home/me/proj/mcp.json".to_string())
+                );
+                if cfg!(windows) {
+                    assert_eq!(command, "npx.cmd");
+                    assert_eq!(args, vec!["--win-only", "--root=/home/me/proj"]);
+                    assert_eq!(env.get("SHELL_FLAG"), Some(&"/c".to_string()));
+                } else {
+                    assert_eq!(command, "npx");
+                    assert_eq!(args, vec!["--root=/home/me/proj"]);
+                    assert_eq!(env.get("SHELL_FLAG"), None);
+                }
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_secret_references_resolved_after_inputs() {
+        use mcpmux_core::EnvSecretResolver;
+        use std::sync::Arc;
+
+        let transport = RegistryConfig::Stdio {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env: HashMap::from([("API_KEY".to_string(), "${input:API_KEY}".to_string())]),
+            metadata: TransportMetadata {
+                inputs: vec![make_input("API_KEY", None)],
+            },
+        };
+        let installed = make_installed(HashMap::from([(
+            "API_KEY".to_string(),
+            "${env:PATH}".to_string(),
+        )]));
+
+        let mut resolved = build_transport_config(&transport, &installed, None);
+        let resolvers = SecretResolverRegistry::new().with_resolver(Arc::new(EnvSecretResolver));
+        resolve_secret_references(&mut resolved, &resolvers)
+            .await
+            .unwrap();
+
+        match resolved {
+            ResolvedTransport::Stdio { env, .. } => {
+                assert_eq!(env.get("API_KEY"), std::env::var("PATH").ok().as_ref());
+            }
+            _ => panic!("Expected Stdio transport"),
+        }
+    }
 }
@@ -0,0 +1,403 @@
+//! Package-runner detection for stdio servers
+//!
+//! Registry servers are usually launched through a package runner: `npx` or
+//! `bunx` for npm packages, `uvx` or `pipx` for PyPI packages. The registry
+//! picks one, but the user may only have another installed (Bun but no
+//! Node.js, pipx but no uv). When the requested runner is missing, the same
+//! package is launched through an installed runner for that ecosystem. When
+//! none is installed, the server fails with install guidance instead of a
+//! bare "command not found".
+//!
+//! The package argument may name a version (`pkg@1.2.3`, `pkg==1.2.3`),
+//! which is kept when switching runners and is where a pinned version (see
+//! `InstalledServer::package_version`) is substituted.
+
+use std::path::Path;
+
+/// Package registry a runner installs from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageEcosystem {
+    Npm,
+    Pypi,
+}
+
+impl PackageEcosystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Pypi => "pypi",
+        }
+    }
+
+    /// Runners able to launch this ecosystem's packages, most preferred first
+    pub fn runners(&self) -> &'static [PackageRunner] {
+        match self {
+            Self::Npm => &[PackageRunner::Npx, PackageRunner::Bunx, PackageRunner::Deno],
+            Self::Pypi => &[PackageRunner::Uvx, PackageRunner::Pipx],
+        }
+    }
+
+    /// Where to get the default runner
+    pub fn install_url(&self) -> &'static str {
+        match self {
+            Self::Npm => "https://nodejs.org/en/download",
+            Self::Pypi => "https://docs.astral.sh/uv/getting-started/installation/",
+        }
+    }
+
+    /// What to install, phrased for an error message
+    pub fn install_hint(&self) -> &'static str {
+        match self {
+            Self::Npm => "Install Node.js (which includes npx), Bun or Deno",
+            Self::Pypi => "Install uv (which includes uvx) or pipx",
+        }
+    }
+}
+
+/// A command that downloads and runs a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageRunner {
+    Npx,
+    Bunx,
+    Deno,
+    Uvx,
+    Pipx,
+}
+
+impl PackageRunner {
+    /// Recognize a runner from a command name or path (`npx`, `/usr/bin/npx`,
+    /// `npx.cmd`, ...)
+    pub fn from_command(command: &str) -> Option<Self> {
+        let name = Path::new(command)
+            .file_stem()?
+            .to_str()?
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "npx" => Some(Self::Npx),
+            "bunx" => Some(Self::Bunx),
+            "deno" => Some(Self::Deno),
+            "uvx" => Some(Self::Uvx),
+            "pipx" => Some(Self::Pipx),
+            _ => None,
+        }
+    }
+
+    /// Executable to look up on PATH
+    pub fn binary(&self) -> &'static str {
+        match self {
+            Self::Npx => "npx",
+            Self::Bunx => "bunx",
+            Self::Deno => "deno",
+            Self::Uvx => "uvx",
+            Self::Pipx => "pipx",
+        }
+    }
+
+    pub fn ecosystem(&self) -> PackageEcosystem {
+        match self {
+            Self::Npx | Self::Bunx | Self::Deno => PackageEcosystem::Npm,
+            Self::Uvx | Self::Pipx => PackageEcosystem::Pypi,
+        }
+    }
+
+    /// Split this runner's arguments into the package and the arguments
+    /// passed to it. Returns `None` for invocations using runner options we
+    /// can't translate to another runner.
+    fn parse_package<'a>(&self, args: &'a [String]) -> Option<(PackageSpec, &'a [String])> {
+        let (flags, rest): (&[&str], &[String]) = match self {
+            Self::Npx => (&["-y", "--yes", "-q", "--quiet"], args),
+            Self::Bunx => (&["--bun", "-y"], args),
+            Self::Uvx => (&[], args),
+            Self::Deno => (
+                &["-A", "--allow-all", "-q", "--quiet"],
+                args.strip_prefix(&["run".to_string()])?,
+            ),
+            Self::Pipx => (&[], args.strip_prefix(&["run".to_string()])?),
+        };
+
+        let start = rest.iter().position(|arg| !flags.contains(&arg.as_str()))?;
+        let package = rest[start].as_str();
+        if package.starts_with('-') {
+            return None;
+        }
+        let package = match self {
+            Self::Deno => package.strip_prefix("npm:")?,
+            _ => package,
+        };
+        let package = PackageSpec::parse(self.ecosystem(), package)?;
+        Some((package, &rest[start + 1..]))
+    }
+
+    /// Command line that runs `package` with `args` through this runner
+    fn invocation(&self, package: &PackageSpec, args: &[String]) -> (String, Vec<String>) {
+        let package = package.to_arg(*self);
+        let mut argv: Vec<String> = match self {
+            Self::Npx => vec!["-y".into(), package],
+            Self::Bunx => vec![package],
+            Self::Deno => vec!["run".into(), "-A".into(), package],
+            Self::Uvx => vec![package],
+            Self::Pipx => vec!["run".into(), package],
+        };
+        argv.extend(args.iter().cloned());
+        (self.binary().to_string(), argv)
+    }
+}
+
+/// A package name plus the version (or npm range/tag) a runner was asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSpec {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl PackageSpec {
+    /// Parse a runner's package argument (`@scope/pkg@^1`, `pkg==1.2`).
+    /// Returns `None` for paths, URLs and PyPI version ranges, which can't be
+    /// carried over to another runner.
+    pub fn parse(ecosystem: PackageEcosystem, spec: &str) -> Option<Self> {
+        if spec.is_empty() || spec.contains("://") || spec.starts_with(['.', '/', '\\']) {
+            return None;
+        }
+        let (name, version) = match ecosystem {
+            // The version follows the last '@' that isn't a scope prefix
+            PackageEcosystem::Npm => match spec.get(1..).and_then(|tail| tail.rfind('@')) {
+                Some(at) => (&spec[..at + 1], Some(&spec[at + 2..])),
+                None => (spec, None),
+            },
+            PackageEcosystem::Pypi => {
+                match spec.split_once("==").or_else(|| spec.split_once('@')) {
+                    Some((name, version)) => (name, Some(version)),
+                    None => (spec, None),
+                }
+            }
+        };
+        if name.contains([':', '<', '>', '=', '!', '~', ',', ';', ' ']) {
+            return None;
+        }
+        let version = version.filter(|v| !v.is_empty() && *v != "latest");
+        if ecosystem == PackageEcosystem::Pypi && version.is_some_and(|v| !is_exact_version(v)) {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        })
+    }
+
+    /// The version, when it names one release rather than a range or tag
+    pub fn exact_version(&self) -> Option<&str> {
+        self.version.as_deref().filter(|v| is_exact_version(v))
+    }
+
+    /// The package argument that makes `runner` run this package
+    fn to_arg(&self, runner: PackageRunner) -> String {
+        let prefix = if runner == PackageRunner::Deno {
+            "npm:"
+        } else {
+            ""
+        };
+        match (&self.version, runner) {
+            (None, _) => format!("{}{}", prefix, self.name),
+            (Some(version), PackageRunner::Pipx) => format!("{}=={}", self.name, version),
+            (Some(version), _) => format!("{}{}@{}", prefix, self.name, version),
+        }
+    }
+}
+
+/// True for a concrete release such as `1.2.3`, `2.0.0-beta.1` or `0.4rc1`
+fn is_exact_version(version: &str) -> bool {
+    version.starts_with(|c: char| c.is_ascii_digit())
+        && !version.contains(['^', '~', '<', '>', '=', '*', '|', ' ', ','])
+        && !version
+            .split('.')
+            .any(|part| part.eq_ignore_ascii_case("x"))
+}
+
+/// The package a runner-launched stdio command runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunnerPackage {
+    pub runner: PackageRunner,
+    pub package: PackageSpec,
+    /// Position of the package argument in the command's arguments
+    arg_index: usize,
+}
+
+impl RunnerPackage {
+    /// Recognize `command args` as a runner invocation we understand
+    pub fn from_command(command: &str, args: &[String]) -> Option<Self> {
+        let runner = PackageRunner::from_command(command)?;
+        let (package, rest) = runner.parse_package(args)?;
+        Some(Self {
+            runner,
+            package,
+            arg_index: args.len() - rest.len() - 1,
+        })
+    }
+
+    /// `args` with the package argument pinned to `version`
+    pub fn pin(&self, args: &[String], version: &str) -> Vec<String> {
+        let pinned = PackageSpec {
+            name: self.package.name.clone(),
+            version: Some(version.to_string()),
+        };
+        let mut args = args.to_vec();
+        args[self.arg_index] = pinned.to_arg(self.runner);
+        args
+    }
+}
+
+/// Outcome of checking a stdio command's runner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeResolution {
+    /// Not a package runner, or the requested runner is installed
+    Unchanged,
+    /// Requested runner is missing; the package runs through another one
+    Substituted {
+        from: PackageRunner,
+        to: PackageRunner,
+        command: String,
+        args: Vec<String>,
+    },
+    /// No runner for the package's ecosystem is installed
+    Missing {
+        requested: PackageRunner,
+        ecosystem: PackageEcosystem,
+    },
+}
+
+/// Pick a runner for `command args`, given which binaries are installed
+pub fn resolve_runtime(
+    command: &str,
+    args: &[String],
+    is_installed: impl Fn(&str) -> bool,
+) -> RuntimeResolution {
+    let Some(requested) = PackageRunner::from_command(command) else {
+        return RuntimeResolution::Unchanged;
+    };
+    if is_installed(command) {
+        return RuntimeResolution::Unchanged;
+    }
+
+    let ecosystem = requested.ecosystem();
+    let missing = RuntimeResolution::Missing {
+        requested,
+        ecosystem,
+    };
+    let Some((package, package_args)) = requested.parse_package(args) else {
+        return missing;
+    };
+    ecosystem
+        .runners()
+        .iter()
+        .find(|runner| **runner != requested && is_installed(runner.binary()))
+        .map(|runner| {
+            let (command, args) = runner.invocation(&package, package_args);
+            RuntimeResolution::Substituted {
+                from: requested,
+                to: *runner,
+                command,
+                args,
+            }
+        })
+        .unwrap_or(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn installed_runner_and_plain_commands_are_unchanged() {
+        let npx = args(&["-y", "@modelcontextprotocol/server-github"]);
+        assert_eq!(
+            resolve_runtime("npx", &npx, |_| true),
+            RuntimeResolution::Unchanged
+        );
+        assert_eq!(
+            resolve_runtime("docker", &args(&["run", "img"]), |_| false),
+            RuntimeResolution::Unchanged
+        );
+    }
+
+    #[test]
+    fn substitutes_an_installed_runner_for_the_same_ecosystem() {
+        let npx = args(&["-y", "@scope/server", "--port", "1"]);
+        assert_eq!(
+            resolve_runtime("npx", &npx, |bin| bin == "deno"),
+            RuntimeResolution::Substituted {
+                from: PackageRunner::Npx,
+                to: PackageRunner::Deno,
+                command: "deno".to_string(),
+                args: args(&["run", "-A", "npm:@scope/server", "--port", "1"]),
+            }
+        );
+
+        let uvx = args(&["mcp-server-fetch"]);
+        assert_eq!(
+            resolve_runtime("uvx", &uvx, |bin| bin == "pipx"),
+            RuntimeResolution::Substituted {
+                from: PackageRunner::Uvx,
+                to: PackageRunner::Pipx,
+                command: "pipx".to_string(),
+                args: args(&["run", "mcp-server-fetch"]),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_missing_ecosystem_when_nothing_can_run_the_package() {
+        let missing = RuntimeResolution::Missing {
+            requested: PackageRunner::Uvx,
+            ecosystem: PackageEcosystem::Pypi,
+        };
+        assert_eq!(
+            resolve_runtime("uvx", &args(&["mcp-server-time"]), |bin| bin == "npx"),
+            missing
+        );
+        // Options we can't translate are never rewritten
+        assert_eq!(
+            resolve_runtime(
+                "uvx",
+                &args(&["--from", "git+https://x", "server"]),
+                |bin| bin == "pipx"
+            ),
+            missing
+        );
+    }
+
+    #[test]
+    fn versions_are_parsed_pinned_and_carried_across_runners() {
+        let npx = args(&["-y", "@scope/server@1.2.0", "--stdio"]);
+        let package = RunnerPackage::from_command("npx", &npx).unwrap();
+        assert_eq!(package.package.name, "@scope/server");
+        assert_eq!(package.package.exact_version(), Some("1.2.0"));
+        assert_eq!(
+            package.pin(&npx, "1.3.0"),
+            args(&["-y", "@scope/server@1.3.0", "--stdio"])
+        );
+
+        let deno = args(&["run", "-A", "npm:server@^2"]);
+        let package = RunnerPackage::from_command("deno", &deno).unwrap();
+        assert_eq!(package.package.version.as_deref(), Some("^2"));
+        assert_eq!(package.package.exact_version(), None);
+        assert_eq!(package.pin(&deno, "2.1.0")[2], "npm:server@2.1.0");
+
+        let uvx = args(&["mcp-server-time@0.6.2"]);
+        assert_eq!(
+            resolve_runtime("uvx", &uvx, |bin| bin == "pipx"),
+            RuntimeResolution::Substituted {
+                from: PackageRunner::Uvx,
+                to: PackageRunner::Pipx,
+                command: "pipx".to_string(),
+                args: args(&["run", "mcp-server-time==0.6.2"]),
+            }
+        );
+        assert!(RunnerPackage::from_command("uvx", &args(&["pkg>=1"])).is_none());
+        assert!(RunnerPackage::from_command("uvx", &args(&["pkg@latest"]))
+            .is_some_and(|p| p.package.version.is_none()));
+    }
+}
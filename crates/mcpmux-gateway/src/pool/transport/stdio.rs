@@ -8,14 +8,27 @@
 //! viewer. This works generically for any runtime (npx, node, docker, python,
 //! etc.). These logs are internal to the desktop app and are never exposed
 //! externally via the HTTP gateway.
-
-use std::collections::HashMap;
+//!
+//! Child processes start from a sanitized environment by default: only the
+//! resolved PATH, a small allowlist of OS essentials (home/temp dirs, locale,
+//! Windows system paths), and the server's declared env are passed through.
+//! Anything else in the desktop environment (cloud credentials, tokens from
+//! the user's shell profile, ...) is not visible to community servers unless
+//! the installation explicitly opts into inheriting the full environment.
+
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
+use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use mcpmux_core::{LogLevel, LogSource, ServerLog, ServerLogManager};
+use mcpmux_core::{
+    ChildProcessRegistry, LogLevel, LogSource, ManagedRuntimes, ServerLog, ServerLogManager,
+    STDERR_TAIL_LINES,
+};
+use parking_lot::Mutex;
 use rmcp::transport::{ConfigureCommandExt, TokioChildProcess};
 use rmcp::ServiceExt;
 use tokio::io::AsyncBufReadExt;
@@ -23,9 +36,12 @@ use tokio::process::{ChildStderr, Command};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::runtime::{resolve_runtime, RuntimeResolution};
 use super::shell_env;
 use super::TransportType;
 use super::{create_client_handler, Transport, TransportConnectResult};
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::{record_traffic, ServerTraffic};
 
 /// Apply platform-specific flags to a child process command.
 ///
@@ -49,6 +65,48 @@ pub fn configure_child_process_platform(cmd: &mut Command) {
     }
 }
 
+/// Parent environment variables passed through to sanitized child processes.
+///
+/// These are the variables runtimes (node, python, docker, ...) need to locate
+/// the user's home, temp and config directories and to behave correctly on
+/// Windows. Matching is case-insensitive because Windows env keys are.
+const BASELINE_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "USERNAME",
+    "LOGNAME",
+    "SHELL",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "TZ",
+    "TERM",
+    "TMPDIR",
+    "TMP",
+    "TEMP",
+    "XDG_RUNTIME_DIR",
+    "XDG_CONFIG_HOME",
+    "XDG_CACHE_HOME",
+    "XDG_DATA_HOME",
+    // Windows essentials
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "USERPROFILE",
+    "HOMEDRIVE",
+    "HOMEPATH",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+    "PROGRAMFILES(X86)",
+    "NUMBER_OF_PROCESSORS",
+    "PROCESSOR_ARCHITECTURE",
+];
+
 /// Returns a helpful hint for common runtime-dependent commands when they fail.
 fn command_hint(command: &str) -> &'static str {
     let cmd = command.rsplit(['/', '\\']).next().unwrap_or(command);
@@ -59,21 +117,34 @@ fn command_hint(command: &str) -> &'static str {
     }
 }
 
+/// Pidfile written for a spawned child, removed once the child exits
+type PidRecordTask = tokio::task::JoinHandle<(Arc<ChildProcessRegistry>, u32)>;
+
+/// Last stderr lines of a child, kept to diagnose a startup crash
+type StderrTail = Arc<Mutex<VecDeque<String>>>;
+
+/// A process that dies sooner than this after spawning counts as an early
+/// exit for crash-loop detection
+const EARLY_EXIT_WINDOW: Duration = Duration::from_secs(10);
+
+/// How long to wait for stderr to drain after a failed handshake
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Spawn an async task that reads lines from the child process stderr
 /// and logs them to the server log manager.
 ///
 /// The task runs until the stderr stream is closed (child process exits)
-/// or an I/O error occurs.
+/// or an I/O error occurs, then removes the child's pidfile. Stderr is
+/// drained even without a log manager so the child never blocks on a full
+/// pipe. The last lines are kept in `tail`.
 fn spawn_stderr_reader(
     stderr: ChildStderr,
     log_manager: Option<Arc<ServerLogManager>>,
     space_id: Uuid,
     server_id: String,
-) {
-    let Some(log_manager) = log_manager else {
-        return;
-    };
-
+    pid_record: Option<PidRecordTask>,
+    tail: StderrTail,
+) -> tokio::task::JoinHandle<()> {
     let space_id_str = space_id.to_string();
 
     tokio::spawn(async move {
@@ -84,6 +155,16 @@ fn spawn_stderr_reader(
             match lines.next_line().await {
                 Ok(Some(line)) if line.is_empty() => continue,
                 Ok(Some(line)) => {
+                    {
+                        let mut tail = tail.lock();
+                        if tail.len() == STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line.clone());
+                    }
+                    let Some(log_manager) = &log_manager else {
+                        continue;
+                    };
                     let level = classify_stderr_line(&line);
                     let log = ServerLog::new(level, LogSource::Stderr, &line);
                     let _ = log_manager.append(&space_id_str, &server_id, log).await;
@@ -103,11 +184,17 @@ fn spawn_stderr_reader(
                 }
             }
         }
-    });
+
+        if let Some(pid_record) = pid_record {
+            if let Ok((registry, pid)) = pid_record.await {
+                registry.remove(pid);
+            }
+        }
+    })
 }
 
 /// Classify a stderr line into a log level based on content heuristics.
-fn classify_stderr_line(line: &str) -> LogLevel {
+pub(super) fn classify_stderr_line(line: &str) -> LogLevel {
     let lower = line.to_lowercase();
     if lower.contains("error") || lower.contains("panic") || lower.contains("fatal") {
         LogLevel::Error
@@ -130,6 +217,11 @@ pub struct StdioTransport {
     log_manager: Option<Arc<ServerLogManager>>,
     connect_timeout: Duration,
     event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    inherit_env: bool,
+    child_processes: Option<Arc<ChildProcessRegistry>>,
+    managed_runtimes: Option<Arc<ManagedRuntimes>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
 }
 
 impl StdioTransport {
@@ -153,9 +245,48 @@ impl StdioTransport {
             log_manager,
             connect_timeout,
             event_tx,
+            inherit_env: false,
+            child_processes: None,
+            managed_runtimes: None,
+            traffic: None,
+            progress: None,
         }
     }
 
+    /// Pass the full parent environment to the child process instead of the
+    /// sanitized baseline (per-server opt-out, see `InstalledServer::inherit_env`).
+    pub fn with_inherit_env(mut self, inherit_env: bool) -> Self {
+        self.inherit_env = inherit_env;
+        self
+    }
+
+    /// Record the spawned process so it can be cleaned up after a crash
+    pub fn with_child_processes(
+        mut self,
+        child_processes: Option<Arc<ChildProcessRegistry>>,
+    ) -> Self {
+        self.child_processes = child_processes;
+        self
+    }
+
+    /// Put McpMux-installed Node.js/uv on the child's PATH when enabled
+    pub fn with_managed_runtimes(mut self, managed_runtimes: Option<Arc<ManagedRuntimes>>) -> Self {
+        self.managed_runtimes = managed_runtimes;
+        self
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Log a message to the server log manager.
     async fn log(&self, level: LogLevel, source: LogSource, message: String) {
         if let Some(log_manager) = &self.log_manager {
@@ -201,14 +332,86 @@ impl Transport for StdioTransport {
         // Homebrew, nvm, Volta, fnm, or /usr/local/bin — this fixes that.
         let shell_path = shell_env::get_shell_path();
 
+        // Managed runtimes (when enabled and installed) come first, so servers
+        // use the pinned Node.js/uv even if the user has another version
+        let managed = match &self.managed_runtimes {
+            Some(runtimes) => runtimes.stdio_env().await,
+            None => Default::default(),
+        };
+        let effective_path = prepend_bin_dirs(&managed.bin_dirs, shell_path);
+        let shell_path = effective_path.as_ref();
+
+        // Registry servers name a package runner (npx, uvx, ...). Fall back to
+        // another installed runner for the same ecosystem, or fail with
+        // install guidance when there is none.
+        let (command, args) = match resolve_runtime(&self.command, &self.args, |bin| {
+            resolve_command(bin, shell_path).is_ok()
+        }) {
+            RuntimeResolution::Unchanged => (self.command.clone(), self.args.clone()),
+            RuntimeResolution::Substituted {
+                from,
+                to,
+                command,
+                args,
+            } => {
+                info!(
+                    server_id = %self.server_id,
+                    "{} not found, running the package with {}",
+                    from.binary(),
+                    to.binary()
+                );
+                self.log(
+                    LogLevel::Info,
+                    LogSource::Connection,
+                    format!(
+                        "{} not found, running the package with {} instead",
+                        from.binary(),
+                        to.binary()
+                    ),
+                )
+                .await;
+                (command, args)
+            }
+            RuntimeResolution::Missing {
+                requested,
+                ecosystem,
+            } => {
+                let runners: Vec<String> = ecosystem
+                    .runners()
+                    .iter()
+                    .map(|runner| runner.binary().to_string())
+                    .collect();
+                let err = format!(
+                    "{} not found. This server runs a {} package: {} ({}), then retry.",
+                    requested.binary(),
+                    ecosystem.as_str(),
+                    ecosystem.install_hint(),
+                    ecosystem.install_url()
+                );
+                error!(server_id = %self.server_id, "{}", err);
+                self.log(LogLevel::Error, LogSource::Connection, err.clone())
+                    .await;
+                if let Some(event_tx) = &self.event_tx {
+                    let _ = event_tx.send(mcpmux_core::DomainEvent::RuntimeMissing {
+                        space_id: self.space_id,
+                        server_id: self.server_id.clone(),
+                        ecosystem: ecosystem.as_str().to_string(),
+                        runners,
+                        install_url: ecosystem.install_url().to_string(),
+                    });
+                }
+                return TransportConnectResult::Failed(err);
+            }
+        };
+
         // Validate command exists, using the shell-resolved PATH when available
-        let command_path = match resolve_command(&self.command, shell_path) {
+        let command_path = match resolve_command(&command, shell_path) {
             Ok(path) => path,
             Err(_) => {
-                let hint = command_hint(&self.command);
+                let hint = command_hint(&command);
                 let err = format!(
                     "Command not found: {}. Ensure it's installed and in PATH.{hint}",
-                    self.command
+                    command
                 );
                 error!(server_id = %self.server_id, "{}", err);
                 self.log(LogLevel::Error, LogSource::Connection, err.clone())
@@ -227,12 +430,28 @@ impl Transport for StdioTransport {
         // - Start with user-configured env vars (from resolution.rs)
         // - Inject the shell-resolved PATH so child processes can find
         //   their own dependencies (e.g., npx needs to find node)
-        let args = self.args.clone();
+        // - Unless the server opted out, clear the inherited environment and
+        //   only pass through the OS baseline allowlist
+        let inherit_env = self.inherit_env;
         let mut env = self.env.clone();
         inject_shell_path(&mut env, shell_path);
+        for (key, value) in managed.env {
+            env.entry(key).or_insert(value);
+        }
+        if !inherit_env {
+            inject_baseline_env(&mut env, std::env::vars());
+            debug!(
+                server_id = %self.server_id,
+                vars = env.len(),
+                "Spawning with sanitized environment"
+            );
+        }
 
         let (transport, child_stderr) =
             match TokioChildProcess::builder(Command::new(&command_path).configure(move |cmd| {
+                if !inherit_env {
+                    cmd.env_clear();
+                }
                 cmd.args(&args).envs(&env).kill_on_drop(true);
                 configure_child_process_platform(cmd);
             }))
@@ -250,20 +469,44 @@ impl Transport for StdioTransport {
                 }
             };
 
+        // Write the pidfile off the runtime: reading the start time shells
+        // out on some platforms
+        let pid_record = match (&self.child_processes, transport.id()) {
+            (Some(registry), Some(pid)) => {
+                let registry = registry.clone();
+                let space_id = self.space_id.to_string();
+                let server_id = self.server_id.clone();
+                let command = command.clone();
+                Some(tokio::task::spawn_blocking(move || {
+                    if let Err(e) = registry.record(pid, &space_id, &server_id, &command) {
+                        warn!(server_id = %server_id, "Failed to record child process: {}", e);
+                    }
+                    (registry, pid)
+                }))
+            }
+            _ => None,
+        };
+
+        let spawned_at = Instant::now();
+
         // Start the async stderr reader if we got a handle
-        if let Some(stderr) = child_stderr {
-            spawn_stderr_reader(
+        let stderr_tail = StderrTail::default();
+        let stderr_reader = if let Some(stderr) = child_stderr {
+            Some(spawn_stderr_reader(
                 stderr,
                 self.log_manager.clone(),
                 self.space_id,
                 self.server_id.clone(),
-            );
+                pid_record,
+                stderr_tail.clone(),
+            ))
         } else {
             warn!(
                 server_id = %self.server_id,
                 "No stderr handle available - process logs will not be captured"
             );
-        }
+            None
+        };
 
         // Create client handler
         let client_handler = create_client_handler(
@@ -271,10 +514,11 @@ impl Transport for StdioTransport {
             self.space_id,
             self.event_tx.clone(),
             self.log_manager.clone(),
+            self.progress.clone(),
         );
 
         // Connect with timeout
-        let connect_future = client_handler.serve(transport);
+        let connect_future = client_handler.serve(record_traffic(transport, self.traffic.clone()));
         let client = match tokio::time::timeout(self.connect_timeout, connect_future).await {
             Ok(Ok(client)) => client,
             Ok(Err(e)) => {
@@ -283,6 +527,16 @@ impl Transport for StdioTransport {
                 error!(server_id = %self.server_id, "{}", err);
                 self.log(LogLevel::Error, LogSource::Connection, err.clone())
                     .await;
+
+                // The process died (or closed stdout) while starting up. Let
+                // stderr drain so the caller can tell why.
+                if spawned_at.elapsed() < EARLY_EXIT_WINDOW {
+                    if let Some(reader) = stderr_reader {
+                        let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, reader).await;
+                    }
+                    let stderr = stderr_tail.lock().iter().cloned().collect();
+                    return TransportConnectResult::Exited { error: err, stderr };
+                }
                 return TransportConnectResult::Failed(err);
             }
             Err(_) => {
@@ -335,6 +589,21 @@ fn resolve_command(
     }
 }
 
+/// PATH with `bin_dirs` in front of the shell-resolved (or process) PATH
+fn prepend_bin_dirs(bin_dirs: &[PathBuf], shell_path: Option<&OsString>) -> Option<OsString> {
+    if bin_dirs.is_empty() {
+        return shell_path.cloned();
+    }
+    let base = shell_path.cloned().or_else(|| std::env::var_os("PATH"));
+    let dirs = bin_dirs
+        .iter()
+        .cloned()
+        .chain(base.iter().flat_map(std::env::split_paths));
+    std::env::join_paths(dirs)
+        .ok()
+        .or_else(|| shell_path.cloned())
+}
+
 /// Inject the shell-resolved PATH into the child process environment.
 ///
 /// This ensures child processes (e.g., npx spawning node) can find their
@@ -353,6 +622,26 @@ fn inject_shell_path(env: &mut HashMap<String, String>, shell_path: Option<&std:
     }
 }
 
+/// Copy allowlisted variables from the parent environment into a sanitized
+/// child environment.
+///
+/// Variables already present in `env` (declared by the server or overridden
+/// by the user, or the shell-resolved PATH) are never replaced.
+fn inject_baseline_env(
+    env: &mut HashMap<String, String>,
+    parent_vars: impl IntoIterator<Item = (String, String)>,
+) {
+    for (key, value) in parent_vars {
+        let allowed = BASELINE_ENV_ALLOWLIST
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(&key));
+        if !allowed || env.keys().any(|k| k.eq_ignore_ascii_case(&key)) {
+            continue;
+        }
+        env.insert(key, value);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +698,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prepend_bin_dirs_puts_managed_runtimes_first() {
+        let base = std::env::join_paths(["/usr/bin", "/bin"]).unwrap();
+        assert_eq!(prepend_bin_dirs(&[], Some(&base)), Some(base.clone()));
+
+        let managed = PathBuf::from("/data/runtimes/node-24/bin");
+        let path = prepend_bin_dirs(std::slice::from_ref(&managed), Some(&base)).unwrap();
+        let dirs: Vec<PathBuf> = std::env::split_paths(&path).collect();
+        assert_eq!(
+            dirs,
+            vec![managed, PathBuf::from("/usr/bin"), PathBuf::from("/bin")]
+        );
+    }
+
     // ── inject_shell_path tests ────────────────────────────────────
 
     #[test]
@@ -470,6 +773,61 @@ mod tests {
         assert_eq!(env.len(), 1, "Should only have PATH");
     }
 
+    // ── inject_baseline_env tests ──────────────────────────────────
+
+    fn parent(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_inject_baseline_env_drops_unlisted_vars() {
+        let mut env = HashMap::new();
+        inject_baseline_env(
+            &mut env,
+            parent(&[
+                ("HOME", "/home/user"),
+                ("AWS_SECRET_ACCESS_KEY", "leak"),
+                ("GITHUB_TOKEN", "ghp_leak"),
+            ]),
+        );
+
+        assert_eq!(env.get("HOME"), Some(&"/home/user".to_string()));
+        assert!(!env.contains_key("AWS_SECRET_ACCESS_KEY"));
+        assert!(!env.contains_key("GITHUB_TOKEN"));
+    }
+
+    #[test]
+    fn test_inject_baseline_env_keeps_declared_values() {
+        let mut env = HashMap::new();
+        env.insert("PATH".to_string(), "/shell/path".to_string());
+        env.insert("HOME".to_string(), "/custom/home".to_string());
+
+        inject_baseline_env(
+            &mut env,
+            parent(&[("PATH", "/usr/bin"), ("HOME", "/home/user")]),
+        );
+
+        assert_eq!(env.get("PATH"), Some(&"/shell/path".to_string()));
+        assert_eq!(env.get("HOME"), Some(&"/custom/home".to_string()));
+    }
+
+    #[test]
+    fn test_inject_baseline_env_is_case_insensitive() {
+        let mut env = HashMap::new();
+        env.insert("Path".to_string(), "C:\\custom".to_string());
+
+        inject_baseline_env(
+            &mut env,
+            parent(&[("PATH", "C:\\Windows"), ("SystemRoot", "C:\\Windows")]),
+        );
+
+        assert_eq!(env.get("Path"), Some(&"C:\\custom".to_string()));
+        assert!(!env.contains_key("PATH"), "Should not duplicate PATH");
+        assert_eq!(env.get("SystemRoot"), Some(&"C:\\Windows".to_string()));
+    }
+
     // ── command_hint tests ─────────────────────────────────────────
 
     #[test]
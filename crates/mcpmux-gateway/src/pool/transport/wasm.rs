@@ -0,0 +1,503 @@
+//! WASI transport for MCP servers compiled to WebAssembly
+//!
+//! The module runs inside the gateway on an embedded wasmtime engine, so no
+//! runtime has to be installed and no process is spawned. Its stdin and
+//! stdout are joined to the usual client over an in-memory pipe, and its
+//! stderr is streamed to the server log as a child process's would be.
+//!
+//! The module's WASI context holds exactly what the installation granted:
+//! the resolved env as its whole environment, the allowed roots as preopened
+//! directories, and the network only when both requested and granted (see
+//! [`mcpmux_core::WasmPermissions`]). It has no other way to reach the host.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use mcpmux_core::{LogLevel, LogSource, ServerLog, ServerLogManager};
+use rmcp::ServiceExt;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, DuplexStream};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use wasmtime::component::{self, Component, ResourceTable};
+use wasmtime::wasmparser::Parser;
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+use wasmtime_wasi::bindings::Command;
+use wasmtime_wasi::pipe::{AsyncReadStream, AsyncWriteStream};
+use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+use wasmtime_wasi::{
+    AsyncStdinStream, AsyncStdoutStream, DirPerms, FilePerms, I32Exit, IoView, WasiCtx,
+    WasiCtxBuilder, WasiView,
+};
+
+use super::stdio::classify_stderr_line;
+use super::{create_client_handler, Transport, TransportConnectResult, TransportType};
+use crate::pool::progress::ProgressRelay;
+use crate::pool::traffic::{record_traffic, ServerTraffic};
+
+/// Buffer of the in-memory pipes to the module
+const PIPE_BUFFER: usize = 64 * 1024;
+
+/// How often a running module is made to yield, so a busy module can't
+/// hold a runtime thread
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Most linear memory a module may grow to
+const MAX_MEMORY_BYTES: usize = 1 << 30;
+
+/// Bytes a module may write ahead of its reader
+const WRITE_BUDGET: usize = 64 * 1024;
+
+/// The engine every module runs on, with its epoch ticking
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(|| {
+        let mut config = Config::new();
+        config.async_support(true).epoch_interruption(true);
+        // Only fails for settings this build doesn't support; these are
+        // supported by every build with the cranelift feature
+        let engine = Engine::new(&config).expect("wasmtime engine config");
+        let ticker = engine.weak();
+        std::thread::spawn(move || {
+            while let Some(engine) = ticker.upgrade() {
+                engine.increment_epoch();
+                drop(engine);
+                std::thread::sleep(EPOCH_TICK);
+            }
+        });
+        engine
+    })
+}
+
+/// A WASI module and the access it was granted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasiModule {
+    /// Path of the `.wasm` file: a `wasm32-wasip1` module or a
+    /// `wasm32-wasip2` component
+    pub path: String,
+    pub args: Vec<String>,
+    /// The only environment variables the module sees
+    pub env: HashMap<String, String>,
+    /// Host directories the module may read and write, each at the same
+    /// path inside the module
+    pub dirs: Vec<String>,
+    /// Open network connections and resolve host names
+    pub network: bool,
+}
+
+/// Store state of a `wasm32-wasip1` module
+struct ModuleState {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+/// Store state of a `wasm32-wasip2` component
+struct ComponentState {
+    wasi: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl IoView for ComponentState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for ComponentState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+fn store_limits() -> StoreLimits {
+    StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY_BYTES)
+        .build()
+}
+
+impl WasiModule {
+    /// The WASI context holding exactly what was granted, with the module's
+    /// stdio joined to the given streams
+    fn context(
+        &self,
+        stdin: impl AsyncRead + Send + Unpin + 'static,
+        stdout: impl AsyncWrite + Send + Unpin + 'static,
+        stderr: impl AsyncWrite + Send + Unpin + 'static,
+    ) -> Result<WasiCtxBuilder> {
+        let mut builder = WasiCtxBuilder::new();
+        builder
+            .stdin(AsyncStdinStream::new(AsyncReadStream::new(stdin)))
+            .stdout(AsyncStdoutStream::new(AsyncWriteStream::new(
+                WRITE_BUDGET,
+                stdout,
+            )))
+            .stderr(AsyncStdoutStream::new(AsyncWriteStream::new(
+                WRITE_BUDGET,
+                stderr,
+            )))
+            .arg(&self.path)
+            .args(&self.args);
+        let mut env: Vec<(&String, &String)> = self.env.iter().collect();
+        env.sort_unstable();
+        builder.envs(&env);
+        for dir in &self.dirs {
+            builder
+                .preopened_dir(dir, dir, DirPerms::all(), FilePerms::all())
+                .with_context(|| format!("Failed to open directory {}", dir))?;
+        }
+        // Without this every socket address is refused
+        if self.network {
+            builder.inherit_network().allow_ip_name_lookup(true);
+        }
+        Ok(builder)
+    }
+
+    /// Compile the module and run it until it exits, returning its exit code
+    pub async fn run(
+        &self,
+        stdin: impl AsyncRead + Send + Unpin + 'static,
+        stdout: impl AsyncWrite + Send + Unpin + 'static,
+        stderr: impl AsyncWrite + Send + Unpin + 'static,
+    ) -> Result<i32> {
+        let bytes = tokio::fs::read(&self.path)
+            .await
+            .with_context(|| format!("Failed to read WASI module {}", self.path))?;
+        let mut context = self.context(stdin, stdout, stderr)?;
+        let result = if Parser::is_component(&bytes) {
+            run_component(bytes, context.build()).await
+        } else {
+            run_module(bytes, context.build_p1()).await
+        };
+        match result {
+            Ok(()) => Ok(0),
+            Err(e) => match e.downcast_ref::<I32Exit>() {
+                Some(exit) => Ok(exit.0),
+                None => Err(e),
+            },
+        }
+    }
+}
+
+async fn run_module(bytes: Vec<u8>, wasi: WasiP1Ctx) -> Result<()> {
+    let engine = engine();
+    // Compiling takes a while for a large module
+    let module = tokio::task::spawn_blocking(move || Module::new(engine, bytes)).await??;
+
+    let mut linker: Linker<ModuleState> = Linker::new(engine);
+    preview1::add_to_linker_async(&mut linker, |state| &mut state.wasi)?;
+    let state = ModuleState {
+        wasi,
+        limits: store_limits(),
+    };
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    store.epoch_deadline_async_yield_and_update(1);
+
+    let instance = linker.instantiate_async(&mut store, &module).await?;
+    let start = instance.get_typed_func::<(), ()>(&mut store, "_start")?;
+    start.call_async(&mut store, ()).await
+}
+
+async fn run_component(bytes: Vec<u8>, wasi: WasiCtx) -> Result<()> {
+    let engine = engine();
+    let component = tokio::task::spawn_blocking(move || Component::new(engine, bytes)).await??;
+
+    let mut linker: component::Linker<ComponentState> = component::Linker::new(engine);
+    wasmtime_wasi::add_to_linker_async(&mut linker)?;
+    let state = ComponentState {
+        wasi,
+        table: ResourceTable::new(),
+        limits: store_limits(),
+    };
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    store.epoch_deadline_async_yield_and_update(1);
+
+    let command = Command::instantiate_async(&mut store, &component, &linker).await?;
+    command
+        .wasi_cli_run()
+        .call_run(&mut store)
+        .await?
+        // The component reports failure without a code
+        .map_err(|()| I32Exit(1).into())
+}
+
+/// Transport running a WASI module in process
+pub struct WasmTransport {
+    module: WasiModule,
+    space_id: Uuid,
+    server_id: String,
+    log_manager: Option<Arc<ServerLogManager>>,
+    connect_timeout: Duration,
+    event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    traffic: Option<Arc<ServerTraffic>>,
+    progress: Option<Arc<ProgressRelay>>,
+}
+
+impl WasmTransport {
+    pub fn new(
+        module: WasiModule,
+        space_id: Uuid,
+        server_id: String,
+        log_manager: Option<Arc<ServerLogManager>>,
+        connect_timeout: Duration,
+        event_tx: Option<tokio::sync::broadcast::Sender<mcpmux_core::DomainEvent>>,
+    ) -> Self {
+        Self {
+            module,
+            space_id,
+            server_id,
+            log_manager,
+            connect_timeout,
+            event_tx,
+            traffic: None,
+            progress: None,
+        }
+    }
+
+    /// Record JSON-RPC frames while recording is on for this server
+    pub fn with_traffic(mut self, traffic: Option<Arc<ServerTraffic>>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Pass the server's progress on tool calls on to the calling clients
+    pub fn with_progress_relay(mut self, progress: Option<Arc<ProgressRelay>>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Log a message
+    async fn log(&self, level: LogLevel, message: String) {
+        append_log(
+            self.log_manager.as_deref(),
+            &self.space_id,
+            &self.server_id,
+            ServerLog::new(level, LogSource::Connection, message),
+        )
+        .await;
+    }
+
+    /// Run the module until it exits, logging how it ended. It stops when
+    /// the client is dropped: its stdin closes and its output has nowhere
+    /// to go.
+    fn spawn_module(&self, module_io: DuplexStream, stderr: DuplexStream) {
+        let module = self.module.clone();
+        let log_manager = self.log_manager.clone();
+        let space_id = self.space_id;
+        let server_id = self.server_id.clone();
+        tokio::spawn(async move {
+            let (stdin, stdout) = tokio::io::split(module_io);
+            let (level, message) = match module.run(stdin, stdout, stderr).await {
+                Ok(0) => (LogLevel::Info, "WASI module exited".to_string()),
+                Ok(code) => (
+                    LogLevel::Warn,
+                    format!("WASI module exited with code {}", code),
+                ),
+                Err(e) => (LogLevel::Error, format!("WASI module failed: {:#}", e)),
+            };
+            debug!(server_id = %server_id, "{}", message);
+            let log = ServerLog::new(level, LogSource::Connection, message);
+            append_log(log_manager.as_deref(), &space_id, &server_id, log).await;
+        });
+    }
+
+    /// Stream the module's stderr to the server log, line by line
+    fn spawn_stderr_reader(&self, stderr: DuplexStream) {
+        let log_manager = self.log_manager.clone();
+        let space_id = self.space_id;
+        let server_id = self.server_id.clone();
+        tokio::spawn(async move {
+            let mut lines = tokio::io::BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.is_empty() {
+                    continue;
+                }
+                let log = ServerLog::new(classify_stderr_line(&line), LogSource::Stderr, &line);
+                append_log(log_manager.as_deref(), &space_id, &server_id, log).await;
+            }
+        });
+    }
+}
+
+async fn append_log(
+    log_manager: Option<&ServerLogManager>,
+    space_id: &Uuid,
+    server_id: &str,
+    log: ServerLog,
+) {
+    if let Some(log_manager) = log_manager {
+        if let Err(e) = log_manager
+            .append(&space_id.to_string(), server_id, log)
+            .await
+        {
+            error!("Failed to write log: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for WasmTransport {
+    async fn connect(&self) -> TransportConnectResult {
+        info!(
+            server_id = %self.server_id,
+            module = %self.module.path,
+            "Starting WASI module"
+        );
+        // Args and env values may hold secrets, so only what was granted is
+        // logged
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Starting WASI module: {} ({} directory(ies), network {})",
+                self.module.path,
+                self.module.dirs.len(),
+                if self.module.network { "on" } else { "off" }
+            ),
+        )
+        .await;
+
+        let (client_io, module_io) = tokio::io::duplex(PIPE_BUFFER);
+        let (stderr, stderr_reader) = tokio::io::duplex(PIPE_BUFFER);
+        self.spawn_stderr_reader(stderr_reader);
+        self.spawn_module(module_io, stderr);
+
+        let client_handler = create_client_handler(
+            &self.server_id,
+            self.space_id,
+            self.event_tx.clone(),
+            self.log_manager.clone(),
+            self.progress.clone(),
+        );
+        let connect_future = client_handler.serve(record_traffic(client_io, self.traffic.clone()));
+        let err = match tokio::time::timeout(self.connect_timeout, connect_future).await {
+            Ok(Ok(client)) => {
+                info!(server_id = %self.server_id, "WASI module connected");
+                return TransportConnectResult::Connected(client);
+            }
+            Ok(Err(e)) => format!("WASI module handshake failed: {}", e),
+            Err(_) => format!("Connection timeout ({:?})", self.connect_timeout),
+        };
+        error!(server_id = %self.server_id, "{}", err);
+        self.log(LogLevel::Error, err.clone()).await;
+        TransportConnectResult::Failed(err)
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Stdio
+    }
+
+    fn description(&self) -> String {
+        format!("wasm:{}", self.module.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Copies stdin to stdout until EOF
+    const ECHO: &str = r#"(module
+      (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+      (memory (export "memory") 1)
+      (func (export "_start")
+        (block $done
+          (loop $copy
+            (i32.store (i32.const 0) (i32.const 16))
+            (i32.store (i32.const 4) (i32.const 1024))
+            (br_if $done (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+            (br_if $done (i32.eqz (i32.load (i32.const 8))))
+            (i32.store (i32.const 4) (i32.load (i32.const 8)))
+            (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 12)))
+            (br $copy)))))"#;
+
+    /// Exits with the number of environment variables it sees
+    const COUNT_ENV: &str = r#"(module
+      (import "wasi_snapshot_preview1" "environ_sizes_get" (func $sizes (param i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "proc_exit" (func $exit (param i32)))
+      (memory (export "memory") 1)
+      (func (export "_start")
+        (drop (call $sizes (i32.const 0) (i32.const 4)))
+        (call $exit (i32.load (i32.const 0)))))"#;
+
+    /// Exits with the errno of looking up its first preopened directory
+    const FIRST_PREOPEN: &str = r#"(module
+      (import "wasi_snapshot_preview1" "fd_prestat_get" (func $prestat (param i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "proc_exit" (func $exit (param i32)))
+      (memory (export "memory") 1)
+      (func (export "_start")
+        (call $exit (call $prestat (i32.const 3) (i32.const 0)))))"#;
+
+    fn module(name: &str, source: &str) -> WasiModule {
+        let path =
+            std::env::temp_dir().join(format!("mcpmux-wasm-{}-{}.wat", name, std::process::id()));
+        std::fs::write(&path, source).unwrap();
+        WasiModule {
+            path: path.to_string_lossy().into_owned(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            dirs: Vec::new(),
+            network: false,
+        }
+    }
+
+    async fn exit_code(module: &WasiModule) -> i32 {
+        module
+            .run(tokio::io::empty(), tokio::io::sink(), tokio::io::sink())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn module_runs_in_process_over_pipes() {
+        let echo = module("echo", ECHO);
+        let (mut client, module_io) = tokio::io::duplex(1024);
+        let (stdin, stdout) = tokio::io::split(module_io);
+        let running = tokio::spawn(async move { echo.run(stdin, stdout, tokio::io::sink()).await });
+
+        client
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"ping\"}\n")
+            .await
+            .unwrap();
+        let mut line = [0u8; 34];
+        client.read_exact(&mut line).await.unwrap();
+        assert_eq!(&line, b"{\"jsonrpc\":\"2.0\",\"method\":\"ping\"}\n");
+
+        // Closing its stdin ends the module
+        drop(client);
+        assert_eq!(running.await.unwrap().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn module_sees_only_its_own_env() {
+        let mut count_env = module("env", COUNT_ENV);
+        assert_eq!(exit_code(&count_env).await, 0);
+
+        count_env.env = HashMap::from([
+            ("TOKEN".to_string(), "secret".to_string()),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ]);
+        assert_eq!(exit_code(&count_env).await, 2);
+    }
+
+    #[tokio::test]
+    async fn module_gets_only_granted_directories() {
+        const ERRNO_BADF: i32 = 8;
+        let mut first_preopen = module("preopen", FIRST_PREOPEN);
+        assert_eq!(exit_code(&first_preopen).await, ERRNO_BADF);
+
+        first_preopen.dirs = vec![std::env::temp_dir().to_string_lossy().into_owned()];
+        assert_eq!(exit_code(&first_preopen).await, 0);
+
+        first_preopen.dirs = vec!["/does/not/exist".to_string()];
+        assert!(first_preopen
+            .run(tokio::io::empty(), tokio::io::sink(), tokio::io::sink())
+            .await
+            .is_err());
+    }
+}
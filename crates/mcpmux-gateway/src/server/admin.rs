@@ -0,0 +1,1518 @@
+//! Remote administration API (`/admin/*`)
+//!
+//! Lets the desktop app or a web UI manage a gateway running on another
+//! machine: list and enable servers, manage grants, view connection
+//! statuses and drive backend OAuth.
+//!
+//! Guarded by an admin token that is distinct from client tokens — client
+//! JWTs and API keys are never accepted here. Only the token's SHA-256 hash
+//! is stored (app setting [`keys::gateway::ADMIN_TOKEN_HASH`]); when it is
+//! unset (and no users exist) the whole surface answers 404.
+//!
+//! A gateway shared by a team adds users, each with their own `mcpa_`
+//! token. Admins manage everything; members see and change only the Spaces
+//! and clients they own, and grants between the two. Users, owners,
+//! sessions, rate limits and pairing need the admin role.
+//!
+//! Backend OAuth on a headless machine works like a device flow: `POST
+//! .../oauth` returns the authorization URL for the admin to open in their
+//! own browser, and the URL the provider redirects to (which won't load,
+//! since it points at the gateway's loopback listener) is posted back to
+//! `/admin/oauth/callback`.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Extension, Path, Query, Request, State},
+    http::{
+        header::{AUTHORIZATION, HOST},
+        HeaderMap, StatusCode,
+    },
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::{delete, get, post, put},
+    Router,
+};
+use mcpmux_core::{
+    keys, validate_user_name, AppSettingsRepository, DomainEvent, EventFilter, LogFilter, LogLevel,
+    LogSource, Space, User, UserRepository, UserRole,
+};
+use mcpmux_storage::{hash_api_key, InboundClient};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::handlers::{effective_base_url, AppState};
+use super::pairing::{generate_pairing_code, pairing_url, PendingPairing};
+use super::rate_limit::{RateLimitPolicy, RateLimitRejections, RateLimiter};
+use super::startup::ConnectOutcome;
+use crate::pool::{
+    ConnectionContext, ConnectionResult, ConnectionStatus, OAuthCallback, ServerKey,
+};
+
+/// Prefix that makes admin tokens recognisable next to `mcpk_` client keys
+pub const ADMIN_TOKEN_PREFIX: &str = "mcpa_";
+
+/// Generate a new admin token (shown once; store it with [`set_admin_token`])
+pub fn generate_admin_token() -> String {
+    format!(
+        "{ADMIN_TOKEN_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// Store the hash of `token`, or remove it with `None` (which turns the
+/// admin API off unless users exist). Takes effect on the next request; no
+/// restart needed.
+pub async fn set_admin_token(
+    settings: &dyn AppSettingsRepository,
+    token: Option<&str>,
+) -> anyhow::Result<()> {
+    match token {
+        Some(token) => {
+            settings
+                .set(keys::gateway::ADMIN_TOKEN_HASH, &hash_api_key(token))
+                .await
+        }
+        None => settings.delete(keys::gateway::ADMIN_TOKEN_HASH).await,
+    }
+}
+
+/// Create a user with a fresh admin token (returned once; only its hash is
+/// stored)
+pub async fn create_user(
+    users: &dyn UserRepository,
+    name: &str,
+    role: UserRole,
+) -> anyhow::Result<(User, String)> {
+    let name = validate_user_name(name).map_err(anyhow::Error::msg)?;
+    let user = User::new(name, role);
+    users.create(&user).await?;
+    let token = rotate_user_token(users, &user.id).await?;
+    Ok((user, token))
+}
+
+/// Replace a user's admin token, returning the new one
+pub async fn rotate_user_token(
+    users: &dyn UserRepository,
+    user_id: &Uuid,
+) -> anyhow::Result<String> {
+    let token = generate_admin_token();
+    users
+        .set_token_hash(user_id, Some(&hash_api_key(&token)))
+        .await?;
+    Ok(token)
+}
+
+/// Every `/admin` route and `/events`, behind the admin token check.
+/// `/admin/rate-limits` reads the [`RateLimiter`] from an extension the
+/// gateway layers on top.
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/admin/status", get(admin_status))
+        .route("/admin/spaces/{space_id}/servers", get(admin_list_servers))
+        .route(
+            "/admin/spaces/{space_id}/servers/{server_id}/enable",
+            post(admin_enable_server),
+        )
+        .route(
+            "/admin/spaces/{space_id}/servers/{server_id}/disable",
+            post(admin_disable_server),
+        )
+        .route(
+            "/admin/spaces/{space_id}/servers/{server_id}/oauth",
+            post(admin_start_oauth),
+        )
+        .route(
+            "/admin/spaces/{space_id}/servers/{server_id}/logs/stream",
+            get(admin_stream_logs),
+        )
+        .route("/events", get(admin_stream_events))
+        .route("/admin/oauth/callback", post(admin_oauth_callback))
+        .route("/admin/clients", get(admin_list_clients))
+        .route("/admin/sessions", get(admin_list_sessions))
+        .route("/admin/pairings", post(admin_create_pairing))
+        .route("/admin/rate-limits", get(admin_rate_limits))
+        .route(
+            "/admin/users",
+            get(admin_list_users).post(admin_create_user),
+        )
+        .route("/admin/users/{user_id}", delete(admin_delete_user))
+        .route(
+            "/admin/users/{user_id}/token",
+            post(admin_rotate_user_token),
+        )
+        .route("/admin/spaces/{space_id}/owner", put(admin_set_space_owner))
+        .route(
+            "/admin/clients/{client_id}/owner",
+            put(admin_set_client_owner),
+        )
+        .route(
+            "/admin/spaces/{space_id}/clients/{client_id}/grants",
+            get(admin_list_grants),
+        )
+        .route(
+            "/admin/spaces/{space_id}/clients/{client_id}/grants/{feature_set_id}",
+            put(admin_grant).delete(admin_revoke),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            admin_auth_middleware,
+        ))
+        .with_state(state)
+}
+
+/// Compare two hashes without short-circuiting on the first mismatch
+fn hashes_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Who an authorized admin request is made by
+#[derive(Debug, Clone)]
+pub enum AdminPrincipal {
+    /// The gateway-wide admin token; manages everything
+    Operator,
+    /// A user with their own token
+    User(User),
+}
+
+impl AdminPrincipal {
+    pub fn is_admin(&self) -> bool {
+        match self {
+            Self::Operator => true,
+            Self::User(user) => user.is_admin(),
+        }
+    }
+
+    pub fn can_access_space(&self, space: &Space) -> bool {
+        match self {
+            Self::Operator => true,
+            Self::User(user) => user.can_access_space(space),
+        }
+    }
+
+    /// Whether something owned by `owner` (a user ID) is theirs to manage
+    pub fn owns(&self, owner: Option<&str>) -> bool {
+        match self {
+            Self::Operator => true,
+            Self::User(user) => user.owns(owner),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Operator => "operator",
+            Self::User(user) => &user.name,
+        }
+    }
+}
+
+/// Outcome of checking a presented admin token
+#[derive(Debug, Clone)]
+pub(super) enum AdminAuth {
+    /// No admin token or user is configured; the admin surface is off
+    Disabled,
+    Rejected,
+    Authorized(AdminPrincipal),
+}
+
+/// Check a bearer token against the gateway-wide admin token hash, then
+/// against users' tokens
+pub(super) async fn check_admin_token(state: &AppState, presented: Option<&str>) -> AdminAuth {
+    let deps = &state.services.dependencies;
+    let stored = match &deps.settings_repo {
+        Some(settings) => settings
+            .get(keys::gateway::ADMIN_TOKEN_HASH)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+    if stored.is_none() && deps.user_repo.list().await.unwrap_or_default().is_empty() {
+        return AdminAuth::Disabled;
+    }
+    let Some(presented) = presented.filter(|token| token.starts_with(ADMIN_TOKEN_PREFIX)) else {
+        return AdminAuth::Rejected;
+    };
+    let hash = hash_api_key(presented);
+    if stored.is_some_and(|stored| hashes_match(&hash, &stored)) {
+        return AdminAuth::Authorized(AdminPrincipal::Operator);
+    }
+    match deps.user_repo.get_by_token_hash(&hash).await {
+        Ok(Some(user)) => AdminAuth::Authorized(AdminPrincipal::User(user)),
+        Ok(None) => AdminAuth::Rejected,
+        Err(e) => {
+            warn!("[Admin] Failed to look up user token: {}", e);
+            AdminAuth::Rejected
+        }
+    }
+}
+
+/// Require `Authorization: Bearer <admin token>` on every `/admin` route,
+/// and hand handlers the [`AdminPrincipal`] as an extension
+pub(super) async fn admin_auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let presented = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let auth = check_admin_token(&state, presented).await;
+    match auth {
+        AdminAuth::Disabled => StatusCode::NOT_FOUND.into_response(),
+        AdminAuth::Rejected => {
+            warn!(
+                "[Admin] Rejected unauthenticated request to {}",
+                request.uri().path()
+            );
+            (StatusCode::UNAUTHORIZED, "Invalid admin token").into_response()
+        }
+        AdminAuth::Authorized(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        }
+    }
+}
+
+/// Why a principal may not touch something
+#[derive(Debug)]
+pub(super) enum AccessError {
+    /// Doesn't exist
+    NotFound(&'static str),
+    /// Belongs to someone else, or doesn't exist; members can't tell which
+    NotOwned(&'static str),
+    /// Needs the admin role
+    Forbidden,
+    Internal(anyhow::Error),
+}
+
+impl IntoResponse for AccessError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::NotFound(what) => (StatusCode::NOT_FOUND, what).into_response(),
+            Self::NotOwned(what) => (StatusCode::FORBIDDEN, what).into_response(),
+            Self::Forbidden => (StatusCode::FORBIDDEN, "Requires the admin role").into_response(),
+            Self::Internal(e) => internal_error(e),
+        }
+    }
+}
+
+pub(super) fn require_admin(principal: &AdminPrincipal) -> Result<(), AccessError> {
+    if principal.is_admin() {
+        Ok(())
+    } else {
+        Err(AccessError::Forbidden)
+    }
+}
+
+/// Check that a member owns a Space. Admins pass without a lookup.
+pub(super) async fn check_space_access(
+    state: &AppState,
+    principal: &AdminPrincipal,
+    space_id: Uuid,
+) -> Result<(), AccessError> {
+    if principal.is_admin() {
+        return Ok(());
+    }
+    match state.services.dependencies.space_repo.get(&space_id).await {
+        Ok(Some(space)) if principal.can_access_space(&space) => Ok(()),
+        Ok(_) => Err(AccessError::NotOwned("Not your Space")),
+        Err(e) => Err(AccessError::Internal(e)),
+    }
+}
+
+/// Check that a member owns a client. Admins pass without a lookup.
+pub(super) async fn check_client_access(
+    state: &AppState,
+    principal: &AdminPrincipal,
+    client_id: &str,
+) -> Result<(), AccessError> {
+    if principal.is_admin() {
+        return Ok(());
+    }
+    match state
+        .services
+        .dependencies
+        .inbound_client_repo
+        .get_client_owner(client_id)
+        .await
+    {
+        Ok(owner) if principal.owns(owner.as_deref()) => Ok(()),
+        Ok(_) => Err(AccessError::NotOwned("Not your client")),
+        Err(e) => Err(AccessError::Internal(e)),
+    }
+}
+
+/// Check that a FeatureSet belongs to the Space it's being granted in
+pub(super) async fn check_feature_set_in_space(
+    state: &AppState,
+    space_id: Uuid,
+    feature_set_id: &str,
+) -> Result<(), AccessError> {
+    let space_id = space_id.to_string();
+    match state
+        .services
+        .dependencies
+        .feature_set_repo
+        .get(feature_set_id)
+        .await
+    {
+        Ok(Some(fs)) if fs.space_id.as_deref() == Some(space_id.as_str()) => Ok(()),
+        Ok(_) => Err(AccessError::NotFound("FeatureSet not found in this Space")),
+        Err(e) => Err(AccessError::Internal(e)),
+    }
+}
+
+/// Which events a principal may see
+pub(super) enum EventScope {
+    All,
+    /// Only events about these Spaces
+    Spaces(HashSet<Uuid>),
+}
+
+impl EventScope {
+    pub(super) fn allows(&self, event: &DomainEvent) -> bool {
+        match self {
+            Self::All => true,
+            Self::Spaces(spaces) => event.space_id().is_some_and(|id| spaces.contains(&id)),
+        }
+    }
+}
+
+/// Events a principal may stream, checking the Space they asked for. A
+/// member's scope is fixed when the stream opens.
+pub(super) async fn event_scope(
+    state: &AppState,
+    principal: &AdminPrincipal,
+    space_id: Option<Uuid>,
+) -> Result<EventScope, AccessError> {
+    if let Some(space_id) = space_id {
+        check_space_access(state, principal, space_id).await?;
+    }
+    if principal.is_admin() {
+        return Ok(EventScope::All);
+    }
+    let spaces = visible_spaces(state, principal)
+        .await
+        .map_err(AccessError::Internal)?;
+    Ok(EventScope::Spaces(
+        spaces.into_iter().map(|space| space.id).collect(),
+    ))
+}
+
+/// Spaces a principal may manage
+pub(super) async fn visible_spaces(
+    state: &AppState,
+    principal: &AdminPrincipal,
+) -> anyhow::Result<Vec<Space>> {
+    let spaces = state.services.dependencies.space_repo.list().await?;
+    Ok(spaces
+        .into_iter()
+        .filter(|space| principal.can_access_space(space))
+        .collect())
+}
+
+/// Clients a principal may manage
+pub(super) async fn visible_clients(
+    state: &AppState,
+    principal: &AdminPrincipal,
+) -> anyhow::Result<Vec<InboundClient>> {
+    let repo = &state.services.dependencies.inbound_client_repo;
+    match principal {
+        AdminPrincipal::User(user) if !user.is_admin() => {
+            repo.list_clients_for_owner(&user.id.to_string()).await
+        }
+        _ => repo.list_clients().await,
+    }
+}
+
+fn internal_error(e: impl std::fmt::Display) -> Response {
+    warn!("[Admin] Request failed: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+}
+
+fn parse_space_id(space_id: &str) -> Result<Uuid, Response> {
+    Uuid::parse_str(space_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid space_id").into_response())
+}
+
+/// Parse a Space ID from the path and check the principal may manage it
+async fn authorize_space(
+    state: &AppState,
+    principal: &AdminPrincipal,
+    space_id: &str,
+) -> Result<Uuid, Response> {
+    let id = parse_space_id(space_id)?;
+    check_space_access(state, principal, id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    Ok(id)
+}
+
+// ============================================================================
+// Status
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct AdminServerStatus {
+    pub server_id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub status: ConnectionStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSpaceStatus {
+    pub id: String,
+    pub name: String,
+    pub servers: Vec<AdminServerStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminStatusResponse {
+    pub version: String,
+    pub base_url: String,
+    pub connected_servers: usize,
+    pub spaces: Vec<AdminSpaceStatus>,
+}
+
+pub(super) async fn server_statuses(
+    state: &AppState,
+    space_id: Uuid,
+) -> anyhow::Result<Vec<AdminServerStatus>> {
+    let installed = state
+        .services
+        .dependencies
+        .installed_server_repo
+        .list_for_space(&space_id.to_string())
+        .await?;
+    let statuses = state
+        .services
+        .server_manager
+        .get_all_statuses(space_id)
+        .await;
+    Ok(installed
+        .into_iter()
+        .map(|server| {
+            let (status, error) = statuses
+                .get(&server.server_id)
+                .map(|(status, _, _, error)| (*status, error.clone()))
+                .unwrap_or_default();
+            AdminServerStatus {
+                name: server.display_name().to_string(),
+                server_id: server.server_id,
+                enabled: server.enabled,
+                status,
+                error,
+            }
+        })
+        .collect())
+}
+
+/// Every Space the principal may manage with its servers' connection status
+pub(super) async fn gateway_status(
+    state: &AppState,
+    principal: &AdminPrincipal,
+) -> anyhow::Result<AdminStatusResponse> {
+    let spaces = visible_spaces(state, principal).await?;
+    let mut result = Vec::with_capacity(spaces.len());
+    for space in spaces {
+        result.push(AdminSpaceStatus {
+            id: space.id.to_string(),
+            servers: server_statuses(state, space.id).await?,
+            name: space.name,
+        });
+    }
+    // Members only learn about their own servers
+    let connected_servers = if principal.is_admin() {
+        state.services.server_manager.connected_count().await
+    } else {
+        result
+            .iter()
+            .flat_map(|space| &space.servers)
+            .filter(|server| server.status == ConnectionStatus::Connected)
+            .count()
+    };
+    Ok(AdminStatusResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        base_url: state.gateway_state.read().await.base_url.clone(),
+        connected_servers,
+        spaces: result,
+    })
+}
+
+/// GET /admin/status - every Space with its servers' connection status
+pub async fn admin_status(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+) -> Response {
+    match gateway_status(&state, &principal).await {
+        Ok(status) => Json(status).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+// ============================================================================
+// Servers
+// ============================================================================
+
+/// GET /admin/spaces/{space_id}/servers
+pub async fn admin_list_servers(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path(space_id): Path<String>,
+) -> Response {
+    let space_uuid = match authorize_space(&state, &principal, &space_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    match server_statuses(&state, space_uuid).await {
+        Ok(servers) => Json(servers).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Why enabling or disabling a server failed
+#[derive(Debug)]
+pub(super) enum ServerChangeError {
+    NotInstalled,
+    /// The organization's managed policy forbids the server
+    Forbidden(mcpmux_core::ManagedPolicyViolation),
+    /// Stored, but the server didn't connect
+    Connect(anyhow::Error),
+    Internal(anyhow::Error),
+}
+
+impl IntoResponse for ServerChangeError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::NotInstalled => (StatusCode::NOT_FOUND, "Server not installed").into_response(),
+            Self::Forbidden(e) => (StatusCode::FORBIDDEN, e.to_string()).into_response(),
+            Self::Connect(e) => (StatusCode::BAD_GATEWAY, e.to_string()).into_response(),
+            Self::Internal(e) => internal_error(e),
+        }
+    }
+}
+
+/// Mark a server enabled and connect it
+pub(super) async fn enable_server(
+    state: &AppState,
+    space_id: &str,
+    server_id: &str,
+) -> Result<ConnectOutcome, ServerChangeError> {
+    let repo = &state.services.dependencies.installed_server_repo;
+    let mut installed = repo
+        .get_by_server_id(space_id, server_id)
+        .await
+        .map_err(ServerChangeError::Internal)?
+        .ok_or(ServerChangeError::NotInstalled)?;
+    state
+        .services
+        .dependencies
+        .managed_policy
+        .check_server(&installed)
+        .map_err(ServerChangeError::Forbidden)?;
+    repo.set_enabled(&installed.id, true)
+        .await
+        .map_err(ServerChangeError::Internal)?;
+    installed.enabled = true;
+    info!("[Admin] Enabling {}/{}", space_id, server_id);
+
+    state
+        .services
+        .startup_orchestrator
+        .connect_server(&installed)
+        .await
+        .map_err(ServerChangeError::Connect)
+}
+
+/// Disconnect and disable a server, keeping its stored tokens
+pub(super) async fn disable_server(
+    state: &AppState,
+    space_id: Uuid,
+    server_id: &str,
+) -> Result<(), ServerChangeError> {
+    let space = space_id.to_string();
+    let repo = &state.services.dependencies.installed_server_repo;
+    let installed = repo
+        .get_by_server_id(&space, server_id)
+        .await
+        .map_err(ServerChangeError::Internal)?
+        .ok_or(ServerChangeError::NotInstalled)?;
+
+    let pool_services = &state.services.pool_services;
+    pool_services
+        .pool_service
+        .remove_instance(space_id, server_id);
+    pool_services
+        .pool_service
+        .oauth_manager()
+        .cancel_flow_for_space(space_id, server_id);
+    state
+        .services
+        .server_manager
+        .set_disconnected(&ServerKey::new(space_id, server_id))
+        .await;
+    repo.set_enabled(&installed.id, false)
+        .await
+        .map_err(ServerChangeError::Internal)?;
+    if let Err(e) = pool_services
+        .feature_service
+        .mark_unavailable(&space, server_id)
+        .await
+    {
+        warn!("[Admin] Failed to mark features unavailable: {}", e);
+    }
+    info!("[Admin] Disabled {}/{}", space, server_id);
+    Ok(())
+}
+
+/// POST /admin/spaces/{space_id}/servers/{server_id}/enable
+///
+/// Marks the server enabled and connects it. OAuth servers that haven't
+/// been authorized end up `auth_required`; start OAuth separately.
+pub async fn admin_enable_server(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, server_id)): Path<(String, String)>,
+) -> Response {
+    if let Err(response) = authorize_space(&state, &principal, &space_id).await {
+        return response;
+    }
+    match enable_server(&state, &space_id, &server_id).await {
+        Ok(ConnectOutcome::NeedsOAuth) => {
+            Json(serde_json::json!({ "status": "auth_required" })).into_response()
+        }
+        Ok(_) => Json(serde_json::json!({ "status": "connected" })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /admin/spaces/{space_id}/servers/{server_id}/disable
+///
+/// Disconnects and disables the server; stored tokens are kept.
+pub async fn admin_disable_server(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, server_id)): Path<(String, String)>,
+) -> Response {
+    let space_uuid = match authorize_space(&state, &principal, &space_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    match disable_server(&state, space_uuid, &server_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// ============================================================================
+// Logs
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminLogStreamQuery {
+    /// Minimum level: `trace`, `debug`, `info`, `warn` or `error`
+    pub level: Option<String>,
+    /// Comma-separated sources, e.g. `stderr,connection`
+    pub source: Option<String>,
+}
+
+impl AdminLogStreamQuery {
+    fn to_filter(&self) -> Result<LogFilter, String> {
+        let min_level = match self.level.as_deref() {
+            Some(level) => {
+                Some(LogLevel::parse(level).ok_or_else(|| format!("Unknown level: {}", level))?)
+            }
+            None => None,
+        };
+        let sources = self
+            .source
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .map(|source| {
+                LogSource::parse(source).ok_or_else(|| format!("Unknown source: {}", source))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(LogFilter { min_level, sources })
+    }
+}
+
+/// GET /admin/spaces/{space_id}/servers/{server_id}/logs/stream
+///
+/// Server-sent events: one `log` event per new entry, as the JSON stored in
+/// the server's log file. Only entries written after the stream opens are
+/// sent.
+pub async fn admin_stream_logs(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, server_id)): Path<(String, String)>,
+    Query(query): Query<AdminLogStreamQuery>,
+) -> Response {
+    if let Err(response) = authorize_space(&state, &principal, &space_id).await {
+        return response;
+    }
+    let filter = match query.to_filter() {
+        Ok(filter) => filter,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let mut tail = state
+        .services
+        .dependencies
+        .log_manager
+        .subscribe_tail(&space_id, &server_id, filter);
+    info!("[Admin] Streaming logs for {}/{}", space_id, server_id);
+
+    let stream = async_stream::stream! {
+        while let Some(log) = tail.next().await {
+            match Event::default().event("log").json_data(&log) {
+                Ok(event) => yield Ok::<_, Infallible>(event),
+                Err(e) => warn!("[Admin] Failed to encode log entry: {}", e),
+            }
+        }
+    };
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EventStreamQuery {
+    /// Only events about this Space
+    pub space_id: Option<String>,
+    /// Comma-separated event types, e.g. `server_status_changed,tools_changed`
+    pub types: Option<String>,
+}
+
+impl EventStreamQuery {
+    fn to_filter(&self) -> Result<EventFilter, String> {
+        let space_id = match self.space_id.as_deref() {
+            Some(space_id) => {
+                Some(Uuid::parse_str(space_id).map_err(|_| "Invalid space_id".to_string())?)
+            }
+            None => None,
+        };
+        let types = self
+            .types
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+        Ok(EventFilter { space_id, types })
+    }
+}
+
+/// GET /events - domain events as server-sent events
+///
+/// Each event is named after its type (`server_status_changed`, ...) and
+/// carries [`DomainEvent::to_sanitized_json`](mcpmux_core::DomainEvent::to_sanitized_json).
+/// Only events from after the stream opens are sent.
+pub async fn admin_stream_events(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Query(query): Query<EventStreamQuery>,
+) -> Response {
+    let filter = match query.to_filter() {
+        Ok(filter) => filter,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let visible = match event_scope(&state, &principal, filter.space_id).await {
+        Ok(visible) => visible,
+        Err(e) => return e.into_response(),
+    };
+    let mut events = state.gateway_state.read().await.subscribe_domain_events();
+    info!("[Admin] Streaming events");
+
+    let stream = async_stream::stream! {
+        loop {
+            match events.recv().await {
+                Ok(event) if filter.matches(&event) && visible.allows(&event) => {
+                    match Event::default()
+                        .event(event.type_name())
+                        .json_data(event.to_sanitized_json())
+                    {
+                        Ok(event) => yield Ok::<_, Infallible>(event),
+                        Err(e) => warn!("[Admin] Failed to encode event: {}", e),
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("[Admin] Event stream fell behind, {} event(s) dropped", missed);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    };
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+// ============================================================================
+// Backend OAuth
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct AdminOAuthStartResponse {
+    /// `connected` when stored tokens were enough, else `authenticating`
+    pub status: ConnectionStatus,
+    /// Open this in a browser, then post the redirected URL to
+    /// `/admin/oauth/callback`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_url: Option<String>,
+}
+
+/// POST /admin/spaces/{space_id}/servers/{server_id}/oauth
+pub async fn admin_start_oauth(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, server_id)): Path<(String, String)>,
+) -> Response {
+    let space_uuid = match authorize_space(&state, &principal, &space_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    let deps = &state.services.dependencies;
+    let installed = match deps
+        .installed_server_repo
+        .get_by_server_id(&space_id, &server_id)
+        .await
+    {
+        Ok(Some(installed)) => installed,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Server not installed").into_response(),
+        Err(e) => return internal_error(e),
+    };
+    let Some(definition) = installed.get_definition() else {
+        return (StatusCode::CONFLICT, "Server has no cached definition").into_response();
+    };
+
+    let manager = &state.services.server_manager;
+    let key = ServerKey::new(space_uuid, &server_id);
+    let workspace = crate::pool::transport::resolution::space_workspace(
+        deps.space_base_dir_repo.as_ref(),
+        &space_uuid,
+    )
+    .await;
+    let profile = crate::pool::transport::resolution::space_profile(
+        deps.environment_profile_repo.as_deref(),
+        &space_uuid,
+    )
+    .await;
+    let transport = match profile.and_then(|profile| {
+        let (transport, profiled) = crate::pool::transport::resolution::apply_profile(
+            profile.as_ref(),
+            &definition.transport,
+            &installed,
+        );
+        crate::pool::transport::resolution::resolve_transport_config(
+            &transport,
+            &profiled,
+            deps.state_dir.as_deref(),
+            workspace.as_deref(),
+        )
+    }) {
+        Ok(transport) => transport,
+        Err(e) => {
+            manager.set_error(&key, e.to_string()).await;
+            return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response();
+        }
+    };
+    manager.set_connecting(&key).await;
+    // Manual connect: starts the OAuth flow, but the browser is the admin's
+    let ctx = ConnectionContext::new(space_uuid, server_id.clone(), transport)
+        .with_timeouts(installed.effective_timeouts(&definition));
+    match state
+        .services
+        .pool_services
+        .pool_service
+        .connect_server(&ctx)
+        .await
+    {
+        ConnectionResult::Connected { features, .. } => {
+            manager.set_connected(&key, features).await;
+            Json(AdminOAuthStartResponse {
+                status: ConnectionStatus::Connected,
+                auth_url: None,
+            })
+            .into_response()
+        }
+        ConnectionResult::OAuthRequired { auth_url } => {
+            manager.set_authenticating(&key, auth_url.clone()).await;
+            info!("[Admin] OAuth started for {}/{}", space_id, server_id);
+            Json(AdminOAuthStartResponse {
+                status: ConnectionStatus::Authenticating,
+                auth_url: Some(auth_url),
+            })
+            .into_response()
+        }
+        ConnectionResult::Failed { error } => {
+            manager.set_error(&key, error.clone()).await;
+            (StatusCode::BAD_GATEWAY, error).into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminOAuthCallbackRequest {
+    /// Full URL the provider redirected the admin's browser to
+    pub callback_url: String,
+}
+
+/// POST /admin/oauth/callback - complete a flow started remotely
+pub async fn admin_oauth_callback(
+    State(state): State<AppState>,
+    Json(request): Json<AdminOAuthCallbackRequest>,
+) -> Response {
+    let Ok(url) = url::Url::parse(&request.callback_url) else {
+        return (StatusCode::BAD_REQUEST, "callback_url is not a URL").into_response();
+    };
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.to_string())
+    };
+    let Some(oauth_state) = param("state") else {
+        return (StatusCode::BAD_REQUEST, "callback_url has no state").into_response();
+    };
+    let callback = OAuthCallback {
+        code: param("code"),
+        state: oauth_state,
+        error: param("error"),
+        error_description: param("error_description"),
+    };
+
+    match state
+        .services
+        .pool_services
+        .pool_service
+        .oauth_manager()
+        .handle_callback(callback)
+    {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => (StatusCode::CONFLICT, e).into_response(),
+    }
+}
+
+// ============================================================================
+// Clients and grants
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct AdminClient {
+    pub client_id: String,
+    pub client_name: String,
+    pub approved: bool,
+    pub last_seen: Option<String>,
+}
+
+/// GET /admin/clients
+pub async fn admin_list_clients(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+) -> Response {
+    match visible_clients(&state, &principal).await {
+        Ok(clients) => Json(
+            clients
+                .into_iter()
+                .map(|c| AdminClient {
+                    client_name: c.client_alias.unwrap_or(c.client_name),
+                    client_id: c.client_id,
+                    approved: c.approved,
+                    last_seen: c.last_seen,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// GET /admin/sessions - connected MCP sessions and their recent activity
+pub async fn admin_list_sessions(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    Json(state.services.session_activity.sessions()).into_response()
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRateLimits {
+    pub policies: Vec<RateLimitPolicy>,
+    pub rejections: Vec<RateLimitRejections>,
+}
+
+/// GET /admin/rate-limits - rate limit policies and the requests they refused
+pub async fn admin_rate_limits(
+    Extension(limiter): Extension<RateLimiter>,
+    Extension(principal): Extension<AdminPrincipal>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    Json(AdminRateLimits {
+        policies: limiter.rules(),
+        rejections: limiter.rejections(),
+    })
+    .into_response()
+}
+
+/// A grant is a member's to change when they own both the Space and the client
+async fn authorize_grant(
+    state: &AppState,
+    principal: &AdminPrincipal,
+    space_id: &str,
+    client_id: &str,
+) -> Result<Uuid, Response> {
+    let id = authorize_space(state, principal, space_id).await?;
+    check_client_access(state, principal, client_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    Ok(id)
+}
+
+/// GET /admin/spaces/{space_id}/clients/{client_id}/grants - FeatureSet IDs
+pub async fn admin_list_grants(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, client_id)): Path<(String, String)>,
+) -> Response {
+    if let Err(response) = authorize_grant(&state, &principal, &space_id, &client_id).await {
+        return response;
+    }
+    match state
+        .services
+        .grant_service
+        .get_grants_for_space(&client_id, &space_id)
+        .await
+    {
+        Ok(grants) => Json(grants).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// PUT /admin/spaces/{space_id}/clients/{client_id}/grants/{feature_set_id}
+pub async fn admin_grant(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, client_id, feature_set_id)): Path<(String, String, String)>,
+) -> Response {
+    let id = match authorize_grant(&state, &principal, &space_id, &client_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    if let Err(e) = check_feature_set_in_space(&state, id, &feature_set_id).await {
+        return e.into_response();
+    }
+    match state
+        .services
+        .grant_service
+        .grant_feature_set(&client_id, &space_id, &feature_set_id)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// DELETE /admin/spaces/{space_id}/clients/{client_id}/grants/{feature_set_id}
+pub async fn admin_revoke(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path((space_id, client_id, feature_set_id)): Path<(String, String, String)>,
+) -> Response {
+    if let Err(response) = authorize_grant(&state, &principal, &space_id, &client_id).await {
+        return response;
+    }
+    match state
+        .services
+        .grant_service
+        .revoke_feature_set(&client_id, &space_id, &feature_set_id)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+// ============================================================================
+// Users
+// ============================================================================
+
+/// GET /admin/users
+pub async fn admin_list_users(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    match state.services.dependencies.user_repo.list().await {
+        Ok(users) => Json(users).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCreateUserRequest {
+    pub name: String,
+    #[serde(default)]
+    pub role: UserRole,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminUserTokenResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    /// Shown once; only its hash is stored
+    pub token: String,
+}
+
+/// POST /admin/users - create a user and their admin token
+pub async fn admin_create_user(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Json(request): Json<AdminCreateUserRequest>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let users = state.services.dependencies.user_repo.as_ref();
+    match create_user(users, &request.name, request.role).await {
+        Ok((user, token)) => {
+            info!(
+                "[Admin] {} created {} user {}",
+                principal.name(),
+                user.role.as_str(),
+                user.name
+            );
+            (
+                StatusCode::CREATED,
+                Json(AdminUserTokenResponse {
+                    user: Some(user),
+                    token,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+fn parse_user_id(user_id: &str) -> Result<Uuid, Response> {
+    Uuid::parse_str(user_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid user_id").into_response())
+}
+
+/// Parse a user ID and check that the user exists
+async fn find_user(state: &AppState, user_id: &str) -> Result<User, Response> {
+    let id = parse_user_id(user_id)?;
+    match state.services.dependencies.user_repo.get(&id).await {
+        Ok(Some(user)) => Ok(user),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "User not found").into_response()),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+/// DELETE /admin/users/{user_id} - their Spaces and clients go back to admins
+pub async fn admin_delete_user(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path(user_id): Path<String>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let user = match find_user(&state, &user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+    match state.services.dependencies.user_repo.delete(&user.id).await {
+        Ok(()) => {
+            info!("[Admin] {} deleted user {}", principal.name(), user.name);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => internal_error(e),
+    }
+}
+
+/// POST /admin/users/{user_id}/token - replace a user's admin token
+pub async fn admin_rotate_user_token(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path(user_id): Path<String>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let user = match find_user(&state, &user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+    let users = state.services.dependencies.user_repo.as_ref();
+    match rotate_user_token(users, &user.id).await {
+        Ok(token) => Json(AdminUserTokenResponse { user: None, token }).into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminSetOwnerRequest {
+    /// New owner; `null` leaves it to admins only
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+/// Parse the requested owner, checking that the user exists
+async fn requested_owner(
+    state: &AppState,
+    request: &AdminSetOwnerRequest,
+) -> Result<Option<Uuid>, Response> {
+    match request.user_id.as_deref() {
+        Some(user_id) => Ok(Some(find_user(state, user_id).await?.id)),
+        None => Ok(None),
+    }
+}
+
+/// PUT /admin/spaces/{space_id}/owner - give a Space to a user
+pub async fn admin_set_space_owner(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path(space_id): Path<String>,
+    Json(request): Json<AdminSetOwnerRequest>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let space_uuid = match parse_space_id(&space_id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    let owner = match requested_owner(&state, &request).await {
+        Ok(owner) => owner,
+        Err(response) => return response,
+    };
+    let spaces = &state.services.dependencies.space_repo;
+    match spaces.get(&space_uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, "Space not found").into_response(),
+        Err(e) => return internal_error(e),
+    }
+    match spaces.set_owner(&space_uuid, owner.as_ref()).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// PUT /admin/clients/{client_id}/owner - give a client to a user
+pub async fn admin_set_client_owner(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    Path(client_id): Path<String>,
+    Json(request): Json<AdminSetOwnerRequest>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let owner = match requested_owner(&state, &request).await {
+        Ok(owner) => owner.map(|id| id.to_string()),
+        Err(response) => return response,
+    };
+    match state
+        .services
+        .dependencies
+        .inbound_client_repo
+        .set_client_owner(&client_id, owner.as_deref())
+        .await
+    {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Client not found").into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+// ============================================================================
+// Device pairing
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminPairingRequest {
+    /// Name for the paired client; the device's own name when unset
+    #[serde(default)]
+    pub client_name: Option<String>,
+    /// Space to lock the paired client to
+    #[serde(default)]
+    pub locked_space_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminPairingResponse {
+    pub code: String,
+    /// What to put in a QR code: the gateway URL plus the code
+    pub pair_url: String,
+    /// Unix timestamp after which the code stops working
+    pub expires_at: i64,
+}
+
+/// POST /admin/pairings - create a one-time pairing code for a device
+pub async fn admin_create_pairing(
+    State(state): State<AppState>,
+    Extension(principal): Extension<AdminPrincipal>,
+    headers: HeaderMap,
+    body: Option<Json<AdminPairingRequest>>,
+) -> Response {
+    if let Err(e) = require_admin(&principal) {
+        return e.into_response();
+    }
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    if let Some(space_id) = request.locked_space_id.as_deref() {
+        if let Err(response) = parse_space_id(space_id) {
+            return response;
+        }
+    }
+
+    let code = generate_pairing_code();
+    let pairing = PendingPairing::new(request.client_name, request.locked_space_id);
+    let expires_at = pairing.expires_at;
+
+    let mut gateway_state = state.gateway_state.write().await;
+    gateway_state.store_pairing(&code, pairing);
+    // The admin reached the gateway from elsewhere, so the device can too
+    let base = effective_base_url(
+        gateway_state.public_base_url.as_deref(),
+        gateway_state.network_bind,
+        headers.get(HOST).and_then(|v| v.to_str().ok()),
+        &gateway_state.base_url,
+    );
+    drop(gateway_state);
+
+    info!("[Admin] Created a pairing code");
+    Json(AdminPairingResponse {
+        pair_url: pairing_url(&base, &code),
+        code,
+        expires_at,
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admin_tokens_are_prefixed_and_unique() {
+        let a = generate_admin_token();
+        let b = generate_admin_token();
+        assert!(a.starts_with(ADMIN_TOKEN_PREFIX));
+        assert_eq!(a.len(), ADMIN_TOKEN_PREFIX.len() + 64);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn hashes_match_requires_equal_length_and_content() {
+        assert!(hashes_match("abc123", "abc123"));
+        assert!(!hashes_match("abc123", "abc124"));
+        assert!(!hashes_match("abc", "abc123"));
+    }
+
+    #[test]
+    fn log_stream_query_parses_level_and_sources() {
+        let query = AdminLogStreamQuery {
+            level: Some("WARN".to_string()),
+            source: Some("stderr, connection".to_string()),
+        };
+        let filter = query.to_filter().unwrap();
+        assert_eq!(filter.min_level, Some(LogLevel::Warn));
+        assert_eq!(
+            filter.sources,
+            vec![LogSource::Stderr, LogSource::Connection]
+        );
+
+        assert_eq!(
+            AdminLogStreamQuery::default().to_filter().unwrap(),
+            LogFilter::default()
+        );
+        let query = AdminLogStreamQuery {
+            level: None,
+            source: Some("stdin".to_string()),
+        };
+        assert!(query.to_filter().is_err());
+    }
+
+    #[test]
+    fn members_see_only_their_spaces_and_events() {
+        let alice = User::new("alice", UserRole::Member);
+        let member = AdminPrincipal::User(alice.clone());
+        let mine = Space::new("Mine").with_owner(alice.id);
+        let theirs = Space::new("Theirs");
+        assert!(member.can_access_space(&mine));
+        assert!(!member.can_access_space(&theirs));
+        assert!(!member.is_admin());
+        assert!(AdminPrincipal::Operator.can_access_space(&theirs));
+        assert!(AdminPrincipal::User(User::new("root", UserRole::Admin)).is_admin());
+
+        let scope = EventScope::Spaces([mine.id].into_iter().collect());
+        let event = |space_id| DomainEvent::ClientGrantChanged {
+            client_id: "client-1".to_string(),
+            space_id,
+        };
+        assert!(scope.allows(&event(mine.id)));
+        assert!(!scope.allows(&event(theirs.id)));
+        assert!(!scope.allows(&DomainEvent::GatewayStopped));
+        assert!(EventScope::All.allows(&DomainEvent::GatewayStopped));
+    }
+
+    #[test]
+    fn event_stream_query_parses_space_and_types() {
+        let space_id = Uuid::new_v4();
+        let query = EventStreamQuery {
+            space_id: Some(space_id.to_string()),
+            types: Some("server_status_changed, tools_changed".to_string()),
+        };
+        assert_eq!(
+            query.to_filter().unwrap(),
+            EventFilter {
+                space_id: Some(space_id),
+                types: vec![
+                    "server_status_changed".to_string(),
+                    "tools_changed".to_string()
+                ],
+            }
+        );
+        assert_eq!(
+            EventStreamQuery::default().to_filter().unwrap(),
+            EventFilter::default()
+        );
+        let query = EventStreamQuery {
+            space_id: Some("default".to_string()),
+            types: None,
+        };
+        assert!(query.to_filter().is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! CORS policies for browser-based clients
+//!
+//! Browser MCP clients (web IDEs such as vscode.dev, hosted chat apps) call
+//! the gateway cross-origin, so it has to answer CORS preflights. Client
+//! requests carry a Bearer token rather than cookies, which is why the
+//! defaults allow any origin; they limit methods and headers to what MCP and
+//! OAuth clients send, and expose the headers a browser client must read.
+//!
+//! Path prefixes can get their own policy. By default the OAuth discovery
+//! and token endpoints only allow what the OAuth flow needs, and desktop
+//! client management (`PUT`/`DELETE /oauth/clients/...`) isn't reachable
+//! cross-origin at all.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    extract::{Request, State},
+    http::{request::Parts, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use tower::{Layer, ServiceExt};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer, ExposeHeaders};
+
+/// Allows any origin, method or header
+pub const CORS_WILDCARD: &str = "*";
+
+/// Response headers browser clients need to read
+const EXPOSED_HEADERS: &[&str] = &["mcp-session-id", "mcp-protocol-version", "www-authenticate"];
+
+/// Request headers MCP clients send
+const MCP_HEADERS: &[&str] = &[
+    "authorization",
+    "content-type",
+    "accept",
+    "last-event-id",
+    "mcp-session-id",
+    "mcp-protocol-version",
+    "x-mcpmux-space",
+    "x-mcpmux-workspace",
+];
+
+/// Request headers OAuth clients send
+const OAUTH_HEADERS: &[&str] = &[
+    "authorization",
+    "content-type",
+    "accept",
+    "mcp-protocol-version",
+];
+
+/// Which browser origins may call the gateway, and how
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsPolicy {
+    /// Origins such as `https://vscode.dev`. `https://*.github.dev` matches
+    /// every subdomain, `*` any origin, and an empty list none.
+    pub allowed_origins: Vec<String>,
+    /// Methods such as `POST`, or `*`
+    pub allowed_methods: Vec<String>,
+    /// Request headers such as `mcp-session-id`, or `*`
+    pub allowed_headers: Vec<String>,
+}
+
+impl CorsPolicy {
+    fn new(origins: &[&str], methods: &[&str], headers: &[&str]) -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            allowed_origins: owned(origins),
+            allowed_methods: owned(methods),
+            allowed_headers: owned(headers),
+        }
+    }
+
+    /// Check that every method and header parses and every origin is `*`
+    /// or a `scheme://host[:port]` origin.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for origin in &self.allowed_origins {
+            if origin != CORS_WILDCARD && !is_origin_pattern(origin) {
+                anyhow::bail!(
+                    "Invalid origin {:?}: use e.g. https://vscode.dev, https://*.github.dev or *",
+                    origin
+                );
+            }
+        }
+        for method in self.allowed_methods.iter().filter(|m| *m != CORS_WILDCARD) {
+            Method::from_bytes(method.as_bytes())
+                .map_err(|_| anyhow::anyhow!("Invalid method {:?}", method))?;
+        }
+        for header in self.allowed_headers.iter().filter(|h| *h != CORS_WILDCARD) {
+            HeaderName::from_bytes(header.as_bytes())
+                .map_err(|_| anyhow::anyhow!("Invalid header name {:?}", header))?;
+        }
+        Ok(())
+    }
+
+    /// The layer enforcing this policy. Invalid entries are skipped.
+    fn layer(&self) -> CorsLayer {
+        let has_wildcard = |items: &[String]| items.iter().any(|i| i == CORS_WILDCARD);
+
+        let origins = if has_wildcard(&self.allowed_origins) {
+            AllowOrigin::any()
+        } else {
+            let patterns = self.allowed_origins.clone();
+            AllowOrigin::predicate(move |origin: &HeaderValue, _: &Parts| {
+                origin
+                    .to_str()
+                    .is_ok_and(|origin| patterns.iter().any(|p| origin_matches(p, origin)))
+            })
+        };
+        let methods = if has_wildcard(&self.allowed_methods) {
+            AllowMethods::any()
+        } else {
+            AllowMethods::list(
+                self.allowed_methods
+                    .iter()
+                    .filter_map(|m| Method::from_bytes(m.as_bytes()).ok()),
+            )
+        };
+        let headers = if has_wildcard(&self.allowed_headers) {
+            AllowHeaders::any()
+        } else {
+            AllowHeaders::list(
+                self.allowed_headers
+                    .iter()
+                    .filter_map(|h| HeaderName::from_bytes(h.as_bytes()).ok()),
+            )
+        };
+
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .expose_headers(ExposeHeaders::list(
+                EXPOSED_HEADERS.iter().map(|h| HeaderName::from_static(h)),
+            ))
+    }
+}
+
+/// CORS policies for the whole gateway
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsConfig {
+    /// Policy for paths no route policy covers
+    pub default: CorsPolicy,
+    /// Policies by path prefix; the longest matching prefix wins
+    #[serde(default)]
+    pub routes: BTreeMap<String, CorsPolicy>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            default: CorsPolicy::new(
+                &[CORS_WILDCARD],
+                &["GET", "POST", "PUT", "DELETE", "OPTIONS"],
+                MCP_HEADERS,
+            ),
+            routes: BTreeMap::from([
+                (
+                    "/.well-known/".to_string(),
+                    CorsPolicy::new(&[CORS_WILDCARD], &["GET", "OPTIONS"], OAUTH_HEADERS),
+                ),
+                (
+                    "/oauth/".to_string(),
+                    CorsPolicy::new(&[CORS_WILDCARD], &["GET", "POST", "OPTIONS"], OAUTH_HEADERS),
+                ),
+            ]),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Validate every policy; route prefixes must start with `/`
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.default.validate()?;
+        for (prefix, policy) in &self.routes {
+            if !prefix.starts_with('/') {
+                anyhow::bail!("CORS route prefix must start with '/': {}", prefix);
+            }
+            policy
+                .validate()
+                .map_err(|e| anyhow::anyhow!("{} (route {})", e, prefix))?;
+        }
+        Ok(())
+    }
+}
+
+/// [`CorsConfig`] turned into layers, ready to apply per request
+#[derive(Clone)]
+pub(crate) struct CorsLayers {
+    default: CorsLayer,
+    /// Longest prefix first
+    routes: Vec<(String, CorsLayer)>,
+}
+
+impl CorsLayers {
+    pub(crate) fn new(config: &CorsConfig) -> Self {
+        let mut routes: Vec<_> = config
+            .routes
+            .iter()
+            .map(|(prefix, policy)| (prefix.clone(), policy.layer()))
+            .collect();
+        routes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        Self {
+            default: config.default.layer(),
+            routes,
+        }
+    }
+
+    pub(crate) fn for_path(&self, path: &str) -> &CorsLayer {
+        self.routes
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .map(|(_, layer)| layer)
+            .unwrap_or(&self.default)
+    }
+}
+
+/// CORS as the running gateway applies it. The reloader switches it on and
+/// off and swaps the policies; requests pick them up as they arrive.
+pub(crate) struct DynamicCors {
+    enabled: AtomicBool,
+    layers: RwLock<Arc<CorsLayers>>,
+}
+
+impl DynamicCors {
+    pub(crate) fn new(enabled: bool, config: &CorsConfig) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            layers: RwLock::new(Arc::new(CorsLayers::new(config))),
+        }
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_config(&self, config: &CorsConfig) {
+        let layers = Arc::new(CorsLayers::new(config));
+        *self.layers.write().unwrap_or_else(|e| e.into_inner()) = layers;
+    }
+
+    fn layers(&self) -> Option<Arc<CorsLayers>> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        Some(
+            self.layers
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        )
+    }
+}
+
+/// Apply the policy for the request path while CORS is enabled, so it can
+/// be changed without rebuilding the router.
+pub(crate) async fn dynamic_cors(
+    State(cors): State<Arc<DynamicCors>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(layers) = cors.layers() else {
+        return next.run(request).await;
+    };
+    let layer = layers.for_path(request.uri().path()).clone();
+    match layer.layer(next).oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
+/// Split `scheme://host[:port]`, rejecting paths, queries and userinfo
+fn split_origin(origin: &str) -> Option<(&str, &str)> {
+    let (scheme, authority) = origin.split_once("://")?;
+    let valid = !scheme.is_empty()
+        && !authority.is_empty()
+        && !authority.contains(['/', '?', '#', '@'])
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some((scheme, authority))
+}
+
+fn is_origin_pattern(pattern: &str) -> bool {
+    split_origin(pattern).is_some_and(|(_, authority)| {
+        let host = authority.strip_prefix("*.").unwrap_or(authority);
+        !host.is_empty() && !host.contains('*')
+    })
+}
+
+/// Whether `origin` matches `pattern`: `*`, an exact origin, or
+/// `scheme://*.domain[:port]` for any subdomain of `domain`
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+    if pattern == CORS_WILDCARD {
+        return true;
+    }
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.eq_ignore_ascii_case(origin) {
+        return true;
+    }
+    let (Some((pattern_scheme, pattern_authority)), Some((scheme, authority))) =
+        (split_origin(pattern), split_origin(origin))
+    else {
+        return false;
+    };
+    let Some(suffix) = pattern_authority.strip_prefix('*') else {
+        return false;
+    };
+    let authority = authority.to_ascii_lowercase();
+    pattern_scheme.eq_ignore_ascii_case(scheme)
+        && authority.ends_with(&suffix.to_ascii_lowercase())
+        && !authority[..authority.len() - suffix.len()].contains(':')
+        && authority.len() > suffix.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn origins_match_exactly_or_by_subdomain() {
+        assert!(origin_matches("*", "https://vscode.dev"));
+        assert!(origin_matches("https://vscode.dev", "https://vscode.dev"));
+        assert!(origin_matches("https://vscode.dev/", "https://VSCode.dev"));
+        assert!(!origin_matches("https://vscode.dev", "http://vscode.dev"));
+        assert!(origin_matches(
+            "https://*.github.dev",
+            "https://a-b.github.dev"
+        ));
+        assert!(!origin_matches(
+            "https://*.github.dev",
+            "https://github.dev"
+        ));
+        assert!(!origin_matches(
+            "https://*.github.dev",
+            "https://evilgithub.dev"
+        ));
+        assert!(!origin_matches(
+            "https://*.github.dev",
+            "http://a.github.dev"
+        ));
+        assert!(origin_matches(
+            "http://*.local:3000",
+            "http://box.local:3000"
+        ));
+        assert!(!origin_matches(
+            "http://*.local:3000",
+            "http://box.local:4000"
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_entries() {
+        assert!(CorsConfig::default().validate().is_ok());
+
+        let mut policy = CorsConfig::default().default;
+        policy.allowed_origins = vec!["vscode.dev".to_string()];
+        assert!(policy.validate().is_err());
+        policy.allowed_origins = vec!["https://vscode.dev/path".to_string()];
+        assert!(policy.validate().is_err());
+        policy.allowed_origins = vec!["https://a.*.dev".to_string()];
+        assert!(policy.validate().is_err());
+
+        let mut policy = CorsConfig::default().default;
+        policy.allowed_headers = vec!["bad header".to_string()];
+        assert!(policy.validate().is_err());
+
+        let mut config = CorsConfig::default();
+        let policy = config.default.clone();
+        config.routes.insert("oauth".to_string(), policy);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn longest_route_prefix_wins() {
+        let mut config = CorsConfig::default();
+        let mut token = config.default.clone();
+        token.allowed_methods = vec!["POST".to_string()];
+        config.routes.insert("/oauth/token".to_string(), token);
+        let layers = CorsLayers::new(&config);
+        let route = |prefix: &str| {
+            let (_, layer) = layers.routes.iter().find(|(p, _)| p == prefix).unwrap();
+            layer
+        };
+        assert!(std::ptr::eq(
+            layers.for_path("/oauth/token"),
+            route("/oauth/token")
+        ));
+        assert!(std::ptr::eq(
+            layers.for_path("/oauth/register"),
+            route("/oauth/")
+        ));
+        assert!(std::ptr::eq(layers.for_path("/mcp"), &layers.default));
+    }
+}
@@ -8,12 +8,16 @@ use std::sync::Arc;
 
 use crate::services::ClientMetadataService;
 use mcpmux_core::{
-    AppSettingsRepository, CimdMetadataFetcher, CredentialRepository, FeatureSetRepository,
-    InboundMcpClientRepository, InstalledServerRepository, OutboundOAuthRepository,
-    ServerDiscoveryService, ServerFeatureRepository, ServerLogManager, SpaceBaseDirRepository,
-    SpaceBuiltinConfigRepository, SpaceRepository, WorkspaceBindingRepository,
+    AppSettingsRepository, CimdMetadataFetcher, ContentFilter, CredentialRepository,
+    EnvironmentProfileRepository, EventJournalRepository, FeatureSetRepository,
+    InboundMcpClientRepository, InjectionScanner, InstalledServerRepository, ManagedPolicy,
+    OutboundOAuthRepository, SecretResolverRegistry, ServerDiscoveryService,
+    ServerFeatureRepository, ServerLogManager, SpaceBaseDirRepository,
+    SpaceBuiltinConfigRepository, SpaceRepository, UserRepository, WorkspaceBindingRepository,
+};
+use mcpmux_storage::{
+    Database, InboundClientRepository, RepositorySet, SqliteInboundClientRepository,
 };
-use mcpmux_storage::{Database, InboundClientRepository};
 use tokio::sync::Mutex;
 
 /// Dependency container for Gateway
@@ -29,7 +33,7 @@ pub struct GatewayDependencies {
     pub feature_repo: Arc<dyn ServerFeatureRepository>,
     pub feature_set_repo: Arc<dyn FeatureSetRepository>,
     pub space_repo: Arc<dyn SpaceRepository>,
-    pub inbound_client_repo: Arc<InboundClientRepository>,
+    pub inbound_client_repo: Arc<dyn InboundClientRepository>,
     /// Trait-based MCP client repository (for Client entity CRUD + pin setters).
     ///
     /// Used by the FeatureSet resolver v2 — separate from `inbound_client_repo`
@@ -43,15 +47,26 @@ pub struct GatewayDependencies {
     /// Per-Space built-in server config (Tool Optimization enablement + tool
     /// toggles), consulted when advertising the `mcpmux_*` tools per Space.
     pub builtin_config_repo: Arc<dyn SpaceBuiltinConfigRepository>,
+    /// People sharing the gateway, each with their own admin token
+    pub user_repo: Arc<dyn UserRepository>,
 
     // Services (Business Layer)
     pub server_discovery: Arc<ServerDiscoveryService>,
     pub log_manager: Arc<ServerLogManager>,
     pub cimd_fetcher: Arc<CimdMetadataFetcher>,
     pub client_metadata_service: Arc<ClientMetadataService>,
+    /// Resolvers for `${env:..}` / `${keychain:..}` / external vault references
+    pub secret_resolvers: Arc<SecretResolverRegistry>,
+    /// Organization rules from the machine-wide policy file
+    pub managed_policy: Arc<ManagedPolicy>,
+    /// Detectors run on tool results; the managed policy's rules by default
+    pub content_filter: Arc<ContentFilter>,
+    /// Prompt-injection scanner from the managed policy, when it enables one
+    pub injection_scanner: Option<Arc<InjectionScanner>>,
 
-    // Database (for Gateway state persistence)
-    pub database: Arc<Mutex<Database>>,
+    /// SQLite database the repositories were built over; `None` when they
+    /// live in PostgreSQL
+    pub database: Option<Arc<Mutex<Database>>>,
 
     // JWT signing secret (optional, for token issuance)
     pub jwt_secret: Option<zeroize::Zeroizing<[u8; mcpmux_storage::JWT_SECRET_SIZE]>>,
@@ -59,6 +74,12 @@ pub struct GatewayDependencies {
     pub state_dir: Option<PathBuf>,
     /// App settings repository (for OAuth port persistence)
     pub settings_repo: Option<Arc<dyn AppSettingsRepository>>,
+    /// Per-Space environment profiles (dev/staging/prod). Needs the field
+    /// encryptor, so hosts inject it; without one no profile is applied.
+    pub environment_profile_repo: Option<Arc<dyn EnvironmentProfileRepository>>,
+    /// Persistent domain event journal. Without one, events are only
+    /// broadcast and consumers can't catch up on what they missed.
+    pub event_journal_repo: Option<Arc<dyn EventJournalRepository>>,
 }
 
 impl GatewayDependencies {
@@ -71,7 +92,7 @@ impl GatewayDependencies {
         feature_repo: Arc<dyn ServerFeatureRepository>,
         feature_set_repo: Arc<dyn FeatureSetRepository>,
         space_repo: Arc<dyn SpaceRepository>,
-        inbound_client_repo: Arc<InboundClientRepository>,
+        inbound_client_repo: Arc<dyn InboundClientRepository>,
         server_discovery: Arc<ServerDiscoveryService>,
         log_manager: Arc<ServerLogManager>,
         cimd_fetcher: Arc<CimdMetadataFetcher>,
@@ -94,6 +115,9 @@ impl GatewayDependencies {
         let builtin_config_repo: Arc<dyn SpaceBuiltinConfigRepository> = Arc::new(
             mcpmux_storage::SqliteSpaceBuiltinConfigRepository::new(database.clone()),
         );
+        let user_repo: Arc<dyn UserRepository> =
+            Arc::new(mcpmux_storage::SqliteUserRepository::new(database.clone()));
+        let managed_policy = ManagedPolicy::load();
 
         Self {
             installed_server_repo,
@@ -107,14 +131,21 @@ impl GatewayDependencies {
             workspace_binding_repo,
             space_base_dir_repo,
             builtin_config_repo,
+            user_repo,
             server_discovery,
             log_manager,
             cimd_fetcher,
             client_metadata_service,
-            database,
+            secret_resolvers: Arc::new(mcpmux_storage::default_secret_resolvers()),
+            content_filter: Arc::new(managed_policy.content_filter()),
+            injection_scanner: managed_policy.injection_scanner().map(Arc::new),
+            managed_policy: Arc::new(managed_policy),
+            database: Some(database),
             jwt_secret,
             state_dir,
             settings_repo: None, // Use builder for this
+            environment_profile_repo: None,
+            event_journal_repo: None,
         }
     }
 }
@@ -127,15 +158,25 @@ pub struct DependenciesBuilder {
     feature_repo: Option<Arc<dyn ServerFeatureRepository>>,
     feature_set_repo: Option<Arc<dyn FeatureSetRepository>>,
     space_repo: Option<Arc<dyn SpaceRepository>>,
-    inbound_client_repo: Option<Arc<InboundClientRepository>>,
+    inbound_client_repo: Option<Arc<dyn InboundClientRepository>>,
+    inbound_mcp_client_repo: Option<Arc<dyn InboundMcpClientRepository>>,
+    workspace_binding_repo: Option<Arc<dyn WorkspaceBindingRepository>>,
+    space_base_dir_repo: Option<Arc<dyn SpaceBaseDirRepository>>,
+    builtin_config_repo: Option<Arc<dyn SpaceBuiltinConfigRepository>>,
+    user_repo: Option<Arc<dyn UserRepository>>,
     server_discovery: Option<Arc<ServerDiscoveryService>>,
     log_manager: Option<Arc<ServerLogManager>>,
     cimd_fetcher: Option<Arc<CimdMetadataFetcher>>,
     client_metadata_service: Option<Arc<ClientMetadataService>>,
+    secret_resolvers: Option<Arc<SecretResolverRegistry>>,
+    managed_policy: Option<Arc<ManagedPolicy>>,
+    content_filter: Option<Arc<ContentFilter>>,
     database: Option<Arc<Mutex<Database>>>,
     jwt_secret: Option<zeroize::Zeroizing<[u8; mcpmux_storage::JWT_SECRET_SIZE]>>,
     state_dir: Option<PathBuf>,
     settings_repo: Option<Arc<dyn AppSettingsRepository>>,
+    environment_profile_repo: Option<Arc<dyn EnvironmentProfileRepository>>,
+    event_journal_repo: Option<Arc<dyn EventJournalRepository>>,
 }
 
 impl DependenciesBuilder {
@@ -148,14 +189,24 @@ impl DependenciesBuilder {
             feature_set_repo: None,
             space_repo: None,
             inbound_client_repo: None,
+            inbound_mcp_client_repo: None,
+            workspace_binding_repo: None,
+            space_base_dir_repo: None,
+            builtin_config_repo: None,
+            user_repo: None,
             server_discovery: None,
             log_manager: None,
             cimd_fetcher: None,
             client_metadata_service: None,
+            secret_resolvers: None,
+            managed_policy: None,
+            content_filter: None,
             database: None,
             jwt_secret: None,
             state_dir: None,
             settings_repo: None,
+            environment_profile_repo: None,
+            event_journal_repo: None,
         }
     }
 
@@ -184,6 +235,27 @@ impl DependenciesBuilder {
         self
     }
 
+    /// Use every repository of `repos`, SQLite or PostgreSQL
+    pub fn with_repositories(mut self, repos: &RepositorySet) -> Self {
+        self.installed_server_repo = Some(repos.installed_servers.clone());
+        self.credential_repo = Some(repos.credentials.clone());
+        self.backend_oauth_repo = Some(repos.outbound_oauth.clone());
+        self.feature_repo = Some(repos.server_features_core.clone());
+        self.feature_set_repo = Some(repos.feature_sets.clone());
+        self.space_repo = Some(repos.spaces.clone());
+        self.inbound_client_repo = Some(repos.inbound_clients.clone());
+        self.inbound_mcp_client_repo = Some(repos.inbound_mcp_clients.clone());
+        self.workspace_binding_repo = Some(repos.workspace_bindings.clone());
+        self.space_base_dir_repo = Some(repos.space_base_dirs.clone());
+        self.builtin_config_repo = Some(repos.builtin_configs.clone());
+        self.user_repo = Some(repos.users.clone());
+        self.settings_repo = Some(repos.settings.clone());
+        self.environment_profile_repo = Some(repos.environment_profiles.clone());
+        self.event_journal_repo = Some(repos.event_journal.clone());
+        self.database = repos.sqlite_database();
+        self
+    }
+
     pub fn with_server_discovery(mut self, service: Arc<ServerDiscoveryService>) -> Self {
         self.server_discovery = Some(service);
         self
@@ -194,6 +266,25 @@ impl DependenciesBuilder {
         self
     }
 
+    pub fn with_secret_resolvers(mut self, resolvers: Arc<SecretResolverRegistry>) -> Self {
+        self.secret_resolvers = Some(resolvers);
+        self
+    }
+
+    /// Use this policy instead of the machine-wide one (embedders and tests)
+    pub fn with_managed_policy(mut self, policy: Arc<ManagedPolicy>) -> Self {
+        self.managed_policy = Some(policy);
+        self
+    }
+
+    /// Run this filter on tool results instead of the managed policy's
+    /// rules, e.g. to add rules with custom detectors
+    pub fn with_content_filter(mut self, filter: Arc<ContentFilter>) -> Self {
+        self.content_filter = Some(filter);
+        self
+    }
+
+    /// SQLite database for the repositories not injected otherwise
     pub fn with_database(mut self, db: Arc<Mutex<Database>>) -> Self {
         self.database = Some(db);
         self
@@ -217,49 +308,78 @@ impl DependenciesBuilder {
         self
     }
 
+    pub fn with_environment_profile_repo(
+        mut self,
+        repo: Arc<dyn EnvironmentProfileRepository>,
+    ) -> Self {
+        self.environment_profile_repo = Some(repo);
+        self
+    }
+
+    pub fn with_event_journal_repo(mut self, repo: Arc<dyn EventJournalRepository>) -> Self {
+        self.event_journal_repo = Some(repo);
+        self
+    }
+
     pub fn build(self) -> Result<GatewayDependencies, String> {
-        let database = self.database.ok_or("database is required")?;
+        let database = self.database;
+        // Repositories not injected are built over the SQLite database
+        let sqlite = || database.clone().ok_or("database is required");
 
         // Create CIMD fetcher if not provided
         let cimd_fetcher = self
             .cimd_fetcher
             .unwrap_or_else(|| Arc::new(CimdMetadataFetcher::default()));
 
+        let inbound_client_repo = match self.inbound_client_repo {
+            Some(repo) => repo,
+            None => Arc::new(SqliteInboundClientRepository::new(sqlite()?)),
+        };
+
         // Create ClientMetadataService if not provided
         let client_metadata_service = self.client_metadata_service.unwrap_or_else(|| {
-            let inbound_client_repo = Arc::new(mcpmux_storage::InboundClientRepository::new(
-                database.clone(),
-            ));
             Arc::new(ClientMetadataService::new(
-                inbound_client_repo,
+                inbound_client_repo.clone(),
                 cimd_fetcher.clone(),
             ))
         });
 
-        // Create repositories from database if not provided
-        let space_repo = self.space_repo.unwrap_or_else(|| {
-            Arc::new(mcpmux_storage::SqliteSpaceRepository::new(database.clone()))
-        });
-
-        let inbound_client_repo = self.inbound_client_repo.unwrap_or_else(|| {
-            Arc::new(mcpmux_storage::InboundClientRepository::new(
-                database.clone(),
-            ))
-        });
-
-        // Resolver v2 repositories — always SQLite-backed for now.
-        let inbound_mcp_client_repo: Arc<dyn InboundMcpClientRepository> = Arc::new(
-            mcpmux_storage::SqliteInboundMcpClientRepository::new(database.clone()),
-        );
-        let workspace_binding_repo: Arc<dyn WorkspaceBindingRepository> = Arc::new(
-            mcpmux_storage::SqliteWorkspaceBindingRepository::new(database.clone()),
-        );
-        let space_base_dir_repo: Arc<dyn SpaceBaseDirRepository> = Arc::new(
-            mcpmux_storage::SqliteSpaceBaseDirRepository::new(database.clone()),
-        );
-        let builtin_config_repo: Arc<dyn SpaceBuiltinConfigRepository> = Arc::new(
-            mcpmux_storage::SqliteSpaceBuiltinConfigRepository::new(database.clone()),
-        );
+        let space_repo: Arc<dyn SpaceRepository> = match self.space_repo {
+            Some(repo) => repo,
+            None => Arc::new(mcpmux_storage::SqliteSpaceRepository::new(sqlite()?)),
+        };
+        let inbound_mcp_client_repo: Arc<dyn InboundMcpClientRepository> =
+            match self.inbound_mcp_client_repo {
+                Some(repo) => repo,
+                None => Arc::new(mcpmux_storage::SqliteInboundMcpClientRepository::new(
+                    sqlite()?,
+                )),
+            };
+        let workspace_binding_repo: Arc<dyn WorkspaceBindingRepository> =
+            match self.workspace_binding_repo {
+                Some(repo) => repo,
+                None => Arc::new(mcpmux_storage::SqliteWorkspaceBindingRepository::new(
+                    sqlite()?,
+                )),
+            };
+        let space_base_dir_repo: Arc<dyn SpaceBaseDirRepository> = match self.space_base_dir_repo {
+            Some(repo) => repo,
+            None => Arc::new(mcpmux_storage::SqliteSpaceBaseDirRepository::new(sqlite()?)),
+        };
+        let builtin_config_repo: Arc<dyn SpaceBuiltinConfigRepository> =
+            match self.builtin_config_repo {
+                Some(repo) => repo,
+                None => Arc::new(mcpmux_storage::SqliteSpaceBuiltinConfigRepository::new(
+                    sqlite()?,
+                )),
+            };
+        let user_repo: Arc<dyn UserRepository> = match self.user_repo {
+            Some(repo) => repo,
+            None => Arc::new(mcpmux_storage::SqliteUserRepository::new(sqlite()?)),
+        };
+        let managed_policy = self
+            .managed_policy
+            .unwrap_or_else(|| Arc::new(ManagedPolicy::load()));
 
         Ok(GatewayDependencies {
             installed_server_repo: self
@@ -279,16 +399,27 @@ impl DependenciesBuilder {
             workspace_binding_repo,
             space_base_dir_repo,
             builtin_config_repo,
+            user_repo,
             server_discovery: self
                 .server_discovery
                 .ok_or("server_discovery is required")?,
             log_manager: self.log_manager.ok_or("log_manager is required")?,
             cimd_fetcher,
             client_metadata_service,
+            secret_resolvers: self
+                .secret_resolvers
+                .unwrap_or_else(|| Arc::new(mcpmux_storage::default_secret_resolvers())),
+            content_filter: self
+                .content_filter
+                .unwrap_or_else(|| Arc::new(managed_policy.content_filter())),
+            injection_scanner: managed_policy.injection_scanner().map(Arc::new),
+            managed_policy,
             database,
             jwt_secret: self.jwt_secret,
             state_dir: self.state_dir,
             settings_repo: self.settings_repo,
+            environment_profile_repo: self.environment_profile_repo,
+            event_journal_repo: self.event_journal_repo,
         })
     }
 }
@@ -0,0 +1,129 @@
+//! Connection draining for graceful shutdown.
+//!
+//! Once draining starts, new MCP sessions and tool calls are refused while
+//! tool calls already running are counted down, so shutdown can wait for
+//! them (up to a deadline) before closing SSE streams and backends.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tokio::sync::Notify;
+
+/// Tracks in-flight tool calls and whether the gateway is shutting down
+#[derive(Debug, Default)]
+pub struct DrainTracker {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl DrainTracker {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Register an in-flight call. Returns `None` once draining has started.
+    pub fn begin(self: &Arc<Self>) -> Option<InFlightGuard> {
+        if self.is_draining() {
+            return None;
+        }
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        // Re-check: shutdown may have started between the two operations
+        if self.is_draining() {
+            drop(InFlightGuard(self.clone()));
+            return None;
+        }
+        Some(InFlightGuard(self.clone()))
+    }
+
+    /// Refuse new sessions and calls from now on
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Number of calls still running
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait until no calls are running. Returns false if `timeout` elapsed first.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// Decrements the in-flight count when dropped
+#[derive(Debug)]
+pub struct InFlightGuard(Arc<DrainTracker>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// Refuse requests that would open a new MCP session while draining.
+///
+/// Requests carrying `Mcp-Session-Id` belong to an existing session and pass
+/// through; `call_tool` refuses new calls on them itself.
+pub async fn reject_new_sessions_while_draining(
+    State(tracker): State<Arc<DrainTracker>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if tracker.is_draining() && !request.headers().contains_key("mcp-session-id") {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "5")],
+            "Gateway is shutting down",
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_idle_returns_once_calls_finish() {
+        let tracker = DrainTracker::new();
+        let guard = tracker.begin().expect("not draining yet");
+        tracker.start_draining();
+        assert!(tracker.begin().is_none());
+        assert!(!tracker.wait_idle(Duration::from_millis(20)).await);
+
+        let waiter = {
+            let tracker = tracker.clone();
+            tokio::spawn(async move { tracker.wait_idle(Duration::from_secs(5)).await })
+        };
+        drop(guard);
+        assert!(waiter.await.unwrap());
+        assert_eq!(tracker.in_flight(), 0);
+    }
+}
@@ -0,0 +1,401 @@
+//! gRPC control plane (`mcpmux.admin.v1.AdminService`)
+//!
+//! The typed counterpart of the [`admin`](super::admin) HTTP API, for
+//! headless deployments and web UIs that want generated clients: server
+//! statuses, enabling servers, grants, and domain events streamed as they
+//! happen. The contract is `proto/mcpmux/admin/v1/admin.proto`.
+//!
+//! Served on its own port ([`GatewayConfig::grpc_port`](super::GatewayConfig))
+//! since the HTTP listeners speak HTTP/1 only. Guarded by the same admin
+//! and user tokens as `/admin/*`, passed as `authorization: Bearer <token>`
+//! metadata, with the same limits on what members may see.
+
+// Every handler returns tonic's `Status`, which is large by design
+#![allow(clippy::result_large_err)]
+
+use std::pin::Pin;
+
+use futures::future::try_join_all;
+use futures::Stream;
+use mcpmux_core::{DomainEvent, EventFilter};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_util::sync::CancellationToken;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::admin::{
+    check_admin_token, check_client_access, check_feature_set_in_space, check_space_access,
+    disable_server, enable_server, event_scope, gateway_status, server_statuses, visible_clients,
+    AccessError, AdminAuth, AdminPrincipal, AdminServerStatus, ServerChangeError,
+};
+use super::handlers::AppState;
+use super::startup::ConnectOutcome;
+use crate::pool::ConnectionStatus;
+
+/// Messages and service traits generated from `admin.proto`
+pub mod proto {
+    tonic::include_proto!("mcpmux.admin.v1");
+}
+
+use proto::admin_service_server::{AdminService, AdminServiceServer};
+
+/// [`AdminService`] over a running gateway's services
+#[derive(Clone)]
+pub struct AdminGrpcService {
+    state: AppState,
+}
+
+impl AdminGrpcService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    pub fn into_server(self) -> AdminServiceServer<Self> {
+        AdminServiceServer::new(self)
+    }
+
+    async fn authorize<T>(&self, request: &Request<T>) -> Result<AdminPrincipal, Status> {
+        let presented = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match check_admin_token(&self.state, presented).await {
+            AdminAuth::Disabled => Err(Status::unimplemented("Remote administration is off")),
+            AdminAuth::Rejected => {
+                warn!("[Admin] Rejected unauthenticated gRPC request");
+                Err(Status::unauthenticated("Invalid admin token"))
+            }
+            AdminAuth::Authorized(principal) => Ok(principal),
+        }
+    }
+
+    /// Check that the principal may manage a Space and a client in it, and
+    /// return the Space ID
+    async fn authorize_grant(
+        &self,
+        principal: &AdminPrincipal,
+        grant: &proto::GrantRef,
+    ) -> Result<Uuid, Status> {
+        let space_id = parse_space_id(&grant.space_id)?;
+        check_space_access(&self.state, principal, space_id).await?;
+        check_client_access(&self.state, principal, &grant.client_id).await?;
+        Ok(space_id)
+    }
+}
+
+fn internal(e: impl std::fmt::Display) -> Status {
+    warn!("[Admin] gRPC request failed: {}", e);
+    Status::internal(e.to_string())
+}
+
+fn parse_space_id(space_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(space_id).map_err(|_| Status::invalid_argument("Invalid space_id"))
+}
+
+impl From<AccessError> for Status {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::NotFound(what) => Status::not_found(what),
+            AccessError::NotOwned(what) => Status::permission_denied(what),
+            AccessError::Forbidden => Status::permission_denied("Requires the admin role"),
+            AccessError::Internal(e) => internal(e),
+        }
+    }
+}
+
+impl From<ServerChangeError> for Status {
+    fn from(e: ServerChangeError) -> Self {
+        match e {
+            ServerChangeError::NotInstalled => Status::not_found("Server not installed"),
+            ServerChangeError::Forbidden(e) => Status::permission_denied(e.to_string()),
+            ServerChangeError::Connect(e) => Status::unavailable(e.to_string()),
+            ServerChangeError::Internal(e) => internal(e),
+        }
+    }
+}
+
+fn proto_status(status: ConnectionStatus) -> proto::ConnectionStatus {
+    match status {
+        ConnectionStatus::Disconnected => proto::ConnectionStatus::Disconnected,
+        ConnectionStatus::Connecting => proto::ConnectionStatus::Connecting,
+        ConnectionStatus::Connected => proto::ConnectionStatus::Connected,
+        ConnectionStatus::Refreshing => proto::ConnectionStatus::Refreshing,
+        ConnectionStatus::AuthRequired => proto::ConnectionStatus::AuthRequired,
+        ConnectionStatus::Authenticating => proto::ConnectionStatus::Authenticating,
+        ConnectionStatus::Error => proto::ConnectionStatus::Error,
+    }
+}
+
+fn proto_server(server: AdminServerStatus) -> proto::ServerStatus {
+    proto::ServerStatus {
+        server_id: server.server_id,
+        name: server.name,
+        enabled: server.enabled,
+        status: proto_status(server.status).into(),
+        error: server.error,
+    }
+}
+
+fn proto_event(event: &DomainEvent) -> proto::Event {
+    proto::Event {
+        r#type: event.type_name().to_string(),
+        timestamp: event.timestamp().to_rfc3339(),
+        space_id: event.space_id().map(|id| id.to_string()),
+        server_id: event.server_id().map(str::to_string),
+        client_id: event.client_id().map(str::to_string),
+        feature_set_id: event.feature_set_id().map(str::to_string),
+        json: event.to_sanitized_json().to_string(),
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl AdminService for AdminGrpcService {
+    async fn get_status(
+        &self,
+        request: Request<proto::GetStatusRequest>,
+    ) -> Result<Response<proto::GetStatusResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let status = gateway_status(&self.state, &principal)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::GetStatusResponse {
+            version: status.version,
+            base_url: status.base_url,
+            connected_servers: status.connected_servers as u32,
+            spaces: status
+                .spaces
+                .into_iter()
+                .map(|space| proto::SpaceStatus {
+                    id: space.id,
+                    name: space.name,
+                    servers: space.servers.into_iter().map(proto_server).collect(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn list_servers(
+        &self,
+        request: Request<proto::ListServersRequest>,
+    ) -> Result<Response<proto::ListServersResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let space_id = parse_space_id(&request.get_ref().space_id)?;
+        check_space_access(&self.state, &principal, space_id).await?;
+        let servers = server_statuses(&self.state, space_id)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::ListServersResponse {
+            servers: servers.into_iter().map(proto_server).collect(),
+        }))
+    }
+
+    async fn enable_server(
+        &self,
+        request: Request<proto::ServerRef>,
+    ) -> Result<Response<proto::EnableServerResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let server = request.into_inner();
+        let space_id = parse_space_id(&server.space_id)?;
+        check_space_access(&self.state, &principal, space_id).await?;
+        let status = match enable_server(&self.state, &server.space_id, &server.server_id).await? {
+            ConnectOutcome::NeedsOAuth => proto::ConnectionStatus::AuthRequired,
+            ConnectOutcome::Connected | ConnectOutcome::AlreadyConnected => {
+                proto::ConnectionStatus::Connected
+            }
+        };
+        Ok(Response::new(proto::EnableServerResponse {
+            status: status.into(),
+        }))
+    }
+
+    async fn disable_server(
+        &self,
+        request: Request<proto::ServerRef>,
+    ) -> Result<Response<proto::DisableServerResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let server = request.into_inner();
+        let space_id = parse_space_id(&server.space_id)?;
+        check_space_access(&self.state, &principal, space_id).await?;
+        disable_server(&self.state, space_id, &server.server_id).await?;
+        Ok(Response::new(proto::DisableServerResponse {}))
+    }
+
+    async fn list_clients(
+        &self,
+        request: Request<proto::ListClientsRequest>,
+    ) -> Result<Response<proto::ListClientsResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let clients = visible_clients(&self.state, &principal)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::ListClientsResponse {
+            clients: clients
+                .into_iter()
+                .map(|c| proto::Client {
+                    client_name: c.client_alias.unwrap_or(c.client_name),
+                    client_id: c.client_id,
+                    approved: c.approved,
+                    last_seen: c.last_seen,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn list_grants(
+        &self,
+        request: Request<proto::ListGrantsRequest>,
+    ) -> Result<Response<proto::ListGrantsResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let request = request.into_inner();
+        let space_id = parse_space_id(&request.space_id)?;
+        check_space_access(&self.state, &principal, space_id).await?;
+        check_client_access(&self.state, &principal, &request.client_id).await?;
+        let feature_set_ids = self
+            .state
+            .services
+            .grant_service
+            .get_grants_for_space(&request.client_id, &request.space_id)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::ListGrantsResponse { feature_set_ids }))
+    }
+
+    async fn grant(
+        &self,
+        request: Request<proto::GrantRef>,
+    ) -> Result<Response<proto::GrantResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let grant = request.into_inner();
+        let space_id = self.authorize_grant(&principal, &grant).await?;
+        check_feature_set_in_space(&self.state, space_id, &grant.feature_set_id).await?;
+        self.state
+            .services
+            .grant_service
+            .grant_feature_set(&grant.client_id, &grant.space_id, &grant.feature_set_id)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::GrantResponse {}))
+    }
+
+    async fn revoke(
+        &self,
+        request: Request<proto::GrantRef>,
+    ) -> Result<Response<proto::RevokeResponse>, Status> {
+        let principal = self.authorize(&request).await?;
+        let grant = request.into_inner();
+        self.authorize_grant(&principal, &grant).await?;
+        self.state
+            .services
+            .grant_service
+            .revoke_feature_set(&grant.client_id, &grant.space_id, &grant.feature_set_id)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(proto::RevokeResponse {}))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let principal = self.authorize(&request).await?;
+        let filter = EventFilter {
+            space_id: match request.get_ref().space_id.as_deref() {
+                Some(space_id) => Some(parse_space_id(space_id)?),
+                None => None,
+            },
+            types: request.get_ref().types.clone(),
+        };
+        let visible = event_scope(&self.state, &principal, filter.space_id).await?;
+        let mut events = self
+            .state
+            .gateway_state
+            .read()
+            .await
+            .subscribe_domain_events();
+        info!("[Admin] Streaming events over gRPC");
+
+        let stream = async_stream::stream! {
+            loop {
+                match events.recv().await {
+                    Ok(event) if filter.matches(&event) && visible.allows(&event) => {
+                        yield Ok(proto_event(&event))
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("[Admin] gRPC event stream fell behind, {} event(s) dropped", missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Serve the control plane on every listener until `stop` is cancelled
+pub(crate) async fn serve_grpc(
+    listeners: Vec<TcpListener>,
+    service: AdminGrpcService,
+    stop: CancellationToken,
+) -> anyhow::Result<()> {
+    try_join_all(listeners.into_iter().map(|listener| {
+        let stop = stop.clone();
+        tonic::transport::Server::builder()
+            .add_service(service.clone().into_server())
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                stop.cancelled().await
+            })
+    }))
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_carry_their_ids_and_json() {
+        let space_id = Uuid::new_v4();
+        let event = DomainEvent::ClientGrantChanged {
+            client_id: "client-1".to_string(),
+            space_id,
+        };
+        let proto = proto_event(&event);
+        assert_eq!(proto.r#type, event.type_name());
+        assert_eq!(proto.space_id, Some(space_id.to_string()));
+        assert_eq!(proto.client_id.as_deref(), Some("client-1"));
+        assert_eq!(proto.server_id, None);
+
+        let json: serde_json::Value = serde_json::from_str(&proto.json).unwrap();
+        assert_eq!(json["type"], event.type_name());
+    }
+
+    #[test]
+    fn every_connection_status_has_a_proto_value() {
+        for status in [
+            ConnectionStatus::Disconnected,
+            ConnectionStatus::Connecting,
+            ConnectionStatus::Connected,
+            ConnectionStatus::Refreshing,
+            ConnectionStatus::AuthRequired,
+            ConnectionStatus::Authenticating,
+            ConnectionStatus::Error,
+        ] {
+            assert_ne!(
+                proto_status(status),
+                proto::ConnectionStatus::Unspecified,
+                "{:?}",
+                status
+            );
+        }
+    }
+}
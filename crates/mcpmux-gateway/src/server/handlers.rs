@@ -194,12 +194,16 @@ pub async fn oauth_metadata(
     // otherwise MCP clients that probe discovery start an OAuth flow even
     // though `/mcp` accepts them without a token. 404 makes them connect
     // tokenlessly.
-    let (public_base_url, network_bind) = {
+    let (public_base_url, network_bind, local_base_url) = {
         let state = app_state.gateway_state.read().await;
         if state.auth_disabled() {
             return Err(StatusCode::NOT_FOUND);
         }
-        (state.public_base_url.clone(), state.network_bind)
+        (
+            state.public_base_url.clone(),
+            state.network_bind,
+            state.base_url.clone(),
+        )
     };
     info!("[Gateway] OAuth metadata request - serving authorization server metadata");
     let host = headers
@@ -209,7 +213,7 @@ pub async fn oauth_metadata(
         public_base_url.as_deref(),
         network_bind,
         host,
-        &app_state.base_url,
+        &local_base_url,
     );
     Ok(Json(OAuthServerMetadata {
         issuer: base.to_string(),
@@ -247,12 +251,16 @@ pub async fn resource_metadata(
 ) -> Result<Json<ProtectedResourceMetadata>, StatusCode> {
     // See `oauth_metadata`: stay silent about auth when it's disabled so clients
     // don't kick off OAuth against a gateway that accepts them tokenlessly.
-    let (public_base_url, network_bind) = {
+    let (public_base_url, network_bind, local_base_url) = {
         let state = app_state.gateway_state.read().await;
         if state.auth_disabled() {
             return Err(StatusCode::NOT_FOUND);
         }
-        (state.public_base_url.clone(), state.network_bind)
+        (
+            state.public_base_url.clone(),
+            state.network_bind,
+            state.base_url.clone(),
+        )
     };
     info!("[Gateway] Protected resource metadata request");
     let host = headers
@@ -262,7 +270,7 @@ pub async fn resource_metadata(
         public_base_url.as_deref(),
         network_bind,
         host,
-        &app_state.base_url,
+        &local_base_url,
     );
     Ok(Json(ProtectedResourceMetadata {
         resource: format!("{}/mcp", base),
@@ -0,0 +1,224 @@
+//! Request size and connection limits
+//!
+//! Every client of a local gateway shares `127.0.0.1`, and a stuck or
+//! hostile one could otherwise open connections without end, trickle
+//! headers in byte by byte to hold them open, or post a body too large to
+//! buffer. [`RequestLimits`] caps all three; the reloader can change them
+//! while the gateway runs.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header::CONTENT_LENGTH, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body_util::{LengthLimitError, Limited};
+use serde::{Deserialize, Serialize};
+
+/// Limits on what a client may send
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLimits {
+    /// Largest request body accepted, in bytes
+    pub max_body_bytes: usize,
+    /// How long a client gets to send a request's headers
+    pub header_read_timeout_secs: u64,
+    /// Open connections allowed from one IP address. Local clients all
+    /// connect from loopback, so this is shared between them.
+    pub max_connections_per_ip: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 8 * 1024 * 1024,
+            header_read_timeout_secs: 10,
+            max_connections_per_ip: 256,
+        }
+    }
+}
+
+impl RequestLimits {
+    pub fn header_read_timeout(&self) -> Duration {
+        Duration::from_secs(self.header_read_timeout_secs)
+    }
+
+    /// Every limit must allow something
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_body_bytes < 1024 {
+            anyhow::bail!("Maximum request body size must be at least 1 KiB");
+        }
+        if self.header_read_timeout_secs == 0 {
+            anyhow::bail!("Header read timeout must be at least a second");
+        }
+        if self.max_connections_per_ip == 0 {
+            anyhow::bail!("At least one connection per IP address must be allowed");
+        }
+        Ok(())
+    }
+}
+
+/// [`RequestLimits`] as the running gateway enforces them, with the open
+/// connections per IP address
+#[derive(Debug, Default)]
+pub(crate) struct Limiter {
+    max_body_bytes: AtomicUsize,
+    header_read_timeout_secs: AtomicU64,
+    max_connections_per_ip: AtomicUsize,
+    open: Mutex<HashMap<IpAddr, usize>>,
+}
+
+impl Limiter {
+    pub(crate) fn new(limits: &RequestLimits) -> Arc<Self> {
+        let limiter = Arc::new(Self::default());
+        limiter.set(limits);
+        limiter
+    }
+
+    /// Apply new limits. Open connections past a lowered per-IP limit stay
+    /// open; new ones are refused until the count drops.
+    pub(crate) fn set(&self, limits: &RequestLimits) {
+        self.max_body_bytes
+            .store(limits.max_body_bytes, Ordering::Relaxed);
+        self.header_read_timeout_secs
+            .store(limits.header_read_timeout_secs, Ordering::Relaxed);
+        self.max_connections_per_ip
+            .store(limits.max_connections_per_ip, Ordering::Relaxed);
+    }
+
+    pub(crate) fn header_read_timeout(&self) -> Duration {
+        Duration::from_secs(self.header_read_timeout_secs.load(Ordering::Relaxed))
+    }
+
+    /// Count a new connection from `ip`, or `None` when `ip` already has
+    /// as many open as allowed
+    pub(crate) fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Option<ConnectionPermit> {
+        let max = self.max_connections_per_ip.load(Ordering::Relaxed);
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        let count = open.entry(ip).or_insert(0);
+        if *count >= max {
+            return None;
+        }
+        *count += 1;
+        Some(ConnectionPermit {
+            limiter: self.clone(),
+            ip,
+        })
+    }
+}
+
+/// An open connection, counted against its IP address until dropped
+pub(crate) struct ConnectionPermit {
+    limiter: Arc<Limiter>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let mut open = self.limiter.open.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = open.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                open.remove(&self.ip);
+            }
+        }
+    }
+}
+
+fn payload_too_large(max: usize) -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!("Request body is larger than {} bytes", max),
+    )
+        .into_response()
+}
+
+/// Refuse bodies over the limit: up front when `Content-Length` says so,
+/// otherwise once reading passes the limit.
+pub(crate) async fn limit_body_size(
+    State(limiter): State<Arc<Limiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let max = limiter.max_body_bytes.load(Ordering::Relaxed);
+    let declared = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|len| len > max as u64) {
+        return payload_too_large(max);
+    }
+    next.run(request.map(|body| Body::new(Limited::new(body, max))))
+        .await
+}
+
+/// Whether reading a body failed because it passed the size limit
+pub(crate) fn is_body_too_large(err: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(err) = source {
+        if err.is::<LengthLimitError>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connections_are_limited_per_ip_until_dropped() {
+        let limiter = Limiter::new(&RequestLimits {
+            max_connections_per_ip: 2,
+            ..Default::default()
+        });
+        let local: IpAddr = "127.0.0.1".parse().unwrap();
+        let other: IpAddr = "192.168.1.20".parse().unwrap();
+
+        let first = limiter.try_acquire(local).unwrap();
+        let _second = limiter.try_acquire(local).unwrap();
+        assert!(limiter.try_acquire(local).is_none());
+        assert!(limiter.try_acquire(other).is_some());
+
+        drop(first);
+        assert!(limiter.try_acquire(local).is_some());
+    }
+
+    #[test]
+    fn validate_rejects_limits_that_allow_nothing() {
+        assert!(RequestLimits::default().validate().is_ok());
+        for limits in [
+            RequestLimits {
+                max_body_bytes: 10,
+                ..Default::default()
+            },
+            RequestLimits {
+                header_read_timeout_secs: 0,
+                ..Default::default()
+            },
+            RequestLimits {
+                max_connections_per_ip: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(limits.validate().is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_fail_to_read() {
+        let body = Body::new(Limited::new(Body::from(vec![0u8; 2048]), 1024));
+        let err = axum::body::to_bytes(body, usize::MAX).await.unwrap_err();
+        assert!(is_body_too_large(&err));
+    }
+}
@@ -0,0 +1,196 @@
+//! Listening on several addresses at once
+//!
+//! Some systems resolve `localhost` to `::1` only, others to `127.0.0.1`
+//! only, so loopback servers listen on both. The IPv4 listener is required;
+//! the IPv6 one is skipped when the OS has IPv6 turned off.
+//!
+//! Connections are served with hyper directly rather than `axum::serve`,
+//! which has no header read timeout or per-IP connection limit.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{body::Body, extract::ConnectInfo, Router};
+use futures::future::try_join_all;
+use hyper::{body::Incoming, server::conn::http1};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use hyper_util::service::TowerToHyperService;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+use tracing::{debug, info, warn};
+
+use super::limits::Limiter;
+
+/// Host that binds both the IPv4 and the IPv6 loopback address
+pub const DUAL_STACK_LOOPBACK: &str = "localhost";
+
+/// The addresses to bind for `host`: both loopback addresses for
+/// [`DUAL_STACK_LOOPBACK`], otherwise the IP address itself (IPv6 in
+/// brackets or not). `None` for anything else.
+pub(crate) fn bind_addrs(host: &str, port: u16) -> Option<Vec<SocketAddr>> {
+    let host = host.trim();
+    if host.is_empty() || host.eq_ignore_ascii_case(DUAL_STACK_LOOPBACK) {
+        return Some(vec![
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port),
+            SocketAddr::new(Ipv6Addr::LOCALHOST.into(), port),
+        ]);
+    }
+    let ip: IpAddr = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host)
+        .parse()
+        .ok()?;
+    Some(vec![SocketAddr::new(ip, port)])
+}
+
+/// Bind every address in `addrs`. The first must bind. Later ones are
+/// skipped with a warning when their address family isn't available, but a
+/// port already taken there is an error: clients resolving `localhost` to
+/// that address would reach the other process instead.
+pub(crate) async fn bind_all(addrs: &[SocketAddr]) -> io::Result<Vec<TcpListener>> {
+    let mut listeners = Vec::with_capacity(addrs.len());
+    for (i, addr) in addrs.iter().enumerate() {
+        match TcpListener::bind(addr).await {
+            Ok(listener) => {
+                info!("[Listeners] Listening on {}", addr);
+                listeners.push(listener);
+            }
+            Err(e) if i > 0 && e.kind() != io::ErrorKind::AddrInUse => {
+                warn!("[Listeners] Not listening on {}: {}", addr, e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(listeners)
+}
+
+/// Serve `router` on every listener until `stop` is cancelled, then wait
+/// for open connections to finish. Ends early with the first listener that
+/// fails.
+pub(crate) async fn serve_all(
+    listeners: Vec<TcpListener>,
+    router: Router,
+    limiter: Arc<Limiter>,
+    stop: CancellationToken,
+) -> io::Result<()> {
+    try_join_all(
+        listeners
+            .into_iter()
+            .map(|listener| serve(listener, router.clone(), limiter.clone(), stop.clone())),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Accept connections on `listener`, each counted against its peer's
+/// per-IP limit and given [`Limiter::header_read_timeout`] to send headers
+async fn serve(
+    listener: TcpListener,
+    router: Router,
+    limiter: Arc<Limiter>,
+    stop: CancellationToken,
+) -> io::Result<()> {
+    let mut connections = JoinSet::new();
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = stop.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) if is_connection_error(&e) => continue,
+                Err(e) => {
+                    // Out of file descriptors and the like; back off
+                    warn!("[Listeners] Accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+        };
+        let Some(permit) = limiter.try_acquire(peer.ip()) else {
+            warn!(
+                "[Listeners] Refused a connection from {}: too many open connections",
+                peer.ip()
+            );
+            continue;
+        };
+
+        let service = TowerToHyperService::new(router.clone().map_request(
+            move |mut request: hyper::Request<Incoming>| {
+                request.extensions_mut().insert(ConnectInfo(peer));
+                request.map(Body::new)
+            },
+        ));
+        let mut builder = http1::Builder::new();
+        builder
+            .timer(TokioTimer::new())
+            .header_read_timeout(limiter.header_read_timeout());
+        let stop = stop.clone();
+        connections.spawn(async move {
+            let _permit = permit;
+            let connection = builder
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades();
+            let mut connection = std::pin::pin!(connection);
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                _ = stop.cancelled() => {
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+            if let Err(e) = result {
+                debug!("[Listeners] Connection from {} ended: {}", peer, e);
+            }
+        });
+    }
+
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+    Ok(())
+}
+
+/// Errors about one connection, rather than the listener
+fn is_connection_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localhost_binds_both_loopback_addresses() {
+        let addrs = bind_addrs("localhost", 45818).unwrap();
+        assert_eq!(
+            addrs,
+            [
+                "127.0.0.1:45818".parse::<SocketAddr>().unwrap(),
+                "[::1]:45818".parse().unwrap()
+            ]
+        );
+        assert_eq!(bind_addrs("", 45818).unwrap(), addrs);
+    }
+
+    #[test]
+    fn ip_hosts_bind_only_themselves() {
+        assert_eq!(
+            bind_addrs("0.0.0.0", 1).unwrap(),
+            ["0.0.0.0:1".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(
+            bind_addrs("[::1]", 1).unwrap(),
+            ["[::1]:1".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(bind_addrs("::", 1).unwrap().len(), 1);
+        assert!(bind_addrs("example.com", 1).is_none());
+    }
+}
@@ -8,6 +8,7 @@ use http_body_util::BodyExt;
 use tracing::{debug, warn, Instrument};
 
 use crate::logging::{RequestSpan, TraceContext};
+use crate::server::is_body_too_large;
 
 /// Maximum body size to log (1MB)
 const MAX_BODY_LOG_SIZE: usize = 1024 * 1024;
@@ -266,6 +267,7 @@ pub async fn http_logging_middleware(request: Request, next: Next) -> Result<Res
             let (parts, body) = request.into_parts();
             let body_bytes = match body.collect().await {
                 Ok(collected) => collected.to_bytes(),
+                Err(e) if is_body_too_large(&e) => return Err(StatusCode::PAYLOAD_TOO_LARGE),
                 Err(e) => {
                     warn!(trace_id = %ctx.trace_id, "Failed to read request body: {}", e);
                     return Err(StatusCode::INTERNAL_SERVER_ERROR);
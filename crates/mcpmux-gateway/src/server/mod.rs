@@ -4,13 +4,22 @@
 //! Self-contained with dependency injection for clean architecture.
 //!
 
+pub mod admin;
+pub mod cors;
 mod dependencies;
+mod drain;
+pub mod grpc;
 mod handlers;
+mod limits;
+mod listeners;
 pub mod logging_middleware;
+pub mod pairing;
+mod pause;
 pub mod rate_limit;
 mod service_container;
 mod startup;
 mod state;
+pub mod well_known;
 
 // Exposed for integration tests that mount these routes against a real
 // ServiceContainer — e.g. asserting the OAuth-discovery endpoints 404 when
@@ -23,10 +32,25 @@ pub use handlers::{
     resource_metadata, AppState,
 };
 
+pub use admin::{
+    create_user, generate_admin_token, rotate_user_token, set_admin_token, AdminPrincipal,
+    ADMIN_TOKEN_PREFIX,
+};
+pub use cors::{CorsConfig, CorsPolicy, CORS_WILDCARD};
 pub use dependencies::{DependenciesBuilder, GatewayDependencies};
+pub use drain::{DrainTracker, InFlightGuard};
+pub use grpc::AdminGrpcService;
 pub use handlers::PendingAuthorization;
+pub use limits::RequestLimits;
+pub(crate) use limits::{is_body_too_large, Limiter};
+pub use listeners::DUAL_STACK_LOOPBACK;
+pub(crate) use listeners::{bind_all, serve_all};
+pub use pairing::{generate_pairing_code, pairing_url, PendingPairing, PAIRING_CODE_TTL};
+pub use pause::{TrafficPause, PAUSED_MESSAGE};
 pub use service_container::ServiceContainer;
-pub use startup::{AutoConnectResult, StartupOrchestrator, TokenRefreshResult};
+pub use startup::{
+    AutoConnectResult, StartupOrchestrator, TokenRefreshResult, DEFAULT_AUTO_CONNECT_CONCURRENCY,
+};
 pub use state::{ClientSession, GatewayState};
 
 use axum::{
@@ -38,8 +62,9 @@ use axum::{
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tower_http::cors::{Any, CorsLayer};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tower_http::trace::TraceLayer;
 use tracing::{debug, info, warn};
 
@@ -53,7 +78,8 @@ use tokio_util::sync::CancellationToken;
 /// Gateway server configuration
 #[derive(Debug, Clone)]
 pub struct GatewayConfig {
-    /// Host to bind to
+    /// Host to bind to: an IP address, or [`DUAL_STACK_LOOPBACK`] to listen
+    /// on both `127.0.0.1` and `::1`
     pub host: String,
     /// Port to listen on
     pub port: u16,
@@ -64,27 +90,62 @@ pub struct GatewayConfig {
     /// reachable origin fronting the gateway, for example a Cloudflare Tunnel
     /// URL such as `https://mcp.example.com`.
     pub public_base_url: Option<String>,
+    /// Host in the base URL advertised without a `public_base_url`, `localhost`
+    /// when unset. Set it to `127.0.0.1` or `::1` where `localhost` resolves to
+    /// an address clients can't use.
+    pub base_url_host: Option<String>,
     /// Enable CORS for browser access
     pub enable_cors: bool,
+    /// Origins, methods and headers CORS allows, by path prefix
+    pub cors: CorsConfig,
+    /// Request limits by path prefix and client
+    pub rate_limits: Vec<rate_limit::RateLimitPolicy>,
+    /// Body size, header timeout and per-IP connection limits
+    pub request_limits: RequestLimits,
+    /// Enabled servers connected at the same time on startup
+    pub auto_connect_concurrency: usize,
+    /// How long shutdown waits for in-flight tool calls before closing
+    /// sessions anyway
+    pub drain_timeout: Duration,
+    /// Port for the gRPC control plane on the same host; off when unset.
+    /// See [`grpc`].
+    pub grpc_port: Option<u16>,
 }
 
+/// How long shutdown waits for backend servers to exit before killing them
+pub const BACKEND_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long shutdown waits for client connections to close after their
+/// sessions are terminated
+const CONNECTION_CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
+
 impl Default for GatewayConfig {
     fn default() -> Self {
         Self {
-            host: "127.0.0.1".to_string(),
+            host: DUAL_STACK_LOOPBACK.to_string(),
             port: mcpmux_core::branding::DEFAULT_GATEWAY_PORT,
             public_base_url: None,
+            base_url_host: None,
             enable_cors: true,
+            cors: CorsConfig::default(),
+            rate_limits: rate_limit::default_rate_limit_rules(),
+            request_limits: RequestLimits::default(),
+            auto_connect_concurrency: DEFAULT_AUTO_CONNECT_CONCURRENCY,
+            drain_timeout: Duration::from_secs(10),
+            grpc_port: None,
         }
     }
 }
 
 impl GatewayConfig {
-    /// Get the socket address
+    /// Get the socket address (the first of [`Self::bind_addrs`])
     pub fn addr(&self) -> SocketAddr {
-        format!("{}:{}", self.host, self.port)
-            .parse()
-            .expect("Invalid address")
+        self.bind_addrs()[0]
+    }
+
+    /// Get every socket address the gateway listens on
+    pub fn bind_addrs(&self) -> Vec<SocketAddr> {
+        listeners::bind_addrs(&self.host, self.port).expect("Invalid address")
     }
 
     /// Get the base URL this gateway advertises to MCP/OAuth clients.
@@ -94,15 +155,15 @@ impl GatewayConfig {
             .map(str::trim)
             .filter(|url| !url.is_empty())
             .map(|url| url.trim_end_matches('/').to_string())
-            .unwrap_or_else(|| format!("http://localhost:{}", self.port))
+            .unwrap_or_else(|| local_base_url(self.base_url_host.as_deref(), self.port))
     }
 
     /// True when the gateway binds to a non-loopback address (e.g. `0.0.0.0`
     /// or a specific LAN interface) — i.e. it is intentionally exposed on the
     /// network rather than being local-only.
     pub fn is_network_bind(&self) -> bool {
-        let host = self.host.trim();
-        !(host.is_empty() || host == "127.0.0.1" || host == "::1" || host == "localhost")
+        !listeners::bind_addrs(&self.host, self.port)
+            .is_some_and(|addrs| addrs.iter().all(|addr| addr.ip().is_loopback()))
     }
 
     /// Host values accepted by rmcp's DNS rebinding protection.
@@ -127,10 +188,14 @@ impl GatewayConfig {
             "::1".to_string(),
         ];
 
-        let bind_host = self.host.trim();
-        if !bind_host.is_empty() {
-            hosts.push(bind_host.to_string());
-            hosts.push(format!("{}:{}", bind_host, self.port));
+        for host in [Some(self.host.as_str()), self.base_url_host.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+        {
+            hosts.push(host.to_string());
+            hosts.push(format!("{}:{}", host, self.port));
         }
 
         if let Some(public_base_url) = self.public_base_url.as_deref() {
@@ -162,6 +227,19 @@ impl GatewayConfig {
     }
 }
 
+/// `http://<host>:<port>` for a local gateway, `host` defaulting to
+/// `localhost`. IPv6 addresses are put in brackets.
+pub fn local_base_url(host: Option<&str>, port: u16) -> String {
+    let host = host.map(str::trim).filter(|host| !host.is_empty());
+    match host {
+        Some(host) if host.contains(':') && !host.starts_with('[') => {
+            format!("http://[{}]:{}", host, port)
+        }
+        Some(host) => format!("http://{}:{}", host, port),
+        None => format!("http://localhost:{}", port),
+    }
+}
+
 /// The desktop-only client-management routes (list / update / delete clients).
 /// `/oauth/clients/{id}/features` is intentionally excluded — it is the public
 /// client-facing endpoint.
@@ -202,6 +280,114 @@ async fn restrict_management_to_loopback(
     next.run(request).await
 }
 
+/// Applies [`GatewayConfig`] changes to a running gateway.
+///
+/// Port and host changes bind the new listener first (so a busy port fails
+/// without disturbing the running one) and hand it to the serve loop; the
+/// old listener stops accepting and drains its open connections. CORS
+/// policies and rate limits switch over on the next request.
+///
+/// Changes that alter the DNS-rebinding allowlist baked into the MCP
+/// service — the public URL, or moving between loopback and network binds —
+/// still need a restart.
+#[derive(Clone)]
+pub struct GatewayReloader {
+    config: Arc<Mutex<GatewayConfig>>,
+    state: Arc<RwLock<GatewayState>>,
+    rate_limiter: rate_limit::RateLimiter,
+    cors: Arc<cors::DynamicCors>,
+    limiter: Arc<Limiter>,
+    listener_tx: mpsc::UnboundedSender<Vec<TcpListener>>,
+}
+
+impl GatewayReloader {
+    /// Configuration currently in effect
+    pub async fn config(&self) -> GatewayConfig {
+        self.config.lock().await.clone()
+    }
+
+    /// Requests refused by rate limiting since the gateway started
+    pub fn rate_limit_rejections(&self) -> Vec<rate_limit::RateLimitRejections> {
+        self.rate_limiter.rejections()
+    }
+
+    /// Apply `new` to the running gateway. On error nothing is changed.
+    pub async fn reload_config(&self, new: GatewayConfig) -> anyhow::Result<()> {
+        let mut current = self.config.lock().await;
+
+        let new_addrs = listeners::bind_addrs(&new.host, new.port)
+            .ok_or_else(|| anyhow::anyhow!("Invalid gateway host: {}", new.host))?;
+        if new.public_base_url != current.public_base_url
+            || new.is_network_bind() != current.is_network_bind()
+        {
+            anyhow::bail!("Changing the public URL or network access requires a gateway restart");
+        }
+        // DNS rebinding protection only knows the hosts allowed at startup
+        if let Some(host) = new.base_url_host.as_deref() {
+            if !current
+                .allowed_hosts()
+                .iter()
+                .any(|allowed| allowed == host)
+            {
+                anyhow::bail!(
+                    "Using {} as the local URL host requires a gateway restart",
+                    host
+                );
+            }
+        }
+
+        new.cors.validate()?;
+        for policy in &new.rate_limits {
+            policy.validate()?;
+        }
+        new.request_limits.validate()?;
+        if new.grpc_port != current.grpc_port {
+            anyhow::bail!("Changing the gRPC port requires a gateway restart");
+        }
+
+        if new_addrs != current.bind_addrs() {
+            let listeners = bind_all(&new_addrs).await?;
+            self.listener_tx
+                .send(listeners)
+                .map_err(|_| anyhow::anyhow!("Gateway is not running"))?;
+            info!(
+                "[Gateway] Rebound from {:?} to {:?}",
+                current.bind_addrs(),
+                new_addrs
+            );
+        }
+        if new.base_url() != current.base_url() {
+            self.state.write().await.set_base_url(new.base_url());
+        }
+        if new.enable_cors != current.enable_cors {
+            self.cors.set_enabled(new.enable_cors);
+            info!(
+                "[Gateway] CORS {}",
+                if new.enable_cors {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+        if new.cors != current.cors {
+            self.cors.set_config(&new.cors);
+            info!("[Gateway] CORS policies updated");
+        }
+        if new.rate_limits != current.rate_limits {
+            self.rate_limiter.set_rules(new.rate_limits.clone());
+            info!("[Gateway] Rate limits updated");
+        }
+        if new.request_limits != current.request_limits {
+            self.limiter.set(&new.request_limits);
+            info!("[Gateway] Request limits updated");
+        }
+
+        *current = new;
+        Ok(())
+    }
+}
+
 /// MCP Gateway Server
 ///
 /// Self-contained server that manages its own services and lifecycle.
@@ -211,6 +397,11 @@ pub struct GatewayServer {
     config: GatewayConfig,
     state: Arc<RwLock<GatewayState>>,
     services: ServiceContainer,
+    reloader: GatewayReloader,
+    /// Listeners bound by [`GatewayReloader`]; taken by the serve loop
+    listener_rx: Option<mpsc::UnboundedReceiver<Vec<TcpListener>>>,
+    /// Cancelled on shutdown to terminate every MCP session and SSE stream
+    sessions_cancel: CancellationToken,
 }
 
 impl GatewayServer {
@@ -238,9 +429,23 @@ impl GatewayServer {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
                 let mut state_guard = state.write().await;
-                state_guard.set_database(dependencies.database.clone());
+                state_guard.set_inbound_client_repository(dependencies.inbound_client_repo.clone());
                 state_guard
                     .set_client_metadata_service(dependencies.client_metadata_service.clone());
+
+                // Subscribe the journal before any service can emit
+                if let Some(repo) = dependencies.event_journal_repo.clone() {
+                    match mcpmux_core::EventJournal::start(
+                        repo,
+                        domain_event_tx.subscribe(),
+                        mcpmux_core::DEFAULT_JOURNAL_RETENTION,
+                    )
+                    .await
+                    {
+                        Ok(journal) => state_guard.set_event_journal(journal),
+                        Err(e) => warn!("[Gateway] Event journal unavailable: {}", e),
+                    }
+                }
             });
         });
 
@@ -249,13 +454,37 @@ impl GatewayServer {
 
         info!("[Gateway] Services initialized successfully");
 
+        let (listener_tx, listener_rx) = mpsc::unbounded_channel();
+        let reloader = GatewayReloader {
+            config: Arc::new(Mutex::new(config.clone())),
+            state: state.clone(),
+            rate_limiter: rate_limit::RateLimiter::new(config.rate_limits.clone()),
+            cors: Arc::new(cors::DynamicCors::new(config.enable_cors, &config.cors)),
+            limiter: Limiter::new(&config.request_limits),
+            listener_tx,
+        };
+
         Self {
             config,
             state,
             services,
+            reloader,
+            listener_rx: Some(listener_rx),
+            sessions_cancel: CancellationToken::new(),
         }
     }
 
+    /// Handle for applying configuration changes while running
+    pub fn reloader(&self) -> GatewayReloader {
+        self.reloader.clone()
+    }
+
+    /// Apply configuration changes without restarting.
+    /// See [`GatewayReloader::reload_config`].
+    pub async fn reload_config(&self, config: GatewayConfig) -> anyhow::Result<()> {
+        self.reloader.reload_config(config).await
+    }
+
     /// Get a reference to the gateway state
     pub fn state(&self) -> Arc<RwLock<GatewayState>> {
         self.state.clone()
@@ -276,6 +505,11 @@ impl GatewayServer {
         self.services.pool_services.feature_service.clone()
     }
 
+    /// Get the routing service
+    pub fn routing_service(&self) -> Arc<crate::pool::RoutingService> {
+        self.services.pool_services.routing_service.clone()
+    }
+
     /// Get the connection service
     pub fn connection_service(&self) -> Arc<crate::pool::ConnectionService> {
         self.services.pool_services.connection_service.clone()
@@ -304,6 +538,11 @@ impl GatewayServer {
         self.services.approval_broker.clone()
     }
 
+    /// Switch for pausing every tool call while keeping sessions up
+    pub fn traffic_pause(&self) -> Arc<TrafficPause> {
+        self.services.traffic_pause.clone()
+    }
+
     /// Session-roots registry (MCP roots reported by connected peers).
     ///
     /// The desktop Workspaces tab reads this to surface every folder
@@ -314,6 +553,22 @@ impl GatewayServer {
         self.services.session_roots.clone()
     }
 
+    /// Live activity of connected MCP sessions, for the Active Connections
+    /// view.
+    pub fn session_activity(&self) -> Arc<crate::services::SessionActivityRegistry> {
+        self.services.session_activity.clone()
+    }
+
+    /// Package version pinning and update checks for runner-launched servers
+    pub fn package_updates(&self) -> Arc<crate::services::PackageUpdateService> {
+        self.services.package_updates.clone()
+    }
+
+    /// In-process access to this gateway, sharing its servers and sessions
+    pub fn embedded(&self) -> crate::EmbeddedGateway {
+        crate::EmbeddedGateway::from_services(Arc::new(self.services.clone()))
+    }
+
     /// Get the OAuth manager
     pub fn oauth_manager(&self) -> Arc<crate::pool::OutboundOAuthManager> {
         self.services.pool_services.oauth_manager.clone()
@@ -327,7 +582,7 @@ impl GatewayServer {
         match self
             .services
             .startup_orchestrator
-            .auto_connect_enabled_servers()
+            .auto_connect_enabled_servers(self.config.auto_connect_concurrency)
             .await
         {
             Ok(result) => {
@@ -344,16 +599,21 @@ impl GatewayServer {
         }
     }
 
+    /// State shared by the HTTP handlers and the gRPC control plane
+    fn app_state(&self) -> AppState {
+        AppState {
+            gateway_state: self.state.clone(),
+            services: Arc::new(self.services.clone()),
+            base_url: self.config.base_url(),
+        }
+    }
+
     /// Build the Axum router
     fn build_router(&self) -> Router {
         let state = self.state.clone();
 
         // Create app state with services
-        let app_state = AppState {
-            gateway_state: state.clone(),
-            services: Arc::new(self.services.clone()),
-            base_url: self.config.base_url(),
-        };
+        let app_state = self.app_state();
 
         // Create MCP notifier (session-keyed fanout, consults the same
         // FeatureSet resolver the request handlers use).
@@ -367,8 +627,24 @@ impl GatewayServer {
             let gw_state = tokio::task::block_in_place(|| state.blocking_read());
             let event_rx = gw_state.subscribe_domain_events();
             notification_bridge.clone().start(event_rx);
+
+            // Pin package versions as servers connect; check for updates daily
+            self.services.package_updates.clone().start(
+                gw_state.subscribe_domain_events(),
+                crate::services::DEFAULT_UPDATE_CHECK_INTERVAL,
+            );
         }
 
+        // Ping connected backends so unhealthy-but-connected servers show up
+        self.services
+            .pool_services
+            .pool_service
+            .clone()
+            .start_health_checks(
+                crate::pool::HEALTH_CHECK_INTERVAL,
+                self.sessions_cancel.clone(),
+            );
+
         // Create OAuth event handler (updates oauth_connected flag on OAuth success)
         {
             let oauth_handler = Arc::new(crate::consumers::OAuthEventHandler::new(
@@ -405,7 +681,7 @@ impl GatewayServer {
         );
         http_cfg.sse_keep_alive = Some(std::time::Duration::from_secs(30));
         http_cfg.sse_retry = Some(std::time::Duration::from_secs(3));
-        http_cfg.cancellation_token = CancellationToken::new();
+        http_cfg.cancellation_token = self.sessions_cancel.clone();
         let mcp_service = StreamableHttpService::new(
             move || {
                 debug!("[Gateway] Creating handler instance for MCP session");
@@ -415,14 +691,18 @@ impl GatewayServer {
             http_cfg,
         );
 
-        // Wrap MCP service with OAuth middleware
-        let mcp_routes =
-            Router::new()
-                .nest_service("/mcp", mcp_service)
-                .layer(middleware::from_fn_with_state(
-                    Arc::new(self.services.clone()),
-                    mcp_oauth_middleware,
-                ));
+        // Wrap MCP service with OAuth middleware; while shutting down, new
+        // sessions are refused before authentication
+        let mcp_routes = Router::new()
+            .nest_service("/mcp", mcp_service)
+            .layer(middleware::from_fn_with_state(
+                Arc::new(self.services.clone()),
+                mcp_oauth_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                self.services.drain_tracker.clone(),
+                drain::reject_new_sessions_while_draining,
+            ));
 
         // Client features endpoint (needs services, public)
         // Supports both DCR (simple IDs) and CIMD (URL-encoded IDs)
@@ -434,6 +714,10 @@ impl GatewayServer {
             )
             .with_state(app_state.clone());
 
+        // Remote administration (admin token, not client tokens). Reachable
+        // off-machine on purpose, so not behind the loopback gate below.
+        let admin_routes = admin::router(app_state.clone());
+
         let mut router = Router::new()
             // Health check (public)
             .route("/health", get(handlers::health))
@@ -457,6 +741,15 @@ impl GatewayServer {
                 "/.well-known/oauth-protected-resource/mcp",
                 get(handlers::resource_metadata),
             )
+            // Discovery documents (public)
+            .route(
+                well_known::MCP_DISCOVERY_PATH,
+                get(well_known::mcp_discovery),
+            )
+            .route(
+                well_known::CLIENT_METADATA_PATH,
+                get(well_known::client_metadata),
+            )
             // Other OAuth endpoints still need GatewayState
             .route("/oauth/authorize", get(handlers::oauth_authorize))
             // Fallback for clients that don't fetch metadata (VS Code default behavior)
@@ -468,6 +761,8 @@ impl GatewayServer {
             // app's own WebView—not by external HTTP clients, scripts, or bots.
             // Client registration (DCR - public)
             .route("/oauth/register", post(handlers::oauth_register))
+            // Device pairing (one-time code -> API key)
+            .route(pairing::PAIRING_PATH, post(pairing::oauth_pair))
             // Client management (for desktop app)
             .route("/oauth/clients", get(handlers::oauth_list_clients))
             // Client CRUD - expects URL-encoded client_id for CIMD clients
@@ -490,14 +785,17 @@ impl GatewayServer {
             );
         }
 
-        // Rate limiter for OAuth endpoints (prevents abuse / consent flooding)
-        let rate_limiter = rate_limit::default_oauth_rate_limiter();
+        // Rate limiter for OAuth endpoints (prevents abuse / consent flooding).
+        // Shared with the reloader so limits can change while running.
+        let rate_limiter = self.reloader.rate_limiter.clone();
 
         let mut router = router
             // Protected MCP routes (using rmcp's StreamableHttpService)
             .merge(mcp_routes)
             // Client features (needs services)
             .merge(client_features_routes)
+            // Remote administration
+            .merge(admin_routes)
             // Global state for all routes
             .with_state(app_state.clone())
             .layer(TraceLayer::new_for_http())
@@ -505,22 +803,22 @@ impl GatewayServer {
             .layer(middleware::from_fn(
                 logging_middleware::http_logging_middleware,
             ))
-            // Rate limiting on OAuth endpoints
+            // Rate limiting (shared policies; per-client ones apply in the MCP auth middleware)
             .layer(axum::Extension(rate_limiter))
             .layer(middleware::from_fn(rate_limit::rate_limit_middleware))
             // Keep desktop-only client management off the LAN on a 0.0.0.0 bind.
-            .layer(middleware::from_fn(restrict_management_to_loopback));
-
-        // Add CORS if enabled
-        if self.config.enable_cors {
-            let cors = CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any);
-            router = router.layer(cors);
-        }
+            .layer(middleware::from_fn(restrict_management_to_loopback))
+            // Refuse oversized request bodies
+            .layer(middleware::from_fn_with_state(
+                self.reloader.limiter.clone(),
+                limits::limit_body_size,
+            ));
 
-        router
+        // CORS (toggled and reconfigured at runtime by the reloader)
+        router.layer(middleware::from_fn_with_state(
+            self.reloader.cors.clone(),
+            cors::dynamic_cors,
+        ))
     }
 
     /// Run the gateway server
@@ -536,17 +834,18 @@ impl GatewayServer {
     }
 
     /// Same as `run`, but accepts a shutdown future. When the future
-    /// resolves, axum stops accepting new connections, drains in-flight
-    /// requests, and closes the TCP listener. Rust `Drop` on the
+    /// resolves, the listener stops accepting connections and the gateway
+    /// drains: running tool calls get up to `drain_timeout` to finish, then
+    /// sessions are terminated and backend servers closed. Rust `Drop` on the
     /// `TcpListener` then releases the port on the OS — preventing the
     /// orphaned-socket condition that force-killed processes leave behind.
     pub async fn run_with_shutdown(
-        self,
+        mut self,
         shutdown: impl std::future::Future<Output = ()> + Send + 'static,
     ) -> anyhow::Result<()> {
-        let addr = self.config.addr();
+        let addrs = self.config.bind_addrs();
 
-        info!("[Gateway] Starting on {}", addr);
+        info!("[Gateway] Starting on {:?}", addrs);
         info!(
             "[Gateway] CORS: {}",
             if self.config.enable_cors {
@@ -569,6 +868,11 @@ impl GatewayServer {
         // MCPNotifier is started in build_router()
         info!("[Gateway] MCPNotifier started (listening to DomainEvents)");
 
+        let mut listener_rx = self
+            .listener_rx
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Gateway server already started"))?;
+
         // Auto-connect enabled servers in background (non-blocking for fast startup)
         // MCP clients will receive list_changed notifications when backends connect
         let self_arc = Arc::new(self);
@@ -621,24 +925,114 @@ impl GatewayServer {
 
         // Build router and start server immediately
         let router = self_arc.build_router();
-        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let mut listeners = bind_all(&addrs).await?;
+
+        let grpc_stop = CancellationToken::new();
+        if let Some(port) = self_arc.config.grpc_port {
+            let grpc_addrs = listeners::bind_addrs(&self_arc.config.host, port)
+                .ok_or_else(|| anyhow::anyhow!("Invalid gRPC address"))?;
+            let grpc_listeners = bind_all(&grpc_addrs).await?;
+            let service = AdminGrpcService::new(self_arc.app_state());
+            let stop = grpc_stop.clone();
+            info!("[Gateway] gRPC control plane on {:?}", grpc_addrs);
+            tokio::spawn(async move {
+                if let Err(e) = grpc::serve_grpc(grpc_listeners, service, stop).await {
+                    warn!("[Gateway] gRPC control plane stopped: {}", e);
+                }
+            });
+        }
+        let _grpc_stop = grpc_stop.drop_guard();
 
         info!("[Gateway] Ready to accept connections (servers connecting in background)");
 
-        axum::serve(
-            listener,
-            router.into_make_service_with_connect_info::<SocketAddr>(),
-        )
-        .with_graceful_shutdown(async move {
-            shutdown.await;
-            info!("[Gateway] Graceful shutdown signal received — closing listener");
-        })
-        .await?;
+        // Serve until shutdown; a reload that rebinds swaps in new listeners
+        let mut shutdown = std::pin::pin!(shutdown);
+        loop {
+            let stop = CancellationToken::new();
+            let mut serving = tokio::spawn(serve_all(
+                listeners,
+                router.clone(),
+                self_arc.reloader.limiter.clone(),
+                stop.clone(),
+            ));
+
+            tokio::select! {
+                _ = &mut shutdown => {
+                    info!("[Gateway] Graceful shutdown signal received — closing listener");
+                    stop.cancel();
+                    self_arc.drain(serving).await?;
+                    break;
+                }
+                result = &mut serving => {
+                    result??;
+                    break;
+                }
+                Some(next) = listener_rx.recv() => {
+                    // The old listener closes now; its open connections
+                    // finish in the background.
+                    stop.cancel();
+                    tokio::spawn(async move {
+                        if let Ok(Err(e)) = serving.await {
+                            warn!("[Gateway] Previous listener ended with error: {}", e);
+                        }
+                    });
+                    listeners = next;
+                }
+            }
+        }
 
         info!("[Gateway] Listener closed, run_with_shutdown returning");
         Ok(())
     }
 
+    /// Shut down after the listener has stopped accepting connections.
+    ///
+    /// 1. Refuse new sessions and tool calls.
+    /// 2. Wait up to `drain_timeout` for running tool calls to return.
+    /// 3. Terminate every MCP session, which ends its SSE stream.
+    /// 4. Close backend connections, killing stdio servers that don't exit
+    ///    within [`BACKEND_CLOSE_TIMEOUT`]. OAuth tokens are kept.
+    /// 5. Wait briefly for client connections to close.
+    async fn drain(
+        &self,
+        mut serving: tokio::task::JoinHandle<std::io::Result<()>>,
+    ) -> anyhow::Result<()> {
+        let tracker = &self.services.drain_tracker;
+        tracker.start_draining();
+
+        let drain_timeout = self.reloader.config().await.drain_timeout;
+        if tracker.in_flight() > 0 {
+            info!(
+                "[Gateway] Waiting up to {:?} for {} in-flight tool call(s)",
+                drain_timeout,
+                tracker.in_flight()
+            );
+        }
+        if !tracker.wait_idle(drain_timeout).await {
+            warn!(
+                "[Gateway] {} tool call(s) still running after {:?} — closing sessions anyway",
+                tracker.in_flight(),
+                drain_timeout
+            );
+        }
+
+        self.sessions_cancel.cancel();
+        self.services
+            .pool_services
+            .pool_service
+            .shutdown_all(BACKEND_CLOSE_TIMEOUT)
+            .await;
+
+        match tokio::time::timeout(CONNECTION_CLOSE_TIMEOUT, &mut serving).await {
+            Ok(result) => result??,
+            Err(_) => {
+                warn!("[Gateway] Client connections still open — closing them");
+                serving.abort();
+            }
+        }
+        Ok(())
+    }
+
     /// Start the server in the background.
     ///
     /// Returns a [`GatewayServerHandle`] with both the `JoinHandle` and a
@@ -647,6 +1041,7 @@ impl GatewayServer {
     /// cleanly. Dropping the sender without using it leaves axum running
     /// until its task is aborted — the old behavior.
     pub fn spawn(self) -> GatewayServerHandle {
+        let reloader = self.reloader();
         let (tx, rx) = tokio::sync::oneshot::channel::<()>();
         let task = tokio::spawn(async move {
             self.run_with_shutdown(async move {
@@ -661,6 +1056,7 @@ impl GatewayServer {
         GatewayServerHandle {
             task,
             shutdown: Some(tx),
+            reloader,
         }
     }
 }
@@ -675,6 +1071,7 @@ impl GatewayServer {
 pub struct GatewayServerHandle {
     pub task: tokio::task::JoinHandle<anyhow::Result<()>>,
     shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    reloader: GatewayReloader,
 }
 
 impl GatewayServerHandle {
@@ -689,6 +1086,22 @@ impl GatewayServerHandle {
     pub fn is_active(&self) -> bool {
         self.shutdown.is_some()
     }
+
+    /// Configuration currently in effect
+    pub async fn config(&self) -> GatewayConfig {
+        self.reloader.config().await
+    }
+
+    /// Apply configuration changes without restarting.
+    /// See [`GatewayReloader::reload_config`].
+    pub async fn reload_config(&self, config: GatewayConfig) -> anyhow::Result<()> {
+        self.reloader.reload_config(config).await
+    }
+
+    /// Requests refused by rate limiting since the gateway started
+    pub fn rate_limit_rejections(&self) -> Vec<rate_limit::RateLimitRejections> {
+        self.reloader.rate_limit_rejections()
+    }
 }
 
 #[cfg(test)]
@@ -708,6 +1121,18 @@ mod config_tests {
         assert_eq!(cfg.base_url(), format!("http://localhost:{}", cfg.port));
     }
 
+    #[test]
+    fn base_url_host_replaces_localhost() {
+        let cfg = GatewayConfig {
+            base_url_host: Some("::1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(cfg.base_url(), format!("http://[::1]:{}", cfg.port));
+        assert_eq!(local_base_url(Some("127.0.0.1"), 1), "http://127.0.0.1:1");
+        assert_eq!(local_base_url(Some("[::1]"), 1), "http://[::1]:1");
+        assert_eq!(local_base_url(Some(" "), 1), "http://localhost:1");
+    }
+
     #[test]
     fn base_url_uses_public_origin_and_trims_trailing_slash() {
         assert_eq!(
@@ -756,7 +1181,7 @@ mod config_tests {
 
     #[test]
     fn is_network_bind_distinguishes_loopback_from_exposed() {
-        for h in ["127.0.0.1", "::1", "localhost", ""] {
+        for h in ["127.0.0.1", "::1", "[::1]", "localhost", ""] {
             assert!(!config_on_host(h).is_network_bind(), "{h:?} is loopback");
         }
         for h in ["0.0.0.0", "::", "192.168.1.50"] {
@@ -778,6 +1203,28 @@ mod config_tests {
             .contains(&"localhost".to_string()));
     }
 
+    #[tokio::test]
+    async fn reload_with_invalid_host_is_rejected() {
+        let config = GatewayConfig::default();
+        let (event_tx, _) = tokio::sync::broadcast::channel(1);
+        let (listener_tx, _listener_rx) = mpsc::unbounded_channel();
+        let reloader = GatewayReloader {
+            config: Arc::new(Mutex::new(config.clone())),
+            state: Arc::new(RwLock::new(GatewayState::new(event_tx))),
+            rate_limiter: rate_limit::RateLimiter::new(config.rate_limits.clone()),
+            cors: Arc::new(cors::DynamicCors::new(config.enable_cors, &config.cors)),
+            limiter: Limiter::new(&config.request_limits),
+            listener_tx,
+        };
+
+        let err = reloader
+            .reload_config(config_on_host("not-an-address"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not-an-address"), "{err}");
+        assert_eq!(reloader.config().await.host, config.host);
+    }
+
     #[test]
     fn management_path_matching_excludes_features_and_oauth_flow() {
         assert!(super::is_management_path("/oauth/clients")); // list
@@ -0,0 +1,267 @@
+//! Pairing remote devices (`/oauth/pair`)
+//!
+//! The desktop app (or an admin over `/admin/pairings`) creates a short
+//! one-time code and shows it with the gateway URL, as text and as a QR
+//! code. The device posts the code to `/oauth/pair` and gets back an API key
+//! for a new pre-approved client, optionally locked to a Space, so nobody
+//! has to copy a long key from one screen to another.
+//!
+//! Codes expire after [`PAIRING_CODE_TTL`] and work once. Only their hash is
+//! kept, and [`MAX_FAILED_PAIRING_ATTEMPTS`] wrong codes in a row drop every
+//! outstanding one, so a code can't be guessed even within the rate limit.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State},
+    http::{header::HOST, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use mcpmux_core::DomainEvent;
+use mcpmux_storage::hash_api_key;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::handlers::{effective_base_url, AppState, TokenErrorResponse};
+use crate::auth::register_api_key_client;
+
+/// Path devices post their pairing code to
+pub const PAIRING_PATH: &str = "/oauth/pair";
+
+/// How long a pairing code can be used
+pub const PAIRING_CODE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Wrong codes in a row after which every outstanding code is dropped
+pub const MAX_FAILED_PAIRING_ATTEMPTS: u32 = 5;
+
+/// Crockford base32: no I, L, O or U to misread
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters in a code, shown in two groups of five (50 bits)
+const CODE_LEN: usize = 10;
+
+/// Client name when neither the pairing nor the device names one
+const DEFAULT_CLIENT_NAME: &str = "Paired device";
+
+/// A pairing code waiting for its device
+#[derive(Debug, Clone)]
+pub struct PendingPairing {
+    /// Name for the new client; the device's own name when `None`
+    pub client_name: Option<String>,
+    /// Space the new client is locked to
+    pub locked_space_id: Option<String>,
+    /// Unix timestamp after which the code is refused
+    pub expires_at: i64,
+}
+
+impl PendingPairing {
+    pub fn new(client_name: Option<String>, locked_space_id: Option<String>) -> Self {
+        Self {
+            client_name: client_name.filter(|name| !name.trim().is_empty()),
+            locked_space_id: locked_space_id.filter(|id| !id.trim().is_empty()),
+            expires_at: chrono::Utc::now().timestamp() + PAIRING_CODE_TTL.as_secs() as i64,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        chrono::Utc::now().timestamp() > self.expires_at
+    }
+}
+
+/// A new random pairing code, e.g. `7KQ2M-X9D4R`
+pub fn generate_pairing_code() -> String {
+    let mut rng = rand::thread_rng();
+    let mut code = String::with_capacity(CODE_LEN + 1);
+    for i in 0..CODE_LEN {
+        if i == CODE_LEN / 2 {
+            code.push('-');
+        }
+        code.push(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char);
+    }
+    code
+}
+
+/// The key a code is stored under. Case, dashes and spaces don't matter, and
+/// O, I and L read as 0, 1 and 1, so a code typed by hand still matches.
+pub(crate) fn pairing_code_hash(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect();
+    hash_api_key(&normalized)
+}
+
+/// The URL a device exchanges `code` at. Everything before
+/// [`PAIRING_PATH`] is the gateway URL, so the QR code needs nothing else.
+pub fn pairing_url(base_url: &str, code: &str) -> String {
+    format!(
+        "{}{}?code={}",
+        base_url.trim_end_matches('/'),
+        PAIRING_PATH,
+        code
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PairingRequest {
+    /// The pairing code; may instead be in the query string
+    #[serde(default)]
+    pub code: Option<String>,
+    /// The device's name, used when the pairing didn't set one
+    #[serde(default)]
+    pub client_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PairingQuery {
+    pub code: Option<String>,
+}
+
+/// Credentials for a paired device
+#[derive(Debug, Serialize)]
+pub struct PairingResponse {
+    pub client_id: String,
+    pub client_name: String,
+    /// Send as `Authorization: Bearer <api_key>`. Shown once.
+    pub api_key: String,
+    pub key_prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_space_id: Option<String>,
+    /// The MCP endpoint to use the key with
+    pub mcp_url: String,
+}
+
+fn pairing_error(status: StatusCode, error: &str, description: &str) -> Response {
+    (
+        status,
+        Json(TokenErrorResponse {
+            error: error.to_string(),
+            error_description: Some(description.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// POST /oauth/pair - exchange a one-time pairing code for an API key
+pub async fn oauth_pair(
+    State(app_state): State<AppState>,
+    Query(query): Query<PairingQuery>,
+    headers: HeaderMap,
+    body: Option<Json<PairingRequest>>,
+) -> Response {
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    let Some(code) = request
+        .code
+        .or(query.code)
+        .filter(|code| !code.trim().is_empty())
+    else {
+        return pairing_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            "Missing pairing code",
+        );
+    };
+
+    let Some(pairing) = app_state.gateway_state.write().await.consume_pairing(&code) else {
+        warn!("[Pairing] Rejected an unknown or expired pairing code");
+        return pairing_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "Pairing code is invalid or expired",
+        );
+    };
+
+    let client_name = pairing
+        .client_name
+        .or(request.client_name)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_CLIENT_NAME.to_string());
+
+    let state = app_state.gateway_state.read().await;
+    let Some(repo) = state.inbound_client_repository() else {
+        return pairing_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            "Database not available",
+        );
+    };
+    let client =
+        match register_api_key_client(repo, &client_name, pairing.locked_space_id.as_deref()).await
+        {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("[Pairing] Failed to register paired client: {}", e);
+                return pairing_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "server_error",
+                    &e.to_string(),
+                );
+            }
+        };
+
+    state.emit_domain_event(DomainEvent::ClientRegistered {
+        client_id: client.client_id.clone(),
+        client_name: client.client_name.clone(),
+        registration_type: Some("preregistered".to_string()),
+    });
+
+    let base = effective_base_url(
+        state.public_base_url.as_deref(),
+        state.network_bind,
+        headers.get(HOST).and_then(|v| v.to_str().ok()),
+        &state.base_url,
+    );
+    info!(
+        "[Pairing] Paired {} ({})",
+        client.client_name, client.client_id
+    );
+
+    Json(PairingResponse {
+        client_id: client.client_id,
+        client_name: client.client_name,
+        api_key: client.api_key,
+        key_prefix: client.key_prefix,
+        locked_space_id: client.locked_space_id,
+        mcp_url: format!("{}/mcp", base),
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_two_groups_of_crockford_base32() {
+        let code = generate_pairing_code();
+        assert_eq!(code.len(), CODE_LEN + 1);
+        assert_eq!(code.as_bytes()[CODE_LEN / 2], b'-');
+        assert!(code
+            .bytes()
+            .filter(|b| *b != b'-')
+            .all(|b| CODE_ALPHABET.contains(&b)));
+    }
+
+    #[test]
+    fn typed_codes_match_despite_case_and_lookalikes() {
+        let hash = pairing_code_hash("10ABC-DEF12");
+        assert_eq!(pairing_code_hash("lo abc def12"), hash);
+        assert_eq!(pairing_code_hash("IOabcdef12"), hash);
+        assert_ne!(pairing_code_hash("10ABC-DEF13"), hash);
+    }
+
+    #[test]
+    fn pairing_url_carries_the_gateway_url() {
+        assert_eq!(
+            pairing_url("https://mcp.example.com/", "7KQ2M-X9D4R"),
+            "https://mcp.example.com/oauth/pair?code=7KQ2M-X9D4R"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! User pause of tool traffic
+//!
+//! Pausing refuses every `tools/call` while leaving the gateway, its client
+//! sessions and its backend connections up, so the user can cut agents off
+//! from their tools at once and resume without anything reconnecting.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Error returned for tool calls made while paused
+pub const PAUSED_MESSAGE: &str =
+    "Tool calls are paused by the user in McpMux. Ask the user to resume them.";
+
+/// Whether tool calls are paused
+#[derive(Debug, Default)]
+pub struct TrafficPause {
+    paused: AtomicBool,
+}
+
+impl TrafficPause {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Pause or resume. Returns whether that changed anything.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::SeqCst) != paused
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_paused_reports_changes() {
+        let pause = TrafficPause::new();
+        assert!(!pause.is_paused());
+        assert!(pause.set_paused(true));
+        assert!(!pause.set_paused(true));
+        assert!(pause.is_paused());
+        assert!(pause.set_paused(false));
+        assert!(!pause.is_paused());
+    }
+}
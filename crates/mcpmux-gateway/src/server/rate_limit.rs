@@ -1,130 +1,394 @@
-//! Simple per-path rate limiting middleware for the gateway.
+//! Rate limiting by route and client.
 //!
-//! Uses a DashMap to track request counts per (path, window) pair.
-//! Designed for a localhost-only gateway where IP-based limiting is
-//! irrelevant (all clients share 127.0.0.1).
+//! A policy table maps a path prefix and a client selector to a limit.
+//! Policies for all clients share one budget and are enforced before
+//! authentication; per-client policies are enforced in the MCP auth
+//! middleware once the client is known, since a client ID anyone can put in
+//! a URL is no basis for a budget. Where several policies match, the longest
+//! route wins, and a policy naming the client beats one for each client.
+//!
+//! Responses carry `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+//! `X-RateLimit-Reset` (seconds), plus `Retry-After` when refused. Refusals
+//! are counted per policy for [`RateLimiter::rejections`].
 
 use axum::{
     extract::Request,
-    http::StatusCode,
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use dashmap::DashMap;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
+use tracing::warn;
 
-/// Configuration for a rate-limited route.
-#[derive(Clone)]
-pub struct RateLimitConfig {
-    /// Maximum requests allowed within the window.
+/// Client selector giving each client its own budget
+pub const EACH_CLIENT: &str = "*";
+
+const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+const RESET_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// One row of the policy table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitPolicy {
+    /// Path prefix, e.g. `/oauth/token`
+    pub route: String,
+    /// `None` for one budget shared by all clients, [`EACH_CLIENT`] for a
+    /// budget per client, or a client ID for that client alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Maximum requests allowed within the window
     pub max_requests: u32,
-    /// Time window duration.
-    pub window: Duration,
+    /// Window length in seconds
+    pub window_secs: u64,
 }
 
+impl RateLimitPolicy {
+    /// A shared limit of `max_requests` per minute on `route`
+    pub fn per_minute(route: &str, max_requests: u32) -> Self {
+        Self {
+            route: route.to_string(),
+            client_id: None,
+            max_requests,
+            window_secs: 60,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs)
+    }
+
+    /// Check the route, client selector and limits
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.route.starts_with('/') {
+            anyhow::bail!("Rate limit route must start with '/': {}", self.route);
+        }
+        if self
+            .client_id
+            .as_deref()
+            .is_some_and(|id| id.trim().is_empty())
+        {
+            anyhow::bail!("Rate limit client ID must not be empty ({})", self.route);
+        }
+        if self.max_requests == 0 || self.window_secs == 0 {
+            anyhow::bail!(
+                "Rate limit for {} needs at least one request per window of at least a second",
+                self.route
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Where a request stands against the policy that applied to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Time until the window resets
+    pub reset_after: Duration,
+}
+
+impl RateLimitStatus {
+    /// Seconds until the window resets, rounded up
+    fn reset_secs(&self) -> u64 {
+        self.reset_after.as_secs() + u64::from(self.reset_after.subsec_nanos() > 0)
+    }
+
+    /// Set the `X-RateLimit-*` headers, unless they already describe a
+    /// tighter policy
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        let tighter = headers
+            .get(&REMAINING_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u32>().ok())
+            .is_some_and(|remaining| remaining < self.remaining);
+        if tighter {
+            return;
+        }
+        headers.insert(LIMIT_HEADER, HeaderValue::from(self.limit));
+        headers.insert(REMAINING_HEADER, HeaderValue::from(self.remaining));
+        headers.insert(RESET_HEADER, HeaderValue::from(self.reset_secs()));
+    }
+
+    /// `429 Too Many Requests` with `Retry-After`
+    pub fn rejection(&self) -> Response {
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            "Rate limit exceeded. Please try again later.",
+        )
+            .into_response();
+        self.apply_headers(response.headers_mut());
+        response.headers_mut().insert(
+            axum::http::header::RETRY_AFTER,
+            HeaderValue::from(self.reset_secs().max(1)),
+        );
+        response
+    }
+}
+
+/// Refusals under one policy, for monitoring
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitRejections {
+    pub route: String,
+    /// The refused client, for per-client policies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    pub count: u64,
+    /// Unix timestamp of the latest refusal
+    pub last_rejected_at: i64,
+}
+
+/// A policy's index in the table and, for per-client policies, the client
+type BucketKey = (usize, Option<String>);
+
+/// A route and, for per-client policies, the client
+type RejectionKey = (String, Option<String>);
+
 /// Shared rate limiter state (clone-friendly via Arc).
 #[derive(Clone)]
 pub struct RateLimiter {
-    /// Map from path prefix → (window_start, request_count).
-    buckets: Arc<DashMap<String, (Instant, u32)>>,
-    /// Configuration per route prefix.
-    rules: Arc<Vec<(String, RateLimitConfig)>>,
+    /// Map from (policy index, client) → (window_start, request_count).
+    buckets: Arc<DashMap<BucketKey, (Instant, u32)>>,
+    /// The policy table (swappable while the gateway runs).
+    rules: Arc<RwLock<Vec<RateLimitPolicy>>>,
+    /// Refusals since the gateway started → (count, last refusal timestamp)
+    rejections: Arc<DashMap<RejectionKey, (u64, i64)>>,
 }
 
 impl RateLimiter {
-    pub fn new(rules: Vec<(String, RateLimitConfig)>) -> Self {
+    pub fn new(rules: Vec<RateLimitPolicy>) -> Self {
         Self {
             buckets: Arc::new(DashMap::new()),
-            rules: Arc::new(rules),
+            rules: Arc::new(RwLock::new(rules)),
+            rejections: Arc::new(DashMap::new()),
         }
     }
 
-    /// Check if the request should be rate limited.
-    /// Returns `true` if the request is within limits (allowed).
-    fn check(&self, path: &str) -> bool {
-        for (prefix, config) in self.rules.iter() {
-            if path.starts_with(prefix) {
-                let mut entry = self
-                    .buckets
-                    .entry(prefix.clone())
-                    .or_insert_with(|| (Instant::now(), 0));
-                let (window_start, count) = entry.value_mut();
-
-                if window_start.elapsed() >= config.window {
-                    // Reset window
-                    *window_start = Instant::now();
-                    *count = 1;
-                    return true;
-                }
+    /// Replace the rules. Current windows are reset.
+    pub fn set_rules(&self, rules: Vec<RateLimitPolicy>) {
+        *self.rules.write().unwrap_or_else(|e| e.into_inner()) = rules;
+        self.buckets.clear();
+    }
 
-                if *count >= config.max_requests {
-                    return false; // Rate limited
-                }
+    /// The policy table in effect
+    pub fn rules(&self) -> Vec<RateLimitPolicy> {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Count a request against the policy that applies to it: a shared one
+    /// when `client_id` is `None`, otherwise a per-client one. `None` when
+    /// no policy applies; `Err` when the request is over the limit.
+    pub fn check(
+        &self,
+        path: &str,
+        client_id: Option<&str>,
+    ) -> Option<Result<RateLimitStatus, RateLimitStatus>> {
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+        let (index, policy) = select_policy(&rules, path, client_id)?;
+        let bucket_client = client_id.filter(|_| policy.client_id.is_some());
+
+        let mut entry = self
+            .buckets
+            .entry((index, bucket_client.map(str::to_string)))
+            .or_insert_with(|| (Instant::now(), 0));
+        let (window_start, count) = entry.value_mut();
+        let window = policy.window();
+        if window_start.elapsed() >= window {
+            *window_start = Instant::now();
+            *count = 0;
+        }
+        let mut status = RateLimitStatus {
+            limit: policy.max_requests,
+            remaining: 0,
+            reset_after: window.saturating_sub(window_start.elapsed()),
+        };
 
-                *count += 1;
-                return true;
-            }
+        if *count >= policy.max_requests {
+            drop(entry);
+            self.record_rejection(&policy.route, bucket_client);
+            return Some(Err(status));
         }
-        true // No matching rule, allow
+        *count += 1;
+        status.remaining = policy.max_requests - *count;
+        Some(Ok(status))
     }
+
+    fn record_rejection(&self, route: &str, client_id: Option<&str>) {
+        warn!(
+            route,
+            client = client_id.unwrap_or("-"),
+            "[RateLimit] Request refused"
+        );
+        let mut entry = self
+            .rejections
+            .entry((route.to_string(), client_id.map(str::to_string)))
+            .or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 = chrono::Utc::now().timestamp();
+    }
+
+    /// Refusals per policy (and client) since the gateway started, most
+    /// frequent first
+    pub fn rejections(&self) -> Vec<RateLimitRejections> {
+        let mut rejections: Vec<_> = self
+            .rejections
+            .iter()
+            .map(|entry| {
+                let ((route, client_id), (count, last_rejected_at)) = entry.pair();
+                RateLimitRejections {
+                    route: route.clone(),
+                    client_id: client_id.clone(),
+                    count: *count,
+                    last_rejected_at: *last_rejected_at,
+                }
+            })
+            .collect();
+        rejections.sort_by(|a, b| b.count.cmp(&a.count).then(a.route.cmp(&b.route)));
+        rejections
+    }
+}
+
+/// The policy for `path`: among shared policies when `client_id` is `None`,
+/// otherwise among those naming the client or [`EACH_CLIENT`]. Longest
+/// route first, then the one naming the client.
+fn select_policy<'a>(
+    rules: &'a [RateLimitPolicy],
+    path: &str,
+    client_id: Option<&str>,
+) -> Option<(usize, &'a RateLimitPolicy)> {
+    rules
+        .iter()
+        .enumerate()
+        .filter(|(_, policy)| path.starts_with(policy.route.as_str()))
+        .filter(
+            |(_, policy)| match (policy.client_id.as_deref(), client_id) {
+                (None, None) => true,
+                (Some(selector), Some(client)) => selector == EACH_CLIENT || selector == client,
+                _ => false,
+            },
+        )
+        .max_by_key(|(_, policy)| {
+            (
+                policy.route.len(),
+                policy.client_id.as_deref() != Some(EACH_CLIENT),
+            )
+        })
 }
 
-/// Axum middleware function for rate limiting.
+/// Axum middleware enforcing the shared policies.
 pub async fn rate_limit_middleware(request: Request, next: Next) -> Response {
     let limiter = request.extensions().get::<RateLimiter>().cloned();
+    let status = limiter.and_then(|limiter| limiter.check(request.uri().path(), None));
 
-    if let Some(limiter) = limiter {
-        let path = request.uri().path().to_string();
-        if !limiter.check(&path) {
-            return (
-                StatusCode::TOO_MANY_REQUESTS,
-                "Rate limit exceeded. Please try again later.",
-            )
-                .into_response();
+    match status {
+        Some(Err(status)) => status.rejection(),
+        Some(Ok(status)) => {
+            let mut response = next.run(request).await;
+            status.apply_headers(response.headers_mut());
+            response
         }
+        None => next.run(request).await,
     }
-
-    next.run(request).await
 }
 
 /// Create the default rate limiter for OAuth endpoints.
 pub fn default_oauth_rate_limiter() -> RateLimiter {
-    RateLimiter::new(vec![
-        (
-            "/oauth/authorize".to_string(),
-            RateLimitConfig {
-                max_requests: 30,
-                window: Duration::from_secs(60),
-            },
-        ),
-        (
-            "/authorize".to_string(),
-            RateLimitConfig {
-                max_requests: 30,
-                window: Duration::from_secs(60),
-            },
-        ),
-        (
-            "/oauth/token".to_string(),
-            RateLimitConfig {
-                max_requests: 60,
-                window: Duration::from_secs(60),
-            },
-        ),
-        (
-            "/oauth/register".to_string(),
-            RateLimitConfig {
-                max_requests: 20,
-                window: Duration::from_secs(60),
-            },
-        ),
-        (
-            "/oauth/clients".to_string(),
-            RateLimitConfig {
-                max_requests: 30,
-                window: Duration::from_secs(60),
-            },
-        ),
-    ])
+    RateLimiter::new(default_rate_limit_rules())
+}
+
+/// Default shared limits (OAuth endpoints and the admin API).
+pub fn default_rate_limit_rules() -> Vec<RateLimitPolicy> {
+    vec![
+        RateLimitPolicy::per_minute("/oauth/authorize", 30),
+        RateLimitPolicy::per_minute("/authorize", 30),
+        RateLimitPolicy::per_minute("/oauth/token", 60),
+        RateLimitPolicy::per_minute("/oauth/register", 20),
+        RateLimitPolicy::per_minute("/oauth/pair", 10),
+        RateLimitPolicy::per_minute("/oauth/clients", 30),
+        RateLimitPolicy::per_minute("/admin", 120),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_rule(prefix: &str, client: &str, max_requests: u32) -> RateLimitPolicy {
+        RateLimitPolicy {
+            client_id: Some(client.to_string()),
+            ..RateLimitPolicy::per_minute(prefix, max_requests)
+        }
+    }
+
+    #[test]
+    fn set_rules_replaces_limits_and_resets_windows() {
+        let limiter = RateLimiter::new(vec![RateLimitPolicy::per_minute("/oauth/token", 1)]);
+        assert!(limiter.check("/oauth/token", None).unwrap().is_ok());
+        assert!(limiter.check("/oauth/token", None).unwrap().is_err());
+
+        limiter.set_rules(vec![RateLimitPolicy::per_minute("/oauth/token", 2)]);
+        assert!(limiter.check("/oauth/token", None).unwrap().is_ok());
+        assert!(limiter.check("/oauth/token", None).unwrap().is_ok());
+        assert!(limiter.check("/oauth/token", None).unwrap().is_err());
+        assert!(limiter.check("/health", None).is_none());
+    }
+
+    #[test]
+    fn each_client_gets_its_own_budget_unless_named() {
+        let limiter = RateLimiter::new(vec![
+            client_rule("/mcp", EACH_CLIENT, 1),
+            client_rule("/mcp", "ci-runner", 3),
+        ]);
+        assert!(limiter.check("/mcp", Some("cursor")).unwrap().is_ok());
+        assert!(limiter.check("/mcp", Some("cursor")).unwrap().is_err());
+        assert!(limiter.check("/mcp", Some("vscode")).unwrap().is_ok());
+        for _ in 0..3 {
+            assert!(limiter.check("/mcp", Some("ci-runner")).unwrap().is_ok());
+        }
+        assert!(limiter.check("/mcp", Some("ci-runner")).unwrap().is_err());
+        // Per-client policies don't apply before the client is known
+        assert!(limiter.check("/mcp", None).is_none());
+    }
+
+    #[test]
+    fn longest_route_wins_and_status_counts_down() {
+        let limiter = RateLimiter::new(vec![
+            RateLimitPolicy::per_minute("/oauth", 100),
+            RateLimitPolicy::per_minute("/oauth/token", 2),
+        ]);
+        let status = limiter.check("/oauth/token", None).unwrap().unwrap();
+        assert_eq!((status.limit, status.remaining), (2, 1));
+        let status = limiter.check("/oauth/register", None).unwrap().unwrap();
+        assert_eq!((status.limit, status.remaining), (100, 99));
+    }
+
+    #[test]
+    fn rejections_are_counted_per_policy_and_client() {
+        let limiter = RateLimiter::new(vec![client_rule("/mcp", EACH_CLIENT, 1)]);
+        limiter.check("/mcp", Some("cursor")).unwrap().ok();
+        for _ in 0..2 {
+            let status = limiter.check("/mcp", Some("cursor")).unwrap().unwrap_err();
+            let response = status.rejection();
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert!(response.headers().contains_key("retry-after"));
+            assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+        }
+        let rejections = limiter.rejections();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].client_id.as_deref(), Some("cursor"));
+        assert_eq!(rejections[0].count, 2);
+    }
+
+    #[test]
+    fn validate_rejects_unusable_policies() {
+        assert!(RateLimitPolicy::per_minute("/mcp", 10).validate().is_ok());
+        assert!(RateLimitPolicy::per_minute("mcp", 10).validate().is_err());
+        assert!(RateLimitPolicy::per_minute("/mcp", 0).validate().is_err());
+        assert!(client_rule("/mcp", " ", 10).validate().is_err());
+    }
 }
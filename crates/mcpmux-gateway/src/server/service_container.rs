@@ -8,12 +8,16 @@ use std::sync::Arc;
 use crate::pool::{PoolServices, ServerManager, ServiceFactory};
 use crate::services::{
     meta_tools, ApprovalBroker, AuthorizationService, ClientMetadataService,
-    FeatureSetResolverService, GrantService, MetaToolRegistry, PrefixCacheService,
-    SessionRootsRegistry, SpaceResolverService,
+    FeatureSetResolverService, GrantService, MetaToolRegistry, PackageUpdateService,
+    PrefixCacheService, RepositorySnapshot, SessionActivityRegistry, SessionRootsRegistry,
+    SpaceResolverService,
 };
 use mcpmux_core::DomainEvent;
 
-use super::{dependencies::GatewayDependencies, GatewayState, StartupOrchestrator};
+use super::{
+    dependencies::GatewayDependencies, drain::DrainTracker, pause::TrafficPause, GatewayState,
+    StartupOrchestrator,
+};
 
 /// Container for all Gateway services
 ///
@@ -40,6 +44,9 @@ pub struct ServiceContainer {
     /// Registry of per-session workspace roots (populated from MCP `roots/list`).
     pub session_roots: Arc<SessionRootsRegistry>,
 
+    /// Live per-session activity (in-flight requests, recent tool calls).
+    pub session_activity: Arc<SessionActivityRegistry>,
+
     /// Broker that asks the desktop UI for user approval on meta-tool writes.
     /// Shared with the Tauri layer so it can attach a publisher + respond.
     pub approval_broker: Arc<ApprovalBroker>,
@@ -59,11 +66,20 @@ pub struct ServiceContainer {
     /// Grant service for centralized grant management with auto-notifications (SRP + DRY)
     pub grant_service: Arc<GrantService>,
 
+    /// Pins runner-launched server packages and checks them for updates
+    pub package_updates: Arc<PackageUpdateService>,
+
     /// Gateway state (for accessing base_url, JWT secret, etc.)
     pub gateway_state: Arc<tokio::sync::RwLock<GatewayState>>,
 
     /// Gateway dependencies (for accessing repositories, etc.)
     pub dependencies: GatewayDependencies,
+
+    /// In-flight tool calls, drained on graceful shutdown
+    pub drain_tracker: Arc<DrainTracker>,
+
+    /// User pause of all tool calls
+    pub traffic_pause: Arc<TrafficPause>,
 }
 
 impl ServiceContainer {
@@ -75,6 +91,20 @@ impl ServiceContainer {
         domain_event_tx: tokio::sync::broadcast::Sender<DomainEvent>,
         gateway_state: Arc<tokio::sync::RwLock<GatewayState>>,
     ) -> Self {
+        // Serve the routing hot path's feature, FeatureSet and grant reads
+        // from memory. Every service below gets the snapshot-backed repos,
+        // so their writes clear it too.
+        let snapshot = RepositorySnapshot::new(
+            deps.feature_repo.clone(),
+            deps.feature_set_repo.clone(),
+            deps.inbound_client_repo.clone(),
+        );
+        snapshot.start(domain_event_tx.subscribe());
+        let mut deps = deps.clone();
+        deps.feature_repo = snapshot.feature_repo();
+        deps.feature_set_repo = snapshot.feature_set_repo();
+        let deps = &deps;
+
         // Create prefix cache service with dependencies
         let prefix_cache_service = Arc::new(PrefixCacheService::new().with_dependencies(
             deps.installed_server_repo.clone(),
@@ -103,14 +133,17 @@ impl ServiceContainer {
         // here; the old per-client pin path is gone (see v2 migration
         // journey in mcpmux.space/diagrams/workppace-root-session/).
         let session_roots = SessionRootsRegistry::new();
-        let feature_set_resolver = Arc::new(FeatureSetResolverService::new(
-            deps.space_repo.clone(),
-            deps.workspace_binding_repo.clone(),
-            session_roots.clone(),
-            deps.inbound_client_repo.clone(),
-            deps.feature_set_repo.clone(),
-            deps.space_base_dir_repo.clone(),
-        ));
+        let feature_set_resolver = Arc::new(
+            FeatureSetResolverService::new(
+                deps.space_repo.clone(),
+                deps.workspace_binding_repo.clone(),
+                session_roots.clone(),
+                deps.inbound_client_repo.clone(),
+                deps.feature_set_repo.clone(),
+                deps.space_base_dir_repo.clone(),
+            )
+            .with_snapshot(snapshot.clone()),
+        );
 
         // Authorization service is now a thin adapter over the resolver.
         let authorization_service =
@@ -137,9 +170,12 @@ impl ServiceContainer {
             Some(deps.builtin_config_repo.clone()),
         );
 
-        // Space resolver — currently just exposes the active Space, but
-        // keeps a stable seam for future session-targeted routing.
-        let space_resolver_service = Arc::new(SpaceResolverService::new(deps.space_repo.clone()));
+        // Space resolver — exposes the active Space and validates per-request
+        // Space selection (X-McpMux-Space header / `space:` token scope).
+        let space_resolver_service = Arc::new(
+            SpaceResolverService::new(deps.space_repo.clone(), deps.inbound_client_repo.clone())
+                .with_snapshot(snapshot.clone()),
+        );
 
         // Create client metadata service
         let client_metadata_service = deps.client_metadata_service.clone();
@@ -153,6 +189,11 @@ impl ServiceContainer {
             domain_event_tx.clone(),
         ));
 
+        let package_updates = Arc::new(PackageUpdateService::new(
+            deps.installed_server_repo.clone(),
+            domain_event_tx.clone(),
+        ));
+
         Self {
             pool_services,
             server_manager,
@@ -160,14 +201,18 @@ impl ServiceContainer {
             authorization_service,
             feature_set_resolver,
             session_roots,
+            session_activity: SessionActivityRegistry::new(),
             approval_broker,
             meta_tool_registry,
             space_resolver_service,
             prefix_cache_service,
             client_metadata_service,
             grant_service,
+            package_updates,
             gateway_state,
             dependencies: deps.clone(),
+            drain_tracker: DrainTracker::new(),
+            traffic_pause: TrafficPause::new(),
         }
     }
 }
@@ -3,10 +3,12 @@
 //! Follows Single Responsibility Principle - only concerned with startup logic.
 //! Keeps GatewayServer focused on serving requests, not initialization.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use anyhow::Result;
-use mcpmux_core::InstalledServer;
+use anyhow::{anyhow, Result};
+use futures::stream::{FuturesUnordered, StreamExt};
+use mcpmux_core::{startup_order, DomainEvent, InstalledServer};
 use tracing::{info, warn};
 
 use crate::pool::{ConnectionContext, ConnectionResult, PoolService, ServerManager};
@@ -14,6 +16,16 @@ use crate::services::PrefixCacheService;
 
 use super::GatewayDependencies;
 
+/// Servers connected at the same time during auto-connect, by default
+pub const DEFAULT_AUTO_CONNECT_CONCURRENCY: usize = 4;
+
+/// `(space_id, server_id)` of an installed server
+type ServerRef = (String, String);
+
+fn server_ref(server: &InstalledServer) -> ServerRef {
+    (server.space_id.clone(), server.server_id.clone())
+}
+
 /// Orchestrates startup tasks for the Gateway
 ///
 /// Keeps initialization logic separate from server logic (SRP).
@@ -124,23 +136,33 @@ impl StartupOrchestrator {
     ///
     /// This runs in the background and doesn't block Gateway startup.
     /// OAuth-based servers without tokens are skipped gracefully.
-    pub async fn auto_connect_enabled_servers(&self) -> Result<AutoConnectResult> {
+    /// Up to `concurrency` servers connect at once, most recently used
+    /// first; a server that declares `depends_on` waits until its
+    /// dependencies have connected (see [`startup_order`]). An
+    /// [`AutoConnectProgress`](DomainEvent::AutoConnectProgress) event is
+    /// sent as each server finishes.
+    pub async fn auto_connect_enabled_servers(
+        &self,
+        concurrency: usize,
+    ) -> Result<AutoConnectResult> {
         info!("[Startup] Auto-connecting enabled servers...");
 
+        let concurrency = concurrency.max(1);
         let mut result = AutoConnectResult::default();
 
         // Get all installed servers
         let installed_servers = self.dependencies.installed_server_repo.list().await?;
 
         // Filter to enabled servers only
-        let enabled_servers: Vec<_> = installed_servers
+        let mut enabled_servers: Vec<_> = installed_servers
             .into_iter()
             .filter(|server| server.enabled)
             .collect();
 
         info!(
-            "[Startup] Found {} enabled server(s) to connect",
-            enabled_servers.len()
+            "[Startup] Found {} enabled server(s) to connect, {} at a time",
+            enabled_servers.len(),
+            concurrency
         );
 
         // IMPORTANT: Pre-set all enabled servers to "Connecting" status BEFORE starting connections
@@ -157,39 +179,103 @@ impl StartupOrchestrator {
             let _ = self.server_manager.set_connecting(&key).await;
         }
 
+        let total = enabled_servers.len();
+        self.report_progress(total, &result);
+
+        // Most recently used first; never-used servers keep the repository's
+        // order after them
+        enabled_servers.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at));
+
+        // Servers only depend on servers in their own space
+        let depends_on: HashMap<ServerRef, Vec<String>> = enabled_servers
+            .iter()
+            .map(|s| {
+                let deps = s.get_definition().map(|d| d.depends_on).unwrap_or_default();
+                (server_ref(s), deps)
+            })
+            .collect();
+
+        let mut cyclic: HashSet<ServerRef> = HashSet::new();
+        let mut spaces: Vec<&str> = Vec::new();
+        for server in &enabled_servers {
+            if !spaces.contains(&server.space_id.as_str()) {
+                spaces.push(&server.space_id);
+            }
+        }
+        for space_id in spaces {
+            let in_space: Vec<(&str, &[String])> = enabled_servers
+                .iter()
+                .filter(|s| s.space_id == space_id)
+                .map(|s| (s.server_id.as_str(), depends_on[&server_ref(s)].as_slice()))
+                .collect();
+            for server_id in startup_order(in_space).cyclic {
+                cyclic.insert((space_id.to_string(), server_id));
+            }
+        }
+
+        // Servers not started yet, highest priority first
+        let mut pending: Vec<InstalledServer> = Vec::with_capacity(total);
         for server in enabled_servers {
-            match self.connect_server(&server).await {
-                Ok(ConnectOutcome::Connected) => {
-                    info!(
-                        "[Startup] ✓ Connected: {}/{}",
-                        server.space_id, server.server_id
-                    );
-                    result.connected.push(server.server_id.clone());
-                }
-                Ok(ConnectOutcome::AlreadyConnected) => {
-                    info!(
-                        "[Startup] ✓ Already connected: {}/{}",
-                        server.space_id, server.server_id
-                    );
-                    result.already_connected.push(server.server_id.clone());
-                }
-                Ok(ConnectOutcome::NeedsOAuth) => {
-                    info!(
-                        "[Startup] ⊗ Skipped (needs OAuth): {}/{}",
-                        server.space_id, server.server_id
-                    );
-                    result.needs_oauth.push(server.server_id.clone());
-                }
-                Err(e) => {
-                    warn!(
-                        "[Startup] ✗ Failed to connect {}/{}: {}",
-                        server.space_id, server.server_id, e
-                    );
-                    result
-                        .failed
-                        .push((server.server_id.clone(), e.to_string()));
+            if cyclic.contains(&server_ref(&server)) {
+                self.skip_server(
+                    &server,
+                    "Dependency cycle between servers".to_string(),
+                    &mut result,
+                )
+                .await;
+                self.report_progress(total, &result);
+            } else {
+                pending.push(server);
+            }
+        }
+        // Servers done with, and whether each one connected
+        let mut finished: HashMap<ServerRef, bool> = HashMap::new();
+        for key in &cyclic {
+            finished.insert(key.clone(), false);
+        }
+
+        let mut in_flight = FuturesUnordered::new();
+        loop {
+            // Start ready servers while there is room
+            while in_flight.len() < concurrency {
+                let Some(index) = pending
+                    .iter()
+                    .position(|server| Self::dependencies_settled(server, &depends_on, &finished))
+                else {
+                    break;
+                };
+                let server = pending.remove(index);
+
+                let key = server_ref(&server);
+                let unmet = depends_on[&key].iter().find(|dep| {
+                    **dep != server.server_id
+                        && !Self::dependency_connected(&server, dep, &finished)
+                });
+                if let Some(dep) = unmet {
+                    let reason = if depends_on.contains_key(&(server.space_id.clone(), dep.clone()))
+                    {
+                        format!("Dependency {} did not connect", dep)
+                    } else {
+                        format!("Dependency {} is not installed or not enabled", dep)
+                    };
+                    self.skip_server(&server, reason, &mut result).await;
+                    finished.insert(key, false);
+                    self.report_progress(total, &result);
+                    continue;
                 }
+
+                in_flight.push(async move {
+                    let outcome = self.connect_server(&server).await;
+                    (server, outcome)
+                });
             }
+
+            let Some((server, outcome)) = in_flight.next().await else {
+                break;
+            };
+            let connected = Self::record_outcome(&server, outcome, &mut result);
+            finished.insert(server_ref(&server), connected);
+            self.report_progress(total, &result);
         }
 
         info!(
@@ -202,8 +288,129 @@ impl StartupOrchestrator {
         Ok(result)
     }
 
+    /// Connect one installed server, regardless of its dependencies
+    pub async fn connect_installed_server(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> Result<AutoConnectResult> {
+        let server = self
+            .dependencies
+            .installed_server_repo
+            .get_by_server_id(space_id, server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server {} is not installed in {}", server_id, space_id))?;
+
+        let mut result = AutoConnectResult::default();
+        let outcome = self.connect_server(&server).await;
+        Self::record_outcome(&server, outcome, &mut result);
+        Ok(result)
+    }
+
+    /// Whether every enabled dependency of `server` has finished connecting,
+    /// one way or the other
+    fn dependencies_settled(
+        server: &InstalledServer,
+        depends_on: &HashMap<ServerRef, Vec<String>>,
+        finished: &HashMap<ServerRef, bool>,
+    ) -> bool {
+        depends_on[&server_ref(server)].iter().all(|dep| {
+            let dep = (server.space_id.clone(), dep.clone());
+            dep.1 == server.server_id
+                || !depends_on.contains_key(&dep)
+                || finished.contains_key(&dep)
+        })
+    }
+
+    /// Whether `dep`, in the same space as `server`, has connected
+    fn dependency_connected(
+        server: &InstalledServer,
+        dep: &str,
+        finished: &HashMap<ServerRef, bool>,
+    ) -> bool {
+        finished
+            .get(&(server.space_id.clone(), dep.to_string()))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Add a finished connection to `result`; true if the server is up
+    fn record_outcome(
+        server: &InstalledServer,
+        outcome: Result<ConnectOutcome>,
+        result: &mut AutoConnectResult,
+    ) -> bool {
+        match outcome {
+            Ok(ConnectOutcome::Connected) => {
+                info!(
+                    "[Startup] ✓ Connected: {}/{}",
+                    server.space_id, server.server_id
+                );
+                result.connected.push(server.server_id.clone());
+                true
+            }
+            Ok(ConnectOutcome::AlreadyConnected) => {
+                info!(
+                    "[Startup] ✓ Already connected: {}/{}",
+                    server.space_id, server.server_id
+                );
+                result.already_connected.push(server.server_id.clone());
+                true
+            }
+            Ok(ConnectOutcome::NeedsOAuth) => {
+                info!(
+                    "[Startup] ⊗ Skipped (needs OAuth): {}/{}",
+                    server.space_id, server.server_id
+                );
+                result.needs_oauth.push(server.server_id.clone());
+                false
+            }
+            Err(e) => {
+                warn!(
+                    "[Startup] ✗ Failed to connect {}/{}: {}",
+                    server.space_id, server.server_id, e
+                );
+                result
+                    .failed
+                    .push((server.server_id.clone(), e.to_string()));
+                false
+            }
+        }
+    }
+
+    /// Tell the UI how far auto-connect has got
+    fn report_progress(&self, total: usize, result: &AutoConnectResult) {
+        self.server_manager.emit(DomainEvent::AutoConnectProgress {
+            total,
+            connected: result.connected.len() + result.already_connected.len(),
+            needs_oauth: result.needs_oauth.len(),
+            failed: result.failed.len(),
+        });
+    }
+
+    /// Leave a server unconnected because of its dependencies
+    async fn skip_server(
+        &self,
+        server: &InstalledServer,
+        reason: String,
+        result: &mut AutoConnectResult,
+    ) {
+        warn!(
+            "[Startup] ✗ Not connecting {}/{}: {}",
+            server.space_id, server.server_id, reason
+        );
+        if let Ok(space_id) = uuid::Uuid::parse_str(&server.space_id) {
+            let key = crate::pool::ServerKey::new(space_id, server.server_id.clone());
+            self.server_manager.set_error(&key, reason.clone()).await;
+        }
+        result.failed.push((server.server_id.clone(), reason));
+    }
+
     /// Connect a single server
-    async fn connect_server(&self, server: &InstalledServer) -> Result<ConnectOutcome> {
+    pub(crate) async fn connect_server(&self, server: &InstalledServer) -> Result<ConnectOutcome> {
+        // Installed before the organization's policy forbade it
+        self.dependencies.managed_policy.check_server(server)?;
+
         // Get server definition: prefer cached definition, fallback to registry for legacy
         let definition = match server.get_definition() {
             Some(def) => def,
@@ -249,23 +456,51 @@ impl StartupOrchestrator {
             return Ok(ConnectOutcome::NeedsOAuth);
         }
 
-        // Build transport config using cached definition
-        let transport_config = crate::pool::transport::resolution::build_transport_config(
-            &definition.transport,
-            server,
-            self.dependencies.state_dir.as_deref(),
-        );
+        let key = crate::pool::ServerKey::new(space_id, server.server_id.clone());
+
+        // Build transport config using cached definition and the space's
+        // active environment profile; bad input values are reported without
+        // starting the server
+        let workspace = crate::pool::transport::resolution::space_workspace(
+            self.dependencies.space_base_dir_repo.as_ref(),
+            &space_id,
+        )
+        .await;
+        let profile = crate::pool::transport::resolution::space_profile(
+            self.dependencies.environment_profile_repo.as_deref(),
+            &space_id,
+        )
+        .await;
+        let transport_config = match profile.and_then(|profile| {
+            let (transport, profiled) = crate::pool::transport::resolution::apply_profile(
+                profile.as_ref(),
+                &definition.transport,
+                server,
+            );
+            crate::pool::transport::resolution::resolve_transport_config(
+                &transport,
+                &profiled,
+                self.dependencies.state_dir.as_deref(),
+                workspace.as_deref(),
+            )
+        }) {
+            Ok(config) => config,
+            Err(e) => {
+                self.server_manager.set_error(&key, e.to_string()).await;
+                return Err(e.into());
+            }
+        };
 
         // Explicitly set state to connecting in ServerManager BEFORE starting connection
         // This ensures the UI reflects the "Connecting" state during startup
-        let key = crate::pool::ServerKey::new(space_id, server.server_id.clone());
         let _ = self.server_manager.set_connecting(&key).await;
 
         // Attempt connection through pool service (auto-connect mode: don't start OAuth flow)
         // For auto-connect, we pass auto_reconnect=true so OAuth-required servers just return
         // OAuthRequired without starting the callback server or opening browser
         let ctx = ConnectionContext::new(space_id, server.server_id.clone(), transport_config)
-            .with_auto_reconnect(true);
+            .with_auto_reconnect(true)
+            .with_timeouts(server.effective_timeouts(&definition));
         let connection_result = self.pool_service.connect_server(&ctx).await;
 
         match connection_result {
@@ -312,7 +547,7 @@ pub struct TokenRefreshResult {
 }
 
 /// Outcome of connecting a single server
-enum ConnectOutcome {
+pub(crate) enum ConnectOutcome {
     Connected,
     AlreadyConnected,
     NeedsOAuth,
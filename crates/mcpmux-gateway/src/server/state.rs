@@ -9,14 +9,17 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 use zeroize::Zeroizing;
 
 use super::handlers::PendingAuthorization;
+use super::pairing::{pairing_code_hash, PendingPairing, MAX_FAILED_PAIRING_ATTEMPTS};
 use crate::services::ClientMetadataService;
-use mcpmux_core::DomainEvent;
-use mcpmux_storage::{Database, InboundClientRepository, JWT_SECRET_SIZE};
+use mcpmux_core::{DomainEvent, EventJournal};
+use mcpmux_storage::{
+    Database, InboundClientRepository, SqliteInboundClientRepository, JWT_SECRET_SIZE,
+};
 use tokio::sync::broadcast;
 
 /// Client session in the gateway
@@ -59,16 +62,20 @@ pub struct GatewayState {
     pub oauth_tokens: HashMap<String, super::super::oauth::OAuthToken>,
     /// Pending authorization codes (code -> PendingAuthorization)
     pub pending_authorizations: HashMap<String, PendingAuthorization>,
+    /// Outstanding pairing codes (code hash -> PendingPairing)
+    pub pending_pairings: HashMap<String, PendingPairing>,
+    /// Wrong pairing codes presented in a row
+    failed_pairing_attempts: u32,
     /// JWT signing secret (for issuing access tokens)
     pub jwt_signing_secret: Option<Zeroizing<[u8; JWT_SECRET_SIZE]>>,
-    /// Database connection (for persistent OAuth storage)
-    db: Option<Arc<Mutex<Database>>>,
     /// Inbound client repository (OAuth + MCP client unified storage)
-    inbound_client_repository: Option<InboundClientRepository>,
+    inbound_client_repository: Option<Arc<dyn InboundClientRepository>>,
     /// Client metadata service (CIMD + DCR resolution)
     client_metadata_service: Option<Arc<ClientMetadataService>>,
     /// Unified event broadcaster (UI subscribes to receive all domain events)
     domain_event_tx: broadcast::Sender<DomainEvent>,
+    /// Persistent record of the domain events, when the host configured one
+    event_journal: Option<EventJournal>,
     /// When true, inbound MCP connections are accepted WITHOUT a Bearer token
     /// (localhost-only convenience). Default false (auth required). Seeded from
     /// the `gateway.auth_disabled` app setting at startup and flipped live by
@@ -87,11 +94,13 @@ impl GatewayState {
             access_keys: HashMap::new(),
             oauth_tokens: HashMap::new(),
             pending_authorizations: HashMap::new(),
+            pending_pairings: HashMap::new(),
+            failed_pairing_attempts: 0,
             jwt_signing_secret: None,
-            db: None,
             inbound_client_repository: None,
             client_metadata_service: None,
             domain_event_tx,
+            event_journal: None,
             auth_disabled: false,
         }
     }
@@ -140,6 +149,17 @@ impl GatewayState {
         self.domain_event_tx.clone()
     }
 
+    /// Set the journal that records the domain events
+    pub fn set_event_journal(&mut self, journal: EventJournal) {
+        self.event_journal = Some(journal);
+    }
+
+    /// The domain event journal, if one is running. Consumers that must not
+    /// miss events read from it instead of subscribing to the broadcast.
+    pub fn event_journal(&self) -> Option<EventJournal> {
+        self.event_journal.clone()
+    }
+
     /// Emit a domain event (new unified emission point)
     pub fn emit_domain_event(&self, event: DomainEvent) {
         if let Err(e) = self.domain_event_tx.send(event) {
@@ -150,13 +170,18 @@ impl GatewayState {
     /// Set the database connection and create OAuth repository
     pub fn set_database(&mut self, db: Arc<Mutex<Database>>) {
         info!("[State] Database connection configured for OAuth persistence");
-        self.inbound_client_repository = Some(InboundClientRepository::new(db.clone()));
-        self.db = Some(db);
+        self.inbound_client_repository = Some(Arc::new(SqliteInboundClientRepository::new(db)));
+    }
+
+    /// Set the repository for persistent OAuth storage, on any backend
+    pub fn set_inbound_client_repository(&mut self, repo: Arc<dyn InboundClientRepository>) {
+        info!("[State] Inbound client repository configured for OAuth persistence");
+        self.inbound_client_repository = Some(repo);
     }
 
     /// Get the inbound client repository (for persistent storage)
-    pub fn inbound_client_repository(&self) -> Option<&InboundClientRepository> {
-        self.inbound_client_repository.as_ref()
+    pub fn inbound_client_repository(&self) -> Option<&dyn InboundClientRepository> {
+        self.inbound_client_repository.as_deref()
     }
 
     /// Set the client metadata service
@@ -184,7 +209,7 @@ impl GatewayState {
 
     /// Check if database is connected
     pub fn has_database(&self) -> bool {
-        self.db.is_some()
+        self.inbound_client_repository.is_some()
     }
 
     /// Set the JWT signing secret
@@ -224,6 +249,40 @@ impl GatewayState {
         result
     }
 
+    /// Store a pairing code for a device to exchange. Expired codes are
+    /// dropped on the way.
+    pub fn store_pairing(&mut self, code: &str, pairing: PendingPairing) {
+        self.pending_pairings.retain(|_, p| !p.is_expired());
+        self.pending_pairings
+            .insert(pairing_code_hash(code), pairing);
+        self.failed_pairing_attempts = 0;
+    }
+
+    /// Consume a pairing code (one-time use). After
+    /// [`MAX_FAILED_PAIRING_ATTEMPTS`] wrong codes in a row every outstanding
+    /// code is dropped.
+    pub fn consume_pairing(&mut self, code: &str) -> Option<PendingPairing> {
+        match self.pending_pairings.remove(&pairing_code_hash(code)) {
+            Some(pairing) if !pairing.is_expired() => {
+                self.failed_pairing_attempts = 0;
+                Some(pairing)
+            }
+            _ => {
+                self.failed_pairing_attempts += 1;
+                if self.failed_pairing_attempts >= MAX_FAILED_PAIRING_ATTEMPTS {
+                    warn!(
+                        "[State] {} wrong pairing codes in a row, dropping {} outstanding code(s)",
+                        self.failed_pairing_attempts,
+                        self.pending_pairings.len()
+                    );
+                    self.pending_pairings.clear();
+                    self.failed_pairing_attempts = 0;
+                }
+                None
+            }
+        }
+    }
+
     /// Register an access key for a client
     pub fn register_access_key(&mut self, access_key: String, client_id: Uuid) {
         info!("[State] Registered access key for client: {}", client_id);
@@ -324,4 +383,31 @@ mod tests {
         state.set_auth_disabled(false);
         assert!(!state.auth_disabled());
     }
+
+    #[test]
+    fn pairing_codes_work_once() {
+        let mut state = GatewayState::default();
+        state.store_pairing("ABCDE-12345", PendingPairing::new(None, None));
+        assert!(state.consume_pairing("abcde12345").is_some());
+        assert!(state.consume_pairing("ABCDE-12345").is_none());
+    }
+
+    #[test]
+    fn wrong_pairing_codes_drop_outstanding_ones() {
+        let mut state = GatewayState::default();
+        state.store_pairing("ABCDE-12345", PendingPairing::new(None, None));
+        for _ in 0..MAX_FAILED_PAIRING_ATTEMPTS {
+            assert!(state.consume_pairing("ZZZZZ-99999").is_none());
+        }
+        assert!(state.consume_pairing("ABCDE-12345").is_none());
+    }
+
+    #[test]
+    fn expired_pairing_codes_are_refused() {
+        let mut state = GatewayState::default();
+        let mut pairing = PendingPairing::new(None, None);
+        pairing.expires_at = chrono::Utc::now().timestamp() - 1;
+        state.store_pairing("ABCDE-12345", pairing);
+        assert!(state.consume_pairing("ABCDE-12345").is_none());
+    }
 }
@@ -0,0 +1,230 @@
+//! Discovery documents the gateway publishes about itself
+//!
+//! - `/.well-known/mcp.json` describes the aggregated MCP server: where its
+//!   endpoint is, what it supports, and how clients authenticate. Tools that
+//!   find a gateway (e.g. on the LAN or behind a tunnel) can configure
+//!   themselves from it without probing.
+//! - `/.well-known/oauth-client-metadata.json` is McpMux's own OAuth Client ID
+//!   Metadata Document, for servers that accept a URL as `client_id` instead
+//!   of dynamic registration. Its URL is the `client_id`, so it is only
+//!   useful to servers that can reach the gateway, i.e. with a public base URL.
+//!
+//! Both use the same advertised base URL as the OAuth metadata endpoints.
+
+use axum::{extract::State, response::Json};
+use mcpmux_core::branding;
+use rmcp::model::{ProtocolVersion, ServerCapabilities};
+use serde::Serialize;
+use tracing::info;
+
+use super::{effective_base_url, AppState};
+use crate::mcp::gateway_capabilities;
+
+/// Path of the MCP discovery document
+pub const MCP_DISCOVERY_PATH: &str = "/.well-known/mcp.json";
+
+/// Path of McpMux's Client ID Metadata Document
+pub const CLIENT_METADATA_PATH: &str = "/.well-known/oauth-client-metadata.json";
+
+/// `/.well-known/mcp.json`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpDiscoveryDocument {
+    pub protocol_version: ProtocolVersion,
+    pub server_info: DiscoveryServerInfo,
+    pub description: String,
+    pub transport: DiscoveryTransport,
+    pub capabilities: ServerCapabilities,
+    pub authentication: DiscoveryAuthentication,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiscoveryServerInfo {
+    pub name: String,
+    pub title: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiscoveryTransport {
+    /// Always `streamable-http`
+    #[serde(rename = "type")]
+    pub transport_type: String,
+    pub endpoint: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryAuthentication {
+    /// Whether `/mcp` needs a bearer token
+    pub required: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schemes: Vec<String>,
+    /// RFC 8414 authorization server metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_server_metadata: Option<String>,
+    /// RFC 9728 protected resource metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_resource_metadata: Option<String>,
+    /// McpMux's own client metadata document
+    pub client_metadata_document: String,
+}
+
+/// OAuth Client ID Metadata Document (draft-ietf-oauth-client-id-metadata-document)
+#[derive(Debug, Serialize)]
+pub struct ClientMetadataDocument {
+    pub client_id: String,
+    pub client_name: String,
+    pub client_uri: String,
+    pub software_id: String,
+    pub software_version: String,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub response_types: Vec<String>,
+    pub token_endpoint_auth_method: String,
+}
+
+/// The discovery document for a gateway advertised at `base`
+pub fn mcp_discovery_document(base: &str, auth_required: bool) -> McpDiscoveryDocument {
+    McpDiscoveryDocument {
+        protocol_version: ProtocolVersion::LATEST,
+        server_info: DiscoveryServerInfo {
+            name: "mcpmux-gateway".to_string(),
+            title: branding::DISPLAY_NAME.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        description: format!(
+            "{} gateway aggregating the MCP servers of the current Space",
+            branding::DISPLAY_NAME
+        ),
+        transport: DiscoveryTransport {
+            transport_type: "streamable-http".to_string(),
+            endpoint: format!("{}/mcp", base),
+        },
+        capabilities: gateway_capabilities(),
+        authentication: DiscoveryAuthentication {
+            required: auth_required,
+            schemes: if auth_required {
+                vec!["oauth2".to_string()]
+            } else {
+                vec![]
+            },
+            authorization_server_metadata: auth_required
+                .then(|| format!("{}/.well-known/oauth-authorization-server", base)),
+            protected_resource_metadata: auth_required
+                .then(|| format!("{}/.well-known/oauth-protected-resource/mcp", base)),
+            client_metadata_document: format!("{}{}", base, CLIENT_METADATA_PATH),
+        },
+    }
+}
+
+/// McpMux's client metadata document, as published at `base`
+pub fn client_metadata_document(base: &str) -> ClientMetadataDocument {
+    ClientMetadataDocument {
+        client_id: format!("{}{}", base, CLIENT_METADATA_PATH),
+        client_name: branding::DISPLAY_NAME.to_string(),
+        client_uri: format!("https://{}", branding::DOMAIN),
+        software_id: branding::IDENTIFIER.to_string(),
+        software_version: env!("CARGO_PKG_VERSION").to_string(),
+        // Loopback redirect (RFC 8252); servers must allow any port on it
+        redirect_uris: vec![branding::oauth_callback_uri_with_port(
+            branding::DEFAULT_OAUTH_CALLBACK_PORT,
+        )],
+        grant_types: vec![
+            "authorization_code".to_string(),
+            "refresh_token".to_string(),
+        ],
+        response_types: vec!["code".to_string()],
+        token_endpoint_auth_method: "none".to_string(),
+    }
+}
+
+/// The advertised base URL, and whether `/mcp` requires authentication
+async fn advertised_base(app_state: &AppState, headers: &axum::http::HeaderMap) -> (String, bool) {
+    let (public_base_url, network_bind, local_base_url, auth_disabled) = {
+        let state = app_state.gateway_state.read().await;
+        (
+            state.public_base_url.clone(),
+            state.network_bind,
+            state.base_url.clone(),
+            state.auth_disabled(),
+        )
+    };
+    let host = headers
+        .get(axum::http::header::HOST)
+        .and_then(|v| v.to_str().ok());
+    let base = effective_base_url(
+        public_base_url.as_deref(),
+        network_bind,
+        host,
+        &local_base_url,
+    );
+    (base, !auth_disabled)
+}
+
+/// `/.well-known/mcp.json` endpoint
+pub async fn mcp_discovery(
+    State(app_state): State<AppState>,
+    headers: axum::http::HeaderMap,
+) -> Json<McpDiscoveryDocument> {
+    info!("[Gateway] MCP discovery document request");
+    let (base, auth_required) = advertised_base(&app_state, &headers).await;
+    Json(mcp_discovery_document(&base, auth_required))
+}
+
+/// `/.well-known/oauth-client-metadata.json` endpoint
+pub async fn client_metadata(
+    State(app_state): State<AppState>,
+    headers: axum::http::HeaderMap,
+) -> Json<ClientMetadataDocument> {
+    info!("[Gateway] Client metadata document request");
+    let (base, _) = advertised_base(&app_state, &headers).await;
+    Json(client_metadata_document(&base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "https://mcp.example.com";
+
+    #[test]
+    fn discovery_document_points_at_gateway_endpoints() {
+        let document = serde_json::to_value(mcp_discovery_document(BASE, true)).unwrap();
+        assert_eq!(document["transport"]["type"], "streamable-http");
+        assert_eq!(
+            document["transport"]["endpoint"],
+            "https://mcp.example.com/mcp"
+        );
+        assert_eq!(document["authentication"]["schemes"][0], "oauth2");
+        assert_eq!(
+            document["authentication"]["protectedResourceMetadata"],
+            "https://mcp.example.com/.well-known/oauth-protected-resource/mcp"
+        );
+        assert_eq!(
+            document["capabilities"]["tools"]["listChanged"],
+            serde_json::json!(true)
+        );
+    }
+
+    #[test]
+    fn discovery_document_without_auth_has_no_oauth_endpoints() {
+        let document = serde_json::to_value(mcp_discovery_document(BASE, false)).unwrap();
+        assert_eq!(document["authentication"]["required"], false);
+        assert!(document["authentication"]
+            .get("authorizationServerMetadata")
+            .is_none());
+        assert!(document["authentication"].get("schemes").is_none());
+    }
+
+    #[test]
+    fn client_id_is_the_document_url() {
+        let document = client_metadata_document(BASE);
+        assert_eq!(
+            document.client_id,
+            "https://mcp.example.com/.well-known/oauth-client-metadata.json"
+        );
+        assert_eq!(document.token_endpoint_auth_method, "none");
+        assert!(document.redirect_uris[0].starts_with("http://127.0.0.1:"));
+    }
+}
@@ -16,14 +16,14 @@ use tracing::{debug, info};
 ///
 /// Handles both traditional clients (DCR, pre-registered) and CIMD clients
 pub struct ClientMetadataService {
-    repository: Arc<InboundClientRepository>,
+    repository: Arc<dyn InboundClientRepository>,
     cimd_fetcher: Arc<CimdMetadataFetcher>,
 }
 
 impl ClientMetadataService {
     /// Create a new client metadata service
     pub fn new(
-        repository: Arc<InboundClientRepository>,
+        repository: Arc<dyn InboundClientRepository>,
         cimd_fetcher: Arc<CimdMetadataFetcher>,
     ) -> Self {
         Self {
@@ -142,6 +142,7 @@ impl ClientMetadataService {
             // client.
             reports_roots: false,
             roots_capability_known: false,
+            suspended: false,
         }
     }
 }
@@ -89,6 +89,16 @@
 //! tier or parameter. This is the deterministic path for clients that don't
 //! report `roots` reliably (e.g. Cursor multiplexing one MCP host across
 //! windows): the header always wins over a stale or absent reported root.
+//!
+//! # Selected Space via the `X-McpMux-Space` header
+//!
+//! A client can also select a Space for its session, with the
+//! `X-McpMux-Space` header or a `space:<id>` token scope. The OAuth middleware
+//! checks the selection against the client's grants and records it with
+//! [`SessionRootsRegistry::set_selected_space`]. A session with a selected
+//! Space resolves only within it: a workspace binding in that Space, else the
+//! client's grants there. It bypasses the roots-pending and default tiers.
+//! A client locked to a Space stays confined to it.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -103,6 +113,7 @@ use serde::Serialize;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
+use super::repository_snapshot::RepositorySnapshot;
 use super::session_roots::SessionRootsRegistry;
 
 /// How long a session that's declared (or might declare) the `roots`
@@ -177,7 +188,7 @@ pub struct FeatureSetResolverService {
     session_roots: Arc<SessionRootsRegistry>,
     /// Reads `client_grants` for the rootless Tier-2 fallback. Stored as a
     /// concrete repo (storage owns this type and there's only ever one).
-    client_repo: Arc<InboundClientRepository>,
+    client_repo: Arc<dyn InboundClientRepository>,
     /// Looks up each Space's Starter FeatureSet for the default fallback
     /// (Tier 1b / Tier 1c-after-grace / Tier 3).
     feature_set_repo: Arc<dyn FeatureSetRepository>,
@@ -189,6 +200,8 @@ pub struct FeatureSetResolverService {
     /// [`DEFAULT_PENDING_ROOTS_GRACE`]. Configurable so tests can force the
     /// post-grace path deterministically without sleeping.
     pending_grace: Duration,
+    /// Serves `client_grants` reads from memory when set
+    snapshot: Option<Arc<RepositorySnapshot>>,
 }
 
 impl FeatureSetResolverService {
@@ -196,7 +209,7 @@ impl FeatureSetResolverService {
         space_repo: Arc<dyn SpaceRepository>,
         binding_repo: Arc<dyn WorkspaceBindingRepository>,
         session_roots: Arc<SessionRootsRegistry>,
-        client_repo: Arc<InboundClientRepository>,
+        client_repo: Arc<dyn InboundClientRepository>,
         feature_set_repo: Arc<dyn FeatureSetRepository>,
         space_base_dir_repo: Arc<dyn SpaceBaseDirRepository>,
     ) -> Self {
@@ -208,6 +221,7 @@ impl FeatureSetResolverService {
             feature_set_repo,
             space_base_dir_repo,
             pending_grace: DEFAULT_PENDING_ROOTS_GRACE,
+            snapshot: None,
         }
     }
 
@@ -247,6 +261,24 @@ impl FeatureSetResolverService {
         self
     }
 
+    /// Read grants through `snapshot` instead of querying the database on
+    /// every resolution
+    pub fn with_snapshot(mut self, snapshot: Arc<RepositorySnapshot>) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
+    async fn grants_for_space(&self, client_id: &str, space_id: &str) -> Result<Vec<String>> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.grants_for_space(client_id, space_id).await,
+            None => {
+                self.client_repo
+                    .get_grants_for_space(client_id, space_id)
+                    .await
+            }
+        }
+    }
+
     /// Fall back to `space_id`'s Starter FeatureSet. `space_id` is the global
     /// default Space for rootless sessions, or a base-dir-scoped Space for an
     /// unmapped folder under that Space's base directory. Returns
@@ -371,6 +403,62 @@ impl FeatureSetResolverService {
         self.default_fallback(locked).await
     }
 
+    /// Resolve a session whose client selected Space `selected`. A header or
+    /// roots binding within `selected` picks the FeatureSet; otherwise the
+    /// client's grants in `selected` apply. Without grants (revoked since the
+    /// selection was checked) the session gets nothing rather than another
+    /// Space's tools.
+    async fn resolve_selected(
+        &self,
+        session_id: &str,
+        client_id: Option<&str>,
+        selected: Uuid,
+    ) -> Result<ResolvedFeatureSet> {
+        if let Some(roots) = self.session_roots.get(session_id) {
+            if let Some(binding) = self.mapping_binding_for_roots(&roots).await? {
+                if binding.space_id == selected {
+                    debug!(
+                        %selected,
+                        workspace_root = %binding.workspace_root,
+                        "[FeatureSetResolver] selected space — binding within selected Space",
+                    );
+                    return Ok(ResolvedFeatureSet {
+                        feature_set_ids: binding.feature_set_ids,
+                        space_id: Some(selected),
+                        source: ResolutionSource::WorkspaceBinding,
+                    });
+                }
+            }
+        }
+
+        let grants = match client_id {
+            Some(cid) => self.grants_for_space(cid, &selected.to_string()).await?,
+            None => Vec::new(),
+        };
+        if grants.is_empty() {
+            debug!(
+                %selected,
+                ?client_id,
+                "[FeatureSetResolver] selected space — no grants left — deny",
+            );
+            return Ok(ResolvedFeatureSet {
+                feature_set_ids: vec![],
+                space_id: Some(selected),
+                source: ResolutionSource::Deny,
+            });
+        }
+        debug!(
+            %selected,
+            grant_count = grants.len(),
+            "[FeatureSetResolver] selected space — resolved via ClientGrant",
+        );
+        Ok(ResolvedFeatureSet {
+            feature_set_ids: grants,
+            space_id: Some(selected),
+            source: ResolutionSource::ClientGrant,
+        })
+    }
+
     /// Borrow the session-roots registry. The notifier uses this to GC
     /// dead sessions out of the registry when reaping the corresponding
     /// peer entries — keeping both stores in sync.
@@ -422,6 +510,14 @@ impl FeatureSetResolverService {
             }
         }
 
+        // Selected Space: the client picked a Space for this session (already
+        // checked against its grants), so resolve within that Space only.
+        if let Some(sid) = session_id {
+            if let Some(selected) = self.session_roots.get_selected_space(sid) {
+                return self.resolve_selected(sid, client_id, selected).await;
+            }
+        }
+
         // Tier 1 / 1b / 1c — branches on roots-capable + roots-arrived state.
         if let Some(sid) = session_id {
             let roots = self.session_roots.get(sid);
@@ -565,8 +661,7 @@ impl FeatureSetResolverService {
             // fingerprint and fire a spurious Deny→Grant flip-notification
             // cycle once the error clears).
             let grants = self
-                .client_repo
-                .get_grants_for_space(cid, &default_space_id.to_string())
+                .grants_for_space(cid, &default_space_id.to_string())
                 .await?;
             if !grants.is_empty() {
                 debug!(
@@ -0,0 +1,221 @@
+//! Gateway federation
+//!
+//! A McpMux gateway can be installed as a backend of another one (a team
+//! gateway aggregating personal ones, or the other way round). Every gateway
+//! advertises itself under the experimental `mcpmux/federation` capability:
+//! a per-process instance ID, plus the IDs of every gateway it reaches through
+//! its own federated backends.
+//!
+//! When a backend advertises the capability:
+//! - it is refused if it is this gateway, or already aggregates it, since
+//!   chaining them would make requests recurse forever
+//! - otherwise it is recorded, so its tool names (already qualified by the
+//!   inner gateway) can be flattened instead of gaining a second prefix
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::RwLock;
+
+use rmcp::model::ServerCapabilities;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Experimental capability key the gateway advertises itself under
+pub const FEDERATION_CAPABILITY: &str = "mcpmux/federation";
+
+/// What a gateway advertises about itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FederationAdvertisement {
+    /// ID of the advertising gateway process
+    pub instance_id: String,
+    /// IDs of every gateway reachable through its federated backends
+    #[serde(default)]
+    pub upstream: Vec<String>,
+}
+
+impl FederationAdvertisement {
+    /// Read the advertisement from a backend's capabilities, if it is a gateway
+    pub fn from_capabilities(capabilities: &ServerCapabilities) -> Option<Self> {
+        let value = capabilities
+            .experimental
+            .as_ref()?
+            .get(FEDERATION_CAPABILITY)?;
+        serde_json::from_value(serde_json::Value::Object(value.clone())).ok()
+    }
+}
+
+/// A backend that would chain the gateway behind itself
+#[derive(Debug, Clone, Error)]
+#[error("Federation loop: '{server_id}' is this gateway, or already aggregates it")]
+pub struct FederationLoop {
+    pub server_id: String,
+}
+
+/// Tracks federated backends and detects loops
+pub struct FederationService {
+    instance_id: String,
+    /// (space_id, server_id) -> what that backend advertised
+    backends: RwLock<HashMap<(String, String), FederationAdvertisement>>,
+}
+
+impl FederationService {
+    pub fn new() -> Self {
+        Self::with_instance_id(Uuid::new_v4().to_string())
+    }
+
+    pub fn with_instance_id(instance_id: impl Into<String>) -> Self {
+        Self {
+            instance_id: instance_id.into(),
+            backends: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// This gateway's advertisement: its ID and everything behind it
+    pub fn advertisement(&self) -> FederationAdvertisement {
+        let backends = self.backends.read().unwrap_or_else(|e| e.into_inner());
+        let upstream: BTreeSet<String> = backends
+            .values()
+            .flat_map(|ad| std::iter::once(&ad.instance_id).chain(ad.upstream.iter()))
+            .cloned()
+            .collect();
+        FederationAdvertisement {
+            instance_id: self.instance_id.clone(),
+            upstream: upstream.into_iter().collect(),
+        }
+    }
+
+    /// Add the federation capability to the gateway's own capabilities
+    pub fn advertise(&self, capabilities: &mut ServerCapabilities) {
+        let serde_json::Value::Object(object) =
+            serde_json::to_value(self.advertisement()).unwrap_or_default()
+        else {
+            return;
+        };
+        capabilities
+            .experimental
+            .get_or_insert_with(Default::default)
+            .insert(FEDERATION_CAPABILITY.to_string(), object);
+    }
+
+    /// Record a backend that is a gateway, refusing one that would form a loop
+    pub fn register(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        advertisement: FederationAdvertisement,
+    ) -> Result<(), FederationLoop> {
+        if advertisement.instance_id == self.instance_id
+            || advertisement.upstream.contains(&self.instance_id)
+        {
+            warn!(
+                "[Federation] Refusing {}/{}: it is this gateway or chained behind it",
+                space_id, server_id
+            );
+            return Err(FederationLoop {
+                server_id: server_id.to_string(),
+            });
+        }
+
+        info!(
+            "[Federation] {}/{} is gateway {} ({} upstream)",
+            space_id,
+            server_id,
+            advertisement.instance_id,
+            advertisement.upstream.len()
+        );
+        self.backends
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((space_id.to_string(), server_id.to_string()), advertisement);
+        Ok(())
+    }
+
+    /// Forget a backend (disabled or uninstalled)
+    pub fn unregister(&self, space_id: &str, server_id: &str) {
+        self.backends
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(space_id.to_string(), server_id.to_string()));
+    }
+
+    /// Whether a backend is another McpMux gateway
+    pub fn is_federated(&self, space_id: &str, server_id: &str) -> bool {
+        self.backends
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(&(space_id.to_string(), server_id.to_string()))
+    }
+}
+
+impl Default for FederationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ad(instance_id: &str, upstream: &[&str]) -> FederationAdvertisement {
+        FederationAdvertisement {
+            instance_id: instance_id.to_string(),
+            upstream: upstream.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_itself_and_gateways_that_aggregate_it() {
+        let service = FederationService::with_instance_id("team");
+
+        assert!(service.register("s", "self", ad("team", &[])).is_err());
+        assert!(service
+            .register("s", "personal", ad("personal", &["laptop", "team"]))
+            .is_err());
+        assert!(!service.is_federated("s", "personal"));
+
+        service
+            .register("s", "personal", ad("personal", &["laptop"]))
+            .unwrap();
+        assert!(service.is_federated("s", "personal"));
+    }
+
+    #[test]
+    fn advertisement_includes_transitive_upstream() {
+        let service = FederationService::with_instance_id("team");
+        service
+            .register("s", "alice", ad("alice", &["alice-laptop"]))
+            .unwrap();
+        service.register("s", "bob", ad("bob", &[])).unwrap();
+        assert_eq!(
+            service.advertisement(),
+            ad("team", &["alice", "alice-laptop", "bob"])
+        );
+
+        service.unregister("s", "alice");
+        assert_eq!(service.advertisement(), ad("team", &["bob"]));
+    }
+
+    #[test]
+    fn advertisement_round_trips_through_capabilities() {
+        let service = FederationService::with_instance_id("team");
+        service.register("s", "bob", ad("bob", &[])).unwrap();
+
+        let mut capabilities = ServerCapabilities::default();
+        service.advertise(&mut capabilities);
+        assert_eq!(
+            FederationAdvertisement::from_capabilities(&capabilities),
+            Some(service.advertisement())
+        );
+        assert_eq!(
+            FederationAdvertisement::from_capabilities(&ServerCapabilities::default()),
+            None
+        );
+    }
+}
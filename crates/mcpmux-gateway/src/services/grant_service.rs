@@ -27,7 +27,7 @@ use uuid::Uuid;
 /// Grant management with automatic event emission.
 pub struct GrantService {
     /// OAuth client grant repository (concrete; storage-owned).
-    client_repo: Arc<InboundClientRepository>,
+    client_repo: Arc<dyn InboundClientRepository>,
     /// Feature set lookup for member-change notifications.
     feature_set_repo: Arc<dyn FeatureSetRepository>,
     /// Domain event broadcaster.
@@ -36,7 +36,7 @@ pub struct GrantService {
 
 impl GrantService {
     pub fn new(
-        client_repo: Arc<InboundClientRepository>,
+        client_repo: Arc<dyn InboundClientRepository>,
         feature_set_repo: Arc<dyn FeatureSetRepository>,
         event_tx: broadcast::Sender<DomainEvent>,
     ) -> Self {
@@ -9,10 +9,14 @@ mod authorization;
 mod client_metadata_service;
 mod event_emitter;
 mod feature_set_resolver;
+mod federation;
 mod grant_service;
 pub mod meta_tools;
 mod notification_emitter;
+mod package_updates;
 mod prefix_cache;
+mod repository_snapshot;
+mod session_activity;
 mod session_roots;
 mod space_resolver;
 
@@ -20,12 +24,21 @@ pub use authorization::AuthorizationService;
 pub use client_metadata_service::ClientMetadataService;
 pub use event_emitter::EventEmitter;
 pub use feature_set_resolver::{FeatureSetResolverService, ResolutionSource, ResolvedFeatureSet};
+pub use federation::{
+    FederationAdvertisement, FederationLoop, FederationService, FEDERATION_CAPABILITY,
+};
 pub use grant_service::GrantService;
 pub use meta_tools::{
     is_meta_tool, ApprovalBroker, ApprovalDecision, ApprovalPayload, ApprovalPublisher,
     ApprovalRequest, ApprovalScope, MetaToolRegistry, MCPMUX_PREFIX,
 };
 pub use notification_emitter::NotificationEmitter;
+pub use package_updates::{PackageUpdate, PackageUpdateService, DEFAULT_UPDATE_CHECK_INTERVAL};
 pub use prefix_cache::PrefixCacheService;
+pub use repository_snapshot::{RepositorySnapshot, SNAPSHOT_MAX_AGE};
+pub use session_activity::{
+    RecentToolCall, RequestActivity, SessionActivity, SessionActivityRegistry, SessionInfo,
+    RECENT_TOOL_CALLS,
+};
 pub use session_roots::SessionRootsRegistry;
-pub use space_resolver::SpaceResolverService;
+pub use space_resolver::{SpaceResolverService, SpaceSelectionError};
@@ -0,0 +1,346 @@
+//! Package version pinning and update checks for runner-launched servers.
+//!
+//! A server started as `npx -y pkg` or `uvx pkg` runs whatever version the
+//! registry serves that day. The first time such a server connects, its
+//! version is recorded on the installation
+//! ([`InstalledServer::package_version`]) and pinned on every later launch.
+//! A periodic check asks npm/PyPI for the latest release and emits
+//! `ServerUpdateAvailable`; [`PackageUpdateService::update_package`] re-pins
+//! to it, and the runner fetches the new version on the next connect.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use mcpmux_core::{ConnectionStatus, DomainEvent, InstalledServer, InstalledServerRepository};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::pool::transport::resolution::build_transport_config;
+use crate::pool::transport::runtime::{PackageEcosystem, RunnerPackage};
+use crate::pool::ResolvedTransport;
+
+/// How often installed packages are checked for new releases
+pub const DEFAULT_UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Delay before the first check, so it doesn't compete with startup
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// A newer release of an installed server's package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageUpdate {
+    pub space_id: Uuid,
+    pub server_id: String,
+    pub ecosystem: PackageEcosystem,
+    pub package: String,
+    pub current_version: String,
+    pub latest_version: String,
+}
+
+impl PackageUpdate {
+    fn to_event(&self) -> DomainEvent {
+        DomainEvent::ServerUpdateAvailable {
+            space_id: self.space_id,
+            server_id: self.server_id.clone(),
+            ecosystem: self.ecosystem.as_str().to_string(),
+            package: self.package.clone(),
+            current_version: self.current_version.clone(),
+            latest_version: self.latest_version.clone(),
+        }
+    }
+}
+
+/// Records, checks and bumps pinned package versions
+pub struct PackageUpdateService {
+    installed_server_repo: Arc<dyn InstalledServerRepository>,
+    event_tx: broadcast::Sender<DomainEvent>,
+    http: reqwest::Client,
+}
+
+impl PackageUpdateService {
+    pub fn new(
+        installed_server_repo: Arc<dyn InstalledServerRepository>,
+        event_tx: broadcast::Sender<DomainEvent>,
+    ) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent("McpMux/1.0")
+            .timeout(Duration::from_secs(15))
+            .build()
+            .unwrap_or_default();
+        Self {
+            installed_server_repo,
+            event_tx,
+            http,
+        }
+    }
+
+    /// The npm/PyPI package an installation runs, if it runs one
+    fn runner_package(installed: &InstalledServer) -> Option<RunnerPackage> {
+        let definition = installed.get_definition()?;
+        match build_transport_config(&definition.transport, installed, None) {
+            ResolvedTransport::Stdio { command, args, .. } => {
+                RunnerPackage::from_command(&command, &args)
+            }
+            ResolvedTransport::Http { .. }
+            | ResolvedTransport::OpenApi { .. }
+            | ResolvedTransport::GraphQl { .. }
+            | ResolvedTransport::Builtin { .. }
+            | ResolvedTransport::Wasm(_) => None,
+        }
+    }
+
+    /// Latest published version of a package
+    pub async fn latest_version(&self, ecosystem: PackageEcosystem, name: &str) -> Result<String> {
+        let url = match ecosystem {
+            PackageEcosystem::Npm => format!(
+                "https://registry.npmjs.org/{}/latest",
+                name.replace('/', "%2F")
+            ),
+            PackageEcosystem::Pypi => format!("https://pypi.org/pypi/{}/json", name),
+        };
+        let body: serde_json::Value = self
+            .http
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let version = match ecosystem {
+            PackageEcosystem::Npm => body.get("version"),
+            PackageEcosystem::Pypi => body.pointer("/info/version"),
+        };
+        version
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("{} returned no version for {}", ecosystem.as_str(), name))
+    }
+
+    /// Pin a server to the version it runs, unless it's already pinned.
+    ///
+    /// An exact version in the definition is kept; an unversioned package is
+    /// pinned to the current latest release. Ranges and dist-tags are left
+    /// for the runner to resolve. Returns the recorded version.
+    pub async fn record_version(&self, space_id: Uuid, server_id: &str) -> Result<Option<String>> {
+        let Some(mut installed) = self
+            .installed_server_repo
+            .get_by_server_id(&space_id.to_string(), server_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if installed.package_version.is_some() {
+            return Ok(None);
+        }
+        let Some(package) = Self::runner_package(&installed) else {
+            return Ok(None);
+        };
+        let version = match (&package.package.version, package.package.exact_version()) {
+            (_, Some(exact)) => exact.to_string(),
+            (None, None) => {
+                self.latest_version(package.runner.ecosystem(), &package.package.name)
+                    .await?
+            }
+            (Some(_), None) => return Ok(None),
+        };
+
+        installed.package_version = Some(version.clone());
+        installed.updated_at = chrono::Utc::now();
+        self.installed_server_repo.update(&installed).await?;
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            "[PackageUpdates] Pinned {} to {}",
+            package.package.name,
+            version
+        );
+        Ok(Some(version))
+    }
+
+    /// Look for newer releases of every pinned package, emitting
+    /// `ServerUpdateAvailable` for each one found
+    pub async fn check_for_updates(&self) -> Result<Vec<PackageUpdate>> {
+        let mut latest_by_package: HashMap<(PackageEcosystem, String), Option<String>> =
+            HashMap::new();
+        let mut updates = Vec::new();
+
+        for installed in self.installed_server_repo.list().await? {
+            let Some(current) = installed.package_version.clone() else {
+                continue;
+            };
+            let Some(package) = Self::runner_package(&installed) else {
+                continue;
+            };
+            let ecosystem = package.runner.ecosystem();
+            let key = (ecosystem, package.package.name.clone());
+            let latest = match latest_by_package.get(&key) {
+                Some(latest) => latest.clone(),
+                None => {
+                    let latest = match self.latest_version(ecosystem, &key.1).await {
+                        Ok(latest) => Some(latest),
+                        Err(e) => {
+                            warn!("[PackageUpdates] Failed to check {}: {}", key.1, e);
+                            None
+                        }
+                    };
+                    latest_by_package.insert(key.clone(), latest.clone());
+                    latest
+                }
+            };
+            let Some(latest) = latest.filter(|latest| is_newer(latest, &current)) else {
+                continue;
+            };
+
+            let Ok(space_id) = Uuid::parse_str(&installed.space_id) else {
+                continue;
+            };
+            let update = PackageUpdate {
+                space_id,
+                server_id: installed.server_id.clone(),
+                ecosystem,
+                package: key.1,
+                current_version: current,
+                latest_version: latest,
+            };
+            let _ = self.event_tx.send(update.to_event());
+            updates.push(update);
+        }
+
+        info!("[PackageUpdates] {} update(s) available", updates.len());
+        Ok(updates)
+    }
+
+    /// Pin a server to the latest release of its package. The new version
+    /// is fetched by the runner the next time the server connects.
+    pub async fn update_package(&self, space_id: Uuid, server_id: &str) -> Result<String> {
+        let mut installed = self
+            .installed_server_repo
+            .get_by_server_id(&space_id.to_string(), server_id)
+            .await?
+            .ok_or_else(|| anyhow!("Server not installed"))?;
+        let package = Self::runner_package(&installed)
+            .ok_or_else(|| anyhow!("Server doesn't run an npm or PyPI package"))?;
+        let latest = self
+            .latest_version(package.runner.ecosystem(), &package.package.name)
+            .await?;
+
+        let previous = installed.package_version.replace(latest.clone());
+        installed.updated_at = chrono::Utc::now();
+        self.installed_server_repo.update(&installed).await?;
+        info!(
+            space_id = %space_id,
+            server_id = server_id,
+            "[PackageUpdates] Updated {} from {} to {}",
+            package.package.name,
+            previous.as_deref().unwrap_or("unpinned"),
+            latest
+        );
+
+        let _ = self.event_tx.send(DomainEvent::ServerConfigUpdated {
+            space_id,
+            server_id: server_id.to_string(),
+        });
+        Ok(latest)
+    }
+
+    /// Record versions as servers connect, and check for updates every
+    /// `check_interval`. Runs until the event channel closes.
+    pub fn start(
+        self: Arc<Self>,
+        mut event_rx: broadcast::Receiver<DomainEvent>,
+        check_interval: Duration,
+    ) {
+        tokio::spawn(async move {
+            let mut checks = tokio::time::interval_at(
+                tokio::time::Instant::now() + FIRST_CHECK_DELAY,
+                check_interval,
+            );
+            checks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    event = event_rx.recv() => match event {
+                        Ok(DomainEvent::ServerStatusChanged {
+                            space_id,
+                            server_id,
+                            status: ConnectionStatus::Connected,
+                            ..
+                        }) => {
+                            let service = self.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = service.record_version(space_id, &server_id).await {
+                                    warn!(
+                                        server_id = %server_id,
+                                        "[PackageUpdates] Failed to record package version: {}",
+                                        e
+                                    );
+                                }
+                            });
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            debug!("[PackageUpdates] Lagged behind, skipped {} events", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = checks.tick() => {
+                        if let Err(e) = self.check_for_updates().await {
+                            warn!("[PackageUpdates] Update check failed: {}", e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Whether `latest` is a later release than `current`. Numeric components
+/// are compared in order; a release beats a pre-release of the same number.
+fn is_newer(latest: &str, current: &str) -> bool {
+    fn key(version: &str) -> (Vec<u64>, bool) {
+        let end = version
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(version.len());
+        let numbers = version[..end]
+            .split('.')
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        (numbers, end == version.len())
+    }
+    key(latest) > key(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_release_versions() {
+        assert!(is_newer("1.10.0", "1.9.3"));
+        assert!(is_newer("2.0.0", "2.0.0-beta.2"));
+        assert!(is_newer("0.6.3", "0.6.2"));
+        assert!(!is_newer("1.2.0", "1.2.0"));
+        assert!(!is_newer("1.2.0", "1.3.0rc1"));
+    }
+
+    #[test]
+    fn finds_the_package_an_installation_runs() {
+        let definition: mcpmux_core::ServerDefinition = serde_json::from_value(serde_json::json!({
+            "id": "time",
+            "name": "Time",
+            "transport": { "type": "stdio", "command": "uvx", "args": ["mcp-server-time"] }
+        }))
+        .expect("definition");
+        let mut installed = InstalledServer::new("space", "time").with_definition(&definition);
+
+        let package = PackageUpdateService::runner_package(&installed).unwrap();
+        assert_eq!(package.package.name, "mcp-server-time");
+        assert_eq!(package.package.exact_version(), None);
+
+        installed.package_version = Some("0.6.2".to_string());
+        let package = PackageUpdateService::runner_package(&installed).unwrap();
+        assert_eq!(package.package.exact_version(), Some("0.6.2"));
+    }
+}
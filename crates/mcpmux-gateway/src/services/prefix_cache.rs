@@ -7,13 +7,20 @@
 //! - Runtime: Stable, first-come-first-served, no stealing
 //!
 //! This prevents client confusion from prefix changes during active connections.
+//!
+//! Features of a federated McpMux gateway are already qualified by that
+//! gateway (`gh_get_me`). Their inner prefixes are claimed as "flat" prefixes
+//! routing to the federated server, so they keep their names instead of
+//! becoming `team_gh_get_me`. An inner prefix that is already taken stays
+//! nested under the federated server's own prefix.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
-use mcpmux_core::{InstalledServerRepository, ServerDiscoveryService};
+use super::FederationService;
+use mcpmux_core::{FeatureType, InstalledServerRepository, ServerDiscoveryService, ServerFeature};
 
 /// Bidirectional cache mapping between server IDs and prefixes
 ///
@@ -25,6 +32,9 @@ struct SpacePrefixCache {
 
     /// Reverse: resolved prefix -> server_id (for routing)
     prefix_to_server: HashMap<String, String>,
+
+    /// Prefixes claimed by federated gateways for their own features
+    flat_prefixes: HashSet<String>,
 }
 
 impl SpacePrefixCache {
@@ -39,8 +49,31 @@ impl SpacePrefixCache {
         self.prefix_to_server.insert(prefix, server_id);
     }
 
+    /// Route a federated gateway's inner prefix to it, unchanged
+    fn claim_flat(&mut self, server_id: &str, prefix: String) {
+        self.prefix_to_server
+            .insert(prefix.clone(), server_id.to_string());
+        self.flat_prefixes.insert(prefix);
+    }
+
+    /// Whether a prefix is a federated gateway's inner prefix
+    fn is_flat(&self, prefix: &str) -> bool {
+        self.flat_prefixes.contains(prefix)
+    }
+
     /// Remove a server's prefix assignment (bidirectional remove)
     fn remove(&mut self, server_id: &str) -> Option<String> {
+        let flat: Vec<String> = self
+            .flat_prefixes
+            .iter()
+            .filter(|p| self.prefix_to_server.get(*p).map(String::as_str) == Some(server_id))
+            .cloned()
+            .collect();
+        for prefix in flat {
+            self.prefix_to_server.remove(&prefix);
+            self.flat_prefixes.remove(&prefix);
+        }
+
         if let Some(prefix) = self.server_to_prefix.remove(server_id) {
             self.prefix_to_server.remove(&prefix);
             Some(prefix)
@@ -80,6 +113,9 @@ pub struct PrefixCacheService {
 
     /// Server discovery service (for getting server definitions)
     server_discovery: Option<Arc<ServerDiscoveryService>>,
+
+    /// Backends that are themselves McpMux gateways
+    federation: Arc<FederationService>,
 }
 
 impl PrefixCacheService {
@@ -88,9 +124,15 @@ impl PrefixCacheService {
             caches: Arc::new(RwLock::new(HashMap::new())),
             installed_server_repo: None,
             server_discovery: None,
+            federation: Arc::new(FederationService::new()),
         }
     }
 
+    /// Federation registry (which backends are McpMux gateways)
+    pub fn federation(&self) -> &Arc<FederationService> {
+        &self.federation
+    }
+
     /// Set dependencies (for startup resolution)
     pub fn with_dependencies(
         mut self,
@@ -297,8 +339,67 @@ impl PrefixCacheService {
             .await
     }
 
+    /// Claim a federated gateway's inner prefixes so its features keep their names
+    ///
+    /// Prefixes already taken by another server are skipped; those features
+    /// stay nested under the gateway's own prefix. Returns the claimed ones.
+    pub async fn claim_flat_prefixes(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        prefixes: impl IntoIterator<Item = String>,
+    ) -> Vec<String> {
+        let mut caches = self.caches.write().await;
+        let cache = caches
+            .entry(space_id.to_string())
+            .or_insert_with(SpacePrefixCache::new);
+
+        let mut claimed = Vec::new();
+        for prefix in prefixes.into_iter().collect::<HashSet<_>>() {
+            match cache.get_server(&prefix).map(str::to_string) {
+                Some(owner) if owner == server_id && cache.is_flat(&prefix) => {}
+                Some(owner) => {
+                    info!(
+                        "[PrefixCache] Prefix '{}' of federated server {} is taken by {} in space {}, keeping it nested",
+                        prefix, server_id, owner, space_id
+                    );
+                    continue;
+                }
+                None => cache.claim_flat(server_id, prefix.clone()),
+            }
+            claimed.push(prefix);
+        }
+        claimed
+    }
+
+    /// Alias to qualify a feature with (for tools/list)
+    ///
+    /// Empty for features of a federated gateway whose inner prefix was
+    /// claimed, so their already-qualified names are used as is.
+    pub async fn alias_for_feature(&self, space_id: &str, feature: &ServerFeature) -> String {
+        if matches!(
+            feature.feature_type,
+            FeatureType::Tool | FeatureType::Prompt
+        ) {
+            if let Some((inner, _)) = feature.feature_name.split_once('_') {
+                let caches = self.caches.read().await;
+                if let Some(cache) = caches.get(space_id) {
+                    if cache.is_flat(inner)
+                        && cache.get_server(inner) == Some(feature.server_id.as_str())
+                    {
+                        return String::new();
+                    }
+                }
+            }
+        }
+        self.get_prefix_for_server(space_id, &feature.server_id)
+            .await
+    }
+
     /// Release a server's prefix (runtime only - no reassignment)
     pub async fn release_prefix_runtime(&self, space_id: &str, server_id: &str) {
+        self.federation.unregister(space_id, server_id);
+
         let mut caches = self.caches.write().await;
 
         if let Some(cache) = caches.get_mut(space_id) {
@@ -330,6 +431,18 @@ impl PrefixCacheService {
         // Split on first underscore - this is unambiguous because prefixes cannot contain underscores
         let (prefix, feature_name) = qualified_name.split_once('_')?;
 
+        // A federated gateway's flattened feature keeps its whole name
+        {
+            let caches = self.caches.read().await;
+            if let Some(cache) = caches.get(space_id) {
+                if cache.is_flat(prefix) {
+                    if let Some(server_id) = cache.get_server(prefix) {
+                        return Some((server_id.to_string(), qualified_name.to_string()));
+                    }
+                }
+            }
+        }
+
         // Resolve prefix to server_id
         let server_id = self
             .get_server_for_prefix(space_id, prefix)
@@ -464,4 +577,91 @@ mod tests {
         let result = service.resolve_qualified_name(space_id, "gh_get_me").await;
         assert_eq!(result, Some((server_id.to_string(), "get_me".to_string())));
     }
+
+    #[tokio::test]
+    async fn test_federated_prefixes_are_flattened() {
+        let service = PrefixCacheService::new();
+        let space_id = "test-space";
+
+        service
+            .assign_prefix_runtime(space_id, "slack-server", Some("slack"))
+            .await;
+        service
+            .assign_prefix_runtime(space_id, "team-gateway", Some("team"))
+            .await;
+
+        // "slack" is taken locally, so only "gh" is flattened
+        let claimed = service
+            .claim_flat_prefixes(
+                space_id,
+                "team-gateway",
+                ["gh".to_string(), "slack".to_string()],
+            )
+            .await;
+        assert_eq!(claimed, vec!["gh".to_string()]);
+
+        let flat = ServerFeature::tool(space_id, "team-gateway", "gh_get_me");
+        let alias = service.alias_for_feature(space_id, &flat).await;
+        assert_eq!(
+            flat.with_server_alias(Some(alias)).qualified_name(),
+            "gh_get_me"
+        );
+
+        let nested = ServerFeature::tool(space_id, "team-gateway", "slack_post");
+        let alias = service.alias_for_feature(space_id, &nested).await;
+        assert_eq!(
+            nested.with_server_alias(Some(alias)).qualified_name(),
+            "team_slack_post"
+        );
+
+        // Both forms route to the gateway with the name it knows
+        assert_eq!(
+            service.resolve_qualified_name(space_id, "gh_get_me").await,
+            Some(("team-gateway".to_string(), "gh_get_me".to_string()))
+        );
+        assert_eq!(
+            service
+                .resolve_qualified_name(space_id, "team_slack_post")
+                .await,
+            Some(("team-gateway".to_string(), "slack_post".to_string()))
+        );
+
+        // Releasing the gateway frees its flat prefixes
+        service
+            .release_prefix_runtime(space_id, "team-gateway")
+            .await;
+        assert!(service.is_prefix_available(space_id, "gh").await);
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn qualified_names_resolve_back_to_their_feature(
+                server_id in "[a-z0-9][a-z0-9./-]{0,15}",
+                alias in proptest::option::of("[a-z0-9][a-z0-9-]{0,7}"),
+                feature_name in "[a-zA-Z0-9_-]{1,16}",
+            ) {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .build()
+                    .unwrap();
+                let (prefix, resolved) = runtime.block_on(async {
+                    let service = PrefixCacheService::new();
+                    let prefix = service
+                        .assign_prefix_runtime("space", &server_id, alias.as_deref())
+                        .await;
+                    let feature = ServerFeature::tool("space", &server_id, &feature_name)
+                        .with_server_alias(Some(prefix.clone()));
+                    let resolved = service
+                        .resolve_qualified_name("space", &feature.qualified_name())
+                        .await;
+                    (prefix, resolved)
+                });
+                prop_assert!(!prefix.contains('_'));
+                prop_assert_eq!(resolved, Some((server_id, feature_name)));
+            }
+        }
+    }
 }
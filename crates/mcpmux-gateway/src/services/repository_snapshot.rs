@@ -0,0 +1,662 @@
+//! Repository snapshot
+//!
+//! Every `tools/call` resolves the caller's FeatureSets, their members and
+//! the features of the Space before it can route, and each of those used to
+//! be a SQLite query behind the global database lock. [`RepositorySnapshot`]
+//! keeps the answers in memory:
+//!
+//! - features per Space (which server provides which feature),
+//! - FeatureSets with their members, and each Space's Starter,
+//! - client grants per `(client, Space)`.
+//!
+//! Writes made through the snapshot's repositories clear it straight away,
+//! and so does any domain event that can change those answers. Writes from
+//! outside the gateway (the CLI, another process) emit no events here, so
+//! no answer is kept longer than [`SNAPSHOT_MAX_AGE`].
+
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use mcpmux_core::{
+    DomainEvent, FeatureSet, FeatureSetMember, FeatureSetRepository, MemberMode, RepoResult,
+    ServerFeature, ServerFeatureRepository,
+};
+use mcpmux_storage::InboundClientRepository;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Longest a snapshot answer is used without going back to the database
+pub const SNAPSHOT_MAX_AGE: Duration = Duration::from_secs(10);
+
+struct Cached<T> {
+    value: T,
+    generation: u64,
+    loaded_at: Instant,
+}
+
+/// In-memory answers to the repository reads on the routing hot path
+pub struct RepositorySnapshot {
+    feature_repo: Arc<dyn ServerFeatureRepository>,
+    feature_set_repo: Arc<dyn FeatureSetRepository>,
+    client_repo: Arc<dyn InboundClientRepository>,
+    /// Bumped on every invalidation; answers loaded under an older
+    /// generation are never used
+    generation: AtomicU64,
+    features: DashMap<String, Cached<Vec<ServerFeature>>>,
+    feature_sets: DashMap<String, Cached<Option<FeatureSet>>>,
+    starters: DashMap<String, Cached<Option<FeatureSet>>>,
+    grants: DashMap<(String, String), Cached<Vec<String>>>,
+}
+
+impl RepositorySnapshot {
+    pub fn new(
+        feature_repo: Arc<dyn ServerFeatureRepository>,
+        feature_set_repo: Arc<dyn FeatureSetRepository>,
+        client_repo: Arc<dyn InboundClientRepository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            feature_repo,
+            feature_set_repo,
+            client_repo,
+            generation: AtomicU64::new(0),
+            features: DashMap::new(),
+            feature_sets: DashMap::new(),
+            starters: DashMap::new(),
+            grants: DashMap::new(),
+        })
+    }
+
+    /// A [`ServerFeatureRepository`] that reads through the snapshot
+    pub fn feature_repo(self: &Arc<Self>) -> Arc<dyn ServerFeatureRepository> {
+        Arc::new(SnapshotFeatureRepository(self.clone()))
+    }
+
+    /// A [`FeatureSetRepository`] that reads through the snapshot
+    pub fn feature_set_repo(self: &Arc<Self>) -> Arc<dyn FeatureSetRepository> {
+        Arc::new(SnapshotFeatureSetRepository(self.clone()))
+    }
+
+    /// Clear the snapshot whenever an event may have changed what it holds
+    pub fn start(self: &Arc<Self>, mut events: broadcast::Receiver<DomainEvent>) {
+        let snapshot = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) if invalidates_snapshot(&event) => {
+                        debug!(
+                            event = event.type_name(),
+                            "[RepositorySnapshot] invalidated"
+                        );
+                        snapshot.invalidate();
+                    }
+                    Ok(_) => {}
+                    // Missed events may have been relevant
+                    Err(RecvError::Lagged(_)) => snapshot.invalidate(),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Drop every answer; the next reads go to the database
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.features.clear();
+        self.feature_sets.clear();
+        self.starters.clear();
+        self.grants.clear();
+    }
+
+    /// Granted FeatureSet ids for a `(client, Space)` pair
+    pub async fn grants_for_space(
+        &self,
+        client_id: &str,
+        space_id: &str,
+    ) -> RepoResult<Vec<String>> {
+        self.read(
+            &self.grants,
+            (client_id.to_string(), space_id.to_string()),
+            || self.client_repo.get_grants_for_space(client_id, space_id),
+        )
+        .await
+    }
+
+    async fn read<K, T, F, Fut>(
+        &self,
+        map: &DashMap<K, Cached<T>>,
+        key: K,
+        load: F,
+    ) -> RepoResult<T>
+    where
+        K: Eq + Hash,
+        T: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = RepoResult<T>>,
+    {
+        let generation = self.generation.load(Ordering::Acquire);
+        if let Some(cached) = map.get(&key) {
+            if cached.generation == generation && cached.loaded_at.elapsed() < SNAPSHOT_MAX_AGE {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let value = load().await?;
+        // An invalidation during the load may have made `value` stale
+        if self.generation.load(Ordering::Acquire) == generation {
+            map.insert(
+                key,
+                Cached {
+                    value: value.clone(),
+                    generation,
+                    loaded_at: Instant::now(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Clear the snapshot after a write, so no later read misses it
+    fn written<T>(&self, result: RepoResult<T>) -> RepoResult<T> {
+        self.invalidate();
+        result
+    }
+}
+
+/// Whether `event` can change features, FeatureSets or grants
+fn invalidates_snapshot(event: &DomainEvent) -> bool {
+    event.affects_mcp_capabilities()
+        || matches!(
+            event,
+            DomainEvent::SpaceCreated { .. }
+                | DomainEvent::SpaceUpdated { .. }
+                | DomainEvent::ServerInstalled { .. }
+                | DomainEvent::ServerUninstalled { .. }
+                | DomainEvent::ServerConfigUpdated { .. }
+                | DomainEvent::ServerEnabled { .. }
+                | DomainEvent::ServerDisabled { .. }
+                | DomainEvent::FeatureSetCreated { .. }
+                | DomainEvent::FeatureSetUpdated { .. }
+                | DomainEvent::ClientUpdated { .. }
+                | DomainEvent::ClientDeleted { .. }
+        )
+}
+
+struct SnapshotFeatureRepository(Arc<RepositorySnapshot>);
+
+#[async_trait]
+impl ServerFeatureRepository for SnapshotFeatureRepository {
+    async fn list_for_space(&self, space_id: &str) -> RepoResult<Vec<ServerFeature>> {
+        let snapshot = &self.0;
+        snapshot
+            .read(&snapshot.features, space_id.to_string(), || {
+                snapshot.feature_repo.list_for_space(space_id)
+            })
+            .await
+    }
+
+    async fn list_for_server(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> RepoResult<Vec<ServerFeature>> {
+        let mut features = self.list_for_space(space_id).await?;
+        features.retain(|f| f.server_id == server_id);
+        Ok(features)
+    }
+
+    async fn get(&self, id: &Uuid) -> RepoResult<Option<ServerFeature>> {
+        self.0.feature_repo.get(id).await
+    }
+
+    async fn upsert(&self, feature: &ServerFeature) -> RepoResult<()> {
+        let result = self.0.feature_repo.upsert(feature).await;
+        self.0.written(result)
+    }
+
+    async fn upsert_many(&self, features: &[ServerFeature]) -> RepoResult<()> {
+        let result = self.0.feature_repo.upsert_many(features).await;
+        self.0.written(result)
+    }
+
+    async fn delete(&self, id: &Uuid) -> RepoResult<()> {
+        let result = self.0.feature_repo.delete(id).await;
+        self.0.written(result)
+    }
+
+    async fn mark_unavailable(&self, space_id: &str, server_id: &str) -> RepoResult<()> {
+        let result = self
+            .0
+            .feature_repo
+            .mark_unavailable(space_id, server_id)
+            .await;
+        self.0.written(result)
+    }
+
+    async fn delete_for_server(&self, space_id: &str, server_id: &str) -> RepoResult<()> {
+        let result = self
+            .0
+            .feature_repo
+            .delete_for_server(space_id, server_id)
+            .await;
+        self.0.written(result)
+    }
+}
+
+struct SnapshotFeatureSetRepository(Arc<RepositorySnapshot>);
+
+#[async_trait]
+impl FeatureSetRepository for SnapshotFeatureSetRepository {
+    async fn list(&self) -> RepoResult<Vec<FeatureSet>> {
+        self.0.feature_set_repo.list().await
+    }
+
+    async fn list_by_space(&self, space_id: &str) -> RepoResult<Vec<FeatureSet>> {
+        self.0.feature_set_repo.list_by_space(space_id).await
+    }
+
+    async fn get(&self, id: &str) -> RepoResult<Option<FeatureSet>> {
+        self.0.feature_set_repo.get(id).await
+    }
+
+    async fn get_with_members(&self, id: &str) -> RepoResult<Option<FeatureSet>> {
+        let snapshot = &self.0;
+        snapshot
+            .read(&snapshot.feature_sets, id.to_string(), || {
+                snapshot.feature_set_repo.get_with_members(id)
+            })
+            .await
+    }
+
+    async fn create(&self, feature_set: &FeatureSet) -> RepoResult<()> {
+        let result = self.0.feature_set_repo.create(feature_set).await;
+        self.0.written(result)
+    }
+
+    async fn update(&self, feature_set: &FeatureSet) -> RepoResult<()> {
+        let result = self.0.feature_set_repo.update(feature_set).await;
+        self.0.written(result)
+    }
+
+    async fn delete(&self, id: &str) -> RepoResult<()> {
+        let result = self.0.feature_set_repo.delete(id).await;
+        self.0.written(result)
+    }
+
+    async fn get_starter_for_space(&self, space_id: &str) -> RepoResult<Option<FeatureSet>> {
+        let snapshot = &self.0;
+        snapshot
+            .read(&snapshot.starters, space_id.to_string(), || {
+                snapshot.feature_set_repo.get_starter_for_space(space_id)
+            })
+            .await
+    }
+
+    async fn ensure_builtin_for_space(&self, space_id: &str) -> RepoResult<()> {
+        let result = self
+            .0
+            .feature_set_repo
+            .ensure_builtin_for_space(space_id)
+            .await;
+        self.0.written(result)
+    }
+
+    async fn add_feature_member(
+        &self,
+        feature_set_id: &str,
+        feature_id: &str,
+        mode: MemberMode,
+    ) -> RepoResult<()> {
+        let result = self
+            .0
+            .feature_set_repo
+            .add_feature_member(feature_set_id, feature_id, mode)
+            .await;
+        self.0.written(result)
+    }
+
+    async fn remove_feature_member(
+        &self,
+        feature_set_id: &str,
+        feature_id: &str,
+    ) -> RepoResult<()> {
+        let result = self
+            .0
+            .feature_set_repo
+            .remove_feature_member(feature_set_id, feature_id)
+            .await;
+        self.0.written(result)
+    }
+
+    async fn get_feature_members(&self, feature_set_id: &str) -> RepoResult<Vec<FeatureSetMember>> {
+        self.0
+            .feature_set_repo
+            .get_feature_members(feature_set_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpmux_core::InboundMcpClientRepository;
+    use mcpmux_storage::{
+        Database, SqliteFeatureSetRepository, SqliteInboundClientRepository,
+        SqliteInboundMcpClientRepository, SqliteServerFeatureRepository,
+    };
+    use std::sync::atomic::AtomicBool;
+    use tokio::sync::{Mutex, Notify};
+
+    /// Default space ID created by migration
+    const SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    /// The repositories the snapshot reads from, written to directly as a
+    /// process outside the gateway would
+    struct Fixture {
+        db: Arc<Mutex<Database>>,
+        features: Arc<dyn ServerFeatureRepository>,
+        feature_sets: Arc<dyn FeatureSetRepository>,
+        clients: Arc<dyn InboundClientRepository>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+            Self {
+                features: Arc::new(SqliteServerFeatureRepository::new(db.clone())),
+                feature_sets: Arc::new(SqliteFeatureSetRepository::new(db.clone())),
+                clients: Arc::new(SqliteInboundClientRepository::new(db.clone())),
+                db,
+            }
+        }
+
+        fn snapshot(&self) -> Arc<RepositorySnapshot> {
+            RepositorySnapshot::new(
+                self.features.clone(),
+                self.feature_sets.clone(),
+                self.clients.clone(),
+            )
+        }
+
+        async fn starter_id(&self) -> String {
+            self.feature_sets
+                .get_starter_for_space(SPACE_ID)
+                .await
+                .unwrap()
+                .expect("Starter for the default space")
+                .id
+        }
+
+        async fn client_id(&self) -> String {
+            let client = mcpmux_core::Client::cursor();
+            SqliteInboundMcpClientRepository::new(self.db.clone())
+                .create(&client)
+                .await
+                .unwrap();
+            client.id.to_string()
+        }
+    }
+
+    fn tool(name: &str) -> ServerFeature {
+        ServerFeature::tool(SPACE_ID, "server1", name)
+    }
+
+    async fn members(repo: &Arc<dyn FeatureSetRepository>, id: &str) -> usize {
+        repo.get_with_members(id)
+            .await
+            .unwrap()
+            .unwrap()
+            .members
+            .len()
+    }
+
+    /// Wait for the event loop to invalidate the snapshot past `generation`
+    async fn invalidated(snapshot: &RepositorySnapshot, generation: u64) -> u64 {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let current = snapshot.generation.load(Ordering::Acquire);
+                if current != generation {
+                    return current;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("snapshot was not invalidated")
+    }
+
+    #[tokio::test]
+    async fn test_answers_are_kept_until_invalidated() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let features = snapshot.feature_repo();
+
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+        fixture.features.upsert(&tool("read_file")).await.unwrap();
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+
+        snapshot.invalidate();
+        assert_eq!(features.list_for_space(SPACE_ID).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_feature_writes_through_the_snapshot_invalidate_it() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let features = snapshot.feature_repo();
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+
+        // Missed until something clears the snapshot
+        fixture.features.upsert(&tool("read_file")).await.unwrap();
+        features.upsert(&tool("write_file")).await.unwrap();
+        assert_eq!(features.list_for_space(SPACE_ID).await.unwrap().len(), 2);
+
+        features
+            .delete_for_server(SPACE_ID, "server1")
+            .await
+            .unwrap();
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feature_set_writes_through_the_snapshot_invalidate_it() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let feature_sets = snapshot.feature_set_repo();
+        let starter_id = fixture.starter_id().await;
+        assert_eq!(members(&feature_sets, &starter_id).await, 0);
+
+        fixture
+            .feature_sets
+            .add_feature_member(&starter_id, "feature-a", MemberMode::Include)
+            .await
+            .unwrap();
+        assert_eq!(members(&feature_sets, &starter_id).await, 0);
+
+        feature_sets
+            .add_feature_member(&starter_id, "feature-b", MemberMode::Include)
+            .await
+            .unwrap();
+        assert_eq!(members(&feature_sets, &starter_id).await, 2);
+
+        feature_sets
+            .remove_feature_member(&starter_id, "feature-a")
+            .await
+            .unwrap();
+        assert_eq!(members(&feature_sets, &starter_id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_relevant_events_invalidate_the_snapshot() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let (tx, rx) = broadcast::channel(16);
+        snapshot.start(rx);
+        let client_id = fixture.client_id().await;
+        let starter_id = fixture.starter_id().await;
+        let feature_sets = snapshot.feature_set_repo();
+
+        assert!(snapshot
+            .grants_for_space(&client_id, SPACE_ID)
+            .await
+            .unwrap()
+            .is_empty());
+        fixture
+            .clients
+            .grant_feature_set(&client_id, SPACE_ID, &starter_id)
+            .await
+            .unwrap();
+        let generation = snapshot.generation.load(Ordering::Acquire);
+        // Irrelevant events leave the snapshot alone
+        tx.send(DomainEvent::GatewayStopped).unwrap();
+        tx.send(DomainEvent::ClientGrantChanged {
+            client_id: client_id.clone(),
+            space_id: Uuid::parse_str(SPACE_ID).unwrap(),
+        })
+        .unwrap();
+        let generation = invalidated(&snapshot, generation).await;
+        assert_eq!(generation, 1);
+        assert_eq!(
+            snapshot
+                .grants_for_space(&client_id, SPACE_ID)
+                .await
+                .unwrap(),
+            vec![starter_id.clone()]
+        );
+
+        assert_eq!(members(&feature_sets, &starter_id).await, 0);
+        fixture
+            .feature_sets
+            .add_feature_member(&starter_id, "feature-a", MemberMode::Include)
+            .await
+            .unwrap();
+        tx.send(DomainEvent::FeatureSetMembersChanged {
+            space_id: Uuid::parse_str(SPACE_ID).unwrap(),
+            feature_set_id: starter_id.clone(),
+            added_count: 1,
+            removed_count: 0,
+        })
+        .unwrap();
+        invalidated(&snapshot, generation).await;
+        assert_eq!(members(&feature_sets, &starter_id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_behind_the_events_invalidates_the_snapshot() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let (tx, rx) = broadcast::channel(1);
+        // Overflow the channel before the event loop gets to run
+        snapshot.start(rx);
+        for _ in 0..3 {
+            tx.send(DomainEvent::GatewayStopped).unwrap();
+        }
+
+        invalidated(&snapshot, 0).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_answers_expire_after_max_age() {
+        let fixture = Fixture::new();
+        let snapshot = fixture.snapshot();
+        let features = snapshot.feature_repo();
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+        fixture.features.upsert(&tool("read_file")).await.unwrap();
+
+        tokio::time::advance(SNAPSHOT_MAX_AGE - Duration::from_millis(1)).await;
+        assert!(features.list_for_space(SPACE_ID).await.unwrap().is_empty());
+
+        tokio::time::advance(Duration::from_millis(1)).await;
+        assert_eq!(features.list_for_space(SPACE_ID).await.unwrap().len(), 1);
+    }
+
+    /// Holds the first `list_for_space` after it has read the database,
+    /// until released
+    struct GatedFeatureRepository {
+        inner: Arc<dyn ServerFeatureRepository>,
+        gated: AtomicBool,
+        loaded: Notify,
+        release: Notify,
+    }
+
+    #[async_trait]
+    impl ServerFeatureRepository for GatedFeatureRepository {
+        async fn list_for_space(&self, space_id: &str) -> RepoResult<Vec<ServerFeature>> {
+            let features = self.inner.list_for_space(space_id).await;
+            if self.gated.swap(false, Ordering::AcqRel) {
+                self.loaded.notify_one();
+                self.release.notified().await;
+            }
+            features
+        }
+
+        async fn list_for_server(
+            &self,
+            space_id: &str,
+            server_id: &str,
+        ) -> RepoResult<Vec<ServerFeature>> {
+            self.inner.list_for_server(space_id, server_id).await
+        }
+
+        async fn get(&self, id: &Uuid) -> RepoResult<Option<ServerFeature>> {
+            self.inner.get(id).await
+        }
+
+        async fn upsert(&self, feature: &ServerFeature) -> RepoResult<()> {
+            self.inner.upsert(feature).await
+        }
+
+        async fn upsert_many(&self, features: &[ServerFeature]) -> RepoResult<()> {
+            self.inner.upsert_many(features).await
+        }
+
+        async fn delete(&self, id: &Uuid) -> RepoResult<()> {
+            self.inner.delete(id).await
+        }
+
+        async fn mark_unavailable(&self, space_id: &str, server_id: &str) -> RepoResult<()> {
+            self.inner.mark_unavailable(space_id, server_id).await
+        }
+
+        async fn delete_for_server(&self, space_id: &str, server_id: &str) -> RepoResult<()> {
+            self.inner.delete_for_server(space_id, server_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_answer_loaded_across_an_invalidation_is_not_kept() {
+        let fixture = Fixture::new();
+        let gated = Arc::new(GatedFeatureRepository {
+            inner: fixture.features.clone(),
+            gated: AtomicBool::new(true),
+            loaded: Notify::new(),
+            release: Notify::new(),
+        });
+        let snapshot = RepositorySnapshot::new(
+            gated.clone(),
+            fixture.feature_sets.clone(),
+            fixture.clients.clone(),
+        );
+        let features = snapshot.feature_repo();
+
+        let load = tokio::spawn({
+            let features = features.clone();
+            async move { features.list_for_space(SPACE_ID).await }
+        });
+        gated.loaded.notified().await;
+        // A write lands and invalidates while the read is still in flight
+        fixture.features.upsert(&tool("read_file")).await.unwrap();
+        snapshot.invalidate();
+        gated.release.notify_one();
+
+        assert!(load.await.unwrap().unwrap().is_empty());
+        assert!(snapshot.features.is_empty());
+        assert_eq!(features.list_for_space(SPACE_ID).await.unwrap().len(), 1);
+    }
+}
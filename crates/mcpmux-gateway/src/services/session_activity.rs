@@ -0,0 +1,271 @@
+//! Live activity of connected MCP sessions.
+//!
+//! Backs the desktop "Active Connections" view and `GET /admin/sessions`:
+//! which clients are connected, on which protocol version, how many of their
+//! requests are running right now, when each was last active and which tools
+//! it called recently. Everything is in memory for the life of the gateway; a
+//! session drops out of the registry once its transport closes.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Tool calls remembered per session
+pub const RECENT_TOOL_CALLS: usize = 20;
+
+/// A tool call a session made
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentToolCall {
+    /// Tool name as the client called it
+    pub tool: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// `false` if the call failed or returned an error result
+    pub success: bool,
+}
+
+/// Snapshot of one connected session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionActivity {
+    pub session_id: String,
+    pub client_id: String,
+    /// Name and version the client gave in `initialize`
+    pub client_name: Option<String>,
+    pub client_version: Option<String>,
+    /// Protocol version negotiated with the client
+    pub protocol_version: Option<String>,
+    pub connected_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub requests_in_flight: usize,
+    /// Newest first, at most [`RECENT_TOOL_CALLS`]
+    pub recent_tool_calls: Vec<RecentToolCall>,
+}
+
+/// How a session identified itself when it connected
+#[derive(Debug, Clone, Default)]
+pub struct SessionInfo {
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub client_version: Option<String>,
+    pub protocol_version: Option<String>,
+}
+
+struct Session {
+    info: SessionInfo,
+    connected_at: DateTime<Utc>,
+    in_flight: AtomicUsize,
+    activity: Mutex<Activity>,
+    /// Whether the session's transport has closed
+    is_closed: Box<dyn Fn() -> bool + Send + Sync>,
+}
+
+struct Activity {
+    last_activity: DateTime<Utc>,
+    recent_tool_calls: VecDeque<RecentToolCall>,
+}
+
+impl Session {
+    fn snapshot(&self, session_id: &str) -> SessionActivity {
+        let activity = self.activity.lock();
+        SessionActivity {
+            session_id: session_id.to_string(),
+            client_id: self.info.client_id.clone(),
+            client_name: self.info.client_name.clone(),
+            client_version: self.info.client_version.clone(),
+            protocol_version: self.info.protocol_version.clone(),
+            connected_at: self.connected_at,
+            last_activity: activity.last_activity,
+            requests_in_flight: self.in_flight.load(Ordering::Relaxed),
+            recent_tool_calls: activity.recent_tool_calls.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Registry of connected sessions and what they're doing
+#[derive(Default)]
+pub struct SessionActivityRegistry {
+    sessions: DashMap<String, Arc<Session>>,
+}
+
+impl SessionActivityRegistry {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Start tracking a session. `is_closed` reports whether its transport
+    /// has gone away; closed sessions are dropped on the next register or
+    /// snapshot.
+    pub fn register(
+        &self,
+        session_id: impl Into<String>,
+        info: SessionInfo,
+        is_closed: impl Fn() -> bool + Send + Sync + 'static,
+    ) {
+        self.prune_closed();
+        let now = Utc::now();
+        self.sessions.insert(
+            session_id.into(),
+            Arc::new(Session {
+                info,
+                connected_at: now,
+                in_flight: AtomicUsize::new(0),
+                activity: Mutex::new(Activity {
+                    last_activity: now,
+                    recent_tool_calls: VecDeque::new(),
+                }),
+                is_closed: Box::new(is_closed),
+            }),
+        );
+    }
+
+    /// Mark the start of a request from `session_id`. It counts as in
+    /// flight until the returned guard is dropped. Requests from sessions
+    /// that aren't registered (no session id, or before `initialized`) are
+    /// not tracked.
+    pub fn begin(&self, session_id: Option<&str>) -> RequestActivity {
+        let session = session_id.and_then(|sid| self.sessions.get(sid).map(|s| s.clone()));
+        if let Some(ref session) = session {
+            session.in_flight.fetch_add(1, Ordering::Relaxed);
+            session.activity.lock().last_activity = Utc::now();
+        }
+        RequestActivity {
+            session,
+            tool_call: None,
+        }
+    }
+
+    /// Every open session, most recently active first
+    pub fn sessions(&self) -> Vec<SessionActivity> {
+        self.prune_closed();
+        let mut sessions: Vec<_> = self
+            .sessions
+            .iter()
+            .map(|entry| entry.value().snapshot(entry.key()))
+            .collect();
+        sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        sessions
+    }
+
+    /// Stop tracking a session
+    pub fn remove(&self, session_id: &str) {
+        self.sessions.remove(session_id);
+    }
+
+    fn prune_closed(&self) {
+        self.sessions.retain(|_, session| !(session.is_closed)());
+    }
+}
+
+/// A request being handled for a session; see
+/// [`SessionActivityRegistry::begin`]
+pub struct RequestActivity {
+    session: Option<Arc<Session>>,
+    tool_call: Option<PendingToolCall>,
+}
+
+struct PendingToolCall {
+    tool: String,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    success: bool,
+}
+
+impl RequestActivity {
+    /// Record this request as a call of `tool` once it finishes. The call
+    /// counts as failed unless [`succeeded`](Self::succeeded) is called.
+    pub fn tool_call(&mut self, tool: &str) {
+        self.tool_call = Some(PendingToolCall {
+            tool: tool.to_string(),
+            started_at: Utc::now(),
+            started: Instant::now(),
+            success: false,
+        });
+    }
+
+    /// Mark the tool call as successful
+    pub fn succeeded(&mut self) {
+        if let Some(ref mut call) = self.tool_call {
+            call.success = true;
+        }
+    }
+}
+
+impl Drop for RequestActivity {
+    fn drop(&mut self) {
+        let Some(ref session) = self.session else {
+            return;
+        };
+        session.in_flight.fetch_sub(1, Ordering::Relaxed);
+        let mut activity = session.activity.lock();
+        activity.last_activity = Utc::now();
+        if let Some(call) = self.tool_call.take() {
+            activity.recent_tool_calls.push_front(RecentToolCall {
+                tool: call.tool,
+                started_at: call.started_at,
+                duration_ms: call.started.elapsed().as_millis() as u64,
+                success: call.success,
+            });
+            activity.recent_tool_calls.truncate(RECENT_TOOL_CALLS);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn tracks_requests_and_tool_calls_until_the_session_closes() {
+        let registry = SessionActivityRegistry::new();
+        let closed = Arc::new(AtomicBool::new(false));
+        let transport = closed.clone();
+        registry.register(
+            "session-1",
+            SessionInfo {
+                client_id: "client-1".to_string(),
+                client_name: Some("Cursor".to_string()),
+                client_version: Some("1.0".to_string()),
+                protocol_version: Some("2025-06-18".to_string()),
+            },
+            move || transport.load(Ordering::Relaxed),
+        );
+
+        let listing = registry.begin(Some("session-1"));
+        let mut call = registry.begin(Some("session-1"));
+        call.tool_call("github_search");
+        let untracked = registry.begin(Some("unknown"));
+        assert_eq!(registry.sessions()[0].requests_in_flight, 2);
+
+        call.succeeded();
+        drop(call);
+        drop(listing);
+        drop(untracked);
+        for _ in 0..RECENT_TOOL_CALLS {
+            registry.begin(Some("session-1")).tool_call("failing");
+        }
+
+        let sessions = registry.sessions();
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert_eq!(session.client_name.as_deref(), Some("Cursor"));
+        assert_eq!(session.requests_in_flight, 0);
+        assert_eq!(session.recent_tool_calls.len(), RECENT_TOOL_CALLS);
+        // Newest first; the oldest call fell off the end
+        assert_eq!(session.recent_tool_calls[0].tool, "failing");
+        assert!(!session.recent_tool_calls[0].success);
+        assert!(session
+            .recent_tool_calls
+            .iter()
+            .all(|call| call.tool == "failing"));
+
+        closed.store(true, Ordering::Relaxed);
+        assert!(registry.sessions().is_empty());
+    }
+}
@@ -15,6 +15,7 @@ use std::time::{Duration, Instant};
 use dashmap::DashMap;
 use mcpmux_core::normalize_workspace_root;
 use tracing::debug;
+use uuid::Uuid;
 
 /// Thread-safe registry mapping `mcp-session-id` to the caller's reported
 /// workspace roots, plus the most recently resolved feature-set id so the
@@ -79,6 +80,14 @@ pub struct SessionRootsRegistry {
     /// resolver, the on-demand probe skip, and the prompt-root derivation all
     /// honor the header with no special-casing. Already normalized on insert.
     pinned: DashMap<String, String>,
+    /// `session_id -> Space selected via the `X-McpMux-Space` header or a
+    /// `space:<id>` token scope`.
+    ///
+    /// Already validated against the client's grants by the OAuth middleware.
+    /// The resolver routes a session with a selected Space to that Space
+    /// only, so IDE windows on different projects can use different Spaces
+    /// through one gateway URL.
+    selected_space: DashMap<String, Uuid>,
 }
 
 impl SessionRootsRegistry {
@@ -91,6 +100,7 @@ impl SessionRootsRegistry {
             probe_lock: DashMap::new(),
             first_seen: DashMap::new(),
             pinned: DashMap::new(),
+            selected_space: DashMap::new(),
         })
     }
 
@@ -216,6 +226,29 @@ impl SessionRootsRegistry {
         self.pinned.get(session_id).map(|v| v.clone())
     }
 
+    /// Select the Space a session routes to. Validate the selection against
+    /// the client's grants before calling this.
+    pub fn set_selected_space(&self, session_id: &str, space_id: Uuid) {
+        if self
+            .selected_space
+            .get(session_id)
+            .is_some_and(|v| *v == space_id)
+        {
+            return;
+        }
+        debug!(
+            %session_id,
+            %space_id,
+            "[SessionRoots] selected space from X-McpMux-Space header / token scope",
+        );
+        self.selected_space.insert(session_id.to_string(), space_id);
+    }
+
+    /// The Space selected for a session, if any
+    pub fn get_selected_space(&self, session_id: &str) -> Option<Uuid> {
+        self.selected_space.get(session_id).map(|v| *v)
+    }
+
     /// Drop a session's roots — call on client disconnect.
     pub fn remove(&self, session_id: &str) {
         self.map.remove(session_id);
@@ -225,6 +258,7 @@ impl SessionRootsRegistry {
         self.probe_lock.remove(session_id);
         self.first_seen.remove(session_id);
         self.pinned.remove(session_id);
+        self.selected_space.remove(session_id);
     }
 
     /// Compare-and-set the session's resolved feature-set id. Returns `true`
@@ -419,6 +453,17 @@ mod tests {
         assert!(reg.get("sess-1").is_none());
     }
 
+    #[test]
+    fn test_selected_space_is_per_session_and_cleared_on_remove() {
+        let reg = SessionRootsRegistry::default();
+        let space = Uuid::new_v4();
+        reg.set_selected_space("sess-1", space);
+        assert_eq!(reg.get_selected_space("sess-1"), Some(space));
+        assert!(reg.get_selected_space("sess-2").is_none());
+        reg.remove("sess-1");
+        assert!(reg.get_selected_space("sess-1").is_none());
+    }
+
     #[test]
     fn test_record_resolution_flips_on_change() {
         let reg = SessionRootsRegistry::default();
@@ -1,31 +1,73 @@
 //! Space Resolution Service
 //!
-//! Picks which Space a connecting client lands in. With per-client connection
-//! modes gone, the answer is always "the active/default Space" — but this
-//! service stays as a thin abstraction so callers don't reach into
-//! SpaceRepository directly and so future per-session targeting (e.g.
-//! WorkspaceBinding-driven space selection) has a single seam to extend.
+//! Picks which Space a connecting client lands in. Without a selection the
+//! answer is the active/default Space. A client may also select a Space per
+//! request — via the `X-McpMux-Space` header or a `space:<id>` token scope —
+//! which [`SpaceResolverService::select_space`] validates against the
+//! client's grants, so IDE windows on different projects can hit different
+//! Spaces through one gateway URL.
 
 use anyhow::{anyhow, Result};
 use mcpmux_core::SpaceRepository;
+use mcpmux_storage::InboundClientRepository;
 use std::sync::Arc;
+use thiserror::Error;
 use uuid::Uuid;
 
+use super::repository_snapshot::RepositorySnapshot;
+
+/// Why a client can't use the Space it selected
+#[derive(Debug, Error)]
+pub enum SpaceSelectionError {
+    #[error("Invalid space id: {0}")]
+    InvalidId(String),
+
+    #[error("Space {0} does not exist")]
+    NotFound(Uuid),
+
+    #[error("Client has no grants in space {0}")]
+    NotGranted(Uuid),
+
+    #[error("Client is locked to space {0}")]
+    Locked(String),
+
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
 pub struct SpaceResolverService {
     space_repo: Arc<dyn SpaceRepository>,
+    client_repo: Arc<dyn InboundClientRepository>,
+    /// Serves `client_grants` reads from memory when set
+    snapshot: Option<Arc<RepositorySnapshot>>,
 }
 
 impl SpaceResolverService {
-    pub fn new(space_repo: Arc<dyn SpaceRepository>) -> Self {
-        Self { space_repo }
+    pub fn new(
+        space_repo: Arc<dyn SpaceRepository>,
+        client_repo: Arc<dyn InboundClientRepository>,
+    ) -> Self {
+        Self {
+            space_repo,
+            client_repo,
+            snapshot: None,
+        }
+    }
+
+    /// Read grants through `snapshot` instead of querying the database on
+    /// every selection
+    pub fn with_snapshot(mut self, snapshot: Arc<RepositorySnapshot>) -> Self {
+        self.snapshot = Some(snapshot);
+        self
     }
 
     /// Resolve which space a client should access.
     ///
-    /// Currently always returns the default/active Space — per-client pins
-    /// no longer exist.  `client_id` is kept in the signature for forward
-    /// compatibility with routing rules keyed on identity (e.g. future
-    /// headless-connection policies).
+    /// Returns the default/active Space — per-client pins no longer exist,
+    /// and per-request selection goes through [`Self::select_space`].
+    /// `client_id` is kept in the signature for forward compatibility with
+    /// routing rules keyed on identity (e.g. future headless-connection
+    /// policies).
     pub async fn resolve_space_for_client(&self, _client_id: &str) -> Result<Uuid> {
         let active_space = self
             .space_repo
@@ -34,4 +76,47 @@ impl SpaceResolverService {
             .ok_or_else(|| anyhow!("No active space set"))?;
         Ok(active_space.id)
     }
+
+    /// Check that `client_id` may use the Space it selected.
+    ///
+    /// The client needs at least one FeatureSet grant in the Space. A client
+    /// locked to a Space can only select that Space, which it is routed to
+    /// anyway.
+    pub async fn select_space(
+        &self,
+        client_id: &str,
+        requested: &str,
+    ) -> Result<Uuid, SpaceSelectionError> {
+        let space_id = requested
+            .trim()
+            .parse::<Uuid>()
+            .map_err(|_| SpaceSelectionError::InvalidId(requested.to_string()))?;
+
+        if let Some(locked) = self.client_repo.get_locked_space(client_id).await? {
+            return if locked == space_id.to_string() {
+                Ok(space_id)
+            } else {
+                Err(SpaceSelectionError::Locked(locked))
+            };
+        }
+
+        if self.space_repo.get(&space_id).await?.is_none() {
+            return Err(SpaceSelectionError::NotFound(space_id));
+        }
+
+        let space = space_id.to_string();
+        let grants = match &self.snapshot {
+            Some(snapshot) => snapshot.grants_for_space(client_id, &space).await?,
+            None => {
+                self.client_repo
+                    .get_grants_for_space(client_id, &space)
+                    .await?
+            }
+        };
+        if grants.is_empty() {
+            return Err(SpaceSelectionError::NotGranted(space_id));
+        }
+
+        Ok(space_id)
+    }
 }
@@ -0,0 +1,108 @@
+//! McpMux SDK
+//!
+//! The stable surface of McpMux for third-party tools: server configuration
+//! types, the repository traits McpMux stores them through, the SQLite
+//! storage the desktop app and CLI share (or a shared PostgreSQL database),
+//! and the gateway. Depend on this
+//! crate rather than `mcpmux-core`, `mcpmux-storage` or `mcpmux-gateway`,
+//! whose internals change every release.
+//!
+//! ## Stability
+//!
+//! Everything re-exported here follows semver on this crate's own version:
+//! a removed or renamed item, a changed signature or a new required trait
+//! method is a major bump. New items, and new variants or fields of types
+//! marked `#[non_exhaustive]`, are minor. Types are re-exported as they are
+//! defined upstream, so an upstream change that would break this surface
+//! is held back or shipped with a major bump here.
+//! Before 1.0 the minor number stands in for the major one, as Cargo
+//! treats it: `0.2` to `0.3` is breaking, `0.2.0` to `0.2.1` is not. CI
+//! runs `cargo semver-checks` against the base branch to hold this.
+//!
+//! ## Example
+//!
+//! Serve the gateway over the desktop app's data, as `mcpmux-cli serve`
+//! does:
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use std::sync::Arc;
+//!
+//! use mcpmux_sdk::gateway::{
+//!     DependenciesBuilder, GatewayConfig, GatewayServer, LogConfig, ServerDiscoveryService,
+//!     ServerLogManager,
+//! };
+//! use mcpmux_sdk::storage::{create_key_provider, FieldEncryptor, RepositorySet, StorageConfig};
+//!
+//! # async fn run(data_dir: &Path) -> anyhow::Result<()> {
+//! let master_key = create_key_provider(data_dir)?.get_or_create_key()?;
+//! let encryptor = Arc::new(FieldEncryptor::new(&master_key)?);
+//! // SQLite in `data_dir`, or PostgreSQL when `MCPMUX_DATABASE_URL` is set
+//! let repos = RepositorySet::open(&StorageConfig::from_env(data_dir), encryptor).await?;
+//!
+//! let dependencies = DependenciesBuilder::new()
+//!     .with_repositories(&repos)
+//!     .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+//!         data_dir.to_path_buf(),
+//!         data_dir.join("spaces"),
+//!     )))
+//!     .with_log_manager(Arc::new(ServerLogManager::new(LogConfig::default())))
+//!     .build()
+//!     .map_err(anyhow::Error::msg)?;
+//!
+//! GatewayServer::new(GatewayConfig::default(), dependencies)
+//!     .run()
+//!     .await
+//! # }
+//! ```
+
+/// Server, Space and FeatureSet configuration
+pub mod config {
+    pub use mcpmux_core::{
+        AuthConfig, FeatureSet, FeatureSetMember, FeatureSetType, FeatureType, InputDefinition,
+        InputOption, InstallationSource, InstalledServer, MemberMode, MemberType, ServerDefinition,
+        ServerFeature, ServerSource, Space, TransportConfig, TransportType, UserServerEntry,
+        UserSpaceConfig, WorkspaceBinding,
+    };
+}
+
+/// Events McpMux emits as servers, features and grants change
+pub mod events {
+    pub use mcpmux_core::DomainEvent;
+}
+
+/// Storage traits; implement them to keep McpMux data elsewhere
+pub mod repository {
+    pub use mcpmux_core::{
+        AppSettingsRepository, CredentialRepository, EnvironmentProfileRepository,
+        EventJournalRepository, FeatureSetRepository, InboundMcpClientRepository,
+        InstalledServerRepository, OutboundOAuthRepository, ServerFeatureRepository,
+        ServerGroupRepository, SpaceBaseDirRepository, SpaceBuiltinConfigRepository,
+        SpaceRepository, WorkspaceBindingRepository,
+    };
+}
+
+/// The SQLite storage shared by the desktop app and the CLI
+pub mod storage {
+    pub use mcpmux_storage::{
+        create_jwt_secret_provider, create_key_provider, default_database_path, Database,
+        FieldEncryptor, InboundClientRepository, JwtSecretProvider, MasterKeyProvider,
+        RepositorySet, SqliteAppSettingsRepository, SqliteCredentialRepository,
+        SqliteEnvironmentProfileRepository, SqliteEventJournalRepository,
+        SqliteFeatureSetRepository, SqliteInboundClientRepository,
+        SqliteInboundMcpClientRepository, SqliteInstalledServerRepository,
+        SqliteOutboundOAuthRepository, SqliteServerFeatureRepository, SqliteServerGroupRepository,
+        SqliteSpaceBaseDirRepository, SqliteSpaceBuiltinConfigRepository, SqliteSpaceRepository,
+        SqliteWorkspaceBindingRepository, StorageConfig, DATABASE_FILE, DATABASE_URL_ENV_VAR,
+    };
+}
+
+/// The gateway, served over HTTP or called in-process
+pub mod gateway {
+    pub use mcpmux_core::{LogConfig, ServerDiscoveryService, ServerLogManager};
+    pub use mcpmux_gateway::{
+        AutoConnectResult, ClientContext, DependenciesBuilder, EmbeddedGateway, GatewayConfig,
+        GatewayDependencies, GatewayServer, GatewayServerHandle, InvalidToolArguments,
+        DEFAULT_AUTO_CONNECT_CONCURRENCY,
+    };
+}
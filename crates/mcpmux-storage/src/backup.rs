@@ -0,0 +1,500 @@
+//! Full configuration backup and restore.
+//!
+//! A backup is a single `.tar.gz` archive containing:
+//! - `manifest.json` — format version, app version, schema version, kind
+//! - `mcpmux.db` — consistent SQLite snapshot (`VACUUM INTO`), which also
+//!   carries app settings and encrypted credentials
+//! - `spaces/*.json` — space configuration files
+//!
+//! Credentials inside the snapshot stay encrypted with this machine's master
+//! key, so a backup restores on the machine that made it. Use the secrets
+//! bundle ([`crate::secrets_bundle`]) to move credentials elsewhere.
+//!
+//! Restoring can't swap the database under a live connection. The snapshot is
+//! staged next to the database and [`apply_pending_restore`] moves it into
+//! place on the next start, before [`Database::open`]. Space config files are
+//! restored immediately. A `pre_restore` backup is taken first so a restore
+//! can always be undone.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{Database, DATABASE_FILE};
+
+/// Current archive format version.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Directory (under the data dir) where backups are written.
+pub const BACKUPS_DIR: &str = "backups";
+
+/// Suffix of the staged database awaiting [`apply_pending_restore`].
+const PENDING_RESTORE_SUFFIX: &str = ".restore";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SPACES_DIR: &str = "spaces";
+const ARCHIVE_PREFIX: &str = "mcpmux-backup-";
+const ARCHIVE_SUFFIX: &str = ".tar.gz";
+
+/// Why a backup was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    /// Requested by the user
+    Manual,
+    /// Taken by the periodic backup task (subject to pruning)
+    Scheduled,
+    /// Taken automatically right before a restore
+    PreRestore,
+}
+
+/// Manifest stored at the root of every archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub schema_version: i64,
+    pub kind: BackupKind,
+    pub created_at: DateTime<Utc>,
+    /// Space config files included (relative to `spaces/`)
+    pub space_files: Vec<String>,
+}
+
+/// A backup archive on disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    /// Archive file name (identifier used for restore/delete)
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub manifest: BackupManifest,
+}
+
+/// Creates, lists, prunes and restores backups for one data directory.
+pub struct BackupManager {
+    data_dir: PathBuf,
+    spaces_dir: PathBuf,
+    backups_dir: PathBuf,
+    db: Arc<Mutex<Database>>,
+}
+
+impl BackupManager {
+    /// Manager for the standard layout: `<data_dir>/mcpmux.db`,
+    /// `<data_dir>/spaces/`, backups in `<data_dir>/backups/`.
+    pub fn new(data_dir: impl Into<PathBuf>, db: Arc<Mutex<Database>>) -> Self {
+        let data_dir = data_dir.into();
+        Self {
+            spaces_dir: data_dir.join(SPACES_DIR),
+            backups_dir: data_dir.join(BACKUPS_DIR),
+            data_dir,
+            db,
+        }
+    }
+
+    /// Override where archives are written.
+    pub fn with_backups_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.backups_dir = dir.into();
+        self
+    }
+
+    /// Directory holding backup archives.
+    pub fn backups_dir(&self) -> &Path {
+        &self.backups_dir
+    }
+
+    /// Snapshot the database and space configs into a new archive.
+    pub async fn create_backup(&self, kind: BackupKind) -> Result<BackupInfo> {
+        std::fs::create_dir_all(&self.backups_dir)
+            .with_context(|| format!("Failed to create {:?}", self.backups_dir))?;
+
+        let created_at = Utc::now();
+        // Short random suffix keeps back-to-back backups from colliding.
+        let file_name = format!(
+            "{}{}-{}-{}{}",
+            ARCHIVE_PREFIX,
+            created_at.format("%Y%m%dT%H%M%SZ"),
+            kind_slug(kind),
+            &uuid::Uuid::new_v4().simple().to_string()[..8],
+            ARCHIVE_SUFFIX
+        );
+        let archive_path = self.backups_dir.join(&file_name);
+        let snapshot_path = self.backups_dir.join(format!("{}.db.tmp", file_name));
+
+        // The snapshot is the only step that needs the DB lock.
+        let schema_version = {
+            let db = self.db.lock().await;
+            db.snapshot_to(&snapshot_path)?;
+            db.schema_version()
+        };
+
+        let spaces_dir = self.spaces_dir.clone();
+        let archive = archive_path.clone();
+        let snapshot = snapshot_path.clone();
+        let result = tokio::task::spawn_blocking(move || -> Result<BackupManifest> {
+            let space_files = list_space_files(&spaces_dir)?;
+            let manifest = BackupManifest {
+                format_version: BACKUP_FORMAT_VERSION,
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                schema_version,
+                kind,
+                created_at,
+                space_files,
+            };
+            write_archive(&archive, &manifest, &snapshot, &spaces_dir)?;
+            Ok(manifest)
+        })
+        .await
+        .context("Backup task panicked")?;
+
+        let _ = std::fs::remove_file(&snapshot_path);
+        let manifest = match result {
+            Ok(m) => m,
+            Err(e) => {
+                let _ = std::fs::remove_file(&archive_path);
+                return Err(e);
+            }
+        };
+
+        let size_bytes = std::fs::metadata(&archive_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+        info!(
+            "[Backup] Created {:?} backup {} ({} bytes)",
+            kind, file_name, size_bytes
+        );
+        Ok(BackupInfo {
+            file_name,
+            size_bytes,
+            manifest,
+        })
+    }
+
+    /// List archives, newest first. Unreadable files are skipped.
+    pub fn list_backups(&self) -> Result<Vec<BackupInfo>> {
+        let entries = match std::fs::read_dir(&self.backups_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut backups = Vec::new();
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !is_archive_name(&file_name) {
+                continue;
+            }
+            match read_manifest(&entry.path()) {
+                Ok(manifest) => backups.push(BackupInfo {
+                    size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    file_name,
+                    manifest,
+                }),
+                Err(e) => warn!("[Backup] Skipping unreadable archive {}: {}", file_name, e),
+            }
+        }
+        backups.sort_by_key(|b| std::cmp::Reverse(b.manifest.created_at));
+        Ok(backups)
+    }
+
+    /// Delete a backup archive.
+    pub fn delete_backup(&self, file_name: &str) -> Result<()> {
+        let path = self.archive_path(file_name)?;
+        std::fs::remove_file(&path).with_context(|| format!("Failed to delete {}", file_name))
+    }
+
+    /// Keep only the `keep` newest scheduled backups. Manual and pre-restore
+    /// backups are never pruned. Returns the number of archives removed.
+    pub fn prune_scheduled(&self, keep: usize) -> Result<usize> {
+        let mut removed = 0;
+        for backup in self
+            .list_backups()?
+            .into_iter()
+            .filter(|b| b.manifest.kind == BackupKind::Scheduled)
+            .skip(keep)
+        {
+            match self.delete_backup(&backup.file_name) {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("[Backup] Failed to prune {}: {}", backup.file_name, e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Restore a backup.
+    ///
+    /// Space configs are replaced immediately; the database is staged and
+    /// takes effect on the next start (see [`apply_pending_restore`]).
+    pub async fn restore_backup(&self, file_name: &str) -> Result<BackupInfo> {
+        let path = self.archive_path(file_name)?;
+        let manifest = read_manifest(&path)?;
+        if manifest.format_version > BACKUP_FORMAT_VERSION {
+            anyhow::bail!(
+                "Backup format {} is newer than this version of McpMux supports",
+                manifest.format_version
+            );
+        }
+        if manifest.schema_version > Database::latest_schema_version() {
+            anyhow::bail!(
+                "Backup was made by a newer McpMux (schema {}); update before restoring",
+                manifest.schema_version
+            );
+        }
+
+        // Safety net: make the current state restorable before touching it.
+        self.create_backup(BackupKind::PreRestore).await?;
+
+        let pending = pending_restore_path(&self.data_dir);
+        let spaces_dir = self.spaces_dir.clone();
+        tokio::task::spawn_blocking(move || extract_archive(&path, &pending, &spaces_dir))
+            .await
+            .context("Restore task panicked")??;
+
+        info!("[Backup] Restored {} (database pending restart)", file_name);
+        Ok(BackupInfo {
+            file_name: file_name.to_string(),
+            size_bytes: 0,
+            manifest,
+        })
+    }
+
+    /// Resolve an IPC-supplied archive name inside the backups dir. Only bare
+    /// archive file names are accepted (no separators, no `..`).
+    fn archive_path(&self, file_name: &str) -> Result<PathBuf> {
+        if !is_archive_name(file_name)
+            || file_name.contains(['/', '\\'])
+            || file_name.contains("..")
+        {
+            anyhow::bail!("Invalid backup name: {}", file_name);
+        }
+        Ok(self.backups_dir.join(file_name))
+    }
+}
+
+/// Move a staged restore into place. Call before [`Database::open`].
+///
+/// Returns `true` when a restore was applied.
+pub fn apply_pending_restore(data_dir: &Path) -> Result<bool> {
+    let pending = pending_restore_path(data_dir);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    let db_path = data_dir.join(DATABASE_FILE);
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = data_dir.join(format!("{}{}", DATABASE_FILE, suffix));
+        if sidecar.exists() {
+            std::fs::remove_file(&sidecar)
+                .with_context(|| format!("Failed to remove {:?}", sidecar))?;
+        }
+    }
+    std::fs::rename(&pending, &db_path)
+        .with_context(|| format!("Failed to move restored database into {:?}", db_path))?;
+    info!("[Backup] Applied pending database restore");
+    Ok(true)
+}
+
+fn pending_restore_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(format!("{}{}", DATABASE_FILE, PENDING_RESTORE_SUFFIX))
+}
+
+fn kind_slug(kind: BackupKind) -> &'static str {
+    match kind {
+        BackupKind::Manual => "manual",
+        BackupKind::Scheduled => "scheduled",
+        BackupKind::PreRestore => "pre_restore",
+    }
+}
+
+fn is_archive_name(name: &str) -> bool {
+    name.starts_with(ARCHIVE_PREFIX) && name.ends_with(ARCHIVE_SUFFIX)
+}
+
+/// Space config file names (`*.json`) directly under `spaces_dir`.
+fn list_space_files(spaces_dir: &Path) -> Result<Vec<String>> {
+    let entries = match std::fs::read_dir(spaces_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn write_archive(
+    archive: &Path,
+    manifest: &BackupManifest,
+    snapshot: &Path,
+    spaces_dir: &Path,
+) -> Result<()> {
+    let file = File::create(archive).with_context(|| format!("Failed to create {:?}", archive))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_FILE, manifest_json.as_slice())?;
+
+    builder.append_path_with_name(snapshot, DATABASE_FILE)?;
+    for name in &manifest.space_files {
+        builder.append_path_with_name(spaces_dir.join(name), format!("{}/{}", SPACES_DIR, name))?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn read_manifest(archive: &Path) -> Result<BackupManifest> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if entry.path()?.as_os_str() == MANIFEST_FILE {
+            return serde_json::from_reader(entry).context("Invalid backup manifest");
+        }
+    }
+    anyhow::bail!("Backup archive has no manifest")
+}
+
+/// Extract the database to `pending_db` and replace the space configs.
+fn extract_archive(archive: &Path, pending_db: &Path, spaces_dir: &Path) -> Result<()> {
+    // Stage space configs first so a bad archive leaves the live dir intact.
+    let staging = spaces_dir.with_extension("restore-staging");
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let file = File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    let mut found_db = false;
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        if path.as_os_str() == DATABASE_FILE {
+            entry.unpack(pending_db)?;
+            found_db = true;
+        } else if let Ok(rest) = path.strip_prefix(SPACES_DIR) {
+            // Only flat `spaces/<name>.json` entries — never trust nested paths.
+            let name = rest.to_string_lossy().to_string();
+            if rest.components().count() == 1 && name.ends_with(".json") {
+                entry.unpack(staging.join(&name))?;
+            }
+        }
+    }
+    if !found_db {
+        let _ = std::fs::remove_dir_all(&staging);
+        anyhow::bail!("Backup archive has no database");
+    }
+
+    std::fs::create_dir_all(spaces_dir)?;
+    for name in list_space_files(spaces_dir)? {
+        std::fs::remove_file(spaces_dir.join(name))?;
+    }
+    for name in list_space_files(&staging)? {
+        std::fs::rename(staging.join(&name), spaces_dir.join(&name))?;
+    }
+    std::fs::remove_dir_all(&staging)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, BackupManager) {
+        let dir = TempDir::new().unwrap();
+        let db = Database::open(&dir.path().join(DATABASE_FILE)).unwrap();
+        std::fs::create_dir_all(dir.path().join(SPACES_DIR)).unwrap();
+        std::fs::write(dir.path().join(SPACES_DIR).join("a.json"), "{\"v\":1}").unwrap();
+        let manager = BackupManager::new(dir.path(), Arc::new(Mutex::new(db)));
+        (dir, manager)
+    }
+
+    #[tokio::test]
+    async fn test_create_and_list_backup() {
+        let (_dir, manager) = setup();
+        let info = manager.create_backup(BackupKind::Manual).await.unwrap();
+        assert_eq!(info.manifest.space_files, vec!["a.json".to_string()]);
+        assert_eq!(
+            info.manifest.schema_version,
+            Database::latest_schema_version()
+        );
+
+        let listed = manager.list_backups().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].file_name, info.file_name);
+    }
+
+    #[tokio::test]
+    async fn test_restore_replaces_spaces_and_stages_db() {
+        let (dir, manager) = setup();
+        let info = manager.create_backup(BackupKind::Manual).await.unwrap();
+
+        let spaces = dir.path().join(SPACES_DIR);
+        std::fs::write(spaces.join("a.json"), "{\"v\":2}").unwrap();
+        std::fs::write(spaces.join("b.json"), "{}").unwrap();
+
+        manager.restore_backup(&info.file_name).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(spaces.join("a.json")).unwrap(),
+            "{\"v\":1}"
+        );
+        assert!(!spaces.join("b.json").exists());
+
+        // A pre-restore backup was taken
+        assert!(manager
+            .list_backups()
+            .unwrap()
+            .iter()
+            .any(|b| b.manifest.kind == BackupKind::PreRestore));
+
+        assert!(apply_pending_restore(dir.path()).unwrap());
+        assert!(!apply_pending_restore(dir.path()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_manual_backups() {
+        let (_dir, manager) = setup();
+        manager.create_backup(BackupKind::Manual).await.unwrap();
+        for _ in 0..3 {
+            manager.create_backup(BackupKind::Scheduled).await.unwrap();
+        }
+        assert_eq!(manager.prune_scheduled(1).unwrap(), 2);
+        let kinds: Vec<_> = manager
+            .list_backups()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.manifest.kind)
+            .collect();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&BackupKind::Manual));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_path_traversal() {
+        let (_dir, manager) = setup();
+        assert!(manager
+            .restore_backup("mcpmux-backup-../../evil.tar.gz")
+            .await
+            .is_err());
+        assert!(manager.delete_backup("/etc/passwd").is_err());
+    }
+}
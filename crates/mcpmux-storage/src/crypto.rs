@@ -3,6 +3,8 @@
 //! Uses AES-256-GCM for authenticated encryption of sensitive fields
 //! like credentials and tokens before storing in the database.
 
+use std::sync::RwLock;
+
 use anyhow::{Context, Result};
 use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
 use ring::rand::{SecureRandom, SystemRandom};
@@ -14,24 +16,41 @@ pub const KEY_SIZE: usize = 32;
 const NONCE_SIZE: usize = 12;
 
 /// Encryptor for sensitive field data.
+///
+/// The key sits behind a lock so a master key rotation can swap it for every
+/// repository sharing this encryptor.
 pub struct FieldEncryptor {
-    key: LessSafeKey,
+    key: RwLock<LessSafeKey>,
     rng: SystemRandom,
 }
 
+fn aead_key(master_key: &[u8; KEY_SIZE]) -> Result<LessSafeKey> {
+    let unbound_key = UnboundKey::new(&AES_256_GCM, master_key)
+        .map_err(|_| anyhow::anyhow!("Failed to create encryption key"))?;
+    Ok(LessSafeKey::new(unbound_key))
+}
+
 impl FieldEncryptor {
     /// Create a new encryptor with the given master key.
     ///
     /// The key must be exactly 32 bytes (256 bits).
     pub fn new(master_key: &[u8; KEY_SIZE]) -> Result<Self> {
-        let unbound_key = UnboundKey::new(&AES_256_GCM, master_key)
-            .map_err(|_| anyhow::anyhow!("Failed to create encryption key"))?;
-        let key = LessSafeKey::new(unbound_key);
+        let key = RwLock::new(aead_key(master_key)?);
         let rng = SystemRandom::new();
 
         Ok(Self { key, rng })
     }
 
+    /// Replace the key in place (after master key rotation).
+    pub fn rekey(&self, master_key: &[u8; KEY_SIZE]) -> Result<()> {
+        let key = aead_key(master_key)?;
+        *self
+            .key
+            .write()
+            .map_err(|_| anyhow::anyhow!("Encryption key lock poisoned"))? = key;
+        Ok(())
+    }
+
     /// Encrypt a plaintext string.
     ///
     /// Returns the ciphertext as a hex-encoded string (nonce + ciphertext + tag).
@@ -46,6 +65,8 @@ impl FieldEncryptor {
         // Encrypt in-place
         let mut in_out = plaintext.as_bytes().to_vec();
         self.key
+            .read()
+            .map_err(|_| anyhow::anyhow!("Encryption key lock poisoned"))?
             .seal_in_place_append_tag(nonce, Aad::empty(), &mut in_out)
             .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
 
@@ -77,6 +98,8 @@ impl FieldEncryptor {
         let mut in_out = encrypted.to_vec();
         let plaintext = self
             .key
+            .read()
+            .map_err(|_| anyhow::anyhow!("Encryption key lock poisoned"))?
             .open_in_place(nonce, Aad::empty(), &mut in_out)
             .map_err(|_| anyhow::anyhow!("Decryption failed - wrong key or corrupted data"))?;
 
@@ -132,6 +155,23 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_rekey_switches_key() {
+        let key1 = generate_master_key().unwrap();
+        let key2 = generate_master_key().unwrap();
+        let encryptor = FieldEncryptor::new(&key1).unwrap();
+        let old = encryptor.encrypt("secret").unwrap();
+
+        encryptor.rekey(&key2).unwrap();
+        assert!(encryptor.decrypt(&old).is_err());
+
+        let new = encryptor.encrypt("secret").unwrap();
+        assert_eq!(
+            FieldEncryptor::new(&key2).unwrap().decrypt(&new).unwrap(),
+            "secret"
+        );
+    }
+
     #[test]
     fn test_different_nonces() {
         let key = generate_master_key().unwrap();
@@ -12,142 +12,42 @@
 //!
 //! To add a new migration:
 //! 1. Create a new file: `migrations/NNN_description.sql`
-//! 2. Add the migration to the `MIGRATIONS` array below
+//! 2. Add the migration to the `MIGRATIONS` array in `migrations.rs`
 //! 3. The migration will auto-run on next app startup
+//!
+//! Never edit a migration that has shipped: its checksum is recorded when
+//! applied, and a mismatch stops the database from opening. Before migrating
+//! an existing file, a snapshot is written to `<db>.pre-migration` so a failed
+//! upgrade can be rolled back with `mcpmux db doctor --rollback`.
+//!
+//! ## Reads
+//!
+//! Writes go through the one connection behind `Mutex<Database>`. A database
+//! file also gets a [`ReadPool`] of read-only connections, which repositories
+//! read through on the request path so that grant and feature lookups don't
+//! wait for a write to release the lock.
 
 use anyhow::{Context, Result};
-use rusqlite::Connection;
-use std::path::Path;
-use tracing::{debug, info};
-
-/// A database migration with version number and SQL content.
-struct Migration {
-    version: i64,
-    name: &'static str,
-    sql: &'static str,
-}
+use rusqlite::{params, Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, OnceCell};
+use tracing::{debug, info, warn};
 
-/// All migrations in order. Add new migrations here.
-///
-/// Note: Migrations have been consolidated into a single clean initial migration.
-/// The schema includes cached_definition for offline operation and excludes
-/// runtime fields (connection_status, last_connected_at, last_error).
-const MIGRATIONS: &[Migration] = &[
-    Migration {
-        version: 1,
-        name: "initial",
-        sql: include_str!("migrations/001_initial.sql"),
-    },
-    Migration {
-        version: 2,
-        name: "featureset_resolver",
-        sql: include_str!("migrations/002_featureset_resolver.sql"),
-    },
-    Migration {
-        version: 3,
-        name: "drop_legacy_grants",
-        sql: include_str!("migrations/003_drop_legacy_grants.sql"),
-    },
-    Migration {
-        version: 4,
-        name: "workspace_modes",
-        sql: include_str!("migrations/004_workspace_modes.sql"),
-    },
-    Migration {
-        version: 5,
-        name: "drop_client_pin",
-        sql: include_str!("migrations/005_drop_client_pin.sql"),
-    },
-    Migration {
-        version: 6,
-        name: "collapse_feature_sets",
-        sql: include_str!("migrations/006_collapse_feature_sets.sql"),
-    },
-    Migration {
-        version: 7,
-        name: "concrete_binding",
-        sql: include_str!("migrations/007_concrete_binding.sql"),
-    },
-    Migration {
-        version: 8,
-        name: "canonical_default_space",
-        sql: include_str!("migrations/008_canonical_default_space.sql"),
-    },
-    Migration {
-        version: 9,
-        name: "restore_client_grants",
-        sql: include_str!("migrations/009_restore_client_grants.sql"),
-    },
-    Migration {
-        version: 10,
-        name: "inbound_client_reports_roots",
-        sql: include_str!("migrations/010_inbound_client_reports_roots.sql"),
-    },
-    Migration {
-        version: 11,
-        name: "inbound_client_roots_capability_known",
-        sql: include_str!("migrations/011_inbound_client_roots_capability_known.sql"),
-    },
-    Migration {
-        version: 12,
-        name: "workspace_binding_feature_sets",
-        sql: include_str!("migrations/012_workspace_binding_feature_sets.sql"),
-    },
-    Migration {
-        version: 13,
-        name: "rename_default_to_starter",
-        sql: include_str!("migrations/013_rename_default_to_starter.sql"),
-    },
-    Migration {
-        version: 14,
-        name: "rewrite_starter_seed_copy",
-        sql: include_str!("migrations/014_rewrite_starter_seed_copy.sql"),
-    },
-    Migration {
-        version: 15,
-        name: "rewrite_starter_seed_copy_v2",
-        sql: include_str!("migrations/015_rewrite_starter_seed_copy_v2.sql"),
-    },
-    Migration {
-        version: 16,
-        name: "space_builtin_servers",
-        sql: include_str!("migrations/016_space_builtin_servers.sql"),
-    },
-    Migration {
-        version: 17,
-        name: "purge_orphaned_feature_set_members",
-        sql: include_str!("migrations/017_purge_orphaned_feature_set_members.sql"),
-    },
-    Migration {
-        version: 18,
-        name: "starter_is_default_fallback_copy",
-        sql: include_str!("migrations/018_starter_is_default_fallback_copy.sql"),
-    },
-    Migration {
-        version: 19,
-        name: "space_base_dirs",
-        sql: include_str!("migrations/019_space_base_dirs.sql"),
-    },
-    Migration {
-        version: 20,
-        name: "inbound_client_api_keys",
-        sql: include_str!("migrations/020_inbound_client_api_keys.sql"),
-    },
-    Migration {
-        version: 21,
-        name: "binding_type",
-        sql: include_str!("migrations/021_binding_type.sql"),
-    },
-    Migration {
-        version: 22,
-        name: "inbound_client_locked_space",
-        sql: include_str!("migrations/022_inbound_client_locked_space.sql"),
-    },
-];
+use crate::migrations::{self, MIGRATIONS};
+
+/// How long a statement waits on a locked database before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Read-only connections kept open per database file
+const READ_CONNECTIONS: usize = 4;
 
 /// SQLite database wrapper.
 pub struct Database {
     conn: Connection,
+    /// Read-only connections, for a database file (not in-memory)
+    readers: Option<Arc<ReadPool>>,
 }
 
 impl Database {
@@ -168,17 +68,70 @@ impl Database {
         // Enable foreign keys
         conn.pragma_update(None, "foreign_keys", "ON")?;
 
-        // Set journal mode to WAL for better concurrency
+        // WAL lets readers run alongside a writer; with it, NORMAL sync is
+        // still crash-safe and skips an fsync on every commit
         conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+
+        // Wait for a lock held by another process (the CLI, a second
+        // instance) instead of failing with SQLITE_BUSY straight away
+        conn.busy_timeout(BUSY_TIMEOUT)?;
 
         debug!("Opened database at {:?}", path);
 
-        let db = Self { conn };
+        let mut db = Self {
+            conn,
+            readers: None,
+        };
+        db.ensure_migrations_table()?;
+        let current_version = db.get_schema_version();
+        if current_version > 0 && current_version < migrations::latest_version() {
+            db.backup_before_migration(path)?;
+        }
         db.run_migrations()?;
 
+        // Opened after migrating, so readers never see a half-migrated schema
+        db.readers = Some(Arc::new(ReadPool::open(path)?));
+
+        Ok(db)
+    }
+
+    /// Open a database without running migrations (for diagnostics).
+    pub(crate) fn open_unmigrated(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open database at {:?}", path))?;
+        let db = Self {
+            conn,
+            readers: None,
+        };
+        db.ensure_migrations_table()?;
         Ok(db)
     }
 
+    /// Path of the snapshot taken before migrating the database at `db_path`.
+    pub fn pre_migration_backup_path(db_path: &Path) -> PathBuf {
+        let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".pre-migration");
+        db_path.with_file_name(name)
+    }
+
+    /// Snapshot the database before applying pending migrations, replacing
+    /// any previous pre-migration snapshot.
+    fn backup_before_migration(&self, path: &Path) -> Result<()> {
+        let backup = Self::pre_migration_backup_path(path);
+        if backup.exists() {
+            std::fs::remove_file(&backup).with_context(|| {
+                format!("Failed to remove old pre-migration backup {:?}", backup)
+            })?;
+        }
+        info!(
+            "Backing up database (schema v{}) to {:?} before migrating",
+            self.get_schema_version(),
+            backup
+        );
+        self.snapshot_to(&backup)
+    }
+
     /// Open an in-memory database (for testing).
     pub fn open_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
@@ -188,7 +141,10 @@ impl Database {
 
         debug!("Opened in-memory database");
 
-        let db = Self { conn };
+        let db = Self {
+            conn,
+            readers: None,
+        };
         db.run_migrations()?;
 
         Ok(db)
@@ -198,6 +154,7 @@ impl Database {
     fn run_migrations(&self) -> Result<()> {
         // First, ensure the schema_migrations table exists
         self.ensure_migrations_table()?;
+        self.verify_applied_migrations()?;
 
         // Get current schema version
         let current_version = self.get_schema_version();
@@ -205,7 +162,7 @@ impl Database {
         info!(
             "Current database schema version: {}, latest available: {}",
             current_version,
-            MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+            migrations::latest_version()
         );
 
         // Disable foreign-key enforcement for the duration of the migration
@@ -257,8 +214,8 @@ impl Database {
 
                 // Record that this migration was applied
                 self.conn.execute(
-                    "INSERT OR REPLACE INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, datetime('now'))",
-                    rusqlite::params![migration.version, migration.name],
+                    "INSERT OR REPLACE INTO schema_migrations (version, name, applied_at, checksum) VALUES (?1, ?2, datetime('now'), ?3)",
+                    params![migration.version, migration.name, migration.checksum()],
                 )?;
 
                 tx.commit()?;
@@ -281,6 +238,87 @@ impl Database {
         Ok(())
     }
 
+    /// Refuse to migrate a database written by a newer build, or one whose
+    /// applied migrations differ from the ones this build ships.
+    ///
+    /// Rows recorded before checksums existed are backfilled.
+    fn verify_applied_migrations(&self) -> Result<()> {
+        let current_version = self.get_schema_version();
+        let latest = migrations::latest_version();
+        if current_version > latest {
+            anyhow::bail!(
+                "Database schema version {} is newer than this build supports ({}). \
+                 Update McpMux, or run `mcpmux db doctor --rollback` to restore the \
+                 pre-migration backup.",
+                current_version,
+                latest
+            );
+        }
+
+        let mismatches = self.checksum_mismatches(true)?;
+        if let Some((version, name)) = mismatches.first() {
+            anyhow::bail!(
+                "Migration {} ({}) was applied from different SQL than this build ships \
+                 (checksum mismatch). Run `mcpmux db doctor` to inspect or repair.",
+                version,
+                name
+            );
+        }
+        Ok(())
+    }
+
+    /// Applied migrations whose recorded checksum differs from this build's.
+    ///
+    /// With `backfill`, missing checksums are recorded instead of reported.
+    pub(crate) fn checksum_mismatches(&self, backfill: bool) -> Result<Vec<(i64, &'static str)>> {
+        let applied: Vec<(i64, Option<String>)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT version, checksum FROM schema_migrations ORDER BY version")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut mismatches = Vec::new();
+        for (version, recorded) in applied {
+            // Versions removed by past consolidation have nothing to compare
+            let Some(migration) = migrations::find(version) else {
+                continue;
+            };
+            let expected = migration.checksum();
+            match recorded {
+                Some(checksum) if checksum == expected => {}
+                Some(_) => mismatches.push((version, migration.name)),
+                None if backfill => {
+                    self.conn.execute(
+                        "UPDATE schema_migrations SET checksum = ?1 WHERE version = ?2",
+                        params![expected, version],
+                    )?;
+                }
+                None => {}
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Record this build's checksums for every applied migration.
+    pub(crate) fn accept_checksums(&self) -> Result<usize> {
+        let mut updated = 0;
+        for (version, name) in self.checksum_mismatches(false)? {
+            warn!(
+                "Accepting changed checksum for migration {} ({})",
+                version, name
+            );
+            if let Some(migration) = migrations::find(version) {
+                updated += self.conn.execute(
+                    "UPDATE schema_migrations SET checksum = ?1 WHERE version = ?2",
+                    params![migration.checksum(), version],
+                )?;
+            }
+        }
+        Ok(updated)
+    }
+
     /// Whether foreign-key enforcement is currently enabled on the connection.
     fn foreign_keys_enabled(&self) -> bool {
         self.conn
@@ -342,13 +380,29 @@ impl Database {
                     "ALTER TABLE schema_migrations ADD COLUMN name TEXT DEFAULT 'unknown';",
                 )?;
             }
+
+            let has_checksum_column: bool = self
+                .conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('schema_migrations') WHERE name='checksum'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap_or(false);
+
+            if !has_checksum_column {
+                info!("Adding checksum column to schema_migrations...");
+                self.conn
+                    .execute_batch("ALTER TABLE schema_migrations ADD COLUMN checksum TEXT;")?;
+            }
         } else {
             // Create new table
             self.conn.execute(
                 "CREATE TABLE schema_migrations (
                     version INTEGER PRIMARY KEY,
                     name TEXT NOT NULL,
-                    applied_at TEXT NOT NULL
+                    applied_at TEXT NOT NULL,
+                    checksum TEXT
                 )",
                 [],
             )?;
@@ -367,11 +421,36 @@ impl Database {
             .unwrap_or(0)
     }
 
+    /// Schema version of this database (highest applied migration).
+    pub fn schema_version(&self) -> i64 {
+        self.get_schema_version()
+    }
+
+    /// Highest schema version this build knows how to migrate to.
+    pub fn latest_schema_version() -> i64 {
+        migrations::latest_version()
+    }
+
+    /// Write a consistent, compacted copy of the database to `path`
+    /// (`VACUUM INTO`). The target must not exist.
+    pub fn snapshot_to(&self, path: &Path) -> Result<()> {
+        let target = path.to_str().context("Snapshot path is not valid UTF-8")?;
+        self.conn
+            .execute("VACUUM INTO ?1", [target])
+            .with_context(|| format!("Failed to snapshot database to {:?}", path))?;
+        Ok(())
+    }
+
     /// Get a reference to the underlying connection.
     pub fn connection(&self) -> &Connection {
         &self.conn
     }
 
+    /// Read-only connections to this database, `None` when it is in memory.
+    pub fn read_pool(&self) -> Option<Arc<ReadPool>> {
+        self.readers.clone()
+    }
+
     /// Execute a closure within a transaction.
     pub fn transaction<T, F>(&self, f: F) -> Result<T>
     where
@@ -384,6 +463,84 @@ impl Database {
     }
 }
 
+/// Read-only connections to a database file.
+///
+/// Under WAL a reader sees the last committed state without waiting for the
+/// writer, so reads through these don't queue behind a write holding the
+/// shared `Mutex<Database>`.
+pub struct ReadPool {
+    path: PathBuf,
+    idle: std::sync::Mutex<Vec<Connection>>,
+}
+
+impl ReadPool {
+    fn open(path: &Path) -> Result<Self> {
+        let idle = (0..READ_CONNECTIONS)
+            .map(|_| Self::connect(path))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            idle: std::sync::Mutex::new(idle),
+        })
+    }
+
+    fn connect(path: &Path) -> Result<Connection> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .with_context(|| format!("Failed to open read connection to {:?}", path))?;
+        // A checkpoint can still briefly lock out readers
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    /// Run `f` on an idle connection, opening another when all are in use.
+    pub fn read<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => Self::connect(&self.path)?,
+        };
+        let result = f(&conn);
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        if idle.len() < READ_CONNECTIONS {
+            idle.push(conn);
+        }
+        result
+    }
+}
+
+/// Reads for a repository over the shared `Mutex<Database>`: through the
+/// database's [`ReadPool`] when it has one, otherwise (in memory) through
+/// the shared connection.
+#[derive(Clone)]
+pub(crate) struct DbReader {
+    db: Arc<Mutex<Database>>,
+    /// Looked up on the first read, since constructors can't lock
+    pool: Arc<OnceCell<Option<Arc<ReadPool>>>>,
+}
+
+impl DbReader {
+    pub(crate) fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self {
+            db,
+            pool: Arc::new(OnceCell::new()),
+        }
+    }
+
+    pub(crate) async fn read<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        let pool = self
+            .pool
+            .get_or_init(|| async { self.db.lock().await.read_pool() })
+            .await;
+        match pool {
+            Some(pool) => pool.read(f),
+            None => f(self.db.lock().await.connection()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -478,7 +635,10 @@ mod tests {
 
         let conn = Connection::open_in_memory().unwrap();
         conn.pragma_update(None, "foreign_keys", "ON").unwrap();
-        let db = Database { conn };
+        let db = Database {
+            conn,
+            readers: None,
+        };
         db.ensure_migrations_table().unwrap();
 
         // Apply migrations up to v4 (last version before the first
@@ -540,6 +700,67 @@ mod tests {
         assert_eq!(clients, 1, "inbound client must survive the rebuilds");
     }
 
+    #[test]
+    fn test_checksum_mismatch_blocks_open() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        drop(Database::open(&db_path).unwrap());
+
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 3",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let err = Database::open(&db_path).err().unwrap().to_string();
+        assert!(err.contains("checksum mismatch"), "{err}");
+    }
+
+    #[test]
+    fn test_newer_schema_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        drop(Database::open(&db_path).unwrap());
+
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (9999, 'future', datetime('now'))",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let err = Database::open(&db_path).err().unwrap().to_string();
+        assert!(err.contains("newer than this build"), "{err}");
+    }
+
+    #[test]
+    fn test_pre_migration_backup_taken_for_pending_migrations() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        drop(Database::open(&db_path).unwrap());
+
+        // Fresh databases have nothing worth backing up
+        let backup = Database::pre_migration_backup_path(&db_path);
+        assert!(!backup.exists());
+
+        // Pretend the last migration is pending
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "DELETE FROM schema_migrations WHERE version = ?1",
+            [migrations::latest_version()],
+        )
+        .unwrap();
+        drop(conn);
+
+        // Re-applying it may fail (e.g. column already exists), like a broken
+        // upgrade would — the snapshot must be there either way
+        let _ = Database::open(&db_path);
+        assert!(backup.exists());
+    }
+
     #[test]
     fn test_persistent_database() {
         let temp_dir = TempDir::new().unwrap();
@@ -569,4 +790,62 @@ mod tests {
 
         assert_eq!(name, "Test");
     }
+
+    #[test]
+    fn test_open_tunes_for_concurrent_writers() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(&temp_dir.path().join("test.db")).unwrap();
+        let conn = db.connection();
+
+        let journal_mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |r| r.get(0))
+            .unwrap();
+        let synchronous: i64 = conn
+            .query_row("PRAGMA synchronous", [], |r| r.get(0))
+            .unwrap();
+        let busy_timeout: i64 = conn
+            .query_row("PRAGMA busy_timeout", [], |r| r.get(0))
+            .unwrap();
+
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(synchronous, 1, "NORMAL");
+        assert_eq!(busy_timeout, BUSY_TIMEOUT.as_millis() as i64);
+    }
+
+    #[tokio::test]
+    async fn test_reads_do_not_wait_for_the_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(Mutex::new(
+            Database::open(&temp_dir.path().join("test.db")).unwrap(),
+        ));
+        let reader = DbReader::new(db.clone());
+        let count = |conn: &Connection| -> Result<i64> {
+            Ok(conn.query_row("SELECT count(*) FROM spaces", [], |r| r.get(0))?)
+        };
+        let before = reader.read(count).await.unwrap();
+
+        // A write in progress, holding the lock
+        let writer = db.lock().await;
+        writer
+            .connection()
+            .execute_batch(
+                "BEGIN;
+                 INSERT INTO spaces (id, name, created_at, updated_at)
+                 VALUES ('test', 'Test', datetime('now'), datetime('now'));",
+            )
+            .unwrap();
+        let during = tokio::time::timeout(Duration::from_secs(1), reader.read(count))
+            .await
+            .expect("read waited for the writer")
+            .unwrap();
+        assert_eq!(during, before, "uncommitted rows aren't visible");
+        writer.connection().execute_batch("COMMIT").unwrap();
+        drop(writer);
+
+        assert_eq!(reader.read(count).await.unwrap(), before + 1);
+        assert!(reader
+            .read(|conn| Ok(conn.execute("DELETE FROM spaces", [])?))
+            .await
+            .is_err());
+    }
 }
@@ -0,0 +1,230 @@
+//! Database diagnostics and recovery (`mcpmux db doctor`).
+//!
+//! Inspects a database without migrating it, and offers two recovery paths
+//! for an app that no longer starts:
+//! - [`repair`]: accept this build's migration checksums and retry pending
+//!   migrations.
+//! - [`rollback`]: put back the snapshot taken before the last migration run,
+//!   keeping the broken file next to it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::info;
+
+use crate::migrations::{self, MIGRATIONS};
+use crate::Database;
+
+/// Result of inspecting a database.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub db_path: PathBuf,
+    pub schema_version: i64,
+    pub latest_version: i64,
+    /// Migrations not yet applied, as `"NNN name"`
+    pub pending: Vec<String>,
+    /// Applied migrations whose SQL differs from this build, as `"NNN name"`
+    pub checksum_mismatches: Vec<String>,
+    /// `PRAGMA integrity_check` messages (empty when healthy)
+    pub integrity_errors: Vec<String>,
+    pub foreign_key_violations: usize,
+    /// Schema version of the pre-migration snapshot, if one exists
+    pub pre_migration_backup: Option<i64>,
+}
+
+impl DoctorReport {
+    /// Whether the app can open this database as-is.
+    pub fn is_healthy(&self) -> bool {
+        self.schema_version <= self.latest_version
+            && self.checksum_mismatches.is_empty()
+            && self.integrity_errors.is_empty()
+            && self.foreign_key_violations == 0
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Database:        {}", self.db_path.display())?;
+        writeln!(
+            f,
+            "Schema version:  {} (this build: {})",
+            self.schema_version, self.latest_version
+        )?;
+        if self.schema_version > self.latest_version {
+            writeln!(f, "  ! written by a newer McpMux build")?;
+        }
+        for m in &self.pending {
+            writeln!(f, "  pending:       {}", m)?;
+        }
+        for m in &self.checksum_mismatches {
+            writeln!(f, "  ! checksum mismatch: {}", m)?;
+        }
+        if self.integrity_errors.is_empty() {
+            writeln!(f, "Integrity:       ok")?;
+        } else {
+            for e in &self.integrity_errors {
+                writeln!(f, "  ! integrity:   {}", e)?;
+            }
+        }
+        writeln!(f, "FK violations:   {}", self.foreign_key_violations)?;
+        match self.pre_migration_backup {
+            Some(v) => writeln!(f, "Rollback point:  schema v{}", v)?,
+            None => writeln!(f, "Rollback point:  none")?,
+        }
+        write!(
+            f,
+            "Status:          {}",
+            if self.is_healthy() {
+                "healthy"
+            } else {
+                "needs attention"
+            }
+        )
+    }
+}
+
+/// Inspect the database at `db_path` without migrating it.
+pub fn diagnose(db_path: &Path) -> Result<DoctorReport> {
+    if !db_path.exists() {
+        anyhow::bail!("No database at {:?}", db_path);
+    }
+    let db = Database::open_unmigrated(db_path)?;
+    let conn = db.connection();
+
+    let schema_version = db.schema_version();
+    let pending = MIGRATIONS
+        .iter()
+        .filter(|m| m.version > schema_version)
+        .map(|m| format!("{:03} {}", m.version, m.name))
+        .collect();
+    let checksum_mismatches = db
+        .checksum_mismatches(true)?
+        .into_iter()
+        .map(|(version, name)| format!("{:03} {}", version, name))
+        .collect();
+
+    let integrity_errors: Vec<String> = {
+        let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|msg| msg != "ok")
+            .collect()
+    };
+    let foreign_key_violations: i64 =
+        conn.query_row("SELECT count(*) FROM pragma_foreign_key_check", [], |r| {
+            r.get(0)
+        })?;
+
+    let backup = Database::pre_migration_backup_path(db_path);
+    let pre_migration_backup = if backup.exists() {
+        Some(Database::open_unmigrated(&backup)?.schema_version())
+    } else {
+        None
+    };
+
+    Ok(DoctorReport {
+        db_path: db_path.to_path_buf(),
+        schema_version,
+        latest_version: migrations::latest_version(),
+        pending,
+        checksum_mismatches,
+        integrity_errors,
+        foreign_key_violations: foreign_key_violations as usize,
+        pre_migration_backup,
+    })
+}
+
+/// Accept this build's migration checksums, then apply pending migrations.
+pub fn repair(db_path: &Path) -> Result<DoctorReport> {
+    {
+        let db = Database::open_unmigrated(db_path)?;
+        let accepted = db.accept_checksums()?;
+        if accepted > 0 {
+            info!(
+                "[Doctor] Accepted {} changed migration checksum(s)",
+                accepted
+            );
+        }
+    }
+    Database::open(db_path).context("Migrations still fail; try `--rollback`")?;
+    diagnose(db_path)
+}
+
+/// Restore the pre-migration snapshot. The current database (and its WAL
+/// files) is moved aside; returns its new path.
+pub fn rollback(db_path: &Path) -> Result<PathBuf> {
+    let backup = Database::pre_migration_backup_path(db_path);
+    if !backup.exists() {
+        anyhow::bail!("No pre-migration backup at {:?}", backup);
+    }
+
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let mut failed_name = db_path.file_name().unwrap_or_default().to_os_string();
+    failed_name.push(format!(".failed-{}", stamp));
+    let failed_path = db_path.with_file_name(&failed_name);
+
+    if db_path.exists() {
+        std::fs::rename(db_path, &failed_path)
+            .with_context(|| format!("Failed to move {:?} aside", db_path))?;
+    }
+    for suffix in ["-wal", "-shm"] {
+        let mut side = db_path.as_os_str().to_os_string();
+        side.push(suffix);
+        let side = PathBuf::from(side);
+        if side.exists() {
+            let mut target = failed_path.as_os_str().to_os_string();
+            target.push(suffix);
+            std::fs::rename(&side, PathBuf::from(target))?;
+        }
+    }
+
+    std::fs::copy(&backup, db_path).with_context(|| format!("Failed to restore {:?}", backup))?;
+    info!(
+        "[Doctor] Restored {:?} from pre-migration backup; previous file kept at {:?}",
+        db_path, failed_path
+    );
+    Ok(failed_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_diagnose_repair_and_rollback() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("mcpmux.db");
+        drop(Database::open(&db_path).unwrap());
+
+        let report = diagnose(&db_path).unwrap();
+        assert!(report.is_healthy(), "{report}");
+        assert!(report.pending.is_empty());
+        assert_eq!(report.pre_migration_backup, None);
+
+        // A tampered checksum is reported, then accepted by repair
+        rusqlite::Connection::open(&db_path)
+            .unwrap()
+            .execute(
+                "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 2",
+                [],
+            )
+            .unwrap();
+        assert_eq!(diagnose(&db_path).unwrap().checksum_mismatches.len(), 1);
+        assert!(repair(&db_path).unwrap().is_healthy());
+
+        // Rollback restores the snapshot and keeps the current file
+        let snapshot = Database::pre_migration_backup_path(&db_path);
+        Database::open(&db_path)
+            .unwrap()
+            .snapshot_to(&snapshot)
+            .unwrap();
+        let failed = rollback(&db_path).unwrap();
+        assert!(failed.exists());
+        assert!(db_path.exists());
+        assert!(Database::open(&db_path).is_ok());
+    }
+}
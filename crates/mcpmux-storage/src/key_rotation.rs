@@ -0,0 +1,238 @@
+//! Master key rotation.
+//!
+//! Generates a fresh AES-256 master key, re-encrypts every
+//! [`FieldEncryptor`]-protected column inside one SQLite transaction, and
+//! swaps the key in the platform key store.
+//!
+//! Ordering keeps the database and key store consistent:
+//! 1. Re-encrypt all rows inside a transaction (not yet committed).
+//! 2. Store the new key. On failure the transaction rolls back — nothing
+//!    changed.
+//! 3. Commit. On failure the old key is written back.
+//! 4. Swap the shared encryptor's key so live repositories use the new key.
+//!
+//! The database lock is held throughout, so repository reads and writes wait
+//! until rotation finishes.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+use zeroize::Zeroizing;
+
+use crate::crypto::{generate_master_key, FieldEncryptor};
+use crate::keychain::MasterKeyProvider;
+use crate::Database;
+
+/// Stage of a rotation, reported through the progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationPhase {
+    ReEncrypting,
+    StoringKey,
+    Completed,
+}
+
+/// Progress update emitted during rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RotationProgress {
+    pub phase: RotationPhase,
+    /// Rows re-encrypted so far
+    pub processed: usize,
+    /// Total encrypted rows
+    pub total: usize,
+}
+
+/// One encrypted column to rotate.
+struct EncryptedColumn {
+    table: &'static str,
+    column: &'static str,
+    /// Legacy rows may hold plaintext JSON; they are encrypted on rotation.
+    allow_plaintext_json: bool,
+}
+
+/// Every column written through [`FieldEncryptor`].
+const ENCRYPTED_COLUMNS: &[EncryptedColumn] = &[
+    EncryptedColumn {
+        table: "credentials",
+        column: "credential_value",
+        allow_plaintext_json: false,
+    },
+    EncryptedColumn {
+        table: "installed_servers",
+        column: "input_values",
+        allow_plaintext_json: true,
+    },
+    EncryptedColumn {
+        table: "environment_profiles",
+        column: "overrides",
+        allow_plaintext_json: false,
+    },
+];
+
+/// Rotate the master key. Returns the number of re-encrypted values.
+pub async fn rotate_master_key(
+    db: &Arc<Mutex<Database>>,
+    encryptor: &FieldEncryptor,
+    key_provider: &dyn MasterKeyProvider,
+    on_progress: impl Fn(RotationProgress),
+) -> Result<usize> {
+    let old_key = key_provider
+        .get_or_create_key()
+        .context("Failed to read current master key")?;
+    let new_key = Zeroizing::new(generate_master_key()?);
+    let new_encryptor = FieldEncryptor::new(&new_key)?;
+
+    let db = db.lock().await;
+    let conn = db.connection();
+
+    // Load every encrypted value up front so progress has a total.
+    let mut rows: Vec<(&EncryptedColumn, String, String)> = Vec::new();
+    for col in ENCRYPTED_COLUMNS {
+        let sql = format!(
+            "SELECT id, {c} FROM {t} WHERE {c} IS NOT NULL AND TRIM({c}) != ''",
+            c = col.column,
+            t = col.table
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let found = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+        for row in found {
+            let (id, value) = row?;
+            rows.push((col, id, value));
+        }
+    }
+    let total = rows.len();
+    info!("[KeyRotation] Re-encrypting {} values", total);
+
+    let tx = conn.unchecked_transaction()?;
+    for (processed, (col, id, value)) in rows.into_iter().enumerate() {
+        let plaintext = match encryptor.decrypt(&value) {
+            Ok(p) => Zeroizing::new(p),
+            Err(_)
+                if col.allow_plaintext_json
+                    && serde_json::from_str::<serde_json::Value>(&value).is_ok() =>
+            {
+                Zeroizing::new(value)
+            }
+            Err(e) => {
+                // Leaving a value under the old key would make it unreadable
+                // forever — abort the whole rotation instead.
+                anyhow::bail!(
+                    "Cannot decrypt {}.{} for row {}: {}",
+                    col.table,
+                    col.column,
+                    id,
+                    e
+                );
+            }
+        };
+        let sql = format!("UPDATE {} SET {} = ?1 WHERE id = ?2", col.table, col.column);
+        tx.execute(&sql, [new_encryptor.encrypt(&plaintext)?, id])?;
+
+        on_progress(RotationProgress {
+            phase: RotationPhase::ReEncrypting,
+            processed: processed + 1,
+            total,
+        });
+    }
+
+    on_progress(RotationProgress {
+        phase: RotationPhase::StoringKey,
+        processed: total,
+        total,
+    });
+    // Dropping `tx` on error rolls back, leaving the old key authoritative.
+    key_provider
+        .store_key(&new_key)
+        .context("Failed to store new master key")?;
+
+    if let Err(e) = tx.commit() {
+        warn!("[KeyRotation] Commit failed, restoring previous key: {}", e);
+        if let Err(restore_err) = key_provider.store_key(&old_key) {
+            error!(
+                "[KeyRotation] Failed to restore previous master key: {}",
+                restore_err
+            );
+        }
+        return Err(e).context("Failed to commit re-encrypted values");
+    }
+
+    encryptor.rekey(&new_key)?;
+    drop(db);
+
+    on_progress(RotationProgress {
+        phase: RotationPhase::Completed,
+        processed: total,
+        total,
+    });
+    info!("[KeyRotation] Master key rotated ({} values)", total);
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keychain::MemoryKeyProvider;
+    use crate::SqliteCredentialRepository;
+    use mcpmux_core::{Credential, CredentialRepository, CredentialType};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_rotation_reencrypts_and_swaps_key() {
+        let provider = MemoryKeyProvider::new();
+        let key = provider.get_or_create_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let repo = SqliteCredentialRepository::new(db.clone(), encryptor.clone());
+
+        let space_id = Uuid::new_v4();
+        db.lock()
+            .await
+            .connection()
+            .execute(
+                "INSERT INTO spaces (id, name, created_at, updated_at) VALUES (?1, 'Test', datetime('now'), datetime('now'))",
+                [space_id.to_string()],
+            )
+            .unwrap();
+        repo.save(&Credential::api_key(space_id, "github", "ghp_123"))
+            .await
+            .unwrap();
+
+        let updates = std::sync::Mutex::new(Vec::new());
+        let count = rotate_master_key(&db, &encryptor, &provider, |p| {
+            updates.lock().unwrap().push(p.phase)
+        })
+        .await
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            *updates.lock().unwrap().last().unwrap(),
+            RotationPhase::Completed
+        );
+
+        // Key changed in the provider
+        let new_key = provider.get_or_create_key().unwrap();
+        assert_ne!(*key, *new_key);
+
+        // Repository still reads through the swapped encryptor
+        let cred = repo
+            .get(&space_id, "github", &CredentialType::ApiKey)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cred.value, "ghp_123");
+
+        // And a fresh encryptor with the new key can read it too
+        let fresh = SqliteCredentialRepository::new(
+            db.clone(),
+            Arc::new(FieldEncryptor::new(&new_key).unwrap()),
+        );
+        assert!(fresh
+            .get(&space_id, "github", &CredentialType::ApiKey)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
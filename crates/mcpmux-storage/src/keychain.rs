@@ -5,9 +5,12 @@
 //! - macOS: Keychain
 //! - Linux: Secret Service (GNOME Keyring, KWallet)
 
+use std::path::Path;
+
 use anyhow::{Context, Result};
 use keyring::Entry;
-use mcpmux_core::branding;
+use mcpmux_core::{branding, SecretError, SecretResolver, SecretResolverRegistry};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 use zeroize::Zeroizing;
 
@@ -33,6 +36,16 @@ pub trait MasterKeyProvider: Send + Sync {
 
     /// Delete the master key (for testing or reset).
     fn delete_key(&self) -> Result<()>;
+
+    /// Replace the stored master key (used by key rotation).
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()>;
+
+    /// Where the key is stored, e.g. a file path or keychain entry. Two
+    /// providers with the same location share one copy of the key; `None`
+    /// when unknown.
+    fn location(&self) -> Option<String> {
+        None
+    }
 }
 
 /// OS Keychain-based master key provider.
@@ -40,6 +53,8 @@ pub trait MasterKeyProvider: Send + Sync {
 /// Stores the master key in the platform's native secure storage.
 pub struct KeychainKeyProvider {
     entry: Entry,
+    /// `service/key name` of the entry
+    name: String,
 }
 
 impl KeychainKeyProvider {
@@ -48,7 +63,10 @@ impl KeychainKeyProvider {
         let entry = Entry::new(branding::KEYCHAIN_SERVICE, MASTER_KEY_NAME)
             .context("Failed to create keychain entry")?;
 
-        Ok(Self { entry })
+        Ok(Self {
+            entry,
+            name: format!("{}/{}", branding::KEYCHAIN_SERVICE, MASTER_KEY_NAME),
+        })
     }
 
     /// Create with a custom service and key name (for testing).
@@ -56,7 +74,10 @@ impl KeychainKeyProvider {
     pub fn with_names(service: &str, key_name: &str) -> Result<Self> {
         let entry = Entry::new(service, key_name).context("Failed to create keychain entry")?;
 
-        Ok(Self { entry })
+        Ok(Self {
+            entry,
+            name: format!("{}/{}", service, key_name),
+        })
     }
 }
 
@@ -117,6 +138,19 @@ impl MasterKeyProvider for KeychainKeyProvider {
             Err(e) => Err(anyhow::anyhow!("Failed to delete key from keychain: {}", e)),
         }
     }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        let hex_key = Zeroizing::new(hex::encode(key));
+        self.entry
+            .set_password(&hex_key)
+            .context("Failed to store master key in keychain")?;
+        info!("Master key replaced in keychain");
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(format!("keychain:{}", self.name))
+    }
 }
 
 impl Default for KeychainKeyProvider {
@@ -125,6 +159,65 @@ impl Default for KeychainKeyProvider {
     }
 }
 
+// ============================================================================
+// Provider Selection
+// ============================================================================
+
+/// File (under `<data_dir>/keys/`) recording the selected key provider.
+///
+/// Lives outside the database because the key is needed to open it.
+const PROVIDER_SELECTION_FILE: &str = "provider.json";
+
+/// Which [`MasterKeyProvider`] holds the master key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyProviderKind {
+    /// Platform default: DPAPI on Windows, OS keychain with file fallback elsewhere
+    #[default]
+    Auto,
+    /// OS keychain (Keychain / Secret Service / Credential Manager)
+    Keychain,
+    /// Key file protected by file permissions (DPAPI-encrypted on Windows)
+    File,
+    /// Key wrapped with a passphrase, entered at startup or read from
+    /// `MCPMUX_MASTER_PASSPHRASE`
+    Passphrase,
+    /// Key wrapped by the TPM (Windows, Linux) or Secure Enclave (macOS)
+    Hardware,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProviderSelection {
+    provider: KeyProviderKind,
+}
+
+/// Read the selected key provider, defaulting to [`KeyProviderKind::Auto`].
+pub fn read_key_provider_kind(data_dir: &Path) -> KeyProviderKind {
+    let path = data_dir.join("keys").join(PROVIDER_SELECTION_FILE);
+    match std::fs::read(&path) {
+        Ok(data) => match serde_json::from_slice::<ProviderSelection>(&data) {
+            Ok(selection) => selection.provider,
+            Err(e) => {
+                warn!("Ignoring invalid key provider selection {:?}: {}", path, e);
+                KeyProviderKind::Auto
+            }
+        },
+        Err(_) => KeyProviderKind::Auto,
+    }
+}
+
+/// Persist the selected key provider.
+pub fn write_key_provider_kind(data_dir: &Path, kind: KeyProviderKind) -> Result<()> {
+    let keys_dir = data_dir.join("keys");
+    std::fs::create_dir_all(&keys_dir)
+        .with_context(|| format!("Failed to create keys directory: {:?}", keys_dir))?;
+    let path = keys_dir.join(PROVIDER_SELECTION_FILE);
+    let data = serde_json::to_vec_pretty(&ProviderSelection { provider: kind })?;
+    std::fs::write(&path, data)
+        .with_context(|| format!("Failed to write key provider selection: {:?}", path))?;
+    Ok(())
+}
+
 // ============================================================================
 // JWT Signing Secret Provider
 // ============================================================================
@@ -273,6 +366,62 @@ pub fn generate_jwt_secret() -> Result<[u8; JWT_SECRET_SIZE]> {
     Ok(secret)
 }
 
+// ============================================================================
+// Secret References
+// ============================================================================
+
+/// Resolves `${keychain:item}` secret references from the OS keychain.
+///
+/// `item` is an account under the McpMux keychain service; `service/account`
+/// reads an entry stored by another application.
+pub struct KeychainSecretResolver;
+
+#[async_trait::async_trait]
+impl SecretResolver for KeychainSecretResolver {
+    fn scheme(&self) -> &str {
+        "keychain"
+    }
+
+    async fn resolve(&self, reference: &str) -> std::result::Result<String, SecretError> {
+        let (service, account) = match reference.split_once('/') {
+            Some((service, account)) if !service.is_empty() && !account.is_empty() => {
+                (service.to_string(), account.to_string())
+            }
+            _ => (
+                branding::KEYCHAIN_SERVICE.to_string(),
+                reference.to_string(),
+            ),
+        };
+
+        let lookup = tokio::task::spawn_blocking(move || {
+            Entry::new(&service, &account).and_then(|entry| entry.get_password())
+        })
+        .await
+        .map_err(|e| SecretError::Backend {
+            scheme: "keychain".to_string(),
+            message: e.to_string(),
+        })?;
+
+        match lookup {
+            Ok(value) => Ok(value),
+            Err(keyring::Error::NoEntry) => Err(SecretError::NotFound {
+                scheme: "keychain".to_string(),
+                reference: reference.to_string(),
+            }),
+            Err(e) => Err(SecretError::Backend {
+                scheme: "keychain".to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+/// Default secret resolvers: `env`, the OS keychain, and any external
+/// backend configured through the environment (Vault, 1Password Connect).
+pub fn default_secret_resolvers() -> SecretResolverRegistry {
+    SecretResolverRegistry::from_env().with_resolver(std::sync::Arc::new(KeychainSecretResolver))
+}
+
 /// In-memory JWT secret provider for testing.
 #[cfg(test)]
 pub struct MemoryJwtSecretProvider {
@@ -373,6 +522,11 @@ impl MasterKeyProvider for MemoryKeyProvider {
         *self.key.lock().unwrap() = None;
         Ok(())
     }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        *self.key.lock().unwrap() = Some(*key);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -424,4 +578,16 @@ mod tests {
         // Clean up
         provider.delete_key().unwrap();
     }
+
+    #[test]
+    fn test_provider_selection_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(read_key_provider_kind(dir.path()), KeyProviderKind::Auto);
+
+        write_key_provider_kind(dir.path(), KeyProviderKind::Passphrase).unwrap();
+        assert_eq!(
+            read_key_provider_kind(dir.path()),
+            KeyProviderKind::Passphrase
+        );
+    }
 }
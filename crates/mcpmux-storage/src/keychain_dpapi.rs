@@ -99,6 +99,24 @@ impl MasterKeyProvider for DpapiKeyProvider {
         }
         Ok(())
     }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        let encrypted =
+            encrypt_data(key, Scope::User).context("Failed to encrypt master key with DPAPI")?;
+
+        // Write-then-rename so a crash never leaves a truncated key behind.
+        let tmp_path = self.key_path.with_extension("tmp");
+        fs::write(&tmp_path, &encrypted)
+            .with_context(|| format!("Failed to write key file: {:?}", tmp_path))?;
+        fs::rename(&tmp_path, &self.key_path)
+            .with_context(|| format!("Failed to replace key file: {:?}", self.key_path))?;
+        info!("Master key DPAPI file replaced");
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(self.key_path.display().to_string())
+    }
 }
 
 /// DPAPI-based JWT signing secret provider.
@@ -113,6 +113,20 @@ impl MasterKeyProvider for FileKeyProvider {
         }
         Ok(())
     }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        // Write-then-rename so a crash never leaves a truncated key behind.
+        let tmp_path = self.key_path.with_extension("tmp");
+        write_key_file(&tmp_path, key)?;
+        fs::rename(&tmp_path, &self.key_path)
+            .with_context(|| format!("Failed to replace key file: {:?}", self.key_path))?;
+        info!("Master key file replaced");
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(self.key_path.display().to_string())
+    }
 }
 
 /// File-based JWT signing secret provider.
@@ -0,0 +1,730 @@
+//! Hardware-backed master key storage.
+//!
+//! The master key is sealed by a key that never leaves the machine's security
+//! hardware and stored in `<data_dir>/keys/master.key.hw`. Copying the file to
+//! another machine (or restoring it from a backup) is useless without the chip
+//! that sealed it.
+//!
+//! - **Windows**: an RSA key in the TPM via the Microsoft Platform Crypto
+//!   Provider, wrapping with RSA-OAEP-SHA256.
+//! - **macOS**: a P-256 key in the Secure Enclave, wrapping with ECIES. The app
+//!   must be signed with a keychain access group for the key to persist.
+//! - **Linux**: a TPM 2.0 sealed object, created through `tpm2-tools`
+//!   (`/dev/tpmrm0` must be accessible, usually via the `tss` group).
+//!
+//! File layout:
+//!
+//! ```text
+//! { "version": 1, "backend": "tpm2", "sealed": "<hex>" }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+use crate::crypto::{generate_master_key, KEY_SIZE};
+use crate::keychain::MasterKeyProvider;
+
+/// File name for the sealed master key.
+const SEALED_KEY_FILE: &str = "master.key.hw";
+
+/// Current sealed key file version.
+const SEALED_KEY_VERSION: u32 = 1;
+
+/// Name of the hardware-held wrapping key (Windows, macOS).
+#[cfg(any(windows, target_os = "macos"))]
+const WRAPPING_KEY_NAME: &str = "McpMux Master Key Wrapping";
+
+/// Seals secrets with a key held in security hardware.
+pub trait KeySealer: Send + Sync {
+    /// Short backend name recorded in the sealed key file.
+    fn backend(&self) -> &'static str;
+
+    /// Seal `secret` so only this machine's hardware can recover it.
+    fn seal(&self, secret: &[u8]) -> Result<Vec<u8>>;
+
+    /// Recover a secret sealed by [`KeySealer::seal`].
+    fn unseal(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>>;
+
+    /// Remove the hardware-held wrapping key, if the backend keeps one.
+    fn delete(&self) -> Result<()>;
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedKeyFile {
+    version: u32,
+    backend: String,
+    sealed: String,
+}
+
+/// Master key provider backed by the TPM or Secure Enclave.
+pub struct HardwareKeyProvider {
+    key_path: PathBuf,
+    sealer: Box<dyn KeySealer>,
+}
+
+impl HardwareKeyProvider {
+    /// Create a provider storing the sealed key in `<data_dir>/keys/`.
+    ///
+    /// Fails when this machine has no supported security hardware.
+    pub fn new(data_dir: &Path) -> Result<Self> {
+        Self::with_sealer(data_dir, platform_sealer(data_dir)?)
+    }
+
+    /// Create a provider with a specific sealer.
+    pub(crate) fn with_sealer(data_dir: &Path, sealer: Box<dyn KeySealer>) -> Result<Self> {
+        let keys_dir = data_dir.join("keys");
+        fs::create_dir_all(&keys_dir)
+            .with_context(|| format!("Failed to create keys directory: {:?}", keys_dir))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&keys_dir, fs::Permissions::from_mode(0o700))?;
+        }
+
+        Ok(Self {
+            key_path: keys_dir.join(SEALED_KEY_FILE),
+            sealer,
+        })
+    }
+
+    /// Whether security hardware usable for sealing is present.
+    pub fn is_available(data_dir: &Path) -> bool {
+        platform_sealer(data_dir).is_ok()
+    }
+
+    fn read_key(&self) -> Result<Zeroizing<[u8; KEY_SIZE]>> {
+        let data = fs::read(&self.key_path)
+            .with_context(|| format!("Failed to read sealed key: {:?}", self.key_path))?;
+        let file: SealedKeyFile =
+            serde_json::from_slice(&data).context("Corrupted sealed key file")?;
+        if file.version > SEALED_KEY_VERSION {
+            anyhow::bail!(
+                "Sealed key version {} is newer than supported version {}",
+                file.version,
+                SEALED_KEY_VERSION
+            );
+        }
+        if file.backend != self.sealer.backend() {
+            anyhow::bail!(
+                "Master key was sealed with {} but this machine uses {}",
+                file.backend,
+                self.sealer.backend()
+            );
+        }
+
+        let sealed = hex::decode(&file.sealed).context("Invalid sealed key encoding")?;
+        let key_bytes = self
+            .sealer
+            .unseal(&sealed)
+            .context("Failed to unseal master key. Was it sealed on another machine?")?;
+        if key_bytes.len() != KEY_SIZE {
+            anyhow::bail!(
+                "Invalid sealed key size: expected {}, got {}",
+                KEY_SIZE,
+                key_bytes.len()
+            );
+        }
+
+        let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+        key.copy_from_slice(&key_bytes);
+        Ok(key)
+    }
+
+    fn write_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        let file = SealedKeyFile {
+            version: SEALED_KEY_VERSION,
+            backend: self.sealer.backend().to_string(),
+            sealed: hex::encode(self.sealer.seal(key)?),
+        };
+
+        // Write-then-rename so a crash never leaves a truncated key behind.
+        let tmp_path = self.key_path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("Failed to write sealed key: {:?}", tmp_path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+        }
+        fs::rename(&tmp_path, &self.key_path)
+            .with_context(|| format!("Failed to replace sealed key: {:?}", self.key_path))?;
+        Ok(())
+    }
+}
+
+impl MasterKeyProvider for HardwareKeyProvider {
+    fn get_or_create_key(&self) -> Result<Zeroizing<[u8; KEY_SIZE]>> {
+        if self.key_path.exists() {
+            debug!("Unsealing master key from {:?}", self.key_path);
+            self.read_key()
+        } else {
+            info!(
+                "No master key found, generating new key sealed with {}",
+                self.sealer.backend()
+            );
+            let key = Zeroizing::new(generate_master_key()?);
+            self.write_key(&key)?;
+            info!("Master key generated and stored in {:?}", self.key_path);
+            Ok(key)
+        }
+    }
+
+    fn key_exists(&self) -> bool {
+        self.key_path.exists()
+    }
+
+    fn delete_key(&self) -> Result<()> {
+        if self.key_path.exists() {
+            fs::remove_file(&self.key_path)
+                .with_context(|| format!("Failed to delete sealed key: {:?}", self.key_path))?;
+            info!("Sealed master key deleted");
+        } else {
+            debug!("No sealed key to delete");
+        }
+        if let Err(e) = self.sealer.delete() {
+            warn!("Failed to remove hardware wrapping key: {}", e);
+        }
+        Ok(())
+    }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        self.write_key(key)?;
+        info!("Sealed master key replaced");
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(self.key_path.display().to_string())
+    }
+}
+
+#[cfg(windows)]
+fn platform_sealer(_data_dir: &Path) -> Result<Box<dyn KeySealer>> {
+    Ok(Box::new(tpm_windows::TpmSealer::open()?))
+}
+
+#[cfg(target_os = "macos")]
+fn platform_sealer(_data_dir: &Path) -> Result<Box<dyn KeySealer>> {
+    Ok(Box::new(secure_enclave::SecureEnclaveSealer::open()?))
+}
+
+#[cfg(target_os = "linux")]
+fn platform_sealer(data_dir: &Path) -> Result<Box<dyn KeySealer>> {
+    Ok(Box::new(tpm_linux::Tpm2ToolsSealer::open(data_dir)?))
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn platform_sealer(_data_dir: &Path) -> Result<Box<dyn KeySealer>> {
+    anyhow::bail!("Hardware key storage is not supported on this platform")
+}
+
+#[cfg(windows)]
+mod tpm_windows {
+    use super::*;
+    use windows_sys::core::{HRESULT, PCWSTR};
+    use windows_sys::Win32::Foundation::NTE_BAD_KEYSET;
+    use windows_sys::Win32::Security::Cryptography::{
+        NCryptCreatePersistedKey, NCryptDecrypt, NCryptDeleteKey, NCryptEncrypt, NCryptFinalizeKey,
+        NCryptFreeObject, NCryptOpenKey, NCryptOpenStorageProvider, BCRYPT_OAEP_PADDING_INFO,
+        BCRYPT_RSA_ALGORITHM, BCRYPT_SHA256_ALGORITHM, MS_PLATFORM_CRYPTO_PROVIDER,
+        NCRYPT_KEY_HANDLE, NCRYPT_PAD_OAEP_FLAG, NCRYPT_PROV_HANDLE, NCRYPT_SILENT_FLAG,
+    };
+
+    fn check(status: HRESULT, what: &str) -> Result<()> {
+        if status < 0 {
+            anyhow::bail!("{} failed: 0x{:08x}", what, status as u32);
+        }
+        Ok(())
+    }
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    /// Wraps with an RSA key held by the Platform Crypto Provider (TPM).
+    pub(super) struct TpmSealer {
+        provider: NCRYPT_PROV_HANDLE,
+        key_name: Vec<u16>,
+    }
+
+    // NCrypt handles may be used from any thread.
+    unsafe impl Send for TpmSealer {}
+    unsafe impl Sync for TpmSealer {}
+
+    impl TpmSealer {
+        pub(super) fn open() -> Result<Self> {
+            let mut provider: NCRYPT_PROV_HANDLE = 0;
+            // SAFETY: `provider` is a valid out pointer for the call.
+            let status =
+                unsafe { NCryptOpenStorageProvider(&mut provider, MS_PLATFORM_CRYPTO_PROVIDER, 0) };
+            check(status, "Opening the TPM (Platform Crypto Provider)")?;
+            Ok(Self {
+                provider,
+                key_name: wide(WRAPPING_KEY_NAME),
+            })
+        }
+
+        fn open_key(&self, create: bool) -> Result<NCRYPT_KEY_HANDLE> {
+            let mut key: NCRYPT_KEY_HANDLE = 0;
+            let name = self.key_name.as_ptr() as PCWSTR;
+            // SAFETY: `provider` is open and `name` is NUL-terminated.
+            let status =
+                unsafe { NCryptOpenKey(self.provider, &mut key, name, 0, NCRYPT_SILENT_FLAG) };
+            if status == NTE_BAD_KEYSET && create {
+                // SAFETY: as above; the key handle is finalized before use.
+                unsafe {
+                    check(
+                        NCryptCreatePersistedKey(
+                            self.provider,
+                            &mut key,
+                            BCRYPT_RSA_ALGORITHM,
+                            name,
+                            0,
+                            0,
+                        ),
+                        "Creating TPM wrapping key",
+                    )?;
+                    if let Err(e) = check(
+                        NCryptFinalizeKey(key, NCRYPT_SILENT_FLAG),
+                        "Finalizing TPM wrapping key",
+                    ) {
+                        NCryptFreeObject(key);
+                        return Err(e);
+                    }
+                }
+                info!("Created TPM wrapping key");
+                return Ok(key);
+            }
+            check(status, "Opening TPM wrapping key")?;
+            Ok(key)
+        }
+
+        /// Run NCryptEncrypt or NCryptDecrypt with OAEP-SHA256 padding.
+        fn crypt(&self, input: &[u8], encrypt: bool) -> Result<Vec<u8>> {
+            let key = self.open_key(encrypt)?;
+            let padding = BCRYPT_OAEP_PADDING_INFO {
+                pszAlgId: BCRYPT_SHA256_ALGORITHM,
+                pbLabel: std::ptr::null_mut(),
+                cbLabel: 0,
+            };
+            let padding_ptr = &padding as *const _ as *const core::ffi::c_void;
+            let op = if encrypt {
+                NCryptEncrypt
+            } else {
+                NCryptDecrypt
+            };
+
+            let result = (|| {
+                let mut len = 0u32;
+                // SAFETY: first call sizes the output buffer.
+                check(
+                    unsafe {
+                        op(
+                            key,
+                            input.as_ptr(),
+                            input.len() as u32,
+                            padding_ptr,
+                            std::ptr::null_mut(),
+                            0,
+                            &mut len,
+                            NCRYPT_PAD_OAEP_FLAG,
+                        )
+                    },
+                    "TPM operation",
+                )?;
+                let mut output = vec![0u8; len as usize];
+                // SAFETY: `output` holds `len` bytes.
+                check(
+                    unsafe {
+                        op(
+                            key,
+                            input.as_ptr(),
+                            input.len() as u32,
+                            padding_ptr,
+                            output.as_mut_ptr(),
+                            len,
+                            &mut len,
+                            NCRYPT_PAD_OAEP_FLAG,
+                        )
+                    },
+                    "TPM operation",
+                )?;
+                output.truncate(len as usize);
+                Ok(output)
+            })();
+
+            // SAFETY: `key` was opened above and is not used afterwards.
+            unsafe { NCryptFreeObject(key) };
+            result
+        }
+    }
+
+    impl Drop for TpmSealer {
+        fn drop(&mut self) {
+            // SAFETY: `provider` was opened in `open` and is not used afterwards.
+            unsafe { NCryptFreeObject(self.provider) };
+        }
+    }
+
+    impl KeySealer for TpmSealer {
+        fn backend(&self) -> &'static str {
+            "windows-tpm"
+        }
+
+        fn seal(&self, secret: &[u8]) -> Result<Vec<u8>> {
+            self.crypt(secret, true)
+        }
+
+        fn unseal(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+            Ok(Zeroizing::new(self.crypt(sealed, false)?))
+        }
+
+        fn delete(&self) -> Result<()> {
+            let key = match self.open_key(false) {
+                Ok(key) => key,
+                Err(_) => return Ok(()),
+            };
+            // SAFETY: NCryptDeleteKey frees the handle on success.
+            let status = unsafe { NCryptDeleteKey(key, 0) };
+            if status < 0 {
+                unsafe { NCryptFreeObject(key) };
+            }
+            check(status, "Deleting TPM wrapping key")
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod secure_enclave {
+    use super::*;
+    use security_framework::item::{
+        ItemClass, ItemSearchOptions, Location, Reference, SearchResult,
+    };
+    use security_framework::key::{Algorithm, GenerateKeyOptions, KeyType, SecKey, Token};
+
+    const ALGORITHM: Algorithm = Algorithm::ECIESEncryptionCofactorVariableIVX963SHA256AESGCM;
+
+    /// Wraps with a P-256 key held by the Secure Enclave.
+    pub(super) struct SecureEnclaveSealer;
+
+    impl SecureEnclaveSealer {
+        pub(super) fn open() -> Result<Self> {
+            // Creating (or finding) the key is the only reliable availability check.
+            Self::key(true)?;
+            Ok(Self)
+        }
+
+        fn find() -> Option<SecKey> {
+            let results = ItemSearchOptions::new()
+                .class(ItemClass::key())
+                .label(WRAPPING_KEY_NAME)
+                .load_refs(true)
+                .search()
+                .ok()?;
+            results.into_iter().find_map(|result| match result {
+                SearchResult::Ref(Reference::Key(key)) => Some(key),
+                _ => None,
+            })
+        }
+
+        fn key(create: bool) -> Result<SecKey> {
+            if let Some(key) = Self::find() {
+                return Ok(key);
+            }
+            if !create {
+                anyhow::bail!("Secure Enclave wrapping key not found");
+            }
+            let mut options = GenerateKeyOptions::default();
+            options
+                .set_key_type(KeyType::ec_sec_prime_random())
+                .set_size_in_bits(256)
+                .set_label(WRAPPING_KEY_NAME)
+                .set_token(Token::SecureEnclave)
+                .set_location(Location::DataProtectionKeychain);
+            let key = SecKey::new(&options)
+                .map_err(|e| anyhow::anyhow!("Secure Enclave unavailable: {}", e))?;
+            info!("Created Secure Enclave wrapping key");
+            Ok(key)
+        }
+    }
+
+    impl KeySealer for SecureEnclaveSealer {
+        fn backend(&self) -> &'static str {
+            "secure-enclave"
+        }
+
+        fn seal(&self, secret: &[u8]) -> Result<Vec<u8>> {
+            let public = Self::key(true)?
+                .public_key()
+                .context("Secure Enclave key has no public key")?;
+            public
+                .encrypt_data(ALGORITHM, secret)
+                .map_err(|e| anyhow::anyhow!("Secure Enclave encryption failed: {}", e))
+        }
+
+        fn unseal(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+            Self::key(false)?
+                .decrypt_data(ALGORITHM, sealed)
+                .map(Zeroizing::new)
+                .map_err(|e| anyhow::anyhow!("Secure Enclave decryption failed: {}", e))
+        }
+
+        fn delete(&self) -> Result<()> {
+            if let Some(key) = Self::find() {
+                key.delete()
+                    .map_err(|e| anyhow::anyhow!("Failed to delete Secure Enclave key: {}", e))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod tpm_linux {
+    use super::*;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// TPM resource manager device used by `tpm2-tools`.
+    const TPM_DEVICE: &str = "/dev/tpmrm0";
+
+    /// Seals with a TPM 2.0 sealed data object via `tpm2-tools`.
+    ///
+    /// The object is created under the owner hierarchy's default primary key,
+    /// which the TPM derives from its seed, so nothing but the sealed
+    /// public/private blobs needs to be stored.
+    pub(super) struct Tpm2ToolsSealer {
+        work_dir: PathBuf,
+    }
+
+    impl Tpm2ToolsSealer {
+        pub(super) fn open(data_dir: &Path) -> Result<Self> {
+            if !Path::new(TPM_DEVICE).exists() {
+                anyhow::bail!("No TPM found ({} does not exist)", TPM_DEVICE);
+            }
+            let found = Command::new("tpm2_unseal")
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false);
+            if !found {
+                anyhow::bail!("TPM found but tpm2-tools is not installed");
+            }
+            Ok(Self {
+                work_dir: data_dir.join("keys").join(".tpm"),
+            })
+        }
+
+        /// Run a tpm2-tools command, optionally feeding `stdin`.
+        fn run(&self, program: &str, args: &[&str], stdin: Option<&[u8]>) -> Result<Vec<u8>> {
+            let mut child = Command::new(program)
+                .args(args)
+                .current_dir(&self.work_dir)
+                .env("TPM2TOOLS_TCTI", format!("device:{}", TPM_DEVICE))
+                .stdin(if stdin.is_some() {
+                    Stdio::piped()
+                } else {
+                    Stdio::null()
+                })
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .with_context(|| format!("Failed to run {}", program))?;
+            if let Some(input) = stdin {
+                child
+                    .stdin
+                    .take()
+                    .context("stdin not captured")?
+                    .write_all(input)?;
+            }
+            let output = child.wait_with_output()?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "{} failed: {}",
+                    program,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Ok(output.stdout)
+        }
+
+        /// Run `f` in a fresh scratch directory for context files.
+        fn in_work_dir<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+            fs::create_dir_all(&self.work_dir)
+                .with_context(|| format!("Failed to create {:?}", self.work_dir))?;
+            let result = self
+                .run(
+                    "tpm2_createprimary",
+                    &["-C", "o", "-c", "primary.ctx"],
+                    None,
+                )
+                .and_then(|_| f());
+            if let Err(e) = fs::remove_dir_all(&self.work_dir) {
+                warn!("Failed to clean up {:?}: {}", self.work_dir, e);
+            }
+            result
+        }
+    }
+
+    impl KeySealer for Tpm2ToolsSealer {
+        fn backend(&self) -> &'static str {
+            "tpm2"
+        }
+
+        fn seal(&self, secret: &[u8]) -> Result<Vec<u8>> {
+            self.in_work_dir(|| {
+                self.run(
+                    "tpm2_create",
+                    &[
+                        "-C",
+                        "primary.ctx",
+                        "-i",
+                        "-",
+                        "-u",
+                        "seal.pub",
+                        "-r",
+                        "seal.priv",
+                    ],
+                    Some(secret),
+                )?;
+                let public = fs::read(self.work_dir.join("seal.pub"))?;
+                let private = fs::read(self.work_dir.join("seal.priv"))?;
+
+                // u32 length of the public blob, then public, then private
+                let mut sealed = (public.len() as u32).to_be_bytes().to_vec();
+                sealed.extend_from_slice(&public);
+                sealed.extend_from_slice(&private);
+                Ok(sealed)
+            })
+        }
+
+        fn unseal(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+            if sealed.len() < 4 {
+                anyhow::bail!("Truncated sealed key");
+            }
+            let (len, rest) = sealed.split_at(4);
+            let public_len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            if rest.len() < public_len {
+                anyhow::bail!("Truncated sealed key");
+            }
+            let (public, private) = rest.split_at(public_len);
+
+            self.in_work_dir(|| {
+                fs::write(self.work_dir.join("seal.pub"), public)?;
+                fs::write(self.work_dir.join("seal.priv"), private)?;
+                self.run(
+                    "tpm2_load",
+                    &[
+                        "-C",
+                        "primary.ctx",
+                        "-u",
+                        "seal.pub",
+                        "-r",
+                        "seal.priv",
+                        "-c",
+                        "seal.ctx",
+                    ],
+                    None,
+                )?;
+                Ok(Zeroizing::new(self.run(
+                    "tpm2_unseal",
+                    &["-c", "seal.ctx"],
+                    None,
+                )?))
+            })
+        }
+
+        fn delete(&self) -> Result<()> {
+            // Sealed objects live only in the key file; nothing is persisted in the TPM.
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// XORs with a fixed pad; stands in for the hardware.
+    struct FakeSealer {
+        deleted: Arc<AtomicBool>,
+    }
+
+    impl KeySealer for FakeSealer {
+        fn backend(&self) -> &'static str {
+            "fake"
+        }
+
+        fn seal(&self, secret: &[u8]) -> Result<Vec<u8>> {
+            Ok(secret.iter().map(|b| b ^ 0x5a).collect())
+        }
+
+        fn unseal(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+            Ok(Zeroizing::new(sealed.iter().map(|b| b ^ 0x5a).collect()))
+        }
+
+        fn delete(&self) -> Result<()> {
+            self.deleted.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn provider(dir: &Path) -> (HardwareKeyProvider, Arc<AtomicBool>) {
+        let deleted = Arc::new(AtomicBool::new(false));
+        let sealer = FakeSealer {
+            deleted: deleted.clone(),
+        };
+        (
+            HardwareKeyProvider::with_sealer(dir, Box::new(sealer)).unwrap(),
+            deleted,
+        )
+    }
+
+    #[test]
+    fn test_sealed_key_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let (p, deleted) = provider(dir.path());
+        assert!(!p.key_exists());
+
+        let key = p.get_or_create_key().unwrap();
+        assert!(p.key_exists());
+        assert_eq!(*key, *p.get_or_create_key().unwrap());
+
+        // Only the sealed form is written
+        let raw = fs::read_to_string(dir.path().join("keys").join(SEALED_KEY_FILE)).unwrap();
+        assert!(!raw.contains(&hex::encode(*key)));
+
+        let replacement = generate_master_key().unwrap();
+        p.store_key(&replacement).unwrap();
+        assert_eq!(*p.get_or_create_key().unwrap(), replacement);
+
+        p.delete_key().unwrap();
+        assert!(!p.key_exists());
+        assert!(deleted.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_other_backend_rejected() {
+        let dir = TempDir::new().unwrap();
+        let (p, _) = provider(dir.path());
+        p.get_or_create_key().unwrap();
+
+        let path = dir.path().join("keys").join(SEALED_KEY_FILE);
+        let mut file: SealedKeyFile = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        file.backend = "tpm2".to_string();
+        fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+
+        let err = p.get_or_create_key().unwrap_err().to_string();
+        assert!(err.contains("sealed with tpm2"), "{err}");
+    }
+}
@@ -0,0 +1,382 @@
+//! Passphrase-protected master key storage.
+//!
+//! For systems without an OS keychain (e.g. Linux distros with no Secret
+//! Service) where a plaintext key file is not acceptable. The random master key
+//! is wrapped with a key derived from a user passphrase (argon2id) and stored
+//! in `<data_dir>/keys/master.key.wrapped`. Changing the passphrase only
+//! rewraps the master key — encrypted data is untouched.
+//!
+//! The passphrase is entered when McpMux starts, or read from
+//! `MCPMUX_MASTER_PASSPHRASE` for headless use.
+//!
+//! File layout:
+//!
+//! ```text
+//! { "version": 2, "kdf": "argon2id", "memory_kib": 65536, "iterations": 3,
+//!   "parallelism": 1, "salt": "<hex>", "wrapped_key": "<hex>" }
+//! ```
+//!
+//! Version 1 files (`"kdf": "pbkdf2-sha256"`) are still read, and rewrapped
+//! with argon2id the first time they are unlocked.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+use crate::crypto::{generate_master_key, FieldEncryptor, KEY_SIZE};
+use crate::keychain::MasterKeyProvider;
+use crate::secrets_bundle::{derive_key, MIN_PASSPHRASE_LEN};
+
+/// Environment variable holding the master key passphrase.
+pub const PASSPHRASE_ENV_VAR: &str = "MCPMUX_MASTER_PASSPHRASE";
+
+/// File name for the wrapped master key.
+const WRAPPED_KEY_FILE: &str = "master.key.wrapped";
+
+/// Current wrapped key file version.
+const WRAPPED_KEY_VERSION: u32 = 2;
+
+/// Key derivation of version 2 files.
+const KDF_ARGON2ID: &str = "argon2id";
+
+/// Key derivation of version 1 files.
+const KDF_PBKDF2: &str = "pbkdf2-sha256";
+
+/// Salt size in bytes.
+const SALT_SIZE: usize = 16;
+
+/// Argon2id cost parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Argon2Cost {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Argon2Cost {
+    /// OWASP 2023 recommendation: 64 MiB, 3 passes, 1 lane.
+    const DEFAULT: Self = Self {
+        memory_kib: 64 * 1024,
+        iterations: 3,
+        parallelism: 1,
+    };
+}
+
+#[derive(Serialize, Deserialize)]
+struct WrappedKeyFile {
+    version: u32,
+    kdf: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    memory_kib: u32,
+    iterations: u32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    parallelism: u32,
+    salt: String,
+    wrapped_key: String,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+/// Derive the key-wrapping key from a passphrase with argon2id.
+fn derive_argon2_key(passphrase: &str, salt: &[u8], cost: Argon2Cost) -> Result<FieldEncryptor> {
+    let params = Params::new(
+        cost.memory_kib,
+        cost.iterations,
+        cost.parallelism,
+        Some(KEY_SIZE),
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid argon2 parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key[..])
+        .map_err(|e| anyhow::anyhow!("Failed to derive key from passphrase: {}", e))?;
+    FieldEncryptor::new(&key)
+}
+
+/// Passphrase-based master key provider.
+pub struct PassphraseKeyProvider {
+    key_path: PathBuf,
+    passphrase: Zeroizing<String>,
+    cost: Argon2Cost,
+}
+
+impl PassphraseKeyProvider {
+    /// Create a provider storing the wrapped key in `<data_dir>/keys/`.
+    pub fn new(data_dir: &Path, passphrase: impl Into<String>) -> Result<Self> {
+        let passphrase = Zeroizing::new(passphrase.into());
+        if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            anyhow::bail!(
+                "Master key passphrase must be at least {} characters",
+                MIN_PASSPHRASE_LEN
+            );
+        }
+
+        let keys_dir = data_dir.join("keys");
+        fs::create_dir_all(&keys_dir)
+            .with_context(|| format!("Failed to create keys directory: {:?}", keys_dir))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&keys_dir, fs::Permissions::from_mode(0o700))?;
+        }
+
+        Ok(Self {
+            key_path: keys_dir.join(WRAPPED_KEY_FILE),
+            passphrase,
+            cost: Argon2Cost::DEFAULT,
+        })
+    }
+
+    /// Create a provider using the passphrase from [`PASSPHRASE_ENV_VAR`].
+    pub fn from_env(data_dir: &Path) -> Result<Self> {
+        let passphrase = std::env::var(PASSPHRASE_ENV_VAR).with_context(|| {
+            format!(
+                "Passphrase key provider selected but {} is not set",
+                PASSPHRASE_ENV_VAR
+            )
+        })?;
+        Self::new(data_dir, passphrase)
+    }
+
+    /// Whether a wrapped key exists in `data_dir`, i.e. whether a passphrase
+    /// entered now unlocks an existing key rather than creating one.
+    pub fn key_exists_in(data_dir: &Path) -> bool {
+        data_dir.join("keys").join(WRAPPED_KEY_FILE).exists()
+    }
+
+    /// Lower the argon2 work factor (for testing).
+    #[cfg(test)]
+    pub(crate) fn with_test_cost(mut self) -> Self {
+        self.cost = Argon2Cost {
+            memory_kib: 8,
+            iterations: 1,
+            parallelism: 1,
+        };
+        self
+    }
+
+    /// Rewrap the stored master key under a new passphrase.
+    pub fn change_passphrase(&self, new_passphrase: &str) -> Result<()> {
+        if new_passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            anyhow::bail!(
+                "Master key passphrase must be at least {} characters",
+                MIN_PASSPHRASE_LEN
+            );
+        }
+        let key = self.read_key()?;
+        self.write_key(&key, new_passphrase)?;
+        info!("Master key passphrase changed");
+        Ok(())
+    }
+
+    fn read_key(&self) -> Result<Zeroizing<[u8; KEY_SIZE]>> {
+        let data = fs::read(&self.key_path)
+            .with_context(|| format!("Failed to read wrapped key: {:?}", self.key_path))?;
+        let file: WrappedKeyFile =
+            serde_json::from_slice(&data).context("Corrupted wrapped key file")?;
+        if file.version > WRAPPED_KEY_VERSION {
+            anyhow::bail!(
+                "Wrapped key version {} is newer than supported version {}",
+                file.version,
+                WRAPPED_KEY_VERSION
+            );
+        }
+
+        let salt = hex::decode(&file.salt).context("Invalid salt encoding")?;
+        let wrapping_key = match file.kdf.as_str() {
+            KDF_ARGON2ID => derive_argon2_key(
+                &self.passphrase,
+                &salt,
+                Argon2Cost {
+                    memory_kib: file.memory_kib,
+                    iterations: file.iterations,
+                    parallelism: file.parallelism,
+                },
+            )?,
+            KDF_PBKDF2 => derive_key(&self.passphrase, &salt, file.iterations)?,
+            other => anyhow::bail!("Unsupported key derivation: {}", other),
+        };
+        let key_hex = Zeroizing::new(
+            wrapping_key
+                .decrypt(&file.wrapped_key)
+                .map_err(|_| anyhow::anyhow!("Wrong master key passphrase"))?,
+        );
+        let key_bytes = Zeroizing::new(hex::decode(&*key_hex).context("Corrupted wrapped key")?);
+        if key_bytes.len() != KEY_SIZE {
+            anyhow::bail!(
+                "Invalid wrapped key size: expected {}, got {}",
+                KEY_SIZE,
+                key_bytes.len()
+            );
+        }
+
+        let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+        key.copy_from_slice(&key_bytes);
+
+        if file.kdf == KDF_PBKDF2 {
+            match self.write_key(&key, &self.passphrase) {
+                Ok(()) => info!("Rewrapped master key with argon2id"),
+                Err(e) => warn!("Failed to rewrap master key with argon2id: {}", e),
+            }
+        }
+        Ok(key)
+    }
+
+    fn write_key(&self, key: &[u8; KEY_SIZE], passphrase: &str) -> Result<()> {
+        let mut salt = [0u8; SALT_SIZE];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| anyhow::anyhow!("Failed to generate salt"))?;
+
+        let key_hex = Zeroizing::new(hex::encode(key));
+        let file = WrappedKeyFile {
+            version: WRAPPED_KEY_VERSION,
+            kdf: KDF_ARGON2ID.to_string(),
+            memory_kib: self.cost.memory_kib,
+            iterations: self.cost.iterations,
+            parallelism: self.cost.parallelism,
+            salt: hex::encode(salt),
+            wrapped_key: derive_argon2_key(passphrase, &salt, self.cost)?.encrypt(&key_hex)?,
+        };
+
+        // Write-then-rename so a crash never leaves a truncated key behind.
+        let tmp_path = self.key_path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("Failed to write wrapped key: {:?}", tmp_path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+        }
+        fs::rename(&tmp_path, &self.key_path)
+            .with_context(|| format!("Failed to replace wrapped key: {:?}", self.key_path))?;
+        Ok(())
+    }
+}
+
+impl MasterKeyProvider for PassphraseKeyProvider {
+    fn get_or_create_key(&self) -> Result<Zeroizing<[u8; KEY_SIZE]>> {
+        if self.key_path.exists() {
+            debug!("Unwrapping master key from {:?}", self.key_path);
+            self.read_key()
+        } else {
+            info!("No master key found, generating new passphrase-wrapped key");
+            let key = Zeroizing::new(generate_master_key()?);
+            self.write_key(&key, &self.passphrase)?;
+            info!("Master key generated and stored in {:?}", self.key_path);
+            Ok(key)
+        }
+    }
+
+    fn key_exists(&self) -> bool {
+        self.key_path.exists()
+    }
+
+    fn delete_key(&self) -> Result<()> {
+        if self.key_path.exists() {
+            fs::remove_file(&self.key_path)
+                .with_context(|| format!("Failed to delete wrapped key: {:?}", self.key_path))?;
+            info!("Wrapped master key deleted");
+        } else {
+            debug!("No wrapped key to delete");
+        }
+        Ok(())
+    }
+
+    fn store_key(&self, key: &[u8; KEY_SIZE]) -> Result<()> {
+        self.write_key(key, &self.passphrase)?;
+        info!("Wrapped master key replaced");
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(self.key_path.display().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn provider(dir: &Path, passphrase: &str) -> PassphraseKeyProvider {
+        PassphraseKeyProvider::new(dir, passphrase)
+            .unwrap()
+            .with_test_cost()
+    }
+
+    fn read_file(dir: &Path) -> WrappedKeyFile {
+        let raw = fs::read(dir.join("keys").join(WRAPPED_KEY_FILE)).unwrap();
+        serde_json::from_slice(&raw).unwrap()
+    }
+
+    #[test]
+    fn test_key_roundtrip_and_passphrase_change() {
+        let dir = TempDir::new().unwrap();
+        let p = provider(dir.path(), "correct horse");
+        let key = p.get_or_create_key().unwrap();
+        assert!(p.key_exists());
+        assert!(PassphraseKeyProvider::key_exists_in(dir.path()));
+        assert_eq!(*key, *p.get_or_create_key().unwrap());
+        assert_eq!(read_file(dir.path()).kdf, KDF_ARGON2ID);
+
+        // The wrapped file never contains the raw key
+        let raw = fs::read_to_string(dir.path().join("keys").join(WRAPPED_KEY_FILE)).unwrap();
+        assert!(!raw.contains(&hex::encode(*key)));
+
+        assert!(provider(dir.path(), "wrong passphrase")
+            .get_or_create_key()
+            .is_err());
+
+        p.change_passphrase("battery staple").unwrap();
+        let reopened = provider(dir.path(), "battery staple");
+        assert_eq!(*key, *reopened.get_or_create_key().unwrap());
+    }
+
+    #[test]
+    fn test_pbkdf2_file_upgraded() {
+        let dir = TempDir::new().unwrap();
+        let p = provider(dir.path(), "correct horse");
+        let key = generate_master_key().unwrap();
+
+        // A version 1 file, as written before argon2id
+        let salt = [7u8; SALT_SIZE];
+        let v1 = WrappedKeyFile {
+            version: 1,
+            kdf: KDF_PBKDF2.to_string(),
+            memory_kib: 0,
+            iterations: 1_000,
+            parallelism: 0,
+            salt: hex::encode(salt),
+            wrapped_key: derive_key("correct horse", &salt, 1_000)
+                .unwrap()
+                .encrypt(&hex::encode(key))
+                .unwrap(),
+        };
+        fs::write(
+            dir.path().join("keys").join(WRAPPED_KEY_FILE),
+            serde_json::to_vec(&v1).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(*p.get_or_create_key().unwrap(), key);
+        let upgraded = read_file(dir.path());
+        assert_eq!(upgraded.version, WRAPPED_KEY_VERSION);
+        assert_eq!(upgraded.kdf, KDF_ARGON2ID);
+        assert_eq!(*p.get_or_create_key().unwrap(), key);
+    }
+
+    #[test]
+    fn test_short_passphrase_rejected() {
+        let dir = TempDir::new().unwrap();
+        assert!(PassphraseKeyProvider::new(dir.path(), "short").is_err());
+    }
+}
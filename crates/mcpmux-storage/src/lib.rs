@@ -1,6 +1,8 @@
 //! McpMux Storage Layer
 //!
-//! SQLite database with field-level encryption for sensitive data.
+//! SQLite database with field-level encryption for sensitive data, or a
+//! shared PostgreSQL database (see [`postgres`]) for gateways behind a load
+//! balancer.
 //!
 //! # Architecture
 //!
@@ -11,8 +13,8 @@
 //! │               Repository Traits                      │
 //! │        (SpaceRepository, CredentialRepository, etc.) │
 //! ├──────────────────────────────────────────────────────┤
-//! │            SQLite Implementations                    │
-//! │    (SqliteSpaceRepository, SqliteCredentialRepo)     │
+//! │       SQLite / PostgreSQL Implementations            │
+//! │    (SqliteSpaceRepository, PgSpaceRepository, ...)   │
 //! ├──────────────────────────────────────────────────────┤
 //! │         FieldEncryptor (AES-256-GCM)                 │
 //! │        (Encrypts tokens/credentials)                 │
@@ -21,7 +23,7 @@
 //! │    (DPAPI file storage / OS Keychain)                 │
 //! ├──────────────────────────────────────────────────────┤
 //! │                   Database                           │
-//! │                   (SQLite)                           │
+//! │             (SQLite / PostgreSQL)                    │
 //! └──────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -49,28 +51,50 @@
 //! // Create repositories
 //! let space_repo = SqliteSpaceRepository::new(db.clone());
 //! let credential_repo = SqliteCredentialRepository::new(db.clone(), encryptor);
+//!
+//! // Or every repository at once, on SQLite or PostgreSQL
+//! // (`MCPMUX_DATABASE_URL`)
+//! let config = StorageConfig::from_env(&data_dir);
+//! let repos = RepositorySet::open(&config, encryptor).await?;
 //! ```
 
+pub mod backup;
 pub mod crypto;
 mod database;
+pub mod doctor;
+mod key_rotation;
 pub mod keychain;
 #[cfg(windows)]
 pub mod keychain_dpapi;
 #[cfg(not(windows))]
 pub mod keychain_file;
+pub mod keychain_hardware;
+pub mod keychain_passphrase;
+mod migrations;
+pub mod postgres;
 mod repositories;
+mod repository_set;
+pub mod secrets_bundle;
 
+pub use backup::{apply_pending_restore, BackupInfo, BackupKind, BackupManager, BackupManifest};
 pub use crypto::{generate_master_key, FieldEncryptor, KEY_SIZE};
-pub use database::Database;
+pub use database::{Database, ReadPool};
+pub use doctor::DoctorReport;
+pub use key_rotation::{rotate_master_key, RotationPhase, RotationProgress};
 pub use keychain::{
-    generate_jwt_secret, JwtSecretProvider, KeychainJwtSecretProvider, KeychainKeyProvider,
-    MasterKeyProvider, JWT_SECRET_SIZE,
+    default_secret_resolvers, generate_jwt_secret, read_key_provider_kind, write_key_provider_kind,
+    JwtSecretProvider, KeyProviderKind, KeychainJwtSecretProvider, KeychainKeyProvider,
+    KeychainSecretResolver, MasterKeyProvider, JWT_SECRET_SIZE,
 };
 #[cfg(windows)]
 pub use keychain_dpapi::{DpapiJwtSecretProvider, DpapiKeyProvider};
 #[cfg(not(windows))]
 pub use keychain_file::{FileJwtSecretProvider, FileKeyProvider};
+pub use keychain_hardware::{HardwareKeyProvider, KeySealer};
+pub use keychain_passphrase::{PassphraseKeyProvider, PASSPHRASE_ENV_VAR};
 pub use repositories::*;
+pub use repository_set::{RepositorySet, StorageConfig, DATABASE_URL_ENV_VAR};
+pub use secrets_bundle::{export_secrets, import_secrets, SecretsBundle, SecretsTransferSummary};
 
 /// Default database file name.
 pub const DATABASE_FILE: &str = "mcpmux.db";
@@ -80,13 +104,37 @@ pub fn default_database_path() -> Option<std::path::PathBuf> {
     dirs::data_local_dir().map(|p| p.join("mcpmux").join(DATABASE_FILE))
 }
 
-/// Create the platform-appropriate master key provider.
+/// Create the master key provider selected in `<data_dir>/keys/provider.json`.
 ///
+/// With [`KeyProviderKind::Auto`] (the default):
 /// - **Windows**: Uses DPAPI file-based storage (key not visible in Credential Manager UI).
 ///   Also migrates existing keys from Credential Manager on first use.
 /// - **macOS/Linux**: Uses the OS keychain (Keychain / Secret Service).
 pub fn create_key_provider(
     data_dir: &std::path::Path,
+) -> anyhow::Result<Box<dyn MasterKeyProvider>> {
+    create_key_provider_of_kind(data_dir, read_key_provider_kind(data_dir))
+}
+
+/// Create a specific master key provider.
+pub fn create_key_provider_of_kind(
+    data_dir: &std::path::Path,
+    kind: KeyProviderKind,
+) -> anyhow::Result<Box<dyn MasterKeyProvider>> {
+    match kind {
+        KeyProviderKind::Auto => create_default_key_provider(data_dir),
+        KeyProviderKind::Keychain => Ok(Box::new(KeychainKeyProvider::new()?)),
+        #[cfg(windows)]
+        KeyProviderKind::File => Ok(Box::new(DpapiKeyProvider::new(data_dir)?)),
+        #[cfg(not(windows))]
+        KeyProviderKind::File => Ok(Box::new(FileKeyProvider::new(data_dir)?)),
+        KeyProviderKind::Passphrase => Ok(Box::new(PassphraseKeyProvider::from_env(data_dir)?)),
+        KeyProviderKind::Hardware => Ok(Box::new(HardwareKeyProvider::new(data_dir)?)),
+    }
+}
+
+fn create_default_key_provider(
+    data_dir: &std::path::Path,
 ) -> anyhow::Result<Box<dyn MasterKeyProvider>> {
     #[cfg(windows)]
     {
@@ -116,6 +164,80 @@ pub fn create_key_provider(
     }
 }
 
+/// Move the master key from `current` into the provider of `kind` and persist
+/// the selection. Takes effect on next start.
+///
+/// Uses [`create_key_provider_of_kind`] for the target, so the passphrase
+/// provider reads `MCPMUX_MASTER_PASSPHRASE`; use [`switch_key_provider_to`]
+/// to supply a provider built with an entered passphrase.
+pub fn switch_key_provider(
+    data_dir: &std::path::Path,
+    current: &dyn MasterKeyProvider,
+    kind: KeyProviderKind,
+) -> anyhow::Result<()> {
+    if kind == read_key_provider_kind(data_dir) {
+        return Ok(());
+    }
+    let target = create_key_provider_of_kind(data_dir, kind)?;
+    switch_key_provider_to(data_dir, current, kind, target.as_ref())
+}
+
+/// Move the master key from `current` into `target` (a provider of `kind`)
+/// and persist the selection.
+///
+/// The key itself is unchanged, so no data needs re-encrypting. The new copy
+/// is written and read back before the selection changes, and the old copy is
+/// only removed when it lives in a different store than the new one.
+pub fn switch_key_provider_to(
+    data_dir: &std::path::Path,
+    current: &dyn MasterKeyProvider,
+    kind: KeyProviderKind,
+    target: &dyn MasterKeyProvider,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let key = current
+        .get_or_create_key()
+        .context("Failed to read current master key")?;
+
+    let current_location = current.location();
+    let target_location = target.location();
+    // Different kinds can resolve to one store (e.g. `Auto` falling back to
+    // the key file). Rewriting it in place is fine; deleting it is not.
+    let same_store = current_location.is_some() && current_location == target_location;
+
+    if !same_store || !target.key_exists() {
+        target
+            .store_key(&key)
+            .context("Failed to store master key in new provider")?;
+    }
+    // Verify before committing to the switch
+    if !target.key_exists() {
+        anyhow::bail!("New key provider did not keep the master key");
+    }
+    let stored = target
+        .get_or_create_key()
+        .context("Failed to read master key back from new provider")?;
+    if *stored != *key {
+        anyhow::bail!("New key provider returned a different master key");
+    }
+    write_key_provider_kind(data_dir, kind)?;
+
+    match (&current_location, &target_location) {
+        (Some(from), Some(to)) if from != to => {
+            if let Err(e) = current.delete_key() {
+                tracing::warn!("Failed to remove master key from previous provider: {}", e);
+            }
+        }
+        (Some(_), Some(_)) => {}
+        _ => tracing::warn!(
+            "Previous key provider location unknown; leaving its copy of the master key in place"
+        ),
+    }
+    tracing::info!("Master key provider switched to {:?}", kind);
+    Ok(())
+}
+
 /// Create the platform-appropriate JWT secret provider.
 ///
 /// - **Windows**: Uses DPAPI file-based storage.
@@ -144,3 +266,86 @@ pub fn create_jwt_secret_provider(
         Ok(Box::new(FileJwtSecretProvider::new(data_dir)?))
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn passphrase_provider(dir: &std::path::Path) -> PassphraseKeyProvider {
+        PassphraseKeyProvider::new(dir, "correct horse")
+            .unwrap()
+            .with_test_cost()
+    }
+
+    #[test]
+    fn test_switch_between_kinds_keeps_key() {
+        let dir = TempDir::new().unwrap();
+        let file = FileKeyProvider::new(dir.path()).unwrap();
+        let key = file.get_or_create_key().unwrap();
+
+        let passphrase = passphrase_provider(dir.path());
+        switch_key_provider_to(dir.path(), &file, KeyProviderKind::Passphrase, &passphrase)
+            .unwrap();
+        assert_eq!(
+            read_key_provider_kind(dir.path()),
+            KeyProviderKind::Passphrase
+        );
+        assert_eq!(*passphrase.get_or_create_key().unwrap(), *key);
+        assert!(!file.key_exists(), "old copy should be removed");
+
+        switch_key_provider_to(dir.path(), &passphrase, KeyProviderKind::File, &file).unwrap();
+        assert_eq!(read_key_provider_kind(dir.path()), KeyProviderKind::File);
+        assert_eq!(*file.get_or_create_key().unwrap(), *key);
+        assert!(!passphrase.key_exists());
+    }
+
+    #[test]
+    fn test_switch_within_same_store_keeps_key() {
+        // `Auto` falling back to the key file and `File` share one store
+        let dir = TempDir::new().unwrap();
+        let auto = FileKeyProvider::new(dir.path()).unwrap();
+        let key = auto.get_or_create_key().unwrap();
+
+        let file = FileKeyProvider::new(dir.path()).unwrap();
+        switch_key_provider_to(dir.path(), &auto, KeyProviderKind::File, &file).unwrap();
+        assert!(file.key_exists());
+        assert_eq!(*file.get_or_create_key().unwrap(), *key);
+
+        switch_key_provider_to(dir.path(), &file, KeyProviderKind::Auto, &auto).unwrap();
+        assert_eq!(*auto.get_or_create_key().unwrap(), *key);
+    }
+
+    #[test]
+    fn test_failed_switch_keeps_selection() {
+        let dir = TempDir::new().unwrap();
+        let file = FileKeyProvider::new(dir.path()).unwrap();
+        let key = file.get_or_create_key().unwrap();
+
+        // A provider that accepts the key but never keeps it
+        struct Broken;
+        impl MasterKeyProvider for Broken {
+            fn get_or_create_key(&self) -> anyhow::Result<zeroize::Zeroizing<[u8; KEY_SIZE]>> {
+                anyhow::bail!("unavailable")
+            }
+            fn key_exists(&self) -> bool {
+                false
+            }
+            fn delete_key(&self) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn store_key(&self, _key: &[u8; KEY_SIZE]) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn location(&self) -> Option<String> {
+                Some("broken".to_string())
+            }
+        }
+
+        assert!(
+            switch_key_provider_to(dir.path(), &file, KeyProviderKind::Hardware, &Broken).is_err()
+        );
+        assert_eq!(read_key_provider_kind(dir.path()), KeyProviderKind::Auto);
+        assert_eq!(*file.get_or_create_key().unwrap(), *key);
+    }
+}
@@ -1,5 +1,248 @@
 //! Database migrations
 //!
-//! Schema version management and migration scripts.
+//! Versioned migration registry. Each migration's SQL is checksummed
+//! (SHA-256) when applied; the runner in [`crate::Database`] refuses to open a
+//! database whose recorded checksums no longer match, or whose schema is newer
+//! than this build knows about.
 
-// Placeholder - will be implemented in Phase 1, Task 01
+use sha2::{Digest, Sha256};
+
+/// A database migration with version number and SQL content.
+pub(crate) struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// All migrations in order. Add new migrations here.
+///
+/// Note: Migrations have been consolidated into a single clean initial migration.
+/// The schema includes cached_definition for offline operation and excludes
+/// runtime fields (connection_status, last_connected_at, last_error).
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("migrations/001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "featureset_resolver",
+        sql: include_str!("migrations/002_featureset_resolver.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "drop_legacy_grants",
+        sql: include_str!("migrations/003_drop_legacy_grants.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "workspace_modes",
+        sql: include_str!("migrations/004_workspace_modes.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "drop_client_pin",
+        sql: include_str!("migrations/005_drop_client_pin.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "collapse_feature_sets",
+        sql: include_str!("migrations/006_collapse_feature_sets.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "concrete_binding",
+        sql: include_str!("migrations/007_concrete_binding.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "canonical_default_space",
+        sql: include_str!("migrations/008_canonical_default_space.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "restore_client_grants",
+        sql: include_str!("migrations/009_restore_client_grants.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "inbound_client_reports_roots",
+        sql: include_str!("migrations/010_inbound_client_reports_roots.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "inbound_client_roots_capability_known",
+        sql: include_str!("migrations/011_inbound_client_roots_capability_known.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "workspace_binding_feature_sets",
+        sql: include_str!("migrations/012_workspace_binding_feature_sets.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "rename_default_to_starter",
+        sql: include_str!("migrations/013_rename_default_to_starter.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "rewrite_starter_seed_copy",
+        sql: include_str!("migrations/014_rewrite_starter_seed_copy.sql"),
+    },
+    Migration {
+        version: 15,
+        name: "rewrite_starter_seed_copy_v2",
+        sql: include_str!("migrations/015_rewrite_starter_seed_copy_v2.sql"),
+    },
+    Migration {
+        version: 16,
+        name: "space_builtin_servers",
+        sql: include_str!("migrations/016_space_builtin_servers.sql"),
+    },
+    Migration {
+        version: 17,
+        name: "purge_orphaned_feature_set_members",
+        sql: include_str!("migrations/017_purge_orphaned_feature_set_members.sql"),
+    },
+    Migration {
+        version: 18,
+        name: "starter_is_default_fallback_copy",
+        sql: include_str!("migrations/018_starter_is_default_fallback_copy.sql"),
+    },
+    Migration {
+        version: 19,
+        name: "space_base_dirs",
+        sql: include_str!("migrations/019_space_base_dirs.sql"),
+    },
+    Migration {
+        version: 20,
+        name: "inbound_client_api_keys",
+        sql: include_str!("migrations/020_inbound_client_api_keys.sql"),
+    },
+    Migration {
+        version: 21,
+        name: "binding_type",
+        sql: include_str!("migrations/021_binding_type.sql"),
+    },
+    Migration {
+        version: 22,
+        name: "inbound_client_locked_space",
+        sql: include_str!("migrations/022_inbound_client_locked_space.sql"),
+    },
+    Migration {
+        version: 23,
+        name: "installed_server_inherit_env",
+        sql: include_str!("migrations/023_installed_server_inherit_env.sql"),
+    },
+    Migration {
+        version: 24,
+        name: "installed_server_fs_allowed_roots",
+        sql: include_str!("migrations/024_installed_server_fs_allowed_roots.sql"),
+    },
+    Migration {
+        version: 25,
+        name: "installed_server_package_version",
+        sql: include_str!("migrations/025_installed_server_package_version.sql"),
+    },
+    Migration {
+        version: 26,
+        name: "installed_server_skip_argument_validation",
+        sql: include_str!("migrations/026_installed_server_skip_argument_validation.sql"),
+    },
+    Migration {
+        version: 27,
+        name: "installed_server_timeouts",
+        sql: include_str!("migrations/027_installed_server_timeouts.sql"),
+    },
+    Migration {
+        version: 28,
+        name: "server_groups",
+        sql: include_str!("migrations/028_server_groups.sql"),
+    },
+    Migration {
+        version: 29,
+        name: "environment_profiles",
+        sql: include_str!("migrations/029_environment_profiles.sql"),
+    },
+    Migration {
+        version: 30,
+        name: "registry_credentials",
+        sql: include_str!("migrations/030_registry_credentials.sql"),
+    },
+    Migration {
+        version: 31,
+        name: "installed_server_tool_hooks",
+        sql: include_str!("migrations/031_installed_server_tool_hooks.sql"),
+    },
+    Migration {
+        version: 32,
+        name: "installed_server_wasm_grants",
+        sql: include_str!("migrations/032_installed_server_wasm_grants.sql"),
+    },
+    Migration {
+        version: 33,
+        name: "installed_server_call_context",
+        sql: include_str!("migrations/033_installed_server_call_context.sql"),
+    },
+    Migration {
+        version: 34,
+        name: "event_journal",
+        sql: include_str!("migrations/034_event_journal.sql"),
+    },
+    Migration {
+        version: 35,
+        name: "inbound_client_suspended",
+        sql: include_str!("migrations/035_inbound_client_suspended.sql"),
+    },
+    Migration {
+        version: 36,
+        name: "installed_server_last_used",
+        sql: include_str!("migrations/036_installed_server_last_used.sql"),
+    },
+    Migration {
+        version: 37,
+        name: "users",
+        sql: include_str!("migrations/037_users.sql"),
+    },
+    Migration {
+        version: 38,
+        name: "server_feature_schema_pins",
+        sql: include_str!("migrations/038_server_feature_schema_pins.sql"),
+    },
+    Migration {
+        version: 39,
+        name: "installed_server_trash",
+        sql: include_str!("migrations/039_installed_server_trash.sql"),
+    },
+];
+
+/// Highest schema version this build knows how to migrate to.
+pub(crate) fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Look up a migration by version.
+pub(crate) fn find(version: i64) -> Option<&'static Migration> {
+    MIGRATIONS.iter().find(|m| m.version == version)
+}
+
+impl Migration {
+    /// Hex SHA-256 of the migration SQL.
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(m.version, i as i64 + 1, "migration {} out of order", m.name);
+        }
+        assert_eq!(latest_version(), MIGRATIONS.len() as i64);
+    }
+}
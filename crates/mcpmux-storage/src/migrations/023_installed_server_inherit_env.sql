@@ -0,0 +1,7 @@
+-- Migration 023: per-server opt-out of child environment sanitization
+--
+-- Stdio servers now start from a minimal environment (PATH, an OS baseline
+-- allowlist, and the server's declared env/inputs) instead of inheriting the
+-- full desktop environment. `inherit_env = 1` restores the old behaviour for
+-- a single installation. Existing rows default to 0 (sanitized).
+ALTER TABLE installed_servers ADD COLUMN inherit_env INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- Migration 024: gateway-enforced allowed roots for filesystem servers
+--
+-- JSON array of user-configured directories. When the installed server's
+-- definition is tagged `filesystem`, the gateway rejects tool calls whose
+-- path-like arguments resolve outside these roots — independent of the
+-- backend server's own configuration. NULL / '[]' = no gateway restriction.
+ALTER TABLE installed_servers ADD COLUMN fs_allowed_roots TEXT;
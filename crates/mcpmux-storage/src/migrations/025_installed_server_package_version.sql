@@ -0,0 +1,7 @@
+-- Migration 025: pinned package version for runner-launched stdio servers
+--
+-- Version of the npm/PyPI package an `npx`/`uvx`/... server runs, recorded
+-- the first time it connects and bumped by explicit package updates. The
+-- gateway substitutes it into the runner's package argument. NULL = run the
+-- version named by the server definition (usually the registry's latest).
+ALTER TABLE installed_servers ADD COLUMN package_version TEXT;
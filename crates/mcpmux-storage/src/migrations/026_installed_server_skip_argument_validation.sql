@@ -0,0 +1,6 @@
+-- Migration 026: opt-out of gateway tool argument validation
+--
+-- The gateway checks tools/call arguments against the tool's declared
+-- inputSchema before forwarding them. Some servers publish schemas that don't
+-- match what they accept; 1 = forward calls to this server unchecked.
+ALTER TABLE installed_servers ADD COLUMN skip_argument_validation INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Migration 027: per-server connect and request timeouts
+--
+-- Seconds to wait for the server to start and for each tool call, prompt or
+-- resource read. NULL = use the server definition's value or the default.
+ALTER TABLE installed_servers ADD COLUMN connect_timeout_secs INTEGER;
+ALTER TABLE installed_servers ADD COLUMN request_timeout_secs INTEGER;
@@ -0,0 +1,22 @@
+-- Migration 028: server groups
+--
+-- User-defined groups ("AWS", "Personal") of the servers installed in a
+-- Space, for bulk enable/disable/reconnect. `server_ids` is a JSON array of
+-- registry server IDs in display order; ids of servers uninstalled later are
+-- ignored on read. `feature_set_id` is the group's FeatureSet (every feature
+-- of its servers), created on demand. Group names are unique per Space
+-- (case-insensitively); ON DELETE CASCADE drops a Space's groups with it.
+
+CREATE TABLE IF NOT EXISTS server_groups (
+    id             TEXT PRIMARY KEY,
+    space_id       TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    name           TEXT NOT NULL,
+    icon           TEXT,
+    server_ids     TEXT NOT NULL DEFAULT '[]',
+    feature_set_id TEXT,
+    created_at     TEXT NOT NULL,
+    updated_at     TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_server_groups_space_name
+    ON server_groups(space_id, name COLLATE NOCASE);
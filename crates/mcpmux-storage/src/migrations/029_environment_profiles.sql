@@ -0,0 +1,25 @@
+-- Migration 029: environment profiles
+--
+-- Named dev/staging/prod profiles of a Space. The active profile's input
+-- values and URLs replace the servers' own when they connect. `overrides`
+-- holds the profile's shared input values and per-server overrides as JSON,
+-- encrypted with the field encryptor since input values may be secrets.
+-- At most one profile per Space is active (partial unique index); names are
+-- unique per Space, case-insensitively. ON DELETE CASCADE drops a Space's
+-- profiles with it.
+
+CREATE TABLE IF NOT EXISTS environment_profiles (
+    id         TEXT PRIMARY KEY,
+    space_id   TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    overrides  TEXT,
+    is_active  INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_environment_profiles_space_name
+    ON environment_profiles(space_id, name COLLATE NOCASE);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_environment_profiles_active
+    ON environment_profiles(space_id) WHERE is_active = 1;
@@ -0,0 +1,50 @@
+-- Migration 030: Credentials for private registries.
+--
+-- Registry credentials are stored in `credentials` like server credentials,
+-- but registries don't belong to a Space: their rows use the nil space ID
+-- ('00000000-0000-0000-0000-000000000000') and a `registry:<id>` server ID.
+-- The foreign key to `spaces` would reject those rows, so the table is
+-- recreated without it (SQLite can't drop a constraint in place). A trigger
+-- keeps the old `ON DELETE CASCADE` behaviour for real Spaces.
+
+CREATE TABLE credentials_new (
+    id TEXT PRIMARY KEY,
+    space_id TEXT NOT NULL,
+    server_id TEXT NOT NULL,
+    credential_type TEXT NOT NULL,
+
+    -- Only the secret value is encrypted (AES-256-GCM). Not a JSON blob.
+    credential_value TEXT NOT NULL,
+
+    -- Metadata stored as plaintext for queryability
+    expires_at TEXT,
+    token_type TEXT,
+    scope TEXT,
+
+    last_used_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+
+    UNIQUE(space_id, server_id, credential_type)
+);
+
+INSERT INTO credentials_new
+    (id, space_id, server_id, credential_type, credential_value, expires_at,
+     token_type, scope, last_used_at, created_at, updated_at)
+SELECT
+    id, space_id, server_id, credential_type, credential_value, expires_at,
+    token_type, scope, last_used_at, created_at, updated_at
+FROM credentials;
+
+DROP TABLE credentials;
+ALTER TABLE credentials_new RENAME TO credentials;
+
+CREATE INDEX IF NOT EXISTS idx_credentials_space_server ON credentials(space_id, server_id);
+CREATE INDEX IF NOT EXISTS idx_credentials_type ON credentials(space_id, server_id, credential_type);
+CREATE INDEX IF NOT EXISTS idx_credentials_expiry ON credentials(credential_type, expires_at);
+
+CREATE TRIGGER IF NOT EXISTS trg_credentials_space_deleted
+AFTER DELETE ON spaces
+BEGIN
+    DELETE FROM credentials WHERE space_id = OLD.id;
+END;
@@ -0,0 +1,5 @@
+-- Migration 031: per-server tool hooks
+--
+-- JSON steps the gateway runs on a server's tool call arguments (before the
+-- call) and results (after it). NULL = no hooks.
+ALTER TABLE installed_servers ADD COLUMN tool_hooks TEXT;
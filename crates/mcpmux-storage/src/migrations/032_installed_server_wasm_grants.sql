@@ -0,0 +1,7 @@
+-- Migration 032: host access granted to WASI servers
+--
+-- 1 = the user allowed the module filesystem access (to the server's allowed
+-- roots) or network access. A module only gets access its definition also
+-- requests.
+ALTER TABLE installed_servers ADD COLUMN wasm_grant_filesystem INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installed_servers ADD COLUMN wasm_grant_network INTEGER NOT NULL DEFAULT 0;
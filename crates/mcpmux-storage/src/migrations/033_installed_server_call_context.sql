@@ -0,0 +1,5 @@
+-- Migration 033: per-server call context
+--
+-- JSON `_meta` fields (value templates) the gateway adds to a server's tool
+-- calls so its logs can attribute traffic to clients. NULL = none.
+ALTER TABLE installed_servers ADD COLUMN call_context TEXT;
@@ -0,0 +1,15 @@
+-- Migration 034: domain event journal
+--
+-- Append-only log of DomainEvents so consumers (the UI bridge, audit
+-- logging) can catch up after a reconnect or restart. `payload` is the
+-- event's JSON; `event_type` and `space_id` are copied out of it for
+-- filtering. AUTOINCREMENT keeps sequence numbers from being reused after
+-- old rows are pruned.
+
+CREATE TABLE IF NOT EXISTS event_journal (
+    sequence    INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type  TEXT NOT NULL,
+    space_id    TEXT,
+    payload     TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
@@ -0,0 +1,6 @@
+-- Migration 035: suspend an inbound client
+--
+-- A suspended client keeps its registration, tokens, API keys and grants, but
+-- the gateway refuses every MCP request it makes with 403 until the user lifts
+-- the suspension. 0 = active (the default).
+ALTER TABLE inbound_clients ADD COLUMN suspended INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Migration 036: when each installed server was last used
+--
+-- RFC 3339 time of the server's most recent tool call, so auto-connect can
+-- start recently used servers first. NULL = never used.
+ALTER TABLE installed_servers ADD COLUMN last_used_at TEXT;
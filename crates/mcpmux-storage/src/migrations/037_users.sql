@@ -0,0 +1,22 @@
+-- Migration 037: users sharing one gateway
+--
+-- A headless gateway run for a small team gets one row per person. Each
+-- user has their own admin token (only its SHA-256 hash is stored, like the
+-- gateway-wide one) and a role: `admin` manages everything, `member` only
+-- the Spaces and clients whose `owner_user_id` is theirs. NULL owner = admins
+-- only. Deleting a user hands what they owned back to admins.
+
+CREATE TABLE IF NOT EXISTS users (
+    id         TEXT PRIMARY KEY,
+    name       TEXT NOT NULL,
+    role       TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('admin', 'member')),
+    token_hash TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_name ON users(name COLLATE NOCASE);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_token_hash ON users(token_hash);
+
+ALTER TABLE spaces ADD COLUMN owner_user_id TEXT REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE inbound_clients ADD COLUMN owner_user_id TEXT REFERENCES users(id) ON DELETE SET NULL;
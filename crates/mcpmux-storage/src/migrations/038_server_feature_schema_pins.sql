@@ -0,0 +1,6 @@
+-- Migration 038: tool schema pinning
+--
+-- A pinned tool keeps the schema clients were given when a server changes it;
+-- the new tool JSON waits in pending_raw_json until the user accepts it.
+ALTER TABLE server_features ADD COLUMN schema_pinned INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE server_features ADD COLUMN pending_raw_json TEXT;
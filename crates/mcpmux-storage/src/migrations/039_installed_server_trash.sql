@@ -0,0 +1,6 @@
+-- Migration 039: trash for uninstalled servers
+--
+-- RFC 3339 time the server was uninstalled. Trashed rows keep their inputs,
+-- credentials and features so the server can be restored; they are deleted
+-- once the retention window passes. NULL = installed.
+ALTER TABLE installed_servers ADD COLUMN deleted_at TEXT;
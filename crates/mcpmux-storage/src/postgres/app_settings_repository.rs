@@ -0,0 +1,123 @@
+//! PostgreSQL implementation of AppSettingsRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::AppSettingsRepository;
+
+use super::{now, PgDatabase};
+
+/// PostgreSQL-backed app settings repository.
+///
+/// Same key-value layout as [`crate::SqliteAppSettingsRepository`].
+pub struct PgAppSettingsRepository {
+    db: PgDatabase,
+}
+
+impl PgAppSettingsRepository {
+    /// Create a new app settings repository.
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AppSettingsRepository for PgAppSettingsRepository {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt("SELECT value FROM app_settings WHERE key = $1", &[&key])
+            .await?;
+        Ok(row.map(|row| row.try_get(0)).transpose()?)
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO app_settings (key, value, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                &[&key, &value, &now()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM app_settings WHERE key = $1", &[&key])
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<(String, String)>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query("SELECT key, value FROM app_settings ORDER BY key", &[])
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+            .collect::<Result<_, tokio_postgres::Error>>()?)
+    }
+
+    async fn list_by_prefix(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        let client = self.db.client().await?;
+        let pattern = format!(
+            "{}%",
+            prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let rows = client
+            .query(
+                "SELECT key, value FROM app_settings WHERE key LIKE $1 ESCAPE '\\' ORDER BY key",
+                &[&pattern],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+            .collect::<Result<_, tokio_postgres::Error>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_set_delete_and_prefix() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgAppSettingsRepository::new(db);
+
+        assert_eq!(
+            repo.get("gateway.auto_start").await.unwrap().as_deref(),
+            Some("true")
+        );
+        assert_eq!(repo.get("test.key").await.unwrap(), None);
+
+        repo.set("test.key", "test_value").await.unwrap();
+        repo.set("test.key", "updated_value").await.unwrap();
+        assert_eq!(
+            repo.get("test.key").await.unwrap().as_deref(),
+            Some("updated_value")
+        );
+
+        repo.set("gateway.port", "45818").await.unwrap();
+        repo.set("gateway_port", "1").await.unwrap();
+        let gateway = repo.list_by_prefix("gateway.").await.unwrap();
+        assert_eq!(
+            gateway.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(),
+            ["gateway.auto_start", "gateway.port"]
+        );
+
+        repo.delete("test.key").await.unwrap();
+        assert_eq!(repo.get("test.key").await.unwrap(), None);
+    }
+}
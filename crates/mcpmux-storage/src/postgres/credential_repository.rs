@@ -0,0 +1,260 @@
+//! PostgreSQL implementation of CredentialRepository.
+//!
+//! One row per (space, server, type); only the secret value is encrypted,
+//! like the SQLite repository.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{Credential, CredentialRepository, CredentialType};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::{parse_datetime, PgDatabase};
+use crate::crypto::FieldEncryptor;
+
+/// PostgreSQL-backed credential repository with field-level encryption.
+pub struct PgCredentialRepository {
+    db: PgDatabase,
+    encryptor: Arc<FieldEncryptor>,
+}
+
+impl PgCredentialRepository {
+    /// Create a new credential repository.
+    pub fn new(db: PgDatabase, encryptor: Arc<FieldEncryptor>) -> Self {
+        Self { db, encryptor }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "space_id, server_id, credential_type, credential_value, expires_at, token_type, scope, last_used_at, created_at, updated_at";
+
+    fn map_row(&self, row: &Row) -> Result<Credential> {
+        let space_id: String = row.try_get(0)?;
+        let credential_type: String = row.try_get(2)?;
+        let encrypted: String = row.try_get(3)?;
+        let value = self
+            .encryptor
+            .decrypt(&encrypted)
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt credential value: {}", e))?;
+        let credential_type = CredentialType::parse(&credential_type)
+            .ok_or_else(|| anyhow::anyhow!("Unknown credential type: {}", credential_type))?;
+        let optional_datetime = |idx: usize| -> Result<_, tokio_postgres::Error> {
+            Ok(row
+                .try_get::<_, Option<String>>(idx)?
+                .map(|s| parse_datetime(&s)))
+        };
+
+        Ok(Credential {
+            space_id: space_id.parse().unwrap_or_else(|_| Uuid::new_v4()),
+            server_id: row.try_get(1)?,
+            credential_type,
+            value,
+            expires_at: optional_datetime(4)?,
+            token_type: row.try_get(5)?,
+            scope: row.try_get(6)?,
+            last_used: optional_datetime(7)?,
+            created_at: parse_datetime(&row.try_get::<_, String>(8)?),
+            updated_at: parse_datetime(&row.try_get::<_, String>(9)?),
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialRepository for PgCredentialRepository {
+    async fn get(
+        &self,
+        space_id: &Uuid,
+        server_id: &str,
+        credential_type: &CredentialType,
+    ) -> Result<Option<Credential>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM credentials WHERE space_id = $1 AND server_id = $2 AND credential_type = $3",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string(), &server_id, &credential_type.as_str()],
+            )
+            .await?;
+        row.map(|row| self.map_row(&row)).transpose()
+    }
+
+    async fn get_all(&self, space_id: &Uuid, server_id: &str) -> Result<Vec<Credential>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM credentials WHERE space_id = $1 AND server_id = $2 ORDER BY credential_type",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string(), &server_id],
+            )
+            .await?;
+        rows.iter().map(|row| self.map_row(row)).collect()
+    }
+
+    async fn save(&self, credential: &Credential) -> Result<()> {
+        let encrypted_value = self
+            .encryptor
+            .encrypt(&credential.value)
+            .map_err(|e| anyhow::anyhow!("Failed to encrypt credential value: {}", e))?;
+
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO credentials (id, space_id, server_id, credential_type, credential_value, expires_at, token_type, scope, last_used_at, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 ON CONFLICT (space_id, server_id, credential_type) DO UPDATE SET
+                    credential_value = excluded.credential_value,
+                    expires_at = excluded.expires_at,
+                    token_type = excluded.token_type,
+                    scope = excluded.scope,
+                    updated_at = excluded.updated_at,
+                    last_used_at = excluded.last_used_at",
+                &[
+                    &Uuid::new_v4().to_string(),
+                    &credential.space_id.to_string(),
+                    &credential.server_id,
+                    &credential.credential_type.as_str(),
+                    &encrypted_value,
+                    &credential.expires_at.map(|dt| dt.to_rfc3339()),
+                    &credential.token_type,
+                    &credential.scope,
+                    &credential.last_used.map(|dt| dt.to_rfc3339()),
+                    &credential.created_at.to_rfc3339(),
+                    &credential.updated_at.to_rfc3339(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        space_id: &Uuid,
+        server_id: &str,
+        credential_type: &CredentialType,
+    ) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "DELETE FROM credentials WHERE space_id = $1 AND server_id = $2 AND credential_type = $3",
+                &[&space_id.to_string(), &server_id, &credential_type.as_str()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_all(&self, space_id: &Uuid, server_id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "DELETE FROM credentials WHERE space_id = $1 AND server_id = $2",
+                &[&space_id.to_string(), &server_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_tokens(&self, space_id: &Uuid, server_id: &str) -> Result<bool> {
+        let client = self.db.client().await?;
+        // Delete only OAuth tokens (access_token + refresh_token), preserve API keys etc.
+        let deleted = client
+            .execute(
+                "DELETE FROM credentials WHERE space_id = $1 AND server_id = $2 AND credential_type IN ('access_token', 'refresh_token')",
+                &[&space_id.to_string(), &server_id],
+            )
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    async fn list_for_space(&self, space_id: &Uuid) -> Result<Vec<Credential>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM credentials WHERE space_id = $1 ORDER BY server_id, credential_type",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string()],
+            )
+            .await?;
+        rows.iter().map(|row| self.map_row(row)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_database, PgSpaceRepository};
+    use super::*;
+    use chrono::Utc;
+    use mcpmux_core::{Space, SpaceRepository};
+
+    #[tokio::test]
+    async fn test_credentials_are_encrypted_and_follow_their_space() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let key = crate::crypto::generate_master_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let repo = PgCredentialRepository::new(db.clone(), encryptor);
+        let spaces = PgSpaceRepository::new(db.clone());
+
+        let space = Space::new("Work");
+        spaces.create(&space).await.unwrap();
+
+        repo.save(&Credential::api_key(space.id, "github", "ghp_old"))
+            .await
+            .unwrap();
+        repo.save(&Credential::api_key(space.id, "github", "ghp_new"))
+            .await
+            .unwrap();
+        let expires = Utc::now() + chrono::Duration::hours(1);
+        repo.save(&Credential::access_token(
+            space.id,
+            "github",
+            "token",
+            Some(expires),
+        ))
+        .await
+        .unwrap();
+
+        let found = repo
+            .get(&space.id, "github", &CredentialType::ApiKey)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.value, "ghp_new");
+        let raw: String = db
+            .client()
+            .await
+            .unwrap()
+            .query_one(
+                "SELECT credential_value FROM credentials WHERE credential_type = 'api_key'",
+                &[],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        assert!(!raw.contains("ghp_new"));
+
+        assert!(repo.clear_tokens(&space.id, "github").await.unwrap());
+        assert_eq!(repo.get_all(&space.id, "github").await.unwrap().len(), 1);
+
+        // Registry credentials use the nil Space ID, which isn't a Space
+        repo.save(&Credential::api_key(
+            Uuid::nil(),
+            "registry:internal",
+            "key",
+        ))
+        .await
+        .unwrap();
+
+        spaces.delete(&space.id).await.unwrap();
+        assert!(repo.list_for_space(&space.id).await.unwrap().is_empty());
+        assert_eq!(repo.list_for_space(&Uuid::nil()).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,228 @@
+//! Connection pool and migration runner for the PostgreSQL backend.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use rustls_platform_verifier::BuilderVerifierExt;
+use tokio_postgres::config::SslMode;
+use tokio_postgres::NoTls;
+use tokio_postgres_rustls::MakeRustlsConnect;
+use tracing::{debug, info};
+
+use super::migrations::{self, MIGRATIONS};
+
+/// Connections kept open per gateway process.
+const POOL_SIZE: usize = 16;
+
+/// Advisory lock key held while migrating, so gateways starting together
+/// don't both apply the same migration.
+const MIGRATION_LOCK_KEY: i64 = 0x6d63_706d_7578; // "mcpmux"
+
+/// A pooled connection to the PostgreSQL database.
+pub type PgClient = deadpool_postgres::Client;
+
+/// PostgreSQL database shared by several gateway processes.
+///
+/// Cheap to clone: clones share one connection pool.
+#[derive(Clone)]
+pub struct PgDatabase {
+    pool: Pool,
+}
+
+impl PgDatabase {
+    /// Connect to the database at `url` and apply pending migrations.
+    ///
+    /// `url` is a `postgres://` URL or a `key=value` connection string. TLS
+    /// is used unless it sets `sslmode=disable`, and server certificates are
+    /// always checked against the platform's trust store.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let config: tokio_postgres::Config =
+            url.parse().context("Invalid PostgreSQL connection URL")?;
+        Self::connect_with_config(config).await
+    }
+
+    pub(crate) async fn connect_with_config(config: tokio_postgres::Config) -> Result<Self> {
+        let manager_config = ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        };
+        let manager = if config.get_ssl_mode() == SslMode::Disable {
+            Manager::from_config(config, NoTls, manager_config)
+        } else {
+            Manager::from_config(config, tls_connector()?, manager_config)
+        };
+        let pool = Pool::builder(manager)
+            .max_size(POOL_SIZE)
+            .build()
+            .context("Failed to create PostgreSQL connection pool")?;
+
+        let db = Self { pool };
+        db.run_migrations().await?;
+        debug!("Connected to PostgreSQL database");
+        Ok(db)
+    }
+
+    /// Take a connection from the pool.
+    pub async fn client(&self) -> Result<PgClient> {
+        self.pool
+            .get()
+            .await
+            .context("Failed to get a PostgreSQL connection")
+    }
+
+    /// Current schema version.
+    pub async fn schema_version(&self) -> Result<i64> {
+        let client = self.client().await?;
+        let row = client
+            .query_one(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+                &[],
+            )
+            .await?;
+        Ok(row.get(0))
+    }
+
+    /// Latest schema version this build can migrate to.
+    pub fn latest_schema_version() -> i64 {
+        migrations::latest_version()
+    }
+
+    /// Apply pending migrations in one transaction.
+    ///
+    /// Refuses a database written by a newer build or whose applied
+    /// migrations differ from the ones this build ships.
+    async fn run_migrations(&self) -> Result<()> {
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+
+        tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY])
+            .await?;
+        tx.batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version    BIGINT PRIMARY KEY,
+                name       TEXT NOT NULL,
+                applied_at TEXT NOT NULL,
+                checksum   TEXT NOT NULL
+            )",
+        )
+        .await?;
+
+        let applied = tx
+            .query(
+                "SELECT version, checksum FROM schema_migrations ORDER BY version",
+                &[],
+            )
+            .await?;
+        let latest = migrations::latest_version();
+        let mut current_version = 0;
+        for row in &applied {
+            let version: i64 = row.get(0);
+            let checksum: String = row.get(1);
+            let Some(migration) = MIGRATIONS.iter().find(|m| m.version == version) else {
+                anyhow::bail!(
+                    "Database schema version {} is newer than this build supports ({}). \
+                     Update McpMux.",
+                    version,
+                    latest
+                );
+            };
+            if checksum != migration.checksum() {
+                anyhow::bail!(
+                    "Migration {} ({}) was applied from different SQL than this build ships \
+                     (checksum mismatch)",
+                    version,
+                    migration.name
+                );
+            }
+            current_version = version;
+        }
+
+        info!(
+            "Current PostgreSQL schema version: {}, latest available: {}",
+            current_version, latest
+        );
+
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
+            info!(
+                "Running migration {} ({})...",
+                migration.version, migration.name
+            );
+            tx.batch_execute(migration.sql).await.with_context(|| {
+                format!(
+                    "Failed to run migration {} ({})",
+                    migration.version, migration.name
+                )
+            })?;
+            tx.execute(
+                "INSERT INTO schema_migrations (version, name, applied_at, checksum)
+                 VALUES ($1, $2, $3, $4)",
+                &[
+                    &migration.version,
+                    &migration.name,
+                    &super::now(),
+                    &migration.checksum(),
+                ],
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// TLS with the platform's certificate verifier.
+///
+/// The crypto provider is passed explicitly so this doesn't depend on which
+/// provider other crates in the process install as the default.
+fn tls_connector() -> Result<MakeRustlsConnect> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_platform_verifier()?
+        .with_no_client_auth();
+    Ok(MakeRustlsConnect::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_migrations_apply_once() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            PgDatabase::latest_schema_version()
+        );
+
+        // A second run finds nothing to do
+        db.run_migrations().await.unwrap();
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            PgDatabase::latest_schema_version()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_is_refused() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        db.client()
+            .await
+            .unwrap()
+            .execute(
+                "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 1",
+                &[],
+            )
+            .await
+            .unwrap();
+
+        let err = db.run_migrations().await.unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+    }
+}
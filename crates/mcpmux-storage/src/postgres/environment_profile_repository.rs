@@ -0,0 +1,223 @@
+//! PostgreSQL implementation of EnvironmentProfileRepository.
+//!
+//! Overrides are encrypted exactly as in the SQLite repository.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{EnvironmentProfile, EnvironmentProfileRepository};
+use tokio_postgres::Row;
+
+use super::{is_unique_violation, parse_datetime, PgDatabase};
+use crate::crypto::FieldEncryptor;
+use crate::repositories::{decrypt_profile, encrypt_profile};
+
+/// PostgreSQL-backed implementation of [`EnvironmentProfileRepository`].
+pub struct PgEnvironmentProfileRepository {
+    db: PgDatabase,
+    encryptor: Arc<FieldEncryptor>,
+}
+
+impl PgEnvironmentProfileRepository {
+    pub fn new(db: PgDatabase, encryptor: Arc<FieldEncryptor>) -> Self {
+        Self { db, encryptor }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "id, space_id, name, overrides, is_active, created_at, updated_at";
+
+    fn map_row(&self, row: &Row) -> Result<EnvironmentProfile> {
+        let profile = EnvironmentProfile {
+            id: row.try_get(0)?,
+            space_id: row.try_get(1)?,
+            name: row.try_get(2)?,
+            inputs: HashMap::new(),
+            servers: HashMap::new(),
+            is_active: row.try_get(4)?,
+            created_at: parse_datetime(&row.try_get::<_, String>(5)?),
+            updated_at: parse_datetime(&row.try_get::<_, String>(6)?),
+        };
+        decrypt_profile(&self.encryptor, profile, row.try_get(3)?)
+    }
+
+    /// Turn the unique (space_id, name) collision into a readable message.
+    fn map_write_error(e: tokio_postgres::Error, name: &str) -> anyhow::Error {
+        if is_unique_violation(&e) {
+            anyhow::anyhow!("An environment profile named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+
+    async fn query(&self, filter: &str, param: &str) -> Result<Vec<EnvironmentProfile>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM environment_profiles WHERE {} ORDER BY lower(name) ASC",
+                    Self::COLUMNS,
+                    filter
+                ),
+                &[&param],
+            )
+            .await?;
+        rows.iter().map(|row| self.map_row(row)).collect()
+    }
+}
+
+#[async_trait]
+impl EnvironmentProfileRepository for PgEnvironmentProfileRepository {
+    async fn list_for_space(&self, space_id: &str) -> Result<Vec<EnvironmentProfile>> {
+        self.query("space_id = $1", space_id).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<EnvironmentProfile>> {
+        Ok(self.query("id = $1", id).await?.into_iter().next())
+    }
+
+    async fn get_active(&self, space_id: &str) -> Result<Option<EnvironmentProfile>> {
+        Ok(self
+            .query("space_id = $1 AND is_active", space_id)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn create(&self, profile: &EnvironmentProfile) -> Result<()> {
+        let overrides = encrypt_profile(&self.encryptor, profile)?;
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO environment_profiles
+                 (id, space_id, name, overrides, is_active, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, FALSE, $5, $6)",
+                &[
+                    &profile.id,
+                    &profile.space_id,
+                    &profile.name,
+                    &overrides,
+                    &profile.created_at.to_rfc3339(),
+                    &profile.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &profile.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, profile: &EnvironmentProfile) -> Result<()> {
+        let overrides = encrypt_profile(&self.encryptor, profile)?;
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "UPDATE environment_profiles
+                 SET name = $2, overrides = $3, updated_at = $4
+                 WHERE id = $1",
+                &[
+                    &profile.id,
+                    &profile.name,
+                    &overrides,
+                    &profile.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &profile.name))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM environment_profiles WHERE id = $1", &[&id])
+            .await?;
+        Ok(())
+    }
+
+    async fn set_active(&self, space_id: &str, profile_id: Option<&str>) -> Result<()> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        tx.execute(
+            "UPDATE environment_profiles SET is_active = FALSE WHERE space_id = $1",
+            &[&space_id],
+        )
+        .await?;
+        if let Some(id) = profile_id {
+            let updated = tx
+                .execute(
+                    "UPDATE environment_profiles SET is_active = TRUE WHERE id = $1 AND space_id = $2",
+                    &[&id, &space_id],
+                )
+                .await?;
+            if updated == 0 {
+                anyhow::bail!("Environment profile not found in this space");
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_overrides_are_encrypted_and_one_profile_is_active() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let key = crate::crypto::generate_master_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let repo = PgEnvironmentProfileRepository::new(db.clone(), encryptor);
+
+        let mut staging = EnvironmentProfile::new("staging", DEFAULT_SPACE_ID);
+        staging
+            .inputs
+            .insert("API_KEY".to_string(), "sk-staging".to_string());
+        repo.create(&staging).await.unwrap();
+        let prod = EnvironmentProfile::new("prod", DEFAULT_SPACE_ID);
+        repo.create(&prod).await.unwrap();
+
+        let raw: String = db
+            .client()
+            .await
+            .unwrap()
+            .query_one(
+                "SELECT overrides FROM environment_profiles WHERE id = $1",
+                &[&staging.id],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        assert!(!raw.contains("sk-staging"));
+
+        repo.set_active(DEFAULT_SPACE_ID, Some(&staging.id))
+            .await
+            .unwrap();
+        repo.set_active(DEFAULT_SPACE_ID, Some(&prod.id))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_active(DEFAULT_SPACE_ID).await.unwrap().unwrap().id,
+            prod.id
+        );
+
+        let found = repo.get(&staging.id).await.unwrap().unwrap();
+        assert!(!found.is_active);
+        assert_eq!(found.inputs["API_KEY"], "sk-staging");
+
+        assert!(repo
+            .set_active(DEFAULT_SPACE_ID, Some("missing"))
+            .await
+            .is_err());
+        repo.set_active(DEFAULT_SPACE_ID, None).await.unwrap();
+        assert!(repo.get_active(DEFAULT_SPACE_ID).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! PostgreSQL implementation of EventJournalRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use mcpmux_core::{DomainEvent, EventJournalRepository, JournaledEvent};
+use tracing::debug;
+
+use super::{parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of [`EventJournalRepository`].
+pub struct PgEventJournalRepository {
+    db: PgDatabase,
+}
+
+impl PgEventJournalRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl EventJournalRepository for PgEventJournalRepository {
+    async fn append(&self, event: &DomainEvent) -> Result<u64> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_one(
+                "INSERT INTO event_journal (event_type, space_id, payload, recorded_at)
+                 VALUES ($1, $2, $3, $4)
+                 RETURNING sequence",
+                &[
+                    &event.type_name(),
+                    &event.space_id().map(|id| id.to_string()),
+                    &serde_json::to_string(event)?,
+                    &Utc::now().to_rfc3339(),
+                ],
+            )
+            .await?;
+        Ok(row.try_get::<_, i64>(0)? as u64)
+    }
+
+    async fn since(&self, sequence: u64, limit: usize) -> Result<Vec<JournaledEvent>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                "SELECT sequence, payload, recorded_at FROM event_journal
+                 WHERE sequence > $1 ORDER BY sequence ASC LIMIT $2",
+                &[&(sequence as i64), &(limit as i64)],
+            )
+            .await?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let sequence: i64 = row.try_get(0)?;
+            let payload: String = row.try_get(1)?;
+            let recorded_at: String = row.try_get(2)?;
+            // Skip events of a kind this version can't read, like SQLite does
+            match serde_json::from_str(&payload) {
+                Ok(event) => events.push(JournaledEvent {
+                    sequence: sequence as u64,
+                    recorded_at: parse_datetime(&recorded_at),
+                    event,
+                }),
+                Err(e) => debug!(sequence, "[EventJournal] Skipping unreadable event: {}", e),
+            }
+        }
+        Ok(events)
+    }
+
+    async fn latest_sequence(&self) -> Result<u64> {
+        let client = self.db.client().await?;
+        // Read the identity's sequence rather than MAX(sequence), so the
+        // answer survives every row being pruned
+        let row = client
+            .query_one(
+                "SELECT COALESCE(pg_sequence_last_value(
+                     pg_get_serial_sequence('event_journal', 'sequence')::regclass), 0)",
+                &[],
+            )
+            .await?;
+        Ok(row.try_get::<_, i64>(0)? as u64)
+    }
+
+    async fn prune(&self, keep: u64) -> Result<u64> {
+        let client = self.db.client().await?;
+        let deleted = client
+            .execute(
+                "DELETE FROM event_journal
+                 WHERE sequence <= (SELECT MAX(sequence) FROM event_journal) - $1",
+                &[&(keep as i64)],
+            )
+            .await?;
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_append_read_and_prune() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgEventJournalRepository::new(db);
+        assert_eq!(repo.latest_sequence().await.unwrap(), 0);
+
+        let space_id = Uuid::new_v4();
+        repo.append(&DomainEvent::SpaceDeleted { space_id })
+            .await
+            .unwrap();
+        repo.append(&DomainEvent::SessionRootsChanged)
+            .await
+            .unwrap();
+        let last = repo.append(&DomainEvent::GatewayStopped).await.unwrap();
+        assert_eq!(last, 3);
+        assert_eq!(repo.latest_sequence().await.unwrap(), 3);
+
+        let events = repo.since(0, 10).await.unwrap();
+        assert_eq!(
+            events.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(events[0].event.space_id(), Some(space_id));
+        assert_eq!(repo.since(1, 1).await.unwrap()[0].sequence, 2);
+
+        assert_eq!(repo.prune(1).await.unwrap(), 2);
+        assert_eq!(repo.since(0, 10).await.unwrap().len(), 1);
+
+        // Sequence numbers are never reused, even once everything is pruned
+        repo.prune(0).await.unwrap();
+        assert_eq!(repo.latest_sequence().await.unwrap(), 3);
+        assert_eq!(repo.append(&DomainEvent::GatewayStopped).await.unwrap(), 4);
+    }
+}
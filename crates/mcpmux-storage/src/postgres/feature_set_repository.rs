@@ -0,0 +1,421 @@
+//! PostgreSQL implementation of FeatureSetRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{
+    FeatureSet, FeatureSetMember, FeatureSetRepository, FeatureSetType, MemberMode, MemberType,
+};
+use tokio_postgres::{GenericClient, Row};
+
+use super::{now, parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of FeatureSetRepository.
+pub struct PgFeatureSetRepository {
+    db: PgDatabase,
+}
+
+impl PgFeatureSetRepository {
+    /// Create a new PostgreSQL feature set repository.
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every `FeatureSet` read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, name, description, icon, space_id, feature_set_type, \
+         server_id, is_builtin, is_deleted, created_at, updated_at";
+
+    /// Parse a row into a FeatureSet (without members).
+    fn map_row(row: &Row) -> Result<FeatureSet, tokio_postgres::Error> {
+        // Members loaded separately
+        let mut feature_set = FeatureSet::new_custom(row.try_get::<_, String>(1)?, String::new());
+        feature_set.id = row.try_get(0)?;
+        feature_set.description = row.try_get(2)?;
+        feature_set.icon = row.try_get(3)?;
+        feature_set.space_id = row.try_get(4)?;
+        feature_set.feature_set_type =
+            FeatureSetType::parse(row.try_get(5)?).unwrap_or(FeatureSetType::Custom);
+        feature_set.server_id = row.try_get(6)?;
+        feature_set.is_builtin = row.try_get(7)?;
+        feature_set.is_deleted = row.try_get(8)?;
+        feature_set.created_at = parse_datetime(row.try_get(9)?);
+        feature_set.updated_at = parse_datetime(row.try_get(10)?);
+        Ok(feature_set)
+    }
+
+    /// Parse a row into a FeatureSetMember.
+    fn map_member(row: &Row) -> Result<FeatureSetMember, tokio_postgres::Error> {
+        let mut member = FeatureSetMember::new(
+            row.try_get::<_, String>(1)?,
+            MemberType::parse(row.try_get(2)?).unwrap_or(MemberType::Feature),
+            row.try_get::<_, String>(3)?,
+            MemberMode::parse(row.try_get(4)?).unwrap_or(MemberMode::Include),
+        );
+        member.id = row.try_get(0)?;
+        Ok(member)
+    }
+
+    /// Load the members of a feature set.
+    async fn load_members(
+        client: &impl GenericClient,
+        feature_set_id: &str,
+    ) -> Result<Vec<FeatureSetMember>> {
+        let rows = client
+            .query(
+                "SELECT id, feature_set_id, member_type, member_id, mode
+                 FROM feature_set_members
+                 WHERE feature_set_id = $1
+                 ORDER BY id",
+                &[&feature_set_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(Self::map_member)
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Insert `members`, all stamped with the same `created_at`.
+    async fn insert_members(
+        client: &impl GenericClient,
+        members: &[FeatureSetMember],
+    ) -> Result<()> {
+        let now = now();
+        for member in members {
+            client
+                .execute(
+                    "INSERT INTO feature_set_members (id, feature_set_id, member_type, member_id, mode, created_at)
+                     VALUES ($1, $2, $3, $4, $5, $6)",
+                    &[
+                        &member.id,
+                        &member.feature_set_id,
+                        &member.member_type.as_str(),
+                        &member.member_id,
+                        &member.mode.as_str(),
+                        &now,
+                    ],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FeatureSetRepository for PgFeatureSetRepository {
+    async fn list(&self) -> Result<Vec<FeatureSet>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM feature_sets
+             WHERE NOT is_deleted
+             ORDER BY is_builtin DESC, name ASC",
+            Self::COLUMNS
+        );
+        let rows = client.query(&sql, &[]).await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn list_by_space(&self, space_id: &str) -> Result<Vec<FeatureSet>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM feature_sets
+             WHERE space_id = $1 AND NOT is_deleted
+             ORDER BY is_builtin DESC, feature_set_type, name ASC",
+            Self::COLUMNS
+        );
+        let rows = client.query(&sql, &[&space_id]).await?;
+        let mut feature_sets = rows
+            .iter()
+            .map(Self::map_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for fs in &mut feature_sets {
+            fs.members = Self::load_members(&**client, &fs.id).await?;
+        }
+
+        Ok(feature_sets)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<FeatureSet>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM feature_sets WHERE id = $1 AND NOT is_deleted",
+            Self::COLUMNS
+        );
+        let row = client.query_opt(&sql, &[&id]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn get_with_members(&self, id: &str) -> Result<Option<FeatureSet>> {
+        let feature_set = self.get(id).await?;
+        if let Some(mut fs) = feature_set {
+            let client = self.db.client().await?;
+            fs.members = Self::load_members(&**client, id).await?;
+            Ok(Some(fs))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn create(&self, feature_set: &FeatureSet) -> Result<()> {
+        let mut client = self.db.client().await?;
+
+        // The set and its members land together or not at all
+        let tx = client.transaction().await?;
+        tx.execute(
+            "INSERT INTO feature_sets
+                (id, name, description, icon, space_id, feature_set_type,
+                 server_id, is_builtin, is_deleted, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+            &[
+                &feature_set.id,
+                &feature_set.name,
+                &feature_set.description,
+                &feature_set.icon,
+                &feature_set.space_id,
+                &feature_set.feature_set_type.as_str(),
+                &feature_set.server_id,
+                &feature_set.is_builtin,
+                &feature_set.is_deleted,
+                &feature_set.created_at.to_rfc3339(),
+                &feature_set.updated_at.to_rfc3339(),
+            ],
+        )
+        .await?;
+        Self::insert_members(&*tx, &feature_set.members).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update(&self, feature_set: &FeatureSet) -> Result<()> {
+        let mut client = self.db.client().await?;
+
+        // Builtin sets keep their name, description and icon; see the SQLite
+        // repository for why the stored flag governs.
+        let tx = client.transaction().await?;
+        let rows_affected = tx
+            .execute(
+                "UPDATE feature_sets
+                 SET name = CASE WHEN is_builtin THEN name ELSE $2 END,
+                     description = CASE WHEN is_builtin THEN description ELSE $3 END,
+                     icon = CASE WHEN is_builtin THEN icon ELSE $4 END,
+                     updated_at = $5
+                 WHERE id = $1 AND NOT is_deleted",
+                &[
+                    &feature_set.id,
+                    &feature_set.name,
+                    &feature_set.description,
+                    &feature_set.icon,
+                    &feature_set.updated_at.to_rfc3339(),
+                ],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("FeatureSet not found: {}", feature_set.id);
+        }
+
+        // Update members: delete old, insert new
+        tx.execute(
+            "DELETE FROM feature_set_members WHERE feature_set_id = $1",
+            &[&feature_set.id],
+        )
+        .await?;
+        Self::insert_members(&*tx, &feature_set.members).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let mut client = self.db.client().await?;
+
+        // Don't allow deleting builtin feature sets
+        let is_builtin = client
+            .query_opt("SELECT is_builtin FROM feature_sets WHERE id = $1", &[&id])
+            .await?
+            .map(|row| row.try_get::<_, bool>(0))
+            .transpose()?
+            .unwrap_or(false);
+
+        if is_builtin {
+            anyhow::bail!("Cannot delete builtin FeatureSet: {}", id);
+        }
+
+        // Soft delete, and drop every reference to it in the same transaction
+        // since the FK cascades never fire for a soft delete.
+        let tx = client.transaction().await?;
+        tx.execute(
+            "UPDATE feature_sets SET is_deleted = TRUE, updated_at = $2 WHERE id = $1",
+            &[&id, &now()],
+        )
+        .await?;
+        tx.execute(
+            "DELETE FROM workspace_binding_feature_sets WHERE feature_set_id = $1",
+            &[&id],
+        )
+        .await?;
+        tx.execute(
+            "DELETE FROM client_grants WHERE feature_set_id = $1",
+            &[&id],
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_starter_for_space(&self, space_id: &str) -> Result<Option<FeatureSet>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM feature_sets
+             WHERE space_id = $1
+               AND feature_set_type IN ('starter', 'default')
+               AND NOT is_deleted",
+            Self::COLUMNS
+        );
+        let row = client.query_opt(&sql, &[&space_id]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn ensure_builtin_for_space(&self, space_id: &str) -> Result<()> {
+        if self.get_starter_for_space(space_id).await?.is_none() {
+            let starter = FeatureSet::new_starter(space_id);
+            self.create(&starter).await?;
+        }
+        Ok(())
+    }
+
+    async fn add_feature_member(
+        &self,
+        feature_set_id: &str,
+        feature_id: &str,
+        mode: MemberMode,
+    ) -> Result<()> {
+        let client = self.db.client().await?;
+        let member = FeatureSetMember::new(feature_set_id, MemberType::Feature, feature_id, mode);
+        Self::insert_members(&**client, &[member]).await
+    }
+
+    async fn remove_feature_member(&self, feature_set_id: &str, feature_id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "DELETE FROM feature_set_members
+                 WHERE feature_set_id = $1 AND member_id = $2 AND member_type = 'feature'",
+                &[&feature_set_id, &feature_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_feature_members(&self, feature_set_id: &str) -> Result<Vec<FeatureSetMember>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                "SELECT id, feature_set_id, member_type, member_id, mode
+                 FROM feature_set_members
+                 WHERE feature_set_id = $1 AND member_type = 'feature'
+                 ORDER BY id",
+                &[&feature_set_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(Self::map_member)
+            .collect::<Result<_, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_crud_operations() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgFeatureSetRepository::new(db);
+
+        let mut fs = FeatureSet::new_custom("My Custom Set", DEFAULT_SPACE_ID)
+            .with_description("A custom feature set");
+        fs.members
+            .push(FeatureSetMember::include_feature(&fs.id, "feature-1"));
+        repo.create(&fs).await.unwrap();
+
+        let found = repo.get_with_members(&fs.id).await.unwrap().unwrap();
+        assert_eq!(found.name, "My Custom Set");
+        assert_eq!(found.members.len(), 1);
+
+        // Migration seeds the Starter set + our custom one
+        assert_eq!(repo.list_by_space(DEFAULT_SPACE_ID).await.unwrap().len(), 2);
+
+        repo.delete(&fs.id).await.unwrap();
+        assert!(repo.get(&fs.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_builtin_identity_is_fixed() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgFeatureSetRepository::new(db);
+
+        let mut starter = repo
+            .get_starter_for_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .expect("Starter FS should exist for the default space");
+        assert!(repo.delete(&starter.id).await.is_err());
+
+        starter.name = "Renamed".to_string();
+        repo.update(&starter).await.unwrap();
+        assert_eq!(
+            repo.get(&starter.id).await.unwrap().unwrap().name,
+            "Starter"
+        );
+
+        repo.ensure_builtin_for_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap();
+        let starters = repo
+            .list_by_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .iter()
+            .filter(|f| matches!(f.feature_set_type, FeatureSetType::Starter))
+            .count();
+        assert_eq!(starters, 1);
+    }
+
+    #[tokio::test]
+    async fn test_feature_members() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgFeatureSetRepository::new(db);
+        let fs = FeatureSet::new_custom("Members", DEFAULT_SPACE_ID);
+        repo.create(&fs).await.unwrap();
+
+        repo.add_feature_member(&fs.id, "feature-1", MemberMode::Include)
+            .await
+            .unwrap();
+        repo.add_feature_member(&fs.id, "feature-2", MemberMode::Exclude)
+            .await
+            .unwrap();
+        assert_eq!(repo.get_feature_members(&fs.id).await.unwrap().len(), 2);
+
+        repo.remove_feature_member(&fs.id, "feature-1")
+            .await
+            .unwrap();
+        let members = repo.get_feature_members(&fs.id).await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].member_id, "feature-2");
+        assert_eq!(members[0].mode, MemberMode::Exclude);
+    }
+}
@@ -0,0 +1,856 @@
+//! PostgreSQL storage for inbound OAuth clients, codes, tokens, API keys and
+//! grants.
+//!
+//! Mirrors [`SqliteInboundClientRepository`](crate::SqliteInboundClientRepository)
+//! behind the same [`InboundClientRepository`] trait.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio_postgres::Row;
+use tracing::{debug, info};
+
+use super::{now, PgDatabase};
+use crate::repositories::{
+    hash_api_key, ApiKeyAuth, AuthorizationCode, InboundApiKey, InboundClient,
+    InboundClientRepository, RegistrationType, TokenRecord, TokenType,
+};
+
+/// Timestamps compared as strings against stored `expires_at` values.
+fn now_seconds() -> String {
+    chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// PostgreSQL-backed implementation of InboundClientRepository
+#[derive(Clone)]
+pub struct PgInboundClientRepository {
+    db: PgDatabase,
+}
+
+impl PgInboundClientRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Same columns, in the same order, as the SQLite repository.
+    const CLIENT_COLUMNS: &'static str = "client_id, registration_type, client_name, client_alias,
+         logo_uri, client_uri, software_id, software_version,
+         redirect_uris, grant_types, response_types, token_endpoint_auth_method, scope,
+         metadata_url, metadata_cached_at, metadata_cache_ttl,
+         last_seen, created_at, updated_at, approved, reports_roots, roots_capability_known,
+         suspended";
+
+    fn map_row_to_client(row: &Row) -> Result<InboundClient, tokio_postgres::Error> {
+        let json_list = |idx: usize| -> Result<Vec<String>, tokio_postgres::Error> {
+            Ok(row
+                .try_get::<_, Option<&str>>(idx)?
+                .and_then(|j| serde_json::from_str(j).ok())
+                .unwrap_or_default())
+        };
+        Ok(InboundClient {
+            client_id: row.try_get(0)?,
+            registration_type: RegistrationType::parse(row.try_get(1)?)
+                .unwrap_or(RegistrationType::Dcr),
+            client_name: row.try_get(2)?,
+            client_alias: row.try_get(3)?,
+            logo_uri: row.try_get(4)?,
+            client_uri: row.try_get(5)?,
+            software_id: row.try_get(6)?,
+            software_version: row.try_get(7)?,
+            redirect_uris: json_list(8)?,
+            grant_types: json_list(9)?,
+            response_types: json_list(10)?,
+            token_endpoint_auth_method: row.try_get(11)?,
+            scope: row.try_get(12)?,
+            metadata_url: row.try_get(13)?,
+            metadata_cached_at: row.try_get(14)?,
+            metadata_cache_ttl: row.try_get(15)?,
+            last_seen: row.try_get(16)?,
+            created_at: row.try_get(17)?,
+            updated_at: row.try_get(18)?,
+            approved: row.try_get(19)?,
+            reports_roots: row.try_get(20)?,
+            roots_capability_known: row.try_get(21)?,
+            suspended: row.try_get(22)?,
+        })
+    }
+
+    fn map_row_to_token(row: &Row) -> Result<TokenRecord, tokio_postgres::Error> {
+        Ok(TokenRecord {
+            id: row.try_get(0)?,
+            client_id: row.try_get(1)?,
+            token_type: TokenType::parse(row.try_get(2)?).unwrap_or(TokenType::Access),
+            token_hash: row.try_get(3)?,
+            scope: row.try_get(4)?,
+            expires_at: row.try_get(5)?,
+            revoked: row.try_get(6)?,
+            created_at: row.try_get(7)?,
+            parent_token_id: row.try_get(8)?,
+        })
+    }
+
+    async fn query_clients(
+        &self,
+        filter: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> Result<Vec<InboundClient>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM inbound_clients {}",
+            Self::CLIENT_COLUMNS,
+            filter
+        );
+        let rows = client.query(&sql, params).await?;
+        Ok(rows
+            .iter()
+            .map(Self::map_row_to_client)
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// One optional column of a client; `None` for an unknown client.
+    async fn client_column<T>(&self, column: &str, client_id: &str) -> Result<Option<T>>
+    where
+        T: for<'a> tokio_postgres::types::FromSql<'a>,
+    {
+        let client = self.db.client().await?;
+        let sql = format!("SELECT {column} FROM inbound_clients WHERE client_id = $1");
+        let row = client.query_opt(&sql, &[&client_id]).await?;
+        Ok(row.map(|r| r.try_get(0)).transpose()?)
+    }
+}
+
+#[async_trait]
+impl InboundClientRepository for PgInboundClientRepository {
+    // =========================================================================
+    // Clients
+    // =========================================================================
+
+    async fn save_client(&self, client: &InboundClient) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO inbound_clients (
+                client_id, registration_type, client_name, client_alias,
+                logo_uri, client_uri, software_id, software_version,
+                redirect_uris, grant_types, response_types, token_endpoint_auth_method, scope,
+                metadata_url, metadata_cached_at, metadata_cache_ttl,
+                last_seen, created_at, updated_at, approved
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+             ON CONFLICT (client_id) DO UPDATE SET
+                registration_type = $2, client_name = $3, client_alias = $4,
+                logo_uri = $5, client_uri = $6, software_id = $7, software_version = $8,
+                redirect_uris = $9, grant_types = $10, response_types = $11,
+                token_endpoint_auth_method = $12, scope = $13,
+                metadata_url = $14, metadata_cached_at = $15, metadata_cache_ttl = $16,
+                last_seen = $17, updated_at = $19, approved = $20",
+            &[
+                &client.client_id,
+                &client.registration_type.as_str(),
+                &client.client_name,
+                &client.client_alias,
+                &client.logo_uri,
+                &client.client_uri,
+                &client.software_id,
+                &client.software_version,
+                &serde_json::to_string(&client.redirect_uris)?,
+                &serde_json::to_string(&client.grant_types)?,
+                &serde_json::to_string(&client.response_types)?,
+                &client.token_endpoint_auth_method,
+                &client.scope,
+                &client.metadata_url,
+                &client.metadata_cached_at,
+                &client.metadata_cache_ttl,
+                &client.last_seen,
+                &client.created_at,
+                &client.updated_at,
+                &client.approved,
+            ],
+        )
+        .await?;
+        debug!(
+            "[OAuth] Saved client: {} ({})",
+            client.client_name, client.client_id
+        );
+        Ok(())
+    }
+
+    async fn get_client(&self, client_id: &str) -> Result<Option<InboundClient>> {
+        Ok(self
+            .query_clients("WHERE client_id = $1", &[&client_id])
+            .await?
+            .pop())
+    }
+
+    async fn find_client_by_name(&self, name: &str) -> Result<Option<InboundClient>> {
+        Ok(self
+            .query_clients("WHERE client_name = $1 LIMIT 1", &[&name])
+            .await?
+            .pop())
+    }
+
+    async fn list_clients(&self) -> Result<Vec<InboundClient>> {
+        self.query_clients("ORDER BY created_at DESC", &[]).await
+    }
+
+    async fn list_clients_for_owner(&self, user_id: &str) -> Result<Vec<InboundClient>> {
+        self.query_clients(
+            "WHERE owner_user_id = $1 ORDER BY created_at DESC",
+            &[&user_id],
+        )
+        .await
+    }
+
+    async fn update_client_last_seen(&self, client_id: &str) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE inbound_clients SET last_seen = $1, updated_at = $1 WHERE client_id = $2",
+            &[&now_seconds(), &client_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn approve_client(&self, client_id: &str) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE inbound_clients SET approved = TRUE, updated_at = $1 WHERE client_id = $2",
+            &[&now_seconds(), &client_id],
+        )
+        .await?;
+        debug!("[OAuth] Approved client: {}", client_id);
+        Ok(())
+    }
+
+    async fn is_client_approved(&self, client_id: &str) -> Result<bool> {
+        Ok(self
+            .client_column::<bool>("approved", client_id)
+            .await?
+            .unwrap_or(false))
+    }
+
+    async fn set_client_suspended(&self, client_id: &str, suspended: bool) -> Result<bool> {
+        let conn = self.db.client().await?;
+        let updated = conn
+            .execute(
+                "UPDATE inbound_clients SET suspended = $1, updated_at = $2 WHERE client_id = $3",
+                &[&suspended, &now_seconds(), &client_id],
+            )
+            .await?;
+        info!(
+            "[OAuth] {} client: {}",
+            if suspended { "Suspended" } else { "Reinstated" },
+            client_id
+        );
+        Ok(updated > 0)
+    }
+
+    async fn is_client_suspended(&self, client_id: &str) -> Result<bool> {
+        Ok(self
+            .client_column::<bool>("suspended", client_id)
+            .await?
+            .unwrap_or(false))
+    }
+
+    /// Store a client's redirect URIs as given.
+    async fn set_redirect_uris(&self, client_id: &str, uris: &[String]) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE inbound_clients SET redirect_uris = $1, updated_at = $2 WHERE client_id = $3",
+            &[&serde_json::to_string(uris)?, &now_seconds(), &client_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_client_alias(
+        &self,
+        client_id: &str,
+        client_alias: Option<String>,
+    ) -> Result<Option<InboundClient>> {
+        {
+            let conn = self.db.client().await?;
+            conn.execute(
+                "UPDATE inbound_clients SET client_alias = $1, updated_at = $2 WHERE client_id = $3",
+                &[&client_alias, &now_seconds(), &client_id],
+            )
+            .await?;
+        }
+        debug!("[OAuth] Updated alias for client: {}", client_id);
+        self.get_client(client_id).await
+    }
+
+    async fn delete_client(&self, client_id: &str) -> Result<bool> {
+        let conn = self.db.client().await?;
+        // Tokens, codes, keys and grants go via ON DELETE CASCADE
+        let rows = conn
+            .execute(
+                "DELETE FROM inbound_clients WHERE client_id = $1",
+                &[&client_id],
+            )
+            .await?;
+        if rows > 0 {
+            info!("[OAuth] Deleted client: {}", client_id);
+        }
+        Ok(rows > 0)
+    }
+
+    async fn set_locked_space(&self, client_id: &str, space_id: Option<&str>) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE inbound_clients SET locked_space_id = $1, updated_at = $2 WHERE client_id = $3",
+            &[&space_id, &now(), &client_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_locked_space(&self, client_id: &str) -> Result<Option<String>> {
+        Ok(self
+            .client_column::<Option<String>>("locked_space_id", client_id)
+            .await?
+            .flatten())
+    }
+
+    async fn set_client_owner(&self, client_id: &str, user_id: Option<&str>) -> Result<bool> {
+        let conn = self.db.client().await?;
+        let rows = conn
+            .execute(
+                "UPDATE inbound_clients SET owner_user_id = $1, updated_at = $2 WHERE client_id = $3",
+                &[&user_id, &now(), &client_id],
+            )
+            .await?;
+        Ok(rows > 0)
+    }
+
+    async fn get_client_owner(&self, client_id: &str) -> Result<Option<String>> {
+        Ok(self
+            .client_column::<Option<String>>("owner_user_id", client_id)
+            .await?
+            .flatten())
+    }
+
+    async fn mark_roots_capability(&self, client_id: &str, declares: bool) -> Result<()> {
+        let conn = self.db.client().await?;
+        // `reports_roots OR $2` keeps it sticky-positive
+        conn.execute(
+            "UPDATE inbound_clients
+                SET roots_capability_known = TRUE,
+                    reports_roots = reports_roots OR $2
+              WHERE client_id = $1",
+            &[&client_id, &declares],
+        )
+        .await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Authorization codes
+    // =========================================================================
+
+    async fn save_authorization_code(&self, code: &AuthorizationCode) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO oauth_authorization_codes
+                (code, client_id, redirect_uri, scope, code_challenge, code_challenge_method, expires_at, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &code.code,
+                &code.client_id,
+                &code.redirect_uri,
+                &code.scope,
+                &code.code_challenge,
+                &code.code_challenge_method,
+                &code.expires_at,
+                &code.created_at,
+            ],
+        )
+        .await?;
+        debug!(
+            "[OAuth] Saved authorization code for client: {}",
+            code.client_id
+        );
+        Ok(())
+    }
+
+    /// Delete and return a code in one statement, so two gateways can't both
+    /// redeem it.
+    async fn consume_authorization_code(&self, code: &str) -> Result<Option<AuthorizationCode>> {
+        let conn = self.db.client().await?;
+        let row = conn
+            .query_opt(
+                "DELETE FROM oauth_authorization_codes WHERE code = $1
+                 RETURNING code, client_id, redirect_uri, scope, code_challenge,
+                           code_challenge_method, expires_at, created_at",
+                &[&code],
+            )
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let auth_code = AuthorizationCode {
+            code: row.try_get(0)?,
+            client_id: row.try_get(1)?,
+            redirect_uri: row.try_get(2)?,
+            scope: row.try_get(3)?,
+            code_challenge: row.try_get(4)?,
+            code_challenge_method: row.try_get(5)?,
+            expires_at: row.try_get(6)?,
+            created_at: row.try_get(7)?,
+        };
+        debug!(
+            "[OAuth] Consumed authorization code for client: {}",
+            auth_code.client_id
+        );
+        Ok(Some(auth_code))
+    }
+
+    async fn cleanup_expired_codes(&self) -> Result<usize> {
+        let conn = self.db.client().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM oauth_authorization_codes WHERE expires_at < $1",
+                &[&now_seconds()],
+            )
+            .await? as usize;
+        if deleted > 0 {
+            info!("[OAuth] Cleaned up {} expired authorization codes", deleted);
+        }
+        Ok(deleted)
+    }
+
+    // =========================================================================
+    // API keys
+    // =========================================================================
+
+    async fn create_api_key(
+        &self,
+        key_id: &str,
+        client_id: &str,
+        plaintext: &str,
+        key_prefix: &str,
+        label: Option<&str>,
+        expires_at: Option<&str>,
+    ) -> Result<()> {
+        let key_hash = hash_api_key(plaintext);
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO inbound_client_api_keys
+                (key_id, client_id, key_hash, key_prefix, label, revoked, expires_at, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, FALSE, $6, $7, $7)",
+            &[
+                &key_id,
+                &client_id,
+                &key_hash,
+                &key_prefix,
+                &label,
+                &expires_at,
+                &now(),
+            ],
+        )
+        .await?;
+        info!(
+            "[ApiKey] Created key {} for client {}",
+            key_prefix, client_id
+        );
+        Ok(())
+    }
+
+    async fn validate_api_key(&self, presented: &str) -> Result<Option<ApiKeyAuth>> {
+        let key_hash = hash_api_key(presented);
+        let now = now();
+        let conn = self.db.client().await?;
+        let row = conn
+            .query_opt(
+                "SELECT key_id, client_id, expires_at
+                 FROM inbound_client_api_keys
+                 WHERE key_hash = $1 AND NOT revoked",
+                &[&key_hash],
+            )
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let key_id: String = row.try_get(0)?;
+        let client_id: String = row.try_get(1)?;
+        let expires_at: Option<String> = row.try_get(2)?;
+
+        // ISO-8601 strings compare lexicographically; reject expired keys.
+        if let Some(exp) = expires_at.as_deref() {
+            if exp <= now.as_str() {
+                return Ok(None);
+            }
+        }
+
+        conn.execute(
+            "UPDATE inbound_client_api_keys SET last_used_at = $1 WHERE key_id = $2",
+            &[&now, &key_id],
+        )
+        .await?;
+        Ok(Some(ApiKeyAuth { key_id, client_id }))
+    }
+
+    async fn list_api_keys(&self, client_id: &str) -> Result<Vec<InboundApiKey>> {
+        let conn = self.db.client().await?;
+        let rows = conn
+            .query(
+                "SELECT key_id, client_id, key_prefix, label, revoked, last_used_at, expires_at, created_at, updated_at
+                 FROM inbound_client_api_keys WHERE client_id = $1 ORDER BY created_at DESC",
+                &[&client_id],
+            )
+            .await?;
+        rows.iter()
+            .map(|r| {
+                Ok(InboundApiKey {
+                    key_id: r.try_get(0)?,
+                    client_id: r.try_get(1)?,
+                    key_prefix: r.try_get(2)?,
+                    label: r.try_get(3)?,
+                    revoked: r.try_get(4)?,
+                    last_used_at: r.try_get(5)?,
+                    expires_at: r.try_get(6)?,
+                    created_at: r.try_get(7)?,
+                    updated_at: r.try_get(8)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn revoke_api_key(&self, key_id: &str) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE inbound_client_api_keys SET revoked = TRUE, updated_at = $1 WHERE key_id = $2",
+            &[&now(), &key_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Tokens
+    // =========================================================================
+
+    async fn save_token(&self, record: &TokenRecord) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO oauth_tokens (id, client_id, token_type, token_hash, scope, expires_at, revoked, created_at, parent_token_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            &[
+                &record.id,
+                &record.client_id,
+                &record.token_type.as_str(),
+                &record.token_hash,
+                &record.scope,
+                &record.expires_at,
+                &record.revoked,
+                &record.created_at,
+                &record.parent_token_id,
+            ],
+        )
+        .await?;
+        debug!(
+            "[OAuth] Saved {} token for client: {}",
+            record.token_type.as_str(),
+            record.client_id
+        );
+        Ok(())
+    }
+
+    async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<TokenRecord>> {
+        let conn = self.db.client().await?;
+        let row = conn
+            .query_opt(
+                "SELECT id, client_id, token_type, token_hash, scope, expires_at, revoked, created_at, parent_token_id
+                 FROM oauth_tokens WHERE token_hash = $1 LIMIT 1",
+                &[&token_hash],
+            )
+            .await?;
+        Ok(row.as_ref().map(Self::map_row_to_token).transpose()?)
+    }
+
+    /// Revoke a token and the tokens issued from it.
+    async fn revoke_token(&self, token_id: &str) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "UPDATE oauth_tokens SET revoked = TRUE WHERE id = $1 OR parent_token_id = $1",
+            &[&token_id],
+        )
+        .await?;
+        info!("[OAuth] Revoked token: {}", token_id);
+        Ok(())
+    }
+
+    async fn revoke_client_tokens(&self, client_id: &str) -> Result<usize> {
+        let conn = self.db.client().await?;
+        let count = conn
+            .execute(
+                "UPDATE oauth_tokens SET revoked = TRUE WHERE client_id = $1",
+                &[&client_id],
+            )
+            .await? as usize;
+        info!("[OAuth] Revoked {} tokens for client: {}", count, client_id);
+        Ok(count)
+    }
+
+    async fn cleanup_expired_tokens(&self) -> Result<usize> {
+        let conn = self.db.client().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM oauth_tokens WHERE expires_at IS NOT NULL AND expires_at < $1",
+                &[&now_seconds()],
+            )
+            .await? as usize;
+        if deleted > 0 {
+            info!("[OAuth] Cleaned up {} expired tokens", deleted);
+        }
+        Ok(deleted)
+    }
+
+    // =========================================================================
+    // Client grants
+    // =========================================================================
+
+    async fn grant_feature_set(
+        &self,
+        client_id: &str,
+        space_id: &str,
+        feature_set_id: &str,
+    ) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO client_grants (client_id, space_id, feature_set_id)
+             VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING",
+            &[&client_id, &space_id, &feature_set_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn revoke_feature_set(
+        &self,
+        client_id: &str,
+        space_id: &str,
+        feature_set_id: &str,
+    ) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "DELETE FROM client_grants
+             WHERE client_id = $1 AND space_id = $2 AND feature_set_id = $3",
+            &[&client_id, &space_id, &feature_set_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_grants_for_space(&self, client_id: &str, space_id: &str) -> Result<Vec<String>> {
+        let conn = self.db.client().await?;
+        let rows = conn
+            .query(
+                "SELECT feature_set_id FROM client_grants
+                 WHERE client_id = $1 AND space_id = $2",
+                &[&client_id, &space_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|r| r.try_get(0))
+            .collect::<Result<_, _>>()?)
+    }
+
+    async fn get_all_grants(&self, client_id: &str) -> Result<HashMap<String, Vec<String>>> {
+        let conn = self.db.client().await?;
+        let rows = conn
+            .query(
+                "SELECT space_id, feature_set_id FROM client_grants
+                 WHERE client_id = $1
+                 ORDER BY space_id",
+                &[&client_id],
+            )
+            .await?;
+        let mut grants: HashMap<String, Vec<String>> = HashMap::new();
+        for row in &rows {
+            grants
+                .entry(row.try_get(0)?)
+                .or_default()
+                .push(row.try_get(1)?);
+        }
+        Ok(grants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_database, PgFeatureSetRepository};
+    use crate::hash_token;
+    use mcpmux_core::{FeatureSet, FeatureSetRepository};
+
+    use super::*;
+
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    fn client(client_id: &str) -> InboundClient {
+        let now = now();
+        InboundClient {
+            client_id: client_id.to_string(),
+            registration_type: RegistrationType::Dcr,
+            client_name: "Cursor".to_string(),
+            client_alias: None,
+            redirect_uris: vec!["http://127.0.0.1/callback".to_string()],
+            grant_types: vec!["authorization_code".to_string()],
+            response_types: vec!["code".to_string()],
+            token_endpoint_auth_method: "none".to_string(),
+            scope: None,
+            approved: false,
+            logo_uri: None,
+            client_uri: None,
+            software_id: None,
+            software_version: None,
+            metadata_url: None,
+            metadata_cached_at: None,
+            metadata_cache_ttl: None,
+            last_seen: None,
+            created_at: now.clone(),
+            updated_at: now,
+            reports_roots: false,
+            roots_capability_known: false,
+            suspended: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clients_through_repository() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgInboundClientRepository::new(db);
+
+        repo.save_client(&client("c1")).await.unwrap();
+        assert!(!repo.is_client_approved("c1").await.unwrap());
+        repo.approve_client("c1").await.unwrap();
+        assert!(repo.is_client_approved("c1").await.unwrap());
+
+        assert!(repo.set_client_suspended("c1", true).await.unwrap());
+        assert!(repo.is_client_suspended("c1").await.unwrap());
+        assert!(!repo.is_client_suspended("missing").await.unwrap());
+        assert!(!repo.set_client_suspended("missing", true).await.unwrap());
+
+        let merged = repo
+            .merge_redirect_uris("c1", vec!["http://localhost/cb".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(merged.len(), 2);
+        assert!(repo
+            .validate_redirect_uri("c1", "http://localhost/cb")
+            .await
+            .unwrap());
+
+        // Roots support is sticky once declared
+        repo.mark_roots_capability("c1", true).await.unwrap();
+        repo.mark_roots_capability("c1", false).await.unwrap();
+        let stored = repo.get_client("c1").await.unwrap().unwrap();
+        assert!(stored.roots_capability_known && stored.reports_roots);
+
+        repo.set_locked_space("c1", Some(DEFAULT_SPACE_ID))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_locked_space("c1").await.unwrap().as_deref(),
+            Some(DEFAULT_SPACE_ID)
+        );
+        assert_eq!(repo.get_locked_space("missing").await.unwrap(), None);
+
+        assert!(repo.delete_client("c1").await.unwrap());
+        assert!(repo.get_client("c1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_codes_tokens_keys_and_grants() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgInboundClientRepository::new(db.clone());
+        repo.save_client(&client("c1")).await.unwrap();
+
+        let code = AuthorizationCode {
+            code: "code-1".to_string(),
+            client_id: "c1".to_string(),
+            redirect_uri: "http://127.0.0.1/callback".to_string(),
+            scope: None,
+            code_challenge: Some("challenge".to_string()),
+            code_challenge_method: Some("S256".to_string()),
+            expires_at: "2999-01-01T00:00:00Z".to_string(),
+            created_at: now(),
+        };
+        repo.save_authorization_code(&code).await.unwrap();
+        assert!(repo
+            .consume_authorization_code("code-1")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo
+            .consume_authorization_code("code-1")
+            .await
+            .unwrap()
+            .is_none());
+
+        let refresh = TokenRecord {
+            id: "t1".to_string(),
+            client_id: "c1".to_string(),
+            token_type: TokenType::Refresh,
+            token_hash: hash_token("refresh"),
+            scope: None,
+            expires_at: None,
+            revoked: false,
+            created_at: now(),
+            parent_token_id: None,
+        };
+        let access = TokenRecord {
+            id: "t2".to_string(),
+            token_type: TokenType::Access,
+            token_hash: hash_token("access"),
+            parent_token_id: Some("t1".to_string()),
+            ..refresh.clone()
+        };
+        repo.save_token(&refresh).await.unwrap();
+        repo.save_token(&access).await.unwrap();
+        assert!(repo.validate_token("access").await.unwrap().is_some());
+        repo.revoke_token("t1").await.unwrap();
+        assert!(repo.validate_token("access").await.unwrap().is_none());
+
+        repo.create_api_key("k1", "c1", "secret", "mcpmux_sec", None, None)
+            .await
+            .unwrap();
+        let auth = repo.validate_api_key("secret").await.unwrap().unwrap();
+        assert_eq!(auth.client_id, "c1");
+        assert!(repo.list_api_keys("c1").await.unwrap()[0]
+            .last_used_at
+            .is_some());
+        repo.revoke_api_key("k1").await.unwrap();
+        assert!(repo.validate_api_key("secret").await.unwrap().is_none());
+
+        let fs = FeatureSet::new_custom("granted", DEFAULT_SPACE_ID);
+        PgFeatureSetRepository::new(db).create(&fs).await.unwrap();
+        repo.grant_feature_set("c1", DEFAULT_SPACE_ID, &fs.id)
+            .await
+            .unwrap();
+        repo.grant_feature_set("c1", DEFAULT_SPACE_ID, &fs.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_grants_for_space("c1", DEFAULT_SPACE_ID)
+                .await
+                .unwrap(),
+            vec![fs.id.clone()]
+        );
+        assert_eq!(repo.get_all_grants("c1").await.unwrap().len(), 1);
+        repo.revoke_feature_set("c1", DEFAULT_SPACE_ID, &fs.id)
+            .await
+            .unwrap();
+        assert!(repo
+            .get_grants_for_space("c1", DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
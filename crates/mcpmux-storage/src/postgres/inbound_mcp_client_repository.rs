@@ -0,0 +1,153 @@
+//! PostgreSQL implementation of InboundMcpClientRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{Client, InboundMcpClientRepository};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::{parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of InboundMcpClientRepository.
+///
+/// Reads identity columns from the unified `inbound_clients` table, like the
+/// SQLite repository.
+pub struct PgInboundMcpClientRepository {
+    db: PgDatabase,
+}
+
+impl PgInboundMcpClientRepository {
+    /// Create a new PostgreSQL client repository.
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every `Client` read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "client_id, client_name, registration_type, last_seen, created_at, updated_at";
+
+    fn map_row(row: &Row) -> Result<Client, tokio_postgres::Error> {
+        Ok(Client {
+            id: row
+                .try_get::<_, String>(0)?
+                .parse()
+                .unwrap_or_else(|_| Uuid::new_v4()),
+            name: row.try_get(1)?,
+            client_type: row.try_get(2)?,
+            access_key: None,
+            last_seen: row.try_get::<_, Option<&str>>(3)?.map(parse_datetime),
+            created_at: parse_datetime(row.try_get(4)?),
+            updated_at: parse_datetime(row.try_get(5)?),
+        })
+    }
+}
+
+#[async_trait]
+impl InboundMcpClientRepository for PgInboundMcpClientRepository {
+    async fn list(&self) -> Result<Vec<Client>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM inbound_clients ORDER BY client_name ASC",
+            Self::COLUMNS
+        );
+        let rows = client.query(&sql, &[]).await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Client>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM inbound_clients WHERE client_id = $1",
+            Self::COLUMNS
+        );
+        let row = client.query_opt(&sql, &[&id.to_string()]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    /// Clients authenticate with OAuth tokens or API keys, neither of which
+    /// is looked up here, so no client has an access key.
+    async fn get_by_access_key(&self, _key_hash: &str) -> Result<Option<Client>> {
+        Ok(None)
+    }
+
+    async fn create(&self, client: &Client) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "INSERT INTO inbound_clients (
+                client_id, registration_type, client_name, last_seen, created_at, updated_at,
+                redirect_uris, grant_types, response_types, token_endpoint_auth_method, scope
+            ) VALUES ($1, 'preregistered', $2, $3, $4, $5, '[]', '[]', '[]', 'none', NULL)",
+            &[
+                &client.id.to_string(),
+                &client.name,
+                &client.last_seen.map(|dt| dt.to_rfc3339()),
+                &client.created_at.to_rfc3339(),
+                &client.updated_at.to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update(&self, client: &Client) -> Result<()> {
+        let conn = self.db.client().await?;
+        let rows_affected = conn
+            .execute(
+                "UPDATE inbound_clients
+                 SET client_name = $2, last_seen = $3, updated_at = $4
+                 WHERE client_id = $1",
+                &[
+                    &client.id.to_string(),
+                    &client.name,
+                    &client.last_seen.map(|dt| dt.to_rfc3339()),
+                    &client.updated_at.to_rfc3339(),
+                ],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("Client not found: {}", client.id);
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let conn = self.db.client().await?;
+        conn.execute(
+            "DELETE FROM inbound_clients WHERE client_id = $1",
+            &[&id.to_string()],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_crud_operations() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgInboundMcpClientRepository::new(db);
+
+        let client = Client::cursor();
+        repo.create(&client).await.unwrap();
+        assert_eq!(repo.get(&client.id).await.unwrap().unwrap().name, "Cursor");
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+
+        let mut updated = client.clone();
+        updated.name = "Cursor AI".to_string();
+        repo.update(&updated).await.unwrap();
+        assert_eq!(
+            repo.get(&client.id).await.unwrap().unwrap().name,
+            "Cursor AI"
+        );
+
+        repo.delete(&client.id).await.unwrap();
+        assert!(repo.get(&client.id).await.unwrap().is_none());
+    }
+}
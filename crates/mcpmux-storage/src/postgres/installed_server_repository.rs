@@ -0,0 +1,394 @@
+//! PostgreSQL implementation of InstalledServerRepository.
+//!
+//! Rows are encoded and decoded by the same helpers as the SQLite
+//! repository, so input values are encrypted identically.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mcpmux_core::{InstalledServer, InstalledServerRepository};
+use tokio_postgres::types::ToSql;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::PgDatabase;
+use crate::crypto::FieldEncryptor;
+use crate::repositories::{
+    build_server, encrypt_input_values, serialize_call_context, serialize_json_map,
+    serialize_json_vec, serialize_source, serialize_tool_hooks, RawServerRow,
+    INSTALLED_SERVER_COLUMNS as COLUMNS,
+};
+
+/// PostgreSQL-backed implementation of InstalledServerRepository.
+pub struct PgInstalledServerRepository {
+    db: PgDatabase,
+    encryptor: Arc<FieldEncryptor>,
+}
+
+impl PgInstalledServerRepository {
+    /// Create a new PostgreSQL installed server repository.
+    pub fn new(db: PgDatabase, encryptor: Arc<FieldEncryptor>) -> Self {
+        Self { db, encryptor }
+    }
+
+    fn extract_row(row: &Row) -> Result<RawServerRow, tokio_postgres::Error> {
+        // Timeouts are BIGINT here; the domain keeps them as u32
+        let timeout = |idx: usize| -> Result<Option<u32>, tokio_postgres::Error> {
+            Ok(row
+                .try_get::<_, Option<i64>>(idx)?
+                .and_then(|secs| u32::try_from(secs).ok()))
+        };
+        Ok(RawServerRow {
+            id: row.try_get(0)?,
+            space_id: row.try_get(1)?,
+            server_id: row.try_get(2)?,
+            server_name: row.try_get(3)?,
+            cached_definition: row.try_get(4)?,
+            input_values: row.try_get(5)?,
+            enabled: row.try_get(6)?,
+            env_overrides: row.try_get(7)?,
+            args_append: row.try_get(8)?,
+            extra_headers: row.try_get(9)?,
+            oauth_connected: row.try_get(10)?,
+            created_at: row.try_get(11)?,
+            updated_at: row.try_get(12)?,
+            source: row.try_get(13)?,
+            inherit_env: row.try_get(14)?,
+            fs_allowed_roots: row.try_get(15)?,
+            package_version: row.try_get(16)?,
+            skip_argument_validation: row.try_get(17)?,
+            connect_timeout_secs: timeout(18)?,
+            request_timeout_secs: timeout(19)?,
+            tool_hooks: row.try_get(20)?,
+            wasm_grant_filesystem: row.try_get(21)?,
+            wasm_grant_network: row.try_get(22)?,
+            call_context: row.try_get(23)?,
+            last_used_at: row.try_get(24)?,
+            deleted_at: row.try_get(25)?,
+        })
+    }
+
+    /// Servers matching `filter`, sorted by `order`.
+    async fn query(
+        &self,
+        filter: &str,
+        order: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<InstalledServer>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!("SELECT {COLUMNS} FROM installed_servers WHERE {filter} ORDER BY {order}"),
+                params,
+            )
+            .await?;
+        rows.iter()
+            .map(|row| build_server(&self.encryptor, Self::extract_row(row)?))
+            .collect()
+    }
+
+    async fn execute(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<()> {
+        let client = self.db.client().await?;
+        client.execute(sql, params).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InstalledServerRepository for PgInstalledServerRepository {
+    async fn list(&self) -> Result<Vec<InstalledServer>> {
+        self.query("deleted_at IS NULL", "created_at DESC", &[])
+            .await
+    }
+
+    async fn list_for_space(&self, space_id: &str) -> Result<Vec<InstalledServer>> {
+        self.query(
+            "space_id = $1 AND deleted_at IS NULL",
+            "created_at DESC",
+            &[&space_id],
+        )
+        .await
+    }
+
+    async fn list_by_source_file(
+        &self,
+        file_path: &std::path::Path,
+    ) -> Result<Vec<InstalledServer>> {
+        // Source format is "user_config:/path/to/file.json"
+        let source = format!("user_config:{}", file_path.display());
+        self.query(
+            "source = $1 AND deleted_at IS NULL",
+            "created_at DESC",
+            &[&source],
+        )
+        .await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<InstalledServer>> {
+        Ok(self
+            .query(
+                "id = $1 AND deleted_at IS NULL",
+                "created_at DESC",
+                &[&id.to_string()],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn get_by_server_id(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> Result<Option<InstalledServer>> {
+        Ok(self
+            .query(
+                "space_id = $1 AND server_id = $2 AND deleted_at IS NULL",
+                "created_at DESC",
+                &[&space_id, &server_id],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn install(&self, server: &InstalledServer) -> Result<()> {
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &server.input_values)?;
+        self.execute(
+            "INSERT INTO installed_servers
+             (id, space_id, server_id, server_name, cached_definition, input_values, enabled, env_overrides,
+              args_append, extra_headers, oauth_connected, created_at, updated_at, source, inherit_env,
+              fs_allowed_roots, package_version, skip_argument_validation, connect_timeout_secs,
+              request_timeout_secs, tool_hooks, wasm_grant_filesystem, wasm_grant_network,
+              call_context)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                     $19, $20, $21, $22, $23, $24)",
+            &[
+                &server.id.to_string(),
+                &server.space_id,
+                &server.server_id,
+                &server.server_name,
+                &server.cached_definition,
+                &encrypted_inputs,
+                &server.enabled,
+                &serialize_json_map(&server.env_overrides),
+                &serialize_json_vec(&server.args_append),
+                &serialize_json_map(&server.extra_headers),
+                &server.oauth_connected,
+                &server.created_at.to_rfc3339(),
+                &server.updated_at.to_rfc3339(),
+                &serialize_source(&server.source),
+                &server.inherit_env,
+                &serialize_json_vec(&server.fs_allowed_roots),
+                &server.package_version,
+                &server.skip_argument_validation,
+                &server.timeouts.connect_secs.map(i64::from),
+                &server.timeouts.request_secs.map(i64::from),
+                &serialize_tool_hooks(&server.tool_hooks),
+                &server.wasm_grants.filesystem,
+                &server.wasm_grants.network,
+                &serialize_call_context(&server.call_context),
+            ],
+        )
+        .await
+    }
+
+    async fn update(&self, server: &InstalledServer) -> Result<()> {
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &server.input_values)?;
+        self.execute(
+            "UPDATE installed_servers
+             SET server_name = $2, cached_definition = $3, input_values = $4, enabled = $5,
+                 env_overrides = $6, args_append = $7, extra_headers = $8, oauth_connected = $9,
+                 updated_at = $10, source = $11, inherit_env = $12,
+                 fs_allowed_roots = $13, package_version = $14, skip_argument_validation = $15,
+                 connect_timeout_secs = $16, request_timeout_secs = $17, tool_hooks = $18,
+                 wasm_grant_filesystem = $19, wasm_grant_network = $20, call_context = $21
+             WHERE id = $1",
+            &[
+                &server.id.to_string(),
+                &server.server_name,
+                &server.cached_definition,
+                &encrypted_inputs,
+                &server.enabled,
+                &serialize_json_map(&server.env_overrides),
+                &serialize_json_vec(&server.args_append),
+                &serialize_json_map(&server.extra_headers),
+                &server.oauth_connected,
+                &Utc::now().to_rfc3339(),
+                &serialize_source(&server.source),
+                &server.inherit_env,
+                &serialize_json_vec(&server.fs_allowed_roots),
+                &server.package_version,
+                &server.skip_argument_validation,
+                &server.timeouts.connect_secs.map(i64::from),
+                &server.timeouts.request_secs.map(i64::from),
+                &serialize_tool_hooks(&server.tool_hooks),
+                &server.wasm_grants.filesystem,
+                &server.wasm_grants.network,
+                &serialize_call_context(&server.call_context),
+            ],
+        )
+        .await
+    }
+
+    async fn uninstall(&self, id: &Uuid) -> Result<()> {
+        self.execute(
+            "DELETE FROM installed_servers WHERE id = $1",
+            &[&id.to_string()],
+        )
+        .await
+    }
+
+    async fn trash(&self, id: &Uuid, at: DateTime<Utc>) -> Result<()> {
+        self.execute(
+            "UPDATE installed_servers SET deleted_at = $2 WHERE id = $1",
+            &[&id.to_string(), &at.to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn restore(&self, id: &Uuid) -> Result<()> {
+        self.execute(
+            "UPDATE installed_servers SET deleted_at = NULL, updated_at = $2 WHERE id = $1",
+            &[&id.to_string(), &Utc::now().to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<InstalledServer>> {
+        self.query("deleted_at IS NOT NULL", "deleted_at DESC", &[])
+            .await
+    }
+
+    async fn list_enabled(&self, space_id: &str) -> Result<Vec<InstalledServer>> {
+        self.query(
+            "space_id = $1 AND enabled AND deleted_at IS NULL",
+            "created_at DESC",
+            &[&space_id],
+        )
+        .await
+    }
+
+    async fn list_enabled_all(&self) -> Result<Vec<InstalledServer>> {
+        self.query("enabled AND deleted_at IS NULL", "created_at DESC", &[])
+            .await
+    }
+
+    async fn set_enabled(&self, id: &Uuid, enabled: bool) -> Result<()> {
+        self.execute(
+            "UPDATE installed_servers SET enabled = $2, updated_at = $3 WHERE id = $1",
+            &[&id.to_string(), &enabled, &Utc::now().to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn set_oauth_connected(&self, id: &Uuid, connected: bool) -> Result<()> {
+        self.execute(
+            "UPDATE installed_servers SET oauth_connected = $2, updated_at = $3 WHERE id = $1",
+            &[&id.to_string(), &connected, &Utc::now().to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn set_last_used(&self, id: &Uuid, at: DateTime<Utc>) -> Result<()> {
+        // Not a config change, so `updated_at` stays as it is
+        self.execute(
+            "UPDATE installed_servers SET last_used_at = $2 WHERE id = $1",
+            &[&id.to_string(), &at.to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn update_inputs(&self, id: &Uuid, input_values: HashMap<String, String>) -> Result<()> {
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &input_values)?;
+        self.execute(
+            "UPDATE installed_servers SET input_values = $2, updated_at = $3 WHERE id = $1",
+            &[&id.to_string(), &encrypted_inputs, &Utc::now().to_rfc3339()],
+        )
+        .await
+    }
+
+    async fn update_cached_definition(
+        &self,
+        id: &Uuid,
+        server_name: Option<String>,
+        cached_definition: Option<String>,
+    ) -> Result<()> {
+        self.execute(
+            "UPDATE installed_servers SET server_name = $2, cached_definition = $3, updated_at = $4 WHERE id = $1",
+            &[
+                &id.to_string(),
+                &server_name,
+                &cached_definition,
+                &Utc::now().to_rfc3339(),
+            ],
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+    use mcpmux_core::ServerTimeouts;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_round_trip_trash_and_restore() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let key = crate::crypto::generate_master_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let repo = PgInstalledServerRepository::new(db.clone(), encryptor);
+
+        let mut server = InstalledServer::new(DEFAULT_SPACE_ID, "github");
+        server
+            .input_values
+            .insert("TOKEN".to_string(), "ghp_secret".to_string());
+        server.args_append = vec!["--verbose".to_string()];
+        server.timeouts = ServerTimeouts {
+            connect_secs: Some(30),
+            request_secs: None,
+        };
+        repo.install(&server).await.unwrap();
+
+        let raw: String = db
+            .client()
+            .await
+            .unwrap()
+            .query_one("SELECT input_values FROM installed_servers", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert!(!raw.contains("ghp_secret"));
+
+        let found = repo
+            .get_by_server_id(DEFAULT_SPACE_ID, "github")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.input_values["TOKEN"], "ghp_secret");
+        assert_eq!(found.args_append, vec!["--verbose"]);
+        assert_eq!(found.timeouts.connect_secs, Some(30));
+
+        repo.set_enabled(&server.id, true).await.unwrap();
+        assert_eq!(repo.list_enabled(DEFAULT_SPACE_ID).await.unwrap().len(), 1);
+
+        repo.trash(&server.id, Utc::now()).await.unwrap();
+        assert!(repo.get(&server.id).await.unwrap().is_none());
+        assert!(repo.list_enabled_all().await.unwrap().is_empty());
+        assert_eq!(repo.list_trashed().await.unwrap().len(), 1);
+
+        repo.restore(&server.id).await.unwrap();
+        assert!(repo.get(&server.id).await.unwrap().is_some());
+        repo.uninstall(&server.id).await.unwrap();
+        assert!(repo.list().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,32 @@
+//! PostgreSQL migrations
+//!
+//! Same scheme as the SQLite registry in [`crate::migrations`]: numbered,
+//! checksummed when applied, never edited once shipped. The numbering is
+//! independent of SQLite's; migration 001 is the SQLite schema at 039.
+
+use crate::migrations::Migration;
+
+/// All PostgreSQL migrations in order. Add new migrations here.
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "initial",
+    sql: include_str!("migrations/001_initial.sql"),
+}];
+
+/// Highest schema version this build knows how to migrate to.
+pub(crate) fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(m.version, i as i64 + 1, "migration {} out of order", m.name);
+        }
+        assert_eq!(latest_version(), MIGRATIONS.len() as i64);
+    }
+}
@@ -0,0 +1,362 @@
+-- PostgreSQL migration 001: initial schema
+--
+-- The SQLite schema at migration 039, table for table and column for
+-- column, so rows move between the two backends unchanged. Timestamps stay
+-- RFC 3339 TEXT (the repositories compare them as strings); flags become
+-- BOOLEAN; NOCASE unique indexes become unique indexes on lower(name).
+
+CREATE TABLE users (
+    id         TEXT PRIMARY KEY,
+    name       TEXT NOT NULL,
+    role       TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('admin', 'member')),
+    token_hash TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_users_name ON users(lower(name));
+CREATE UNIQUE INDEX idx_users_token_hash ON users(token_hash);
+
+CREATE TABLE spaces (
+    id            TEXT PRIMARY KEY,
+    name          TEXT NOT NULL,
+    icon          TEXT,
+    description   TEXT,
+    is_default    BOOLEAN NOT NULL DEFAULT FALSE,
+    sort_order    INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT NOT NULL,
+    updated_at    TEXT NOT NULL,
+    owner_user_id TEXT REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_spaces_is_default ON spaces(is_default);
+
+CREATE TABLE installed_servers (
+    id                       TEXT PRIMARY KEY,
+    space_id                 TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    server_id                TEXT NOT NULL,
+    server_name              TEXT,
+    cached_definition        TEXT,
+    input_values             TEXT NOT NULL DEFAULT '{}',
+    enabled                  BOOLEAN NOT NULL DEFAULT FALSE,
+    env_overrides            TEXT NOT NULL DEFAULT '{}',
+    args_append              TEXT NOT NULL DEFAULT '[]',
+    extra_headers            TEXT NOT NULL DEFAULT '{}',
+    oauth_connected          BOOLEAN NOT NULL DEFAULT FALSE,
+    source                   TEXT NOT NULL DEFAULT 'registry',
+    created_at               TEXT NOT NULL,
+    updated_at               TEXT NOT NULL,
+    inherit_env              BOOLEAN NOT NULL DEFAULT FALSE,
+    fs_allowed_roots         TEXT,
+    package_version          TEXT,
+    skip_argument_validation BOOLEAN NOT NULL DEFAULT FALSE,
+    connect_timeout_secs     BIGINT,
+    request_timeout_secs     BIGINT,
+    tool_hooks               TEXT,
+    wasm_grant_filesystem    BOOLEAN NOT NULL DEFAULT FALSE,
+    wasm_grant_network       BOOLEAN NOT NULL DEFAULT FALSE,
+    call_context             TEXT,
+    last_used_at             TEXT,
+    deleted_at               TEXT,
+    UNIQUE (space_id, server_id)
+);
+
+CREATE INDEX idx_installed_servers_space ON installed_servers(space_id);
+CREATE INDEX idx_installed_servers_enabled ON installed_servers(space_id, enabled);
+CREATE INDEX idx_installed_servers_source ON installed_servers(source);
+
+CREATE TABLE outbound_oauth_clients (
+    id            TEXT PRIMARY KEY,
+    space_id      TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    server_id     TEXT NOT NULL,
+    server_url    TEXT NOT NULL,
+    client_id     TEXT NOT NULL,
+    redirect_uri  TEXT,
+    metadata_json TEXT,
+    created_at    TEXT NOT NULL,
+    updated_at    TEXT NOT NULL,
+    UNIQUE (space_id, server_id)
+);
+
+CREATE TABLE server_features (
+    id               TEXT PRIMARY KEY,
+    space_id         TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    server_id        TEXT NOT NULL,
+    feature_type     TEXT NOT NULL,
+    feature_name     TEXT NOT NULL,
+    display_name     TEXT,
+    description      TEXT,
+    raw_json         TEXT,
+    discovered_at    TEXT NOT NULL,
+    last_seen_at     TEXT NOT NULL,
+    is_available     BOOLEAN NOT NULL DEFAULT TRUE,
+    schema_pinned    BOOLEAN NOT NULL DEFAULT FALSE,
+    pending_raw_json TEXT,
+    UNIQUE (space_id, server_id, feature_type, feature_name)
+);
+
+CREATE INDEX idx_server_features_space ON server_features(space_id);
+CREATE INDEX idx_server_features_server ON server_features(space_id, server_id);
+
+CREATE TABLE feature_sets (
+    id               TEXT PRIMARY KEY,
+    name             TEXT NOT NULL,
+    description      TEXT,
+    icon             TEXT,
+    space_id         TEXT REFERENCES spaces(id) ON DELETE CASCADE,
+    feature_set_type TEXT NOT NULL DEFAULT 'custom',
+    server_id        TEXT,
+    is_builtin       BOOLEAN NOT NULL DEFAULT FALSE,
+    is_deleted       BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL
+);
+
+CREATE INDEX idx_feature_sets_space ON feature_sets(space_id);
+CREATE INDEX idx_feature_sets_type ON feature_sets(feature_set_type);
+
+CREATE TABLE feature_set_members (
+    id             TEXT PRIMARY KEY,
+    feature_set_id TEXT NOT NULL REFERENCES feature_sets(id) ON DELETE CASCADE,
+    member_type    TEXT NOT NULL,
+    member_id      TEXT NOT NULL,
+    mode           TEXT NOT NULL DEFAULT 'include',
+    created_at     TEXT NOT NULL,
+    UNIQUE (feature_set_id, member_type, member_id)
+);
+
+CREATE INDEX idx_fsm_feature_set ON feature_set_members(feature_set_id);
+
+CREATE TABLE inbound_clients (
+    client_id                  TEXT PRIMARY KEY,
+    registration_type          TEXT NOT NULL CHECK (registration_type IN ('cimd', 'dcr', 'preregistered')),
+    client_name                TEXT NOT NULL,
+    client_alias               TEXT,
+    logo_uri                   TEXT,
+    client_uri                 TEXT,
+    software_id                TEXT,
+    software_version           TEXT,
+    redirect_uris              TEXT NOT NULL,
+    grant_types                TEXT NOT NULL,
+    response_types             TEXT NOT NULL,
+    token_endpoint_auth_method TEXT NOT NULL,
+    scope                      TEXT,
+    metadata_url               TEXT,
+    metadata_cached_at         TEXT,
+    metadata_cache_ttl         BIGINT DEFAULT 3600,
+    grants                     TEXT,
+    approved                   BOOLEAN NOT NULL DEFAULT FALSE,
+    last_seen                  TEXT,
+    created_at                 TEXT NOT NULL,
+    updated_at                 TEXT NOT NULL,
+    reports_roots              BOOLEAN NOT NULL DEFAULT FALSE,
+    roots_capability_known     BOOLEAN NOT NULL DEFAULT FALSE,
+    locked_space_id            TEXT,
+    suspended                  BOOLEAN NOT NULL DEFAULT FALSE,
+    owner_user_id              TEXT REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_inbound_clients_name ON inbound_clients(client_name);
+
+CREATE TABLE oauth_authorization_codes (
+    code                  TEXT PRIMARY KEY,
+    client_id             TEXT NOT NULL REFERENCES inbound_clients(client_id) ON DELETE CASCADE,
+    redirect_uri          TEXT NOT NULL,
+    scope                 TEXT,
+    code_challenge        TEXT NOT NULL,
+    code_challenge_method TEXT NOT NULL DEFAULT 'S256',
+    state                 TEXT,
+    expires_at            TEXT NOT NULL,
+    created_at            TEXT NOT NULL
+);
+
+CREATE TABLE oauth_tokens (
+    id              TEXT PRIMARY KEY,
+    client_id       TEXT NOT NULL REFERENCES inbound_clients(client_id) ON DELETE CASCADE,
+    token_type      TEXT NOT NULL,
+    token_hash      TEXT NOT NULL,
+    scope           TEXT,
+    expires_at      TEXT,
+    revoked         BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at      TEXT NOT NULL,
+    parent_token_id TEXT REFERENCES oauth_tokens(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_oauth_tokens_hash ON oauth_tokens(token_hash);
+CREATE INDEX idx_oauth_tokens_client ON oauth_tokens(client_id);
+
+CREATE TABLE inbound_client_api_keys (
+    key_id       TEXT PRIMARY KEY,
+    client_id    TEXT NOT NULL REFERENCES inbound_clients(client_id) ON DELETE CASCADE,
+    key_hash     TEXT NOT NULL UNIQUE,
+    key_prefix   TEXT NOT NULL,
+    label        TEXT,
+    revoked      BOOLEAN NOT NULL DEFAULT FALSE,
+    last_used_at TEXT,
+    expires_at   TEXT,
+    created_at   TEXT NOT NULL,
+    updated_at   TEXT NOT NULL
+);
+
+CREATE INDEX idx_api_keys_client ON inbound_client_api_keys(client_id);
+
+CREATE TABLE client_grants (
+    client_id      TEXT NOT NULL REFERENCES inbound_clients(client_id) ON DELETE CASCADE,
+    space_id       TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    feature_set_id TEXT NOT NULL REFERENCES feature_sets(id) ON DELETE CASCADE,
+    PRIMARY KEY (client_id, space_id, feature_set_id)
+);
+
+CREATE INDEX idx_client_grants_space ON client_grants(space_id);
+
+CREATE TABLE settings (
+    key        TEXT PRIMARY KEY,
+    value      TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE app_settings (
+    key        TEXT PRIMARY KEY,
+    value      TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE workspace_bindings (
+    id             TEXT PRIMARY KEY,
+    workspace_root TEXT NOT NULL UNIQUE,
+    space_id       TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    created_at     TEXT NOT NULL,
+    updated_at     TEXT NOT NULL,
+    binding_type   TEXT NOT NULL DEFAULT 'path'
+);
+
+CREATE INDEX idx_workspace_bindings_space ON workspace_bindings(space_id);
+
+CREATE TABLE workspace_binding_feature_sets (
+    binding_id     TEXT NOT NULL REFERENCES workspace_bindings(id) ON DELETE CASCADE,
+    feature_set_id TEXT NOT NULL REFERENCES feature_sets(id) ON DELETE CASCADE,
+    sort_order     INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (binding_id, feature_set_id)
+);
+
+CREATE TABLE space_builtin_servers (
+    space_id   TEXT NOT NULL,
+    server_id  TEXT NOT NULL,
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (space_id, server_id)
+);
+
+CREATE TABLE space_builtin_tools (
+    space_id   TEXT NOT NULL,
+    server_id  TEXT NOT NULL,
+    tool_name  TEXT NOT NULL,
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (space_id, server_id, tool_name)
+);
+
+CREATE TABLE space_base_dirs (
+    id         TEXT PRIMARY KEY,
+    space_id   TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    path       TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_space_base_dirs_space ON space_base_dirs(space_id);
+
+CREATE TABLE server_groups (
+    id             TEXT PRIMARY KEY,
+    space_id       TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    name           TEXT NOT NULL,
+    icon           TEXT,
+    server_ids     TEXT NOT NULL DEFAULT '[]',
+    feature_set_id TEXT,
+    created_at     TEXT NOT NULL,
+    updated_at     TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_server_groups_space_name ON server_groups(space_id, lower(name));
+
+CREATE TABLE environment_profiles (
+    id         TEXT PRIMARY KEY,
+    space_id   TEXT NOT NULL REFERENCES spaces(id) ON DELETE CASCADE,
+    name       TEXT NOT NULL,
+    overrides  TEXT,
+    is_active  BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_environment_profiles_space_name
+    ON environment_profiles(space_id, lower(name));
+CREATE UNIQUE INDEX idx_environment_profiles_active
+    ON environment_profiles(space_id) WHERE is_active;
+
+-- Registry credentials use the nil Space ID, so there is no foreign key to
+-- `spaces`; a trigger removes a Space's credentials instead.
+CREATE TABLE credentials (
+    id               TEXT PRIMARY KEY,
+    space_id         TEXT NOT NULL,
+    server_id        TEXT NOT NULL,
+    credential_type  TEXT NOT NULL,
+    credential_value TEXT NOT NULL,
+    expires_at       TEXT,
+    token_type       TEXT,
+    scope            TEXT,
+    last_used_at     TEXT,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL,
+    UNIQUE (space_id, server_id, credential_type)
+);
+
+CREATE FUNCTION delete_space_credentials() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM credentials WHERE space_id = OLD.id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_credentials_space_deleted
+AFTER DELETE ON spaces
+FOR EACH ROW EXECUTE FUNCTION delete_space_credentials();
+
+CREATE INDEX idx_credentials_expiry ON credentials(credential_type, expires_at);
+
+-- The identity's sequence never hands out a number twice, so sequence
+-- numbers survive pruning like SQLite's AUTOINCREMENT.
+CREATE TABLE event_journal (
+    sequence    BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    event_type  TEXT NOT NULL,
+    space_id    TEXT,
+    payload     TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+INSERT INTO spaces (id, name, icon, description, is_default, sort_order, created_at, updated_at)
+VALUES (
+    '00000000-0000-0000-0000-000000000001',
+    'My Space',
+    '🏠',
+    'Default workspace for your MCP servers',
+    TRUE,
+    0,
+    to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"'),
+    to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"')
+);
+
+INSERT INTO feature_sets (id, name, description, icon, space_id, feature_set_type, is_builtin, created_at, updated_at)
+VALUES (
+    'fs_default_00000000-0000-0000-0000-000000000001',
+    'Starter',
+    'Auto-created with this Space — the default set for folders you haven''t explicitly mapped. Edit which tools it includes to change what they get. Its name is fixed and it can''t be deleted.',
+    '⭐',
+    '00000000-0000-0000-0000-000000000001',
+    'starter',
+    TRUE,
+    to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"'),
+    to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"')
+);
+
+INSERT INTO app_settings (key, value, updated_at)
+VALUES ('gateway.auto_start', 'true', to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"'));
@@ -0,0 +1,97 @@
+//! PostgreSQL storage backend.
+//!
+//! For a gateway shared by a team: several gateway processes serve the same
+//! Spaces, clients and grants from one database. The schema mirrors the
+//! SQLite one table for table, so each `Pg*Repository` behaves like its
+//! `Sqlite*` counterpart and rows can be copied between the two.
+//!
+//! Backups, `mcpmux db doctor` and master key rotation work on the SQLite
+//! file and don't apply here; use the database's own tooling instead.
+
+mod app_settings_repository;
+mod credential_repository;
+mod database;
+mod environment_profile_repository;
+mod event_journal_repository;
+mod feature_set_repository;
+mod inbound_client_repository;
+mod inbound_mcp_client_repository;
+mod installed_server_repository;
+mod migrations;
+mod outbound_oauth_client_repository;
+mod server_feature_repository;
+mod server_group_repository;
+mod space_base_dir_repository;
+mod space_builtin_config_repository;
+mod space_repository;
+mod user_repository;
+mod workspace_binding_repository;
+
+pub use app_settings_repository::PgAppSettingsRepository;
+pub use credential_repository::PgCredentialRepository;
+pub use database::{PgClient, PgDatabase};
+pub use environment_profile_repository::PgEnvironmentProfileRepository;
+pub use event_journal_repository::PgEventJournalRepository;
+pub use feature_set_repository::PgFeatureSetRepository;
+pub use inbound_client_repository::PgInboundClientRepository;
+pub use inbound_mcp_client_repository::PgInboundMcpClientRepository;
+pub use installed_server_repository::PgInstalledServerRepository;
+pub use outbound_oauth_client_repository::PgOutboundOAuthRepository;
+pub use server_feature_repository::PgServerFeatureRepository;
+pub use server_group_repository::PgServerGroupRepository;
+pub use space_base_dir_repository::PgSpaceBaseDirRepository;
+pub use space_builtin_config_repository::PgSpaceBuiltinConfigRepository;
+pub use space_repository::PgSpaceRepository;
+pub use user_repository::PgUserRepository;
+pub use workspace_binding_repository::PgWorkspaceBindingRepository;
+
+use chrono::{DateTime, Utc};
+use tokio_postgres::error::SqlState;
+
+/// Current time in the format the SQLite repositories write.
+pub(crate) fn now() -> String {
+    Utc::now().to_rfc3339()
+}
+
+/// Parse a stored timestamp, falling back to now for unreadable values like
+/// the SQLite repositories do.
+pub(crate) fn parse_datetime(s: &str) -> DateTime<Utc> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return dt.with_timezone(&Utc);
+    }
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return dt.and_utc();
+    }
+    Utc::now()
+}
+
+/// Whether `e` is a unique constraint violation.
+pub(crate) fn is_unique_violation(e: &tokio_postgres::Error) -> bool {
+    e.code() == Some(&SqlState::UNIQUE_VIOLATION)
+}
+
+/// Environment variable with the URL of a PostgreSQL database for tests.
+///
+/// Each test runs in a schema of its own; without the variable the
+/// PostgreSQL tests pass without doing anything.
+#[cfg(test)]
+pub(crate) const TEST_DATABASE_URL_ENV_VAR: &str = "MCPMUX_TEST_DATABASE_URL";
+
+/// A freshly migrated database in a new schema, or `None` when
+/// [`TEST_DATABASE_URL_ENV_VAR`] isn't set.
+#[cfg(test)]
+pub(crate) async fn test_database() -> Option<PgDatabase> {
+    let url = std::env::var(TEST_DATABASE_URL_ENV_VAR).ok()?;
+    let mut config: tokio_postgres::Config = url.parse().unwrap();
+    let schema = format!("test_{}", uuid::Uuid::new_v4().simple());
+
+    let (client, connection) = config.connect(tokio_postgres::NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+        .batch_execute(&format!("CREATE SCHEMA {schema}"))
+        .await
+        .unwrap();
+
+    config.options(format!("-c search_path={schema}"));
+    Some(PgDatabase::connect_with_config(config).await.unwrap())
+}
@@ -0,0 +1,169 @@
+//! PostgreSQL implementation of OutboundOAuthRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{OutboundOAuthRegistration, OutboundOAuthRepository, StoredOAuthMetadata};
+use tokio_postgres::Row;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed outbound OAuth client repository.
+pub struct PgOutboundOAuthRepository {
+    db: PgDatabase,
+}
+
+impl PgOutboundOAuthRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, space_id, server_id, server_url, client_id, redirect_uri, metadata_json, created_at, updated_at";
+
+    fn map_row(row: &Row) -> Result<OutboundOAuthRegistration, tokio_postgres::Error> {
+        let id: String = row.try_get(0)?;
+        let space_id: String = row.try_get(1)?;
+        let metadata_json: Option<String> = row.try_get(6)?;
+        let metadata: Option<StoredOAuthMetadata> = metadata_json.and_then(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| warn!("Failed to parse stored OAuth metadata: {}", e))
+                .ok()
+        });
+        Ok(OutboundOAuthRegistration {
+            id: id.parse().unwrap_or_else(|_| Uuid::new_v4()),
+            space_id: space_id.parse().unwrap_or_else(|_| Uuid::new_v4()),
+            server_id: row.try_get(2)?,
+            server_url: row.try_get(3)?,
+            client_id: row.try_get(4)?,
+            redirect_uri: row.try_get(5)?,
+            metadata,
+            created_at: parse_datetime(&row.try_get::<_, String>(7)?),
+            updated_at: parse_datetime(&row.try_get::<_, String>(8)?),
+        })
+    }
+}
+
+#[async_trait]
+impl OutboundOAuthRepository for PgOutboundOAuthRepository {
+    async fn get(
+        &self,
+        space_id: &Uuid,
+        server_id: &str,
+    ) -> Result<Option<OutboundOAuthRegistration>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM outbound_oauth_clients WHERE space_id = $1 AND server_id = $2",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string(), &server_id],
+            )
+            .await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn save(&self, reg: &OutboundOAuthRegistration) -> Result<()> {
+        let metadata_json: Option<String> = reg
+            .metadata
+            .as_ref()
+            .and_then(|m| serde_json::to_string(m).ok());
+
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO outbound_oauth_clients (
+                    id, space_id, server_id, server_url, client_id, redirect_uri, metadata_json, created_at, updated_at
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (space_id, server_id) DO UPDATE SET
+                    server_url = excluded.server_url,
+                    client_id = excluded.client_id,
+                    redirect_uri = excluded.redirect_uri,
+                    metadata_json = excluded.metadata_json,
+                    updated_at = excluded.updated_at",
+                &[
+                    &reg.id.to_string(),
+                    &reg.space_id.to_string(),
+                    &reg.server_id,
+                    &reg.server_url,
+                    &reg.client_id,
+                    &reg.redirect_uri,
+                    &metadata_json,
+                    &reg.created_at.to_rfc3339(),
+                    &reg.updated_at.to_rfc3339(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, space_id: &Uuid, server_id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "DELETE FROM outbound_oauth_clients WHERE space_id = $1 AND server_id = $2",
+                &[&space_id.to_string(), &server_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn list_for_space(&self, space_id: &Uuid) -> Result<Vec<OutboundOAuthRegistration>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM outbound_oauth_clients WHERE space_id = $1 ORDER BY server_id",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string()],
+            )
+            .await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backend_oauth_crud() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgOutboundOAuthRepository::new(db);
+        let space_id: Uuid = "00000000-0000-0000-0000-000000000001".parse().unwrap();
+
+        let mut reg = OutboundOAuthRegistration::new(
+            space_id,
+            "cloudflare-bindings",
+            "https://bindings.mcp.cloudflare.com",
+            "client_123",
+            "http://127.0.0.1:9876/callback",
+        );
+        repo.save(&reg).await.unwrap();
+
+        // Saving again for the same server replaces the registration
+        reg.client_id = "client_456".to_string();
+        repo.save(&reg).await.unwrap();
+        let found = repo
+            .get(&space_id, "cloudflare-bindings")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.client_id, "client_456");
+        assert!(found.matches_redirect_uri("http://127.0.0.1:9876/callback"));
+        assert_eq!(repo.list_for_space(&space_id).await.unwrap().len(), 1);
+
+        repo.delete(&space_id, "cloudflare-bindings").await.unwrap();
+        assert!(repo
+            .get(&space_id, "cloudflare-bindings")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,474 @@
+//! PostgreSQL implementation of ServerFeatureRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio_postgres::{GenericClient, Row};
+
+use super::{now, parse_datetime, PgDatabase};
+use crate::repositories::{FeatureType, ServerFeature, ServerFeatureRepository};
+
+/// PostgreSQL-backed implementation of ServerFeatureRepository
+pub struct PgServerFeatureRepository {
+    db: PgDatabase,
+}
+
+impl PgServerFeatureRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every `ServerFeature` read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, space_id, server_id, feature_type, feature_name, \
+         display_name, description, raw_json, discovered_at, \
+         last_seen_at, is_available, schema_pinned, pending_raw_json";
+
+    fn map_row(row: &Row) -> Result<ServerFeature, tokio_postgres::Error> {
+        let json = |idx: usize| -> Result<Option<serde_json::Value>, tokio_postgres::Error> {
+            Ok(row
+                .try_get::<_, Option<String>>(idx)?
+                .and_then(|s| serde_json::from_str(&s).ok()))
+        };
+        Ok(ServerFeature {
+            id: row.try_get(0)?,
+            space_id: row.try_get(1)?,
+            server_id: row.try_get(2)?,
+            feature_type: FeatureType::parse(row.try_get(3)?).unwrap_or(FeatureType::Tool),
+            feature_name: row.try_get(4)?,
+            display_name: row.try_get(5)?,
+            description: row.try_get(6)?,
+            raw_json: json(7)?,
+            discovered_at: parse_datetime(row.try_get(8)?),
+            last_seen_at: parse_datetime(row.try_get(9)?),
+            is_available: row.try_get(10)?,
+            schema_pinned: row.try_get(11)?,
+            pending_raw_json: json(12)?,
+        })
+    }
+
+    async fn upsert_on(client: &impl GenericClient, feature: &ServerFeature) -> Result<()> {
+        let to_json = |json: &Option<serde_json::Value>| {
+            json.as_ref()
+                .map(|s| serde_json::to_string(s).unwrap_or_default())
+        };
+
+        // The pin is set by the user, never by discovery
+        client
+            .execute(
+                "INSERT INTO server_features
+                    (id, space_id, server_id, feature_type, feature_name,
+                     display_name, description, raw_json, discovered_at,
+                     last_seen_at, is_available, schema_pinned, pending_raw_json)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                 ON CONFLICT (space_id, server_id, feature_type, feature_name) DO UPDATE SET
+                    display_name = COALESCE($6, server_features.display_name),
+                    description = COALESCE($7, server_features.description),
+                    raw_json = COALESCE($8, server_features.raw_json),
+                    last_seen_at = $10,
+                    is_available = $11,
+                    pending_raw_json = $13",
+                &[
+                    &feature.id,
+                    &feature.space_id,
+                    &feature.server_id,
+                    &feature.feature_type.as_str(),
+                    &feature.feature_name,
+                    &feature.display_name,
+                    &feature.description,
+                    &to_json(&feature.raw_json),
+                    &feature.discovered_at.to_rfc3339(),
+                    &feature.last_seen_at.to_rfc3339(),
+                    &feature.is_available,
+                    &feature.schema_pinned,
+                    &to_json(&feature.pending_raw_json),
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_on(client: &impl GenericClient, id: &str) -> Result<Option<ServerFeature>> {
+        let sql = format!(
+            "SELECT {} FROM server_features WHERE id = $1",
+            Self::COLUMNS
+        );
+        let row = client.query_opt(&sql, &[&id]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    /// Move a feature's pending JSON into `raw_json`; false when it had none
+    async fn accept_pending_on(client: &impl GenericClient, id: &str) -> Result<bool> {
+        let Some(pending) = Self::get_on(client, id)
+            .await?
+            .and_then(|f| f.pending_raw_json)
+        else {
+            return Ok(false);
+        };
+        let description = pending
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(str::to_string);
+        client
+            .execute(
+                "UPDATE server_features
+                 SET raw_json = $2, description = COALESCE($3, description), pending_raw_json = NULL
+                 WHERE id = $1",
+                &[&id, &serde_json::to_string(&pending)?, &description],
+            )
+            .await?;
+        Ok(true)
+    }
+
+    async fn list_where(
+        &self,
+        filter: &str,
+        order: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> Result<Vec<ServerFeature>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM server_features WHERE {} ORDER BY {}",
+            Self::COLUMNS,
+            filter,
+            order
+        );
+        let rows = client.query(&sql, params).await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+}
+
+#[async_trait]
+impl ServerFeatureRepository for PgServerFeatureRepository {
+    async fn list_by_space(&self, space_id: &str) -> Result<Vec<ServerFeature>> {
+        self.list_where(
+            "space_id = $1",
+            "server_id, feature_type, feature_name",
+            &[&space_id],
+        )
+        .await
+    }
+
+    async fn list_by_server(&self, space_id: &str, server_id: &str) -> Result<Vec<ServerFeature>> {
+        self.list_where(
+            "space_id = $1 AND server_id = $2",
+            "feature_type, feature_name",
+            &[&space_id, &server_id],
+        )
+        .await
+    }
+
+    async fn list_by_type(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        feature_type: FeatureType,
+    ) -> Result<Vec<ServerFeature>> {
+        self.list_where(
+            "space_id = $1 AND server_id = $2 AND feature_type = $3",
+            "feature_name",
+            &[&space_id, &server_id, &feature_type.as_str()],
+        )
+        .await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ServerFeature>> {
+        let client = self.db.client().await?;
+        Self::get_on(&**client, id).await
+    }
+
+    async fn get_by_name(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        feature_type: FeatureType,
+        name: &str,
+    ) -> Result<Option<ServerFeature>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM server_features
+             WHERE space_id = $1 AND server_id = $2 AND feature_type = $3 AND feature_name = $4",
+            Self::COLUMNS
+        );
+        let row = client
+            .query_opt(
+                &sql,
+                &[&space_id, &server_id, &feature_type.as_str(), &name],
+            )
+            .await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn upsert(&self, feature: &ServerFeature) -> Result<()> {
+        let client = self.db.client().await?;
+        Self::upsert_on(&**client, feature).await
+    }
+
+    /// Upsert every feature in one transaction
+    async fn upsert_many(&self, features: &[ServerFeature]) -> Result<()> {
+        if features.is_empty() {
+            return Ok(());
+        }
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        for feature in features {
+            Self::upsert_on(&*tx, feature).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn mark_unavailable_except(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        feature_type: FeatureType,
+        available_names: &[String],
+    ) -> Result<()> {
+        let client = self.db.client().await?;
+        // `<> ALL` of an empty array is true, so an empty list marks them all
+        client
+            .execute(
+                "UPDATE server_features
+                 SET is_available = FALSE, last_seen_at = $5
+                 WHERE space_id = $1 AND server_id = $2 AND feature_type = $3
+                   AND feature_name <> ALL($4)",
+                &[
+                    &space_id,
+                    &server_id,
+                    &feature_type.as_str(),
+                    &available_names,
+                    &now(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_schema_pinned(&self, id: &str, pinned: bool) -> Result<Option<ServerFeature>> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        if !pinned {
+            Self::accept_pending_on(&*tx, id).await?;
+        }
+        tx.execute(
+            "UPDATE server_features SET schema_pinned = $2 WHERE id = $1",
+            &[&id, &pinned],
+        )
+        .await?;
+        let feature = Self::get_on(&*tx, id).await?;
+        tx.commit().await?;
+        Ok(feature)
+    }
+
+    async fn accept_pending_schema(&self, id: &str) -> Result<Option<ServerFeature>> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        if !Self::accept_pending_on(&*tx, id).await? {
+            return Ok(None);
+        }
+        let feature = Self::get_on(&*tx, id).await?;
+        tx.commit().await?;
+        Ok(feature)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM server_features WHERE id = $1", &[&id])
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_by_server(&self, space_id: &str, server_id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "DELETE FROM server_features WHERE space_id = $1 AND server_id = $2",
+                &[&space_id, &server_id],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+// Same conversions as the SQLite repository, for the gateway services
+#[async_trait]
+impl mcpmux_core::ServerFeatureRepository for PgServerFeatureRepository {
+    async fn list_for_space(
+        &self,
+        space_id: &str,
+    ) -> mcpmux_core::RepoResult<Vec<mcpmux_core::ServerFeature>> {
+        let features = self.list_by_space(space_id).await?;
+        Ok(features.into_iter().map(|f| f.into()).collect())
+    }
+
+    async fn list_for_server(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> mcpmux_core::RepoResult<Vec<mcpmux_core::ServerFeature>> {
+        let features = self.list_by_server(space_id, server_id).await?;
+        Ok(features.into_iter().map(|f| f.into()).collect())
+    }
+
+    async fn get(
+        &self,
+        id: &uuid::Uuid,
+    ) -> mcpmux_core::RepoResult<Option<mcpmux_core::ServerFeature>> {
+        let result = ServerFeatureRepository::get(self, &id.to_string()).await?;
+        Ok(result.map(|f| f.into()))
+    }
+
+    async fn upsert(&self, feature: &mcpmux_core::ServerFeature) -> mcpmux_core::RepoResult<()> {
+        let storage_feature: ServerFeature = feature.clone().into();
+        ServerFeatureRepository::upsert(self, &storage_feature).await
+    }
+
+    async fn upsert_many(
+        &self,
+        features: &[mcpmux_core::ServerFeature],
+    ) -> mcpmux_core::RepoResult<()> {
+        let storage_features: Vec<ServerFeature> =
+            features.iter().map(|f| f.clone().into()).collect();
+        ServerFeatureRepository::upsert_many(self, &storage_features).await
+    }
+
+    async fn delete(&self, id: &uuid::Uuid) -> mcpmux_core::RepoResult<()> {
+        ServerFeatureRepository::delete(self, &id.to_string()).await
+    }
+
+    async fn mark_unavailable(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> mcpmux_core::RepoResult<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "UPDATE server_features SET is_available = FALSE WHERE space_id = $1 AND server_id = $2",
+                &[&space_id, &server_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_for_server(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> mcpmux_core::RepoResult<()> {
+        ServerFeatureRepository::delete_by_server(self, space_id, server_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_upsert_and_mark_unavailable() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgServerFeatureRepository::new(db);
+
+        let read = ServerFeature::new_tool(DEFAULT_SPACE_ID, "server1", "read_file")
+            .with_display_name("Read File");
+        let write = ServerFeature::new_tool(DEFAULT_SPACE_ID, "server1", "write_file");
+        repo.upsert_many(&[read.clone(), write.clone()])
+            .await
+            .unwrap();
+
+        // Rediscovery without a display name keeps the stored one
+        let mut again = ServerFeature::new_tool(DEFAULT_SPACE_ID, "server1", "read_file");
+        again.id = read.id.clone();
+        repo.upsert(&again).await.unwrap();
+        let found = repo
+            .get_by_name(DEFAULT_SPACE_ID, "server1", FeatureType::Tool, "read_file")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.display_name.as_deref(), Some("Read File"));
+
+        repo.mark_unavailable_except(
+            DEFAULT_SPACE_ID,
+            "server1",
+            FeatureType::Tool,
+            &["read_file".to_string()],
+        )
+        .await
+        .unwrap();
+        let tools = repo
+            .list_by_type(DEFAULT_SPACE_ID, "server1", FeatureType::Tool)
+            .await
+            .unwrap();
+        assert_eq!(tools.len(), 2);
+        assert!(tools[0].is_available);
+        assert!(!tools[1].is_available);
+
+        repo.mark_unavailable_except(DEFAULT_SPACE_ID, "server1", FeatureType::Tool, &[])
+            .await
+            .unwrap();
+        assert!(repo
+            .list_by_server(DEFAULT_SPACE_ID, "server1")
+            .await
+            .unwrap()
+            .iter()
+            .all(|f| !f.is_available));
+
+        repo.delete_by_server(DEFAULT_SPACE_ID, "server1")
+            .await
+            .unwrap();
+        assert!(repo
+            .list_by_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_schema_pin() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgServerFeatureRepository::new(db);
+
+        let mut feature = ServerFeature::new_tool(DEFAULT_SPACE_ID, "server1", "search")
+            .with_raw_json(serde_json::json!({ "name": "search", "description": "v1" }));
+        repo.upsert(&feature).await.unwrap();
+        assert!(repo
+            .accept_pending_schema(&feature.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(
+            repo.set_schema_pinned(&feature.id, true)
+                .await
+                .unwrap()
+                .unwrap()
+                .schema_pinned
+        );
+
+        feature.pending_raw_json =
+            Some(serde_json::json!({ "name": "search", "description": "v2" }));
+        repo.upsert(&feature).await.unwrap();
+        let held = repo.get(&feature.id).await.unwrap().unwrap();
+        assert!(held.schema_pinned);
+        assert_eq!(held.raw_json.unwrap()["description"], "v1");
+
+        // Unpinning accepts the pending schema
+        let unpinned = repo
+            .set_schema_pinned(&feature.id, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!unpinned.schema_pinned);
+        assert_eq!(unpinned.raw_json.unwrap()["description"], "v2");
+        assert_eq!(unpinned.description.as_deref(), Some("v2"));
+        assert!(unpinned.pending_raw_json.is_none());
+    }
+}
@@ -0,0 +1,171 @@
+//! PostgreSQL implementation of ServerGroupRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{ServerGroup, ServerGroupRepository};
+use tokio_postgres::Row;
+
+use super::{is_unique_violation, parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of [`ServerGroupRepository`].
+pub struct PgServerGroupRepository {
+    db: PgDatabase,
+}
+
+impl PgServerGroupRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "id, space_id, name, icon, server_ids, feature_set_id, created_at, updated_at";
+
+    fn map_row(row: &Row) -> Result<ServerGroup, tokio_postgres::Error> {
+        let server_ids: String = row.try_get(4)?;
+        Ok(ServerGroup {
+            id: row.try_get(0)?,
+            space_id: row.try_get(1)?,
+            name: row.try_get(2)?,
+            icon: row.try_get(3)?,
+            server_ids: serde_json::from_str(&server_ids).unwrap_or_default(),
+            feature_set_id: row.try_get(5)?,
+            created_at: parse_datetime(&row.try_get::<_, String>(6)?),
+            updated_at: parse_datetime(&row.try_get::<_, String>(7)?),
+        })
+    }
+
+    /// Turn the unique (space_id, name) collision into a readable message.
+    fn map_write_error(e: tokio_postgres::Error, name: &str) -> anyhow::Error {
+        if is_unique_violation(&e) {
+            anyhow::anyhow!("A server group named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+}
+
+#[async_trait]
+impl ServerGroupRepository for PgServerGroupRepository {
+    async fn list_for_space(&self, space_id: &str) -> Result<Vec<ServerGroup>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM server_groups WHERE space_id = $1 ORDER BY lower(name) ASC",
+                    Self::COLUMNS
+                ),
+                &[&space_id],
+            )
+            .await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ServerGroup>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt(
+                &format!("SELECT {} FROM server_groups WHERE id = $1", Self::COLUMNS),
+                &[&id],
+            )
+            .await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn create(&self, group: &ServerGroup) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO server_groups
+                 (id, space_id, name, icon, server_ids, feature_set_id, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    &group.id,
+                    &group.space_id,
+                    &group.name,
+                    &group.icon,
+                    &serde_json::to_string(&group.server_ids)?,
+                    &group.feature_set_id,
+                    &group.created_at.to_rfc3339(),
+                    &group.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &group.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, group: &ServerGroup) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "UPDATE server_groups
+                 SET name = $2, icon = $3, server_ids = $4, feature_set_id = $5, updated_at = $6
+                 WHERE id = $1",
+                &[
+                    &group.id,
+                    &group.name,
+                    &group.icon,
+                    &serde_json::to_string(&group.server_ids)?,
+                    &group.feature_set_id,
+                    &group.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &group.name))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM server_groups WHERE id = $1", &[&id])
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_crud_and_unique_names() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgServerGroupRepository::new(db);
+
+        let mut group = ServerGroup::new("AWS", DEFAULT_SPACE_ID).with_icon("☁️");
+        repo.create(&group).await.unwrap();
+        group.set_servers(["aws-docs".to_string(), "aws-cdk".to_string()]);
+        repo.update(&group).await.unwrap();
+        let found = repo.get(&group.id).await.unwrap().unwrap();
+        assert_eq!(found.server_ids, vec!["aws-docs", "aws-cdk"]);
+
+        let err = repo
+            .create(&ServerGroup::new("aws", DEFAULT_SPACE_ID))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        repo.create(&ServerGroup::new("personal", DEFAULT_SPACE_ID))
+            .await
+            .unwrap();
+        let names: Vec<String> = repo
+            .list_for_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+        assert_eq!(names, vec!["AWS", "personal"]);
+
+        repo.delete(&group.id).await.unwrap();
+        assert!(repo.get(&group.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,141 @@
+//! PostgreSQL implementation of SpaceBaseDirRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use mcpmux_core::{path_is_within, SpaceBaseDir, SpaceBaseDirRepository};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::{is_unique_violation, parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of [`SpaceBaseDirRepository`].
+pub struct PgSpaceBaseDirRepository {
+    db: PgDatabase,
+}
+
+impl PgSpaceBaseDirRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, space_id, path, created_at";
+
+    fn map_row(row: &Row) -> Result<SpaceBaseDir, tokio_postgres::Error> {
+        Ok(SpaceBaseDir {
+            id: row.try_get(0)?,
+            space_id: row.try_get(1)?,
+            path: row.try_get(2)?,
+            created_at: parse_datetime(&row.try_get::<_, String>(3)?),
+        })
+    }
+}
+
+#[async_trait]
+impl SpaceBaseDirRepository for PgSpaceBaseDirRepository {
+    async fn list_all(&self) -> Result<Vec<SpaceBaseDir>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM space_base_dirs ORDER BY path ASC",
+                    Self::COLUMNS
+                ),
+                &[],
+            )
+            .await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn list_by_space(&self, space_id: &Uuid) -> Result<Vec<SpaceBaseDir>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM space_base_dirs WHERE space_id = $1 ORDER BY path ASC",
+                    Self::COLUMNS
+                ),
+                &[&space_id.to_string()],
+            )
+            .await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn add(&self, space_id: &Uuid, path: &str) -> Result<SpaceBaseDir> {
+        if path.trim().is_empty() {
+            anyhow::bail!("Base directory path is empty");
+        }
+        let row = SpaceBaseDir {
+            id: Uuid::new_v4().to_string(),
+            space_id: space_id.to_string(),
+            path: path.to_string(),
+            created_at: Utc::now(),
+        };
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO space_base_dirs (id, space_id, path, created_at) VALUES ($1, $2, $3, $4)",
+                &[&row.id, &row.space_id, &row.path, &row.created_at.to_rfc3339()],
+            )
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    anyhow::anyhow!("That folder is already a base directory of a space: {path}")
+                } else {
+                    anyhow::Error::from(e)
+                }
+            })?;
+        Ok(row)
+    }
+
+    async fn remove(&self, id: &str) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM space_base_dirs WHERE id = $1", &[&id])
+            .await?;
+        Ok(())
+    }
+
+    async fn find_space_for_root(&self, root: &str) -> Result<Option<Uuid>> {
+        if root.trim().is_empty() {
+            return Ok(None);
+        }
+        // Longest prefix match in Rust, like the SQLite repository
+        let all = self.list_all().await?;
+        let best = all
+            .iter()
+            .filter(|bd| path_is_within(root, &bd.path))
+            .max_by_key(|bd| bd.path.len());
+        Ok(best.and_then(|bd| bd.space_id.parse::<Uuid>().ok()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_longest_match_and_unique_path() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgSpaceBaseDirRepository::new(db);
+        let space: Uuid = "00000000-0000-0000-0000-000000000001".parse().unwrap();
+
+        repo.add(&space, "/work").await.unwrap();
+        let nested = repo.add(&space, "/work/client").await.unwrap();
+        let err = repo.add(&space, "/work").await.unwrap_err();
+        assert!(err.to_string().contains("already a base directory"));
+
+        assert_eq!(
+            repo.find_space_for_root("/work/client/app").await.unwrap(),
+            Some(space)
+        );
+        assert_eq!(repo.find_space_for_root("/home").await.unwrap(), None);
+
+        repo.remove(&nested.id).await.unwrap();
+        assert_eq!(repo.list_by_space(&space).await.unwrap().len(), 1);
+    }
+}
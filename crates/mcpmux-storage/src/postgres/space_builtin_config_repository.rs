@@ -0,0 +1,91 @@
+//! PostgreSQL implementation of [`SpaceBuiltinConfigRepository`].
+//!
+//! Stores only deviations from the default, like the SQLite repository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::SpaceBuiltinConfigRepository;
+
+use super::{now, PgDatabase};
+
+pub struct PgSpaceBuiltinConfigRepository {
+    db: PgDatabase,
+}
+
+impl PgSpaceBuiltinConfigRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SpaceBuiltinConfigRepository for PgSpaceBuiltinConfigRepository {
+    async fn server_enabled_override(
+        &self,
+        space_id: &str,
+        server_id: &str,
+    ) -> Result<Option<bool>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt(
+                "SELECT enabled FROM space_builtin_servers WHERE space_id = $1 AND server_id = $2",
+                &[&space_id, &server_id],
+            )
+            .await?;
+        Ok(row.map(|row| row.try_get(0)).transpose()?)
+    }
+
+    async fn disabled_tools(&self, space_id: &str, server_id: &str) -> Result<Vec<String>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                "SELECT tool_name FROM space_builtin_tools \
+                 WHERE space_id = $1 AND server_id = $2 AND NOT enabled",
+                &[&space_id, &server_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| row.try_get(0))
+            .collect::<Result<_, _>>()?)
+    }
+
+    async fn set_server_enabled(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        enabled: bool,
+    ) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO space_builtin_servers (space_id, server_id, enabled, updated_at) \
+                 VALUES ($1, $2, $3, $4) \
+                 ON CONFLICT (space_id, server_id) \
+                 DO UPDATE SET enabled = excluded.enabled, updated_at = excluded.updated_at",
+                &[&space_id, &server_id, &enabled, &now()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_tool_enabled(
+        &self,
+        space_id: &str,
+        server_id: &str,
+        tool_name: &str,
+        enabled: bool,
+    ) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO space_builtin_tools (space_id, server_id, tool_name, enabled, updated_at) \
+                 VALUES ($1, $2, $3, $4, $5) \
+                 ON CONFLICT (space_id, server_id, tool_name) \
+                 DO UPDATE SET enabled = excluded.enabled, updated_at = excluded.updated_at",
+                &[&space_id, &server_id, &tool_name, &enabled, &now()],
+            )
+            .await?;
+        Ok(())
+    }
+}
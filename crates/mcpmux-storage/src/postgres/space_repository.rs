@@ -0,0 +1,236 @@
+//! PostgreSQL implementation of SpaceRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{Space, SpaceRepository};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::{parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of SpaceRepository.
+pub struct PgSpaceRepository {
+    db: PgDatabase,
+}
+
+impl PgSpaceRepository {
+    /// Create a new PostgreSQL space repository.
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every `Space` read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "id, name, icon, description, is_default, sort_order, created_at, updated_at, owner_user_id";
+
+    fn map_row(row: &Row) -> Result<Space, tokio_postgres::Error> {
+        let id_str: String = row.try_get(0)?;
+        let mut space = Space::new(row.try_get::<_, String>(1)?);
+        space.id = id_str.parse().unwrap_or_else(|_| Uuid::new_v4());
+        space.icon = row.try_get(2)?;
+        space.description = row.try_get(3)?;
+        space.is_default = row.try_get(4)?;
+        space.sort_order = row.try_get(5)?;
+        space.owner_id = row
+            .try_get::<_, Option<String>>(8)?
+            .and_then(|id| id.parse().ok());
+        space.created_at = parse_datetime(&row.try_get::<_, String>(6)?);
+        space.updated_at = parse_datetime(&row.try_get::<_, String>(7)?);
+        Ok(space)
+    }
+}
+
+#[async_trait]
+impl SpaceRepository for PgSpaceRepository {
+    async fn list(&self) -> Result<Vec<Space>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM spaces ORDER BY sort_order ASC, name ASC",
+            Self::COLUMNS
+        );
+        let rows = client.query(&sql, &[]).await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Space>> {
+        let client = self.db.client().await?;
+        let sql = format!("SELECT {} FROM spaces WHERE id = $1", Self::COLUMNS);
+        let row = client.query_opt(&sql, &[&id.to_string()]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn create(&self, space: &Space) -> Result<()> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        let space_id = space.id.to_string();
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+        tx.execute(
+            "INSERT INTO spaces (id, name, icon, description, is_default, sort_order, created_at, updated_at, owner_user_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            &[
+                &space_id,
+                &space.name,
+                &space.icon,
+                &space.description,
+                &space.is_default,
+                &space.sort_order,
+                &space.created_at.to_rfc3339(),
+                &space.updated_at.to_rfc3339(),
+                &space.owner_id.map(|id| id.to_string()),
+            ],
+        )
+        .await?;
+
+        // Seed the builtin "Starter" FeatureSet, as the SQLite repository does
+        tx.execute(
+            "INSERT INTO feature_sets (id, name, description, icon, space_id, feature_set_type, is_builtin, created_at, updated_at)
+             VALUES ($1, 'Starter', 'Auto-created with this Space — the default set for folders you haven''t explicitly mapped. Edit which tools it includes to change what they get. Its name is fixed and it can''t be deleted.', '⭐', $2, 'starter', TRUE, $3, $3)
+             ON CONFLICT (id) DO NOTHING",
+            &[&format!("fs_default_{}", space_id), &space_id, &now],
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update(&self, space: &Space) -> Result<()> {
+        let client = self.db.client().await?;
+        let rows_affected = client
+            .execute(
+                "UPDATE spaces
+                 SET name = $2, icon = $3, description = $4, is_default = $5, sort_order = $6, updated_at = $7
+                 WHERE id = $1",
+                &[
+                    &space.id.to_string(),
+                    &space.name,
+                    &space.icon,
+                    &space.description,
+                    &space.is_default,
+                    &space.sort_order,
+                    &space.updated_at.to_rfc3339(),
+                ],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("Space not found: {}", space.id);
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute("DELETE FROM spaces WHERE id = $1", &[&id.to_string()])
+            .await?;
+        Ok(())
+    }
+
+    async fn get_default(&self) -> Result<Option<Space>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM spaces WHERE is_default LIMIT 1",
+            Self::COLUMNS
+        );
+        let row = client.query_opt(&sql, &[]).await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+
+    async fn set_default(&self, id: &Uuid) -> Result<()> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        tx.execute("UPDATE spaces SET is_default = FALSE", &[])
+            .await?;
+        let rows_affected = tx
+            .execute(
+                "UPDATE spaces SET is_default = TRUE WHERE id = $1",
+                &[&id.to_string()],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("Space not found: {}", id);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn set_owner(&self, id: &Uuid, owner_id: Option<&Uuid>) -> Result<()> {
+        let client = self.db.client().await?;
+        let rows_affected = client
+            .execute(
+                "UPDATE spaces SET owner_user_id = $2 WHERE id = $1",
+                &[&id.to_string(), &owner_id.map(|id| id.to_string())],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("Space not found: {}", id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_database;
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_crud_operations() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgSpaceRepository::new(db);
+
+        let initial = repo.list().await.unwrap();
+        assert_eq!(initial.len(), 1);
+        assert_eq!(initial[0].name, "My Space");
+
+        let space = Space::new("Test Space").with_icon("🧪");
+        repo.create(&space).await.unwrap();
+        assert_eq!(
+            repo.get(&space.id).await.unwrap().unwrap().name,
+            "Test Space"
+        );
+        assert_eq!(repo.list().await.unwrap().len(), 2);
+
+        let mut updated = space.clone();
+        updated.name = "Updated Space".to_string();
+        repo.update(&updated).await.unwrap();
+        assert_eq!(
+            repo.get(&space.id).await.unwrap().unwrap().name,
+            "Updated Space"
+        );
+
+        repo.delete(&space.id).await.unwrap();
+        assert!(repo.get(&space.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_default_space() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgSpaceRepository::new(db);
+        assert_eq!(repo.get_default().await.unwrap().unwrap().name, "My Space");
+
+        let space2 = Space::new("Space 2");
+        repo.create(&space2).await.unwrap();
+        repo.set_default(&space2.id).await.unwrap();
+        assert_eq!(repo.get_default().await.unwrap().unwrap().name, "Space 2");
+
+        let default_uuid = Uuid::parse_str(DEFAULT_SPACE_ID).unwrap();
+        repo.set_default(&default_uuid).await.unwrap();
+        assert_eq!(repo.get_default().await.unwrap().unwrap().name, "My Space");
+
+        assert!(repo.set_default(&Uuid::new_v4()).await.is_err());
+        assert_eq!(repo.get_default().await.unwrap().unwrap().name, "My Space");
+    }
+}
@@ -0,0 +1,193 @@
+//! PostgreSQL implementation of UserRepository.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use mcpmux_core::{User, UserRepository, UserRole};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use super::{is_unique_violation, parse_datetime, PgDatabase};
+
+/// PostgreSQL-backed implementation of [`UserRepository`].
+pub struct PgUserRepository {
+    db: PgDatabase,
+}
+
+impl PgUserRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, name, role, created_at, updated_at";
+
+    fn map_row(row: &Row) -> Result<User, tokio_postgres::Error> {
+        let id: String = row.try_get(0)?;
+        Ok(User {
+            id: id.parse().unwrap_or_else(|_| Uuid::new_v4()),
+            name: row.try_get(1)?,
+            role: UserRole::parse(&row.try_get::<_, String>(2)?).unwrap_or_default(),
+            created_at: parse_datetime(&row.try_get::<_, String>(3)?),
+            updated_at: parse_datetime(&row.try_get::<_, String>(4)?),
+        })
+    }
+
+    /// Turn the unique name collision into a readable message.
+    fn map_write_error(e: tokio_postgres::Error, name: &str) -> anyhow::Error {
+        if is_unique_violation(&e) {
+            anyhow::anyhow!("A user named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+
+    async fn query_one(&self, filter: &str, value: &str) -> Result<Option<User>> {
+        let client = self.db.client().await?;
+        let row = client
+            .query_opt(
+                &format!("SELECT {} FROM users WHERE {}", Self::COLUMNS, filter),
+                &[&value],
+            )
+            .await?;
+        Ok(row.as_ref().map(Self::map_row).transpose()?)
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn list(&self) -> Result<Vec<User>> {
+        let client = self.db.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM users ORDER BY lower(name) ASC",
+                    Self::COLUMNS
+                ),
+                &[],
+            )
+            .await?;
+        Ok(rows.iter().map(Self::map_row).collect::<Result<_, _>>()?)
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<User>> {
+        self.query_one("id = $1", &id.to_string()).await
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<User>> {
+        self.query_one("lower(name) = lower($1)", name).await
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<Option<User>> {
+        self.query_one("token_hash = $1", token_hash).await
+    }
+
+    async fn create(&self, user: &User) -> Result<()> {
+        let client = self.db.client().await?;
+        client
+            .execute(
+                "INSERT INTO users (id, name, role, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5)",
+                &[
+                    &user.id.to_string(),
+                    &user.name,
+                    &user.role.as_str(),
+                    &user.created_at.to_rfc3339(),
+                    &user.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &user.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, user: &User) -> Result<()> {
+        let client = self.db.client().await?;
+        let rows_affected = client
+            .execute(
+                "UPDATE users SET name = $2, role = $3, updated_at = $4 WHERE id = $1",
+                &[
+                    &user.id.to_string(),
+                    &user.name,
+                    &user.role.as_str(),
+                    &user.updated_at.to_rfc3339(),
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_write_error(e, &user.name))?;
+        if rows_affected == 0 {
+            anyhow::bail!("User not found: {}", user.id);
+        }
+        Ok(())
+    }
+
+    async fn set_token_hash(&self, id: &Uuid, token_hash: Option<&str>) -> Result<()> {
+        let client = self.db.client().await?;
+        let rows_affected = client
+            .execute(
+                "UPDATE users SET token_hash = $2, updated_at = $3 WHERE id = $1",
+                &[&id.to_string(), &token_hash, &Utc::now().to_rfc3339()],
+            )
+            .await?;
+        if rows_affected == 0 {
+            anyhow::bail!("User not found: {}", id);
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let client = self.db.client().await?;
+        // ON DELETE SET NULL releases their Spaces and clients
+        client
+            .execute("DELETE FROM users WHERE id = $1", &[&id.to_string()])
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_database, PgSpaceRepository};
+    use super::*;
+    use mcpmux_core::{Space, SpaceRepository};
+
+    #[tokio::test]
+    async fn test_crud_tokens_and_ownership() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let users = PgUserRepository::new(db.clone());
+        let spaces = PgSpaceRepository::new(db);
+
+        let mut alice = User::new("alice", UserRole::Member);
+        users.create(&alice).await.unwrap();
+        let err = users
+            .create(&User::new("Alice", UserRole::Admin))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        users
+            .set_token_hash(&alice.id, Some("hash_a"))
+            .await
+            .unwrap();
+        let found = users.get_by_token_hash("hash_a").await.unwrap().unwrap();
+        assert_eq!(found.id, alice.id);
+        assert!(users.get_by_token_hash("hash_b").await.unwrap().is_none());
+
+        alice.role = UserRole::Admin;
+        users.update(&alice).await.unwrap();
+        assert!(users
+            .get_by_name("ALICE")
+            .await
+            .unwrap()
+            .unwrap()
+            .is_admin());
+
+        let space = Space::new("Alice's").with_owner(alice.id);
+        spaces.create(&space).await.unwrap();
+        users.delete(&alice.id).await.unwrap();
+        assert!(users.get(&alice.id).await.unwrap().is_none());
+        assert_eq!(spaces.get(&space.id).await.unwrap().unwrap().owner_id, None);
+    }
+}
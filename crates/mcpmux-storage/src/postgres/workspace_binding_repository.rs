@@ -0,0 +1,333 @@
+//! PostgreSQL implementation of [`WorkspaceBindingRepository`].
+//!
+//! Same tables and lookup rules as the SQLite repository: a binding's
+//! FeatureSets come from the `workspace_binding_feature_sets` junction in
+//! `sort_order`, and roots match exactly, never by ancestor.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcpmux_core::{BindingType, WorkspaceBinding, WorkspaceBindingRepository};
+use tokio_postgres::{GenericClient, Row};
+use uuid::Uuid;
+
+use super::{parse_datetime, PgDatabase};
+
+pub struct PgWorkspaceBindingRepository {
+    db: PgDatabase,
+}
+
+impl PgWorkspaceBindingRepository {
+    pub fn new(db: PgDatabase) -> Self {
+        Self { db }
+    }
+
+    const SELECT_COLS: &'static str =
+        "id, workspace_root, space_id, created_at, updated_at, binding_type";
+
+    /// Map a `workspace_bindings` row; `feature_set_ids` is filled by the
+    /// caller from the junction.
+    fn map_row(row: &Row) -> Result<WorkspaceBinding, tokio_postgres::Error> {
+        let space_id = row
+            .try_get::<_, &str>(2)?
+            .parse()
+            .unwrap_or_else(|_| Uuid::nil());
+        let mut binding =
+            WorkspaceBinding::new_multi(row.try_get::<_, String>(1)?, space_id, Vec::new());
+        binding.id = row
+            .try_get::<_, &str>(0)?
+            .parse()
+            .unwrap_or_else(|_| Uuid::new_v4());
+        binding.binding_type = BindingType::parse(row.try_get(5)?);
+        binding.created_at = parse_datetime(row.try_get(3)?);
+        binding.updated_at = parse_datetime(row.try_get(4)?);
+        Ok(binding)
+    }
+
+    /// Replace the junction rows for `binding_id`, keeping the slice order
+    /// as `sort_order`.
+    async fn rewrite_fs_for_binding(
+        client: &impl GenericClient,
+        binding_id: &str,
+        feature_set_ids: &[String],
+    ) -> Result<()> {
+        client
+            .execute(
+                "DELETE FROM workspace_binding_feature_sets WHERE binding_id = $1",
+                &[&binding_id],
+            )
+            .await?;
+        for (idx, fs_id) in feature_set_ids.iter().enumerate() {
+            client
+                .execute(
+                    "INSERT INTO workspace_binding_feature_sets
+                        (binding_id, feature_set_id, sort_order)
+                     VALUES ($1, $2, $3)",
+                    &[&binding_id, fs_id, &(idx as i32)],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Fetch bindings matching `where_clause` (`""` for all) with their
+    /// FeatureSet lists, skipping soft-deleted FeatureSets.
+    async fn fetch_bindings(
+        &self,
+        where_clause: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> Result<Vec<WorkspaceBinding>> {
+        let client = self.db.client().await?;
+        let sql = format!(
+            "SELECT {} FROM workspace_bindings {} ORDER BY workspace_root",
+            Self::SELECT_COLS,
+            where_clause,
+        );
+        let mut bindings = client
+            .query(&sql, params)
+            .await?
+            .iter()
+            .map(Self::map_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        if bindings.is_empty() {
+            return Ok(bindings);
+        }
+
+        let ids: Vec<String> = bindings.iter().map(|b| b.id.to_string()).collect();
+        let rows = client
+            .query(
+                "SELECT wbfs.binding_id, wbfs.feature_set_id
+                   FROM workspace_binding_feature_sets wbfs
+                   JOIN feature_sets fs ON fs.id = wbfs.feature_set_id
+                  WHERE wbfs.binding_id = ANY($1)
+                    AND NOT fs.is_deleted
+                  ORDER BY wbfs.binding_id, wbfs.sort_order, wbfs.feature_set_id",
+                &[&ids],
+            )
+            .await?;
+        let mut fs_map: HashMap<String, Vec<String>> = HashMap::new();
+        for row in &rows {
+            fs_map
+                .entry(row.try_get(0)?)
+                .or_default()
+                .push(row.try_get(1)?);
+        }
+        for binding in &mut bindings {
+            if let Some(fs_ids) = fs_map.remove(&binding.id.to_string()) {
+                binding.feature_set_ids = fs_ids;
+            }
+        }
+        Ok(bindings)
+    }
+}
+
+#[async_trait]
+impl WorkspaceBindingRepository for PgWorkspaceBindingRepository {
+    async fn list(&self) -> Result<Vec<WorkspaceBinding>> {
+        self.fetch_bindings("", &[]).await
+    }
+
+    async fn list_for_space(&self, space_id: &Uuid) -> Result<Vec<WorkspaceBinding>> {
+        self.fetch_bindings("WHERE space_id = $1", &[&space_id.to_string()])
+            .await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<WorkspaceBinding>> {
+        let mut bindings = self
+            .fetch_bindings("WHERE id = $1", &[&id.to_string()])
+            .await?;
+        Ok(bindings.pop())
+    }
+
+    async fn create(&self, binding: &WorkspaceBinding) -> Result<()> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        tx.execute(
+            "INSERT INTO workspace_bindings
+                (id, workspace_root, space_id, created_at, updated_at, binding_type)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            &[
+                &binding.id.to_string(),
+                &binding.workspace_root,
+                &binding.space_id.to_string(),
+                &binding.created_at.to_rfc3339(),
+                &binding.updated_at.to_rfc3339(),
+                &binding.binding_type.as_str(),
+            ],
+        )
+        .await?;
+        Self::rewrite_fs_for_binding(&*tx, &binding.id.to_string(), &binding.feature_set_ids)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update(&self, binding: &WorkspaceBinding) -> Result<()> {
+        let mut client = self.db.client().await?;
+        let tx = client.transaction().await?;
+        let rows_affected = tx
+            .execute(
+                "UPDATE workspace_bindings
+                 SET workspace_root = $2, space_id = $3, updated_at = $4, binding_type = $5
+                 WHERE id = $1",
+                &[
+                    &binding.id.to_string(),
+                    &binding.workspace_root,
+                    &binding.space_id.to_string(),
+                    &binding.updated_at.to_rfc3339(),
+                    &binding.binding_type.as_str(),
+                ],
+            )
+            .await?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("WorkspaceBinding not found: {}", binding.id);
+        }
+
+        Self::rewrite_fs_for_binding(&*tx, &binding.id.to_string(), &binding.feature_set_ids)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let client = self.db.client().await?;
+        // Junction rows go away via ON DELETE CASCADE.
+        client
+            .execute(
+                "DELETE FROM workspace_bindings WHERE id = $1",
+                &[&id.to_string()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn find_exact_for_roots(
+        &self,
+        candidate_roots: &[String],
+    ) -> Result<Option<WorkspaceBinding>> {
+        if candidate_roots.is_empty() {
+            return Ok(None);
+        }
+
+        // Candidates are tried in order, so the first root that matches wins
+        let bindings = self.list().await?;
+        for root in candidate_roots {
+            if let Some(b) = bindings
+                .iter()
+                .find(|b| b.binding_type == BindingType::Path && &b.workspace_root == root)
+            {
+                return Ok(Some(b.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn find_by_id_key(&self, key: &str) -> Result<Option<WorkspaceBinding>> {
+        if key.is_empty() {
+            return Ok(None);
+        }
+        let bindings = self.list().await?;
+        Ok(bindings
+            .into_iter()
+            .find(|b| b.binding_type == BindingType::Id && b.workspace_root == key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_database, PgFeatureSetRepository};
+    use super::*;
+    use mcpmux_core::{FeatureSet, FeatureSetRepository};
+
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_bindings_round_trip() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgWorkspaceBindingRepository::new(db.clone());
+        let feature_sets = PgFeatureSetRepository::new(db);
+        let space_id = Uuid::parse_str(DEFAULT_SPACE_ID).unwrap();
+
+        let fs1 = FeatureSet::new_custom("first", DEFAULT_SPACE_ID);
+        let fs2 = FeatureSet::new_custom("second", DEFAULT_SPACE_ID);
+        feature_sets.create(&fs1).await.unwrap();
+        feature_sets.create(&fs2).await.unwrap();
+
+        let binding =
+            WorkspaceBinding::new_multi("/multi", space_id, vec![fs1.id.clone(), fs2.id.clone()]);
+        repo.create(&binding).await.unwrap();
+        let got = repo.get(&binding.id).await.unwrap().unwrap();
+        assert_eq!(got.feature_set_ids, vec![fs1.id.clone(), fs2.id.clone()]);
+
+        // Deleting a FeatureSet drops it from the binding
+        feature_sets.delete(&fs1.id).await.unwrap();
+        let got = repo.get(&binding.id).await.unwrap().unwrap();
+        assert_eq!(got.feature_set_ids, vec![fs2.id.clone()]);
+
+        let mut updated = got;
+        updated.feature_set_ids = vec![];
+        repo.update(&updated).await.unwrap();
+        assert!(repo
+            .get(&binding.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .feature_set_ids
+            .is_empty());
+
+        assert_eq!(repo.list_for_space(&space_id).await.unwrap().len(), 1);
+        assert!(repo
+            .list_for_space(&Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty());
+
+        repo.delete(&binding.id).await.unwrap();
+        assert!(repo.get(&binding.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_path_and_id_bindings_match_exactly() {
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let repo = PgWorkspaceBindingRepository::new(db);
+        let space_id = Uuid::parse_str(DEFAULT_SPACE_ID).unwrap();
+
+        repo.create(&WorkspaceBinding::new_multi("/work", space_id, vec![]))
+            .await
+            .unwrap();
+        repo.create(&WorkspaceBinding::new_id("mcp_abc12345", space_id, vec![]))
+            .await
+            .unwrap();
+
+        assert!(repo
+            .find_exact_for_roots(&["/work/proj".to_string()])
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .find_exact_for_roots(&["/work".to_string()])
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo
+            .find_exact_for_roots(&["mcp_abc12345".to_string()])
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            repo.find_by_id_key("mcp_abc12345")
+                .await
+                .unwrap()
+                .unwrap()
+                .binding_type,
+            BindingType::Id
+        );
+        assert!(repo.find_by_id_key("/work").await.unwrap().is_none());
+    }
+}
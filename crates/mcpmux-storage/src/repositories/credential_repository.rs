@@ -508,4 +508,41 @@ mod tests {
         assert_eq!(cred_type, "api_key");
         assert!(expires_at.is_none()); // API keys don't expire
     }
+
+    #[tokio::test]
+    async fn test_credentials_outside_a_space() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let key = crate::crypto::generate_master_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let repo = SqliteCredentialRepository::new(db.clone(), encryptor);
+
+        // Registry credentials use the nil space, which has no spaces row
+        repo.save(&Credential::api_key(Uuid::nil(), "registry:acme", "tok"))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_all(&Uuid::nil(), "registry:acme")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Deleting a real space still removes its credentials
+        let space_id = Uuid::new_v4();
+        create_test_space(&db, &space_id).await;
+        repo.save(&Credential::api_key(space_id, "github", "ghp"))
+            .await
+            .unwrap();
+        db.lock()
+            .await
+            .connection()
+            .execute(
+                "DELETE FROM spaces WHERE id = ?",
+                params![space_id.to_string()],
+            )
+            .unwrap();
+        assert!(repo.list_for_space(&space_id).await.unwrap().is_empty());
+        assert_eq!(repo.list_for_space(&Uuid::nil()).await.unwrap().len(), 1);
+    }
 }
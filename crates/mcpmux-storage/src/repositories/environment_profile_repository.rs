@@ -0,0 +1,289 @@
+//! SQLite implementation of EnvironmentProfileRepository.
+//!
+//! A profile's input values and per-server overrides are stored together in
+//! the `overrides` column, encrypted with the [`FieldEncryptor`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mcpmux_core::{EnvironmentProfile, EnvironmentProfileRepository, ProfileServerOverrides};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{crypto::FieldEncryptor, Database};
+
+/// The encrypted part of a profile
+#[derive(Default, Serialize, Deserialize)]
+struct StoredOverrides {
+    #[serde(default)]
+    inputs: HashMap<String, String>,
+    #[serde(default)]
+    servers: HashMap<String, ProfileServerOverrides>,
+}
+
+/// Decrypt stored overrides into `profile`. Unlike a missing value, a value
+/// that can't be decrypted is an error: connecting with the profile's
+/// settings silently dropped would hit the wrong environment.
+pub(crate) fn decrypt_profile(
+    encryptor: &FieldEncryptor,
+    mut profile: EnvironmentProfile,
+    overrides: Option<String>,
+) -> Result<EnvironmentProfile> {
+    let Some(data) = overrides.filter(|d| !d.trim().is_empty()) else {
+        return Ok(profile);
+    };
+    let json = encryptor.decrypt(&data).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to decrypt environment profile \"{}\": {}",
+            profile.name,
+            e
+        )
+    })?;
+    let stored: StoredOverrides = serde_json::from_str(&json)?;
+    profile.inputs = stored.inputs;
+    profile.servers = stored.servers;
+    Ok(profile)
+}
+
+/// Encrypt a profile's input values and per-server overrides for storage.
+pub(crate) fn encrypt_profile(
+    encryptor: &FieldEncryptor,
+    profile: &EnvironmentProfile,
+) -> Result<String> {
+    let json = serde_json::to_string(&StoredOverrides {
+        inputs: profile.inputs.clone(),
+        servers: profile.servers.clone(),
+    })?;
+    encryptor
+        .encrypt(&json)
+        .map_err(|e| anyhow::anyhow!("Failed to encrypt environment profile: {}", e))
+}
+
+/// A profile row before its overrides are decrypted
+struct ProfileRow {
+    profile: EnvironmentProfile,
+    overrides: Option<String>,
+}
+
+/// SQLite-backed implementation of [`EnvironmentProfileRepository`].
+pub struct SqliteEnvironmentProfileRepository {
+    db: Arc<Mutex<Database>>,
+    encryptor: Arc<FieldEncryptor>,
+}
+
+impl SqliteEnvironmentProfileRepository {
+    pub fn new(db: Arc<Mutex<Database>>, encryptor: Arc<FieldEncryptor>) -> Self {
+        Self { db, encryptor }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "id, space_id, name, overrides, is_active, created_at, updated_at";
+
+    fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ProfileRow> {
+        Ok(ProfileRow {
+            profile: EnvironmentProfile {
+                id: row.get(0)?,
+                space_id: row.get(1)?,
+                name: row.get(2)?,
+                inputs: HashMap::new(),
+                servers: HashMap::new(),
+                is_active: row.get::<_, i64>(4)? != 0,
+                created_at: Self::parse_datetime(&row.get::<_, String>(5)?),
+                updated_at: Self::parse_datetime(&row.get::<_, String>(6)?),
+            },
+            overrides: row.get(3)?,
+        })
+    }
+
+    /// Turn the UNIQUE(space_id, name) collision into a readable message.
+    fn map_write_error(e: rusqlite::Error, name: &str) -> anyhow::Error {
+        if e.to_string().to_lowercase().contains("unique") {
+            anyhow::anyhow!("An environment profile named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+
+    async fn query(&self, filter: &str, param: &str) -> Result<Vec<EnvironmentProfile>> {
+        let rows = {
+            let db = self.db.lock().await;
+            let conn = db.connection();
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM environment_profiles WHERE {} ORDER BY name COLLATE NOCASE ASC",
+                Self::COLUMNS,
+                filter
+            ))?;
+            let rows = stmt
+                .query_map(params![param], Self::map_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows
+        };
+        rows.into_iter()
+            .map(|row| decrypt_profile(&self.encryptor, row.profile, row.overrides))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EnvironmentProfileRepository for SqliteEnvironmentProfileRepository {
+    async fn list_for_space(&self, space_id: &str) -> Result<Vec<EnvironmentProfile>> {
+        self.query("space_id = ?", space_id).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<EnvironmentProfile>> {
+        let row = {
+            let db = self.db.lock().await;
+            let conn = db.connection();
+            conn.query_row(
+                &format!(
+                    "SELECT {} FROM environment_profiles WHERE id = ?",
+                    Self::COLUMNS
+                ),
+                params![id],
+                Self::map_row,
+            )
+            .optional()?
+        };
+        row.map(|row| decrypt_profile(&self.encryptor, row.profile, row.overrides))
+            .transpose()
+    }
+
+    async fn get_active(&self, space_id: &str) -> Result<Option<EnvironmentProfile>> {
+        Ok(self
+            .query("space_id = ? AND is_active = 1", space_id)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn create(&self, profile: &EnvironmentProfile) -> Result<()> {
+        let overrides = encrypt_profile(&self.encryptor, profile)?;
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO environment_profiles
+             (id, space_id, name, overrides, is_active, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6)",
+            params![
+                profile.id,
+                profile.space_id,
+                profile.name,
+                overrides,
+                profile.created_at.to_rfc3339(),
+                profile.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Self::map_write_error(e, &profile.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, profile: &EnvironmentProfile) -> Result<()> {
+        let overrides = encrypt_profile(&self.encryptor, profile)?;
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "UPDATE environment_profiles
+             SET name = ?2, overrides = ?3, updated_at = ?4
+             WHERE id = ?1",
+            params![
+                profile.id,
+                profile.name,
+                overrides,
+                profile.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Self::map_write_error(e, &profile.name))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute("DELETE FROM environment_profiles WHERE id = ?", params![id])?;
+        Ok(())
+    }
+
+    async fn set_active(&self, space_id: &str, profile_id: Option<&str>) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE environment_profiles SET is_active = 0 WHERE space_id = ?",
+            params![space_id],
+        )?;
+        if let Some(id) = profile_id {
+            let updated = tx.execute(
+                "UPDATE environment_profiles SET is_active = 1 WHERE id = ?1 AND space_id = ?2",
+                params![id, space_id],
+            )?;
+            if updated == 0 {
+                anyhow::bail!("Environment profile not found in this space");
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_overrides_are_encrypted_and_one_profile_is_active() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let key = crate::crypto::generate_master_key().unwrap();
+        let encryptor = Arc::new(FieldEncryptor::new(&key).unwrap());
+        let repo = SqliteEnvironmentProfileRepository::new(db.clone(), encryptor);
+
+        let mut staging = EnvironmentProfile::new("staging", DEFAULT_SPACE_ID);
+        staging
+            .inputs
+            .insert("API_KEY".to_string(), "sk-staging".to_string());
+        repo.create(&staging).await.unwrap();
+        let prod = EnvironmentProfile::new("prod", DEFAULT_SPACE_ID);
+        repo.create(&prod).await.unwrap();
+
+        let raw: String = db
+            .lock()
+            .await
+            .connection()
+            .query_row(
+                "SELECT overrides FROM environment_profiles WHERE id = ?",
+                params![staging.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!raw.contains("sk-staging"));
+
+        repo.set_active(DEFAULT_SPACE_ID, Some(&staging.id))
+            .await
+            .unwrap();
+        repo.set_active(DEFAULT_SPACE_ID, Some(&prod.id))
+            .await
+            .unwrap();
+        let active = repo.get_active(DEFAULT_SPACE_ID).await.unwrap().unwrap();
+        assert_eq!(active.id, prod.id);
+
+        let found = repo.get(&staging.id).await.unwrap().unwrap();
+        assert!(!found.is_active);
+        assert_eq!(found.inputs["API_KEY"], "sk-staging");
+
+        repo.set_active(DEFAULT_SPACE_ID, None).await.unwrap();
+        assert!(repo.get_active(DEFAULT_SPACE_ID).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! SQLite implementation of EventJournalRepository.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mcpmux_core::{DomainEvent, EventJournalRepository, JournaledEvent};
+use rusqlite::params;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::Database;
+
+/// SQLite-backed implementation of [`EventJournalRepository`].
+pub struct SqliteEventJournalRepository {
+    db: Arc<Mutex<Database>>,
+}
+
+impl SqliteEventJournalRepository {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self { db }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+}
+
+#[async_trait]
+impl EventJournalRepository for SqliteEventJournalRepository {
+    async fn append(&self, event: &DomainEvent) -> Result<u64> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO event_journal (event_type, space_id, payload, recorded_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                event.type_name(),
+                event.space_id().map(|id| id.to_string()),
+                serde_json::to_string(event)?,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }
+
+    async fn since(&self, sequence: u64, limit: usize) -> Result<Vec<JournaledEvent>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let mut stmt = conn.prepare(
+            "SELECT sequence, payload, recorded_at FROM event_journal
+             WHERE sequence > ?1 ORDER BY sequence ASC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![sequence as i64, limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Events of a kind a newer version wrote (or an older one dropped)
+        // don't parse; skip them rather than failing the whole read.
+        Ok(rows
+            .into_iter()
+            .filter_map(
+                |(sequence, payload, recorded_at)| match serde_json::from_str(&payload) {
+                    Ok(event) => Some(JournaledEvent {
+                        sequence: sequence as u64,
+                        recorded_at: Self::parse_datetime(&recorded_at),
+                        event,
+                    }),
+                    Err(e) => {
+                        debug!(sequence, "[EventJournal] Skipping unreadable event: {}", e);
+                        None
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn latest_sequence(&self) -> Result<u64> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        // Read the AUTOINCREMENT counter rather than MAX(sequence), so the
+        // answer survives every row being pruned
+        let latest: Option<i64> = conn
+            .query_row(
+                "SELECT seq FROM sqlite_sequence WHERE name = 'event_journal'",
+                [],
+                |row| row.get(0),
+            )
+            .ok();
+        Ok(latest.unwrap_or(0) as u64)
+    }
+
+    async fn prune(&self, keep: u64) -> Result<u64> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let deleted = conn.execute(
+            "DELETE FROM event_journal
+             WHERE sequence <= (SELECT MAX(sequence) FROM event_journal) - ?1",
+            params![keep as i64],
+        )?;
+        Ok(deleted as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_append_read_and_prune() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let repo = SqliteEventJournalRepository::new(db);
+        assert_eq!(repo.latest_sequence().await.unwrap(), 0);
+
+        let space_id = Uuid::new_v4();
+        repo.append(&DomainEvent::SpaceDeleted { space_id })
+            .await
+            .unwrap();
+        repo.append(&DomainEvent::SessionRootsChanged)
+            .await
+            .unwrap();
+        let last = repo.append(&DomainEvent::GatewayStopped).await.unwrap();
+        assert_eq!(last, 3);
+        assert_eq!(repo.latest_sequence().await.unwrap(), 3);
+
+        let events = repo.since(0, 10).await.unwrap();
+        assert_eq!(
+            events.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(events[0].event.space_id(), Some(space_id));
+        assert_eq!(repo.since(1, 1).await.unwrap()[0].sequence, 2);
+
+        assert_eq!(repo.prune(1).await.unwrap(), 2);
+        assert_eq!(repo.since(0, 10).await.unwrap().len(), 1);
+
+        // Sequence numbers are never reused, even once everything is pruned
+        repo.prune(0).await.unwrap();
+        assert_eq!(repo.latest_sequence().await.unwrap(), 3);
+        assert_eq!(repo.append(&DomainEvent::GatewayStopped).await.unwrap(), 4);
+    }
+}
@@ -13,17 +13,22 @@ use mcpmux_core::{
 use rusqlite::{params, OptionalExtension};
 use tokio::sync::Mutex;
 
+use crate::database::DbReader;
 use crate::Database;
 
 /// SQLite-backed implementation of FeatureSetRepository.
 pub struct SqliteFeatureSetRepository {
     db: Arc<Mutex<Database>>,
+    reader: DbReader,
 }
 
 impl SqliteFeatureSetRepository {
     /// Create a new SQLite feature set repository.
     pub fn new(db: Arc<Mutex<Database>>) -> Self {
-        Self { db }
+        Self {
+            reader: DbReader::new(db.clone()),
+            db,
+        }
     }
 
     /// Parse a datetime string to DateTime<Utc>.
@@ -41,52 +46,52 @@ impl SqliteFeatureSetRepository {
 
     /// Parse a row into a FeatureSet (without members).
     fn row_to_feature_set(row: &rusqlite::Row<'_>) -> rusqlite::Result<FeatureSet> {
-        Ok(FeatureSet {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            icon: row.get(3)?,
-            space_id: row.get(4)?,
-            feature_set_type: FeatureSetType::parse(&row.get::<_, String>(5)?)
-                .unwrap_or(FeatureSetType::Custom),
-            server_id: row.get(6)?,
-            is_builtin: row.get::<_, i32>(7)? == 1,
-            is_deleted: row.get::<_, i32>(8)? == 1,
-            created_at: Self::parse_datetime(&row.get::<_, String>(9)?),
-            updated_at: Self::parse_datetime(&row.get::<_, String>(10)?),
-            members: vec![], // Members loaded separately
-        })
+        // Members loaded separately
+        let mut feature_set = FeatureSet::new_custom(row.get::<_, String>(1)?, String::new());
+        feature_set.id = row.get(0)?;
+        feature_set.description = row.get(2)?;
+        feature_set.icon = row.get(3)?;
+        feature_set.space_id = row.get(4)?;
+        feature_set.feature_set_type =
+            FeatureSetType::parse(&row.get::<_, String>(5)?).unwrap_or(FeatureSetType::Custom);
+        feature_set.server_id = row.get(6)?;
+        feature_set.is_builtin = row.get::<_, i32>(7)? == 1;
+        feature_set.is_deleted = row.get::<_, i32>(8)? == 1;
+        feature_set.created_at = Self::parse_datetime(&row.get::<_, String>(9)?);
+        feature_set.updated_at = Self::parse_datetime(&row.get::<_, String>(10)?);
+        Ok(feature_set)
     }
 
     /// Parse a row into a FeatureSetMember.
     fn row_to_member(row: &rusqlite::Row<'_>) -> rusqlite::Result<FeatureSetMember> {
-        Ok(FeatureSetMember {
-            id: row.get(0)?,
-            feature_set_id: row.get(1)?,
-            member_type: MemberType::parse(&row.get::<_, String>(2)?)
-                .unwrap_or(MemberType::Feature),
-            member_id: row.get(3)?,
-            mode: MemberMode::parse(&row.get::<_, String>(4)?).unwrap_or(MemberMode::Include),
-        })
+        let mut member = FeatureSetMember::new(
+            row.get::<_, String>(1)?,
+            MemberType::parse(&row.get::<_, String>(2)?).unwrap_or(MemberType::Feature),
+            row.get::<_, String>(3)?,
+            MemberMode::parse(&row.get::<_, String>(4)?).unwrap_or(MemberMode::Include),
+        );
+        member.id = row.get(0)?;
+        Ok(member)
     }
 
     /// Load members for a feature set
     async fn load_members(&self, feature_set_id: &str) -> Result<Vec<FeatureSetMember>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, feature_set_id, member_type, member_id, mode
-             FROM feature_set_members
-             WHERE feature_set_id = ?
-             ORDER BY id",
-        )?;
-
-        let members = stmt
-            .query_map(params![feature_set_id], Self::row_to_member)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(members)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, feature_set_id, member_type, member_id, mode
+                     FROM feature_set_members
+                     WHERE feature_set_id = ?
+                     ORDER BY id",
+                )?;
+
+                let members = stmt
+                    .query_map(params![feature_set_id], Self::row_to_member)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(members)
+            })
+            .await
     }
 
     /// Load members for a feature set (synchronous version for use with locked connection)
@@ -112,64 +117,67 @@ impl SqliteFeatureSetRepository {
 #[async_trait]
 impl FeatureSetRepository for SqliteFeatureSetRepository {
     async fn list(&self) -> Result<Vec<FeatureSet>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, icon, space_id, feature_set_type, 
-                    server_id, is_builtin, is_deleted, created_at, updated_at 
-             FROM feature_sets 
-             WHERE is_deleted = 0
-             ORDER BY is_builtin DESC, name ASC",
-        )?;
-
-        let feature_sets = stmt
-            .query_map([], Self::row_to_feature_set)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(feature_sets)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, name, description, icon, space_id, feature_set_type, 
+                            server_id, is_builtin, is_deleted, created_at, updated_at 
+                     FROM feature_sets 
+                     WHERE is_deleted = 0
+                     ORDER BY is_builtin DESC, name ASC",
+                )?;
+
+                let feature_sets = stmt
+                    .query_map([], Self::row_to_feature_set)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(feature_sets)
+            })
+            .await
     }
 
     async fn list_by_space(&self, space_id: &str) -> Result<Vec<FeatureSet>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, icon, space_id, feature_set_type, 
-                    server_id, is_builtin, is_deleted, created_at, updated_at 
-             FROM feature_sets 
-             WHERE space_id = ? AND is_deleted = 0
-             ORDER BY is_builtin DESC, feature_set_type, name ASC",
-        )?;
-
-        let mut feature_sets = stmt
-            .query_map(params![space_id], Self::row_to_feature_set)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Load members for each feature set
-        for fs in &mut feature_sets {
-            fs.members = Self::get_members_sync(conn, &fs.id)?;
-        }
-
-        Ok(feature_sets)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, name, description, icon, space_id, feature_set_type, 
+                            server_id, is_builtin, is_deleted, created_at, updated_at 
+                     FROM feature_sets 
+                     WHERE space_id = ? AND is_deleted = 0
+                     ORDER BY is_builtin DESC, feature_set_type, name ASC",
+                )?;
+
+                let mut feature_sets = stmt
+                    .query_map(params![space_id], Self::row_to_feature_set)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // Load members for each feature set
+                for fs in &mut feature_sets {
+                    fs.members = Self::get_members_sync(conn, &fs.id)?;
+                }
+
+                Ok(feature_sets)
+            })
+            .await
     }
 
     async fn get(&self, id: &str) -> Result<Option<FeatureSet>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let result = conn
-            .query_row(
-                "SELECT id, name, description, icon, space_id, feature_set_type, 
-                        server_id, is_builtin, is_deleted, created_at, updated_at 
-                 FROM feature_sets 
-                 WHERE id = ? AND is_deleted = 0",
-                params![id],
-                Self::row_to_feature_set,
-            )
-            .optional()?;
-
-        Ok(result)
+        self.reader
+            .read(|conn| {
+                let result = conn
+                    .query_row(
+                        "SELECT id, name, description, icon, space_id, feature_set_type, 
+                                server_id, is_builtin, is_deleted, created_at, updated_at 
+                         FROM feature_sets 
+                         WHERE id = ? AND is_deleted = 0",
+                        params![id],
+                        Self::row_to_feature_set,
+                    )
+                    .optional()?;
+
+                Ok(result)
+            })
+            .await
     }
 
     async fn get_with_members(&self, id: &str) -> Result<Option<FeatureSet>> {
@@ -186,7 +194,9 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
         let db = self.db.lock().await;
         let conn = db.connection();
 
-        conn.execute(
+        // The set and its members land together or not at all
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
             "INSERT INTO feature_sets 
                 (id, name, description, icon, space_id, feature_set_type, 
                  server_id, is_builtin, is_deleted, created_at, updated_at)
@@ -209,7 +219,7 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
         // Insert members if any
         let now = chrono::Utc::now().to_rfc3339();
         for member in &feature_set.members {
-            conn.execute(
+            tx.execute(
                 "INSERT INTO feature_set_members (id, feature_set_id, member_type, member_id, mode, created_at)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
@@ -222,6 +232,7 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
                 ],
             )?;
         }
+        tx.commit()?;
 
         Ok(())
     }
@@ -238,7 +249,8 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
         // struct), so the lock holds for every caller, including the
         // member-set command that routes through update(). Custom sets update
         // normally.
-        let rows_affected = conn.execute(
+        let tx = conn.unchecked_transaction()?;
+        let rows_affected = tx.execute(
             "UPDATE feature_sets
              SET name = CASE WHEN is_builtin = 1 THEN name ELSE ?2 END,
                  description = CASE WHEN is_builtin = 1 THEN description ELSE ?3 END,
@@ -259,14 +271,14 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
         }
 
         // Update members: delete old, insert new
-        conn.execute(
+        tx.execute(
             "DELETE FROM feature_set_members WHERE feature_set_id = ?",
             params![feature_set.id],
         )?;
 
         let now = chrono::Utc::now().to_rfc3339();
         for member in &feature_set.members {
-            conn.execute(
+            tx.execute(
                 "INSERT INTO feature_set_members (id, feature_set_id, member_type, member_id, mode, created_at)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
@@ -279,6 +291,7 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
                 ],
             )?;
         }
+        tx.commit()?;
 
         Ok(())
     }
@@ -326,26 +339,27 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
     }
 
     async fn get_starter_for_space(&self, space_id: &str) -> Result<Option<FeatureSet>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        // Match on `'starter' OR 'default'` so a freshly-migrated DB and a
-        // pre-013 read both resolve correctly; migration 013 itself
-        // rewrites stored rows so the legacy alias is dead weight quickly.
-        let result = conn
-            .query_row(
-                "SELECT id, name, description, icon, space_id, feature_set_type,
-                        server_id, is_builtin, is_deleted, created_at, updated_at
-                 FROM feature_sets
-                 WHERE space_id = ?
-                   AND feature_set_type IN ('starter', 'default')
-                   AND is_deleted = 0",
-                params![space_id],
-                Self::row_to_feature_set,
-            )
-            .optional()?;
-
-        Ok(result)
+        self.reader
+            .read(|conn| {
+                // Match on `'starter' OR 'default'` so a freshly-migrated DB and a
+                // pre-013 read both resolve correctly; migration 013 itself
+                // rewrites stored rows so the legacy alias is dead weight quickly.
+                let result = conn
+                    .query_row(
+                        "SELECT id, name, description, icon, space_id, feature_set_type,
+                                server_id, is_builtin, is_deleted, created_at, updated_at
+                         FROM feature_sets
+                         WHERE space_id = ?
+                           AND feature_set_type IN ('starter', 'default')
+                           AND is_deleted = 0",
+                        params![space_id],
+                        Self::row_to_feature_set,
+                    )
+                    .optional()?;
+
+                Ok(result)
+            })
+            .await
     }
 
     async fn ensure_builtin_for_space(&self, space_id: &str) -> Result<()> {
@@ -366,13 +380,7 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
         let db = self.db.lock().await;
         let conn = db.connection();
 
-        let member = FeatureSetMember {
-            id: uuid::Uuid::new_v4().to_string(),
-            feature_set_id: feature_set_id.to_string(),
-            member_type: MemberType::Feature,
-            member_id: feature_id.to_string(),
-            mode,
-        };
+        let member = FeatureSetMember::new(feature_set_id, MemberType::Feature, feature_id, mode);
 
         conn.execute(
             "INSERT INTO feature_set_members (id, feature_set_id, member_type, member_id, mode, created_at)
@@ -406,21 +414,22 @@ impl FeatureSetRepository for SqliteFeatureSetRepository {
 
     /// Get all feature members (not feature_set members) of a feature set
     async fn get_feature_members(&self, feature_set_id: &str) -> Result<Vec<FeatureSetMember>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, feature_set_id, member_type, member_id, mode
-             FROM feature_set_members
-             WHERE feature_set_id = ?1 AND member_type = 'feature'
-             ORDER BY id",
-        )?;
-
-        let members = stmt
-            .query_map(params![feature_set_id], Self::row_to_member)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(members)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, feature_set_id, member_type, member_id, mode
+                     FROM feature_set_members
+                     WHERE feature_set_id = ?1 AND member_type = 'feature'
+                     ORDER BY id",
+                )?;
+
+                let members = stmt
+                    .query_map(params![feature_set_id], Self::row_to_member)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(members)
+            })
+            .await
     }
 }
 
@@ -11,13 +11,16 @@
 //! 3. Pre-registration - server pre-configures client_id
 
 use anyhow::Result;
-use rusqlite::params;
+use async_trait::async_trait;
+use rusqlite::{params, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, info};
 
+use crate::database::DbReader;
 use crate::Database;
 
 /// Client registration type (per MCP spec 2025-11-25)
@@ -107,6 +110,11 @@ pub struct InboundClient {
     /// that haven't opened a session yet — the UI hides the capability
     /// badge in that state instead of misleadingly showing "Rootless".
     pub roots_capability_known: bool,
+
+    /// `true` while the user has suspended this client. The gateway refuses
+    /// every MCP request from a suspended client with 403; its registration,
+    /// credentials and grants are kept so lifting the suspension restores it.
+    pub suspended: bool,
 }
 
 /// Authorization code (pending exchange)
@@ -130,14 +138,14 @@ pub enum TokenType {
 }
 
 impl TokenType {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             TokenType::Access => "access",
             TokenType::Refresh => "refresh",
         }
     }
 
-    fn parse(s: &str) -> Option<Self> {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
         match s {
             "access" => Some(TokenType::Access),
             "refresh" => Some(TokenType::Refresh),
@@ -181,15 +189,278 @@ pub struct ApiKeyAuth {
     pub client_id: String,
 }
 
-/// OAuth Repository with database persistence
-pub struct InboundClientRepository {
+/// SHA-256 hex of a token (we never store raw tokens)
+pub fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// SHA-256 hex of an API key — the only form ever persisted. Same algorithm
+/// as [`hash_token`]; named separately for intent.
+pub fn hash_api_key(key: &str) -> String {
+    hash_token(key)
+}
+
+/// Repository trait for inbound clients, their codes, tokens, API keys and
+/// grants
+#[async_trait]
+pub trait InboundClientRepository: Send + Sync {
+    // =========================================================================
+    // Client Operations (unified inbound_clients table)
+    // =========================================================================
+
+    /// Register or update an inbound client (supports CIMD, DCR, pre-registered)
+    async fn save_client(&self, client: &InboundClient) -> Result<()>;
+
+    /// Get a client by ID
+    async fn get_client(&self, client_id: &str) -> Result<Option<InboundClient>>;
+
+    /// Find client by name (for idempotent DCR)
+    ///
+    /// Allows a client to register with different redirect_uris
+    async fn find_client_by_name(&self, name: &str) -> Result<Option<InboundClient>>;
+
+    /// Strict byte-equal membership check of a redirect URI in the client's
+    /// registered list. This is a low-level DB lookup; for OAuth policy
+    /// decisions (including RFC 8252 §7.3 loopback-port flexibility) use
+    /// `mcpmux_gateway::oauth::is_redirect_uri_allowed` instead.
+    async fn validate_redirect_uri(&self, client_id: &str, redirect_uri: &str) -> Result<bool> {
+        if let Some(client) = self.get_client(client_id).await? {
+            Ok(client.redirect_uris.iter().any(|uri| uri == redirect_uri))
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// List all registered OAuth clients
+    async fn list_clients(&self) -> Result<Vec<InboundClient>>;
+
+    /// Clients owned by a user (see [`set_client_owner`](Self::set_client_owner))
+    async fn list_clients_for_owner(&self, user_id: &str) -> Result<Vec<InboundClient>>;
+
+    /// Update a client's last_seen timestamp
+    async fn update_client_last_seen(&self, client_id: &str) -> Result<()>;
+
+    /// Mark a client as approved by the user
+    ///
+    /// This is called when user explicitly approves the OAuth consent.
+    /// Only approved clients get silent re-authentication.
+    async fn approve_client(&self, client_id: &str) -> Result<()>;
+
+    /// Check if a client has been approved by the user
+    async fn is_client_approved(&self, client_id: &str) -> Result<bool>;
+
+    /// Suspend or reinstate a client. Returns `false` if no such client exists.
+    async fn set_client_suspended(&self, client_id: &str, suspended: bool) -> Result<bool>;
+
+    /// Check if a client is suspended
+    async fn is_client_suspended(&self, client_id: &str) -> Result<bool>;
+
+    /// Store a client's redirect URIs as given
+    async fn set_redirect_uris(&self, client_id: &str, uris: &[String]) -> Result<()>;
+
+    /// Merge new redirect URIs with existing ones for a client
+    /// Avoids duplicates and preserves existing URIs
+    async fn merge_redirect_uris(
+        &self,
+        client_id: &str,
+        new_uris: Vec<String>,
+    ) -> Result<Vec<String>> {
+        // Get existing client
+        let existing_client = self.get_client(client_id).await?;
+        let mut merged_uris = existing_client.map(|c| c.redirect_uris).unwrap_or_default();
+
+        // Add new URIs (avoid duplicates)
+        for uri in new_uris {
+            if !merged_uris.contains(&uri) {
+                merged_uris.push(uri);
+            }
+        }
+
+        self.set_redirect_uris(client_id, &merged_uris).await?;
+        debug!(
+            "[OAuth] Merged redirect_uris for client: {} -> {:?}",
+            client_id, merged_uris
+        );
+        Ok(merged_uris)
+    }
+
+    /// Update a client's human-facing alias.
+    async fn update_client_alias(
+        &self,
+        client_id: &str,
+        client_alias: Option<String>,
+    ) -> Result<Option<InboundClient>>;
+
+    /// Delete a client and all associated tokens
+    async fn delete_client(&self, client_id: &str) -> Result<bool>;
+
+    // =========================================================================
+    // Authorization Code Operations
+    // =========================================================================
+
+    /// Save an authorization code
+    async fn save_authorization_code(&self, code: &AuthorizationCode) -> Result<()>;
+
+    /// Get and consume an authorization code (one-time use)
+    async fn consume_authorization_code(&self, code: &str) -> Result<Option<AuthorizationCode>>;
+
+    /// Clean up expired authorization codes
+    async fn cleanup_expired_codes(&self) -> Result<usize>;
+
+    // =========================================================================
+    // Inbound client API keys (long-lived, host-issued bearer credentials)
+    // =========================================================================
+
+    /// Persist a freshly-generated API key for a client. The caller generates
+    /// the random `plaintext` (shown to the user once) and a unique `key_id`;
+    /// only the SHA-256 hash + a display prefix are stored.
+    async fn create_api_key(
+        &self,
+        key_id: &str,
+        client_id: &str,
+        plaintext: &str,
+        key_prefix: &str,
+        label: Option<&str>,
+        expires_at: Option<&str>,
+    ) -> Result<()>;
+
+    /// Validate a presented API key: look up a live (non-revoked, unexpired) key
+    /// by hash, touch `last_used_at`, and return the owning client.
+    async fn validate_api_key(&self, presented: &str) -> Result<Option<ApiKeyAuth>>;
+
+    /// List a client's API keys (no secrets — prefix + metadata only).
+    async fn list_api_keys(&self, client_id: &str) -> Result<Vec<InboundApiKey>>;
+
+    /// Revoke a single API key (irreversible — it can never authenticate again).
+    async fn revoke_api_key(&self, key_id: &str) -> Result<()>;
+
+    /// Set (or clear, with `None`) the Space a client is locked to. A locked
+    /// client is confined to that Space during resolution (see the gateway
+    /// FeatureSet resolver).
+    async fn set_locked_space(&self, client_id: &str, space_id: Option<&str>) -> Result<()>;
+
+    /// The Space a client is locked to, if any.
+    async fn get_locked_space(&self, client_id: &str) -> Result<Option<String>>;
+
+    /// Set (or clear, with `None`) the user who owns a client on a shared
+    /// gateway. Members may only manage the clients they own.
+    async fn set_client_owner(&self, client_id: &str, user_id: Option<&str>) -> Result<bool>;
+
+    /// The user who owns a client, if any.
+    async fn get_client_owner(&self, client_id: &str) -> Result<Option<String>>;
+
+    // =========================================================================
+    // Token Operations
+    // =========================================================================
+
+    /// Save a token record
+    async fn save_token(&self, record: &TokenRecord) -> Result<()>;
+
+    /// Find a token by its hash
+    async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<TokenRecord>>;
+
+    /// Validate a token (check hash, expiration, revocation)
+    async fn validate_token(&self, token: &str) -> Result<Option<TokenRecord>> {
+        let hash = hash_token(token);
+
+        if let Some(record) = self.find_token_by_hash(&hash).await? {
+            // Check if revoked
+            if record.revoked {
+                debug!("[OAuth] Token rejected: revoked");
+                return Ok(None);
+            }
+
+            // Check if expired
+            if let Some(expires_at) = &record.expires_at {
+                let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+                if expires_at < &now {
+                    debug!("[OAuth] Token rejected: expired");
+                    return Ok(None);
+                }
+            }
+
+            Ok(Some(record))
+        } else {
+            debug!("[OAuth] Token not found in database");
+            Ok(None)
+        }
+    }
+
+    /// Revoke a token (and all child tokens)
+    async fn revoke_token(&self, token_id: &str) -> Result<()>;
+
+    /// Revoke all tokens for a client
+    async fn revoke_client_tokens(&self, client_id: &str) -> Result<usize>;
+
+    /// Clean up expired tokens
+    async fn cleanup_expired_tokens(&self) -> Result<usize>;
+
+    // =========================================================================
+    // Client Grants (Feature Set Permissions for rootless OAuth clients)
+    //
+    // Consulted by FeatureSetResolverService when a session belongs to a
+    // client that did not declare the MCP `roots` capability (or has no
+    // workspace context). Roots-capable clients route through
+    // WorkspaceBinding instead — these methods are the rootless fallback.
+    // =========================================================================
+
+    /// Grant a feature set to a client in a specific space.
+    async fn grant_feature_set(
+        &self,
+        client_id: &str,
+        space_id: &str,
+        feature_set_id: &str,
+    ) -> Result<()>;
+
+    /// Revoke a feature set from a client in a specific space.
+    async fn revoke_feature_set(
+        &self,
+        client_id: &str,
+        space_id: &str,
+        feature_set_id: &str,
+    ) -> Result<()>;
+
+    /// Record the MCP `roots` capability state for a client.
+    ///
+    /// Called from the gateway's `on_initialized` for *every* session,
+    /// regardless of whether the client declared the capability. After the
+    /// first call:
+    ///   - `roots_capability_known` flips to 1 and stays there.
+    ///   - `reports_roots` is sticky-positive: it goes 0 → 1 the first
+    ///     session that declares roots, but a later session that doesn't
+    ///     declare can't flip it back to 0. This prevents the UI badge
+    ///     from bouncing on transient rootless reconnects from a normally
+    ///     roots-capable client.
+    ///
+    /// Reset by re-approving the client (delete + re-DCR).
+    async fn mark_roots_capability(&self, client_id: &str, declares: bool) -> Result<()>;
+
+    /// Get all granted feature_set_ids for a (client, space) pair.
+    /// Empty Vec means "no grant" → resolver returns Deny.
+    async fn get_grants_for_space(&self, client_id: &str, space_id: &str) -> Result<Vec<String>>;
+
+    /// Get every grant for a client across all spaces, grouped by space_id.
+    /// Used by the Clients UI to render the full permission picture.
+    async fn get_all_grants(&self, client_id: &str) -> Result<HashMap<String, Vec<String>>>;
+}
+
+/// SQLite-backed implementation of InboundClientRepository
+#[derive(Clone)]
+pub struct SqliteInboundClientRepository {
+    /// Writes lock the database; reads go through the reader
     db: Arc<Mutex<Database>>,
+    reader: DbReader,
 }
 
-impl InboundClientRepository {
+impl SqliteInboundClientRepository {
     /// Create a new inbound client repository with a database
     pub fn new(db: Arc<Mutex<Database>>) -> Self {
-        Self { db }
+        Self {
+            reader: DbReader::new(db.clone()),
+            db,
+        }
     }
 
     // =========================================================================
@@ -205,6 +476,7 @@ impl InboundClientRepository {
         let approved_int: i32 = row.get::<_, Option<i32>>(19)?.unwrap_or(0);
         let reports_roots_int: i32 = row.get::<_, Option<i32>>(20)?.unwrap_or(0);
         let roots_capability_known_int: i32 = row.get::<_, Option<i32>>(21)?.unwrap_or(0);
+        let suspended_int: i32 = row.get::<_, Option<i32>>(22)?.unwrap_or(0);
 
         Ok(InboundClient {
             client_id: row.get(0)?,
@@ -238,6 +510,7 @@ impl InboundClientRepository {
             approved: approved_int != 0,
             reports_roots: reports_roots_int != 0,
             roots_capability_known: roots_capability_known_int != 0,
+            suspended: suspended_int != 0,
         })
     }
 
@@ -247,14 +520,17 @@ impl InboundClientRepository {
          logo_uri, client_uri, software_id, software_version,
          redirect_uris, grant_types, response_types, token_endpoint_auth_method, scope,
          metadata_url, metadata_cached_at, metadata_cache_ttl,
-         last_seen, created_at, updated_at, approved, reports_roots, roots_capability_known";
+         last_seen, created_at, updated_at, approved, reports_roots, roots_capability_known,
+         suspended";
+}
 
+#[async_trait]
+impl InboundClientRepository for SqliteInboundClientRepository {
     // =========================================================================
     // Client Operations (unified inbound_clients table)
     // =========================================================================
 
-    /// Register or update an inbound client (supports CIMD, DCR, pre-registered)
-    pub async fn save_client(&self, client: &InboundClient) -> Result<()> {
+    async fn save_client(&self, client: &InboundClient) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         conn.execute(
@@ -303,28 +579,27 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Get a client by ID
-    pub async fn get_client(&self, client_id: &str) -> Result<Option<InboundClient>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-        let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM inbound_clients WHERE client_id = ?1",
-            Self::CLIENT_COLUMNS
-        ))?;
-
-        let result = stmt.query_row(params![client_id], Self::map_row_to_client);
-
-        match result {
-            Ok(client) => Ok(Some(client)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e.into()),
-        }
+    async fn get_client(&self, client_id: &str) -> Result<Option<InboundClient>> {
+        let reader = &self.reader;
+        reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM inbound_clients WHERE client_id = ?1",
+                    Self::CLIENT_COLUMNS
+                ))?;
+
+                let result = stmt.query_row(params![client_id], Self::map_row_to_client);
+
+                match result {
+                    Ok(client) => Ok(Some(client)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(e.into()),
+                }
+            })
+            .await
     }
 
-    /// Find client by name (for idempotent DCR)
-    ///
-    /// Allows a client to register with different redirect_uris
-    pub async fn find_client_by_name(&self, name: &str) -> Result<Option<InboundClient>> {
+    async fn find_client_by_name(&self, name: &str) -> Result<Option<InboundClient>> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
@@ -342,20 +617,7 @@ impl InboundClientRepository {
         }
     }
 
-    /// Strict byte-equal membership check of a redirect URI in the client's
-    /// registered list. This is a low-level DB lookup; for OAuth policy
-    /// decisions (including RFC 8252 §7.3 loopback-port flexibility) use
-    /// `mcpmux_gateway::oauth::is_redirect_uri_allowed` instead.
-    pub async fn validate_redirect_uri(&self, client_id: &str, redirect_uri: &str) -> Result<bool> {
-        if let Some(client) = self.get_client(client_id).await? {
-            Ok(client.redirect_uris.iter().any(|uri| uri == redirect_uri))
-        } else {
-            Ok(false)
-        }
-    }
-
-    /// List all registered OAuth clients
-    pub async fn list_clients(&self) -> Result<Vec<InboundClient>> {
+    async fn list_clients(&self) -> Result<Vec<InboundClient>> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let mut stmt = conn.prepare(&format!(
@@ -370,8 +632,19 @@ impl InboundClientRepository {
         Ok(result)
     }
 
-    /// Update a client's last_seen timestamp
-    pub async fn update_client_last_seen(&self, client_id: &str) -> Result<()> {
+    async fn list_clients_for_owner(&self, user_id: &str) -> Result<Vec<InboundClient>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM inbound_clients WHERE owner_user_id = ?1 ORDER BY created_at DESC",
+            Self::CLIENT_COLUMNS
+        ))?;
+
+        let clients = stmt.query_map(params![user_id], Self::map_row_to_client)?;
+        Ok(clients.collect::<Result<_, _>>()?)
+    }
+
+    async fn update_client_last_seen(&self, client_id: &str) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
@@ -383,11 +656,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Mark a client as approved by the user
-    ///
-    /// This is called when user explicitly approves the OAuth consent.
-    /// Only approved clients get silent re-authentication.
-    pub async fn approve_client(&self, client_id: &str) -> Result<()> {
+    async fn approve_client(&self, client_id: &str) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
@@ -399,57 +668,68 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Check if a client has been approved by the user
-    pub async fn is_client_approved(&self, client_id: &str) -> Result<bool> {
+    async fn is_client_approved(&self, client_id: &str) -> Result<bool> {
+        let reader = &self.reader;
+        reader
+            .read(|conn| {
+                let approved: i32 = conn
+                    .query_row(
+                        "SELECT approved FROM inbound_clients WHERE client_id = ?1",
+                        params![client_id],
+                        |row| row.get(0),
+                    )
+                    .unwrap_or(0);
+                Ok(approved != 0)
+            })
+            .await
+    }
+
+    async fn set_client_suspended(&self, client_id: &str, suspended: bool) -> Result<bool> {
         let db = self.db.lock().await;
         let conn = db.connection();
-        let approved: i32 = conn
-            .query_row(
-                "SELECT approved FROM inbound_clients WHERE client_id = ?1",
-                params![client_id],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-        Ok(approved != 0)
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let updated = conn.execute(
+            "UPDATE inbound_clients SET suspended = ?1, updated_at = ?2 WHERE client_id = ?3",
+            params![suspended as i32, now, client_id],
+        )?;
+        info!(
+            "[OAuth] {} client: {}",
+            if suspended { "Suspended" } else { "Reinstated" },
+            client_id
+        );
+        Ok(updated > 0)
     }
 
-    /// Merge new redirect URIs with existing ones for a client
-    /// Avoids duplicates and preserves existing URIs
-    pub async fn merge_redirect_uris(
-        &self,
-        client_id: &str,
-        new_uris: Vec<String>,
-    ) -> Result<Vec<String>> {
-        // Get existing client
-        let existing_client = self.get_client(client_id).await?;
-        let mut merged_uris = existing_client.map(|c| c.redirect_uris).unwrap_or_default();
-
-        // Add new URIs (avoid duplicates)
-        for uri in new_uris {
-            if !merged_uris.contains(&uri) {
-                merged_uris.push(uri);
-            }
-        }
+    async fn is_client_suspended(&self, client_id: &str) -> Result<bool> {
+        let reader = &self.reader;
+        reader
+            .read(|conn| {
+                // An unknown client isn't suspended; a failed query is an
+                // error, so callers can refuse rather than let the client through
+                let suspended: Option<i32> = conn
+                    .query_row(
+                        "SELECT suspended FROM inbound_clients WHERE client_id = ?1",
+                        params![client_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(suspended.unwrap_or(0) != 0)
+            })
+            .await
+    }
 
-        // Update in database
+    async fn set_redirect_uris(&self, client_id: &str, uris: &[String]) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
-        let uris_json = serde_json::to_string(&merged_uris)?;
         let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
         conn.execute(
             "UPDATE inbound_clients SET redirect_uris = ?1, updated_at = ?2 WHERE client_id = ?3",
-            params![uris_json, now, client_id],
+            params![serde_json::to_string(uris)?, now, client_id],
         )?;
-
-        debug!(
-            "[OAuth] Merged redirect_uris for client: {} -> {:?}",
-            client_id, merged_uris
-        );
-        Ok(merged_uris)
+        Ok(())
     }
 
-    /// Update a client's human-facing alias.
-    pub async fn update_client_alias(
+    async fn update_client_alias(
         &self,
         client_id: &str,
         client_alias: Option<String>,
@@ -467,8 +747,7 @@ impl InboundClientRepository {
         self.get_client(client_id).await
     }
 
-    /// Delete a client and all associated tokens
-    pub async fn delete_client(&self, client_id: &str) -> Result<bool> {
+    async fn delete_client(&self, client_id: &str) -> Result<bool> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
@@ -491,8 +770,7 @@ impl InboundClientRepository {
     // Authorization Code Operations
     // =========================================================================
 
-    /// Save an authorization code
-    pub async fn save_authorization_code(&self, code: &AuthorizationCode) -> Result<()> {
+    async fn save_authorization_code(&self, code: &AuthorizationCode) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         conn.execute(
@@ -517,11 +795,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Get and consume an authorization code (one-time use)
-    pub async fn consume_authorization_code(
-        &self,
-        code: &str,
-    ) -> Result<Option<AuthorizationCode>> {
+    async fn consume_authorization_code(&self, code: &str) -> Result<Option<AuthorizationCode>> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
@@ -562,8 +836,7 @@ impl InboundClientRepository {
         }
     }
 
-    /// Clean up expired authorization codes
-    pub async fn cleanup_expired_codes(&self) -> Result<usize> {
+    async fn cleanup_expired_codes(&self) -> Result<usize> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let deleted = conn.execute(
@@ -576,31 +849,11 @@ impl InboundClientRepository {
         Ok(deleted)
     }
 
-    // =========================================================================
-    // Token Operations
-    // =========================================================================
-
-    /// Hash a token for storage (we never store raw tokens)
-    pub fn hash_token(token: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(token.as_bytes());
-        hex::encode(hasher.finalize())
-    }
-
     // =========================================================================
     // Inbound client API keys (long-lived, host-issued bearer credentials)
     // =========================================================================
 
-    /// SHA-256 hex of an API key — the only form ever persisted. Same algorithm
-    /// as `hash_token`; named separately for intent.
-    pub fn hash_api_key(key: &str) -> String {
-        Self::hash_token(key)
-    }
-
-    /// Persist a freshly-generated API key for a client. The caller generates
-    /// the random `plaintext` (shown to the user once) and a unique `key_id`;
-    /// only the SHA-256 hash + a display prefix are stored.
-    pub async fn create_api_key(
+    async fn create_api_key(
         &self,
         key_id: &str,
         client_id: &str,
@@ -610,7 +863,7 @@ impl InboundClientRepository {
         expires_at: Option<&str>,
     ) -> Result<()> {
         let now = chrono::Utc::now().to_rfc3339();
-        let hash = Self::hash_api_key(plaintext);
+        let hash = hash_api_key(plaintext);
         let db = self.db.lock().await;
         let conn = db.connection();
         conn.execute(
@@ -626,10 +879,8 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Validate a presented API key: look up a live (non-revoked, unexpired) key
-    /// by hash, touch `last_used_at`, and return the owning client.
-    pub async fn validate_api_key(&self, presented: &str) -> Result<Option<ApiKeyAuth>> {
-        let hash = Self::hash_api_key(presented);
+    async fn validate_api_key(&self, presented: &str) -> Result<Option<ApiKeyAuth>> {
+        let hash = hash_api_key(presented);
         let now = chrono::Utc::now().to_rfc3339();
         let db = self.db.lock().await;
         let conn = db.connection();
@@ -667,8 +918,7 @@ impl InboundClientRepository {
         Ok(Some(ApiKeyAuth { key_id, client_id }))
     }
 
-    /// List a client's API keys (no secrets — prefix + metadata only).
-    pub async fn list_api_keys(&self, client_id: &str) -> Result<Vec<InboundApiKey>> {
+    async fn list_api_keys(&self, client_id: &str) -> Result<Vec<InboundApiKey>> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let mut stmt = conn.prepare(
@@ -695,8 +945,7 @@ impl InboundClientRepository {
         Ok(keys)
     }
 
-    /// Revoke a single API key (irreversible — it can never authenticate again).
-    pub async fn revoke_api_key(&self, key_id: &str) -> Result<()> {
+    async fn revoke_api_key(&self, key_id: &str) -> Result<()> {
         let now = chrono::Utc::now().to_rfc3339();
         let db = self.db.lock().await;
         let conn = db.connection();
@@ -707,10 +956,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Set (or clear, with `None`) the Space a client is locked to. A locked
-    /// client is confined to that Space during resolution (see the gateway
-    /// FeatureSet resolver).
-    pub async fn set_locked_space(&self, client_id: &str, space_id: Option<&str>) -> Result<()> {
+    async fn set_locked_space(&self, client_id: &str, space_id: Option<&str>) -> Result<()> {
         let now = chrono::Utc::now().to_rfc3339();
         let db = self.db.lock().await;
         let conn = db.connection();
@@ -721,8 +967,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// The Space a client is locked to, if any.
-    pub async fn get_locked_space(&self, client_id: &str) -> Result<Option<String>> {
+    async fn get_locked_space(&self, client_id: &str) -> Result<Option<String>> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let result = conn.query_row(
@@ -737,8 +982,33 @@ impl InboundClientRepository {
         }
     }
 
-    /// Save a token record
-    pub async fn save_token(&self, record: &TokenRecord) -> Result<()> {
+    async fn set_client_owner(&self, client_id: &str, user_id: Option<&str>) -> Result<bool> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let rows = conn.execute(
+            "UPDATE inbound_clients SET owner_user_id = ?1, updated_at = ?2 WHERE client_id = ?3",
+            params![user_id, now, client_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    async fn get_client_owner(&self, client_id: &str) -> Result<Option<String>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let result = conn.query_row(
+            "SELECT owner_user_id FROM inbound_clients WHERE client_id = ?1",
+            params![client_id],
+            |r| r.get::<_, Option<String>>(0),
+        );
+        match result {
+            Ok(v) => Ok(v),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save_token(&self, record: &TokenRecord) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         conn.execute(
@@ -764,8 +1034,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Find a token by its hash
-    pub async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<TokenRecord>> {
+    async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<TokenRecord>> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let mut stmt = conn.prepare(
@@ -797,35 +1066,7 @@ impl InboundClientRepository {
         }
     }
 
-    /// Validate a token (check hash, expiration, revocation)
-    pub async fn validate_token(&self, token: &str) -> Result<Option<TokenRecord>> {
-        let hash = Self::hash_token(token);
-
-        if let Some(record) = self.find_token_by_hash(&hash).await? {
-            // Check if revoked
-            if record.revoked {
-                debug!("[OAuth] Token rejected: revoked");
-                return Ok(None);
-            }
-
-            // Check if expired
-            if let Some(expires_at) = &record.expires_at {
-                let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-                if expires_at < &now {
-                    debug!("[OAuth] Token rejected: expired");
-                    return Ok(None);
-                }
-            }
-
-            Ok(Some(record))
-        } else {
-            debug!("[OAuth] Token not found in database");
-            Ok(None)
-        }
-    }
-
-    /// Revoke a token (and all child tokens)
-    pub async fn revoke_token(&self, token_id: &str) -> Result<()> {
+    async fn revoke_token(&self, token_id: &str) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
@@ -845,8 +1086,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Revoke all tokens for a client
-    pub async fn revoke_client_tokens(&self, client_id: &str) -> Result<usize> {
+    async fn revoke_client_tokens(&self, client_id: &str) -> Result<usize> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let count = conn.execute(
@@ -857,8 +1097,7 @@ impl InboundClientRepository {
         Ok(count)
     }
 
-    /// Clean up expired tokens
-    pub async fn cleanup_expired_tokens(&self) -> Result<usize> {
+    async fn cleanup_expired_tokens(&self) -> Result<usize> {
         let db = self.db.lock().await;
         let conn = db.connection();
         let deleted = conn.execute(
@@ -880,8 +1119,7 @@ impl InboundClientRepository {
     // WorkspaceBinding instead — these methods are the rootless fallback.
     // =========================================================================
 
-    /// Grant a feature set to a client in a specific space.
-    pub async fn grant_feature_set(
+    async fn grant_feature_set(
         &self,
         client_id: &str,
         space_id: &str,
@@ -899,8 +1137,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Revoke a feature set from a client in a specific space.
-    pub async fn revoke_feature_set(
+    async fn revoke_feature_set(
         &self,
         client_id: &str,
         space_id: &str,
@@ -918,20 +1155,7 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Record the MCP `roots` capability state for a client.
-    ///
-    /// Called from the gateway's `on_initialized` for *every* session,
-    /// regardless of whether the client declared the capability. After the
-    /// first call:
-    ///   - `roots_capability_known` flips to 1 and stays there.
-    ///   - `reports_roots` is sticky-positive: it goes 0 → 1 the first
-    ///     session that declares roots, but a later session that doesn't
-    ///     declare can't flip it back to 0. This prevents the UI badge
-    ///     from bouncing on transient rootless reconnects from a normally
-    ///     roots-capable client.
-    ///
-    /// Reset by re-approving the client (delete + re-DCR).
-    pub async fn mark_roots_capability(&self, client_id: &str, declares: bool) -> Result<()> {
+    async fn mark_roots_capability(&self, client_id: &str, declares: bool) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
         // `MAX(reports_roots, ?2)` is the sticky-positive update — once 1,
@@ -946,34 +1170,25 @@ impl InboundClientRepository {
         Ok(())
     }
 
-    /// Get all granted feature_set_ids for a (client, space) pair.
-    /// Empty Vec means "no grant" → resolver returns Deny.
-    pub async fn get_grants_for_space(
-        &self,
-        client_id: &str,
-        space_id: &str,
-    ) -> Result<Vec<String>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT feature_set_id FROM client_grants
-             WHERE client_id = ?1 AND space_id = ?2",
-        )?;
+    async fn get_grants_for_space(&self, client_id: &str, space_id: &str) -> Result<Vec<String>> {
+        let reader = &self.reader;
+        reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT feature_set_id FROM client_grants
+                     WHERE client_id = ?1 AND space_id = ?2",
+                )?;
 
-        let grants = stmt
-            .query_map(params![client_id, space_id], |row| row.get::<_, String>(0))?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
+                let grants = stmt
+                    .query_map(params![client_id, space_id], |row| row.get::<_, String>(0))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(grants)
+                Ok(grants)
+            })
+            .await
     }
 
-    /// Get every grant for a client across all spaces, grouped by space_id.
-    /// Used by the Clients UI to render the full permission picture.
-    pub async fn get_all_grants(
-        &self,
-        client_id: &str,
-    ) -> Result<std::collections::HashMap<String, Vec<String>>> {
+    async fn get_all_grants(&self, client_id: &str) -> Result<HashMap<String, Vec<String>>> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
@@ -983,8 +1198,7 @@ impl InboundClientRepository {
              ORDER BY space_id",
         )?;
 
-        let mut grants: std::collections::HashMap<String, Vec<String>> =
-            std::collections::HashMap::new();
+        let mut grants: HashMap<String, Vec<String>> = HashMap::new();
 
         let rows = stmt.query_map(params![client_id], |row| {
             Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
@@ -1005,12 +1219,29 @@ mod tests {
 
     #[test]
     fn test_hash_token() {
-        let hash1 = InboundClientRepository::hash_token("test_token");
-        let hash2 = InboundClientRepository::hash_token("test_token");
-        let hash3 = InboundClientRepository::hash_token("different_token");
+        let hash1 = hash_token("test_token");
+        let hash2 = hash_token("test_token");
+        let hash3 = hash_token("different_token");
 
         assert_eq!(hash1, hash2);
         assert_ne!(hash1, hash3);
         assert_eq!(hash1.len(), 64); // SHA-256 hex = 64 chars
     }
+
+    #[tokio::test]
+    async fn test_is_client_suspended_reports_query_errors() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let repo = SqliteInboundClientRepository::new(db.clone());
+
+        // An unknown client isn't suspended
+        assert!(!repo.is_client_suspended("missing").await.unwrap());
+
+        // Any other failure is returned, never read as "not suspended"
+        db.lock()
+            .await
+            .connection()
+            .execute_batch("ALTER TABLE inbound_clients RENAME TO inbound_clients_gone")
+            .unwrap();
+        assert!(repo.is_client_suspended("missing").await.is_err());
+    }
 }
@@ -7,29 +7,44 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use mcpmux_core::{InstallationSource, InstalledServer, InstalledServerRepository};
+use mcpmux_core::{
+    CallContext, InstallationSource, InstalledServer, InstalledServerRepository, ServerTimeouts,
+    ToolHooks, WasmPermissions,
+};
 use rusqlite::{params, OptionalExtension};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::{crypto::FieldEncryptor, Database};
 
-/// Raw row data extracted from SQLite before decryption.
-struct RawServerRow {
-    id: String,
-    space_id: String,
-    server_id: String,
-    server_name: Option<String>,
-    cached_definition: Option<String>,
-    input_values: Option<String>,
-    enabled: bool,
-    env_overrides: Option<String>,
-    args_append: Option<String>,
-    extra_headers: Option<String>,
-    oauth_connected: bool,
-    created_at: String,
-    updated_at: String,
-    source: Option<String>,
+/// Raw row data read from the database before decryption.
+pub(crate) struct RawServerRow {
+    pub(crate) id: String,
+    pub(crate) space_id: String,
+    pub(crate) server_id: String,
+    pub(crate) server_name: Option<String>,
+    pub(crate) cached_definition: Option<String>,
+    pub(crate) input_values: Option<String>,
+    pub(crate) enabled: bool,
+    pub(crate) env_overrides: Option<String>,
+    pub(crate) args_append: Option<String>,
+    pub(crate) extra_headers: Option<String>,
+    pub(crate) inherit_env: bool,
+    pub(crate) fs_allowed_roots: Option<String>,
+    pub(crate) package_version: Option<String>,
+    pub(crate) skip_argument_validation: bool,
+    pub(crate) connect_timeout_secs: Option<u32>,
+    pub(crate) request_timeout_secs: Option<u32>,
+    pub(crate) tool_hooks: Option<String>,
+    pub(crate) wasm_grant_filesystem: bool,
+    pub(crate) wasm_grant_network: bool,
+    pub(crate) call_context: Option<String>,
+    pub(crate) last_used_at: Option<String>,
+    pub(crate) deleted_at: Option<String>,
+    pub(crate) oauth_connected: bool,
+    pub(crate) created_at: String,
+    pub(crate) updated_at: String,
+    pub(crate) source: Option<String>,
 }
 
 /// SQLite-backed implementation of InstalledServerRepository.
@@ -44,115 +59,6 @@ impl SqliteInstalledServerRepository {
         Self { db, encryptor }
     }
 
-    /// Encrypt input values for storage.
-    fn encrypt_input_values(&self, values: &HashMap<String, String>) -> Result<String> {
-        let json = Self::serialize_json_map(values);
-        self.encryptor
-            .encrypt(&json)
-            .map_err(|e| anyhow::anyhow!("Failed to encrypt input values: {}", e))
-    }
-
-    /// Decrypt input values from storage.
-    ///
-    /// Three cases, kept distinct so a real failure can't masquerade as an
-    /// empty config (which would silently launch a server with all its
-    /// secrets missing):
-    ///   * `None` / empty column → no input values (`Ok(empty)`).
-    ///   * Decrypts cleanly → parse the plaintext JSON (a parse failure here
-    ///     is corruption → error).
-    ///   * Decrypt fails → it may be a legacy *unencrypted* row, so try a
-    ///     plaintext-JSON parse; if THAT also fails the data is neither
-    ///     decryptable nor valid plaintext (wrong master key or tampered
-    ///     ciphertext) → propagate a hard error rather than returning empty.
-    fn decrypt_input_values(&self, stored: Option<String>) -> Result<HashMap<String, String>> {
-        let Some(data) = stored else {
-            return Ok(HashMap::new());
-        };
-        if data.trim().is_empty() {
-            return Ok(HashMap::new());
-        }
-        // Try decrypting first (new encrypted format).
-        if let Ok(json) = self.encryptor.decrypt(&data) {
-            return serde_json::from_str(&json)
-                .map_err(|e| anyhow::anyhow!("Corrupt decrypted input values (not JSON): {}", e));
-        }
-        // Fallback: legacy unencrypted row stored as plaintext JSON.
-        serde_json::from_str(&data).map_err(|e| {
-            anyhow::anyhow!(
-                "Failed to decrypt input values and data is not valid plaintext JSON \
-                 (wrong master key or tampered ciphertext): {}",
-                e
-            )
-        })
-    }
-
-    /// Parse a datetime string to DateTime<Utc>.
-    fn parse_datetime(s: &str) -> DateTime<Utc> {
-        // Try RFC3339 first
-        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
-            return dt.with_timezone(&Utc);
-        }
-        // Try SQLite datetime format
-        if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-            return dt.and_utc();
-        }
-        Utc::now()
-    }
-
-    /// Parse JSON string to HashMap.
-    fn parse_json_map(s: Option<String>) -> HashMap<String, String> {
-        s.and_then(|json| serde_json::from_str(&json).ok())
-            .unwrap_or_default()
-    }
-
-    /// Parse JSON string to Vec.
-    fn parse_json_vec(s: Option<String>) -> Vec<String> {
-        s.and_then(|json| serde_json::from_str(&json).ok())
-            .unwrap_or_default()
-    }
-
-    /// Serialize HashMap to JSON string.
-    fn serialize_json_map(map: &HashMap<String, String>) -> String {
-        serde_json::to_string(map).unwrap_or_else(|_| "{}".to_string())
-    }
-
-    /// Serialize Vec to JSON string.
-    fn serialize_json_vec(vec: &[String]) -> String {
-        serde_json::to_string(vec).unwrap_or_else(|_| "[]".to_string())
-    }
-
-    /// Serialize InstallationSource to database string format.
-    /// Format: "registry" | "user_config:/path/to/file.json" | "manual_entry"
-    fn serialize_source(source: &InstallationSource) -> String {
-        match source {
-            InstallationSource::Registry => "registry".to_string(),
-            InstallationSource::UserConfig { file_path } => {
-                format!("user_config:{}", file_path.display())
-            }
-            InstallationSource::ManualEntry => "manual_entry".to_string(),
-        }
-    }
-
-    /// Parse InstallationSource from database string format.
-    fn parse_source(s: Option<String>) -> InstallationSource {
-        match s.as_deref() {
-            Some("registry") | None => InstallationSource::Registry,
-            Some("manual_entry") => InstallationSource::ManualEntry,
-            Some(s) if s.starts_with("user_config:") => {
-                let path = s.strip_prefix("user_config:").unwrap_or("");
-                InstallationSource::UserConfig {
-                    file_path: PathBuf::from(path),
-                }
-            }
-            _ => InstallationSource::Registry,
-        }
-    }
-
-    /// Standard column list for SELECT queries
-    const SELECT_COLUMNS: &'static str =
-        "id, space_id, server_id, server_name, cached_definition, input_values, enabled, env_overrides,
-         args_append, extra_headers, oauth_connected, created_at, updated_at, source";
-
     /// Extract raw row data (used in the closure passed to rusqlite).
     fn extract_row(row: &rusqlite::Row) -> rusqlite::Result<RawServerRow> {
         Ok(RawServerRow {
@@ -170,33 +76,207 @@ impl SqliteInstalledServerRepository {
             created_at: row.get(11)?,
             updated_at: row.get(12)?,
             source: row.get(13)?,
+            inherit_env: row.get(14)?,
+            fs_allowed_roots: row.get(15)?,
+            package_version: row.get(16)?,
+            skip_argument_validation: row.get(17)?,
+            connect_timeout_secs: row.get(18)?,
+            request_timeout_secs: row.get(19)?,
+            tool_hooks: row.get(20)?,
+            wasm_grant_filesystem: row.get(21)?,
+            wasm_grant_network: row.get(22)?,
+            call_context: row.get(23)?,
+            last_used_at: row.get(24)?,
+            deleted_at: row.get(25)?,
         })
     }
+}
 
-    /// Build InstalledServer from extracted row data (needs &self for decryption).
-    fn build_server(&self, row: RawServerRow) -> Result<InstalledServer> {
-        let input_values = self
-            .decrypt_input_values(row.input_values)
-            .map_err(|e| anyhow::anyhow!("server {}: {}", row.server_id, e))?;
-        Ok(InstalledServer {
-            id: Uuid::parse_str(&row.id).unwrap_or_else(|_| Uuid::new_v4()),
-            space_id: row.space_id,
-            server_id: row.server_id,
-            server_name: row.server_name,
-            cached_definition: row.cached_definition,
-            input_values,
-            enabled: row.enabled,
-            env_overrides: Self::parse_json_map(row.env_overrides),
-            args_append: Self::parse_json_vec(row.args_append),
-            extra_headers: Self::parse_json_map(row.extra_headers),
-            oauth_connected: row.oauth_connected,
-            source: Self::parse_source(row.source),
-            created_at: Self::parse_datetime(&row.created_at),
-            updated_at: Self::parse_datetime(&row.updated_at),
-        })
+/// Encrypt input values for storage.
+pub(crate) fn encrypt_input_values(
+    encryptor: &FieldEncryptor,
+    values: &HashMap<String, String>,
+) -> Result<String> {
+    let json = serialize_json_map(values);
+    encryptor
+        .encrypt(&json)
+        .map_err(|e| anyhow::anyhow!("Failed to encrypt input values: {}", e))
+}
+
+/// Decrypt input values from storage.
+///
+/// Three cases, kept distinct so a real failure can't masquerade as an
+/// empty config (which would silently launch a server with all its
+/// secrets missing):
+///   * `None` / empty column → no input values (`Ok(empty)`).
+///   * Decrypts cleanly → parse the plaintext JSON (a parse failure here
+///     is corruption → error).
+///   * Decrypt fails → it may be a legacy *unencrypted* row, so try a
+///     plaintext-JSON parse; if THAT also fails the data is neither
+///     decryptable nor valid plaintext (wrong master key or tampered
+///     ciphertext) → propagate a hard error rather than returning empty.
+fn decrypt_input_values(
+    encryptor: &FieldEncryptor,
+    stored: Option<String>,
+) -> Result<HashMap<String, String>> {
+    let Some(data) = stored else {
+        return Ok(HashMap::new());
+    };
+    if data.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    // Try decrypting first (new encrypted format).
+    if let Ok(json) = encryptor.decrypt(&data) {
+        return serde_json::from_str(&json)
+            .map_err(|e| anyhow::anyhow!("Corrupt decrypted input values (not JSON): {}", e));
+    }
+    // Fallback: legacy unencrypted row stored as plaintext JSON.
+    serde_json::from_str(&data).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to decrypt input values and data is not valid plaintext JSON \
+             (wrong master key or tampered ciphertext): {}",
+            e
+        )
+    })
+}
+
+/// Parse a datetime string to DateTime<Utc>.
+fn parse_datetime(s: &str) -> DateTime<Utc> {
+    // Try RFC3339 first
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return dt.with_timezone(&Utc);
+    }
+    // Try SQLite datetime format
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return dt.and_utc();
+    }
+    Utc::now()
+}
+
+/// Parse JSON string to HashMap.
+fn parse_json_map(s: Option<String>) -> HashMap<String, String> {
+    s.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Parse JSON string to Vec.
+fn parse_json_vec(s: Option<String>) -> Vec<String> {
+    s.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Serialize HashMap to JSON string.
+pub(crate) fn serialize_json_map(map: &HashMap<String, String>) -> String {
+    serde_json::to_string(map).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Serialize Vec to JSON string.
+pub(crate) fn serialize_json_vec(vec: &[String]) -> String {
+    serde_json::to_string(vec).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Parse tool hooks; unreadable hooks are dropped rather than failing
+/// the whole row.
+fn parse_tool_hooks(s: Option<String>) -> ToolHooks {
+    s.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Serialize tool hooks to JSON, or NULL when there are none.
+pub(crate) fn serialize_tool_hooks(hooks: &ToolHooks) -> Option<String> {
+    (!hooks.is_empty()).then(|| serde_json::to_string(hooks).unwrap_or_default())
+}
+
+/// Parse call context; unreadable context is dropped rather than failing
+/// the whole row.
+fn parse_call_context(s: Option<String>) -> CallContext {
+    s.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Serialize call context to JSON, or NULL when there is none.
+pub(crate) fn serialize_call_context(context: &CallContext) -> Option<String> {
+    (!context.is_empty()).then(|| serde_json::to_string(context).unwrap_or_default())
+}
+
+/// Serialize InstallationSource to database string format.
+/// Format: "registry" | "user_config:/path/to/file.json" | "manual_entry"
+pub(crate) fn serialize_source(source: &InstallationSource) -> String {
+    match source {
+        InstallationSource::Registry => "registry".to_string(),
+        InstallationSource::UserConfig { file_path } => {
+            format!("user_config:{}", file_path.display())
+        }
+        InstallationSource::ManualEntry => "manual_entry".to_string(),
+        // Read back as the registry default, as `parse_source` does for
+        // anything it doesn't know
+        _ => "registry".to_string(),
     }
 }
 
+/// Parse InstallationSource from database string format.
+fn parse_source(s: Option<String>) -> InstallationSource {
+    match s.as_deref() {
+        Some("registry") | None => InstallationSource::Registry,
+        Some("manual_entry") => InstallationSource::ManualEntry,
+        Some(s) if s.starts_with("user_config:") => {
+            let path = s.strip_prefix("user_config:").unwrap_or("");
+            InstallationSource::UserConfig {
+                file_path: PathBuf::from(path),
+            }
+        }
+        _ => InstallationSource::Registry,
+    }
+}
+
+/// Standard column list for SELECT queries; both backends read rows by
+/// these positions.
+pub(crate) const SELECT_COLUMNS: &str =
+    "id, space_id, server_id, server_name, cached_definition, input_values, enabled, env_overrides,
+     args_append, extra_headers, oauth_connected, created_at, updated_at, source, inherit_env,
+     fs_allowed_roots, package_version, skip_argument_validation, connect_timeout_secs,
+     request_timeout_secs, tool_hooks, wasm_grant_filesystem, wasm_grant_network, call_context,
+     last_used_at, deleted_at";
+
+/// Build InstalledServer from extracted row data.
+pub(crate) fn build_server(
+    encryptor: &FieldEncryptor,
+    row: RawServerRow,
+) -> Result<InstalledServer> {
+    let input_values = decrypt_input_values(encryptor, row.input_values)
+        .map_err(|e| anyhow::anyhow!("server {}: {}", row.server_id, e))?;
+    let mut server = InstalledServer::new(row.space_id, row.server_id)
+        .with_inputs(input_values)
+        .with_enabled(row.enabled)
+        .with_source(parse_source(row.source));
+    server.id = Uuid::parse_str(&row.id).unwrap_or_else(|_| Uuid::new_v4());
+    server.server_name = row.server_name;
+    server.cached_definition = row.cached_definition;
+    server.env_overrides = parse_json_map(row.env_overrides);
+    server.args_append = parse_json_vec(row.args_append);
+    server.extra_headers = parse_json_map(row.extra_headers);
+    server.inherit_env = row.inherit_env;
+    server.fs_allowed_roots = parse_json_vec(row.fs_allowed_roots);
+    server.package_version = row.package_version;
+    server.skip_argument_validation = row.skip_argument_validation;
+    server.timeouts = ServerTimeouts {
+        connect_secs: row.connect_timeout_secs,
+        request_secs: row.request_timeout_secs,
+    };
+    server.tool_hooks = parse_tool_hooks(row.tool_hooks);
+    server.wasm_grants = WasmPermissions {
+        filesystem: row.wasm_grant_filesystem,
+        network: row.wasm_grant_network,
+    };
+    server.call_context = parse_call_context(row.call_context);
+    server.oauth_connected = row.oauth_connected;
+    server.last_used_at = row.last_used_at.as_deref().map(parse_datetime);
+    server.deleted_at = row.deleted_at.as_deref().map(parse_datetime);
+    server.created_at = parse_datetime(&row.created_at);
+    server.updated_at = parse_datetime(&row.updated_at);
+    Ok(server)
+}
+
 #[async_trait]
 impl InstalledServerRepository for SqliteInstalledServerRepository {
     async fn list(&self) -> Result<Vec<InstalledServer>> {
@@ -204,15 +284,17 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers ORDER BY created_at DESC",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE deleted_at IS NULL ORDER BY created_at DESC",
+            SELECT_COLUMNS
         ))?;
 
         let rows: Vec<_> = stmt
             .query_map([], Self::extract_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
-        rows.into_iter().map(|r| self.build_server(r)).collect()
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
     }
 
     async fn list_for_space(&self, space_id: &str) -> Result<Vec<InstalledServer>> {
@@ -220,15 +302,17 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE space_id = ?1 ORDER BY created_at DESC",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE space_id = ?1 AND deleted_at IS NULL ORDER BY created_at DESC",
+            SELECT_COLUMNS
         ))?;
 
         let rows: Vec<_> = stmt
             .query_map([space_id], Self::extract_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
-        rows.into_iter().map(|r| self.build_server(r)).collect()
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
     }
 
     async fn list_by_source_file(
@@ -242,15 +326,17 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let source_prefix = format!("user_config:{}", file_path.display());
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE source = ?1 ORDER BY created_at DESC",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE source = ?1 AND deleted_at IS NULL ORDER BY created_at DESC",
+            SELECT_COLUMNS
         ))?;
 
         let rows: Vec<_> = stmt
             .query_map([&source_prefix], Self::extract_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
-        rows.into_iter().map(|r| self.build_server(r)).collect()
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
     }
 
     async fn get(&self, id: &Uuid) -> Result<Option<InstalledServer>> {
@@ -258,15 +344,15 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE id = ?1",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE id = ?1 AND deleted_at IS NULL",
+            SELECT_COLUMNS
         ))?;
 
         let row = stmt
             .query_row([id.to_string()], Self::extract_row)
             .optional()?;
 
-        row.map(|r| self.build_server(r)).transpose()
+        row.map(|r| build_server(&self.encryptor, r)).transpose()
     }
 
     async fn get_by_server_id(
@@ -278,28 +364,32 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE space_id = ?1 AND server_id = ?2",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE space_id = ?1 AND server_id = ?2 AND deleted_at IS NULL",
+            SELECT_COLUMNS
         ))?;
 
         let row = stmt
             .query_row([space_id, server_id], Self::extract_row)
             .optional()?;
 
-        row.map(|r| self.build_server(r)).transpose()
+        row.map(|r| build_server(&self.encryptor, r)).transpose()
     }
 
     async fn install(&self, server: &InstalledServer) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
-        let encrypted_inputs = self.encrypt_input_values(&server.input_values)?;
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &server.input_values)?;
 
         conn.execute(
             "INSERT INTO installed_servers
              (id, space_id, server_id, server_name, cached_definition, input_values, enabled, env_overrides,
-              args_append, extra_headers, oauth_connected, created_at, updated_at, source)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+              args_append, extra_headers, oauth_connected, created_at, updated_at, source, inherit_env,
+              fs_allowed_roots, package_version, skip_argument_validation, connect_timeout_secs,
+              request_timeout_secs, tool_hooks, wasm_grant_filesystem, wasm_grant_network,
+              call_context)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                     ?19, ?20, ?21, ?22, ?23, ?24)",
             params![
                 server.id.to_string(),
                 server.space_id,
@@ -308,13 +398,23 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
                 server.cached_definition,
                 encrypted_inputs,
                 server.enabled,
-                Self::serialize_json_map(&server.env_overrides),
-                Self::serialize_json_vec(&server.args_append),
-                Self::serialize_json_map(&server.extra_headers),
+                serialize_json_map(&server.env_overrides),
+                serialize_json_vec(&server.args_append),
+                serialize_json_map(&server.extra_headers),
                 server.oauth_connected,
                 server.created_at.to_rfc3339(),
                 server.updated_at.to_rfc3339(),
-                Self::serialize_source(&server.source),
+                serialize_source(&server.source),
+                server.inherit_env,
+                serialize_json_vec(&server.fs_allowed_roots),
+                server.package_version,
+                server.skip_argument_validation,
+                server.timeouts.connect_secs,
+                server.timeouts.request_secs,
+                serialize_tool_hooks(&server.tool_hooks),
+                server.wasm_grants.filesystem,
+                server.wasm_grants.network,
+                serialize_call_context(&server.call_context),
             ],
         )?;
         Ok(())
@@ -324,13 +424,16 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let db = self.db.lock().await;
         let conn = db.connection();
 
-        let encrypted_inputs = self.encrypt_input_values(&server.input_values)?;
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &server.input_values)?;
 
         conn.execute(
             "UPDATE installed_servers
              SET server_name = ?2, cached_definition = ?3, input_values = ?4, enabled = ?5,
                  env_overrides = ?6, args_append = ?7, extra_headers = ?8, oauth_connected = ?9,
-                 updated_at = ?10, source = ?11
+                 updated_at = ?10, source = ?11, inherit_env = ?12,
+                 fs_allowed_roots = ?13, package_version = ?14, skip_argument_validation = ?15,
+                 connect_timeout_secs = ?16, request_timeout_secs = ?17, tool_hooks = ?18,
+                 wasm_grant_filesystem = ?19, wasm_grant_network = ?20, call_context = ?21
              WHERE id = ?1",
             params![
                 server.id.to_string(),
@@ -338,12 +441,22 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
                 server.cached_definition,
                 encrypted_inputs,
                 server.enabled,
-                Self::serialize_json_map(&server.env_overrides),
-                Self::serialize_json_vec(&server.args_append),
-                Self::serialize_json_map(&server.extra_headers),
+                serialize_json_map(&server.env_overrides),
+                serialize_json_vec(&server.args_append),
+                serialize_json_map(&server.extra_headers),
                 server.oauth_connected,
                 Utc::now().to_rfc3339(),
-                Self::serialize_source(&server.source),
+                serialize_source(&server.source),
+                server.inherit_env,
+                serialize_json_vec(&server.fs_allowed_roots),
+                server.package_version,
+                server.skip_argument_validation,
+                server.timeouts.connect_secs,
+                server.timeouts.request_secs,
+                serialize_tool_hooks(&server.tool_hooks),
+                server.wasm_grants.filesystem,
+                server.wasm_grants.network,
+                serialize_call_context(&server.call_context),
             ],
         )?;
         Ok(())
@@ -360,20 +473,62 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         Ok(())
     }
 
+    async fn trash(&self, id: &Uuid, at: DateTime<Utc>) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+
+        conn.execute(
+            "UPDATE installed_servers SET deleted_at = ?2 WHERE id = ?1",
+            params![id.to_string(), at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    async fn restore(&self, id: &Uuid) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+
+        conn.execute(
+            "UPDATE installed_servers SET deleted_at = NULL, updated_at = ?2 WHERE id = ?1",
+            params![id.to_string(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    async fn list_trashed(&self) -> Result<Vec<InstalledServer>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM installed_servers WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+            SELECT_COLUMNS
+        ))?;
+
+        let rows: Vec<_> = stmt
+            .query_map([], Self::extract_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
+    }
+
     async fn list_enabled(&self, space_id: &str) -> Result<Vec<InstalledServer>> {
         let db = self.db.lock().await;
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE space_id = ?1 AND enabled = 1 ORDER BY created_at DESC",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE space_id = ?1 AND enabled = 1 AND deleted_at IS NULL ORDER BY created_at DESC",
+            SELECT_COLUMNS
         ))?;
 
         let rows: Vec<_> = stmt
             .query_map([space_id], Self::extract_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
-        rows.into_iter().map(|r| self.build_server(r)).collect()
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
     }
 
     async fn list_enabled_all(&self) -> Result<Vec<InstalledServer>> {
@@ -381,15 +536,17 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let conn = db.connection();
 
         let mut stmt = conn.prepare(&format!(
-            "SELECT {} FROM installed_servers WHERE enabled = 1 ORDER BY created_at DESC",
-            Self::SELECT_COLUMNS
+            "SELECT {} FROM installed_servers WHERE enabled = 1 AND deleted_at IS NULL ORDER BY created_at DESC",
+            SELECT_COLUMNS
         ))?;
 
         let rows: Vec<_> = stmt
             .query_map([], Self::extract_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
-        rows.into_iter().map(|r| self.build_server(r)).collect()
+        rows.into_iter()
+            .map(|r| build_server(&self.encryptor, r))
+            .collect()
     }
 
     async fn set_enabled(&self, id: &Uuid, enabled: bool) -> Result<()> {
@@ -414,6 +571,18 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         Ok(())
     }
 
+    async fn set_last_used(&self, id: &Uuid, at: DateTime<Utc>) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+
+        // Not a config change, so `updated_at` stays as it is
+        conn.execute(
+            "UPDATE installed_servers SET last_used_at = ?2 WHERE id = ?1",
+            params![id.to_string(), at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
     async fn update_inputs(
         &self,
         id: &Uuid,
@@ -422,7 +591,7 @@ impl InstalledServerRepository for SqliteInstalledServerRepository {
         let db = self.db.lock().await;
         let conn = db.connection();
 
-        let encrypted_inputs = self.encrypt_input_values(&input_values)?;
+        let encrypted_inputs = encrypt_input_values(&self.encryptor, &input_values)?;
 
         tracing::debug!(
             "[InstalledServerRepo] Updating inputs for {}: {} values (encrypted)",
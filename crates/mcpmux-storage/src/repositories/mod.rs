@@ -2,31 +2,46 @@
 
 mod app_settings_repository;
 mod credential_repository;
+mod environment_profile_repository;
+mod event_journal_repository;
 mod feature_set_repository;
 mod inbound_client_repository;
 mod inbound_mcp_client_repository;
 mod installed_server_repository;
 mod outbound_oauth_client_repository;
 mod server_feature_repository;
+mod server_group_repository;
 mod space_base_dir_repository;
 mod space_builtin_config_repository;
 mod space_repository;
+mod user_repository;
 mod workspace_binding_repository;
 
 pub use app_settings_repository::SqliteAppSettingsRepository;
 pub use credential_repository::SqliteCredentialRepository;
+pub use environment_profile_repository::SqliteEnvironmentProfileRepository;
+pub(crate) use environment_profile_repository::{decrypt_profile, encrypt_profile};
+pub use event_journal_repository::SqliteEventJournalRepository;
 pub use feature_set_repository::SqliteFeatureSetRepository;
 pub use inbound_client_repository::{
-    ApiKeyAuth, AuthorizationCode, InboundApiKey, InboundClient, InboundClientRepository,
-    RegistrationType, TokenRecord, TokenType,
+    hash_api_key, hash_token, ApiKeyAuth, AuthorizationCode, InboundApiKey, InboundClient,
+    InboundClientRepository, RegistrationType, SqliteInboundClientRepository, TokenRecord,
+    TokenType,
 };
 pub use inbound_mcp_client_repository::SqliteInboundMcpClientRepository;
 pub use installed_server_repository::SqliteInstalledServerRepository;
+pub(crate) use installed_server_repository::{
+    build_server, encrypt_input_values, serialize_call_context, serialize_json_map,
+    serialize_json_vec, serialize_source, serialize_tool_hooks, RawServerRow,
+    SELECT_COLUMNS as INSTALLED_SERVER_COLUMNS,
+};
 pub use outbound_oauth_client_repository::SqliteOutboundOAuthRepository;
 pub use server_feature_repository::{
     FeatureType, ServerFeature, ServerFeatureRepository, SqliteServerFeatureRepository,
 };
+pub use server_group_repository::SqliteServerGroupRepository;
 pub use space_base_dir_repository::SqliteSpaceBaseDirRepository;
 pub use space_builtin_config_repository::SqliteSpaceBuiltinConfigRepository;
 pub use space_repository::SqliteSpaceRepository;
+pub use user_repository::SqliteUserRepository;
 pub use workspace_binding_repository::SqliteWorkspaceBindingRepository;
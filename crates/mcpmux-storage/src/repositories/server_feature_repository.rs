@@ -8,10 +8,11 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, OptionalExtension};
+use rusqlite::{params, Connection, OptionalExtension};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use crate::database::DbReader;
 use crate::Database;
 
 /// Feature type enumeration
@@ -55,6 +56,10 @@ pub struct ServerFeature {
     pub discovered_at: DateTime<Utc>,
     pub last_seen_at: DateTime<Utc>,
     pub is_available: bool,
+    /// Clients keep this tool's schema until a change is accepted
+    pub schema_pinned: bool,
+    /// Changed JSON of a pinned tool, waiting to be accepted
+    pub pending_raw_json: Option<serde_json::Value>,
 }
 
 impl ServerFeature {
@@ -77,6 +82,8 @@ impl ServerFeature {
             discovered_at: now,
             last_seen_at: now,
             is_available: true,
+            schema_pinned: false,
+            pending_raw_json: None,
         }
     }
 
@@ -99,6 +106,8 @@ impl ServerFeature {
             discovered_at: now,
             last_seen_at: now,
             is_available: true,
+            schema_pinned: false,
+            pending_raw_json: None,
         }
     }
 
@@ -121,6 +130,8 @@ impl ServerFeature {
             discovered_at: now,
             last_seen_at: now,
             is_available: true,
+            schema_pinned: false,
+            pending_raw_json: None,
         }
     }
 
@@ -184,6 +195,14 @@ pub trait ServerFeatureRepository: Send + Sync {
         available_names: &[String],
     ) -> Result<()>;
 
+    /// Pin or unpin a tool's schema. Unpinning accepts a pending schema.
+    /// Returns the feature when it exists.
+    async fn set_schema_pinned(&self, id: &str, pinned: bool) -> Result<Option<ServerFeature>>;
+
+    /// Give clients a pinned tool's pending schema; it stays pinned at the
+    /// new schema. Returns the feature when it had a pending schema.
+    async fn accept_pending_schema(&self, id: &str) -> Result<Option<ServerFeature>>;
+
     /// Delete a feature
     async fn delete(&self, id: &str) -> Result<()>;
 
@@ -194,11 +213,55 @@ pub trait ServerFeatureRepository: Send + Sync {
 /// SQLite-backed implementation of ServerFeatureRepository
 pub struct SqliteServerFeatureRepository {
     db: Arc<Mutex<Database>>,
+    reader: DbReader,
 }
 
 impl SqliteServerFeatureRepository {
     pub fn new(db: Arc<Mutex<Database>>) -> Self {
-        Self { db }
+        Self {
+            reader: DbReader::new(db.clone()),
+            db,
+        }
+    }
+
+    fn upsert_on(conn: &Connection, feature: &ServerFeature) -> Result<()> {
+        let to_json = |json: &Option<serde_json::Value>| {
+            json.as_ref()
+                .map(|s| serde_json::to_string(s).unwrap_or_default())
+        };
+
+        // The pin is set by the user, never by discovery
+        conn.execute(
+            "INSERT INTO server_features 
+                (id, space_id, server_id, feature_type, feature_name, 
+                 display_name, description, raw_json, discovered_at, 
+                 last_seen_at, is_available, schema_pinned, pending_raw_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(space_id, server_id, feature_type, feature_name) DO UPDATE SET
+                display_name = COALESCE(?6, display_name),
+                description = COALESCE(?7, description),
+                raw_json = COALESCE(?8, raw_json),
+                last_seen_at = ?10,
+                is_available = ?11,
+                pending_raw_json = ?13",
+            params![
+                feature.id,
+                feature.space_id,
+                feature.server_id,
+                feature.feature_type.as_str(),
+                feature.feature_name,
+                feature.display_name,
+                feature.description,
+                to_json(&feature.raw_json),
+                feature.discovered_at.to_rfc3339(),
+                feature.last_seen_at.to_rfc3339(),
+                if feature.is_available { 1 } else { 0 },
+                feature.schema_pinned,
+                to_json(&feature.pending_raw_json),
+            ],
+        )?;
+
+        Ok(())
     }
 
     fn parse_datetime(s: &str) -> DateTime<Utc> {
@@ -213,6 +276,40 @@ impl SqliteServerFeatureRepository {
         Utc::now()
     }
 
+    fn get_on(conn: &Connection, id: &str) -> Result<Option<ServerFeature>> {
+        let result = conn
+            .query_row(
+                "SELECT id, space_id, server_id, feature_type, feature_name, 
+                        display_name, description, raw_json, discovered_at, 
+                        last_seen_at, is_available, schema_pinned, pending_raw_json
+                 FROM server_features
+                 WHERE id = ?",
+                params![id],
+                Self::row_to_feature,
+            )
+            .optional()?;
+
+        Ok(result)
+    }
+
+    /// Move a feature's pending JSON into `raw_json`; false when it had none
+    fn accept_pending_on(conn: &Connection, id: &str) -> Result<bool> {
+        let Some(pending) = Self::get_on(conn, id)?.and_then(|f| f.pending_raw_json) else {
+            return Ok(false);
+        };
+        let description = pending
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(str::to_string);
+        conn.execute(
+            "UPDATE server_features
+             SET raw_json = ?2, description = COALESCE(?3, description), pending_raw_json = NULL
+             WHERE id = ?1",
+            params![id, serde_json::to_string(&pending)?, description],
+        )?;
+        Ok(true)
+    }
+
     fn row_to_feature(row: &rusqlite::Row<'_>) -> rusqlite::Result<ServerFeature> {
         let raw_json_str: Option<String> = row.get(7)?;
         Ok(ServerFeature {
@@ -228,6 +325,10 @@ impl SqliteServerFeatureRepository {
             discovered_at: Self::parse_datetime(&row.get::<_, String>(8)?),
             last_seen_at: Self::parse_datetime(&row.get::<_, String>(9)?),
             is_available: row.get::<_, i32>(10)? == 1,
+            schema_pinned: row.get(11)?,
+            pending_raw_json: row
+                .get::<_, Option<String>>(12)?
+                .and_then(|s| serde_json::from_str(&s).ok()),
         })
     }
 }
@@ -235,43 +336,45 @@ impl SqliteServerFeatureRepository {
 #[async_trait]
 impl ServerFeatureRepository for SqliteServerFeatureRepository {
     async fn list_by_space(&self, space_id: &str) -> Result<Vec<ServerFeature>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, space_id, server_id, feature_type, feature_name, 
-                    display_name, description, raw_json, discovered_at, 
-                    last_seen_at, is_available
-             FROM server_features
-             WHERE space_id = ?
-             ORDER BY server_id, feature_type, feature_name",
-        )?;
-
-        let features = stmt
-            .query_map(params![space_id], Self::row_to_feature)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(features)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, space_id, server_id, feature_type, feature_name, 
+                            display_name, description, raw_json, discovered_at, 
+                            last_seen_at, is_available, schema_pinned, pending_raw_json
+                     FROM server_features
+                     WHERE space_id = ?
+                     ORDER BY server_id, feature_type, feature_name",
+                )?;
+
+                let features = stmt
+                    .query_map(params![space_id], Self::row_to_feature)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(features)
+            })
+            .await
     }
 
     async fn list_by_server(&self, space_id: &str, server_id: &str) -> Result<Vec<ServerFeature>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, space_id, server_id, feature_type, feature_name, 
-                    display_name, description, raw_json, discovered_at, 
-                    last_seen_at, is_available
-             FROM server_features
-             WHERE space_id = ? AND server_id = ?
-             ORDER BY feature_type, feature_name",
-        )?;
-
-        let features = stmt
-            .query_map(params![space_id, server_id], Self::row_to_feature)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(features)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, space_id, server_id, feature_type, feature_name, 
+                            display_name, description, raw_json, discovered_at, 
+                            last_seen_at, is_available, schema_pinned, pending_raw_json
+                     FROM server_features
+                     WHERE space_id = ? AND server_id = ?
+                     ORDER BY feature_type, feature_name",
+                )?;
+
+                let features = stmt
+                    .query_map(params![space_id, server_id], Self::row_to_feature)?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(features)
+            })
+            .await
     }
 
     async fn list_by_type(
@@ -280,45 +383,31 @@ impl ServerFeatureRepository for SqliteServerFeatureRepository {
         server_id: &str,
         feature_type: FeatureType,
     ) -> Result<Vec<ServerFeature>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let mut stmt = conn.prepare(
-            "SELECT id, space_id, server_id, feature_type, feature_name, 
-                    display_name, description, raw_json, discovered_at, 
-                    last_seen_at, is_available
-             FROM server_features
-             WHERE space_id = ? AND server_id = ? AND feature_type = ?
-             ORDER BY feature_name",
-        )?;
-
-        let features = stmt
-            .query_map(
-                params![space_id, server_id, feature_type.as_str()],
-                Self::row_to_feature,
-            )?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(features)
+        self.reader
+            .read(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, space_id, server_id, feature_type, feature_name, 
+                            display_name, description, raw_json, discovered_at, 
+                            last_seen_at, is_available, schema_pinned, pending_raw_json
+                     FROM server_features
+                     WHERE space_id = ? AND server_id = ? AND feature_type = ?
+                     ORDER BY feature_name",
+                )?;
+
+                let features = stmt
+                    .query_map(
+                        params![space_id, server_id, feature_type.as_str()],
+                        Self::row_to_feature,
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(features)
+            })
+            .await
     }
 
     async fn get(&self, id: &str) -> Result<Option<ServerFeature>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let result = conn
-            .query_row(
-                "SELECT id, space_id, server_id, feature_type, feature_name, 
-                        display_name, description, raw_json, discovered_at, 
-                        last_seen_at, is_available
-                 FROM server_features
-                 WHERE id = ?",
-                params![id],
-                Self::row_to_feature,
-            )
-            .optional()?;
-
-        Ok(result)
+        self.reader.read(|conn| Self::get_on(conn, id)).await
     }
 
     async fn get_by_name(
@@ -328,68 +417,43 @@ impl ServerFeatureRepository for SqliteServerFeatureRepository {
         feature_type: FeatureType,
         name: &str,
     ) -> Result<Option<ServerFeature>> {
-        let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let result = conn
-            .query_row(
-                "SELECT id, space_id, server_id, feature_type, feature_name, 
-                        display_name, description, raw_json, discovered_at, 
-                        last_seen_at, is_available
-                 FROM server_features
-                 WHERE space_id = ? AND server_id = ? AND feature_type = ? AND feature_name = ?",
-                params![space_id, server_id, feature_type.as_str(), name],
-                Self::row_to_feature,
-            )
-            .optional()?;
-
-        Ok(result)
+        self.reader
+            .read(|conn| {
+                let result = conn
+                    .query_row(
+                        "SELECT id, space_id, server_id, feature_type, feature_name, 
+                                display_name, description, raw_json, discovered_at, 
+                                last_seen_at, is_available, schema_pinned, pending_raw_json
+                         FROM server_features
+                         WHERE space_id = ? AND server_id = ? AND feature_type = ? AND feature_name = ?",
+                        params![space_id, server_id, feature_type.as_str(), name],
+                        Self::row_to_feature,
+                    )
+                    .optional()?;
+
+                Ok(result)
+            })
+            .await
     }
 
     async fn upsert(&self, feature: &ServerFeature) -> Result<()> {
         let db = self.db.lock().await;
-        let conn = db.connection();
-
-        let raw_json_str = feature
-            .raw_json
-            .as_ref()
-            .map(|s| serde_json::to_string(s).unwrap_or_default());
-
-        conn.execute(
-            "INSERT INTO server_features 
-                (id, space_id, server_id, feature_type, feature_name, 
-                 display_name, description, raw_json, discovered_at, 
-                 last_seen_at, is_available)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
-             ON CONFLICT(space_id, server_id, feature_type, feature_name) DO UPDATE SET
-                display_name = COALESCE(?6, display_name),
-                description = COALESCE(?7, description),
-                raw_json = COALESCE(?8, raw_json),
-                last_seen_at = ?10,
-                is_available = ?11",
-            params![
-                feature.id,
-                feature.space_id,
-                feature.server_id,
-                feature.feature_type.as_str(),
-                feature.feature_name,
-                feature.display_name,
-                feature.description,
-                raw_json_str,
-                feature.discovered_at.to_rfc3339(),
-                feature.last_seen_at.to_rfc3339(),
-                if feature.is_available { 1 } else { 0 },
-            ],
-        )?;
-
-        Ok(())
+        Self::upsert_on(db.connection(), feature)
     }
 
+    /// Upsert every feature in one transaction, so a discovery of many
+    /// features takes the lock and syncs the WAL once
     async fn upsert_many(&self, features: &[ServerFeature]) -> Result<()> {
-        for feature in features {
-            self.upsert(feature).await?;
+        if features.is_empty() {
+            return Ok(());
         }
-        Ok(())
+        let db = self.db.lock().await;
+        db.transaction(|conn| {
+            for feature in features {
+                Self::upsert_on(conn, feature)?;
+            }
+            Ok(())
+        })
     }
 
     async fn mark_unavailable_except(
@@ -444,6 +508,28 @@ impl ServerFeatureRepository for SqliteServerFeatureRepository {
         Ok(())
     }
 
+    async fn set_schema_pinned(&self, id: &str, pinned: bool) -> Result<Option<ServerFeature>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        if !pinned {
+            Self::accept_pending_on(conn, id)?;
+        }
+        conn.execute(
+            "UPDATE server_features SET schema_pinned = ?2 WHERE id = ?1",
+            params![id, pinned],
+        )?;
+        Self::get_on(conn, id)
+    }
+
+    async fn accept_pending_schema(&self, id: &str) -> Result<Option<ServerFeature>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        if !Self::accept_pending_on(conn, id)? {
+            return Ok(None);
+        }
+        Self::get_on(conn, id)
+    }
+
     async fn delete(&self, id: &str) -> Result<()> {
         let db = self.db.lock().await;
         let conn = db.connection();
@@ -468,24 +554,25 @@ impl ServerFeatureRepository for SqliteServerFeatureRepository {
 // Conversions between storage and core ServerFeature types
 impl From<ServerFeature> for mcpmux_core::ServerFeature {
     fn from(f: ServerFeature) -> Self {
-        mcpmux_core::ServerFeature {
-            id: uuid::Uuid::parse_str(&f.id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
-            space_id: f.space_id,
-            server_id: f.server_id,
-            server_alias: None, // Enriched later with prefix from cache
-            feature_type: match f.feature_type {
-                FeatureType::Tool => mcpmux_core::FeatureType::Tool,
-                FeatureType::Prompt => mcpmux_core::FeatureType::Prompt,
-                FeatureType::Resource => mcpmux_core::FeatureType::Resource,
-            },
-            feature_name: f.feature_name,
-            display_name: f.display_name,
-            description: f.description,
-            raw_json: f.raw_json,
-            discovered_at: f.discovered_at,
-            last_seen_at: f.last_seen_at,
-            is_available: f.is_available,
-        }
+        let feature_type = match f.feature_type {
+            FeatureType::Tool => mcpmux_core::FeatureType::Tool,
+            FeatureType::Prompt => mcpmux_core::FeatureType::Prompt,
+            FeatureType::Resource => mcpmux_core::FeatureType::Resource,
+        };
+        // `server_alias` is enriched later with the prefix from the cache
+        let mut feature =
+            mcpmux_core::ServerFeature::new(f.space_id, f.server_id, feature_type, f.feature_name);
+        feature.id = uuid::Uuid::parse_str(&f.id).unwrap_or_else(|_| uuid::Uuid::new_v4());
+        feature.display_name = f.display_name;
+        feature.description = f.description;
+        feature.annotations = mcpmux_core::ToolAnnotations::from_raw_json(f.raw_json.as_ref());
+        feature.raw_json = f.raw_json;
+        feature.discovered_at = f.discovered_at;
+        feature.last_seen_at = f.last_seen_at;
+        feature.is_available = f.is_available;
+        feature.schema_pinned = f.schema_pinned;
+        feature.pending_raw_json = f.pending_raw_json;
+        feature
     }
 }
 
@@ -495,11 +582,7 @@ impl From<mcpmux_core::ServerFeature> for ServerFeature {
             id: f.id.to_string(),
             space_id: f.space_id,
             server_id: f.server_id,
-            feature_type: match f.feature_type {
-                mcpmux_core::FeatureType::Tool => FeatureType::Tool,
-                mcpmux_core::FeatureType::Prompt => FeatureType::Prompt,
-                mcpmux_core::FeatureType::Resource => FeatureType::Resource,
-            },
+            feature_type: FeatureType::parse(f.feature_type.as_str()).unwrap_or(FeatureType::Tool),
             feature_name: f.feature_name,
             display_name: f.display_name,
             description: f.description,
@@ -507,6 +590,8 @@ impl From<mcpmux_core::ServerFeature> for ServerFeature {
             discovered_at: f.discovered_at,
             last_seen_at: f.last_seen_at,
             is_available: f.is_available,
+            schema_pinned: f.schema_pinned,
+            pending_raw_json: f.pending_raw_json,
         }
     }
 }
@@ -624,4 +709,44 @@ mod tests {
         assert_eq!(retrieved.feature_name, "read_file");
         assert_eq!(retrieved.display_name, Some("Read File".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_schema_pin() {
+        let db = setup_test_db().await;
+        let repo = SqliteServerFeatureRepository::new(db);
+
+        let mut feature = ServerFeature::new_tool(DEFAULT_SPACE_ID, "server1", "search")
+            .with_raw_json(serde_json::json!({ "name": "search", "description": "v1" }));
+        repo.upsert(&feature).await.unwrap();
+        assert!(repo
+            .accept_pending_schema(&feature.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        let pinned = repo
+            .set_schema_pinned(&feature.id, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pinned.schema_pinned);
+
+        // Discovery holds the new JSON back without touching the pin
+        feature.pending_raw_json =
+            Some(serde_json::json!({ "name": "search", "description": "v2" }));
+        repo.upsert(&feature).await.unwrap();
+        let held = repo.get(&feature.id).await.unwrap().unwrap();
+        assert!(held.schema_pinned);
+        assert_eq!(held.raw_json.unwrap()["description"], "v1");
+
+        let accepted = repo
+            .accept_pending_schema(&feature.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(accepted.raw_json.unwrap()["description"], "v2");
+        assert_eq!(accepted.description.as_deref(), Some("v2"));
+        assert!(accepted.pending_raw_json.is_none());
+        assert!(accepted.schema_pinned);
+    }
 }
@@ -0,0 +1,179 @@
+//! SQLite implementation of ServerGroupRepository.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mcpmux_core::{ServerGroup, ServerGroupRepository};
+use rusqlite::{params, OptionalExtension};
+use tokio::sync::Mutex;
+
+use crate::Database;
+
+/// SQLite-backed implementation of [`ServerGroupRepository`].
+pub struct SqliteServerGroupRepository {
+    db: Arc<Mutex<Database>>,
+}
+
+impl SqliteServerGroupRepository {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self { db }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str =
+        "id, space_id, name, icon, server_ids, feature_set_id, created_at, updated_at";
+
+    fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ServerGroup> {
+        let server_ids: String = row.get(4)?;
+        Ok(ServerGroup {
+            id: row.get(0)?,
+            space_id: row.get(1)?,
+            name: row.get(2)?,
+            icon: row.get(3)?,
+            server_ids: serde_json::from_str(&server_ids).unwrap_or_default(),
+            feature_set_id: row.get(5)?,
+            created_at: Self::parse_datetime(&row.get::<_, String>(6)?),
+            updated_at: Self::parse_datetime(&row.get::<_, String>(7)?),
+        })
+    }
+
+    /// Turn the UNIQUE(space_id, name) collision into a readable message.
+    fn map_write_error(e: rusqlite::Error, name: &str) -> anyhow::Error {
+        if e.to_string().to_lowercase().contains("unique") {
+            anyhow::anyhow!("A server group named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+}
+
+#[async_trait]
+impl ServerGroupRepository for SqliteServerGroupRepository {
+    async fn list_for_space(&self, space_id: &str) -> Result<Vec<ServerGroup>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM server_groups WHERE space_id = ? ORDER BY name COLLATE NOCASE ASC",
+            Self::COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map(params![space_id], Self::map_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ServerGroup>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let group = conn
+            .query_row(
+                &format!("SELECT {} FROM server_groups WHERE id = ?", Self::COLUMNS),
+                params![id],
+                Self::map_row,
+            )
+            .optional()?;
+        Ok(group)
+    }
+
+    async fn create(&self, group: &ServerGroup) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO server_groups
+             (id, space_id, name, icon, server_ids, feature_set_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                group.id,
+                group.space_id,
+                group.name,
+                group.icon,
+                serde_json::to_string(&group.server_ids)?,
+                group.feature_set_id,
+                group.created_at.to_rfc3339(),
+                group.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Self::map_write_error(e, &group.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, group: &ServerGroup) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "UPDATE server_groups
+             SET name = ?2, icon = ?3, server_ids = ?4, feature_set_id = ?5, updated_at = ?6
+             WHERE id = ?1",
+            params![
+                group.id,
+                group.name,
+                group.icon,
+                serde_json::to_string(&group.server_ids)?,
+                group.feature_set_id,
+                group.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Self::map_write_error(e, &group.name))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute("DELETE FROM server_groups WHERE id = ?", params![id])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Default space ID created by migration
+    const DEFAULT_SPACE_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    #[tokio::test]
+    async fn test_crud_and_unique_names() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let repo = SqliteServerGroupRepository::new(db);
+
+        let mut group = ServerGroup::new("AWS", DEFAULT_SPACE_ID).with_icon("☁️");
+        repo.create(&group).await.unwrap();
+
+        group.set_servers(["aws-docs".to_string(), "aws-cdk".to_string()]);
+        group.feature_set_id = Some("fs_aws".to_string());
+        repo.update(&group).await.unwrap();
+
+        let found = repo.get(&group.id).await.unwrap().unwrap();
+        assert_eq!(found.server_ids, vec!["aws-docs", "aws-cdk"]);
+        assert_eq!(found.feature_set_id.as_deref(), Some("fs_aws"));
+
+        // Names are unique per space, ignoring case
+        let duplicate = ServerGroup::new("aws", DEFAULT_SPACE_ID);
+        let err = repo.create(&duplicate).await.unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        repo.create(&ServerGroup::new("Personal", DEFAULT_SPACE_ID))
+            .await
+            .unwrap();
+        let names: Vec<String> = repo
+            .list_for_space(DEFAULT_SPACE_ID)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.name)
+            .collect();
+        assert_eq!(names, vec!["AWS", "Personal"]);
+
+        repo.delete(&group.id).await.unwrap();
+        assert!(repo.get(&group.id).await.unwrap().is_none());
+    }
+}
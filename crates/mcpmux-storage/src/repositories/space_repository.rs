@@ -37,20 +37,22 @@ impl SqliteSpaceRepository {
 
     /// Columns selected for every `Space` read. Order must match `map_row`.
     const COLUMNS: &'static str =
-        "id, name, icon, description, is_default, sort_order, created_at, updated_at";
+        "id, name, icon, description, is_default, sort_order, created_at, updated_at, owner_user_id";
 
     fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Space> {
         let id_str: String = row.get(0)?;
-        Ok(Space {
-            id: id_str.parse().unwrap_or_else(|_| Uuid::new_v4()),
-            name: row.get(1)?,
-            icon: row.get(2)?,
-            description: row.get(3)?,
-            is_default: row.get::<_, i32>(4)? == 1,
-            sort_order: row.get(5)?,
-            created_at: Self::parse_datetime(&row.get::<_, String>(6)?),
-            updated_at: Self::parse_datetime(&row.get::<_, String>(7)?),
-        })
+        let mut space = Space::new(row.get::<_, String>(1)?);
+        space.id = id_str.parse().unwrap_or_else(|_| Uuid::new_v4());
+        space.icon = row.get(2)?;
+        space.description = row.get(3)?;
+        space.is_default = row.get::<_, i32>(4)? == 1;
+        space.sort_order = row.get(5)?;
+        space.owner_id = row
+            .get::<_, Option<String>>(8)?
+            .and_then(|id| id.parse().ok());
+        space.created_at = Self::parse_datetime(&row.get::<_, String>(6)?);
+        space.updated_at = Self::parse_datetime(&row.get::<_, String>(7)?);
+        Ok(space)
     }
 }
 
@@ -92,8 +94,8 @@ impl SpaceRepository for SqliteSpaceRepository {
         let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
         conn.execute(
-            "INSERT INTO spaces (id, name, icon, description, is_default, sort_order, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO spaces (id, name, icon, description, is_default, sort_order, created_at, updated_at, owner_user_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 space_id,
                 space.name,
@@ -103,6 +105,7 @@ impl SpaceRepository for SqliteSpaceRepository {
                 space.sort_order,
                 space.created_at.to_rfc3339(),
                 space.updated_at.to_rfc3339(),
+                space.owner_id.map(|id| id.to_string()),
             ],
         )?;
 
@@ -194,6 +197,22 @@ impl SpaceRepository for SqliteSpaceRepository {
 
         Ok(())
     }
+
+    async fn set_owner(&self, id: &Uuid, owner_id: Option<&Uuid>) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+
+        let rows_affected = conn.execute(
+            "UPDATE spaces SET owner_user_id = ?2 WHERE id = ?1",
+            params![id.to_string(), owner_id.map(|id| id.to_string())],
+        )?;
+
+        if rows_affected == 0 {
+            anyhow::bail!("Space not found: {}", id);
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
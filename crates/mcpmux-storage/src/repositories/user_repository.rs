@@ -0,0 +1,200 @@
+//! SQLite implementation of UserRepository.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mcpmux_core::{User, UserRepository, UserRole};
+use rusqlite::{params, OptionalExtension};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::Database;
+
+/// SQLite-backed implementation of [`UserRepository`].
+pub struct SqliteUserRepository {
+    db: Arc<Mutex<Database>>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self { db }
+    }
+
+    fn parse_datetime(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    /// Columns selected for every read. Order must match `map_row`.
+    const COLUMNS: &'static str = "id, name, role, created_at, updated_at";
+
+    fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<User> {
+        let id: String = row.get(0)?;
+        Ok(User {
+            id: id.parse().unwrap_or_else(|_| Uuid::new_v4()),
+            name: row.get(1)?,
+            role: UserRole::parse(&row.get::<_, String>(2)?).unwrap_or_default(),
+            created_at: Self::parse_datetime(&row.get::<_, String>(3)?),
+            updated_at: Self::parse_datetime(&row.get::<_, String>(4)?),
+        })
+    }
+
+    /// Turn the UNIQUE(name) collision into a readable message.
+    fn map_write_error(e: rusqlite::Error, name: &str) -> anyhow::Error {
+        if e.to_string().to_lowercase().contains("unique") {
+            anyhow::anyhow!("A user named \"{name}\" already exists")
+        } else {
+            anyhow::Error::from(e)
+        }
+    }
+
+    async fn query_one(&self, filter: &str, value: &str) -> Result<Option<User>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let user = conn
+            .query_row(
+                &format!("SELECT {} FROM users WHERE {}", Self::COLUMNS, filter),
+                params![value],
+                Self::map_row,
+            )
+            .optional()?;
+        Ok(user)
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn list(&self) -> Result<Vec<User>> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM users ORDER BY name COLLATE NOCASE ASC",
+            Self::COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map([], Self::map_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<User>> {
+        self.query_one("id = ?", &id.to_string()).await
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<User>> {
+        self.query_one("name = ? COLLATE NOCASE", name).await
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> Result<Option<User>> {
+        self.query_one("token_hash = ?", token_hash).await
+    }
+
+    async fn create(&self, user: &User) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO users (id, name, role, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                user.id.to_string(),
+                user.name,
+                user.role.as_str(),
+                user.created_at.to_rfc3339(),
+                user.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Self::map_write_error(e, &user.name))?;
+        Ok(())
+    }
+
+    async fn update(&self, user: &User) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let rows_affected = conn
+            .execute(
+                "UPDATE users SET name = ?2, role = ?3, updated_at = ?4 WHERE id = ?1",
+                params![
+                    user.id.to_string(),
+                    user.name,
+                    user.role.as_str(),
+                    user.updated_at.to_rfc3339(),
+                ],
+            )
+            .map_err(|e| Self::map_write_error(e, &user.name))?;
+        if rows_affected == 0 {
+            anyhow::bail!("User not found: {}", user.id);
+        }
+        Ok(())
+    }
+
+    async fn set_token_hash(&self, id: &Uuid, token_hash: Option<&str>) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        let rows_affected = conn.execute(
+            "UPDATE users SET token_hash = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id.to_string(), token_hash, Utc::now().to_rfc3339()],
+        )?;
+        if rows_affected == 0 {
+            anyhow::bail!("User not found: {}", id);
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<()> {
+        let db = self.db.lock().await;
+        let conn = db.connection();
+        // ON DELETE SET NULL releases their Spaces and clients
+        conn.execute("DELETE FROM users WHERE id = ?", params![id.to_string()])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SqliteSpaceRepository;
+    use mcpmux_core::{Space, SpaceRepository};
+
+    #[tokio::test]
+    async fn test_crud_tokens_and_ownership() {
+        let db = Arc::new(Mutex::new(Database::open_in_memory().unwrap()));
+        let users = SqliteUserRepository::new(db.clone());
+        let spaces = SqliteSpaceRepository::new(db);
+
+        let mut alice = User::new("alice", UserRole::Member);
+        users.create(&alice).await.unwrap();
+        let err = users
+            .create(&User::new("Alice", UserRole::Admin))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        users
+            .set_token_hash(&alice.id, Some("hash_a"))
+            .await
+            .unwrap();
+        let found = users.get_by_token_hash("hash_a").await.unwrap().unwrap();
+        assert_eq!(found.id, alice.id);
+        assert_eq!(found.role, UserRole::Member);
+        assert!(users.get_by_token_hash("hash_b").await.unwrap().is_none());
+
+        alice.role = UserRole::Admin;
+        users.update(&alice).await.unwrap();
+        let found = users.get_by_name("ALICE").await.unwrap().unwrap();
+        assert!(found.is_admin());
+
+        let space = Space::new("Alice's").with_owner(alice.id);
+        spaces.create(&space).await.unwrap();
+        let stored = spaces.get(&space.id).await.unwrap().unwrap();
+        assert_eq!(stored.owner_id, Some(alice.id));
+
+        // Deleting the user hands the Space back to admins
+        users.delete(&alice.id).await.unwrap();
+        assert!(users.get(&alice.id).await.unwrap().is_none());
+        let stored = spaces.get(&space.id).await.unwrap().unwrap();
+        assert_eq!(stored.owner_id, None);
+    }
+}
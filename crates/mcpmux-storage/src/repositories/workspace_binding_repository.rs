@@ -69,15 +69,14 @@ impl SqliteWorkspaceBindingRepository {
         let updated_at: String = row.get(4)?;
         let binding_type: String = row.get(5)?;
 
-        Ok(WorkspaceBinding {
-            id: id_str.parse().unwrap_or_else(|_| Uuid::new_v4()),
-            workspace_root,
-            binding_type: BindingType::parse(&binding_type),
-            space_id: space_id_str.parse().unwrap_or_else(|_| Uuid::nil()),
-            feature_set_ids: Vec::new(), // filled in by caller
-            created_at: Self::parse_datetime(&created_at),
-            updated_at: Self::parse_datetime(&updated_at),
-        })
+        let space_id = space_id_str.parse().unwrap_or_else(|_| Uuid::nil());
+        // `feature_set_ids` filled in by caller
+        let mut binding = WorkspaceBinding::new_multi(workspace_root, space_id, Vec::new());
+        binding.id = id_str.parse().unwrap_or_else(|_| Uuid::new_v4());
+        binding.binding_type = BindingType::parse(&binding_type);
+        binding.created_at = Self::parse_datetime(&created_at);
+        binding.updated_at = Self::parse_datetime(&updated_at);
+        Ok(binding)
     }
 
     /// Bulk-load `(binding_id, feature_set_ids)` from the junction for the
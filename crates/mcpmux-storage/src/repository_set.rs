@@ -0,0 +1,244 @@
+//! Every repository over one database, SQLite or PostgreSQL.
+//!
+//! Hosts pick the backend with a [`StorageConfig`] and build everything from
+//! the resulting [`RepositorySet`], so none of them names a concrete
+//! repository type.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use mcpmux_core::{
+    AppSettingsRepository, CredentialRepository, EnvironmentProfileRepository,
+    EventJournalRepository, FeatureSetRepository, InboundMcpClientRepository,
+    InstalledServerRepository, OutboundOAuthRepository, ServerGroupRepository,
+    SpaceBaseDirRepository, SpaceBuiltinConfigRepository, SpaceRepository, UserRepository,
+    WorkspaceBindingRepository,
+};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::postgres::{
+    PgAppSettingsRepository, PgCredentialRepository, PgDatabase, PgEnvironmentProfileRepository,
+    PgEventJournalRepository, PgFeatureSetRepository, PgInboundClientRepository,
+    PgInboundMcpClientRepository, PgInstalledServerRepository, PgOutboundOAuthRepository,
+    PgServerFeatureRepository, PgServerGroupRepository, PgSpaceBaseDirRepository,
+    PgSpaceBuiltinConfigRepository, PgSpaceRepository, PgUserRepository,
+    PgWorkspaceBindingRepository,
+};
+use crate::{
+    Database, FieldEncryptor, InboundClientRepository, ServerFeatureRepository,
+    SqliteAppSettingsRepository, SqliteCredentialRepository, SqliteEnvironmentProfileRepository,
+    SqliteEventJournalRepository, SqliteFeatureSetRepository, SqliteInboundClientRepository,
+    SqliteInboundMcpClientRepository, SqliteInstalledServerRepository,
+    SqliteOutboundOAuthRepository, SqliteServerFeatureRepository, SqliteServerGroupRepository,
+    SqliteSpaceBaseDirRepository, SqliteSpaceBuiltinConfigRepository, SqliteSpaceRepository,
+    SqliteUserRepository, SqliteWorkspaceBindingRepository, DATABASE_FILE,
+};
+
+/// Environment variable with a PostgreSQL URL to store everything in instead
+/// of the SQLite file in the data directory.
+pub const DATABASE_URL_ENV_VAR: &str = "MCPMUX_DATABASE_URL";
+
+/// Which database the repositories live in.
+#[derive(Clone, PartialEq, Eq)]
+pub enum StorageConfig {
+    /// A SQLite file, for a gateway on one machine
+    Sqlite { path: PathBuf },
+    /// A PostgreSQL database shared by several gateways
+    Postgres { url: String },
+}
+
+impl StorageConfig {
+    /// PostgreSQL when `MCPMUX_DATABASE_URL` is set, otherwise the SQLite
+    /// file in `data_dir`.
+    pub fn from_env(data_dir: &Path) -> Self {
+        match std::env::var(DATABASE_URL_ENV_VAR) {
+            Ok(url) if !url.trim().is_empty() => Self::Postgres { url },
+            _ => Self::Sqlite {
+                path: data_dir.join(DATABASE_FILE),
+            },
+        }
+    }
+}
+
+// Hand-written so a URL's password never ends up in a log line
+impl std::fmt::Debug for StorageConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sqlite { path } => f.debug_struct("Sqlite").field("path", path).finish(),
+            Self::Postgres { .. } => f.debug_struct("Postgres").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// All repositories, over one database.
+#[derive(Clone)]
+pub struct RepositorySet {
+    pub spaces: Arc<dyn SpaceRepository>,
+    pub installed_servers: Arc<dyn InstalledServerRepository>,
+    pub credentials: Arc<dyn CredentialRepository>,
+    pub outbound_oauth: Arc<dyn OutboundOAuthRepository>,
+    pub feature_sets: Arc<dyn FeatureSetRepository>,
+    /// Discovered features, with the storage-level queries the UI uses
+    pub server_features: Arc<dyn ServerFeatureRepository>,
+    /// The same features through the core trait, for the gateway services
+    pub server_features_core: Arc<dyn mcpmux_core::ServerFeatureRepository>,
+    pub inbound_clients: Arc<dyn InboundClientRepository>,
+    pub inbound_mcp_clients: Arc<dyn InboundMcpClientRepository>,
+    pub workspace_bindings: Arc<dyn WorkspaceBindingRepository>,
+    pub space_base_dirs: Arc<dyn SpaceBaseDirRepository>,
+    pub builtin_configs: Arc<dyn SpaceBuiltinConfigRepository>,
+    pub environment_profiles: Arc<dyn EnvironmentProfileRepository>,
+    pub event_journal: Arc<dyn EventJournalRepository>,
+    pub settings: Arc<dyn AppSettingsRepository>,
+    pub server_groups: Arc<dyn ServerGroupRepository>,
+    pub users: Arc<dyn UserRepository>,
+    sqlite: Option<Arc<Mutex<Database>>>,
+}
+
+impl RepositorySet {
+    /// Open the configured database (applying migrations) and build the
+    /// repositories over it.
+    pub async fn open(config: &StorageConfig, encryptor: Arc<FieldEncryptor>) -> Result<Self> {
+        match config {
+            StorageConfig::Sqlite { path } => {
+                info!("Opening database at {:?}", path);
+                let db = Database::open(path)?;
+                Ok(Self::sqlite(Arc::new(Mutex::new(db)), encryptor))
+            }
+            StorageConfig::Postgres { url } => {
+                info!("Connecting to PostgreSQL database");
+                let db = PgDatabase::connect(url).await?;
+                Ok(Self::postgres(db, encryptor))
+            }
+        }
+    }
+
+    /// Repositories over an open SQLite database.
+    pub fn sqlite(db: Arc<Mutex<Database>>, encryptor: Arc<FieldEncryptor>) -> Self {
+        let server_features = Arc::new(SqliteServerFeatureRepository::new(db.clone()));
+        Self {
+            spaces: Arc::new(SqliteSpaceRepository::new(db.clone())),
+            installed_servers: Arc::new(SqliteInstalledServerRepository::new(
+                db.clone(),
+                encryptor.clone(),
+            )),
+            credentials: Arc::new(SqliteCredentialRepository::new(
+                db.clone(),
+                encryptor.clone(),
+            )),
+            outbound_oauth: Arc::new(SqliteOutboundOAuthRepository::new(db.clone())),
+            feature_sets: Arc::new(SqliteFeatureSetRepository::new(db.clone())),
+            server_features: server_features.clone(),
+            server_features_core: server_features,
+            inbound_clients: Arc::new(SqliteInboundClientRepository::new(db.clone())),
+            inbound_mcp_clients: Arc::new(SqliteInboundMcpClientRepository::new(db.clone())),
+            workspace_bindings: Arc::new(SqliteWorkspaceBindingRepository::new(db.clone())),
+            space_base_dirs: Arc::new(SqliteSpaceBaseDirRepository::new(db.clone())),
+            builtin_configs: Arc::new(SqliteSpaceBuiltinConfigRepository::new(db.clone())),
+            environment_profiles: Arc::new(SqliteEnvironmentProfileRepository::new(
+                db.clone(),
+                encryptor,
+            )),
+            event_journal: Arc::new(SqliteEventJournalRepository::new(db.clone())),
+            settings: Arc::new(SqliteAppSettingsRepository::new(db.clone())),
+            server_groups: Arc::new(SqliteServerGroupRepository::new(db.clone())),
+            users: Arc::new(SqliteUserRepository::new(db.clone())),
+            sqlite: Some(db),
+        }
+    }
+
+    /// Repositories over a PostgreSQL database.
+    pub fn postgres(db: PgDatabase, encryptor: Arc<FieldEncryptor>) -> Self {
+        let server_features = Arc::new(PgServerFeatureRepository::new(db.clone()));
+        Self {
+            spaces: Arc::new(PgSpaceRepository::new(db.clone())),
+            installed_servers: Arc::new(PgInstalledServerRepository::new(
+                db.clone(),
+                encryptor.clone(),
+            )),
+            credentials: Arc::new(PgCredentialRepository::new(db.clone(), encryptor.clone())),
+            outbound_oauth: Arc::new(PgOutboundOAuthRepository::new(db.clone())),
+            feature_sets: Arc::new(PgFeatureSetRepository::new(db.clone())),
+            server_features: server_features.clone(),
+            server_features_core: server_features,
+            inbound_clients: Arc::new(PgInboundClientRepository::new(db.clone())),
+            inbound_mcp_clients: Arc::new(PgInboundMcpClientRepository::new(db.clone())),
+            workspace_bindings: Arc::new(PgWorkspaceBindingRepository::new(db.clone())),
+            space_base_dirs: Arc::new(PgSpaceBaseDirRepository::new(db.clone())),
+            builtin_configs: Arc::new(PgSpaceBuiltinConfigRepository::new(db.clone())),
+            environment_profiles: Arc::new(PgEnvironmentProfileRepository::new(
+                db.clone(),
+                encryptor,
+            )),
+            event_journal: Arc::new(PgEventJournalRepository::new(db.clone())),
+            settings: Arc::new(PgAppSettingsRepository::new(db.clone())),
+            server_groups: Arc::new(PgServerGroupRepository::new(db.clone())),
+            users: Arc::new(PgUserRepository::new(db)),
+            sqlite: None,
+        }
+    }
+
+    /// The SQLite database, for what only works on the local file (backups,
+    /// `db doctor`, key rotation). `None` on PostgreSQL.
+    pub fn sqlite_database(&self) -> Option<Arc<Mutex<Database>>> {
+        self.sqlite.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_hides_database_url() {
+        let config = StorageConfig::Postgres {
+            url: "postgres://admin:hunter2@db/mcpmux".to_string(),
+        };
+        assert!(!format!("{config:?}").contains("hunter2"));
+    }
+
+    #[tokio::test]
+    async fn test_open_sqlite() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = StorageConfig::Sqlite {
+            path: dir.path().join(DATABASE_FILE),
+        };
+        let encryptor =
+            Arc::new(FieldEncryptor::new(&crate::generate_master_key().unwrap()).unwrap());
+        let repos = RepositorySet::open(&config, encryptor).await.unwrap();
+
+        assert!(repos.sqlite_database().is_some());
+        assert!(repos.spaces.get_default().await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_set_shares_one_database() {
+        let Some(db) = crate::postgres::test_database().await else {
+            return;
+        };
+        let encryptor =
+            Arc::new(FieldEncryptor::new(&crate::generate_master_key().unwrap()).unwrap());
+        let repos = RepositorySet::postgres(db, encryptor);
+        assert!(repos.sqlite_database().is_none());
+
+        // The seeded Space is visible through the set, and a client saved
+        // through one repository is visible through another
+        let space = repos.spaces.get_default().await.unwrap().unwrap();
+        let client = mcpmux_core::Client::cursor();
+        repos.inbound_mcp_clients.create(&client).await.unwrap();
+        assert!(repos
+            .inbound_clients
+            .get_client(&client.id.to_string())
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repos
+            .feature_sets
+            .get_starter_for_space(&space.id.to_string())
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,396 @@
+//! Passphrase-encrypted export/import of secrets.
+//!
+//! Moves credentials, outbound OAuth registrations and saved server inputs to
+//! another machine without redoing every OAuth flow. The master key never
+//! leaves the keychain: the bundle is re-encrypted with a key derived from a
+//! user passphrase (PBKDF2-HMAC-SHA256) and sealed with AES-256-GCM via
+//! [`FieldEncryptor`].
+//!
+//! File layout (JSON envelope, payload encrypted):
+//!
+//! ```text
+//! { "format": "mcpmux-secrets", "version": 1, "kdf": "pbkdf2-sha256",
+//!   "iterations": 600000, "salt": "<hex>", "ciphertext": "<hex>" }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use mcpmux_core::{
+    Credential, CredentialRepository, CredentialType, InstalledServerRepository,
+    OutboundOAuthRegistration, OutboundOAuthRepository,
+};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::crypto::{FieldEncryptor, KEY_SIZE};
+
+/// Envelope `format` marker.
+const BUNDLE_FORMAT: &str = "mcpmux-secrets";
+
+/// Current bundle version.
+pub const SECRETS_BUNDLE_VERSION: u32 = 1;
+
+/// PBKDF2 iterations (OWASP 2023 recommendation for HMAC-SHA256).
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Salt size in bytes.
+const SALT_SIZE: usize = 16;
+
+/// Minimum passphrase length accepted for export.
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Serializable form of a [`Credential`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedCredential {
+    pub space_id: Uuid,
+    pub server_id: String,
+    pub credential_type: CredentialType,
+    pub value: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+}
+
+impl From<&Credential> for ExportedCredential {
+    fn from(c: &Credential) -> Self {
+        Self {
+            space_id: c.space_id,
+            server_id: c.server_id.clone(),
+            credential_type: c.credential_type.clone(),
+            value: c.value.clone(),
+            expires_at: c.expires_at,
+            token_type: c.token_type.clone(),
+            scope: c.scope.clone(),
+        }
+    }
+}
+
+impl From<ExportedCredential> for Credential {
+    fn from(c: ExportedCredential) -> Self {
+        let now = Utc::now();
+        Self {
+            space_id: c.space_id,
+            server_id: c.server_id,
+            credential_type: c.credential_type,
+            value: c.value,
+            expires_at: c.expires_at,
+            token_type: c.token_type,
+            scope: c.scope,
+            created_at: now,
+            updated_at: now,
+            last_used: None,
+        }
+    }
+}
+
+/// Saved input values of one installed server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedInputs {
+    pub space_id: String,
+    pub server_id: String,
+    pub input_values: HashMap<String, String>,
+}
+
+/// Decrypted bundle contents.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretsBundle {
+    pub exported_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub credentials: Vec<ExportedCredential>,
+    #[serde(default)]
+    pub oauth_registrations: Vec<OutboundOAuthRegistration>,
+    #[serde(default)]
+    pub server_inputs: Vec<ExportedInputs>,
+}
+
+/// Counts reported back to the UI after export/import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SecretsTransferSummary {
+    pub credentials: usize,
+    pub oauth_registrations: usize,
+    pub server_inputs: usize,
+    /// Entries skipped on import because the server isn't installed here
+    pub skipped: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BundleEnvelope {
+    format: String,
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    ciphertext: String,
+}
+
+pub(crate) fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<FieldEncryptor> {
+    let iterations = NonZeroU32::new(iterations).context("Invalid PBKDF2 iteration count")?;
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key[..],
+    );
+    FieldEncryptor::new(&key)
+}
+
+/// Encrypt a bundle with a passphrase, returning the file contents.
+pub fn encrypt_bundle(bundle: &SecretsBundle, passphrase: &str) -> Result<Vec<u8>> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        anyhow::bail!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        );
+    }
+
+    let mut salt = [0u8; SALT_SIZE];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate salt"))?;
+
+    let plaintext = Zeroizing::new(serde_json::to_string(bundle)?);
+    let ciphertext = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?.encrypt(&plaintext)?;
+
+    let envelope = BundleEnvelope {
+        format: BUNDLE_FORMAT.to_string(),
+        version: SECRETS_BUNDLE_VERSION,
+        kdf: "pbkdf2-sha256".to_string(),
+        iterations: PBKDF2_ITERATIONS,
+        salt: hex::encode(salt),
+        ciphertext,
+    };
+    Ok(serde_json::to_vec_pretty(&envelope)?)
+}
+
+/// Decrypt bundle file contents with a passphrase.
+pub fn decrypt_bundle(data: &[u8], passphrase: &str) -> Result<SecretsBundle> {
+    let envelope: BundleEnvelope =
+        serde_json::from_slice(data).context("Not a McpMux secrets bundle")?;
+    if envelope.format != BUNDLE_FORMAT {
+        anyhow::bail!("Not a McpMux secrets bundle");
+    }
+    if envelope.version > SECRETS_BUNDLE_VERSION {
+        anyhow::bail!(
+            "Secrets bundle version {} is newer than supported version {}",
+            envelope.version,
+            SECRETS_BUNDLE_VERSION
+        );
+    }
+    if envelope.kdf != "pbkdf2-sha256" {
+        anyhow::bail!("Unsupported key derivation: {}", envelope.kdf);
+    }
+
+    let salt = hex::decode(&envelope.salt).context("Invalid salt encoding")?;
+    let plaintext = Zeroizing::new(
+        derive_key(passphrase, &salt, envelope.iterations)?
+            .decrypt(&envelope.ciphertext)
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted bundle"))?,
+    );
+    serde_json::from_str(&plaintext).context("Corrupted secrets bundle payload")
+}
+
+/// Collect all secrets for every space that has installed servers.
+pub async fn collect_secrets(
+    installed_repo: &dyn InstalledServerRepository,
+    credential_repo: &dyn CredentialRepository,
+    oauth_repo: &dyn OutboundOAuthRepository,
+) -> Result<SecretsBundle> {
+    let installed = installed_repo.list().await?;
+    let space_ids: HashSet<Uuid> = installed
+        .iter()
+        .filter_map(|s| Uuid::parse_str(&s.space_id).ok())
+        .collect();
+
+    let mut bundle = SecretsBundle {
+        exported_at: Some(Utc::now()),
+        ..Default::default()
+    };
+    for space_id in &space_ids {
+        for credential in credential_repo.list_for_space(space_id).await? {
+            bundle.credentials.push((&credential).into());
+        }
+        bundle
+            .oauth_registrations
+            .extend(oauth_repo.list_for_space(space_id).await?);
+    }
+    bundle.server_inputs = installed
+        .into_iter()
+        .filter(|s| !s.input_values.is_empty())
+        .map(|s| ExportedInputs {
+            space_id: s.space_id,
+            server_id: s.server_id,
+            input_values: s.input_values,
+        })
+        .collect();
+    Ok(bundle)
+}
+
+/// Write secrets into the local repositories.
+///
+/// Entries for servers that aren't installed in the same space on this
+/// machine are skipped — install the server first, then re-import.
+pub async fn apply_secrets(
+    bundle: SecretsBundle,
+    installed_repo: &dyn InstalledServerRepository,
+    credential_repo: &dyn CredentialRepository,
+    oauth_repo: &dyn OutboundOAuthRepository,
+) -> Result<SecretsTransferSummary> {
+    let installed: HashMap<(String, String), Uuid> = installed_repo
+        .list()
+        .await?
+        .into_iter()
+        .map(|s| ((s.space_id, s.server_id), s.id))
+        .collect();
+    let is_installed = |space_id: &str, server_id: &str| {
+        installed.get(&(space_id.to_string(), server_id.to_string()))
+    };
+
+    let mut summary = SecretsTransferSummary::default();
+
+    for credential in bundle.credentials {
+        if is_installed(&credential.space_id.to_string(), &credential.server_id).is_none() {
+            summary.skipped += 1;
+            continue;
+        }
+        credential_repo.save(&credential.into()).await?;
+        summary.credentials += 1;
+    }
+
+    for registration in bundle.oauth_registrations {
+        if is_installed(&registration.space_id.to_string(), &registration.server_id).is_none() {
+            summary.skipped += 1;
+            continue;
+        }
+        oauth_repo.save(&registration).await?;
+        summary.oauth_registrations += 1;
+    }
+
+    for inputs in bundle.server_inputs {
+        match is_installed(&inputs.space_id, &inputs.server_id) {
+            Some(id) => {
+                installed_repo
+                    .update_inputs(id, inputs.input_values)
+                    .await?;
+                summary.server_inputs += 1;
+            }
+            None => summary.skipped += 1,
+        }
+    }
+
+    if summary.skipped > 0 {
+        warn!(
+            "[SecretsBundle] Skipped {} entries for servers not installed on this machine",
+            summary.skipped
+        );
+    }
+    Ok(summary)
+}
+
+/// Export all secrets to an encrypted file.
+pub async fn export_secrets(
+    path: &Path,
+    passphrase: &str,
+    installed_repo: &dyn InstalledServerRepository,
+    credential_repo: &dyn CredentialRepository,
+    oauth_repo: &dyn OutboundOAuthRepository,
+) -> Result<SecretsTransferSummary> {
+    let bundle = collect_secrets(installed_repo, credential_repo, oauth_repo).await?;
+    let data = encrypt_bundle(&bundle, passphrase)?;
+    tokio::fs::write(path, data)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let summary = SecretsTransferSummary {
+        credentials: bundle.credentials.len(),
+        oauth_registrations: bundle.oauth_registrations.len(),
+        server_inputs: bundle.server_inputs.len(),
+        skipped: 0,
+    };
+    info!(
+        "[SecretsBundle] Exported {} credentials, {} OAuth registrations, {} input sets",
+        summary.credentials, summary.oauth_registrations, summary.server_inputs
+    );
+    Ok(summary)
+}
+
+/// Import secrets from an encrypted file.
+pub async fn import_secrets(
+    path: &Path,
+    passphrase: &str,
+    installed_repo: &dyn InstalledServerRepository,
+    credential_repo: &dyn CredentialRepository,
+    oauth_repo: &dyn OutboundOAuthRepository,
+) -> Result<SecretsTransferSummary> {
+    let data = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let bundle = decrypt_bundle(&data, passphrase)?;
+    let summary = apply_secrets(bundle, installed_repo, credential_repo, oauth_repo).await?;
+    info!(
+        "[SecretsBundle] Imported {} credentials, {} OAuth registrations, {} input sets",
+        summary.credentials, summary.oauth_registrations, summary.server_inputs
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bundle() -> SecretsBundle {
+        SecretsBundle {
+            exported_at: Some(Utc::now()),
+            credentials: vec![(&Credential::api_key(Uuid::new_v4(), "github", "ghp_123")).into()],
+            oauth_registrations: vec![],
+            server_inputs: vec![ExportedInputs {
+                space_id: Uuid::new_v4().to_string(),
+                server_id: "github".to_string(),
+                input_values: HashMap::from([("TOKEN".to_string(), "abc".to_string())]),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let bundle = sample_bundle();
+        let data = encrypt_bundle(&bundle, "correct horse battery").unwrap();
+
+        // Secrets must not appear in the file
+        let text = String::from_utf8(data.clone()).unwrap();
+        assert!(!text.contains("ghp_123"));
+
+        let decrypted = decrypt_bundle(&data, "correct horse battery").unwrap();
+        assert_eq!(decrypted.credentials[0].value, "ghp_123");
+        assert_eq!(
+            decrypted.server_inputs[0].input_values.get("TOKEN"),
+            Some(&"abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let data = encrypt_bundle(&sample_bundle(), "correct horse battery").unwrap();
+        let err = decrypt_bundle(&data, "wrong passphrase").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"));
+    }
+
+    #[test]
+    fn test_short_passphrase_rejected() {
+        assert!(encrypt_bundle(&sample_bundle(), "short").is_err());
+    }
+
+    #[test]
+    fn test_rejects_foreign_files() {
+        assert!(decrypt_bundle(b"{\"hello\": 1}", "whatever-pass").is_err());
+    }
+}
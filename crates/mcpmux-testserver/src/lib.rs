@@ -0,0 +1,36 @@
+//! McpMux test server
+//!
+//! A mock MCP server whose tools, prompts and resources come from a
+//! [`ServerSpec`], with induced latency and scheduled failures, served over
+//! stdio or Streamable HTTP. The integration tests run it in process; the
+//! `mcpmux-testserver` binary serves a spec file, so a gateway setup can be
+//! tried against a server that behaves exactly as configured.
+//!
+//! ```no_run
+//! use mcpmux_testserver::{Failure, FailureMode, ServerSpec, TestServer, ToolSpec};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let server = TestServer::new(ServerSpec {
+//!     tools: vec![
+//!         ToolSpec::echo("echo"),
+//!         ToolSpec::text("flaky", "ok").failing(Failure {
+//!             mode: FailureMode::Error,
+//!             every: 2,
+//!             after: 0,
+//!         }),
+//!     ],
+//!     ..Default::default()
+//! });
+//! let http = server.clone().serve_http("127.0.0.1:0".parse()?).await?;
+//! println!("MCP endpoint: {}", http.url);
+//! # Ok(())
+//! # }
+//! ```
+
+mod server;
+mod spec;
+
+pub use server::{HttpServer, TestServer};
+pub use spec::{
+    Failure, FailureMode, PromptSpec, ResourceSpec, ServerSpec, ToolResponse, ToolSpec,
+};
@@ -0,0 +1,98 @@
+//! Mock MCP server for trying out a gateway setup.
+//!
+//! ```text
+//! mcpmux-testserver [--spec <file.json>] [--http <addr>]
+//! ```
+//!
+//! Serves stdio unless `--http` is given. Without `--spec` it serves the
+//! built-in example: `echo`, `hello`, a `slow` tool and a `fail` tool, a
+//! `greet` prompt and a `test://hello` resource.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use mcpmux_testserver::{ServerSpec, TestServer};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str = "Usage: mcpmux-testserver [--spec <file.json>] [--http <addr>]\n\n\
+     Serves stdio unless --http is given (e.g. --http 127.0.0.1:3333).\n\
+     Without --spec, serves a built-in example server.";
+
+struct Options {
+    spec: Option<PathBuf>,
+    http: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options> {
+    let mut options = Options {
+        spec: None,
+        http: None,
+    };
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let Some(value) = inline.or_else(|| rest.next().cloned()) else {
+            bail!("{} requires a value", name);
+        };
+        match name {
+            "--spec" => options.spec = Some(PathBuf::from(value)),
+            "--http" => options.http = Some(value),
+            _ => bail!("Unknown argument: {}", arg),
+        }
+    }
+    Ok(options)
+}
+
+async fn run(options: Options) -> Result<()> {
+    let spec = match &options.spec {
+        Some(path) => ServerSpec::load(path)?,
+        None => ServerSpec::example(),
+    };
+    let server = TestServer::new(spec);
+    match options.http {
+        Some(addr) => {
+            let http = server.serve_http(addr.parse()?).await?;
+            // The URL goes to stdout for scripts to pick up
+            println!("{}", http.url);
+            tokio::signal::ctrl_c().await?;
+            http.shutdown();
+            Ok(())
+        }
+        None => server.serve_stdio().await,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args
+        .first()
+        .is_some_and(|a| matches!(a.as_str(), "help" | "--help" | "-h"))
+    {
+        println!("{}", USAGE);
+        return;
+    }
+
+    // stdout carries the protocol in stdio mode, so logs go to stderr
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let result = match parse_args(&args) {
+        Ok(options) => run(options).await,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
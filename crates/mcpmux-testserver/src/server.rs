@@ -0,0 +1,535 @@
+//! The MCP server behind a [`ServerSpec`]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::time::Duration;
+
+use anyhow::Result;
+use rmcp::model::{
+    CallToolRequestParams, CallToolResult, Content, GetPromptRequestParams, GetPromptResult,
+    Implementation, JsonObject, ListPromptsResult, ListResourcesResult, ListToolsResult,
+    PaginatedRequestParams, Prompt, ReadResourceRequestParams, ReadResourceResult, Resource,
+    ServerCapabilities, ServerInfo, Tool, ToolsCapability,
+};
+use rmcp::service::{NotificationContext, RequestContext};
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler, ServiceExt};
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::spec::{FailureMode, ServerSpec, ToolResponse, ToolSpec};
+
+/// A mock MCP server. Clones share the spec, call counts and latency, so a
+/// test can keep one to inspect or adjust the server it is serving.
+#[derive(Clone)]
+pub struct TestServer {
+    spec: Arc<RwLock<ServerSpec>>,
+    /// Server-wide latency in milliseconds, adjustable while serving
+    latency_ms: Arc<AtomicU64>,
+    calls: Arc<Mutex<HashMap<String, u32>>>,
+    clients: Arc<Mutex<Vec<Implementation>>>,
+}
+
+impl TestServer {
+    pub fn new(spec: ServerSpec) -> Self {
+        Self {
+            latency_ms: Arc::new(AtomicU64::new(spec.latency_ms)),
+            spec: Arc::new(RwLock::new(spec)),
+            calls: Arc::default(),
+            clients: Arc::default(),
+        }
+    }
+
+    fn spec(&self) -> RwLockReadGuard<'_, ServerSpec> {
+        self.spec.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replace the tools. Clients see them on their next `tools/list`; a
+    /// [`ToolResponse::ListChanged`] tool can tell them to look.
+    pub fn set_tools(&self, tools: Vec<ToolSpec>) {
+        self.spec.write().unwrap_or_else(|e| e.into_inner()).tools = tools;
+    }
+
+    /// `clientInfo` of every client that has finished initializing
+    pub fn clients(&self) -> Vec<Implementation> {
+        self.clients
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Delay every request from now on by `latency`
+    pub fn set_latency(&self, latency: Duration) {
+        self.latency_ms
+            .store(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Calls made to `tool` so far, failed ones included
+    pub fn calls(&self, tool: &str) -> u32 {
+        self.lock_calls().get(tool).copied().unwrap_or(0)
+    }
+
+    fn lock_calls(&self) -> std::sync::MutexGuard<'_, HashMap<String, u32>> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn delay(&self, latency: Option<u64>) {
+        let ms = latency.unwrap_or_else(|| self.latency_ms.load(Ordering::Relaxed));
+        if ms > 0 {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+        }
+    }
+
+    /// Serve over stdin/stdout until the client disconnects
+    pub async fn serve_stdio(self) -> Result<()> {
+        let service = self.serve(rmcp::transport::stdio()).await?;
+        service.waiting().await?;
+        Ok(())
+    }
+
+    /// Serve Streamable HTTP at `/mcp` on `addr` (port 0 picks a free one)
+    pub async fn serve_http(self, addr: SocketAddr) -> Result<HttpServer> {
+        let ct = CancellationToken::new();
+        let mut config = StreamableHttpServerConfig::default();
+        config.stateful_mode = true;
+        config.cancellation_token = ct.child_token();
+        let service = StreamableHttpService::new(
+            move || Ok(self.clone()),
+            Arc::new(LocalSessionManager::default()),
+            config,
+        );
+        let router = axum::Router::new().nest_service("/mcp", service);
+
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let shutdown = ct.clone();
+        tokio::spawn(async move {
+            let server = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { shutdown.cancelled().await });
+            if let Err(e) = server.await {
+                tracing::error!("[TestServer] HTTP server failed: {}", e);
+            }
+        });
+        info!("[TestServer] Serving http://{}/mcp", addr);
+
+        Ok(HttpServer {
+            url: format!("http://{}/mcp", addr),
+            ct,
+        })
+    }
+
+    /// Answer a call to the tool `name` as the spec says, counting it
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: JsonObject,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(tool) = self.tool(name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown tool: {}", name),
+                None,
+            ));
+        };
+        let call = {
+            let mut calls = self.lock_calls();
+            let count = calls.entry(name.to_string()).or_insert(0);
+            *count += 1;
+            *count
+        };
+
+        self.delay(tool.latency_ms).await;
+
+        match tool.failure.as_ref().filter(|f| f.fails(call)) {
+            Some(failure) => match failure.mode {
+                FailureMode::Error => Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Injected failure of '{}' on call {}",
+                    name, call
+                ))])),
+                FailureMode::Protocol => Err(McpError::internal_error(
+                    format!("Injected failure of '{}' on call {}", name, call),
+                    None,
+                )),
+                FailureMode::Hang => std::future::pending().await,
+            },
+            None => Ok(respond(&tool, arguments)),
+        }
+    }
+
+    fn tool(&self, name: &str) -> Option<ToolSpec> {
+        self.spec().tools.iter().find(|t| t.name == name).cloned()
+    }
+}
+
+/// The page of `items` at `cursor`, and the cursor of the next page. Cursors
+/// are item offsets.
+fn page<T>(
+    items: Vec<T>,
+    page_size: Option<usize>,
+    params: Option<PaginatedRequestParams>,
+) -> Result<(Vec<T>, Option<String>), McpError> {
+    let start = match params.and_then(|params| params.cursor) {
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .ok()
+            .filter(|&start| start <= items.len())
+            .ok_or_else(|| McpError::invalid_params(format!("Invalid cursor: {}", cursor), None))?,
+        None => 0,
+    };
+    let end = page_size.map_or(items.len(), |size| (start + size).min(items.len()));
+    let next = (end < items.len()).then(|| end.to_string());
+    Ok((
+        items.into_iter().skip(start).take(end - start).collect(),
+        next,
+    ))
+}
+
+fn respond(tool: &ToolSpec, arguments: JsonObject) -> CallToolResult {
+    match &tool.response {
+        ToolResponse::Echo => {
+            CallToolResult::success(vec![Content::text(Value::Object(arguments).to_string())])
+        }
+        ToolResponse::Text(text) => CallToolResult::success(vec![Content::text(text.clone())]),
+        ToolResponse::Json(value) => {
+            let mut result = CallToolResult::success(vec![Content::text(value.to_string())]);
+            result.structured_content = Some(value.clone());
+            result
+        }
+        ToolResponse::ListChanged => CallToolResult::success(Vec::new()),
+    }
+}
+
+fn tool(spec: &ToolSpec) -> Tool {
+    let schema: JsonObject = serde_json::from_value(spec.input_schema()).unwrap_or_default();
+    let description = spec
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Test tool {}", spec.name));
+    Tool::new(spec.name.clone(), description, Arc::new(schema))
+}
+
+impl ServerHandler for TestServer {
+    fn get_info(&self) -> ServerInfo {
+        // Empty prompt and resource lists are fine to advertise
+        let capabilities = ServerCapabilities::builder()
+            .enable_tools_with(ToolsCapability {
+                list_changed: Some(true),
+            })
+            .enable_prompts()
+            .enable_resources()
+            .build();
+        let mut info = ServerInfo::new(capabilities);
+        info.server_info = Implementation::new(self.spec().name.clone(), env!("CARGO_PKG_VERSION"));
+        info
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        if let Some(info) = context.peer.peer_info() {
+            self.clients
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(info.client_info.clone());
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        self.delay(None).await;
+        let (tools, page_size) = {
+            let spec = self.spec();
+            (spec.tools.iter().map(tool).collect(), spec.page_size)
+        };
+        let (tools, next_cursor) = page(tools, page_size, params)?;
+        let mut result = ListToolsResult::with_all_items(tools);
+        result.next_cursor = next_cursor;
+        Ok(result)
+    }
+
+    async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let notify = self
+            .tool(&params.name)
+            .is_some_and(|tool| tool.response == ToolResponse::ListChanged);
+        let result = self
+            .call(&params.name, params.arguments.unwrap_or_default())
+            .await?;
+        if notify && result.is_error != Some(true) {
+            context
+                .peer
+                .notify_tool_list_changed()
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        }
+        Ok(result)
+    }
+
+    async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        self.delay(None).await;
+        let (prompts, page_size) = {
+            let spec = self.spec();
+            let prompts: Vec<Prompt> = spec
+                .prompts
+                .iter()
+                .filter_map(|prompt| {
+                    let arguments: Vec<Value> = prompt
+                        .arguments
+                        .iter()
+                        .map(|name| json!({ "name": name, "required": true }))
+                        .collect();
+                    serde_json::from_value(json!({
+                        "name": prompt.name,
+                        "description": prompt.description,
+                        "arguments": arguments,
+                    }))
+                    .ok()
+                })
+                .collect();
+            (prompts, spec.page_size)
+        };
+        let (prompts, next_cursor) = page(prompts, page_size, params)?;
+        let mut result = ListPromptsResult::with_all_items(prompts);
+        result.next_cursor = next_cursor;
+        Ok(result)
+    }
+
+    async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        self.delay(None).await;
+        let prompt = self
+            .spec()
+            .prompts
+            .iter()
+            .find(|p| p.name == params.name)
+            .cloned();
+        let Some(prompt) = prompt else {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", params.name),
+                None,
+            ));
+        };
+        let text = prompt.render(&params.arguments.unwrap_or_default());
+        serde_json::from_value(json!({
+            "description": prompt.description,
+            "messages": [{ "role": "user", "content": { "type": "text", "text": text } }],
+        }))
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    async fn list_resources(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        self.delay(None).await;
+        let (resources, page_size) = {
+            let spec = self.spec();
+            let resources: Vec<Resource> = spec
+                .resources
+                .iter()
+                .filter_map(|resource| {
+                    serde_json::from_value(json!({
+                        "uri": resource.uri,
+                        "name": resource.name.as_deref().unwrap_or(&resource.uri),
+                        "mimeType": resource.mime_type,
+                    }))
+                    .ok()
+                })
+                .collect();
+            (resources, spec.page_size)
+        };
+        let (resources, next_cursor) = page(resources, page_size, params)?;
+        let mut result = ListResourcesResult::with_all_items(resources);
+        result.next_cursor = next_cursor;
+        Ok(result)
+    }
+
+    async fn read_resource(
+        &self,
+        params: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        self.delay(None).await;
+        let resource = self
+            .spec()
+            .resources
+            .iter()
+            .find(|r| r.uri == params.uri)
+            .cloned();
+        let Some(resource) = resource else {
+            return Err(McpError::invalid_params(
+                format!("Unknown resource: {}", params.uri),
+                None,
+            ));
+        };
+        let contents = serde_json::from_value(json!([{
+            "uri": resource.uri,
+            "mimeType": resource.mime_type,
+            "text": resource.text,
+        }]))
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(ReadResourceResult::new(contents))
+    }
+}
+
+/// A test server serving HTTP; stops when dropped
+pub struct HttpServer {
+    /// Streamable HTTP endpoint, e.g. `http://127.0.0.1:41234/mcp`
+    pub url: String,
+    ct: CancellationToken,
+}
+
+impl HttpServer {
+    pub fn shutdown(&self) {
+        self.ct.cancel();
+    }
+}
+
+impl Drop for HttpServer {
+    fn drop(&mut self) {
+        self.ct.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Failure;
+
+    fn args(value: Value) -> JsonObject {
+        value.as_object().cloned().unwrap_or_default()
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    #[tokio::test]
+    async fn tools_answer_as_specified() {
+        let server = TestServer::new(ServerSpec {
+            tools: vec![
+                ToolSpec::echo("echo"),
+                ToolSpec::json("status", json!({ "ok": true })),
+            ],
+            ..Default::default()
+        });
+
+        let echoed = server.call("echo", args(json!({ "a": 1 }))).await.unwrap();
+        assert_eq!(text(&echoed), r#"{"a":1}"#);
+
+        let status = server.call("status", JsonObject::new()).await.unwrap();
+        assert_eq!(status.structured_content, Some(json!({ "ok": true })));
+
+        assert!(server.call("missing", JsonObject::new()).await.is_err());
+        assert_eq!(server.calls("echo"), 1);
+    }
+
+    #[tokio::test]
+    async fn injected_failures_follow_the_schedule() {
+        let server = TestServer::new(ServerSpec {
+            tools: vec![
+                ToolSpec::echo("flaky").failing(Failure {
+                    mode: FailureMode::Error,
+                    every: 2,
+                    after: 0,
+                }),
+                ToolSpec::echo("broken").failing(Failure {
+                    mode: FailureMode::Protocol,
+                    every: 1,
+                    after: 1,
+                }),
+            ],
+            ..Default::default()
+        });
+
+        let flaky: Vec<bool> = call_results(&server, "flaky", 4).await;
+        assert_eq!(flaky, vec![false, true, false, true]);
+
+        assert!(server.call("broken", JsonObject::new()).await.is_ok());
+        assert!(server.call("broken", JsonObject::new()).await.is_err());
+    }
+
+    fn cursor(cursor: &str) -> Option<PaginatedRequestParams> {
+        serde_json::from_value(json!({ "cursor": cursor })).ok()
+    }
+
+    #[test]
+    fn lists_are_paged_by_offset() {
+        let items: Vec<u32> = (0..5).collect();
+        assert_eq!(
+            page(items.clone(), None, None).unwrap(),
+            (items.clone(), None)
+        );
+
+        let (first, next) = page(items.clone(), Some(2), None).unwrap();
+        assert_eq!((first, next.as_deref()), (vec![0, 1], Some("2")));
+        let (last, next) = page(items.clone(), Some(2), cursor("4")).unwrap();
+        assert_eq!((last, next), (vec![4], None));
+
+        assert!(page(items.clone(), Some(2), cursor("6")).is_err());
+        assert!(page(items, Some(2), cursor("next")).is_err());
+    }
+
+    #[tokio::test]
+    async fn set_tools_replaces_what_is_served() {
+        let server = TestServer::new(ServerSpec {
+            tools: vec![ToolSpec::echo("old")],
+            ..Default::default()
+        });
+        server.set_tools(vec![ToolSpec::echo("new")]);
+
+        assert!(server.call("old", JsonObject::new()).await.is_err());
+        assert!(server.call("new", JsonObject::new()).await.is_ok());
+    }
+
+    async fn call_results(server: &TestServer, tool: &str, calls: usize) -> Vec<bool> {
+        let mut failed = Vec::new();
+        for _ in 0..calls {
+            let result = server.call(tool, JsonObject::new()).await.unwrap();
+            failed.push(result.is_error.unwrap_or(false));
+        }
+        failed
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn latency_delays_calls_and_hang_never_answers() {
+        let server = TestServer::new(ServerSpec {
+            tools: vec![
+                ToolSpec::echo("slow").with_latency(Duration::from_secs(5)),
+                ToolSpec::echo("stuck").failing(Failure {
+                    mode: FailureMode::Hang,
+                    every: 1,
+                    after: 0,
+                }),
+            ],
+            ..Default::default()
+        });
+
+        let started = tokio::time::Instant::now();
+        server.call("slow", JsonObject::new()).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(5));
+
+        let stuck = tokio::time::timeout(
+            Duration::from_secs(60),
+            server.call("stuck", JsonObject::new()),
+        )
+        .await;
+        assert!(stuck.is_err());
+    }
+}
@@ -0,0 +1,381 @@
+//! What a test server offers, and how it misbehaves
+//!
+//! A [`ServerSpec`] can be built in code or loaded from JSON, e.g.
+//!
+//! ```json
+//! {
+//!   "name": "flaky-search",
+//!   "latency_ms": 50,
+//!   "page_size": 2,
+//!   "tools": [
+//!     { "name": "search", "response": { "json": { "hits": [] } } },
+//!     { "name": "deploy", "latency_ms": 2000,
+//!       "failure": { "mode": "error", "every": 3 } }
+//!   ],
+//!   "prompts": [{ "name": "greet", "text": "Hello, {{name}}!", "arguments": ["name"] }],
+//!   "resources": [{ "uri": "test://readme", "text": "Read me" }]
+//! }
+//! ```
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Everything a test server offers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerSpec {
+    #[serde(default = "default_name")]
+    pub name: String,
+    /// Delay before answering any request, in milliseconds
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Items per page of `tools/list`, `prompts/list` and `resources/list`;
+    /// everything in one page if unset
+    #[serde(default)]
+    pub page_size: Option<usize>,
+    #[serde(default)]
+    pub tools: Vec<ToolSpec>,
+    #[serde(default)]
+    pub prompts: Vec<PromptSpec>,
+    #[serde(default)]
+    pub resources: Vec<ResourceSpec>,
+}
+
+fn default_name() -> String {
+    "mcpmux-testserver".to_string()
+}
+
+impl Default for ServerSpec {
+    fn default() -> Self {
+        Self {
+            name: default_name(),
+            latency_ms: 0,
+            page_size: None,
+            tools: Vec::new(),
+            prompts: Vec::new(),
+            resources: Vec::new(),
+        }
+    }
+}
+
+impl ServerSpec {
+    /// Load a spec from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let spec: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid server spec in {}", path.display()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// What the binary serves without a spec: an echo and a text tool, a
+    /// slow and a failing one, a prompt and a resource
+    pub fn example() -> Self {
+        Self {
+            tools: vec![
+                ToolSpec::echo("echo"),
+                ToolSpec::text("hello", "Hello from mcpmux-testserver"),
+                ToolSpec::echo("slow").with_latency(Duration::from_secs(2)),
+                ToolSpec::text("fail", "This tool always fails").failing(Failure {
+                    mode: FailureMode::Error,
+                    every: 1,
+                    after: 0,
+                }),
+            ],
+            prompts: vec![PromptSpec {
+                name: "greet".to_string(),
+                description: Some("Greet someone".to_string()),
+                arguments: vec!["name".to_string()],
+                text: "Say hello to {{name}}".to_string(),
+            }],
+            resources: vec![ResourceSpec {
+                uri: "test://hello".to_string(),
+                name: Some("hello".to_string()),
+                mime_type: Some("text/plain".to_string()),
+                text: "Hello from mcpmux-testserver".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Names must be unique, pages can't be empty and failure schedules
+    /// must fail at some point
+    pub fn validate(&self) -> Result<()> {
+        if self.page_size == Some(0) {
+            bail!("page_size must be at least 1");
+        }
+        let mut names = std::collections::HashSet::new();
+        for tool in &self.tools {
+            if !names.insert(tool.name.as_str()) {
+                bail!("Tool '{}' is defined twice", tool.name);
+            }
+            if tool.failure.as_ref().is_some_and(|f| f.every == 0) {
+                bail!("Tool '{}' has a failure with `every` of 0", tool.name);
+            }
+        }
+        names.clear();
+        for prompt in &self.prompts {
+            if !names.insert(prompt.name.as_str()) {
+                bail!("Prompt '{}' is defined twice", prompt.name);
+            }
+        }
+        names.clear();
+        for resource in &self.resources {
+            if !names.insert(resource.uri.as_str()) {
+                bail!("Resource '{}' is defined twice", resource.uri);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn latency(&self) -> Duration {
+        Duration::from_millis(self.latency_ms)
+    }
+}
+
+/// A tool and what calling it returns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// JSON Schema of the arguments; any object if unset
+    #[serde(default)]
+    pub input_schema: Option<Value>,
+    #[serde(default)]
+    pub response: ToolResponse,
+    /// Delay before answering a call, instead of the server's latency
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+    #[serde(default)]
+    pub failure: Option<Failure>,
+}
+
+impl ToolSpec {
+    pub fn new(name: impl Into<String>, response: ToolResponse) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            input_schema: None,
+            response,
+            latency_ms: None,
+            failure: None,
+        }
+    }
+
+    /// A tool that returns its arguments
+    pub fn echo(name: impl Into<String>) -> Self {
+        Self::new(name, ToolResponse::Echo)
+    }
+
+    /// A tool that returns fixed text
+    pub fn text(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self::new(name, ToolResponse::Text(text.into()))
+    }
+
+    /// A tool that returns fixed structured content
+    pub fn json(name: impl Into<String>, value: Value) -> Self {
+        Self::new(name, ToolResponse::Json(value))
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_input_schema(mut self, schema: Value) -> Self {
+        self.input_schema = Some(schema);
+        self
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
+
+    pub fn failing(mut self, failure: Failure) -> Self {
+        self.failure = Some(failure);
+        self
+    }
+
+    pub fn input_schema(&self) -> Value {
+        self.input_schema
+            .clone()
+            .unwrap_or_else(|| json!({ "type": "object" }))
+    }
+}
+
+/// What a successful tool call returns
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResponse {
+    /// The call's arguments, as JSON text
+    #[default]
+    Echo,
+    /// Fixed text
+    Text(String),
+    /// Fixed structured content, also sent as JSON text
+    Json(Value),
+    /// Nothing, after sending `notifications/tools/list_changed` to the
+    /// caller
+    ListChanged,
+}
+
+/// When and how a tool fails: the first `after` calls succeed, then every
+/// `every`th call fails (every call for 1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Failure {
+    pub mode: FailureMode,
+    #[serde(default = "default_every")]
+    pub every: u32,
+    #[serde(default)]
+    pub after: u32,
+}
+
+fn default_every() -> u32 {
+    1
+}
+
+impl Failure {
+    /// Whether the `call`th call (counting from 1) fails
+    pub fn fails(&self, call: u32) -> bool {
+        call > self.after && (call - self.after).is_multiple_of(self.every)
+    }
+}
+
+/// How a failing call fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureMode {
+    /// A tool result with `isError` set
+    Error,
+    /// A JSON-RPC error instead of a result
+    Protocol,
+    /// No answer at all; the caller has to time out
+    Hang,
+}
+
+/// A prompt with one user message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Argument names, all required
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    /// The message, with `{{argument}}` replaced by the argument's value
+    pub text: String,
+}
+
+impl PromptSpec {
+    pub fn render(&self, arguments: &serde_json::Map<String, Value>) -> String {
+        self.arguments.iter().fold(self.text.clone(), |text, name| {
+            let value = match arguments.get(name) {
+                Some(Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            text.replace(&format!("{{{{{}}}}}", name), &value)
+        })
+    }
+}
+
+/// A text resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceSpec {
+    pub uri: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    pub text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_follow_their_schedule() {
+        let failure = Failure {
+            mode: FailureMode::Error,
+            every: 3,
+            after: 2,
+        };
+        let failed: Vec<u32> = (1..=10).filter(|&call| failure.fails(call)).collect();
+        assert_eq!(failed, vec![5, 8]);
+
+        let always = Failure {
+            mode: FailureMode::Hang,
+            every: 1,
+            after: 0,
+        };
+        assert!((1..=5).all(|call| always.fails(call)));
+    }
+
+    #[test]
+    fn spec_parses_from_json() {
+        let spec: ServerSpec = serde_json::from_value(json!({
+            "latency_ms": 50,
+            "page_size": 2,
+            "tools": [
+                { "name": "search", "response": { "json": { "hits": [] } } },
+                { "name": "deploy", "failure": { "mode": "protocol", "every": 2 } },
+                { "name": "echo", "response": "echo" }
+            ],
+            "prompts": [{ "name": "greet", "text": "Hello, {{name}}!", "arguments": ["name"] }]
+        }))
+        .unwrap();
+        spec.validate().unwrap();
+
+        assert_eq!(spec.name, "mcpmux-testserver");
+        assert_eq!(spec.latency(), Duration::from_millis(50));
+        assert_eq!(spec.page_size, Some(2));
+        assert_eq!(
+            spec.tools[0].response,
+            ToolResponse::Json(json!({ "hits": [] }))
+        );
+        assert_eq!(spec.tools[1].response, ToolResponse::Echo);
+        assert_eq!(
+            spec.tools[1].failure,
+            Some(Failure {
+                mode: FailureMode::Protocol,
+                every: 2,
+                after: 0
+            })
+        );
+
+        let args = json!({ "name": "Ada" });
+        assert_eq!(
+            spec.prompts[0].render(args.as_object().unwrap()),
+            "Hello, Ada!"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_duplicates_and_impossible_failures() {
+        let mut spec = ServerSpec {
+            tools: vec![ToolSpec::echo("a"), ToolSpec::echo("a")],
+            ..Default::default()
+        };
+        assert!(spec.validate().is_err());
+
+        spec.tools = vec![ToolSpec::echo("a").failing(Failure {
+            mode: FailureMode::Error,
+            every: 0,
+            after: 0,
+        })];
+        assert!(spec.validate().is_err());
+
+        spec.tools = vec![ToolSpec::echo("a")];
+        spec.page_size = Some(0);
+        assert!(spec.validate().is_err());
+
+        assert!(ServerSpec::example().validate().is_ok());
+    }
+}
@@ -0,0 +1,97 @@
+//! Feature aggregation across many servers
+//!
+//! `tools/list` merges the tools of every server in the Space and filters
+//! them through the client's FeatureSets; the cost grows with both.
+
+use std::sync::Arc;
+
+use benches::fixtures;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mcpmux_core::{FeatureSetRepository, FeatureType, ServerFeatureRepository};
+use mcpmux_gateway::{FeatureService, PrefixCacheService};
+use tests::mocks::{MockFeatureSetRepository, MockServerFeatureRepository};
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+const TOOLS_PER_SERVER: usize = 20;
+
+struct Fixture {
+    space_id: String,
+    grant: String,
+    service: FeatureService,
+}
+
+async fn fixture(servers: usize) -> Fixture {
+    let space_id = Uuid::new_v4().to_string();
+    let feature_repo = Arc::new(MockServerFeatureRepository::new());
+    let feature_set_repo = Arc::new(MockFeatureSetRepository::new());
+    let prefix_cache = Arc::new(PrefixCacheService::new());
+
+    let features = fixtures::tools(&space_id, servers, TOOLS_PER_SERVER);
+    feature_repo.upsert_many(&features).await.unwrap();
+    for s in 0..servers {
+        prefix_cache
+            .assign_prefix_runtime(&space_id, &fixtures::server_id(s), None)
+            .await;
+    }
+    let grant = fixtures::grant_all(&space_id, &features);
+    feature_set_repo.create(&grant).await.unwrap();
+
+    Fixture {
+        space_id,
+        grant: grant.id,
+        service: FeatureService::new(
+            feature_repo as Arc<dyn ServerFeatureRepository>,
+            feature_set_repo as Arc<dyn FeatureSetRepository>,
+            prefix_cache,
+        ),
+    }
+}
+
+fn aggregate(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("aggregation");
+    for servers in [10, 100, 500] {
+        let f = runtime.block_on(fixture(servers));
+        group.throughput(Throughput::Elements((servers * TOOLS_PER_SERVER) as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("all_features_for_space", servers),
+            &f,
+            |b, f| {
+                b.to_async(&runtime).iter(|| async {
+                    f.service
+                        .get_all_features_for_space(&f.space_id, Some(FeatureType::Tool))
+                        .await
+                        .unwrap()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("tools_for_grants", servers), &f, |b, f| {
+            b.to_async(&runtime).iter(|| async {
+                f.service
+                    .get_tools_for_grants(&f.space_id, std::slice::from_ref(&f.grant))
+                    .await
+                    .unwrap()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("qualified_tool_lookup", servers),
+            &f,
+            |b, f| {
+                let last = format!("{}_tool_0", fixtures::server_id(servers - 1));
+                b.to_async(&runtime).iter(|| async {
+                    f.service
+                        .find_server_for_qualified_tool(&f.space_id, &last)
+                        .await
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, aggregate);
+criterion_main!(benches);
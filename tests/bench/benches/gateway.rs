@@ -0,0 +1,57 @@
+//! End-to-end tool calls through an in-process gateway
+//!
+//! A real MCP client session over loopback HTTP, so each call pays for the
+//! transport, session lookup, routing resolution and result encoding.
+
+use benches::gateway::BenchGateway;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rmcp::model::CallToolRequestParams;
+use serde_json::json;
+use tokio::runtime::Runtime;
+
+const TOOLS_PER_SERVER: usize = 20;
+
+fn tool_calls(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("gateway");
+    for servers in [10, 100] {
+        let (gateway, client) = runtime.block_on(async {
+            let gateway = BenchGateway::start(servers, TOOLS_PER_SERVER).await;
+            let client = gateway.connect().await;
+            (gateway, client)
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("list_tools", servers),
+            &client,
+            |b, client| {
+                b.to_async(&runtime)
+                    .iter(|| async { client.list_tools(Default::default()).await.unwrap() })
+            },
+        );
+
+        let mut search = CallToolRequestParams::new("mcpmux_search_tools");
+        search.arguments = json!({ "query": "tool_1" }).as_object().cloned();
+        group.bench_with_input(
+            BenchmarkId::new("call_tool", servers),
+            &search,
+            |b, search| {
+                b.to_async(&runtime).iter(|| async {
+                    let result = client.call_tool(search.clone()).await.unwrap();
+                    assert!(!result.is_error.unwrap_or(false));
+                    result
+                })
+            },
+        );
+
+        runtime.block_on(async {
+            client.cancel().await.ok();
+        });
+        drop(gateway);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tool_calls);
+criterion_main!(benches);
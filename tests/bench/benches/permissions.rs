@@ -0,0 +1,76 @@
+//! Permission filtering with large pattern lists
+//!
+//! Every `tools/list` runs each tool name against every include and exclude
+//! pattern of the client's permission sets.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use mcpmux_gateway::permissions::HasName;
+use mcpmux_gateway::{PermissionFilter, PermissionSet};
+use uuid::Uuid;
+
+const TOOLS: usize = 1_000;
+
+struct Tool(String);
+
+impl HasName for Tool {
+    fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+fn tools() -> Vec<Tool> {
+    (0..TOOLS)
+        .map(|i| Tool(format!("server{}_tool_{}", i % 50, i)))
+        .collect()
+}
+
+/// `patterns` include patterns, a tenth as many excludes, mixing exact
+/// names, prefixes, suffixes and infixes. Only a few match any tool, so
+/// most names are checked against every pattern.
+fn permission_set(patterns: usize) -> PermissionSet {
+    let pattern = |i: usize| match i % 4 {
+        0 => format!("other{}_tool_{}", i, i),
+        1 => format!("other{}_*", i),
+        2 => format!("*_missing_{}", i),
+        _ => format!("other*_{}_*", i),
+    };
+    let mut tools_include: Vec<String> = (0..patterns).map(pattern).collect();
+    tools_include.push("server1_*".to_string());
+    PermissionSet {
+        id: Uuid::new_v4(),
+        tools_include,
+        tools_exclude: (0..patterns / 10).map(pattern).collect(),
+        prompts_include: vec![],
+        prompts_exclude: vec![],
+        resources_include: vec![],
+        resources_exclude: vec![],
+    }
+}
+
+fn filter_tools(c: &mut Criterion) {
+    let client_id = Uuid::new_v4();
+    let mut group = c.benchmark_group("permissions/filter_tools");
+    group.throughput(Throughput::Elements(TOOLS as u64));
+    // The largest lists take a good fraction of a second per iteration
+    group.sample_size(20);
+
+    for patterns in [10, 100, 1_000, 5_000] {
+        let mut filter = PermissionFilter::new();
+        filter.set_client_permissions(client_id, vec![permission_set(patterns)]);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(patterns),
+            &filter,
+            |b, filter| {
+                b.iter_batched(
+                    tools,
+                    |tools| filter.filter_tools(&client_id, tools),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, filter_tools);
+criterion_main!(benches);
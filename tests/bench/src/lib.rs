@@ -0,0 +1,245 @@
+//! Shared fixtures for the McpMux benchmarks.
+//!
+//! Run with `cargo bench -p benches`. To check a change for regressions,
+//! save a baseline first with `cargo bench -p benches -- --save-baseline main`,
+//! then compare with `cargo bench -p benches -- --baseline main`; criterion
+//! flags every benchmark that got slower.
+
+/// Generated features and FeatureSets
+pub mod fixtures {
+    use mcpmux_core::{FeatureSet, FeatureSetMember, ServerFeature};
+
+    /// Id of the `index`th generated server
+    pub fn server_id(index: usize) -> String {
+        format!("server-{}", index)
+    }
+
+    /// `servers` servers with `tools_per_server` available tools each
+    pub fn tools(space_id: &str, servers: usize, tools_per_server: usize) -> Vec<ServerFeature> {
+        (0..servers)
+            .flat_map(|s| {
+                (0..tools_per_server).map(move |t| {
+                    let mut tool =
+                        ServerFeature::tool(space_id, server_id(s), format!("tool_{}", t))
+                            .with_description(format!("Tool {} of server {}", t, s));
+                    tool.is_available = true;
+                    tool
+                })
+            })
+            .collect()
+    }
+
+    /// A Custom FeatureSet with every feature in `features` as a member
+    pub fn grant_all(space_id: &str, features: &[ServerFeature]) -> FeatureSet {
+        let mut fs = FeatureSet::new_custom("All (benchmark fixture)", space_id);
+        fs.members = features
+            .iter()
+            .map(|feature| FeatureSetMember::include_feature(&fs.id, &feature.id.to_string()))
+            .collect();
+        fs
+    }
+}
+
+/// A gateway served in-process over loopback HTTP
+pub mod gateway {
+    use std::sync::Arc;
+
+    use axum::{body::Body, http::Request, middleware, middleware::Next, response::Response};
+    use axum::{extract::State, Router};
+    use mcpmux_core::{
+        DomainEvent, FeatureSetRepository, ServerDiscoveryService, ServerFeatureRepository,
+        ServerLogManager, SpaceRepository,
+    };
+    use mcpmux_gateway::consumers::MCPNotifier;
+    use mcpmux_gateway::mcp::McpMuxGatewayHandler;
+    use mcpmux_gateway::server::{
+        DependenciesBuilder, GatewayDependencies, GatewayState, ServiceContainer,
+    };
+    use mcpmux_storage::{
+        InboundClient, InboundClientRepository, RegistrationType, SqliteInboundClientRepository,
+        SqliteSpaceRepository,
+    };
+    use rmcp::service::RunningService;
+    use rmcp::transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    };
+    use rmcp::transport::StreamableHttpClientTransport;
+    use rmcp::{RoleClient, ServiceExt};
+    use tests::db::TestDatabase;
+    use tests::mocks::*;
+    use tokio::sync::broadcast;
+    use tokio_util::sync::CancellationToken;
+    use uuid::Uuid;
+
+    use super::fixtures;
+
+    const CLIENT_ID: &str = "benchmark-client";
+
+    #[derive(Clone)]
+    struct Identity {
+        client_id: String,
+        space_id: Uuid,
+    }
+
+    /// Stands in for OAuth: every request comes from the benchmark client
+    async fn inject_identity(
+        State(identity): State<Arc<Identity>>,
+        mut request: Request<Body>,
+        next: Next,
+    ) -> Response {
+        let headers = request.headers_mut();
+        headers.insert("x-mcpmux-client-id", identity.client_id.parse().unwrap());
+        headers.insert(
+            "x-mcpmux-space-id",
+            identity.space_id.to_string().parse().unwrap(),
+        );
+        next.run(request).await
+    }
+
+    /// A gateway whose default Space holds `servers` x `tools_per_server`
+    /// tools, with the full request path: HTTP, sessions, routing and the
+    /// `mcpmux_*` meta tools.
+    pub struct BenchGateway {
+        pub url: String,
+        ct: CancellationToken,
+    }
+
+    impl BenchGateway {
+        pub async fn start(servers: usize, tools_per_server: usize) -> Self {
+            let ct = CancellationToken::new();
+            let database = Arc::new(tokio::sync::Mutex::new(TestDatabase::in_memory().db));
+            let space_id = Uuid::new_v4();
+
+            let feature_repo = Arc::new(MockServerFeatureRepository::new());
+            let features = fixtures::tools(&space_id.to_string(), servers, tools_per_server);
+            feature_repo
+                .upsert_many(&features)
+                .await
+                .expect("seed features");
+
+            let space_repo = Arc::new(SqliteSpaceRepository::new(database.clone()));
+            let now = chrono::Utc::now();
+            let mut space = mcpmux_core::domain::Space::new("Benchmark Space").set_default();
+            space.id = space_id;
+            space_repo.create(&space).await.expect("create space");
+            space_repo
+                .set_default(&space_id)
+                .await
+                .expect("set default");
+
+            let inbound_client_repo =
+                Arc::new(SqliteInboundClientRepository::new(database.clone()));
+            let now = now.to_rfc3339();
+            inbound_client_repo
+                .save_client(&InboundClient {
+                    client_id: CLIENT_ID.to_string(),
+                    registration_type: RegistrationType::Dcr,
+                    client_name: "benchmark-client".to_string(),
+                    client_alias: None,
+                    redirect_uris: vec![],
+                    grant_types: vec!["authorization_code".to_string()],
+                    response_types: vec!["code".to_string()],
+                    token_endpoint_auth_method: "none".to_string(),
+                    scope: None,
+                    approved: true,
+                    logo_uri: None,
+                    client_uri: None,
+                    software_id: None,
+                    software_version: None,
+                    metadata_url: None,
+                    metadata_cached_at: None,
+                    metadata_cache_ttl: None,
+                    last_seen: None,
+                    created_at: now.clone(),
+                    updated_at: now,
+                    reports_roots: false,
+                    roots_capability_known: false,
+                    suspended: false,
+                })
+                .await
+                .expect("save client");
+
+            let deps = DependenciesBuilder::new()
+                .with_installed_server_repo(Arc::new(MockInstalledServerRepository::new()))
+                .with_credential_repo(Arc::new(MockCredentialRepository::new()))
+                .with_backend_oauth_repo(Arc::new(MockOutboundOAuthRepository::new()))
+                .with_feature_repo(feature_repo as Arc<dyn ServerFeatureRepository>)
+                .with_feature_set_repo(
+                    Arc::new(MockFeatureSetRepository::new()) as Arc<dyn FeatureSetRepository>
+                )
+                .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                    std::path::PathBuf::from("bench-data"),
+                    std::path::PathBuf::from("bench-spaces"),
+                )))
+                .with_log_manager(Arc::new(ServerLogManager::new(
+                    mcpmux_core::LogConfig::default(),
+                )))
+                .with_database(database)
+                .build()
+                .expect("build dependencies");
+            let deps = GatewayDependencies {
+                space_repo: space_repo as Arc<dyn SpaceRepository>,
+                inbound_client_repo,
+                ..deps
+            };
+
+            let (event_tx, _) = broadcast::channel::<DomainEvent>(256);
+            let mut gw_state = GatewayState::new(event_tx.clone());
+            gw_state.set_base_url("http://127.0.0.1:0".to_string());
+            let services = Arc::new(ServiceContainer::initialize(
+                &deps,
+                event_tx.clone(),
+                Arc::new(tokio::sync::RwLock::new(gw_state)),
+            ));
+            let notifier = Arc::new(MCPNotifier::new(
+                services.feature_set_resolver.clone(),
+                services.pool_services.feature_service.clone(),
+            ));
+            notifier.clone().start(event_tx.subscribe());
+            let handler = McpMuxGatewayHandler::new(services, notifier);
+
+            let mut http_cfg = StreamableHttpServerConfig::default();
+            http_cfg.stateful_mode = true;
+            http_cfg.cancellation_token = ct.child_token();
+            let mcp_service = StreamableHttpService::new(
+                move || Ok(handler.clone()),
+                Arc::new(LocalSessionManager::default()),
+                http_cfg,
+            );
+            let identity = Arc::new(Identity {
+                client_id: CLIENT_ID.to_string(),
+                space_id,
+            });
+            let router = Router::new()
+                .nest_service("/mcp", mcp_service)
+                .layer(middleware::from_fn_with_state(identity, inject_identity));
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("bind");
+            let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+            let shutdown = ct.clone();
+            tokio::spawn(async move {
+                axum::serve(listener, router)
+                    .with_graceful_shutdown(async move { shutdown.cancelled().await })
+                    .await
+                    .unwrap();
+            });
+
+            Self { url, ct }
+        }
+
+        /// Open an initialized MCP session with the gateway
+        pub async fn connect(&self) -> RunningService<RoleClient, ()> {
+            ().serve(StreamableHttpClientTransport::from_uri(self.url.clone()))
+                .await
+                .expect("connect to gateway")
+        }
+    }
+
+    impl Drop for BenchGateway {
+        fn drop(&mut self) {
+            self.ct.cancel();
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! `mcpmux-testserver` over stdio, for integration tests that spawn a real
+//! stdio server. Cargo hands its path to the tests as
+//! `CARGO_BIN_EXE_stdio-testserver`.
+//!
+//! Usage: `stdio-testserver [spec.json]`
+
+use mcpmux_testserver::{ServerSpec, TestServer};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let spec = match std::env::args_os().nth(1) {
+        Some(path) => ServerSpec::load(path.as_ref())?,
+        None => ServerSpec::example(),
+    };
+    TestServer::new(spec).serve_stdio().await
+}
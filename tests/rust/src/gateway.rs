@@ -0,0 +1,298 @@
+//! A full gateway with `mcpmux-testserver` behind it
+//!
+//! Serves the gateway's Streamable HTTP endpoint on a random port, with the
+//! test server connected over Streamable HTTP as [`MOCK_SERVER_ID`] and
+//! every feature it offers in the Space's Starter, which rootless clients
+//! get. A test middleware stands in for OAuth.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{body::Body, http::Request, middleware, middleware::Next, response::Response, Router};
+use mcpmux_core::{
+    DomainEvent, FeatureSetRepository, MemberMode, ServerDiscoveryService, ServerFeature,
+    ServerFeatureRepository, ServerLogManager,
+};
+use mcpmux_gateway::{
+    consumers::MCPNotifier,
+    mcp::McpMuxGatewayHandler,
+    pool::{ConnectionContext, ConnectionResult, ResolvedTransport},
+    server::{DependenciesBuilder, GatewayDependencies, GatewayState, ServiceContainer},
+};
+use mcpmux_storage::{
+    InboundClient, InboundClientRepository, RegistrationType, SqliteInboundClientRepository,
+};
+use mcpmux_testserver::{HttpServer, ServerSpec, TestServer};
+use rmcp::{
+    service::RunningService,
+    transport::{
+        streamable_http_server::{
+            session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+        },
+        StreamableHttpClientTransport,
+    },
+    ClientHandler, RoleClient, ServiceExt,
+};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::db::TestDatabase;
+use crate::mocks::*;
+
+/// Server id of the test server behind the gateway
+pub const MOCK_SERVER_ID: &str = "mock";
+
+/// Stands in for OAuth: every request comes from the gateway's client
+async fn identity_middleware(
+    axum::extract::State(identity): axum::extract::State<Arc<(String, Uuid)>>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let (client_id, space_id) = &*identity;
+    request
+        .headers_mut()
+        .insert("x-mcpmux-client-id", client_id.parse().unwrap());
+    request
+        .headers_mut()
+        .insert("x-mcpmux-space-id", space_id.to_string().parse().unwrap());
+    next.run(request).await
+}
+
+/// The gateway, shut down on drop
+pub struct MockGateway {
+    /// MCP endpoint
+    pub url: String,
+    pub space_id: Uuid,
+    /// Inbound client every request comes from
+    pub client_id: String,
+    pub services: Arc<ServiceContainer>,
+    pub feature_repo: Arc<MockServerFeatureRepository>,
+    pub feature_set_repo: Arc<MockFeatureSetRepository>,
+    pub event_tx: broadcast::Sender<DomainEvent>,
+    /// The test server behind the gateway
+    pub server: TestServer,
+    http: HttpServer,
+    ct: CancellationToken,
+}
+
+impl MockGateway {
+    /// Start a gateway in front of a test server serving `spec`, its
+    /// features qualified with `prefix`
+    pub async fn start(spec: ServerSpec, prefix: &str) -> Self {
+        let server = TestServer::new(spec);
+        let http = server
+            .clone()
+            .serve_http("127.0.0.1:0".parse().unwrap())
+            .await
+            .expect("serve test server");
+
+        let database = Arc::new(tokio::sync::Mutex::new(TestDatabase::in_memory().db));
+        let feature_repo = Arc::new(MockServerFeatureRepository::new());
+        let feature_set_repo = Arc::new(MockFeatureSetRepository::new());
+
+        let space_id = Uuid::new_v4();
+        let space_repo = Arc::new(mcpmux_storage::SqliteSpaceRepository::new(database.clone()));
+        let mut space = mcpmux_core::domain::Space::new("Mock").set_default();
+        space.id = space_id;
+        mcpmux_core::SpaceRepository::create(&*space_repo, &space)
+            .await
+            .expect("create space");
+        mcpmux_core::SpaceRepository::set_default(&*space_repo, &space_id)
+            .await
+            .expect("set default");
+
+        let client_id = Uuid::new_v4().to_string();
+        let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(database.clone()));
+        let now = chrono::Utc::now().to_rfc3339();
+        inbound_client_repo
+            .save_client(&InboundClient {
+                client_id: client_id.clone(),
+                registration_type: RegistrationType::Dcr,
+                client_name: "mock-gateway-client".to_string(),
+                client_alias: None,
+                redirect_uris: vec![],
+                grant_types: vec!["authorization_code".to_string()],
+                response_types: vec!["code".to_string()],
+                token_endpoint_auth_method: "none".to_string(),
+                scope: None,
+                approved: true,
+                logo_uri: None,
+                client_uri: None,
+                software_id: None,
+                software_version: None,
+                metadata_url: None,
+                metadata_cached_at: None,
+                metadata_cache_ttl: None,
+                last_seen: None,
+                created_at: now.clone(),
+                updated_at: now,
+                reports_roots: false,
+                roots_capability_known: false,
+                suspended: false,
+            })
+            .await
+            .expect("save client");
+
+        let deps = DependenciesBuilder::new()
+            .with_installed_server_repo(Arc::new(MockInstalledServerRepository::new()))
+            .with_credential_repo(Arc::new(MockCredentialRepository::new()))
+            .with_backend_oauth_repo(Arc::new(MockOutboundOAuthRepository::new()))
+            .with_feature_repo(feature_repo.clone() as Arc<dyn ServerFeatureRepository>)
+            .with_feature_set_repo(feature_set_repo.clone() as Arc<dyn FeatureSetRepository>)
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                std::path::PathBuf::from("test-data"),
+                std::path::PathBuf::from("test-spaces"),
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(
+                mcpmux_core::LogConfig::default(),
+            )))
+            .with_database(database)
+            .build()
+            .expect("build dependencies");
+        let deps = GatewayDependencies {
+            space_repo: space_repo as Arc<dyn mcpmux_core::SpaceRepository>,
+            inbound_client_repo,
+            ..deps
+        };
+
+        let (event_tx, _) = broadcast::channel::<DomainEvent>(256);
+        let mut state = GatewayState::new(event_tx.clone());
+        state.set_base_url("http://127.0.0.1:0".to_string());
+        let services = Arc::new(ServiceContainer::initialize(
+            &deps,
+            event_tx.clone(),
+            Arc::new(tokio::sync::RwLock::new(state)),
+        ));
+        services
+            .prefix_cache_service
+            .assign_prefix_runtime(&space_id.to_string(), MOCK_SERVER_ID, Some(prefix))
+            .await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let url = format!(
+            "http://127.0.0.1:{}/mcp",
+            listener.local_addr().unwrap().port()
+        );
+        let gateway = Self {
+            url,
+            space_id,
+            client_id: client_id.clone(),
+            services,
+            feature_repo,
+            feature_set_repo,
+            event_tx,
+            server,
+            http,
+            ct: CancellationToken::new(),
+        };
+        gateway.connect_server().await;
+        gateway
+            .feature_set_repo
+            .ensure_builtin_for_space(&space_id.to_string())
+            .await
+            .unwrap();
+        for feature in gateway.discovered().await {
+            gateway.grant(&feature).await;
+        }
+
+        let notifier = Arc::new(MCPNotifier::new(
+            gateway.services.feature_set_resolver.clone(),
+            gateway.services.pool_services.feature_service.clone(),
+        ));
+        notifier.clone().start(gateway.event_tx.subscribe());
+        let handler = McpMuxGatewayHandler::new(gateway.services.clone(), notifier);
+
+        let mut config = StreamableHttpServerConfig::default();
+        config.stateful_mode = true;
+        config.json_response = false;
+        config.cancellation_token = gateway.ct.child_token();
+        let mcp_service = StreamableHttpService::new(
+            move || Ok(handler.clone()),
+            Arc::new(LocalSessionManager::default()),
+            config,
+        );
+        let router =
+            Router::new()
+                .nest_service("/mcp", mcp_service)
+                .layer(middleware::from_fn_with_state(
+                    Arc::new((client_id, space_id)),
+                    identity_middleware,
+                ));
+
+        let ct = gateway.ct.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled().await })
+                .await
+                .unwrap();
+        });
+
+        gateway
+    }
+
+    /// Connect the test server, or reconnect it after a disconnect, which
+    /// rediscovers its features
+    pub async fn connect_server(&self) {
+        let ctx = ConnectionContext::new(
+            self.space_id,
+            MOCK_SERVER_ID,
+            ResolvedTransport::Http {
+                url: self.http.url.clone(),
+                headers: HashMap::new(),
+                query: HashMap::new(),
+            },
+        );
+        match self
+            .services
+            .pool_services
+            .pool_service
+            .connect_server(&ctx)
+            .await
+        {
+            ConnectionResult::Connected { .. } => {}
+            ConnectionResult::OAuthRequired { .. } => panic!("test server asked for OAuth"),
+            ConnectionResult::Failed { error } => panic!("connect failed: {}", error),
+        }
+    }
+
+    /// Features discovered on the test server
+    pub async fn discovered(&self) -> Vec<ServerFeature> {
+        self.feature_repo
+            .list_for_server(&self.space_id.to_string(), MOCK_SERVER_ID)
+            .await
+            .unwrap()
+    }
+
+    /// Add a feature to the Starter
+    pub async fn grant(&self, feature: &ServerFeature) {
+        let starter = self
+            .feature_set_repo
+            .get_starter_for_space(&self.space_id.to_string())
+            .await
+            .unwrap()
+            .expect("starter feature set");
+        self.feature_set_repo
+            .add_feature_member(&starter.id, &feature.id.to_string(), MemberMode::Include)
+            .await
+            .unwrap();
+    }
+
+    /// Connect an MCP client to the gateway
+    pub async fn client<H: ClientHandler>(&self, handler: H) -> RunningService<RoleClient, H> {
+        let transport = StreamableHttpClientTransport::from_uri(self.url.clone());
+        let client = handler.serve(transport).await.expect("connect to gateway");
+        // Session registration and hash priming finish after initialize
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        client
+    }
+}
+
+impl Drop for MockGateway {
+    fn drop(&mut self) {
+        self.ct.cancel();
+    }
+}
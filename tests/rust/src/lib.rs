@@ -15,6 +15,12 @@ pub use mocks::MockRepositories;
 pub mod services;
 pub use services::ServerManagerTestHarness;
 
+/// A full gateway in front of a test server
+pub mod gateway;
+
+/// Replay of recorded sessions through the gateway
+pub mod replay;
+
 /// Event testing utilities
 pub mod events {
     use mcpmux_core::DomainEvent;
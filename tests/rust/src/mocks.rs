@@ -3,7 +3,9 @@
 //! In-memory implementations of all repository traits for fast, isolated tests.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::RwLock;
 use uuid::Uuid;
 
@@ -77,6 +79,13 @@ impl SpaceRepository for MockSpaceRepository {
         *self.default_id.write().unwrap() = Some(*id);
         Ok(())
     }
+
+    async fn set_owner(&self, id: &Uuid, owner_id: Option<&Uuid>) -> RepoResult<()> {
+        if let Some(space) = self.spaces.write().unwrap().get_mut(id) {
+            space.owner_id = owner_id.copied();
+        }
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -86,6 +95,7 @@ impl SpaceRepository for MockSpaceRepository {
 #[derive(Default)]
 pub struct MockInstalledServerRepository {
     servers: RwLock<HashMap<Uuid, InstalledServer>>,
+    fail_lookups: AtomicBool,
 }
 
 impl MockInstalledServerRepository {
@@ -97,12 +107,24 @@ impl MockInstalledServerRepository {
         self.servers.write().unwrap().insert(server.id, server);
         self
     }
+
+    /// Make `get_by_server_id` fail, as when the database is unavailable
+    pub fn fail_lookups(&self) {
+        self.fail_lookups.store(true, Ordering::SeqCst);
+    }
 }
 
 #[async_trait]
 impl InstalledServerRepository for MockInstalledServerRepository {
     async fn list(&self) -> RepoResult<Vec<InstalledServer>> {
-        Ok(self.servers.read().unwrap().values().cloned().collect())
+        Ok(self
+            .servers
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| s.deleted_at.is_none())
+            .cloned()
+            .collect())
     }
 
     async fn list_for_space(&self, space_id: &str) -> RepoResult<Vec<InstalledServer>> {
@@ -111,7 +133,7 @@ impl InstalledServerRepository for MockInstalledServerRepository {
             .read()
             .unwrap()
             .values()
-            .filter(|s| s.space_id == space_id)
+            .filter(|s| s.space_id == space_id && s.deleted_at.is_none())
             .cloned()
             .collect())
     }
@@ -124,7 +146,13 @@ impl InstalledServerRepository for MockInstalledServerRepository {
     }
 
     async fn get(&self, id: &Uuid) -> RepoResult<Option<InstalledServer>> {
-        Ok(self.servers.read().unwrap().get(id).cloned())
+        Ok(self
+            .servers
+            .read()
+            .unwrap()
+            .get(id)
+            .filter(|s| s.deleted_at.is_none())
+            .cloned())
     }
 
     async fn get_by_server_id(
@@ -132,12 +160,15 @@ impl InstalledServerRepository for MockInstalledServerRepository {
         space_id: &str,
         server_id: &str,
     ) -> RepoResult<Option<InstalledServer>> {
+        if self.fail_lookups.load(Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("database is unavailable"));
+        }
         Ok(self
             .servers
             .read()
             .unwrap()
             .values()
-            .find(|s| s.space_id == space_id && s.server_id == server_id)
+            .find(|s| s.space_id == space_id && s.server_id == server_id && s.deleted_at.is_none())
             .cloned())
     }
 
@@ -162,13 +193,40 @@ impl InstalledServerRepository for MockInstalledServerRepository {
         Ok(())
     }
 
+    async fn trash(&self, id: &Uuid, at: DateTime<Utc>) -> RepoResult<()> {
+        if let Some(server) = self.servers.write().unwrap().get_mut(id) {
+            server.deleted_at = Some(at);
+        }
+        Ok(())
+    }
+
+    async fn restore(&self, id: &Uuid) -> RepoResult<()> {
+        if let Some(server) = self.servers.write().unwrap().get_mut(id) {
+            server.deleted_at = None;
+        }
+        Ok(())
+    }
+
+    async fn list_trashed(&self) -> RepoResult<Vec<InstalledServer>> {
+        let mut trashed: Vec<_> = self
+            .servers
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| s.deleted_at.is_some())
+            .cloned()
+            .collect();
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(trashed)
+    }
+
     async fn list_enabled(&self, space_id: &str) -> RepoResult<Vec<InstalledServer>> {
         Ok(self
             .servers
             .read()
             .unwrap()
             .values()
-            .filter(|s| s.space_id == space_id && s.enabled)
+            .filter(|s| s.space_id == space_id && s.enabled && s.deleted_at.is_none())
             .cloned()
             .collect())
     }
@@ -179,7 +237,7 @@ impl InstalledServerRepository for MockInstalledServerRepository {
             .read()
             .unwrap()
             .values()
-            .filter(|s| s.enabled)
+            .filter(|s| s.enabled && s.deleted_at.is_none())
             .cloned()
             .collect())
     }
@@ -198,6 +256,13 @@ impl InstalledServerRepository for MockInstalledServerRepository {
         Ok(())
     }
 
+    async fn set_last_used(&self, id: &Uuid, at: DateTime<Utc>) -> RepoResult<()> {
+        if let Some(server) = self.servers.write().unwrap().get_mut(id) {
+            server.last_used_at = Some(at);
+        }
+        Ok(())
+    }
+
     async fn update_inputs(
         &self,
         id: &Uuid,
@@ -426,13 +491,7 @@ impl FeatureSetRepository for MockFeatureSetRepository {
         feature_id: &str,
         mode: MemberMode,
     ) -> RepoResult<()> {
-        let member = FeatureSetMember {
-            id: Uuid::new_v4().to_string(),
-            feature_set_id: feature_set_id.to_string(),
-            member_type: MemberType::Feature,
-            member_id: feature_id.to_string(),
-            mode,
-        };
+        let member = FeatureSetMember::new(feature_set_id, MemberType::Feature, feature_id, mode);
         self.members
             .write()
             .unwrap()
@@ -0,0 +1,236 @@
+//! Deterministic replay of recorded sessions
+//!
+//! A [`SessionRecording`] holds what a client asked the gateway for and the
+//! call the gateway routed to the server for each request. Replaying it
+//! sends the same requests, one at a time, through a fresh [`MockGateway`]
+//! in front of a test server serving the recording's spec, and compares
+//! the routed calls, captured with the gateway's traffic recorder, with
+//! the recorded ones.
+//!
+//! Recordings come from traffic exported from a server's Traffic tab in the
+//! desktop app ([`SessionRecording::from_traffic`]), or are written by hand
+//! to reproduce a report, e.g.
+//!
+//! ```json
+//! {
+//!   "prefix": "gh",
+//!   "spec": { "tools": [{ "name": "search_code" }] },
+//!   "steps": [
+//!     { "request": { "method": "tools/call", "params": { "name": "gh_search_code" } },
+//!       "routed": { "method": "tools/call", "params": { "name": "search_code" } } },
+//!     { "request": { "method": "tools/call", "params": { "name": "gh_missing" } },
+//!       "routed": null }
+//!   ]
+//! }
+//! ```
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use mcpmux_gateway::{TrafficDirection, TrafficFrame};
+use mcpmux_testserver::ServerSpec;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::gateway::{MockGateway, MOCK_SERVER_ID};
+
+/// Methods whose requests the gateway routes to a server
+const ROUTED_METHODS: [&str; 3] = ["tools/call", "prompts/get", "resources/read"];
+
+/// A recorded session against one server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecording {
+    /// What the test server behind the gateway offers
+    pub spec: ServerSpec,
+    /// Prefix qualifying the server's tools and prompts
+    pub prefix: String,
+    pub steps: Vec<ReplayStep>,
+}
+
+/// One client request and what reached the server for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub request: SessionRequest,
+    /// `None` if the request must not reach the server
+    pub routed: Option<RoutedCall>,
+}
+
+/// A `tools/call`, `prompts/get` or `resources/read` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRequest {
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A request as the server received it, reduced to what routing decides:
+/// the server's own name (or URI) and the arguments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutedCall {
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl RoutedCall {
+    /// The routed part of a request; `None` for requests that aren't
+    /// routed, such as the handshake, lists and pings
+    pub fn from_request(method: &str, params: &Value) -> Option<Self> {
+        let keys: &[&str] = match method {
+            "tools/call" | "prompts/get" => &["name", "arguments"],
+            "resources/read" => &["uri"],
+            _ => return None,
+        };
+        let params: Map<String, Value> = keys
+            .iter()
+            .filter_map(|key| Some((key.to_string(), params.get(*key)?.clone())))
+            .collect();
+        Some(Self {
+            method: method.to_string(),
+            params: Value::Object(params),
+        })
+    }
+
+    /// The routed requests in recorded traffic, oldest first
+    pub fn from_traffic(frames: &[TrafficFrame]) -> Result<Vec<Self>> {
+        let mut calls = Vec::new();
+        for frame in frames {
+            if frame.direction != TrafficDirection::Outgoing {
+                continue;
+            }
+            let Some(method) = frame
+                .method
+                .as_deref()
+                .filter(|method| ROUTED_METHODS.contains(method))
+            else {
+                continue;
+            };
+            if frame.truncated {
+                bail!("Frame {} ({}) is truncated", frame.seq, method);
+            }
+            let message: Value = serde_json::from_str(&frame.raw)
+                .with_context(|| format!("Frame {} is not JSON", frame.seq))?;
+            calls.extend(Self::from_request(method, &message["params"]));
+        }
+        Ok(calls)
+    }
+}
+
+/// A step whose request was routed differently than recorded
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    /// Index into the recording's steps
+    pub step: usize,
+    pub request: SessionRequest,
+    pub expected: Option<RoutedCall>,
+    /// Every call routed for the request; retries show up more than once
+    pub actual: Vec<RoutedCall>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {}: {} {} routed to {}, recorded {}",
+            self.step,
+            self.request.method,
+            self.request.params,
+            serde_json::to_string(&self.actual).unwrap_or_default(),
+            serde_json::to_string(&self.expected).unwrap_or_default()
+        )
+    }
+}
+
+impl SessionRecording {
+    /// A recording of the routed requests in a server's exported traffic,
+    /// each replayed as the client request the prefix qualifies it to
+    pub fn from_traffic(spec: ServerSpec, prefix: &str, frames: &[TrafficFrame]) -> Result<Self> {
+        let steps = RoutedCall::from_traffic(frames)?
+            .into_iter()
+            .map(|call| {
+                let mut params = call.params.clone();
+                if call.method != "resources/read" {
+                    if let Some(name) = params.get("name").and_then(Value::as_str) {
+                        params["name"] = Value::String(format!("{}_{}", prefix, name));
+                    }
+                }
+                ReplayStep {
+                    request: SessionRequest {
+                        method: call.method.clone(),
+                        params,
+                    },
+                    routed: Some(call),
+                }
+            })
+            .collect();
+        Ok(Self {
+            spec,
+            prefix: prefix.to_string(),
+            steps,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let recording: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid session recording in {}", path.display()))?;
+        recording.spec.validate()?;
+        for step in &recording.steps {
+            if !ROUTED_METHODS.contains(&step.request.method.as_str()) {
+                bail!("Can't replay {} requests", step.request.method);
+            }
+        }
+        Ok(recording)
+    }
+
+    /// Replay the session against a fresh gateway. Returns the steps that
+    /// were routed differently; a request failing is not a mismatch.
+    pub async fn replay(&self) -> Result<Vec<Mismatch>> {
+        let gateway = MockGateway::start(self.spec.clone(), &self.prefix).await;
+        let traffic = gateway.services.pool_services.pool_service.traffic();
+        traffic.set_enabled(gateway.space_id, MOCK_SERVER_ID, true);
+        let client = gateway.client(()).await;
+        let peer = client.peer();
+
+        let mut next_seq = 0;
+        let mut mismatches = Vec::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            let params = step.request.params.clone();
+            match step.request.method.as_str() {
+                "tools/call" => {
+                    let _ = peer.call_tool(serde_json::from_value(params)?).await;
+                }
+                "prompts/get" => {
+                    let _ = peer.get_prompt(serde_json::from_value(params)?).await;
+                }
+                "resources/read" => {
+                    let _ = peer.read_resource(serde_json::from_value(params)?).await;
+                }
+                other => bail!("Can't replay {} requests", other),
+            }
+
+            let frames: Vec<TrafficFrame> = traffic
+                .frames(gateway.space_id, MOCK_SERVER_ID)
+                .into_iter()
+                .filter(|frame| frame.seq >= next_seq)
+                .collect();
+            if let Some(last) = frames.last() {
+                next_seq = last.seq + 1;
+            }
+            let actual = RoutedCall::from_traffic(&frames)?;
+            if actual.as_slice() != step.routed.as_slice() {
+                mismatches.push(Mismatch {
+                    step: index,
+                    request: step.request.clone(),
+                    expected: step.routed.clone(),
+                    actual,
+                });
+            }
+        }
+
+        client.cancel().await.ok();
+        Ok(mismatches)
+    }
+}
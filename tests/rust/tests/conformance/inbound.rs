@@ -0,0 +1,159 @@
+//! Inbound: a client of the gateway's Streamable HTTP endpoint, with the
+//! test server connected behind it as `mock`
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mcpmux_core::{DomainEvent, FeatureSetRepository};
+use mcpmux_testserver::ToolSpec;
+use rmcp::{model::*, service::NotificationContext, RoleClient};
+use tokio::sync::Notify;
+
+use super::scenarios::{self, spec, Expected};
+use tests::gateway::{MockGateway, MOCK_SERVER_ID};
+
+async fn start() -> MockGateway {
+    MockGateway::start(spec(), MOCK_SERVER_ID).await
+}
+
+fn expected() -> Expected {
+    let spec = spec();
+    Expected {
+        server_name: "mcpmux-gateway",
+        tools: spec
+            .tools
+            .iter()
+            .map(|tool| format!("{}_{}", MOCK_SERVER_ID, tool.name))
+            .collect(),
+        prompts: spec
+            .prompts
+            .iter()
+            .map(|prompt| format!("{}_{}", MOCK_SERVER_ID, prompt.name))
+            .collect(),
+        resources: spec.resources.iter().map(|res| res.uri.clone()).collect(),
+        page_size: None,
+    }
+}
+
+#[derive(Clone, Default)]
+struct ConformanceClient {
+    /// Requested instead of the latest version
+    protocol_version: Option<ProtocolVersion>,
+    tools_changed: Arc<Notify>,
+}
+
+impl rmcp::ClientHandler for ConformanceClient {
+    fn get_info(&self) -> ClientInfo {
+        let mut info = ClientInfo::new(
+            ClientCapabilities::default(),
+            Implementation::new("conformance-client", "1.0.0"),
+        );
+        if let Some(version) = &self.protocol_version {
+            info.protocol_version = version.clone();
+        }
+        info
+    }
+
+    fn on_tool_list_changed(
+        &self,
+        _context: NotificationContext<RoleClient>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.tools_changed.notify_one();
+        async {}
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn initialize() {
+    let gw = start().await;
+    let client = gw.client(ConformanceClient::default()).await;
+    scenarios::initialize(client.peer(), &expected());
+    scenarios::capabilities(client.peer(), &expected());
+    client.cancel().await.ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn initialize_negotiates_an_older_protocol_version() {
+    let gw = start().await;
+    let client = gw
+        .client(ConformanceClient {
+            protocol_version: Some(ProtocolVersion::V_2024_11_05),
+            ..Default::default()
+        })
+        .await;
+    let info = client.peer().peer_info().expect("server info");
+    assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+    scenarios::pagination(client.peer(), &expected()).await;
+    client.cancel().await.ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pagination() {
+    let gw = start().await;
+    let client = gw.client(ConformanceClient::default()).await;
+    scenarios::pagination(client.peer(), &expected()).await;
+    client.cancel().await.ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn notifications() {
+    let gw = start().await;
+    let handler = ConformanceClient::default();
+    let tools_changed = handler.tools_changed.clone();
+    let client = gw.client(handler).await;
+
+    // The server grows a tool; the gateway rediscovers on reconnect, then the
+    // server's own list_changed is forwarded to the client
+    let mut tools = spec().tools;
+    tools.push(ToolSpec::echo("added"));
+    gw.server.set_tools(tools);
+    gw.services
+        .pool_services
+        .pool_service
+        .disconnect_server(gw.space_id, MOCK_SERVER_ID)
+        .await
+        .expect("disconnect");
+    gw.connect_server().await;
+    client
+        .peer()
+        .call_tool(CallToolRequestParams::new("mock_refresh"))
+        .await
+        .expect("call refresh");
+    tokio::time::timeout(Duration::from_secs(5), tools_changed.notified())
+        .await
+        .expect("tools/list_changed reaches the client");
+
+    // Granted, the new tool is listed on the client's next tools/list
+    let added = gw
+        .discovered()
+        .await
+        .into_iter()
+        .find(|feature| feature.feature_name == "added")
+        .expect("added tool discovered");
+    gw.grant(&added).await;
+    let starter = gw
+        .feature_set_repo
+        .get_starter_for_space(&gw.space_id.to_string())
+        .await
+        .unwrap()
+        .expect("starter feature set");
+    let _ = gw.event_tx.send(DomainEvent::FeatureSetMembersChanged {
+        space_id: gw.space_id,
+        feature_set_id: starter.id,
+        added_count: 1,
+        removed_count: 0,
+    });
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let tools = client.peer().list_all_tools().await.expect("tools/list");
+            if tools.iter().any(|tool| tool.name == "mock_added") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("added tool listed");
+
+    client.cancel().await.ok();
+}
@@ -0,0 +1,17 @@
+//! MCP Conformance Tests
+//!
+//! Runs the same protocol scenarios against every MCP connection McpMux
+//! makes, with `mcpmux-testserver` on the other end:
+//! - Inbound: a client talking to the gateway's Streamable HTTP endpoint
+//! - Outbound: the gateway's pool talking to a server over each transport
+//!   (Streamable HTTP and stdio)
+//!
+//! Scenarios:
+//! - initialize: handshake, server info and protocol version
+//! - capability negotiation: advertised capabilities match what is served
+//! - pagination: cursors are followed to the end of every list
+//! - notifications: `list_changed` reaches the other side
+
+mod inbound;
+mod outbound;
+mod scenarios;
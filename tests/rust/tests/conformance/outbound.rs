@@ -0,0 +1,234 @@
+//! Outbound: the gateway's pool as the client of a server on each transport
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mcpmux_core::{DomainEvent, ServerDiscoveryService, ServerFeatureRepository, ServerLogManager};
+use mcpmux_gateway::pool::{
+    ConnectionContext, ConnectionResult, PoolServices, ResolvedTransport, ServiceFactory,
+};
+use mcpmux_gateway::server::DependenciesBuilder;
+use mcpmux_gateway::PrefixCacheService;
+use mcpmux_testserver::{HttpServer, ServerSpec, TestServer};
+use rmcp::model::CallToolRequestParams;
+use rmcp::service::Peer;
+use rmcp::RoleClient;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::scenarios::{self, spec, Expected};
+use tests::db::TestDatabase;
+use tests::mocks::*;
+
+const SERVER_ID: &str = "mock";
+
+fn expected(spec: &ServerSpec) -> Expected {
+    Expected {
+        server_name: "conformance",
+        tools: spec.tools.iter().map(|tool| tool.name.clone()).collect(),
+        prompts: spec
+            .prompts
+            .iter()
+            .map(|prompt| prompt.name.clone())
+            .collect(),
+        resources: spec.resources.iter().map(|res| res.uri.clone()).collect(),
+        page_size: spec.page_size,
+    }
+}
+
+/// The server under test, kept running for the length of a test
+enum Server {
+    Http(TestServer, HttpServer),
+    Stdio(tempfile::TempDir),
+}
+
+struct Fixture {
+    space_id: Uuid,
+    pool: PoolServices,
+    event_tx: broadcast::Sender<DomainEvent>,
+    feature_repo: Arc<MockServerFeatureRepository>,
+    server: Server,
+}
+
+impl Fixture {
+    async fn http(spec: ServerSpec) -> Self {
+        let server = TestServer::new(spec);
+        let http = server
+            .clone()
+            .serve_http("127.0.0.1:0".parse().unwrap())
+            .await
+            .expect("serve test server");
+        let transport = ResolvedTransport::Http {
+            url: http.url.clone(),
+            headers: HashMap::new(),
+            query: HashMap::new(),
+        };
+        Self::connect(transport, Server::Http(server, http)).await
+    }
+
+    async fn stdio(spec: ServerSpec) -> Self {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("spec.json");
+        std::fs::write(&path, serde_json::to_vec(&spec).unwrap()).expect("write spec");
+        let transport = ResolvedTransport::Stdio {
+            command: env!("CARGO_BIN_EXE_stdio-testserver").to_string(),
+            args: vec![path.to_string_lossy().into_owned()],
+            env: HashMap::new(),
+            inherit_env: false,
+        };
+        Self::connect(transport, Server::Stdio(dir)).await
+    }
+
+    async fn connect(transport: ResolvedTransport, server: Server) -> Self {
+        let feature_repo = Arc::new(MockServerFeatureRepository::new());
+        let deps = DependenciesBuilder::new()
+            .with_installed_server_repo(Arc::new(MockInstalledServerRepository::new()))
+            .with_credential_repo(Arc::new(MockCredentialRepository::new()))
+            .with_backend_oauth_repo(Arc::new(MockOutboundOAuthRepository::new()))
+            .with_feature_repo(feature_repo.clone() as Arc<dyn ServerFeatureRepository>)
+            .with_feature_set_repo(Arc::new(MockFeatureSetRepository::new()))
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                std::path::PathBuf::from("test-data"),
+                std::path::PathBuf::from("test-spaces"),
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(
+                mcpmux_core::LogConfig::default(),
+            )))
+            .with_database(Arc::new(tokio::sync::Mutex::new(
+                TestDatabase::in_memory().db,
+            )))
+            .build()
+            .expect("build dependencies");
+
+        let space_id = Uuid::new_v4();
+        let prefix_cache = Arc::new(PrefixCacheService::new());
+        prefix_cache
+            .assign_prefix_runtime(&space_id.to_string(), SERVER_ID, Some(SERVER_ID))
+            .await;
+        let (event_tx, _) = broadcast::channel(256);
+        let pool = ServiceFactory::create_pool_services(&deps, event_tx.clone(), prefix_cache);
+
+        let ctx = ConnectionContext::new(space_id, SERVER_ID, transport);
+        match pool.pool_service.connect_server(&ctx).await {
+            ConnectionResult::Connected { .. } => {}
+            ConnectionResult::OAuthRequired { .. } => panic!("test server asked for OAuth"),
+            ConnectionResult::Failed { error } => panic!("connect failed: {}", error),
+        }
+
+        Self {
+            space_id,
+            pool,
+            event_tx,
+            feature_repo,
+            server,
+        }
+    }
+
+    /// The pool's client session with the server
+    fn peer(&self) -> Peer<RoleClient> {
+        self.pool
+            .pool_service
+            .get_instance(self.space_id, SERVER_ID)
+            .and_then(|instance| instance.with_client(|client| client.peer().clone()))
+            .expect("connected instance")
+    }
+
+    /// Names (URIs for resources) of the features discovery stored
+    async fn discovered(&self) -> Vec<String> {
+        self.feature_repo
+            .list_for_server(&self.space_id.to_string(), SERVER_ID)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|feature| feature.feature_name)
+            .collect()
+    }
+}
+
+async fn initialize(f: Fixture) {
+    let expected = expected(&spec());
+    let peer = f.peer();
+    scenarios::initialize(&peer, &expected);
+    scenarios::capabilities(&peer, &expected);
+
+    if let Server::Http(server, _) = &f.server {
+        let clients = server.clients();
+        assert!(
+            clients.iter().any(|client| client.name == "mcpmux-mock"),
+            "server saw the gateway's client info, got {:?}",
+            clients
+        );
+    }
+}
+
+async fn pagination(f: Fixture) {
+    let expected = expected(&spec());
+    scenarios::pagination(&f.peer(), &expected).await;
+
+    // Discovery followed every cursor too
+    let discovered = f.discovered().await;
+    for name in expected
+        .tools
+        .iter()
+        .chain(&expected.prompts)
+        .chain(&expected.resources)
+    {
+        assert!(discovered.contains(name), "{} not discovered", name);
+    }
+}
+
+async fn notifications(f: Fixture) {
+    let events = f.event_tx.subscribe();
+    f.peer()
+        .call_tool(CallToolRequestParams::new("refresh"))
+        .await
+        .expect("call refresh");
+
+    let event = tests::events::wait_for_event(events, Duration::from_secs(5), |event| {
+        matches!(event, DomainEvent::ToolsChanged { server_id, .. } if server_id == SERVER_ID)
+    })
+    .await;
+    assert!(
+        event.is_some(),
+        "tools/list_changed from the server reached the event bus"
+    );
+}
+
+mod http {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn initialize() {
+        super::initialize(Fixture::http(spec()).await).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pagination() {
+        super::pagination(Fixture::http(spec()).await).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn notifications() {
+        super::notifications(Fixture::http(spec()).await).await;
+    }
+}
+
+mod stdio {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn initialize() {
+        super::initialize(Fixture::stdio(spec()).await).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pagination() {
+        super::pagination(Fixture::stdio(spec()).await).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn notifications() {
+        super::notifications(Fixture::stdio(spec()).await).await;
+    }
+}
@@ -0,0 +1,155 @@
+//! Scenarios shared by every connection under test, run from the client end
+
+use mcpmux_testserver::{PromptSpec, ResourceSpec, ServerSpec, ToolResponse, ToolSpec};
+use rmcp::model::{PaginatedRequestParams, ProtocolVersion};
+use rmcp::service::Peer;
+use rmcp::RoleClient;
+use serde_json::json;
+
+/// Five tools, three prompts and three resources, two to a page
+pub fn spec() -> ServerSpec {
+    ServerSpec {
+        name: "conformance".to_string(),
+        page_size: Some(2),
+        tools: vec![
+            ToolSpec::echo("echo"),
+            ToolSpec::text("hello", "Hello"),
+            ToolSpec::text("status", "ok"),
+            ToolSpec::echo("search"),
+            ToolSpec::new("refresh", ToolResponse::ListChanged),
+        ],
+        prompts: ["greet", "summarize", "review"]
+            .into_iter()
+            .map(|name| PromptSpec {
+                name: name.to_string(),
+                description: None,
+                arguments: Vec::new(),
+                text: format!("Prompt {}", name),
+            })
+            .collect(),
+        resources: ["readme", "license", "changelog"]
+            .into_iter()
+            .map(|name| ResourceSpec {
+                uri: format!("test://{}", name),
+                name: Some(name.to_string()),
+                mime_type: Some("text/plain".to_string()),
+                text: format!("Resource {}", name),
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// What the server at the other end serves. It may serve more, such as the
+/// gateway's own `mcpmux_*` tools.
+pub struct Expected {
+    pub server_name: &'static str,
+    pub tools: Vec<String>,
+    pub prompts: Vec<String>,
+    /// Resource URIs
+    pub resources: Vec<String>,
+    /// Items per page of a list, or `None` for every item in one page
+    pub page_size: Option<usize>,
+}
+
+/// The handshake completed with the expected server on a version the client
+/// supports
+pub fn initialize(peer: &Peer<RoleClient>, expected: &Expected) {
+    let info = peer.peer_info().expect("server info after initialize");
+    assert_eq!(info.server_info.name, expected.server_name);
+    assert!(!info.server_info.version.is_empty());
+    assert!(
+        info.protocol_version.to_string() <= ProtocolVersion::LATEST.to_string(),
+        "server answered with {}, newer than the client's {}",
+        info.protocol_version,
+        ProtocolVersion::LATEST
+    );
+}
+
+/// Everything served is advertised, including that tool list changes are
+/// announced
+pub fn capabilities(peer: &Peer<RoleClient>, expected: &Expected) {
+    let capabilities = &peer.peer_info().expect("server info").capabilities;
+    let tools = capabilities.tools.as_ref().expect("tools capability");
+    assert_eq!(tools.list_changed, Some(true));
+    if !expected.prompts.is_empty() {
+        assert!(capabilities.prompts.is_some(), "prompts capability");
+    }
+    if !expected.resources.is_empty() {
+        assert!(capabilities.resources.is_some(), "resources capability");
+    }
+}
+
+fn at(cursor: &str) -> Option<PaginatedRequestParams> {
+    Some(serde_json::from_value(json!({ "cursor": cursor })).expect("cursor params"))
+}
+
+/// Walking `tools/list` page by page reaches the end and agrees with the
+/// client's own cursor following, and every list holds what is served
+pub async fn pagination(peer: &Peer<RoleClient>, expected: &Expected) {
+    let mut names = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages = 0;
+    loop {
+        let page = peer
+            .list_tools(cursor.as_deref().and_then(at))
+            .await
+            .expect("tools/list");
+        if let Some(size) = expected.page_size {
+            assert!(page.tools.len() <= size, "page larger than {}", size);
+        }
+        names.extend(page.tools.iter().map(|tool| tool.name.to_string()));
+        pages += 1;
+        assert!(pages <= names.len() + 1, "cursors stopped making progress");
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    let expected_pages = match expected.page_size {
+        Some(size) => names.len().div_ceil(size).max(1),
+        None => 1,
+    };
+    assert_eq!(pages, expected_pages);
+
+    let all: Vec<String> = peer
+        .list_all_tools()
+        .await
+        .expect("all tools")
+        .iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    assert_eq!(all, names);
+    for tool in &expected.tools {
+        assert!(names.contains(tool), "tool {} not listed", tool);
+    }
+
+    let prompts: Vec<String> = peer
+        .list_all_prompts()
+        .await
+        .expect("all prompts")
+        .iter()
+        .map(|prompt| prompt.name.to_string())
+        .collect();
+    for prompt in &expected.prompts {
+        assert!(prompts.contains(prompt), "prompt {} not listed", prompt);
+    }
+
+    let resources: Vec<String> = peer
+        .list_all_resources()
+        .await
+        .expect("all resources")
+        .iter()
+        .map(|resource| resource.uri.to_string())
+        .collect();
+    for uri in &expected.resources {
+        assert!(resources.contains(uri), "resource {} not listed", uri);
+    }
+
+    if expected.page_size.is_some() {
+        assert!(
+            peer.list_tools(at("not-a-cursor")).await.is_err(),
+            "invalid cursors are rejected"
+        );
+    }
+}
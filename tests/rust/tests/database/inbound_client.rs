@@ -4,8 +4,8 @@
 //! These test the INBOUND flow: AI clients (Cursor, Claude) connecting TO McpMux.
 
 use mcpmux_storage::{
-    AuthorizationCode, InboundClient, InboundClientRepository, RegistrationType, TokenRecord,
-    TokenType,
+    hash_token, AuthorizationCode, InboundClient, InboundClientRepository, RegistrationType,
+    SqliteInboundClientRepository, TokenRecord, TokenType,
 };
 use std::sync::Arc;
 use tests::db::TestDatabase;
@@ -39,6 +39,7 @@ fn create_test_client(name: &str) -> InboundClient {
         updated_at: now,
         reports_roots: false,
         roots_capability_known: false,
+        suspended: false,
     }
 }
 
@@ -50,7 +51,7 @@ fn create_test_client(name: &str) -> InboundClient {
 async fn test_save_and_get_client() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Test Client");
     let client_id = client.client_id.clone();
@@ -74,7 +75,7 @@ async fn test_save_and_get_client() {
 async fn test_find_client_by_name() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Cursor IDE");
     repo.save_client(&client).await.unwrap();
@@ -99,7 +100,7 @@ async fn test_find_client_by_name() {
 async fn test_client_update_preserves_fields() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let mut client = create_test_client("Original");
     repo.save_client(&client).await.unwrap();
@@ -118,7 +119,7 @@ async fn test_client_update_preserves_fields() {
 async fn test_approve_client() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Pending Approval");
     repo.save_client(&client).await.unwrap();
@@ -139,11 +140,44 @@ async fn test_approve_client() {
     assert!(loaded.approved);
 }
 
+#[tokio::test]
+async fn test_suspend_client() {
+    let test_db = TestDatabase::new();
+    let db = Arc::new(Mutex::new(test_db.db));
+    let repo = SqliteInboundClientRepository::new(db);
+
+    let client = create_test_client("Misbehaving Agent");
+    repo.save_client(&client).await.unwrap();
+    repo.approve_client(&client.client_id).await.unwrap();
+    assert!(!repo.is_client_suspended(&client.client_id).await.unwrap());
+
+    assert!(repo
+        .set_client_suspended(&client.client_id, true)
+        .await
+        .unwrap());
+    assert!(repo.is_client_suspended(&client.client_id).await.unwrap());
+
+    // Re-saving the registration (e.g. a DCR re-register) keeps the suspension
+    repo.save_client(&client).await.unwrap();
+    let loaded = repo.get_client(&client.client_id).await.unwrap().unwrap();
+    assert!(loaded.suspended);
+    assert!(loaded.approved);
+
+    repo.set_client_suspended(&client.client_id, false)
+        .await
+        .unwrap();
+    assert!(!repo.is_client_suspended(&client.client_id).await.unwrap());
+
+    // Unknown clients are never suspended
+    assert!(!repo.set_client_suspended("unknown", true).await.unwrap());
+    assert!(!repo.is_client_suspended("unknown").await.unwrap());
+}
+
 #[tokio::test]
 async fn test_list_clients() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client1 = create_test_client("Client A");
     let client2 = create_test_client("Client B");
@@ -158,7 +192,7 @@ async fn test_list_clients() {
 async fn test_delete_client() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("To Delete");
     let client_id = client.client_id.clone();
@@ -184,7 +218,7 @@ async fn test_delete_client() {
 async fn test_validate_redirect_uri() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let mut client = create_test_client("URI Test");
     client.redirect_uris = vec![
@@ -214,7 +248,7 @@ async fn test_validate_redirect_uri() {
 async fn test_merge_redirect_uris() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let mut client = create_test_client("Merge Test");
     client.redirect_uris = vec!["http://127.0.0.1:8080/callback".to_string()];
@@ -246,7 +280,7 @@ async fn test_merge_redirect_uris() {
 async fn test_authorization_code_save_and_consume() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     // First save a client (FK constraint)
     let client = create_test_client("Auth Code Client");
@@ -289,7 +323,7 @@ async fn test_authorization_code_save_and_consume() {
 async fn test_authorization_code_not_found() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let consumed = repo
         .consume_authorization_code("nonexistent")
@@ -309,13 +343,13 @@ async fn test_authorization_code_not_found() {
 async fn test_save_and_find_token() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Token Client");
     repo.save_client(&client).await.unwrap();
 
     let token_value = "access_token_xyz";
-    let token_hash = InboundClientRepository::hash_token(token_value);
+    let token_hash = hash_token(token_value);
 
     let record = TokenRecord {
         id: uuid::Uuid::new_v4().to_string(),
@@ -349,7 +383,7 @@ async fn test_save_and_find_token() {
 async fn test_validate_token() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Validate Client");
     repo.save_client(&client).await.unwrap();
@@ -359,7 +393,7 @@ async fn test_validate_token() {
         id: uuid::Uuid::new_v4().to_string(),
         client_id: client.client_id.clone(),
         token_type: TokenType::Access,
-        token_hash: InboundClientRepository::hash_token(token_value),
+        token_hash: hash_token(token_value),
         scope: None,
         expires_at: Some("2030-01-01T00:00:00Z".to_string()),
         revoked: false,
@@ -384,7 +418,7 @@ async fn test_validate_token() {
 async fn test_validate_expired_token() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Expired Client");
     repo.save_client(&client).await.unwrap();
@@ -394,7 +428,7 @@ async fn test_validate_expired_token() {
         id: uuid::Uuid::new_v4().to_string(),
         client_id: client.client_id.clone(),
         token_type: TokenType::Access,
-        token_hash: InboundClientRepository::hash_token(token_value),
+        token_hash: hash_token(token_value),
         scope: None,
         expires_at: Some("2020-01-01T00:00:00Z".to_string()), // expired
         revoked: false,
@@ -412,7 +446,7 @@ async fn test_validate_expired_token() {
 async fn test_validate_revoked_token() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Revoked Client");
     repo.save_client(&client).await.unwrap();
@@ -422,7 +456,7 @@ async fn test_validate_revoked_token() {
         id: uuid::Uuid::new_v4().to_string(),
         client_id: client.client_id.clone(),
         token_type: TokenType::Access,
-        token_hash: InboundClientRepository::hash_token(token_value),
+        token_hash: hash_token(token_value),
         scope: None,
         expires_at: Some("2030-01-01T00:00:00Z".to_string()),
         revoked: true, // revoked
@@ -440,7 +474,7 @@ async fn test_validate_revoked_token() {
 async fn test_revoke_token_and_children() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Revoke Test");
     repo.save_client(&client).await.unwrap();
@@ -453,7 +487,7 @@ async fn test_revoke_token_and_children() {
         id: refresh_id.clone(),
         client_id: client.client_id.clone(),
         token_type: TokenType::Refresh,
-        token_hash: InboundClientRepository::hash_token("refresh_token"),
+        token_hash: hash_token("refresh_token"),
         scope: None,
         expires_at: None,
         revoked: false,
@@ -467,7 +501,7 @@ async fn test_revoke_token_and_children() {
         id: access_id.clone(),
         client_id: client.client_id.clone(),
         token_type: TokenType::Access,
-        token_hash: InboundClientRepository::hash_token("access_token"),
+        token_hash: hash_token("access_token"),
         scope: None,
         expires_at: Some("2030-01-01T00:00:00Z".to_string()),
         revoked: false,
@@ -483,12 +517,12 @@ async fn test_revoke_token_and_children() {
 
     // Both should be revoked
     let refresh_check = repo
-        .find_token_by_hash(&InboundClientRepository::hash_token("refresh_token"))
+        .find_token_by_hash(&hash_token("refresh_token"))
         .await
         .unwrap()
         .unwrap();
     let access_check = repo
-        .find_token_by_hash(&InboundClientRepository::hash_token("access_token"))
+        .find_token_by_hash(&hash_token("access_token"))
         .await
         .unwrap()
         .unwrap();
@@ -500,7 +534,7 @@ async fn test_revoke_token_and_children() {
 async fn test_revoke_client_tokens() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Multi Token");
     repo.save_client(&client).await.unwrap();
@@ -511,7 +545,7 @@ async fn test_revoke_client_tokens() {
             id: uuid::Uuid::new_v4().to_string(),
             client_id: client.client_id.clone(),
             token_type: TokenType::Access,
-            token_hash: InboundClientRepository::hash_token(&format!("token_{}", i)),
+            token_hash: hash_token(&format!("token_{}", i)),
             scope: None,
             expires_at: None,
             revoked: false,
@@ -555,7 +589,7 @@ async fn test_revoke_client_tokens() {
 async fn test_update_client_alias() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Alias Test");
     repo.save_client(&client).await.unwrap();
@@ -573,7 +607,7 @@ async fn test_update_client_alias() {
 async fn test_update_last_seen() {
     let test_db = TestDatabase::new();
     let db = Arc::new(Mutex::new(test_db.db));
-    let repo = InboundClientRepository::new(db);
+    let repo = SqliteInboundClientRepository::new(db);
 
     let client = create_test_client("Last Seen");
     repo.save_client(&client).await.unwrap();
@@ -152,6 +152,71 @@ async fn test_installed_server_uninstall() {
     assert!(deleted.is_none());
 }
 
+#[tokio::test]
+async fn test_installed_server_trash_and_restore() {
+    let test_db = TestDatabase::new();
+    let db = Arc::new(Mutex::new(test_db.db));
+    let server_repo = SqliteInstalledServerRepository::new(Arc::clone(&db), test_encryptor());
+    let space_repo = SqliteSpaceRepository::new(db);
+
+    let space = fixtures::test_space("Test Space");
+    SpaceRepository::create(&space_repo, &space).await.unwrap();
+    let space_id = space.id.to_string();
+
+    let server = fixtures::test_installed_server(&space_id, "to-trash")
+        .with_input("API_KEY", "secret-value");
+    let server_id = server.id;
+    InstalledServerRepository::install(&server_repo, &server)
+        .await
+        .unwrap();
+
+    InstalledServerRepository::trash(&server_repo, &server_id, chrono::Utc::now())
+        .await
+        .expect("Failed to trash server");
+
+    // Hidden from every regular query
+    assert!(InstalledServerRepository::get(&server_repo, &server_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(
+        InstalledServerRepository::get_by_server_id(&server_repo, &space_id, "to-trash")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        InstalledServerRepository::list_enabled(&server_repo, &space_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let trashed = InstalledServerRepository::list_trashed(&server_repo)
+        .await
+        .unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert!(trashed[0].deleted_at.is_some());
+    assert_eq!(
+        trashed[0].input_values.get("API_KEY"),
+        Some(&"secret-value".to_string())
+    );
+
+    InstalledServerRepository::restore(&server_repo, &server_id)
+        .await
+        .expect("Failed to restore server");
+    let restored = InstalledServerRepository::get(&server_repo, &server_id)
+        .await
+        .unwrap()
+        .expect("Restored server should be visible");
+    assert!(restored.deleted_at.is_none());
+    assert!(restored.enabled);
+    assert!(InstalledServerRepository::list_trashed(&server_repo)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
 #[tokio::test]
 async fn test_installed_server_set_enabled() {
     let test_db = TestDatabase::new();
@@ -0,0 +1,262 @@
+//! Auto-connect against real MCP servers
+//!
+//! Installs several `mcpmux-testserver`s, served over Streamable HTTP and
+//! last used at different times, and auto-connects them through the
+//! StartupOrchestrator. The order and overlap of the connections are read
+//! back from the ServerManager's status events.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use mcpmux_core::{
+    ConnectionStatus, DomainEvent, FeatureSetRepository, InstalledServer, ServerDefinition,
+    ServerDiscoveryService, ServerFeatureRepository, ServerLogManager, ServerTimeouts,
+};
+use mcpmux_gateway::pool::ServiceFactory;
+use mcpmux_gateway::server::DependenciesBuilder;
+use mcpmux_gateway::{AutoConnectResult, PrefixCacheService, StartupOrchestrator};
+use mcpmux_testserver::{HttpServer, ServerSpec, TestServer, ToolSpec};
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use tests::db::TestDatabase;
+use tests::mocks::*;
+
+/// How long each test server takes over every list request, so connections
+/// overlap long enough to be seen
+const LATENCY: Duration = Duration::from_millis(200);
+
+/// A server to install
+enum Backend {
+    /// A test server, last used this many minutes ago (`None`: never)
+    Serving(Option<i64>),
+    /// Accepts connections and never answers, last used most recently
+    Hung,
+}
+
+fn definition(server_id: &str, url: &str) -> ServerDefinition {
+    serde_json::from_value(json!({
+        "id": server_id,
+        "name": server_id,
+        "transport": { "type": "http", "url": url },
+    }))
+    .expect("server definition")
+}
+
+/// A URL that accepts connections but never responds
+async fn hung_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+    url
+}
+
+struct Fixture {
+    startup: StartupOrchestrator,
+    events: broadcast::Receiver<DomainEvent>,
+    _servers: Vec<HttpServer>,
+}
+
+impl Fixture {
+    /// Install and enable one server per backend, named `s0`, `s1`, …
+    async fn start(backends: &[Backend]) -> Self {
+        let space_id = Uuid::new_v4().to_string();
+        let mut installed_repo = MockInstalledServerRepository::new();
+        let mut servers = Vec::new();
+        for (index, backend) in backends.iter().enumerate() {
+            let server_id = format!("s{index}");
+            let (url, last_used_at) = match backend {
+                Backend::Serving(minutes_ago) => {
+                    let server = TestServer::new(ServerSpec {
+                        name: server_id.clone(),
+                        latency_ms: LATENCY.as_millis() as u64,
+                        tools: vec![ToolSpec::echo("echo")],
+                        ..Default::default()
+                    });
+                    let http = server
+                        .serve_http("127.0.0.1:0".parse().unwrap())
+                        .await
+                        .expect("serve test server");
+                    let url = http.url.clone();
+                    servers.push(http);
+                    (
+                        url,
+                        minutes_ago.map(|m| Utc::now() - chrono::Duration::minutes(m)),
+                    )
+                }
+                Backend::Hung => (hung_url().await, Some(Utc::now())),
+            };
+            let mut installed = InstalledServer::new(&space_id, &server_id)
+                .with_definition(&definition(&server_id, &url))
+                .with_enabled(true);
+            installed.last_used_at = last_used_at;
+            installed.timeouts = ServerTimeouts {
+                connect_secs: Some(5),
+                request_secs: None,
+            };
+            installed_repo = installed_repo.with_server(installed);
+        }
+
+        let deps = DependenciesBuilder::new()
+            .with_installed_server_repo(Arc::new(installed_repo))
+            .with_credential_repo(Arc::new(MockCredentialRepository::new()))
+            .with_backend_oauth_repo(Arc::new(MockOutboundOAuthRepository::new()))
+            .with_feature_repo(
+                Arc::new(MockServerFeatureRepository::new()) as Arc<dyn ServerFeatureRepository>
+            )
+            .with_feature_set_repo(
+                Arc::new(MockFeatureSetRepository::new()) as Arc<dyn FeatureSetRepository>
+            )
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                std::path::PathBuf::from("test-data"),
+                std::path::PathBuf::from("test-spaces"),
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(
+                mcpmux_core::LogConfig::default(),
+            )))
+            .with_database(Arc::new(tokio::sync::Mutex::new(
+                TestDatabase::in_memory().db,
+            )))
+            .build()
+            .expect("build dependencies");
+
+        let prefix_cache = Arc::new(PrefixCacheService::new());
+        let (event_tx, events) = broadcast::channel(4096);
+        let pool = ServiceFactory::create_pool_services(&deps, event_tx, prefix_cache.clone());
+        let startup = StartupOrchestrator::new(
+            pool.pool_service.clone(),
+            pool.server_manager.clone(),
+            deps,
+            prefix_cache,
+        );
+
+        Self {
+            startup,
+            events,
+            _servers: servers,
+        }
+    }
+
+    async fn connect(&mut self, concurrency: usize) -> (AutoConnectResult, Connections) {
+        let result = self
+            .startup
+            .auto_connect_enabled_servers(concurrency)
+            .await
+            .expect("auto-connect");
+        (result, Connections::read(&mut self.events))
+    }
+}
+
+/// What the status events say about the connections
+#[derive(Debug, Default)]
+struct Connections {
+    /// Servers in the order their connections started
+    started: Vec<String>,
+    /// Servers in the order their connections finished, either way
+    finished: Vec<String>,
+    /// Most connections in flight at once
+    peak: usize,
+}
+
+impl Connections {
+    fn read(events: &mut broadcast::Receiver<DomainEvent>) -> Self {
+        let mut connections = Self::default();
+        // Auto-connect marks every server Connecting up front, then again as
+        // its connection starts
+        let mut marked = HashSet::new();
+        let mut in_flight = HashSet::new();
+        while let Ok(event) = events.try_recv() {
+            let DomainEvent::ServerStatusChanged {
+                server_id, status, ..
+            } = event
+            else {
+                continue;
+            };
+            match status {
+                ConnectionStatus::Connecting => {
+                    if !marked.insert(server_id.clone()) {
+                        connections.started.push(server_id.clone());
+                        in_flight.insert(server_id);
+                        connections.peak = connections.peak.max(in_flight.len());
+                    }
+                }
+                ConnectionStatus::Connected
+                | ConnectionStatus::Error
+                | ConnectionStatus::OAuthRequired => {
+                    if in_flight.remove(&server_id) {
+                        connections.finished.push(server_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        connections
+    }
+}
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn connects_most_recently_used_first() {
+    let mut fixture = Fixture::start(&[
+        Backend::Serving(Some(30)),
+        Backend::Serving(None),
+        Backend::Serving(Some(1)),
+        Backend::Serving(Some(10)),
+    ])
+    .await;
+
+    let (result, connections) = fixture.connect(1).await;
+
+    let expected = ids(&["s2", "s3", "s0", "s1"]);
+    assert_eq!(connections.started, expected);
+    assert_eq!(result.connected, expected);
+    assert_eq!(connections.peak, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn connects_at_most_concurrency_servers_at_once() {
+    let backends: Vec<Backend> = (0..6).map(|i| Backend::Serving(Some(i))).collect();
+    let mut fixture = Fixture::start(&backends).await;
+
+    let (result, connections) = fixture.connect(2).await;
+
+    assert_eq!(connections.peak, 2, "{connections:?}");
+    assert_eq!(connections.started.len(), 6);
+    assert_eq!(result.connected.len(), 6);
+    assert!(result.failed.is_empty(), "{:?}", result.failed);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_connect_does_not_hold_up_the_others() {
+    let mut fixture = Fixture::start(&[
+        Backend::Hung,
+        Backend::Serving(Some(1)),
+        Backend::Serving(Some(2)),
+        Backend::Serving(Some(3)),
+    ])
+    .await;
+
+    let (result, connections) = fixture.connect(2).await;
+
+    // The hung server starts first and times out last; the rest connect
+    // through the other slot meanwhile
+    assert_eq!(connections.started[0], "s0");
+    assert_eq!(connections.finished.last().map(String::as_str), Some("s0"));
+    let mut connected = result.connected.clone();
+    connected.sort();
+    assert_eq!(connected, ids(&["s1", "s2", "s3"]));
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, "s0");
+}
@@ -0,0 +1,193 @@
+//! The embedded gateway against a real MCP server
+//!
+//! Lists and calls through `EmbeddedGateway` on the services of a full
+//! gateway, so results can be compared with what `/mcp` serves.
+
+use mcpmux_gateway::{ClientContext, EmbeddedGateway, InvalidToolArguments, PAUSED_MESSAGE};
+use mcpmux_testserver::{PromptSpec, ResourceSpec, ServerSpec, ToolSpec};
+use rmcp::model::CallToolRequestParams;
+use serde_json::json;
+
+use tests::gateway::{MockGateway, MOCK_SERVER_ID};
+
+fn spec() -> ServerSpec {
+    ServerSpec {
+        tools: vec![
+            ToolSpec::echo("echo"),
+            ToolSpec::echo("strict").with_input_schema(json!({
+                "type": "object",
+                "properties": { "count": { "type": "integer" } },
+                "required": ["count"]
+            })),
+        ],
+        prompts: vec![PromptSpec {
+            name: "greet".to_string(),
+            description: None,
+            arguments: vec!["name".to_string()],
+            text: "Hello {{name}}".to_string(),
+        }],
+        resources: vec![ResourceSpec {
+            uri: "test://readme".to_string(),
+            name: None,
+            mime_type: None,
+            text: "Read me".to_string(),
+        }],
+        ..Default::default()
+    }
+}
+
+async fn start() -> (MockGateway, EmbeddedGateway, ClientContext) {
+    let gateway = MockGateway::start(spec(), MOCK_SERVER_ID).await;
+    let embedded = EmbeddedGateway::from_services(gateway.services.clone());
+    let client = ClientContext::new(gateway.client_id.clone()).with_client_name("embedded-test");
+    (gateway, embedded, client)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lists_what_mcp_clients_see() {
+    let (gateway, embedded, client) = start().await;
+
+    let mut embedded_tools: Vec<String> = embedded
+        .list_tools(&client)
+        .await
+        .expect("list tools")
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    assert!(embedded_tools.contains(&"mock_echo".to_string()));
+
+    let mcp_client = gateway.client(()).await;
+    let mut mcp_tools: Vec<String> = mcp_client
+        .peer()
+        .list_all_tools()
+        .await
+        .expect("tools/list")
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    mcp_client.cancel().await.ok();
+    embedded_tools.sort();
+    mcp_tools.sort();
+    assert_eq!(embedded_tools, mcp_tools);
+
+    let prompts = embedded.list_prompts(&client).await.expect("list prompts");
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "mock_greet");
+
+    let resources = embedded
+        .list_resources(&client)
+        .await
+        .expect("list resources");
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "test://readme");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn routes_calls_by_qualified_name() {
+    let (_gateway, embedded, client) = start().await;
+
+    let result = embedded
+        .call_tool(&client, "mock_echo", json!({ "message": "hi" }))
+        .await
+        .expect("call tool");
+    assert!(!result.is_error.unwrap_or(false));
+    let text = result.content[0].as_text().expect("text content");
+    assert!(text.text.contains("hi"), "echoed {}", text.text);
+
+    // Unqualified or unknown names aren't routed
+    assert!(embedded
+        .call_tool(&client, "echo", json!({}))
+        .await
+        .is_err());
+    assert!(embedded
+        .call_tool(&client, "mock_missing", json!({}))
+        .await
+        .is_err());
+
+    // Invalid arguments keep their violations
+    let err = embedded
+        .call_tool(&client, "mock_strict", json!({ "count": "three" }))
+        .await
+        .expect_err("arguments fail the schema");
+    assert!(err.downcast_ref::<InvalidToolArguments>().is_some());
+
+    let prompt = embedded
+        .get_prompt(
+            &client,
+            "mock_greet",
+            json!({ "name": "Ada" }).as_object().cloned(),
+        )
+        .await
+        .expect("get prompt");
+    assert!(serde_json::to_string(&prompt)
+        .unwrap()
+        .contains("Hello Ada"));
+
+    let contents = embedded
+        .read_resource(&client, "test://readme")
+        .await
+        .expect("read resource");
+    assert!(serde_json::to_string(&contents)
+        .unwrap()
+        .contains("Read me"));
+    assert!(embedded
+        .read_resource(&client, "test://missing")
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn refuses_calls_after_shutdown() {
+    let (_gateway, embedded, client) = start().await;
+    embedded.shutdown().await;
+    let err = embedded
+        .call_tool(&client, "mock_echo", json!({}))
+        .await
+        .expect_err("shut down");
+    assert!(err.to_string().contains("shutting down"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn refuses_calls_while_paused() {
+    let (gateway, embedded, client) = start().await;
+    let pause = gateway.services.traffic_pause.clone();
+
+    assert!(pause.set_paused(true));
+    let err = embedded
+        .call_tool(&client, "mock_echo", json!({ "message": "hi" }))
+        .await
+        .expect_err("paused");
+    assert_eq!(err.to_string(), PAUSED_MESSAGE);
+    assert_eq!(gateway.server.calls("echo"), 0);
+
+    assert!(pause.set_paused(false));
+    let result = embedded
+        .call_tool(&client, "mock_echo", json!({ "message": "hi" }))
+        .await
+        .expect("call after resume");
+    assert!(!result.is_error.unwrap_or(false));
+    assert_eq!(gateway.server.calls("echo"), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mcp_refuses_calls_while_paused() {
+    let (gateway, _embedded, _client) = start().await;
+    let pause = gateway.services.traffic_pause.clone();
+    let mcp_client = gateway.client(()).await;
+    let call = || {
+        mcp_client
+            .peer()
+            .call_tool(CallToolRequestParams::new("mock_echo"))
+    };
+
+    pause.set_paused(true);
+    let err = call().await.expect_err("paused");
+    assert!(err.to_string().contains(PAUSED_MESSAGE), "got {err}");
+    assert_eq!(gateway.server.calls("echo"), 0);
+
+    pause.set_paused(false);
+    let result = call().await.expect("call after resume");
+    assert!(!result.is_error.unwrap_or(false));
+    assert_eq!(gateway.server.calls("echo"), 1);
+    mcp_client.cancel().await.ok();
+}
@@ -2,5 +2,8 @@
 //!
 //! Tests for ServerManager state machine and connection handling.
 
+mod auto_connect;
+mod embedded;
 mod server_manager;
 mod stdio_transport;
+mod testserver;
@@ -196,7 +196,7 @@ async fn test_docker_command_not_found_includes_hint() {
 
     let result = transport.connect().await;
     match result {
-        TransportConnectResult::Failed(msg) => {
+        TransportConnectResult::Failed(msg) | TransportConnectResult::Exited { error: msg, .. } => {
             // If docker is not installed, we get "Command not found" with hint.
             // If docker IS installed but daemon isn't running, we'd get a different error with hint.
             // Either way, the hint should be present.
@@ -291,10 +291,7 @@ async fn test_stderr_capture_logs_to_server_log_manager() {
     use std::sync::Arc;
 
     let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
-    let log_config = LogConfig {
-        base_dir: temp_dir.path().to_path_buf(),
-        ..Default::default()
-    };
+    let log_config = LogConfig::new(temp_dir.path());
     let log_manager = Arc::new(ServerLogManager::new(log_config));
     let space_id = uuid::Uuid::new_v4();
     let server_id = "test-stderr-server".to_string();
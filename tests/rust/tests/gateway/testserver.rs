@@ -0,0 +1,307 @@
+//! Pool and routing against a real MCP server
+//!
+//! Connects the gateway's pool to an in-process `mcpmux-testserver` over
+//! Streamable HTTP, then routes tool calls through the RoutingService the
+//! way `tools/call` does, including the server's injected failures.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mcpmux_core::{
+    FeatureSet, FeatureSetMember, FeatureSetRepository, ServerDiscoveryService,
+    ServerFeatureRepository, ServerLogManager,
+};
+use mcpmux_gateway::pool::{
+    ConnectionContext, ConnectionResult, PoolServices, ResolvedTransport, ServiceFactory,
+};
+use mcpmux_gateway::server::DependenciesBuilder;
+use mcpmux_gateway::PrefixCacheService;
+use mcpmux_testserver::{
+    Failure, FailureMode, HttpServer, ServerSpec, TestServer, ToolResponse, ToolSpec,
+};
+use serde_json::json;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use tests::db::TestDatabase;
+use tests::mocks::*;
+
+const SERVER_ID: &str = "mock";
+
+struct Fixture {
+    space_id: Uuid,
+    pool: PoolServices,
+    installed_repo: Arc<MockInstalledServerRepository>,
+    feature_repo: Arc<MockServerFeatureRepository>,
+    feature_set_repo: Arc<MockFeatureSetRepository>,
+    server: TestServer,
+    _http: HttpServer,
+}
+
+impl Fixture {
+    async fn start(spec: ServerSpec) -> Self {
+        let server = TestServer::new(spec);
+        let http = server
+            .clone()
+            .serve_http("127.0.0.1:0".parse().unwrap())
+            .await
+            .expect("serve test server");
+
+        let installed_repo = Arc::new(MockInstalledServerRepository::new());
+        let feature_repo = Arc::new(MockServerFeatureRepository::new());
+        let feature_set_repo = Arc::new(MockFeatureSetRepository::new());
+        let deps = DependenciesBuilder::new()
+            .with_installed_server_repo(installed_repo.clone())
+            .with_credential_repo(Arc::new(MockCredentialRepository::new()))
+            .with_backend_oauth_repo(Arc::new(MockOutboundOAuthRepository::new()))
+            .with_feature_repo(feature_repo.clone() as Arc<dyn ServerFeatureRepository>)
+            .with_feature_set_repo(feature_set_repo.clone() as Arc<dyn FeatureSetRepository>)
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                std::path::PathBuf::from("test-data"),
+                std::path::PathBuf::from("test-spaces"),
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(
+                mcpmux_core::LogConfig::default(),
+            )))
+            .with_database(Arc::new(tokio::sync::Mutex::new(
+                TestDatabase::in_memory().db,
+            )))
+            .build()
+            .expect("build dependencies");
+
+        let space_id = Uuid::new_v4();
+        let prefix_cache = Arc::new(PrefixCacheService::new());
+        prefix_cache
+            .assign_prefix_runtime(&space_id.to_string(), SERVER_ID, Some(SERVER_ID))
+            .await;
+        let (event_tx, _) = broadcast::channel(64);
+        let pool = ServiceFactory::create_pool_services(&deps, event_tx, prefix_cache);
+
+        let ctx = ConnectionContext::new(
+            space_id,
+            SERVER_ID,
+            ResolvedTransport::Http {
+                url: http.url.clone(),
+                headers: HashMap::new(),
+                query: HashMap::new(),
+            },
+        );
+        match pool.pool_service.connect_server(&ctx).await {
+            ConnectionResult::Connected { .. } => {}
+            ConnectionResult::OAuthRequired { .. } => panic!("test server asked for OAuth"),
+            ConnectionResult::Failed { error } => panic!("connect failed: {}", error),
+        }
+
+        Self {
+            space_id,
+            pool,
+            installed_repo,
+            feature_repo,
+            feature_set_repo,
+            server,
+            _http: http,
+        }
+    }
+
+    /// A FeatureSet granting every discovered feature
+    async fn grant_all(&self) -> String {
+        self.grant(|_| true).await
+    }
+
+    /// A FeatureSet granting the discovered features `include` accepts
+    async fn grant(&self, include: impl Fn(&str) -> bool) -> String {
+        let space_id = self.space_id.to_string();
+        let mut fs = FeatureSet::new_custom("Test fixture", &space_id);
+        for feature in self.feature_repo.list_for_space(&space_id).await.unwrap() {
+            if !include(&feature.feature_name) {
+                continue;
+            }
+            fs.members.push(FeatureSetMember::include_feature(
+                &fs.id,
+                &feature.id.to_string(),
+            ));
+        }
+        self.feature_set_repo.create(&fs).await.unwrap();
+        fs.id
+    }
+}
+
+fn flaky(mode: FailureMode) -> Failure {
+    Failure {
+        mode,
+        every: 2,
+        after: 0,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn discovered_tools_route_to_the_server() {
+    let f = Fixture::start(ServerSpec {
+        tools: vec![
+            ToolSpec::echo("echo"),
+            ToolSpec::new("status", ToolResponse::Json(json!({ "ok": true }))),
+        ],
+        ..Default::default()
+    })
+    .await;
+
+    let names: Vec<String> = f
+        .feature_repo
+        .list_for_server(&f.space_id.to_string(), SERVER_ID)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|feature| feature.feature_name)
+        .collect();
+    assert!(names.contains(&"echo".to_string()));
+    assert!(names.contains(&"status".to_string()));
+
+    let grant = f.grant_all().await;
+    let result = f
+        .pool
+        .routing_service
+        .call_tool(
+            f.space_id,
+            std::slice::from_ref(&grant),
+            "mock_echo",
+            json!({ "message": "hi" }),
+            None,
+        )
+        .await
+        .expect("call echo");
+    assert!(!result.is_error);
+    assert_eq!(
+        result.content_json().unwrap()[0]["text"],
+        r#"{"message":"hi"}"#
+    );
+
+    let result = f
+        .pool
+        .routing_service
+        .call_tool(f.space_id, &[grant], "mock_status", json!({}), None)
+        .await
+        .expect("call status");
+    assert_eq!(result.structured_content, Some(json!({ "ok": true })));
+    assert_eq!(f.server.calls("echo"), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn injected_failures_reach_the_caller() {
+    let f = Fixture::start(ServerSpec {
+        tools: vec![
+            ToolSpec::echo("flaky").failing(flaky(FailureMode::Error)),
+            ToolSpec::echo("broken").failing(flaky(FailureMode::Protocol)),
+        ],
+        ..Default::default()
+    })
+    .await;
+    let grant = f.grant_all().await;
+    let call = |tool: &'static str| {
+        let routing = f.pool.routing_service.clone();
+        let grant = grant.clone();
+        let space_id = f.space_id;
+        async move {
+            routing
+                .call_tool(space_id, &[grant], tool, json!({}), None)
+                .await
+        }
+    };
+
+    assert!(!call("mock_flaky").await.unwrap().is_error);
+    assert!(call("mock_flaky").await.unwrap().is_error);
+
+    assert!(!call("mock_broken").await.unwrap().is_error);
+    let broken = call("mock_broken").await;
+    assert!(broken.map(|r| r.is_error).unwrap_or(true));
+    assert_eq!(f.server.calls("broken"), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn server_latency_slows_calls_down() {
+    let f = Fixture::start(ServerSpec {
+        tools: vec![ToolSpec::echo("echo")],
+        ..Default::default()
+    })
+    .await;
+    let grant = f.grant_all().await;
+
+    f.server.set_latency(Duration::from_millis(300));
+    let started = std::time::Instant::now();
+    f.pool
+        .routing_service
+        .call_tool(f.space_id, &[grant], "mock_echo", json!({}), None)
+        .await
+        .expect("call echo");
+    assert!(started.elapsed() >= Duration::from_millis(300));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tool_call_needs_a_grant_unless_bypassed() {
+    let f = Fixture::start(ServerSpec {
+        tools: vec![ToolSpec::echo("echo"), ToolSpec::echo("secret")],
+        ..Default::default()
+    })
+    .await;
+    let grant = f.grant(|name| name == "echo").await;
+    let test_call = |tool: &'static str, bypass_grants: bool| {
+        let routing = f.pool.routing_service.clone();
+        let grant = grant.clone();
+        let space_id = f.space_id;
+        async move {
+            routing
+                .test_tool_call(
+                    space_id,
+                    &[grant],
+                    SERVER_ID,
+                    tool,
+                    json!({}),
+                    bypass_grants,
+                )
+                .await
+        }
+    };
+
+    assert!(!test_call("echo", false).await.unwrap().is_error);
+
+    let refused = test_call("secret", false).await.unwrap_err();
+    assert!(refused
+        .to_string()
+        .contains("not allowed by the selected grants"));
+    assert_eq!(f.server.calls("secret"), 0);
+
+    assert!(!test_call("secret", true).await.unwrap().is_error);
+    assert_eq!(f.server.calls("secret"), 1);
+
+    // Bypassing grants still only reaches tools the server has
+    let missing = test_call("nope", true).await.unwrap_err();
+    assert!(missing.to_string().contains("not found on server"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_installation_lookup_rejects_the_call() {
+    let f = Fixture::start(ServerSpec {
+        tools: vec![ToolSpec::echo("read_file")],
+        ..Default::default()
+    })
+    .await;
+    let grant = f.grant_all().await;
+    f.installed_repo.fail_lookups();
+
+    // Without the installation there is no path policy to check, so the
+    // call must not go out
+    let error = f
+        .pool
+        .routing_service
+        .call_tool(
+            f.space_id,
+            &[grant],
+            "mock_read_file",
+            json!({ "path": "/etc/passwd" }),
+            None,
+        )
+        .await
+        .expect_err("lookup failure must reject the call");
+    assert!(error.to_string().contains("database is unavailable"));
+    assert_eq!(f.server.calls("read_file"), 0);
+}
@@ -26,8 +26,9 @@ use mcpmux_core::{
 use mcpmux_gateway::services::{FeatureSetResolverService, ResolutionSource, SessionRootsRegistry};
 use mcpmux_gateway::{FeatureService, PrefixCacheService};
 use mcpmux_storage::{
-    Database, InboundClientRepository, SqliteFeatureSetRepository, SqliteServerFeatureRepository,
-    SqliteSpaceBaseDirRepository, SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
+    Database, SqliteFeatureSetRepository, SqliteInboundClientRepository,
+    SqliteServerFeatureRepository, SqliteSpaceBaseDirRepository, SqliteSpaceRepository,
+    SqliteWorkspaceBindingRepository,
 };
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -59,7 +60,7 @@ impl Ctx {
             Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
         let feature_repo: Arc<dyn ServerFeatureRepository> =
             Arc::new(SqliteServerFeatureRepository::new(db.clone()));
-        let client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+        let client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
 
         let space = space_repo.get_default().await.unwrap().unwrap();
         let space_id = space.id;
@@ -361,12 +362,8 @@ async fn composition_cycle_terminates_and_returns_union() {
     ctx.fs_repo.create(&fs_x).await.unwrap();
     ctx.fs_repo.create(&fs_y).await.unwrap();
 
-    let member = |fs_id: &str, mtype: MemberType, mid: String| FeatureSetMember {
-        id: Uuid::new_v4().to_string(),
-        feature_set_id: fs_id.to_string(),
-        member_type: mtype,
-        member_id: mid,
-        mode: MemberMode::Include,
+    let member = |fs_id: &str, mtype: MemberType, mid: String| {
+        FeatureSetMember::new(fs_id, mtype, mid, MemberMode::Include)
     };
 
     // X ⊇ {gh_issue (feature), Y (featureset)}
@@ -27,7 +27,8 @@ use mcpmux_core::{
 use mcpmux_gateway::services::{FeatureSetResolverService, ResolutionSource, SessionRootsRegistry};
 use mcpmux_storage::{
     Database, InboundClient, InboundClientRepository, RegistrationType, SqliteFeatureSetRepository,
-    SqliteSpaceBaseDirRepository, SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
+    SqliteInboundClientRepository, SqliteSpaceBaseDirRepository, SqliteSpaceRepository,
+    SqliteWorkspaceBindingRepository,
 };
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -38,7 +39,7 @@ struct Fixture {
     space_repo: Arc<dyn SpaceRepository>,
     binding_repo: Arc<dyn WorkspaceBindingRepository>,
     fs_repo: Arc<dyn FeatureSetRepository>,
-    client_repo: Arc<InboundClientRepository>,
+    client_repo: Arc<dyn InboundClientRepository>,
     base_dir_repo: Arc<dyn SpaceBaseDirRepository>,
     space_id: Uuid,
     /// The default Space's auto-seeded Starter FS — the target of every
@@ -56,7 +57,7 @@ impl Fixture {
             Arc::new(SqliteFeatureSetRepository::new(db.clone()));
         let binding_repo: Arc<dyn WorkspaceBindingRepository> =
             Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
-        let client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+        let client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
         let base_dir_repo: Arc<dyn SpaceBaseDirRepository> =
             Arc::new(SqliteSpaceBaseDirRepository::new(db.clone()));
 
@@ -170,6 +171,7 @@ impl Fixture {
             updated_at: now,
             reports_roots: false,
             roots_capability_known: false,
+            suspended: false,
         };
         self.client_repo.save_client(&c).await.unwrap();
     }
@@ -1047,3 +1049,78 @@ async fn locked_client_ignores_out_of_space_client_id_mapping() {
     assert_eq!(r.source, ResolutionSource::SpaceDefault);
     assert_eq!(r.feature_set_ids, vec![f.starter_fs_id]);
 }
+
+// ---------------------------------------------------------------------------
+// Selected Space — `X-McpMux-Space` header / `space:` token scope
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn selected_space_routes_to_the_clients_grants_there() {
+    // Two windows of the same client select different Spaces: each session
+    // resolves to the client's grants in its own Space.
+    let f = Fixture::new().await;
+    f.make_client("multi").await;
+    let other_base = if cfg!(windows) { "d:\\sel" } else { "/sel" };
+    let (other_space, other_starter) = f.make_space_with_base_dir("Selected", other_base).await;
+    f.client_repo
+        .grant_feature_set("multi", &f.space_id.to_string(), &f.fs_a_id)
+        .await
+        .unwrap();
+    f.client_repo
+        .grant_feature_set("multi", &other_space.to_string(), &other_starter)
+        .await
+        .unwrap();
+
+    f.session_roots.set_selected_space("s1", other_space);
+    f.session_roots.set_selected_space("s2", f.space_id);
+    let r1 = f.resolver.resolve(Some("s1"), Some("multi")).await.unwrap();
+    let r2 = f.resolver.resolve(Some("s2"), Some("multi")).await.unwrap();
+
+    assert_eq!(r1.source, ResolutionSource::ClientGrant);
+    assert_eq!(r1.space_id, Some(other_space));
+    assert_eq!(r1.feature_set_ids, vec![other_starter]);
+    assert_eq!(r2.space_id, Some(f.space_id));
+    assert_eq!(r2.feature_set_ids, vec![f.fs_a_id]);
+}
+
+#[tokio::test]
+async fn selected_space_ignores_bindings_in_other_spaces() {
+    // A workspace binding pointing outside the selected Space doesn't pull
+    // the session out of it; one inside the Space picks the FeatureSet.
+    let f = Fixture::new().await;
+    f.make_client("sel").await;
+    let other_base = if cfg!(windows) { "d:\\away" } else { "/away" };
+    let (other_space, other_starter) = f.make_space_with_base_dir("Away", other_base).await;
+    f.client_repo
+        .grant_feature_set("sel", &f.space_id.to_string(), &f.fs_a_id)
+        .await
+        .unwrap();
+    f.binding_repo
+        .create(&WorkspaceBinding::new_id(
+            "away",
+            other_space,
+            vec![other_starter],
+        ))
+        .await
+        .unwrap();
+    f.binding_repo
+        .create(&WorkspaceBinding::new_id(
+            "home",
+            f.space_id,
+            vec![f.fs_b_id.clone()],
+        ))
+        .await
+        .unwrap();
+
+    f.session_roots.set_selected_space("s", f.space_id);
+    f.session_roots.set_pinned("s", "away");
+    let r = f.resolver.resolve(Some("s"), Some("sel")).await.unwrap();
+    assert_eq!(r.source, ResolutionSource::ClientGrant);
+    assert_eq!(r.space_id, Some(f.space_id));
+    assert_eq!(r.feature_set_ids, vec![f.fs_a_id.clone()]);
+
+    f.session_roots.set_pinned("s", "home");
+    let r = f.resolver.resolve(Some("s"), Some("sel")).await.unwrap();
+    assert_eq!(r.source, ResolutionSource::WorkspaceBinding);
+    assert_eq!(r.feature_set_ids, vec![f.fs_b_id]);
+}
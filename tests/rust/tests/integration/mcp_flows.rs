@@ -14,8 +14,8 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use mcpmux_core::{
-    FeatureSet, FeatureSetMember, FeatureSetRepository, FeatureType, MemberMode, MemberType,
-    ServerFeature, ServerFeatureRepository,
+    FeatureSet, FeatureSetMember, FeatureSetRepository, FeatureType, ServerFeature,
+    ServerFeatureRepository,
 };
 use mcpmux_gateway::{FeatureService, PrefixCacheService};
 use tests::mocks::{MockFeatureSetRepository, MockServerFeatureRepository};
@@ -27,11 +27,7 @@ fn create_feature(
     name: &str,
     feature_type: FeatureType,
 ) -> ServerFeature {
-    let mut feature = match feature_type {
-        FeatureType::Tool => ServerFeature::tool(space_id, server_id, name),
-        FeatureType::Prompt => ServerFeature::prompt(space_id, server_id, name),
-        FeatureType::Resource => ServerFeature::resource(space_id, server_id, name),
-    };
+    let mut feature = ServerFeature::new(space_id, server_id, feature_type, name);
     feature.is_available = true;
     feature
 }
@@ -98,13 +94,10 @@ impl TestContext {
             .await
             .unwrap()
         {
-            fs.members.push(FeatureSetMember {
-                id: Uuid::new_v4().to_string(),
-                feature_set_id: fs.id.clone(),
-                member_type: MemberType::Feature,
-                member_id: feature.id.to_string(),
-                mode: MemberMode::Include,
-            });
+            fs.members.push(FeatureSetMember::include_feature(
+                &fs.id,
+                &feature.id.to_string(),
+            ));
         }
         fs
     }
@@ -122,13 +115,10 @@ impl TestContext {
             .await
             .unwrap()
         {
-            fs.members.push(FeatureSetMember {
-                id: Uuid::new_v4().to_string(),
-                feature_set_id: fs.id.clone(),
-                member_type: MemberType::Feature,
-                member_id: feature.id.to_string(),
-                mode: MemberMode::Include,
-            });
+            fs.members.push(FeatureSetMember::include_feature(
+                &fs.id,
+                &feature.id.to_string(),
+            ));
         }
         fs
     }
@@ -181,13 +171,10 @@ async fn test_list_tools_with_restricted_grant() {
 
     // Create custom grant with only safe_read
     let mut custom_fs = FeatureSet::new_custom("Safe Tools", &ctx.space_id);
-    custom_fs.members.push(FeatureSetMember {
-        id: Uuid::new_v4().to_string(),
-        feature_set_id: custom_fs.id.clone(),
-        member_id: tool_a_id.to_string(),
-        member_type: MemberType::Feature,
-        mode: MemberMode::Include,
-    });
+    custom_fs.members.push(FeatureSetMember::include_feature(
+        &custom_fs.id,
+        &tool_a_id.to_string(),
+    ));
     let custom_fs_id = ctx.add_feature_set(custom_fs).await;
 
     let tools = ctx
@@ -560,13 +547,10 @@ async fn test_features_dont_leak_between_spaces() {
 
     // Create "grant-everything-in-work" FS manually (no new_all helper any more).
     let mut work_all = FeatureSet::new_custom("All (test fixture)", &space_work);
-    work_all.members.push(FeatureSetMember {
-        id: Uuid::new_v4().to_string(),
-        feature_set_id: work_all.id.clone(),
-        member_type: MemberType::Feature,
-        member_id: work_tool.id.to_string(),
-        mode: MemberMode::Include,
-    });
+    work_all.members.push(FeatureSetMember::include_feature(
+        &work_all.id,
+        &work_tool.id.to_string(),
+    ));
     let work_all_id = work_all.id.clone();
     feature_set_repo.create(&work_all).await.unwrap();
 
@@ -22,7 +22,7 @@ use mcpmux_gateway::services::{
     FeatureSetResolverService, MetaToolRegistry, PrefixCacheService, SessionRootsRegistry,
 };
 use mcpmux_storage::{
-    Database, InboundClientRepository, SqliteFeatureSetRepository,
+    Database, SqliteFeatureSetRepository, SqliteInboundClientRepository,
     SqliteInboundMcpClientRepository, SqliteServerFeatureRepository, SqliteSpaceBaseDirRepository,
     SqliteSpaceBuiltinConfigRepository, SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
 };
@@ -101,7 +101,7 @@ impl Fixture {
         let session_roots = SessionRootsRegistry::new();
         let session_id = "sess-meta".to_string();
 
-        let inbound_client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+        let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
         let resolver = Arc::new(FeatureSetResolverService::new(
             space_repo.clone(),
             binding_repo.clone(),
@@ -1052,7 +1052,7 @@ async fn bare_registry(
     let client_id = client.id.to_string();
     client_repo.create(&client).await.unwrap();
 
-    let inbound_client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+    let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
     let resolver = Arc::new(FeatureSetResolverService::new(
         space_repo.clone(),
         binding_repo.clone(),
@@ -1161,7 +1161,7 @@ async fn per_space_config_controls_registry_visibility() {
         Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
     let server_feature_repo: Arc<dyn ServerFeatureRepository> =
         Arc::new(SqliteServerFeatureRepository::new(db.clone()));
-    let inbound_client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+    let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
 
     let space_id = space_repo.get_default().await.unwrap().unwrap().id;
 
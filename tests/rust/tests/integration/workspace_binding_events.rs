@@ -22,8 +22,8 @@ use mcpmux_core::{
 };
 use mcpmux_gateway::services::{FeatureSetResolverService, ResolutionSource, SessionRootsRegistry};
 use mcpmux_storage::{
-    Database, InboundClientRepository, SqliteFeatureSetRepository, SqliteSpaceBaseDirRepository,
-    SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
+    Database, SqliteFeatureSetRepository, SqliteInboundClientRepository,
+    SqliteSpaceBaseDirRepository, SqliteSpaceRepository, SqliteWorkspaceBindingRepository,
 };
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -44,7 +44,7 @@ impl Ctx {
             Arc::new(SqliteFeatureSetRepository::new(db.clone()));
         let binding_repo: Arc<dyn WorkspaceBindingRepository> =
             Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
-        let inbound_client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+        let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
 
         let default_space = space_repo.get_default().await.unwrap().unwrap();
         let space_id = default_space.id;
@@ -140,7 +140,7 @@ async fn binding_to_non_default_space_reroutes_session() {
         Arc::new(SqliteFeatureSetRepository::new(db.clone()));
     let binding_repo: Arc<dyn WorkspaceBindingRepository> =
         Arc::new(SqliteWorkspaceBindingRepository::new(db.clone()));
-    let inbound_client_repo = Arc::new(InboundClientRepository::new(db.clone()));
+    let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(db.clone()));
 
     let default_space = space_repo.get_default().await.unwrap().unwrap();
 
@@ -0,0 +1,99 @@
+//! Replay of Recorded Sessions
+//!
+//! Every recording in `sessions/` is replayed through a fresh gateway in
+//! front of `mcpmux-testserver`, and must be routed exactly as recorded.
+//! To turn a user's report into a regression test, export the server's
+//! traffic from its Traffic tab, convert it with
+//! `SessionRecording::from_traffic` (or write the steps by hand) and add
+//! the result here.
+
+use std::path::Path;
+
+use mcpmux_gateway::TrafficFrame;
+use mcpmux_testserver::{ServerSpec, ToolSpec};
+use rmcp::model::CallToolRequestParams;
+use serde_json::json;
+
+use tests::gateway::{MockGateway, MOCK_SERVER_ID};
+use tests::replay::{RoutedCall, SessionRecording};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recorded_sessions_route_as_recorded() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/replay/sessions");
+    let mut replayed = 0;
+    for entry in std::fs::read_dir(&dir).expect("sessions directory") {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let recording = SessionRecording::load(&path).expect("load recording");
+        let mismatches = recording.replay().await.expect("replay");
+        assert!(
+            mismatches.is_empty(),
+            "{} was routed differently:\n{}",
+            path.display(),
+            mismatches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        replayed += 1;
+    }
+    assert!(replayed > 0, "no recordings in {}", dir.display());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recorded_traffic_replays_identically() {
+    let spec = ServerSpec {
+        tools: vec![ToolSpec::echo("echo"), ToolSpec::echo("list_issues")],
+        ..Default::default()
+    };
+
+    // Record a session the way the desktop app does
+    let gateway = MockGateway::start(spec.clone(), "gh").await;
+    let traffic = gateway.services.pool_services.pool_service.traffic();
+    traffic.set_enabled(gateway.space_id, MOCK_SERVER_ID, true);
+    let client = gateway.client(()).await;
+    for (name, arguments) in [
+        ("gh_echo", json!({ "message": "hi" })),
+        ("gh_list_issues", json!({ "state": "open" })),
+        ("gh_echo", json!({})),
+    ] {
+        let mut params = CallToolRequestParams::new(name);
+        params.arguments = arguments.as_object().cloned();
+        client.peer().call_tool(params).await.expect("call tool");
+    }
+    client.cancel().await.ok();
+
+    // Exported, read back and replayed
+    let exported =
+        serde_json::to_string(&traffic.frames(gateway.space_id, MOCK_SERVER_ID)).unwrap();
+    let frames: Vec<TrafficFrame> = serde_json::from_str(&exported).unwrap();
+    let recording = SessionRecording::from_traffic(spec, "gh", &frames).expect("recording");
+    assert_eq!(recording.steps.len(), 3);
+    assert_eq!(recording.steps[1].request.params["name"], "gh_list_issues");
+    assert_eq!(
+        recording.steps[1].routed,
+        Some(RoutedCall {
+            method: "tools/call".to_string(),
+            params: json!({ "name": "list_issues", "arguments": { "state": "open" } }),
+        })
+    );
+    assert!(recording.replay().await.expect("replay").is_empty());
+
+    // A different routing decision is reported against its step
+    let mut altered = recording.clone();
+    altered.steps[1].routed.as_mut().unwrap().params["name"] = json!("list");
+    let mismatches = altered.replay().await.expect("replay");
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].step, 1);
+    assert_eq!(
+        mismatches[0].actual,
+        recording.steps[1]
+            .routed
+            .clone()
+            .into_iter()
+            .collect::<Vec<_>>()
+    );
+}
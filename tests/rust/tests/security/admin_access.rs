@@ -0,0 +1,438 @@
+//! What a member may do through the admin API, over HTTP and gRPC
+//!
+//! Alice and Bob are members, each owning a Space, a client and a
+//! FeatureSet. Alice is refused everything of Bob's (403 /
+//! `PERMISSION_DENIED`) and every admin-only route, and a grant must name a
+//! FeatureSet of the Space it's made in.
+
+use std::sync::Arc;
+
+use mcpmux_core::{
+    DomainEvent, FeatureSet, ServerDiscoveryService, ServerLogManager, Space, UserRole,
+};
+use mcpmux_gateway::server::grpc::proto::{self, admin_service_server::AdminService};
+use mcpmux_gateway::server::{
+    admin, create_user, AdminGrpcService, AppState, DependenciesBuilder, GatewayState,
+    ServiceContainer,
+};
+use mcpmux_storage::{
+    generate_master_key, FieldEncryptor, InboundClient, RegistrationType, RepositorySet,
+};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tonic::Code;
+use uuid::Uuid;
+
+use tests::db::TestDatabase;
+
+/// A member's Space, client and FeatureSet
+struct Owned {
+    space_id: String,
+    client_id: String,
+    feature_set_id: String,
+}
+
+impl Owned {
+    async fn create(repos: &RepositorySet, owner: Uuid, name: &str) -> Self {
+        let space = Space::new(name).with_owner(owner);
+        repos.spaces.create(&space).await.expect("create space");
+
+        let client_id = format!("mcp_{name}");
+        let now = chrono::Utc::now().to_rfc3339();
+        let client = InboundClient {
+            client_id: client_id.clone(),
+            registration_type: RegistrationType::Preregistered,
+            client_name: format!("{name}'s laptop"),
+            client_alias: None,
+            redirect_uris: vec![],
+            grant_types: vec![],
+            response_types: vec![],
+            token_endpoint_auth_method: "none".to_string(),
+            scope: None,
+            approved: true,
+            logo_uri: None,
+            client_uri: None,
+            software_id: None,
+            software_version: None,
+            metadata_url: None,
+            metadata_cached_at: None,
+            metadata_cache_ttl: None,
+            last_seen: None,
+            created_at: now.clone(),
+            updated_at: now,
+            reports_roots: false,
+            roots_capability_known: false,
+            suspended: false,
+        };
+        repos
+            .inbound_clients
+            .save_client(&client)
+            .await
+            .expect("save client");
+        repos
+            .inbound_clients
+            .set_client_owner(&client_id, Some(&owner.to_string()))
+            .await
+            .expect("set client owner");
+
+        let feature_set = FeatureSet::new_custom(format!("{name}'s tools"), space.id.to_string());
+        repos
+            .feature_sets
+            .create(&feature_set)
+            .await
+            .expect("create feature set");
+
+        Self {
+            space_id: space.id.to_string(),
+            client_id,
+            feature_set_id: feature_set.id,
+        }
+    }
+
+    fn grants_path(&self, client_id: &str) -> String {
+        format!(
+            "/admin/spaces/{}/clients/{}/grants",
+            self.space_id, client_id
+        )
+    }
+
+    fn grant_ref(&self, client_id: &str, feature_set_id: &str) -> proto::GrantRef {
+        proto::GrantRef {
+            space_id: self.space_id.clone(),
+            client_id: client_id.to_string(),
+            feature_set_id: feature_set_id.to_string(),
+        }
+    }
+}
+
+struct Harness {
+    url: String,
+    grpc: AdminGrpcService,
+    admin_token: String,
+    alice_token: String,
+    alice_id: Uuid,
+    alice: Owned,
+    bob: Owned,
+    ct: CancellationToken,
+}
+
+impl Harness {
+    /// Serve the admin routes over a fresh database with an admin, Alice
+    /// and Bob
+    async fn start() -> Self {
+        let ct = CancellationToken::new();
+        let database = Arc::new(tokio::sync::Mutex::new(TestDatabase::in_memory().db));
+        let encryptor = Arc::new(FieldEncryptor::new(&generate_master_key().unwrap()).unwrap());
+        let repos = RepositorySet::sqlite(database, encryptor);
+
+        let (_, admin_token) = create_user(repos.users.as_ref(), "root", UserRole::Admin)
+            .await
+            .expect("create admin");
+        let (alice_user, alice_token) =
+            create_user(repos.users.as_ref(), "alice", UserRole::Member)
+                .await
+                .expect("create alice");
+        let (bob_user, _) = create_user(repos.users.as_ref(), "bob", UserRole::Member)
+            .await
+            .expect("create bob");
+        let alice = Owned::create(&repos, alice_user.id, "alice").await;
+        let bob = Owned::create(&repos, bob_user.id, "bob").await;
+
+        let deps = DependenciesBuilder::new()
+            .with_repositories(&repos)
+            .with_server_discovery(Arc::new(ServerDiscoveryService::new(
+                std::path::PathBuf::from("test-data"),
+                std::path::PathBuf::from("test-spaces"),
+            )))
+            .with_log_manager(Arc::new(ServerLogManager::new(
+                mcpmux_core::LogConfig::default(),
+            )))
+            .build()
+            .expect("build dependencies");
+
+        let (event_tx, _) = broadcast::channel::<DomainEvent>(64);
+        let gateway_state = Arc::new(tokio::sync::RwLock::new(GatewayState::new(
+            event_tx.clone(),
+        )));
+        let services = Arc::new(ServiceContainer::initialize(
+            &deps,
+            event_tx,
+            gateway_state.clone(),
+        ));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = AppState {
+            gateway_state,
+            services,
+            base_url: url.clone(),
+        };
+        let router = admin::router(state.clone());
+        let ct_clone = ct.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct_clone.cancelled().await })
+                .await
+                .unwrap();
+        });
+
+        Self {
+            url,
+            grpc: AdminGrpcService::new(state),
+            admin_token,
+            alice_token,
+            alice_id: alice_user.id,
+            alice,
+            bob,
+            ct,
+        }
+    }
+
+    async fn send(
+        &self,
+        token: &str,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> StatusCode {
+        let mut request = reqwest::Client::new()
+            .request(method, format!("{}{}", self.url, path))
+            .bearer_auth(token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        request.send().await.expect("request").status()
+    }
+
+    async fn alice(&self, method: Method, path: &str) -> StatusCode {
+        self.send(&self.alice_token, method, path, None).await
+    }
+
+    /// A gRPC request carrying Alice's token
+    fn as_alice<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", self.alice_token).parse().unwrap(),
+        );
+        request
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.ct.cancel();
+    }
+}
+
+/// The status a gRPC call ended with
+fn code<T>(result: Result<tonic::Response<T>, tonic::Status>) -> Code {
+    match result {
+        Ok(_) => Code::Ok,
+        Err(status) => status.code(),
+    }
+}
+
+#[tokio::test]
+async fn member_is_refused_other_users_spaces_clients_grants_and_events() {
+    let h = Harness::start().await;
+    let (alice, bob) = (&h.alice, &h.bob);
+
+    // Their own Space and grants
+    let own_servers = format!("/admin/spaces/{}/servers", alice.space_id);
+    assert_eq!(h.alice(Method::GET, &own_servers).await, StatusCode::OK);
+    let own_grants = alice.grants_path(&alice.client_id);
+    assert_eq!(h.alice(Method::GET, &own_grants).await, StatusCode::OK);
+
+    // Bob's Space, and one that doesn't exist, look the same
+    let bobs_servers = format!("/admin/spaces/{}/servers", bob.space_id);
+    assert_eq!(
+        h.alice(Method::GET, &bobs_servers).await,
+        StatusCode::FORBIDDEN
+    );
+    let missing = format!("/admin/spaces/{}/servers", Uuid::new_v4());
+    assert_eq!(h.alice(Method::GET, &missing).await, StatusCode::FORBIDDEN);
+
+    // Grants between Bob's client and Alice's Space, and the other way round
+    let bobs_client = alice.grants_path(&bob.client_id);
+    assert_eq!(
+        h.alice(Method::GET, &bobs_client).await,
+        StatusCode::FORBIDDEN
+    );
+    let bobs_space = bob.grants_path(&alice.client_id);
+    assert_eq!(
+        h.alice(Method::GET, &bobs_space).await,
+        StatusCode::FORBIDDEN
+    );
+    for method in [Method::PUT, Method::DELETE] {
+        let path = format!("{}/{}", bob.grants_path(&bob.client_id), bob.feature_set_id);
+        assert_eq!(h.alice(method.clone(), &path).await, StatusCode::FORBIDDEN);
+        let path = format!(
+            "{}/{}",
+            alice.grants_path(&bob.client_id),
+            alice.feature_set_id
+        );
+        assert_eq!(h.alice(method, &path).await, StatusCode::FORBIDDEN);
+    }
+
+    // Events about Bob's Space
+    let bobs_events = format!("/events?space_id={}", bob.space_id);
+    assert_eq!(
+        h.alice(Method::GET, &bobs_events).await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn admin_only_routes_refuse_members() {
+    let h = Harness::start().await;
+    let alice_id = h.alice_id;
+    let routes = [
+        (Method::GET, "/admin/users".to_string(), None),
+        (
+            Method::POST,
+            "/admin/users".to_string(),
+            Some(json!({ "name": "mallory", "role": "admin" })),
+        ),
+        (Method::DELETE, format!("/admin/users/{alice_id}"), None),
+        (Method::POST, format!("/admin/users/{alice_id}/token"), None),
+        (
+            Method::PUT,
+            format!("/admin/spaces/{}/owner", h.alice.space_id),
+            Some(json!({ "user_id": null })),
+        ),
+        (
+            Method::PUT,
+            format!("/admin/clients/{}/owner", h.alice.client_id),
+            Some(json!({ "user_id": null })),
+        ),
+        (Method::GET, "/admin/sessions".to_string(), None),
+        (Method::POST, "/admin/pairings".to_string(), Some(json!({}))),
+    ];
+    for (method, path, body) in routes {
+        assert_eq!(
+            h.send(&h.alice_token, method.clone(), &path, body).await,
+            StatusCode::FORBIDDEN,
+            "{method} {path}"
+        );
+    }
+
+    // The same token still works where members are allowed, and the admin
+    // gets through
+    assert_eq!(h.alice(Method::GET, "/admin/clients").await, StatusCode::OK);
+    assert_eq!(
+        h.send(&h.admin_token, Method::GET, "/admin/users", None)
+            .await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn grant_must_name_a_feature_set_of_the_space() {
+    let h = Harness::start().await;
+    let (alice, bob) = (&h.alice, &h.bob);
+    let grants = alice.grants_path(&alice.client_id);
+
+    for token in [&h.alice_token, &h.admin_token] {
+        for feature_set_id in [bob.feature_set_id.clone(), Uuid::new_v4().to_string()] {
+            let path = format!("{grants}/{feature_set_id}");
+            assert_eq!(
+                h.send(token, Method::PUT, &path, None).await,
+                StatusCode::NOT_FOUND
+            );
+        }
+    }
+    let path = format!("{grants}/{}", alice.feature_set_id);
+    assert_eq!(h.alice(Method::PUT, &path).await, StatusCode::NO_CONTENT);
+
+    let granted: Vec<String> = reqwest::Client::new()
+        .get(format!("{}{grants}", h.url))
+        .bearer_auth(&h.alice_token)
+        .send()
+        .await
+        .expect("request")
+        .json()
+        .await
+        .expect("grants");
+    assert_eq!(granted, vec![alice.feature_set_id.clone()]);
+}
+
+#[tokio::test]
+async fn grpc_member_is_refused_other_users_spaces_clients_grants_and_events() {
+    let h = Harness::start().await;
+    let (alice, bob) = (&h.alice, &h.bob);
+
+    let list_servers = |space_id: &str| {
+        h.as_alice(proto::ListServersRequest {
+            space_id: space_id.to_string(),
+        })
+    };
+    assert_eq!(
+        code(h.grpc.list_servers(list_servers(&alice.space_id)).await),
+        Code::Ok
+    );
+    assert_eq!(
+        code(h.grpc.list_servers(list_servers(&bob.space_id)).await),
+        Code::PermissionDenied
+    );
+
+    let list_grants = |space_id: &str, client_id: &str| {
+        h.as_alice(proto::ListGrantsRequest {
+            space_id: space_id.to_string(),
+            client_id: client_id.to_string(),
+        })
+    };
+    assert_eq!(
+        code(
+            h.grpc
+                .list_grants(list_grants(&alice.space_id, &bob.client_id))
+                .await
+        ),
+        Code::PermissionDenied
+    );
+    assert_eq!(
+        code(
+            h.grpc
+                .list_grants(list_grants(&bob.space_id, &alice.client_id))
+                .await
+        ),
+        Code::PermissionDenied
+    );
+
+    let bobs_client = alice.grant_ref(&bob.client_id, &alice.feature_set_id);
+    assert_eq!(
+        code(h.grpc.grant(h.as_alice(bobs_client.clone())).await),
+        Code::PermissionDenied
+    );
+    assert_eq!(
+        code(h.grpc.revoke(h.as_alice(bobs_client)).await),
+        Code::PermissionDenied
+    );
+    let bobs_space = bob.grant_ref(&alice.client_id, &bob.feature_set_id);
+    assert_eq!(
+        code(h.grpc.grant(h.as_alice(bobs_space)).await),
+        Code::PermissionDenied
+    );
+
+    // A FeatureSet of another Space can't be granted in Alice's
+    let bobs_feature_set = alice.grant_ref(&alice.client_id, &bob.feature_set_id);
+    assert_eq!(
+        code(h.grpc.grant(h.as_alice(bobs_feature_set)).await),
+        Code::NotFound
+    );
+    let own = alice.grant_ref(&alice.client_id, &alice.feature_set_id);
+    assert_eq!(code(h.grpc.grant(h.as_alice(own)).await), Code::Ok);
+
+    let bobs_events = h.as_alice(proto::StreamEventsRequest {
+        space_id: Some(bob.space_id.clone()),
+        types: vec![],
+    });
+    assert_eq!(
+        code(h.grpc.stream_events(bobs_events).await),
+        Code::PermissionDenied
+    );
+}
@@ -1,6 +1,7 @@
 //! Security integration tests
 //!
-//! Tests for crypto, keychain, and JWT handling.
+//! Tests for crypto, keychain, JWT handling, and who may use the admin API.
 
+mod admin_access;
 mod crypto;
 mod jwt;
@@ -3,7 +3,9 @@
 //!   - a live key in `Authorization: Bearer mcpk_…` is accepted (200) and the
 //!     middleware injects the owning client's id,
 //!   - an unknown key is rejected (401),
-//!   - a revoked key is rejected (401).
+//!   - a revoked key is rejected (401),
+//!   - a suspended client is refused (403), and so is every request when its
+//!     suspension can't be checked.
 //!
 //! This is the headless/remote auth path that needs no interactive consent —
 //! the secure way to connect when the gateway is exposed over the network.
@@ -22,7 +24,8 @@ use mcpmux_gateway::{
     server::{DependenciesBuilder, GatewayDependencies, GatewayState, ServiceContainer},
 };
 use mcpmux_storage::{
-    InboundClient, InboundClientRepository, RegistrationType, SqliteSpaceRepository,
+    Database, InboundClient, InboundClientRepository, RegistrationType,
+    SqliteInboundClientRepository, SqliteSpaceRepository,
 };
 use std::sync::Arc;
 use tokio::sync::broadcast;
@@ -46,7 +49,8 @@ async fn echo_client_id(req: Request<Body>) -> Response {
 
 struct Harness {
     url: String,
-    client_repo: Arc<InboundClientRepository>,
+    database: Arc<tokio::sync::Mutex<Database>>,
+    client_repo: Arc<dyn InboundClientRepository>,
     client_id: String,
     api_key: String,
     key_id: String,
@@ -65,16 +69,8 @@ impl Harness {
         let database = Arc::new(tokio::sync::Mutex::new(test_db.db));
 
         let space_repo = Arc::new(SqliteSpaceRepository::new(database.clone()));
-        let space = mcpmux_core::domain::Space {
-            id: space_id,
-            name: "Test Space".to_string(),
-            icon: None,
-            description: None,
-            is_default: true,
-            sort_order: 0,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+        let mut space = mcpmux_core::domain::Space::new("Test Space").set_default();
+        space.id = space_id;
         mcpmux_core::SpaceRepository::create(&*space_repo, &space)
             .await
             .expect("create space");
@@ -85,7 +81,7 @@ impl Harness {
         // Register a Preregistered, approved client + an API key over the SAME
         // database Arc the gateway's middleware reads from, so a write here is
         // visible to the middleware's own repo instance.
-        let client_repo = Arc::new(InboundClientRepository::new(database.clone()));
+        let client_repo = Arc::new(SqliteInboundClientRepository::new(database.clone()));
         let client_id = format!("mcp_{}", &Uuid::new_v4().simple().to_string()[..8]);
         let now = chrono::Utc::now().to_rfc3339();
         let client = InboundClient {
@@ -111,6 +107,7 @@ impl Harness {
             updated_at: now,
             reports_roots: false,
             roots_capability_known: false,
+            suspended: false,
         };
         client_repo.save_client(&client).await.expect("save client");
         let key_id = Uuid::new_v4().to_string();
@@ -136,7 +133,7 @@ impl Harness {
             .with_log_manager(Arc::new(ServerLogManager::new(
                 mcpmux_core::LogConfig::default(),
             )))
-            .with_database(database)
+            .with_database(database.clone())
             .build()
             .expect("build dependencies");
         let deps = GatewayDependencies {
@@ -175,6 +172,7 @@ impl Harness {
 
         Self {
             url: format!("http://127.0.0.1:{port}/mcp"),
+            database,
             client_repo,
             client_id,
             api_key,
@@ -247,3 +245,49 @@ async fn revoked_api_key_is_rejected() {
         "a revoked key must be rejected"
     );
 }
+
+#[tokio::test]
+async fn suspended_client_is_refused() {
+    let h = Harness::start().await;
+    assert!(h
+        .client_repo
+        .set_client_suspended(&h.client_id, true)
+        .await
+        .expect("suspend"));
+    let resp = h.post_with_bearer(&h.api_key).await;
+    assert_eq!(
+        resp.status(),
+        reqwest::StatusCode::FORBIDDEN,
+        "a suspended client's live key must be refused"
+    );
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "client_suspended");
+
+    // Reinstating restores access with the same key
+    h.client_repo
+        .set_client_suspended(&h.client_id, false)
+        .await
+        .expect("reinstate");
+    assert_eq!(
+        h.post_with_bearer(&h.api_key).await.status(),
+        reqwest::StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn failed_suspension_check_denies_the_request() {
+    let h = Harness::start().await;
+    // The key still validates, but the suspension lookup fails
+    h.database
+        .lock()
+        .await
+        .connection()
+        .execute_batch("ALTER TABLE inbound_clients RENAME TO inbound_clients_gone")
+        .expect("rename table");
+    let resp = h.post_with_bearer(&h.api_key).await;
+    assert_eq!(
+        resp.status(),
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        "a client whose suspension can't be checked must not get through"
+    );
+}
@@ -29,7 +29,7 @@ use mcpmux_gateway::{
         GatewayDependencies, GatewayState, ServiceContainer,
     },
 };
-use mcpmux_storage::{InboundClientRepository, SqliteSpaceRepository};
+use mcpmux_storage::{SqliteInboundClientRepository, SqliteSpaceRepository};
 use rmcp::transport::streamable_http_server::{
     session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
 };
@@ -74,16 +74,10 @@ impl Harness {
         // Seed a default space so an authenticated client can resolve one.
         let space_repo = Arc::new(SqliteSpaceRepository::new(database.clone()));
         let space_id = Uuid::new_v4();
-        let space = mcpmux_core::domain::Space {
-            id: space_id,
-            name: "Test Space".to_string(),
-            icon: Some("test".to_string()),
-            description: None,
-            is_default: true,
-            sort_order: 0,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+        let mut space = mcpmux_core::domain::Space::new("Test Space")
+            .with_icon("test")
+            .set_default();
+        space.id = space_id;
         SpaceRepository::create(&*space_repo, &space)
             .await
             .expect("create space");
@@ -91,7 +85,7 @@ impl Harness {
             .await
             .expect("set default");
 
-        let inbound_client_repo = Arc::new(InboundClientRepository::new(database.clone()));
+        let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(database.clone()));
 
         let deps = DependenciesBuilder::new()
             .with_installed_server_repo(Arc::new(MockInstalledServerRepository::new()))
@@ -16,7 +16,9 @@ use mcpmux_gateway::{
     mcp::{mcp_oauth_middleware, McpMuxGatewayHandler},
     server::{DependenciesBuilder, GatewayState, ServiceContainer},
 };
-use mcpmux_storage::{InboundClient, InboundClientRepository, RegistrationType};
+use mcpmux_storage::{
+    InboundClient, InboundClientRepository, RegistrationType, SqliteInboundClientRepository,
+};
 use rmcp::{
     model::*,
     service::NotificationContext,
@@ -115,16 +117,10 @@ impl TestGateway {
 
         // Create a default space in the space repo via database
         let space_repo = Arc::new(mcpmux_storage::SqliteSpaceRepository::new(database.clone()));
-        let space = mcpmux_core::domain::Space {
-            id: space_id,
-            name: "Test Space".to_string(),
-            icon: Some("test".to_string()),
-            description: None,
-            is_default: true,
-            sort_order: 0,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+        let mut space = mcpmux_core::domain::Space::new("Test Space")
+            .with_icon("test")
+            .set_default();
+        space.id = space_id;
         mcpmux_core::SpaceRepository::create(&*space_repo, &space)
             .await
             .expect("create space");
@@ -133,7 +129,7 @@ impl TestGateway {
             .expect("set default");
 
         // Create inbound client repository and register our test client
-        let inbound_client_repo = Arc::new(InboundClientRepository::new(database.clone()));
+        let inbound_client_repo = Arc::new(SqliteInboundClientRepository::new(database.clone()));
         let now = chrono::Utc::now().to_rfc3339();
         let test_client = InboundClient {
             client_id: client_id.to_string(),
@@ -158,6 +154,7 @@ impl TestGateway {
             updated_at: now,
             reports_roots: false,
             roots_capability_known: false,
+            suspended: false,
         };
         inbound_client_repo
             .save_client(&test_client)
@@ -38,16 +38,8 @@ impl Harness {
         let database = Arc::new(tokio::sync::Mutex::new(test_db.db));
 
         let space_repo = Arc::new(SqliteSpaceRepository::new(database.clone()));
-        let space = mcpmux_core::domain::Space {
-            id: space_id,
-            name: "Test Space".to_string(),
-            icon: None,
-            description: None,
-            is_default: true,
-            sort_order: 0,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+        let mut space = mcpmux_core::domain::Space::new("Test Space").set_default();
+        space.id = space_id;
         mcpmux_core::SpaceRepository::create(&*space_repo, &space)
             .await
             .expect("create space");
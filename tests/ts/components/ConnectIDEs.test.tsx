@@ -5,20 +5,30 @@ import userEvent from '@testing-library/user-event';
 vi.mock('../../../apps/desktop/src/lib/api/clientInstall', () => ({
   addToVscode: vi.fn(),
   addToCursor: vi.fn(),
+  addToWindsurf: vi.fn(),
+  addToZed: vi.fn(),
+  detectInstalledClients: vi.fn().mockResolvedValue([]),
 }));
 
 import { ConnectIDEs } from '../../../apps/desktop/src/components/ConnectIDEs';
 import {
   addToVscode,
   addToCursor,
+  addToWindsurf,
+  addToZed,
+  detectInstalledClients,
 } from '../../../apps/desktop/src/lib/api/clientInstall';
 
 const mockedAddVscode = vi.mocked(addToVscode);
 const mockedAddCursor = vi.mocked(addToCursor);
+const mockedAddWindsurf = vi.mocked(addToWindsurf);
+const mockedAddZed = vi.mocked(addToZed);
+const mockedDetect = vi.mocked(detectInstalledClients);
 
 describe('ConnectIDEs', () => {
   beforeEach(() => {
     vi.clearAllMocks();
+    mockedDetect.mockResolvedValue([]);
   });
 
   it('should render the card title', () => {
@@ -122,6 +132,57 @@ describe('ConnectIDEs', () => {
     expect(mockedAddCursor).toHaveBeenCalledWith('http://localhost:45818');
   });
 
+  it('should call addToWindsurf and addToZed from their popovers', async () => {
+    const user = userEvent.setup();
+    mockedAddWindsurf.mockResolvedValue('/home/u/.codeium/windsurf/mcp_config.json');
+    mockedAddZed.mockResolvedValue('/home/u/.config/zed/settings.json');
+
+    render(
+      <ConnectIDEs gatewayUrl="http://localhost:45818" gatewayRunning={true} />
+    );
+
+    await user.click(screen.getByTestId('client-icon-windsurf'));
+    await user.click(screen.getByRole('button', { name: /Add to Windsurf/i }));
+    expect(mockedAddWindsurf).toHaveBeenCalledWith('http://localhost:45818');
+
+    await user.click(screen.getByTestId('client-icon-zed'));
+    await user.click(screen.getByRole('button', { name: /Add to Zed/i }));
+    expect(mockedAddZed).toHaveBeenCalledWith('http://localhost:45818');
+  });
+
+  it('should show the install error and keep the popover open', async () => {
+    const user = userEvent.setup();
+    mockedAddZed.mockRejectedValue('settings.json is not plain JSON');
+
+    render(
+      <ConnectIDEs gatewayUrl="http://localhost:45818" gatewayRunning={true} />
+    );
+
+    await user.click(screen.getByTestId('client-icon-zed'));
+    await user.click(screen.getByRole('button', { name: /Add to Zed/i }));
+
+    expect(await screen.findByText(/not plain JSON/)).toBeInTheDocument();
+    expect(screen.getByTestId('client-popover')).toBeInTheDocument();
+  });
+
+  it('should warn when a client is not detected', async () => {
+    const user = userEvent.setup();
+    mockedDetect.mockResolvedValue([
+      { client: 'zed', installed: false },
+      { client: 'cursor', installed: true },
+    ]);
+
+    render(
+      <ConnectIDEs gatewayUrl="http://localhost:45818" gatewayRunning={true} />
+    );
+
+    await user.click(screen.getByTestId('client-icon-zed'));
+    expect(await screen.findByText(/Zed was not detected/)).toBeInTheDocument();
+
+    await user.click(screen.getByTestId('client-icon-cursor'));
+    expect(screen.queryByText(/was not detected/)).not.toBeInTheDocument();
+  });
+
   it('should show Copy command for Claude Code', async () => {
     const user = userEvent.setup();
     render(
@@ -28,7 +28,7 @@ vi.mock('@/lib/api/registry', () => ({
   getRegistryUiConfig: vi.fn().mockResolvedValue(null),
   getRegistryHomeConfig: vi.fn().mockResolvedValue(null),
   listInstalledServers: vi.fn().mockResolvedValue([]),
-  isRegistryOffline: vi.fn().mockResolvedValue(false),
+  getRegistrySyncStatus: vi.fn().mockResolvedValue({ offline: false, last_synced_at: null }),
   installServer: vi.fn().mockResolvedValue(undefined),
   uninstallServer: vi.fn().mockResolvedValue(undefined),
   setServerEnabled: vi.fn().mockResolvedValue(undefined),
@@ -7,7 +7,7 @@ vi.mock('../../../apps/desktop/src/lib/api/registry', () => ({
   getRegistryUiConfig: vi.fn(),
   getRegistryHomeConfig: vi.fn(),
   listInstalledServers: vi.fn(),
-  isRegistryOffline: vi.fn(),
+  getRegistrySyncStatus: vi.fn(),
   installServer: vi.fn(),
   setServerEnabled: vi.fn(),
   uninstallServer: vi.fn(),
@@ -80,6 +80,7 @@ describe('registryStore', () => {
       error: null,
       selectedServer: null,
       isOffline: false,
+      lastSyncedAt: null,
     });
   });
 